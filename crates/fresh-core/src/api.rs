@@ -346,6 +346,54 @@ pub struct BufferInfo {
     pub compose_width: Option<u16>,
     /// The detected language for this buffer (e.g., "rust", "markdown", "text")
     pub language: String,
+    /// Number of steps Undo can take back
+    pub undo_depth: usize,
+    /// Number of steps Redo can take forward
+    pub redo_depth: usize,
+    /// When the buffer was last saved this session, in milliseconds since the
+    /// Unix epoch (null if it hasn't been saved since it was opened)
+    #[ts(type = "number | null")]
+    pub last_saved_at: Option<u64>,
+}
+
+/// An entry of the Back/Forward navigation history
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PositionHistoryEntry {
+    /// Buffer the position is in
+    #[ts(type = "number")]
+    pub buffer_id: BufferId,
+    /// Cursor byte offset
+    pub position: usize,
+    /// Whether this is where the user currently is in the history. Entries
+    /// after it are reachable with Forward, entries before it with Back.
+    pub current: bool,
+}
+
+/// Payload of the `buffer_modified_changed` hook, fired when a buffer goes
+/// from saved to modified or back (including undoing to the saved state)
+///
+/// Example: `{ "buffer_id": 3, "modified": false }`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BufferModifiedChangedEvent {
+    /// Buffer whose modified state changed
+    #[ts(type = "number")]
+    pub buffer_id: BufferId,
+    /// Whether the buffer now has unsaved changes
+    pub modified: bool,
+}
+
+/// Payload of the `before_quit` hook, fired when the user asks to quit.
+/// Call `editor.cancelQuit()` from the handler (synchronously, before any
+/// `await`) to keep the editor open.
+///
+/// Example: `{ "modified_count": 2 }`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BeforeQuitEvent {
+    /// Number of buffers with unsaved changes that would be asked about
+    pub modified_count: usize,
 }
 
 fn serialize_path<S: serde::Serializer>(path: &Option<PathBuf>, s: S) -> Result<S::Ok, S::Error> {
@@ -773,6 +821,10 @@ pub struct EditorStateSnapshot {
     #[ts(type = "any")]
     pub plugin_view_states: HashMap<BufferId, HashMap<String, serde_json::Value>>,
 
+    /// Most recent entries of the Back/Forward navigation history, newest
+    /// first (at most 10)
+    pub position_history: Vec<PositionHistoryEntry>,
+
     /// Tracks which split was active when plugin_view_states was last populated.
     /// When the active split changes, plugin_view_states is fully repopulated.
     #[serde(skip)]
@@ -801,6 +853,7 @@ impl EditorStateSnapshot {
             user_config: serde_json::Value::Null,
             editor_mode: None,
             plugin_view_states: HashMap::new(),
+            position_history: Vec::new(),
             plugin_view_states_split: 0,
         }
     }
@@ -862,7 +915,11 @@ pub enum PluginCommand {
     },
 
     /// Set status message
-    SetStatus { message: String },
+    SetStatus {
+        message: String,
+        /// Plugin that set the message, shown in the `*Messages*` transcript
+        plugin_name: Option<String>,
+    },
 
     /// Apply a theme by name
     ApplyTheme { theme_name: String },
@@ -1098,6 +1155,9 @@ pub enum PluginCommand {
     /// where render marks lines as "seen" before the plugin has registered.
     RefreshAllLines,
 
+    /// Keep the editor open: sent from a `before_quit` handler
+    CancelQuit,
+
     /// Sentinel sent by the plugin thread after a hook has been fully processed.
     /// Used by the render loop to wait deterministically for plugin responses
     /// (e.g., conceal commands from `lines_changed`) instead of polling.
@@ -2533,7 +2593,10 @@ impl PluginApi {
 
     /// Set the status message
     pub fn set_status(&self, message: String) -> Result<(), String> {
-        self.send_command(PluginCommand::SetStatus { message })
+        self.send_command(PluginCommand::SetStatus {
+            message,
+            plugin_name: None,
+        })
     }
 
     /// Open a file at a specific line and column (1-indexed)
@@ -2913,7 +2976,7 @@ mod tests {
 
         let received = rx.try_recv().unwrap();
         match received {
-            PluginCommand::SetStatus { message } => {
+            PluginCommand::SetStatus { message, .. } => {
                 assert_eq!(message, "Test status");
             }
             _ => panic!("Wrong command type"),
@@ -2959,6 +3022,9 @@ mod tests {
                 is_composing_in_any_split: false,
                 compose_width: None,
                 language: "text".to_string(),
+                undo_depth: 0,
+                redo_depth: 0,
+                last_saved_at: None,
             };
             snapshot.buffers.insert(BufferId(1), buffer_info);
         }
@@ -3003,6 +3069,9 @@ mod tests {
                     is_composing_in_any_split: false,
                     compose_width: None,
                     language: "text".to_string(),
+                    undo_depth: 0,
+                    redo_depth: 0,
+                    last_saved_at: None,
                 },
             );
             snapshot.buffers.insert(
@@ -3017,6 +3086,9 @@ mod tests {
                     is_composing_in_any_split: false,
                     compose_width: None,
                     language: "text".to_string(),
+                    undo_depth: 0,
+                    redo_depth: 0,
+                    last_saved_at: None,
                 },
             );
             snapshot.buffers.insert(
@@ -3031,6 +3103,9 @@ mod tests {
                     is_composing_in_any_split: false,
                     compose_width: None,
                     language: "text".to_string(),
+                    undo_depth: 0,
+                    redo_depth: 0,
+                    last_saved_at: None,
                 },
             );
         }
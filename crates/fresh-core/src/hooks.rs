@@ -29,6 +29,10 @@ pub enum HookArgs {
     /// A buffer was closed
     BufferClosed { buffer_id: BufferId },
 
+    /// A buffer went from saved to modified or back, including by undoing
+    /// to the saved state. Fired once per change, not on every edit.
+    BufferModifiedChanged { buffer_id: BufferId, modified: bool },
+
     /// Before text is inserted
     BeforeInsert {
         buffer_id: BufferId,
@@ -290,6 +294,12 @@ pub enum HookArgs {
 
     /// Terminal was resized
     Resize { width: u16, height: u16 },
+
+    /// The user asked to quit. Handlers can call `editor.cancelQuit()`.
+    BeforeQuit {
+        /// Number of modified buffers that would be asked about
+        modified_count: usize,
+    },
 }
 
 /// Information about a single line for the LinesChanged hook
@@ -757,6 +767,18 @@ pub fn hook_args_to_json(args: &HookArgs) -> Result<serde_json::Value> {
                 "height": height,
             })
         }
+        HookArgs::BufferModifiedChanged {
+            buffer_id,
+            modified,
+        } => {
+            serde_json::json!({
+                "buffer_id": buffer_id.0,
+                "modified": modified,
+            })
+        }
+        HookArgs::BeforeQuit { modified_count } => {
+            serde_json::json!({ "modified_count": modified_count })
+        }
     };
 
     Ok(json_value)
@@ -302,6 +302,19 @@ type BufferInfo = {
 	* The detected language for this buffer (e.g., "rust", "markdown", "text")
 	*/
 	language: string;
+	/**
+	* Number of steps Undo can take back
+	*/
+	undo_depth: number;
+	/**
+	* Number of steps Redo can take forward
+	*/
+	redo_depth: number;
+	/**
+	* When the buffer was last saved this session, in milliseconds since the
+	* Unix epoch (null if it hasn't been saved since it was opened)
+	*/
+	last_saved_at: number | null;
 };
 type JsDiagnostic = {
 	/**
@@ -525,6 +538,22 @@ type InlineOverlay = {
 	*/
 	properties?: Record<string, any>;
 };
+type BufferModifiedChangedEvent = {
+	/**
+	* Buffer whose modified state changed
+	*/
+	buffer_id: number;
+	/**
+	* Whether the buffer now has unsaved changes
+	*/
+	modified: boolean;
+};
+type BeforeQuitEvent = {
+	/**
+	* Number of buffers with unsaved changes that would be asked about
+	*/
+	modified_count: number;
+};
 type BackgroundProcessResult = {
 	/**
 	* Unique process ID for later reference
@@ -750,6 +779,21 @@ type LspServerPackConfig = {
 	*/
 	processLimits: ProcessLimitsPackConfig | null;
 };
+type PositionHistoryEntry = {
+	/**
+	* Buffer the position is in
+	*/
+	buffer_id: number;
+	/**
+	* Cursor byte offset
+	*/
+	position: number;
+	/**
+	* Whether this is where the user currently is in the history. Entries
+	* after it are reachable with Forward, entries before it with Back.
+	*/
+	current: boolean;
+};
 type ReplaceResult = {
 	/**
 	* Number of replacements made
@@ -882,6 +926,10 @@ interface EditorAPI {
 	*/
 	getAllCursors(): CursorInfo[];
 	/**
+	* Get the most recent Back/Forward navigation history entries, newest first
+	*/
+	getPositionHistory(): PositionHistoryEntry[];
+	/**
 	* Get all cursor positions as byte offsets
 	*/
 	getAllCursorPositions(): number[];
@@ -959,6 +1007,11 @@ interface EditorAPI {
 	*/
 	off(eventName: string, handlerName: string): void;
 	/**
+	* Keep the editor open. Call from a `before_quit` handler, before any
+	* `await`; calls made later arrive after the quit went ahead.
+	*/
+	cancelQuit(): boolean;
+	/**
 	* Get an environment variable
 	*/
 	getEnv(name: string): string | null;
@@ -273,6 +273,9 @@ function loadThemeSections(): ThemeSection[] {
 
     for (const [fieldName, fieldSchema] of Object.entries(sectionProps)) {
       const fieldObj = fieldSchema as Record<string, unknown>;
+      // Only colors are edited here; other settings (e.g. accessibility
+      // attributes) are kept as-is in the theme file
+      if (fieldObj.$ref !== "#/$defs/ColorDef") continue;
       const fieldDesc = (fieldObj.description as string) || "";
 
       // Generate i18n keys from field names
@@ -287,6 +290,8 @@ function loadThemeSections(): ThemeSection[] {
       });
     }
 
+    if (fields.length === 0) continue;
+
     // Sort fields alphabetically (use simple comparison to avoid ICU issues in QuickJS)
     fields.sort((a, b) => (a.key < b.key ? -1 : a.key > b.key ? 1 : 0));
 
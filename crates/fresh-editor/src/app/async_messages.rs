@@ -666,6 +666,21 @@ impl Editor {
         self.pull_diagnostics_for_language(&language);
     }
 
+    /// Handle workspace/applyEdit request from the LSP server, typically sent
+    /// while it runs a code action's command
+    pub(super) fn handle_lsp_apply_edit(
+        &mut self,
+        language: String,
+        edit: lsp_types::WorkspaceEdit,
+    ) {
+        tracing::info!("LSP ({}) requested a workspace edit", language);
+        if let Err(e) = self.apply_workspace_edit(edit) {
+            self.set_important_status_message(
+                t!("lsp.quick_fix_failed", error = e.to_string()).to_string(),
+            );
+        }
+    }
+
     /// Re-pull diagnostics for all open buffers associated with the given language.
     fn pull_diagnostics_for_language(&mut self, language: &str) {
         // Collect URIs and their previous result IDs
@@ -767,7 +782,7 @@ impl Editor {
         // Show important messages in status bar
         match message_type {
             LspMessageType::Error | LspMessageType::Warning => {
-                self.status_message = Some(format!("LSP ({}): {}", language, message));
+                self.show_important_status_message(format!("LSP ({}): {}", language, message));
             }
             _ => {
                 // Info and Log messages are not shown in status bar
@@ -819,7 +834,7 @@ impl Editor {
             if was_running {
                 if let Some(lsp) = self.lsp.as_mut() {
                     let message = lsp.handle_server_crash(&language);
-                    self.status_message = Some(message);
+                    self.show_important_status_message(message);
                 }
             }
         }
@@ -967,16 +982,17 @@ impl Editor {
                 if *count >= RAPID_REVERT_THRESHOLD {
                     // Disable auto-revert
                     self.auto_revert_enabled = false;
-                    self.status_message = Some(format!(
-                        "Auto-revert disabled: {} is updating too frequently (use Ctrl+Shift+R to re-enable)",
-                        path_buf.file_name().unwrap_or_default().to_string_lossy()
-                    ));
+                    self.remote_file_poller = None;
                     tracing::info!(
                         "Auto-revert disabled for {:?} ({} reverts in {:?})",
                         path_buf,
                         count,
                         DEBOUNCE_WINDOW
                     );
+                    self.show_status_message(format!(
+                        "Auto-revert disabled: {} is updating too frequently (use Ctrl+Shift+R to re-enable)",
+                        path_buf.file_name().unwrap_or_default().to_string_lossy()
+                    ));
                     return false;
                 }
             } else {
@@ -1095,7 +1111,8 @@ impl Editor {
     /// Process TypeScript plugin commands
     ///
     /// Returns true if any visual commands were processed (i.e. a re-render is needed).
-    /// No-op sentinels like `HookCompleted` do not count.
+    /// No-op sentinels like `HookCompleted` do not count, except the one
+    /// ending `before_quit`, which can open the quit prompt.
     pub(super) fn process_plugin_commands(&mut self) -> bool {
         let commands = self.plugin_manager.process_commands();
         if commands.is_empty() {
@@ -1104,7 +1121,7 @@ impl Editor {
 
         let has_visual_commands = commands
             .iter()
-            .any(|c| !matches!(c, fresh_core::api::PluginCommand::HookCompleted { .. }));
+            .any(|c| !matches!(c, fresh_core::api::PluginCommand::HookCompleted { hook_name } if hook_name != "before_quit"));
 
         let cmd_names: Vec<String> = commands.iter().map(|c| c.debug_variant_name()).collect();
         tracing::trace!(
@@ -1193,7 +1210,7 @@ impl Editor {
         let restart_results = lsp.process_pending_restarts();
 
         for (language, success, message) in restart_results {
-            self.status_message = Some(message.clone());
+            self.show_status_message(message.clone());
 
             if success {
                 self.resend_did_open_for_language(&language);
@@ -1211,6 +1228,8 @@ impl Editor {
                 if state.language == language {
                     self.buffer_metadata
                         .get(buf_id)
+                        // Files on another host than the servers' are left out
+                        .filter(|meta| meta.remote_connection.is_none())
                         .and_then(|meta| meta.file_path().map(|p| (*buf_id, p.clone())))
                 } else {
                     None
@@ -0,0 +1,142 @@
+//! Config-driven auto-commands.
+//!
+//! `auto_commands` in the config lists commands to run when a file is opened,
+//! saved, or has its language changed, optionally limited to a language or a
+//! file glob. Commands are looked up like keybinding actions (`format_buffer`)
+//! or by their command palette name (`Format Buffer`) and act on the buffer
+//! that triggered the event.
+
+use std::collections::HashMap;
+
+use super::Editor;
+use crate::config::{AutoCommand, AutoCommandEvent};
+use crate::input::keybindings::Action;
+use crate::model::event::BufferId;
+use crate::primitives::glob_match::file_glob_matches;
+use rust_i18n::t;
+
+impl Editor {
+    /// Run the auto-commands configured for `event` on `buffer_id`.
+    ///
+    /// Commands act on the active buffer, so nothing runs when the buffer is
+    /// in the background. Commands triggered from within an auto-command
+    /// (e.g. a save issued by an `on_save` command) don't fire auto-commands
+    /// again.
+    pub(crate) fn run_auto_commands(&mut self, event: AutoCommandEvent, buffer_id: BufferId) {
+        if self.running_auto_commands || self.active_buffer() != buffer_id {
+            return;
+        }
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        let language = state.language.clone();
+        let path = state
+            .buffer
+            .file_path()
+            .map(|p| p.to_string_lossy().into_owned());
+
+        let matching: Vec<AutoCommand> = self
+            .config
+            .auto_commands
+            .iter()
+            .filter(|ac| ac.enabled && ac.event == event)
+            .filter(|ac| ac.language.as_deref().is_none_or(|l| l == language))
+            .filter(|ac| match (&ac.pattern, &path) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(pattern), Some(path)) => file_glob_matches(pattern, path),
+            })
+            .cloned()
+            .collect();
+        if matching.is_empty() {
+            return;
+        }
+
+        self.running_auto_commands = true;
+        for auto_command in matching {
+            let Some(action) = self.resolve_command(&auto_command.command, &auto_command.args)
+            else {
+                tracing::warn!("Unknown auto-command: {}", auto_command.command);
+                self.set_status_message(
+                    t!("auto_commands.unknown", commands = &auto_command.command).to_string(),
+                );
+                continue;
+            };
+            tracing::debug!(
+                "Running auto-command '{}' for {:?}",
+                auto_command.command,
+                event
+            );
+            if let Err(e) = self.handle_action(action) {
+                tracing::warn!("Auto-command '{}' failed: {}", auto_command.command, e);
+                self.set_important_status_message(
+                    t!(
+                        "auto_commands.failed",
+                        command = &auto_command.command,
+                        error = e.to_string()
+                    )
+                    .to_string(),
+                );
+            }
+        }
+        self.running_auto_commands = false;
+    }
+
+    /// Remember that `buffer_id` was just loaded from disk so its `on_open`
+    /// auto-commands run once it is shown.
+    pub(crate) fn queue_open_auto_commands(&mut self, buffer_id: BufferId) {
+        if self
+            .config
+            .auto_commands
+            .iter()
+            .any(|ac| ac.event == AutoCommandEvent::OnOpen)
+        {
+            self.pending_open_auto_commands.insert(buffer_id);
+        }
+    }
+
+    /// Run the queued `on_open` auto-commands for `buffer_id`, if any.
+    pub(crate) fn run_pending_open_auto_commands(&mut self, buffer_id: BufferId) {
+        if self.pending_open_auto_commands.remove(&buffer_id) {
+            self.run_auto_commands(AutoCommandEvent::OnOpen, buffer_id);
+        }
+    }
+
+    /// Check that every configured auto-command names a known command and
+    /// report the ones that don't in the status bar.
+    pub(crate) fn validate_auto_commands(&mut self) {
+        let unknown: Vec<String> = self
+            .config
+            .auto_commands
+            .iter()
+            .filter(|ac| self.resolve_command(&ac.command, &ac.args).is_none())
+            .map(|ac| ac.command.clone())
+            .collect();
+        if unknown.is_empty() {
+            return;
+        }
+        tracing::warn!("Unknown commands in auto_commands: {}", unknown.join(", "));
+        self.set_status_message(
+            t!("auto_commands.unknown", commands = unknown.join(", ")).to_string(),
+        );
+    }
+
+    /// Look up the action for a command: an action name first, then a
+    /// command palette name.
+    pub(super) fn resolve_command(
+        &self,
+        command: &str,
+        args: &HashMap<String, serde_json::Value>,
+    ) -> Option<Action> {
+        if let Some(action) = Action::from_str(command, args) {
+            return Some(action);
+        }
+        self.command_registry
+            .read()
+            .unwrap()
+            .get_all()
+            .into_iter()
+            .find(|c| c.name == command || c.get_localized_name() == command)
+            .map(|c| c.action)
+    }
+}
@@ -0,0 +1,200 @@
+//! Bookmark picker.
+//!
+//! Lists every bookmark, numbered and named, in the prompt's suggestion list
+//! as "name — file:line — line text", most recently used first, followed by
+//! the line marks of the open buffers under the gutter glyph. Typing filters
+//! the list with the same fuzzy matcher as the command palette, and
+//! confirming jumps to the bookmark, reopening its file if needed.
+
+use super::line_marks::{line_mark_lines, LINE_MARK_SYMBOL};
+use super::types::Bookmark;
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::model::event::BufferId;
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+
+/// Longest line preview shown for a bookmark, in characters
+const PREVIEW_CHARS: usize = 60;
+
+/// Bytes read past a bookmark in a closed file to find the end of its line
+const PREVIEW_READ_BYTES: usize = 256;
+
+/// Suggestion value prefixes telling numbered bookmarks from named ones
+const REGISTER_PREFIX: &str = "register:";
+const NAMED_PREFIX: &str = "name:";
+const LINE_MARK_PREFIX: &str = "mark:";
+
+impl Editor {
+    /// Open the bookmark picker
+    pub fn open_bookmark_picker(&mut self) {
+        let mut entries: Vec<(String, String, &Bookmark)> = self
+            .bookmarks
+            .iter()
+            .map(|(key, bookmark)| {
+                (
+                    key.to_string(),
+                    format!("{}{}", REGISTER_PREFIX, key),
+                    bookmark,
+                )
+            })
+            .chain(self.named_bookmarks.iter().map(|(name, bookmark)| {
+                (name.clone(), format!("{}{}", NAMED_PREFIX, name), bookmark)
+            }))
+            .collect();
+        // Most recently used first; bookmarks not used since they were
+        // restored follow by name
+        entries.sort_by(|a, b| {
+            b.2.last_used
+                .cmp(&a.2.last_used)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+
+        // Line marks have no name or use time, so they follow by buffer and line
+        let mut marked_buffers: Vec<_> = self.buffers.iter().collect();
+        marked_buffers.sort_by_key(|(id, _)| id.0);
+        let line_marks: Vec<(String, Bookmark)> = marked_buffers
+            .into_iter()
+            .flat_map(|(&buffer_id, state)| {
+                line_mark_lines(state).into_iter().map(move |line| {
+                    let position = state.buffer.line_start_offset(line).unwrap_or(0);
+                    (
+                        format!("{}{}:{}", LINE_MARK_PREFIX, buffer_id.0, position),
+                        line_mark_bookmark(buffer_id, position),
+                    )
+                })
+            })
+            .collect();
+
+        if entries.is_empty() && line_marks.is_empty() {
+            self.set_status_message(t!("bookmark.none_set").to_string());
+            return;
+        }
+
+        let suggestions: Vec<Suggestion> = entries
+            .iter()
+            .map(|(label, value, bookmark)| (label.as_str(), value, *bookmark))
+            .chain(
+                line_marks
+                    .iter()
+                    .map(|(value, bookmark)| (LINE_MARK_SYMBOL, value, bookmark)),
+            )
+            .map(|(label, value, bookmark)| {
+                let (file, line, preview) = self.bookmark_preview(bookmark);
+                Suggestion {
+                    text: format!("{} — {}:{} — {}", label, file, line + 1, preview),
+                    description: None,
+                    value: Some(value.clone()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+
+        self.start_prompt_with_suggestions(
+            "Bookmarks: ".to_string(),
+            PromptType::BookmarkPicker,
+            suggestions,
+        );
+    }
+
+    /// Jump to the bookmark chosen in the picker. Input that isn't one of
+    /// the picker's values is taken as a bookmark name.
+    pub(super) fn jump_from_bookmark_picker(&mut self, value: &str) {
+        if let Some(key) = value
+            .strip_prefix(REGISTER_PREFIX)
+            .and_then(|key| key.chars().next())
+        {
+            self.jump_to_bookmark(key);
+        } else if let Some(mark) = value.strip_prefix(LINE_MARK_PREFIX) {
+            let Some((buffer_id, position)) = mark.split_once(':') else {
+                return;
+            };
+            if let (Ok(buffer_id), Ok(position)) = (buffer_id.parse(), position.parse()) {
+                self.go_to_bookmark(&line_mark_bookmark(BufferId(buffer_id), position));
+            }
+        } else {
+            let name = value.strip_prefix(NAMED_PREFIX).unwrap_or(value);
+            self.jump_to_named_bookmark(name);
+        }
+    }
+
+    /// The file name, 0-indexed line and line text of a bookmark, read from
+    /// its buffer, or from its file when the buffer is closed
+    fn bookmark_preview(&self, bookmark: &Bookmark) -> (String, usize, String) {
+        let file = bookmark
+            .file_path
+            .as_deref()
+            .map(|path| {
+                path.strip_prefix(&self.working_dir)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .or_else(|| {
+                bookmark
+                    .buffer_id
+                    .and_then(|id| self.buffer_metadata.get(&id))
+                    .map(|m| m.display_name.clone())
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let open_state = bookmark
+            .buffer_id
+            .and_then(|id| self.buffers.get(&id))
+            .or_else(|| {
+                let path = bookmark.file_path.as_deref()?;
+                self.buffers
+                    .values()
+                    .find(|state| state.buffer.file_path() == Some(path))
+            });
+        let (line, text) = match open_state {
+            Some(state) => {
+                let position = bookmark.position.min(state.buffer.len());
+                let line = state.buffer.get_line_number(position);
+                let text = state.buffer.get_line(line).unwrap_or_default();
+                (line, String::from_utf8_lossy(&text).into_owned())
+            }
+            None => self.closed_file_line(bookmark).unwrap_or_default(),
+        };
+
+        let preview: String = text.trim().chars().take(PREVIEW_CHARS).collect();
+        (file, line, preview)
+    }
+
+    /// The 0-indexed line and line text at a bookmark in a file that isn't
+    /// open, reading only up to the end of that line
+    fn closed_file_line(&self, bookmark: &Bookmark) -> Option<(usize, String)> {
+        let path = bookmark.file_path.as_deref()?;
+        let size = self.filesystem.metadata(path).ok()?.size as usize;
+        let position = bookmark.position.min(size);
+        let len = (position + PREVIEW_READ_BYTES).min(size);
+        let bytes = self.filesystem.read_range(path, 0, len).ok()?;
+
+        let line = bytes[..position].iter().filter(|&&b| b == b'\n').count();
+        let line_start = bytes[..position]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        let line_end = bytes[position..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(bytes.len(), |i| position + i);
+        Some((
+            line,
+            String::from_utf8_lossy(&bytes[line_start..line_end]).into_owned(),
+        ))
+    }
+}
+
+/// A line mark as a bookmark, so the picker can preview and jump to it like
+/// the others. Marks live in their buffer, so there is no file to reopen.
+fn line_mark_bookmark(buffer_id: BufferId, position: usize) -> Bookmark {
+    Bookmark {
+        buffer_id: Some(buffer_id),
+        file_path: None,
+        position,
+        last_used: None,
+    }
+}
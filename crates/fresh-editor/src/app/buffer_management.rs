@@ -10,17 +10,25 @@
 
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::app::warning_domains::WarningDomain;
+use crate::config::AutoCommandEvent;
+use crate::model::buffer::Buffer;
 use crate::model::event::{BufferId, Event, LeafId};
+use crate::primitives::detected_language::DetectedLanguage;
+use crate::services::stdin_spool::{SpoolContents, StdinSpool};
 use crate::state::EditorState;
 use crate::view::prompt::PromptType;
 use crate::view::split::SplitViewState;
 
 use super::help;
-use super::Editor;
+use super::{Editor, LOCAL_CONNECTION};
+
+/// Wait ID of the files given to `fresh --wait` (session clients count up from 1)
+const CLI_WAIT_ID: u64 = u64::MAX;
 
 impl Editor {
     /// Get the preferred split for opening a file.
@@ -39,6 +47,27 @@ impl Editor {
     /// If the file doesn't exist, creates an unsaved buffer with that filename.
     /// Saving the buffer will create the file.
     pub fn open_file(&mut self, path: &Path) -> anyhow::Result<BufferId> {
+        self.open_file_with_connection(path, None)
+    }
+
+    /// Open a file on another remote connection ("user@host[:port]") and
+    /// return its buffer ID
+    ///
+    /// The buffer keeps that connection's filesystem, so it is read from and
+    /// saved to the host it came from.
+    pub fn open_file_on_connection(
+        &mut self,
+        path: &Path,
+        connection: &str,
+    ) -> anyhow::Result<BufferId> {
+        self.open_file_with_connection(path, Some(connection))
+    }
+
+    pub(super) fn open_file_with_connection(
+        &mut self,
+        path: &Path,
+        connection: Option<&str>,
+    ) -> anyhow::Result<BufferId> {
         // Check whether the active buffer had a file path before loading.
         // If it didn't, open_file_no_focus may replace the empty initial buffer
         // in-place (same buffer ID, new content), and we need to notify plugins.
@@ -48,7 +77,18 @@ impl Editor {
             .and_then(|s| s.buffer.file_path())
             .is_some();
 
-        let buffer_id = self.open_file_no_focus(path)?;
+        let buffer_id = self.open_file_no_focus_on(path, connection)?;
+
+        // Remember local files across sessions for the Recent Files picker
+        let local_path = self
+            .buffers
+            .get(&buffer_id)
+            .filter(|s| s.buffer.filesystem().remote_connection_info().is_none())
+            .and_then(|s| s.buffer.file_path())
+            .map(Path::to_path_buf);
+        if let Some(local_path) = local_path {
+            self.record_recent_file(&local_path);
+        }
 
         // Check if this was an already-open buffer or a new one
         // For already-open buffers, just switch to them
@@ -68,6 +108,10 @@ impl Editor {
             self.position_history.commit_pending_movement();
         }
 
+        // Run on_open auto-commands after the status message below is set,
+        // rather than from set_active_buffer
+        let run_open_auto_commands = self.pending_open_auto_commands.remove(&buffer_id);
+
         self.set_active_buffer(buffer_id);
 
         // If the initial empty buffer was replaced in-place with file content,
@@ -100,11 +144,31 @@ impl Editor {
             .map(|s| s.buffer.is_binary())
             .unwrap_or(false);
 
+        let has_long_lines = self
+            .buffers
+            .get(&buffer_id)
+            .map(|s| s.long_lines)
+            .unwrap_or(false);
+
         // Show appropriate status message for binary vs regular files
         if is_binary {
-            self.status_message = Some(t!("buffer.opened_binary", name = display_name).to_string());
+            self.show_status_message(t!("buffer.opened_binary", name = display_name).to_string());
+        } else if has_long_lines {
+            self.show_status_message(
+                t!("buffer.opened_long_lines", name = display_name).to_string(),
+            );
         } else {
-            self.status_message = Some(t!("buffer.opened", name = display_name).to_string());
+            self.show_status_message(t!("buffer.opened", name = display_name).to_string());
+        }
+
+        // A file that replaced the initial empty buffer in-place is also freshly loaded
+        let freshly_loaded = is_new_buffer || !active_had_path;
+        if freshly_loaded && self.config.editor.large_file_line_scan {
+            self.start_background_line_scan(buffer_id);
+        }
+
+        if run_open_auto_commands {
+            self.run_auto_commands(AutoCommandEvent::OnOpen, buffer_id);
         }
 
         Ok(buffer_id)
@@ -117,10 +181,60 @@ impl Editor {
     ///
     /// If the file doesn't exist, creates an unsaved buffer with that filename.
     pub fn open_file_no_focus(&mut self, path: &Path) -> anyhow::Result<BufferId> {
+        self.open_file_no_focus_on(path, None)
+    }
+
+    /// Open a file without switching focus to it, on the remote connection
+    /// `connection` ("user@host[:port]"), on this machine for
+    /// [`LOCAL_CONNECTION`], or on the editor's filesystem
+    pub(super) fn open_file_no_focus_on(
+        &mut self,
+        path: &Path,
+        connection: Option<&str>,
+    ) -> anyhow::Result<BufferId> {
+        self.open_file_as(path, connection, None)
+    }
+
+    /// Read a file into the placeholder buffer `buffer_id`, which is already
+    /// in its tabs, without switching focus to it
+    pub(super) fn open_file_into(
+        &mut self,
+        path: &Path,
+        buffer_id: BufferId,
+    ) -> anyhow::Result<BufferId> {
+        self.open_file_as(path, None, Some(buffer_id))
+    }
+
+    /// Open a file without switching focus to it, into the placeholder
+    /// buffer `into` if given
+    fn open_file_as(
+        &mut self,
+        path: &Path,
+        connection: Option<&str>,
+        into: Option<BufferId>,
+    ) -> anyhow::Result<BufferId> {
+        // Naming the primary connection is the same as naming none, and so
+        // is naming this machine when the editor isn't remote
+        let primary = self
+            .filesystem
+            .remote_connection_info()
+            .unwrap_or(LOCAL_CONNECTION);
+        let connection = connection.filter(|c| *c != primary);
+        let filesystem = match connection {
+            Some(LOCAL_CONNECTION) => Arc::clone(&self.local_filesystem),
+            Some(connection) => self
+                .filesystem
+                .connection_filesystem(connection)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(t!("buffer.unknown_connection", connection = connection))
+                })?,
+            None => Arc::clone(&self.filesystem),
+        };
+
         // Resolve relative paths against appropriate base directory
         // For remote mode, use the remote home directory; for local, use working_dir
-        let base_dir = if self.filesystem.remote_connection_info().is_some() {
-            self.filesystem
+        let base_dir = if filesystem.remote_connection_info().is_some() {
+            filesystem
                 .home_dir()
                 .unwrap_or_else(|_| self.working_dir.clone())
         } else {
@@ -135,18 +249,24 @@ impl Editor {
 
         // Determine if we're opening a non-existent file (for creating new files)
         // Use filesystem trait method to support remote files
-        let file_exists = self.filesystem.exists(&resolved_path);
+        let file_exists = filesystem.exists(&resolved_path);
 
         // Save the user-visible (non-canonicalized) path for language detection.
         // Glob patterns in language config should match the path as the user sees it,
         // not the canonical path (e.g., on macOS /var -> /private/var symlinks).
         let display_path = resolved_path.clone();
 
+        // With follow_symlinks off, a symlink keeps its own path (only its parent
+        // directory is canonicalized); saving writes through the link.
+        let keep_link = file_exists
+            && !self.config.editor.follow_symlinks
+            && filesystem.is_symlink(&resolved_path).unwrap_or(false);
+
         // Canonicalize the path to resolve symlinks and normalize path components
         // This ensures consistent path representation throughout the editor
         // For non-existent files, we need to canonicalize the parent directory and append the filename
-        let canonical_path = if file_exists {
-            self.filesystem
+        let canonical_path = if file_exists && !keep_link {
+            filesystem
                 .canonicalize(&resolved_path)
                 .unwrap_or_else(|_| resolved_path.clone())
         } else {
@@ -156,7 +276,7 @@ impl Editor {
                     // No parent means just a filename, use base dir
                     base_dir.clone()
                 } else {
-                    self.filesystem
+                    filesystem
                         .canonicalize(parent)
                         .unwrap_or_else(|_| parent.to_path_buf())
                 };
@@ -174,24 +294,48 @@ impl Editor {
         // Check if the path is a directory (after following symlinks via canonicalize)
         // Directories cannot be opened as files in the editor
         // Use filesystem trait method to support remote files
-        if self.filesystem.is_dir(path).unwrap_or(false) {
+        if filesystem.is_dir(path).unwrap_or(false) {
             anyhow::bail!(t!("buffer.cannot_open_directory"));
         }
 
+        // FIFOs, sockets and devices are not files: reading a FIFO blocks until
+        // a writer shows up, and devices may never end. Refuse before opening.
+        if file_exists {
+            if let Ok(kind) = filesystem.file_kind(path) {
+                if kind.is_special() {
+                    anyhow::bail!(t!(
+                        "buffer.cannot_open_special",
+                        kind = kind.description(),
+                        path = path.display().to_string()
+                    ));
+                }
+            }
+        }
+
         // Check if file is already open - return existing buffer without switching
         let already_open = self
             .buffers
             .iter()
-            .find(|(_, state)| state.buffer.file_path() == Some(path))
+            .find(|(id, state)| {
+                into.is_none()
+                    && state.buffer.file_path() == Some(path)
+                    && self
+                        .buffer_metadata
+                        .get(id)
+                        .and_then(|m| m.remote_connection.as_deref())
+                        == connection
+            })
             .map(|(id, _)| *id);
 
         if let Some(id) = already_open {
+            // A restored file that wasn't read yet is read now
+            self.load_deferred_buffer(id);
             return Ok(id);
         }
 
         // If the current buffer is empty and unmodified, replace it instead of creating a new one
         // Note: Don't replace composite buffers (they appear empty but are special views)
-        let replace_current = {
+        let replace_current = into.is_none() && {
             let current_state = self.buffers.get(&self.active_buffer()).unwrap();
             !current_state.is_composite_buffer
                 && current_state.buffer.is_empty()
@@ -199,7 +343,9 @@ impl Editor {
                 && current_state.buffer.file_path().is_none()
         };
 
-        let buffer_id = if replace_current {
+        let buffer_id = if let Some(id) = into {
+            id
+        } else if replace_current {
             // Reuse the current empty buffer
             self.active_buffer()
         } else {
@@ -217,13 +363,37 @@ impl Editor {
             self.grammar_registry.available_syntaxes().len(),
             self.grammar_registry.user_extensions_debug()
         );
-        let mut state = if file_exists {
+        // Remote files that would be read in one request are streamed in
+        // chunks instead, with the buffer opened at once
+        let streamed = if file_exists {
+            self.remote_download_sample(&filesystem, &canonical_path)
+                .and_then(|(size, sample)| {
+                    let buffer = crate::model::buffer::Buffer::for_streamed_file(
+                        canonical_path.clone(),
+                        &sample,
+                        Arc::clone(&filesystem),
+                    )?;
+                    Some((size, buffer))
+                })
+        } else {
+            None
+        };
+        let stream_size = streamed.as_ref().map(|(size, _)| *size);
+
+        let mut state = if let Some((_, buffer)) = streamed {
+            let detected = crate::primitives::detected_language::DetectedLanguage::from_path(
+                &display_path,
+                &self.grammar_registry,
+                &self.config.languages,
+            );
+            EditorState::from_buffer_with_language(buffer, detected)
+        } else if file_exists {
             // Load from canonical path (for I/O and dedup), detect language from
             // display path (for glob pattern matching against user-visible names).
             let buffer = crate::model::buffer::Buffer::load_from_file(
                 &canonical_path,
                 self.config.editor.large_file_threshold_bytes as usize,
-                Arc::clone(&self.filesystem),
+                Arc::clone(&filesystem),
             )?;
             let detected = crate::primitives::detected_language::DetectedLanguage::from_path(
                 &display_path,
@@ -235,20 +405,31 @@ impl Editor {
             // File doesn't exist - create empty buffer with the file path set
             EditorState::new_with_path(
                 self.config.editor.large_file_threshold_bytes as usize,
-                Arc::clone(&self.filesystem),
+                Arc::clone(&filesystem),
                 path.to_path_buf(),
             )
         };
         // Note: line_wrap_enabled is set on SplitViewState.viewport when the split is created
 
+        let detected_encoding = file_exists.then(|| state.buffer.encoding());
+
         // Check if the buffer contains binary content
         let is_binary = state.buffer.is_binary();
         if is_binary {
             // Make binary buffers read-only
             state.editing_disabled = true;
             tracing::info!("Detected binary file: {}", path.display());
+        } else if file_exists {
+            // Re-apply language/encoding/line ending the user picked before
+            self.apply_file_overrides(&mut state, path);
         }
 
+        // Minified files: one enormous line makes wrapping-free rendering,
+        // highlighting context and bracket scans slow, so soft-wrap them and
+        // keep the expensive per-character work inside the viewport
+        let long_lines =
+            !is_binary && state.detect_long_lines(self.config.editor.long_line_threshold);
+
         // Set whitespace visibility, use_tabs, and tab_size based on language config
         // with fallback to global editor config for tab_size
         // Use the buffer's stored language (already set by from_file_with_languages)
@@ -256,6 +437,8 @@ impl Editor {
             crate::config::WhitespaceVisibility::from_editor_config(&self.config.editor);
         state.buffer_settings.auto_close = self.config.editor.auto_close;
         state.buffer_settings.auto_surround = self.config.editor.auto_surround;
+        state.buffer_settings.word_rules = self.config.word_rules(&state.language);
+        state.buffer_settings.auto_close_pairs = self.config.auto_close_pairs(&state.language);
         if let Some(lang_config) = self.config.languages.get(&state.language) {
             whitespace = whitespace.with_language_tab_override(lang_config.show_whitespace_tabs);
             state.buffer_settings.use_tabs = lang_config.use_tabs;
@@ -285,29 +468,47 @@ impl Editor {
             .configure_for_line_numbers(self.config.editor.line_numbers);
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(buffer_id, self.new_event_log());
 
         // Create metadata for this buffer
         let mut metadata =
             super::types::BufferMetadata::with_file(path.to_path_buf(), &self.working_dir);
+        metadata.remote_connection = connection.map(str::to_string);
+        metadata.detected_encoding = detected_encoding;
 
         // Mark binary files in metadata and disable LSP
         if is_binary {
             metadata.binary = true;
             metadata.read_only = true;
             metadata.disable_lsp(t!("buffer.binary_file").to_string());
+        } else if connection.is_some() {
+            // Language servers run on the editor's own host only, so files
+            // opened on another host, or locally while editing remotely, go
+            // without
+            metadata.disable_lsp(t!("lsp.other_host").to_string());
         }
 
         // Check if the file is read-only on disk (filesystem permissions)
         if file_exists && !metadata.read_only {
-            if let Ok(file_meta) = self.filesystem.metadata(path) {
+            if let Ok(file_meta) = filesystem.metadata(path) {
                 if file_meta.is_readonly {
                     metadata.read_only = true;
                 }
             }
         }
 
+        // A remembered "Toggle Read Only" choice wins over the file permissions
+        if !is_binary {
+            if let Some(read_only) = self.file_overrides.get(path).and_then(|o| o.read_only) {
+                metadata.read_only = read_only;
+            }
+        }
+
+        // --readonly wins over everything
+        if self.open_read_only {
+            metadata.read_only = true;
+        }
+
         // Mark read-only files (library, binary, or filesystem-readonly) as editing-disabled
         if metadata.read_only {
             if let Some(state) = self.buffers.get_mut(&buffer_id) {
@@ -315,32 +516,41 @@ impl Editor {
             }
         }
 
-        // Notify LSP about the newly opened file (skip for binary files)
-        if !is_binary {
+        // Notify LSP about the newly opened file (skip for binary files and
+        // files on other hosts)
+        if !is_binary && connection.is_none() {
             self.notify_lsp_file_opened(path, buffer_id, &mut metadata);
         }
 
         // Store metadata for this buffer
         self.buffer_metadata.insert(buffer_id, metadata);
 
-        // Add buffer to the preferred split's tabs (but don't switch to it)
-        // Uses preferred_split_for_file() to avoid opening in labeled splits (e.g., sidebars)
-        let target_split = self.preferred_split_for_file();
-        if let Some(view_state) = self.split_view_states.get_mut(&target_split) {
-            view_state.add_buffer(buffer_id);
-            // Initialize per-buffer view state for the new buffer with config defaults
-            let buf_state = view_state.ensure_buffer_state(buffer_id);
-            buf_state.apply_config_defaults(
-                self.config.editor.line_numbers,
-                self.config.editor.line_wrap,
-                self.config.editor.wrap_indent,
-                self.config.editor.rulers.clone(),
-            );
-        }
+        if let Some(size) = stream_size {
+            self.start_remote_download(buffer_id, Arc::clone(&filesystem), path, size);
+        }
+
+        // A placeholder buffer is in its tabs already, with the state the
+        // workspace saved
+        if into.is_none() {
+            // Add buffer to the preferred split's tabs (but don't switch to it)
+            // Uses preferred_split_for_file() to avoid opening in labeled splits (e.g., sidebars)
+            let target_split = self.preferred_split_for_file();
+            if let Some(view_state) = self.split_view_states.get_mut(&target_split) {
+                view_state.add_buffer(buffer_id);
+                // Initialize per-buffer view state for the new buffer with config defaults
+                let buf_state = view_state.ensure_buffer_state(buffer_id);
+                buf_state.apply_config_defaults(
+                    self.config.editor.line_numbers,
+                    self.config.editor.line_wrap || long_lines,
+                    self.config.editor.wrap_indent,
+                    self.config.editor.rulers.clone(),
+                );
+            }
 
-        // Restore global file state (scroll/cursor position) if available
-        // This persists file positions across projects and editor instances
-        self.restore_global_file_state(buffer_id, path, target_split);
+            // Restore global file state (scroll/cursor position) if available
+            // This persists file positions across projects and editor instances
+            self.restore_global_file_state(buffer_id, path, target_split);
+        }
 
         // Emit control event
         self.emit_event(
@@ -362,6 +572,7 @@ impl Editor {
                 path: path.to_path_buf(),
             },
         );
+        self.queue_open_auto_commands(buffer_id);
 
         Ok(buffer_id)
     }
@@ -419,8 +630,7 @@ impl Editor {
         let state = EditorState::from_buffer_with_language(buffer, detected);
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(buffer_id, self.new_event_log());
 
         // Create metadata
         let metadata =
@@ -443,7 +653,7 @@ impl Editor {
         self.set_active_buffer(buffer_id);
 
         let display_name = path.display().to_string();
-        self.status_message = Some(t!("buffer.opened", name = display_name).to_string());
+        self.show_status_message(t!("buffer.opened", name = display_name).to_string());
 
         Ok(buffer_id)
     }
@@ -520,8 +730,7 @@ impl Editor {
             .configure_for_line_numbers(self.config.editor.line_numbers);
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(buffer_id, self.new_event_log());
 
         let metadata =
             super::types::BufferMetadata::with_file(path.to_path_buf(), &self.working_dir);
@@ -547,7 +756,9 @@ impl Editor {
 
     /// Reload the current file with a specific encoding.
     ///
-    /// Requires the buffer to have no unsaved modifications.
+    /// Requires the buffer to have no unsaved modifications. The file's bytes
+    /// are decoded again, so byte offsets change: the undo history is
+    /// cleared and cursors follow their lines as on revert.
     pub fn reload_with_encoding(
         &mut self,
         encoding: crate::model::buffer::Encoding,
@@ -579,19 +790,28 @@ impl Editor {
         )?;
 
         // Update the buffer in the editor state
+        let mut old_text = None;
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            old_text = state.buffer.to_string();
             state.buffer = new_buffer;
             // Invalidate highlighting
             state.highlighter.invalidate_all();
         }
+        let new_text = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.to_string());
 
-        // Reset cursor to start in the split view state
-        let split_id = self.split_manager.active_split();
-        if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
-            if let Some(buf_state) = view_state.keyed_states.get_mut(&buffer_id) {
-                buf_state.cursors = crate::model::cursor::Cursors::new();
-            }
+        // Move cursors and scroll positions to where their lines went
+        self.relocate_views_by_content(buffer_id, old_text.as_deref(), new_text.as_deref());
+
+        // Old edits refer to the old decoding's byte offsets
+        let new_log = self.new_event_log();
+        if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
+            *event_log = new_log;
         }
+        self.seen_byte_ranges.remove(&buffer_id);
+        self.notify_lsp_file_changed(&path);
 
         Ok(())
     }
@@ -656,8 +876,7 @@ impl Editor {
             .configure_for_line_numbers(self.config.editor.line_numbers);
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(buffer_id, self.new_event_log());
 
         let metadata =
             super::types::BufferMetadata::with_file(path.to_path_buf(), &self.working_dir);
@@ -685,7 +904,7 @@ impl Editor {
             .map(|m| m.display_name.clone())
             .unwrap_or_else(|| path.display().to_string());
 
-        self.status_message = Some(t!("buffer.opened", name = display_name).to_string());
+        self.show_status_message(t!("buffer.opened", name = display_name).to_string());
 
         Ok(buffer_id)
     }
@@ -698,28 +917,44 @@ impl Editor {
         use crate::workspace::PersistedFileWorkspace;
 
         // Load the per-file state for this path (lazy load from disk)
-        let file_state = match PersistedFileWorkspace::load(path) {
-            Some(state) => state,
-            None => return, // No saved state for this file
-        };
+        let file_state =
+            match PersistedFileWorkspace::load(&self.dir_context.file_states_dir(), path) {
+                Some(state) => state,
+                None => return, // No saved state for this file
+            };
 
-        // Get the buffer to validate positions
-        let max_pos = match self.buffers.get(&buffer_id) {
-            Some(buffer) => buffer.buffer.len(),
-            None => return,
+        // The file may have shrunk since: clamp to its end, on a character
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        let max_pos = state.buffer.len();
+        let clamp = |pos: usize| state.buffer.snap_to_char_boundary(pos.min(max_pos));
+        let cursor_pos = clamp(file_state.cursor.position);
+        let anchor = file_state.cursor.anchor.map(clamp);
+        // Past the end, scroll to the cursor's line instead
+        let top_byte = if file_state.scroll.top_byte <= max_pos {
+            file_state.scroll.top_byte
+        } else {
+            let cursor_line = state.buffer.get_line_number(cursor_pos);
+            state.buffer.line_start_offset(cursor_line).unwrap_or(0)
         };
 
         // Apply cursor position and viewport (scroll) state to SplitViewState
-        if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+        let remember_positions = self.config.session.remember_file_positions;
+        if let Some(view_state) = self
+            .split_view_states
+            .get_mut(&split_id)
+            .filter(|_| remember_positions)
+        {
             if let Some(buf_state) = view_state.keyed_states.get_mut(&buffer_id) {
-                let cursor_pos = file_state.cursor.position.min(max_pos);
                 buf_state.cursors.primary_mut().position = cursor_pos;
-                buf_state.cursors.primary_mut().anchor =
-                    file_state.cursor.anchor.map(|a| a.min(max_pos));
+                buf_state.cursors.primary_mut().anchor = anchor;
             }
-            view_state.viewport.top_byte = file_state.scroll.top_byte;
+            view_state.viewport.top_byte = top_byte;
             view_state.viewport.left_column = file_state.scroll.left_column;
         }
+
+        self.restore_line_marks(buffer_id, &file_state.line_marks);
     }
 
     /// Save file state when a buffer is closed (for per-file session persistence)
@@ -781,10 +1016,18 @@ impl Editor {
             compose_width: None,
             plugin_state: std::collections::HashMap::new(),
             folds: Vec::new(),
+            line_marks: self
+                .buffers
+                .get(&buffer_id)
+                .map(super::line_marks::line_mark_lines)
+                .unwrap_or_default(),
+            show_line_numbers: None,
+            line_wrap: None,
+            whitespace: None,
         };
 
         // Save to disk
-        PersistedFileWorkspace::save(&abs_path, file_state);
+        PersistedFileWorkspace::save(&self.dir_context.file_states_dir(), &abs_path, file_state);
         tracing::debug!("Saved file state on close for {:?}", abs_path);
     }
 
@@ -793,6 +1036,7 @@ impl Editor {
     /// Line and column are 1-indexed (matching typical editor conventions).
     /// If the line is out of bounds, navigates to the last line.
     /// If the column is out of bounds, navigates to the end of the line.
+    /// With `editor.center_on_goto`, the target line is centered in the view.
     pub fn goto_line_col(&mut self, line: usize, column: Option<usize>) {
         if line == 0 {
             return; // Line numbers are 1-indexed
@@ -809,6 +1053,13 @@ impl Editor {
 
         if let Some(state) = self.buffers.get(&buffer_id) {
             let has_line_index = state.buffer.line_count().is_some();
+            if !has_line_index {
+                // Large file without line feed counts: there is no way to map
+                // the line to a byte offset yet, so count lines first and jump
+                // once the scan completes.
+                self.goto_line_col_after_scan(buffer_id, line, column);
+                return;
+            }
             let has_line_scan = state.buffer.has_line_feed_scan();
             let buffer_len = state.buffer.len();
 
@@ -821,7 +1072,7 @@ impl Editor {
             // since offset_to_position may not be able to reverse-resolve it accurately.
             let mut known_line: Option<usize> = None;
 
-            let position = if has_line_scan && has_line_index {
+            let position = if has_line_scan {
                 // Scanned large file: use tree metadata to find exact line offset
                 let max_line = state.buffer.line_count().unwrap_or(1).saturating_sub(1);
                 let actual_line = target_line.min(max_line);
@@ -837,8 +1088,7 @@ impl Editor {
                     0
                 }
             } else {
-                // Small file with full line starts or no line index:
-                // use exact line position
+                // Small file with full line starts: use exact line position
                 let max_line = state.buffer.line_count().unwrap_or(1).saturating_sub(1);
                 let actual_line = target_line.min(max_line);
                 state.buffer.line_col_to_position(actual_line, target_col)
@@ -865,6 +1115,33 @@ impl Editor {
                 state.primary_cursor_line_number = crate::model::buffer::LineNumber::Absolute(line);
             }
         }
+
+        if self.config.editor.center_on_goto {
+            // Size the viewport to its content area first: a file opened
+            // before its first render still has the whole terminal's height
+            self.recompute_layout(self.terminal_width, self.terminal_height);
+            self.apply_event_to_active_buffer(&Event::Recenter);
+        }
+    }
+
+    /// Defer `goto_line_col` until the line feed scan of `buffer_id` finishes,
+    /// starting the scan unless one is already running for it
+    fn goto_line_col_after_scan(
+        &mut self,
+        buffer_id: BufferId,
+        line: usize,
+        column: Option<usize>,
+    ) {
+        let scanning = self
+            .line_scan_state
+            .as_ref()
+            .is_some_and(|scan| scan.buffer_id == buffer_id);
+        if !scanning {
+            self.start_incremental_line_scan(false);
+        }
+        if let Some(scan) = self.line_scan_state.as_mut() {
+            scan.goto_on_complete = Some((line, column));
+        }
     }
 
     /// Select a range in the active buffer. Lines/columns are 1-indexed.
@@ -987,9 +1264,10 @@ impl Editor {
         state
             .buffer
             .set_default_line_ending(self.config.editor.default_line_ending.to_line_ending());
+        state.buffer_settings.word_rules = self.config.word_rules(&state.language);
+        state.buffer_settings.auto_close_pairs = self.config.auto_close_pairs(&state.language);
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(buffer_id, self.new_event_log());
         self.buffer_metadata
             .insert(buffer_id, crate::app::types::BufferMetadata::new());
 
@@ -1008,7 +1286,7 @@ impl Editor {
             );
         }
 
-        self.status_message = Some(t!("buffer.new").to_string());
+        self.show_status_message(t!("buffer.new").to_string());
 
         buffer_id
     }
@@ -1026,6 +1304,22 @@ impl Editor {
         &mut self,
         temp_path: &Path,
         thread_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
+    ) -> AnyhowResult<BufferId> {
+        self.open_stdin_spool(Arc::new(StdinSpool::from_file(temp_path)), thread_handle)
+    }
+
+    /// Create a new buffer from stdin collected by a [`StdinSpool`]
+    ///
+    /// Input still held in memory is loaded directly; input that spilled to
+    /// a temp file is loaded lazily from it, like [`Self::open_stdin_buffer`].
+    ///
+    /// # Arguments
+    /// * `spool` - Where the background thread is collecting stdin
+    /// * `thread_handle` - Optional handle to the background thread reading stdin
+    pub fn open_stdin_spool(
+        &mut self,
+        spool: Arc<StdinSpool>,
+        thread_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
     ) -> AnyhowResult<BufferId> {
         // Save current position before switching to new buffer
         self.position_history.commit_pending_movement();
@@ -1058,20 +1352,32 @@ impl Editor {
             id
         };
 
-        // Get file size for status message before loading
-        let file_size = self.filesystem.metadata(temp_path)?.size as usize;
-
-        // Load from temp file using EditorState::from_file_with_languages
-        // This enables lazy chunk loading for large inputs (>100MB by default)
-        let mut state = EditorState::from_file_with_languages(
-            temp_path,
-            self.terminal_width,
-            self.terminal_height,
-            self.config.editor.large_file_threshold_bytes as usize,
-            &self.grammar_registry,
-            &self.config.languages,
-            Arc::clone(&self.filesystem),
-        )?;
+        let (mut state, file_size) = match spool.contents_since(0) {
+            SpoolContents::Memory(bytes) => {
+                let size = bytes.len();
+                let buffer = Buffer::from_bytes(bytes, Arc::clone(&self.filesystem));
+                let state =
+                    EditorState::from_buffer_with_language(buffer, DetectedLanguage::plain_text());
+                (state, size)
+            }
+            SpoolContents::File(temp_path) => {
+                // Get file size for status message before loading
+                let file_size = self.filesystem.metadata(&temp_path)?.size as usize;
+
+                // Load from temp file using EditorState::from_file_with_languages
+                // This enables lazy chunk loading for large inputs (>100MB by default)
+                let state = EditorState::from_file_with_languages(
+                    &temp_path,
+                    self.terminal_width,
+                    self.terminal_height,
+                    self.config.editor.large_file_threshold_bytes as usize,
+                    &self.grammar_registry,
+                    &self.config.languages,
+                    Arc::clone(&self.filesystem),
+                )?;
+                (state, file_size)
+            }
+        };
 
         // Clear the file path so the buffer is "unnamed" for save purposes
         // The Unloaded chunks still reference the temp file for lazy loading
@@ -1079,10 +1385,12 @@ impl Editor {
         // Clear modified flag - content is "fresh" from stdin (vim behavior)
         state.buffer.clear_modified();
 
-        // Set tab size, auto_close, and auto_surround from config
+        // Set tab size, auto_close, auto_surround and word rules from config
         state.buffer_settings.tab_size = self.config.editor.tab_size;
         state.buffer_settings.auto_close = self.config.editor.auto_close;
         state.buffer_settings.auto_surround = self.config.editor.auto_surround;
+        state.buffer_settings.word_rules = self.config.word_rules(&state.language);
+        state.buffer_settings.auto_close_pairs = self.config.auto_close_pairs(&state.language);
 
         // Apply line_numbers default from config
         state
@@ -1090,8 +1398,7 @@ impl Editor {
             .configure_for_line_numbers(self.config.editor.line_numbers);
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(buffer_id, self.new_event_log());
 
         // Create metadata for this buffer (no file path)
         let metadata =
@@ -1117,7 +1424,7 @@ impl Editor {
         // If no thread handle, it means data is already complete (testing scenario)
         let complete = thread_handle.is_none();
         self.stdin_streaming = Some(super::StdinStreamingState {
-            temp_path: temp_path.to_path_buf(),
+            spool,
             buffer_id,
             last_known_size: file_size,
             complete,
@@ -1125,7 +1432,7 @@ impl Editor {
         });
 
         // Status will be updated by poll_stdin_streaming
-        self.status_message = Some(t!("stdin.streaming").to_string());
+        self.show_status_message(t!("stdin.streaming").to_string());
 
         Ok(buffer_id)
     }
@@ -1133,7 +1440,7 @@ impl Editor {
     /// Poll stdin streaming state and extend buffer if file grew.
     /// Returns true if the status changed (needs render).
     pub fn poll_stdin_streaming(&mut self) -> bool {
-        let Some(ref mut stream_state) = self.stdin_streaming else {
+        let Some(stream_state) = &self.stdin_streaming else {
             return false;
         };
 
@@ -1143,28 +1450,18 @@ impl Editor {
 
         let mut changed = false;
 
-        // Check current file size
-        let current_size = self
-            .filesystem
-            .metadata(&stream_state.temp_path)
-            .map(|m| m.size as usize)
-            .unwrap_or(stream_state.last_known_size);
-
-        // If file grew, extend the buffer
-        if current_size > stream_state.last_known_size {
-            if let Some(editor_state) = self.buffers.get_mut(&stream_state.buffer_id) {
-                editor_state
-                    .buffer
-                    .extend_streaming(&stream_state.temp_path, current_size);
-            }
-            stream_state.last_known_size = current_size;
-
+        // If stdin grew, extend the buffer
+        if let Some(current_size) = self.pull_stdin_data() {
             // Update status message with current progress
             self.status_message =
                 Some(t!("stdin.streaming_bytes", bytes = current_size).to_string());
             changed = true;
         }
 
+        let Some(ref mut stream_state) = self.stdin_streaming else {
+            return changed;
+        };
+
         // Check if background thread has finished
         let thread_finished = stream_state
             .thread_handle
@@ -1181,12 +1478,15 @@ impl Editor {
                     }
                     Ok(Err(e)) => {
                         tracing::warn!("Stdin streaming error: {}", e);
-                        self.status_message =
-                            Some(t!("stdin.read_error", error = e.to_string()).to_string());
+                        self.show_important_status_message(
+                            t!("stdin.read_error", error = e.to_string()).to_string(),
+                        );
                     }
                     Err(_) => {
                         tracing::warn!("Stdin streaming thread panicked");
-                        self.status_message = Some(t!("stdin.read_error_panic").to_string());
+                        self.show_important_status_message(
+                            t!("stdin.read_error_panic").to_string(),
+                        );
                     }
                 }
             }
@@ -1200,28 +1500,55 @@ impl Editor {
     /// Mark stdin streaming as complete.
     /// Called when the background thread finishes.
     pub fn complete_stdin_streaming(&mut self) {
-        if let Some(ref mut stream_state) = self.stdin_streaming {
-            stream_state.complete = true;
+        let Some(stream_state) = self.stdin_streaming.as_mut() else {
+            return;
+        };
+        stream_state.complete = true;
 
-            // Final poll to get any remaining data
-            let final_size = self
-                .filesystem
-                .metadata(&stream_state.temp_path)
-                .map(|m| m.size as usize)
-                .unwrap_or(stream_state.last_known_size);
+        // Final poll to get any remaining data
+        self.pull_stdin_data();
 
-            if final_size > stream_state.last_known_size {
-                if let Some(editor_state) = self.buffers.get_mut(&stream_state.buffer_id) {
-                    editor_state
-                        .buffer
-                        .extend_streaming(&stream_state.temp_path, final_size);
+        let bytes = self
+            .stdin_streaming
+            .as_ref()
+            .map_or(0, |s| s.last_known_size);
+        self.show_status_message(t!("stdin.read_complete", bytes = bytes).to_string());
+    }
+
+    /// Append stdin that arrived since the last poll to the stdin buffer.
+    /// Returns the new size if it grew.
+    fn pull_stdin_data(&mut self) -> Option<usize> {
+        let stream_state = self.stdin_streaming.as_mut()?;
+        let old_size = stream_state.last_known_size;
+        let editor_state = self.buffers.get_mut(&stream_state.buffer_id);
+
+        let new_size = match stream_state.spool.contents_since(old_size) {
+            SpoolContents::Memory(bytes) => {
+                let new_size = old_size + bytes.len();
+                if let Some(editor_state) = editor_state {
+                    editor_state.buffer.append_streaming_bytes(bytes);
+                }
+                new_size
+            }
+            SpoolContents::File(temp_path) => {
+                // The file holds everything from the start, including bytes
+                // already appended while stdin was still in memory
+                let new_size = self
+                    .filesystem
+                    .metadata(&temp_path)
+                    .map(|m| m.size as usize)
+                    .unwrap_or(old_size);
+                if new_size > old_size {
+                    if let Some(editor_state) = editor_state {
+                        editor_state.buffer.extend_streaming(&temp_path, new_size);
+                    }
                 }
-                stream_state.last_known_size = final_size;
+                new_size
             }
+        };
 
-            self.status_message =
-                Some(t!("stdin.read_complete", bytes = stream_state.last_known_size).to_string());
-        }
+        stream_state.last_known_size = new_size;
+        (new_size > old_size).then_some(new_size)
     }
 
     /// Check if stdin streaming is active (not complete).
@@ -1232,6 +1559,35 @@ impl Editor {
             .unwrap_or(false)
     }
 
+    /// Write the stdin buffer as it is now to `output`, for `--to-stdout`.
+    /// Writes nothing if stdin wasn't read or its buffer was closed.
+    pub fn write_stdin_buffer(&mut self, output: &mut dyn std::io::Write) -> AnyhowResult<()> {
+        const CHUNK_SIZE: usize = 1024 * 1024;
+
+        let Some(buffer_id) = self.stdin_streaming.as_ref().map(|s| s.buffer_id) else {
+            return Ok(());
+        };
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return Ok(());
+        };
+
+        // Chunk by chunk, so lazily loaded input isn't read into memory at once
+        let len = state.buffer.len();
+        let mut offset = 0;
+        while offset < len {
+            let chunk = state
+                .buffer
+                .get_text_range_mut(offset, CHUNK_SIZE.min(len - offset))?;
+            if chunk.is_empty() {
+                break;
+            }
+            output.write_all(&chunk)?;
+            offset += chunk.len();
+        }
+        output.flush()?;
+        Ok(())
+    }
+
     /// Create a new virtual buffer (not backed by a file)
     ///
     /// # Arguments
@@ -1267,8 +1623,7 @@ impl Editor {
             .configure_for_line_numbers(self.config.editor.line_numbers);
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(buffer_id, self.new_event_log());
 
         // Set virtual buffer metadata
         let metadata = super::types::BufferMetadata::virtual_buffer(name, mode, read_only);
@@ -1501,7 +1856,7 @@ impl Editor {
     /// Otherwise, opens the warning log file for the user to view.
     pub fn show_warnings_popup(&mut self) {
         if !self.warning_domains.has_any_warnings() {
-            self.status_message = Some(t!("warnings.none").to_string());
+            self.show_status_message(t!("warnings.none").to_string());
             return;
         }
 
@@ -1548,9 +1903,9 @@ impl Editor {
 
         if !self.warning_domains.lsp.has_warnings() {
             if self.lsp_status.is_empty() {
-                self.status_message = Some(t!("lsp.no_server_active").to_string());
+                self.show_status_message(t!("lsp.no_server_active").to_string());
             } else {
-                self.status_message = Some(t!("lsp.status", status = &self.lsp_status).to_string());
+                self.show_status_message(t!("lsp.status", status = &self.lsp_status).to_string());
             }
             return;
         }
@@ -1628,6 +1983,20 @@ impl Editor {
         if let Some((wait_id, _)) = self.wait_tracking.remove(&id) {
             self.completed_waits.push(wait_id);
         }
+        // `fresh --wait` returns once every file it opened is closed
+        let mut last_waited_file = false;
+        if let Some(cli_wait) = &mut self.cli_wait {
+            if cli_wait.buffers.remove(&id) {
+                if self
+                    .buffers
+                    .get(&id)
+                    .is_some_and(|s| s.buffer.is_modified())
+                {
+                    cli_wait.abandoned = true;
+                }
+                last_waited_file = cli_wait.buffers.is_empty();
+            }
+        }
 
         // Save file state before closing (for per-file session persistence)
         self.save_file_state_on_close(id);
@@ -1729,6 +2098,7 @@ impl Editor {
         self.event_logs.remove(&id);
         self.seen_byte_ranges.remove(&id);
         self.buffer_metadata.remove(&id);
+        self.deferred_buffers.remove(&id);
         if let Some((request_id, _, _)) = self.semantic_tokens_in_flight.remove(&id) {
             self.pending_semantic_token_requests.remove(&request_id);
         }
@@ -1739,6 +2109,10 @@ impl Editor {
         self.semantic_tokens_range_last_request.remove(&id);
         self.semantic_tokens_range_applied.remove(&id);
         self.semantic_tokens_full_debounce.remove(&id);
+        self.url_buffers.remove(&id);
+        self.buffer_saved_at.remove(&id);
+        self.end_open_buffers_diff_for(id);
+        self.reported_modified.remove(&id);
 
         // Remove buffer from panel_ids mapping if it was a panel buffer
         // This prevents stale entries when the same panel_id is reused later
@@ -1755,6 +2129,11 @@ impl Editor {
             self.focus_file_explorer();
         }
 
+        // Quit as usual, so other unsaved buffers are still asked about
+        if last_waited_file {
+            self.quit();
+        }
+
         Ok(())
     }
 
@@ -2427,6 +2806,38 @@ impl Editor {
             end_column,
             message,
             wait_id,
+            connection: None,
+            search: None,
+        });
+    }
+
+    /// Move to the first match of a regex in the file queued last, once it
+    /// has been opened
+    pub fn queue_file_search(&mut self, pattern: String) {
+        if let Some(pending) = self.pending_file_opens.last_mut() {
+            pending.search = Some(pattern);
+        }
+    }
+
+    /// Queue a file on another remote connection ("user@host[:port]") to be
+    /// opened after the TUI starts
+    pub fn queue_remote_file_open(
+        &mut self,
+        path: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+        connection: String,
+    ) {
+        self.pending_file_opens.push(super::PendingFileOpen {
+            path,
+            line,
+            column,
+            end_line: None,
+            end_column: None,
+            message: None,
+            wait_id: None,
+            connection: Some(connection),
+            search: None,
         });
     }
 
@@ -2449,8 +2860,19 @@ impl Editor {
                 pending_file.path
             );
 
-            match self.open_file(&pending_file.path) {
-                Ok(_) => {
+            // `--wait` callers wait for the buffer to close, so always open those as text
+            if pending_file.wait_id.is_none() && self.try_open_externally(&pending_file.path) {
+                processed_any = true;
+                continue;
+            }
+
+            let open_before: HashSet<BufferId> = self.buffers.keys().copied().collect();
+            let opened = match &pending_file.connection {
+                Some(connection) => self.open_file_on_connection(&pending_file.path, connection),
+                None => self.open_file(&pending_file.path),
+            };
+            match opened {
+                Ok(buffer_id) => {
                     // Navigate to line/column or select range if specified
                     if let (Some(line), Some(end_line)) = (pending_file.line, pending_file.end_line)
                     {
@@ -2463,6 +2885,26 @@ impl Editor {
                     } else if let Some(line) = pending_file.line {
                         self.goto_line_col(line, pending_file.column);
                     }
+                    if let Some(pattern) = &pending_file.search {
+                        self.search_regex_from_cursor(pattern);
+                    }
+                    // A restored workspace buffer may carry unsaved content for this
+                    // path; keep the recovered text and say so instead of reloading
+                    let recovered = open_before.contains(&buffer_id)
+                        && self
+                            .buffers
+                            .get(&buffer_id)
+                            .is_some_and(|s| s.buffer.is_modified());
+                    if recovered {
+                        let name = self
+                            .buffer_metadata
+                            .get(&buffer_id)
+                            .map(|m| m.display_name.clone())
+                            .unwrap_or_else(|| pending_file.path.display().to_string());
+                        self.set_status_message(
+                            t!("status.recovered_over_cli_file", name = name).to_string(),
+                        );
+                    }
                     // Show hover message popup if specified
                     let has_popup = pending_file.message.is_some();
                     if let Some(ref msg) = pending_file.message {
@@ -2471,7 +2913,14 @@ impl Editor {
                     // Track wait ID for --wait support
                     if let Some(wait_id) = pending_file.wait_id {
                         let buffer_id = self.active_buffer();
-                        self.wait_tracking.insert(buffer_id, (wait_id, has_popup));
+                        match &mut self.cli_wait {
+                            Some(cli_wait) if wait_id == CLI_WAIT_ID => {
+                                cli_wait.buffers.insert(buffer_id);
+                            }
+                            _ => {
+                                self.wait_tracking.insert(buffer_id, (wait_id, has_popup));
+                            }
+                        }
                     }
                     processed_any = true;
                 }
@@ -2484,7 +2933,7 @@ impl Editor {
                         self.start_large_file_encoding_confirmation(confirmation);
                     } else {
                         // For other errors, show status message (consistent with file browser)
-                        self.set_status_message(
+                        self.set_important_status_message(
                             t!("file.error_opening", error = e.to_string()).to_string(),
                         );
                     }
@@ -2510,6 +2959,29 @@ impl Editor {
         processed_any
     }
 
+    /// Wait for the files queued so far, as `fresh --wait` does: the editor
+    /// quits once all of them have been closed
+    pub fn wait_for_queued_files(&mut self) {
+        for pending in &mut self.pending_file_opens {
+            pending.wait_id = Some(CLI_WAIT_ID);
+        }
+        self.cli_wait = Some(super::CliWait::default());
+    }
+
+    /// Exit code for `fresh --wait`: 1 when a waited-for file was closed, or
+    /// is still open, with unsaved changes; 0 otherwise.
+    ///
+    /// Returns None without `--wait`.
+    pub fn cli_wait_exit_code(&self) -> Option<i32> {
+        let cli_wait = self.cli_wait.as_ref()?;
+        let unsaved = cli_wait.buffers.iter().any(|id| {
+            self.buffers
+                .get(id)
+                .is_some_and(|state| state.buffer.is_modified())
+        });
+        Some(if cli_wait.abandoned || unsaved { 1 } else { 0 })
+    }
+
     /// Take and return completed wait IDs (for --wait support).
     pub fn take_completed_waits(&mut self) -> Vec<u64> {
         std::mem::take(&mut self.completed_waits)
@@ -2542,11 +3014,25 @@ impl Editor {
                 scanned_bytes: 0,
                 updates: Vec::new(),
                 open_goto_line_on_complete: open_goto_line,
+                goto_on_complete: None,
             });
             self.set_status_message(t!("goto.scanning_progress", percent = 0).to_string());
         }
     }
 
+    /// Start counting the lines of a freshly opened large file
+    /// (`editor.large_file_line_scan`). Does nothing for buffers that already
+    /// have a line index, or while another scan is running.
+    fn start_background_line_scan(&mut self, buffer_id: BufferId) {
+        let needs_scan = self
+            .buffers
+            .get(&buffer_id)
+            .is_some_and(|s| s.buffer.line_count().is_none() && !s.buffer.is_binary());
+        if needs_scan && self.line_scan_state.is_none() && self.active_buffer() == buffer_id {
+            self.start_incremental_line_scan(false);
+        }
+    }
+
     /// Process chunks for the incremental line-feed scan.
     /// Returns `true` if the UI should re-render (progress updated or scan finished).
     pub fn process_line_scan(&mut self) -> bool {
@@ -2681,12 +3167,23 @@ impl Editor {
         if open_goto {
             self.open_goto_line_if_active(scan.buffer_id);
         }
+        if let Some((line, column)) = scan.goto_on_complete {
+            let has_line_index = self
+                .buffers
+                .get(&scan.buffer_id)
+                .is_some_and(|s| s.buffer.line_count().is_some());
+            if has_line_index && self.active_buffer() == scan.buffer_id {
+                self.goto_line_col(line, column);
+            }
+        }
     }
 
     fn finish_line_scan_with_error(&mut self, e: std::io::Error) {
         let scan = self.line_scan_state.take().unwrap();
         let open_goto = scan.open_goto_line_on_complete;
-        self.set_status_message(t!("goto.scan_failed", error = e.to_string()).to_string());
+        self.set_important_status_message(
+            t!("goto.scan_failed", error = e.to_string()).to_string(),
+        );
         if open_goto {
             self.open_goto_line_if_active(scan.buffer_id);
         }
@@ -2724,6 +3221,10 @@ impl Editor {
         if scan.scan.is_done() {
             self.finish_search_scan();
         } else {
+            self.publish_search_scan_matches();
+            let Some(scan) = self.search_scan_state.as_ref() else {
+                return true;
+            };
             let pct = scan.scan.progress_percent();
             let match_count = scan.scan.matches.len();
             self.set_status_message(format!(
@@ -2774,18 +3275,62 @@ impl Editor {
         Ok(())
     }
 
-    /// Finalize the incremental search scan: take the accumulated matches
-    /// and hand them to `finalize_search()` which sets search_state, moves
-    /// the cursor, and creates viewport overlays.
-    fn finish_search_scan(&mut self) {
-        let scan = self.search_scan_state.take().unwrap();
+    /// Hand the matches found so far to `search_state`, so the status bar
+    /// counts them and F3 can step through them while the scan goes on.
+    /// The first hand-over waits for a match at or after the cursor, the one
+    /// the finished search would select too.
+    pub(super) fn publish_search_scan_matches(&mut self) {
+        let Some(scan) = self.search_scan_state.as_mut() else {
+            return;
+        };
+        if scan.published > 0 {
+            let Some(search_state) = self.search_state.as_mut() else {
+                // The search was dismissed; stop counting
+                self.search_scan_state = None;
+                return;
+            };
+            for m in &scan.scan.matches[scan.published..] {
+                search_state.matches.push(m.byte_offset);
+                search_state.match_lengths.push(m.length);
+            }
+            scan.published = scan.scan.matches.len();
+            return;
+        }
+
         let buffer_id = scan.buffer_id;
+        if buffer_id != self.active_buffer() {
+            return;
+        }
+        let cursor_pos = self.active_cursors().primary().position;
+        let Some(scan) = self.search_scan_state.as_mut() else {
+            return;
+        };
+        if scan
+            .scan
+            .matches
+            .last()
+            .is_none_or(|m| m.byte_offset < cursor_pos)
+        {
+            return;
+        }
         let match_ranges: Vec<(usize, usize)> = scan
             .scan
             .matches
             .iter()
             .map(|m| (m.byte_offset, m.length))
             .collect();
+        scan.published = match_ranges.len();
+        let query = scan.query.clone();
+        self.finalize_search(&query, match_ranges, false, None, false);
+    }
+
+    /// Finalize the incremental search scan: take the accumulated matches
+    /// and hand them to `finalize_search()` which sets search_state, moves
+    /// the cursor, and creates viewport overlays. If part of them were
+    /// handed over already, the rest are added and the count is final.
+    fn finish_search_scan(&mut self) {
+        let scan = self.search_scan_state.take().unwrap();
+        let buffer_id = scan.buffer_id;
         let capped = scan.scan.capped;
         let query = scan.query;
 
@@ -2796,12 +3341,42 @@ impl Editor {
             state.buffer.refresh_saved_root_if_unmodified();
         }
 
+        if scan.published > 0 {
+            let Some(search_state) = self.search_state.as_mut() else {
+                return;
+            };
+            for m in &scan.scan.matches[scan.published..] {
+                search_state.matches.push(m.byte_offset);
+                search_state.match_lengths.push(m.length);
+            }
+            search_state.capped = capped;
+            search_state.complete = true;
+            let num_matches = search_state.matches.len();
+            let total = search_state.total_label(num_matches);
+            self.set_status_message(format!(
+                "Found {} match{} for '{}'",
+                total,
+                if num_matches == 1 { "" } else { "es" },
+                query
+            ));
+            if buffer_id == self.active_buffer() {
+                self.refresh_search_overlays();
+            }
+            return;
+        }
+
+        let match_ranges: Vec<(usize, usize)> = scan
+            .scan
+            .matches
+            .iter()
+            .map(|m| (m.byte_offset, m.length))
+            .collect();
         if match_ranges.is_empty() {
             self.search_state = None;
             self.set_status_message(format!("No matches found for '{}'", query));
             return;
         }
 
-        self.finalize_search(&query, match_ranges, capped, None);
+        self.finalize_search(&query, match_ranges, capped, None, true);
     }
 }
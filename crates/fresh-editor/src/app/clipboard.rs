@@ -53,7 +53,7 @@ impl Editor {
             let text = self.copy_block_selection_text();
             if !text.is_empty() {
                 self.clipboard.copy(text);
-                self.status_message = Some(t!("clipboard.copied").to_string());
+                self.show_status_message(t!("clipboard.copied").to_string());
             }
             return;
         }
@@ -84,7 +84,7 @@ impl Editor {
 
             if !text.is_empty() {
                 self.clipboard.copy(text);
-                self.status_message = Some(t!("clipboard.copied").to_string());
+                self.show_status_message(t!("clipboard.copied").to_string());
             }
         } else {
             // No selection: copy entire line(s) for each cursor
@@ -111,7 +111,7 @@ impl Editor {
 
             if !text.is_empty() {
                 self.clipboard.copy(text);
-                self.status_message = Some(t!("clipboard.copied_line").to_string());
+                self.show_status_message(t!("clipboard.copied_line").to_string());
             }
         }
     }
@@ -221,7 +221,7 @@ impl Editor {
             .any(|(_, cursor)| cursor.selection_range().is_some());
 
         if !has_selection {
-            self.status_message = Some(t!("clipboard.no_selection").to_string());
+            self.show_status_message(t!("clipboard.no_selection").to_string());
             return;
         }
 
@@ -236,7 +236,7 @@ impl Editor {
         let theme = match self.theme_registry.get_cloned(theme_name) {
             Some(t) => t,
             None => {
-                self.status_message = Some(format!("Theme '{}' not found", theme_name));
+                self.show_important_status_message(format!("Theme '{}' not found", theme_name));
                 return;
             }
         };
@@ -249,7 +249,7 @@ impl Editor {
             .collect();
 
         if ranges.is_empty() {
-            self.status_message = Some(t!("clipboard.no_selection").to_string());
+            self.show_status_message(t!("clipboard.no_selection").to_string());
             return;
         }
 
@@ -287,7 +287,7 @@ impl Editor {
         };
 
         if text.is_empty() {
-            self.status_message = Some(t!("clipboard.no_text").to_string());
+            self.show_status_message(t!("clipboard.no_text").to_string());
             return;
         }
 
@@ -322,11 +322,12 @@ impl Editor {
 
         // Copy the HTML to clipboard (with plain text fallback)
         if self.clipboard.copy_html(&html, &text) {
-            self.status_message =
-                Some(t!("clipboard.copied_with_theme", theme = theme_name).to_string());
+            self.show_status_message(
+                t!("clipboard.copied_with_theme", theme = theme_name).to_string(),
+            );
         } else {
             self.clipboard.copy(text);
-            self.status_message = Some(t!("clipboard.copied_plain").to_string());
+            self.show_status_message(t!("clipboard.copied_plain").to_string());
         }
     }
 
@@ -430,7 +431,7 @@ impl Editor {
             }
 
             if !deletions.is_empty() {
-                self.status_message = Some(t!("clipboard.cut").to_string());
+                self.show_status_message(t!("clipboard.cut").to_string());
             }
         } else {
             // No selection: delete entire line(s) for each cursor
@@ -489,7 +490,7 @@ impl Editor {
             }
 
             if !deletions.is_empty() {
-                self.status_message = Some(t!("clipboard.cut_line").to_string());
+                self.show_status_message(t!("clipboard.cut_line").to_string());
             }
         }
     }
@@ -534,7 +535,7 @@ impl Editor {
         if let Some(prompt) = self.prompt.as_mut() {
             prompt.insert_str(&normalized);
             self.update_prompt_suggestions();
-            self.status_message = Some(t!("clipboard.pasted").to_string());
+            self.show_status_message(t!("clipboard.pasted").to_string());
             return;
         }
 
@@ -610,7 +611,7 @@ impl Editor {
             self.apply_event_to_active_buffer(&event);
         }
 
-        self.status_message = Some(t!("clipboard.pasted").to_string());
+        self.show_status_message(t!("clipboard.pasted").to_string());
     }
 
     /// Set clipboard content for testing purposes
@@ -665,8 +666,9 @@ impl Editor {
                 self.active_event_log_mut().append(event.clone());
                 self.apply_event_to_active_buffer(&event);
 
-                self.status_message =
-                    Some(t!("clipboard.added_cursor_match", count = total_cursors).to_string());
+                self.show_status_message(
+                    t!("clipboard.added_cursor_match", count = total_cursors).to_string(),
+                );
             }
             AddCursorResult::WordSelected {
                 word_start,
@@ -690,7 +692,7 @@ impl Editor {
                 self.apply_event_to_active_buffer(&event);
             }
             AddCursorResult::Failed { message } => {
-                self.status_message = Some(message);
+                self.show_status_message(message);
             }
         }
     }
@@ -716,11 +718,12 @@ impl Editor {
                 self.active_event_log_mut().append(event.clone());
                 self.apply_event_to_active_buffer(&event);
 
-                self.status_message =
-                    Some(t!("clipboard.added_cursor_above", count = total_cursors).to_string());
+                self.show_status_message(
+                    t!("clipboard.added_cursor_above", count = total_cursors).to_string(),
+                );
             }
             AddCursorResult::Failed { message } => {
-                self.status_message = Some(message);
+                self.show_status_message(message);
             }
             AddCursorResult::WordSelected { .. } => unreachable!(),
         }
@@ -747,11 +750,12 @@ impl Editor {
                 self.active_event_log_mut().append(event.clone());
                 self.apply_event_to_active_buffer(&event);
 
-                self.status_message =
-                    Some(t!("clipboard.added_cursor_below", count = total_cursors).to_string());
+                self.show_status_message(
+                    t!("clipboard.added_cursor_below", count = total_cursors).to_string(),
+                );
             }
             AddCursorResult::Failed { message } => {
-                self.status_message = Some(message);
+                self.show_status_message(message);
             }
             AddCursorResult::WordSelected { .. } => unreachable!(),
         }
@@ -773,7 +777,11 @@ impl Editor {
             cursor_positions
                 .into_iter()
                 .filter_map(|start| {
-                    let end = find_word_start_right(&state.buffer, start);
+                    let end = find_word_start_right(
+                        &state.buffer,
+                        start,
+                        &state.buffer_settings.word_rules,
+                    );
                     if end > start {
                         Some(start..end)
                     } else {
@@ -801,7 +809,7 @@ impl Editor {
         if !text.is_empty() {
             let len = text.len();
             self.clipboard.copy(text);
-            self.status_message = Some(t!("clipboard.yanked", count = len).to_string());
+            self.show_status_message(t!("clipboard.yanked", count = len).to_string());
         }
     }
 
@@ -817,7 +825,8 @@ impl Editor {
             cursor_positions
                 .into_iter()
                 .filter_map(|end| {
-                    let start = find_word_start_left(&state.buffer, end);
+                    let start =
+                        find_word_start_left(&state.buffer, end, &state.buffer_settings.word_rules);
                     if start < end {
                         Some(start..end)
                     } else {
@@ -844,7 +853,7 @@ impl Editor {
         if !text.is_empty() {
             let len = text.len();
             self.clipboard.copy(text);
-            self.status_message = Some(t!("clipboard.yanked", count = len).to_string());
+            self.show_status_message(t!("clipboard.yanked", count = len).to_string());
         }
     }
 
@@ -891,7 +900,7 @@ impl Editor {
         if !text.is_empty() {
             let len = text.len();
             self.clipboard.copy(text);
-            self.status_message = Some(t!("clipboard.yanked", count = len).to_string());
+            self.show_status_message(t!("clipboard.yanked", count = len).to_string());
         }
     }
 
@@ -933,7 +942,7 @@ impl Editor {
         if !text.is_empty() {
             let len = text.len();
             self.clipboard.copy(text);
-            self.status_message = Some(t!("clipboard.yanked", count = len).to_string());
+            self.show_status_message(t!("clipboard.yanked", count = len).to_string());
         }
     }
 }
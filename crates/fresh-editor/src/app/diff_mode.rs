@@ -0,0 +1,868 @@
+//! Diff and merge sessions.
+//!
+//! This module provides:
+//! - `fresh --diff LEFT RIGHT`: both files side by side in scroll-locked
+//!   splits, with RIGHT as the output buffer
+//! - "Diff: Compare Open Buffers": the same view for two buffers that are
+//!   already open, comparing their current (possibly unsaved) content
+//! - `fresh --merge LOCAL REMOTE MERGED`: LOCAL and REMOTE side by side with
+//!   the merge result below them as the output buffer
+//! - Line highlighting of every hunk, in the `diff-mode` overlay namespace
+//! - Hunk navigation and accept-left/accept-right, which write the chosen
+//!   side of a change into the output buffer
+//! - The process exit code git expects from a difftool/mergetool
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use super::{Editor, LOCAL_CONNECTION};
+use crate::input::commands::Suggestion;
+use crate::model::composite_buffer::DiffHunk;
+use crate::model::event::{BufferId, Event, LeafId, SplitDirection};
+use crate::model::line_diff::diff_hunks;
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+use crate::view::prompt::PromptType;
+use crate::view::scroll_sync::{ScrollSyncGroupId, SyncAnchor};
+use crate::view::split::SplitViewState;
+use ratatui::style::{Color, Style};
+use rust_i18n::t;
+
+/// Namespace of the overlays that highlight changed lines
+pub fn diff_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("diff-mode".to_string())
+}
+
+/// Below search matches and diagnostics, which stay visible on changed lines
+const DIFF_HIGHLIGHT_PRIORITY: i32 = -10;
+
+/// A file to compare: on this machine, or on a remote connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffFile {
+    pub path: PathBuf,
+    /// SSH connection ("user@host[:port]") the file lives on; None for a
+    /// local file, even when the editor itself is remote
+    pub connection: Option<String>,
+}
+
+impl DiffFile {
+    pub fn local(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            connection: None,
+        }
+    }
+
+    /// Whether `path` is the null device, which git passes for the missing
+    /// side of an added or deleted file
+    pub fn is_null_device(path: &Path) -> bool {
+        path == Path::new("/dev/null")
+            || (cfg!(windows) && path.to_str().is_some_and(|p| p.eq_ignore_ascii_case("nul")))
+    }
+}
+
+/// State of a diff or merge session
+#[derive(Debug, Clone)]
+pub(crate) struct DiffSession {
+    left: BufferId,
+    right: BufferId,
+    /// Buffer that receives accepted changes: RIGHT when diffing, MERGED when merging
+    output: BufferId,
+    sync_group: ScrollSyncGroupId,
+    /// Started from the command palette on open buffers rather than with
+    /// `--diff`/`--merge`, so it doesn't decide the exit code
+    open_buffers: bool,
+}
+
+impl DiffSession {
+    fn is_merge(&self) -> bool {
+        self.output != self.right
+    }
+}
+
+/// Which side of the comparison a change is taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffSide {
+    Left,
+    Right,
+}
+
+/// A `<<<<<<<` ... `>>>>>>>` conflict in the merge output, as line ranges
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConflictBlock {
+    /// The whole block including the marker lines
+    lines: Range<usize>,
+    /// Our side (between `<<<<<<<` and `|||||||` or `=======`)
+    ours: Range<usize>,
+    /// Their side (between `=======` and `>>>>>>>`)
+    theirs: Range<usize>,
+}
+
+impl ConflictBlock {
+    fn side(&self, side: DiffSide) -> Range<usize> {
+        match side {
+            DiffSide::Left => self.ours.clone(),
+            DiffSide::Right => self.theirs.clone(),
+        }
+    }
+}
+
+/// Find the conflict blocks in `lines`, ignoring unterminated ones.
+fn conflict_blocks(lines: &[&str]) -> Vec<ConflictBlock> {
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut ours_end = None;
+    let mut separator = None;
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with("<<<<<<<") {
+            start = Some(i);
+            ours_end = None;
+            separator = None;
+        } else if line.starts_with("|||||||") && start.is_some() && separator.is_none() {
+            ours_end.get_or_insert(i);
+        } else if line.starts_with("=======") && start.is_some() && separator.is_none() {
+            ours_end.get_or_insert(i);
+            separator = Some(i);
+        } else if line.starts_with(">>>>>>>") {
+            if let (Some(start), Some(ours_end), Some(separator)) = (start, ours_end, separator) {
+                blocks.push(ConflictBlock {
+                    lines: start..i + 1,
+                    ours: start + 1..ours_end,
+                    theirs: separator + 1..i,
+                });
+            }
+            start = None;
+        }
+    }
+    blocks
+}
+
+/// Compute the edit that replaces `lines` of `text` with `new_lines`.
+///
+/// Lines are separated by `\n`, so the last line has no terminator. Returns
+/// the byte range to replace and the replacement text.
+fn replace_lines(text: &str, lines: Range<usize>, new_lines: &[&str]) -> (Range<usize>, String) {
+    let mut starts = vec![0];
+    starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+    let line_count = starts.len();
+
+    if lines.end < line_count {
+        let replacement = new_lines.iter().map(|line| format!("{}\n", line)).collect();
+        (starts[lines.start]..starts[lines.end], replacement)
+    } else if lines.start == line_count {
+        // Appending after the last line
+        (
+            text.len()..text.len(),
+            format!("\n{}", new_lines.join("\n")),
+        )
+    } else if new_lines.is_empty() {
+        // Removing the last lines also removes the newline before them
+        let start = starts[lines.start].saturating_sub(1);
+        (start..text.len(), String::new())
+    } else {
+        (starts[lines.start]..text.len(), new_lines.join("\n"))
+    }
+}
+
+/// Whether `line` is in the part of `hunk` on `side`.
+///
+/// A side with no lines (a pure insertion or deletion) matches the line it
+/// sits at.
+fn hunk_contains(hunk: &DiffHunk, side: DiffSide, line: usize) -> bool {
+    let (start, count) = hunk_side(hunk, side);
+    (start..start + count).contains(&line) || (count == 0 && line == start)
+}
+
+/// Start line and line count of `hunk` on `side`
+fn hunk_side(hunk: &DiffHunk, side: DiffSide) -> (usize, usize) {
+    match side {
+        DiffSide::Left => (hunk.old_start, hunk.old_count),
+        DiffSide::Right => (hunk.new_start, hunk.new_count),
+    }
+}
+
+impl Editor {
+    /// Start a diff session comparing `left` and `right`, or a merge session
+    /// writing into `merged` when it is given.
+    pub fn start_diff_session(
+        &mut self,
+        left: &Path,
+        right: &Path,
+        merged: Option<&Path>,
+    ) -> anyhow::Result<()> {
+        self.start_diff_session_for(
+            &DiffFile::local(left),
+            &DiffFile::local(right),
+            merged.map(DiffFile::local).as_ref(),
+        )
+    }
+
+    /// Like [`Self::start_diff_session`], for files that may be remote
+    pub fn start_diff_session_for(
+        &mut self,
+        left: &DiffFile,
+        right: &DiffFile,
+        merged: Option<&DiffFile>,
+    ) -> anyhow::Result<()> {
+        let left_id = match left.connection {
+            None if DiffFile::is_null_device(&left.path) => self.open_null_device(&left.path),
+            _ => self.open_file_with_connection(&left.path, self.diff_file_connection(left))?,
+        };
+        let right_id = match right.connection {
+            None if DiffFile::is_null_device(&right.path) => self.open_null_device(&right.path),
+            _ => self.open_file_no_focus_on(&right.path, self.diff_file_connection(right))?,
+        };
+        let output_id = match merged {
+            Some(merged) => {
+                self.open_file_no_focus_on(&merged.path, self.diff_file_connection(merged))?
+            }
+            None => right_id,
+        };
+        for buffer_id in [left_id, right_id] {
+            if buffer_id != output_id {
+                if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                    state.editing_disabled = true;
+                }
+            }
+        }
+
+        if output_id != right_id {
+            // The merge result goes below the two versions being compared
+            let top = self.split_manager.active_split();
+            self.split_with_buffer(SplitDirection::Horizontal, output_id, 0.6);
+            self.focus_split(top, left_id);
+        }
+        let left_split = self.split_manager.active_split();
+        let Some(right_split) = self.split_with_buffer(SplitDirection::Vertical, right_id, 0.5)
+        else {
+            anyhow::bail!("Failed to create the diff split");
+        };
+        self.focus_split(left_split, left_id);
+
+        let sync_group = self
+            .scroll_sync_manager
+            .create_group(left_split.into(), right_split.into());
+        self.diff_session = Some(DiffSession {
+            left: left_id,
+            right: right_id,
+            output: output_id,
+            sync_group,
+            open_buffers: false,
+        });
+
+        let hunks = self.refresh_diff_hunks();
+        let message = if let Some(merged) = merged {
+            t!(
+                "diff.merge_started",
+                count = hunks.len(),
+                path = merged.path.display().to_string()
+            )
+        } else if hunks.is_empty() {
+            t!("diff.identical")
+        } else {
+            t!("diff.started", count = hunks.len())
+        };
+        self.set_status_message(message.to_string());
+        Ok(())
+    }
+
+    /// Connection to open one side of a diff on. Local files are read from
+    /// this machine even when the editor is editing a remote host.
+    /// An empty, read-only stand-in for the null device side of a diff
+    fn open_null_device(&mut self, path: &Path) -> BufferId {
+        self.create_virtual_buffer(path.display().to_string(), "special".to_string(), true)
+    }
+
+    fn diff_file_connection<'a>(&self, file: &'a DiffFile) -> Option<&'a str> {
+        match &file.connection {
+            Some(connection) => Some(connection),
+            None if self.filesystem.remote_connection_info().is_some() => Some(LOCAL_CONNECTION),
+            None => None,
+        }
+    }
+
+    /// Exit code for a diff session: 0 when the output buffer has been saved
+    /// (and, when merging, has no conflict markers left), 1 otherwise.
+    ///
+    /// Returns None outside of a diff session.
+    pub fn diff_session_exit_code(&self) -> Option<i32> {
+        let session = self.diff_session.as_ref().filter(|s| !s.open_buffers)?;
+        let state = self.buffers.get(&session.output)?;
+        let unresolved = session.is_merge() && {
+            let text = state.buffer.to_string().unwrap_or_default();
+            !conflict_blocks(&text.split('\n').collect::<Vec<_>>()).is_empty()
+        };
+        Some(if state.buffer.is_modified() || unresolved {
+            1
+        } else {
+            0
+        })
+    }
+
+    /// Compare two open buffers. When exactly two splits are visible showing
+    /// different buffers, those are compared; otherwise prompt for both.
+    pub fn start_diff_buffers(&mut self) {
+        if self.diff_session.as_ref().is_some_and(|s| !s.open_buffers) {
+            self.set_status_message(t!("diff.session_active").to_string());
+            return;
+        }
+        let leaves = self.split_manager.root().leaf_split_ids();
+        if let [left_split, right_split] = leaves[..] {
+            let left = self.split_manager.buffer_for_split(left_split);
+            let right = self.split_manager.buffer_for_split(right_split);
+            if let (Some(left), Some(right)) = (left, right) {
+                if left != right {
+                    self.start_buffer_diff_in_splits(left, right, left_split, right_split);
+                    return;
+                }
+            }
+        }
+
+        let suggestions = self.diff_buffer_suggestions(None);
+        if suggestions.len() < 2 {
+            self.set_status_message(t!("diff.need_two_buffers").to_string());
+            return;
+        }
+        self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
+            t!("diff.choose_first_buffer").to_string(),
+            PromptType::DiffBuffers { first: None },
+            suggestions,
+        ));
+    }
+
+    /// Handle a buffer chosen in the compare prompt: ask for the second
+    /// buffer, or start comparing once both are known
+    pub(super) fn handle_diff_buffer_chosen(&mut self, first: Option<BufferId>, chosen: BufferId) {
+        if !self.buffers.contains_key(&chosen) {
+            return;
+        }
+        let Some(first) = first else {
+            let name = self.diff_buffer_name(chosen);
+            self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
+                t!("diff.choose_second_buffer", name = name).to_string(),
+                PromptType::DiffBuffers {
+                    first: Some(chosen),
+                },
+                self.diff_buffer_suggestions(Some(chosen)),
+            ));
+            return;
+        };
+        if first == chosen {
+            return;
+        }
+
+        // Show the first buffer in the active split and the second beside it
+        self.set_active_buffer(first);
+        let left_split = self.split_manager.active_split();
+        let Some(right_split) = self.split_with_buffer(SplitDirection::Vertical, chosen, 0.5)
+        else {
+            return;
+        };
+        self.focus_split(left_split, first);
+        self.start_buffer_diff_in_splits(first, chosen, left_split, right_split);
+    }
+
+    /// Compare `left` and `right`, already shown in the given splits
+    fn start_buffer_diff_in_splits(
+        &mut self,
+        mut left: BufferId,
+        mut right: BufferId,
+        mut left_split: LeafId,
+        mut right_split: LeafId,
+    ) {
+        for buffer_id in [left, right] {
+            let loaded = self
+                .buffers
+                .get(&buffer_id)
+                .is_some_and(|state| state.buffer.to_string().is_some());
+            if !loaded {
+                let name = self.diff_buffer_name(buffer_id);
+                self.set_status_message(t!("diff.buffer_not_loaded", name = name).to_string());
+                return;
+            }
+        }
+        // Changes are taken into the right buffer, so keep a read-only one
+        // (such as a virtual buffer) on the left
+        if self.is_read_only_buffer(right) && !self.is_read_only_buffer(left) {
+            std::mem::swap(&mut left, &mut right);
+            std::mem::swap(&mut left_split, &mut right_split);
+        }
+
+        self.end_open_buffers_diff();
+        self.scroll_sync_manager
+            .remove_groups_for_split(left_split.into());
+        self.scroll_sync_manager
+            .remove_groups_for_split(right_split.into());
+        let sync_group = self
+            .scroll_sync_manager
+            .create_group(left_split.into(), right_split.into());
+        self.diff_session = Some(DiffSession {
+            left,
+            right,
+            output: right,
+            sync_group,
+            open_buffers: true,
+        });
+
+        let hunks = self.refresh_diff_hunks();
+        let left_name = self.diff_buffer_name(left);
+        let right_name = self.diff_buffer_name(right);
+        let message = if hunks.is_empty() {
+            t!(
+                "diff.buffers_identical",
+                left = left_name,
+                right = right_name
+            )
+        } else {
+            t!(
+                "diff.buffers_started",
+                count = hunks.len(),
+                left = left_name,
+                right = right_name
+            )
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// End a "Compare Open Buffers" session, if one is running. Sessions
+    /// started with `--diff`/`--merge` last until the editor exits.
+    pub(super) fn end_open_buffers_diff(&mut self) {
+        if self.diff_session.as_ref().is_some_and(|s| s.open_buffers) {
+            if let Some(session) = self.diff_session.take() {
+                self.scroll_sync_manager.remove_group(session.sync_group);
+                self.clear_diff_highlights(&[session.left, session.right]);
+            }
+        }
+    }
+
+    /// End the "Compare Open Buffers" session if it shows `buffer_id`
+    pub(super) fn end_open_buffers_diff_for(&mut self, buffer_id: BufferId) {
+        let involved = self
+            .diff_session
+            .as_ref()
+            .is_some_and(|s| s.open_buffers && (s.left == buffer_id || s.right == buffer_id));
+        if involved {
+            self.end_open_buffers_diff();
+        }
+    }
+
+    /// Open buffers to offer in the compare prompt, leaving out `exclude`
+    fn diff_buffer_suggestions(&self, exclude: Option<BufferId>) -> Vec<Suggestion> {
+        let mut buffer_ids: Vec<BufferId> = self
+            .buffers
+            .keys()
+            .copied()
+            .filter(|id| Some(*id) != exclude)
+            .filter(|id| {
+                !self
+                    .buffer_metadata
+                    .get(id)
+                    .is_some_and(|meta| meta.hidden_from_tabs)
+            })
+            .collect();
+        buffer_ids.sort_by_key(|id| id.0);
+        buffer_ids
+            .into_iter()
+            .map(|buffer_id| {
+                let modified = self
+                    .buffers
+                    .get(&buffer_id)
+                    .is_some_and(|state| state.buffer.is_modified());
+                Suggestion {
+                    text: self.diff_buffer_name(buffer_id),
+                    description: modified.then(|| "(modified)".to_string()),
+                    value: Some(buffer_id.0.to_string()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect()
+    }
+
+    fn diff_buffer_name(&self, buffer_id: BufferId) -> String {
+        self.buffer_metadata
+            .get(&buffer_id)
+            .map(|meta| meta.display_name.clone())
+            .unwrap_or_else(|| format!("Buffer {:?}", buffer_id))
+    }
+
+    fn is_read_only_buffer(&self, buffer_id: BufferId) -> bool {
+        self.buffers
+            .get(&buffer_id)
+            .is_some_and(|state| state.editing_disabled)
+    }
+
+    /// Move the cursor to the next (or previous) change in the active split.
+    ///
+    /// In the merge output this jumps between conflict blocks.
+    pub fn diff_goto_hunk(&mut self, forward: bool) {
+        let Some(session) = self.diff_session.clone() else {
+            self.set_status_message(t!("diff.no_session").to_string());
+            return;
+        };
+        let buffer_id = self.active_buffer();
+        let line = self.active_cursor_line();
+
+        let starts: Vec<usize> = if buffer_id == session.left || buffer_id == session.right {
+            let side = if buffer_id == session.left {
+                DiffSide::Left
+            } else {
+                DiffSide::Right
+            };
+            self.refresh_diff_hunks()
+                .iter()
+                .map(|hunk| hunk_side(hunk, side).0)
+                .collect()
+        } else if buffer_id == session.output {
+            let text = self.buffer_text(buffer_id);
+            conflict_blocks(&text.split('\n').collect::<Vec<_>>())
+                .iter()
+                .map(|block| block.lines.start)
+                .collect()
+        } else {
+            self.set_status_message(t!("diff.no_session").to_string());
+            return;
+        };
+
+        let target = if forward {
+            starts.into_iter().find(|start| *start > line)
+        } else {
+            starts.into_iter().rev().find(|start| *start < line)
+        };
+        match target {
+            Some(start) => self.goto_line_col(start + 1, None),
+            None => self.set_status_message(t!("diff.no_more_hunks").to_string()),
+        }
+    }
+
+    /// Write the left or right version of the change at the cursor into the
+    /// output buffer.
+    pub fn diff_accept(&mut self, from_left: bool) {
+        let Some(session) = self.diff_session.clone() else {
+            self.set_status_message(t!("diff.no_session").to_string());
+            return;
+        };
+        if self.is_read_only_buffer(session.output) {
+            let name = self.diff_buffer_name(session.output);
+            self.set_status_message(t!("diff.output_read_only", name = name).to_string());
+            return;
+        }
+        let side = if from_left {
+            DiffSide::Left
+        } else {
+            DiffSide::Right
+        };
+        let buffer_id = self.active_buffer();
+        let line = self.active_cursor_line();
+        let output_text = self.buffer_text(session.output);
+        let output_lines: Vec<&str> = output_text.split('\n').collect();
+
+        // The lines of the output to replace, and the text to put there
+        let edit: Option<(Range<usize>, Vec<String>)> =
+            if buffer_id == session.output && session.is_merge() {
+                conflict_blocks(&output_lines)
+                    .into_iter()
+                    .find(|block| block.lines.contains(&line))
+                    .map(|block| {
+                        let chosen = output_lines[block.side(side)]
+                            .iter()
+                            .map(|l| l.to_string())
+                            .collect();
+                        (block.lines, chosen)
+                    })
+            } else if buffer_id == session.left || buffer_id == session.right {
+                let cursor_side = if buffer_id == session.left {
+                    DiffSide::Left
+                } else {
+                    DiffSide::Right
+                };
+                let Some(hunk) = self
+                    .refresh_diff_hunks()
+                    .into_iter()
+                    .find(|hunk| hunk_contains(hunk, cursor_side, line))
+                else {
+                    self.set_status_message(t!("diff.no_hunk").to_string());
+                    return;
+                };
+                let side_lines = |buffer: BufferId, side: DiffSide| -> Vec<String> {
+                    let (start, count) = hunk_side(&hunk, side);
+                    self.buffer_text(buffer)
+                        .split('\n')
+                        .skip(start)
+                        .take(count)
+                        .map(|l| l.to_string())
+                        .collect()
+                };
+                let left_lines = side_lines(session.left, DiffSide::Left);
+                let right_lines = side_lines(session.right, DiffSide::Right);
+                let chosen = match side {
+                    DiffSide::Left => left_lines.clone(),
+                    DiffSide::Right => right_lines.clone(),
+                };
+
+                if session.is_merge() {
+                    // The hunk is resolved in the conflict block holding either version
+                    let matches = |range: Range<usize>, lines: &[String]| {
+                        output_lines[range].iter().eq(lines.iter())
+                    };
+                    conflict_blocks(&output_lines)
+                        .into_iter()
+                        .find(|block| {
+                            matches(block.ours.clone(), &left_lines)
+                                || matches(block.theirs.clone(), &right_lines)
+                        })
+                        .map(|block| (block.lines, chosen))
+                } else if side == DiffSide::Right {
+                    // The right file is the output, so it already has this version
+                    self.set_status_message(t!("diff.kept_right").to_string());
+                    return;
+                } else {
+                    let (start, count) = hunk_side(&hunk, DiffSide::Right);
+                    Some((start..start + count, chosen))
+                }
+            } else {
+                self.set_status_message(t!("diff.no_session").to_string());
+                return;
+            };
+
+        let Some((lines, chosen)) = edit else {
+            let message = if session.is_merge() {
+                t!("diff.no_conflict")
+            } else {
+                t!("diff.no_hunk")
+            };
+            self.set_status_message(message.to_string());
+            return;
+        };
+
+        let chosen: Vec<&str> = chosen.iter().map(String::as_str).collect();
+        let (range, text) = replace_lines(&output_text, lines, &chosen);
+        let cursor_id = self
+            .split_manager
+            .splits_for_buffer(session.output)
+            .into_iter()
+            .next()
+            .and_then(|split| self.split_view_states.get(&split))
+            .and_then(|vs| vs.keyed_states.get(&session.output))
+            .map(|bvs| bvs.cursors.primary_id())
+            .unwrap_or_else(|| self.active_cursors().primary_id());
+
+        let mut events = Vec::new();
+        if !range.is_empty() {
+            events.push(Event::Delete {
+                range: range.clone(),
+                deleted_text: output_text[range.clone()].to_string(),
+                cursor_id,
+            });
+        }
+        if !text.is_empty() {
+            events.push(Event::Insert {
+                position: range.start,
+                text,
+                cursor_id,
+            });
+        }
+        let description = if from_left {
+            t!("diff.accepted_left")
+        } else {
+            t!("diff.accepted_right")
+        }
+        .to_string();
+        if let Err(e) =
+            self.apply_events_to_buffer_as_bulk_edit(session.output, events, description.clone())
+        {
+            self.set_important_status_message(
+                t!("diff.accept_failed", error = e.to_string()).to_string(),
+            );
+            return;
+        }
+        self.refresh_diff_hunks();
+        self.set_status_message(description);
+    }
+
+    /// Recompute the hunks between the two compared buffers and update the
+    /// scroll sync anchors and line highlights to match.
+    fn refresh_diff_hunks(&mut self) -> Vec<DiffHunk> {
+        let Some(session) = self.diff_session.clone() else {
+            return Vec::new();
+        };
+        let hunks = diff_hunks(
+            self.buffer_text(session.left).as_bytes(),
+            self.buffer_text(session.right).as_bytes(),
+        );
+
+        let mut anchors = vec![SyncAnchor {
+            left_line: 0,
+            right_line: 0,
+        }];
+        for hunk in &hunks {
+            anchors.push(SyncAnchor {
+                left_line: hunk.old_start,
+                right_line: hunk.new_start,
+            });
+            anchors.push(SyncAnchor {
+                left_line: hunk.old_start + hunk.old_count,
+                right_line: hunk.new_start + hunk.new_count,
+            });
+        }
+        self.scroll_sync_manager
+            .set_anchors(session.sync_group, anchors);
+
+        self.clear_diff_highlights(&[session.left, session.right]);
+        for hunk in &hunks {
+            // Only one side has lines when a hunk is a pure removal or addition
+            let bg_theme = match (hunk.old_count, hunk.new_count) {
+                (_, 0) => "editor.diff_remove_bg",
+                (0, _) => "editor.diff_add_bg",
+                _ => "editor.diff_modify_bg",
+            };
+            let (left_start, left_count) = hunk_side(hunk, DiffSide::Left);
+            let (right_start, right_count) = hunk_side(hunk, DiffSide::Right);
+            self.highlight_diff_lines(session.left, left_start..left_start + left_count, bg_theme);
+            self.highlight_diff_lines(
+                session.right,
+                right_start..right_start + right_count,
+                bg_theme,
+            );
+        }
+        hunks
+    }
+
+    /// Give `lines` of a buffer the background of theme key `bg_theme`
+    fn highlight_diff_lines(&mut self, buffer_id: BufferId, lines: Range<usize>, bg_theme: &str) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let total = state.buffer.len();
+        let namespace = diff_namespace();
+        for line in lines {
+            let Some(start) = state.buffer.line_start_offset(line) else {
+                break;
+            };
+            // Stop before the newline so the fill doesn't reach the next line
+            let end = state
+                .buffer
+                .line_start_offset(line + 1)
+                .map_or(total, |next| next.saturating_sub(1))
+                .max(start);
+            let face = OverlayFace::ThemedStyle {
+                fallback_style: Style::default().bg(Color::DarkGray),
+                fg_theme: None,
+                bg_theme: Some(bg_theme.to_string()),
+            };
+            let overlay = Overlay::with_namespace(
+                &mut state.marker_list,
+                start..end,
+                face,
+                namespace.clone(),
+            )
+            .with_priority_value(DIFF_HIGHLIGHT_PRIORITY)
+            .with_extend_to_line_end(true);
+            state.overlays.add(overlay);
+        }
+    }
+
+    /// Remove the changed-line highlights from these buffers
+    fn clear_diff_highlights(&mut self, buffer_ids: &[BufferId]) {
+        let namespace = diff_namespace();
+        for buffer_id in buffer_ids {
+            if let Some(state) = self.buffers.get_mut(buffer_id) {
+                state
+                    .overlays
+                    .clear_namespace(&namespace, &mut state.marker_list);
+            }
+        }
+    }
+
+    /// Split the active pane, showing `buffer_id` in the new (now active) split
+    fn split_with_buffer(
+        &mut self,
+        direction: SplitDirection,
+        buffer_id: BufferId,
+        ratio: f32,
+    ) -> Option<LeafId> {
+        let split_id = self
+            .split_manager
+            .split_active(direction, buffer_id, ratio)
+            .ok()?;
+        let mut view_state =
+            SplitViewState::with_buffer(self.terminal_width, self.terminal_height, buffer_id);
+        view_state.apply_config_defaults(
+            self.config.editor.line_numbers,
+            self.config.editor.line_wrap,
+            self.config.editor.wrap_indent,
+            self.config.editor.rulers.clone(),
+        );
+        self.split_view_states.insert(split_id, view_state);
+        Some(split_id)
+    }
+
+    /// Line of the primary cursor in the active buffer
+    fn active_cursor_line(&self) -> usize {
+        let position = self.active_cursors().primary().position;
+        self.buffers
+            .get(&self.active_buffer())
+            .map(|state| state.buffer.get_line_number(position))
+            .unwrap_or(0)
+    }
+
+    /// Full text of a buffer (empty if it isn't loaded)
+    fn buffer_text(&self, buffer_id: BufferId) -> String {
+        self.buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.to_string())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(text: &str, lines: Range<usize>, new_lines: &[&str]) -> String {
+        let (range, replacement) = replace_lines(text, lines, new_lines);
+        let mut result = text.to_string();
+        result.replace_range(range, &replacement);
+        result
+    }
+
+    #[test]
+    fn test_replace_lines() {
+        assert_eq!(apply("a\nb\nc\n", 1..2, &["B", "B2"]), "a\nB\nB2\nc\n");
+        assert_eq!(apply("a\nb\nc\n", 1..2, &[]), "a\nc\n");
+        assert_eq!(apply("a\nc\n", 1..1, &["b"]), "a\nb\nc\n");
+        // Without a trailing newline the last line has no terminator
+        assert_eq!(apply("a\nb", 1..2, &["x"]), "a\nx");
+        assert_eq!(apply("a\nb", 1..2, &[]), "a");
+        assert_eq!(apply("a", 1..1, &["b"]), "a\nb");
+    }
+
+    #[test]
+    fn test_conflict_blocks() {
+        let text = "keep\n<<<<<<< HEAD\nours\n=======\ntheirs\nmore\n>>>>>>> branch\nend";
+        let lines: Vec<&str> = text.split('\n').collect();
+        assert_eq!(
+            conflict_blocks(&lines),
+            vec![ConflictBlock {
+                lines: 1..7,
+                ours: 2..3,
+                theirs: 4..6,
+            }]
+        );
+
+        // diff3 style: the base section is not part of either side
+        let text = "<<<<<<< ours\na\n||||||| base\nb\n=======\nc\n>>>>>>> theirs";
+        let lines: Vec<&str> = text.split('\n').collect();
+        assert_eq!(
+            conflict_blocks(&lines),
+            vec![ConflictBlock {
+                lines: 0..7,
+                ours: 1..2,
+                theirs: 5..6,
+            }]
+        );
+
+        // Unterminated blocks are ignored
+        assert!(conflict_blocks(&["<<<<<<< x", "a", "======="]).is_empty());
+    }
+}
@@ -145,7 +145,7 @@ impl Editor {
                 Ok(home) => home,
                 Err(e) => {
                     tracing::error!("Failed to get remote home directory: {}", e);
-                    self.set_status_message(format!("Failed to get remote home: {}", e));
+                    self.set_important_status_message(format!("Failed to get remote home: {}", e));
                     return;
                 }
             }
@@ -352,6 +352,9 @@ impl Editor {
                 self.file_explorer_toggle_expand();
             } else {
                 tracing::info!("[SYNTAX DEBUG] file_explorer opening file: {:?}", path);
+                if self.try_open_externally(&path) {
+                    return Ok(());
+                }
                 match self.open_file(&path) {
                     Ok(_) => {
                         self.set_status_message(
@@ -367,7 +370,7 @@ impl Editor {
                         {
                             self.start_large_file_encoding_confirmation(confirmation);
                         } else {
-                            self.set_status_message(
+                            self.set_important_status_message(
                                 t!("file.error_opening", error = e.to_string()).to_string(),
                             );
                         }
@@ -413,7 +416,7 @@ impl Editor {
                     }
                 }
                 Err(e) => {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("explorer.error_refreshing", error = e.to_string()).to_string(),
                     );
                 }
@@ -464,7 +467,7 @@ impl Editor {
                                 self.prompt = Some(prompt);
                             }
                             Err(e) => {
-                                self.set_status_message(
+                                self.set_important_status_message(
                                     t!("explorer.error_creating_file", error = e.to_string())
                                         .to_string(),
                                 );
@@ -515,7 +518,7 @@ impl Editor {
                                 self.prompt = Some(prompt);
                             }
                             Err(e) => {
-                                self.set_status_message(
+                                self.set_important_status_message(
                                     t!("explorer.error_creating_dir", error = e.to_string())
                                         .to_string(),
                                 );
@@ -613,7 +616,7 @@ impl Editor {
                 self.key_context = KeyContext::FileExplorer;
             }
             Err(e) => {
-                self.set_status_message(
+                self.set_important_status_message(
                     t!("explorer.error_trash", error = e.to_string()).to_string(),
                 );
             }
@@ -699,6 +702,8 @@ impl Editor {
 
             match result {
                 Ok(_) => {
+                    self.file_overrides.rename(&original_path, &new_path);
+
                     // Refresh the parent directory and select the renamed item
                     if let Some(explorer) = &mut self.file_explorer {
                         if let Some(selected_id) = explorer.get_selected() {
@@ -720,28 +725,7 @@ impl Editor {
                         .map(|(id, _)| *id);
 
                     if let Some(buffer_id) = buffer_to_update {
-                        // Update the buffer's file path after rename
-                        if let Some(state) = self.buffers.get_mut(&buffer_id) {
-                            state.buffer.rename_file_path(new_path.clone());
-                        }
-
-                        // Update the buffer metadata
-                        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
-                            // Compute new URI
-                            let file_uri = super::types::file_path_to_lsp_uri(&new_path);
-
-                            // Update kind with new path and URI
-                            metadata.kind = super::BufferKind::File {
-                                path: new_path.clone(),
-                                uri: file_uri,
-                            };
-
-                            // Update display name
-                            metadata.display_name = super::BufferMetadata::display_name_for_path(
-                                &new_path,
-                                &self.working_dir,
-                            );
-                        }
+                        self.rebind_buffer_path(buffer_id, new_path.clone());
 
                         // Only switch focus to the buffer if this is a new file being created
                         // For renaming existing files from the explorer, keep focus in explorer.
@@ -755,7 +739,7 @@ impl Editor {
                     );
                 }
                 Err(e) => {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("explorer.error_renaming", error = e.to_string()).to_string(),
                     );
                 }
@@ -763,6 +747,23 @@ impl Editor {
         }
     }
 
+    /// Point a buffer at its file's new path after the file was renamed on disk
+    pub(super) fn rebind_buffer_path(&mut self, buffer_id: BufferId, new_path: PathBuf) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.rename_file_path(new_path.clone());
+        }
+
+        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+            let file_uri = super::types::file_path_to_lsp_uri(&new_path);
+            metadata.display_name =
+                super::BufferMetadata::display_name_for_path(&new_path, &self.working_dir);
+            metadata.kind = super::BufferKind::File {
+                path: new_path,
+                uri: file_uri,
+            };
+        }
+    }
+
     pub fn file_explorer_toggle_hidden(&mut self) {
         let show_hidden = if let Some(explorer) = &mut self.file_explorer {
             explorer.toggle_show_hidden();
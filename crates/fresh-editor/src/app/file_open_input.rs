@@ -326,6 +326,9 @@ impl Editor {
 
         // Open the file with auto-detected encoding
         tracing::info!("[SYNTAX DEBUG] file_open_dialog opening file: {:?}", path);
+        if self.try_open_externally(&path) {
+            return;
+        }
         if let Err(e) = self.open_file(&path) {
             // Check if this is a large file encoding confirmation error
             if let Some(confirmation) =
@@ -352,7 +355,7 @@ impl Editor {
                     },
                 );
             } else {
-                self.set_status_message(
+                self.set_important_status_message(
                     t!("file.error_opening", error = e.to_string()).to_string(),
                 );
             }
@@ -450,7 +453,9 @@ impl Editor {
 
         // Open the file - this will create an unsaved buffer with the path set
         if let Err(e) = self.open_file(&path) {
-            self.set_status_message(t!("file.error_opening", error = e.to_string()).to_string());
+            self.set_important_status_message(
+                t!("file.error_opening", error = e.to_string()).to_string(),
+            );
         } else {
             self.set_status_message(
                 t!("file.created_new", path = path.display().to_string()).to_string(),
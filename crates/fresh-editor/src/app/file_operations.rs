@@ -10,26 +10,54 @@
 
 use crate::model::buffer::SudoSaveRequired;
 use crate::view::prompt::PromptType;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use lsp_types::TextDocumentContentChangeEvent;
 use rust_i18n::t;
 
-use crate::model::event::{BufferId, EventLog};
+use crate::model::event::BufferId;
+use crate::primitives::content_anchor::Relocator;
+use crate::primitives::path_utils::expand_tilde;
 use crate::services::lsp::manager::LspSpawnResult;
+use crate::services::workspace_trust::WorkspaceTrust;
 use crate::state::EditorState;
 
-use super::{BufferMetadata, Editor};
+use super::{normalize_path, BufferMetadata, Editor};
+
+/// Polling state of an open file for auto-revert
+#[derive(Debug, Clone, Copy)]
+pub(super) struct FilePollState {
+    /// Size when the file was last loaded, saved or checked. Coarse mtimes
+    /// (e.g. on NFS) can miss a change that the size still reveals.
+    size: u64,
+    /// Number of consecutive polls that found the file unchanged
+    unchanged_polls: u32,
+    /// Earliest time the file is checked again
+    next_poll: std::time::Instant,
+}
 
 impl Editor {
     /// Save the active buffer
     pub fn save(&mut self) -> anyhow::Result<()> {
+        if self.remote_transfer_busy() {
+            return Ok(());
+        }
+
         let path = self
             .active_state()
             .buffer
             .file_path()
             .map(|p| p.to_path_buf());
 
+        self.trim_trailing_whitespace_before_save();
+        self.ensure_final_newline_before_save();
+
+        // Large remote buffers upload in the background
+        if self.start_remote_upload() {
+            return Ok(());
+        }
+
         match self.active_state_mut().buffer.save() {
             Ok(()) => self.finalize_save(path),
             Err(e) => {
@@ -47,6 +75,57 @@ impl Editor {
         }
     }
 
+    /// Write the active buffer to `path` without making it the buffer's file.
+    /// The buffer stays associated with, and modified relative to, its own
+    /// file: nothing is marked saved and the original's mtime isn't updated.
+    pub fn write_copy_to(&mut self, path: &Path) -> std::io::Result<()> {
+        self.active_state().buffer.write_copy_to(path)
+    }
+
+    /// Start the prompt for the destination of "Write Copy", pre-filled with
+    /// the buffer's own path
+    pub fn start_write_copy_prompt(&mut self) {
+        let current_path = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| {
+                p.strip_prefix(&self.working_dir)
+                    .unwrap_or(p)
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .unwrap_or_default();
+        self.start_prompt_with_initial_text(
+            t!("file.write_copy_prompt").to_string(),
+            PromptType::WriteCopy,
+            current_path,
+        );
+    }
+
+    /// Handle the "Write Copy" prompt confirmation
+    pub(super) fn handle_write_copy(&mut self, input: &str) {
+        let input = input.trim();
+        if input.is_empty() {
+            self.set_status_message(t!("file.save_as_no_filename").to_string());
+            return;
+        }
+        let expanded_path = expand_tilde(input);
+        let full_path = if expanded_path.is_absolute() {
+            normalize_path(&expanded_path)
+        } else {
+            normalize_path(&self.working_dir.join(&expanded_path))
+        };
+        match self.write_copy_to(&full_path) {
+            Ok(()) => self.set_status_message(
+                t!("file.wrote_copy", path = full_path.display().to_string()).to_string(),
+            ),
+            Err(e) => self.set_important_status_message(
+                t!("file.write_copy_failed", error = e.to_string()).to_string(),
+            ),
+        }
+    }
+
     /// Internal helper to finalize save state (mark as saved, notify LSP, etc.)
     pub(crate) fn finalize_save(&mut self, path: Option<PathBuf>) -> anyhow::Result<()> {
         let buffer_id = self.active_buffer();
@@ -76,21 +155,22 @@ impl Editor {
         }
 
         if !silent {
-            self.status_message = Some(t!("status.file_saved").to_string());
+            self.show_status_message(t!("status.file_saved").to_string());
         }
 
         // Mark the event log position as saved (for undo modified tracking)
         if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
             event_log.mark_saved();
         }
+        self.buffer_saved_at
+            .insert(buffer_id, std::time::SystemTime::now());
 
         // Update file modification time after save
         if let Some(ref p) = path {
-            if let Ok(metadata) = self.filesystem.metadata(p) {
-                if let Some(mtime) = metadata.modified {
-                    self.file_mod_times.insert(p.clone(), mtime);
-                }
-            }
+            self.watch_file(p);
+        }
+        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+            metadata.missing_on_disk = None;
         }
 
         // Notify LSP of save
@@ -133,8 +213,7 @@ impl Editor {
                     // Actions ran successfully - if status_message was set by run_on_save_actions
                     // (e.g., for missing optional formatters), keep it. Otherwise update status.
                     if self.status_message.as_deref() == Some(&t!("status.file_saved")) {
-                        self.status_message =
-                            Some(t!("status.file_saved_with_actions").to_string());
+                        self.show_status_message(t!("status.file_saved_with_actions").to_string());
                     }
                     // else: keep the message set by run_on_save_actions (e.g., missing formatter)
                 }
@@ -143,9 +222,11 @@ impl Editor {
                 }
                 Err(e) => {
                     // Action failed, show error but don't fail the save
-                    self.status_message = Some(e);
+                    self.show_status_message(e);
                 }
             }
+
+            self.run_auto_commands(crate::config::AutoCommandEvent::OnSave, buffer_id);
         }
 
         Ok(())
@@ -174,7 +255,12 @@ impl Editor {
         // Collect info for modified buffers that have a file path
         let mut to_save = Vec::new();
         for (id, state) in &self.buffers {
-            if state.buffer.is_modified() {
+            // Don't silently recreate a file that was deleted on disk, or
+            // write one that is read-only
+            if state.buffer.is_modified()
+                && !self.is_missing_on_disk(*id)
+                && !self.is_read_only(*id)
+            {
                 if let Some(path) = state.buffer.file_path() {
                     to_save.push((*id, path.to_path_buf()));
                 }
@@ -213,7 +299,10 @@ impl Editor {
     pub fn save_all_on_exit(&mut self) -> anyhow::Result<usize> {
         let mut to_save = Vec::new();
         for (id, state) in &self.buffers {
-            if state.buffer.is_modified() {
+            if state.buffer.is_modified()
+                && !self.is_missing_on_disk(*id)
+                && !self.is_read_only(*id)
+            {
                 if let Some(path) = state.buffer.file_path() {
                     if !path.as_os_str().is_empty() {
                         to_save.push((*id, path.to_path_buf()));
@@ -257,25 +346,20 @@ impl Editor {
         let path = match self.active_state().buffer.file_path() {
             Some(p) => p.to_path_buf(),
             None => {
-                self.status_message = Some(t!("status.no_file_to_revert").to_string());
+                self.show_status_message(t!("status.no_file_to_revert").to_string());
                 return Ok(false);
             }
         };
 
         if !path.exists() {
-            self.status_message =
-                Some(t!("status.file_not_exists", path = path.display().to_string()).to_string());
+            self.show_important_status_message(
+                t!("status.file_not_exists", path = path.display().to_string()).to_string(),
+            );
             return Ok(false);
         }
 
-        // Save scroll position (from SplitViewState) and cursor positions before reloading
-        let active_split = self.split_manager.active_split();
-        let (old_top_byte, old_left_column) = self
-            .split_view_states
-            .get(&active_split)
-            .map(|vs| (vs.viewport.top_byte, vs.viewport.left_column))
-            .unwrap_or((0, 0));
-        let old_cursors = self.active_cursors().clone();
+        // Keep the old text so cursors and scroll position can follow their lines
+        let old_text = self.active_state().buffer.to_string();
 
         // Preserve user settings before reloading
         let old_buffer_settings = self.active_state().buffer_settings.clone();
@@ -289,17 +373,10 @@ impl Editor {
             self.config.editor.large_file_threshold_bytes as usize,
             &self.grammar_registry,
             &self.config.languages,
-            std::sync::Arc::clone(&self.filesystem),
+            std::sync::Arc::clone(self.active_state().buffer.filesystem()),
         )?;
+        let new_text = new_state.buffer.to_string();
 
-        // Restore cursor positions (clamped to valid range for new file size)
-        let new_file_size = new_state.buffer.len();
-        let mut restored_cursors = old_cursors;
-        restored_cursors.map(|cursor| {
-            cursor.position = cursor.position.min(new_file_size);
-            // Clear selection since the content may have changed
-            cursor.clear_selection();
-        });
         // Restore user settings (tab size, indentation, etc.)
         new_state.buffer_settings = old_buffer_settings;
         new_state.editing_disabled = old_editing_disabled;
@@ -312,37 +389,25 @@ impl Editor {
             // Note: line_wrap_enabled is now in SplitViewState.viewport
         }
 
-        // Restore cursor positions in SplitViewState (clamped to valid range for new file size)
-        let active_split = self.split_manager.active_split();
-        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
-            view_state.cursors = restored_cursors;
-        }
-
-        // Restore scroll position in SplitViewState (clamped to valid range for new file size)
-        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
-            view_state.viewport.top_byte = old_top_byte.min(new_file_size);
-            view_state.viewport.left_column = old_left_column;
-        }
+        // Move cursors and scroll positions to where their lines went
+        self.relocate_views_by_content(buffer_id, old_text.as_deref(), new_text.as_deref());
 
         // Clear the undo/redo history for this buffer
+        let new_log = self.new_event_log();
         if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
-            *event_log = EventLog::new();
+            *event_log = new_log;
         }
 
         // Clear seen_byte_ranges so plugins get notified of all visible lines
         self.seen_byte_ranges.remove(&buffer_id);
 
         // Update the file modification time
-        if let Ok(metadata) = self.filesystem.metadata(&path) {
-            if let Some(mtime) = metadata.modified {
-                self.file_mod_times.insert(path.clone(), mtime);
-            }
-        }
+        self.watch_file(&path);
 
         // Notify LSP that the file was changed
         self.notify_lsp_file_changed(&path);
 
-        self.status_message = Some(t!("status.reverted").to_string());
+        self.show_status_message(t!("status.reverted").to_string());
         Ok(true)
     }
 
@@ -351,9 +416,15 @@ impl Editor {
         self.auto_revert_enabled = !self.auto_revert_enabled;
 
         if self.auto_revert_enabled {
-            self.status_message = Some(t!("status.auto_revert_enabled").to_string());
+            // Remote files are checked in the background, at their own
+            // interval, from the next poll on
+            let seconds = self.config.editor.auto_revert_poll_interval_ms as f64 / 1000.0;
+            self.show_status_message(
+                t!("status.auto_revert_enabled", seconds = seconds).to_string(),
+            );
         } else {
-            self.status_message = Some(t!("status.auto_revert_disabled").to_string());
+            self.remote_file_poller = None;
+            self.show_status_message(t!("status.auto_revert_disabled").to_string());
         }
     }
 
@@ -379,30 +450,84 @@ impl Editor {
         if elapsed < poll_interval {
             return false;
         }
-        self.last_auto_revert_poll = self.time_source.now();
+        let now = self.time_source.now();
+        self.last_auto_revert_poll = now;
 
-        // Collect paths of open files that need checking
-        let files_to_check: Vec<PathBuf> = self
+        // Collect paths of open files
+        let mut open_files: HashSet<PathBuf> = self
             .buffers
             .values()
             .filter_map(|state| state.buffer.file_path().map(PathBuf::from))
             .collect();
 
+        // Forget files that are no longer open
+        self.file_mod_times
+            .retain(|path, _| open_files.contains(path));
+        self.file_poll_states
+            .retain(|path, _| open_files.contains(path));
+        self.file_rapid_change_counts
+            .retain(|path, _| open_files.contains(path));
+
+        // Remote files are checked in the background
+        let remote_files = self.update_remote_file_poller();
+        open_files.retain(|path| !remote_files.contains(path));
+
+        // Unchanged files back off, but the active buffer is always checked
+        let active_path = self.active_state().buffer.file_path().map(PathBuf::from);
+        let max_interval = std::time::Duration::from_millis(
+            self.config
+                .editor
+                .auto_revert_max_poll_interval_ms
+                .max(self.config.editor.auto_revert_poll_interval_ms),
+        );
+
         let mut any_changed = false;
 
-        for path in files_to_check {
-            // Get current mtime
-            let current_mtime = match self.filesystem.metadata(&path) {
+        for path in open_files {
+            if active_path.as_ref() != Some(&path)
+                && self
+                    .file_poll_states
+                    .get(&path)
+                    .is_some_and(|state| now < state.next_poll)
+            {
+                continue;
+            }
+
+            // Get current mtime and size
+            let (current_mtime, current_size) = match self
+                .filesystem_for_path(&path)
+                .metadata(&path)
+            {
                 Ok(meta) => match meta.modified {
-                    Some(mtime) => mtime,
+                    Some(mtime) => (mtime, meta.size),
                     None => continue,
                 },
-                Err(_) => continue, // File might have been deleted
+                Err(_) => {
+                    // Only a file that was seen on disk can go missing
+                    if self.file_mod_times.contains_key(&path) && self.handle_file_missing(&path) {
+                        any_changed = true;
+                    }
+                    continue;
+                }
             };
+            self.handle_file_reappeared(&path);
 
-            // Check if mtime has changed
+            // Check if mtime or size has changed
             if let Some(&stored_mtime) = self.file_mod_times.get(&path) {
-                if current_mtime != stored_mtime {
+                let changed =
+                    current_mtime != stored_mtime || self.file_size_changed(&path, current_size);
+                if let Some(state) = self.file_poll_states.get_mut(&path) {
+                    state.unchanged_polls = if changed {
+                        0
+                    } else {
+                        state.unchanged_polls.saturating_add(1)
+                    };
+                    let backoff = poll_interval
+                        .saturating_mul(1 << state.unchanged_polls.min(16))
+                        .min(max_interval);
+                    state.next_poll = now + backoff;
+                }
+                if changed {
                     // Handle the file change (this includes debouncing)
                     // Note: file_mod_times is updated by handle_file_changed after successful revert,
                     // not here, to avoid the race where the revert check sees the already-updated mtime
@@ -412,14 +537,51 @@ impl Editor {
                     }
                 }
             } else {
-                // First time seeing this file, record its mtime
-                self.file_mod_times.insert(path, current_mtime);
+                // First time seeing this file, record its mtime and size
+                self.watch_file(&path);
             }
         }
 
         any_changed
     }
 
+    /// Hand the open remote files to the background poller, starting it if
+    /// needed. Large files aren't polled. Returns the paths of all open
+    /// remote files, polled or not.
+    fn update_remote_file_poller(&mut self) -> HashSet<PathBuf> {
+        let mut remote_files = HashSet::new();
+        let mut polled = Vec::new();
+        for state in self.buffers.values() {
+            let filesystem = state.buffer.filesystem();
+            let Some(path) = state.buffer.file_path() else {
+                continue;
+            };
+            if filesystem.remote_connection_info().is_none() {
+                continue;
+            }
+            remote_files.insert(path.to_path_buf());
+            if !state.buffer.is_large_file() {
+                polled.push((path.to_path_buf(), std::sync::Arc::clone(filesystem)));
+            }
+        }
+
+        if self.remote_file_poller.is_none() && !polled.is_empty() {
+            if let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) {
+                self.remote_file_poller = Some(crate::services::remote::RemoteFilePoller::start(
+                    runtime.handle(),
+                    std::time::Duration::from_millis(
+                        self.config.editor.remote_auto_revert_poll_interval_ms,
+                    ),
+                    bridge.sender(),
+                ));
+            }
+        }
+        if let Some(poller) = &self.remote_file_poller {
+            poller.set_watched(polled);
+        }
+        remote_files
+    }
+
     /// Poll for file tree changes (called from main loop)
     ///
     /// Checks modification times of expanded directories to detect new/deleted files.
@@ -637,6 +799,17 @@ impl Editor {
                     "LSP for {} not auto-starting (auto_start=false). Use command palette to start manually.",
                     language
                 );
+                // In an untrusted workspace, servers that would have started
+                // on their own ask first. While the workspace itself is
+                // undecided its trust prompt covers them.
+                let would_auto_start = lsp.get_config(&language).is_some_and(|c| c.auto_start);
+                if would_auto_start
+                    && self.workspace_trust == WorkspaceTrust::Untrusted
+                    && self.pending_lsp_confirmation.is_none()
+                    && lsp.take_trust_prompt(&language)
+                {
+                    self.show_lsp_confirmation_popup(&language);
+                }
             }
             LspSpawnResult::NotConfigured => {
                 tracing::debug!("No LSP server configured for language: {}", language);
@@ -647,17 +820,51 @@ impl Editor {
         }
     }
 
-    /// Record a file's modification time (called when opening files)
+    /// Record a file's modification time and size (called when opening,
+    /// saving or reverting files)
     /// This is used by the polling-based auto-revert to detect external changes
     pub(crate) fn watch_file(&mut self, path: &Path) {
         // Record current modification time for polling
-        if let Ok(metadata) = self.filesystem.metadata(path) {
+        if let Ok(metadata) = self.filesystem_for_path(path).metadata(path) {
             if let Some(mtime) = metadata.modified {
                 self.file_mod_times.insert(path.to_path_buf(), mtime);
             }
+            self.file_poll_states.insert(
+                path.to_path_buf(),
+                FilePollState {
+                    size: metadata.size,
+                    unchanged_polls: 0,
+                    next_poll: self.time_source.now(),
+                },
+            );
         }
     }
 
+    /// Filesystem holding `path`: that of a buffer open on it, which may be
+    /// another remote connection, or else the editor's
+    pub(crate) fn filesystem_for_path(
+        &self,
+        path: &Path,
+    ) -> std::sync::Arc<dyn crate::model::filesystem::FileSystem + Send + Sync> {
+        self.buffers
+            .values()
+            .find(|state| state.buffer.file_path() == Some(path))
+            .map(|state| std::sync::Arc::clone(state.buffer.filesystem()))
+            .unwrap_or_else(|| std::sync::Arc::clone(&self.filesystem))
+    }
+
+    /// Size recorded for the file when it was last loaded, saved or checked
+    pub(super) fn recorded_file_size(&self, path: &Path) -> Option<u64> {
+        self.file_poll_states.get(path).map(|state| state.size)
+    }
+
+    /// Whether the file's size differs from the last recorded one
+    fn file_size_changed(&self, path: &Path, size: u64) -> bool {
+        self.file_poll_states
+            .get(path)
+            .is_some_and(|state| state.size != size)
+    }
+
     /// Notify LSP that a file's contents changed (e.g., after revert)
     pub(crate) fn notify_lsp_file_changed(&mut self, path: &Path) {
         use crate::services::lsp::manager::LspSpawnResult;
@@ -755,27 +962,19 @@ impl Editor {
     /// Revert a specific buffer by ID without affecting the active viewport.
     ///
     /// This is used for auto-reverting background buffers that aren't currently
-    /// visible in the active split. It reloads the buffer content and updates
-    /// cursors (clamped to valid positions), but does NOT touch any viewport state.
+    /// visible in the active split. It reloads the buffer content and moves this
+    /// buffer's cursors and scroll positions along with their lines, but does NOT
+    /// touch the viewport of whatever the active split is showing.
     pub(crate) fn revert_buffer_by_id(
         &mut self,
         buffer_id: BufferId,
         path: &Path,
     ) -> anyhow::Result<()> {
         // Preserve user settings before reloading
-        // TODO: Consider moving line numbers to SplitViewState (per-view setting)
-        // Get cursors from split view states for this buffer (find any split showing it)
-        let old_cursors = self
-            .split_view_states
-            .values()
-            .find_map(|vs| {
-                if vs.keyed_states.contains_key(&buffer_id) {
-                    vs.keyed_states.get(&buffer_id).map(|bs| bs.cursors.clone())
-                } else {
-                    None
-                }
-            })
-            .unwrap_or_default();
+        let old_text = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|s| s.buffer.to_string());
         let (old_buffer_settings, old_editing_disabled) = self
             .buffers
             .get(&buffer_id)
@@ -790,18 +989,11 @@ impl Editor {
             self.config.editor.large_file_threshold_bytes as usize,
             &self.grammar_registry,
             &self.config.languages,
-            std::sync::Arc::clone(&self.filesystem),
+            self.filesystem_for_path(path),
         )?;
 
-        // Get the new file size for clamping
-        let new_file_size = new_state.buffer.len();
+        let new_text = new_state.buffer.to_string();
 
-        // Restore cursor positions (clamped to valid range for new file size)
-        let mut restored_cursors = old_cursors;
-        restored_cursors.map(|cursor| {
-            cursor.position = cursor.position.min(new_file_size);
-            cursor.clear_selection();
-        });
         // Restore user settings (tab size, indentation, etc.)
         new_state.buffer_settings = old_buffer_settings;
         new_state.editing_disabled = old_editing_disabled;
@@ -812,27 +1004,20 @@ impl Editor {
             *state = new_state;
         }
 
-        // Restore cursors in any split view states that have this buffer
-        for vs in self.split_view_states.values_mut() {
-            if let Some(buf_state) = vs.keyed_states.get_mut(&buffer_id) {
-                buf_state.cursors = restored_cursors.clone();
-            }
-        }
+        // Move cursors in any split view states that have this buffer
+        self.relocate_views_by_content(buffer_id, old_text.as_deref(), new_text.as_deref());
 
         // Clear the undo/redo history for this buffer
+        let new_log = self.new_event_log();
         if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
-            *event_log = EventLog::new();
+            *event_log = new_log;
         }
 
         // Clear seen_byte_ranges so plugins get notified of all visible lines
         self.seen_byte_ranges.remove(&buffer_id);
 
         // Update the file modification time
-        if let Ok(metadata) = self.filesystem.metadata(path) {
-            if let Some(mtime) = metadata.modified {
-                self.file_mod_times.insert(path.to_path_buf(), mtime);
-            }
-        }
+        self.watch_file(path);
 
         // Notify LSP that the file was changed
         self.notify_lsp_file_changed(path);
@@ -840,6 +1025,41 @@ impl Editor {
         Ok(())
     }
 
+    /// Move every split's cursors and scroll position in `buffer_id` from
+    /// `old_text` to the buffer's new content by matching line content, so
+    /// lines added or removed elsewhere don't jump the view somewhere
+    /// unrelated. Selections are cleared since the text under them may have
+    /// changed. Without both texts (large files) positions are only clamped.
+    pub(crate) fn relocate_views_by_content(
+        &mut self,
+        buffer_id: BufferId,
+        old_text: Option<&str>,
+        new_text: Option<&str>,
+    ) {
+        let new_len = self
+            .buffers
+            .get(&buffer_id)
+            .map(|s| s.buffer.len())
+            .unwrap_or(0);
+        let relocator = old_text
+            .zip(new_text)
+            .map(|(old, new)| Relocator::new(old, new));
+        let relocate = |position: usize| match &relocator {
+            Some(relocator) => relocator.relocate(position),
+            None => position.min(new_len),
+        };
+
+        for vs in self.split_view_states.values_mut() {
+            if let Some(buf_state) = vs.keyed_states.get_mut(&buffer_id) {
+                buf_state.cursors.map(|cursor| {
+                    cursor.position = relocate(cursor.position);
+                    cursor.clear_selection();
+                });
+                buf_state.viewport.top_byte = relocate(buf_state.viewport.top_byte);
+            }
+        }
+    }
+
     /// Handle a file change notification (from file watcher)
     pub fn handle_file_changed(&mut self, changed_path: &str) {
         let path = PathBuf::from(changed_path);
@@ -856,6 +1076,26 @@ impl Editor {
             return;
         }
 
+        // Check if the file actually changed (compare mod times), on the host
+        // it is on. Remote files polled in the background report deletions
+        // and reappearances through here too.
+        let metadata = match self.filesystem_for_path(&path).metadata(&path) {
+            Ok(metadata) => {
+                self.handle_file_reappeared(&path);
+                metadata
+            }
+            Err(_) => {
+                if self.file_mod_times.contains_key(&path) {
+                    self.handle_file_missing(&path);
+                }
+                return;
+            }
+        };
+        let Some(current_mtime) = metadata.modified else {
+            return;
+        };
+        let current_size = metadata.size;
+
         for buffer_id in buffer_ids {
             // Skip terminal buffers - they manage their own content via PTY streaming
             // and should not be auto-reverted (which would reset editing_disabled and line_numbers)
@@ -868,24 +1108,16 @@ impl Editor {
                 None => continue,
             };
 
-            // Check if the file actually changed (compare mod times)
             // We use optimistic concurrency: check mtime, and if we decide to revert,
             // re-check to handle the race where a save completed between our checks.
-            let current_mtime = match self
-                .filesystem
-                .metadata(&path)
-                .ok()
-                .and_then(|m| m.modified)
-            {
-                Some(mtime) => mtime,
-                None => continue, // Can't read file, skip
-            };
+            let size_changed = self.file_size_changed(&path, current_size);
 
-            let dominated_by_stored = self
-                .file_mod_times
-                .get(&path)
-                .map(|stored| current_mtime <= *stored)
-                .unwrap_or(false);
+            let dominated_by_stored = !size_changed
+                && self
+                    .file_mod_times
+                    .get(&path)
+                    .map(|stored| current_mtime <= *stored)
+                    .unwrap_or(false);
 
             if dominated_by_stored {
                 continue;
@@ -893,7 +1125,7 @@ impl Editor {
 
             // If buffer has local modifications, show a warning (don't auto-revert)
             if state.buffer.is_modified() {
-                self.status_message = Some(format!(
+                self.show_status_message(format!(
                     "File {} changed on disk (buffer has unsaved changes)",
                     path.display()
                 ));
@@ -905,11 +1137,12 @@ impl Editor {
                 // Optimistic concurrency: re-check mtime before reverting.
                 // A save may have completed between our first check and now,
                 // updating file_mod_times. If so, skip the revert.
-                let still_needs_revert = self
-                    .file_mod_times
-                    .get(&path)
-                    .map(|stored| current_mtime > *stored)
-                    .unwrap_or(true);
+                let still_needs_revert = self.file_size_changed(&path, current_size)
+                    || self
+                        .file_mod_times
+                        .get(&path)
+                        .map(|stored| current_mtime > *stored)
+                        .unwrap_or(true);
 
                 if !still_needs_revert {
                     continue;
@@ -944,11 +1177,12 @@ impl Editor {
     /// Check if saving would overwrite changes made by another process
     /// Returns Some(current_mtime) if there's a conflict, None otherwise
     pub fn check_save_conflict(&self) -> Option<std::time::SystemTime> {
-        let path = self.active_state().buffer.file_path()?;
+        let buffer = &self.active_state().buffer;
+        let path = buffer.file_path()?;
 
-        // Get current file modification time
-        let current_mtime = self
-            .filesystem
+        // Get current file modification time, from the host the file is on
+        let current_mtime = buffer
+            .filesystem()
             .metadata(path)
             .ok()
             .and_then(|m| m.modified)?;
@@ -0,0 +1,98 @@
+//! Remembered per-file overrides.
+//!
+//! Language, encoding, line ending and read-only choices made by hand are
+//! recorded in the project's [`FileOverrideStore`] and re-applied when the
+//! file is opened again, after auto-detection.
+//!
+//! [`FileOverrideStore`]: crate::services::file_overrides::FileOverrideStore
+
+use std::path::Path;
+use std::sync::Arc;
+
+use super::Editor;
+use crate::primitives::detected_language::DetectedLanguage;
+use crate::services::file_overrides::FileOverride;
+use crate::state::EditorState;
+use rust_i18n::t;
+
+impl Editor {
+    /// Apply the remembered language, encoding and line ending for `path` to
+    /// a freshly loaded buffer. Read-only is applied with the buffer metadata.
+    pub(super) fn apply_file_overrides(&self, state: &mut EditorState, path: &Path) {
+        let Some(overrides) = self.file_overrides.get(path) else {
+            return;
+        };
+
+        // Re-read the file first: the encoding changes the buffer content
+        if let Some(encoding) = overrides.encoding {
+            if state.buffer.encoding() != encoding {
+                match crate::model::buffer::Buffer::load_from_file_with_encoding(
+                    path,
+                    encoding,
+                    Arc::clone(&self.filesystem),
+                    crate::model::buffer::BufferConfig {
+                        estimated_line_length: self.config.editor.estimated_line_length,
+                    },
+                ) {
+                    Ok(buffer) => state.buffer = buffer,
+                    Err(e) => tracing::warn!(
+                        "Failed to reload {:?} as {}: {}",
+                        path,
+                        encoding.display_name(),
+                        e
+                    ),
+                }
+            }
+        }
+
+        if let Some(language) = &overrides.language {
+            if language == "Plain Text" {
+                state.apply_language(DetectedLanguage::plain_text());
+            } else if let Some(detected) = DetectedLanguage::from_syntax_name(
+                language,
+                &self.grammar_registry,
+                &self.config.languages,
+            ) {
+                state.apply_language(detected);
+            }
+        }
+
+        if let Some(line_ending) = overrides.line_ending() {
+            if state.buffer.line_ending() != line_ending {
+                state.buffer.set_line_ending(line_ending);
+            }
+        }
+    }
+
+    /// Record an override for the active buffer's file, if it has one.
+    pub(super) fn remember_file_override(&mut self, f: impl FnOnce(&mut FileOverride)) {
+        let buffer_id = self.active_buffer();
+        let Some(path) = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_path())
+            .map(|p| p.to_path_buf())
+        else {
+            return;
+        };
+        self.file_overrides.update(&path, f);
+    }
+
+    /// Forget all overrides for the active buffer's file.
+    ///
+    /// The current buffer keeps its settings; the file uses auto-detection
+    /// the next time it is opened.
+    pub fn clear_file_overrides(&mut self) {
+        let path = self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .and_then(|m| m.file_path())
+            .map(|p| p.to_path_buf());
+        let message = match path {
+            Some(path) if self.file_overrides.remove(&path) => t!("file_overrides.cleared"),
+            Some(_) => t!("file_overrides.none"),
+            None => t!("file_overrides.no_file"),
+        };
+        self.set_status_message(message.to_string());
+    }
+}
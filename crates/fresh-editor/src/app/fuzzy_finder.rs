@@ -0,0 +1,379 @@
+//! Fuzzy file finder.
+//!
+//! Walks the working directory on the tokio runtime's blocking pool with the
+//! `ignore` crate, so `.gitignore`, `.ignore` and hidden files are skipped the
+//! way `git` and `rg` skip them, and streams the relative paths back in
+//! batches through the async bridge. Each batch is scored against the current
+//! query as it arrives, so the list fills in while a large tree is still being
+//! walked. The walker waits while [`MAX_IN_FLIGHT_BATCHES`] batches are
+//! unread, and rescoring everything after an edit of the query waits for
+//! typing to pause for [`QUERY_DEBOUNCE`].
+//!
+//! The walk reads the local disk, so the finder is turned off when the
+//! editor is on a remote host.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::input::fuzzy::fuzzy_match;
+use crate::services::async_bridge::AsyncMessage;
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+/// Paths sent per message
+const BATCH_SIZE: usize = 512;
+
+/// Batches the walker may send ahead of the editor reading them
+const MAX_IN_FLIGHT_BATCHES: usize = 8;
+
+/// Files listed at most; the walk stops there
+const MAX_FILES: usize = 200_000;
+
+/// Matches shown
+const MAX_RESULTS: usize = 100;
+
+/// Pause in typing before the whole list is scored again
+const QUERY_DEBOUNCE: Duration = Duration::from_millis(60);
+
+/// Tells the batches of a walk from those of an earlier, abandoned one
+static NEXT_WALK_ID: AtomicU64 = AtomicU64::new(0);
+
+/// An open fuzzy finder and the walk feeding it
+pub(crate) struct FuzzyFinder {
+    walk_id: u64,
+    /// Paths found so far, relative to the working directory, in walk order
+    paths: Vec<String>,
+    /// Query `matches` were scored for
+    query: String,
+    /// Best matches as (score, index into `paths`), best first
+    matches: Vec<(i32, usize)>,
+    /// Query typed since the last scoring, and when it was last edited
+    pending_query: Option<(String, Instant)>,
+    in_flight: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Drop for FuzzyFinder {
+    fn drop(&mut self) {
+        // Stop the walk; nobody reads its batches anymore
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl FuzzyFinder {
+    /// Score `paths[from..]` against the current query and keep the best
+    fn score_from(&mut self, from: usize) {
+        for (index, path) in self.paths.iter().enumerate().skip(from) {
+            if self.query.is_empty() {
+                if self.matches.len() >= MAX_RESULTS {
+                    break;
+                }
+                self.matches.push((0, index));
+                continue;
+            }
+            let result = fuzzy_match(&self.query, path);
+            if result.matched {
+                self.matches.push((result.score, index));
+            }
+        }
+        // Ties keep walk order
+        self.matches
+            .sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.matches.truncate(MAX_RESULTS);
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion> {
+        self.matches
+            .iter()
+            .map(|&(_, index)| Suggestion {
+                text: self.paths[index].clone(),
+                description: None,
+                value: Some(self.paths[index].clone()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect()
+    }
+}
+
+impl Editor {
+    /// Open the fuzzy file finder over the working directory
+    pub fn open_fuzzy_finder(&mut self) {
+        if self.filesystem.remote_connection_info().is_some() {
+            self.set_status_message(t!("fuzzy_finder.remote").to_string());
+            return;
+        }
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            self.set_status_message(t!("fuzzy_finder.unavailable").to_string());
+            return;
+        };
+
+        let finder = FuzzyFinder {
+            walk_id: NEXT_WALK_ID.fetch_add(1, Ordering::Relaxed),
+            paths: Vec::new(),
+            query: String::new(),
+            matches: Vec::new(),
+            pending_query: None,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+        let walk = Walk {
+            root: self.working_dir.clone(),
+            follow_symlinks: self.config.editor.fuzzy_finder_follow_symlinks,
+            walk_id: finder.walk_id,
+            in_flight: Arc::clone(&finder.in_flight),
+            cancelled: Arc::clone(&finder.cancelled),
+            sender: bridge.sender(),
+        };
+        runtime.spawn_blocking(move || walk.run());
+        self.fuzzy_finder = Some(finder);
+
+        self.start_prompt_with_suggestions(
+            t!("fuzzy_finder.prompt").to_string(),
+            PromptType::FuzzyFinder,
+            Vec::new(),
+        );
+    }
+
+    /// Note an edit of the finder's query; it is applied once typing pauses
+    pub(super) fn fuzzy_finder_query_changed(&mut self, query: &str) {
+        let now = self.time_source.now();
+        if let Some(finder) = &mut self.fuzzy_finder {
+            finder.pending_query = Some((query.to_string(), now));
+        }
+    }
+
+    /// Score the whole list again if the query changed and typing has
+    /// paused. Returns true if the suggestions changed.
+    pub fn check_fuzzy_finder_query_timer(&mut self) -> bool {
+        if !self.fuzzy_finder_is_open() {
+            return false;
+        }
+        let Some(finder) = &mut self.fuzzy_finder else {
+            return false;
+        };
+        let Some((query, edited_at)) = finder.pending_query.take() else {
+            return false;
+        };
+        if self.time_source.elapsed_since(edited_at) < QUERY_DEBOUNCE {
+            finder.pending_query = Some((query, edited_at));
+            return false;
+        }
+        finder.query = query;
+        finder.matches.clear();
+        finder.score_from(0);
+        self.show_fuzzy_finder_matches(true);
+        true
+    }
+
+    /// Add a batch of paths from the walk
+    pub(super) fn handle_fuzzy_finder_files(
+        &mut self,
+        walk_id: u64,
+        paths: Vec<String>,
+        done: bool,
+    ) {
+        if !self.fuzzy_finder_is_open() {
+            return;
+        }
+        let Some(finder) = &mut self.fuzzy_finder else {
+            return;
+        };
+        if finder.walk_id != walk_id {
+            return;
+        }
+        finder.in_flight.fetch_sub(1, Ordering::AcqRel);
+        let from = finder.paths.len();
+        finder.paths.extend(paths);
+        finder.score_from(from);
+        self.show_fuzzy_finder_matches(false);
+        if done {
+            tracing::debug!(
+                "Fuzzy finder walk done: {} files",
+                self.fuzzy_finder.as_ref().map_or(0, |f| f.paths.len())
+            );
+        }
+    }
+
+    /// Close the finder and return the file chosen in it, if it exists
+    pub(super) fn take_fuzzy_finder_choice(&mut self, input: &str) -> Option<PathBuf> {
+        self.fuzzy_finder = None;
+        let path = self.working_dir.join(input);
+        if input.is_empty() || !path.is_file() {
+            self.set_status_message(t!("fuzzy_finder.no_match").to_string());
+            return None;
+        }
+        Some(path)
+    }
+
+    /// Whether the finder's prompt is still up. A finder left behind by a
+    /// closed prompt is dropped, stopping its walk.
+    fn fuzzy_finder_is_open(&mut self) -> bool {
+        let open = self
+            .prompt
+            .as_ref()
+            .is_some_and(|prompt| prompt.prompt_type == PromptType::FuzzyFinder);
+        if !open {
+            self.fuzzy_finder = None;
+        }
+        open && self.fuzzy_finder.is_some()
+    }
+
+    /// Put the current matches in the prompt. The selection is kept while
+    /// more files come in, and reset to the top for a new query.
+    fn show_fuzzy_finder_matches(&mut self, new_query: bool) {
+        let (Some(finder), Some(prompt)) = (&self.fuzzy_finder, &mut self.prompt) else {
+            return;
+        };
+        prompt.suggestions = finder.suggestions();
+        prompt.selected_suggestion = match prompt.selected_suggestion {
+            _ if prompt.suggestions.is_empty() => None,
+            Some(selected) if !new_query && selected < prompt.suggestions.len() => Some(selected),
+            _ => Some(0),
+        };
+    }
+}
+
+/// A walk of the working directory, run on a blocking thread
+struct Walk {
+    root: PathBuf,
+    follow_symlinks: bool,
+    walk_id: u64,
+    in_flight: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+    sender: mpsc::Sender<AsyncMessage>,
+}
+
+impl Walk {
+    fn run(self) {
+        let walker = ignore::WalkBuilder::new(&self.root)
+            .follow_links(self.follow_symlinks)
+            // Honor .gitignore outside git repositories too
+            .require_git(false)
+            .build();
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        let mut total = 0;
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                // Unreadable directories and symlink loops
+                Err(e) => {
+                    tracing::debug!("Fuzzy finder skipped an entry: {}", e);
+                    continue;
+                }
+            };
+            if !entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(&self.root) else {
+                continue;
+            };
+            batch.push(relative.to_string_lossy().into_owned());
+            total += 1;
+            if total == MAX_FILES {
+                tracing::info!("Fuzzy finder stopped after {} files", MAX_FILES);
+                break;
+            }
+            if batch.len() == BATCH_SIZE && !self.send(std::mem::take(&mut batch), false) {
+                return;
+            }
+        }
+        self.send(batch, true);
+    }
+
+    /// Send a batch once the editor has caught up. Returns false when the
+    /// finder is gone.
+    fn send(&self, paths: Vec<String>, done: bool) -> bool {
+        while self.in_flight.load(Ordering::Acquire) >= MAX_IN_FLIGHT_BATCHES {
+            if self.cancelled.load(Ordering::Relaxed) {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        if self.cancelled.load(Ordering::Relaxed) {
+            return false;
+        }
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        let message = AsyncMessage::FuzzyFinderFiles {
+            walk_id: self.walk_id,
+            paths,
+            done,
+        };
+        self.sender.send(message).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finder(paths: &[&str]) -> FuzzyFinder {
+        FuzzyFinder {
+            walk_id: 0,
+            paths: paths.iter().map(|path| path.to_string()).collect(),
+            query: String::new(),
+            matches: Vec::new(),
+            pending_query: None,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    #[test]
+    fn test_batches_are_scored_incrementally() {
+        let mut finder = finder(&["docs/readme.md", "src/main.rs"]);
+        finder.query = "main".to_string();
+        finder.score_from(0);
+        assert_eq!(finder.suggestions().len(), 1);
+
+        finder.paths.push("src/main_window.rs".to_string());
+        finder.paths.push("tests/other.rs".to_string());
+        finder.score_from(2);
+        let texts: Vec<_> = finder.suggestions().into_iter().map(|s| s.text).collect();
+        assert_eq!(texts.len(), 2);
+        assert!(texts.contains(&"src/main.rs".to_string()));
+        assert!(texts.contains(&"src/main_window.rs".to_string()));
+    }
+
+    #[test]
+    fn test_walk_skips_gitignored_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "build/\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("build")).unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("build").join("out.o"), "").unwrap();
+        std::fs::write(dir.path().join("src").join("lib.rs"), "").unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        Walk {
+            root: dir.path().to_path_buf(),
+            follow_symlinks: false,
+            walk_id: 7,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            sender,
+        }
+        .run();
+
+        match receiver.recv().unwrap() {
+            AsyncMessage::FuzzyFinderFiles {
+                walk_id,
+                paths,
+                done,
+            } => {
+                assert_eq!(walk_id, 7);
+                assert!(done);
+                let expected = PathBuf::from("src").join("lib.rs");
+                assert_eq!(paths, vec![expected.to_string_lossy().into_owned()]);
+            }
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+}
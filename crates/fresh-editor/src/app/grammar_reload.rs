@@ -0,0 +1,153 @@
+//! Reloading TextMate grammars without restarting.
+//!
+//! The grammar registry is rebuilt from disk on a background thread (user
+//! grammars in `~/.config/fresh/grammars/` and language packs in
+//! `~/.config/fresh/languages/packages/`), plus any grammars plugins have
+//! registered. The finished registry arrives as a `GrammarRegistryBuilt`
+//! message, which swaps the `Arc` and re-detects the language of open buffers.
+//!
+//! Reloads are started by the Reload Grammars command or by polling the
+//! grammar directories for changes, the same way open files are polled for
+//! auto-revert.
+
+use super::Editor;
+use crate::primitives::grammar::GrammarRegistry;
+use crate::services::async_bridge::AsyncMessage;
+use rust_i18n::t;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// How often the grammar directories are checked for changes
+const GRAMMAR_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How deep below each grammar directory changes are noticed. Language packs
+/// keep their grammars in `packages/<pack>/grammars/<file>`.
+const GRAMMAR_SCAN_DEPTH: usize = 4;
+
+/// Summary of the grammar directories' contents; a change in the number of
+/// entries or the newest modification time means something was installed,
+/// removed or edited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct GrammarDirsFingerprint {
+    entries: usize,
+    newest: Option<SystemTime>,
+}
+
+impl Editor {
+    /// Rebuild the grammar registry from disk (Reload Grammars command)
+    pub fn reload_grammars(&mut self) {
+        self.grammar_dirs_fingerprint = grammar_dirs_fingerprint(&self.dir_context.config_dir);
+        self.start_grammar_reload();
+    }
+
+    /// Spawn the background rebuild, or queue it behind a build that is
+    /// already running
+    fn start_grammar_reload(&mut self) {
+        if self.grammar_build_in_progress {
+            self.grammar_full_reload_pending = true;
+            return;
+        }
+        let Some(sender) = self.async_bridge.as_ref().map(|bridge| bridge.sender()) else {
+            return;
+        };
+        self.grammar_full_reload_pending = false;
+        self.grammar_full_reload_in_flight = true;
+        self.grammar_build_in_progress = true;
+        self.set_status_message(t!("grammar.reloading").to_string());
+
+        let config_dir = self.dir_context.config_dir.clone();
+        let plugin_grammars = self.plugin_grammars.clone();
+        std::thread::Builder::new()
+            .name("grammar-reload".to_string())
+            .spawn(move || {
+                let mut registry = GrammarRegistry::for_editor(config_dir);
+                if !plugin_grammars.is_empty() {
+                    match GrammarRegistry::with_additional_grammars(&registry, &plugin_grammars) {
+                        Some(with_plugins) => registry = Arc::new(with_plugins),
+                        None => tracing::warn!("Failed to re-add plugin grammars after reload"),
+                    }
+                }
+                // Ok to ignore: receiver may be gone if app is shutting down.
+                drop(sender.send(AsyncMessage::GrammarRegistryBuilt {
+                    registry,
+                    callback_ids: Vec::new(),
+                }));
+            })
+            .ok();
+    }
+
+    /// Called once a background grammar build has been swapped in. Reports a
+    /// finished reload and starts one that was queued behind the build.
+    pub(super) fn finish_grammar_reload(&mut self) {
+        if std::mem::take(&mut self.grammar_full_reload_in_flight) {
+            self.set_status_message(
+                t!(
+                    "grammar.reloaded",
+                    count = self.grammar_registry.available_syntaxes().len()
+                )
+                .to_string(),
+            );
+        }
+        if self.grammar_full_reload_pending {
+            self.start_grammar_reload();
+        }
+    }
+
+    /// Poll the grammar directories (called from main loop) and reload when
+    /// their contents change. The first poll only records the baseline.
+    pub fn poll_grammar_changes(&mut self) {
+        if self.time_source.elapsed_since(self.last_grammar_poll) < GRAMMAR_POLL_INTERVAL {
+            return;
+        }
+        self.last_grammar_poll = self.time_source.now();
+
+        let fingerprint = grammar_dirs_fingerprint(&self.dir_context.config_dir);
+        if !self.grammar_dirs_polled {
+            self.grammar_dirs_polled = true;
+            self.grammar_dirs_fingerprint = fingerprint;
+            return;
+        }
+        if fingerprint != self.grammar_dirs_fingerprint {
+            tracing::info!("Grammar directories changed, reloading grammars");
+            self.grammar_dirs_fingerprint = fingerprint;
+            self.start_grammar_reload();
+        }
+    }
+}
+
+/// Fingerprint of the user grammar and language pack directories, or `None`
+/// when neither exists
+fn grammar_dirs_fingerprint(config_dir: &Path) -> Option<GrammarDirsFingerprint> {
+    let dirs: [PathBuf; 2] = [
+        GrammarRegistry::grammars_directory(config_dir),
+        config_dir.join("languages/packages"),
+    ];
+    let mut fingerprint: Option<GrammarDirsFingerprint> = None;
+    for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+        let fp = fingerprint.get_or_insert(GrammarDirsFingerprint {
+            entries: 0,
+            newest: None,
+        });
+        scan_dir(dir, GRAMMAR_SCAN_DEPTH, fp);
+    }
+    fingerprint
+}
+
+fn scan_dir(dir: &Path, depth: usize, fingerprint: &mut GrammarDirsFingerprint) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        fingerprint.entries += 1;
+        if let Ok(modified) = metadata.modified() {
+            fingerprint.newest = fingerprint.newest.max(Some(modified));
+        }
+        if metadata.is_dir() && depth > 1 {
+            scan_dir(&entry.path(), depth - 1, fingerprint);
+        }
+    }
+}
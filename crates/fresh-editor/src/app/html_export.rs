@@ -0,0 +1,174 @@
+//! Export of buffer content as syntax-highlighted HTML.
+//!
+//! This module provides:
+//! - "Export Buffer as HTML": writes the buffer (or selection) to a standalone
+//!   HTML file using the current theme's colors
+//! - "Copy as Rich Text": copies the selection as HTML with the current theme
+
+use std::io::{self, BufWriter};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use super::{normalize_path, Editor};
+use crate::primitives::highlighter::HighlightSpan;
+use crate::primitives::path_utils::expand_tilde;
+use crate::services::styled_html::{HtmlDocumentWriter, HtmlExportOptions};
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+
+/// Bytes of buffer content highlighted and written per step, so huge
+/// buffers are streamed to disk instead of being rendered in one piece
+const EXPORT_CHUNK_BYTES: usize = 256 * 1024;
+
+impl Editor {
+    /// Start the prompt for the HTML export destination, pre-filled with the
+    /// buffer's file name plus `.html`.
+    pub fn start_export_html_prompt(&mut self) {
+        let initial = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|path| {
+                let mut name = path.as_os_str().to_os_string();
+                name.push(".html");
+                let path = PathBuf::from(name);
+                path.strip_prefix(&self.working_dir)
+                    .map(Path::to_path_buf)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .unwrap_or_else(|| "export.html".to_string());
+        self.start_prompt_with_initial_text(
+            t!("export.html_prompt").to_string(),
+            PromptType::ExportHtml,
+            initial,
+        );
+    }
+
+    /// Handle the HTML export prompt confirmation.
+    pub fn handle_export_html(&mut self, input: &str) {
+        let input = input.trim();
+        if input.is_empty() {
+            self.set_status_message(t!("export.html_cancelled").to_string());
+            return;
+        }
+
+        let expanded_path = expand_tilde(input);
+        let full_path = if expanded_path.is_absolute() {
+            normalize_path(&expanded_path)
+        } else {
+            normalize_path(&self.working_dir.join(&expanded_path))
+        };
+
+        match self.export_active_buffer_html(&full_path) {
+            Ok(()) => self.set_status_message(
+                t!("export.html_done", path = full_path.display().to_string()).to_string(),
+            ),
+            Err(e) => self.set_important_status_message(
+                t!("export.html_failed", error = e.to_string()).to_string(),
+            ),
+        }
+    }
+
+    /// Copy the selection as HTML styled with the current theme.
+    pub fn copy_as_rich_text(&mut self) {
+        let theme_name = self.theme.name.clone();
+        self.copy_selection_with_theme(&theme_name);
+    }
+
+    /// Write the primary selection, or the whole buffer when nothing is
+    /// selected, to `path` as a standalone HTML document.
+    fn export_active_buffer_html(&mut self, path: &Path) -> io::Result<()> {
+        let options = HtmlExportOptions {
+            line_numbers: self.config.editor.export_html_line_numbers,
+            wrap_width: self.config.editor.export_html_wrap_width,
+        };
+        let context_bytes = self.config.editor.highlight_context_bytes;
+        let theme = self.theme.clone();
+        let title = self
+            .active_state()
+            .buffer
+            .file_path()
+            .and_then(|p| p.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.get_buffer_display_name(self.active_buffer()));
+        let timestamp = chrono::Local::now()
+            .format("%Y-%m-%d %H:%M:%S %z")
+            .to_string();
+        let range = self
+            .active_cursors()
+            .primary()
+            .selection_range()
+            .unwrap_or_else(|| 0..self.active_state().buffer.len());
+
+        let file = self.filesystem.create_file(path)?;
+        let state = self.active_state_mut();
+        let first_line = state.buffer.get_line_number(range.start) + 1;
+        let last_line = state.buffer.get_line_number(range.end) + 1;
+        let mut writer = HtmlDocumentWriter::begin(
+            BufWriter::new(file),
+            &title,
+            &timestamp,
+            &theme,
+            options,
+            first_line,
+            last_line,
+        )?;
+
+        let mut offset = range.start;
+        while offset < range.end {
+            let mut bytes = state
+                .buffer
+                .get_text_range_mut(offset, (range.end - offset).min(EXPORT_CHUNK_BYTES))
+                .map_err(io::Error::other)?;
+            if bytes.is_empty() {
+                break;
+            }
+            if offset + bytes.len() < range.end {
+                // End the chunk on a line break when possible, and never
+                // inside a multi-byte character
+                let cut = match bytes.iter().rposition(|&b| b == b'\n') {
+                    Some(newline) => newline + 1,
+                    None => bytes
+                        .iter()
+                        .rposition(|&b| (b & 0xC0) != 0x80)
+                        .filter(|&start| start > 0)
+                        .unwrap_or(bytes.len()),
+                };
+                bytes.truncate(cut);
+            }
+            let chunk = offset..offset + bytes.len();
+            let text = String::from_utf8_lossy(&bytes);
+
+            let spans = state.highlighter.highlight_viewport(
+                &state.buffer,
+                chunk.start,
+                chunk.end,
+                &theme,
+                context_bytes,
+            );
+            writer.write_chunk(&text, &spans_relative_to(spans, &chunk))?;
+            offset = chunk.end;
+        }
+
+        writer.finish()?.into_inner().map_err(|e| e.into_error())?;
+        Ok(())
+    }
+}
+
+/// Clip highlight spans to `range` and make them relative to its start
+fn spans_relative_to(spans: Vec<HighlightSpan>, range: &Range<usize>) -> Vec<HighlightSpan> {
+    spans
+        .into_iter()
+        .filter_map(|span| {
+            let start = span.range.start.max(range.start);
+            let end = span.range.end.min(range.end);
+            (start < end).then(|| HighlightSpan {
+                range: start - range.start..end - range.start,
+                color: span.color,
+                category: span.category,
+            })
+        })
+        .collect()
+}
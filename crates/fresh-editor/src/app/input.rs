@@ -51,6 +51,11 @@ impl Editor {
             return Ok(());
         }
 
+        // Any key press ends a native terminal selection started with Alt+click
+        if self.mouse_capture_suspended {
+            self.resume_mouse_capture();
+        }
+
         // Try terminal input dispatch first (handles terminal mode and re-entry)
         if self.dispatch_terminal_input(&key_event).is_some() {
             return Ok(());
@@ -240,6 +245,8 @@ impl Editor {
             Action::LspCompletion
             | Action::LspGotoDefinition
             | Action::LspReferences
+            | Action::LspPeekDefinition
+            | Action::LspPeekReferences
             | Action::LspHover
             | Action::None => {
                 // Don't cancel for LSP actions or no-op
@@ -281,12 +288,15 @@ impl Editor {
                         String::new(),
                     );
                     self.init_file_open_state();
+                } else if self.is_read_only(self.active_buffer()) {
+                    self.set_status_message(t!("buffer.read_only_save").to_string());
+                } else if self.prompt_if_file_missing() {
+                    // The file was deleted or moved on disk; the prompt decides
                 } else if self.check_save_conflict().is_some() {
                     // Check if file was modified externally since we opened/saved it
-                    self.start_prompt(
-                        t!("file.file_changed_prompt").to_string(),
-                        PromptType::ConfirmSaveConflict,
-                    );
+                    let message = t!("file.file_changed_prompt").to_string();
+                    self.record_status_message(message.trim_end(), None, true);
+                    self.start_prompt(message, PromptType::ConfirmSaveConflict);
                 } else {
                     self.save()?;
                 }
@@ -317,6 +327,12 @@ impl Editor {
                 self.prefill_open_file_prompt();
                 self.init_file_open_state();
             }
+            Action::RecentFiles => self.open_recent_files_picker(),
+            Action::FuzzyFindFile => self.open_fuzzy_finder(),
+            Action::OpenAsText => self.open_last_external_as_text(),
+            Action::SaveLocalCopy => self.save_local_copy(),
+            Action::WriteCopy => self.start_write_copy_prompt(),
+            Action::RecoveryCheckpoint => self.force_recovery_checkpoint(),
             Action::SwitchProject => {
                 self.start_prompt(
                     t!("file.switch_project_prompt").to_string(),
@@ -329,11 +345,20 @@ impl Editor {
                     .buffers
                     .get(&self.active_buffer())
                     .is_none_or(|s| s.buffer.line_count().is_some());
+                let active_buffer = self.active_buffer();
+                let scan_running = self
+                    .line_scan_state
+                    .as_mut()
+                    .filter(|scan| scan.buffer_id == active_buffer);
                 if has_line_index {
                     self.start_prompt(
                         t!("file.goto_line_prompt").to_string(),
                         PromptType::GotoLine,
                     );
+                } else if let Some(scan) = scan_running {
+                    // Already counting lines (e.g. `editor.large_file_line_scan`):
+                    // open the prompt when the scan completes
+                    scan.open_goto_line_on_complete = true;
                 } else {
                     self.start_prompt(
                         t!("goto.scan_confirm_prompt", yes = "y", no = "N").to_string(),
@@ -371,7 +396,7 @@ impl Editor {
                 } else {
                     // No local changes, just revert
                     if let Err(e) = self.revert_file() {
-                        self.set_status_message(
+                        self.set_important_status_message(
                             t!("error.failed_to_revert", error = e.to_string()).to_string(),
                         );
                     }
@@ -380,9 +405,10 @@ impl Editor {
             Action::ToggleAutoRevert => {
                 self.toggle_auto_revert();
             }
+            Action::CancelRemoteTransfer => self.cancel_remote_transfer(),
             Action::FormatBuffer => {
                 if let Err(e) = self.format_buffer() {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("error.format_failed", error = e.to_string()).to_string(),
                     );
                 }
@@ -395,7 +421,7 @@ impl Editor {
                     self.set_status_message(t!("whitespace.no_trailing").to_string());
                 }
                 Err(e) => {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("error.trim_whitespace_failed", error = e).to_string(),
                     );
                 }
@@ -408,7 +434,7 @@ impl Editor {
                     self.set_status_message(t!("whitespace.already_has_newline").to_string());
                 }
                 Err(e) => {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("error.ensure_newline_failed", error = e).to_string(),
                     );
                 }
@@ -432,9 +458,10 @@ impl Editor {
                         return Ok(());
                     }
                 }
-                self.copy_selection()
+                self.copy_selection_guarded()
             }
             Action::CopyWithTheme(theme) => self.copy_selection_with_theme(&theme),
+            Action::CopyAsRichText => self.copy_as_rich_text(),
             Action::Cut => {
                 if self.is_editing_disabled() {
                     self.set_status_message(t!("buffer.editing_disabled").to_string());
@@ -462,6 +489,7 @@ impl Editor {
             Action::ShowHelp => {
                 self.open_help_manual();
             }
+            Action::OpenTutorial => self.open_tutorial(),
             Action::ShowKeyboardShortcuts => {
                 self.open_keyboard_shortcuts();
             }
@@ -471,6 +499,12 @@ impl Editor {
             Action::ShowStatusLog => {
                 self.open_status_log();
             }
+            Action::ShowMessages => {
+                self.open_messages_buffer();
+            }
+            Action::ToggleMessagesFilter => {
+                self.toggle_messages_filter();
+            }
             Action::ShowLspStatus => {
                 self.show_lsp_status_popup();
             }
@@ -527,22 +561,7 @@ impl Editor {
                 // Start Quick Open with file suggestions (default mode)
                 self.start_quick_open();
             }
-            Action::ToggleLineWrap => {
-                self.config.editor.line_wrap = !self.config.editor.line_wrap;
-
-                // Update all viewports to reflect the new line wrap setting
-                for view_state in self.split_view_states.values_mut() {
-                    view_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
-                    view_state.viewport.wrap_indent = self.config.editor.wrap_indent;
-                }
-
-                let state = if self.config.editor.line_wrap {
-                    t!("view.state_enabled").to_string()
-                } else {
-                    t!("view.state_disabled").to_string()
-                };
-                self.set_status_message(t!("view.line_wrap_state", state = state).to_string());
-            }
+            Action::ToggleLineWrap => self.toggle_line_wrap(),
             Action::ToggleReadOnly => {
                 let buffer_id = self.active_buffer();
                 let is_now_read_only = self
@@ -551,6 +570,10 @@ impl Editor {
                     .map(|m| !m.read_only)
                     .unwrap_or(false);
                 self.mark_buffer_read_only(buffer_id, is_now_read_only);
+                // Under --readonly the choice only lasts for this session
+                if !self.open_read_only {
+                    self.remember_file_override(|o| o.read_only = Some(is_now_read_only));
+                }
 
                 let state_str = if is_now_read_only {
                     t!("view.state_enabled").to_string()
@@ -559,6 +582,7 @@ impl Editor {
                 };
                 self.set_status_message(t!("view.read_only_state", state = state_str).to_string());
             }
+            Action::ClearFileOverrides => self.clear_file_overrides(),
             Action::ToggleComposeMode => {
                 self.handle_toggle_compose_mode();
             }
@@ -615,6 +639,12 @@ impl Editor {
             Action::LspReferences => {
                 self.request_references()?;
             }
+            Action::LspPeekDefinition => {
+                self.peek_definition()?;
+            }
+            Action::LspPeekReferences => {
+                self.peek_references()?;
+            }
             Action::LspSignatureHelp => {
                 self.request_signature_help();
             }
@@ -630,12 +660,18 @@ impl Editor {
             Action::LspToggleForBuffer => {
                 self.handle_lsp_toggle_for_buffer();
             }
+            Action::LspShowEnvironment => {
+                self.show_lsp_environment();
+            }
             Action::ToggleInlayHints => {
                 self.toggle_inlay_hints();
             }
             Action::DumpConfig => {
                 self.dump_config();
             }
+            Action::ManageWorkspaceTrust => {
+                self.manage_workspace_trust();
+            }
             Action::SelectTheme => {
                 self.start_select_theme_prompt();
             }
@@ -689,6 +725,21 @@ impl Editor {
                     false,
                 );
             }
+            Action::ProjectSearch => {
+                self.start_search_prompt(
+                    t!("project_search.prompt").to_string(),
+                    PromptType::ProjectSearch,
+                    false,
+                );
+            }
+            Action::ProjectSearchOpenResult => self.open_project_search_result(),
+            Action::ProjectReplace => {
+                self.start_search_prompt(
+                    t!("project_replace.search_prompt").to_string(),
+                    PromptType::ProjectReplaceSearch,
+                    false,
+                );
+            }
             Action::FindInSelection => {
                 self.start_search_prompt(
                     t!("file.search_prompt").to_string(),
@@ -745,10 +796,18 @@ impl Editor {
             Action::ToggleMenuBar => self.toggle_menu_bar(),
             Action::ToggleTabBar => self.toggle_tab_bar(),
             Action::ToggleStatusBar => self.toggle_status_bar(),
+            Action::ZoomIn => self.zoom_in(),
+            Action::ZoomOut => self.zoom_out(),
             Action::ToggleVerticalScrollbar => self.toggle_vertical_scrollbar(),
             Action::ToggleHorizontalScrollbar => self.toggle_horizontal_scrollbar(),
             Action::ToggleLineNumbers => self.toggle_line_numbers(),
             Action::ToggleScrollSync => self.toggle_scroll_sync(),
+            Action::TogglePrivacyMode => self.toggle_privacy_mode(),
+            Action::DiffBuffers => self.start_diff_buffers(),
+            Action::DiffNextHunk => self.diff_goto_hunk(true),
+            Action::DiffPrevHunk => self.diff_goto_hunk(false),
+            Action::DiffAcceptLeft => self.diff_accept(true),
+            Action::DiffAcceptRight => self.diff_accept(false),
             Action::ToggleMouseCapture => self.toggle_mouse_capture(),
             Action::ToggleMouseHover => self.toggle_mouse_hover(),
             Action::ToggleDebugHighlights => self.toggle_debug_highlights(),
@@ -784,6 +843,7 @@ impl Editor {
             Action::SetLanguage => {
                 self.start_set_language_prompt();
             }
+            Action::ReloadGrammars => self.reload_grammars(),
             Action::ToggleIndentationStyle => {
                 if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
                     state.buffer_settings.use_tabs = !state.buffer_settings.use_tabs;
@@ -796,15 +856,7 @@ impl Editor {
                 }
             }
             Action::ToggleTabIndicators | Action::ToggleWhitespaceIndicators => {
-                if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
-                    state.buffer_settings.whitespace.toggle_all();
-                    let status = if state.buffer_settings.whitespace.any_visible() {
-                        t!("toggle.whitespace_indicators_shown")
-                    } else {
-                        t!("toggle.whitespace_indicators_hidden")
-                    };
-                    self.set_status_message(status.to_string());
-                }
+                self.toggle_whitespace_indicators();
             }
             Action::ResetBufferSettings => self.reset_buffer_settings(),
             Action::FocusFileExplorer => self.focus_file_explorer(),
@@ -825,9 +877,9 @@ impl Editor {
             Action::FileExplorerToggleGitignored => self.file_explorer_toggle_gitignored(),
             Action::FileExplorerSearchClear => self.file_explorer_search_clear(),
             Action::FileExplorerSearchBackspace => self.file_explorer_search_pop_char(),
-            Action::RemoveSecondaryCursors => {
+            action @ (Action::RemoveSecondaryCursors | Action::ClearSecondaryCursors) => {
                 // Convert action to events and apply them
-                if let Some(events) = self.action_to_events(Action::RemoveSecondaryCursors) {
+                if let Some(events) = self.action_to_events(action) {
                     // Wrap in batch for atomic undo
                     let batch = Event::Batch {
                         events: events.clone(),
@@ -846,6 +898,10 @@ impl Editor {
                 }
             }
 
+            Action::RotatePrimaryCursorNext => self.rotate_primary_cursor(true),
+            Action::RotatePrimaryCursorPrev => self.rotate_primary_cursor(false),
+            Action::SelectionInfo => self.show_selection_info(),
+
             // Menu navigation actions
             Action::MenuActivate => {
                 self.handle_menu_activate();
@@ -913,6 +969,18 @@ impl Editor {
             Action::ToggleComment => {
                 self.toggle_comment();
             }
+            Action::ReflowParagraph => self.reflow_paragraph(Some(self.config.editor.wrap_column)),
+            Action::ReflowParagraphToColumn => {
+                self.start_prompt_with_initial_text(
+                    t!("reflow.column_prompt").to_string(),
+                    PromptType::ReflowColumn,
+                    self.config.editor.wrap_column.to_string(),
+                );
+            }
+            Action::UnwrapParagraph => self.reflow_paragraph(None),
+            Action::SortLines => self.sort_selected_lines(false, false, false),
+            Action::SortLinesNumeric => self.sort_selected_lines(true, false, false),
+            Action::SortLinesReverse => self.sort_selected_lines(false, true, false),
             Action::ToggleFold => {
                 self.toggle_fold_at_cursor();
             }
@@ -925,6 +993,9 @@ impl Editor {
             Action::JumpToPreviousError => {
                 self.jump_to_previous_error();
             }
+            Action::FixNextProblem => {
+                self.fix_next_problem()?;
+            }
             Action::SetBookmark(key) => {
                 self.set_bookmark(key);
             }
@@ -937,6 +1008,10 @@ impl Editor {
             Action::ListBookmarks => {
                 self.list_bookmarks();
             }
+            Action::ToggleLineMark => self.toggle_line_mark(),
+            Action::NextLineMark => self.goto_line_mark(true),
+            Action::PrevLineMark => self.goto_line_mark(false),
+            Action::ClearLineMarks => self.clear_line_marks(),
             Action::ToggleSearchCaseSensitive => {
                 self.search_case_sensitive = !self.search_case_sensitive;
                 let state = if self.search_case_sensitive {
@@ -1061,6 +1136,15 @@ impl Editor {
                     self.set_status_message(t!("status.no_macro_recorded").to_string());
                 }
             }
+            Action::PromptSaveMacro => {
+                self.start_prompt(t!("macro.save_prompt").to_string(), PromptType::SaveMacro);
+            }
+            Action::PromptLoadMacro => {
+                self.start_prompt(t!("macro.load_prompt").to_string(), PromptType::LoadMacro);
+            }
+            Action::ListSavedMacros => {
+                self.list_saved_macros_in_buffer();
+            }
             Action::PromptSetBookmark => {
                 self.start_prompt("Set bookmark (0-9): ".to_string(), PromptType::SetBookmark);
             }
@@ -1070,6 +1154,30 @@ impl Editor {
                     PromptType::JumpToBookmark,
                 );
             }
+            Action::BookmarkSet => {
+                let suggestions = self.named_bookmark_suggestions();
+                self.start_prompt_with_suggestions(
+                    "Set bookmark: ".to_string(),
+                    PromptType::SetNamedBookmark,
+                    suggestions,
+                );
+                // The name typed is used, choosing an existing name fills it in
+                if let Some(prompt) = self.prompt.as_mut() {
+                    prompt.selected_suggestion = None;
+                    prompt.sync_input_on_navigate = true;
+                }
+            }
+            Action::BookmarkGoto => {
+                let suggestions = self.named_bookmark_suggestions();
+                self.start_prompt_with_suggestions(
+                    "Go to bookmark: ".to_string(),
+                    PromptType::JumpToNamedBookmark,
+                    suggestions,
+                );
+            }
+            Action::OpenBookmarkPicker => {
+                self.open_bookmark_picker();
+            }
             Action::None => {}
             Action::DeleteBackward => {
                 if self.is_editing_disabled() {
@@ -1160,7 +1268,10 @@ impl Editor {
                             ));
                         }
                         Err(e) => {
-                            self.set_status_message(format!("Failed to load plugin: {}", e));
+                            self.set_important_status_message(format!(
+                                "Failed to load plugin: {}",
+                                e
+                            ));
                             tracing::error!("LoadPluginFromBuffer error: {}", e);
                         }
                     }
@@ -1178,6 +1289,9 @@ impl Editor {
             Action::OpenTerminal => {
                 self.open_terminal();
             }
+            Action::OpenRemoteTerminal => {
+                self.open_remote_terminal();
+            }
             Action::CloseTerminal => {
                 self.close_terminal();
             }
@@ -1229,6 +1343,16 @@ impl Editor {
                 // Run shell command on buffer/selection, replace content
                 self.start_shell_command_prompt(true);
             }
+            Action::Prettify => self.prettify_buffer(),
+            Action::ExportHtml => {
+                self.start_export_html_prompt();
+            }
+            Action::ExportSettingsBundle => {
+                self.start_export_settings_bundle_prompt();
+            }
+            Action::ImportSettingsBundle => {
+                self.start_import_settings_bundle_prompt();
+            }
             Action::OpenSettings => {
                 self.open_settings();
             }
@@ -1285,6 +1409,12 @@ impl Editor {
             Action::EventDebug => {
                 self.open_event_debug();
             }
+            Action::ShowUndoHistorySizes => {
+                self.open_undo_history_sizes();
+            }
+            Action::DiagnoseBufferPerformance => {
+                self.diagnose_buffer_performance();
+            }
             Action::OpenKeybindingEditor => {
                 self.open_keybinding_editor();
             }
@@ -1394,8 +1524,11 @@ impl Editor {
         row: u16,
         delta: i32,
     ) -> AnyhowResult<()> {
+        // Scroll the focused split, or the one under the mouse
+        // (`mouse.scroll_hovered_split`)
+        let (target_split, buffer_id) = self.wheel_target_split(col, row);
+
         // Notify plugins of mouse scroll so they can handle it for virtual buffers
-        let buffer_id = self.active_buffer();
         self.plugin_manager.run_hook(
             "mouse_scroll",
             fresh_core::hooks::HookArgs::MouseScroll {
@@ -1442,10 +1575,9 @@ impl Editor {
             }
         }
 
-        // Otherwise, scroll the editor in the active split
+        // Otherwise, scroll the editor in the target split
         // Use SplitViewState's viewport (View events go to SplitViewState, not EditorState)
-        let active_split = self.split_manager.active_split();
-        let buffer_id = self.active_buffer();
+        let active_split = target_split;
 
         // Check if this is a composite buffer - if so, use composite scroll
         if self.is_composite_buffer(buffer_id) {
@@ -1541,11 +1673,11 @@ impl Editor {
     /// Handle horizontal scroll (Shift+ScrollWheel or native ScrollLeft/ScrollRight)
     pub(super) fn handle_horizontal_scroll(
         &mut self,
-        _col: u16,
-        _row: u16,
+        col: u16,
+        row: u16,
         delta: i32,
     ) -> AnyhowResult<()> {
-        let active_split = self.split_manager.active_split();
+        let (active_split, _) = self.wheel_target_split(col, row);
 
         if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
             // Don't scroll horizontally when line wrap is enabled
@@ -2397,6 +2529,7 @@ impl Editor {
     }
 
     /// Check whether a gutter click at `target_position` should toggle a fold.
+    /// Only lines showing a fold arrow (per `fold_indicators`) are clickable.
     /// Returns `Some(target_position)` (the byte to fold at) or `None`.
     fn fold_toggle_byte_from_position(
         state: &crate::state::EditorState,
@@ -2404,6 +2537,7 @@ impl Editor {
         target_position: usize,
         content_col: u16,
         gutter_width: u16,
+        fold_indicators: crate::config::FoldIndicators,
     ) -> Option<usize> {
         if content_col >= gutter_width {
             return None;
@@ -2414,7 +2548,10 @@ impl Editor {
 
         // Already collapsed → allow toggling (unfold)
         if collapsed_header_bytes.contains_key(&line_start) {
-            return Some(target_position);
+            return fold_indicators.shows(true).then_some(target_position);
+        }
+        if !fold_indicators.shows(false) {
+            return None;
         }
 
         // Check LSP folding ranges first (line-based comparison unavoidable)
@@ -2509,6 +2646,7 @@ impl Editor {
                 target_position,
                 content_col,
                 gutter_width,
+                self.config.editor.fold_indicators,
             ) {
                 return Some((*buffer_id, byte_pos));
             }
@@ -2587,7 +2725,7 @@ impl Editor {
             .and_then(|vs| vs.compose_width);
 
         // Calculate clicked position in buffer
-        let (toggle_fold_byte, onclick_action, target_position, cursor_snapshot) =
+        let (toggle_fold_byte, line_mark_click, onclick_action, target_position, cursor_snapshot) =
             if let Some(state) = self.buffers.get(&buffer_id) {
                 let gutter_width = state.margins.left_total_width() as u16;
 
@@ -2622,7 +2760,12 @@ impl Editor {
                     target_position,
                     content_col,
                     gutter_width,
+                    self.config.editor.fold_indicators,
                 );
+                // The first gutter column holds line indicators; clicking it
+                // (on a line without a fold toggle) sets or clears a line mark.
+                // Clicks on the line number itself keep their usual behavior.
+                let line_mark_click = gutter_width > 0 && content_col == 0;
 
                 let cursor_snapshot = self
                     .split_view_states
@@ -2653,6 +2796,7 @@ impl Editor {
 
                 (
                     toggle_fold_byte,
+                    line_mark_click,
                     onclick_action,
                     target_position,
                     cursor_snapshot,
@@ -2666,6 +2810,11 @@ impl Editor {
             return Ok(());
         }
 
+        if line_mark_click && !self.is_terminal_buffer(buffer_id) {
+            self.toggle_line_mark_at(buffer_id, target_position);
+            return Ok(());
+        }
+
         let (primary_cursor_id, old_position, old_anchor, old_sticky_column, deselect_on_move) =
             cursor_snapshot;
 
@@ -2683,13 +2832,21 @@ impl Editor {
             return Ok(());
         }
 
+        if !self.config.editor.mouse.click_moves_cursor {
+            return Ok(());
+        }
+
+        // Alt+drag selects a rectangle starting at the clicked position
+        let block_selection = modifiers.contains(KeyModifiers::ALT);
+
         // Move cursor to clicked position (respect shift for selection)
         // Both modifiers supported since some terminals intercept shift+click.
-        let extend_selection =
-            modifiers.contains(KeyModifiers::SHIFT) || modifiers.contains(KeyModifiers::CONTROL);
+        let extend_selection = !block_selection
+            && (modifiers.contains(KeyModifiers::SHIFT)
+                || modifiers.contains(KeyModifiers::CONTROL));
         let new_anchor = if extend_selection {
             Some(old_anchor.unwrap_or(old_position))
-        } else if deselect_on_move {
+        } else if deselect_on_move || block_selection {
             None
         } else {
             old_anchor
@@ -2715,11 +2872,15 @@ impl Editor {
         self.active_event_log_mut().append(event.clone());
         self.apply_event_to_active_buffer(&event);
         self.track_cursor_movement(&event);
+        if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+            view_state.cursors.primary_mut().clear_block_selection();
+        }
 
         // Start text selection drag for potential mouse drag
         self.mouse_state.dragging_text_selection = true;
         self.mouse_state.drag_selection_split = Some(split_id);
         self.mouse_state.drag_selection_anchor = Some(new_anchor.unwrap_or(target_position));
+        self.mouse_state.drag_selection_block = block_selection;
 
         Ok(())
     }
@@ -2771,6 +2932,9 @@ impl Editor {
                         // Double-click or Enter will focus the editor
                         let path = node.entry.path.clone();
                         let name = node.entry.name.clone();
+                        if self.try_open_externally(&path) {
+                            return Ok(());
+                        }
                         match self.open_file(&path) {
                             Ok(_) => {
                                 self.set_status_message(
@@ -2784,7 +2948,7 @@ impl Editor {
                                 >() {
                                     self.start_large_file_encoding_confirmation(confirmation);
                                 } else {
-                                    self.set_status_message(
+                                    self.set_important_status_message(
                                         rust_i18n::t!("file.error_opening", error = e.to_string())
                                             .to_string(),
                                     );
@@ -2933,17 +3097,23 @@ impl Editor {
         }
 
         let current_encoding = self.active_state().buffer.encoding();
+        let detected_encoding = self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .and_then(|m| m.detected_encoding);
 
         let suggestions: Vec<crate::input::commands::Suggestion> = Encoding::all()
             .iter()
             .map(|enc| {
                 let is_current = *enc == current_encoding;
+                let is_detected = Some(*enc) == detected_encoding;
                 crate::input::commands::Suggestion {
                     text: format!("{} ({})", enc.display_name(), enc.description()),
-                    description: if is_current {
-                        Some("current".to_string())
-                    } else {
-                        None
+                    description: match (is_current, is_detected) {
+                        (true, true) => Some("current, detected".to_string()),
+                        (true, false) => Some("current".to_string()),
+                        (false, true) => Some("detected".to_string()),
+                        (false, false) => None,
                     },
                     value: Some(enc.display_name().to_string()),
                     disabled: false,
@@ -3494,7 +3664,7 @@ impl Editor {
             self.config.locale = crate::config::LocaleName(Some(locale_name.to_string()));
 
             // Regenerate menus with the new locale
-            self.menus = crate::config::MenuConfig::translated();
+            self.rebuild_menus();
 
             // Refresh command palette commands with new locale
             if let Ok(mut registry) = self.command_registry.write() {
@@ -3666,7 +3836,7 @@ impl Editor {
         // This allows them to press Up to get back to history items
         // Reset history navigation when typing in a prompt
         if let Some(ref prompt) = self.prompt {
-            if let Some(key) = Self::prompt_type_to_history_key(&prompt.prompt_type) {
+            if let Some(key) = Self::prompt_history_key(prompt) {
                 if let Some(history) = self.prompt_histories.get_mut(&key) {
                     history.reset_navigation();
                 }
@@ -3674,6 +3844,7 @@ impl Editor {
         }
 
         if let Some(prompt) = self.prompt_mut() {
+            prompt.browsing_history = false;
             // Use insert_str to properly handle selection deletion
             let s = c.to_string();
             prompt.insert_str(&s);
@@ -189,7 +189,7 @@ impl Editor {
         // Process each deferred action
         for action in ctx.deferred_actions {
             if let Err(e) = self.execute_deferred_action(action) {
-                self.set_status_message(
+                self.set_important_status_message(
                     t!("error.deferred_action", error = e.to_string()).to_string(),
                 );
             }
@@ -329,6 +329,12 @@ impl Editor {
                 self.clipboard.copy(text);
                 self.set_status_message(t!("clipboard.copied").to_string());
             }
+            DeferredAction::PeekScroll(delta) => {
+                self.scroll_peek(delta);
+            }
+            DeferredAction::PeekSelectLocation(delta) => {
+                self.select_peek_location(delta);
+            }
 
             // Generic action execution
             DeferredAction::ExecuteAction(kb_action) => {
@@ -471,21 +477,21 @@ impl Editor {
 
     /// Navigate to previous history entry in prompt.
     fn prompt_history_prev(&mut self) {
-        // Get the prompt type and current input
-        let prompt_info = self
-            .prompt
-            .as_ref()
-            .map(|p| (p.prompt_type.clone(), p.input.clone()));
-
-        if let Some((prompt_type, current_input)) = prompt_info {
-            // Get the history key for this prompt type
-            if let Some(key) = Self::prompt_type_to_history_key(&prompt_type) {
-                if let Some(history) = self.prompt_histories.get_mut(&key) {
-                    if let Some(entry) = history.navigate_prev(&current_input) {
-                        if let Some(ref mut prompt) = self.prompt {
-                            prompt.set_input(entry);
-                        }
-                    }
+        // Get the history key and current input
+        let prompt_info = self.prompt.as_ref().and_then(|p| {
+            Self::prompt_history_key(p).map(|key| (key, p.recalls_commands(), p.input.clone()))
+        });
+
+        if let Some((key, is_command, current_input)) = prompt_info {
+            // Quick Open keeps its `>` prefix out of the shared command history
+            let current_input = if is_command {
+                current_input.trim_start_matches('>').to_string()
+            } else {
+                current_input
+            };
+            if let Some(history) = self.prompt_histories.get_mut(&key) {
+                if let Some(entry) = history.navigate_prev(&current_input) {
+                    self.set_prompt_input_from_history(entry, true);
                 }
             }
         }
@@ -493,21 +499,37 @@ impl Editor {
 
     /// Navigate to next history entry in prompt.
     fn prompt_history_next(&mut self) {
-        let prompt_type = self.prompt.as_ref().map(|p| p.prompt_type.clone());
-
-        if let Some(prompt_type) = prompt_type {
-            // Get the history key for this prompt type
-            if let Some(key) = Self::prompt_type_to_history_key(&prompt_type) {
-                if let Some(history) = self.prompt_histories.get_mut(&key) {
-                    if let Some(entry) = history.navigate_next() {
-                        if let Some(ref mut prompt) = self.prompt {
-                            prompt.set_input(entry);
-                        }
-                    }
+        let key = self.prompt.as_ref().and_then(Self::prompt_history_key);
+
+        if let Some(key) = key {
+            if let Some(history) = self.prompt_histories.get_mut(&key) {
+                if let Some(entry) = history.navigate_next() {
+                    // Past the most recent entry navigation ends with the
+                    // original input restored
+                    let still_browsing = history.is_navigating();
+                    self.set_prompt_input_from_history(entry, still_browsing);
                 }
             }
         }
     }
+
+    /// Put a history entry into the prompt. A recalled command also filters
+    /// the palette down to it, so confirming runs it.
+    fn set_prompt_input_from_history(&mut self, entry: String, browsing: bool) {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+        let is_command = prompt.recalls_commands();
+        if is_command && prompt.prompt_type == crate::view::prompt::PromptType::QuickOpen {
+            prompt.set_input(format!(">{}", entry));
+        } else {
+            prompt.set_input(entry);
+        }
+        prompt.browsing_history = browsing;
+        if is_command {
+            self.update_prompt_suggestions();
+        }
+    }
 }
 
 #[cfg(test)]
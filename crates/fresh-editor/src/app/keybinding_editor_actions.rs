@@ -81,7 +81,10 @@ impl Editor {
         let config_value = match serde_json::to_value(&self.config.keybindings) {
             Ok(v) => v,
             Err(e) => {
-                self.set_status_message(format!("Failed to serialize keybindings: {}", e));
+                self.set_important_status_message(format!(
+                    "Failed to serialize keybindings: {}",
+                    e
+                ));
                 return;
             }
         };
@@ -103,7 +106,7 @@ impl Editor {
                 self.set_status_message("Keybinding changes saved".to_string());
             }
             Err(e) => {
-                self.set_status_message(format!("Failed to save keybindings: {}", e));
+                self.set_important_status_message(format!("Failed to save keybindings: {}", e));
             }
         }
     }
@@ -0,0 +1,175 @@
+//! Anonymous line marks.
+//!
+//! Quick unnamed marks, set by clicking the gutter indicator column or with
+//! Toggle Line Mark, and cycled with Next/Previous Line Mark. Unlike the
+//! char-register bookmarks they belong to a buffer: each mark is a line
+//! indicator in the buffer's margins, so it is anchored by a marker and
+//! follows edits. Marks are saved per file with the rest of the session.
+
+use super::Editor;
+use crate::model::event::{BufferId, Event};
+use crate::state::EditorState;
+use crate::view::margin::LineIndicator;
+use ratatui::style::Color;
+use rust_i18n::t;
+
+/// Margin namespace holding line marks
+pub(super) const LINE_MARK_NAMESPACE: &str = "line-mark";
+
+/// Gutter glyph for a marked line
+pub(super) const LINE_MARK_SYMBOL: &str = "◆";
+
+/// Drawn over git gutter (10) and diff-since-save (5) indicators
+const LINE_MARK_PRIORITY: i32 = 20;
+
+impl Editor {
+    /// Set or clear the line mark on the primary cursor's line
+    pub fn toggle_line_mark(&mut self) {
+        let buffer_id = self.active_buffer();
+        let position = self.active_cursors().primary().position;
+        self.toggle_line_mark_at(buffer_id, position);
+    }
+
+    /// Set or clear the line mark on the line containing `byte_pos`
+    pub(super) fn toggle_line_mark_at(&mut self, buffer_id: BufferId, byte_pos: usize) {
+        let color = self.theme.diagnostic_info_fg;
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let line = state.buffer.get_line_number(byte_pos);
+        let on_line: Vec<_> = state
+            .margins
+            .line_indicator_positions(LINE_MARK_NAMESPACE)
+            .into_iter()
+            .filter(|&(_, pos)| state.buffer.get_line_number(pos) == line)
+            .collect();
+
+        let message = if on_line.is_empty() {
+            let line_start = state.buffer.line_start_offset(line).unwrap_or(byte_pos);
+            add_line_mark(state, line_start, color);
+            t!("line_mark.set", line = line + 1)
+        } else {
+            for (marker_id, _) in on_line {
+                state
+                    .margins
+                    .remove_line_indicator(marker_id, LINE_MARK_NAMESPACE);
+            }
+            t!("line_mark.removed", line = line + 1)
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Move the cursor to the next (or previous) marked line, wrapping
+    /// around the buffer
+    pub fn goto_line_mark(&mut self, forward: bool) {
+        let cursor = *self.active_cursors().primary();
+        let cursor_id = self.active_cursors().primary_id();
+        let state = self.active_state();
+        let current_line = state.buffer.get_line_number(cursor.position);
+        let marks: Vec<(usize, usize)> = state
+            .margins
+            .line_indicator_positions(LINE_MARK_NAMESPACE)
+            .into_iter()
+            .map(|(_, pos)| (state.buffer.get_line_number(pos), pos))
+            .collect();
+
+        let target = if forward {
+            marks
+                .iter()
+                .find(|&&(line, _)| line > current_line)
+                .or(marks.first())
+        } else {
+            marks
+                .iter()
+                .rev()
+                .find(|&&(line, _)| line < current_line)
+                .or(marks.last())
+        };
+        let Some(&(line, new_position)) = target else {
+            self.set_status_message(t!("line_mark.none").to_string());
+            return;
+        };
+
+        let event = Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position,
+            old_anchor: cursor.anchor,
+            new_anchor: None,
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: 0,
+        };
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+
+        let active_split = self.split_manager.active_split();
+        let active_buffer = self.active_buffer();
+        if let (Some(view_state), Some(state)) = (
+            self.split_view_states.get_mut(&active_split),
+            self.buffers.get_mut(&active_buffer),
+        ) {
+            view_state.ensure_cursor_visible(&mut state.buffer, &state.marker_list);
+        }
+
+        let index = marks.iter().position(|&(l, _)| l == line).unwrap_or(0);
+        self.set_status_message(
+            t!(
+                "line_mark.jumped",
+                line = line + 1,
+                current = index + 1,
+                total = marks.len()
+            )
+            .to_string(),
+        );
+    }
+
+    /// Remove every line mark in the active buffer
+    pub fn clear_line_marks(&mut self) {
+        let count = line_mark_lines(self.active_state()).len();
+        self.active_state_mut()
+            .margins
+            .clear_line_indicators_for_namespace(LINE_MARK_NAMESPACE);
+        self.set_status_message(t!("line_mark.cleared", count = count).to_string());
+    }
+
+    /// Restore saved line marks (0-indexed line numbers) into a buffer
+    pub(super) fn restore_line_marks(&mut self, buffer_id: BufferId, lines: &[usize]) {
+        let color = self.theme.diagnostic_info_fg;
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            restore_line_marks(state, lines, color);
+        }
+    }
+}
+
+/// Sorted, de-duplicated 0-indexed line numbers of the marks in a buffer
+pub(super) fn line_mark_lines(state: &EditorState) -> Vec<usize> {
+    let mut lines: Vec<usize> = state
+        .margins
+        .line_indicator_positions(LINE_MARK_NAMESPACE)
+        .into_iter()
+        .map(|(_, pos)| state.buffer.get_line_number(pos))
+        .collect();
+    lines.dedup();
+    lines
+}
+
+/// Replace a buffer's line marks with marks on `lines`. Lines past the end of
+/// the buffer (the file shrank since the session was saved) are dropped.
+pub(super) fn restore_line_marks(state: &mut EditorState, lines: &[usize], color: Color) {
+    state
+        .margins
+        .clear_line_indicators_for_namespace(LINE_MARK_NAMESPACE);
+    for &line in lines {
+        if let Some(line_start) = state.buffer.line_start_offset(line) {
+            add_line_mark(state, line_start, color);
+        }
+    }
+}
+
+fn add_line_mark(state: &mut EditorState, line_start: usize, color: Color) {
+    state.margins.set_line_indicator(
+        line_start,
+        LINE_MARK_NAMESPACE.to_string(),
+        LineIndicator::new(LINE_MARK_SYMBOL, color, LINE_MARK_PRIORITY),
+    );
+}
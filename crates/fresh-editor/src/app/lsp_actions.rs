@@ -41,7 +41,7 @@ impl Editor {
         };
 
         let (success, message) = lsp.manual_restart(&language);
-        self.status_message = Some(message);
+        self.show_status_message(message);
 
         if !success {
             return;
@@ -166,6 +166,85 @@ impl Editor {
         }
     }
 
+    /// Show the current language's LSP server command, where it resolves on
+    /// the PATH the server will see, and the variables it sees differently
+    /// from the editor.
+    pub fn show_lsp_environment(&mut self) {
+        use crate::config::ShellEnvMode;
+        use crate::view::popup::{Popup, PopupPosition};
+        use ratatui::style::Style;
+
+        let buffer_id = self.active_buffer();
+        let Some(language) = self.buffers.get(&buffer_id).map(|s| s.language.clone()) else {
+            return;
+        };
+        let Some(config) = self
+            .lsp
+            .as_ref()
+            .and_then(|lsp| lsp.get_config(&language))
+            .filter(|c| !c.command.is_empty())
+            .cloned()
+        else {
+            self.set_status_message(t!("lsp.no_server_configured").to_string());
+            return;
+        };
+
+        let process_env = crate::services::shell_env::current();
+        let mut md = String::new();
+        let command_line = std::iter::once(config.command.as_str())
+            .chain(config.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+        md.push_str(&format!(
+            "**{}:** `{}`\n\n",
+            t!("lsp.env_command"),
+            command_line
+        ));
+        let resolved = match process_env.find_command(&config.command, &config.env) {
+            Some(path) => format!("`{}`", path.display()),
+            None => format!("*{}*", t!("lsp.env_not_found")),
+        };
+        md.push_str(&format!("**{}:** {}\n\n", t!("lsp.env_resolved"), resolved));
+
+        let mode = match process_env.mode() {
+            ShellEnvMode::Inherit => t!("lsp.env_mode_inherit"),
+            ShellEnvMode::LoginShell => t!("lsp.env_mode_login_shell"),
+            ShellEnvMode::Explicit => t!("lsp.env_mode_explicit"),
+        };
+        md.push_str(&format!("**{}:** {}\n\n", t!("lsp.env_mode"), mode));
+        if let Some(error) = process_env.error() {
+            md.push_str(&format!(
+                "*{}*\n\n",
+                t!("lsp.env_login_shell_failed", error = error)
+            ));
+        }
+
+        md.push_str(&format!("**{}:**\n\n", t!("lsp.env_diff")));
+        let diff = process_env.diff(&config.env);
+        if diff.is_empty() {
+            md.push_str(&format!("{}\n", t!("lsp.env_no_diff")));
+        }
+        for (name, own, value) in diff {
+            let own = own.map_or_else(
+                || format!("*{}*", t!("lsp.env_unset")),
+                |v| format!("`{v}`"),
+            );
+            md.push_str(&format!("- `{}`: {} → `{}`\n", name, own, value));
+        }
+
+        let mut popup = Popup::markdown(&md, &self.theme, Some(&self.grammar_registry));
+        popup.title = Some(t!("lsp.env_title", language = language).to_string());
+        popup.transient = false;
+        popup.position = PopupPosition::Centered;
+        popup.width = 80;
+        popup.max_height = 20;
+        popup.border_style = Style::default().fg(self.theme.popup_border_fg);
+        popup.background_style = Style::default().bg(self.theme.popup_bg);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.popups.show(popup);
+        }
+    }
+
     /// Handle the LspToggleForBuffer action.
     ///
     /// Toggles LSP on/off for the current buffer only.
@@ -0,0 +1,244 @@
+//! Downloading missing language servers.
+//!
+//! When a server's command isn't installed and its language has an
+//! `lsp_downloads` entry, Fresh asks whether to download it. The download
+//! runs in the background with progress in the status bar, is checked
+//! against the pinned SHA-256 and installed under the data directory, and
+//! the server is then started from there. Servers installed this way are
+//! picked up again in later sessions without asking. Declining, or setting
+//! `allow_network_fetch` to false, keeps the usual "not found" error.
+
+use std::path::{Path, PathBuf};
+
+use super::file_open::format_size;
+use super::Editor;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::lsp::download;
+use crate::services::url_fetch;
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+
+/// Bytes received between progress updates in the status bar
+const PROGRESS_STEP: u64 = 1024 * 1024;
+
+impl Editor {
+    /// Command `language`'s server is configured with, before any downloaded
+    /// binary was substituted for it
+    fn configured_lsp_command(&self, language: &str) -> Option<String> {
+        self.config
+            .lsp
+            .get(language)
+            .or_else(|| self.lsp.as_ref()?.get_config(language))
+            .map(|config| config.command.clone())
+    }
+
+    /// Start `language`'s server from a binary downloaded in an earlier
+    /// session, if there is one and it isn't already the one that failed
+    pub(super) fn use_downloaded_lsp_server(&mut self, language: &str) -> bool {
+        let Some(download) = self.config.lsp_downloads.get(language) else {
+            return false;
+        };
+        let Some(command) = self.configured_lsp_command(language) else {
+            return false;
+        };
+        let Some(path) =
+            download::installed_binary(&self.dir_context.data_dir, language, &command, download)
+        else {
+            return false;
+        };
+        let already_used = self
+            .lsp
+            .as_ref()
+            .and_then(|lsp| lsp.get_config(language))
+            .is_some_and(|config| Path::new(&config.command) == path);
+        if already_used {
+            return false;
+        }
+
+        tracing::info!(
+            "{} not found, using downloaded {} server at {:?}",
+            command,
+            language,
+            path
+        );
+        self.start_lsp_server_at(language, &path);
+        true
+    }
+
+    /// Ask whether to download `language`'s missing server, if it has an
+    /// `lsp_downloads` entry and the user hasn't already said no
+    pub(super) fn offer_lsp_download(&mut self, language: &str) {
+        let Some(download) = self.config.lsp_downloads.get(language) else {
+            return;
+        };
+        if !self.config.allow_network_fetch
+            || self.lsp_downloads_declined.contains(language)
+            || self.lsp_downloads_in_progress.contains(language)
+            || self.prompt.is_some()
+        {
+            return;
+        }
+        let host = download::url_host(&download.url).to_string();
+        let command = self
+            .configured_lsp_command(language)
+            .unwrap_or_else(|| language.to_string());
+        self.start_prompt(
+            t!(
+                "lsp.download_confirm",
+                language = language,
+                command = command,
+                host = host
+            )
+            .to_string(),
+            PromptType::ConfirmLspDownload {
+                language: language.to_string(),
+            },
+        );
+    }
+
+    /// Don't offer `language`'s server again this session
+    pub(super) fn decline_lsp_download(&mut self, language: &str) {
+        self.lsp_downloads_declined.insert(language.to_string());
+        self.set_status_message(t!("lsp.download_declined", language = language).to_string());
+    }
+
+    /// Download, verify and install `language`'s server in the background
+    pub(super) fn start_lsp_download(&mut self, language: &str) {
+        let Some(download) = self.config.lsp_downloads.get(language).cloned() else {
+            return;
+        };
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            self.set_important_status_message(
+                t!(
+                    "lsp.download_failed",
+                    language = language,
+                    error = "async runtime not available"
+                )
+                .to_string(),
+            );
+            return;
+        };
+        if !self.lsp_downloads_in_progress.insert(language.to_string()) {
+            return;
+        }
+
+        let sender = bridge.sender();
+        let language_owned = language.to_string();
+        let command = self
+            .configured_lsp_command(language)
+            .unwrap_or_else(|| language.to_string());
+        let data_dir = self.dir_context.data_dir.clone();
+        let host = download::url_host(&download.url).to_string();
+        tracing::info!(
+            "Downloading {} language server from {}",
+            language,
+            download.url
+        );
+        runtime.spawn_blocking(move || {
+            let language = language_owned;
+            let mut last_reported = 0;
+            let result = url_fetch::fetch_url_with_limits(
+                &download.url,
+                download::MAX_SERVER_BYTES,
+                download::DOWNLOAD_TIMEOUT,
+                |received, total| {
+                    if received - last_reported < PROGRESS_STEP {
+                        return;
+                    }
+                    last_reported = received;
+                    // Receiver may be dropped if editor is shutting down
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = sender.send(AsyncMessage::LspDownloadProgress {
+                        language: language.clone(),
+                        received,
+                        total,
+                    });
+                },
+            )
+            .and_then(|fetched| {
+                download::install(&data_dir, &language, &command, &download, &fetched.content)
+            });
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = sender.send(AsyncMessage::LspDownloaded { language, result });
+        });
+
+        self.set_status_message(
+            t!("lsp.downloading", language = language, host = host).to_string(),
+        );
+    }
+
+    /// Show how far along a server download is
+    pub(super) fn handle_lsp_download_progress(
+        &mut self,
+        language: &str,
+        received: u64,
+        total: Option<u64>,
+    ) {
+        let message = match total {
+            Some(total) => t!(
+                "lsp.download_progress_of",
+                language = language,
+                received = format_size(received),
+                total = format_size(total)
+            ),
+            None => t!(
+                "lsp.download_progress",
+                language = language,
+                received = format_size(received)
+            ),
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Start the server from a finished download, or report why it failed
+    pub(super) fn handle_lsp_downloaded(
+        &mut self,
+        language: &str,
+        result: Result<PathBuf, String>,
+    ) {
+        self.lsp_downloads_in_progress.remove(language);
+        match result {
+            Ok(path) => {
+                self.start_lsp_server_at(language, &path);
+                self.set_status_message(
+                    t!(
+                        "lsp.download_installed",
+                        language = language,
+                        path = path.display().to_string()
+                    )
+                    .to_string(),
+                );
+            }
+            Err(error) => {
+                tracing::warn!("Failed to download {} language server: {}", language, error);
+                self.set_important_status_message(
+                    t!("lsp.download_failed", language = language, error = error).to_string(),
+                );
+            }
+        }
+    }
+
+    /// Point `language`'s server config at `path` and (re)start it. Only the
+    /// running LSP manager is updated, so the user's config keeps the plain
+    /// command.
+    fn start_lsp_server_at(&mut self, language: &str, path: &Path) {
+        let Some(lsp) = self.lsp.as_mut() else {
+            return;
+        };
+        let Some(mut config) = self
+            .config
+            .lsp
+            .get(language)
+            .or_else(|| lsp.get_config(language))
+            .cloned()
+        else {
+            return;
+        };
+        config.command = path.to_string_lossy().into_owned();
+        lsp.set_language_config(language.to_string(), config);
+        let (success, _) = lsp.manual_restart(language);
+        if success {
+            self.reopen_buffers_for_language(language);
+        }
+    }
+}
@@ -21,6 +21,7 @@ use crate::model::event::{BufferId, Event};
 use crate::primitives::word_navigation::{find_word_end, find_word_start};
 use crate::view::prompt::{Prompt, PromptType};
 
+use super::peek::PeekKind;
 use super::{uri_to_path, Editor, SemanticTokenRangeRequest};
 
 /// Ensure every line in a docstring is separated by a blank line.
@@ -145,8 +146,13 @@ impl Editor {
 
         self.pending_goto_definition_request = None;
 
+        if self.take_pending_peek(PeekKind::Definition) {
+            self.show_peek(PeekKind::Definition, locations);
+            return Ok(());
+        }
+
         if locations.is_empty() {
-            self.status_message = Some(t!("lsp.no_definition").to_string());
+            self.show_status_message(t!("lsp.no_definition").to_string());
             return Ok(());
         }
 
@@ -165,7 +171,7 @@ impl Editor {
                     {
                         self.start_large_file_encoding_confirmation(confirmation);
                     } else {
-                        self.set_status_message(
+                        self.set_important_status_message(
                             t!("file.error_opening", error = e.to_string()).to_string(),
                         );
                     }
@@ -212,7 +218,7 @@ impl Editor {
                 }
             }
 
-            self.status_message = Some(
+            self.show_status_message(
                 t!(
                     "lsp.jumped_to_definition",
                     path = path.display().to_string(),
@@ -221,7 +227,7 @@ impl Editor {
                 .to_string(),
             );
         } else {
-            self.status_message = Some(t!("lsp.cannot_open_definition").to_string());
+            self.show_status_message(t!("lsp.cannot_open_definition").to_string());
         }
 
         Ok(())
@@ -447,6 +453,8 @@ impl Editor {
 
     /// Request LSP go-to-definition at current cursor position
     pub(crate) fn request_goto_definition(&mut self) -> AnyhowResult<()> {
+        self.take_pending_peek(PeekKind::Definition);
+
         // Get the current buffer and cursor position
         let cursor_pos = self.active_cursors().primary().position;
         let state = self.active_state();
@@ -786,6 +794,8 @@ impl Editor {
 
     /// Request LSP find references at current cursor position
     pub(crate) fn request_references(&mut self) -> AnyhowResult<()> {
+        self.take_pending_peek(PeekKind::References);
+
         // Get the current buffer and cursor position
         let cursor_pos = self.active_cursors().primary().position;
         let state = self.active_state();
@@ -795,7 +805,7 @@ impl Editor {
             let text = match state.buffer.to_string() {
                 Some(t) => t,
                 None => {
-                    self.set_status_message(t!("error.buffer_not_loaded").to_string());
+                    self.set_important_status_message(t!("error.buffer_not_loaded").to_string());
                     return Ok(());
                 }
             };
@@ -1036,6 +1046,8 @@ impl Editor {
 
     /// Request LSP code actions at current cursor position
     pub(crate) fn request_code_actions(&mut self) -> AnyhowResult<()> {
+        self.pending_fix_next_problem = false;
+
         // Get the current buffer and cursor position
         let cursor_pos = self.active_cursors().primary().position;
         let selection_range = self.active_cursors().primary().selection_range();
@@ -1053,10 +1065,28 @@ impl Editor {
             (line as u32, character as u32, line as u32, character as u32)
         };
 
-        // Get diagnostics at cursor position for context
-        // TODO: Implement diagnostic retrieval when needed
-        let diagnostics: Vec<lsp_types::Diagnostic> = Vec::new();
+        // Pass the diagnostics touching the requested range as context, so
+        // servers can offer quick fixes for them
         let buffer_id = self.active_buffer();
+        let diagnostics: Vec<lsp_types::Diagnostic> = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_uri())
+            .and_then(|uri| self.stored_diagnostics.get(uri.as_str()))
+            .map(|diags| {
+                let start = (start_line, start_char);
+                let end = (end_line, end_char);
+                diags
+                    .iter()
+                    .filter(|d| {
+                        let d_start = (d.range.start.line, d.range.start.character);
+                        let d_end = (d.range.end.line, d.range.end.character);
+                        d_start <= end && start <= d_end
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
         let request_id = self.next_lsp_request_id;
 
         // Use helper to ensure didOpen is sent before the request
@@ -1095,6 +1125,26 @@ impl Editor {
         Ok(())
     }
 
+    /// Jump to the next diagnostic and request quick fixes for it.
+    ///
+    /// The response is handled by [`Self::handle_code_actions_response`],
+    /// which applies a single quick fix directly (when
+    /// `editor.fix_next_problem_auto_apply` is set) or shows the menu.
+    pub(crate) fn fix_next_problem(&mut self) -> AnyhowResult<()> {
+        if !self.jump_to_next_error() {
+            return Ok(());
+        }
+
+        self.request_code_actions()?;
+        if self.pending_code_actions_request.is_some() {
+            self.pending_fix_next_problem = true;
+        } else {
+            self.set_status_message(t!("lsp.no_quick_fix").to_string());
+        }
+
+        Ok(())
+    }
+
     /// Handle code actions response from LSP
     pub(crate) fn handle_code_actions_response(
         &mut self,
@@ -1110,6 +1160,15 @@ impl Editor {
         self.pending_code_actions_request = None;
         self.update_lsp_status_from_server_statuses();
 
+        let actions = if std::mem::take(&mut self.pending_fix_next_problem) {
+            match self.apply_single_quick_fix(actions) {
+                Some(quick_fixes) => quick_fixes,
+                None => return,
+            }
+        } else {
+            actions
+        };
+
         if actions.is_empty() {
             self.set_status_message(t!("lsp.no_code_actions").to_string());
             return;
@@ -1156,6 +1215,78 @@ impl Editor {
         );
     }
 
+    /// Narrow a code action response for "Fix Next Problem" down to its quick
+    /// fixes and apply the fix directly when it is the only one.
+    ///
+    /// Returns the quick fixes still to be shown in the menu, or `None` when
+    /// the response has been fully handled.
+    fn apply_single_quick_fix(
+        &mut self,
+        actions: Vec<lsp_types::CodeActionOrCommand>,
+    ) -> Option<Vec<lsp_types::CodeActionOrCommand>> {
+        let quick_fixes: Vec<lsp_types::CodeActionOrCommand> = actions
+            .into_iter()
+            .filter(|action| match action {
+                lsp_types::CodeActionOrCommand::CodeAction(ca) => ca
+                    .kind
+                    .as_ref()
+                    .is_none_or(|kind| kind.as_str().starts_with("quickfix")),
+                lsp_types::CodeActionOrCommand::Command(_) => false,
+            })
+            .collect();
+
+        if quick_fixes.is_empty() {
+            self.set_status_message(t!("lsp.no_quick_fix").to_string());
+            return None;
+        }
+
+        if quick_fixes.len() == 1 && self.config.editor.fix_next_problem_auto_apply {
+            if let lsp_types::CodeActionOrCommand::CodeAction(ca) = &quick_fixes[0] {
+                if ca.edit.is_some() || ca.command.is_some() {
+                    let title = ca.title.clone();
+                    let command = ca.command.clone();
+                    let buffer_id = self.active_buffer();
+                    // The edit is applied first, then the command runs
+                    let applied = match ca.edit.clone().map(|edit| self.apply_workspace_edit(edit))
+                    {
+                        None | Some(Ok(Some(_))) => true,
+                        Some(Ok(None)) => false,
+                        Some(Err(e)) => {
+                            self.set_important_status_message(
+                                t!("lsp.quick_fix_failed", error = e.to_string()).to_string(),
+                            );
+                            false
+                        }
+                    };
+                    if applied {
+                        if let Some(command) = command {
+                            self.execute_lsp_command(buffer_id, command);
+                        }
+                        self.set_status_message(
+                            t!("lsp.quick_fix_applied", title = title).to_string(),
+                        );
+                    }
+                    return None;
+                }
+            }
+        }
+
+        Some(quick_fixes)
+    }
+
+    /// Run a code action's command on the language server of `buffer_id`
+    fn execute_lsp_command(&mut self, buffer_id: BufferId, command: lsp_types::Command) {
+        let name = command.command.clone();
+        let sent = self
+            .with_lsp_for_buffer(buffer_id, |handle, _uri, _language| {
+                handle.execute_command(command).is_ok()
+            })
+            .unwrap_or(false);
+        if !sent {
+            tracing::warn!("Could not send command {} to the language server", name);
+        }
+    }
+
     /// Handle find references response from LSP
     pub(crate) fn handle_references_response(
         &mut self,
@@ -1177,6 +1308,12 @@ impl Editor {
         self.pending_references_request = None;
         self.update_lsp_status_from_server_statuses();
 
+        if self.take_pending_peek(PeekKind::References) {
+            self.pending_references_symbol.clear();
+            self.show_peek(PeekKind::References, locations);
+            return Ok(());
+        }
+
         if locations.is_empty() {
             self.set_status_message(t!("lsp.no_references").to_string());
             return Ok(());
@@ -1246,6 +1383,40 @@ impl Editor {
                 .then(b.range.start.character.cmp(&a.range.start.character))
         });
 
+        // A single edit replacing the whole document (how some servers format)
+        // would only shift cursors by offset; remember the old text and views so
+        // they can follow their lines instead
+        let full_document_text = match edits.as_slice() {
+            [edit] => self.buffers.get(&buffer_id).and_then(|state| {
+                let start = state.buffer.lsp_position_to_byte(
+                    edit.range.start.line as usize,
+                    edit.range.start.character as usize,
+                );
+                let end = state.buffer.lsp_position_to_byte(
+                    edit.range.end.line as usize,
+                    edit.range.end.character as usize,
+                );
+                if start == 0 && end >= state.buffer.len() {
+                    state.buffer.to_string()
+                } else {
+                    None
+                }
+            }),
+            _ => None,
+        };
+        let saved_views: Vec<_> = if full_document_text.is_some() {
+            self.split_view_states
+                .iter()
+                .filter_map(|(split_id, vs)| {
+                    vs.keyed_states
+                        .get(&buffer_id)
+                        .map(|bs| (*split_id, bs.cursors.clone(), bs.viewport.top_byte))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         // Collect all events for this buffer into a batch
         let mut batch_events = Vec::new();
         let mut changes = 0;
@@ -1329,9 +1500,143 @@ impl Editor {
             )?;
         }
 
+        if let Some(old_text) = full_document_text {
+            for (split_id, cursors, top_byte) in saved_views {
+                if let Some(bs) = self
+                    .split_view_states
+                    .get_mut(&split_id)
+                    .and_then(|vs| vs.keyed_states.get_mut(&buffer_id))
+                {
+                    bs.cursors = cursors;
+                    bs.viewport.top_byte = top_byte;
+                }
+            }
+            let new_text = self
+                .buffers
+                .get(&buffer_id)
+                .and_then(|state| state.buffer.to_string());
+            self.relocate_views_by_content(buffer_id, Some(&old_text), new_text.as_deref());
+        }
+
         Ok(changes)
     }
 
+    /// Apply a `WorkspaceEdit` (from a rename or code action), opening the
+    /// affected files as needed.
+    ///
+    /// Returns the number of edits applied, or `None` if a file could not be
+    /// opened (the reason is already shown in the status bar).
+    pub(crate) fn apply_workspace_edit(
+        &mut self,
+        workspace_edit: lsp_types::WorkspaceEdit,
+    ) -> AnyhowResult<Option<usize>> {
+        let mut total_changes = 0;
+
+        // Handle changes (map of URI -> Vec<TextEdit>)
+        if let Some(changes) = workspace_edit.changes {
+            for (uri, edits) in changes {
+                if let Ok(path) = uri_to_path(&uri) {
+                    let buffer_id = match self.open_file(&path) {
+                        Ok(id) => id,
+                        Err(e) => {
+                            // Check if this is a large file encoding confirmation error
+                            if let Some(confirmation) = e.downcast_ref::<
+                                crate::model::buffer::LargeFileEncodingConfirmation,
+                            >() {
+                                self.start_large_file_encoding_confirmation(confirmation);
+                            } else {
+                                self.set_important_status_message(
+                                    t!("file.error_opening", error = e.to_string())
+                                        .to_string(),
+                                );
+                            }
+                            return Ok(None);
+                        }
+                    };
+                    total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
+                }
+            }
+        }
+
+        // Handle document_changes (TextDocumentEdit[])
+        // This is what rust-analyzer sends instead of changes
+        if let Some(document_changes) = workspace_edit.document_changes {
+            use lsp_types::DocumentChanges;
+
+            let text_edits = match document_changes {
+                DocumentChanges::Edits(edits) => edits,
+                DocumentChanges::Operations(ops) => {
+                    // Extract TextDocumentEdit from operations
+                    ops.into_iter()
+                        .filter_map(|op| {
+                            if let lsp_types::DocumentChangeOperation::Edit(edit) = op {
+                                Some(edit)
+                            } else {
+                                None
+                            }
+                        })
+                        .collect()
+                }
+            };
+
+            for text_doc_edit in text_edits {
+                let uri = text_doc_edit.text_document.uri;
+
+                if let Ok(path) = uri_to_path(&uri) {
+                    let buffer_id = match self.open_file(&path) {
+                        Ok(id) => id,
+                        Err(e) => {
+                            // Check if this is a large file encoding confirmation error
+                            if let Some(confirmation) = e.downcast_ref::<
+                                crate::model::buffer::LargeFileEncodingConfirmation,
+                            >() {
+                                self.start_large_file_encoding_confirmation(confirmation);
+                            } else {
+                                self.set_important_status_message(
+                                    t!("file.error_opening", error = e.to_string())
+                                        .to_string(),
+                                );
+                            }
+                            return Ok(None);
+                        }
+                    };
+
+                    // Extract TextEdit from OneOf<TextEdit, AnnotatedTextEdit>
+                    let edits: Vec<lsp_types::TextEdit> = text_doc_edit
+                        .edits
+                        .into_iter()
+                        .map(|one_of| match one_of {
+                            lsp_types::OneOf::Left(text_edit) => text_edit,
+                            lsp_types::OneOf::Right(annotated) => annotated.text_edit,
+                        })
+                        .collect();
+
+                    // Log the edits for debugging
+                    tracing::info!(
+                        "Applying {} edits from rust-analyzer for {:?}:",
+                        edits.len(),
+                        path
+                    );
+                    for (i, edit) in edits.iter().enumerate() {
+                        tracing::info!(
+                            "  Edit {}: line {}:{}-{}:{} -> {:?}",
+                            i,
+                            edit.range.start.line,
+                            edit.range.start.character,
+                            edit.range.end.line,
+                            edit.range.end.character,
+                            edit.new_text
+                        );
+                    }
+
+                    total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
+                }
+            }
+        }
+
+        Ok(Some(total_changes))
+    }
+
     /// Handle rename response from LSP
     pub fn handle_rename_response(
         &mut self,
@@ -1353,112 +1658,11 @@ impl Editor {
                     })
                 );
 
-                // Apply the workspace edit
-                let mut total_changes = 0;
-
-                // Handle changes (map of URI -> Vec<TextEdit>)
-                if let Some(changes) = workspace_edit.changes {
-                    for (uri, edits) in changes {
-                        if let Ok(path) = uri_to_path(&uri) {
-                            let buffer_id = match self.open_file(&path) {
-                                Ok(id) => id,
-                                Err(e) => {
-                                    // Check if this is a large file encoding confirmation error
-                                    if let Some(confirmation) = e.downcast_ref::<
-                                        crate::model::buffer::LargeFileEncodingConfirmation,
-                                    >() {
-                                        self.start_large_file_encoding_confirmation(confirmation);
-                                    } else {
-                                        self.set_status_message(
-                                            t!("file.error_opening", error = e.to_string())
-                                                .to_string(),
-                                        );
-                                    }
-                                    return Ok(());
-                                }
-                            };
-                            total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
-                        }
-                    }
-                }
-
-                // Handle document_changes (TextDocumentEdit[])
-                // This is what rust-analyzer sends instead of changes
-                if let Some(document_changes) = workspace_edit.document_changes {
-                    use lsp_types::DocumentChanges;
-
-                    let text_edits = match document_changes {
-                        DocumentChanges::Edits(edits) => edits,
-                        DocumentChanges::Operations(ops) => {
-                            // Extract TextDocumentEdit from operations
-                            ops.into_iter()
-                                .filter_map(|op| {
-                                    if let lsp_types::DocumentChangeOperation::Edit(edit) = op {
-                                        Some(edit)
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect()
-                        }
-                    };
-
-                    for text_doc_edit in text_edits {
-                        let uri = text_doc_edit.text_document.uri;
-
-                        if let Ok(path) = uri_to_path(&uri) {
-                            let buffer_id = match self.open_file(&path) {
-                                Ok(id) => id,
-                                Err(e) => {
-                                    // Check if this is a large file encoding confirmation error
-                                    if let Some(confirmation) = e.downcast_ref::<
-                                        crate::model::buffer::LargeFileEncodingConfirmation,
-                                    >() {
-                                        self.start_large_file_encoding_confirmation(confirmation);
-                                    } else {
-                                        self.set_status_message(
-                                            t!("file.error_opening", error = e.to_string())
-                                                .to_string(),
-                                        );
-                                    }
-                                    return Ok(());
-                                }
-                            };
-
-                            // Extract TextEdit from OneOf<TextEdit, AnnotatedTextEdit>
-                            let edits: Vec<lsp_types::TextEdit> = text_doc_edit
-                                .edits
-                                .into_iter()
-                                .map(|one_of| match one_of {
-                                    lsp_types::OneOf::Left(text_edit) => text_edit,
-                                    lsp_types::OneOf::Right(annotated) => annotated.text_edit,
-                                })
-                                .collect();
-
-                            // Log the edits for debugging
-                            tracing::info!(
-                                "Applying {} edits from rust-analyzer for {:?}:",
-                                edits.len(),
-                                path
-                            );
-                            for (i, edit) in edits.iter().enumerate() {
-                                tracing::info!(
-                                    "  Edit {}: line {}:{}-{}:{} -> {:?}",
-                                    i,
-                                    edit.range.start.line,
-                                    edit.range.start.character,
-                                    edit.range.end.line,
-                                    edit.range.end.character,
-                                    edit.new_text
-                                );
-                            }
-
-                            total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
-                        }
-                    }
-                }
+                let Some(total_changes) = self.apply_workspace_edit(workspace_edit)? else {
+                    return Ok(());
+                };
 
-                self.status_message = Some(t!("lsp.renamed", count = total_changes).to_string());
+                self.show_status_message(t!("lsp.renamed", count = total_changes).to_string());
             }
             Err(error) => {
                 // Per LSP spec: ContentModified errors (-32801) should NOT be shown to user
@@ -1469,10 +1673,12 @@ impl Editor {
                         "LSP rename: ContentModified error (expected, ignoring): {}",
                         error
                     );
-                    self.status_message = Some(t!("lsp.rename_cancelled").to_string());
+                    self.show_status_message(t!("lsp.rename_cancelled").to_string());
                 } else {
                     // Show other errors to user
-                    self.status_message = Some(t!("lsp.rename_failed", error = &error).to_string());
+                    self.show_important_status_message(
+                        t!("lsp.rename_failed", error = &error).to_string(),
+                    );
                 }
             }
         }
@@ -1804,7 +2010,7 @@ impl Editor {
 
             // Check if we're on a word
             if word_start >= word_end {
-                self.status_message = Some(t!("lsp.no_symbol_at_cursor").to_string());
+                self.show_status_message(t!("lsp.no_symbol_at_cursor").to_string());
                 return Ok(());
             }
 
@@ -1861,7 +2067,7 @@ impl Editor {
 
         // Check if the name actually changed
         if new_name == original_text {
-            self.status_message = Some(t!("lsp.name_unchanged").to_string());
+            self.show_status_message(t!("lsp.name_unchanged").to_string());
             return;
         }
 
@@ -1908,7 +2114,7 @@ impl Editor {
             .and_then(|m| m.file_path())
             .is_none()
         {
-            self.status_message = Some(t!("lsp.cannot_rename_unsaved").to_string());
+            self.show_status_message(t!("lsp.cannot_rename_unsaved").to_string());
         }
     }
 
@@ -38,6 +38,7 @@ impl Editor {
         // Simple state lookups
         let line_numbers = self.is_line_numbers_visible();
         let line_wrap = self.is_line_wrap_enabled();
+        let whitespace_indicators = self.is_whitespace_visible();
         let compose_mode = self.is_compose_mode();
         let file_explorer_visible = self.file_explorer_visible;
         let file_explorer_focused = self.is_file_explorer_focused();
@@ -63,6 +64,10 @@ impl Editor {
         // Scroll sync state
         let scroll_sync = self.same_buffer_scroll_sync;
         let has_same_buffer_splits = self.has_same_buffer_splits();
+        let has_multiple_splits = self.split_manager.root().count_leaves() > 1;
+
+        let auto_revert = self.auto_revert_enabled;
+        let can_paste = self.can_paste();
 
         // Keybinding map state
         let active_keymap: &str = &self.config.active_keybinding_map;
@@ -76,6 +81,7 @@ impl Editor {
             .set(context_keys::KEYMAP_MACOS_GUI, active_keymap == "macos-gui")
             .set(context_keys::LINE_NUMBERS, line_numbers)
             .set(context_keys::LINE_WRAP, line_wrap)
+            .set(context_keys::WHITESPACE_INDICATORS, whitespace_indicators)
             .set(context_keys::COMPOSE_MODE, compose_mode)
             .set(context_keys::FILE_EXPLORER, file_explorer_visible)
             .set(context_keys::FILE_EXPLORER_FOCUSED, file_explorer_focused)
@@ -92,7 +98,21 @@ impl Editor {
             .set(context_keys::VERTICAL_SCROLLBAR, vertical_scrollbar)
             .set(context_keys::HORIZONTAL_SCROLLBAR, horizontal_scrollbar)
             .set(context_keys::SCROLL_SYNC, scroll_sync)
-            .set(context_keys::HAS_SAME_BUFFER_SPLITS, has_same_buffer_splits);
+            .set(context_keys::HAS_SAME_BUFFER_SPLITS, has_same_buffer_splits)
+            .set(context_keys::HAS_MULTIPLE_SPLITS, has_multiple_splits)
+            .set(context_keys::AUTO_REVERT, auto_revert)
+            .set(context_keys::CAN_PASTE, can_paste);
+    }
+
+    /// Check if there is anything to paste.
+    ///
+    /// Reading the system clipboard can be slow, so it is only consulted
+    /// while a menu is open; otherwise paste is reported as available.
+    fn can_paste(&self) -> bool {
+        if self.menu_state.active_menu.is_none() {
+            return true;
+        }
+        !self.clipboard.is_empty()
     }
 
     /// Check if line numbers are visible in the active split.
@@ -113,6 +133,20 @@ impl Editor {
             .unwrap_or(false)
     }
 
+    /// Check if whitespace indicators are shown in the active split.
+    fn is_whitespace_visible(&self) -> bool {
+        let active_split = self.split_manager.active_split();
+        self.split_view_states
+            .get(&active_split)
+            .and_then(|vs| vs.whitespace)
+            .or_else(|| {
+                self.buffers
+                    .get(&self.active_buffer())
+                    .map(|state| state.buffer_settings.whitespace)
+            })
+            .is_some_and(|ws| ws.any_visible())
+    }
+
     /// Check if compose mode is active in the current buffer.
     fn is_compose_mode(&self) -> bool {
         let active_split = self.split_manager.active_split();
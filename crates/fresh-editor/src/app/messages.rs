@@ -0,0 +1,220 @@
+//! The `*Messages*` transcript.
+//!
+//! The status bar only shows the latest status message, so every message,
+//! including the ones plugins set, is also recorded with a timestamp in a
+//! capped ring. "Show Messages" opens the ring in a read-only buffer that
+//! follows new messages while it is open. Errors and conflict warnings are
+//! tagged as important, and `f` in that buffer toggles showing only those.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::{BufferId, OverlayColorSpec, OverlayOptions};
+use crate::primitives::text_property::TextPropertyEntry;
+
+/// Display name of the transcript buffer
+pub(crate) const MESSAGES_BUFFER_NAME: &str = "*Messages*";
+
+/// Buffer mode of the transcript buffer (binds `f` to the filter)
+const MESSAGES_MODE: &str = "messages";
+
+/// Number of messages kept before the oldest are dropped
+const MAX_MESSAGES: usize = 1000;
+
+/// One recorded status message
+#[derive(Debug, Clone)]
+pub(crate) struct MessageEntry {
+    pub time: DateTime<Local>,
+    /// Plugin that set the message (None for the editor itself)
+    pub source: Option<String>,
+    pub text: String,
+    pub important: bool,
+}
+
+/// Capped ring of recent status messages
+#[derive(Debug, Default)]
+pub(crate) struct MessageLog {
+    entries: VecDeque<MessageEntry>,
+    /// Show only important messages in the transcript buffer
+    important_only: bool,
+}
+
+impl MessageLog {
+    /// Record a message, dropping the oldest once the log is full
+    pub fn push(&mut self, text: &str, source: Option<&str>, important: bool) {
+        // Repeated identical messages (e.g. while scrolling) add no information
+        if let Some(last) = self.entries.back_mut() {
+            if last.text == text && last.source.as_deref() == source {
+                last.time = Local::now();
+                last.important |= important;
+                return;
+            }
+        }
+        if self.entries.len() == MAX_MESSAGES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(MessageEntry {
+            time: Local::now(),
+            source: source.map(str::to_string),
+            text: text.to_string(),
+            important,
+        });
+    }
+
+    /// Messages shown under the current filter, oldest first
+    pub fn visible(&self) -> impl Iterator<Item = &MessageEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| !self.important_only || entry.important)
+    }
+
+    pub fn important_only(&self) -> bool {
+        self.important_only
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl Editor {
+    /// Record a status message in the transcript and refresh the
+    /// `*Messages*` buffer if it is open
+    pub(crate) fn record_status_message(
+        &mut self,
+        message: &str,
+        source: Option<&str>,
+        important: bool,
+    ) {
+        if message.trim().is_empty() {
+            return;
+        }
+        self.message_log.push(message, source, important);
+        if let Some(buffer_id) = self.messages_buffer_id() {
+            self.refresh_messages_buffer(buffer_id);
+        }
+    }
+
+    /// Open the `*Messages*` transcript, or switch to it if already open
+    pub fn open_messages_buffer(&mut self) {
+        let buffer_id = match self.messages_buffer_id() {
+            Some(buffer_id) => buffer_id,
+            None => {
+                let buffer_id = self.create_virtual_buffer(
+                    MESSAGES_BUFFER_NAME.to_string(),
+                    MESSAGES_MODE.to_string(),
+                    true,
+                );
+                if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                    state.editing_disabled = true;
+                    state.margins.configure_for_line_numbers(false);
+                }
+                buffer_id
+            }
+        };
+        self.refresh_messages_buffer(buffer_id);
+        self.set_active_buffer(buffer_id);
+
+        // Start at the most recent message
+        let end = self
+            .buffers
+            .get(&buffer_id)
+            .map(|state| state.buffer.len())
+            .unwrap_or(0);
+        if let Some(buf_state) = self
+            .split_view_states
+            .get_mut(&self.split_manager.active_split())
+            .and_then(|vs| vs.keyed_states.get_mut(&buffer_id))
+        {
+            buf_state.cursors.primary_mut().position = end;
+        }
+    }
+
+    /// Toggle between all messages and important messages only
+    pub fn toggle_messages_filter(&mut self) {
+        self.message_log.important_only = !self.message_log.important_only;
+        if let Some(buffer_id) = self.messages_buffer_id() {
+            self.refresh_messages_buffer(buffer_id);
+        }
+    }
+
+    fn messages_buffer_id(&self) -> Option<BufferId> {
+        self.buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == MESSAGES_BUFFER_NAME && m.virtual_mode().is_some())
+            .map(|(id, _)| *id)
+    }
+
+    /// Rewrite the transcript buffer from the message log
+    fn refresh_messages_buffer(&mut self, buffer_id: BufferId) {
+        let header = if self.message_log.important_only() {
+            t!("messages.header_important", count = self.message_log.len())
+        } else {
+            t!("messages.header_all", count = self.message_log.len())
+        };
+        let mut entries = vec![
+            TextPropertyEntry::text(format!("{}\n\n", header)).with_style(OverlayOptions {
+                bold: true,
+                ..Default::default()
+            }),
+        ];
+        for message in self.message_log.visible() {
+            let mut line = message.time.format("%H:%M:%S ").to_string();
+            if let Some(source) = &message.source {
+                line.push_str(&format!("[{}] ", source));
+            }
+            // Keep one message per line
+            line.push_str(&message.text.replace('\n', " "));
+            line.push('\n');
+            let mut entry = TextPropertyEntry::text(line);
+            if message.important {
+                entry = entry.with_style(OverlayOptions {
+                    fg: Some(OverlayColorSpec::theme_key("diagnostic.error_fg")),
+                    ..Default::default()
+                });
+            }
+            entries.push(entry);
+        }
+
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::warn!("Failed to refresh {}: {}", MESSAGES_BUFFER_NAME, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_log_is_capped() {
+        let mut log = MessageLog::default();
+        for i in 0..MAX_MESSAGES + 5 {
+            log.push(&format!("message {}", i), None, false);
+        }
+        assert_eq!(log.len(), MAX_MESSAGES);
+        assert_eq!(log.visible().next().unwrap().text, "message 5");
+    }
+
+    #[test]
+    fn test_message_log_collapses_repeats() {
+        let mut log = MessageLog::default();
+        log.push("Saved", None, false);
+        log.push("Saved", None, false);
+        log.push("Saved", Some("git"), false);
+        assert_eq!(log.len(), 2);
+    }
+
+    #[test]
+    fn test_message_log_important_filter() {
+        let mut log = MessageLog::default();
+        log.push("Saved", None, false);
+        log.push("Error saving file", None, true);
+        log.important_only = true;
+        let visible: Vec<_> = log.visible().map(|m| m.text.as_str()).collect();
+        assert_eq!(visible, vec!["Error saving file"]);
+    }
+}
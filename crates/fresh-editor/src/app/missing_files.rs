@@ -0,0 +1,300 @@
+//! Files deleted or moved by another program while open.
+//!
+//! Auto-revert polling notices when an open file can no longer be stat'ed.
+//! The buffer keeps its content and is marked modified, and its tab and the
+//! status bar show `[missing]`. Saving then asks whether to recreate the
+//! file, save it elsewhere, close the buffer or, when a file with the same
+//! size and modification time appeared in the same directory, follow the
+//! rename. The state clears by itself if the file comes back.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use rust_i18n::t;
+
+use super::types::MissingOnDisk;
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::view::prompt::PromptType;
+
+impl Editor {
+    /// Whether the buffer's file is currently missing on disk
+    pub(crate) fn is_missing_on_disk(&self, buffer_id: BufferId) -> bool {
+        self.buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|m| m.missing_on_disk.is_some())
+    }
+
+    /// Mark the buffers showing `path` as missing on disk.
+    /// Returns true if any buffer newly entered that state.
+    pub(super) fn handle_file_missing(&mut self, path: &Path) -> bool {
+        let buffer_ids: Vec<BufferId> = self
+            .buffers
+            .iter()
+            .filter(|(_, state)| state.buffer.file_path() == Some(path))
+            .map(|(id, _)| *id)
+            .filter(|id| !self.terminal_buffers.contains_key(id))
+            .filter(|id| !self.is_missing_on_disk(*id))
+            .collect();
+        if buffer_ids.is_empty() {
+            return false;
+        }
+
+        let renamed_to = self.find_renamed_file(path, buffer_ids[0]);
+        for &buffer_id in &buffer_ids {
+            let Some(state) = self.buffers.get_mut(&buffer_id) else {
+                continue;
+            };
+            // Keep the content: it is now the only copy
+            let forced_modified = !state.buffer.is_modified();
+            state.buffer.set_modified(true);
+            if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+                metadata.missing_on_disk = Some(MissingOnDisk {
+                    forced_modified,
+                    renamed_to: renamed_to.clone(),
+                });
+            }
+        }
+        tracing::info!(
+            "Open file missing on disk: {:?} (renamed to {:?})",
+            path,
+            renamed_to
+        );
+
+        let name = self.display_name_for(buffer_ids[0]);
+        let message = match &renamed_to {
+            Some(new_path) => t!(
+                "file.missing_renamed",
+                name = name,
+                new_name = self.relative_display_path(new_path)
+            ),
+            None => t!("file.missing_on_disk", name = name),
+        };
+        self.set_important_status_message(message.to_string());
+        true
+    }
+
+    /// Clear the missing state of the buffers showing `path` now that the
+    /// file exists again. Buffers that were only marked modified to protect
+    /// their content go back to unmodified, so auto-revert can reload them.
+    pub(super) fn handle_file_reappeared(&mut self, path: &Path) {
+        let buffer_ids: Vec<BufferId> = self
+            .buffers
+            .iter()
+            .filter(|(_, state)| state.buffer.file_path() == Some(path))
+            .map(|(id, _)| *id)
+            .collect();
+
+        let mut any_cleared = false;
+        for buffer_id in buffer_ids {
+            let Some(missing) = self
+                .buffer_metadata
+                .get_mut(&buffer_id)
+                .and_then(|m| m.missing_on_disk.take())
+            else {
+                continue;
+            };
+            any_cleared = true;
+            if missing.forced_modified {
+                if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                    state.buffer.set_modified(false);
+                }
+            }
+        }
+
+        if any_cleared {
+            tracing::info!("Missing file reappeared: {:?}", path);
+            self.set_status_message(
+                t!(
+                    "file.reappeared_on_disk",
+                    name = self.relative_display_path(path)
+                )
+                .to_string(),
+            );
+        }
+    }
+
+    /// Before saving the active buffer, ask what to do if its file is gone.
+    /// Also catches deletions auto-revert polling hasn't seen (e.g. when it
+    /// is disabled). Returns true if a prompt was shown instead of saving.
+    pub(super) fn prompt_if_file_missing(&mut self) -> bool {
+        let buffer_id = self.active_buffer();
+        let Some(path) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map(PathBuf::from)
+        else {
+            return false;
+        };
+
+        if !self.is_missing_on_disk(buffer_id) {
+            // Only a file that was seen on disk can go missing; a new file
+            // that was never saved simply doesn't exist yet
+            if !self.file_mod_times.contains_key(&path) || self.filesystem.exists(&path) {
+                return false;
+            }
+            self.handle_file_missing(&path);
+        }
+
+        let name = self.display_name_for(buffer_id);
+        let renamed_to = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.missing_on_disk.as_ref())
+            .and_then(|m| m.renamed_to.clone());
+        let message = match renamed_to {
+            Some(new_path) => t!(
+                "file.missing_renamed_prompt",
+                name = name,
+                new_name = self.relative_display_path(&new_path)
+            ),
+            None => t!("file.missing_prompt", name = name),
+        };
+        self.start_prompt(
+            message.to_string(),
+            PromptType::ConfirmMissingFile { buffer_id },
+        );
+        true
+    }
+
+    /// Handle the answer to the missing file prompt
+    pub(super) fn handle_confirm_missing_file(&mut self, input: &str, buffer_id: BufferId) {
+        let choice = input.trim().to_lowercase();
+        let renamed_to = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.missing_on_disk.as_ref())
+            .and_then(|m| m.renamed_to.clone());
+
+        match (choice.chars().next(), renamed_to) {
+            (Some('f'), Some(new_path)) => self.follow_rename(buffer_id, new_path),
+            (Some('r'), _) => {
+                if let Err(e) = self.save() {
+                    self.set_important_status_message(
+                        t!("file.save_failed", error = e.to_string()).to_string(),
+                    );
+                }
+            }
+            (Some('a'), _) => {
+                if let Err(e) = self.handle_action(crate::input::keybindings::Action::SaveAs) {
+                    tracing::warn!("Failed to start Save As: {}", e);
+                }
+            }
+            (Some('c'), _) => {
+                if let Err(e) = self.force_close_buffer(buffer_id) {
+                    self.set_status_message(
+                        t!("file.cannot_close", error = e.to_string()).to_string(),
+                    );
+                } else {
+                    self.set_status_message(t!("buffer.changes_discarded").to_string());
+                }
+            }
+            _ => self.set_status_message(t!("buffer.save_cancelled").to_string()),
+        }
+    }
+
+    /// Rebind the buffer to the file it was renamed to
+    fn follow_rename(&mut self, buffer_id: BufferId, new_path: PathBuf) {
+        if !self.filesystem.exists(&new_path) {
+            self.set_status_message(
+                t!(
+                    "file.missing_rename_gone",
+                    name = self.relative_display_path(&new_path)
+                )
+                .to_string(),
+            );
+            return;
+        }
+
+        let old_path = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            .map(PathBuf::from);
+        if let Some(old_path) = &old_path {
+            self.file_overrides.rename(old_path, &new_path);
+        }
+        self.rebind_buffer_path(buffer_id, new_path.clone());
+
+        // The renamed file holds what the buffer held before it went missing
+        let missing = self
+            .buffer_metadata
+            .get_mut(&buffer_id)
+            .and_then(|m| m.missing_on_disk.take());
+        if missing.is_some_and(|m| m.forced_modified) {
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state.buffer.set_modified(false);
+            }
+        }
+        self.watch_file(&new_path);
+
+        self.set_status_message(
+            t!(
+                "file.followed_rename",
+                name = self.relative_display_path(&new_path)
+            )
+            .to_string(),
+        );
+    }
+
+    /// Look for the file `path` was most likely renamed to: a file in the
+    /// same directory, not open in any buffer, with the size and
+    /// modification time last recorded for `path`. When the buffer was
+    /// unmodified, the content must match too.
+    fn find_renamed_file(&self, path: &Path, buffer_id: BufferId) -> Option<PathBuf> {
+        let mtime = *self.file_mod_times.get(path)?;
+        let size = self.recorded_file_size(path)?;
+        let dir = path.parent()?;
+        let open_files: HashSet<PathBuf> = self
+            .buffers
+            .values()
+            .filter_map(|state| state.buffer.file_path().map(PathBuf::from))
+            .collect();
+
+        let candidates: Vec<PathBuf> = self
+            .filesystem
+            .read_dir(dir)
+            .ok()?
+            .into_iter()
+            .filter(|entry| entry.is_file() && !open_files.contains(&entry.path))
+            .filter(|entry| {
+                let metadata = entry
+                    .metadata
+                    .clone()
+                    .or_else(|| self.filesystem.metadata(&entry.path).ok());
+                metadata.is_some_and(|m| m.size == size && m.modified == Some(mtime))
+            })
+            .map(|entry| entry.path)
+            .collect();
+
+        let content = self
+            .buffers
+            .get(&buffer_id)
+            .filter(|state| !state.buffer.is_modified())
+            .and_then(|state| state.buffer.to_string());
+        match content {
+            Some(content) => candidates.into_iter().find(|candidate| {
+                self.filesystem
+                    .read_file(candidate)
+                    .is_ok_and(|bytes| bytes == content.as_bytes())
+            }),
+            None => candidates.into_iter().next(),
+        }
+    }
+
+    fn display_name_for(&self, buffer_id: BufferId) -> String {
+        self.buffer_metadata
+            .get(&buffer_id)
+            .map(|m| m.display_name.clone())
+            .unwrap_or_default()
+    }
+
+    fn relative_display_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.working_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+}
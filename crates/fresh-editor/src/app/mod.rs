@@ -1,46 +1,71 @@
 mod async_messages;
+mod auto_commands;
+mod bookmark_picker;
 mod buffer_management;
 mod calibration_actions;
 pub mod calibration_wizard;
 mod clipboard;
 mod composite_buffer_actions;
+mod diff_mode;
 pub mod event_debug;
 mod event_debug_actions;
 mod file_explorer;
 pub mod file_open;
 mod file_open_input;
 mod file_operations;
+mod file_overrides;
+mod fuzzy_finder;
+mod grammar_reload;
 mod help;
+mod html_export;
 mod input;
 mod input_dispatch;
 pub mod keybinding_editor;
 mod keybinding_editor_actions;
+mod line_marks;
 mod lsp_actions;
+mod lsp_download;
 mod lsp_requests;
 mod menu_actions;
 mod menu_context;
+mod messages;
+mod missing_files;
 mod mouse_input;
 mod on_save_actions;
+mod open_external;
+mod peek;
+mod perf_diagnosis;
 mod plugin_commands;
 mod popup_actions;
+mod privacy;
+mod project_replace;
+pub mod project_search;
 mod prompt_actions;
+mod recent_files;
 mod recovery_actions;
 mod regex_replace;
+mod remote_transfer;
 mod render;
+mod saved_macros;
 mod settings_actions;
+mod settings_bundle;
 mod shell_command;
 mod split_actions;
+mod startup_commands;
 mod tab_drag;
 mod terminal;
 mod terminal_input;
 mod terminal_mouse;
 mod theme_inspect;
 mod toggle_actions;
+mod tutorial;
 pub mod types;
 mod undo_actions;
+mod url_open;
 mod view_actions;
 pub mod warning_domains;
 pub mod workspace;
+mod workspace_trust;
 
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
@@ -92,16 +117,30 @@ pub fn editor_tick(
     if editor.check_completion_trigger_timer() {
         needs_render = true;
     }
+    if editor.check_fuzzy_finder_query_timer() {
+        needs_render = true;
+    }
     editor.check_diagnostic_pull_timer();
     if editor.check_warning_log() {
         needs_render = true;
     }
+    if editor.check_undo_history_trimmed() {
+        needs_render = true;
+    }
     if editor.poll_stdin_streaming() {
         needs_render = true;
     }
 
-    if let Err(e) = editor.auto_recovery_save_dirty_buffers() {
-        tracing::debug!("Auto-recovery-save error: {}", e);
+    // A large recovery write is announced one tick early so its indicator
+    // is drawn before the write blocks
+    let recovery_write_was_in_progress = editor.recovery_write_in_progress();
+    if !editor.announce_large_recovery_write() {
+        if let Err(e) = editor.auto_recovery_save_dirty_buffers() {
+            tracing::debug!("Auto-recovery-save error: {}", e);
+        }
+    }
+    if editor.recovery_write_in_progress() != recovery_write_was_in_progress {
+        needs_render = true;
     }
     if let Err(e) = editor.auto_save_persistent_buffers() {
         tracing::debug!("Auto-save (disk) error: {}", e);
@@ -147,6 +186,88 @@ pub(crate) fn normalize_path(path: &std::path::Path) -> std::path::PathBuf {
     }
 }
 
+/// Directories to load TypeScript plugins from, in load order:
+/// 1. Next to the executable (for cargo-dist installations)
+/// 2. In the working directory (for development/local usage), only when the
+///    workspace is trusted
+/// 3. From embedded plugins (for cargo-binstall, when embed-plugins feature is enabled)
+/// 4. User plugins directory (~/.config/fresh/plugins)
+/// 5. Package manager installed plugins (~/.config/fresh/plugins/packages/*)
+fn plugin_dirs(
+    working_dir: &Path,
+    dir_context: &DirectoryContext,
+    workspace_trusted: bool,
+) -> Vec<PathBuf> {
+    let mut plugin_dirs: Vec<PathBuf> = vec![];
+
+    // Check next to executable first (for cargo-dist installations)
+    if let Ok(exe_path) = std::env::current_exe() {
+        if let Some(exe_dir) = exe_path.parent() {
+            let exe_plugin_dir = exe_dir.join("plugins");
+            if exe_plugin_dir.exists() {
+                plugin_dirs.push(exe_plugin_dir);
+            }
+        }
+    }
+
+    // Then check working directory (for development)
+    let working_plugin_dir = working_dir.join("plugins");
+    if working_plugin_dir.exists() && !plugin_dirs.contains(&working_plugin_dir) {
+        if workspace_trusted {
+            plugin_dirs.push(working_plugin_dir);
+        } else {
+            tracing::info!(
+                "Workspace not trusted, skipping plugins in {:?}",
+                working_plugin_dir
+            );
+        }
+    }
+
+    // If no disk plugins found, try embedded plugins (cargo-binstall builds)
+    #[cfg(feature = "embed-plugins")]
+    if plugin_dirs.is_empty() {
+        if let Some(embedded_dir) = crate::services::plugins::embedded::get_embedded_plugins_dir() {
+            tracing::info!("Using embedded plugins from: {:?}", embedded_dir);
+            plugin_dirs.push(embedded_dir.clone());
+        }
+    }
+
+    // Always check user config plugins directory (~/.config/fresh/plugins)
+    let user_plugins_dir = dir_context.config_dir.join("plugins");
+    if user_plugins_dir.exists() && !plugin_dirs.contains(&user_plugins_dir) {
+        tracing::info!("Found user plugins directory: {:?}", user_plugins_dir);
+        plugin_dirs.push(user_plugins_dir.clone());
+    }
+
+    // Check for package manager installed plugins (~/.config/fresh/plugins/packages/*)
+    let packages_dir = dir_context.config_dir.join("plugins").join("packages");
+    if packages_dir.exists() {
+        if let Ok(entries) = std::fs::read_dir(&packages_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                // Skip hidden directories (like .index for registry cache)
+                if path.is_dir() {
+                    if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                        if !name.starts_with('.') {
+                            tracing::info!("Found package manager plugin: {:?}", path);
+                            plugin_dirs.push(path);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if plugin_dirs.is_empty() {
+        tracing::debug!(
+            "No plugins directory found next to executable or in working dir: {:?}",
+            working_dir
+        );
+    }
+
+    plugin_dirs
+}
+
 use self::types::{
     Bookmark, CachedLayout, EventLineInfo, InteractiveReplaceState, LspMessageEntry,
     LspProgressInfo, MacroRecordingState, MouseState, SearchState, TabContextMenu,
@@ -169,6 +290,7 @@ use crate::model::filesystem::FileSystem;
 use crate::services::async_bridge::{AsyncBridge, AsyncMessage};
 use crate::services::fs::FsManager;
 use crate::services::lsp::manager::LspManager;
+use crate::services::plugins::command_cache::PluginCommandCache;
 use crate::services::plugins::PluginManager;
 use crate::services::recovery::{RecoveryConfig, RecoveryService};
 use crate::services::time_source::{RealTimeSource, SharedTimeSource};
@@ -199,6 +321,7 @@ use std::sync::{Arc, RwLock};
 use std::time::Instant;
 
 // Re-export BufferId from event module for backward compatibility
+pub use self::diff_mode::DiffFile;
 pub use self::types::{BufferKind, BufferMetadata, HoverTarget};
 pub use self::warning_domains::{
     GeneralWarningDomain, LspWarningDomain, WarningAction, WarningActionId, WarningDomain,
@@ -206,6 +329,9 @@ pub use self::warning_domains::{
 };
 pub use crate::model::event::BufferId;
 
+/// Connection name for files on this machine while the editor is remote
+pub const LOCAL_CONNECTION: &str = "local";
+
 /// Helper function to convert lsp_types::Uri to PathBuf
 fn uri_to_path(uri: &lsp_types::Uri) -> Result<PathBuf, String> {
     // Convert to url::Url for path conversion
@@ -293,6 +419,12 @@ pub struct Editor {
     /// When true, `flush_pending_grammars()` defers work until the build completes.
     grammar_build_in_progress: bool,
 
+    /// Durations of startup phases, reported by `--profile-startup`
+    startup_timings: Vec<(&'static str, std::time::Duration)>,
+
+    /// When background startup work (grammar build, plugin loading) was started
+    background_startup: Option<Instant>,
+
     /// Cancellation flag for the current streaming grep search.
     streaming_grep_cancellation: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
 
@@ -301,6 +433,25 @@ pub struct Editor {
     /// when the background build finishes.
     pending_grammar_callbacks: Vec<fresh_core::api::JsCallbackId>,
 
+    /// Every grammar plugins have registered so far, re-added when the
+    /// registry is rebuilt from disk by Reload Grammars
+    plugin_grammars: Vec<(String, PathBuf, Vec<String>)>,
+
+    /// A full grammar reload is waiting for the running build to finish
+    grammar_full_reload_pending: bool,
+
+    /// The running grammar build is a full reload (reported when it finishes)
+    grammar_full_reload_in_flight: bool,
+
+    /// Last time we polled the grammar directories for changes
+    last_grammar_poll: std::time::Instant,
+
+    /// Whether the grammar directories have been polled at least once
+    grammar_dirs_polled: bool,
+
+    /// Contents of the grammar directories at the last poll
+    grammar_dirs_fingerprint: Option<grammar_reload::GrammarDirsFingerprint>,
+
     /// Active theme
     theme: crate::view::theme::Theme,
 
@@ -452,6 +603,69 @@ pub struct Editor {
     /// Whether mouse capture is enabled
     mouse_enabled: bool,
 
+    /// Whether mouse capture is temporarily released for native terminal
+    /// selection (Alt+click); restored on the next key press
+    mouse_capture_suspended: bool,
+
+    /// Settings bundle being reviewed, waiting for the user to choose which
+    /// categories to apply
+    pending_settings_import: Option<crate::services::settings_bundle::SettingsBundle>,
+
+    /// Buffers loaded from disk whose `on_open` auto-commands haven't run yet
+    pending_open_auto_commands: HashSet<BufferId>,
+
+    /// Files restored from the workspace whose content isn't read until
+    /// they are first shown
+    deferred_buffers: HashMap<BufferId, workspace::DeferredBuffer>,
+
+    /// Set while auto-commands run, so they don't trigger each other
+    running_auto_commands: bool,
+
+    /// Diff or merge session started with `--diff`/`--merge`
+    diff_session: Option<diff_mode::DiffSession>,
+
+    /// Last file handed to an `open_external` program, for "Open as Text"
+    last_external_open: Option<PathBuf>,
+
+    /// Interactive tutorial being worked through (`--tutor`)
+    tutorial: Option<tutorial::TutorialSession>,
+
+    /// Fuzzy file finder being shown, with the walk feeding it
+    fuzzy_finder: Option<fuzzy_finder::FuzzyFinder>,
+
+    /// Latest project search, with the walk feeding its results buffer
+    project_search: Option<project_search::ProjectSearch>,
+
+    /// Project replace waiting for its scan or for confirmation
+    project_replace: Option<project_replace::ProjectReplace>,
+
+    /// Buffers downloaded from a URL, with the URL they came from
+    url_buffers: HashMap<BufferId, String>,
+
+    /// Languages whose `lsp_downloads` server is being downloaded
+    lsp_downloads_in_progress: HashSet<String>,
+
+    /// Languages the user declined to download a server for this session
+    lsp_downloads_declined: HashSet<String>,
+
+    /// When each buffer was last saved this session (for plugins)
+    buffer_saved_at: HashMap<BufferId, std::time::SystemTime>,
+
+    /// Modified state last reported through `buffer_modified_changed`
+    reported_modified: HashMap<BufferId, bool>,
+
+    /// A quit is waiting for `before_quit` handlers to finish
+    before_quit_pending: bool,
+
+    /// A `before_quit` handler called `editor.cancelQuit()`
+    quit_cancelled_by_plugin: bool,
+
+    /// Secret patterns while Privacy Mode is on (`None` when off)
+    privacy_masker: Option<crate::primitives::secret_mask::SecretMasker>,
+
+    /// Language/encoding/line ending/read-only choices remembered per file
+    file_overrides: crate::services::file_overrides::FileOverrideStore,
+
     /// Whether same-buffer splits sync their scroll positions
     same_buffer_scroll_sync: bool,
 
@@ -463,6 +677,9 @@ pub struct Editor {
     /// Whether GPM is being used for mouse input (requires software cursor)
     gpm_active: bool,
 
+    /// Open every file read-only (`--readonly`)
+    open_read_only: bool,
+
     /// Current keybinding context
     key_context: KeyContext,
 
@@ -513,6 +730,15 @@ pub struct Editor {
     /// Pending LSP code actions request ID (if any)
     pending_code_actions_request: Option<u64>,
 
+    /// Whether the pending code actions request came from "Fix Next Problem"
+    pending_fix_next_problem: bool,
+
+    /// Definition/references request whose response should open a peek popup
+    pending_peek: Option<peek::PeekKind>,
+
+    /// State behind the open Peek Definition / Peek References popup
+    peek: Option<peek::PeekView>,
+
     /// Pending LSP inlay hints request ID (if any)
     pending_inlay_hints_request: Option<u64>,
 
@@ -669,6 +895,9 @@ pub struct Editor {
     /// Bookmarks (character key -> bookmark)
     bookmarks: HashMap<char, Bookmark>,
 
+    /// Named bookmarks (name -> bookmark)
+    named_bookmarks: HashMap<String, Bookmark>,
+
     /// Global search options (persist across searches)
     search_case_sensitive: bool,
     search_whole_word: bool,
@@ -707,6 +936,15 @@ pub struct Editor {
     /// When Some, a confirmation popup is shown asking user to approve LSP spawn
     pending_lsp_confirmation: Option<String>,
 
+    /// Trust state of the working directory (see `services::workspace_trust`)
+    workspace_trust: crate::services::workspace_trust::WorkspaceTrust,
+
+    /// Pending workspace trust question, answered through a list popup
+    pending_trust_confirmation: Option<workspace_trust::TrustConfirmation>,
+
+    /// Whether on-save commands may run in an untrusted workspace this session
+    on_save_commands_allowed: bool,
+
     /// Pending close buffer - buffer to close after SaveFileAs completes
     /// Used when closing a modified buffer that needs to be saved first
     pending_close_buffer: Option<BufferId>,
@@ -724,6 +962,23 @@ pub struct Editor {
     /// Maps file path to last known modification time
     file_mod_times: HashMap<PathBuf, std::time::SystemTime>,
 
+    /// Last known size and polling back-off for open files (for auto-revert)
+    file_poll_states: HashMap<PathBuf, file_operations::FilePollState>,
+
+    /// Background checks of open remote files (for auto-revert), started
+    /// once one is open
+    remote_file_poller: Option<crate::services::remote::RemoteFilePoller>,
+
+    /// Keepalive pings of remote connections, started once a remote buffer
+    /// is shown
+    remote_latency_monitor: Option<crate::services::remote::RemoteLatencyMonitor>,
+
+    /// Recent round trips per remote connection (for the status bar badge)
+    remote_latency: HashMap<String, crate::services::remote::RollingLatency>,
+
+    /// Chunked downloads and uploads of remote buffers, by transfer id
+    remote_transfers: HashMap<u64, remote_transfer::BufferTransfer>,
+
     /// Last known modification times for expanded directories (for file tree refresh)
     /// Maps directory path to last known modification time
     dir_mod_times: HashMap<PathBuf, std::time::SystemTime>,
@@ -750,6 +1005,10 @@ pub struct Editor {
     /// Last auto-recovery-save time for rate limiting
     last_auto_recovery_save: std::time::Instant,
 
+    /// A recovery save of a large buffer is due; the status bar shows an
+    /// indicator for one frame before the (blocking) write happens
+    recovery_write_in_progress: bool,
+
     /// Last persistent auto-save time for rate limiting (disk)
     last_persistent_auto_save: std::time::Instant,
 
@@ -767,6 +1026,12 @@ pub struct Editor {
     /// Status message log path (for viewing full status history)
     status_log_path: Option<PathBuf>,
 
+    /// Recent status messages, shown in the `*Messages*` buffer
+    message_log: messages::MessageLog,
+
+    /// Last "Diagnose Buffer Performance" report, kept until its LSP probe answers
+    perf_report: Option<perf_diagnosis::BufferPerfReport>,
+
     /// Warning domain registry for extensible warning indicators
     /// Contains LSP warnings, general warnings, and can be extended by plugins
     warning_domains: WarningDomainRegistry,
@@ -855,6 +1120,12 @@ pub struct Editor {
     wait_tracking: HashMap<BufferId, (u64, bool)>,
     /// Wait IDs that have completed (buffer closed or popup dismissed)
     completed_waits: Vec<u64>,
+    /// Files opened from the command line with `fresh --wait`
+    cli_wait: Option<CliWait>,
+    /// Commands from `--execute`, run after the first render
+    startup_commands: Option<startup_commands::StartupCommands>,
+    /// An `--execute-strict` command failed
+    startup_commands_failed: bool,
 
     /// Stdin streaming state (if reading from stdin)
     stdin_streaming: Option<StdinStreamingState>,
@@ -887,6 +1158,19 @@ pub struct PendingFileOpen {
     pub message: Option<String>,
     /// Wait ID for --wait tracking (if the CLI is blocking until done)
     pub wait_id: Option<u64>,
+    /// Remote connection to open the file on (None = the editor's filesystem)
+    pub connection: Option<String>,
+    /// Regex to move the cursor to the first match of after opening
+    pub search: Option<String>,
+}
+
+/// Buffers `fresh --wait` is waiting for
+#[derive(Debug, Default)]
+struct CliWait {
+    /// Waited-for buffers that are still open
+    buffers: HashSet<BufferId>,
+    /// A waited-for buffer was closed with unsaved changes
+    abandoned: bool,
 }
 
 /// State for an incremental chunked search on large files.
@@ -908,6 +1192,10 @@ struct SearchScanState {
     case_sensitive: bool,
     whole_word: bool,
     use_regex: bool,
+    /// Number of `scan.matches` already in `search_state`. The matches found
+    /// so far are handed over once one lies at or after the cursor, so the
+    /// user can step through them before the scan finishes.
+    published: usize,
 }
 
 /// State for an incremental line-feed scan (non-blocking Go to Line)
@@ -925,12 +1213,15 @@ struct LineScanState {
     /// Whether to open the Go to Line prompt after the scan completes.
     /// True when triggered from the Go to Line flow, false from the command palette.
     open_goto_line_on_complete: bool,
+    /// Pending `goto_line_col` (1-indexed line, optional column) to perform
+    /// once the scan completes, e.g. a `file:line` open of a large file.
+    goto_on_complete: Option<(usize, Option<usize>)>,
 }
 
 /// State for tracking stdin streaming in background
 pub struct StdinStreamingState {
-    /// Path to temp file where stdin is being written
-    pub temp_path: PathBuf,
+    /// Where stdin is being collected (in memory or a temp file)
+    pub spool: Arc<crate::services::stdin_spool::StdinSpool>,
     /// Buffer ID for the stdin buffer
     pub buffer_id: BufferId,
     /// Last known file size (for detecting growth)
@@ -989,8 +1280,10 @@ impl Editor {
             None,
             color_capability,
             grammar_registry,
+            true,
         )?;
         editor.start_background_grammar_build();
+        editor.start_background_plugin_load();
         Ok(editor)
     }
 
@@ -1023,6 +1316,7 @@ impl Editor {
             time_source,
             color_capability,
             grammar_registry,
+            false,
         )
     }
 
@@ -1041,6 +1335,7 @@ impl Editor {
         time_source: Option<SharedTimeSource>,
         color_capability: crate::view::color_support::ColorCapability,
         grammar_registry: Arc<crate::primitives::grammar::GrammarRegistry>,
+        load_plugins_in_background: bool,
     ) -> AnyhowResult<Self> {
         // Use provided time_source or default to RealTimeSource
         let time_source = time_source.unwrap_or_else(RealTimeSource::shared);
@@ -1054,6 +1349,19 @@ impl Editor {
         // This ensures consistent path comparisons throughout the editor
         let working_dir = working_dir.canonicalize().unwrap_or(working_dir);
 
+        // Remote paths can't be checked locally, so overrides are only
+        // persisted for local projects
+        let file_overrides = if filesystem.remote_connection_info().is_none() {
+            crate::services::file_overrides::FileOverrideStore::load(
+                dir_context.file_overrides_path(&working_dir),
+            )
+        } else {
+            Default::default()
+        };
+
+        let workspace_trust =
+            crate::services::workspace_trust::workspace_trust(&dir_context, &working_dir);
+
         // Load all themes into registry
         let theme_loader = crate::view::theme::ThemeLoader::new(dir_context.themes_dir());
         let theme_registry = theme_loader.load_all();
@@ -1097,10 +1405,18 @@ impl Editor {
             .configure_for_line_numbers(config.editor.line_numbers);
         state.buffer_settings.tab_size = config.editor.tab_size;
         state.buffer_settings.auto_close = config.editor.auto_close;
+        state.buffer_settings.word_rules = config.word_rules(&state.language);
+        state.buffer_settings.auto_close_pairs = config.auto_close_pairs(&state.language);
         // Note: line_wrap_enabled is now stored in SplitViewState.viewport
         tracing::info!("EditorState created for buffer {:?}", buffer_id);
         buffers.insert(buffer_id, state);
-        event_logs.insert(buffer_id, EventLog::new());
+        event_logs.insert(
+            buffer_id,
+            EventLog::with_limits(
+                config.editor.max_undo_entries,
+                config.editor.max_undo_memory_mb * 1024 * 1024,
+            ),
+        );
 
         // Create metadata for the initial empty buffer
         let mut buffer_metadata = HashMap::new();
@@ -1126,6 +1442,7 @@ impl Editor {
 
         // Create LSP manager with async support
         let mut lsp = LspManager::new(root_uri);
+        lsp.set_workspace_trusted(workspace_trust.is_trusted());
 
         // Configure runtime and bridge if available
         if let Some(ref runtime) = tokio_runtime {
@@ -1171,12 +1488,15 @@ impl Editor {
         let theme_cache = Arc::new(RwLock::new(theme_registry.to_json_map()));
 
         // Initialize plugin manager (handles both enabled and disabled cases internally)
+        let mut startup_timings = Vec::new();
+        let phase_start = Instant::now();
         let plugin_manager = PluginManager::new(
             enable_plugins,
             Arc::clone(&command_registry),
             dir_context.clone(),
             Arc::clone(&theme_cache),
         );
+        startup_timings.push(("plugin thread spawn", phase_start.elapsed()));
 
         // Update the plugin state snapshot with working_dir BEFORE loading plugins
         // This ensures plugins can call getCwd() correctly during initialization
@@ -1186,77 +1506,13 @@ impl Editor {
             snapshot.working_dir = working_dir.clone();
         }
 
-        // Load TypeScript plugins from multiple directories:
-        // 1. Next to the executable (for cargo-dist installations)
-        // 2. In the working directory (for development/local usage)
-        // 3. From embedded plugins (for cargo-binstall, when embed-plugins feature is enabled)
-        // 4. User plugins directory (~/.config/fresh/plugins)
-        // 5. Package manager installed plugins (~/.config/fresh/plugins/packages/*)
-        if plugin_manager.is_active() {
-            let mut plugin_dirs: Vec<std::path::PathBuf> = vec![];
-
-            // Check next to executable first (for cargo-dist installations)
-            if let Ok(exe_path) = std::env::current_exe() {
-                if let Some(exe_dir) = exe_path.parent() {
-                    let exe_plugin_dir = exe_dir.join("plugins");
-                    if exe_plugin_dir.exists() {
-                        plugin_dirs.push(exe_plugin_dir);
-                    }
-                }
-            }
-
-            // Then check working directory (for development)
-            let working_plugin_dir = working_dir.join("plugins");
-            if working_plugin_dir.exists() && !plugin_dirs.contains(&working_plugin_dir) {
-                plugin_dirs.push(working_plugin_dir);
-            }
-
-            // If no disk plugins found, try embedded plugins (cargo-binstall builds)
-            #[cfg(feature = "embed-plugins")]
-            if plugin_dirs.is_empty() {
-                if let Some(embedded_dir) =
-                    crate::services::plugins::embedded::get_embedded_plugins_dir()
-                {
-                    tracing::info!("Using embedded plugins from: {:?}", embedded_dir);
-                    plugin_dirs.push(embedded_dir.clone());
-                }
-            }
-
-            // Always check user config plugins directory (~/.config/fresh/plugins)
-            let user_plugins_dir = dir_context.config_dir.join("plugins");
-            if user_plugins_dir.exists() && !plugin_dirs.contains(&user_plugins_dir) {
-                tracing::info!("Found user plugins directory: {:?}", user_plugins_dir);
-                plugin_dirs.push(user_plugins_dir.clone());
-            }
-
-            // Check for package manager installed plugins (~/.config/fresh/plugins/packages/*)
-            let packages_dir = dir_context.config_dir.join("plugins").join("packages");
-            if packages_dir.exists() {
-                if let Ok(entries) = std::fs::read_dir(&packages_dir) {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        // Skip hidden directories (like .index for registry cache)
-                        if path.is_dir() {
-                            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                                if !name.starts_with('.') {
-                                    tracing::info!("Found package manager plugin: {:?}", path);
-                                    plugin_dirs.push(path);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-
-            if plugin_dirs.is_empty() {
-                tracing::debug!(
-                    "No plugins directory found next to executable or in working dir: {:?}",
-                    working_dir
-                );
-            }
-
-            // Load from all found plugin directories, respecting config
-            for plugin_dir in plugin_dirs {
+        // Production entry points queue plugin loading on the plugin thread
+        // (see `start_background_plugin_load`); tests load synchronously so
+        // plugin commands exist as soon as the editor is constructed.
+        if plugin_manager.is_active() && !load_plugins_in_background {
+            let phase_start = Instant::now();
+            for plugin_dir in plugin_dirs(&working_dir, &dir_context, workspace_trust.is_trusted())
+            {
                 tracing::info!("Loading TypeScript plugins from: {:?}", plugin_dir);
                 let (errors, discovered_plugins) =
                     plugin_manager.load_plugins_from_dir_with_config(&plugin_dir, &config.plugins);
@@ -1280,14 +1536,17 @@ impl Editor {
                     );
                 }
             }
+            startup_timings.push(("plugin loading", phase_start.elapsed()));
         }
 
         // Extract config values before moving config into the struct
         let file_explorer_width = config.file_explorer.width;
         let recovery_enabled = config.editor.recovery_enabled;
+        let recovery_max_size_mb = u64::from(config.editor.recovery_max_size_mb);
         let check_for_updates = config.check_for_updates;
-        let show_menu_bar = config.editor.show_menu_bar;
-        let show_tab_bar = config.editor.show_tab_bar;
+        let ui_density = config.editor.ui_density;
+        let show_menu_bar = config.editor.show_menu_bar && ui_density.shows_menu_bar();
+        let show_tab_bar = config.editor.show_tab_bar && ui_density.shows_tab_bar();
         let show_status_bar = config.editor.show_status_bar;
 
         // Start periodic update checker if enabled (also sends daily telemetry)
@@ -1319,8 +1578,16 @@ impl Editor {
             pending_grammars: Vec::new(),
             grammar_reload_pending: false,
             grammar_build_in_progress: false,
+            startup_timings,
+            background_startup: None,
             streaming_grep_cancellation: None,
             pending_grammar_callbacks: Vec::new(),
+            plugin_grammars: Vec::new(),
+            grammar_full_reload_pending: false,
+            grammar_full_reload_in_flight: false,
+            last_grammar_poll: time_source.now(),
+            grammar_dirs_polled: false,
+            grammar_dirs_fingerprint: None,
             theme,
             theme_registry,
             theme_cache,
@@ -1369,9 +1636,30 @@ impl Editor {
             tab_bar_visible: show_tab_bar,
             status_bar_visible: show_status_bar,
             mouse_enabled: true,
+            mouse_capture_suspended: false,
+            pending_settings_import: None,
+            pending_open_auto_commands: HashSet::new(),
+            deferred_buffers: HashMap::new(),
+            running_auto_commands: false,
+            diff_session: None,
+            last_external_open: None,
+            tutorial: None,
+            fuzzy_finder: None,
+            project_search: None,
+            project_replace: None,
+            url_buffers: HashMap::new(),
+            lsp_downloads_in_progress: HashSet::new(),
+            lsp_downloads_declined: HashSet::new(),
+            buffer_saved_at: HashMap::new(),
+            reported_modified: HashMap::new(),
+            before_quit_pending: false,
+            quit_cancelled_by_plugin: false,
+            privacy_masker: None,
+            file_overrides,
             same_buffer_scroll_sync: false,
             mouse_cursor_position: None,
             gpm_active: false,
+            open_read_only: false,
             key_context: KeyContext::Normal,
             menu_state: crate::view::ui::MenuState::new(dir_context.themes_dir()),
             menus: crate::config::MenuConfig::translated(),
@@ -1388,6 +1676,9 @@ impl Editor {
             pending_references_symbol: String::new(),
             pending_signature_help_request: None,
             pending_code_actions_request: None,
+            pending_fix_next_problem: false,
+            pending_peek: None,
+            peek: None,
             pending_inlay_hints_request: None,
             pending_folding_range_requests: HashMap::new(),
             folding_ranges_in_flight: HashMap::new(),
@@ -1427,7 +1718,7 @@ impl Editor {
             prompt_histories: {
                 // Load prompt histories from disk if available
                 let mut histories = HashMap::new();
-                for history_name in ["search", "replace", "goto_line"] {
+                for history_name in ["search", "replace", "goto_line", "command"] {
                     let path = dir_context.prompt_history_path(history_name);
                     let history = crate::input::input_history::InputHistory::load_from_file(&path)
                         .unwrap_or_else(|e| {
@@ -1451,6 +1742,7 @@ impl Editor {
             stored_folding_ranges: HashMap::new(),
             event_broadcaster: crate::model::control_event::EventBroadcaster::default(),
             bookmarks: HashMap::new(),
+            named_bookmarks: HashMap::new(),
             search_case_sensitive: true,
             search_whole_word: false,
             search_use_regex: false,
@@ -1465,11 +1757,19 @@ impl Editor {
             plugin_render_requested: false,
             chord_state: Vec::new(),
             pending_lsp_confirmation: None,
+            workspace_trust,
+            pending_trust_confirmation: None,
+            on_save_commands_allowed: false,
             pending_close_buffer: None,
             auto_revert_enabled: true,
             last_auto_revert_poll: time_source.now(),
             last_file_tree_poll: time_source.now(),
             file_mod_times: HashMap::new(),
+            file_poll_states: HashMap::new(),
+            remote_file_poller: None,
+            remote_latency_monitor: None,
+            remote_latency: HashMap::new(),
+            remote_transfers: HashMap::new(),
             dir_mod_times: HashMap::new(),
             file_rapid_change_counts: HashMap::new(),
             file_open_state: None,
@@ -1477,6 +1777,7 @@ impl Editor {
             recovery_service: {
                 let recovery_config = RecoveryConfig {
                     enabled: recovery_enabled,
+                    max_total_bytes: recovery_max_size_mb * 1024 * 1024,
                     ..RecoveryConfig::default()
                 };
                 RecoveryService::with_config_and_dir(recovery_config, dir_context.recovery_dir())
@@ -1484,11 +1785,14 @@ impl Editor {
             full_redraw_requested: false,
             time_source: time_source.clone(),
             last_auto_recovery_save: time_source.now(),
+            recovery_write_in_progress: false,
             last_persistent_auto_save: time_source.now(),
             active_custom_contexts: HashSet::new(),
             editor_mode: None,
             warning_log: None,
             status_log_path: None,
+            message_log: messages::MessageLog::default(),
+            perf_report: None,
             warning_domains: WarningDomainRegistry::new(),
             update_checker,
             terminal_manager: crate::services::terminal::TerminalManager::new(),
@@ -1514,6 +1818,9 @@ impl Editor {
             pending_hot_exit_recovery: false,
             wait_tracking: HashMap::new(),
             completed_waits: Vec::new(),
+            cli_wait: None,
+            startup_commands: None,
+            startup_commands_failed: false,
             stdin_streaming: None,
             line_scan_state: None,
             search_scan_state: None,
@@ -1526,21 +1833,32 @@ impl Editor {
 
         // Apply clipboard configuration
         editor.clipboard.apply_config(&editor.config.clipboard);
+        crate::services::shell_env::configure(&editor.config.process);
 
         #[cfg(feature = "plugins")]
-        {
-            editor.update_plugin_state_snapshot();
-            if editor.plugin_manager.is_active() {
-                editor.plugin_manager.run_hook(
-                    "editor_initialized",
-                    crate::services::plugins::hooks::HookArgs::EditorInitialized,
-                );
-            }
+        editor.update_plugin_state_snapshot();
+
+        // With background loading these wait for the plugins, see
+        // `start_background_plugin_load`
+        if !load_plugins_in_background {
+            editor.run_editor_initialized_hook();
+            editor.validate_auto_commands();
         }
 
         Ok(editor)
     }
 
+    /// Tell plugins the editor is up
+    fn run_editor_initialized_hook(&self) {
+        #[cfg(feature = "plugins")]
+        if self.plugin_manager.is_active() {
+            self.plugin_manager.run_hook(
+                "editor_initialized",
+                crate::services::plugins::hooks::HookArgs::EditorInitialized,
+            );
+        }
+    }
+
     /// Get a reference to the event broadcaster
     pub fn event_broadcaster(&self) -> &crate::model::control_event::EventBroadcaster {
         &self.event_broadcaster
@@ -1555,6 +1873,7 @@ impl Editor {
             return;
         };
         self.grammar_build_in_progress = true;
+        self.background_startup.get_or_insert_with(Instant::now);
         let sender = bridge.sender();
         let config_dir = self.dir_context.config_dir.clone();
         std::thread::Builder::new()
@@ -1571,6 +1890,105 @@ impl Editor {
             .ok();
     }
 
+    /// Queue loading all plugins on the plugin thread without waiting, so the
+    /// first frame isn't held up by bundling and compiling them. Hooks and
+    /// plugin actions sent meanwhile queue behind the loads on the plugin
+    /// thread, so they only wait if they arrive before loading is done.
+    /// Results are merged by `process_finished_plugin_loads`.
+    fn start_background_plugin_load(&mut self) {
+        if !self.plugin_manager.is_active() {
+            self.validate_auto_commands();
+            return;
+        }
+        self.background_startup.get_or_insert_with(Instant::now);
+        for plugin_dir in plugin_dirs(
+            &self.working_dir,
+            &self.dir_context,
+            self.workspace_trust.is_trusted(),
+        ) {
+            tracing::info!("Queueing TypeScript plugins from: {:?}", plugin_dir);
+            self.plugin_manager
+                .start_loading_plugins_from_dir_with_config(&plugin_dir, &self.config.plugins);
+        }
+        // Queued behind the loads, so every plugin sees it
+        self.run_editor_initialized_hook();
+        if !self.plugin_manager.plugin_loads_pending() {
+            self.validate_auto_commands();
+            return;
+        }
+        // Until the plugins register their commands, list the ones they
+        // registered last session
+        let cache = PluginCommandCache::load(&self.dir_context);
+        let registry = self.command_registry.read().unwrap();
+        for command in cache.commands {
+            registry.register_placeholder(plugin_commands::plugin_command_to_internal(command));
+        }
+    }
+
+    /// Merge the results of background plugin loads that have finished.
+    /// Returns true if any finished.
+    fn process_finished_plugin_loads(&mut self) -> bool {
+        let finished = self.plugin_manager.take_finished_plugin_loads();
+        if finished.is_empty() {
+            return false;
+        }
+        for (errors, discovered_plugins) in finished {
+            for (name, plugin_config) in discovered_plugins {
+                self.config.plugins.insert(name, plugin_config);
+            }
+            for err in &errors {
+                tracing::error!("TypeScript plugin load error: {}", err);
+            }
+        }
+        if !self.plugin_manager.plugin_loads_pending() {
+            self.record_background_startup_phase("plugin loading");
+            // The plugins sent their commands before reporting the load as
+            // done, so they are all registered once these are handled
+            self.process_plugin_commands();
+            self.remember_plugin_commands();
+            // Auto-commands may name plugin commands, which exist only now
+            self.validate_auto_commands();
+        }
+        true
+    }
+
+    /// Drop placeholder commands no plugin registered again, and remember
+    /// the registered ones for the next startup
+    fn remember_plugin_commands(&mut self) {
+        let registry = self.command_registry.read().unwrap();
+        registry.remove_placeholders();
+        let mut cache = PluginCommandCache::load(&self.dir_context);
+        cache.commands = registry
+            .plugin_commands()
+            .into_iter()
+            .filter_map(plugin_commands::internal_to_plugin_command)
+            .collect();
+        if let Err(e) = cache.save() {
+            tracing::warn!("Failed to save plugin commands: {}", e);
+        }
+    }
+
+    /// Record how long a background startup job took, once
+    fn record_background_startup_phase(&mut self, phase: &'static str) {
+        let Some(start) = self.background_startup else {
+            return;
+        };
+        if !self.startup_timings.iter().any(|(name, _)| *name == phase) {
+            self.startup_timings.push((phase, start.elapsed()));
+        }
+    }
+
+    /// Durations of startup phases measured inside the editor. Background
+    /// jobs (grammar build, plugin loading) appear once they have finished.
+    pub fn startup_timings(&self) -> &[(&'static str, std::time::Duration)] {
+        &self.startup_timings
+    }
+
+    /// Whether startup work running in the background hasn't finished yet
+    pub fn startup_work_pending(&self) -> bool {
+        self.grammar_build_in_progress || self.plugin_manager.plugin_loads_pending()
+    }
+
     /// Get a reference to the async bridge (if available)
     pub fn async_bridge(&self) -> Option<&AsyncBridge> {
         self.async_bridge.as_ref()
@@ -1691,6 +2109,13 @@ impl Editor {
         self.active_state().editing_disabled
     }
 
+    /// Whether a buffer is marked read-only, so it must not be saved
+    pub(crate) fn is_read_only(&self, buffer_id: BufferId) -> bool {
+        self.buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|m| m.read_only)
+    }
+
     /// Mark a buffer as read-only, setting both metadata and editor state consistently.
     /// This is the single entry point for making a buffer read-only.
     pub fn mark_buffer_read_only(&mut self, buffer_id: BufferId, read_only: bool) {
@@ -1867,10 +2292,22 @@ impl Editor {
 
     /// Set the process spawner for plugin command execution
     /// Use RemoteProcessSpawner for remote editing, LocalProcessSpawner for local
+    ///
+    /// When editing remotely, language servers are spawned through it too,
+    /// so they run on the remote host against the remote paths.
     pub fn set_process_spawner(
         &mut self,
         spawner: Arc<dyn crate::services::remote::ProcessSpawner>,
     ) {
+        if self.filesystem.remote_connection_info().is_some() {
+            if let Some(lsp) = &mut self.lsp {
+                lsp.set_remote_spawner(Some(Arc::clone(&spawner)));
+                // A working directory on this machine means nothing there
+                if !self.filesystem.is_dir(&self.working_dir).unwrap_or(false) {
+                    lsp.set_root_uri(None);
+                }
+            }
+        }
         self.process_spawner = spawner;
     }
 
@@ -1881,6 +2318,22 @@ impl Editor {
         self.filesystem.remote_connection_info()
     }
 
+    /// Remote connection of the active buffer: its own connection when it
+    /// was opened on another host, none when it was opened on this machine,
+    /// otherwise the editor's
+    pub fn active_remote_connection(&self) -> Option<&str> {
+        match self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .and_then(|m| m.remote_connection.as_deref())
+        {
+            // A file on this machine while the editor is remote
+            Some(LOCAL_CONNECTION) => None,
+            Some(connection) => Some(connection),
+            None => self.remote_connection_info(),
+        }
+    }
+
     /// Get the status log path
     pub fn get_status_log_path(&self) -> Option<&PathBuf> {
         self.status_log_path.as_ref()
@@ -1961,7 +2414,7 @@ impl Editor {
     pub fn clear_warnings(&mut self) {
         self.warning_domains.general.clear();
         self.warning_domains.lsp.clear();
-        self.status_message = Some("Warnings cleared".to_string());
+        self.show_status_message("Warnings cleared".to_string());
     }
 
     /// Check if any LSP server is in error state
@@ -2200,6 +2653,9 @@ impl Editor {
             return; // No change
         }
 
+        // A restored file that wasn't read yet is read when first shown
+        self.load_deferred_buffer(buffer_id);
+
         // Dismiss transient popups and clear hover state when switching buffers
         self.on_editor_focus_lost();
 
@@ -2255,6 +2711,8 @@ impl Editor {
             "buffer_activated",
             crate::services::plugins::hooks::HookArgs::BufferActivated { buffer_id },
         );
+
+        self.run_pending_open_auto_commands(buffer_id);
     }
 
     /// Focus a split and its buffer, handling all side effects including terminal mode.
@@ -2339,6 +2797,57 @@ impl Editor {
         &mut self.split_view_states.get_mut(&split_id).unwrap().cursors
     }
 
+    /// Make the next (or previous) cursor in document order the primary one
+    /// and scroll it into view
+    pub fn rotate_primary_cursor(&mut self, forward: bool) {
+        if self.active_cursors().count() < 2 {
+            self.set_status_message(t!("status.single_cursor").to_string());
+            return;
+        }
+        self.active_cursors_mut().rotate_primary(forward);
+
+        let active_split = self.split_manager.active_split();
+        let active_buffer = self.active_buffer();
+        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+            let state = self.buffers.get_mut(&active_buffer).unwrap();
+            view_state.ensure_cursor_visible(&mut state.buffer, &state.marker_list);
+        }
+
+        let cursors = self.active_cursors();
+        let message = t!(
+            "status.primary_cursor",
+            index = cursors.primary_index() + 1,
+            count = cursors.count()
+        );
+        self.set_status_message(message.to_string());
+    }
+
+    /// Report the byte, character, word and line count of the selection(s),
+    /// aggregated across all cursors
+    pub fn show_selection_info(&mut self) {
+        let cursors = self.active_cursors().clone();
+        let stats = self.active_state_mut().selection_stats(&cursors);
+        let message = match stats.selections {
+            0 => t!("selection_info.none"),
+            1 => t!(
+                "selection_info.summary",
+                bytes = stats.bytes,
+                chars = stats.chars,
+                words = stats.words,
+                lines = stats.lines
+            ),
+            count => t!(
+                "selection_info.summary_multi",
+                count = count,
+                bytes = stats.bytes,
+                chars = stats.chars,
+                words = stats.words,
+                lines = stats.lines
+            ),
+        };
+        self.set_status_message(message.to_string());
+    }
+
     /// Set completion items for type-to-filter (for testing)
     pub fn set_completion_items(&mut self, items: Vec<lsp_types::CompletionItem>) {
         self.completion_items = Some(items);
@@ -3143,6 +3652,27 @@ impl Editor {
         self.event_logs.get_mut(&self.active_buffer()).unwrap()
     }
 
+    /// Fire `buffer_modified_changed` for buffers whose modified state
+    /// flipped since the last check. Newly opened buffers are only recorded.
+    fn fire_buffer_modified_changed(&mut self) {
+        let mut changed = Vec::new();
+        for (buffer_id, state) in &self.buffers {
+            let modified = state.buffer.is_modified();
+            if self.reported_modified.insert(*buffer_id, modified) == Some(!modified) {
+                changed.push((*buffer_id, modified));
+            }
+        }
+        for (buffer_id, modified) in changed {
+            self.plugin_manager.run_hook(
+                "buffer_modified_changed",
+                crate::services::plugins::hooks::HookArgs::BufferModifiedChanged {
+                    buffer_id,
+                    modified,
+                },
+            );
+        }
+    }
+
     /// Update the buffer's modified flag based on event log position
     /// Call this after undo/redo to correctly track whether the buffer
     /// has returned to its saved state
@@ -3272,7 +3802,42 @@ impl Editor {
     }
 
     /// Request the editor to quit
+    ///
+    /// If plugins handle `before_quit`, the hook runs first and the quit
+    /// continues once it has finished, unless a handler cancelled it. Asking
+    /// to quit again while the hook is still running quits without waiting.
     pub fn quit(&mut self) {
+        if !self.before_quit_pending && self.plugin_manager.has_hook_handlers("before_quit") {
+            self.before_quit_pending = true;
+            self.quit_cancelled_by_plugin = false;
+            self.plugin_manager.run_hook(
+                "before_quit",
+                crate::services::plugins::hooks::HookArgs::BeforeQuit {
+                    modified_count: self.count_modified_buffers_needing_prompt(),
+                },
+            );
+            return;
+        }
+        self.before_quit_pending = false;
+        self.quit_after_plugins();
+    }
+
+    /// Continue a quit once `before_quit` handlers have run
+    fn handle_before_quit_completed(&mut self) {
+        if !self.before_quit_pending {
+            return;
+        }
+        self.before_quit_pending = false;
+        if self.quit_cancelled_by_plugin {
+            self.quit_cancelled_by_plugin = false;
+            self.set_status_message(t!("status.quit_cancelled_by_plugin").to_string());
+        } else {
+            self.quit_after_plugins();
+        }
+    }
+
+    /// Quit, asking about unsaved changes first
+    fn quit_after_plugins(&mut self) {
         // Check for unsaved file-backed buffers (unnamed buffers are auto-persisted
         // when persist_unnamed_buffers is enabled)
         let modified_count = self.count_modified_buffers_needing_prompt();
@@ -3915,7 +4480,7 @@ impl Editor {
         // Determine prompt type and reset appropriate history navigation
         if let Some(ref prompt) = self.prompt {
             // Reset history navigation for this prompt type
-            if let Some(key) = Self::prompt_type_to_history_key(&prompt.prompt_type) {
+            if let Some(key) = Self::prompt_history_key(prompt) {
                 if let Some(history) = self.prompt_histories.get_mut(&key) {
                     history.reset_navigation();
                 }
@@ -3942,6 +4507,12 @@ impl Editor {
                     };
                     self.apply_event_to_active_buffer(&remove_overlay_event);
                 }
+                PromptType::ApplySettingsBundle => {
+                    self.pending_settings_import = None;
+                }
+                PromptType::ConfirmLspDownload { language } => {
+                    self.lsp_downloads_declined.insert(language.clone());
+                }
                 PromptType::OpenFile | PromptType::SwitchProject | PromptType::SaveFileAs => {
                     // Clear file browser state
                     self.file_open_state = None;
@@ -3960,7 +4531,7 @@ impl Editor {
 
         self.prompt = None;
         self.pending_search_range = None;
-        self.status_message = Some(t!("search.cancelled").to_string());
+        self.show_status_message(t!("search.cancelled").to_string());
 
         // Restore original theme if we were in SelectTheme prompt
         if let Some(original_theme) = theme_to_restore {
@@ -4026,6 +4597,12 @@ impl Editor {
                     | PromptType::SelectTheme { .. }
                     | PromptType::SelectLocale
                     | PromptType::SwitchToTab
+                    | PromptType::JumpToNamedBookmark
+                    | PromptType::BookmarkPicker
+                    | PromptType::RecentFiles
+                    | PromptType::FuzzyFinder
+                    | PromptType::DiffBuffers { .. }
+                    | PromptType::ManageWorkspaceTrust
                     | PromptType::SetLanguage
                     | PromptType::SetEncoding
                     | PromptType::SetLineEnding
@@ -4043,7 +4620,7 @@ impl Editor {
                                     .unwrap()
                                     .record_usage(&suggestion.text);
                             }
-                            self.set_status_message(
+                            self.set_important_status_message(
                                 t!(
                                     "error.command_not_available",
                                     command = suggestion.text.clone()
@@ -4073,7 +4650,7 @@ impl Editor {
                 if !is_valid {
                     // Restore the prompt and don't confirm
                     self.prompt = Some(prompt);
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("error.no_lsp_match", input = final_input.clone()).to_string(),
                     );
                     return None;
@@ -4143,18 +4720,30 @@ impl Editor {
     fn prompt_type_to_history_key(prompt_type: &crate::view::prompt::PromptType) -> Option<String> {
         use crate::view::prompt::PromptType;
         match prompt_type {
-            PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
-                Some("search".to_string())
-            }
-            PromptType::Replace { .. } | PromptType::QueryReplace { .. } => {
-                Some("replace".to_string())
-            }
+            PromptType::Search
+            | PromptType::ReplaceSearch
+            | PromptType::QueryReplaceSearch
+            | PromptType::ProjectSearch
+            | PromptType::ProjectReplaceSearch => Some("search".to_string()),
+            PromptType::Replace { .. }
+            | PromptType::QueryReplace { .. }
+            | PromptType::ProjectReplace { .. } => Some("replace".to_string()),
             PromptType::GotoLine => Some("goto_line".to_string()),
+            PromptType::Command => Some("command".to_string()),
             PromptType::Plugin { custom_type } => Some(format!("plugin:{}", custom_type)),
             _ => None,
         }
     }
 
+    /// Get the history key for a prompt. Quick Open's `>` command mode
+    /// shares the command palette's history.
+    fn prompt_history_key(prompt: &Prompt) -> Option<String> {
+        if prompt.recalls_commands() {
+            return Some("command".to_string());
+        }
+        Self::prompt_type_to_history_key(&prompt.prompt_type)
+    }
+
     /// Get the current global editor mode (e.g., "vi-normal", "vi-insert")
     /// Returns None if no special mode is active
     pub fn editor_mode(&self) -> Option<String> {
@@ -4199,10 +4788,36 @@ impl Editor {
     /// Set a status message to display in the status bar
     pub fn set_status_message(&mut self, message: String) {
         tracing::info!(target: "status", "{}", message);
+        self.record_status_message(&message, None, false);
+        self.plugin_status_message = None;
+        self.status_message = Some(message);
+    }
+
+    /// Set a status message that is tagged as important (an error or a
+    /// conflict) in the `*Messages*` transcript
+    pub fn set_important_status_message(&mut self, message: String) {
+        tracing::info!(target: "status", "{}", message);
+        self.record_status_message(&message, None, true);
         self.plugin_status_message = None;
         self.status_message = Some(message);
     }
 
+    /// Set a status message shown next to the plugin status message,
+    /// which stays in place
+    pub(crate) fn show_status_message(&mut self, message: String) {
+        tracing::info!(target: "status", "{}", message);
+        self.record_status_message(&message, None, false);
+        self.status_message = Some(message);
+    }
+
+    /// Like [`Self::show_status_message`], tagged as important in the
+    /// `*Messages*` transcript
+    pub(crate) fn show_important_status_message(&mut self, message: String) {
+        tracing::info!(target: "status", "{}", message);
+        self.record_status_message(&message, None, true);
+        self.status_message = Some(message);
+    }
+
     /// Get the current status message
     pub fn get_status_message(&self) -> Option<&String> {
         self.plugin_status_message
@@ -4268,6 +4883,10 @@ impl Editor {
                 // Update Quick Open suggestions based on prefix
                 self.update_quick_open_suggestions(&input);
             }
+            PromptType::FuzzyFinder => {
+                // Scored once typing pauses, see check_fuzzy_finder_query_timer
+                self.fuzzy_finder_query_changed(&input);
+            }
             PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
                 // Update incremental search highlights as user types
                 self.update_search_highlights(&input);
@@ -4276,7 +4895,15 @@ impl Editor {
                     history.reset_navigation();
                 }
             }
-            PromptType::Replace { .. } | PromptType::QueryReplace { .. } => {
+            PromptType::ProjectSearch | PromptType::ProjectReplaceSearch => {
+                // Reset history navigation when user types - allows Up to navigate history
+                if let Some(history) = self.prompt_histories.get_mut("search") {
+                    history.reset_navigation();
+                }
+            }
+            PromptType::Replace { .. }
+            | PromptType::QueryReplace { .. }
+            | PromptType::ProjectReplace { .. } => {
                 // Reset history navigation when user types - allows Up to navigate history
                 if let Some(history) = self.prompt_histories.get_mut("replace") {
                     history.reset_navigation();
@@ -4316,6 +4943,12 @@ impl Editor {
                 }
             }
             PromptType::SwitchToTab
+            | PromptType::SetNamedBookmark
+            | PromptType::JumpToNamedBookmark
+            | PromptType::BookmarkPicker
+            | PromptType::RecentFiles
+            | PromptType::DiffBuffers { .. }
+            | PromptType::ManageWorkspaceTrust
             | PromptType::SelectTheme { .. }
             | PromptType::StopLspServer
             | PromptType::SetLanguage
@@ -4347,8 +4980,13 @@ impl Editor {
         // This ensures plugin errors surface quickly instead of causing silent hangs
         self.plugin_manager.check_thread_health();
 
+        let plugins_loaded = self.process_finished_plugin_loads();
+
+        // Verify the tutorial exercise if the tutorial buffer was edited
+        let tutorial_advanced = self.check_tutorial_progress();
+
         let Some(bridge) = &self.async_bridge else {
-            return false;
+            return tutorial_advanced || plugins_loaded;
         };
 
         let messages = {
@@ -4381,7 +5019,7 @@ impl Editor {
                         language,
                         completion_trigger_characters
                     );
-                    self.status_message = Some(format!("LSP ({}) ready", language));
+                    self.show_status_message(format!("LSP ({}) ready", language));
 
                     // Store completion trigger characters
                     if let Some(lsp) = &mut self.lsp {
@@ -4409,8 +5047,20 @@ impl Editor {
                     error,
                     stderr_log_path,
                 } => {
+                    let not_found = error.contains("not found") || error.contains("NotFound");
+                    // Downloads install on this machine, which doesn't help
+                    // a server missing on the remote host
+                    let remote = self.lsp.as_ref().is_some_and(|lsp| lsp.is_remote());
+                    // A server downloaded earlier stands in for a missing command
+                    if not_found && !remote && self.use_downloaded_lsp_server(&language) {
+                        continue;
+                    }
+
                     tracing::error!("LSP error for {}: {}", language, error);
-                    self.status_message = Some(format!("LSP error ({}): {}", language, error));
+                    self.show_important_status_message(format!(
+                        "LSP error ({}): {}",
+                        language, error
+                    ));
 
                     // Get server command from config for the hook
                     let server_command = self
@@ -4421,7 +5071,7 @@ impl Editor {
                         .unwrap_or_else(|| "unknown".to_string());
 
                     // Determine error type from error message
-                    let error_type = if error.contains("not found") || error.contains("NotFound") {
+                    let error_type = if not_found {
                         "not_found"
                     } else if error.contains("permission") || error.contains("PermissionDenied") {
                         "spawn_failed"
@@ -4443,6 +5093,10 @@ impl Editor {
                         },
                     );
 
+                    if not_found && !remote {
+                        self.offer_lsp_download(&language);
+                    }
+
                     // Open stderr log as read-only buffer if it exists and has content
                     // Opens in background (new tab) without stealing focus
                     if let Some(log_path) = stderr_log_path {
@@ -4452,7 +5106,7 @@ impl Editor {
                             match self.open_file_no_focus(&log_path) {
                                 Ok(buffer_id) => {
                                     self.mark_buffer_read_only(buffer_id, true);
-                                    self.status_message = Some(format!(
+                                    self.show_important_status_message(format!(
                                         "LSP error ({}): {} - See stderr log",
                                         language, error
                                     ));
@@ -4533,6 +5187,13 @@ impl Editor {
                 } => {
                     self.handle_lsp_folding_ranges(request_id, uri, ranges);
                 }
+                AsyncMessage::LspLatencyProbe {
+                    language: _,
+                    request_id,
+                    latency,
+                } => {
+                    self.handle_lsp_latency_probe(request_id, latency);
+                }
                 AsyncMessage::LspSemanticTokens {
                     request_id,
                     uri,
@@ -4546,9 +5207,33 @@ impl Editor {
                 AsyncMessage::LspDiagnosticRefresh { language } => {
                     self.handle_lsp_diagnostic_refresh(language);
                 }
+                AsyncMessage::LspApplyEdit { language, edit } => {
+                    self.handle_lsp_apply_edit(language, edit);
+                }
                 AsyncMessage::FileChanged { path } => {
                     self.handle_async_file_changed(path);
                 }
+                AsyncMessage::RemoteLatency {
+                    connection,
+                    round_trip,
+                } => {
+                    self.remote_latency
+                        .entry(connection)
+                        .or_default()
+                        .record(round_trip);
+                }
+                AsyncMessage::RemoteDownloadChunk { transfer_id, bytes } => {
+                    self.handle_remote_download_chunk(transfer_id, bytes);
+                }
+                AsyncMessage::RemoteUploadProgress { transfer_id, sent } => {
+                    self.handle_remote_upload_progress(transfer_id, sent);
+                }
+                AsyncMessage::RemoteTransferFinished {
+                    transfer_id,
+                    result,
+                } => {
+                    self.handle_remote_transfer_finished(transfer_id, result);
+                }
                 AsyncMessage::GitStatusChanged { status } => {
                     tracing::info!("Git status changed: {}", status);
                     // TODO: Handle git status changes
@@ -4696,6 +5381,27 @@ impl Editor {
                 AsyncMessage::FileOpenShortcutsLoaded(shortcuts) => {
                     self.handle_file_open_shortcuts_loaded(shortcuts);
                 }
+                AsyncMessage::FuzzyFinderFiles {
+                    walk_id,
+                    paths,
+                    done,
+                } => {
+                    self.handle_fuzzy_finder_files(walk_id, paths, done);
+                }
+                AsyncMessage::ProjectSearchMatches {
+                    search_id,
+                    matches,
+                    done,
+                } => {
+                    self.handle_project_search_matches(search_id, matches, done);
+                }
+                AsyncMessage::ProjectReplaceScanned {
+                    replace_id,
+                    files,
+                    read_only,
+                } => {
+                    self.handle_project_replace_scanned(replace_id, files, read_only);
+                }
                 AsyncMessage::TerminalOutput { terminal_id } => {
                     // Terminal output received - check if we should auto-jump back to terminal mode
                     tracing::trace!("Terminal output received for {:?}", terminal_id);
@@ -4805,6 +5511,33 @@ impl Editor {
                         exit_code,
                     );
                 }
+                AsyncMessage::ExternalOpenFailed {
+                    path,
+                    command,
+                    error,
+                } => {
+                    self.handle_external_open_failed(&path, &command, &error);
+                }
+                AsyncMessage::UrlDownloadProgress {
+                    url,
+                    received,
+                    total,
+                } => {
+                    self.handle_url_download_progress(&url, received, total);
+                }
+                AsyncMessage::UrlDownloaded { url, result } => {
+                    self.handle_url_downloaded(&url, result);
+                }
+                AsyncMessage::LspDownloadProgress {
+                    language,
+                    received,
+                    total,
+                } => {
+                    self.handle_lsp_download_progress(&language, received, total);
+                }
+                AsyncMessage::LspDownloaded { language, result } => {
+                    self.handle_lsp_downloaded(&language, result);
+                }
                 AsyncMessage::GrammarRegistryBuilt {
                     registry,
                     callback_ids,
@@ -4815,6 +5548,9 @@ impl Editor {
                     );
                     self.grammar_registry = registry;
                     self.grammar_build_in_progress = false;
+                    if callback_ids.is_empty() {
+                        self.record_background_startup_phase("grammar build");
+                    }
 
                     // Re-detect syntax for all open buffers with the full registry
                     let buffers_to_update: Vec<_> = self
@@ -4849,6 +5585,7 @@ impl Editor {
 
                     // Flush any plugin grammars that arrived during the build
                     self.flush_pending_grammars();
+                    self.finish_grammar_reload();
                 }
             }
         }
@@ -4861,6 +5598,9 @@ impl Editor {
             self.update_plugin_state_snapshot();
         }
 
+        // Tell plugins about buffers that became modified or unmodified
+        self.fire_buffer_modified_changed();
+
         // Process TypeScript plugin commands
         let processed_any_commands = {
             let _s = tracing::info_span!("process_plugin_commands").entered();
@@ -4914,9 +5654,19 @@ impl Editor {
             let _s = tracing::info_span!("poll_file_tree_changes").entered();
             self.poll_file_tree_changes()
         };
+        {
+            let _s = tracing::info_span!("poll_grammar_changes").entered();
+            self.poll_grammar_changes();
+        }
 
         // Trigger render if any async messages, plugin commands were processed, or plugin requested render
-        needs_render || processed_any_commands || plugin_render || file_changes || tree_changes
+        needs_render
+            || processed_any_commands
+            || plugin_render
+            || file_changes
+            || tree_changes
+            || tutorial_advanced
+            || plugins_loaded
     }
 
     /// Update LSP status bar string from active progress operations
@@ -5019,6 +5769,12 @@ impl Editor {
                 let compose_width = active_vs
                     .and_then(|vs| vs.buffer_state(*buffer_id))
                     .and_then(|bs| bs.compose_width);
+                let event_log = self.event_logs.get(buffer_id);
+                let last_saved_at = self
+                    .buffer_saved_at
+                    .get(buffer_id)
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|since_epoch| since_epoch.as_millis() as u64);
                 let is_composing_in_any_split = self.split_view_states.values().any(|vs| {
                     vs.buffer_state(*buffer_id)
                         .map(|bs| matches!(bs.view_mode, crate::state::ViewMode::Compose))
@@ -5034,6 +5790,9 @@ impl Editor {
                     is_composing_in_any_split,
                     compose_width,
                     language: state.language.clone(),
+                    undo_depth: event_log.map_or(0, |log| log.undo_depth()),
+                    redo_depth: event_log.map_or(0, |log| log.redo_depth()),
+                    last_saved_at,
                 };
                 snapshot.buffers.insert(*buffer_id, buffer_info);
 
@@ -5143,6 +5902,18 @@ impl Editor {
             // Update editor mode (for vi mode and other modal editing)
             snapshot.editor_mode = self.editor_mode.clone();
 
+            // Update Back/Forward navigation history (10 most recent entries)
+            snapshot.position_history = self
+                .position_history
+                .recent(10)
+                .into_iter()
+                .map(|(entry, current)| fresh_core::api::PositionHistoryEntry {
+                    buffer_id: entry.buffer_id,
+                    position: entry.position,
+                    current,
+                })
+                .collect();
+
             // Update plugin view states from active split's BufferViewState.plugin_state.
             // If the active split changed, fully repopulate. Otherwise, merge using
             // or_insert to preserve JS-side write-through entries that haven't
@@ -5448,8 +6219,17 @@ impl Editor {
             PluginCommand::RefreshAllLines => {
                 self.handle_refresh_all_lines();
             }
-            PluginCommand::HookCompleted { .. } => {
-                // Sentinel processed in render loop; no-op if encountered elsewhere.
+            PluginCommand::CancelQuit => {
+                if self.before_quit_pending {
+                    self.quit_cancelled_by_plugin = true;
+                }
+            }
+            PluginCommand::HookCompleted { hook_name } => {
+                // Sentinel processed in render loop; only the end of a
+                // `before_quit` hook is acted on here.
+                if hook_name == "before_quit" {
+                    self.handle_before_quit_completed();
+                }
             }
             PluginCommand::SetLineIndicator {
                 buffer_id,
@@ -5490,8 +6270,11 @@ impl Editor {
             }
 
             // ==================== Status/Prompt Commands ====================
-            PluginCommand::SetStatus { message } => {
-                self.handle_set_status(message);
+            PluginCommand::SetStatus {
+                message,
+                plugin_name,
+            } => {
+                self.handle_set_status(message, plugin_name);
             }
             PluginCommand::ApplyTheme { theme_name } => {
                 self.apply_theme(&theme_name);
@@ -5721,6 +6504,7 @@ impl Editor {
                     let handle = runtime.spawn(async move {
                         let mut child = match TokioCommand::new(&command)
                             .args(&args)
+                            .envs(crate::services::shell_env::current().vars())
                             .current_dir(&effective_cwd)
                             .stdout(std::process::Stdio::piped())
                             .stderr(std::process::Stdio::piped())
@@ -6311,12 +7095,12 @@ impl Editor {
                 // 3. Persist the config change
                 if let Err(e) = self.save_config() {
                     tracing::error!("Failed to save config: {}", e);
-                    self.status_message = Some(format!(
+                    self.show_important_status_message(format!(
                         "LSP disabled for {} (config save failed)",
                         language
                     ));
                 } else {
-                    self.status_message = Some(format!("LSP disabled for {}", language));
+                    self.show_status_message(format!("LSP disabled for {}", language));
                 }
 
                 // 4. Clear any LSP-related warnings for this language
@@ -6328,10 +7112,10 @@ impl Editor {
 
                 let success = if let Some(ref mut lsp) = self.lsp {
                     let (ok, msg) = lsp.manual_restart(&language);
-                    self.status_message = Some(msg);
+                    self.show_status_message(msg);
                     ok
                 } else {
-                    self.status_message = Some("No LSP manager available".to_string());
+                    self.show_status_message("No LSP manager available".to_string());
                     false
                 };
 
@@ -6349,19 +7133,18 @@ impl Editor {
                         if let Some(ref mut lsp) = self.lsp {
                             let restarted = lsp.set_language_root_uri(&language, parsed_uri);
                             if restarted {
-                                self.status_message = Some(format!(
+                                self.show_status_message(format!(
                                     "LSP root updated for {} (restarting server)",
                                     language
                                 ));
                             } else {
-                                self.status_message =
-                                    Some(format!("LSP root set for {}", language));
+                                self.show_status_message(format!("LSP root set for {}", language));
                             }
                         }
                     }
                     Err(e) => {
                         tracing::error!("Invalid LSP root URI '{}': {}", uri, e);
-                        self.status_message = Some(format!("Invalid LSP root URI: {}", e));
+                        self.show_important_status_message(format!("Invalid LSP root URI: {}", e));
                     }
                 }
             }
@@ -6709,12 +7492,12 @@ impl Editor {
                     tracing::debug!("Saved buffer {:?} to path", buffer_id);
                 }
                 Err(e) => {
-                    self.handle_set_status(format!("Error saving: {}", e));
+                    self.handle_set_status(format!("Error saving: {}", e), None);
                     tracing::error!("Failed to save buffer to path: {}", e);
                 }
             }
         } else {
-            self.handle_set_status(format!("Buffer {:?} not found", buffer_id));
+            self.handle_set_status(format!("Buffer {:?} not found", buffer_id), None);
             tracing::warn!("SaveBufferToPath: buffer {:?} not found", buffer_id);
         }
     }
@@ -7156,6 +7939,47 @@ mod tests {
         assert!(!editor.should_quit());
     }
 
+    #[test]
+    fn test_plugin_menu_items_survive_menu_rebuild() {
+        use crate::config::MenuItem;
+        use fresh_core::api::MenuPosition;
+
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            Config::default(),
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+        let item = MenuItem::Action {
+            label: "Plugin Item".to_string(),
+            action: "plugin_action".to_string(),
+            args: HashMap::new(),
+            when: None,
+            checkbox: None,
+        };
+        let has_item = |editor: &Editor| {
+            editor.menus.menus.iter().any(|m| {
+                m.id.as_deref() == Some("File")
+                    && matches!(m.items.first(), Some(MenuItem::Action { label, .. }) if label == "Plugin Item")
+            })
+        };
+
+        // Menus are addressable by id regardless of the translated label
+        editor.handle_add_menu_item("File".to_string(), item, MenuPosition::Top);
+        assert!(has_item(&editor));
+
+        editor.rebuild_menus();
+        assert!(has_item(&editor));
+
+        editor.handle_remove_menu_item("File".to_string(), "Plugin Item".to_string());
+        editor.rebuild_menus();
+        assert!(!has_item(&editor));
+    }
+
     #[test]
     fn test_new_buffer() {
         let config = Config::default();
@@ -8188,6 +9012,7 @@ mod tests {
             case_sensitive: false,
             whole_word: false,
             use_regex: false,
+            published: 0,
         });
 
         // process_search_scan should finalize the search (not loop forever)
@@ -8216,6 +9041,129 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_search_wraps_and_counts_matches() {
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+
+        let cursor_id = editor.active_cursors().primary_id();
+        editor.apply_event_to_active_buffer(&Event::Insert {
+            position: 0,
+            text: "foo bar foo baz foo".to_string(),
+            cursor_id,
+        });
+
+        // The cursor is past the last match, so the search starts over
+        editor.perform_search("foo");
+        assert_eq!(editor.search_match_count().as_deref(), Some("1/3"));
+
+        editor.find_next();
+        editor.find_next();
+        assert_eq!(editor.search_match_count().as_deref(), Some("3/3"));
+        assert_eq!(
+            editor.get_status_message().map(String::as_str),
+            Some("Match 3 of 3")
+        );
+
+        editor.find_next();
+        assert_eq!(editor.search_match_count().as_deref(), Some("1/3"));
+        assert_eq!(editor.active_cursors().primary().position, 0);
+        assert_eq!(
+            editor.get_status_message().map(String::as_str),
+            Some("Search wrapped: match 1 of 3")
+        );
+
+        editor.find_previous();
+        assert_eq!(editor.search_match_count().as_deref(), Some("3/3"));
+        assert_eq!(editor.active_cursors().primary().position, 16);
+        assert_eq!(
+            editor.get_status_message().map(String::as_str),
+            Some("Search wrapped: match 3 of 3")
+        );
+
+        editor.clear_search_highlights();
+        assert_eq!(editor.search_match_count(), None);
+    }
+
+    #[test]
+    fn test_search_scan_publishes_matches_before_finishing() {
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+
+        // A scan halfway through a large file, with matches after the cursor
+        let buffer_id = editor.active_buffer();
+        let chunk = |leaf_index| crate::model::buffer::LineScanChunk {
+            leaf_index,
+            byte_len: 100,
+            already_known: true,
+        };
+        let found = |byte_offset| crate::model::buffer::SearchMatch {
+            byte_offset,
+            length: 4,
+            line: 1,
+            column: byte_offset + 1,
+            context: String::new(),
+        };
+        editor.search_scan_state = Some(SearchScanState {
+            buffer_id,
+            leaves: Vec::new(),
+            scan: crate::model::buffer::ChunkedSearchState {
+                chunks: vec![chunk(0), chunk(1)],
+                next_chunk: 1,
+                next_doc_offset: 100,
+                total_bytes: 200,
+                scanned_bytes: 100,
+                regex: regex::bytes::Regex::new("test").unwrap(),
+                matches: vec![found(10), found(50)],
+                overlap_tail: Vec::new(),
+                overlap_doc_offset: 0,
+                max_matches: 10_000,
+                capped: false,
+                query_len: 4,
+                running_line: 1,
+            },
+            query: "test".to_string(),
+            search_range: None,
+            case_sensitive: false,
+            whole_word: false,
+            use_regex: false,
+            published: 0,
+        });
+
+        editor.publish_search_scan_matches();
+        assert_eq!(editor.search_match_count().as_deref(), Some("1/2+"));
+
+        // More matches and the end of the scan make the count final
+        let scan = editor.search_scan_state.as_mut().unwrap();
+        scan.scan.matches.push(found(150));
+        scan.scan.next_chunk = 2;
+        assert!(editor.process_search_scan());
+        assert!(editor.search_scan_state.is_none());
+        assert_eq!(editor.search_match_count().as_deref(), Some("1/3"));
+        assert_eq!(
+            editor.get_status_message().map(String::as_str),
+            Some("Found 3 matches for 'test'")
+        );
+    }
+
     #[test]
     fn test_bookmarks() {
         let config = Config::default();
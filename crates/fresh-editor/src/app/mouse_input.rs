@@ -17,12 +17,27 @@ use crate::view::ui::tabs::TabHit;
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
 
+/// Columns the tab bar scrolls per wheel tick or scroll-arrow click
+const TAB_SCROLL_STEP: usize = 10;
+
 impl Editor {
     /// Handle a mouse event.
     /// Returns true if a re-render is needed.
     pub fn handle_mouse(
         &mut self,
         mouse_event: crossterm::event::MouseEvent,
+    ) -> AnyhowResult<bool> {
+        self.handle_mouse_with_ticks(mouse_event, 1)
+    }
+
+    /// Handle a mouse event that stands for `ticks` identical wheel events
+    /// coalesced by the event loop. Wheel scrolling is multiplied by `ticks`;
+    /// other events ignore it.
+    /// Returns true if a re-render is needed.
+    pub fn handle_mouse_with_ticks(
+        &mut self,
+        mouse_event: crossterm::event::MouseEvent,
+        ticks: u16,
     ) -> AnyhowResult<bool> {
         use crossterm::event::{MouseButton, MouseEventKind};
 
@@ -69,6 +84,22 @@ impl Editor {
                 (false, false)
             };
 
+        // Alt+click hands the mouse back to the terminal for native selection.
+        // The press is held until we know whether it becomes an Alt+drag, which
+        // makes a block selection instead.
+        if matches!(mouse_event.kind, MouseEventKind::Down(MouseButton::Left)) {
+            self.mouse_state.alt_press = None;
+            if mouse_event
+                .modifiers
+                .contains(crossterm::event::KeyModifiers::ALT)
+                && self.config.editor.mouse.alt_bypasses_capture
+                && self.mouse_enabled
+            {
+                self.mouse_state.alt_press = Some((col, row));
+                return Ok(false);
+            }
+        }
+
         // When keybinding editor is open, capture all mouse events
         if self.keybinding_editor.is_some() {
             return self.handle_keybinding_editor_mouse(mouse_event);
@@ -151,6 +182,10 @@ impl Editor {
             }
         }
 
+        let ticks = i32::from(ticks.max(1));
+        let scroll_lines = self.config.editor.mouse.scroll_lines as i32 * ticks;
+        let scroll_columns = self.config.editor.mouse.scroll_columns as i32 * ticks;
+
         match mouse_event.kind {
             MouseEventKind::Down(MouseButton::Left) => {
                 if is_double_click || is_triple_click {
@@ -178,10 +213,18 @@ impl Editor {
                 needs_render = true;
             }
             MouseEventKind::Drag(MouseButton::Left) => {
+                if let Some((press_col, press_row)) = self.mouse_state.alt_press.take() {
+                    self.handle_mouse_click(press_col, press_row, mouse_event.modifiers)?;
+                }
                 self.handle_mouse_drag(col, row)?;
                 needs_render = true;
             }
             MouseEventKind::Up(MouseButton::Left) => {
+                if self.mouse_state.alt_press.take().is_some() {
+                    self.suspend_mouse_capture();
+                    return Ok(true);
+                }
+
                 // Check if we were dragging a separator to trigger terminal resize
                 let was_dragging_separator = self.mouse_state.dragging_separator.is_some();
 
@@ -214,6 +257,7 @@ impl Editor {
                 self.mouse_state.dragging_text_selection = false;
                 self.mouse_state.drag_selection_split = None;
                 self.mouse_state.drag_selection_anchor = None;
+                self.mouse_state.drag_selection_block = false;
                 // Clear popup scrollbar drag state
                 self.mouse_state.dragging_popup_scrollbar = None;
                 self.mouse_state.drag_start_popup_scroll = None;
@@ -284,20 +328,25 @@ impl Editor {
                     .modifiers
                     .contains(crossterm::event::KeyModifiers::SHIFT)
                 {
-                    self.handle_horizontal_scroll(col, row, -3)?;
+                    if !self.handle_tab_bar_scroll(col, row, -ticks) {
+                        self.handle_horizontal_scroll(col, row, -scroll_columns)?;
+                    }
                     needs_render = true;
-                } else if self.handle_prompt_scroll(-3) {
+                } else if self.handle_tab_bar_scroll(col, row, -ticks) {
+                    // Wheel over a tab bar scrolls the tabs
+                    needs_render = true;
+                } else if self.handle_prompt_scroll(-scroll_lines) {
                     // Check if prompt with suggestions is active and should handle scroll
                     needs_render = true;
                 } else if self.is_file_open_active()
                     && self.is_mouse_over_file_browser(col, row)
-                    && self.handle_file_open_scroll(-3)
+                    && self.handle_file_open_scroll(-scroll_lines)
                 {
                     // Check if file browser is active and mouse is over it
                     needs_render = true;
                 } else if self.is_mouse_over_any_popup(col, row) {
                     // Scroll the popup content (works for all popups including completion)
-                    self.scroll_popup(-3);
+                    self.scroll_popup(-scroll_lines);
                     needs_render = true;
                 } else {
                     // If in terminal mode, exit to scrollback mode first so scrolling works
@@ -308,7 +357,7 @@ impl Editor {
                     }
                     // Dismiss hover/signature help popups on scroll
                     self.dismiss_transient_popups();
-                    self.handle_mouse_scroll(col, row, -3)?;
+                    self.handle_mouse_wheel(col, row, -scroll_lines)?;
                     // Sync viewport from SplitViewState to EditorState so rendering sees the scroll
                     needs_render = true;
                 }
@@ -319,19 +368,24 @@ impl Editor {
                     .modifiers
                     .contains(crossterm::event::KeyModifiers::SHIFT)
                 {
-                    self.handle_horizontal_scroll(col, row, 3)?;
+                    if !self.handle_tab_bar_scroll(col, row, ticks) {
+                        self.handle_horizontal_scroll(col, row, scroll_columns)?;
+                    }
                     needs_render = true;
-                } else if self.handle_prompt_scroll(3) {
+                } else if self.handle_tab_bar_scroll(col, row, ticks) {
+                    // Wheel over a tab bar scrolls the tabs
+                    needs_render = true;
+                } else if self.handle_prompt_scroll(scroll_lines) {
                     // Check if prompt with suggestions is active and should handle scroll
                     needs_render = true;
                 } else if self.is_file_open_active()
                     && self.is_mouse_over_file_browser(col, row)
-                    && self.handle_file_open_scroll(3)
+                    && self.handle_file_open_scroll(scroll_lines)
                 {
                     needs_render = true;
                 } else if self.is_mouse_over_any_popup(col, row) {
                     // Scroll the popup content (works for all popups including completion)
-                    self.scroll_popup(3);
+                    self.scroll_popup(scroll_lines);
                     needs_render = true;
                 } else {
                     // If in terminal mode, exit to scrollback mode first so scrolling works
@@ -342,19 +396,23 @@ impl Editor {
                     }
                     // Dismiss hover/signature help popups on scroll
                     self.dismiss_transient_popups();
-                    self.handle_mouse_scroll(col, row, 3)?;
+                    self.handle_mouse_wheel(col, row, scroll_lines)?;
                     // Sync viewport from SplitViewState to EditorState so rendering sees the scroll
                     needs_render = true;
                 }
             }
             MouseEventKind::ScrollLeft => {
                 // Native horizontal scroll left
-                self.handle_horizontal_scroll(col, row, -3)?;
+                if !self.handle_tab_bar_scroll(col, row, -ticks) {
+                    self.handle_horizontal_scroll(col, row, -scroll_columns)?;
+                }
                 needs_render = true;
             }
             MouseEventKind::ScrollRight => {
                 // Native horizontal scroll right
-                self.handle_horizontal_scroll(col, row, 3)?;
+                if !self.handle_tab_bar_scroll(col, row, ticks) {
+                    self.handle_horizontal_scroll(col, row, scroll_columns)?;
+                }
                 needs_render = true;
             }
             MouseEventKind::Down(MouseButton::Right) => {
@@ -1058,6 +1116,73 @@ impl Editor {
         Ok(())
     }
 
+    /// Scroll the tab bar under the mouse by `ticks` steps (negative = left).
+    /// Returns false if the mouse is not over a tab bar.
+    fn handle_tab_bar_scroll(&mut self, col: u16, row: u16, ticks: i32) -> bool {
+        let Some(split_id) = self
+            .cached_layout
+            .tab_layouts
+            .iter()
+            .find(|(_, tab_layout)| {
+                let area = tab_layout.bar_area;
+                col >= area.x
+                    && col < area.x + area.width
+                    && row >= area.y
+                    && row < area.y + area.height
+            })
+            .map(|(split_id, _)| *split_id)
+        else {
+            return false;
+        };
+        if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+            let step = TAB_SCROLL_STEP * ticks.unsigned_abs() as usize;
+            view_state.tab_scroll_offset = if ticks < 0 {
+                view_state.tab_scroll_offset.saturating_sub(step)
+            } else {
+                view_state.tab_scroll_offset.saturating_add(step)
+            };
+        }
+        true
+    }
+
+    /// The split a wheel event at (col, row) scrolls: the split under the
+    /// mouse with `mouse.scroll_hovered_split`, otherwise the focused one.
+    pub(super) fn wheel_target_split(&self, col: u16, row: u16) -> (LeafId, BufferId) {
+        if self.config.editor.mouse.scroll_hovered_split {
+            let hovered = self.cached_layout.split_areas.iter().find(
+                |(_, _, content_rect, scrollbar_rect, _, _)| {
+                    [content_rect, scrollbar_rect].iter().any(|rect| {
+                        col >= rect.x
+                            && col < rect.x + rect.width
+                            && row >= rect.y
+                            && row < rect.y + rect.height
+                    })
+                },
+            );
+            if let Some((split_id, buffer_id, _, _, _, _)) = hovered {
+                return (*split_id, *buffer_id);
+            }
+        }
+        (self.split_manager.active_split(), self.active_buffer())
+    }
+
+    /// Handle a vertical wheel step over the editor: scroll the viewport, or
+    /// move the cursor when `mouse.wheel_moves_cursor` is enabled
+    fn handle_mouse_wheel(&mut self, col: u16, row: u16, delta: i32) -> AnyhowResult<()> {
+        if !self.config.editor.mouse.wheel_moves_cursor {
+            return self.handle_mouse_scroll(col, row, delta);
+        }
+        let action = if delta < 0 {
+            Action::MoveUp
+        } else {
+            Action::MoveDown
+        };
+        for _ in 0..delta.unsigned_abs() {
+            self.handle_action(action.clone())?;
+        }
+        Ok(())
+    }
+
     /// Handle double-click in editor content area - selects the word under cursor
     fn handle_editor_double_click(
         &mut self,
@@ -1072,6 +1197,10 @@ impl Editor {
         // Focus this split
         self.focus_split(split_id, buffer_id);
 
+        if !self.config.editor.mouse.click_moves_cursor {
+            return Ok(());
+        }
+
         // Get cached view line mappings for this split
         let cached_mappings = self
             .cached_layout
@@ -1195,6 +1324,10 @@ impl Editor {
         // Focus this split
         self.focus_split(split_id, buffer_id);
 
+        if !self.config.editor.mouse.click_moves_cursor {
+            return Ok(());
+        }
+
         // Get cached view line mappings for this split
         let cached_mappings = self
             .cached_layout
@@ -1396,7 +1529,7 @@ impl Editor {
                     // Open the URL in the default browser
                     #[cfg(feature = "runtime")]
                     if let Err(e) = open::that(&url) {
-                        self.set_status_message(format!("Failed to open URL: {}", e));
+                        self.set_important_status_message(format!("Failed to open URL: {}", e));
                     } else {
                         self.set_status_message(format!("Opening: {}", url));
                     }
@@ -1763,7 +1896,7 @@ impl Editor {
 
         if let Some(split_id) = close_split_click {
             if let Err(e) = self.split_manager.close_split(split_id) {
-                self.set_status_message(
+                self.set_important_status_message(
                     t!("error.cannot_close_split", error = e.to_string()).to_string(),
                 );
             } else {
@@ -1852,7 +1985,7 @@ impl Editor {
                     self.set_status_message("ScrollLeft clicked!".to_string());
                     if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
                         view_state.tab_scroll_offset =
-                            view_state.tab_scroll_offset.saturating_sub(10);
+                            view_state.tab_scroll_offset.saturating_sub(TAB_SCROLL_STEP);
                     }
                     return Ok(());
                 }
@@ -1861,7 +1994,7 @@ impl Editor {
                     self.set_status_message("ScrollRight clicked!".to_string());
                     if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
                         view_state.tab_scroll_offset =
-                            view_state.tab_scroll_offset.saturating_add(10);
+                            view_state.tab_scroll_offset.saturating_add(TAB_SCROLL_STEP);
                     }
                     return Ok(());
                 }
@@ -2183,6 +2316,12 @@ impl Editor {
                 .offset_to_position(target_position)
                 .map(|pos| pos.column)
                 .unwrap_or(old_sticky_column);
+            // Alt+drag: the rectangle's corner is the clicked line and column
+            let block_anchor = self.mouse_state.drag_selection_block.then(|| {
+                let line = state.buffer.get_line_number(anchor_position);
+                let line_start = state.buffer.line_start_offset(line).unwrap_or(0);
+                (line, anchor_position.saturating_sub(line_start))
+            });
             let event = Event::MoveCursor {
                 cursor_id: primary_cursor_id,
                 old_position,
@@ -2202,6 +2341,9 @@ impl Editor {
                 .map(|vs| &mut vs.cursors)
             {
                 state.apply(cursors, &event);
+                if let Some((line, column)) = block_anchor {
+                    cursors.primary_mut().start_block_selection(line, column);
+                }
             }
         }
 
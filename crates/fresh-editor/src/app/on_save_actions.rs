@@ -3,14 +3,17 @@
 //! This module handles running configured actions when files are saved,
 //! such as formatters, linters, and other tools.
 
+use std::collections::BTreeMap;
 use std::io::Write;
 use std::path::Path;
 use std::process::{Command, Stdio};
 use std::time::Duration;
 
 use super::Editor;
-use crate::config::{FormatterConfig, OnSaveAction};
-use crate::model::event::Event;
+use crate::config::{BufferConfig, FormatterConfig, OnSaveAction};
+use crate::model::buffer::LineEnding;
+use crate::model::event::{CursorId, Event};
+use crate::primitives::content_anchor::Relocator;
 use rust_i18n::t;
 
 /// Result of running a formatter or on-save action
@@ -36,23 +39,6 @@ impl Editor {
 
         let mut ran_any_action = false;
 
-        // Run whitespace cleanup actions first (before formatter)
-        if self.config.editor.trim_trailing_whitespace_on_save && self.trim_trailing_whitespace()? {
-            ran_any_action = true;
-        }
-
-        if self.config.editor.ensure_final_newline_on_save && self.ensure_final_newline()? {
-            ran_any_action = true;
-        }
-
-        // If whitespace cleanup made changes, re-save
-        if ran_any_action {
-            if let Err(e) = self.active_state_mut().buffer.save() {
-                return Err(format!("Failed to re-save after whitespace cleanup: {}", e));
-            }
-            self.active_event_log_mut().mark_saved();
-        }
-
         // Get language from buffer's stored state
         let language = self.active_state().language.clone();
 
@@ -61,6 +47,18 @@ impl Editor {
             None => return Ok(ran_any_action),
         };
 
+        // Commands that run on save need confirmation in an untrusted workspace
+        let runs_commands = (lang_config.format_on_save && lang_config.formatter.is_some())
+            || lang_config.on_save.iter().any(|action| action.enabled);
+        if runs_commands && !self.workspace_trust.is_trusted() && !self.on_save_commands_allowed {
+            if self.pending_trust_confirmation.is_none() {
+                self.show_trust_confirmation(
+                    super::workspace_trust::TrustConfirmation::OnSaveCommands,
+                );
+            }
+            return Ok(ran_any_action);
+        }
+
         // Run formatter if format_on_save is enabled
         if lang_config.format_on_save {
             if let Some(ref formatter) = lang_config.formatter {
@@ -75,7 +73,7 @@ impl Editor {
                         ran_any_action = true;
                     }
                     ActionResult::CommandNotFound(cmd) => {
-                        self.status_message = Some(format!(
+                        self.show_important_status_message(format!(
                             "Formatter '{}' not found (install it for auto-formatting)",
                             cmd
                         ));
@@ -161,7 +159,9 @@ impl Editor {
         let file_path_str = file_path.display().to_string();
 
         // Check if command exists
-        if !command_exists(&formatter.command) {
+        let process_env = crate::services::shell_env::current();
+        let env = process_env.vars_with(&formatter.env);
+        if !command_exists(&formatter.command, &env) {
             return ActionResult::CommandNotFound(formatter.command.clone());
         }
 
@@ -183,6 +183,7 @@ impl Editor {
         // Set up the command
         let mut cmd = Command::new(&shell);
         cmd.args(["-c", &full_command])
+            .envs(&env)
             .current_dir(&project_root)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
@@ -283,7 +284,9 @@ impl Editor {
         let file_path_str = file_path.display().to_string();
 
         // Check if command exists
-        if !command_exists(&action.command) {
+        let process_env = crate::services::shell_env::current();
+        let env = process_env.vars_with(&action.env);
+        if !command_exists(&action.command, &env) {
             return ActionResult::CommandNotFound(action.command.clone());
         }
 
@@ -316,6 +319,7 @@ impl Editor {
         // Set up the command
         let mut cmd = Command::new(&shell);
         cmd.args(["-c", &full_command])
+            .envs(&env)
             .current_dir(&working_dir)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
@@ -421,6 +425,19 @@ impl Editor {
         let old_cursor_pos = self.active_cursors().primary().position;
         let old_anchor = self.active_cursors().primary().anchor;
         let old_sticky_column = self.active_cursors().primary().sticky_column;
+        let active_split = self.split_manager.active_split();
+        let old_top_byte = self
+            .split_view_states
+            .get(&active_split)
+            .map(|vs| vs.viewport.top_byte)
+            .unwrap_or(0);
+
+        // Follow the cursor's line and the top visible line into the formatted
+        // text, so reformatting code above doesn't jump the view
+        let relocator = Relocator::new(&buffer_content, output);
+        let new_cursor_pos = relocator.relocate(old_cursor_pos);
+        let new_anchor = old_anchor.map(|a| relocator.relocate(a));
+        let new_top_byte = relocator.relocate(old_top_byte);
 
         // Delete all content and insert new
         let delete_event = Event::Delete {
@@ -435,9 +452,7 @@ impl Editor {
         };
 
         // After delete+insert, cursor will be at output.len()
-        // Restore cursor to original position (or clamp to new buffer length)
         let new_buffer_len = output.len();
-        let new_cursor_pos = old_cursor_pos.min(new_buffer_len);
 
         // Only add MoveCursor event if position actually changes
         let mut events = vec![delete_event, insert_event];
@@ -447,7 +462,7 @@ impl Editor {
                 old_position: new_buffer_len, // Where cursor is after insert
                 new_position: new_cursor_pos,
                 old_anchor: None,
-                new_anchor: old_anchor.map(|a| a.min(new_buffer_len)),
+                new_anchor,
                 old_sticky_column: 0,
                 new_sticky_column: old_sticky_column,
             };
@@ -462,63 +477,249 @@ impl Editor {
         self.active_event_log_mut().append(batch.clone());
         self.apply_event_to_active_buffer(&batch);
 
+        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+            view_state.viewport.top_byte = new_top_byte;
+        }
+
         Ok(())
     }
 
     /// Trim trailing whitespace from all lines in the active buffer.
     /// Returns Ok(true) if any changes were made, Ok(false) if buffer unchanged.
     pub fn trim_trailing_whitespace(&mut self) -> Result<bool, String> {
-        let content = self.active_state().buffer.to_string().unwrap_or_default();
-
-        // Process each line and trim trailing whitespace
-        let trimmed: String = content
-            .lines()
-            .map(|line| line.trim_end())
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        // Preserve original trailing newline if present
-        let trimmed = if content.ends_with('\n') && !trimmed.ends_with('\n') {
-            format!("{}\n", trimmed)
-        } else {
-            trimmed
-        };
+        Ok(self.delete_trailing_whitespace(false))
+    }
 
-        if trimmed == content {
-            return Ok(false);
+    /// Trim trailing whitespace before the active buffer is written, if
+    /// `trim_trailing_whitespace_on_save` is on for its language. Binary and
+    /// large-file buffers are left alone.
+    pub(super) fn trim_trailing_whitespace_before_save(&mut self) -> bool {
+        let state = self.active_state();
+        if state.buffer.is_binary() || state.buffer.is_large_file() {
+            return false;
         }
+        let buffer_config = BufferConfig::resolve(&self.config, Some(&state.language));
+        buffer_config.trim_trailing_whitespace_on_save
+            && !self.is_editing_disabled()
+            && self.delete_trailing_whitespace(true)
+    }
 
-        self.replace_buffer_with_output(&trimmed)?;
-        Ok(true)
+    /// Delete the trailing whitespace of every line as one undo step. With
+    /// `keep_cursor_lines`, whitespace a cursor is inside or after is kept, so
+    /// saving mid-edit doesn't eat the space just typed. Returns true if
+    /// anything was deleted.
+    fn delete_trailing_whitespace(&mut self, keep_cursor_lines: bool) -> bool {
+        let Some(content) = self.active_state().buffer.to_string() else {
+            return false;
+        };
+        let cursors: Vec<_> = self
+            .active_cursors()
+            .iter()
+            .map(|(id, c)| (id, c.position, c.anchor, c.sticky_column))
+            .collect();
+
+        let mut ranges = Vec::new();
+        let mut line_start = 0;
+        for line in content.split_inclusive('\n') {
+            let text = line.trim_end_matches(['\n', '\r']);
+            let end = line_start + text.len();
+            let start = line_start + text.trim_end().len();
+            let in_use = keep_cursor_lines
+                && cursors
+                    .iter()
+                    .any(|&(_, pos, _, _)| pos > start && pos <= end);
+            if start < end && !in_use {
+                ranges.push(start..end);
+            }
+            line_start += line.len();
+        }
+        if ranges.is_empty() {
+            return false;
+        }
+
+        // Where a position ends up once the ranges are gone
+        let relocate = |pos: usize| {
+            pos - ranges
+                .iter()
+                .map(|r| pos.min(r.end) - pos.min(r.start))
+                .sum::<usize>()
+        };
+        let primary_id = self.active_cursors().primary_id();
+        let mut events: Vec<Event> = ranges
+            .iter()
+            .map(|range| Event::Delete {
+                range: range.clone(),
+                deleted_text: content[range.clone()].to_string(),
+                cursor_id: primary_id,
+            })
+            .collect();
+        events.extend(
+            cursors.iter().map(
+                |&(cursor_id, position, anchor, sticky_column)| Event::MoveCursor {
+                    cursor_id,
+                    old_position: position,
+                    new_position: relocate(position),
+                    old_anchor: anchor,
+                    new_anchor: anchor.map(relocate),
+                    old_sticky_column: sticky_column,
+                    new_sticky_column: sticky_column,
+                },
+            ),
+        );
+
+        if let Some(bulk_edit) =
+            self.apply_events_as_bulk_edit(events, "Trim trailing whitespace".to_string())
+        {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+        true
     }
 
     /// Ensure the buffer ends with a newline.
     /// Returns Ok(true) if a newline was added, Ok(false) if already ends with newline.
     pub fn ensure_final_newline(&mut self) -> Result<bool, String> {
-        let content = self.active_state().buffer.to_string().unwrap_or_default();
+        let len = self.active_state().buffer.len();
 
         // Empty buffers don't need a newline
-        if content.is_empty() {
+        if len == 0 {
             return Ok(false);
         }
 
-        if content.ends_with('\n') {
+        let last_byte = self
+            .active_state_mut()
+            .buffer
+            .get_text_range_mut(len - 1, 1)
+            .map_err(|e| e.to_string())?;
+        if matches!(last_byte.as_slice(), [b'\n'] | [b'\r']) {
             return Ok(false);
         }
 
-        let with_newline = format!("{}\n", content);
-        self.replace_buffer_with_output(&with_newline)?;
+        // Append the file's own line ending, leaving the cursor where it was
+        let line_ending = self.active_state().buffer.line_ending().as_str();
+        let cursor_id = self.active_cursors().primary_id();
+        let cursor = *self.active_cursors().primary();
+        let batch = Event::Batch {
+            events: vec![
+                Event::Insert {
+                    position: len,
+                    text: line_ending.to_string(),
+                    cursor_id,
+                },
+                Event::MoveCursor {
+                    cursor_id,
+                    old_position: len + line_ending.len(),
+                    new_position: cursor.position,
+                    old_anchor: None,
+                    new_anchor: cursor.anchor,
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: cursor.sticky_column,
+                },
+            ],
+            description: "Ensure final newline".to_string(),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
         Ok(true)
     }
+
+    /// Add a missing final newline before the active buffer is written, if
+    /// `ensure_final_newline_on_save` is on. Binary buffers are left alone.
+    pub(super) fn ensure_final_newline_before_save(&mut self) {
+        if !self.config.editor.ensure_final_newline_on_save
+            || self.active_state().buffer.is_binary()
+            || self.is_editing_disabled()
+        {
+            return;
+        }
+        if let Err(e) = self.ensure_final_newline() {
+            tracing::warn!("Failed to add final newline before saving: {}", e);
+        }
+    }
+
+    /// Switch the active buffer to `line_ending`, rewriting every line break
+    /// that differs, as a single undo step that also restores the old line
+    /// ending. Large and binary files are only converted when saved.
+    pub fn convert_line_endings(&mut self, line_ending: LineEnding) {
+        if self.is_editing_disabled() {
+            self.active_state_mut().buffer.set_line_ending(line_ending);
+            return;
+        }
+
+        let state = self.active_state();
+        let old_snapshot = state.buffer.snapshot_buffer_state();
+        let text = if state.buffer.is_large_file() || state.buffer.is_binary() {
+            None
+        } else {
+            state.buffer.to_string()
+        };
+        let target = line_ending.as_str();
+        let mut events = Vec::new();
+        if let Some(text) = &text {
+            let bytes = text.as_bytes();
+            let mut pos = 0;
+            while pos < bytes.len() {
+                let len = match bytes[pos] {
+                    b'\r' if bytes.get(pos + 1) == Some(&b'\n') => 2,
+                    b'\r' | b'\n' => 1,
+                    _ => {
+                        pos += 1;
+                        continue;
+                    }
+                };
+                let old = &text[pos..pos + len];
+                if old != target {
+                    // Cursors aren't moved by the edits themselves; the bulk
+                    // edit shifts them past the breaks before them
+                    events.push(Event::Delete {
+                        range: pos..pos + len,
+                        deleted_text: old.to_string(),
+                        cursor_id: CursorId::UNDO_SENTINEL,
+                    });
+                    events.push(Event::Insert {
+                        position: pos,
+                        text: target.to_string(),
+                        cursor_id: CursorId::UNDO_SENTINEL,
+                    });
+                }
+                pos += len;
+            }
+        }
+
+        let description = "Convert line endings".to_string();
+        let mut bulk_edit = self
+            .apply_events_as_bulk_edit(events, description.clone())
+            .unwrap_or_else(|| {
+                // No line break to rewrite; the step only changes the ending
+                let cursors: Vec<_> = self
+                    .active_cursors()
+                    .iter()
+                    .map(|(id, c)| (id, c.position, c.anchor))
+                    .collect();
+                Event::BulkEdit {
+                    old_snapshot: Some(old_snapshot),
+                    new_snapshot: None,
+                    old_cursors: cursors.clone(),
+                    new_cursors: cursors,
+                    description,
+                }
+            });
+        let buffer = &mut self.active_state_mut().buffer;
+        buffer.set_line_ending(line_ending);
+        if let Event::BulkEdit { new_snapshot, .. } = &mut bulk_edit {
+            *new_snapshot = Some(buffer.snapshot_buffer_state());
+        }
+        self.active_event_log_mut().append(bulk_edit);
+    }
 }
 
-/// Check if a command exists in the system PATH.
-fn command_exists(command: &str) -> bool {
+/// Check if a command exists on the PATH of a child process with `env`.
+fn command_exists(command: &str, env: &BTreeMap<String, String>) -> bool {
     // Use 'which' on Unix or 'where' on Windows to check if command exists
     #[cfg(unix)]
     {
         Command::new("which")
             .arg(command)
+            .envs(env)
             .stdout(Stdio::null())
             .stderr(Stdio::null())
             .status()
@@ -530,6 +731,7 @@ fn command_exists(command: &str) -> bool {
     {
         Command::new("where")
             .arg(command)
+            .envs(env)
             .stdout(Stdio::null())
             .stderr(Stdio::null())
             .status()
@@ -540,6 +742,7 @@ fn command_exists(command: &str) -> bool {
     #[cfg(not(any(unix, windows)))]
     {
         // On other platforms, assume command exists and let it fail at runtime
+        let _ = env;
         true
     }
 }
@@ -0,0 +1,150 @@
+//! Opening files with external programs.
+//!
+//! `open_external` in the config maps globs to programs (e.g. images to an
+//! image viewer). Files opened from the command line, the file explorer, the
+//! Open File dialog, Quick Open or a plugin are handed to the matching
+//! program instead of being loaded as text; "Open as Text" loads the last
+//! such file in the editor anyway.
+//!
+//! Programs run through the editor's process spawner. On a remote host
+//! there is no local display to show them on, so matching files are refused
+//! and can only be opened as text.
+
+use std::path::Path;
+
+use super::Editor;
+use crate::config::OpenExternalRule;
+use crate::primitives::glob_match::file_glob_matches;
+use crate::services::async_bridge::AsyncMessage;
+use rust_i18n::t;
+
+impl Editor {
+    /// The enabled `open_external` rule matching `path`, if any
+    fn open_external_rule(&self, path: &Path) -> Option<OpenExternalRule> {
+        let path_str = path.to_string_lossy();
+        self.config
+            .open_external
+            .iter()
+            .find(|rule| rule.enabled && file_glob_matches(&rule.pattern, &path_str))
+            .cloned()
+    }
+
+    /// Hand `path` to its `open_external` program instead of loading it as
+    /// text. Returns false (and does nothing) when no rule applies, the file
+    /// doesn't exist, or it is already open in a buffer.
+    pub(crate) fn try_open_externally(&mut self, path: &Path) -> bool {
+        let Some(rule) = self.open_external_rule(path) else {
+            return false;
+        };
+        let is_remote = self.filesystem.remote_connection_info().is_some();
+        if !is_remote && !path.is_file() {
+            return false;
+        }
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let already_open = self.buffers.values().any(|state| {
+            state
+                .buffer
+                .file_path()
+                .is_some_and(|p| p == path || p == canonical)
+        });
+        if already_open {
+            return false;
+        }
+
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        self.last_external_open = Some(path.to_path_buf());
+
+        if is_remote {
+            self.set_status_message(t!("open_external.remote", file = &name).to_string());
+            return true;
+        }
+
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            self.set_important_status_message(
+                t!(
+                    "open_external.failed",
+                    file = &name,
+                    command = &rule.command,
+                    error = "async runtime not available"
+                )
+                .to_string(),
+            );
+            return true;
+        };
+
+        let mut args = rule.args.clone();
+        args.push(path.to_string_lossy().into_owned());
+        let cwd = Some(self.working_dir.to_string_lossy().into_owned());
+        let sender = bridge.sender();
+        let spawner = self.process_spawner.clone();
+        let command = rule.command.clone();
+        let path = path.to_path_buf();
+        tracing::info!("Opening {:?} with {}", path, command);
+
+        runtime.spawn(async move {
+            let error = match spawner.spawn(command.clone(), args, cwd).await {
+                Ok(result) if result.exit_code == 0 => return,
+                Ok(result) if !result.stderr.trim().is_empty() => result.stderr.trim().to_string(),
+                Ok(result) => format!("exit code {}", result.exit_code),
+                Err(e) => e.to_string(),
+            };
+            // Receiver may be dropped if editor is shutting down
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = sender.send(AsyncMessage::ExternalOpenFailed {
+                path,
+                command,
+                error,
+            });
+        });
+
+        self.set_status_message(
+            t!(
+                "open_external.opened",
+                file = &name,
+                command = &rule.command
+            )
+            .to_string(),
+        );
+        true
+    }
+
+    /// Report a program started by an `open_external` rule that failed
+    pub(super) fn handle_external_open_failed(&mut self, path: &Path, command: &str, error: &str) {
+        tracing::warn!("Failed to open {:?} with {}: {}", path, command, error);
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        self.set_important_status_message(
+            t!(
+                "open_external.failed",
+                file = &name,
+                command = command,
+                error = error
+            )
+            .to_string(),
+        );
+    }
+
+    /// Load the last file handed to an external program as text
+    pub fn open_last_external_as_text(&mut self) {
+        let Some(path) = self.last_external_open.take() else {
+            self.set_status_message(t!("open_external.none").to_string());
+            return;
+        };
+        if let Err(e) = self.open_file(&path) {
+            if let Some(confirmation) =
+                e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
+            {
+                self.start_large_file_encoding_confirmation(confirmation);
+            } else {
+                self.set_important_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+            }
+        }
+    }
+}
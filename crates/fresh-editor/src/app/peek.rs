@@ -0,0 +1,427 @@
+//! Peek Definition / Peek References.
+//!
+//! Shows the result of a definition or references request in a read-only,
+//! syntax-highlighted popup below the cursor instead of navigating to it.
+//! The peeked file is loaded like any other buffer but kept out of the
+//! buffer list and tabs; it is dropped as soon as the popup closes. Enter
+//! turns the peek into a real jump (recorded in the position history).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ratatui::style::Style;
+use rust_i18n::t;
+
+use super::{uri_to_path, Editor};
+use crate::state::EditorState;
+use crate::view::markdown::StyledLine;
+use crate::view::popup::{Popup, PopupContent, PopupKind, PopupPosition};
+
+/// Rows of code shown in the peek popup
+const PEEK_ROWS: usize = 12;
+
+/// Lines of context shown above the peeked line
+const PEEK_CONTEXT_ABOVE: usize = 3;
+
+/// Maximum width of the location list shown when there are several locations
+const PEEK_SIDEBAR_MAX_WIDTH: usize = 32;
+
+/// Which request a pending peek is waiting for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PeekKind {
+    Definition,
+    References,
+}
+
+/// A location shown in the peek popup (0-based LSP line and character)
+struct PeekLocation {
+    path: PathBuf,
+    line: usize,
+    character: usize,
+}
+
+/// State behind the open peek popup
+pub(crate) struct PeekView {
+    locations: Vec<PeekLocation>,
+    selected: usize,
+    /// First line of the peeked file shown in the popup
+    top_line: usize,
+    /// Files loaded only for peeking (files already open use their buffer)
+    loaded: HashMap<PathBuf, EditorState>,
+}
+
+impl Editor {
+    /// Request the definition under the cursor and show it in a peek popup
+    pub(crate) fn peek_definition(&mut self) -> anyhow::Result<()> {
+        self.request_goto_definition()?;
+        if self.pending_goto_definition_request.is_some() {
+            self.pending_peek = Some(PeekKind::Definition);
+        }
+        Ok(())
+    }
+
+    /// Request the references to the symbol under the cursor and show them in
+    /// a peek popup
+    pub(crate) fn peek_references(&mut self) -> anyhow::Result<()> {
+        self.request_references()?;
+        if self.pending_references_request.is_some() {
+            self.pending_peek = Some(PeekKind::References);
+        }
+        Ok(())
+    }
+
+    /// If the response to a pending request of `kind` should be peeked
+    /// rather than navigated to, consume the pending peek and return true.
+    pub(crate) fn take_pending_peek(&mut self, kind: PeekKind) -> bool {
+        if self.pending_peek == Some(kind) {
+            self.pending_peek = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Open the peek popup for the locations returned by the server
+    pub(crate) fn show_peek(&mut self, kind: PeekKind, locations: Vec<lsp_types::Location>) {
+        let locations: Vec<PeekLocation> = locations
+            .iter()
+            .filter_map(|loc| {
+                uri_to_path(&loc.uri).ok().map(|path| PeekLocation {
+                    path,
+                    line: loc.range.start.line as usize,
+                    character: loc.range.start.character as usize,
+                })
+            })
+            .collect();
+
+        if locations.is_empty() {
+            let message = match kind {
+                PeekKind::Definition => t!("lsp.no_definition"),
+                PeekKind::References => t!("lsp.no_references"),
+            };
+            self.set_status_message(message.to_string());
+            return;
+        }
+
+        let top_line = locations[0].line.saturating_sub(PEEK_CONTEXT_ABOVE);
+        self.peek = Some(PeekView {
+            locations,
+            selected: 0,
+            top_line,
+            loaded: HashMap::new(),
+        });
+        self.refresh_peek_popup();
+    }
+
+    /// Scroll the peeked file by `delta` lines
+    pub(crate) fn scroll_peek(&mut self, delta: i32) {
+        let Some(peek) = self.peek.as_mut() else {
+            return;
+        };
+        peek.top_line = peek.top_line.saturating_add_signed(delta as isize);
+        self.refresh_peek_popup();
+    }
+
+    /// Switch the peek popup to another location, wrapping around
+    pub(crate) fn select_peek_location(&mut self, delta: i32) {
+        let Some(peek) = self.peek.as_mut() else {
+            return;
+        };
+        let count = peek.locations.len() as isize;
+        peek.selected = (peek.selected as isize + delta as isize).rem_euclid(count) as usize;
+        peek.top_line = peek.locations[peek.selected]
+            .line
+            .saturating_sub(PEEK_CONTEXT_ABOVE);
+        self.refresh_peek_popup();
+    }
+
+    /// Whether the topmost popup of the active buffer is the peek popup
+    pub(crate) fn is_peek_popup_active(&self) -> bool {
+        self.active_state()
+            .popups
+            .top()
+            .is_some_and(|p| p.kind == PopupKind::Peek)
+    }
+
+    /// Close the peek popup and jump to the location it shows
+    pub(crate) fn promote_peek(&mut self) {
+        let Some(peek) = self.peek.take() else {
+            self.hide_popup();
+            return;
+        };
+        self.hide_popup();
+        let location = &peek.locations[peek.selected];
+
+        // Record where we came from, even when the jump stays in this buffer
+        self.position_history.commit_pending_movement();
+        let cursors = self.active_cursors();
+        let position = cursors.primary().position;
+        let anchor = cursors.primary().anchor;
+        self.position_history
+            .record_movement(self.active_buffer(), position, anchor);
+        self.position_history.commit_pending_movement();
+
+        if let Err(e) = self.open_file(&location.path) {
+            if let Some(confirmation) =
+                e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
+            {
+                self.start_large_file_encoding_confirmation(confirmation);
+            } else {
+                self.set_important_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+            }
+            return;
+        }
+        let offset = self
+            .active_state()
+            .buffer
+            .lsp_position_to_byte(location.line, location.character);
+        self.goto_byte_offset(offset);
+        self.set_status_message(
+            t!(
+                "lsp.jumped_to_location",
+                path = location.path.display().to_string(),
+                line = location.line + 1
+            )
+            .to_string(),
+        );
+    }
+
+    /// Load the file for the selected location if no open buffer has it
+    fn load_peek_file(&mut self) -> anyhow::Result<()> {
+        let Some(peek) = self.peek.as_ref() else {
+            return Ok(());
+        };
+        let path = peek.locations[peek.selected].path.clone();
+        if peek.loaded.contains_key(&path) || self.open_buffer_for_path(&path).is_some() {
+            return Ok(());
+        }
+
+        let buffer = crate::model::buffer::Buffer::load_from_file(
+            &path,
+            self.config.editor.large_file_threshold_bytes as usize,
+            Arc::clone(&self.filesystem),
+        )?;
+        let detected = crate::primitives::detected_language::DetectedLanguage::from_path(
+            &path,
+            &self.grammar_registry,
+            &self.config.languages,
+        );
+        let state = EditorState::from_buffer_with_language(buffer, detected);
+        if let Some(peek) = self.peek.as_mut() {
+            peek.loaded.insert(path, state);
+        }
+        Ok(())
+    }
+
+    fn open_buffer_for_path(&self, path: &Path) -> Option<crate::model::event::BufferId> {
+        self.buffers
+            .iter()
+            .find(|(_, state)| state.buffer.file_path() == Some(path))
+            .map(|(id, _)| *id)
+    }
+
+    /// Rebuild the peek popup for the current location and scroll position,
+    /// showing it if it isn't already the topmost popup
+    fn refresh_peek_popup(&mut self) {
+        if let Err(e) = self.load_peek_file() {
+            self.peek = None;
+            self.set_important_status_message(
+                t!("file.error_opening", error = e.to_string()).to_string(),
+            );
+            return;
+        }
+        let Some((title, lines)) = self.build_peek_lines() else {
+            return;
+        };
+
+        if self.is_peek_popup_active() {
+            if let Some(popup) = self.active_state_mut().popups.top_mut() {
+                popup.title = Some(title);
+                popup.content = PopupContent::Markdown(lines);
+            }
+            return;
+        }
+
+        let mut popup = Popup::text(Vec::new(), &self.theme)
+            .with_kind(PopupKind::Peek)
+            .with_title(title)
+            .with_position(PopupPosition::BelowCursor)
+            .with_width(self.terminal_width)
+            .with_max_height(PEEK_ROWS as u16 + 2);
+        popup.content = PopupContent::Markdown(lines);
+        let buffer_id = self.active_buffer();
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.popups.show(popup);
+        }
+    }
+
+    /// Render the visible part of the peeked file (plus the location list
+    /// when there are several locations) as styled popup lines
+    fn build_peek_lines(&mut self) -> Option<(String, Vec<StyledLine>)> {
+        // Leave room for the border and the popup's scrollbar gutter so rows
+        // are never wrapped
+        let inner_width = (self.terminal_width as usize).saturating_sub(4);
+        let context_bytes = self.config.editor.highlight_context_bytes;
+        let theme = &self.theme;
+        let working_dir = &self.working_dir;
+        let peek = self.peek.as_mut()?;
+        let location = &peek.locations[peek.selected];
+        let state = match self
+            .buffers
+            .values_mut()
+            .find(|state| state.buffer.file_path() == Some(location.path.as_path()))
+        {
+            Some(state) => state,
+            None => peek.loaded.get_mut(&location.path)?,
+        };
+
+        // Clamp scrolling to the file
+        if let Some(line_count) = state.buffer.line_count() {
+            let max_top = line_count.saturating_sub(PEEK_ROWS);
+            peek.top_line = peek.top_line.min(max_top);
+        }
+        let top_line = peek.top_line;
+        let start = state.buffer.line_col_to_position(top_line, 0);
+        let end = state.buffer.line_col_to_position(top_line + PEEK_ROWS, 0);
+        let bytes = state
+            .buffer
+            .get_text_range_mut(start, end.saturating_sub(start))
+            .ok()?;
+        let text = String::from_utf8_lossy(&bytes).into_owned();
+        let spans =
+            state
+                .highlighter
+                .highlight_viewport(&state.buffer, start, end, theme, context_bytes);
+        let tab_size = state.buffer_settings.tab_size.max(1);
+
+        let display_path = |path: &Path| {
+            path.strip_prefix(working_dir)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        };
+        let title = if peek.locations.len() > 1 {
+            t!(
+                "lsp.peek_title_multi",
+                path = display_path(&location.path),
+                line = location.line + 1,
+                index = peek.selected + 1,
+                count = peek.locations.len()
+            )
+        } else {
+            t!(
+                "lsp.peek_title",
+                path = display_path(&location.path),
+                line = location.line + 1
+            )
+        }
+        .to_string();
+
+        // Location list labels, shown as a sidebar when there are several
+        let labels: Vec<String> = if peek.locations.len() > 1 {
+            peek.locations
+                .iter()
+                .map(|loc| {
+                    let name = loc
+                        .path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    format!(" {}:{}", name, loc.line + 1)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let sidebar_width = labels
+            .iter()
+            .map(|l| l.chars().count() + 1)
+            .max()
+            .unwrap_or(0)
+            .min(PEEK_SIDEBAR_MAX_WIDTH)
+            .min(inner_width / 3);
+        let first_label = peek.selected.saturating_sub(PEEK_ROWS - 1);
+
+        let gutter_width = (top_line + PEEK_ROWS).to_string().len();
+        let text_style = Style::default().fg(theme.popup_text_fg);
+        let mut lines = Vec::with_capacity(PEEK_ROWS);
+        let mut line_offset = 0;
+        let mut source_lines = text.split_inclusive('\n');
+        for row in 0..PEEK_ROWS {
+            let mut styled = StyledLine::new();
+
+            if sidebar_width > 0 {
+                let index = first_label + row;
+                let label: String = labels
+                    .get(index)
+                    .map(|l| l.chars().take(sidebar_width).collect())
+                    .unwrap_or_default();
+                let style = if index == peek.selected {
+                    text_style.bg(theme.popup_selection_bg)
+                } else {
+                    text_style
+                };
+                styled.push(format!("{:<sidebar_width$}", label), style);
+                styled.push("│".to_string(), Style::default().fg(theme.popup_border_fg));
+            }
+
+            let Some(source) = source_lines.next() else {
+                lines.push(styled);
+                continue;
+            };
+            let line_number = top_line + row;
+            let is_target = line_number == location.line;
+            let line_bg = is_target.then_some(theme.current_line_bg);
+            let with_bg = |style: Style| match line_bg {
+                Some(bg) => style.bg(bg),
+                None => style,
+            };
+
+            styled.push(
+                format!("{:>gutter_width$} ", line_number + 1),
+                with_bg(Style::default().fg(theme.line_number_fg)),
+            );
+
+            let code_width = inner_width.saturating_sub(sidebar_width + 1 + gutter_width + 1);
+            let mut col = 0;
+            let mut run = String::new();
+            let mut run_style = text_style;
+            for (i, ch) in source.char_indices() {
+                if ch == '\n' || ch == '\r' || col >= code_width {
+                    break;
+                }
+                let offset = start + line_offset + i;
+                let style = spans
+                    .iter()
+                    .find(|span| span.range.contains(&offset))
+                    .map_or(text_style, |span| text_style.fg(span.color));
+                if style != run_style && !run.is_empty() {
+                    styled.push(std::mem::take(&mut run), with_bg(run_style));
+                }
+                run_style = style;
+                if ch == '\t' {
+                    let width = (tab_size - col % tab_size).min(code_width - col);
+                    run.extend(std::iter::repeat_n(' ', width));
+                    col += width;
+                } else {
+                    run.push(ch);
+                    col += 1;
+                }
+            }
+            if !run.is_empty() {
+                styled.push(run, with_bg(run_style));
+            }
+            if is_target && col < code_width {
+                // Fill the rest of the row so the peeked line stands out
+                styled.push(" ".repeat(code_width - col), with_bg(text_style));
+            }
+            line_offset += source.len();
+            lines.push(styled);
+        }
+
+        Some((title, lines))
+    }
+}
@@ -0,0 +1,524 @@
+//! "Diagnose Buffer Performance".
+//!
+//! Collects what usually makes a buffer slow — its size and longest line,
+//! the stage timings of its last render, how many overlays, virtual texts
+//! and markers it carries, the degradation modes in effect and the
+//! round-trip latency of its language server — and shows them in a popup
+//! with the most likely cause on top. The LSP latency arrives
+//! asynchronously, so the popup is shown right away and refreshed once the
+//! probe answers.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::state::{EditorState, RenderTimings};
+
+/// Overlays (or virtual texts) in one namespace before they are suspected
+const DECORATION_THRESHOLD: usize = 5_000;
+
+/// Render stage time that is noticeably slow for a single frame
+const SLOW_STAGE: Duration = Duration::from_millis(50);
+
+/// LSP round-trip time after which edits feel laggy
+const SLOW_LSP: Duration = Duration::from_millis(500);
+
+/// Round-trip latency of the buffer's language server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LspLatency {
+    /// No server is running for the buffer
+    NotRunning,
+    /// Probe sent, waiting for the answer
+    Measuring,
+    Measured(Duration),
+    Failed(String),
+}
+
+/// Most likely reason the buffer is slow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SuspectedCause {
+    Overlays { namespace: String, count: usize },
+    VirtualTexts { namespace: String, count: usize },
+    LongLine { length: usize },
+    SlowHighlight(Duration),
+    SlowLayout(Duration),
+    SlowDecorations(Duration),
+    SlowLsp(Duration),
+}
+
+/// Snapshot of the active buffer taken by "Diagnose Buffer Performance"
+#[derive(Debug, Clone)]
+pub(crate) struct BufferPerfReport {
+    pub buffer_id: BufferId,
+    pub name: String,
+    pub bytes: usize,
+    /// None when the line count is unknown (large files without a line index)
+    pub lines: Option<usize>,
+    /// None when the buffer was not scanned (large files)
+    pub longest_line: Option<usize>,
+    pub backend: String,
+    pub timings: RenderTimings,
+    /// Overlay counts by namespace ("" for overlays without one)
+    pub overlays: BTreeMap<String, usize>,
+    /// Virtual text counts by namespace ("" for entries without one)
+    pub virtual_texts: BTreeMap<String, usize>,
+    pub markers: usize,
+    pub large_file: bool,
+    pub long_lines: bool,
+    /// Line length that turns on long-line mode (0 = disabled)
+    pub long_line_threshold: usize,
+    /// LSP request id of the latency probe, if one was sent
+    pub probe_request_id: Option<u64>,
+    pub lsp: LspLatency,
+}
+
+impl BufferPerfReport {
+    fn collect(buffer_id: BufferId, name: String, state: &EditorState) -> Self {
+        let large_file = state.buffer.is_large_file();
+        let longest_line = if large_file {
+            None
+        } else {
+            state
+                .buffer
+                .to_string()
+                .map(|text| text.split('\n').map(str::len).max().unwrap_or(0))
+        };
+
+        let mut overlays = BTreeMap::new();
+        for overlay in state.overlays.all() {
+            let namespace = overlay
+                .namespace
+                .as_ref()
+                .map(|ns| ns.0.clone())
+                .unwrap_or_default();
+            *overlays.entry(namespace).or_insert(0) += 1;
+        }
+        let mut virtual_texts = BTreeMap::new();
+        for vtext in state.virtual_texts.iter() {
+            let namespace = vtext
+                .namespace
+                .as_ref()
+                .map(|ns| ns.as_str().to_string())
+                .unwrap_or_default();
+            *virtual_texts.entry(namespace).or_insert(0) += 1;
+        }
+
+        Self {
+            buffer_id,
+            name,
+            bytes: state.buffer.len(),
+            lines: state.buffer.line_count(),
+            longest_line,
+            backend: state.highlighter.backend_name().to_string(),
+            timings: state.render_timings,
+            overlays,
+            virtual_texts,
+            markers: state.marker_list.marker_count(),
+            large_file,
+            long_lines: state.long_lines,
+            long_line_threshold: 0,
+            probe_request_id: None,
+            lsp: LspLatency::NotRunning,
+        }
+    }
+
+    /// The candidate that exceeds its threshold by the widest margin
+    pub fn suspected_cause(&self) -> Option<SuspectedCause> {
+        let mut candidates: Vec<(f64, SuspectedCause)> = Vec::new();
+        let ratio = |value: f64, threshold: f64| value / threshold;
+
+        for (namespace, &count) in &self.overlays {
+            candidates.push((
+                ratio(count as f64, DECORATION_THRESHOLD as f64),
+                SuspectedCause::Overlays {
+                    namespace: namespace.clone(),
+                    count,
+                },
+            ));
+        }
+        for (namespace, &count) in &self.virtual_texts {
+            candidates.push((
+                ratio(count as f64, DECORATION_THRESHOLD as f64),
+                SuspectedCause::VirtualTexts {
+                    namespace: namespace.clone(),
+                    count,
+                },
+            ));
+        }
+        // Long-line mode already limits the damage once it is on
+        if let (Some(length), false, true) = (
+            self.longest_line,
+            self.long_lines,
+            self.long_line_threshold > 0,
+        ) {
+            candidates.push((
+                ratio(length as f64, self.long_line_threshold as f64),
+                SuspectedCause::LongLine { length },
+            ));
+        }
+        let slow =
+            |d: Duration, threshold: Duration| ratio(d.as_secs_f64(), threshold.as_secs_f64());
+        candidates.push((
+            slow(self.timings.highlight, SLOW_STAGE),
+            SuspectedCause::SlowHighlight(self.timings.highlight),
+        ));
+        candidates.push((
+            slow(self.timings.layout, SLOW_STAGE),
+            SuspectedCause::SlowLayout(self.timings.layout),
+        ));
+        candidates.push((
+            slow(self.timings.decorations, SLOW_STAGE),
+            SuspectedCause::SlowDecorations(self.timings.decorations),
+        ));
+        if let LspLatency::Measured(latency) = self.lsp {
+            candidates.push((slow(latency, SLOW_LSP), SuspectedCause::SlowLsp(latency)));
+        }
+
+        candidates
+            .into_iter()
+            .filter(|(score, _)| *score >= 1.0)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, cause)| cause)
+    }
+
+    /// Popup text in markdown
+    fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        match self.suspected_cause() {
+            Some(cause) => md.push_str(&format!(
+                "**{}**\n\n",
+                t!("perf.suspected_cause", cause = describe_cause(&cause))
+            )),
+            None => md.push_str(&format!("{}\n\n", t!("perf.no_suspected_cause"))),
+        }
+
+        let lines = self
+            .lines
+            .map(group_digits)
+            .unwrap_or_else(|| t!("perf.unknown").to_string());
+        let longest = self
+            .longest_line
+            .map(group_digits)
+            .unwrap_or_else(|| t!("perf.unknown").to_string());
+        md.push_str(&format!(
+            "- {}\n",
+            t!(
+                "perf.size",
+                size = super::file_open::format_size(self.bytes as u64),
+                lines = lines,
+                longest = longest
+            )
+        ));
+        md.push_str(&format!(
+            "- {}\n",
+            t!(
+                "perf.highlighting",
+                backend = self.backend,
+                time = format_duration(self.timings.highlight)
+            )
+        ));
+        md.push_str(&format!(
+            "- {}\n",
+            t!(
+                "perf.layout",
+                layout = format_duration(self.timings.layout),
+                decorations = format_duration(self.timings.decorations)
+            )
+        ));
+        md.push_str(&format!(
+            "- {}\n",
+            t!(
+                "perf.overlays",
+                count = group_digits(self.overlays.values().sum()),
+                by_namespace = by_namespace(&self.overlays)
+            )
+        ));
+        md.push_str(&format!(
+            "- {}\n",
+            t!(
+                "perf.virtual_texts",
+                count = group_digits(self.virtual_texts.values().sum()),
+                by_namespace = by_namespace(&self.virtual_texts)
+            )
+        ));
+        md.push_str(&format!(
+            "- {}\n",
+            t!("perf.markers", count = group_digits(self.markers))
+        ));
+        let lsp = match &self.lsp {
+            LspLatency::NotRunning => t!("perf.lsp_not_running").to_string(),
+            LspLatency::Measuring => t!("perf.lsp_measuring").to_string(),
+            LspLatency::Measured(latency) => format_duration(*latency),
+            LspLatency::Failed(error) => t!("perf.lsp_failed", error = error).to_string(),
+        };
+        md.push_str(&format!("- {}\n", t!("perf.lsp", latency = lsp)));
+        md.push_str(&format!(
+            "- {}\n",
+            t!(
+                "perf.modes",
+                large_file = on_off(self.large_file),
+                long_lines = on_off(self.long_lines)
+            )
+        ));
+        md.push_str(&format!("\n*{}*", t!("perf.hint_dismiss")));
+        md
+    }
+}
+
+impl Editor {
+    /// Collect performance data for the active buffer and show it in a popup
+    pub fn diagnose_buffer_performance(&mut self) {
+        let buffer_id = self.active_buffer();
+        let name = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .map(|m| m.display_name.clone())
+            .unwrap_or_default();
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        let mut report = BufferPerfReport::collect(buffer_id, name, state);
+        report.long_line_threshold = self.config.editor.long_line_threshold;
+
+        let lsp_enabled = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|m| m.lsp_enabled);
+        let language = state.language.clone();
+        if lsp_enabled {
+            if let Some(handle) = self
+                .lsp
+                .as_mut()
+                .and_then(|lsp| lsp.get_handle_mut(&language))
+            {
+                let request_id = self.next_lsp_request_id;
+                self.next_lsp_request_id += 1;
+                report.lsp = match handle.latency_probe(request_id) {
+                    Ok(()) => {
+                        report.probe_request_id = Some(request_id);
+                        LspLatency::Measuring
+                    }
+                    Err(e) => LspLatency::Failed(e),
+                };
+            }
+        }
+
+        self.show_perf_report_popup(&report, false);
+        self.perf_report = Some(report);
+    }
+
+    /// Record the answer to a latency probe and refresh the popup if it is
+    /// still open
+    pub(crate) fn handle_lsp_latency_probe(
+        &mut self,
+        request_id: u64,
+        latency: Result<Duration, String>,
+    ) {
+        let Some(mut report) = self.perf_report.take() else {
+            return;
+        };
+        if report.probe_request_id != Some(request_id) {
+            self.perf_report = Some(report);
+            return;
+        }
+        report.lsp = match latency {
+            Ok(latency) => LspLatency::Measured(latency),
+            Err(error) => LspLatency::Failed(error),
+        };
+        self.show_perf_report_popup(&report, true);
+        self.perf_report = Some(report);
+    }
+
+    /// Show the report, or with `replace_only` update it only if its popup
+    /// is still on top
+    fn show_perf_report_popup(&mut self, report: &BufferPerfReport, replace_only: bool) {
+        use crate::view::popup::{Popup, PopupPosition};
+        use ratatui::style::Style;
+
+        let title = t!("perf.title", name = report.name).to_string();
+        let Some(state) = self.buffers.get(&report.buffer_id) else {
+            return;
+        };
+        let showing = state.popups.top().and_then(|p| p.title.as_deref()) == Some(title.as_str());
+        if replace_only && !showing {
+            return;
+        }
+
+        let mut popup = Popup::markdown(
+            &report.to_markdown(),
+            &self.theme,
+            Some(&self.grammar_registry),
+        );
+        popup.title = Some(title);
+        popup.transient = false;
+        popup.position = PopupPosition::Centered;
+        popup.width = 80;
+        popup.max_height = 20;
+        popup.border_style = Style::default().fg(self.theme.popup_border_fg);
+        popup.background_style = Style::default().bg(self.theme.popup_bg);
+
+        if let Some(state) = self.buffers.get_mut(&report.buffer_id) {
+            if showing {
+                state.popups.hide();
+            }
+            state.popups.show(popup);
+        }
+    }
+}
+
+fn describe_cause(cause: &SuspectedCause) -> String {
+    match cause {
+        SuspectedCause::Overlays { namespace, count } if namespace == "search" => {
+            t!("perf.cause_search_overlays", count = group_digits(*count)).to_string()
+        }
+        SuspectedCause::Overlays { namespace, count } => t!(
+            "perf.cause_overlays",
+            count = group_digits(*count),
+            namespace = namespace_label(namespace)
+        )
+        .to_string(),
+        SuspectedCause::VirtualTexts { namespace, count } => t!(
+            "perf.cause_virtual_texts",
+            count = group_digits(*count),
+            namespace = namespace_label(namespace)
+        )
+        .to_string(),
+        SuspectedCause::LongLine { length } => {
+            t!("perf.cause_long_line", length = group_digits(*length)).to_string()
+        }
+        SuspectedCause::SlowHighlight(time) => {
+            t!("perf.cause_highlight", time = format_duration(*time)).to_string()
+        }
+        SuspectedCause::SlowLayout(time) => {
+            t!("perf.cause_layout", time = format_duration(*time)).to_string()
+        }
+        SuspectedCause::SlowDecorations(time) => {
+            t!("perf.cause_decorations", time = format_duration(*time)).to_string()
+        }
+        SuspectedCause::SlowLsp(time) => {
+            t!("perf.cause_lsp", time = format_duration(*time)).to_string()
+        }
+    }
+}
+
+fn namespace_label(namespace: &str) -> String {
+    if namespace.is_empty() {
+        t!("perf.no_namespace").to_string()
+    } else {
+        namespace.to_string()
+    }
+}
+
+/// "(search 12,000, git 40)", largest first, or "" when there are none
+fn by_namespace(counts: &BTreeMap<String, usize>) -> String {
+    if counts.is_empty() {
+        return String::new();
+    }
+    let mut counts: Vec<_> = counts.iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(a.1));
+    let parts: Vec<_> = counts
+        .into_iter()
+        .map(|(namespace, count)| {
+            format!("{} {}", namespace_label(namespace), group_digits(*count))
+        })
+        .collect();
+    format!("({})", parts.join(", "))
+}
+
+fn on_off(on: bool) -> String {
+    if on {
+        t!("perf.on").to_string()
+    } else {
+        t!("perf.off").to_string()
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// 12000 -> "12,000"
+fn group_digits(value: usize) -> String {
+    let digits = value.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> BufferPerfReport {
+        BufferPerfReport {
+            buffer_id: BufferId(1),
+            name: "main.rs".to_string(),
+            bytes: 1000,
+            lines: Some(40),
+            longest_line: Some(80),
+            backend: "tree-sitter".to_string(),
+            timings: RenderTimings::default(),
+            overlays: BTreeMap::new(),
+            virtual_texts: BTreeMap::new(),
+            markers: 0,
+            large_file: false,
+            long_lines: false,
+            long_line_threshold: 10_000,
+            probe_request_id: None,
+            lsp: LspLatency::NotRunning,
+        }
+    }
+
+    #[test]
+    fn test_fast_buffer_has_no_suspected_cause() {
+        assert_eq!(report().suspected_cause(), None);
+    }
+
+    #[test]
+    fn test_worst_offender_wins() {
+        let mut report = report();
+        report.overlays.insert("search".to_string(), 12_000);
+        report.timings.highlight = Duration::from_millis(60);
+        assert_eq!(
+            report.suspected_cause(),
+            Some(SuspectedCause::Overlays {
+                namespace: "search".to_string(),
+                count: 12_000
+            })
+        );
+
+        report.lsp = LspLatency::Measured(Duration::from_secs(3));
+        assert_eq!(
+            report.suspected_cause(),
+            Some(SuspectedCause::SlowLsp(Duration::from_secs(3)))
+        );
+    }
+
+    #[test]
+    fn test_long_line_only_suspected_without_long_line_mode() {
+        let mut report = report();
+        report.longest_line = Some(50_000);
+        assert_eq!(
+            report.suspected_cause(),
+            Some(SuspectedCause::LongLine { length: 50_000 })
+        );
+        report.long_lines = true;
+        assert_eq!(report.suspected_cause(), None);
+    }
+
+    #[test]
+    fn test_group_digits() {
+        assert_eq!(group_digits(0), "0");
+        assert_eq!(group_digits(999), "999");
+        assert_eq!(group_digits(12_000), "12,000");
+        assert_eq!(group_digits(1_234_567), "1,234,567");
+    }
+}
@@ -25,6 +25,75 @@ const IGNORED_DIRS: &[&str] = &[
     ".DS_Store",
 ];
 
+/// Convert a plugin API command to the internal command it registers
+pub(super) fn plugin_command_to_internal(
+    command: fresh_core::command::Command,
+) -> crate::input::commands::Command {
+    use crate::input::commands::{Command as EditorCommand, CommandSource};
+    use crate::input::keybindings::Action;
+
+    EditorCommand {
+        name: command.name,
+        description: command.description,
+        action: Action::PluginAction(command.action_name),
+        contexts: vec![], // Plugin commands available in all contexts by default
+        custom_contexts: command.custom_contexts,
+        source: CommandSource::Plugin(command.plugin_name),
+    }
+}
+
+/// Convert an internal command registered by a plugin back to the plugin
+/// API command, or `None` if a plugin didn't register it
+pub(super) fn internal_to_plugin_command(
+    command: crate::input::commands::Command,
+) -> Option<fresh_core::command::Command> {
+    use crate::input::commands::CommandSource;
+    use crate::input::keybindings::Action;
+
+    match (command.action, command.source) {
+        (Action::PluginAction(action_name), CommandSource::Plugin(plugin_name)) => {
+            Some(fresh_core::command::Command {
+                name: command.name,
+                description: command.description,
+                action_name,
+                plugin_name,
+                custom_contexts: command.custom_contexts,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Label of an action or submenu item
+fn menu_item_label(item: &crate::config::MenuItem) -> Option<&String> {
+    match item {
+        crate::config::MenuItem::Action { label, .. }
+        | crate::config::MenuItem::Submenu { label, .. } => Some(label),
+        _ => None,
+    }
+}
+
+/// Insert `item` into `menu` at `position`, returning the index used
+fn insert_menu_item(
+    menu: &mut crate::config::Menu,
+    item: crate::config::MenuItem,
+    position: MenuPosition,
+) -> usize {
+    let find = |label: &String| {
+        menu.items
+            .iter()
+            .position(|i| menu_item_label(i) == Some(label))
+    };
+    let insert_idx = match &position {
+        MenuPosition::Top => 0,
+        MenuPosition::Bottom => menu.items.len(),
+        MenuPosition::Before(label) => find(label).unwrap_or(menu.items.len()),
+        MenuPosition::After(label) => find(label).map(|i| i + 1).unwrap_or(menu.items.len()),
+    };
+    menu.items.insert(insert_idx, item);
+    insert_idx
+}
+
 /// Build `FileSearchOptions` from the common grep parameters.
 fn make_search_opts(
     fixed_string: bool,
@@ -113,8 +182,14 @@ impl Editor {
 
     /// Find a menu by label, searching built-in menus first then plugin menus.
     fn find_menu_by_label_mut(&mut self, label: &str) -> Option<&mut crate::config::Menu> {
-        // Check built-in menus first
-        if let Some(menu) = self.menus.menus.iter_mut().find(|m| m.label == label) {
+        // Check built-in menus first (by label, or by id so plugins can
+        // target them regardless of the UI locale)
+        if let Some(menu) = self
+            .menus
+            .menus
+            .iter_mut()
+            .find(|m| m.label == label || m.id.as_deref() == Some(label))
+        {
             return Some(menu);
         }
         // Then check plugin menus
@@ -124,6 +199,21 @@ impl Editor {
             .find(|m| m.label == label)
     }
 
+    /// Rebuild the built-in menus with the current locale, re-inserting the
+    /// items plugins contributed to them
+    pub(super) fn rebuild_menus(&mut self) {
+        self.menus = crate::config::MenuConfig::translated();
+        let plugin_items = self.menu_state.plugin_items.clone();
+        for contribution in plugin_items {
+            if let Some(menu) = self.menus.menus.iter_mut().find(|m| {
+                m.label == contribution.menu_label
+                    || m.id.as_deref() == Some(contribution.menu_label.as_str())
+            }) {
+                insert_menu_item(menu, contribution.item, contribution.position);
+            }
+        }
+    }
+
     // ==================== Overlay Commands ====================
 
     /// Handle AddOverlay command
@@ -457,37 +547,19 @@ impl Editor {
         position: MenuPosition,
     ) {
         if let Some(menu) = self.find_menu_by_label_mut(&menu_label) {
-            // Insert at the specified position
-            let insert_idx = match position {
-                MenuPosition::Top => 0,
-                MenuPosition::Bottom => menu.items.len(),
-                MenuPosition::Before(label) => menu
-                    .items
-                    .iter()
-                    .position(|i| match i {
-                        crate::config::MenuItem::Action { label: l, .. }
-                        | crate::config::MenuItem::Submenu { label: l, .. } => l == &label,
-                        _ => false,
-                    })
-                    .unwrap_or(menu.items.len()),
-                MenuPosition::After(label) => menu
-                    .items
-                    .iter()
-                    .position(|i| match i {
-                        crate::config::MenuItem::Action { label: l, .. }
-                        | crate::config::MenuItem::Submenu { label: l, .. } => l == &label,
-                        _ => false,
-                    })
-                    .map(|i| i + 1)
-                    .unwrap_or(menu.items.len()),
-            };
-
-            menu.items.insert(insert_idx, item);
+            let insert_idx = insert_menu_item(menu, item.clone(), position.clone());
             tracing::info!(
                 "Added menu item to '{}' at position {}",
                 menu_label,
                 insert_idx
             );
+            self.menu_state
+                .plugin_items
+                .push(crate::view::ui::menu::PluginMenuItem {
+                    menu_label,
+                    item,
+                    position,
+                });
         } else {
             tracing::warn!("Menu '{}' not found for adding item", menu_label);
         }
@@ -558,6 +630,10 @@ impl Editor {
 
     /// Handle RemoveMenuItem command
     pub(super) fn handle_remove_menu_item(&mut self, menu_label: String, item_label: String) {
+        self.menu_state.plugin_items.retain(|contribution| {
+            contribution.menu_label != menu_label
+                || menu_item_label(&contribution.item) != Some(&item_label)
+        });
         if let Some(menu) = self.find_menu_by_label_mut(&menu_label) {
             // Remove item with matching label
             let original_len = menu.items.len();
@@ -970,6 +1046,10 @@ impl Editor {
         line: Option<usize>,
         column: Option<usize>,
     ) -> AnyhowResult<()> {
+        if self.try_open_externally(&path) {
+            return Ok(());
+        }
+
         // Open the file
         if let Err(e) = self.open_file(&path) {
             tracing::error!("Failed to open file from plugin: {}", e);
@@ -1252,7 +1332,7 @@ impl Editor {
     // ==================== Status/Prompt Commands ====================
 
     /// Handle SetStatus command
-    pub(super) fn handle_set_status(&mut self, message: String) {
+    pub(super) fn handle_set_status(&mut self, message: String, plugin_name: Option<String>) {
         if message.trim().is_empty() {
             self.plugin_status_message = None;
         } else {
@@ -1261,13 +1341,14 @@ impl Editor {
             // Detect plugin errors and collect them for test assertions
             // Error patterns: "Plugin error", "JS error", "handler error"
             let lower = message.to_lowercase();
-            if lower.contains("plugin error")
+            let is_error = lower.contains("plugin error")
                 || lower.contains("js error")
                 || lower.contains("handler error")
-                || lower.contains("error in")
-            {
+                || lower.contains("error in");
+            if is_error {
                 self.plugin_errors.push(message.clone());
             }
+            self.record_status_message(&message, plugin_name.as_deref(), is_error);
             // Clear core status message so only plugin message shows
             self.status_message = None;
             self.plugin_status_message = Some(message.clone());
@@ -1402,18 +1483,7 @@ impl Editor {
 
     /// Handle RegisterCommand command
     pub(super) fn handle_register_command(&self, command: fresh_core::command::Command) {
-        use crate::input::commands::{Command as EditorCommand, CommandSource};
-        use crate::input::keybindings::Action;
-
-        // Convert from plugin API command to internal command
-        let internal_command = EditorCommand {
-            name: command.name.clone(),
-            description: command.description,
-            action: Action::PluginAction(command.action_name),
-            contexts: vec![], // Plugin commands available in all contexts by default
-            custom_contexts: command.custom_contexts,
-            source: CommandSource::Plugin(command.plugin_name),
-        };
+        let internal_command = plugin_command_to_internal(command);
 
         tracing::debug!(
             "handle_register_command: name='{}', action={:?}",
@@ -1575,6 +1645,7 @@ impl Editor {
                 args: f.args,
                 stdin: true,       // Default: read from stdin
                 timeout_ms: 10000, // Default: 10 second timeout
+                env: Default::default(),
             }),
             ..Default::default()
         };
@@ -1678,6 +1749,9 @@ impl Editor {
             })
             .collect();
 
+        // Remember them so a full reload from disk keeps plugin grammars
+        self.plugin_grammars.extend(additional.iter().cloned());
+
         // Update config.languages with the extensions so detect_language() works
         for (language, _path, extensions) in &additional {
             let lang_config = self.config.languages.entry(language.clone()).or_default();
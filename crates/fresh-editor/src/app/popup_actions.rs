@@ -21,6 +21,12 @@ impl Editor {
     ///
     /// Returns `PopupConfirmResult` indicating what the caller should do next.
     pub fn handle_popup_confirm(&mut self) -> PopupConfirmResult {
+        // Enter in a peek popup jumps to the peeked location
+        if self.is_peek_popup_active() {
+            self.promote_peek();
+            return PopupConfirmResult::EarlyReturn;
+        }
+
         // Check if this is an action popup (from plugin showActionPopup)
         if let Some((popup_id, _actions)) = &self.active_action_popup {
             let popup_id = popup_id.clone();
@@ -47,6 +53,21 @@ impl Editor {
             return PopupConfirmResult::EarlyReturn;
         }
 
+        // Check if this is a workspace trust popup
+        if self.pending_trust_confirmation.is_some() {
+            let action = self
+                .active_state()
+                .popups
+                .top()
+                .and_then(|p| p.selected_item())
+                .and_then(|item| item.data.clone());
+            if let Some(action) = action {
+                self.hide_popup();
+                self.handle_trust_confirmation_response(&action);
+                return PopupConfirmResult::EarlyReturn;
+            }
+        }
+
         // Check if this is an LSP confirmation popup
         if self.pending_lsp_confirmation.is_some() {
             let action = self
@@ -183,7 +204,9 @@ impl Editor {
             return;
         }
 
-        if self.pending_lsp_confirmation.is_some() {
+        if self.pending_trust_confirmation.is_some() {
+            self.cancel_trust_confirmation();
+        } else if self.pending_lsp_confirmation.is_some() {
             self.pending_lsp_confirmation = None;
             self.set_status_message(t!("lsp.startup_cancelled_msg").to_string());
         }
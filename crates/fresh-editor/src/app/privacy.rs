@@ -0,0 +1,119 @@
+//! Privacy Mode for screen sharing.
+//!
+//! While on, values that look like secrets are drawn as block characters in
+//! buffers and terminals, file names in the recent-files picker are masked,
+//! and the status bar shows a watermark. Masking only affects rendering; the
+//! buffer is never modified. Copying a selection that covers a masked secret
+//! asks for confirmation first.
+
+use super::Editor;
+use crate::primitives::secret_mask::{SecretMasker, MASK_CHAR};
+use crate::services::terminal::TerminalCell;
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+
+impl Editor {
+    /// Turn Privacy Mode on or off. Patterns are read from the `privacy`
+    /// config section each time it's turned on.
+    pub fn toggle_privacy_mode(&mut self) {
+        if self.privacy_masker.take().is_some() {
+            self.set_status_message(t!("privacy.disabled").to_string());
+        } else {
+            let privacy = &self.config.privacy;
+            self.privacy_masker = Some(SecretMasker::new(
+                &privacy.extra_patterns,
+                privacy.mask_env_files,
+            ));
+            self.set_status_message(t!("privacy.enabled").to_string());
+        }
+    }
+
+    /// Whether Privacy Mode is on
+    pub fn is_privacy_mode(&self) -> bool {
+        self.privacy_masker.is_some()
+    }
+
+    /// Copy the selection, asking first if Privacy Mode is hiding a secret
+    /// inside it
+    pub(super) fn copy_selection_guarded(&mut self) {
+        if self.selection_reveals_secret() {
+            let copy_key = t!("prompt.key.copy").to_string();
+            let cancel_key = t!("prompt.key.cancel").to_string();
+            self.start_prompt(
+                t!(
+                    "privacy.copy_confirm",
+                    copy_key = copy_key,
+                    cancel_key = cancel_key
+                )
+                .to_string(),
+                PromptType::ConfirmCopySecret,
+            );
+        } else {
+            self.copy_selection();
+        }
+    }
+
+    /// Whether copying now would put a masked secret on the clipboard. Whole
+    /// lines are scanned so a key before the selection still counts; without
+    /// a selection the whole line is what gets copied.
+    fn selection_reveals_secret(&mut self) -> bool {
+        let Some(masker) = self.privacy_masker.clone() else {
+            return false;
+        };
+        let copied: Vec<_> = self
+            .active_cursors()
+            .iter()
+            .map(|(_, cursor)| {
+                cursor
+                    .selection_range()
+                    .unwrap_or(cursor.position..cursor.position)
+            })
+            .collect();
+        let env_file = self
+            .active_state()
+            .buffer
+            .file_path()
+            .and_then(|path| path.file_name())
+            .is_some_and(|name| SecretMasker::is_env_file(&name.to_string_lossy()));
+
+        let state = self.active_state_mut();
+        copied.into_iter().any(|range| {
+            let Some((first_line, _)) = state.get_line_at_offset(range.start) else {
+                return false;
+            };
+            let last_line_end = state
+                .get_line_at_offset(range.end)
+                .map(|(start, content)| start + content.len())
+                .unwrap_or(range.end);
+            let (start, end) = if range.is_empty() {
+                (first_line, last_line_end)
+            } else {
+                (range.start, range.end)
+            };
+            let text = state.get_text_range(first_line, last_line_end);
+            masker
+                .secret_ranges(&text, env_file)
+                .iter()
+                .any(|secret| first_line + secret.start < end && start < first_line + secret.end)
+        })
+    }
+}
+
+/// Mask secrets in one row of terminal cells
+pub(super) fn mask_terminal_row(row: &mut [TerminalCell], masker: &SecretMasker) {
+    let mut text = String::with_capacity(row.len());
+    let mut cell_offsets = Vec::with_capacity(row.len());
+    for cell in row.iter() {
+        cell_offsets.push(text.len());
+        text.push(cell.c);
+    }
+    let ranges = masker.secret_ranges(&text, false);
+    if ranges.is_empty() {
+        return;
+    }
+    for (cell, offset) in row.iter_mut().zip(cell_offsets) {
+        if ranges.iter().any(|range| range.contains(&offset)) {
+            cell.c = MASK_CHAR;
+        }
+    }
+}
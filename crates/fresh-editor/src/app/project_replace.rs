@@ -0,0 +1,292 @@
+//! Replace across the project.
+//!
+//! `project_replace` scans the working directory on the tokio runtime's
+//! blocking pool, with the same walk as project search, and reports the files
+//! containing matches through the async bridge. Nothing is touched until the
+//! user confirms the file count; then each file is opened and its matches are
+//! replaced on the main thread as one undoable edit per buffer, like Replace
+//! does in a single buffer. The files are left modified, not saved.
+//!
+//! Like project search, the scan skips files over the large-file threshold
+//! and reads the local disk, so replace is turned off on a remote host.
+
+use super::project_search::walk_text_files;
+use super::regex_replace::ReplaceTemplate;
+use super::Editor;
+use crate::services::async_bridge::AsyncMessage;
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+
+/// Tells the scan of a replace from that of an earlier, abandoned one
+static NEXT_REPLACE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A replace waiting for its scan or for confirmation
+pub(crate) struct ProjectReplace {
+    replace_id: u64,
+    query: String,
+    replacement: String,
+    /// `replacement` parsed for `regex`, if its capture groups and case
+    /// escapes are expanded
+    template: Option<ReplaceTemplate>,
+    regex: regex::bytes::Regex,
+    /// Writable files with matches, found by the scan
+    files: Vec<PathBuf>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Drop for ProjectReplace {
+    fn drop(&mut self) {
+        // Stop the scan; nobody reads its result anymore
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Editor {
+    /// Replace every match of `query` with `replacement` in the files under
+    /// the working directory, skipping what `.gitignore` ignores. `query` is
+    /// a regular expression if `regex` is set, and `replacement` may then
+    /// use its capture groups and case escapes; the case and whole-word
+    /// search toggles apply as in buffer search. Asks for confirmation once
+    /// the files are known.
+    pub fn project_replace(&mut self, query: &str, replacement: &str, regex: bool) {
+        if self.filesystem.remote_connection_info().is_some() {
+            self.set_status_message(t!("project_replace.remote").to_string());
+            return;
+        }
+        if query.is_empty() {
+            self.set_status_message(t!("replace.empty_query").to_string());
+            return;
+        }
+        let compiled =
+            match regex::bytes::RegexBuilder::new(&self.project_search_pattern(query, regex))
+                .case_insensitive(!self.search_case_sensitive)
+                .build()
+            {
+                Ok(compiled) => compiled,
+                Err(e) => {
+                    self.set_important_status_message(
+                        t!("error.invalid_regex", error = e.to_string()).to_string(),
+                    );
+                    return;
+                }
+            };
+        let template = if regex {
+            match self.parse_replace_template(replacement, &compiled) {
+                Some(template) => Some(template),
+                None => return,
+            }
+        } else {
+            None
+        };
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            self.set_status_message(t!("project_search.unavailable").to_string());
+            return;
+        };
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let replace_id = NEXT_REPLACE_ID.fetch_add(1, Ordering::Relaxed);
+        let scan = Scan {
+            root: self.working_dir.clone(),
+            max_file_bytes: self.config.editor.large_file_threshold_bytes,
+            regex: compiled.clone(),
+            replace_id,
+            cancelled: Arc::clone(&cancelled),
+            sender: bridge.sender(),
+        };
+        runtime.spawn_blocking(move || scan.run());
+
+        self.project_replace = Some(ProjectReplace {
+            replace_id,
+            query: query.to_string(),
+            replacement: replacement.to_string(),
+            template,
+            regex: compiled,
+            files: Vec::new(),
+            cancelled,
+        });
+        self.set_status_message(t!("project_replace.scanning", search = query).to_string());
+    }
+
+    /// Take the scan's result and ask before replacing anything
+    pub(super) fn handle_project_replace_scanned(
+        &mut self,
+        replace_id: u64,
+        files: Vec<(PathBuf, usize)>,
+        read_only: usize,
+    ) {
+        let Some(pending) = &mut self.project_replace else {
+            return;
+        };
+        if pending.replace_id != replace_id {
+            return;
+        }
+        if files.is_empty() {
+            let search = pending.query.clone();
+            self.project_replace = None;
+            self.set_status_message(t!("search.no_occurrences", search = search).to_string());
+            return;
+        }
+        let count: usize = files.iter().map(|(_, count)| count).sum();
+        pending.files = files.into_iter().map(|(path, _)| path).collect();
+        let mut message = t!(
+            "project_replace.confirm_prompt",
+            count = count,
+            files = pending.files.len(),
+            search = &pending.query,
+            yes = "y",
+            no = "N"
+        )
+        .to_string();
+        if read_only > 0 {
+            message = format!(
+                "{}{}",
+                t!("project_replace.read_only_skipped", count = read_only),
+                message
+            );
+        }
+        self.start_prompt(message, PromptType::ProjectReplaceConfirm);
+    }
+
+    /// Answer to the confirmation prompt: replace in every file found, or
+    /// drop the replace
+    pub(super) fn confirm_project_replace(&mut self, answer: &str) {
+        let Some(pending) = self.project_replace.take() else {
+            return;
+        };
+        let answer = answer.trim().to_lowercase();
+        if answer != "y" && answer != "yes" {
+            self.set_status_message(t!("project_replace.cancelled").to_string());
+            return;
+        }
+
+        let original_buffer = self.active_buffer();
+        let description = format!("Replace all '{}' in project", pending.query);
+        let mut replaced = 0;
+        let mut changed_files = 0;
+        let mut skipped = 0;
+        for path in &pending.files {
+            let Some(count) = self.replace_in_file(path, &pending, description.clone()) else {
+                skipped += 1;
+                continue;
+            };
+            if count > 0 {
+                replaced += count;
+                changed_files += 1;
+            }
+        }
+        self.set_active_buffer(original_buffer);
+
+        let mut message = t!(
+            "project_replace.done",
+            count = replaced,
+            files = changed_files
+        )
+        .to_string();
+        if skipped > 0 {
+            message = format!(
+                "{} {}",
+                message,
+                t!("project_replace.skipped", count = skipped)
+            );
+        }
+        self.set_status_message(message);
+    }
+
+    /// Open `path` and replace the matches in its buffer. Returns the number
+    /// replaced, or None if the file can't be opened or is read-only.
+    fn replace_in_file(
+        &mut self,
+        path: &Path,
+        pending: &ProjectReplace,
+        description: String,
+    ) -> Option<usize> {
+        let buffer_id = match self.open_file(path) {
+            Ok(buffer_id) => buffer_id,
+            Err(e) => {
+                tracing::warn!("Project replace skipped {}: {}", path.display(), e);
+                return None;
+            }
+        };
+        if self.is_read_only(buffer_id) || self.is_editing_disabled() {
+            return None;
+        }
+        // The buffer, not the file on disk, is what gets edited
+        let contents = {
+            let state = self.active_state_mut();
+            let len = state.buffer.len();
+            match state.buffer.get_text_range_mut(0, len) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    tracing::warn!("Project replace skipped {}: {}", path.display(), e);
+                    return None;
+                }
+            }
+        };
+        let matches: Vec<(usize, usize, String)> = if let Some(template) = &pending.template {
+            super::regex_replace::collect_regex_matches(&pending.regex, &contents, template)
+                .into_iter()
+                .map(|m| (m.offset, m.len, m.replacement))
+                .collect()
+        } else {
+            pending
+                .regex
+                .find_iter(&contents)
+                .map(|m| (m.start(), m.len(), pending.replacement.clone()))
+                .collect()
+        };
+        if !matches.is_empty() {
+            self.apply_replacements(&matches, description);
+        }
+        Some(matches.len())
+    }
+}
+
+/// A scan of the working directory for files to replace in, run on a
+/// blocking thread
+struct Scan {
+    root: PathBuf,
+    /// Files larger than this are skipped
+    max_file_bytes: u64,
+    regex: regex::bytes::Regex,
+    replace_id: u64,
+    cancelled: Arc<AtomicBool>,
+    sender: mpsc::Sender<AsyncMessage>,
+}
+
+impl Scan {
+    fn run(self) {
+        let mut files = Vec::new();
+        let mut read_only = 0;
+        walk_text_files(&self.root, self.max_file_bytes, |path, _, contents| {
+            if self.cancelled.load(Ordering::Relaxed) {
+                return false;
+            }
+            let count = self.regex.find_iter(contents).count();
+            if count == 0 {
+                return true;
+            }
+            let writable = std::fs::metadata(path)
+                .map(|metadata| !metadata.permissions().readonly())
+                .unwrap_or(false);
+            if writable {
+                files.push((path.to_path_buf(), count));
+            } else {
+                read_only += 1;
+            }
+            true
+        });
+        if self.cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+        // The editor may already be shutting down
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.sender.send(AsyncMessage::ProjectReplaceScanned {
+            replace_id: self.replace_id,
+            files,
+            read_only,
+        });
+    }
+}
@@ -0,0 +1,491 @@
+//! Project-wide search.
+//!
+//! Walks the working directory on the tokio runtime's blocking pool with the
+//! `ignore` crate, the way the fuzzy finder does, and searches each text file
+//! line by line. Matches stream back in batches through the async bridge into
+//! the read-only `*Project Search*` buffer, one `path:line:col: text` line
+//! each, so the first hits show while the walk goes on. Enter on a result
+//! opens the file at the match.
+//!
+//! Files over the large-file threshold are skipped rather than read whole.
+//! The walk reads the local disk, so the search is turned off when the
+//! editor is on a remote host.
+
+use super::Editor;
+use crate::model::event::{BufferId, OverlayColorSpec, OverlayOptions};
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::async_bridge::AsyncMessage;
+use rust_i18n::t;
+use serde::Deserialize;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+/// Display name of the results buffer
+const RESULTS_BUFFER_NAME: &str = "*Project Search*";
+
+/// Buffer mode of the results buffer (binds Enter to open a result)
+const RESULTS_MODE: &str = "grep-results";
+
+/// Matches listed at most; the search stops there
+const MAX_MATCHES: usize = 10_000;
+
+/// Matches sent per message at most
+const BATCH_SIZE: usize = 256;
+
+/// Longest wait before matches found so far are sent
+const FLUSH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Leading bytes checked for a NUL to tell binary files, as `rg` does
+const BINARY_CHECK_BYTES: usize = 8192;
+
+/// Bytes of a matching line shown in the results
+const MAX_LINE_BYTES: usize = 240;
+
+/// Tells the batches of a search from those of an earlier, abandoned one
+static NEXT_SEARCH_ID: AtomicU64 = AtomicU64::new(0);
+
+/// One matching line
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectSearchMatch {
+    /// Path relative to the working directory
+    pub path: String,
+    /// 1-indexed line number
+    pub line: usize,
+    /// 1-indexed byte column of the first match on the line
+    pub column: usize,
+    /// The line, cut at [`MAX_LINE_BYTES`]
+    pub text: String,
+    /// Byte range of the first match within `text`
+    pub range: Range<usize>,
+}
+
+/// Where a result line leads, stored as a text property on the line
+#[derive(Deserialize)]
+struct ResultLocation {
+    file: PathBuf,
+    line: usize,
+    column: usize,
+}
+
+/// A search and the buffer showing its results
+pub(crate) struct ProjectSearch {
+    search_id: u64,
+    buffer_id: BufferId,
+    query: String,
+    matches: Vec<ProjectSearchMatch>,
+    /// Whether the walk is finished
+    done: bool,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Drop for ProjectSearch {
+    fn drop(&mut self) {
+        // Stop the walk; nobody reads its batches anymore
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Editor {
+    /// Search every file under the working directory, skipping what
+    /// `.gitignore` ignores, and list the matching lines in the
+    /// `*Project Search*` buffer as they are found. `query` is a regular
+    /// expression if `regex` is set; the case and whole-word search toggles
+    /// apply as in buffer search.
+    pub fn project_search(&mut self, query: &str, regex: bool) {
+        if self.filesystem.remote_connection_info().is_some() {
+            self.set_status_message(t!("project_search.remote").to_string());
+            return;
+        }
+        if query.is_empty() {
+            self.set_status_message(t!("search.cancelled").to_string());
+            return;
+        }
+        let compiled = match regex::RegexBuilder::new(&self.project_search_pattern(query, regex))
+            .case_insensitive(!self.search_case_sensitive)
+            .build()
+        {
+            Ok(compiled) => compiled,
+            Err(e) => {
+                self.set_important_status_message(
+                    t!("error.invalid_regex", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            self.set_status_message(t!("project_search.unavailable").to_string());
+            return;
+        };
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let search_id = NEXT_SEARCH_ID.fetch_add(1, Ordering::Relaxed);
+        let walk = Search {
+            root: self.working_dir.clone(),
+            max_file_bytes: self.config.editor.large_file_threshold_bytes,
+            regex: compiled,
+            search_id,
+            cancelled: Arc::clone(&cancelled),
+            sender: bridge.sender(),
+        };
+        runtime.spawn_blocking(move || walk.run());
+
+        let buffer_id = self.project_search_buffer_id().unwrap_or_else(|| {
+            let buffer_id = self.create_virtual_buffer(
+                RESULTS_BUFFER_NAME.to_string(),
+                RESULTS_MODE.to_string(),
+                true,
+            );
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state.editing_disabled = true;
+                state.margins.configure_for_line_numbers(false);
+            }
+            buffer_id
+        });
+        // Replacing an earlier search stops its walk
+        self.project_search = Some(ProjectSearch {
+            search_id,
+            buffer_id,
+            query: query.to_string(),
+            matches: Vec::new(),
+            done: false,
+            cancelled,
+        });
+        self.refresh_project_search_buffer();
+        self.set_active_buffer(buffer_id);
+        self.move_project_search_cursor(0);
+    }
+
+    /// Add a batch of matches from the walk
+    pub(super) fn handle_project_search_matches(
+        &mut self,
+        search_id: u64,
+        matches: Vec<ProjectSearchMatch>,
+        done: bool,
+    ) {
+        let Some(search) = &mut self.project_search else {
+            return;
+        };
+        if search.search_id != search_id {
+            return;
+        }
+        if !self.buffers.contains_key(&search.buffer_id) {
+            // The results buffer was closed
+            self.project_search = None;
+            return;
+        }
+        let first_results = search.matches.is_empty() && !matches.is_empty();
+        search.matches.extend(matches);
+        search.done = done;
+        let first_result_offset = self.refresh_project_search_buffer();
+        if first_results {
+            self.move_project_search_cursor(first_result_offset);
+        }
+    }
+
+    /// Pattern searching for `query` under the whole-word toggle, escaped
+    /// unless `regex` is set
+    pub(super) fn project_search_pattern(&self, query: &str, regex: bool) -> String {
+        let pattern = if regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        if self.search_whole_word {
+            format!(r"\b{}\b", pattern)
+        } else {
+            pattern
+        }
+    }
+
+    /// Open the file of the result under the cursor at its match
+    pub fn open_project_search_result(&mut self) {
+        let location = self.get_text_properties_at_cursor().and_then(|properties| {
+            properties
+                .iter()
+                .find_map(|property| property.get_as::<ResultLocation>("location"))
+        });
+        if let Some(location) = location {
+            self.open_file_with_jump(location.file, Some(location.line), Some(location.column));
+        }
+    }
+
+    fn project_search_buffer_id(&self) -> Option<BufferId> {
+        self.buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == RESULTS_BUFFER_NAME && m.virtual_mode().is_some())
+            .map(|(id, _)| *id)
+    }
+
+    /// Rewrite the results buffer from the matches so far. Returns the
+    /// offset of the first result line.
+    fn refresh_project_search_buffer(&mut self) -> usize {
+        let Some(search) = &self.project_search else {
+            return 0;
+        };
+        let files = 1 + search
+            .matches
+            .windows(2)
+            .filter(|pair| pair[0].path != pair[1].path)
+            .count();
+        let mut header = if !search.done {
+            t!(
+                "project_search.header_running",
+                query = &search.query,
+                count = search.matches.len()
+            )
+            .to_string()
+        } else if search.matches.is_empty() {
+            t!("project_search.header_none", query = &search.query).to_string()
+        } else {
+            t!(
+                "project_search.header_done",
+                query = &search.query,
+                count = search.matches.len(),
+                files = files
+            )
+            .to_string()
+        };
+        if search.matches.len() >= MAX_MATCHES {
+            header.push_str(&format!(
+                " {}",
+                t!("project_search.truncated", max = MAX_MATCHES)
+            ));
+        }
+        let header = format!("{}\n\n", header);
+        let first_result_offset = header.len();
+
+        let path_style = OverlayOptions {
+            fg: Some(OverlayColorSpec::theme_key("syntax.function")),
+            ..Default::default()
+        };
+        let match_style = OverlayOptions {
+            fg: Some(OverlayColorSpec::theme_key("search.match_fg")),
+            bg: Some(OverlayColorSpec::theme_key("search.match_bg")),
+            ..Default::default()
+        };
+        let mut entries = vec![TextPropertyEntry::text(header).with_style(OverlayOptions {
+            bold: true,
+            ..Default::default()
+        })];
+        for found in &search.matches {
+            let prefix = format!("{}:{}:{}: ", found.path, found.line, found.column);
+            let location = serde_json::json!({
+                "file": self.working_dir.join(&found.path),
+                "line": found.line,
+                "column": found.column,
+            });
+            entries.push(
+                TextPropertyEntry::text(format!("{}{}\n", prefix, found.text))
+                    .with_property("location", location)
+                    .with_inline_overlay(0, found.path.len(), path_style.clone())
+                    .with_inline_overlay(
+                        prefix.len() + found.range.start,
+                        prefix.len() + found.range.end,
+                        match_style.clone(),
+                    ),
+            );
+        }
+
+        let buffer_id = search.buffer_id;
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::warn!("Failed to refresh {}: {}", RESULTS_BUFFER_NAME, e);
+        }
+        first_result_offset
+    }
+
+    /// Put the cursor of the results buffer at `position`
+    fn move_project_search_cursor(&mut self, position: usize) {
+        let Some(buffer_id) = self.project_search.as_ref().map(|search| search.buffer_id) else {
+            return;
+        };
+        for view_state in self.split_view_states.values_mut() {
+            if let Some(buf_state) = view_state.keyed_states.get_mut(&buffer_id) {
+                buf_state.cursors.primary_mut().position = position;
+            }
+        }
+    }
+}
+
+/// A search of the working directory, run on a blocking thread
+struct Search {
+    root: PathBuf,
+    /// Files larger than this are skipped
+    max_file_bytes: u64,
+    regex: regex::Regex,
+    search_id: u64,
+    cancelled: Arc<AtomicBool>,
+    sender: mpsc::Sender<AsyncMessage>,
+}
+
+/// Call `visit` with the path, the path relative to `root` and the contents
+/// of each file under `root`, skipping what `.gitignore` ignores, files over
+/// `max_file_bytes` and binary and unreadable files, until it returns false
+pub(super) fn walk_text_files(
+    root: &Path,
+    max_file_bytes: u64,
+    mut visit: impl FnMut(&Path, &str, &[u8]) -> bool,
+) {
+    let walker = ignore::WalkBuilder::new(root)
+        // Honor .gitignore outside git repositories too
+        .require_git(false)
+        .build();
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            // Unreadable directories and symlink loops
+            Err(e) => {
+                tracing::debug!("Project walk skipped an entry: {}", e);
+                continue;
+            }
+        };
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        if entry
+            .metadata()
+            .is_ok_and(|metadata| metadata.len() > max_file_bytes)
+        {
+            tracing::debug!("Project walk skipped large file {:?}", entry.path());
+            continue;
+        }
+        let Ok(bytes) = std::fs::read(entry.path()) else {
+            continue;
+        };
+        if bytes[..bytes.len().min(BINARY_CHECK_BYTES)].contains(&0) {
+            continue;
+        }
+        if !visit(entry.path(), &relative.to_string_lossy(), &bytes) {
+            return;
+        }
+    }
+}
+
+impl Search {
+    fn run(self) {
+        let mut batch = Vec::new();
+        let mut sent = 0;
+        let mut last_sent: Option<Instant> = None;
+        let mut gone = false;
+        walk_text_files(&self.root, self.max_file_bytes, |_, relative, contents| {
+            if self.cancelled.load(Ordering::Relaxed) {
+                gone = true;
+                return false;
+            }
+            self.search_file(relative, contents, &mut batch);
+
+            if sent + batch.len() >= MAX_MATCHES {
+                batch.truncate(MAX_MATCHES - sent);
+                tracing::info!("Project search stopped after {} matches", MAX_MATCHES);
+                return false;
+            }
+            // The first hits go out at once, later ones in batches
+            let due = last_sent.is_none_or(|at| at.elapsed() >= FLUSH_INTERVAL);
+            if batch.len() >= BATCH_SIZE || (!batch.is_empty() && due) {
+                sent += batch.len();
+                if !self.send(std::mem::take(&mut batch), false) {
+                    gone = true;
+                    return false;
+                }
+                last_sent = Some(Instant::now());
+            }
+            true
+        });
+        if !gone {
+            self.send(batch, true);
+        }
+    }
+
+    /// Add the lines of the file at `relative` matching the search to
+    /// `matches`
+    fn search_file(&self, relative: &str, contents: &[u8], matches: &mut Vec<ProjectSearchMatch>) {
+        let text = String::from_utf8_lossy(contents);
+        for (index, line) in text.lines().enumerate() {
+            let Some(found) = self.regex.find(line) else {
+                continue;
+            };
+            let mut end = line.len().min(MAX_LINE_BYTES);
+            while !line.is_char_boundary(end) {
+                end -= 1;
+            }
+            matches.push(ProjectSearchMatch {
+                path: relative.to_string(),
+                line: index + 1,
+                column: found.start() + 1,
+                text: line[..end].to_string(),
+                range: found.start().min(end)..found.end().min(end),
+            });
+        }
+    }
+
+    /// Send a batch. Returns false when the search is gone.
+    fn send(&self, matches: Vec<ProjectSearchMatch>, done: bool) -> bool {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return false;
+        }
+        let message = AsyncMessage::ProjectSearchMatches {
+            search_id: self.search_id,
+            matches,
+            done,
+        };
+        self.sender.send(message).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_skips_ignored_binary_and_large_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "build/\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("build")).unwrap();
+        std::fs::write(dir.path().join("build").join("out.txt"), "needle\n").unwrap();
+        std::fs::write(dir.path().join("blob.bin"), b"needle\0\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "hay\r\n  a needle\n").unwrap();
+        std::fs::write(dir.path().join("huge.log"), "needle\n".repeat(100)).unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        Search {
+            root: dir.path().to_path_buf(),
+            max_file_bytes: 100,
+            regex: regex::Regex::new("needle").unwrap(),
+            search_id: 3,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            sender,
+        }
+        .run();
+
+        let mut found = Vec::new();
+        for message in receiver.try_iter() {
+            match message {
+                AsyncMessage::ProjectSearchMatches {
+                    search_id, matches, ..
+                } => {
+                    assert_eq!(search_id, 3);
+                    found.extend(matches);
+                }
+                other => panic!("unexpected message {:?}", other),
+            }
+        }
+        assert_eq!(
+            found,
+            vec![ProjectSearchMatch {
+                path: "notes.txt".to_string(),
+                line: 2,
+                column: 5,
+                text: "  a needle".to_string(),
+                range: 4..10,
+            }]
+        );
+    }
+}
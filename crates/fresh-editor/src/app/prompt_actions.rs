@@ -142,6 +142,23 @@ impl Editor {
             PromptType::Search => {
                 self.perform_search(&input);
             }
+            PromptType::ProjectSearch => {
+                self.project_search(&input, self.search_use_regex);
+            }
+            PromptType::ProjectReplaceSearch => {
+                self.start_prompt(
+                    t!("project_replace.replace_prompt", search = &input).to_string(),
+                    PromptType::ProjectReplace {
+                        search: input.clone(),
+                    },
+                );
+            }
+            PromptType::ProjectReplace { search } => {
+                self.project_replace(&search, &input, self.search_use_regex);
+            }
+            PromptType::ProjectReplaceConfirm => {
+                self.confirm_project_replace(&input);
+            }
             PromptType::ReplaceSearch => {
                 self.perform_search(&input);
                 self.start_prompt(
@@ -185,7 +202,7 @@ impl Editor {
                         .record_usage(&cmd_name);
                     return PromptResult::ExecuteAction(action);
                 } else {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("error.unknown_command", input = &input).to_string(),
                     );
                 }
@@ -199,7 +216,9 @@ impl Editor {
                     self.set_status_message(t!("goto.line_must_be_positive").to_string());
                 }
                 Err(_) => {
-                    self.set_status_message(t!("error.invalid_line", input = &input).to_string());
+                    self.set_important_status_message(
+                        t!("error.invalid_line", input = &input).to_string(),
+                    );
                 }
             },
             PromptType::GotoByteOffset => {
@@ -244,7 +263,7 @@ impl Editor {
             }
             PromptType::SetBackgroundFile => {
                 if let Err(e) = self.load_ansi_background(&input) {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("error.background_load_failed", error = e.to_string()).to_string(),
                     );
                 }
@@ -262,7 +281,9 @@ impl Editor {
                     );
                 }
                 Err(_) => {
-                    self.set_status_message(t!("error.invalid_blend", input = &input).to_string());
+                    self.set_important_status_message(
+                        t!("error.invalid_blend", input = &input).to_string(),
+                    );
                 }
             },
             PromptType::SetComposeWidth => {
@@ -278,6 +299,12 @@ impl Editor {
             PromptType::PlayMacro => {
                 self.handle_register_input(&input, |editor, c| editor.play_macro(c), "Macro");
             }
+            PromptType::SaveMacro => {
+                self.handle_save_macro_input(&input);
+            }
+            PromptType::LoadMacro => {
+                self.handle_load_macro_input(&input);
+            }
             PromptType::SetBookmark => {
                 self.handle_register_input(&input, |editor, c| editor.set_bookmark(c), "Bookmark");
             }
@@ -288,6 +315,23 @@ impl Editor {
                     "Bookmark",
                 );
             }
+            PromptType::SetNamedBookmark => {
+                self.set_named_bookmark(&input);
+            }
+            PromptType::JumpToNamedBookmark => {
+                self.jump_to_named_bookmark(&input);
+            }
+            PromptType::BookmarkPicker => {
+                self.jump_from_bookmark_picker(&input);
+            }
+            PromptType::RecentFiles => {
+                self.open_file_with_jump(self.working_dir.join(&input), None, None);
+            }
+            PromptType::FuzzyFinder => {
+                if let Some(path) = self.take_fuzzy_finder_choice(&input) {
+                    self.open_file_with_jump(path, None, None);
+                }
+            }
             PromptType::Plugin { custom_type } => {
                 tracing::info!(
                     "prompt_confirmed: dispatching hook for prompt_type='{}', input='{}', selected_index={:?}",
@@ -311,7 +355,7 @@ impl Editor {
                 let revert_key = t!("prompt.key.revert").to_string().to_lowercase();
                 if input_lower == revert_key || input_lower == "revert" {
                     if let Err(e) = self.revert_file() {
-                        self.set_status_message(
+                        self.set_important_status_message(
                             t!("file.revert_failed", error = e.to_string()).to_string(),
                         );
                     }
@@ -319,11 +363,28 @@ impl Editor {
                     self.set_status_message(t!("buffer.revert_cancelled").to_string());
                 }
             }
+            PromptType::ConfirmCopySecret => {
+                let input_lower = input.trim().to_lowercase();
+                let copy_key = t!("prompt.key.copy").to_string().to_lowercase();
+                if input_lower == copy_key || input_lower == "copy" {
+                    self.copy_selection();
+                } else {
+                    self.set_status_message(t!("privacy.copy_cancelled").to_string());
+                }
+            }
+            PromptType::ConfirmLspDownload { language } => {
+                let input_lower = input.trim().to_lowercase();
+                if input_lower == "y" || input_lower == "yes" {
+                    self.start_lsp_download(&language);
+                } else {
+                    self.decline_lsp_download(&language);
+                }
+            }
             PromptType::ConfirmSaveConflict => {
                 let input_lower = input.trim().to_lowercase();
                 if input_lower == "o" || input_lower == "overwrite" {
                     if let Err(e) = self.save() {
-                        self.set_status_message(
+                        self.set_important_status_message(
                             t!("file.save_failed", error = e.to_string()).to_string(),
                         );
                     }
@@ -331,6 +392,9 @@ impl Editor {
                     self.set_status_message(t!("buffer.save_cancelled").to_string());
                 }
             }
+            PromptType::ConfirmMissingFile { buffer_id } => {
+                self.handle_confirm_missing_file(&input, buffer_id);
+            }
             PromptType::ConfirmSudoSave { info } => {
                 let input_lower = input.trim().to_lowercase();
                 if input_lower == "y" || input_lower == "yes" {
@@ -361,13 +425,13 @@ impl Editor {
                                 .finalize_external_save(info.dest_path.clone())
                             {
                                 tracing::warn!("Failed to finalize sudo save: {}", e);
-                                self.set_status_message(
+                                self.set_important_status_message(
                                     t!("prompt.sudo_save_failed", error = e.to_string())
                                         .to_string(),
                                 );
                             } else if let Err(e) = self.finalize_save(Some(info.dest_path)) {
                                 tracing::warn!("Failed to finalize save after sudo: {}", e);
-                                self.set_status_message(
+                                self.set_important_status_message(
                                     t!("prompt.sudo_save_failed", error = e.to_string())
                                         .to_string(),
                                 );
@@ -375,7 +439,7 @@ impl Editor {
                         }
                         Err(e) => {
                             tracing::warn!("Sudo save failed: {}", e);
-                            self.set_status_message(
+                            self.set_important_status_message(
                                 t!("prompt.sudo_save_failed", error = e.to_string()).to_string(),
                             );
                             // Best-effort cleanup of temp file.
@@ -445,7 +509,7 @@ impl Editor {
                 // Default (empty input or load key) loads the file
                 if input_lower.is_empty() || input_lower == load_key {
                     if let Err(e) = self.open_file_large_encoding_confirmed(&path) {
-                        self.set_status_message(
+                        self.set_important_status_message(
                             t!("file.error_opening", error = e.to_string()).to_string(),
                         );
                     }
@@ -457,7 +521,7 @@ impl Editor {
                 } else {
                     // Unknown input - default to load
                     if let Err(e) = self.open_file_large_encoding_confirmed(&path) {
-                        self.set_status_message(
+                        self.set_important_status_message(
                             t!("file.error_opening", error = e.to_string()).to_string(),
                         );
                     }
@@ -486,6 +550,14 @@ impl Editor {
                     self.switch_to_tab(BufferId(id));
                 }
             }
+            PromptType::DiffBuffers { first } => {
+                if let Ok(id) = input.trim().parse::<usize>() {
+                    self.handle_diff_buffer_chosen(first, BufferId(id));
+                }
+            }
+            PromptType::ManageWorkspaceTrust => {
+                self.revoke_workspace_trust(input.trim());
+            }
             PromptType::QueryReplaceConfirm => {
                 // This is handled by InsertChar, not PromptConfirm
                 // But if somehow Enter is pressed, treat it as skip (n)
@@ -504,6 +576,14 @@ impl Editor {
             PromptType::SetTabSize => {
                 self.handle_set_tab_size(&input);
             }
+            PromptType::ReflowColumn => match input.trim().parse::<usize>() {
+                Ok(column) if column > 0 => self.reflow_paragraph(Some(column)),
+                _ => {
+                    self.set_important_status_message(
+                        t!("error.invalid_reflow_column", input = &input).to_string(),
+                    );
+                }
+            },
             PromptType::SetLineEnding => {
                 self.handle_set_line_ending(&input);
             }
@@ -516,6 +596,21 @@ impl Editor {
             PromptType::ShellCommand { replace } => {
                 self.handle_shell_command(&input, replace);
             }
+            PromptType::ExportHtml => {
+                self.handle_export_html(&input);
+            }
+            PromptType::WriteCopy => {
+                self.handle_write_copy(&input);
+            }
+            PromptType::ExportSettingsBundle => {
+                self.handle_export_settings_bundle(&input);
+            }
+            PromptType::ImportSettingsBundle => {
+                self.handle_import_settings_bundle(&input);
+            }
+            PromptType::ApplySettingsBundle => {
+                self.handle_apply_settings_bundle(&input);
+            }
             PromptType::AsyncPrompt => {
                 // Resolve the pending async prompt callback with the input text
                 if let Some(callback_id) = self.pending_async_prompt_callback.take() {
@@ -585,6 +680,7 @@ impl Editor {
 
                 let metadata = BufferMetadata::with_file(full_path.clone(), &self.working_dir);
                 self.buffer_metadata.insert(self.active_buffer(), metadata);
+                self.promote_url_buffer(self.active_buffer());
 
                 // Auto-detect language if it's currently "text"
                 // This ensures syntax highlighting works immediately after "Save As"
@@ -616,17 +712,15 @@ impl Editor {
                 }
 
                 self.active_event_log_mut().mark_saved();
+                self.buffer_saved_at
+                    .insert(self.active_buffer(), std::time::SystemTime::now());
                 tracing::debug!(
                     "SaveFileAs AFTER mark_saved: event_log index={}, len={}",
                     self.active_event_log().current_index(),
                     self.active_event_log().len()
                 );
 
-                if let Ok(metadata) = self.filesystem.metadata(&full_path) {
-                    if let Some(mtime) = metadata.modified {
-                        self.file_mod_times.insert(full_path.clone(), mtime);
-                    }
-                }
+                self.watch_file(&full_path);
 
                 self.notify_lsp_save();
 
@@ -643,6 +737,11 @@ impl Editor {
                     },
                 );
 
+                self.run_auto_commands(
+                    crate::config::AutoCommandEvent::OnSave,
+                    self.active_buffer(),
+                );
+
                 if let Some(buffer_to_close) = self.pending_close_buffer.take() {
                     if let Err(e) = self.force_close_buffer(buffer_to_close) {
                         self.set_status_message(
@@ -659,7 +758,9 @@ impl Editor {
             }
             Err(e) => {
                 self.pending_close_buffer = None;
-                self.set_status_message(t!("file.error_saving", error = e.to_string()).to_string());
+                self.set_important_status_message(
+                    t!("file.error_saving", error = e.to_string()).to_string(),
+                );
             }
         }
     }
@@ -685,7 +786,7 @@ impl Editor {
                     );
                 }
                 _ => {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("error.invalid_compose_width", input = input).to_string(),
                     );
                 }
@@ -770,7 +871,9 @@ impl Editor {
                 self.set_status_message(t!("settings.tab_size_positive").to_string());
             }
             Err(_) => {
-                self.set_status_message(t!("error.invalid_tab_size", input = input).to_string());
+                self.set_important_status_message(
+                    t!("error.invalid_tab_size", input = input).to_string(),
+                );
             }
         }
     }
@@ -792,13 +895,18 @@ impl Editor {
 
         match line_ending {
             Some(le) => {
-                self.active_state_mut().buffer.set_line_ending(le);
+                self.convert_line_endings(le);
+                self.remember_file_override(|o| {
+                    o.line_ending = Some(le.display_name().to_string())
+                });
                 self.set_status_message(
                     t!("settings.line_ending_set", value = le.display_name()).to_string(),
                 );
             }
             None => {
-                self.set_status_message(t!("error.unknown_line_ending", input = input).to_string());
+                self.set_important_status_message(
+                    t!("error.unknown_line_ending", input = input).to_string(),
+                );
             }
         }
     }
@@ -807,26 +915,12 @@ impl Editor {
     fn handle_set_encoding(&mut self, input: &str) {
         use crate::model::buffer::Encoding;
 
-        let trimmed = input.trim();
-
-        // First try to match the full input against encoding display names
-        // This handles multi-word names like "UTF-16 LE" and "UTF-8 BOM"
-        let encoding = Encoding::all()
-            .iter()
-            .find(|enc| enc.display_name().eq_ignore_ascii_case(trimmed))
-            .copied()
-            .or_else(|| {
-                // If no match, try extracting before the parenthesis (e.g., "UTF-8" from "UTF-8 (Unicode)")
-                let before_paren = trimmed.split('(').next().unwrap_or(trimmed).trim();
-                Encoding::all()
-                    .iter()
-                    .find(|enc| enc.display_name().eq_ignore_ascii_case(before_paren))
-                    .copied()
-            });
+        let encoding = Encoding::from_name(input.trim());
 
         match encoding {
             Some(enc) => {
                 self.active_state_mut().buffer.set_encoding(enc);
+                self.remember_file_override(|o| o.encoding = Some(enc));
                 self.set_status_message(format!("Encoding set to {}", enc.display_name()));
             }
             None => {
@@ -844,20 +938,7 @@ impl Editor {
         use crate::model::buffer::Encoding;
         use crate::view::prompt::PromptType;
 
-        let trimmed = input.trim();
-
-        // Parse the encoding from input
-        let encoding = Encoding::all()
-            .iter()
-            .find(|enc| enc.display_name().eq_ignore_ascii_case(trimmed))
-            .copied()
-            .or_else(|| {
-                let before_paren = trimmed.split('(').next().unwrap_or(trimmed).trim();
-                Encoding::all()
-                    .iter()
-                    .find(|enc| enc.display_name().eq_ignore_ascii_case(before_paren))
-                    .copied()
-            });
+        let encoding = Encoding::from_name(input.trim());
 
         match encoding {
             Some(enc) => {
@@ -899,7 +980,7 @@ impl Editor {
 
                 // Open the file with the specified encoding
                 if let Err(e) = self.open_file_with_encoding(path, enc) {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("file.error_opening", error = e.to_string()).to_string(),
                     );
                 } else {
@@ -921,27 +1002,15 @@ impl Editor {
     fn handle_reload_with_encoding(&mut self, input: &str) {
         use crate::model::buffer::Encoding;
 
-        let trimmed = input.trim();
-
-        // Parse the encoding from input
-        let encoding = Encoding::all()
-            .iter()
-            .find(|enc| enc.display_name().eq_ignore_ascii_case(trimmed))
-            .copied()
-            .or_else(|| {
-                let before_paren = trimmed.split('(').next().unwrap_or(trimmed).trim();
-                Encoding::all()
-                    .iter()
-                    .find(|enc| enc.display_name().eq_ignore_ascii_case(before_paren))
-                    .copied()
-            });
+        let encoding = Encoding::from_name(input.trim());
 
         match encoding {
             Some(enc) => {
                 // Reload the file with the specified encoding
                 if let Err(e) = self.reload_with_encoding(enc) {
-                    self.set_status_message(format!("Failed to reload: {}", e));
+                    self.set_important_status_message(format!("Failed to reload: {}", e));
                 } else {
+                    self.remember_file_override(|o| o.encoding = Some(enc));
                     self.set_status_message(format!(
                         "Reloaded with {} encoding",
                         enc.display_name()
@@ -977,8 +1046,11 @@ impl Editor {
             let buffer_id = self.active_buffer();
             if let Some(state) = self.buffers.get_mut(&buffer_id) {
                 state.apply_language(DetectedLanguage::plain_text());
+                state.buffer_settings.word_rules = self.config.word_rules("text");
+                state.buffer_settings.auto_close_pairs = self.config.auto_close_pairs("text");
                 self.set_status_message("Language set to Plain Text".to_string());
             }
+            self.remember_file_override(|o| o.language = Some("Plain Text".to_string()));
             #[cfg(feature = "plugins")]
             self.update_plugin_state_snapshot();
             self.plugin_manager.run_hook(
@@ -988,6 +1060,10 @@ impl Editor {
                     language: "text".to_string(),
                 },
             );
+            self.run_auto_commands(
+                crate::config::AutoCommandEvent::OnLanguageChange,
+                self.active_buffer(),
+            );
             return;
         }
 
@@ -1001,8 +1077,11 @@ impl Editor {
             let buffer_id = self.active_buffer();
             if let Some(state) = self.buffers.get_mut(&buffer_id) {
                 state.apply_language(detected);
+                state.buffer_settings.word_rules = self.config.word_rules(&language);
+                state.buffer_settings.auto_close_pairs = self.config.auto_close_pairs(&language);
                 self.set_status_message(format!("Language set to {}", trimmed));
             }
+            self.remember_file_override(|o| o.language = Some(trimmed.to_string()));
             #[cfg(feature = "plugins")]
             self.update_plugin_state_snapshot();
             self.plugin_manager.run_hook(
@@ -1012,6 +1091,7 @@ impl Editor {
                     language,
                 },
             );
+            self.run_auto_commands(crate::config::AutoCommandEvent::OnLanguageChange, buffer_id);
         } else {
             self.set_status_message(format!("Unknown language: {}", input));
         }
@@ -1057,7 +1137,7 @@ impl Editor {
                 let old_active = self.active_buffer();
                 self.set_active_buffer(buffer_id);
                 if let Err(e) = self.save() {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("file.save_failed", error = e.to_string()).to_string(),
                     );
                     self.set_active_buffer(old_active);
@@ -1112,7 +1192,7 @@ impl Editor {
                     self.should_quit = true;
                 }
                 Err(e) => {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("file.save_failed", error = e.to_string()).to_string(),
                     );
                     return true; // Early return, stay in editor
@@ -1205,7 +1285,9 @@ impl Editor {
                     self.set_status_message(t!("goto.line_must_be_positive").to_string());
                 }
             } else {
-                self.set_status_message(t!("error.invalid_line", input = line_str).to_string());
+                self.set_important_status_message(
+                    t!("error.invalid_line", input = line_str).to_string(),
+                );
             }
             return PromptResult::Done;
         }
@@ -1267,6 +1349,10 @@ impl Editor {
                         .write()
                         .unwrap()
                         .record_usage(&cmd_name);
+                    // Shared with the command palette's Up/Down history
+                    let history = self.get_or_create_prompt_history("command");
+                    history.push(cmd_name);
+                    history.reset_navigation();
                     return PromptResult::ExecuteAction(action);
                 }
             }
@@ -1309,12 +1395,15 @@ impl Editor {
         PromptResult::Done
     }
 
-    fn open_file_with_jump(
+    pub(super) fn open_file_with_jump(
         &mut self,
         full_path: std::path::PathBuf,
         line: Option<usize>,
         column: Option<usize>,
     ) {
+        if self.try_open_externally(&full_path) {
+            return;
+        }
         match self.open_file(&full_path) {
             Ok(_) => {
                 if let Some(line) = line {
@@ -1331,7 +1420,7 @@ impl Editor {
                 {
                     self.start_large_file_encoding_confirmation(confirmation);
                 } else {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("file.error_opening", error = e.to_string()).to_string(),
                     );
                 }
@@ -0,0 +1,60 @@
+//! Recent Files picker.
+//!
+//! Opening a local file records it in the [`RecentFiles`] list shared by all
+//! sessions. The picker shows that list newest first, skipping files that no
+//! longer exist, filtered as you type with the command palette's fuzzy
+//! matcher; confirming opens the file.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::services::recent_files::RecentFiles;
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+use std::path::Path;
+
+impl Editor {
+    /// Remember `path` as the most recently opened file
+    pub(super) fn record_recent_file(&self, path: &Path) {
+        let limit = self.config.editor.recent_files_limit;
+        if limit == 0 {
+            return;
+        }
+        let mut recent = RecentFiles::load(&self.dir_context);
+        if recent.record(path, limit) {
+            if let Err(e) = recent.save() {
+                tracing::warn!("Failed to save recent files: {}", e);
+            }
+        }
+    }
+
+    /// Open the Recent Files picker
+    pub fn open_recent_files_picker(&mut self) {
+        let recent = RecentFiles::load(&self.dir_context);
+        let suggestions: Vec<Suggestion> = recent
+            .existing()
+            .map(|file| Suggestion {
+                text: file
+                    .path
+                    .strip_prefix(&self.working_dir)
+                    .unwrap_or(&file.path)
+                    .display()
+                    .to_string(),
+                description: None,
+                value: Some(file.path.display().to_string()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+        if suggestions.is_empty() {
+            self.set_status_message(t!("recent_files.none").to_string());
+            return;
+        }
+
+        self.start_prompt_with_suggestions(
+            "Recent files: ".to_string(),
+            PromptType::RecentFiles,
+            suggestions,
+        );
+    }
+}
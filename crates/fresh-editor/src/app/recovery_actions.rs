@@ -8,6 +8,7 @@
 //! - Cleaning up recovery files
 
 use anyhow::Result as AnyhowResult;
+use rust_i18n::t;
 
 use crate::model::event::BufferId;
 
@@ -32,7 +33,7 @@ impl Editor {
                     state.buffer.set_recovery_pending(true);
                 }
             }
-            self.save_pending_recovery_buffers()?;
+            self.save_pending_recovery_buffers(true)?;
 
             // Collect recovery IDs for buffers that should survive this session
             let preserve_ids = self.recovery_ids_to_preserve();
@@ -113,12 +114,19 @@ impl Editor {
                         match self.open_file(&path) {
                             Ok(_) => {
                                 // Replace buffer content with recovered content
+                                let buffer_id = self.active_buffer();
                                 let state = self.active_state_mut();
+                                let old_text = state.buffer.to_string();
                                 let total = state.buffer.total_bytes();
                                 state.buffer.delete(0..total);
                                 state.buffer.insert(0, &text);
                                 // Mark as modified since it differs from disk
                                 state.buffer.set_modified(true);
+                                self.relocate_views_by_content(
+                                    buffer_id,
+                                    old_text.as_deref(),
+                                    Some(&text),
+                                );
                                 recovered_count += 1;
                                 tracing::info!("Recovered buffer: {}", path.display());
                             }
@@ -182,6 +190,13 @@ impl Editor {
                 }
                 Ok(RecoveryResult::Corrupted { id, reason }) => {
                     tracing::warn!("Recovery file {} corrupted: {}", id, reason);
+                    self.set_status_message(
+                        t!(
+                            "status.recovery_corrupted",
+                            path = entry.metadata.display_name()
+                        )
+                        .to_string(),
+                    );
                 }
                 Ok(RecoveryResult::NotFound { id }) => {
                     tracing::warn!("Recovery file {} not found", id);
@@ -202,33 +217,115 @@ impl Editor {
     }
 
     /// Perform auto-recovery-save for all modified buffers if needed.
-    /// Called frequently (every frame); rate-limited by `auto_recovery_save_interval_secs`.
+    /// Called frequently (every frame); rate-limited by `auto_recovery_save_interval_secs`,
+    /// which is read on every call so a reloaded config applies right away.
     pub fn auto_recovery_save_dirty_buffers(&mut self) -> AnyhowResult<usize> {
         if !self.recovery_service.is_enabled() {
+            self.recovery_write_in_progress = false;
             return Ok(0);
         }
 
-        let interval = std::time::Duration::from_secs(
-            self.config.editor.auto_recovery_save_interval_secs as u64,
-        );
-        if self.time_source.elapsed_since(self.last_auto_recovery_save) < interval {
+        if !self.auto_recovery_save_due() {
             return Ok(0);
         }
 
-        let saved = self.save_pending_recovery_buffers()?;
+        let result = self.save_pending_recovery_buffers(false);
+        self.recovery_write_in_progress = false;
         self.last_auto_recovery_save = self.time_source.now();
-        Ok(saved)
+        result
+    }
+
+    /// Write recovery data for every buffer with unsaved changes right now,
+    /// ignoring the auto-save interval and any write backoff. Useful before
+    /// a risky operation; the next periodic save is a full interval away.
+    pub fn force_recovery_checkpoint(&mut self) {
+        if !self.recovery_service.is_enabled() {
+            self.set_status_message(t!("status.checkpoint_disabled").to_string());
+            return;
+        }
+
+        let result = self.save_pending_recovery_buffers(true);
+        self.recovery_write_in_progress = false;
+        self.last_auto_recovery_save = self.time_source.now();
+        match result {
+            // A full disk switches recovery off and says so itself
+            Ok(_) if !self.recovery_service.is_enabled() => {}
+            Ok(count) => {
+                self.set_status_message(t!("status.checkpoint_saved", count = count).to_string())
+            }
+            Err(e) => {
+                tracing::warn!("Recovery checkpoint failed: {}", e);
+                self.set_important_status_message(
+                    t!("status.checkpoint_failed", error = e.to_string()).to_string(),
+                );
+            }
+        }
+    }
+
+    /// Whether a recovery write of a large buffer is about to happen
+    pub fn recovery_write_in_progress(&self) -> bool {
+        self.recovery_write_in_progress
+    }
+
+    /// Flag the next auto-recovery-save if it will write a large buffer, so
+    /// the "saving recovery" indicator can be rendered before the write
+    /// blocks. Returns `true` if the flag was just set; the event loop then
+    /// renders and leaves the save itself to its next tick.
+    pub fn announce_large_recovery_write(&mut self) -> bool {
+        if self.recovery_write_in_progress
+            || !self.recovery_service.is_enabled()
+            || !self.auto_recovery_save_due()
+        {
+            return false;
+        }
+        self.recovery_write_in_progress = self.has_large_pending_recovery();
+        self.recovery_write_in_progress
+    }
+
+    /// Whether the auto-recovery-save interval has passed since the last save
+    fn auto_recovery_save_due(&self) -> bool {
+        let interval = std::time::Duration::from_secs(
+            self.config.editor.auto_recovery_save_interval_secs as u64,
+        );
+        self.time_source.elapsed_since(self.last_auto_recovery_save) >= interval
+    }
+
+    /// Check if any large buffer is waiting for a (non-backed-off) recovery save
+    fn has_large_pending_recovery(&self) -> bool {
+        self.buffers.iter().any(|(buffer_id, state)| {
+            let is_large = state.buffer.is_large_file()
+                || self
+                    .recovery_service
+                    .is_large_buffer(state.buffer.total_bytes());
+            if state.is_composite_buffer || !state.buffer.is_recovery_pending() || !is_large {
+                return false;
+            }
+            let recovery_id = self
+                .buffer_metadata
+                .get(buffer_id)
+                .and_then(|meta| meta.recovery_id.clone())
+                .unwrap_or_else(|| {
+                    self.recovery_service
+                        .get_buffer_id(state.buffer.file_path())
+                });
+            !self.recovery_service.is_backed_off(&recovery_id)
+        })
     }
 
     /// Save all buffers marked `recovery_pending` to recovery storage.
     /// Shared by the periodic auto-save and the exit flush.
-    fn save_pending_recovery_buffers(&mut self) -> AnyhowResult<usize> {
+    ///
+    /// The periodic save (`flush == false`) skips buffers that are still
+    /// backing off after a slow write; the exit flush saves everything.
+    /// If the disk fills up, recovery is switched off with a warning.
+    fn save_pending_recovery_buffers(&mut self, flush: bool) -> AnyhowResult<usize> {
         if !self.recovery_service.is_enabled() {
             return Ok(0);
         }
 
         // Collect buffer IDs that need recovery (immutable pass).
-        // Skip composite/hidden buffers — they are not real user content.
+        // Skip composite/hidden buffers — they are not real user content —
+        // and read-only ones, which must not leave copies behind.
         let buffers_needing_recovery: Vec<_> = self
             .buffers
             .iter()
@@ -237,7 +334,7 @@ impl Editor {
                     return None;
                 }
                 if let Some(meta) = self.buffer_metadata.get(buffer_id) {
-                    if meta.hidden_from_tabs || meta.is_virtual() {
+                    if meta.hidden_from_tabs || meta.is_virtual() || meta.read_only {
                         return None;
                     }
                 }
@@ -282,6 +379,9 @@ impl Editor {
                     self.recovery_service.get_buffer_id(path.as_deref())
                 };
                 let recovery_pending = state.buffer.is_recovery_pending();
+                if !flush && self.recovery_service.is_backed_off(&recovery_id) {
+                    return None;
+                }
                 if self
                     .recovery_service
                     .needs_auto_recovery_save(&recovery_id, recovery_pending)
@@ -295,8 +395,15 @@ impl Editor {
 
         let mut saved_count = 0;
         for (buffer_id, recovery_id, path) in buffer_info {
-            if self.save_buffer_to_recovery(&buffer_id, &recovery_id, path.as_deref())? {
-                saved_count += 1;
+            match self.save_buffer_to_recovery(&buffer_id, &recovery_id, path.as_deref()) {
+                Ok(true) => saved_count += 1,
+                Ok(false) => {}
+                Err(_) if self.recovery_service.is_suspended() => {
+                    // Out of disk space: keep editing, just without recovery
+                    self.set_status_message(t!("status.recovery_disabled_disk_full").to_string());
+                    break;
+                }
+                Err(e) => return Err(e),
             }
         }
         Ok(saved_count)
@@ -33,12 +33,6 @@ fn normalize_replacement(template: &str) -> String {
     let mut i = 0;
     while i < bytes.len() {
         if bytes[i] == b'$' && i + 1 < bytes.len() && bytes[i + 1].is_ascii_digit() {
-            // Already braced: ${ … }
-            if i + 1 < bytes.len() && bytes[i + 1] == b'{' {
-                out.push(bytes[i] as char);
-                i += 1;
-                continue;
-            }
             // Collect the digit run
             let start = i + 1;
             let mut end = start;
@@ -54,13 +48,136 @@ fn normalize_replacement(template: &str) -> String {
             out.push('}');
             i = end;
         } else {
-            out.push(bytes[i] as char);
-            i += 1;
+            // Copy up to the next `$`, keeping multi-byte characters whole
+            let next = i + template[i..].chars().next().unwrap().len_utf8();
+            let end = template[next..]
+                .find('$')
+                .map_or(template.len(), |offset| next + offset);
+            out.push_str(&template[i..end]);
+            i = end;
         }
     }
     out
 }
 
+/// Case change applied to the expanded text after a `\U`, `\L` or `\E`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CaseChange {
+    None,
+    Upper,
+    Lower,
+}
+
+/// Why a replacement template can't be used with a pattern
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateError {
+    /// A `$N`, `${N}` or `${name}` naming a group the pattern doesn't have
+    UnknownGroup(String),
+    /// A `${` without its closing brace
+    UnclosedGroup,
+}
+
+/// A replacement template checked against the pattern it replaces matches
+/// of. Besides `$1`, `${1}`, `${name}` and `$$`, it may contain `\U` and
+/// `\L` to upper- or lowercase the rest of the replacement, and `\E` to end
+/// that.
+#[derive(Debug, Clone)]
+pub struct ReplaceTemplate {
+    /// The template as written
+    raw: String,
+    /// Runs of the template between case escapes, normalized for
+    /// [`regex::bytes::Captures::expand`]
+    segments: Vec<(CaseChange, String)>,
+}
+
+impl ReplaceTemplate {
+    /// Parse `template`, checking that every group it refers to is a group
+    /// of `regex`
+    pub fn parse(template: &str, regex: &regex::bytes::Regex) -> Result<Self, TemplateError> {
+        let mut segments = Vec::new();
+        let mut case = CaseChange::None;
+        let mut rest = template;
+        loop {
+            let escape = rest.match_indices('\\').find_map(|(i, _)| {
+                let change = match rest.as_bytes().get(i + 1) {
+                    Some(b'U') => CaseChange::Upper,
+                    Some(b'L') => CaseChange::Lower,
+                    Some(b'E') => CaseChange::None,
+                    _ => return None,
+                };
+                Some((i, change))
+            });
+            let end = escape.map_or(rest.len(), |(i, _)| i);
+            if end > 0 {
+                let segment = normalize_replacement(&rest[..end]);
+                check_groups(&segment, regex)?;
+                segments.push((case, segment));
+            }
+            let Some((i, change)) = escape else {
+                break;
+            };
+            case = change;
+            rest = &rest[i + 2..];
+        }
+        Ok(Self {
+            raw: template.to_string(),
+            segments,
+        })
+    }
+
+    /// The replacement for the match `caps`
+    pub fn expand(&self, caps: &regex::bytes::Captures) -> String {
+        let mut out = String::new();
+        for (case, segment) in &self.segments {
+            let mut expanded = Vec::new();
+            caps.expand(segment.as_bytes(), &mut expanded);
+            let expanded = String::from_utf8_lossy(&expanded);
+            match case {
+                CaseChange::None => out.push_str(&expanded),
+                CaseChange::Upper => out.push_str(&expanded.to_uppercase()),
+                CaseChange::Lower => out.push_str(&expanded.to_lowercase()),
+            }
+        }
+        out
+    }
+}
+
+/// Check the group references of a normalized template segment, following
+/// the regex crate's syntax: `$$` is a literal `$`, and a `$` not followed by
+/// a name or `{` is kept as is.
+fn check_groups(segment: &str, regex: &regex::bytes::Regex) -> Result<(), TemplateError> {
+    let mut rest = segment;
+    while let Some(i) = rest.find('$') {
+        rest = &rest[i + 1..];
+        let name = if let Some(braced) = rest.strip_prefix('{') {
+            let close = braced.find('}').ok_or(TemplateError::UnclosedGroup)?;
+            rest = &braced[close + 1..];
+            &braced[..close]
+        } else if let Some(after) = rest.strip_prefix('$') {
+            rest = after;
+            continue;
+        } else {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let name = &rest[..len];
+            rest = &rest[len..];
+            name
+        };
+        if name.is_empty() {
+            continue;
+        }
+        let known = match name.parse::<usize>() {
+            Ok(index) => index < regex.captures_len(),
+            Err(_) => regex.capture_names().flatten().any(|n| n == name),
+        };
+        if !known {
+            return Err(TemplateError::UnknownGroup(name.to_string()));
+        }
+    }
+    Ok(())
+}
+
 /// A single match found in a buffer, together with its expanded replacement.
 #[derive(Debug, Clone)]
 pub struct ReplaceMatch {
@@ -72,43 +189,36 @@ pub struct ReplaceMatch {
     pub replacement: String,
 }
 
-/// Find every match of `regex` in `haystack` and expand `replacement_template`
-/// for each one (honouring `$1`, `${name}`, etc.).
+/// Find every match of `regex` in `haystack` and expand `template` for each
+/// one.
 pub fn collect_regex_matches(
     regex: &regex::bytes::Regex,
     haystack: &[u8],
-    replacement_template: &str,
+    template: &ReplaceTemplate,
 ) -> Vec<ReplaceMatch> {
-    let normalized = normalize_replacement(replacement_template);
     regex
         .captures_iter(haystack)
         .map(|caps| {
             let m = caps.get(0).unwrap();
-            let mut expanded = Vec::new();
-            caps.expand(normalized.as_bytes(), &mut expanded);
             ReplaceMatch {
                 offset: m.start(),
                 len: m.len(),
-                replacement: String::from_utf8_lossy(&expanded).into_owned(),
+                replacement: template.expand(&caps),
             }
         })
         .collect()
 }
 
-/// Expand capture-group references in `replacement_template` against a single
-/// match. Returns the template unchanged when no captures are found.
+/// Expand `template` against a single match. Returns the template unchanged
+/// when no captures are found.
 pub fn expand_replacement(
     regex: &regex::bytes::Regex,
     matched_bytes: &[u8],
-    replacement_template: &str,
+    template: &ReplaceTemplate,
 ) -> String {
-    if let Some(caps) = regex.captures(matched_bytes) {
-        let normalized = normalize_replacement(replacement_template);
-        let mut dst = Vec::new();
-        caps.expand(normalized.as_bytes(), &mut dst);
-        String::from_utf8_lossy(&dst).into_owned()
-    } else {
-        replacement_template.to_string()
+    match regex.captures(matched_bytes) {
+        Some(caps) => template.expand(&caps),
+        None => template.raw.clone(),
     }
 }
 
@@ -116,6 +226,10 @@ pub fn expand_replacement(
 mod tests {
     use super::*;
 
+    fn template(re: &regex::bytes::Regex, template: &str) -> ReplaceTemplate {
+        ReplaceTemplate::parse(template, re).unwrap()
+    }
+
     #[test]
     fn build_regex_returns_none_when_disabled() {
         assert!(build_regex("foo", false, false, true).is_none());
@@ -146,7 +260,7 @@ mod tests {
     fn collect_regex_matches_literal_replacement() {
         let re = build_regex("Sig:.*", true, false, true).unwrap();
         let input = b"AAAAAA\nSig: hello\nBBBBBB\nSig: world\nCCCCCC";
-        let matches = collect_regex_matches(&re, input, "");
+        let matches = collect_regex_matches(&re, input, &template(&re, ""));
 
         assert_eq!(matches.len(), 2);
         assert_eq!(matches[0].offset, 7);
@@ -159,7 +273,7 @@ mod tests {
     fn collect_regex_matches_with_capture_groups() {
         let re = build_regex(r"(\w+)@(\w+)", true, false, true).unwrap();
         let input = b"alice@example bob@test";
-        let matches = collect_regex_matches(&re, input, "$2=$1");
+        let matches = collect_regex_matches(&re, input, &template(&re, "$2=$1"));
 
         assert_eq!(matches.len(), 2);
         assert_eq!(matches[0].replacement, "example=alice");
@@ -170,7 +284,7 @@ mod tests {
     fn expand_replacement_with_groups() {
         let re = build_regex(r"(\d+)-(\d+)", true, false, true).unwrap();
         let matched = b"123-456";
-        let result = expand_replacement(&re, matched, "$2/$1");
+        let result = expand_replacement(&re, matched, &template(&re, "$2/$1"));
         assert_eq!(result, "456/123");
     }
 
@@ -178,7 +292,7 @@ mod tests {
     fn expand_replacement_no_groups() {
         let re = build_regex("hello", true, false, true).unwrap();
         let matched = b"hello";
-        let result = expand_replacement(&re, matched, "world");
+        let result = expand_replacement(&re, matched, &template(&re, "world"));
         assert_eq!(result, "world");
     }
 
@@ -196,6 +310,47 @@ mod tests {
         assert_eq!(normalize_replacement("$name"), "$name");
         // Literal $$ → passed through ($ not followed by digit)
         assert_eq!(normalize_replacement("$$"), "$$");
+        // Multi-byte characters are copied whole
+        assert_eq!(normalize_replacement("é$1ü"), "é${1}ü");
+    }
+
+    #[test]
+    fn case_escapes_change_the_rest_of_the_replacement() {
+        let re = build_regex(r"(?P<key>\w+)=(\w+)", true, false, true).unwrap();
+        let matches =
+            collect_regex_matches(&re, b"Name=Value", &template(&re, r"\U${key}\E_\L$2\E!"));
+        assert_eq!(matches[0].replacement, "NAME_value!");
+
+        // Literal text is changed too, up to the end without \E
+        let re = build_regex(r"(\w+)", true, false, true).unwrap();
+        let result = expand_replacement(&re, "straße".as_bytes(), &template(&re, r"x\U$1 y"));
+        assert_eq!(result, "xSTRASSE Y");
+
+        // Other backslashes are kept
+        let result = expand_replacement(&re, b"a", &template(&re, r"\n\$1"));
+        assert_eq!(result, r"\n\a");
+    }
+
+    #[test]
+    fn parse_rejects_unknown_groups() {
+        let re = build_regex(r"(?P<key>\w+)=(\w+)", true, false, true).unwrap();
+        assert!(ReplaceTemplate::parse("$0 $1 $2 ${key} $key $$3 $ end", &re).is_ok());
+        assert_eq!(
+            ReplaceTemplate::parse("$3", &re).unwrap_err(),
+            TemplateError::UnknownGroup("3".to_string())
+        );
+        assert_eq!(
+            ReplaceTemplate::parse(r"\U${value}", &re).unwrap_err(),
+            TemplateError::UnknownGroup("value".to_string())
+        );
+        assert_eq!(
+            ReplaceTemplate::parse("$val", &re).unwrap_err(),
+            TemplateError::UnknownGroup("val".to_string())
+        );
+        assert_eq!(
+            ReplaceTemplate::parse("${key", &re).unwrap_err(),
+            TemplateError::UnclosedGroup
+        );
     }
 
     /// Matches Python: re.sub(r'bla(bla)', r'oo\1oo', 'blablabla') == 'ooblaoobla'
@@ -203,7 +358,7 @@ mod tests {
     fn collect_regex_matches_capture_group_blabla() {
         let re = build_regex(r"bla(bla)", true, false, true).unwrap();
         let input = b"blablabla";
-        let matches = collect_regex_matches(&re, input, "oo$1oo");
+        let matches = collect_regex_matches(&re, input, &template(&re, "oo$1oo"));
 
         assert_eq!(matches.len(), 1);
         assert_eq!(matches[0].offset, 0);
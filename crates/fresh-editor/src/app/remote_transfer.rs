@@ -0,0 +1,321 @@
+//! Progress of chunked remote downloads and uploads.
+//!
+//! Opening a remote file that would be read in one request, and explicitly
+//! saving a large remote buffer held in memory, go through
+//! [`RemoteTransfer`] instead. A download opens the buffer at once, read-only,
+//! and appends each chunk as it arrives, the way stdin is streamed; a failed
+//! or cancelled download closes the buffer so a partial file is never saved
+//! over the real one. An upload keeps the buffer read-only until the file is
+//! replaced. The status bar shows the progress of the latest chunk.
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::model::filesystem::FileSystem;
+use crate::services::remote::{RemoteTransfer, STREAMED_TRANSFER_BYTES};
+use rust_i18n::t;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A remote transfer filling or saving a buffer
+pub(crate) struct BufferTransfer {
+    transfer: RemoteTransfer,
+    buffer_id: BufferId,
+    /// File name shown in progress messages
+    name: String,
+    /// Path uploaded to, None for a download
+    upload_path: Option<PathBuf>,
+    done: u64,
+    total: u64,
+}
+
+impl Editor {
+    /// Sample of `path` to open it as a streamed download from, if it is a
+    /// remote file large enough to stream that would otherwise be read
+    /// whole. Files past `large_file_threshold_bytes` load lazily instead.
+    pub(super) fn remote_download_sample(
+        &self,
+        filesystem: &Arc<dyn FileSystem + Send + Sync>,
+        path: &Path,
+    ) -> Option<(u64, Vec<u8>)> {
+        if filesystem.remote_connection_info().is_none()
+            || self.tokio_runtime.is_none()
+            || self.async_bridge.is_none()
+        {
+            return None;
+        }
+        let size = filesystem.metadata(path).ok()?.size;
+        if size < STREAMED_TRANSFER_BYTES || size >= self.config.editor.large_file_threshold_bytes {
+            return None;
+        }
+        let sample = filesystem.read_range(path, 0, 8 * 1024).ok()?;
+        Some((size, sample))
+    }
+
+    /// Fill `buffer_id`, opened empty and read-only, from `path` in the
+    /// background
+    pub(super) fn start_remote_download(
+        &mut self,
+        buffer_id: BufferId,
+        filesystem: Arc<dyn FileSystem + Send + Sync>,
+        path: &Path,
+        size: u64,
+    ) {
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            return;
+        };
+        let transfer = RemoteTransfer::download(
+            runtime.handle(),
+            bridge.sender(),
+            filesystem,
+            path.to_path_buf(),
+            size,
+        );
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+        }
+        self.track_remote_transfer(transfer, buffer_id, path, None, size);
+    }
+
+    /// Save the active buffer as a chunked upload if it is large, remote and
+    /// held in memory. Returns false if it should be saved the usual way.
+    pub(super) fn start_remote_upload(&mut self) -> bool {
+        let buffer_id = self.active_buffer();
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            return false;
+        };
+        let buffer = &self.active_state().buffer;
+        let filesystem = Arc::clone(buffer.filesystem());
+        if filesystem.remote_connection_info().is_none()
+            || (buffer.len() as u64) < STREAMED_TRANSFER_BYTES
+        {
+            return false;
+        }
+        let Some(path) = buffer.file_path().map(Path::to_path_buf) else {
+            return false;
+        };
+        // Replacing the file would take it over from its owner; the usual
+        // save writes in place for that
+        if filesystem.exists(&path) && !filesystem.is_owner(&path) {
+            return false;
+        }
+        let contents = match buffer.in_memory_save_contents() {
+            Ok(Some(contents)) => contents,
+            Ok(None) => return false,
+            Err(e) => {
+                tracing::warn!("Falling back to a plain save of {}: {}", path.display(), e);
+                return false;
+            }
+        };
+
+        let total = contents.len() as u64;
+        let transfer = RemoteTransfer::upload(
+            runtime.handle(),
+            bridge.sender(),
+            filesystem,
+            path.clone(),
+            contents,
+        );
+        self.active_state_mut().editing_disabled = true;
+        self.track_remote_transfer(transfer, buffer_id, &path, Some(path.clone()), total);
+        true
+    }
+
+    /// Whether the active buffer is still downloading or uploading, which
+    /// keeps it from being saved; tells the user if so
+    pub(super) fn remote_transfer_busy(&mut self) -> bool {
+        let Some(transfer) = self.buffer_transfer(self.active_buffer()) else {
+            return false;
+        };
+        let message = t!("remote_transfer.busy", name = &transfer.name).to_string();
+        self.set_status_message(message);
+        true
+    }
+
+    fn track_remote_transfer(
+        &mut self,
+        transfer: RemoteTransfer,
+        buffer_id: BufferId,
+        path: &Path,
+        upload_path: Option<PathBuf>,
+        total: u64,
+    ) {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        let transfer = BufferTransfer {
+            transfer,
+            buffer_id,
+            name,
+            upload_path,
+            done: 0,
+            total,
+        };
+        self.set_status_message(transfer_progress(&transfer));
+        self.remote_transfers
+            .insert(transfer.transfer.id(), transfer);
+    }
+
+    fn buffer_transfer(&self, buffer_id: BufferId) -> Option<&BufferTransfer> {
+        self.remote_transfers
+            .values()
+            .find(|transfer| transfer.buffer_id == buffer_id)
+    }
+
+    /// Append a downloaded chunk to its buffer
+    pub(super) fn handle_remote_download_chunk(&mut self, transfer_id: u64, bytes: Vec<u8>) {
+        let Some(transfer) = self.remote_transfers.get_mut(&transfer_id) else {
+            return;
+        };
+        let Some(state) = self.buffers.get_mut(&transfer.buffer_id) else {
+            // The buffer was closed; dropping the transfer stops it
+            self.remote_transfers.remove(&transfer_id);
+            return;
+        };
+        transfer.done += bytes.len() as u64;
+        state.buffer.append_streaming_bytes(bytes);
+        let message = transfer_progress(transfer);
+        self.set_status_message(message);
+    }
+
+    /// Show how much of an upload is written
+    pub(super) fn handle_remote_upload_progress(&mut self, transfer_id: u64, sent: u64) {
+        let Some(transfer) = self.remote_transfers.get_mut(&transfer_id) else {
+            return;
+        };
+        transfer.done = sent;
+        let message = transfer_progress(transfer);
+        self.set_status_message(message);
+    }
+
+    /// Make the buffer of a finished transfer editable again, or close it if
+    /// its download failed
+    pub(super) fn handle_remote_transfer_finished(
+        &mut self,
+        transfer_id: u64,
+        result: Result<(), String>,
+    ) {
+        let Some(transfer) = self.remote_transfers.remove(&transfer_id) else {
+            return;
+        };
+        let buffer_id = transfer.buffer_id;
+        if !self.buffers.contains_key(&buffer_id) {
+            return;
+        }
+
+        match (transfer.upload_path, result) {
+            (None, Ok(())) => {
+                self.restore_editing(buffer_id);
+                if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                    state.buffer.finish_streamed_file();
+                }
+                self.set_status_message(t!("buffer.opened", name = &transfer.name).to_string());
+            }
+            (None, Err(error)) => {
+                if let Err(e) = self.force_close_buffer(buffer_id) {
+                    tracing::warn!("Could not close partial download: {}", e);
+                }
+                self.set_important_status_message(
+                    t!(
+                        "remote_transfer.download_failed",
+                        name = &transfer.name,
+                        error = error
+                    )
+                    .to_string(),
+                );
+            }
+            (Some(path), Ok(())) => {
+                self.restore_editing(buffer_id);
+                let finalized = self
+                    .buffers
+                    .get_mut(&buffer_id)
+                    .map(|state| state.buffer.finalize_external_save(path.clone()));
+                if let Some(Err(e)) = finalized {
+                    tracing::warn!("Could not update saved state of {}: {}", path.display(), e);
+                }
+                // On-save actions work on the active buffer only
+                let silent = buffer_id != self.active_buffer();
+                if let Err(e) = self.finalize_save_buffer(buffer_id, Some(path), silent) {
+                    tracing::warn!("Failed to finalize upload: {}", e);
+                }
+                if silent {
+                    self.set_status_message(t!("status.file_saved").to_string());
+                }
+            }
+            (Some(_), Err(error)) => {
+                self.restore_editing(buffer_id);
+                self.set_important_status_message(
+                    t!(
+                        "remote_transfer.upload_failed",
+                        name = &transfer.name,
+                        error = error
+                    )
+                    .to_string(),
+                );
+            }
+        }
+    }
+
+    /// Cancel the download or upload of the active buffer. A cancelled
+    /// download closes its buffer; a cancelled upload leaves the file as it
+    /// was.
+    pub fn cancel_remote_transfer(&mut self) {
+        let buffer_id = self.active_buffer();
+        let Some(transfer_id) = self
+            .buffer_transfer(buffer_id)
+            .map(|transfer| transfer.transfer.id())
+        else {
+            self.set_status_message(t!("remote_transfer.none").to_string());
+            return;
+        };
+        // Dropping the transfer stops it
+        let Some(transfer) = self.remote_transfers.remove(&transfer_id) else {
+            return;
+        };
+        if transfer.upload_path.is_some() {
+            self.restore_editing(buffer_id);
+        } else if let Err(e) = self.force_close_buffer(buffer_id) {
+            tracing::warn!("Could not close cancelled download: {}", e);
+        }
+        self.set_status_message(t!("remote_transfer.cancelled", name = &transfer.name).to_string());
+    }
+
+    /// Let the user edit a buffer again after its transfer, unless it is
+    /// read-only anyway
+    fn restore_editing(&mut self, buffer_id: BufferId) {
+        let read_only = self.is_read_only(buffer_id);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = read_only;
+        }
+    }
+}
+
+/// "Downloading access.log: 45% (134.0/300.0 MB)"
+fn transfer_progress(transfer: &BufferTransfer) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    let percent = (transfer.done * 100)
+        .checked_div(transfer.total)
+        .unwrap_or(100);
+    let done = format!("{:.1}", transfer.done as f64 / MB);
+    let total = format!("{:.1}", transfer.total as f64 / MB);
+    let name = &transfer.name;
+    if transfer.upload_path.is_some() {
+        t!(
+            "remote_transfer.uploading",
+            name = name,
+            percent = percent,
+            done = done,
+            total = total
+        )
+        .to_string()
+    } else {
+        t!(
+            "remote_transfer.downloading",
+            name = name,
+            percent = percent,
+            done = done,
+            total = total
+        )
+        .to_string()
+    }
+}
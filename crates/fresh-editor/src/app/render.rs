@@ -1,13 +1,22 @@
+use super::regex_replace::{ReplaceTemplate, TemplateError};
 use super::*;
+use crate::primitives::secret_mask::mask_all;
+use crate::primitives::word_navigation::{is_whole_word_match, WordRules};
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
 
+/// Average round trip above which the remote latency badge turns yellow
+const SLOW_ROUND_TRIP: std::time::Duration = std::time::Duration::from_millis(200);
+
 impl Editor {
     /// Render the editor to the terminal
     pub fn render(&mut self, frame: &mut Frame) {
         let _span = tracing::info_span!("render").entered();
         let size = frame.area();
 
+        // Restored files shown by any means other than `set_active_buffer`
+        self.load_shown_deferred_buffers();
+
         // Save frame dimensions for recompute_layout (used by macro replay)
         self.cached_layout.last_frame_width = size.width;
         self.cached_layout.last_frame_height = size.height;
@@ -102,6 +111,9 @@ impl Editor {
                     | PromptType::Replace { .. }
                     | PromptType::QueryReplaceSearch
                     | PromptType::QueryReplace { .. }
+                    | PromptType::ProjectSearch
+                    | PromptType::ProjectReplaceSearch
+                    | PromptType::ProjectReplace { .. }
             )
         });
 
@@ -443,12 +455,15 @@ impl Editor {
             is_maximized,
             self.config.editor.relative_line_numbers,
             self.tab_bar_visible,
+            self.config.editor.ui_density.is_tight(),
             self.config.editor.use_terminal_bg,
             self.session_mode || !self.config.editor.cursor_style.is_block(),
             self.software_cursor_only,
             self.config.editor.show_vertical_scrollbar,
             self.config.editor.show_horizontal_scrollbar,
             self.config.editor.diagnostics_inline_text,
+            self.config.editor.fold_indicators,
+            self.privacy_masker.as_ref(),
         );
 
         drop(_content_span);
@@ -551,7 +566,14 @@ impl Editor {
             .get(&self.active_buffer())
             .map(|m| m.display_name.clone())
             .unwrap_or_else(|| "[No Name]".to_string());
-        let status_message = self.status_message.clone();
+        let mut status_message = self.status_message.clone();
+        if self.recovery_write_in_progress {
+            let indicator = t!("status.recovery_saving").to_string();
+            status_message = Some(match status_message {
+                Some(msg) if !msg.is_empty() => format!("{} | {}", indicator, msg),
+                _ => indicator,
+            });
+        }
         let plugin_status_message = self.plugin_status_message.clone();
         let prompt = self.prompt.clone();
         let lsp_status = self.lsp_status.clone();
@@ -588,8 +610,20 @@ impl Editor {
                 _ => StatusBarHover::None,
             };
 
-            // Get remote connection info if editing remote files
-            let remote_connection = self.remote_connection_info().map(|s| s.to_string());
+            // Get remote connection info if editing remote files. The latency
+            // badge, when shown, reports a connection that is down itself.
+            let remote_latency_badge = self.remote_latency_badge();
+            let remote_badge = remote_latency_badge
+                .as_ref()
+                .map(|(text, level)| (text.as_str(), *level));
+            let remote_connection = self.active_remote_connection().map(|s| {
+                if remote_badge.is_some() || self.active_state().buffer.filesystem().is_connected()
+                {
+                    s.to_string()
+                } else {
+                    format!("{} {}", s, t!("status.remote_disconnected"))
+                }
+            });
 
             // Get session name for display (only in session mode)
             let session_name = self.session_name().map(|s| s.to_string());
@@ -607,6 +641,8 @@ impl Editor {
                 .get(&active_buf)
                 .map(|m| m.read_only)
                 .unwrap_or(false);
+            let is_missing_on_disk = self.is_missing_on_disk(active_buf);
+            let search_count = self.search_match_count();
             let status_bar_layout = StatusBarRenderer::render_status_bar(
                 frame,
                 main_chunks[status_bar_idx],
@@ -624,8 +660,12 @@ impl Editor {
                 general_warning_count,        // Pass general warning count for badge
                 status_bar_hover,             // Pass hover state for indicator styling
                 remote_connection.as_deref(), // Pass remote connection info
+                remote_badge,                 // Pass remote latency badge
                 session_name.as_deref(),      // Pass session name for status bar display
                 is_read_only,                 // Pass read-only flag from metadata
+                is_missing_on_disk,
+                search_count.as_deref(),
+                self.privacy_masker.is_some(),
             );
 
             // Store status bar layout for click detection
@@ -1019,6 +1059,52 @@ impl Editor {
         );
     }
 
+    /// Latency badge of the active buffer's remote connection, e.g.
+    /// "ssh:host 42ms", with how alarming it is: slow round trips warn, a
+    /// missed ping or a lost connection is an error. Starts measuring the
+    /// connection the first time it is shown.
+    fn remote_latency_badge(&mut self) -> Option<(String, WarningLevel)> {
+        if !self.config.editor.show_remote_latency {
+            return None;
+        }
+        let connection = self.active_remote_connection()?.to_string();
+        let filesystem = std::sync::Arc::clone(self.active_state().buffer.filesystem());
+
+        if self.remote_latency_monitor.is_none() {
+            if let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) {
+                self.remote_latency_monitor =
+                    Some(crate::services::remote::RemoteLatencyMonitor::start(
+                        runtime.handle(),
+                        bridge.sender(),
+                    ));
+            }
+        }
+        if let Some(monitor) = &self.remote_latency_monitor {
+            monitor.watch(&connection, &filesystem);
+        }
+
+        if !filesystem.is_connected() {
+            return Some((
+                format!("ssh:{} {}", connection, t!("status.remote_reconnecting")),
+                WarningLevel::Error,
+            ));
+        }
+        let latency = self.remote_latency.get(&connection);
+        let average = latency.and_then(|latency| latency.average());
+        let text = match average {
+            Some(average) => format!("ssh:{} {}ms", connection, average.as_millis()),
+            None => format!("ssh:{} …", connection),
+        };
+        let level = if latency.is_some_and(|latency| latency.missed()) {
+            WarningLevel::Error
+        } else if average.is_some_and(|average| average > SLOW_ROUND_TRIP) {
+            WarningLevel::Warning
+        } else {
+            WarningLevel::None
+        };
+        Some((text, level))
+    }
+
     /// Render the Quick Open hints line showing available mode prefixes
     fn render_quick_open_hints(
         frame: &mut Frame,
@@ -1112,6 +1198,24 @@ impl Editor {
 
         frame.render_widget(ratatui::widgets::Clear, suggestions_area);
 
+        // Privacy Mode hides the recent-files lists of Quick Open and the
+        // Recent Files picker until something has been typed
+        let lists_recent_files =
+            is_quick_open || prompt.prompt_type == crate::view::prompt::PromptType::RecentFiles;
+        let masked_prompt;
+        let prompt =
+            if lists_recent_files && self.privacy_masker.is_some() && prompt.input.is_empty() {
+                let mut masked = prompt.clone();
+                for suggestion in &mut masked.suggestions {
+                    suggestion.text = mask_all(&suggestion.text);
+                    suggestion.description = suggestion.description.as_deref().map(mask_all);
+                }
+                masked_prompt = masked;
+                &masked_prompt
+            } else {
+                prompt
+            };
+
         self.cached_layout.suggestions_area = SuggestionsRenderer::render_with_hover(
             frame,
             suggestions_area,
@@ -1508,6 +1612,11 @@ impl Editor {
 
     /// Hide the topmost popup
     pub fn hide_popup(&mut self) {
+        // Drop the files loaded for a peek along with its popup
+        if self.is_peek_popup_active() {
+            self.peek = None;
+        }
+
         let event = Event::HidePopup;
         self.active_event_log_mut().append(event.clone());
         self.apply_event_to_active_buffer(&event);
@@ -1719,7 +1828,7 @@ impl Editor {
     ///
     /// This is called after an LSP server is started to notify it about
     /// the current file so it can provide features like diagnostics.
-    fn notify_lsp_current_file_opened(&mut self, language: &str) {
+    pub(super) fn notify_lsp_current_file_opened(&mut self, language: &str) {
         // Get buffer metadata for the active buffer
         let metadata = match self.buffer_metadata.get(&self.active_buffer()) {
             Some(m) => m,
@@ -2105,6 +2214,7 @@ impl Editor {
     pub fn action_to_events(&mut self, action: Action) -> Option<Vec<Event>> {
         let auto_indent = self.config.editor.auto_indent;
         let estimated_line_length = self.config.editor.estimated_line_length;
+        let toggle_case_wraps_lines = self.config.editor.toggle_case_wraps_lines;
 
         // Get viewport height from SplitViewState (the authoritative source)
         let active_split = self.split_manager.active_split();
@@ -2136,6 +2246,7 @@ impl Editor {
             .get_mut(&active_split)
             .unwrap()
             .cursors;
+
         convert_action_to_events(
             state,
             cursors,
@@ -2146,6 +2257,7 @@ impl Editor {
             auto_surround,
             estimated_line_length,
             viewport_height,
+            toggle_case_wraps_lines,
         )
     }
 
@@ -2362,8 +2474,10 @@ impl Editor {
         // Get theme colors and search settings before borrowing state
         let search_bg = self.theme.search_match_bg;
         let search_fg = self.theme.search_match_fg;
+        let search_modifier = self.theme.distinctions().search_match;
         let case_sensitive = self.search_case_sensitive;
-        let whole_word = self.search_whole_word;
+        let word_rules = self.whole_word_rules();
+        let whole_word = self.search_whole_word && word_rules.is_none();
         let use_regex = self.search_use_regex;
         let ns = self.search_namespace.clone();
 
@@ -2433,11 +2547,19 @@ impl Editor {
 
         // Find all matches using regex
         for mat in regex.find_iter(&visible_text) {
+            if let Some(rules) = &word_rules {
+                if !is_whole_word_match(&visible_text, mat.start(), mat.end(), rules) {
+                    continue;
+                }
+            }
             let absolute_pos = visible_start + mat.start();
             let match_len = mat.end() - mat.start();
 
             // Add overlay for this match
-            let search_style = ratatui::style::Style::default().fg(search_fg).bg(search_bg);
+            let search_style = ratatui::style::Style::default()
+                .fg(search_fg)
+                .bg(search_bg)
+                .add_modifier(search_modifier);
             let overlay = crate::view::overlay::Overlay::with_namespace(
                 &mut state.marker_list,
                 absolute_pos..(absolute_pos + match_len),
@@ -2452,17 +2574,29 @@ impl Editor {
         }
     }
 
+    /// Word rules that whole-word matches must be checked against, when the
+    /// active buffer's words differ from the regex `\b` boundaries (sub-words
+    /// or extra word characters). `None` when `\b` in the pattern is enough.
+    fn whole_word_rules(&self) -> Option<WordRules> {
+        if !self.search_whole_word {
+            return None;
+        }
+        let rules = &self.active_state().buffer_settings.word_rules;
+        (*rules != WordRules::default()).then(|| rules.clone())
+    }
+
     /// Build a compiled regex from the current search settings and query.
-    fn build_search_regex(&self, query: &str) -> Result<regex::Regex, String> {
+    /// `whole_word` wraps the pattern in `\b` word boundaries.
+    fn build_search_regex(&self, query: &str, whole_word: bool) -> Result<regex::Regex, String> {
         let regex_pattern = if self.search_use_regex {
-            if self.search_whole_word {
+            if whole_word {
                 format!(r"\b{}\b", query)
             } else {
                 query.to_string()
             }
         } else {
             let escaped = regex::escape(query);
-            if self.search_whole_word {
+            if whole_word {
                 format!(r"\b{}\b", escaped)
             } else {
                 escaped
@@ -2475,6 +2609,17 @@ impl Editor {
             .map_err(|e| e.to_string())
     }
 
+    /// Search for a regex whatever the regex and whole-word toggles say, as
+    /// for `+/pattern` on the command line. The cursor moves to the first
+    /// match at or after it.
+    pub(super) fn search_regex_from_cursor(&mut self, pattern: &str) {
+        let use_regex = std::mem::replace(&mut self.search_use_regex, true);
+        let whole_word = std::mem::replace(&mut self.search_whole_word, false);
+        self.perform_search(pattern);
+        self.search_use_regex = use_regex;
+        self.search_whole_word = whole_word;
+    }
+
     /// Perform a search and update search state.
     ///
     /// For large files (lazy-loaded buffers), this starts an incremental
@@ -2491,19 +2636,26 @@ impl Editor {
         }
 
         let search_range = self.pending_search_range.take();
+        let is_large = self.active_state().buffer.is_large_file();
+
+        // The incremental scan of large files can only match whole words
+        // with `\b`, so it ignores sub-words and extra word characters
+        let word_rules = self
+            .whole_word_rules()
+            .filter(|_| !is_large || search_range.is_some());
+        let whole_word_regex = self.search_whole_word && word_rules.is_none();
 
         // Build the regex early so we can bail on invalid patterns
-        let regex = match self.build_search_regex(query) {
+        let regex = match self.build_search_regex(query, whole_word_regex) {
             Ok(r) => r,
             Err(e) => {
                 self.search_state = None;
-                self.set_status_message(t!("error.invalid_regex", error = e).to_string());
+                self.set_important_status_message(t!("error.invalid_regex", error = e).to_string());
                 return;
             }
         };
 
         // For large files, start an incremental (non-blocking) search scan
-        let is_large = self.active_state().buffer.is_large_file();
         if is_large && search_range.is_none() {
             self.start_search_scan(query, regex);
             return;
@@ -2518,7 +2670,7 @@ impl Editor {
                 Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
                 Err(e) => {
                     tracing::warn!("Failed to load buffer for search: {}", e);
-                    self.set_status_message(t!("error.buffer_not_loaded").to_string());
+                    self.set_important_status_message(t!("error.buffer_not_loaded").to_string());
                     return;
                 }
             }
@@ -2536,6 +2688,12 @@ impl Editor {
         let mut match_ranges: Vec<(usize, usize)> = Vec::new();
         let mut capped = false;
         for m in regex.find_iter(search_slice) {
+            if let Some(rules) = &word_rules {
+                let (start, end) = (search_start + m.start(), search_start + m.end());
+                if !is_whole_word_match(&buffer_content, start, end, rules) {
+                    continue;
+                }
+            }
             if match_ranges.len() >= SearchState::MAX_MATCHES {
                 capped = true;
                 break;
@@ -2554,7 +2712,7 @@ impl Editor {
             return;
         }
 
-        self.finalize_search(query, match_ranges, capped, search_range);
+        self.finalize_search(query, match_ranges, capped, search_range, true);
     }
 
     /// Common finalization after all matches have been collected (inline or
@@ -2565,12 +2723,16 @@ impl Editor {
     /// based position tracking keeps F3 correct across edits.  For large
     /// files (`viewport_only == true`), only visible-viewport overlays are
     /// created to avoid multi-GB overlay allocations.
+    ///
+    /// `complete` is false when the incremental scan hands over the matches
+    /// found so far; the scan keeps its progress in the status message then.
     pub(super) fn finalize_search(
         &mut self,
         query: &str,
         match_ranges: Vec<(usize, usize)>,
         capped: bool,
         search_range: Option<std::ops::Range<usize>>,
+        complete: bool,
     ) {
         let matches: Vec<usize> = match_ranges.iter().map(|(pos, _)| *pos).collect();
         let match_lengths: Vec<usize> = match_ranges.iter().map(|(_, len)| *len).collect();
@@ -2595,6 +2757,7 @@ impl Editor {
                 view_state.ensure_cursor_visible(&mut state.buffer, &state.marker_list);
             }
         }
+        self.center_on_search_match();
 
         let num_matches = matches.len();
 
@@ -2606,6 +2769,7 @@ impl Editor {
             wrap_search: search_range.is_none(),
             search_range,
             capped,
+            complete,
         });
 
         if is_large {
@@ -2615,6 +2779,7 @@ impl Editor {
             // Small file: overlays for ALL matches so markers auto-track edits
             let search_bg = self.theme.search_match_bg;
             let search_fg = self.theme.search_match_fg;
+            let search_modifier = self.theme.distinctions().search_match;
             let ns = self.search_namespace.clone();
             let state = self.active_state_mut();
             state.overlays.clear_namespace(&ns, &mut state.marker_list);
@@ -2624,7 +2789,10 @@ impl Editor {
                 .map(|(p, _)| p)
                 .zip(match_lengths.iter())
             {
-                let search_style = ratatui::style::Style::default().fg(search_fg).bg(search_bg);
+                let search_style = ratatui::style::Style::default()
+                    .fg(search_fg)
+                    .bg(search_bg)
+                    .add_modifier(search_modifier);
                 let overlay = crate::view::overlay::Overlay::with_namespace(
                     &mut state.marker_list,
                     pos..(pos + len),
@@ -2638,6 +2806,9 @@ impl Editor {
             }
         }
 
+        if !complete {
+            return;
+        }
         let cap_suffix = if capped { "+" } else { "" };
         let msg = if self.search_state.as_ref().unwrap().search_range.is_some() {
             format!(
@@ -2666,6 +2837,7 @@ impl Editor {
         let _span = tracing::info_span!("refresh_search_overlays").entered();
         let search_bg = self.theme.search_match_bg;
         let search_fg = self.theme.search_match_fg;
+        let search_modifier = self.theme.distinctions().search_match;
         let ns = self.search_namespace.clone();
 
         // Determine the visible byte range from the active viewport
@@ -2721,7 +2893,10 @@ impl Editor {
         let state = self.active_state_mut();
 
         for (pos, len) in &viewport_matches {
-            let search_style = ratatui::style::Style::default().fg(search_fg).bg(search_bg);
+            let search_style = ratatui::style::Style::default()
+                .fg(search_fg)
+                .bg(search_bg)
+                .add_modifier(search_modifier);
             let overlay = crate::view::overlay::Overlay::with_namespace(
                 &mut state.marker_list,
                 *pos..(*pos + *len),
@@ -2790,6 +2965,7 @@ impl Editor {
                 case_sensitive: self.search_case_sensitive,
                 whole_word: self.search_whole_word,
                 use_regex: self.search_use_regex,
+                published: 0,
             });
             self.set_status_message(t!("goto.scanning_progress", percent = 0).to_string());
         }
@@ -2815,6 +2991,33 @@ impl Editor {
         positions
     }
 
+    /// Recenter the viewport on the match the cursor just jumped to when
+    /// `editor.center_on_match` is set. Otherwise the preceding
+    /// `ensure_cursor_visible` has already scrolled it into view, keeping
+    /// `scroll_offset` lines of context around it.
+    fn center_on_search_match(&mut self) {
+        if self.config.editor.center_on_match {
+            self.apply_event_to_active_buffer(&Event::Recenter);
+        }
+    }
+
+    /// "Match 3 of 57" after moving to a match, or a wrap notice if the
+    /// move went past the end or the start of the buffer
+    fn set_match_status(&mut self, current: usize, total: String, wrapped: bool) {
+        let message = if wrapped {
+            t!("search.wrapped", current = current, total = total)
+        } else {
+            t!("search.match_of", current = current, total = total)
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Current match and match count of the active search for the status
+    /// bar, e.g. "3/57"
+    pub(super) fn search_match_count(&self) -> Option<String> {
+        self.search_state.as_ref().map(SearchState::count_label)
+    }
+
     /// Find the next match.
     ///
     /// For small files, overlay markers are used as the source of truth
@@ -2842,9 +3045,15 @@ impl Editor {
             }
 
             let current_index = search_state.current_match_index.unwrap_or(0);
+            let mut wrapped = false;
             let next_index = if current_index + 1 < match_positions.len() {
                 current_index + 1
+            } else if !search_state.complete {
+                // The scan hasn't reached the rest of the file yet
+                self.set_status_message(t!("search.still_searching").to_string());
+                return;
             } else if search_state.wrap_search {
+                wrapped = true;
                 0 // Wrap to beginning
             } else {
                 self.set_status_message(t!("search.no_matches").to_string());
@@ -2853,7 +3062,7 @@ impl Editor {
 
             search_state.current_match_index = Some(next_index);
             let match_pos = match_positions[next_index];
-            let matches_len = match_positions.len();
+            let total = search_state.total_label(match_positions.len());
 
             {
                 let active_split = self.split_manager.active_split();
@@ -2865,15 +3074,9 @@ impl Editor {
                     view_state.ensure_cursor_visible(&mut state.buffer, &state.marker_list);
                 }
             }
+            self.center_on_search_match();
 
-            self.set_status_message(
-                t!(
-                    "search.match_of",
-                    current = next_index + 1,
-                    total = matches_len
-                )
-                .to_string(),
-            );
+            self.set_match_status(next_index + 1, total, wrapped);
 
             if is_large {
                 self.refresh_search_overlays();
@@ -2909,9 +3112,15 @@ impl Editor {
             }
 
             let current_index = search_state.current_match_index.unwrap_or(0);
+            let mut wrapped = false;
             let prev_index = if current_index > 0 {
                 current_index - 1
+            } else if !search_state.complete {
+                // The last match isn't known until the scan finishes
+                self.set_status_message(t!("search.still_searching").to_string());
+                return;
             } else if search_state.wrap_search {
+                wrapped = true;
                 match_positions.len() - 1 // Wrap to end
             } else {
                 self.set_status_message(t!("search.no_matches").to_string());
@@ -2920,7 +3129,7 @@ impl Editor {
 
             search_state.current_match_index = Some(prev_index);
             let match_pos = match_positions[prev_index];
-            let matches_len = match_positions.len();
+            let total = search_state.total_label(match_positions.len());
 
             {
                 let active_split = self.split_manager.active_split();
@@ -2932,15 +3141,9 @@ impl Editor {
                     view_state.ensure_cursor_visible(&mut state.buffer, &state.marker_list);
                 }
             }
+            self.center_on_search_match();
 
-            self.set_status_message(
-                t!(
-                    "search.match_of",
-                    current = prev_index + 1,
-                    total = matches_len
-                )
-                .to_string(),
-            );
+            self.set_match_status(prev_index + 1, total, wrapped);
 
             if is_large {
                 self.refresh_search_overlays();
@@ -3141,20 +3344,45 @@ impl Editor {
         regex.find(&bytes).map(|m| m.len())
     }
 
-    /// Expand capture group references (e.g. $1, $2, ${name}) in the replacement string
-    /// for a regex match at the given buffer position. Returns the expanded replacement.
-    fn expand_regex_replacement(
+    /// Parse the replacement for matches of `regex`. Tells the user what is
+    /// wrong with it, such as a `$3` the pattern has no group for, and
+    /// returns None if it can't be used.
+    pub(super) fn parse_replace_template(
         &mut self,
+        replacement: &str,
         regex: &regex::bytes::Regex,
+    ) -> Option<ReplaceTemplate> {
+        match ReplaceTemplate::parse(replacement, regex) {
+            Ok(template) => Some(template),
+            Err(TemplateError::UnknownGroup(group)) => {
+                self.set_important_status_message(
+                    t!("replace.unknown_group", group = group).to_string(),
+                );
+                None
+            }
+            Err(TemplateError::UnclosedGroup) => {
+                self.set_important_status_message(t!("replace.unclosed_group").to_string());
+                None
+            }
+        }
+    }
+
+    /// The replacement for the interactive replace match at `pos`, with
+    /// capture groups and case escapes expanded in regex mode
+    fn interactive_replacement_at(
+        &mut self,
+        ir_state: &InteractiveReplaceState,
         pos: usize,
         match_len: usize,
-        replacement: &str,
     ) -> String {
+        let (Some(regex), Some(template)) = (&ir_state.regex, &ir_state.template) else {
+            return ir_state.replacement.clone();
+        };
         let state = self.active_state_mut();
-        if let Ok(bytes) = state.buffer.get_text_range_mut(pos, match_len) {
-            return super::regex_replace::expand_replacement(regex, &bytes, replacement);
+        match state.buffer.get_text_range_mut(pos, match_len) {
+            Ok(bytes) => super::regex_replace::expand_replacement(regex, &bytes, template),
+            Err(_) => ir_state.replacement.clone(),
         }
-        replacement.to_string()
     }
 
     /// Replaces all occurrences of the search query with the replacement text
@@ -3172,6 +3400,9 @@ impl Editor {
         // Find all matches first (before making any modifications)
         // Each match is (position, length, expanded_replacement)
         let matches: Vec<(usize, usize, String)> = if let Some(ref regex) = compiled_regex {
+            let Some(template) = self.parse_replace_template(replacement, regex) else {
+                return;
+            };
             // Regex mode: load buffer content as bytes and find all matches
             // with capture group expansion in the replacement template
             let buffer_bytes = {
@@ -3181,12 +3412,14 @@ impl Editor {
                     Ok(bytes) => bytes,
                     Err(e) => {
                         tracing::warn!("Failed to load buffer for replace: {}", e);
-                        self.set_status_message(t!("error.buffer_not_loaded").to_string());
+                        self.set_important_status_message(
+                            t!("error.buffer_not_loaded").to_string(),
+                        );
                         return;
                     }
                 }
             };
-            super::regex_replace::collect_regex_matches(regex, &buffer_bytes, replacement)
+            super::regex_replace::collect_regex_matches(regex, &buffer_bytes, &template)
                 .into_iter()
                 .map(|m| (m.offset, m.len, m.replacement))
                 .collect()
@@ -3219,13 +3452,43 @@ impl Editor {
             return;
         }
 
+        let description = format!("Replace all '{}' with '{}'", search, replacement);
+        self.apply_replacements(&matches, description);
+
+        // Clear search state since positions are now invalid
+        self.search_state = None;
+
+        // Clear any search highlight overlays
+        let ns = self.search_namespace.clone();
+        let state = self.active_state_mut();
+        state.overlays.clear_namespace(&ns, &mut state.marker_list);
+
+        // Set status message
+        self.set_status_message(
+            t!(
+                "search.replaced",
+                count = count,
+                search = search,
+                replace = replacement
+            )
+            .to_string(),
+        );
+    }
+
+    /// Replace `matches`, as (position, length, replacement), in the active
+    /// buffer as one undoable edit
+    pub(super) fn apply_replacements(
+        &mut self,
+        matches: &[(usize, usize, String)],
+        description: String,
+    ) {
         // Get cursor info for the event
         let cursor_id = self.active_cursors().primary_id();
 
         // Create Delete+Insert events for each match
         // Events will be processed in reverse order by apply_events_as_bulk_edit
-        let mut events = Vec::with_capacity(count * 2);
-        for (match_pos, match_len, expanded_replacement) in &matches {
+        let mut events = Vec::with_capacity(matches.len() * 2);
+        for (match_pos, match_len, expanded_replacement) in matches {
             // Get the actual matched text for the delete event
             let deleted_text = self
                 .active_state_mut()
@@ -3245,29 +3508,9 @@ impl Editor {
         }
 
         // Apply all replacements using BulkEdit for O(n) performance
-        let description = format!("Replace all '{}' with '{}'", search, replacement);
         if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, description) {
             self.active_event_log_mut().append(bulk_edit);
         }
-
-        // Clear search state since positions are now invalid
-        self.search_state = None;
-
-        // Clear any search highlight overlays
-        let ns = self.search_namespace.clone();
-        let state = self.active_state_mut();
-        state.overlays.clear_namespace(&ns, &mut state.marker_list);
-
-        // Set status message
-        self.set_status_message(
-            t!(
-                "search.replaced",
-                count = count,
-                search = search,
-                replace = replacement
-            )
-            .to_string(),
-        );
     }
 
     /// Start interactive replace mode (query-replace)
@@ -3278,6 +3521,13 @@ impl Editor {
         }
 
         let compiled_regex = self.build_replace_regex(search);
+        let template = match &compiled_regex {
+            Some(regex) => match self.parse_replace_template(replacement, regex) {
+                Some(template) => Some(template),
+                None => return,
+            },
+            None => None,
+        };
 
         // Find the first match lazily (don't find all matches upfront)
         let start_pos = self.active_cursors().primary().position;
@@ -3314,7 +3564,7 @@ impl Editor {
         };
 
         // Initialize interactive replace state with just the current match
-        self.interactive_replace_state = Some(InteractiveReplaceState {
+        let ir_state = InteractiveReplaceState {
             search: search.to_string(),
             replacement: replacement.to_string(),
             current_match_pos: first_match_pos,
@@ -3323,7 +3573,10 @@ impl Editor {
             has_wrapped: false,
             replacements_made: 0,
             regex: compiled_regex,
-        });
+            template,
+        };
+        let message = self.query_replace_message(&ir_state);
+        self.interactive_replace_state = Some(ir_state);
 
         // Move cursor to first match
         let active_split = self.split_manager.active_split();
@@ -3337,10 +3590,33 @@ impl Editor {
         }
 
         // Show the query-replace prompt
-        self.prompt = Some(Prompt::new(
-            "Replace? (y)es (n)o (a)ll (c)ancel: ".to_string(),
-            PromptType::QueryReplaceConfirm,
-        ));
+        self.prompt = Some(Prompt::new(message, PromptType::QueryReplaceConfirm));
+    }
+
+    /// The query-replace prompt for the current match. In regex mode it
+    /// shows what the match would be replaced with, since that differs
+    /// from match to match.
+    fn query_replace_message(&mut self, ir_state: &InteractiveReplaceState) -> String {
+        let wrapped = if ir_state.has_wrapped {
+            "[Wrapped] "
+        } else {
+            ""
+        };
+        if ir_state.template.is_none() {
+            return format!("{}Replace? (y)es (n)o (a)ll (c)ancel: ", wrapped);
+        }
+        let preview = self
+            .interactive_replacement_at(
+                ir_state,
+                ir_state.current_match_pos,
+                ir_state.current_match_len,
+            )
+            .replace('\n', "\\n")
+            .replace('\t', "\\t");
+        format!(
+            "{}Replace with '{}'? (y)es (n)o (a)ll (c)ancel: ",
+            wrapped, preview
+        )
     }
 
     /// Handle interactive replace key press (y/n/a/c)
@@ -3353,11 +3629,11 @@ impl Editor {
         match c {
             'y' | 'Y' => {
                 // Replace current match
-                self.replace_current_match(&ir_state)?;
+                let inserted_len = self.replace_current_match(&ir_state)?;
                 ir_state.replacements_made += 1;
 
                 // Find next match lazily (after the replacement)
-                let search_pos = ir_state.current_match_pos + ir_state.replacement.len();
+                let search_pos = ir_state.current_match_pos + inserted_len;
                 if let Some((next_match, match_len, wrapped)) =
                     self.find_next_match_for_replace(&ir_state, search_pos)
                 {
@@ -3433,16 +3709,8 @@ impl Editor {
                             .active_state_mut()
                             .get_text_range(match_pos, match_pos + match_len);
                         // Expand capture group references if in regex mode
-                        let replacement_text = if let Some(ref regex) = ir_state.regex {
-                            self.expand_regex_replacement(
-                                regex,
-                                match_pos,
-                                match_len,
-                                &ir_state.replacement,
-                            )
-                        } else {
-                            ir_state.replacement.clone()
-                        };
+                        let replacement_text =
+                            self.interactive_replacement_at(&ir_state, match_pos, match_len);
                         events.push(Event::Delete {
                             range: match_pos..match_pos + match_len,
                             deleted_text,
@@ -3568,21 +3836,19 @@ impl Editor {
         }
     }
 
-    /// Replace the current match in interactive replace mode
+    /// Replace the current match in interactive replace mode. Returns the
+    /// length of the text it was replaced with.
     pub(super) fn replace_current_match(
         &mut self,
         ir_state: &InteractiveReplaceState,
-    ) -> AnyhowResult<()> {
+    ) -> AnyhowResult<usize> {
         let match_pos = ir_state.current_match_pos;
         let match_len = ir_state.current_match_len;
         let range = match_pos..(match_pos + match_len);
 
         // Expand capture group references if in regex mode
-        let replacement_text = if let Some(ref regex) = ir_state.regex {
-            self.expand_regex_replacement(regex, match_pos, match_len, &ir_state.replacement)
-        } else {
-            ir_state.replacement.clone()
-        };
+        let replacement_text = self.interactive_replacement_at(ir_state, match_pos, match_len);
+        let inserted_len = replacement_text.len();
 
         // Get the deleted text for the event
         let deleted_text = self
@@ -3633,7 +3899,7 @@ impl Editor {
         self.active_event_log_mut().append(batch.clone());
         self.apply_event_to_active_buffer(&batch);
 
-        Ok(())
+        Ok(inserted_len)
     }
 
     /// Move cursor to the current match in interactive replace
@@ -3648,13 +3914,10 @@ impl Editor {
             let state = self.buffers.get_mut(&active_buffer).unwrap();
             view_state.ensure_cursor_visible(&mut state.buffer, &state.marker_list);
         }
+        self.center_on_search_match();
 
         // Update the prompt message (show [Wrapped] if we've wrapped around)
-        let msg = if ir_state.has_wrapped {
-            "[Wrapped] Replace? (y)es (n)o (a)ll (c)ancel: ".to_string()
-        } else {
-            "Replace? (y)es (n)o (a)ll (c)ancel: ".to_string()
-        };
+        let msg = self.query_replace_message(ir_state);
         if let Some(ref mut prompt) = self.prompt {
             if prompt.prompt_type == PromptType::QueryReplaceConfirm {
                 prompt.message = msg;
@@ -3992,6 +4255,107 @@ impl Editor {
         );
     }
 
+    /// Re-wrap the paragraph under each cursor (or the paragraphs in each
+    /// selection) at `column`, or join them into single lines when `column`
+    /// is `None`. Comment prefixes of the buffer's language are kept on
+    /// every line.
+    pub(super) fn reflow_paragraph(&mut self, column: Option<usize>) {
+        if self.is_editing_disabled() {
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            return;
+        }
+
+        let comment_prefix = self
+            .config
+            .languages
+            .get(&self.active_state().language)
+            .and_then(|lang_config| lang_config.comment_prefix.clone());
+        let options = crate::input::reflow::ReflowOptions {
+            column,
+            comment_prefix: comment_prefix.as_deref().map(str::trim),
+            tab_size: self.active_state().buffer_settings.tab_size,
+        };
+        let estimated_line_length = self.config.editor.estimated_line_length;
+        let cursors = self.active_cursors().clone();
+
+        let mut events = Vec::new();
+        let changed = crate::input::reflow::reflow_paragraphs(
+            self.active_state_mut(),
+            &cursors,
+            &mut events,
+            &options,
+            estimated_line_length,
+        );
+        if changed == 0 {
+            return;
+        }
+
+        let description = if column.is_none() {
+            "Unwrap paragraph"
+        } else {
+            "Reflow paragraph"
+        };
+        if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, description.to_string()) {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+    }
+
+    /// Sort the whole lines touched by the primary selection, or every line
+    /// of the buffer when nothing is selected, as a single undo step. A
+    /// selection ending at the start of a line leaves that line out.
+    pub fn sort_selected_lines(&mut self, numeric: bool, reverse: bool, case_insensitive: bool) {
+        if self.is_editing_disabled() {
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            return;
+        }
+
+        let cursor_id = self.active_cursors().primary_id();
+        let selection = self.active_cursors().primary().selection_range();
+        let state = self.active_state_mut();
+        let buffer_len = state.buffer.len();
+        let range = match selection.filter(|range| !range.is_empty()) {
+            Some(range) => {
+                let first_line = state.buffer.get_line_number(range.start);
+                let last_line = state.buffer.get_line_number(range.end - 1);
+                let start = state.buffer.line_start_offset(first_line).unwrap_or(0);
+                let end = state
+                    .buffer
+                    .line_start_offset(last_line + 1)
+                    .unwrap_or(buffer_len);
+                start..end.max(range.end)
+            }
+            None => 0..buffer_len,
+        };
+
+        let text = state.get_text_range(range.start, range.end);
+        let options = crate::input::sort_lines::SortOptions {
+            numeric,
+            reverse,
+            case_insensitive,
+        };
+        let line_ending = state.buffer.line_ending().as_str();
+        let sorted = crate::input::sort_lines::sort_lines(&text, line_ending, options);
+        if sorted == text {
+            return;
+        }
+
+        let events = vec![
+            Event::Delete {
+                range: range.clone(),
+                deleted_text: text,
+                cursor_id,
+            },
+            Event::Insert {
+                position: range.start,
+                text: sorted,
+                cursor_id,
+            },
+        ];
+        if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, "Sort lines".to_string()) {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+    }
+
     /// Go to matching bracket
     pub(super) fn goto_matching_bracket(&mut self) {
         let cursor = *self.active_cursors().primary();
@@ -4091,7 +4455,9 @@ impl Editor {
     }
 
     /// Jump to next error/diagnostic
-    pub(super) fn jump_to_next_error(&mut self) {
+    ///
+    /// Returns whether the cursor was moved to a diagnostic.
+    pub(super) fn jump_to_next_error(&mut self) -> bool {
         let diagnostic_ns = self.lsp_diagnostic_namespace.clone();
         let cursor_pos = self.active_cursors().primary().position;
         let cursor_id = self.active_cursors().primary_id();
@@ -4115,7 +4481,7 @@ impl Editor {
 
         if diagnostic_positions.is_empty() {
             self.set_status_message(t!("diagnostics.none").to_string());
-            return;
+            return false;
         }
 
         // Sort positions
@@ -4154,6 +4520,9 @@ impl Editor {
             }) {
                 self.set_status_message(msg);
             }
+            true
+        } else {
+            false
         }
     }
 
@@ -4386,6 +4755,8 @@ impl Editor {
             self.config.editor.show_vertical_scrollbar,
             self.config.editor.show_horizontal_scrollbar,
             self.config.editor.diagnostics_inline_text,
+            self.config.editor.fold_indicators,
+            self.privacy_masker.as_ref(),
         );
 
         self.cached_layout.view_line_mappings = view_line_mappings;
@@ -4446,7 +4817,10 @@ impl Editor {
                 | Action::ListMacros
                 | Action::PromptRecordMacro
                 | Action::PromptPlayMacro
-                | Action::PlayLastMacro => {}
+                | Action::PlayLastMacro
+                | Action::PromptSaveMacro
+                | Action::PromptLoadMacro
+                | Action::ListSavedMacros => {}
                 // When recording PromptConfirm, capture the current prompt text
                 // so it can be replayed correctly
                 Action::PromptConfirm => {
@@ -4472,7 +4846,7 @@ impl Editor {
                 let json = match serde_json::to_string_pretty(actions) {
                     Ok(json) => json,
                     Err(e) => {
-                        self.set_status_message(
+                        self.set_important_status_message(
                             t!("macro.serialize_failed", error = e.to_string()).to_string(),
                         );
                         return;
@@ -4509,7 +4883,7 @@ impl Editor {
             .configure_for_line_numbers(self.config.editor.line_numbers);
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs.insert(buffer_id, EventLog::new());
+        self.event_logs.insert(buffer_id, self.new_event_log());
 
         // Set buffer content
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
@@ -4532,7 +4906,10 @@ impl Editor {
             binary: false,
             lsp_opened_with: std::collections::HashSet::new(),
             hidden_from_tabs: false,
+            missing_on_disk: None,
             recovery_id: None,
+            remote_connection: None,
+            detected_encoding: None,
         };
         self.buffer_metadata.insert(buffer_id, metadata);
 
@@ -4584,7 +4961,7 @@ impl Editor {
             .configure_for_line_numbers(self.config.editor.line_numbers);
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs.insert(buffer_id, EventLog::new());
+        self.event_logs.insert(buffer_id, self.new_event_log());
 
         // Set buffer content
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
@@ -4607,7 +4984,10 @@ impl Editor {
             binary: false,
             lsp_opened_with: std::collections::HashSet::new(),
             hidden_from_tabs: false,
+            missing_on_disk: None,
             recovery_id: None,
+            remote_connection: None,
+            detected_encoding: None,
         };
         self.buffer_metadata.insert(buffer_id, metadata);
 
@@ -4618,56 +4998,173 @@ impl Editor {
 
     /// Set a bookmark at the current position
     pub(super) fn set_bookmark(&mut self, key: char) {
-        let buffer_id = self.active_buffer();
-        let position = self.active_cursors().primary().position;
-        self.bookmarks.insert(
-            key,
-            Bookmark {
-                buffer_id,
-                position,
-            },
-        );
+        let bookmark = self.bookmark_at_cursor();
+        self.bookmarks.insert(key, bookmark);
         self.set_status_message(t!("bookmark.set", key = key).to_string());
     }
 
     /// Jump to a bookmark
     pub(super) fn jump_to_bookmark(&mut self, key: char) {
         if let Some(bookmark) = self.bookmarks.get(&key).cloned() {
-            // Switch to the buffer if needed
-            if bookmark.buffer_id != self.active_buffer() {
-                if self.buffers.contains_key(&bookmark.buffer_id) {
-                    self.set_active_buffer(bookmark.buffer_id);
-                } else {
+            match self.go_to_bookmark(&bookmark) {
+                Some(buffer_id) => {
+                    if let Some(bookmark) = self.bookmarks.get_mut(&key) {
+                        bookmark.buffer_id = Some(buffer_id);
+                        bookmark.last_used = Some(std::time::Instant::now());
+                    }
+                    self.set_status_message(t!("bookmark.jumped", key = key).to_string());
+                }
+                None => {
                     self.set_status_message(t!("bookmark.buffer_gone", key = key).to_string());
                     self.bookmarks.remove(&key);
-                    return;
                 }
             }
+        } else {
+            self.set_status_message(t!("bookmark.not_set", key = key).to_string());
+        }
+    }
 
-            // Move cursor to bookmark position
-            let cursor = *self.active_cursors().primary();
-            let cursor_id = self.active_cursors().primary_id();
-            let state = self.active_state_mut();
-            let new_pos = bookmark.position.min(state.buffer.len());
+    /// Set a named bookmark at the current position, replacing any bookmark
+    /// with that name
+    pub(super) fn set_named_bookmark(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            self.set_status_message(t!("bookmark.name_not_specified").to_string());
+            return;
+        }
+        let bookmark = self.bookmark_at_cursor();
+        self.named_bookmarks.insert(name.to_string(), bookmark);
+        self.set_status_message(t!("bookmark.set", key = name).to_string());
+    }
 
-            let event = Event::MoveCursor {
-                cursor_id,
-                old_position: cursor.position,
-                new_position: new_pos,
-                old_anchor: cursor.anchor,
-                new_anchor: None,
-                old_sticky_column: cursor.sticky_column,
-                new_sticky_column: 0,
-            };
+    /// Jump to a named bookmark
+    pub(super) fn jump_to_named_bookmark(&mut self, name: &str) {
+        let name = name.trim();
+        let Some(bookmark) = self.named_bookmarks.get(name).cloned() else {
+            self.set_status_message(t!("bookmark.not_set", key = name).to_string());
+            return;
+        };
+        match self.go_to_bookmark(&bookmark) {
+            Some(buffer_id) => {
+                if let Some(bookmark) = self.named_bookmarks.get_mut(name) {
+                    bookmark.buffer_id = Some(buffer_id);
+                    bookmark.last_used = Some(std::time::Instant::now());
+                }
+                self.set_status_message(t!("bookmark.jumped", key = name).to_string());
+            }
+            None => {
+                self.set_status_message(t!("bookmark.buffer_gone", key = name).to_string());
+                self.named_bookmarks.remove(name);
+            }
+        }
+    }
 
-            self.active_event_log_mut().append(event.clone());
-            self.apply_event_to_active_buffer(&event);
-            self.set_status_message(t!("bookmark.jumped", key = key).to_string());
-        } else {
-            self.set_status_message(t!("bookmark.not_set", key = key).to_string());
+    /// Names of the named bookmarks as prompt suggestions, with where each
+    /// one points
+    pub(super) fn named_bookmark_suggestions(&self) -> Vec<crate::input::commands::Suggestion> {
+        let mut names: Vec<_> = self.named_bookmarks.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .map(|name| crate::input::commands::Suggestion {
+                text: name.clone(),
+                description: Some(self.bookmark_location(&self.named_bookmarks[name])),
+                value: Some(name.clone()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect()
+    }
+
+    /// A bookmark for the primary cursor in the active buffer
+    fn bookmark_at_cursor(&self) -> Bookmark {
+        let state = self.active_state();
+        let file_path = state.buffer.file_path().map(|path| {
+            state
+                .buffer
+                .filesystem()
+                .canonicalize(path)
+                .unwrap_or_else(|_| path.to_path_buf())
+        });
+        Bookmark {
+            buffer_id: Some(self.active_buffer()),
+            file_path,
+            position: self.active_cursors().primary().position,
+            last_used: Some(std::time::Instant::now()),
         }
     }
 
+    /// Move the cursor to a bookmark, switching to its buffer first. When the
+    /// buffer was closed (or the bookmark was restored from the workspace),
+    /// its file is opened again. Returns the buffer, or None when neither
+    /// the buffer nor the file is left.
+    pub(super) fn go_to_bookmark(&mut self, bookmark: &Bookmark) -> Option<BufferId> {
+        let open_buffer = bookmark
+            .buffer_id
+            .filter(|id| self.buffers.contains_key(id))
+            .or_else(|| {
+                let path = bookmark.file_path.as_deref()?;
+                self.buffers
+                    .iter()
+                    .find(|(_, state)| state.buffer.file_path() == Some(path))
+                    .map(|(id, _)| *id)
+            });
+        let buffer_id = match open_buffer {
+            Some(buffer_id) => {
+                if buffer_id != self.active_buffer() {
+                    self.set_active_buffer(buffer_id);
+                }
+                buffer_id
+            }
+            None => {
+                let path = bookmark.file_path.clone()?;
+                if !self.filesystem.exists(&path) {
+                    return None;
+                }
+                self.open_file(&path).ok()?
+            }
+        };
+
+        // Move cursor to bookmark position
+        let cursor = *self.active_cursors().primary();
+        let cursor_id = self.active_cursors().primary_id();
+        let state = self.active_state_mut();
+        let new_pos = bookmark.position.min(state.buffer.len());
+
+        let event = Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: new_pos,
+            old_anchor: cursor.anchor,
+            new_anchor: None,
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: 0,
+        };
+
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+        Some(buffer_id)
+    }
+
+    /// Where a bookmark points, for listings: the buffer name (or the file
+    /// name when the buffer is closed) and the byte offset
+    fn bookmark_location(&self, bookmark: &Bookmark) -> String {
+        let name = bookmark
+            .buffer_id
+            .and_then(|id| self.buffer_metadata.get(&id))
+            .map(|m| m.display_name.clone())
+            .or_else(|| {
+                bookmark
+                    .file_path
+                    .as_deref()
+                    .and_then(|p| p.file_name())
+                    .map(|n| n.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+        format!("{} @ {}", name, bookmark.position)
+    }
+
     /// Clear a bookmark
     pub(super) fn clear_bookmark(&mut self, key: char) {
         if self.bookmarks.remove(&key).is_some() {
@@ -4677,29 +5174,48 @@ impl Editor {
         }
     }
 
-    /// List all bookmarks
+    /// List all bookmarks, followed by the line marks of every open buffer
     pub(super) fn list_bookmarks(&mut self) {
-        if self.bookmarks.is_empty() {
-            self.set_status_message(t!("bookmark.none_set").to_string());
-            return;
-        }
-
         let mut bookmark_list: Vec<_> = self.bookmarks.iter().collect();
         bookmark_list.sort_by_key(|(k, _)| *k);
-
-        let list_str: String = bookmark_list
+        let mut named_list: Vec<_> = self.named_bookmarks.iter().collect();
+        named_list.sort_by_key(|(name, _)| name.as_str());
+
+        let buffer_name = |buffer_id: &BufferId| {
+            self.buffer_metadata
+                .get(buffer_id)
+                .map(|m| m.display_name.as_str())
+                .unwrap_or("unknown")
+        };
+        let mut entries: Vec<String> = bookmark_list
             .iter()
-            .map(|(k, bm)| {
-                let buffer_name = self
-                    .buffer_metadata
-                    .get(&bm.buffer_id)
-                    .map(|m| m.display_name.as_str())
-                    .unwrap_or("unknown");
-                format!("'{}': {} @ {}", k, buffer_name, bm.position)
-            })
-            .collect::<Vec<_>>()
-            .join(", ");
+            .map(|(k, bm)| format!("'{}': {}", k, self.bookmark_location(bm)))
+            .chain(
+                named_list
+                    .iter()
+                    .map(|(name, bm)| format!("'{}': {}", name, self.bookmark_location(bm))),
+            )
+            .collect();
+
+        let mut marked_buffers: Vec<_> = self.buffers.iter().collect();
+        marked_buffers.sort_by_key(|(id, _)| id.0);
+        for (buffer_id, state) in marked_buffers {
+            for line in super::line_marks::line_mark_lines(state) {
+                entries.push(format!(
+                    "{} {}:{}",
+                    super::line_marks::LINE_MARK_SYMBOL,
+                    buffer_name(buffer_id),
+                    line + 1
+                ));
+            }
+        }
+
+        if entries.is_empty() {
+            self.set_status_message(t!("bookmark.none_set").to_string());
+            return;
+        }
 
+        let list_str = entries.join(", ");
         self.set_status_message(t!("bookmark.list", list = list_str).to_string());
     }
 
@@ -4759,6 +5275,7 @@ impl Editor {
             &self.buffers,
             &self.buffer_metadata,
             &self.composite_buffers,
+            self.config.editor.ui_density.is_tight(),
         );
 
         let total_tabs_width: usize = tab_widths.iter().sum();
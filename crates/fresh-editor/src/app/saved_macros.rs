@@ -0,0 +1,158 @@
+//! Save Macro, Load Macro and List Saved Macros.
+//!
+//! Save Macro stores the macro in a register under a name in the
+//! [`SavedMacros`] file shared by all sessions; Load Macro copies a saved
+//! macro into a register, ready to play. The prompts take the register and
+//! the name separated by a space, in the order of the command: "1 wrap
+//! lines" saves register 1 as "wrap lines", "wrap lines 1" loads it back.
+
+use super::Editor;
+use crate::services::saved_macros::SavedMacros;
+use rust_i18n::t;
+
+/// Buffer name for the saved macro list
+const SAVED_MACROS_BUFFER_NAME: &str = "*Saved Macros*";
+
+impl Editor {
+    /// Handle the Save Macro prompt: "<register> <name>"
+    pub(super) fn handle_save_macro_input(&mut self, input: &str) {
+        let input = input.trim();
+        let (register, name) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+        if let Some(key) = self.parse_macro_register(register) {
+            self.save_macro(key, name.trim());
+        }
+    }
+
+    /// Handle the Load Macro prompt: "<name> <register>"
+    pub(super) fn handle_load_macro_input(&mut self, input: &str) {
+        let input = input.trim();
+        let Some((name, register)) = input.rsplit_once(char::is_whitespace) else {
+            self.set_status_message(t!("register.not_specified").to_string());
+            return;
+        };
+        if let Some(key) = self.parse_macro_register(register) {
+            self.load_macro(name.trim(), key);
+        }
+    }
+
+    /// The register named by `register`, which must be a single digit
+    fn parse_macro_register(&mut self, register: &str) -> Option<char> {
+        let mut chars = register.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_digit() => Some(c),
+            (None, _) => {
+                self.set_status_message(t!("register.not_specified").to_string());
+                None
+            }
+            _ => {
+                self.set_status_message(t!("register.must_be_digit", "type" = "Macro").to_string());
+                None
+            }
+        }
+    }
+
+    /// Save the macro in register `key` as `name`, replacing any macro saved
+    /// with that name
+    pub(super) fn save_macro(&mut self, key: char, name: &str) {
+        if name.is_empty() {
+            self.set_status_message(t!("macro.name_not_specified").to_string());
+            return;
+        }
+        let Some(actions) = self.macros.get(&key) else {
+            self.set_status_message(t!("macro.not_found", key = key).to_string());
+            return;
+        };
+        let count = actions.len();
+        let result = SavedMacros::load(&self.dir_context).and_then(|mut saved| {
+            saved.insert(name, actions).map_err(std::io::Error::other)?;
+            saved.save()
+        });
+        match result {
+            Ok(()) => self.set_status_message(
+                t!("macro.saved_as", key = key, name = name, count = count).to_string(),
+            ),
+            Err(e) => self.set_important_status_message(
+                t!("macro.save_failed", error = e.to_string()).to_string(),
+            ),
+        }
+    }
+
+    /// Load the macro saved as `name` into register `key`
+    pub(super) fn load_macro(&mut self, name: &str, key: char) {
+        let saved = match SavedMacros::load(&self.dir_context) {
+            Ok(saved) => saved,
+            Err(e) => {
+                self.set_important_status_message(
+                    t!("macro.load_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+        match saved.get(name) {
+            Some(Ok(actions)) => {
+                let count = actions.len();
+                self.macros.insert(key, actions);
+                self.last_macro_register = Some(key);
+                self.set_status_message(
+                    t!("macro.loaded", name = name, key = key, count = count).to_string(),
+                );
+            }
+            Some(Err(e)) => self.set_important_status_message(
+                t!("macro.unreadable", name = name, error = e.to_string()).to_string(),
+            ),
+            None => self.set_status_message(t!("macro.not_stored", name = name).to_string()),
+        }
+    }
+
+    /// Show the saved macros and their actions in a read-only buffer
+    pub(super) fn list_saved_macros_in_buffer(&mut self) {
+        let saved = match SavedMacros::load(&self.dir_context) {
+            Ok(saved) => saved,
+            Err(e) => {
+                self.set_important_status_message(
+                    t!("macro.load_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+        if saved.is_empty() {
+            self.set_status_message(t!("macro.none_saved").to_string());
+            return;
+        }
+
+        let mut content = format!(
+            "// Saved Macros\n// Stored in {}\n\n",
+            self.dir_context.saved_macros_path().display()
+        );
+        for (name, actions) in saved.iter() {
+            match actions {
+                Ok(actions) => {
+                    content.push_str(&format!("Macro '{}': {} actions\n", name, actions.len()));
+                    // The form the action is stored in, which is also what a
+                    // hand-edited macros.json needs
+                    for (i, action) in actions.iter().enumerate() {
+                        let json =
+                            serde_json::to_string(action).unwrap_or_else(|e| format!("<{}>", e));
+                        content.push_str(&format!("  {}. {}\n", i + 1, json));
+                    }
+                }
+                Err(e) => content.push_str(&format!("Macro '{}': unreadable: {}\n", name, e)),
+            }
+            content.push('\n');
+        }
+
+        let buffer_id = self.create_virtual_buffer(
+            SAVED_MACROS_BUFFER_NAME.to_string(),
+            "macro-list".to_string(),
+            true,
+        );
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.insert(0, &content);
+            state.buffer.clear_modified();
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+        self.set_active_buffer(buffer_id);
+        self.set_status_message(t!("macro.showing_saved", count = saved.len()).to_string());
+    }
+}
@@ -111,15 +111,15 @@ impl Editor {
 
         // Apply locale change at runtime
         if old_locale != self.config.locale {
-            if let Some(locale) = self.config.locale.as_option() {
-                crate::i18n::set_locale(locale);
+            if let Some(locale) = self.config.locale.as_option().map(str::to_string) {
+                crate::i18n::set_locale(&locale);
                 // Regenerate menus with the new locale
-                self.menus = crate::config::MenuConfig::translated();
+                self.rebuild_menus();
                 tracing::info!("Locale changed to '{}'", locale);
             } else {
                 // Auto-detect from environment
                 crate::i18n::init();
-                self.menus = crate::config::MenuConfig::translated();
+                self.rebuild_menus();
                 tracing::info!("Locale reset to auto-detect");
             }
             // Refresh command palette commands with new locale
@@ -131,6 +131,9 @@ impl Editor {
         // Handle plugin enable/disable changes
         self.apply_plugin_config_changes(&old_plugins);
 
+        // Environment for processes spawned from now on
+        crate::services::shell_env::configure(&self.config.process);
+
         // Update keybindings
         self.keybindings = KeybindingResolver::new(&self.config);
 
@@ -150,16 +153,18 @@ impl Editor {
         }
 
         // Apply bar visibility changes immediately
-        self.menu_bar_visible = self.config.editor.show_menu_bar;
-        self.tab_bar_visible = self.config.editor.show_tab_bar;
+        self.apply_ui_density();
+        self.apply_undo_limits();
         self.status_bar_visible = self.config.editor.show_status_bar;
 
-        // Propagate tab_size/use_tabs/auto_close/whitespace visibility to all open buffers
+        // Propagate tab_size/use_tabs/auto_close/word rules/whitespace visibility to all open buffers
         // Each buffer resolves its settings from its language + the new global config
         for state in self.buffers.values_mut() {
             let mut whitespace =
                 crate::config::WhitespaceVisibility::from_editor_config(&self.config.editor);
             state.buffer_settings.auto_close = self.config.editor.auto_close;
+            state.buffer_settings.word_rules = self.config.word_rules(&state.language);
+            state.buffer_settings.auto_close_pairs = self.config.auto_close_pairs(&state.language);
             if let Some(lang_config) = self.config.languages.get(&state.language) {
                 state.buffer_settings.tab_size =
                     lang_config.tab_size.unwrap_or(self.config.editor.tab_size);
@@ -296,7 +301,7 @@ impl Editor {
                 {
                     self.start_large_file_encoding_confirmation(confirmation);
                 } else {
-                    self.set_status_message(
+                    self.set_important_status_message(
                         t!("file.error_opening", error = e.to_string()).to_string(),
                     );
                 }
@@ -617,7 +622,10 @@ impl Editor {
                     tracing::info!("Loading newly enabled plugin: {}", name);
                     if let Err(e) = self.plugin_manager.load_plugin(path) {
                         tracing::error!("Failed to load plugin '{}': {}", name, e);
-                        self.set_status_message(format!("Failed to load plugin '{}': {}", name, e));
+                        self.set_important_status_message(format!(
+                            "Failed to load plugin '{}': {}",
+                            name, e
+                        ));
                     }
                 }
             } else {
@@ -625,7 +633,10 @@ impl Editor {
                 tracing::info!("Unloading disabled plugin: {}", name);
                 if let Err(e) = self.plugin_manager.unload_plugin(&name) {
                     tracing::error!("Failed to unload plugin '{}': {}", name, e);
-                    self.set_status_message(format!("Failed to unload plugin '{}': {}", name, e));
+                    self.set_important_status_message(format!(
+                        "Failed to unload plugin '{}': {}",
+                        name, e
+                    ));
                 }
             }
         }
@@ -0,0 +1,257 @@
+//! Settings bundle export and import.
+//!
+//! This module provides:
+//! - "Sync Settings: Export Bundle": writes config, keybindings, themes, the
+//!   package list and macros to a single portable file
+//! - "Sync Settings: Import Bundle": reads a bundle, shows what would change in
+//!   a review buffer, then applies the categories the user keeps in the prompt
+
+use std::path::PathBuf;
+
+use super::{normalize_path, Editor};
+use crate::config::Config;
+use crate::primitives::path_utils::expand_tilde;
+use crate::services::settings_bundle::{
+    format_review, merge_user_config, read_user_config, review, BundleCategory, SettingsBundle,
+};
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+
+/// Display name of the buffer showing a bundle review
+const REVIEW_BUFFER_NAME: &str = "*Settings Bundle Review*";
+
+/// File name suggested when exporting a bundle
+const DEFAULT_BUNDLE_NAME: &str = "fresh-settings.json";
+
+impl Editor {
+    /// Start the prompt for the settings bundle destination.
+    pub fn start_export_settings_bundle_prompt(&mut self) {
+        self.start_prompt_with_initial_text(
+            t!("settings_bundle.export_prompt").to_string(),
+            PromptType::ExportSettingsBundle,
+            DEFAULT_BUNDLE_NAME.to_string(),
+        );
+    }
+
+    /// Start the prompt for the settings bundle to import.
+    pub fn start_import_settings_bundle_prompt(&mut self) {
+        self.start_prompt(
+            t!("settings_bundle.import_prompt").to_string(),
+            PromptType::ImportSettingsBundle,
+        );
+    }
+
+    /// Handle the export prompt confirmation.
+    pub fn handle_export_settings_bundle(&mut self, input: &str) {
+        let Some(path) = self.resolve_bundle_path(input) else {
+            self.set_status_message(t!("settings_bundle.cancelled").to_string());
+            return;
+        };
+
+        let result = SettingsBundle::collect(
+            &self.dir_context.config_dir,
+            self.dir_context.home_dir.as_deref(),
+            &self.macros,
+        )
+        .and_then(|(bundle, excluded)| {
+            self.filesystem
+                .write_file(&path, bundle.to_json().as_bytes())
+                .map(|()| excluded)
+        });
+
+        match result {
+            Ok(excluded) if excluded.is_empty() => self.set_status_message(
+                t!(
+                    "settings_bundle.exported",
+                    path = path.display().to_string()
+                )
+                .to_string(),
+            ),
+            Ok(excluded) => {
+                tracing::warn!(
+                    "Settings bundle export left out secrets: {}",
+                    excluded.join(", ")
+                );
+                self.set_status_message(
+                    t!(
+                        "settings_bundle.exported_without_secrets",
+                        path = path.display().to_string(),
+                        keys = excluded.join(", ")
+                    )
+                    .to_string(),
+                );
+            }
+            Err(e) => self.set_important_status_message(
+                t!("settings_bundle.export_failed", error = e.to_string()).to_string(),
+            ),
+        }
+    }
+
+    /// Handle the import prompt confirmation: read the bundle, show the
+    /// review and ask which categories to apply.
+    pub fn handle_import_settings_bundle(&mut self, input: &str) {
+        let Some(path) = self.resolve_bundle_path(input) else {
+            self.set_status_message(t!("settings_bundle.cancelled").to_string());
+            return;
+        };
+
+        let incoming = self
+            .filesystem
+            .read_file(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| SettingsBundle::from_json(&String::from_utf8_lossy(&bytes)));
+        let incoming = match incoming {
+            Ok(bundle) => bundle,
+            Err(e) => {
+                self.set_important_status_message(
+                    t!("settings_bundle.import_failed", error = e).to_string(),
+                );
+                return;
+            }
+        };
+        let local = match SettingsBundle::collect(
+            &self.dir_context.config_dir,
+            self.dir_context.home_dir.as_deref(),
+            &self.macros,
+        ) {
+            Ok((local, _)) => local,
+            Err(e) => {
+                self.set_important_status_message(
+                    t!("settings_bundle.import_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+
+        let changes = review(&local, &incoming);
+        if changes.is_empty() {
+            self.set_status_message(t!("settings_bundle.up_to_date").to_string());
+            return;
+        }
+        self.show_settings_bundle_review(&format_review(&incoming, &changes));
+
+        // Packages are only listed in the review; everything else can be applied
+        let categories: Vec<&str> = changes
+            .keys()
+            .filter(|c| **c != BundleCategory::Packages)
+            .map(|c| c.key())
+            .collect();
+        self.pending_settings_import = Some(incoming);
+        self.start_prompt_with_initial_text(
+            t!("settings_bundle.apply_prompt").to_string(),
+            PromptType::ApplySettingsBundle,
+            categories.join(", "),
+        );
+    }
+
+    /// Apply the categories named in `input` from the reviewed bundle.
+    pub fn handle_apply_settings_bundle(&mut self, input: &str) {
+        let Some(bundle) = self.pending_settings_import.take() else {
+            return;
+        };
+        let categories: Vec<BundleCategory> = input
+            .split([',', ' '])
+            .filter_map(|name| BundleCategory::from_key(name.trim()))
+            .collect();
+        if categories.is_empty() {
+            self.set_status_message(t!("settings_bundle.cancelled").to_string());
+            return;
+        }
+
+        let config_dir = self.dir_context.config_dir.clone();
+        let home_dir = self.dir_context.home_dir.clone();
+        let mut applied = Vec::new();
+
+        if categories
+            .iter()
+            .any(|c| matches!(c, BundleCategory::Config | BundleCategory::Keybindings))
+        {
+            let merged = read_user_config(&config_dir).and_then(|current| {
+                let merged = merge_user_config(
+                    &current,
+                    &bundle,
+                    &categories,
+                    &config_dir,
+                    home_dir.as_deref(),
+                );
+                std::fs::create_dir_all(&config_dir)?;
+                let json = serde_json::to_string_pretty(&merged)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+                std::fs::write(config_dir.join(Config::FILENAME), json)
+            });
+            if let Err(e) = merged {
+                self.set_important_status_message(
+                    t!("settings_bundle.import_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+            self.reload_config();
+            applied.extend(
+                [BundleCategory::Config, BundleCategory::Keybindings]
+                    .into_iter()
+                    .filter(|c| categories.contains(c)),
+            );
+        }
+
+        if let Some(themes) = bundle
+            .themes
+            .as_ref()
+            .filter(|_| categories.contains(&BundleCategory::Themes))
+        {
+            let themes_dir = self.dir_context.themes_dir();
+            let written = std::fs::create_dir_all(&themes_dir).and_then(|()| {
+                themes
+                    .iter()
+                    .try_for_each(|(name, content)| std::fs::write(themes_dir.join(name), content))
+            });
+            if let Err(e) = written {
+                self.set_important_status_message(
+                    t!("settings_bundle.import_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+            self.reload_themes();
+            applied.push(BundleCategory::Themes);
+        }
+
+        if let Some(macros) = bundle
+            .macros
+            .filter(|_| categories.contains(&BundleCategory::Macros))
+        {
+            self.macros.extend(macros);
+            applied.push(BundleCategory::Macros);
+        }
+
+        let applied: Vec<&str> = applied.iter().map(|c| c.key()).collect();
+        self.set_status_message(
+            t!("settings_bundle.imported", categories = applied.join(", ")).to_string(),
+        );
+    }
+
+    /// Resolve a bundle path entered in a prompt (None when empty)
+    fn resolve_bundle_path(&self, input: &str) -> Option<PathBuf> {
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        let expanded_path = expand_tilde(input);
+        Some(if expanded_path.is_absolute() {
+            normalize_path(&expanded_path)
+        } else {
+            normalize_path(&self.working_dir.join(&expanded_path))
+        })
+    }
+
+    /// Show the import review in a read-only buffer
+    fn show_settings_bundle_review(&mut self, content: &str) {
+        let buffer_id =
+            self.create_virtual_buffer(REVIEW_BUFFER_NAME.to_string(), "special".to_string(), true);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.insert(0, content);
+            state.buffer.clear_modified();
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+        self.set_active_buffer(buffer_id);
+    }
+}
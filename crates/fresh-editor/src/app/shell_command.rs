@@ -28,8 +28,18 @@ impl Editor {
     /// Execute a shell command with the current buffer/selection as stdin.
     /// Returns Ok(output) on success, Err(error_message) on failure.
     pub fn execute_shell_command(&mut self, command: &str) -> Result<String, String> {
+        self.execute_shell_command_on(command, true)
+    }
+
+    /// Execute a shell command with the selection (if `use_selection` and there
+    /// is one) or the entire buffer as stdin.
+    fn execute_shell_command_on(
+        &mut self,
+        command: &str,
+        use_selection: bool,
+    ) -> Result<String, String> {
         // Get the input text (selection or entire buffer)
-        let input = self.get_shell_input();
+        let input = self.get_shell_input(use_selection);
 
         // Detect the shell to use
         let shell = detect_shell();
@@ -37,6 +47,7 @@ impl Editor {
         // Execute the command
         let mut child = Command::new(&shell)
             .args(["-c", command])
+            .envs(crate::services::shell_env::current().vars())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -76,9 +87,13 @@ impl Editor {
     }
 
     /// Get the input for shell command (selection or entire buffer).
-    fn get_shell_input(&mut self) -> String {
+    fn get_shell_input(&mut self, use_selection: bool) -> String {
         // First get selection range
-        let selection_range = { self.active_cursors().primary().selection_range() };
+        let selection_range = if use_selection {
+            self.active_cursors().primary().selection_range()
+        } else {
+            None
+        };
 
         // Check if there's a selection
         if let Some(selection) = selection_range {
@@ -95,14 +110,65 @@ impl Editor {
     /// If `replace` is true, replaces the selection/buffer with output.
     /// If `replace` is false, creates a new buffer with the output.
     pub fn handle_shell_command(&mut self, command: &str, replace: bool) {
+        self.run_shell_command(command, replace, true);
+    }
+
+    /// Reformat the entire buffer by piping it through the formatter configured
+    /// for its language, the same way Shell Command (Replace) does. Without a
+    /// stdin formatter the replace prompt opens so a command can be typed.
+    pub fn prettify_buffer(&mut self) {
+        let language = self.active_state().language.clone();
+        let formatter = self
+            .config
+            .languages
+            .get(&language)
+            .and_then(|lc| lc.formatter.clone())
+            .filter(|f| f.stdin);
+
+        let Some(formatter) = formatter else {
+            self.start_shell_command_prompt(true);
+            self.set_status_message(
+                t!("shell.prettify_no_formatter", language = language).to_string(),
+            );
+            return;
+        };
+
+        let file_path = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default();
+        let command = std::iter::once(formatter.command.clone())
+            .chain(
+                formatter
+                    .args
+                    .iter()
+                    .map(|arg| arg.replace("$FILE", &file_path)),
+            )
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        self.run_shell_command(&command, true, false);
+
+        // Formatted output normally has short lines again
+        let threshold = self.config.editor.long_line_threshold;
+        self.active_state_mut().detect_long_lines(threshold);
+    }
+
+    /// Run `command` on the selection (if `use_selection` and there is one) or
+    /// the entire buffer, then replace it or show the output in a new buffer.
+    fn run_shell_command(&mut self, command: &str, replace: bool, use_selection: bool) {
         // Capture selection range first
-        let selection_range = {
+        let selection_range = if use_selection {
             let primary = self.active_cursors().primary();
             primary.selection_range().map(|sel| {
                 let start = sel.start.min(sel.end);
                 let end = sel.start.max(sel.end);
                 (start, end)
             })
+        } else {
+            None
         };
 
         // Now get the deleted text if there's a selection
@@ -114,7 +180,7 @@ impl Editor {
         };
         let has_selection = selection_info.is_some();
 
-        match self.execute_shell_command(command) {
+        match self.execute_shell_command_on(command, use_selection) {
             Ok(output) => {
                 if replace {
                     self.replace_with_shell_output(&output, has_selection, selection_info);
@@ -261,6 +327,7 @@ impl Editor {
         let shell = detect_shell();
         let mut child = Command::new(&shell)
             .args(["-c", command])
+            .envs(crate::services::shell_env::current().vars())
             .spawn()
             .map_err(|e| anyhow::anyhow!("Failed to spawn shell: {}", e))?;
 
@@ -0,0 +1,122 @@
+//! Commands given on the command line with `--execute`.
+//!
+//! `fresh file.txt --execute "goto-line 100; toggle-line-numbers"` runs each
+//! `;`-separated command once the first frame is on screen, through the same
+//! action pipeline as keybindings, so edits can be undone. Commands are
+//! looked up like auto-commands: by action name (with `-` or `_`) or by
+//! command palette name. Text after the name answers the prompt the command
+//! opens, e.g. the line number for `goto-line`.
+
+use std::collections::HashMap;
+
+use super::Editor;
+use crate::input::keybindings::Action;
+use rust_i18n::t;
+
+/// Commands waiting for the first frame
+#[derive(Debug)]
+pub(super) struct StartupCommands {
+    commands: Vec<String>,
+    /// `--execute-strict`: quit with status 1 at the first failure
+    strict: bool,
+}
+
+/// Split an `--execute` script into its commands
+fn split_script(script: &str) -> Vec<String> {
+    script
+        .split(';')
+        .map(str::trim)
+        .filter(|command| !command.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl Editor {
+    /// Queue the `;`-separated commands of `script` to run after the first
+    /// render. With `strict`, the first command that fails stops the rest
+    /// and makes the editor quit with status 1.
+    pub fn queue_startup_commands(&mut self, script: &str, strict: bool) {
+        let commands = split_script(script);
+        if !commands.is_empty() {
+            self.startup_commands = Some(StartupCommands { commands, strict });
+        }
+    }
+
+    /// Run the queued startup commands, if any. Returns true if any ran.
+    pub fn run_startup_commands(&mut self) -> bool {
+        let Some(startup) = self.startup_commands.take() else {
+            return false;
+        };
+        for command in &startup.commands {
+            tracing::info!("Running startup command '{}'", command);
+            let Err(error) = self.run_startup_command(command) else {
+                continue;
+            };
+            tracing::warn!("Startup command '{}' failed: {}", command, error);
+            self.set_important_status_message(
+                t!("execute.failed", command = command, error = error).to_string(),
+            );
+            if startup.strict {
+                self.startup_commands_failed = true;
+                self.should_quit = true;
+                break;
+            }
+        }
+        true
+    }
+
+    /// Exit code for `--execute-strict`: 1 when a command failed, None otherwise
+    pub fn startup_commands_exit_code(&self) -> Option<i32> {
+        self.startup_commands_failed.then_some(1)
+    }
+
+    /// Run one command, answering the prompt it opens with its argument
+    fn run_startup_command(&mut self, command: &str) -> Result<(), String> {
+        let (action, argument) = self
+            .resolve_startup_command(command)
+            .ok_or_else(|| t!("execute.unknown_command").to_string())?;
+        self.handle_action(action).map_err(|e| e.to_string())?;
+        if argument.is_empty() {
+            return Ok(());
+        }
+        if self.prompt.is_none() {
+            return Err(t!("execute.unexpected_argument", argument = argument).to_string());
+        }
+        self.handle_action(Action::PromptConfirmWithText(argument.to_string()))
+            .map_err(|e| e.to_string())
+    }
+
+    /// Find the action for a command and the argument that follows its name.
+    /// Longer names win, so "Go to Line 100" is "Go to Line" with "100".
+    fn resolve_startup_command<'a>(&self, command: &'a str) -> Option<(Action, &'a str)> {
+        let no_args = HashMap::new();
+        let word_ends = command
+            .char_indices()
+            .filter(|(_, c)| c.is_whitespace())
+            .map(|(i, _)| i)
+            .chain(std::iter::once(command.len()));
+        let mut name_ends: Vec<usize> = word_ends.collect();
+        name_ends.reverse();
+        name_ends.into_iter().find_map(|end| {
+            let name = command[..end].trim_end();
+            let action = self
+                .resolve_command(&name.replace('-', "_"), &no_args)
+                .or_else(|| self.resolve_command(name, &no_args))?;
+            Some((action, command[end..].trim()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_script() {
+        assert_eq!(
+            split_script("goto-line 100; toggle-line-numbers;;  "),
+            vec!["goto-line 100", "toggle-line-numbers"]
+        );
+        assert!(split_script(" ; ").is_empty());
+    }
+}
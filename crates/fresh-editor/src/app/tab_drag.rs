@@ -382,7 +382,7 @@ impl Editor {
             Err(e) => {
                 // Restore active split on error
                 self.split_manager.set_active_split(original_active);
-                self.set_status_message(
+                self.set_important_status_message(
                     t!("error.split_failed", error = e.to_string()).to_string(),
                 );
             }
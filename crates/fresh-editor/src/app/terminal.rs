@@ -24,13 +24,53 @@
 //!   - Performance: O(1) ≈ 1ms
 
 use super::{BufferId, BufferMetadata, Editor};
-use crate::services::terminal::TerminalId;
+use crate::services::terminal::{TerminalId, TerminalManager};
 use crate::state::EditorState;
 use rust_i18n::t;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 impl Editor {
     /// Open a new terminal in the current split
     pub fn open_terminal(&mut self) {
+        let cwd = Some(self.working_dir.clone());
+        self.open_terminal_with(|manager, cols, rows, log_path, backing_path| {
+            manager.spawn(cols, rows, cwd, log_path, backing_path)
+        });
+    }
+
+    /// Open a terminal in the current split running a shell on the remote
+    /// host of the active buffer, in the directory of its file
+    pub fn open_remote_terminal(&mut self) {
+        let buffer = &self.active_state().buffer;
+        let filesystem = Arc::clone(buffer.filesystem());
+        if filesystem.remote_connection_info().is_none() {
+            self.set_status_message(t!("terminal.not_remote").to_string());
+            return;
+        }
+        let cwd = buffer
+            .file_path()
+            .and_then(Path::parent)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(Path::to_path_buf);
+        self.open_terminal_with(|manager, cols, rows, _log_path, backing_path| {
+            // Remote terminals aren't restored, so their output isn't logged
+            manager.spawn_remote(cols, rows, filesystem.as_ref(), cwd, None, backing_path)
+        });
+    }
+
+    /// Open a terminal in the current split, with `spawn` starting its shell
+    /// given the terminal size and its log and backing file paths
+    fn open_terminal_with(
+        &mut self,
+        spawn: impl FnOnce(
+            &mut TerminalManager,
+            u16,
+            u16,
+            Option<PathBuf>,
+            Option<PathBuf>,
+        ) -> Result<TerminalId, String>,
+    ) {
         // Get the current split dimensions for the terminal size
         let (cols, rows) = self.get_terminal_dimensions();
 
@@ -59,10 +99,10 @@ impl Editor {
             .terminal_backing_files
             .get(&predicted_terminal_id)
             .cloned();
-        match self.terminal_manager.spawn(
+        match spawn(
+            &mut self.terminal_manager,
             cols,
             rows,
-            Some(self.working_dir.clone()),
             Some(log_path.clone()),
             backing_path_for_spawn,
         ) {
@@ -182,8 +222,7 @@ impl Editor {
         self.terminal_buffers.insert(buffer_id, terminal_id);
 
         // Initialize event log for undo/redo
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(buffer_id, self.new_event_log());
 
         // Set up split view state
         if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
@@ -238,8 +277,7 @@ impl Editor {
         );
         self.buffer_metadata.insert(buffer_id, metadata);
         self.terminal_buffers.insert(buffer_id, terminal_id);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(buffer_id, self.new_event_log());
 
         buffer_id
     }
@@ -752,6 +790,11 @@ impl Editor {
                         for row in 0..rows {
                             content.push(state.get_line(row));
                         }
+                        if let Some(masker) = &self.privacy_masker {
+                            for row in &mut content {
+                                super::privacy::mask_terminal_row(row, masker);
+                            }
+                        }
 
                         // Clear the content area first
                         frame.render_widget(ratatui::widgets::Clear, *content_rect);
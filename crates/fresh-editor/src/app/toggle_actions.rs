@@ -8,7 +8,7 @@
 
 use rust_i18n::t;
 
-use crate::config::Config;
+use crate::config::{Config, UiDensity};
 use crate::config_io::{ConfigLayer, ConfigResolver};
 use crate::input::keybindings::KeybindingResolver;
 
@@ -43,6 +43,52 @@ impl Editor {
         }
     }
 
+    /// Toggle line wrapping in the active split.
+    ///
+    /// Other splits (including other views of the same buffer) keep their own
+    /// setting; `editor.line_wrap` only seeds newly created views.
+    pub fn toggle_line_wrap(&mut self) {
+        let active_split = self.split_manager.active_split();
+        let wrap_indent = self.config.editor.wrap_indent;
+        if let Some(vs) = self.split_view_states.get_mut(&active_split) {
+            let enabled = !vs.viewport.line_wrap_enabled;
+            vs.viewport.line_wrap_enabled = enabled;
+            vs.viewport.wrap_indent = wrap_indent;
+            let state = if enabled {
+                t!("view.state_enabled").to_string()
+            } else {
+                t!("view.state_disabled").to_string()
+            };
+            self.set_status_message(t!("view.line_wrap_state", state = state).to_string());
+        }
+    }
+
+    /// Toggle whitespace indicators in the active split.
+    ///
+    /// The first toggle in a split stores a per-split override derived from
+    /// the buffer's settings, so other views of the buffer are unaffected.
+    pub fn toggle_whitespace_indicators(&mut self) {
+        let active_split = self.split_manager.active_split();
+        let Some(buffer_whitespace) = self
+            .buffers
+            .get(&self.active_buffer())
+            .map(|state| state.buffer_settings.whitespace)
+        else {
+            return;
+        };
+        if let Some(vs) = self.split_view_states.get_mut(&active_split) {
+            let mut whitespace = vs.whitespace.unwrap_or(buffer_whitespace);
+            whitespace.toggle_all();
+            vs.whitespace = Some(whitespace);
+            let status = if whitespace.any_visible() {
+                t!("toggle.whitespace_indicators_shown")
+            } else {
+                t!("toggle.whitespace_indicators_hidden")
+            };
+            self.set_status_message(status.to_string());
+        }
+    }
+
     /// Toggle debug highlight mode for the active buffer
     /// When enabled, shows byte positions and highlight span info for debugging
     pub fn toggle_debug_highlights(&mut self) {
@@ -89,6 +135,47 @@ impl Editor {
         self.tab_bar_visible
     }
 
+    /// Zoom in: switch to the next roomier density preset
+    pub fn zoom_in(&mut self) {
+        match self.config.editor.ui_density.roomier() {
+            Some(density) => self.set_ui_density(density),
+            None => self.set_status_message(t!("zoom.at_max").to_string()),
+        }
+    }
+
+    /// Zoom out: switch to the next denser preset, showing more text rows
+    pub fn zoom_out(&mut self) {
+        match self.config.editor.ui_density.denser() {
+            Some(density) => self.set_ui_density(density),
+            None => self.set_status_message(t!("zoom.at_min").to_string()),
+        }
+    }
+
+    /// Change the density preset, apply it and persist it to the user config
+    fn set_ui_density(&mut self, density: UiDensity) {
+        self.config.editor.ui_density = density;
+        self.apply_ui_density();
+        self.persist_config_change(
+            "/editor/ui_density",
+            serde_json::Value::String(density.as_str().to_string()),
+        );
+        self.set_status_message(t!("zoom.density", density = density.as_str()).to_string());
+    }
+
+    /// Derive menu/tab bar visibility from config and the density preset.
+    ///
+    /// Mouse hit-testing reads the same visibility flags and the cached layout
+    /// is rebuilt on the next render, so both stay consistent after a change.
+    pub(super) fn apply_ui_density(&mut self) {
+        let density = self.config.editor.ui_density;
+        self.menu_bar_visible = self.config.editor.show_menu_bar && density.shows_menu_bar();
+        self.tab_bar_visible = self.config.editor.show_tab_bar && density.shows_tab_bar();
+        if !self.menu_bar_visible {
+            self.menu_bar_auto_shown = false;
+            self.menu_state.close_menu();
+        }
+    }
+
     /// Toggle status bar visibility
     pub fn toggle_status_bar(&mut self) {
         self.status_bar_visible = !self.status_bar_visible;
@@ -163,8 +250,14 @@ impl Editor {
             state.buffer_settings.tab_size = tab_size;
             state.buffer_settings.use_tabs = use_tabs;
             state.buffer_settings.auto_close = auto_close;
+            state.buffer_settings.word_rules = self.config.word_rules(&state.language);
+            state.buffer_settings.auto_close_pairs = self.config.auto_close_pairs(&state.language);
             state.buffer_settings.whitespace = whitespace;
         }
+        let active_split = self.split_manager.active_split();
+        if let Some(vs) = self.split_view_states.get_mut(&active_split) {
+            vs.whitespace = None;
+        }
 
         self.set_status_message(t!("toggle.buffer_settings_reset").to_string());
     }
@@ -193,6 +286,30 @@ impl Editor {
         self.mouse_enabled
     }
 
+    /// Release mouse capture until the next key press so the terminal's own
+    /// text selection can be used
+    pub fn suspend_mouse_capture(&mut self) {
+        use std::io::stdout;
+
+        self.mouse_capture_suspended = true;
+        // Best-effort terminal mouse capture toggle.
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = crossterm::execute!(stdout(), crossterm::event::DisableMouseCapture);
+        self.set_status_message(t!("toggle.mouse_capture_suspended").to_string());
+    }
+
+    /// Restore mouse capture released by [`Self::suspend_mouse_capture`]
+    pub fn resume_mouse_capture(&mut self) {
+        use std::io::stdout;
+
+        self.mouse_capture_suspended = false;
+        if self.mouse_enabled {
+            // Best-effort terminal mouse capture toggle.
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = crossterm::execute!(stdout(), crossterm::event::EnableMouseCapture);
+        }
+    }
+
     /// Toggle mouse hover for LSP on/off
     pub fn toggle_mouse_hover(&mut self) {
         self.config.editor.mouse_hover_enabled = !self.config.editor.mouse_hover_enabled;
@@ -221,6 +338,12 @@ impl Editor {
         self.gpm_active = active;
     }
 
+    /// Open every file read-only from now on (`--readonly`). "Toggle Read
+    /// Only" still makes a single buffer writable, for this session only.
+    pub fn set_open_read_only(&mut self, read_only: bool) {
+        self.open_read_only = read_only;
+    }
+
     /// Toggle inlay hints visibility
     pub fn toggle_inlay_hints(&mut self) {
         self.config.editor.enable_inlay_hints = !self.config.editor.enable_inlay_hints;
@@ -242,7 +365,7 @@ impl Editor {
     pub fn dump_config(&mut self) {
         // Create the config directory if it doesn't exist
         if let Err(e) = self.filesystem.create_dir_all(&self.dir_context.config_dir) {
-            self.set_status_message(
+            self.set_important_status_message(
                 t!("error.config_dir_failed", error = e.to_string()).to_string(),
             );
             return;
@@ -277,7 +400,7 @@ impl Editor {
                 }
             }
             Err(e) => {
-                self.set_status_message(
+                self.set_important_status_message(
                     t!("error.config_save_failed", error = e.to_string()).to_string(),
                 );
             }
@@ -326,11 +449,12 @@ impl Editor {
 
         // Update clipboard configuration
         self.clipboard.apply_config(&self.config.clipboard);
+        crate::services::shell_env::configure(&self.config.process);
 
         // Apply bar visibility changes immediately
-        self.menu_bar_visible = self.config.editor.show_menu_bar;
-        self.tab_bar_visible = self.config.editor.show_tab_bar;
+        self.apply_ui_density();
         self.status_bar_visible = self.config.editor.show_status_bar;
+        self.apply_undo_limits();
 
         // Update LSP configs
         if let Some(ref mut lsp) = self.lsp {
@@ -339,6 +463,8 @@ impl Editor {
             }
         }
 
+        self.validate_auto_commands();
+
         // Emit event so plugins know config changed
         let config_path = Config::find_config_path(&self.working_dir);
         self.emit_event(
@@ -0,0 +1,386 @@
+//! Interactive tutorial (`fresh --tutor`, "Tutorial" in the command palette).
+//!
+//! The tutorial is an editable scratch buffer that reveals one lesson at a
+//! time. Each lesson ends with an exercise region between two marker lines;
+//! whenever the buffer changes, the region is compared with the lesson's
+//! expected text and, once it matches, the next lesson is appended below.
+//!
+//! All text comes from the locale files, so lessons are translated along with
+//! the rest of the UI. The index of the next lesson is saved in the data
+//! directory so the tutorial resumes where the user left off.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::Editor;
+use crate::model::event::{BufferId, Event};
+use rust_i18n::t;
+
+/// Name of the tutorial buffer
+pub const TUTORIAL_BUFFER_NAME: &str = "*Fresh Tutorial*";
+
+/// Number of lessons in the tutorial
+pub const LESSON_COUNT: usize = 7;
+
+/// Text of one lesson, resolved from the current locale
+#[derive(Debug, Clone)]
+pub struct Lesson {
+    pub title: String,
+    pub body: String,
+    /// Initial content of the exercise region
+    pub practice: String,
+    /// What the exercise region must contain to pass
+    pub expected: String,
+    /// The lesson also requires the tutorial to be shown in two splits
+    pub needs_split: bool,
+}
+
+/// Look up lesson `index` (0-based) in the current locale
+pub fn lesson_at(index: usize) -> Option<Lesson> {
+    let (title, body, practice, expected, needs_split) = match index {
+        0 => (
+            t!("tutorial.movement.title"),
+            t!("tutorial.movement.body"),
+            t!("tutorial.movement.practice"),
+            t!("tutorial.movement.expected"),
+            false,
+        ),
+        1 => (
+            t!("tutorial.selection.title"),
+            t!("tutorial.selection.body"),
+            t!("tutorial.selection.practice"),
+            t!("tutorial.selection.expected"),
+            false,
+        ),
+        2 => (
+            t!("tutorial.lines.title"),
+            t!("tutorial.lines.body"),
+            t!("tutorial.lines.practice"),
+            t!("tutorial.lines.expected"),
+            false,
+        ),
+        3 => (
+            t!("tutorial.multi_cursor.title"),
+            t!("tutorial.multi_cursor.body"),
+            t!("tutorial.multi_cursor.practice"),
+            t!("tutorial.multi_cursor.expected"),
+            false,
+        ),
+        4 => (
+            t!("tutorial.search.title"),
+            t!("tutorial.search.body"),
+            t!("tutorial.search.practice"),
+            t!("tutorial.search.expected"),
+            false,
+        ),
+        5 => (
+            t!("tutorial.splits.title"),
+            t!("tutorial.splits.body"),
+            t!("tutorial.splits.practice"),
+            t!("tutorial.splits.expected"),
+            true,
+        ),
+        6 => (
+            t!("tutorial.palette.title"),
+            t!("tutorial.palette.body"),
+            t!("tutorial.palette.practice"),
+            t!("tutorial.palette.expected"),
+            false,
+        ),
+        _ => return None,
+    };
+    Some(Lesson {
+        title: title.to_string(),
+        body: body.to_string(),
+        practice: practice.to_string(),
+        expected: expected.to_string(),
+        needs_split,
+    })
+}
+
+/// Marker line that opens the exercise region of lesson `index`
+fn exercise_start_marker(index: usize) -> String {
+    format!(
+        "──── {} ▼ ────",
+        t!("tutorial.exercise", number = index + 1)
+    )
+}
+
+/// Marker line that closes the exercise region of lesson `index`
+fn exercise_end_marker(index: usize) -> String {
+    format!(
+        "──── {} ▲ ────",
+        t!("tutorial.exercise", number = index + 1)
+    )
+}
+
+/// Render lesson `index` as it is appended to the tutorial buffer.
+/// Returns the text and the offset of the exercise region within it.
+fn render_lesson(index: usize, lesson: &Lesson) -> (String, usize) {
+    let heading = t!(
+        "tutorial.lesson_heading",
+        number = index + 1,
+        count = LESSON_COUNT,
+        title = &lesson.title
+    )
+    .to_string();
+    let mut text = format!(
+        "\n{}\n{}\n\n{}\n\n{}\n",
+        heading,
+        "=".repeat(heading.chars().count()),
+        lesson.body,
+        exercise_start_marker(index)
+    );
+    let practice_offset = text.len();
+    text.push_str(&lesson.practice);
+    text.push('\n');
+    text.push_str(&exercise_end_marker(index));
+    text.push('\n');
+    (text, practice_offset)
+}
+
+/// The content between the exercise markers of lesson `index`, if both
+/// markers are still in `content`
+fn exercise_region(content: &str, index: usize) -> Option<&str> {
+    let start_marker = exercise_start_marker(index);
+    let end_marker = exercise_end_marker(index);
+    let start = content.find(&start_marker)? + start_marker.len();
+    let end = start + content[start..].find(&end_marker)?;
+    Some(&content[start..end])
+}
+
+/// Compare an exercise region with the expected text, ignoring trailing
+/// whitespace and blank lines around the text
+fn region_matches(region: &str, expected: &str) -> bool {
+    fn normalize(text: &str) -> Vec<&str> {
+        let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+        let first = lines.iter().position(|l| !l.is_empty());
+        let last = lines.iter().rposition(|l| !l.is_empty());
+        match (first, last) {
+            (Some(first), Some(last)) => lines[first..=last].to_vec(),
+            _ => Vec::new(),
+        }
+    }
+    normalize(region) == normalize(expected)
+}
+
+/// Saved tutorial progress
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TutorialProgress {
+    /// Index of the next lesson to complete
+    lesson: usize,
+}
+
+impl TutorialProgress {
+    fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) {
+        let result = (|| -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let content = serde_json::to_string_pretty(self)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            std::fs::write(path, content)
+        })();
+        if let Err(e) = result {
+            tracing::warn!("Failed to save tutorial progress to {:?}: {}", path, e);
+        }
+    }
+}
+
+/// A tutorial buffer being worked through
+#[derive(Debug)]
+pub(crate) struct TutorialSession {
+    buffer_id: BufferId,
+    /// Index of the lesson whose exercise is being checked
+    lesson: usize,
+    /// Buffer version at the last check, so unchanged buffers aren't re-checked
+    checked_version: u64,
+}
+
+impl Editor {
+    /// Open the tutorial, resuming at the first lesson not yet completed.
+    ///
+    /// If the tutorial buffer is already open, switch to it instead.
+    pub fn open_tutorial(&mut self) {
+        if let Some(buffer_id) = self.tutorial.as_ref().map(|s| s.buffer_id) {
+            if self.buffers.contains_key(&buffer_id) {
+                self.set_active_buffer(buffer_id);
+                return;
+            }
+        }
+
+        let progress_path = self.dir_context.tutorial_progress_path();
+        let mut lesson_index = TutorialProgress::load(&progress_path).lesson;
+        if lesson_index >= LESSON_COUNT {
+            lesson_index = 0;
+        }
+
+        let buffer_id = self.create_virtual_buffer(
+            TUTORIAL_BUFFER_NAME.to_string(),
+            "tutorial".to_string(),
+            false,
+        );
+
+        let intro = if lesson_index == 0 {
+            t!("tutorial.intro").to_string()
+        } else {
+            t!("tutorial.resumed", number = lesson_index + 1).to_string()
+        };
+        let lesson = lesson_at(lesson_index).expect("lesson index is in range");
+        let (lesson_text, practice_offset) = render_lesson(lesson_index, &lesson);
+        let content = format!("{}\n{}", intro, lesson_text);
+        let practice_position = content.len() - lesson_text.len() + practice_offset;
+
+        let version = if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.insert(0, &content);
+            state.buffer.clear_modified();
+            state.margins.configure_for_line_numbers(false);
+            state.buffer.version()
+        } else {
+            0
+        };
+
+        self.set_active_buffer(buffer_id);
+        self.move_tutorial_cursor(practice_position);
+        self.tutorial = Some(TutorialSession {
+            buffer_id,
+            lesson: lesson_index,
+            checked_version: version,
+        });
+    }
+
+    /// Check the current exercise after the tutorial buffer changed, and
+    /// reveal the next lesson when it passes. Returns whether anything changed.
+    pub(crate) fn check_tutorial_progress(&mut self) -> bool {
+        let Some(session) = &self.tutorial else {
+            return false;
+        };
+        let buffer_id = session.buffer_id;
+        let lesson_index = session.lesson;
+        let checked_version = session.checked_version;
+
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            // The tutorial buffer was closed
+            self.tutorial = None;
+            return false;
+        };
+        let version = state.buffer.version();
+        if version == checked_version || self.active_buffer() != buffer_id {
+            return false;
+        }
+        if let Some(session) = &mut self.tutorial {
+            session.checked_version = version;
+        }
+
+        let Some(lesson) = lesson_at(lesson_index) else {
+            return false;
+        };
+        let content = state.buffer.to_string().unwrap_or_default();
+        let passed = exercise_region(&content, lesson_index)
+            .is_some_and(|region| region_matches(region, &lesson.expected))
+            && (!lesson.needs_split || self.split_manager.splits_for_buffer(buffer_id).len() > 1);
+        if !passed {
+            return false;
+        }
+
+        let next_index = lesson_index + 1;
+        TutorialProgress { lesson: next_index }.save(&self.dir_context.tutorial_progress_path());
+
+        let (text, practice_offset) = match lesson_at(next_index) {
+            Some(next) => {
+                let (text, offset) = render_lesson(next_index, &next);
+                (text, Some(offset))
+            }
+            None => (format!("\n{}\n", t!("tutorial.finished")), None),
+        };
+        let end = content.len();
+        let separator = if content.ends_with('\n') { "" } else { "\n" };
+        let insert = Event::Insert {
+            position: end,
+            text: format!("{}{}", separator, text),
+            cursor_id: self.active_cursors().primary_id(),
+        };
+        self.active_event_log_mut().append(insert.clone());
+        self.apply_event_to_active_buffer(&insert);
+
+        let version = self
+            .buffers
+            .get(&buffer_id)
+            .map(|s| s.buffer.version())
+            .unwrap_or_default();
+        match practice_offset {
+            Some(offset) => {
+                self.move_tutorial_cursor(end + separator.len() + offset);
+                self.tutorial = Some(TutorialSession {
+                    buffer_id,
+                    lesson: next_index,
+                    checked_version: version,
+                });
+                self.set_status_message(
+                    t!("tutorial.checkpoint", number = lesson_index + 1).to_string(),
+                );
+            }
+            None => {
+                self.tutorial = None;
+                self.set_status_message(t!("tutorial.completed").to_string());
+            }
+        }
+        true
+    }
+
+    /// Collapse to one cursor at `position` in the tutorial and scroll to it
+    fn move_tutorial_cursor(&mut self, position: usize) {
+        let cursors = self.active_cursors_mut();
+        cursors.remove_secondary();
+        let primary = cursors.primary_mut();
+        primary.move_to(position, false);
+
+        let active_split = self.split_manager.active_split();
+        let active_buffer = self.active_buffer();
+        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+            let state = self.buffers.get_mut(&active_buffer).unwrap();
+            view_state.ensure_cursor_visible(&mut state.buffer, &state.marker_list);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_lesson_has_text() {
+        for index in 0..LESSON_COUNT {
+            let lesson = lesson_at(index).unwrap();
+            assert!(!lesson.title.is_empty());
+            assert!(!lesson.expected.is_empty());
+            assert!(!region_matches(&lesson.practice, &lesson.expected));
+        }
+        assert!(lesson_at(LESSON_COUNT).is_none());
+    }
+
+    #[test]
+    fn test_exercise_region_and_matching() {
+        let lesson = lesson_at(0).unwrap();
+        let (text, offset) = render_lesson(0, &lesson);
+        assert!(text[offset..].starts_with(&lesson.practice));
+
+        let region = exercise_region(&text, 0).unwrap();
+        assert!(region_matches(region, &lesson.practice));
+        assert!(exercise_region(&text, 1).is_none());
+
+        let solved = text.replace(&lesson.practice, &format!("{}   \n", lesson.expected));
+        assert!(region_matches(
+            exercise_region(&solved, 0).unwrap(),
+            &lesson.expected
+        ));
+    }
+}
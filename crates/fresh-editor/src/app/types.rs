@@ -38,8 +38,10 @@ pub(super) struct SearchState {
     /// Optional search range (for search in selection)
     pub search_range: Option<Range<usize>>,
     /// True if the match count was capped at MAX_MATCHES
-    #[allow(dead_code)]
     pub capped: bool,
+    /// False while the incremental scan of a large file is still counting;
+    /// `matches` then holds the ones found so far
+    pub complete: bool,
 }
 
 impl SearchState {
@@ -47,15 +49,38 @@ impl SearchState {
     /// Prevents unbounded memory usage when searching for common patterns
     /// in large files.
     pub const MAX_MATCHES: usize = 100_000;
+
+    /// `count` as a match total, e.g. "57", or "57+" while more matches may
+    /// follow
+    pub fn total_label(&self, count: usize) -> String {
+        if self.capped || !self.complete {
+            format!("{}+", count)
+        } else {
+            count.to_string()
+        }
+    }
+
+    /// Position of the current match for the status bar, e.g. "3/57"
+    pub fn count_label(&self) -> String {
+        let current = self.current_match_index.map_or(0, |index| index + 1);
+        format!("{}/{}", current, self.total_label(self.matches.len()))
+    }
 }
 
 /// A bookmark in the editor (position in a specific buffer)
 #[derive(Debug, Clone)]
 pub(super) struct Bookmark {
-    /// Buffer ID where the bookmark is set
-    pub buffer_id: BufferId,
+    /// Buffer ID where the bookmark is set, if that buffer has been open
+    /// since the bookmark was set or restored
+    pub buffer_id: Option<BufferId>,
+    /// Canonical path of the buffer's file, used to reopen it once the
+    /// buffer is closed or the editor restarted
+    pub file_path: Option<PathBuf>,
     /// Byte offset position in the buffer
     pub position: usize,
+    /// When the bookmark was last set or jumped to in this run, for ordering
+    /// the bookmark picker
+    pub last_used: Option<std::time::Instant>,
 }
 
 /// State for interactive replace (query-replace)
@@ -77,6 +102,8 @@ pub(super) struct InteractiveReplaceState {
     pub replacements_made: usize,
     /// Compiled regex for regex-mode replace (None when regex mode is off)
     pub regex: Option<regex::bytes::Regex>,
+    /// The replacement parsed for `regex` (None when regex mode is off)
+    pub template: Option<super::regex_replace::ReplaceTemplate>,
 }
 
 /// The kind of buffer (file-backed or virtual)
@@ -128,10 +155,23 @@ pub struct BufferMetadata {
     /// Whether this buffer should be hidden from tabs (used for composite source buffers)
     pub hidden_from_tabs: bool,
 
+    /// Set while the buffer's file is missing on disk (deleted or moved by
+    /// another program). Cleared when the file reappears or is saved again.
+    pub missing_on_disk: Option<MissingOnDisk>,
+
     /// Stable recovery ID for unnamed buffers.
     /// For file-backed buffers, recovery ID is computed from the path hash.
     /// For unnamed buffers, this is generated once and reused across auto-saves.
     pub recovery_id: Option<String>,
+
+    /// SSH connection ("user@host[:port]") the file was opened on when it is
+    /// not the editor's primary connection. The buffer's own filesystem
+    /// talks to that connection, so saves and reloads reach the right host.
+    pub remote_connection: Option<String>,
+
+    /// Encoding detected when the file was loaded from disk, before any
+    /// remembered or user-chosen encoding was applied
+    pub detected_encoding: Option<crate::model::buffer::Encoding>,
 }
 
 impl BufferMetadata {
@@ -165,6 +205,16 @@ impl BufferMetadata {
     }
 }
 
+/// State of a file-backed buffer whose file disappeared from disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingOnDisk {
+    /// The buffer was unmodified when the file disappeared and was marked
+    /// modified only to protect its content
+    pub forced_modified: bool,
+    /// A file in the same directory that looks like the renamed original
+    pub renamed_to: Option<PathBuf>,
+}
+
 impl Default for BufferMetadata {
     fn default() -> Self {
         Self::new()
@@ -186,7 +236,10 @@ impl BufferMetadata {
             binary: false,
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
+            missing_on_disk: None,
             recovery_id: None,
+            remote_connection: None,
+            detected_encoding: None,
         }
     }
 
@@ -205,7 +258,10 @@ impl BufferMetadata {
             binary: false,
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
+            missing_on_disk: None,
             recovery_id: None,
+            remote_connection: None,
+            detected_encoding: None,
         }
     }
 
@@ -242,7 +298,10 @@ impl BufferMetadata {
             binary: false,
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
+            missing_on_disk: None,
             recovery_id: None,
+            remote_connection: None,
+            detected_encoding: None,
         }
     }
 
@@ -377,7 +436,10 @@ impl BufferMetadata {
             binary: false,
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
+            missing_on_disk: None,
             recovery_id: None,
+            remote_connection: None,
+            detected_encoding: None,
         }
     }
 
@@ -394,7 +456,10 @@ impl BufferMetadata {
             binary: false,
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: true,
+            missing_on_disk: None,
             recovery_id: None,
+            remote_connection: None,
+            detected_encoding: None,
         }
     }
 
@@ -727,6 +792,11 @@ pub(super) struct MouseState {
     pub drag_selection_split: Option<LeafId>,
     /// The buffer byte position where the selection anchor is
     pub drag_selection_anchor: Option<usize>,
+    /// Whether the drag selects a rectangle (Alt was held at the click)
+    pub drag_selection_block: bool,
+    /// Screen position of an Alt+click held back until it turns into a drag
+    /// or is released (see `MouseConfig::alt_bypasses_capture`)
+    pub alt_press: Option<(u16, u16)>,
     /// Tab drag state (for drag-to-split functionality)
     pub dragging_tab: Option<TabDragState>,
     /// Whether we're currently dragging a popup scrollbar (popup index)
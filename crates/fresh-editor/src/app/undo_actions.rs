@@ -1,9 +1,142 @@
 //! Undo and redo action handlers.
 
+use super::file_open::format_size;
 use super::Editor;
+use crate::model::event::{EventLog, OverlayOptions};
+use crate::primitives::text_property::TextPropertyEntry;
 use rust_i18n::t;
 
+/// Display name of the undo history sizes buffer
+const UNDO_SIZES_BUFFER_NAME: &str = "*Undo History*";
+
 impl Editor {
+    /// Create an event log bounded by the configured undo history limits.
+    pub(super) fn new_event_log(&self) -> EventLog {
+        EventLog::with_limits(
+            self.config.editor.max_undo_entries,
+            self.config.editor.max_undo_memory_mb * 1024 * 1024,
+        )
+    }
+
+    /// Apply the configured undo history limits to all open buffers.
+    pub(super) fn apply_undo_limits(&mut self) {
+        let max_entries = self.config.editor.max_undo_entries;
+        let max_text_bytes = self.config.editor.max_undo_memory_mb * 1024 * 1024;
+        for event_log in self.event_logs.values_mut() {
+            event_log.set_limits(max_entries, max_text_bytes);
+        }
+    }
+
+    /// Stream the checkpoint content of undo histories that crossed their
+    /// limits, and notify the user the first time a buffer's undo history is truncated.
+    ///
+    /// Returns true if a status message was set (needs re-render).
+    pub fn check_undo_history_trimmed(&mut self) -> bool {
+        for (buffer_id, log) in self.event_logs.iter_mut() {
+            if !log.needs_checkpoint_content() {
+                continue;
+            }
+            if let Some(state) = self.buffers.get(buffer_id) {
+                log.stream_checkpoint_content(&state.buffer);
+            }
+        }
+
+        let trimmed = self
+            .event_logs
+            .values_mut()
+            .fold(false, |any, log| log.take_trim_notice() || any);
+        if trimmed {
+            self.set_status_message(
+                t!(
+                    "undo.history_trimmed",
+                    entries = self.config.editor.max_undo_entries,
+                    mb = self.config.editor.max_undo_memory_mb
+                )
+                .to_string(),
+            );
+        }
+        trimmed
+    }
+
+    /// Open a read-only buffer listing the undo history size of every open buffer,
+    /// largest first, for tracking down memory growth in long sessions.
+    pub fn open_undo_history_sizes(&mut self) {
+        let mut rows: Vec<(usize, String)> = self
+            .event_logs
+            .iter()
+            .map(|(buffer_id, log)| {
+                let name = self
+                    .buffer_metadata
+                    .get(buffer_id)
+                    .map(|m| m.display_name.clone())
+                    .unwrap_or_else(|| format!("{:?}", buffer_id));
+                let compacted = log.checkpoint().map_or_else(
+                    || t!("undo.sizes_not_compacted").to_string(),
+                    |checkpoint| {
+                        t!(
+                            "undo.sizes_compacted",
+                            entries = checkpoint.entries,
+                            text = format_size(checkpoint.text_bytes as u64)
+                        )
+                        .to_string()
+                    },
+                );
+                let memory = log.approx_memory_bytes();
+                let line = t!(
+                    "undo.sizes_line",
+                    name = name,
+                    entries = log.len(),
+                    position = log.current_index(),
+                    text = format_size(log.text_bytes() as u64),
+                    memory = format_size(memory as u64),
+                    compacted = compacted
+                )
+                .to_string();
+                (memory, line)
+            })
+            .collect();
+        rows.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+
+        let header = t!(
+            "undo.sizes_header",
+            entries = self.config.editor.max_undo_entries,
+            mb = self.config.editor.max_undo_memory_mb
+        );
+        let mut entries = vec![
+            TextPropertyEntry::text(format!("{}\n\n", header)).with_style(OverlayOptions {
+                bold: true,
+                ..Default::default()
+            }),
+        ];
+        entries.extend(
+            rows.into_iter()
+                .map(|(_, line)| TextPropertyEntry::text(format!("{}\n", line))),
+        );
+
+        let existing = self
+            .buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == UNDO_SIZES_BUFFER_NAME && m.virtual_mode().is_some())
+            .map(|(id, _)| *id);
+        let buffer_id = existing.unwrap_or_else(|| {
+            let buffer_id = self.create_virtual_buffer(
+                UNDO_SIZES_BUFFER_NAME.to_string(),
+                "special".to_string(),
+                true,
+            );
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state.editing_disabled = true;
+                state.margins.configure_for_line_numbers(false);
+            }
+            buffer_id
+        });
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::warn!("Failed to fill {}: {}", UNDO_SIZES_BUFFER_NAME, e);
+            return;
+        }
+        self.set_active_buffer(buffer_id);
+    }
+
     /// Handle Undo action - revert the last edit operation.
     pub fn handle_undo(&mut self) {
         if self.is_editing_disabled() {
@@ -17,6 +150,10 @@ impl Editor {
         let events = event_log.undo();
         let after_idx = self.active_event_log().current_index();
 
+        if !can_undo && self.active_event_log().trimmed_count() > 0 {
+            self.set_status_message(t!("undo.history_limit_reached").to_string());
+        }
+
         tracing::debug!(
             "Undo: before_idx={}, after_idx={}, can_undo={}, events_count={}",
             before_idx,
@@ -0,0 +1,209 @@
+//! Opening files from http(s) URLs.
+//!
+//! `fresh https://...` downloads the file in the background and opens it as
+//! a read-only buffer named after the URL's last path segment, with the
+//! language detected from that name. "Save a Local Copy" writes it to disk,
+//! after which it is an ordinary editable file. Setting
+//! `allow_network_fetch` to false turns downloads off entirely.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use super::file_open::format_size;
+use super::types::BufferMetadata;
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::detected_language::DetectedLanguage;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::url_fetch::{self, FetchedUrl};
+use crate::state::EditorState;
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+
+/// Bytes received between progress updates in the status bar
+const PROGRESS_STEP: u64 = 256 * 1024;
+
+impl Editor {
+    /// Download `url` in the background and open it as a read-only buffer
+    pub fn open_url(&mut self, url: &str) {
+        if !self.config.allow_network_fetch {
+            self.set_status_message(t!("url.disabled", url = url).to_string());
+            return;
+        }
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            self.set_important_status_message(
+                t!(
+                    "url.failed",
+                    url = url,
+                    error = "async runtime not available"
+                )
+                .to_string(),
+            );
+            return;
+        };
+
+        let sender = bridge.sender();
+        let url_owned = url.to_string();
+        tracing::info!("Downloading {}", url);
+        runtime.spawn_blocking(move || {
+            let url = url_owned;
+            let mut last_reported = 0;
+            let result = url_fetch::fetch_url(&url, |received, total| {
+                if received - last_reported < PROGRESS_STEP {
+                    return;
+                }
+                last_reported = received;
+                // Receiver may be dropped if editor is shutting down
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = sender.send(AsyncMessage::UrlDownloadProgress {
+                    url: url.clone(),
+                    received,
+                    total,
+                });
+            });
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = sender.send(AsyncMessage::UrlDownloaded { url, result });
+        });
+
+        self.set_status_message(t!("url.downloading", url = url).to_string());
+    }
+
+    /// Show how far along a URL download is
+    pub(super) fn handle_url_download_progress(
+        &mut self,
+        url: &str,
+        received: u64,
+        total: Option<u64>,
+    ) {
+        let message = match total {
+            Some(total) => t!(
+                "url.progress_of",
+                url = url,
+                received = format_size(received),
+                total = format_size(total)
+            ),
+            None => t!("url.progress", url = url, received = format_size(received)),
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Open a finished download, or report why it failed
+    pub(super) fn handle_url_downloaded(&mut self, url: &str, result: Result<FetchedUrl, String>) {
+        let fetched = match result {
+            Ok(fetched) => fetched,
+            Err(error) => {
+                tracing::warn!("Failed to download {}: {}", url, error);
+                self.set_important_status_message(
+                    t!("url.failed", url = url, error = error).to_string(),
+                );
+                return;
+            }
+        };
+
+        let name = url_fetch::url_file_name(&fetched.final_url);
+        let size = fetched.content.len() as u64;
+        let buffer_id = self.open_downloaded_buffer(&name, fetched.content);
+        self.url_buffers.insert(buffer_id, fetched.final_url);
+        self.set_status_message(
+            t!("url.opened", name = &name, size = format_size(size)).to_string(),
+        );
+    }
+
+    /// Create a read-only, unnamed buffer holding downloaded content
+    fn open_downloaded_buffer(&mut self, name: &str, content: Vec<u8>) -> BufferId {
+        self.position_history.commit_pending_movement();
+        let cursors = self.active_cursors();
+        let position = cursors.primary().position;
+        let anchor = cursors.primary().anchor;
+        self.position_history
+            .record_movement(self.active_buffer(), position, anchor);
+        self.position_history.commit_pending_movement();
+
+        // Replace the initial empty buffer rather than opening next to it
+        let replace_current = self
+            .buffers
+            .get(&self.active_buffer())
+            .is_some_and(|state| {
+                !state.is_composite_buffer
+                    && state.buffer.is_empty()
+                    && !state.buffer.is_modified()
+                    && state.buffer.file_path().is_none()
+            });
+        let buffer_id = if replace_current {
+            self.active_buffer()
+        } else {
+            let id = BufferId(self.next_buffer_id);
+            self.next_buffer_id += 1;
+            id
+        };
+
+        let mut state = EditorState::new(
+            self.terminal_width,
+            self.terminal_height,
+            self.config.editor.large_file_threshold_bytes as usize,
+            Arc::clone(&self.filesystem),
+        );
+        state.buffer =
+            crate::model::buffer::Buffer::from_bytes(content, Arc::clone(&self.filesystem));
+        state.apply_language(DetectedLanguage::from_path(
+            Path::new(name),
+            &self.grammar_registry,
+            &self.config.languages,
+        ));
+        state.buffer_settings.tab_size = self.config.editor.tab_size;
+        state.buffer_settings.auto_close = self.config.editor.auto_close;
+        state.buffer_settings.auto_surround = self.config.editor.auto_surround;
+        state.buffer_settings.word_rules = self.config.word_rules(&state.language);
+        state.buffer_settings.auto_close_pairs = self.config.auto_close_pairs(&state.language);
+        state
+            .margins
+            .configure_for_line_numbers(self.config.editor.line_numbers);
+
+        self.buffers.insert(buffer_id, state);
+        self.event_logs.insert(buffer_id, self.new_event_log());
+        self.buffer_metadata
+            .insert(buffer_id, BufferMetadata::new_unnamed(name.to_string()));
+        self.mark_buffer_read_only(buffer_id, true);
+
+        let active_split = self.split_manager.active_split();
+        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+            view_state.add_buffer(buffer_id);
+            let buf_state = view_state.ensure_buffer_state(buffer_id);
+            buf_state.apply_config_defaults(
+                self.config.editor.line_numbers,
+                self.config.editor.line_wrap,
+                self.config.editor.wrap_indent,
+                self.config.editor.rulers.clone(),
+            );
+        }
+
+        self.set_active_buffer(buffer_id);
+        buffer_id
+    }
+
+    /// Prompt for a path to save the active downloaded buffer to
+    pub fn save_local_copy(&mut self) {
+        if !self.url_buffers.contains_key(&self.active_buffer()) {
+            self.set_status_message(t!("url.not_downloaded").to_string());
+            return;
+        }
+        let name = self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .map(|m| m.display_name.clone())
+            .unwrap_or_default();
+        self.start_prompt_with_initial_text(
+            t!("file.save_as_prompt").to_string(),
+            PromptType::SaveFileAs,
+            name,
+        );
+        self.init_file_open_state();
+    }
+
+    /// A downloaded buffer was saved to disk: it is now an ordinary file
+    pub(super) fn promote_url_buffer(&mut self, buffer_id: BufferId) {
+        if self.url_buffers.remove(&buffer_id).is_some() {
+            self.mark_buffer_read_only(buffer_id, false);
+        }
+    }
+}
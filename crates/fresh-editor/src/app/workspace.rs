@@ -21,7 +21,16 @@
 //! terminal mode.
 //!
 //! Performance: O(1) ≈ 10ms (lazy load) vs O(n) ≈ 1000ms (log replay)
+//!
+//! ## Deferred Files
+//!
+//! Only the files shown in a split (and those with unsaved changes to
+//! recover) are read on restore. The other tabs get an empty placeholder
+//! buffer, and their saved cursor, scroll and folds wait in a
+//! [`DeferredBuffer`] until the tab is first shown; see
+//! [`Editor::load_deferred_buffer`].
 
+use rust_i18n::t;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::time::Instant;
@@ -43,6 +52,14 @@ use crate::workspace::{
 use super::types::Bookmark;
 use super::Editor;
 
+/// A file from the restored workspace whose content hasn't been read yet
+pub(crate) struct DeferredBuffer {
+    /// Canonical path of the file
+    path: PathBuf,
+    /// Saved view states of the file, per split showing it
+    file_states: Vec<(LeafId, SerializedFileState)>,
+}
+
 /// Workspace persistence state tracker
 ///
 /// Tracks dirty state and handles debounced saving for crash resistance.
@@ -107,10 +124,14 @@ impl Editor {
         let mut terminal_indices: HashMap<TerminalId, usize> = HashMap::new();
         let mut seen = HashSet::new();
         for terminal_id in self.terminal_buffers.values().copied() {
+            let handle = self.terminal_manager.get(terminal_id);
+            // A shell on a remote host can't be started again on restore
+            if handle.is_some_and(|h| h.is_remote()) {
+                continue;
+            }
             if seen.insert(terminal_id) {
                 let idx = terminals.len();
                 terminal_indices.insert(terminal_id, idx);
-                let handle = self.terminal_manager.get(terminal_id);
                 let (cols, rows) = handle
                     .map(|h| h.size())
                     .unwrap_or((self.terminal_width, self.terminal_height));
@@ -169,7 +190,7 @@ impl Editor {
         let mut split_states = HashMap::new();
         for (leaf_id, view_state) in &self.split_view_states {
             let active_buffer = active_buffers.get(leaf_id).copied();
-            let serialized = serialize_split_view_state(
+            let mut serialized = serialize_split_view_state(
                 view_state,
                 &self.buffers,
                 &self.buffer_metadata,
@@ -178,6 +199,21 @@ impl Editor {
                 &self.terminal_buffers,
                 &terminal_indices,
             );
+            // Deferred files keep the state they were restored with
+            for (buffer_id, deferred) in &self.deferred_buffers {
+                let stashed = deferred
+                    .file_states
+                    .iter()
+                    .find(|(leaf, _)| leaf == leaf_id);
+                let rel_path = deferred.path.strip_prefix(&self.working_dir);
+                if let (Some((_, file_state)), Ok(rel_path)) = (stashed, rel_path) {
+                    if view_state.keyed_states.contains_key(buffer_id) {
+                        serialized
+                            .file_states
+                            .insert(rel_path.to_path_buf(), file_state.clone());
+                    }
+                }
+            }
             tracing::trace!(
                 "Split {:?}: {} open tabs, active_buffer={:?}",
                 leaf_id,
@@ -239,7 +275,11 @@ impl Editor {
                 .get("replace")
                 .map(|h| h.items().to_vec())
                 .unwrap_or_default(),
-            command_palette: Vec::new(), // Future: when command palette has history
+            command_palette: self
+                .prompt_histories
+                .get("command")
+                .map(|h| h.items().to_vec())
+                .unwrap_or_default(),
             goto_line: self
                 .prompt_histories
                 .get("goto_line")
@@ -264,6 +304,11 @@ impl Editor {
         // Capture bookmarks
         let bookmarks =
             serialize_bookmarks(&self.bookmarks, &self.buffer_metadata, &self.working_dir);
+        let named_bookmarks = serialize_bookmarks(
+            &self.named_bookmarks,
+            &self.buffer_metadata,
+            &self.working_dir,
+        );
 
         // Capture external files (files outside working_dir)
         // These are stored as absolute paths since they can't be made relative
@@ -317,12 +362,14 @@ impl Editor {
             working_dir: self.working_dir.clone(),
             split_layout,
             active_split_id: SplitId::from(self.split_manager.active_split()).0,
+            maximized_split_id: self.split_manager.maximized_split().map(|id| id.0),
             split_states,
             config_overrides,
             file_explorer,
             histories,
             search_options,
             bookmarks,
+            named_bookmarks,
             terminals,
             external_files,
             unnamed_buffers,
@@ -350,7 +397,10 @@ impl Editor {
     }
 
     /// Save global file states for all open file buffers
-    fn save_all_global_file_states(&self) {
+    ///
+    /// Also called on exit when workspaces are off, so files still reopen
+    /// where they were left.
+    pub fn save_all_global_file_states(&self) {
         // Collect all file states from all splits
         for (leaf_id, view_state) in &self.split_view_states {
             // Get the active buffer for this split
@@ -370,6 +420,10 @@ impl Editor {
 
     /// Save file state for a specific buffer (used when closing files and saving workspace)
     fn save_buffer_file_state(&self, buffer_id: BufferId, view_state: &SplitViewState) {
+        // A file that was never shown still has its remembered state
+        if self.deferred_buffers.contains_key(&buffer_id) {
+            return;
+        }
         // Get the file path for this buffer
         let abs_path = match self.buffer_metadata.get(&buffer_id) {
             Some(metadata) => match metadata.file_path() {
@@ -406,10 +460,18 @@ impl Editor {
             compose_width: None,
             plugin_state: std::collections::HashMap::new(),
             folds: Vec::new(),
+            line_marks: self
+                .buffers
+                .get(&buffer_id)
+                .map(super::line_marks::line_mark_lines)
+                .unwrap_or_default(),
+            show_line_numbers: None,
+            line_wrap: None,
+            whitespace: None,
         };
 
         // Save to disk immediately
-        PersistedFileWorkspace::save(&abs_path, file_state);
+        PersistedFileWorkspace::save(&self.dir_context.file_states_dir(), &abs_path, file_state);
     }
 
     /// Sync all active terminal visible screens to their backing files.
@@ -618,6 +680,10 @@ impl Editor {
             self.get_or_create_prompt_history("goto_line")
                 .push(item.clone());
         }
+        for item in &workspace.histories.command_palette {
+            self.get_or_create_prompt_history("command")
+                .push(item.clone());
+        }
 
         // 4. Restore file explorer state
         self.file_explorer_visible = workspace.file_explorer.visible;
@@ -647,6 +713,17 @@ impl Editor {
             file_paths
         );
         let mut path_to_buffer: HashMap<PathBuf, BufferId> = HashMap::new();
+        // Files that no longer exist are left out of the layout
+        let mut missing_files: Vec<PathBuf> = Vec::new();
+
+        // Only the files shown in a split and those with unsaved changes to
+        // recover are read now, the others when first shown
+        let shown_files = collect_shown_file_paths(&workspace.split_states);
+        let recovery_entries = if self.config.editor.hot_exit {
+            self.recovery_service.list_recoverable().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
 
         for rel_path in file_paths {
             let abs_path = self.working_dir.join(&rel_path);
@@ -656,6 +733,18 @@ impl Editor {
                 abs_path.exists()
             );
             if abs_path.exists() {
+                if !shown_files.contains(&rel_path) {
+                    let canonical_path = self
+                        .filesystem
+                        .canonicalize(&abs_path)
+                        .unwrap_or_else(|_| abs_path.clone());
+                    let recovery_id = self.recovery_service.get_buffer_id(Some(&canonical_path));
+                    if !recovery_entries.iter().any(|e| e.id == recovery_id) {
+                        let buffer_id = self.open_file_deferred(canonical_path);
+                        path_to_buffer.insert(rel_path, buffer_id);
+                        continue;
+                    }
+                }
                 // Open the file (this will reuse existing buffer if already open)
                 match self.open_file_internal(&abs_path) {
                     Ok(buffer_id) => {
@@ -668,6 +757,7 @@ impl Editor {
                 }
             } else {
                 tracing::debug!("Skipping non-existent file: {:?}", abs_path);
+                missing_files.push(rel_path);
             }
         }
 
@@ -697,15 +787,19 @@ impl Editor {
                     }
                 } else {
                     tracing::debug!("Skipping non-existent external file: {:?}", abs_path);
+                    missing_files.push(abs_path.clone());
                 }
             }
         }
 
         // 5b2. Apply hot exit recovery: restore unsaved changes to file-backed buffers
         if self.config.editor.hot_exit {
-            let entries = self.recovery_service.list_recoverable().unwrap_or_default();
+            let entries = recovery_entries;
             if !entries.is_empty() {
                 for (_, &buffer_id) in &path_to_buffer {
+                    if self.deferred_buffers.contains_key(&buffer_id) {
+                        continue;
+                    }
                     let file_path = self
                         .buffers
                         .get(&buffer_id)
@@ -886,21 +980,29 @@ impl Editor {
                 .set_active_split(LeafId(new_active_split));
         }
 
-        // 7. Restore bookmarks
-        for (key, bookmark) in &workspace.bookmarks {
-            if let Some(&buffer_id) = path_to_buffer.get(&bookmark.file_path) {
-                // Verify position is valid
-                if let Some(buffer) = self.buffers.get(&buffer_id) {
-                    let pos = bookmark.position.min(buffer.buffer.len());
-                    self.bookmarks.insert(
-                        *key,
-                        Bookmark {
-                            buffer_id,
-                            position: pos,
-                        },
-                    );
-                }
+        // Maximizing applies to the active split, so activate the maximized
+        // one for a moment
+        let maximized = workspace
+            .maximized_split_id
+            .and_then(|id| split_id_map.get(&id).copied());
+        if let Some(maximized) = maximized {
+            let active = self.split_manager.active_split();
+            self.split_manager.set_active_split(LeafId(maximized));
+            if let Err(e) = self.split_manager.maximize_split() {
+                tracing::warn!("Failed to restore maximized split: {}", e);
             }
+            self.split_manager.set_active_split(active);
+        }
+
+        // 7. Restore bookmarks. Those in files that aren't open reopen them
+        // when jumped to.
+        for (key, bookmark) in &workspace.bookmarks {
+            let bookmark = self.restore_bookmark(bookmark, &path_to_buffer);
+            self.bookmarks.insert(*key, bookmark);
+        }
+        for (name, bookmark) in &workspace.named_bookmarks {
+            let bookmark = self.restore_bookmark(bookmark, &path_to_buffer);
+            self.named_bookmarks.insert(name.clone(), bookmark);
         }
 
         // Clean up orphaned buffers: the initial empty buffer created at startup
@@ -909,6 +1011,13 @@ impl Editor {
             .split_view_states
             .values()
             .flat_map(|vs| vs.open_buffers.iter().copied())
+            .chain(
+                self.split_manager
+                    .root()
+                    .get_leaves_with_rects(ratatui::layout::Rect::default())
+                    .into_iter()
+                    .map(|(_, buffer_id, _)| buffer_id),
+            )
             .collect();
         let orphans: Vec<BufferId> = self
             .buffers
@@ -928,6 +1037,21 @@ impl Editor {
             self.buffer_metadata.remove(&id);
         }
 
+        if !missing_files.is_empty() {
+            let names: Vec<String> = missing_files
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            self.set_status_message(
+                t!(
+                    "workspace.skipped_missing_files",
+                    count = missing_files.len(),
+                    files = names.join(", ")
+                )
+                .to_string(),
+            );
+        }
+
         tracing::debug!(
             "Workspace restore complete: {} splits, {} buffers",
             self.split_view_states.len(),
@@ -1089,6 +1213,119 @@ impl Editor {
         self.open_file(path).map_err(WorkspaceError::Io)
     }
 
+    /// Add a placeholder buffer for a restored file that isn't shown yet.
+    /// The file is read by `load_deferred_buffer` when first shown.
+    fn open_file_deferred(&mut self, path: PathBuf) -> BufferId {
+        let buffer_id = BufferId(self.next_buffer_id);
+        self.next_buffer_id += 1;
+
+        let mut state = EditorState::new_with_path(
+            self.config.editor.large_file_threshold_bytes as usize,
+            std::sync::Arc::clone(&self.filesystem),
+            path.clone(),
+        );
+        state.editing_disabled = true;
+        self.buffers.insert(buffer_id, state);
+        self.event_logs.insert(buffer_id, self.new_event_log());
+        self.buffer_metadata.insert(
+            buffer_id,
+            super::types::BufferMetadata::with_file(path.clone(), &self.working_dir),
+        );
+        self.deferred_buffers.insert(
+            buffer_id,
+            DeferredBuffer {
+                path,
+                file_states: Vec::new(),
+            },
+        );
+        buffer_id
+    }
+
+    /// Read a restored file that wasn't shown until now into its placeholder
+    /// buffer, and apply the view state it was saved with. Does nothing for
+    /// other buffers.
+    pub(super) fn load_deferred_buffer(&mut self, buffer_id: BufferId) {
+        let Some(deferred) = self.deferred_buffers.remove(&buffer_id) else {
+            return;
+        };
+        tracing::debug!("Loading deferred file {:?}", deferred.path);
+        if let Err(e) = self.open_file_into(&deferred.path, buffer_id) {
+            tracing::warn!("Failed to load deferred file {:?}: {}", deferred.path, e);
+            self.set_important_status_message(
+                t!("file.error_opening", error = e.to_string()).to_string(),
+            );
+            return;
+        }
+        for (leaf_id, file_state) in &deferred.file_states {
+            if let Some(view_state) = self.split_view_states.get_mut(leaf_id) {
+                restore_file_state(
+                    view_state,
+                    buffer_id,
+                    self.buffers.get_mut(&buffer_id),
+                    file_state,
+                    self.theme.diagnostic_info_fg,
+                );
+            }
+        }
+    }
+
+    /// Number of restored files whose content hasn't been read yet
+    pub fn deferred_buffer_count(&self) -> usize {
+        self.deferred_buffers.len()
+    }
+
+    /// Read the deferred files that are shown in a split
+    pub(super) fn load_shown_deferred_buffers(&mut self) {
+        if self.deferred_buffers.is_empty() {
+            return;
+        }
+        let shown: Vec<BufferId> = self
+            .split_manager
+            .root()
+            .get_leaves_with_rects(ratatui::layout::Rect::default())
+            .into_iter()
+            .map(|(_, buffer_id, _)| buffer_id)
+            .filter(|buffer_id| self.deferred_buffers.contains_key(buffer_id))
+            .collect();
+        for buffer_id in shown {
+            self.load_deferred_buffer(buffer_id);
+        }
+    }
+
+    /// A bookmark from the workspace, pointing at its buffer if the file is
+    /// open (within the working directory or not)
+    fn restore_bookmark(
+        &self,
+        bookmark: &SerializedBookmark,
+        path_to_buffer: &HashMap<PathBuf, BufferId>,
+    ) -> Bookmark {
+        let file_path = self.working_dir.join(&bookmark.file_path);
+        // Files that weren't read yet are read when the bookmark is jumped to
+        let buffer_id = path_to_buffer
+            .get(&bookmark.file_path)
+            .copied()
+            .or_else(|| {
+                self.buffer_metadata
+                    .iter()
+                    .find(|(_, meta)| {
+                        meta.file_path().map(PathBuf::as_path) == Some(file_path.as_path())
+                    })
+                    .map(|(id, _)| *id)
+            })
+            .filter(|id| !self.deferred_buffers.contains_key(id));
+        // Verify position is valid
+        let position = match buffer_id.and_then(|id| self.buffers.get(&id)) {
+            Some(state) => bookmark.position.min(state.buffer.len()),
+            None => bookmark.position,
+        };
+        Bookmark {
+            buffer_id,
+            file_path: Some(file_path),
+            position,
+            last_used: None,
+        }
+    }
+
     /// Recursively restore the split layout from a serialized tree
     fn restore_split_node(
         &mut self,
@@ -1364,63 +1601,21 @@ impl Editor {
                     None => continue,
                 }
             };
-            let max_pos = self
-                .buffers
-                .get(&buffer_id)
-                .map(|b| b.buffer.len())
-                .unwrap_or(0);
-
-            // Ensure keyed state exists for this buffer
-            let buf_state = view_state.ensure_buffer_state(buffer_id);
-
-            let cursor_pos = file_state.cursor.position.min(max_pos);
-            buf_state.cursors.primary_mut().position = cursor_pos;
-            buf_state.cursors.primary_mut().anchor =
-                file_state.cursor.anchor.map(|a| a.min(max_pos));
-            buf_state.cursors.primary_mut().sticky_column = file_state.cursor.sticky_column;
-
-            buf_state.viewport.top_byte = file_state.scroll.top_byte.min(max_pos);
-            buf_state.viewport.top_view_line_offset = file_state.scroll.top_view_line_offset;
-            buf_state.viewport.left_column = file_state.scroll.left_column;
-            buf_state.viewport.set_skip_resize_sync();
-
-            // Restore per-buffer view mode and compose width
-            buf_state.view_mode = match file_state.view_mode {
-                SerializedViewMode::Source => ViewMode::Source,
-                SerializedViewMode::Compose => ViewMode::Compose,
-            };
-            buf_state.compose_width = file_state.compose_width;
-            buf_state.plugin_state = file_state.plugin_state.clone();
-            if let Some(state) = self.buffers.get_mut(&buffer_id) {
-                buf_state.folds.clear(&mut state.marker_list);
-                for fold in &file_state.folds {
-                    let start_line = fold.header_line.saturating_add(1);
-                    let end_line = fold.end_line;
-                    if start_line > end_line {
-                        continue;
-                    }
-                    let Some(start_byte) = state.buffer.line_start_offset(start_line) else {
-                        continue;
-                    };
-                    let end_byte = state
-                        .buffer
-                        .line_start_offset(end_line.saturating_add(1))
-                        .unwrap_or_else(|| state.buffer.len());
-                    buf_state.folds.add(
-                        &mut state.marker_list,
-                        start_byte,
-                        end_byte,
-                        fold.placeholder.clone(),
-                    );
-                }
+            // Files that weren't read yet get their state once they are
+            if let Some(deferred) = self.deferred_buffers.get_mut(&buffer_id) {
+                view_state.ensure_buffer_state(buffer_id);
+                deferred
+                    .file_states
+                    .push((current_split_id, file_state.clone()));
+                continue;
             }
 
-            tracing::trace!(
-                "Restored keyed state for {:?}: cursor={}, top_byte={}, view_mode={:?}",
-                rel_path,
-                cursor_pos,
-                buf_state.viewport.top_byte,
-                buf_state.view_mode,
+            restore_file_state(
+                view_state,
+                buffer_id,
+                self.buffers.get_mut(&buffer_id),
+                file_state,
+                self.theme.diagnostic_info_fg,
             );
         }
 
@@ -1674,6 +1869,10 @@ fn serialize_split_view_state(
                     .collect::<Vec<_>>()
             })
             .unwrap_or_default();
+        let line_marks = buffers
+            .get(buffer_id)
+            .map(super::line_marks::line_mark_lines)
+            .unwrap_or_default();
 
         file_states.insert(
             state_key,
@@ -1705,6 +1904,10 @@ fn serialize_split_view_state(
                 compose_width: buf_state.compose_width,
                 plugin_state: buf_state.plugin_state.clone(),
                 folds,
+                line_marks,
+                show_line_numbers: Some(buf_state.show_line_numbers),
+                line_wrap: Some(buf_state.viewport.line_wrap_enabled),
+                whitespace: buf_state.whitespace,
             },
         );
     }
@@ -1733,29 +1936,131 @@ fn serialize_split_view_state(
     }
 }
 
-fn serialize_bookmarks(
-    bookmarks: &HashMap<char, Bookmark>,
+/// Apply the saved state of a file to its view state in a split. Folds and
+/// line marks need the file's content, so they are skipped without it.
+fn restore_file_state(
+    view_state: &mut SplitViewState,
+    buffer_id: BufferId,
+    state: Option<&mut EditorState>,
+    file_state: &SerializedFileState,
+    line_mark_color: ratatui::style::Color,
+) {
+    let max_pos = state.as_ref().map(|s| s.buffer.len()).unwrap_or(0);
+
+    // Ensure keyed state exists for this buffer
+    let buf_state = view_state.ensure_buffer_state(buffer_id);
+
+    let cursor_pos = file_state.cursor.position.min(max_pos);
+    buf_state.cursors.primary_mut().position = cursor_pos;
+    buf_state.cursors.primary_mut().anchor = file_state.cursor.anchor.map(|a| a.min(max_pos));
+    buf_state.cursors.primary_mut().sticky_column = file_state.cursor.sticky_column;
+
+    buf_state.viewport.top_byte = file_state.scroll.top_byte.min(max_pos);
+    buf_state.viewport.top_view_line_offset = file_state.scroll.top_view_line_offset;
+    buf_state.viewport.left_column = file_state.scroll.left_column;
+    buf_state.viewport.set_skip_resize_sync();
+
+    // Restore per-buffer view mode and compose width
+    buf_state.view_mode = match file_state.view_mode {
+        SerializedViewMode::Source => ViewMode::Source,
+        SerializedViewMode::Compose => ViewMode::Compose,
+    };
+    buf_state.compose_width = file_state.compose_width;
+    buf_state.plugin_state = file_state.plugin_state.clone();
+
+    // Restore per-split display settings
+    if let Some(show_line_numbers) = file_state.show_line_numbers {
+        buf_state.show_line_numbers = show_line_numbers;
+    }
+    if let Some(line_wrap) = file_state.line_wrap {
+        buf_state.viewport.line_wrap_enabled = line_wrap;
+    }
+    buf_state.whitespace = file_state.whitespace;
+    if let Some(state) = state {
+        buf_state.folds.clear(&mut state.marker_list);
+        for fold in &file_state.folds {
+            let start_line = fold.header_line.saturating_add(1);
+            let end_line = fold.end_line;
+            if start_line > end_line {
+                continue;
+            }
+            let Some(start_byte) = state.buffer.line_start_offset(start_line) else {
+                continue;
+            };
+            let end_byte = state
+                .buffer
+                .line_start_offset(end_line.saturating_add(1))
+                .unwrap_or_else(|| state.buffer.len());
+            buf_state.folds.add(
+                &mut state.marker_list,
+                start_byte,
+                end_byte,
+                fold.placeholder.clone(),
+            );
+        }
+        super::line_marks::restore_line_marks(state, &file_state.line_marks, line_mark_color);
+    }
+
+    tracing::trace!(
+        "Restored keyed state for {:?}: cursor={}, top_byte={}, view_mode={:?}",
+        buffer_id,
+        cursor_pos,
+        buf_state.viewport.top_byte,
+        buf_state.view_mode,
+    );
+}
+
+/// Bookmarks with their files relative to `working_dir`, or absolute for
+/// files outside it. Bookmarks in unnamed buffers are left out.
+fn serialize_bookmarks<K: Clone + Eq + std::hash::Hash>(
+    bookmarks: &HashMap<K, Bookmark>,
     buffer_metadata: &HashMap<BufferId, super::types::BufferMetadata>,
     working_dir: &Path,
-) -> HashMap<char, SerializedBookmark> {
+) -> HashMap<K, SerializedBookmark> {
     bookmarks
         .iter()
         .filter_map(|(key, bookmark)| {
-            buffer_metadata
-                .get(&bookmark.buffer_id)
+            let abs_path = bookmark
+                .buffer_id
+                .and_then(|id| buffer_metadata.get(&id))
                 .and_then(|meta| meta.file_path())
-                .and_then(|abs_path| {
-                    abs_path.strip_prefix(working_dir).ok().map(|rel_path| {
-                        (
-                            *key,
-                            SerializedBookmark {
-                                file_path: rel_path.to_path_buf(),
-                                position: bookmark.position,
-                            },
-                        )
-                    })
-                })
+                .map(PathBuf::as_path)
+                .or(bookmark.file_path.as_deref())?;
+            let file_path = abs_path.strip_prefix(working_dir).unwrap_or(abs_path);
+            Some((
+                key.clone(),
+                SerializedBookmark {
+                    file_path: file_path.to_path_buf(),
+                    position: bookmark.position,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Collect the file paths of the active tab of each split. A split without
+/// an active tab (its active buffer wasn't saved) shows its first file.
+fn collect_shown_file_paths(
+    split_states: &HashMap<usize, SerializedSplitViewState>,
+) -> HashSet<PathBuf> {
+    split_states
+        .values()
+        .filter_map(|state| {
+            if state.open_tabs.is_empty() {
+                return state.open_files.get(state.active_file_index);
+            }
+            match state.active_tab_index {
+                Some(index) => match state.open_tabs.get(index) {
+                    Some(SerializedTabRef::File(path)) => Some(path),
+                    _ => None,
+                },
+                None => state.open_tabs.iter().find_map(|tab| match tab {
+                    SerializedTabRef::File(path) => Some(path),
+                    _ => None,
+                }),
+            }
         })
+        .cloned()
         .collect()
 }
 
@@ -0,0 +1,262 @@
+//! Workspace trust prompts and the "Manage Workspace Trust" command.
+//!
+//! The trust rules themselves live in `services::workspace_trust`; this
+//! module asks the user, applies a decision to the running editor and lets
+//! remembered decisions be revoked.
+
+use super::Editor;
+use crate::model::event::{
+    PopupContentData, PopupData, PopupKindHint, PopupListItemData, PopupPositionData,
+};
+use crate::services::lsp::manager::LspSpawnResult;
+use crate::services::workspace_trust::{self, TrustPolicy, TrustStore, WorkspaceTrust};
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+
+/// A trust question waiting for an answer in a list popup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrustConfirmation {
+    /// Whether to trust the working directory
+    Workspace,
+    /// Whether to run formatters and on-save actions in an untrusted workspace
+    OnSaveCommands,
+}
+
+impl Editor {
+    /// Ask whether to trust the working directory if it was never decided
+    pub fn prompt_workspace_trust_if_unknown(&mut self) {
+        if self.workspace_trust == WorkspaceTrust::Unknown
+            && self.pending_trust_confirmation.is_none()
+        {
+            self.show_trust_confirmation(TrustConfirmation::Workspace);
+        }
+    }
+
+    /// Show the popup for a trust question
+    pub(super) fn show_trust_confirmation(&mut self, confirmation: TrustConfirmation) {
+        self.pending_trust_confirmation = Some(confirmation);
+
+        let item = |text: String, detail: String, data: &str| PopupListItemData {
+            text,
+            detail: Some(detail),
+            icon: None,
+            data: Some(data.to_string()),
+        };
+        let (title, items) = match confirmation {
+            TrustConfirmation::Workspace => (
+                t!(
+                    "trust.prompt_title",
+                    path = self.working_dir.display().to_string()
+                )
+                .to_string(),
+                vec![
+                    item(
+                        t!("trust.trust").to_string(),
+                        t!("trust.trust_detail").to_string(),
+                        "trust",
+                    ),
+                    item(
+                        t!("trust.restricted").to_string(),
+                        t!("trust.restricted_detail").to_string(),
+                        "restricted",
+                    ),
+                ],
+            ),
+            TrustConfirmation::OnSaveCommands => (
+                t!("trust.on_save_title").to_string(),
+                vec![
+                    item(
+                        t!("trust.on_save_allow").to_string(),
+                        t!("trust.on_save_allow_detail").to_string(),
+                        "allow",
+                    ),
+                    item(
+                        t!("trust.on_save_skip").to_string(),
+                        t!("trust.on_save_skip_detail").to_string(),
+                        "skip",
+                    ),
+                ],
+            ),
+        };
+
+        self.show_popup(PopupData {
+            kind: PopupKindHint::List,
+            title: Some(title),
+            description: None,
+            transient: false,
+            content: PopupContentData::List { items, selected: 0 },
+            position: PopupPositionData::Centered,
+            width: 60,
+            max_height: 6,
+            bordered: true,
+        });
+    }
+
+    /// Handle the answer to a trust question. Returns false if none was pending.
+    pub(super) fn handle_trust_confirmation_response(&mut self, action: &str) -> bool {
+        let Some(confirmation) = self.pending_trust_confirmation.take() else {
+            return false;
+        };
+
+        match (confirmation, action) {
+            (TrustConfirmation::Workspace, "trust") => {
+                self.remember_workspace_trust(true);
+                self.apply_workspace_trust(WorkspaceTrust::Trusted);
+                self.set_status_message(t!("trust.trusted_msg").to_string());
+            }
+            (TrustConfirmation::Workspace, _) => {
+                self.remember_workspace_trust(false);
+                self.apply_workspace_trust(WorkspaceTrust::Untrusted);
+                self.set_status_message(t!("trust.restricted_msg").to_string());
+            }
+            (TrustConfirmation::OnSaveCommands, "allow") => {
+                self.on_save_commands_allowed = true;
+                match self.run_on_save_actions() {
+                    Ok(_) => self.set_status_message(t!("trust.on_save_allowed_msg").to_string()),
+                    Err(e) => self.set_status_message(e),
+                }
+            }
+            (TrustConfirmation::OnSaveCommands, _) => {
+                self.set_status_message(t!("trust.on_save_skipped_msg").to_string());
+            }
+        }
+        true
+    }
+
+    /// The trust popup was dismissed without an answer
+    pub(super) fn cancel_trust_confirmation(&mut self) {
+        match self.pending_trust_confirmation.take() {
+            Some(TrustConfirmation::Workspace) => {
+                self.set_status_message(t!("trust.undecided_msg").to_string());
+            }
+            Some(TrustConfirmation::OnSaveCommands) => {
+                self.set_status_message(t!("trust.on_save_skipped_msg").to_string());
+            }
+            None => {}
+        }
+    }
+
+    fn remember_workspace_trust(&mut self, trusted: bool) {
+        let mut store = TrustStore::load(&self.dir_context);
+        store.set(&self.working_dir, trusted);
+        if let Err(e) = store.save() {
+            tracing::warn!("Failed to save workspace trust decision: {}", e);
+        }
+    }
+
+    /// Switch the running editor to a new trust state. Trusting loads what
+    /// was held back; plugins already loaded stay until the editor restarts.
+    fn apply_workspace_trust(&mut self, trust: WorkspaceTrust) {
+        let was_trusted = self.workspace_trust.is_trusted();
+        self.workspace_trust = trust;
+        if let Some(lsp) = &mut self.lsp {
+            lsp.set_workspace_trusted(trust.is_trusted());
+        }
+        self.reload_config();
+
+        if trust.is_trusted() && !was_trusted {
+            let plugin_dir = self.working_dir.join("plugins");
+            if self.plugin_manager.is_active() && plugin_dir.is_dir() {
+                self.plugin_manager
+                    .start_loading_plugins_from_dir_with_config(&plugin_dir, &self.config.plugins);
+            }
+            // Start the server for the file in view, as opening it would have
+            let language = self.active_state().language.clone();
+            if self
+                .lsp
+                .as_mut()
+                .is_some_and(|lsp| lsp.try_spawn(&language) == LspSpawnResult::Spawned)
+            {
+                self.notify_lsp_current_file_opened(&language);
+            }
+        } else if !trust.is_trusted() && !was_trusted {
+            // Ask about the server for the file in view, as opening it would have
+            let language = self.active_state().language.clone();
+            let ask = self.lsp.as_mut().is_some_and(|lsp| {
+                lsp.try_spawn(&language) == LspSpawnResult::NotAutoStart
+                    && lsp.get_config(&language).is_some_and(|c| c.auto_start)
+                    && lsp.take_trust_prompt(&language)
+            });
+            if ask {
+                self.show_lsp_confirmation_popup(&language);
+            }
+        }
+    }
+
+    /// List remembered trust decisions so one can be revoked
+    pub fn manage_workspace_trust(&mut self) {
+        let store = TrustStore::load(&self.dir_context);
+        let current = workspace_trust::canonical_workspace(&self.working_dir);
+
+        let mut suggestions = Vec::new();
+        if store.decision(&current).is_none() && workspace_trust::policy() == TrustPolicy::Ask {
+            suggestions.push(trust_suggestion(
+                &current.display().to_string(),
+                t!("trust.state_undecided").to_string(),
+                true,
+            ));
+        }
+        for (path, trusted) in store.decisions() {
+            let state = if trusted {
+                t!("trust.state_trusted")
+            } else {
+                t!("trust.state_untrusted")
+            };
+            suggestions.push(trust_suggestion(
+                &path.display().to_string(),
+                state.to_string(),
+                path == current,
+            ));
+        }
+
+        self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
+            t!("trust.manage_prompt").to_string(),
+            PromptType::ManageWorkspaceTrust,
+            suggestions,
+        ));
+    }
+
+    /// Forget the trust decision for a workspace. The working directory is
+    /// asked about again right away.
+    pub(super) fn revoke_workspace_trust(&mut self, path: &str) {
+        if path.is_empty() {
+            return;
+        }
+        let path = std::path::Path::new(path);
+        let mut store = TrustStore::load(&self.dir_context);
+        if store.revoke(path) {
+            if let Err(e) = store.save() {
+                tracing::warn!("Failed to save workspace trust decisions: {}", e);
+            }
+            self.set_status_message(
+                t!("trust.revoked", path = path.display().to_string()).to_string(),
+            );
+        }
+
+        let is_current = workspace_trust::canonical_workspace(path)
+            == workspace_trust::canonical_workspace(&self.working_dir);
+        if is_current && workspace_trust::policy() == TrustPolicy::Ask {
+            self.show_trust_confirmation(TrustConfirmation::Workspace);
+        }
+    }
+}
+
+fn trust_suggestion(
+    path: &str,
+    state: String,
+    is_current: bool,
+) -> crate::input::commands::Suggestion {
+    let description = if is_current {
+        t!("trust.state_current", state = state).to_string()
+    } else {
+        state
+    };
+    crate::input::commands::Suggestion {
+        text: path.to_string(),
+        description: Some(description),
+        value: Some(path.to_string()),
+        disabled: false,
+        keybinding: None,
+        source: None,
+    }
+}
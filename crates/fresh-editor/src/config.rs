@@ -1,3 +1,5 @@
+use crate::config_io::RuntimeLayer;
+use crate::primitives::word_navigation::WordRules;
 use crate::types::{context_keys, LspServerConfig, ProcessLimits};
 
 use rust_i18n::t;
@@ -15,8 +17,14 @@ pub struct ThemeName(pub String);
 
 impl ThemeName {
     /// Built-in theme options shown in the settings dropdown
-    pub const BUILTIN_OPTIONS: &'static [&'static str] =
-        &["dark", "light", "high-contrast", "nostalgia"];
+    pub const BUILTIN_OPTIONS: &'static [&'static str] = &[
+        "dark",
+        "light",
+        "high-contrast",
+        "wcag-contrast",
+        "deuteranopia",
+        "nostalgia",
+    ];
 }
 
 impl Deref for ThemeName {
@@ -338,6 +346,148 @@ impl JsonSchema for AcceptSuggestionOnEnter {
     }
 }
 
+/// Which fold arrows are drawn in the gutter. Clicking an arrow folds or
+/// unfolds its block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FoldIndicators {
+    /// Arrows on every foldable line and on collapsed folds
+    #[default]
+    Always,
+    /// Arrows on collapsed folds only
+    Collapsed,
+    /// No arrows; fold with the keyboard commands
+    Never,
+}
+
+impl FoldIndicators {
+    /// Whether an arrow is drawn (and clickable) for a fold in this state
+    pub fn shows(self, collapsed: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Collapsed => collapsed,
+            Self::Never => false,
+        }
+    }
+}
+
+impl JsonSchema for FoldIndicators {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("FoldIndicators")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Which fold arrows are drawn in the gutter",
+            "type": "string",
+            "enum": ["always", "collapsed", "never"],
+            "default": "always"
+        })
+    }
+}
+
+/// How far word movement, deletion and selection go inside identifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WordGranularity {
+    /// Whole identifiers, like `myVariableName` or `MAX_SIZE`
+    #[default]
+    Word,
+    /// Parts of identifiers: camelCase humps, `_`-separated parts and
+    /// letter/digit runs
+    Subword,
+}
+
+impl JsonSchema for WordGranularity {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("WordGranularity")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "How far word movement, deletion and selection go inside identifiers",
+            "type": "string",
+            "enum": ["word", "subword"],
+            "default": "word"
+        })
+    }
+}
+
+/// Screen density preset, cycled with zoom in / zoom out.
+///
+/// Terminals own the font size, so "zooming" trades editor chrome for text
+/// rows instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UiDensity {
+    /// All configured bars are shown
+    #[default]
+    Comfortable,
+    /// The menu bar is hidden and tabs drop their padding
+    Compact,
+    /// The menu bar and tab bar are hidden
+    Dense,
+}
+
+impl UiDensity {
+    /// Whether elements are drawn without the padding around them
+    pub fn is_tight(self) -> bool {
+        self != Self::Comfortable
+    }
+
+    /// Whether the menu bar is shown at this density (if enabled in config)
+    pub fn shows_menu_bar(self) -> bool {
+        self == Self::Comfortable
+    }
+
+    /// Whether the tab bar is shown at this density (if enabled in config)
+    pub fn shows_tab_bar(self) -> bool {
+        self != Self::Dense
+    }
+
+    /// The next denser preset ("zoom out"), or None at the densest
+    pub fn denser(self) -> Option<Self> {
+        match self {
+            Self::Comfortable => Some(Self::Compact),
+            Self::Compact => Some(Self::Dense),
+            Self::Dense => None,
+        }
+    }
+
+    /// The next roomier preset ("zoom in"), or None at the roomiest
+    pub fn roomier(self) -> Option<Self> {
+        match self {
+            Self::Comfortable => None,
+            Self::Compact => Some(Self::Comfortable),
+            Self::Dense => Some(Self::Compact),
+        }
+    }
+
+    /// Config string for this preset
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Comfortable => "comfortable",
+            Self::Compact => "compact",
+            Self::Dense => "dense",
+        }
+    }
+}
+
+impl JsonSchema for UiDensity {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("UiDensity")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Screen density preset",
+            "type": "string",
+            "enum": ["comfortable", "compact", "dense"],
+            "default": "comfortable"
+        })
+    }
+}
+
 impl PartialEq<KeybindingMapName> for str {
     fn eq(&self, other: &KeybindingMapName) -> bool {
         self == other.0
@@ -380,6 +530,11 @@ pub struct Config {
     #[serde(default = "default_true")]
     pub check_for_updates: bool,
 
+    /// Allow opening http(s) URLs given on the command line (default: true).
+    /// When disabled, the editor never downloads files from the network.
+    #[serde(default = "default_true")]
+    pub allow_network_fetch: bool,
+
     /// Editor behavior settings (indentation, line numbers, wrapping, etc.)
     #[serde(default)]
     pub editor: EditorConfig,
@@ -421,10 +576,38 @@ pub struct Config {
     #[serde(default)]
     pub lsp: HashMap<String, LspServerConfig>,
 
+    /// Language servers that may be downloaded when their command isn't
+    /// installed, by language. Fresh only offers a download for languages
+    /// listed here, and asks before fetching anything.
+    #[serde(default)]
+    pub lsp_downloads: HashMap<String, LspDownload>,
+
+    /// Commands run automatically on editor events (open, save, language change)
+    #[serde(default)]
+    pub auto_commands: Vec<AutoCommand>,
+
+    /// Programs that open matching files instead of the text editor
+    /// (e.g., images and PDFs)
+    #[serde(default)]
+    pub open_external: Vec<OpenExternalRule>,
+
     /// Warning notification settings
     #[serde(default)]
     pub warnings: WarningsConfig,
 
+    /// Privacy Mode settings (masking secrets while screen sharing)
+    #[serde(default)]
+    pub privacy: PrivacyConfig,
+
+    /// Session (workspace) restore settings
+    #[serde(default)]
+    pub session: SessionRestoreConfig,
+
+    /// Environment of processes the editor spawns (LSP servers, formatters,
+    /// on-save actions, shell commands, terminals and plugin processes)
+    #[serde(default)]
+    pub process: ProcessConfig,
+
     /// Plugin configurations by plugin name
     /// Plugins are auto-discovered from the plugins directory.
     /// Use this to enable/disable specific plugins.
@@ -435,6 +618,11 @@ pub struct Config {
     /// Package manager settings for plugin/theme installation
     #[serde(default)]
     pub packages: PackagesConfig,
+
+    /// Overrides for this run only, left out when a layer is saved
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub runtime_layer: RuntimeLayer,
 }
 
 fn default_keybinding_map_name() -> KeybindingMapName {
@@ -455,7 +643,7 @@ fn default_theme_name() -> ThemeName {
 ///
 /// These are the final resolved flags after applying master toggle,
 /// global config, and per-language overrides. Used directly by the renderer.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WhitespaceVisibility {
     pub spaces_leading: bool,
     pub spaces_inner: bool,
@@ -561,6 +749,17 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub relative_line_numbers: bool,
 
+    /// Fold arrows in the gutter next to foldable blocks (by indentation or
+    /// LSP folding ranges), clickable to fold and unfold.
+    /// - "always": on every foldable line (▾) and collapsed fold (▸)
+    /// - "collapsed": on collapsed folds only
+    /// - "never": hidden; gutter clicks set line marks instead
+    ///
+    /// Default: "always"
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Display"))]
+    pub fold_indicators: FoldIndicators,
+
     /// Wrap long lines to fit the window width (default for new views)
     #[serde(default = "default_true")]
     #[schemars(extend("x-section" = "Display"))]
@@ -600,6 +799,25 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub show_status_bar: bool,
 
+    /// Whether the status bar shows the latency of the remote connection
+    /// while a remote file is active, e.g. "ssh:host 42ms".
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_remote_latency: bool,
+
+    /// Screen density, trading editor chrome for text rows.
+    /// - "comfortable": all configured bars are shown
+    /// - "compact": the menu bar is hidden and tabs drop their padding
+    /// - "dense": the menu bar and tab bar are hidden
+    ///
+    /// Changed at runtime with the Zoom In / Zoom Out commands.
+    ///
+    /// Default: "comfortable"
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Display"))]
+    pub ui_density: UiDensity,
+
     /// Whether the vertical scrollbar is visible in each split pane.
     /// Can be toggled at runtime via command palette or keybinding.
     /// Default: true
@@ -638,6 +856,19 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub rulers: Vec<usize>,
 
+    /// Include line numbers when exporting a buffer as HTML.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub export_html_line_numbers: bool,
+
+    /// Column at which long lines are wrapped when exporting a buffer as HTML.
+    /// Set to 0 to keep lines unwrapped.
+    /// Default: 0
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Display"))]
+    pub export_html_wrap_width: usize,
+
     // ===== Whitespace =====
     /// Master toggle for whitespace indicator visibility.
     /// When disabled, no whitespace indicators (·, →) are shown regardless
@@ -718,11 +949,48 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Editing"))]
     pub auto_surround: bool,
 
+    /// When toggling the case of the character under the cursor at the end of
+    /// a line, move on to the start of the next line instead of stopping.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub toggle_case_wraps_lines: bool,
+
+    /// How far Ctrl+Left/Right, Ctrl+Backspace/Delete, word selection
+    /// (double-click) and whole-word search go inside identifiers: "word"
+    /// treats `myVariableName` as one word, "subword" stops at camelCase
+    /// humps, underscores and letter/digit changes.
+    /// Default: "word"
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub word_granularity: WordGranularity,
+
+    /// Column at which Reflow Paragraph wraps prose and comment blocks.
+    /// Default: 80
+    #[serde(default = "default_wrap_column")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub wrap_column: usize,
+
     /// Minimum lines to keep visible above/below cursor when scrolling
     #[serde(default = "default_scroll_offset")]
     #[schemars(extend("x-section" = "Editing"))]
     pub scroll_offset: usize,
 
+    /// Recenter the viewport on each search match when jumping between
+    /// matches, instead of scrolling it just far enough into view.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub center_on_match: bool,
+
+    /// Center the target line in the viewport after Go to Line, opening
+    /// `file:line` and other jumps to a line, instead of scrolling it just
+    /// far enough into view.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub center_on_goto: bool,
+
     /// Default line ending format for new files.
     /// Files loaded from disk will use their detected line ending format.
     /// Options: "lf" (Unix/Linux/macOS), "crlf" (Windows), "cr" (Classic Mac)
@@ -743,6 +1011,28 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Editing"))]
     pub ensure_final_newline_on_save: bool,
 
+    /// When opening a symlink, edit its target instead of the link.
+    /// When false, the buffer keeps the link's path; saving still writes
+    /// through the link so it is not replaced by a regular file.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub follow_symlinks: bool,
+
+    /// How many recently opened files to remember across sessions for the
+    /// Recent Files picker.
+    /// Default: 100
+    #[serde(default = "default_recent_files_limit")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub recent_files_limit: usize,
+
+    /// Whether the fuzzy file finder follows symlinked directories while
+    /// walking the working directory. Symlink loops are detected and skipped.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub fuzzy_finder_follow_symlinks: bool,
+
     // ===== Bracket Matching =====
     /// Highlight matching bracket pairs when cursor is on a bracket.
     /// Default: true
@@ -812,6 +1102,14 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Diagnostics"))]
     pub diagnostics_inline_text: bool,
 
+    /// Whether "Fix Next Problem" applies the quick fix directly when the language
+    /// server offers exactly one for the problem.
+    /// When disabled, the code action menu is always shown.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Diagnostics"))]
+    pub fix_next_problem_auto_apply: bool,
+
     // ===== Mouse =====
     /// Whether mouse hover triggers LSP hover requests.
     /// When enabled, hovering over code with the mouse will show documentation.
@@ -834,6 +1132,11 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Mouse"))]
     pub double_click_time_ms: u64,
 
+    /// Mouse wheel, click and capture behavior
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Mouse"))]
+    pub mouse: MouseConfig,
+
     /// Whether to enable persistent auto-save (save to original file on disk).
     /// When enabled, modified buffers are saved to their original file path
     /// at a configurable interval.
@@ -883,6 +1186,14 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Recovery"))]
     pub auto_recovery_save_interval_secs: u32,
 
+    /// Maximum total size of the recovery directory in megabytes.
+    /// When exceeded, recovery data left over from earlier sessions is pruned
+    /// oldest first. Buffers of the current session are never pruned.
+    /// Default: 1024 MB
+    #[serde(default = "default_recovery_max_size_mb")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub recovery_max_size_mb: u32,
+
     /// Poll interval in milliseconds for auto-reverting open buffers.
     /// When auto-revert is enabled, file modification times are checked at this interval.
     /// Lower values detect external changes faster but use more CPU.
@@ -891,6 +1202,25 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Recovery"))]
     pub auto_revert_poll_interval_ms: u64,
 
+    /// Longest interval in milliseconds between auto-revert checks of an open file.
+    /// Files that keep not changing are checked less and less often, backing off
+    /// from `auto_revert_poll_interval_ms` up to this interval. The active buffer
+    /// is always checked at the base interval. Set it to the base interval to
+    /// disable the back-off.
+    /// Default: 16000ms (16 seconds)
+    #[serde(default = "default_auto_revert_max_poll_interval")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub auto_revert_max_poll_interval_ms: u64,
+
+    /// Poll interval in milliseconds for auto-reverting buffers of files on
+    /// remote hosts. Each check is a round trip over SSH, so they are made in
+    /// the background and less often than for local files. Files above
+    /// `large_file_threshold_bytes` aren't checked.
+    /// Default: 5000ms (5 seconds)
+    #[serde(default = "default_remote_auto_revert_poll_interval")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub remote_auto_revert_poll_interval_ms: u64,
+
     // ===== Keyboard =====
     /// Enable keyboard enhancement: disambiguate escape codes using CSI-u sequences.
     /// This allows unambiguous reading of Escape and modified keys.
@@ -936,6 +1266,20 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Performance"))]
     pub snapshot_interval: usize,
 
+    /// Maximum number of undo history entries kept per buffer.
+    /// The oldest entries are discarded first. 0 = unlimited.
+    /// Default: 100000
+    #[serde(default = "default_max_undo_entries")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub max_undo_entries: usize,
+
+    /// Maximum size in megabytes of inserted/deleted text kept in the undo
+    /// history of each buffer. The oldest entries are discarded first. 0 = unlimited.
+    /// Default: 256
+    #[serde(default = "default_max_undo_memory_mb")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub max_undo_memory_mb: usize,
+
     /// Number of bytes to look back/forward from the viewport for syntax highlighting context.
     /// Larger values improve accuracy for multi-line constructs (strings, comments, nested blocks)
     /// but may slow down highlighting for very large files.
@@ -969,6 +1313,22 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Performance"))]
     pub read_concurrency: usize,
 
+    /// Count the lines of large files in the background after opening them.
+    /// Once the scan finishes, the gutter shows exact line numbers instead of
+    /// byte offsets and Go to Line jumps without asking to scan first.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub large_file_line_scan: bool,
+
+    /// Line length in bytes above which a file is treated as minified.
+    /// Such files are soft-wrapped, highlighted only within the viewport and
+    /// skip matching-bracket highlighting. 0 disables the check.
+    /// Default: 10000
+    #[serde(default = "default_long_line_threshold")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub long_line_threshold: usize,
+
     /// Poll interval in milliseconds for refreshing expanded directories in the file explorer.
     /// Directory modification times are checked at this interval to detect new/deleted files.
     /// Lower values detect changes faster but use more CPU.
@@ -1007,6 +1367,10 @@ fn default_read_concurrency() -> usize {
     64
 }
 
+fn default_long_line_threshold() -> usize {
+    10_000
+}
+
 fn default_true() -> bool {
     true
 }
@@ -1035,10 +1399,22 @@ fn default_snapshot_interval() -> usize {
     100
 }
 
+fn default_max_undo_entries() -> usize {
+    100_000
+}
+
+fn default_max_undo_memory_mb() -> usize {
+    256
+}
+
 fn default_estimated_line_length() -> usize {
     80
 }
 
+fn default_wrap_column() -> usize {
+    80
+}
+
 fn default_auto_save_interval() -> u32 {
     30 // 30 seconds between persistent auto-saves
 }
@@ -1047,6 +1423,10 @@ fn default_auto_recovery_save_interval() -> u32 {
     2 // 2 seconds between recovery saves
 }
 
+fn default_recovery_max_size_mb() -> u32 {
+    1024
+}
+
 fn default_highlight_context_bytes() -> usize {
     10_000 // 10KB context for accurate syntax highlighting
 }
@@ -1059,10 +1439,30 @@ fn default_double_click_time() -> u64 {
     500 // 500ms window for detecting double-clicks
 }
 
+fn default_mouse_scroll_lines() -> usize {
+    3
+}
+
+fn default_mouse_scroll_columns() -> usize {
+    3
+}
+
+fn default_recent_files_limit() -> usize {
+    100
+}
+
 fn default_auto_revert_poll_interval() -> u64 {
     2000 // 2 seconds between file mtime checks
 }
 
+fn default_auto_revert_max_poll_interval() -> u64 {
+    16_000 // unchanged files back off to one check every 16 seconds
+}
+
+fn default_remote_auto_revert_poll_interval() -> u64 {
+    5000 // 5 seconds between remote file stats
+}
+
 fn default_file_tree_poll_interval() -> u64 {
     3000 // 3 seconds between directory mtime checks
 }
@@ -1074,35 +1474,53 @@ impl Default for EditorConfig {
             auto_indent: true,
             auto_close: true,
             auto_surround: true,
+            toggle_case_wraps_lines: false,
+            word_granularity: WordGranularity::default(),
+            wrap_column: default_wrap_column(),
             line_numbers: true,
             relative_line_numbers: false,
+            fold_indicators: FoldIndicators::default(),
             scroll_offset: default_scroll_offset(),
+            center_on_match: false,
+            center_on_goto: true,
             syntax_highlighting: true,
             line_wrap: true,
             wrap_indent: true,
             highlight_timeout_ms: default_highlight_timeout(),
             snapshot_interval: default_snapshot_interval(),
+            max_undo_entries: default_max_undo_entries(),
+            max_undo_memory_mb: default_max_undo_memory_mb(),
             large_file_threshold_bytes: default_large_file_threshold(),
             estimated_line_length: default_estimated_line_length(),
             enable_inlay_hints: true,
             enable_semantic_tokens_full: false,
             diagnostics_inline_text: false,
+            fix_next_problem_auto_apply: true,
             auto_save_enabled: false,
             auto_save_interval_secs: default_auto_save_interval(),
             persist_unnamed_buffers: true,
             hot_exit: true,
             recovery_enabled: true,
             auto_recovery_save_interval_secs: default_auto_recovery_save_interval(),
+            recovery_max_size_mb: default_recovery_max_size_mb(),
             highlight_context_bytes: default_highlight_context_bytes(),
             mouse_hover_enabled: true,
             mouse_hover_delay_ms: default_mouse_hover_delay(),
             double_click_time_ms: default_double_click_time(),
+            mouse: MouseConfig::default(),
             auto_revert_poll_interval_ms: default_auto_revert_poll_interval(),
+            auto_revert_max_poll_interval_ms: default_auto_revert_max_poll_interval(),
+            remote_auto_revert_poll_interval_ms: default_remote_auto_revert_poll_interval(),
             read_concurrency: default_read_concurrency(),
+            large_file_line_scan: false,
+            long_line_threshold: default_long_line_threshold(),
             file_tree_poll_interval_ms: default_file_tree_poll_interval(),
             default_line_ending: LineEndingOption::default(),
             trim_trailing_whitespace_on_save: false,
             ensure_final_newline_on_save: false,
+            follow_symlinks: true,
+            recent_files_limit: default_recent_files_limit(),
+            fuzzy_finder_follow_symlinks: false,
             highlight_matching_brackets: true,
             rainbow_brackets: true,
             cursor_style: CursorStyle::default(),
@@ -1117,10 +1535,14 @@ impl Default for EditorConfig {
             show_menu_bar: true,
             show_tab_bar: true,
             show_status_bar: true,
+            show_remote_latency: true,
+            ui_density: UiDensity::default(),
             show_vertical_scrollbar: true,
             show_horizontal_scrollbar: false,
             use_terminal_bg: false,
             rulers: Vec::new(),
+            export_html_line_numbers: false,
+            export_html_wrap_width: 0,
             whitespace_show: true,
             whitespace_spaces_leading: false,
             whitespace_spaces_inner: false,
@@ -1192,6 +1614,60 @@ impl Default for ClipboardConfig {
     }
 }
 
+/// Mouse configuration (`editor.mouse`)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MouseConfig {
+    /// Number of lines scrolled (or moved, see `wheel_moves_cursor`)
+    /// per mouse wheel notch.
+    /// Default: 3
+    #[serde(default = "default_mouse_scroll_lines")]
+    pub scroll_lines: usize,
+
+    /// Whether the mouse wheel moves the cursor instead of scrolling the viewport.
+    /// Default: false
+    #[serde(default = "default_false")]
+    pub wheel_moves_cursor: bool,
+
+    /// Number of columns scrolled per Shift+wheel notch or horizontal wheel
+    /// (trackpad) tick when line wrap is off.
+    /// Default: 3
+    #[serde(default = "default_mouse_scroll_columns")]
+    pub scroll_columns: usize,
+
+    /// Whether the mouse wheel scrolls the split under the mouse pointer
+    /// instead of the focused split.
+    /// Default: false
+    #[serde(default = "default_false")]
+    pub scroll_hovered_split: bool,
+
+    /// Whether clicking in the text area moves the cursor and starts a selection.
+    /// When disabled, clicks on text are ignored while tabs, menus, scrollbars
+    /// and other UI elements still respond to the mouse.
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub click_moves_cursor: bool,
+
+    /// Whether Alt+click temporarily releases mouse capture so the terminal's
+    /// own text selection can be used. Capture resumes on the next key press.
+    /// Alt+drag still makes a block selection either way.
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub alt_bypasses_capture: bool,
+}
+
+impl Default for MouseConfig {
+    fn default() -> Self {
+        Self {
+            scroll_lines: default_mouse_scroll_lines(),
+            wheel_moves_cursor: false,
+            scroll_columns: default_mouse_scroll_columns(),
+            scroll_hovered_split: false,
+            click_moves_cursor: true,
+            alt_bypasses_capture: true,
+        }
+    }
+}
+
 /// Terminal configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TerminalConfig {
@@ -1226,6 +1702,109 @@ impl Default for WarningsConfig {
     }
 }
 
+/// Privacy Mode configuration
+///
+/// While Privacy Mode is on, text matching the built-in secret patterns
+/// (passwords, tokens, API keys, AWS keys) is masked on screen.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PrivacyConfig {
+    /// Additional regular expressions to mask, on top of the built-in ones.
+    /// When a pattern has a capture group, only the group is masked.
+    #[serde(default)]
+    pub extra_patterns: Vec<String>,
+
+    /// Mask every value in `.env` files (default: true)
+    #[serde(default = "default_true")]
+    pub mask_env_files: bool,
+}
+
+impl Default for PrivacyConfig {
+    fn default() -> Self {
+        Self {
+            extra_patterns: Vec::new(),
+            mask_env_files: true,
+        }
+    }
+}
+
+/// Session restore configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SessionRestoreConfig {
+    /// Restore the previous session even when files are passed on the
+    /// command line. The files are opened on top of it and focused; a file
+    /// that was already part of the session is focused rather than opened
+    /// twice. When off, passing files skips the session (default: true)
+    #[serde(default = "default_true")]
+    pub restore_with_cli_files: bool,
+
+    /// Reopen each file at the cursor and scroll position it had when it was
+    /// last closed, even outside a restored session. A line given on the
+    /// command line (`file:line:col`) wins (default: true)
+    #[serde(default = "default_true")]
+    pub remember_file_positions: bool,
+}
+
+impl Default for SessionRestoreConfig {
+    fn default() -> Self {
+        Self {
+            restore_with_cli_files: true,
+            remember_file_positions: true,
+        }
+    }
+}
+
+/// Where spawned processes get their environment from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShellEnvMode {
+    /// The editor's own environment
+    #[default]
+    Inherit,
+    /// The environment of an interactive login shell (`$SHELL -lic env`),
+    /// captured once at startup. Picks up PATH changes from shell profiles
+    /// when the editor is launched from a desktop entry or launcher.
+    LoginShell,
+    /// The editor's own environment plus the `process.env` map
+    Explicit,
+}
+
+impl JsonSchema for ShellEnvMode {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("ShellEnvMode")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Where spawned processes get their environment from",
+            "type": "string",
+            "enum": ["inherit", "login_shell", "explicit"],
+            "default": "inherit"
+        })
+    }
+}
+
+/// Environment of spawned processes
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ProcessConfig {
+    /// Where spawned processes get their environment from.
+    /// - "inherit": the editor's own environment
+    /// - "login_shell": the environment of `$SHELL -lic env`, captured once
+    ///   at startup
+    /// - "explicit": the editor's own environment plus `env`
+    ///
+    /// Only local processes are affected; processes on a remote host keep
+    /// the remote environment.
+    ///
+    /// Default: "inherit"
+    #[serde(default)]
+    pub shell_env: ShellEnvMode,
+
+    /// Variables added to every spawned process when `shell_env` is
+    /// "explicit". Values may reference other variables as `${env:VAR}`.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
 /// Package manager configuration for plugins and themes
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PackagesConfig {
@@ -1342,6 +1921,11 @@ pub struct FormatterConfig {
     /// Timeout in milliseconds (default: 10000)
     #[serde(default = "default_on_save_timeout")]
     pub timeout_ms: u64,
+
+    /// Extra environment variables for the formatter.
+    /// Values may reference other variables as `${env:VAR}`.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
 }
 
 /// Action to run when a file is saved (for linters, etc.)
@@ -1373,12 +1957,94 @@ pub struct OnSaveAction {
     /// Set to false to disable an action without removing it from config
     #[serde(default = "default_true")]
     pub enabled: bool,
+
+    /// Extra environment variables for the command.
+    /// Values may reference other variables as `${env:VAR}`.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
 }
 
 fn default_on_save_timeout() -> u64 {
     10000
 }
 
+/// Editor event that triggers an auto-command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoCommandEvent {
+    /// A file was opened in a buffer
+    OnOpen,
+    /// A buffer was saved to disk
+    OnSave,
+    /// The language of a buffer was changed with "Set Language"
+    OnLanguageChange,
+}
+
+/// Command run automatically when an editor event fires
+///
+/// Example: `{ "event": "on_save", "language": "rust", "command": "format_buffer" }`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/command"))]
+pub struct AutoCommand {
+    /// Event that triggers the command
+    pub event: AutoCommandEvent,
+
+    /// Command to run: an action name (e.g., "format_buffer") or a
+    /// command palette name (e.g., "Format Buffer")
+    pub command: String,
+
+    /// Optional arguments for the action
+    #[serde(default)]
+    pub args: HashMap<String, serde_json::Value>,
+
+    /// Only run for buffers of this language (e.g., "rust")
+    #[serde(default)]
+    pub language: Option<String>,
+
+    /// Only run for files matching this glob (e.g., "*.md", "/src/**/*.rs")
+    #[serde(default)]
+    pub pattern: Option<String>,
+
+    /// Whether this auto-command is enabled (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// External program used to open files matching a glob
+///
+/// Example: `{ "pattern": "*.png", "command": "xdg-open" }`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/pattern"))]
+pub struct OpenExternalRule {
+    /// Files this rule applies to (e.g., "*.pdf", "**/assets/*.png")
+    pub pattern: String,
+
+    /// Program to run; the file path is passed as its last argument
+    pub command: String,
+
+    /// Extra arguments placed before the file path
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Whether this rule is enabled (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// Where to download a language server binary from
+///
+/// Example: `{ "url": "https://example.com/server.gz", "sha256": "9f86d0..." }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/url"))]
+pub struct LspDownload {
+    /// URL of the server binary; a `.gz` file is decompressed after download
+    pub url: String,
+
+    /// SHA-256 of the file at `url`, as hex. Downloads that don't match are
+    /// discarded.
+    pub sha256: String,
+}
+
 /// Language-specific configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[schemars(extend("x-display-field" = "/grammar"))]
@@ -1413,6 +2079,19 @@ pub struct LanguageConfig {
     #[serde(default)]
     pub auto_surround: Option<bool>,
 
+    /// Characters besides letters, digits and `_` that are part of words in
+    /// this language, e.g. "-" for kebab-case identifiers. If not specified,
+    /// CSS and Lisp dialects use "-" and other languages none.
+    #[serde(default)]
+    pub word_characters: Option<String>,
+
+    /// Bracket and quote pairs to auto-close in this language, each a
+    /// two-character string such as "()" or "<>". Only ASCII pairs are used.
+    /// If not specified, (), [], {} and the quotes the language uses
+    /// (none in plain text, no ' in Markdown).
+    #[serde(default)]
+    pub auto_close_pairs: Option<Vec<String>>,
+
     /// Preferred highlighter backend (auto, tree-sitter, or textmate)
     #[serde(default)]
     pub highlighter: HighlighterPreference,
@@ -1446,6 +2125,12 @@ pub struct LanguageConfig {
     #[serde(default)]
     pub format_on_save: bool,
 
+    /// Whether to trim trailing whitespace when saving files of this language.
+    /// If not specified (`null`), falls back to the global
+    /// `editor.trim_trailing_whitespace_on_save` setting.
+    #[serde(default)]
+    pub trim_trailing_whitespace_on_save: Option<bool>,
+
     /// Actions to run when a file of this language is saved (linters, etc.)
     /// Actions are run in order; if any fails (non-zero exit), subsequent actions don't run
     /// Note: Use `formatter` + `format_on_save` for formatting, not on_save
@@ -1485,6 +2170,9 @@ pub struct BufferConfig {
     /// Whether to format on save
     pub format_on_save: bool,
 
+    /// Whether to trim trailing whitespace on save
+    pub trim_trailing_whitespace_on_save: bool,
+
     /// Actions to run when saving
     pub on_save: Vec<OnSaveAction>,
 
@@ -1518,6 +2206,7 @@ impl BufferConfig {
             whitespace,
             formatter: None,
             format_on_save: false,
+            trim_trailing_whitespace_on_save: editor.trim_trailing_whitespace_on_save,
             on_save: Vec::new(),
             highlighter: HighlighterPreference::Auto,
             textmate_grammar: None,
@@ -1562,6 +2251,11 @@ impl BufferConfig {
                 // Format on save: from language config
                 config.format_on_save = lang_config.format_on_save;
 
+                // Trim trailing whitespace on save: language override
+                if let Some(trim) = lang_config.trim_trailing_whitespace_on_save {
+                    config.trim_trailing_whitespace_on_save = trim;
+                }
+
                 // On save actions: from language config
                 config.on_save = lang_config.on_save.clone();
 
@@ -1706,6 +2400,7 @@ impl Default for Config {
             theme: default_theme_name(),
             locale: LocaleName::default(),
             check_for_updates: true,
+            allow_network_fetch: true,
             editor: EditorConfig::default(),
             file_explorer: FileExplorerConfig::default(),
             file_browser: FileBrowserConfig::default(),
@@ -1716,9 +2411,16 @@ impl Default for Config {
             active_keybinding_map: default_keybinding_map_name(),
             languages: Self::default_languages(),
             lsp: Self::default_lsp_config(),
+            lsp_downloads: HashMap::new(),
+            auto_commands: Vec::new(),
+            open_external: Vec::new(),
             warnings: WarningsConfig::default(),
+            privacy: PrivacyConfig::default(),
+            session: SessionRestoreConfig::default(),
+            process: ProcessConfig::default(),
             plugins: HashMap::new(), // Populated when scanning for plugins
             packages: PackagesConfig::default(),
+            runtime_layer: RuntimeLayer::default(),
         }
     }
 }
@@ -1780,6 +2482,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.file.auto_revert").to_string(),
+                        action: "toggle_auto_revert".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::AUTO_REVERT.to_string()),
+                    },
                     MenuItem::Action {
                         label: t!("menu.file.reload_with_encoding").to_string(),
                         action: "reload_with_encoding".to_string(),
@@ -1863,7 +2572,7 @@ impl MenuConfig {
                         label: t!("menu.edit.paste").to_string(),
                         action: "paste".to_string(),
                         args: HashMap::new(),
-                        when: None,
+                        when: Some(context_keys::CAN_PASTE.to_string()),
                         checkbox: None,
                     },
                     MenuItem::Separator { separator: true },
@@ -1970,6 +2679,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: Some(context_keys::LINE_WRAP.to_string()),
                     },
+                    MenuItem::Action {
+                        label: t!("menu.view.whitespace_indicators").to_string(),
+                        action: "toggle_whitespace_indicators".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::WHITESPACE_INDICATORS.to_string()),
+                    },
                     MenuItem::Action {
                         label: t!("menu.view.mouse_support").to_string(),
                         action: "toggle_mouse_capture".to_string(),
@@ -2062,7 +2778,7 @@ impl MenuConfig {
                         label: t!("menu.view.close_split").to_string(),
                         action: "close_split".to_string(),
                         args: HashMap::new(),
-                        when: None,
+                        when: Some(context_keys::HAS_MULTIPLE_SPLITS.to_string()),
                         checkbox: None,
                     },
                     MenuItem::Action {
@@ -2491,6 +3207,27 @@ impl MenuConfig {
     }
 }
 
+/// The pairs auto-closed when a language sets no `auto_close_pairs`. Plain
+/// text has no quote pairs, and Markdown uses ' as an apostrophe.
+pub fn default_auto_close_pairs(language: &str) -> Vec<(char, char)> {
+    let mut pairs = vec![('(', ')'), ('[', ']'), ('{', '}')];
+    match language {
+        "text" => {}
+        "markdown" | "mdx" => pairs.extend([('"', '"'), ('`', '`')]),
+        _ => pairs.extend([('"', '"'), ('\'', '\''), ('`', '`')]),
+    }
+    pairs
+}
+
+/// The `(open, close)` characters of an `auto_close_pairs` entry, or None
+/// unless it is exactly two ASCII characters
+pub fn parse_auto_close_pair(pair: &str) -> Option<(char, char)> {
+    match pair.chars().collect::<Vec<_>>()[..] {
+        [open, close] if open.is_ascii() && close.is_ascii() => Some((open, close)),
+        _ => None,
+    }
+}
+
 impl Config {
     /// The config filename used throughout the application
     pub(crate) const FILENAME: &'static str = "config.json";
@@ -2536,6 +3273,53 @@ impl Config {
         }
     }
 
+    /// Word boundary rules for buffers of a language: the global
+    /// `word_granularity` plus the language's `word_characters`
+    pub fn word_rules(&self, language: &str) -> WordRules {
+        let extra_chars = match self
+            .languages
+            .get(language)
+            .and_then(|lang| lang.word_characters.as_deref())
+        {
+            Some(chars) => chars.chars().collect(),
+            // Kebab-case identifiers are the norm here
+            None => match language {
+                "css" | "scss" | "less" | "lisp" | "commonlisp" | "scheme" | "racket"
+                | "clojure" | "elisp" | "fennel" => vec!['-'],
+                _ => Vec::new(),
+            },
+        };
+        WordRules {
+            subword: self.editor.word_granularity == WordGranularity::Subword,
+            extra_chars,
+        }
+    }
+
+    /// The bracket and quote pairs auto-closed in buffers of a language
+    pub fn auto_close_pairs(&self, language: &str) -> Vec<(char, char)> {
+        match self
+            .languages
+            .get(language)
+            .and_then(|lang| lang.auto_close_pairs.as_ref())
+        {
+            Some(pairs) => pairs
+                .iter()
+                .filter_map(|pair| {
+                    let parsed = parse_auto_close_pair(pair);
+                    if parsed.is_none() {
+                        tracing::warn!(
+                            "Ignoring auto_close_pairs entry {:?} for {}: not two ASCII characters",
+                            pair,
+                            language
+                        );
+                    }
+                    parsed
+                })
+                .collect(),
+            None => default_auto_close_pairs(language),
+        }
+    }
+
     /// Resolve a keymap with inheritance
     /// Returns all bindings from the keymap and its parent chain
     pub fn resolve_keymap(&self, map_name: &str) -> Vec<Keybinding> {
@@ -2596,6 +3380,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2606,6 +3393,7 @@ impl Config {
                     args: vec!["--edition".to_string(), "2021".to_string()],
                     stdin: true,
                     timeout_ms: 10000,
+                    env: HashMap::new(),
                 }),
                 format_on_save: false,
                 on_save: vec![],
@@ -2622,6 +3410,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2632,6 +3423,7 @@ impl Config {
                     args: vec!["--stdin-filepath".to_string(), "$FILE".to_string()],
                     stdin: true,
                     timeout_ms: 10000,
+                    env: HashMap::new(),
                 }),
                 format_on_save: false,
                 on_save: vec![],
@@ -2648,6 +3440,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2658,6 +3453,7 @@ impl Config {
                     args: vec!["--stdin-filepath".to_string(), "$FILE".to_string()],
                     stdin: true,
                     timeout_ms: 10000,
+                    env: HashMap::new(),
                 }),
                 format_on_save: false,
                 on_save: vec![],
@@ -2674,6 +3470,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2688,6 +3487,7 @@ impl Config {
                     ],
                     stdin: true,
                     timeout_ms: 10000,
+                    env: HashMap::new(),
                 }),
                 format_on_save: false,
                 on_save: vec![],
@@ -2704,6 +3504,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2714,6 +3517,7 @@ impl Config {
                     args: vec![],
                     stdin: true,
                     timeout_ms: 10000,
+                    env: HashMap::new(),
                 }),
                 format_on_save: false,
                 on_save: vec![],
@@ -2737,6 +3541,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2747,6 +3554,7 @@ impl Config {
                     args: vec![],
                     stdin: true,
                     timeout_ms: 10000,
+                    env: HashMap::new(),
                 }),
                 format_on_save: false,
                 on_save: vec![],
@@ -2763,6 +3571,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2799,6 +3610,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2824,6 +3638,9 @@ impl Config {
                 auto_indent: false,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2845,6 +3662,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2866,6 +3686,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2876,6 +3699,7 @@ impl Config {
                     args: vec!["--stdin-filepath".to_string(), "$FILE".to_string()],
                     stdin: true,
                     timeout_ms: 10000,
+                    env: HashMap::new(),
                 }),
                 format_on_save: false,
                 on_save: vec![],
@@ -2892,6 +3716,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2913,6 +3740,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2923,6 +3753,7 @@ impl Config {
                     args: vec!["--stdin-filepath".to_string(), "$FILE".to_string()],
                     stdin: true,
                     timeout_ms: 10000,
+                    env: HashMap::new(),
                 }),
                 format_on_save: false,
                 on_save: vec![],
@@ -2939,6 +3770,9 @@ impl Config {
                 auto_indent: false,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -2961,6 +3795,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: false,
@@ -2971,6 +3808,7 @@ impl Config {
                     args: vec![],
                     stdin: true,
                     timeout_ms: 10000,
+                    env: HashMap::new(),
                 }),
                 format_on_save: false,
                 on_save: vec![],
@@ -2987,6 +3825,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: false,
@@ -3008,6 +3849,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -3029,6 +3873,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -3057,6 +3904,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -3078,6 +3928,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -3100,6 +3953,9 @@ impl Config {
                 auto_indent: false,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -3126,6 +3982,9 @@ impl Config {
                 auto_indent: false,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -3152,6 +4011,9 @@ impl Config {
                 auto_indent: false,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -3173,6 +4035,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -3194,6 +4059,9 @@ impl Config {
                 auto_indent: false,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -3215,6 +4083,9 @@ impl Config {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
@@ -3941,6 +4812,9 @@ mod tests {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: false, // Go hides tab indicators
@@ -3951,6 +4825,7 @@ mod tests {
                     args: vec![],
                     stdin: true,
                     timeout_ms: 10000,
+                    env: HashMap::new(),
                 }),
                 format_on_save: true,
                 on_save: vec![],
@@ -3967,6 +4842,22 @@ mod tests {
         assert_eq!(buffer_config.formatter.as_ref().unwrap().command, "gofmt");
     }
 
+    #[test]
+    fn test_buffer_config_trim_trailing_whitespace_language_override() {
+        let mut config = Config::default();
+        config.editor.trim_trailing_whitespace_on_save = true;
+        config.languages.insert(
+            "markdown".to_string(),
+            LanguageConfig {
+                trim_trailing_whitespace_on_save: Some(false),
+                ..Default::default()
+            },
+        );
+
+        assert!(BufferConfig::resolve(&config, Some("rust")).trim_trailing_whitespace_on_save);
+        assert!(!BufferConfig::resolve(&config, Some("markdown")).trim_trailing_whitespace_on_save);
+    }
+
     #[test]
     fn test_buffer_config_unknown_language_uses_global() {
         let config = Config::default();
@@ -3998,4 +4889,32 @@ mod tests {
         let tabs_config = BufferConfig::resolve(&config_with_tabs, Some("makefile"));
         assert_eq!(tabs_config.indent_string(), "\t");
     }
+
+    #[test]
+    fn test_word_rules_for_language() {
+        let mut config = Config::default();
+        assert_eq!(config.word_rules("rust"), WordRules::default());
+        assert_eq!(config.word_rules("css").extra_chars, vec!['-']);
+
+        config.editor.word_granularity = WordGranularity::Subword;
+        config.languages.insert(
+            "javascript".to_string(),
+            LanguageConfig {
+                word_characters: Some("$".to_string()),
+                ..Default::default()
+            },
+        );
+        config.languages.insert(
+            "css".to_string(),
+            LanguageConfig {
+                word_characters: Some(String::new()),
+                ..Default::default()
+            },
+        );
+        let js = config.word_rules("javascript");
+        assert!(js.subword);
+        assert_eq!(js.extra_chars, vec!['$']);
+        // An explicit empty set overrides the built-in default
+        assert!(config.word_rules("css").extra_chars.is_empty());
+    }
 }
@@ -4,7 +4,7 @@
 //! that require runtime dependencies (dirs, tracing).
 //! These are separated from config.rs to allow schema-only builds.
 
-use crate::config::{Config, ConfigError};
+use crate::config::{Config, ConfigError, ThemeName};
 use crate::partial_config::{Merge, PartialConfig, SessionConfig};
 use serde_json::Value;
 use std::path::{Path, PathBuf};
@@ -100,6 +100,89 @@ fn set_json_pointer(root: &mut Value, pointer: &str, value: Value) {
     }
 }
 
+/// Prefix of environment variables that override config fields
+const ENV_OVERRIDE_PREFIX: &str = "FRESH_";
+
+/// `FRESH_*` variables with other meanings, never treated as config overrides
+const NON_CONFIG_ENV_VARS: &[&str] = &[
+    "FRESH_COLOR_MODE",
+    "FRESH_LOG_SPANS",
+    "FRESH_SSH_PASSPHRASE",
+    "FRESH_SSH_ASKPASS_TERMINAL",
+];
+
+/// Config values that apply to this run only (`FRESH_*` environment variables
+/// and command-line flags), on top of all layers.
+///
+/// Saving a layer leaves these values out, so a one-off override never ends
+/// up in a config file unless the user changes that setting themselves.
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeLayer {
+    /// JSON pointer -> (runtime value, value from the layers below)
+    values: std::collections::BTreeMap<String, (Value, Value)>,
+}
+
+impl RuntimeLayer {
+    /// Record that `pointer` was set to `value` over the layered value `below`.
+    /// A second override of the same field keeps the original layered value.
+    fn record(&mut self, pointer: String, value: Value, below: Value) {
+        self.values
+            .entry(pointer)
+            .and_modify(|entry| entry.0 = value.clone())
+            .or_insert((value, below));
+    }
+
+    /// `config` with every field still holding its runtime value reset to
+    /// the value from the layers below
+    fn strip(&self, config: &Config) -> Result<Config, ConfigError> {
+        if self.values.is_empty() {
+            return Ok(config.clone());
+        }
+        let mut root =
+            serde_json::to_value(config).map_err(|e| ConfigError::SerializeError(e.to_string()))?;
+        for (pointer, (value, below)) in &self.values {
+            if root.pointer(pointer) == Some(value) {
+                set_json_pointer(&mut root, pointer, below.clone());
+            }
+        }
+        serde_json::from_value(root).map_err(|e| ConfigError::SerializeError(e.to_string()))
+    }
+}
+
+/// JSON pointer of the scalar config field an environment variable names.
+/// `field` is the lowercased name without the prefix; levels are separated by
+/// `__` or `.`, or by a single `_` after the name of a nested section.
+fn env_field_pointer(root: &Value, field: &str) -> Option<String> {
+    fn resolve(value: &Value, segments: &[&str], pointer: String) -> Option<String> {
+        let Some((segment, rest)) = segments.split_first() else {
+            return (!value.is_object() && !value.is_array()).then_some(pointer);
+        };
+        let map = value.as_object()?;
+        if let Some(child) = map.get(*segment) {
+            if let Some(found) = resolve(child, rest, format!("{}/{}", pointer, segment)) {
+                return Some(found);
+            }
+        }
+        // A section name followed by `_` and the rest of the segment
+        map.iter().find_map(|(key, child)| {
+            let remainder = segment.strip_prefix(key.as_str())?.strip_prefix('_')?;
+            if !child.is_object() || remainder.is_empty() {
+                return None;
+            }
+            let mut nested = vec![remainder];
+            nested.extend_from_slice(rest);
+            resolve(child, &nested, format!("{}/{}", pointer, key))
+        })
+    }
+
+    let normalized = field.replace("__", ".");
+    let segments: Vec<&str> = normalized.split('.').collect();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        return None;
+    }
+    resolve(root, &segments, String::new())
+}
+
 /// Remove a value at a JSON pointer path.
 fn remove_json_pointer(root: &mut Value, pointer: &str) {
     if pointer.is_empty() || pointer == "/" {
@@ -247,17 +330,30 @@ impl ConfigLayer {
 pub struct ConfigResolver {
     dir_context: DirectoryContext,
     working_dir: PathBuf,
+    /// When false, command-like settings in the project and session layers
+    /// are ignored (see `services::workspace_trust`)
+    workspace_trusted: bool,
 }
 
 impl ConfigResolver {
     /// Create a new ConfigResolver for a working directory.
     pub fn new(dir_context: DirectoryContext, working_dir: PathBuf) -> Self {
+        let workspace_trusted =
+            crate::services::workspace_trust::workspace_trust(&dir_context, &working_dir)
+                .is_trusted();
         Self {
             dir_context,
             working_dir,
+            workspace_trusted,
         }
     }
 
+    /// Override whether the workspace is trusted.
+    pub fn with_workspace_trusted(mut self, trusted: bool) -> Self {
+        self.workspace_trusted = trusted;
+        self
+    }
+
     /// Load all layers and merge them into a resolved Config.
     ///
     /// Layers are merged from highest to lowest precedence:
@@ -274,6 +370,13 @@ impl ConfigResolver {
             merged.merge_from(&project_partial);
         }
 
+        // Both layers live in the workspace, so an untrusted one can't make
+        // the editor run commands
+        if !self.workspace_trusted {
+            tracing::info!("Workspace not trusted, ignoring its command settings");
+            merged.strip_command_settings();
+        }
+
         // Merge in User Platform layer (e.g., config_linux.json)
         if let Some(platform_partial) = self.load_user_platform_layer()? {
             tracing::debug!("Loaded user platform config layer");
@@ -394,6 +497,9 @@ impl ConfigResolver {
             ));
         }
 
+        // Values set for this run only never reach the file
+        let config = &config.runtime_layer.strip(config)?;
+
         // Calculate parent config (merge all layers below target)
         let parent_partial = self.resolve_up_to_layer(layer)?;
 
@@ -715,6 +821,213 @@ impl ConfigResolver {
 
         Ok(sources)
     }
+
+    /// The config files that exist on disk, lowest precedence first:
+    /// user, user platform, project, session.
+    pub fn existing_layer_paths(&self) -> Vec<PathBuf> {
+        std::iter::once(self.user_config_path())
+            .chain(self.user_platform_config_path())
+            .chain([self.project_config_path(), self.session_config_path()])
+            .filter(|path| path.exists())
+            .collect()
+    }
+}
+
+// ============================================================================
+// Config Checking
+// ============================================================================
+
+/// The generated config schema, used to spot keys no setting reads
+const CONFIG_SCHEMA: &str = include_str!("../plugins/config-schema.json");
+
+/// A problem found in a config file by [`check_config_file`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    pub path: PathBuf,
+    /// 1-based line and column, when the problem has a position
+    pub position: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.position {
+            Some((line, column)) => write!(
+                f,
+                "{}:{}:{}: {}",
+                self.path.display(),
+                line,
+                column,
+                self.message
+            ),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+/// Check a single config file on its own, without merging it with the
+/// other layers: JSON syntax, value types, keys no setting reads, and
+/// keybindings that would be dropped when loaded.
+///
+/// Loading is lenient (unknown keys are ignored and bad bindings skipped),
+/// so this is the only place these problems surface.
+pub fn check_config_file(path: &Path) -> Vec<ConfigProblem> {
+    let problem = |position: Option<(usize, usize)>, message: String| ConfigProblem {
+        path: path.to_path_buf(),
+        position,
+        message,
+    };
+    let json_problem = |e: serde_json::Error| {
+        let message = e.to_string();
+        let suffix = format!(" at line {} column {}", e.line(), e.column());
+        let message = message
+            .strip_suffix(&suffix)
+            .unwrap_or(&message)
+            .to_string();
+        problem((e.line() > 0).then_some((e.line(), e.column())), message)
+    };
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return vec![problem(None, e.to_string())],
+    };
+    let value: Value = match serde_json::from_str(&content) {
+        Ok(value) => value,
+        Err(e) => return vec![json_problem(e)],
+    };
+    let migrated = match migrate_config(value) {
+        Ok(migrated) => migrated,
+        Err(e) => return vec![problem(None, e.to_string())],
+    };
+
+    let mut problems = Vec::new();
+
+    // Deserialize the text itself rather than the migrated value so type
+    // errors keep their line and column
+    let partial = match serde_json::from_str::<PartialConfig>(&content) {
+        Ok(partial) => Some(partial),
+        Err(e) => {
+            problems.push(json_problem(e));
+            None
+        }
+    };
+
+    let schema: Value = serde_json::from_str(CONFIG_SCHEMA).unwrap_or_default();
+    let mut unknown = Vec::new();
+    collect_unknown_keys(&migrated, &schema, &schema, "", &mut unknown);
+    problems.extend(
+        unknown
+            .into_iter()
+            .map(|key| problem(None, format!("unknown key \"{}\"", key))),
+    );
+
+    if let Some(partial) = partial {
+        let mut maps: Vec<_> = partial.keybinding_maps.iter().flatten().collect();
+        maps.sort_by_key(|(name, _)| *name);
+        let bindings = partial
+            .keybindings
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, binding)| (format!("keybindings[{}]", i), binding))
+            .chain(maps.into_iter().flat_map(|(name, map)| {
+                map.bindings.iter().enumerate().map(move |(i, binding)| {
+                    (format!("keybinding_maps.{}.bindings[{}]", name, i), binding)
+                })
+            }));
+        for (key, binding) in bindings {
+            for message in crate::input::keybindings::KeybindingResolver::binding_problems(binding)
+            {
+                problems.push(problem(None, format!("{}: {}", key, message)));
+            }
+        }
+
+        let mut languages: Vec<_> = partial.languages.iter().flatten().collect();
+        languages.sort_by_key(|(name, _)| *name);
+        for (name, language) in languages {
+            let pairs = language.auto_close_pairs.iter().flatten().enumerate();
+            for (i, pair) in pairs {
+                if crate::config::parse_auto_close_pair(pair).is_none() {
+                    problems.push(problem(
+                        None,
+                        format!(
+                            "languages.{}.auto_close_pairs[{}]: \"{}\" is not two ASCII characters",
+                            name, i, pair
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    problems
+}
+
+/// Follow `$ref`s and skip the `null` arm of optional values
+fn resolve_schema<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    if let Some(name) = schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.strip_prefix("#/$defs/"))
+    {
+        if let Some(def) = root.get("$defs").and_then(|defs| defs.get(name)) {
+            return resolve_schema(def, root);
+        }
+    }
+    let non_null = schema
+        .get("anyOf")
+        .and_then(Value::as_array)
+        .and_then(|arms| {
+            arms.iter()
+                .find(|arm| arm.get("type").and_then(Value::as_str) != Some("null"))
+        });
+    match non_null {
+        Some(arm) => resolve_schema(arm, root),
+        None => schema,
+    }
+}
+
+/// Collect the dotted paths of object keys that `schema` doesn't describe
+fn collect_unknown_keys(
+    value: &Value,
+    schema: &Value,
+    root: &Value,
+    prefix: &str,
+    unknown: &mut Vec<String>,
+) {
+    let schema = resolve_schema(schema, root);
+    match value {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let additional = schema.get("additionalProperties");
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                if let Some(child_schema) = properties.and_then(|p| p.get(key)) {
+                    collect_unknown_keys(child, child_schema, root, &path, unknown);
+                } else if let Some(child_schema) = additional.filter(|a| a.is_object()) {
+                    collect_unknown_keys(child, child_schema, root, &path, unknown);
+                } else if properties.is_some()
+                    && additional != Some(&Value::Bool(true))
+                    && !(prefix.is_empty() && key == "$schema")
+                {
+                    unknown.push(path);
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    let path = format!("{}[{}]", prefix, i);
+                    collect_unknown_keys(item, item_schema, root, &path, unknown);
+                }
+            }
+        }
+        _ => {}
+    }
 }
 
 /// Recursively collect all non-null leaf paths in a JSON value.
@@ -811,10 +1124,12 @@ impl Config {
     /// Load configuration using the 4-level layer system.
     ///
     /// Merges layers in precedence order: Session > Project > User > System
-    /// Falls back to defaults for any unspecified values.
+    /// Falls back to defaults for any unspecified values. `FRESH_*`
+    /// environment variables override the result in the runtime layer
+    /// (see [`Self::apply_env_overrides`]).
     pub fn load_with_layers(dir_context: &DirectoryContext, working_dir: &Path) -> Self {
         let resolver = ConfigResolver::new(dir_context.clone(), working_dir.to_path_buf());
-        match resolver.resolve() {
+        let mut config = match resolver.resolve() {
             Ok(config) => {
                 tracing::info!("Loaded layered config for {}", working_dir.display());
                 config
@@ -823,7 +1138,110 @@ impl Config {
                 tracing::warn!("Failed to load layered config: {}, using defaults", e);
                 Self::default()
             }
+        };
+        config.apply_env_overrides(std::env::vars());
+        config
+    }
+
+    /// Load the configuration file given with `--config`, which takes the
+    /// place of the layers. `FRESH_*` environment variables override it as
+    /// they do the layered config.
+    pub fn load_from_file_with_env(path: &Path) -> Result<Self, ConfigError> {
+        Self::load_from_file_with_vars(path, std::env::vars())
+    }
+
+    fn load_from_file_with_vars(
+        path: &Path,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        let mut config = Self::load_from_file(path)?;
+        config.apply_env_overrides(vars);
+        Ok(config)
+    }
+
+    /// Override config fields from `FRESH_*` environment variables.
+    ///
+    /// The rest of the name is the field's path in lowercase, e.g.
+    /// `FRESH_THEME` or `FRESH_EDITOR_LINE_WRAP`; `__` or `.` may separate
+    /// the levels explicitly (`FRESH_EDITOR__LINE_WRAP`). Values are parsed as
+    /// JSON scalars, falling back to a plain string. Variables that name no
+    /// field, or whose value doesn't fit it, are logged and skipped. The
+    /// overrides go into the [`RuntimeLayer`], so saving leaves them out.
+    pub fn apply_env_overrides(&mut self, vars: impl IntoIterator<Item = (String, String)>) {
+        let overrides: Vec<(String, String)> = vars
+            .into_iter()
+            .filter(|(name, _)| {
+                name.starts_with(ENV_OVERRIDE_PREFIX)
+                    && !NON_CONFIG_ENV_VARS.contains(&name.as_str())
+            })
+            .collect();
+        if overrides.is_empty() {
+            return;
         }
+        let mut root = match serde_json::to_value(&*self) {
+            Ok(root) => root,
+            Err(e) => {
+                tracing::warn!("Failed to apply environment overrides: {}", e);
+                return;
+            }
+        };
+        let mut runtime_layer = self.runtime_layer.clone();
+
+        for (name, raw) in overrides {
+            let field = name[ENV_OVERRIDE_PREFIX.len()..].to_lowercase();
+            let Some(pointer) = env_field_pointer(&root, &field) else {
+                tracing::warn!("Ignoring {}: no config field named '{}'", name, field);
+                continue;
+            };
+            // Try the JSON reading first, then the text itself: `dark` isn't
+            // JSON, and a string field may hold something like `42`
+            let mut values = vec![Value::String(raw.clone())];
+            if let Ok(value) = serde_json::from_str::<Value>(&raw) {
+                if !value.is_object() && !value.is_array() {
+                    values.insert(0, value);
+                }
+            }
+            let mut error = None;
+            for value in values {
+                let mut candidate = root.clone();
+                set_json_pointer(&mut candidate, &pointer, value.clone());
+                match serde_json::from_value::<Config>(candidate.clone()) {
+                    Ok(_) => {
+                        tracing::info!("Config {} overridden by {}", pointer, name);
+                        let below = root.pointer(&pointer).cloned().unwrap_or(Value::Null);
+                        runtime_layer.record(pointer.clone(), value, below);
+                        root = candidate;
+                        error = None;
+                        break;
+                    }
+                    Err(e) => error = error.or(Some(e)),
+                }
+            }
+            if let Some(e) = error {
+                tracing::warn!("Ignoring {}: {}", name, e);
+            }
+        }
+
+        match serde_json::from_value(root) {
+            Ok(config) => {
+                *self = config;
+                self.runtime_layer = runtime_layer;
+            }
+            Err(e) => tracing::warn!("Failed to apply environment overrides: {}", e),
+        }
+    }
+
+    /// Use `theme` for this run only (`--theme`), through the
+    /// [`RuntimeLayer`] so saving keeps the theme from the config files.
+    pub fn override_theme(&mut self, theme: ThemeName) {
+        if let (Ok(value), Ok(below)) = (
+            serde_json::to_value(&theme),
+            serde_json::to_value(&self.theme),
+        ) {
+            self.runtime_layer
+                .record("/theme".to_string(), value, below);
+        }
+        self.theme = theme;
     }
 
     /// Read the raw user config file content as JSON.
@@ -982,6 +1400,26 @@ impl DirectoryContext {
         self.data_dir.join("workspaces")
     }
 
+    /// Get the directory of remembered per-file cursor and scroll positions
+    pub fn file_states_dir(&self) -> std::path::PathBuf {
+        self.data_dir.join("file_states")
+    }
+
+    /// Get the path of the recently opened files list
+    pub fn recent_files_path(&self) -> std::path::PathBuf {
+        self.data_dir.join("recent_files.json")
+    }
+
+    /// Get the path of the plugin commands remembered from the last session
+    pub fn plugin_commands_path(&self) -> std::path::PathBuf {
+        self.data_dir.join("plugin_commands.json")
+    }
+
+    /// Get the path of the remembered workspace trust decisions
+    pub fn workspace_trust_path(&self) -> std::path::PathBuf {
+        self.data_dir.join("workspace_trust.json")
+    }
+
     /// Get the history file path for a specific prompt type
     /// This is the generic method used by prompt_histories HashMap.
     /// history_name can be: "search", "replace", "goto_line", "plugin:custom_name", etc.
@@ -1017,6 +1455,19 @@ impl DirectoryContext {
         self.terminals_dir().join(encoded)
     }
 
+    /// Get the tutorial progress file path
+    pub fn tutorial_progress_path(&self) -> std::path::PathBuf {
+        self.data_dir.join("tutorial.json")
+    }
+
+    /// Get the per-file override store for a specific working directory
+    pub fn file_overrides_path(&self, working_dir: &std::path::Path) -> std::path::PathBuf {
+        let encoded = crate::workspace::encode_path_for_filename(working_dir);
+        self.data_dir
+            .join("file_overrides")
+            .join(format!("{}.json", encoded))
+    }
+
     /// Get the config file path
     pub fn config_path(&self) -> std::path::PathBuf {
         self.config_dir.join(Config::FILENAME)
@@ -1027,6 +1478,11 @@ impl DirectoryContext {
         self.config_dir.join("themes")
     }
 
+    /// Get the path of the macros saved across sessions
+    pub fn saved_macros_path(&self) -> std::path::PathBuf {
+        self.config_dir.join("macros.json")
+    }
+
     /// Get the grammars directory path
     pub fn grammars_dir(&self) -> std::path::PathBuf {
         self.config_dir.join("grammars")
@@ -1142,6 +1598,29 @@ mod tests {
         drop(temp);
     }
 
+    #[test]
+    fn resolver_ignores_command_settings_of_untrusted_workspace() {
+        let (temp, resolver) = create_test_resolver();
+
+        let project_config_path = resolver.project_config_path();
+        std::fs::create_dir_all(project_config_path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &project_config_path,
+            r#"{
+                "editor": {"tab_size": 7},
+                "lsp": {"rust": {"command": "./evil-server"}},
+                "languages": {"rust": {"on_save": [{"command": "./evil-hook"}]}}
+            }"#,
+        )
+        .unwrap();
+
+        let config = resolver.with_workspace_trusted(false).resolve().unwrap();
+        assert_eq!(config.editor.tab_size, 7); // Harmless settings still apply
+        assert_ne!(config.lsp["rust"].command, "./evil-server");
+        assert!(config.languages["rust"].on_save.is_empty());
+        drop(temp);
+    }
+
     #[test]
     fn layer_precedence_ordering() {
         assert!(ConfigLayer::Session.precedence() > ConfigLayer::Project.precedence());
@@ -2209,4 +2688,161 @@ mod tests {
             saved_content
         );
     }
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn env_overrides_set_nested_fields() {
+        let mut config = Config::default();
+        config.apply_env_overrides(env(&[
+            ("FRESH_THEME", "nord"),
+            ("FRESH_EDITOR_LINE_WRAP", "false"),
+            ("FRESH_EDITOR__TAB_SIZE", "3"),
+            ("PATH", "/usr/bin"),
+        ]));
+        assert_eq!(config.theme.0, "nord");
+        assert!(!config.editor.line_wrap);
+        assert_eq!(config.editor.tab_size, 3);
+    }
+
+    #[test]
+    fn env_overrides_skip_unknown_and_mistyped_variables() {
+        let mut config = Config::default();
+        config.apply_env_overrides(env(&[
+            ("FRESH_NO_SUCH_SETTING", "1"),
+            ("FRESH_EDITOR_TAB_SIZE", "wide"),
+            ("FRESH_EDITOR", "{}"),
+            ("FRESH_COLOR_MODE", "16"),
+        ]));
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::to_value(Config::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn env_overrides_apply_to_explicit_config_file() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("custom.json");
+        std::fs::write(&path, r#"{"theme": "light", "editor": {"tab_size": 2}}"#).unwrap();
+
+        let config = Config::load_from_file_with_vars(
+            &path,
+            env(&[
+                ("FRESH_EDITOR_TAB_SIZE", "3"),
+                ("FRESH_EDITOR_LINE_WRAP", "false"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.theme.0, "light");
+        assert_eq!(config.editor.tab_size, 3);
+        assert!(!config.editor.line_wrap);
+    }
+
+    #[test]
+    fn env_overrides_are_not_saved() {
+        let (_temp, resolver) = create_test_resolver();
+        let user_path = resolver.user_config_path();
+        std::fs::create_dir_all(user_path.parent().unwrap()).unwrap();
+        std::fs::write(&user_path, r#"{"editor": {"tab_size": 2}}"#).unwrap();
+
+        let mut config = resolver.resolve().unwrap();
+        config.apply_env_overrides(env(&[
+            ("FRESH_THEME", "nord"),
+            ("FRESH_EDITOR_TAB_SIZE", "3"),
+        ]));
+        assert_eq!(config.theme.0, "nord");
+        config.editor.line_numbers = false;
+        resolver.save_to_layer(&config, ConfigLayer::User).unwrap();
+
+        let saved: Value =
+            serde_json::from_str(&std::fs::read_to_string(&user_path).unwrap()).unwrap();
+        assert_eq!(saved.pointer("/theme"), None);
+        assert_eq!(saved.pointer("/editor/tab_size"), Some(&Value::from(2)));
+        assert_eq!(
+            saved.pointer("/editor/line_numbers"),
+            Some(&Value::from(false))
+        );
+
+        // An overridden setting the user changes afterwards is saved
+        config.editor.tab_size = 6;
+        resolver.save_to_layer(&config, ConfigLayer::User).unwrap();
+        assert_eq!(resolver.resolve().unwrap().editor.tab_size, 6);
+    }
+
+    #[test]
+    fn theme_override_is_not_saved() {
+        let (_temp, resolver) = create_test_resolver();
+        let mut config = resolver.resolve().unwrap();
+        config.override_theme(ThemeName::from("nord"));
+        config.editor.tab_size = 2;
+        resolver.save_to_layer(&config, ConfigLayer::User).unwrap();
+
+        let resolved = resolver.resolve().unwrap();
+        assert_eq!(resolved.theme, Config::default().theme);
+        assert_eq!(resolved.editor.tab_size, 2);
+    }
+
+    #[test]
+    fn check_config_reports_parse_error_position() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.json");
+        std::fs::write(&path, "{\n  \"theme\": \"dark\",\n  \"editor\": {\n}").unwrap();
+
+        let problems = check_config_file(&path);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].position, Some((4, 1)));
+        assert!(problems[0]
+            .to_string()
+            .starts_with(&format!("{}:4:1: ", path.display())));
+    }
+
+    #[test]
+    fn check_config_reports_unknown_keys_and_bad_keybindings() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.json");
+        let config = serde_json::json!({
+            "$schema": "https://example.com/schema.json",
+            "editor": { "tab_size": 2, "tab_sise": 4 },
+            "languages": {
+                "rust": { "auto_indent": true, "indent": 4 },
+                "html": { "auto_close_pairs": ["<>", "(", "«»"] }
+            },
+            "keybindings": [
+                { "key": "s", "modifiers": ["ctrl"], "action": "save" },
+                { "key": "s", "modifiers": ["hyper"], "action": "save_everything" }
+            ]
+        });
+        std::fs::write(&path, config.to_string()).unwrap();
+
+        let messages: Vec<String> = check_config_file(&path)
+            .into_iter()
+            .map(|problem| problem.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "unknown key \"editor.tab_sise\"",
+                "unknown key \"languages.rust.indent\"",
+                "keybindings[1]: unknown action \"save_everything\"",
+                "keybindings[1]: unknown modifier \"hyper\"",
+                "languages.html.auto_close_pairs[1]: \"(\" is not two ASCII characters",
+                "languages.html.auto_close_pairs[2]: \"«»\" is not two ASCII characters",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_config_accepts_default_config() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.json");
+        let config = serde_json::to_string_pretty(&Config::default()).unwrap();
+        std::fs::write(&path, config).unwrap();
+
+        assert_eq!(check_config_file(&path), vec![]);
+    }
 }
@@ -54,7 +54,7 @@ pub fn run_gui(
 
     #[allow(unused_mut)]
     let mut loaded_config = if let Some(path) = config_path {
-        config::Config::load_from_file(path)
+        config::Config::load_from_file_with_env(path)
             .with_context(|| format!("Failed to load config from {}", path.display()))?
     } else {
         config::Config::load_with_layers(&dir_context, &working_dir)
@@ -123,6 +123,8 @@ pub fn run_gui(
             tracing::warn!("Failed to start recovery session: {}", e);
         }
 
+        editor.prompt_workspace_trust_if_unknown();
+
         Ok(EditorApp {
             editor,
             workspace_enabled,
@@ -2,14 +2,15 @@
 
 use crate::input::keybindings::Action;
 use crate::input::line_move::{move_lines, LineMoveDirection};
+use crate::input::number_increment::increment_numbers;
 use crate::model::buffer::{Buffer, LineEnding};
-use crate::model::cursor::{Cursors, Position2D, SelectionMode};
+use crate::model::cursor::{Cursor, Cursors, Position2D, SelectionMode};
 use crate::model::event::{CursorId, Event};
 use crate::primitives::display_width::{byte_offset_at_visual_column, str_width};
 use crate::primitives::highlighter::HighlightCategory;
 use crate::primitives::word_navigation::{
-    find_word_end, find_word_end_right, find_word_start, find_word_start_left,
-    find_word_start_right,
+    find_word_end, find_word_end_right, find_word_range, find_word_start, find_word_start_left,
+    find_word_start_right, WordRules,
 };
 use crate::state::EditorState;
 use std::ops::Range;
@@ -357,6 +358,12 @@ fn convert_block_selection_to_cursors(
             line_content.len()
         };
 
+        // Rows that end before the block starts get no cursor. The cursor's
+        // own row always reaches the block, so at least one row remains.
+        if line_len < min_col {
+            continue;
+        }
+
         // Clamp columns to actual line length
         let actual_min_col = min_col.min(line_len);
         let actual_max_col = max_col.min(line_len);
@@ -400,28 +407,145 @@ fn convert_block_selection_to_cursors(
     events
 }
 
-/// Get the matching close character for auto-pairing.
-pub fn get_auto_close_char(ch: char, auto_close: bool, language: &str) -> Option<char> {
-    if !auto_close {
-        return None;
+/// Split every multi-line selection into one cursor per line, placed at the
+/// end of the line (or at the selection end on its last line).
+/// A selection ending at the start of a line doesn't cover that line.
+fn split_selection_into_lines_events(
+    state: &mut EditorState,
+    cursors: &Cursors,
+    estimated_line_length: usize,
+) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut next_cursor_id = cursors.iter().map(|(id, _)| id.0).max().unwrap_or(0) + 1;
+
+    for (cursor_id, cursor) in cursors.iter() {
+        let Some(range) = cursor.selection_range() else {
+            continue;
+        };
+
+        let mut positions = Vec::new();
+        let mut iter = state
+            .buffer
+            .line_iterator(range.start, estimated_line_length);
+        while let Some((line_start, content)) = iter.next_line() {
+            if line_start >= range.end && !positions.is_empty() {
+                break;
+            }
+            let line_end = line_start + content_len_without_line_ending(&content);
+            positions.push(line_end.min(range.end));
+        }
+
+        // The existing cursor takes the first line, new cursors the rest
+        let mut positions = positions.into_iter();
+        let Some(first) = positions.next() else {
+            continue;
+        };
+        add_move_cursor_event(
+            &mut events,
+            cursor_id,
+            cursor.position,
+            first,
+            cursor.anchor,
+            None,
+            cursor.sticky_column,
+        );
+        for position in positions {
+            events.push(Event::AddCursor {
+                cursor_id: CursorId(next_cursor_id),
+                position,
+                anchor: None,
+            });
+            next_cursor_id += 1;
+        }
     }
-    // Disable auto-closing quotes in plain text files
-    if language == "text" && matches!(ch, '"' | '\'' | '`') {
-        return None;
+
+    events
+}
+
+/// Replace each run of cursors on the same or adjacent lines with a single
+/// selection spanning all of them. Runs separated by an untouched line stay
+/// separate. The primary cursor (or the oldest cursor of a run) is kept.
+fn merge_cursors_into_selection_events(state: &mut EditorState, cursors: &Cursors) -> Vec<Event> {
+    let mut spans: Vec<(CursorId, &Cursor, Range<usize>)> = cursors
+        .iter()
+        .map(|(id, cursor)| {
+            let range = cursor
+                .selection_range()
+                .unwrap_or(cursor.position..cursor.position);
+            (id, cursor, range)
+        })
+        .collect();
+    spans.sort_by_key(|(_, _, range)| (range.start, range.end));
+
+    // Group into runs of cursors whose lines touch
+    let mut runs: Vec<Vec<(CursorId, &Cursor, Range<usize>)>> = Vec::new();
+    let mut run_end_line = 0;
+    for (id, cursor, range) in spans {
+        let start_line = byte_to_2d(&state.buffer, range.start).line;
+        let end_line = byte_to_2d(&state.buffer, range.end).line;
+        match runs.last_mut() {
+            Some(run) if start_line <= run_end_line + 1 => {
+                run.push((id, cursor, range));
+                run_end_line = run_end_line.max(end_line);
+            }
+            _ => {
+                runs.push(vec![(id, cursor, range)]);
+                run_end_line = end_line;
+            }
+        }
     }
-    // Disable auto-closing single quotes in markdown (used as apostrophes)
-    if matches!(language, "markdown" | "mdx") && ch == '\'' {
-        return None;
+
+    let primary_id = cursors.primary_id();
+    let mut events = Vec::new();
+    for run in runs.into_iter().filter(|run| run.len() > 1) {
+        let start = run.iter().map(|(_, _, r)| r.start).min().unwrap_or(0);
+        let end = run.iter().map(|(_, _, r)| r.end).max().unwrap_or(0);
+        let keep = run
+            .iter()
+            .find(|(id, _, _)| *id == primary_id)
+            .or_else(|| run.iter().min_by_key(|(id, _, _)| id.0))
+            .map(|(id, _, _)| *id)
+            .expect("run is not empty");
+
+        for (cursor_id, cursor, _) in &run {
+            if *cursor_id == keep {
+                add_move_cursor_event(
+                    &mut events,
+                    *cursor_id,
+                    cursor.position,
+                    end,
+                    cursor.anchor,
+                    (start != end).then_some(start),
+                    cursor.sticky_column,
+                );
+            } else {
+                events.push(Event::RemoveCursor {
+                    cursor_id: *cursor_id,
+                    position: cursor.position,
+                    anchor: cursor.anchor,
+                });
+            }
+        }
     }
-    match ch {
-        '(' => Some(')'),
-        '[' => Some(']'),
-        '{' => Some('}'),
-        '"' => Some('"'),
-        '\'' => Some('\''),
-        '`' => Some('`'),
-        _ => None,
+
+    events
+}
+
+/// Get the matching close character for auto-pairing, using the default
+/// pairs of `language`.
+pub fn get_auto_close_char(ch: char, auto_close: bool, language: &str) -> Option<char> {
+    if !auto_close {
+        return None;
     }
+    close_char_for(ch, &crate::config::default_auto_close_pairs(language))
+}
+
+/// The close character `pairs` give for the open character `ch`
+fn close_char_for(ch: char, pairs: &[(char, char)]) -> Option<char> {
+    pairs
+        .iter()
+        .find(|(open, _)| *open == ch)
+        .map(|(_, close)| *close)
 }
 
 /// Calculate the correct indent for a closing delimiter using tree-sitter.
@@ -698,7 +822,9 @@ fn insert_char_events(
     auto_surround: bool,
 ) {
     let is_closing_delimiter = matches!(ch, '}' | ')' | ']');
-    let auto_close_char = get_auto_close_char(ch, auto_close, &state.language);
+    let pairs = &state.buffer_settings.auto_close_pairs;
+    let auto_close_char = close_char_for(ch, pairs).filter(|_| auto_close);
+    let skips_over = auto_close && pairs.iter().any(|(_, close)| *close == ch);
     let cursor_data = collect_insert_cursor_data(state, cursors);
 
     for data in cursor_data {
@@ -745,10 +871,8 @@ fn insert_char_events(
             });
         }
 
-        // Try skip-over logic for closing brackets/quotes
-        // Single quotes are excluded in markdown (apostrophes, not paired quotes)
-        let skip_single_quote = ch == '\'' && matches!(state.language.as_str(), "markdown" | "mdx");
-        if auto_close && matches!(ch, ')' | ']' | '}' | '"' | '\'' | '`') && !skip_single_quote {
+        // Try skip-over logic for the closing character of a pair
+        if skips_over {
             if let Some(next_byte) = data.char_after {
                 if next_byte == ch as u8 {
                     // Try skip-over with dedent for closing delimiters
@@ -822,6 +946,96 @@ fn max_cursor_position(buffer: &Buffer) -> usize {
     buffer.len()
 }
 
+/// Whether a line (including its line ending) contains only whitespace
+fn is_blank_line(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Find the start of the first non-blank line at or after the line containing `pos`
+fn next_non_blank_line(
+    buffer: &mut Buffer,
+    pos: usize,
+    estimated_line_length: usize,
+) -> Option<usize> {
+    let mut iter = buffer.line_iterator(pos, estimated_line_length);
+    while let Some((line_start, line)) = iter.next_line() {
+        if !is_blank_line(&line) {
+            return Some(line_start);
+        }
+    }
+    None
+}
+
+/// Find the start of the last non-blank line before the line containing `pos`
+fn prev_non_blank_line(
+    buffer: &mut Buffer,
+    pos: usize,
+    estimated_line_length: usize,
+) -> Option<usize> {
+    let mut iter = buffer.line_iterator(pos, estimated_line_length);
+    while let Some((line_start, line)) = iter.prev() {
+        if !is_blank_line(&line) {
+            return Some(line_start);
+        }
+    }
+    None
+}
+
+/// Byte range of the blank-line-delimited paragraph containing the
+/// non-blank line at `pos`, including the last line's line ending
+fn paragraph_bounds(buffer: &mut Buffer, pos: usize, estimated_line_length: usize) -> Range<usize> {
+    let mut iter = buffer.line_iterator(pos, estimated_line_length);
+    let mut start = iter.current_position();
+    while let Some((line_start, line)) = iter.prev() {
+        if is_blank_line(&line) {
+            break;
+        }
+        start = line_start;
+    }
+
+    let mut iter = buffer.line_iterator(pos, estimated_line_length);
+    let mut end = start;
+    while let Some((line_start, line)) = iter.next_line() {
+        if is_blank_line(&line) {
+            break;
+        }
+        end = line_start + line.len();
+    }
+    start..end
+}
+
+/// Range selected by SelectParagraph for a cursor with the given selection.
+///
+/// Without a selection this is the paragraph under the cursor (or the next
+/// one when the cursor is on a blank line). With a selection, the selection
+/// grows to include the following paragraph, or the preceding one once the
+/// end of the buffer is reached.
+fn select_paragraph_range(
+    buffer: &mut Buffer,
+    position: usize,
+    selection: Option<Range<usize>>,
+    estimated_line_length: usize,
+) -> Option<Range<usize>> {
+    match selection {
+        None => {
+            let line = next_non_blank_line(buffer, position, estimated_line_length)
+                .or_else(|| prev_non_blank_line(buffer, position, estimated_line_length))?;
+            Some(paragraph_bounds(buffer, line, estimated_line_length))
+        }
+        Some(range) => {
+            if let Some(line) = next_non_blank_line(buffer, range.end, estimated_line_length) {
+                let next = paragraph_bounds(buffer, line, estimated_line_length);
+                if next.end > range.end {
+                    return Some(range.start..next.end);
+                }
+            }
+            let line = prev_non_blank_line(buffer, range.start, estimated_line_length)?;
+            let prev = paragraph_bounds(buffer, line, estimated_line_length);
+            Some(prev.start..range.end)
+        }
+    }
+}
+
 /// Transform selected text (or current word if no selection) using the given transform function.
 /// Processes cursors in reverse order to avoid position shifts.
 fn transform_case<F>(
@@ -866,6 +1080,100 @@ fn transform_case<F>(
     }
 }
 
+/// Toggle the case of the character under each cursor and move past it (vim `~`)
+///
+/// Characters without case are stepped over unchanged. At the end of a line the
+/// cursor either stops or, with `wrap_lines`, continues to the next line start.
+fn toggle_case_and_advance(
+    state: &mut EditorState,
+    cursors: &Cursors,
+    events: &mut Vec<Event>,
+    wrap_lines: bool,
+) {
+    let max_pos = state.buffer.len();
+    let mut targets: Vec<_> = cursors
+        .iter()
+        .map(|(cursor_id, cursor)| (cursor_id, *cursor))
+        .collect();
+    // Process from the end of the buffer so earlier positions stay valid
+    targets.sort_by_key(|(_, cursor)| std::cmp::Reverse(cursor.position));
+
+    for (cursor_id, cursor) in targets {
+        let pos = cursor.position;
+        // A UTF-8 character is at most 4 bytes long
+        let text = state.get_text_range(pos, (pos + 4).min(max_pos));
+        let Some(ch) = text.chars().next() else {
+            continue;
+        };
+        let is_line_ending = |c: char| c == '\r' || c == '\n';
+
+        if is_line_ending(ch) {
+            if wrap_lines {
+                let new_position = next_position_for_crlf(&state.buffer, pos, max_pos);
+                add_move_cursor_event(
+                    events,
+                    cursor_id,
+                    pos,
+                    new_position,
+                    cursor.anchor,
+                    None,
+                    cursor.sticky_column,
+                );
+            }
+            continue;
+        }
+
+        // Destination in pre-edit coordinates: just past the character, or
+        // past the following line ending when wrapping
+        let char_end = pos + ch.len_utf8();
+        let mut target = char_end;
+        if wrap_lines
+            && state
+                .get_text_range(char_end, (char_end + 1).min(max_pos))
+                .starts_with(is_line_ending)
+        {
+            target = next_position_for_crlf(&state.buffer, char_end, max_pos);
+        }
+
+        let toggled: String = if ch.is_lowercase() {
+            ch.to_uppercase().collect()
+        } else if ch.is_uppercase() {
+            ch.to_lowercase().collect()
+        } else {
+            ch.to_string()
+        };
+
+        let mut position = pos;
+        if toggled != ch.to_string() {
+            events.push(Event::Delete {
+                range: pos..char_end,
+                deleted_text: ch.to_string(),
+                cursor_id,
+            });
+            // The insert leaves the cursor right after the toggled text, whose
+            // length may differ from the original (e.g. `ß` -> `SS`)
+            position = pos + toggled.len();
+            target = target - char_end + position;
+            events.push(Event::Insert {
+                position: pos,
+                text: toggled,
+                cursor_id,
+            });
+        }
+        if target != position {
+            add_move_cursor_event(
+                events,
+                cursor_id,
+                position,
+                target,
+                cursor.anchor,
+                None,
+                cursor.sticky_column,
+            );
+        }
+    }
+}
+
 /// Convert an action into a sequence of events that can be applied to the editor state
 ///
 /// # Parameters
@@ -877,6 +1185,7 @@ fn transform_case<F>(
 /// * `auto_surround` - Whether to surround selections with matching pairs
 /// * `estimated_line_length` - Estimated bytes per line for large files
 /// * `viewport_height` - Height of the viewport in lines (for PageUp/PageDown)
+/// * `toggle_case_wraps_lines` - Whether toggling case at a line end moves to the next line
 ///
 /// # Returns
 /// * `Some(Vec<Event>)` - Events to apply for this action
@@ -891,6 +1200,7 @@ pub fn action_to_events(
     auto_surround: bool,
     estimated_line_length: usize,
     viewport_height: u16,
+    toggle_case_wraps_lines: bool,
 ) -> Option<Vec<Event>> {
     // For virtual buffers with hidden cursors, ignore movement and editing actions
     if !state.show_cursors && action.is_movement_or_editing() {
@@ -1523,7 +1833,11 @@ pub fn action_to_events(
 
         Action::MoveWordLeft => {
             for (cursor_id, cursor) in cursors.iter() {
-                let new_pos = find_word_start_left(&state.buffer, cursor.position);
+                let new_pos = find_word_start_left(
+                    &state.buffer,
+                    cursor.position,
+                    &state.buffer_settings.word_rules,
+                );
                 // Preserve anchor if deselect_on_move is false (Emacs mark mode)
                 let new_anchor = if cursor.deselect_on_move {
                     None
@@ -1544,7 +1858,11 @@ pub fn action_to_events(
 
         Action::MoveWordRight => {
             for (cursor_id, cursor) in cursors.iter() {
-                let new_pos = find_word_start_right(&state.buffer, cursor.position);
+                let new_pos = find_word_start_right(
+                    &state.buffer,
+                    cursor.position,
+                    &state.buffer_settings.word_rules,
+                );
                 // Preserve anchor if deselect_on_move is false (Emacs mark mode)
                 let new_anchor = if cursor.deselect_on_move {
                     None
@@ -1565,7 +1883,11 @@ pub fn action_to_events(
 
         Action::MoveWordEnd => {
             for (cursor_id, cursor) in cursors.iter() {
-                let new_pos = find_word_end_right(&state.buffer, cursor.position);
+                let new_pos = find_word_end_right(
+                    &state.buffer,
+                    cursor.position,
+                    &state.buffer_settings.word_rules,
+                );
                 // Preserve anchor if deselect_on_move is false (Emacs mark mode)
                 let new_anchor = if cursor.deselect_on_move {
                     None
@@ -1946,7 +2268,11 @@ pub fn action_to_events(
 
         Action::SelectWordLeft => {
             for (cursor_id, cursor) in cursors.iter() {
-                let new_pos = find_word_start_left(&state.buffer, cursor.position);
+                let new_pos = find_word_start_left(
+                    &state.buffer,
+                    cursor.position,
+                    &state.buffer_settings.word_rules,
+                );
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
                 events.push(Event::MoveCursor {
                     cursor_id,
@@ -1962,7 +2288,11 @@ pub fn action_to_events(
 
         Action::SelectWordRight => {
             for (cursor_id, cursor) in cursors.iter() {
-                let new_pos = find_word_start_right(&state.buffer, cursor.position);
+                let new_pos = find_word_start_right(
+                    &state.buffer,
+                    cursor.position,
+                    &state.buffer_settings.word_rules,
+                );
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
                 events.push(Event::MoveCursor {
                     cursor_id,
@@ -1978,7 +2308,11 @@ pub fn action_to_events(
 
         Action::SelectWordEnd => {
             for (cursor_id, cursor) in cursors.iter() {
-                let new_pos = find_word_end_right(&state.buffer, cursor.position);
+                let new_pos = find_word_end_right(
+                    &state.buffer,
+                    cursor.position,
+                    &state.buffer_settings.word_rules,
+                );
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
                 events.push(Event::MoveCursor {
                     cursor_id,
@@ -2126,12 +2460,13 @@ pub fn action_to_events(
 
         Action::SelectWord => {
             for (cursor_id, cursor) in cursors.iter() {
-                // Find word boundaries at current position
-                // First find the start of the word we're in/adjacent to
-                let word_start = find_word_start(&state.buffer, cursor.position);
-                // Then find the end of that word (from the start, not from cursor)
-                // This ensures we select the current word, not the next one
-                let word_end = find_word_end(&state.buffer, word_start);
+                // Find the word (or sub-word) we're in or adjacent to
+                let word = find_word_range(
+                    &state.buffer,
+                    cursor.position,
+                    &state.buffer_settings.word_rules,
+                );
+                let (word_start, word_end) = (word.start, word.end);
 
                 if word_start < word_end {
                     events.push(Event::MoveCursor {
@@ -2218,16 +2553,14 @@ pub fn action_to_events(
                                 .first()
                                 .copied();
 
-                            // Check if we're between matching brackets/quotes
-                            let is_matching_pair = matches!(
-                                (char_before, char_after),
-                                (Some(b'('), Some(b')'))
-                                    | (Some(b'['), Some(b']'))
-                                    | (Some(b'{'), Some(b'}'))
-                                    | (Some(b'"'), Some(b'"'))
-                                    | (Some(b'\''), Some(b'\''))
-                                    | (Some(b'`'), Some(b'`'))
-                            );
+                            // Check if we're between the two characters of a pair
+                            let is_matching_pair =
+                                state.buffer_settings.auto_close_pairs.iter().any(
+                                    |&(open, close)| {
+                                        char_before == Some(open as u8)
+                                            && char_after == Some(close as u8)
+                                    },
+                                );
 
                             if is_matching_pair {
                                 // Delete both opening and closing characters
@@ -2286,7 +2619,11 @@ pub fn action_to_events(
                     if let Some(range) = cursor.selection_range() {
                         Some((cursor_id, range))
                     } else {
-                        let word_start = find_word_start_left(&state.buffer, cursor.position);
+                        let word_start = find_word_start_left(
+                            &state.buffer,
+                            cursor.position,
+                            &state.buffer_settings.word_rules,
+                        );
                         if word_start < cursor.position {
                             Some((cursor_id, word_start..cursor.position))
                         } else {
@@ -2308,7 +2645,11 @@ pub fn action_to_events(
                     if let Some(range) = cursor.selection_range() {
                         Some((cursor_id, range))
                     } else {
-                        let word_end = find_word_start_right(&state.buffer, cursor.position);
+                        let word_end = find_word_start_right(
+                            &state.buffer,
+                            cursor.position,
+                            &state.buffer_settings.word_rules,
+                        );
                         if cursor.position < word_end {
                             Some((cursor_id, cursor.position..word_end))
                         } else {
@@ -2448,46 +2789,30 @@ pub fn action_to_events(
             transform_case(state, cursors, &mut events, |s| s.to_lowercase());
         }
 
-        Action::SortLines => {
-            // Sort selected lines alphabetically
-            // Process cursors in reverse order to avoid position shifts
-            let line_ending = state.buffer.line_ending().as_str();
-            let mut selections: Vec<_> = cursors
-                .iter()
-                .filter_map(|(cursor_id, cursor)| {
-                    cursor.selection_range().map(|range| (cursor_id, range))
-                })
-                .collect();
-            selections.sort_by_key(|(_, range)| std::cmp::Reverse(range.start));
-
-            for (cursor_id, range) in selections {
-                let text = state.get_text_range(range.start, range.end);
-                // Split into lines, preserving the original line ending style
-                let mut lines: Vec<&str> = text.lines().collect();
-                // Check if original text ends with a newline
-                let ends_with_newline = text.ends_with('\n') || text.ends_with("\r\n");
-
-                if lines.len() > 1 {
-                    lines.sort();
-                    let mut sorted_text = lines.join(line_ending);
-                    if ends_with_newline {
-                        sorted_text.push_str(line_ending);
-                    }
+        Action::ToggleCaseAndAdvance => {
+            toggle_case_and_advance(state, cursors, &mut events, toggle_case_wraps_lines);
+        }
 
-                    if sorted_text != text {
-                        events.push(Event::Delete {
-                            range: range.clone(),
-                            deleted_text: text,
-                            cursor_id,
-                        });
-                        events.push(Event::Insert {
-                            position: range.start,
-                            text: sorted_text,
-                            cursor_id,
-                        });
-                    }
-                }
-            }
+        Action::IncrementNumber(amount) => {
+            increment_numbers(
+                state,
+                cursors,
+                &mut events,
+                amount,
+                false,
+                estimated_line_length,
+            );
+        }
+
+        Action::IncrementNumberSequential(amount) => {
+            increment_numbers(
+                state,
+                cursors,
+                &mut events,
+                amount,
+                true,
+                estimated_line_length,
+            );
         }
 
         Action::OpenLine => {
@@ -2621,6 +2946,33 @@ pub fn action_to_events(
             }
         }
 
+        Action::ClearSecondaryCursors => {
+            // Like RemoveSecondaryCursors, but keep the primary cursor where it is
+            let primary_id = cursors.primary_id();
+            for (cursor_id, cursor) in cursors.iter() {
+                if cursor_id != primary_id {
+                    events.push(Event::RemoveCursor {
+                        cursor_id,
+                        position: cursor.position,
+                        anchor: cursor.anchor,
+                    });
+                }
+                events.push(Event::ClearAnchor { cursor_id });
+            }
+        }
+
+        Action::SplitSelectionIntoLines => {
+            events.extend(split_selection_into_lines_events(
+                state,
+                cursors,
+                estimated_line_length,
+            ));
+        }
+
+        Action::MergeCursorsIntoSelection => {
+            events.extend(merge_cursors_into_selection_events(state, cursors));
+        }
+
         Action::ScrollUp => {
             events.push(Event::Scroll { line_offset: -1 });
         }
@@ -2636,6 +2988,12 @@ pub fn action_to_events(
         | Action::Save
         | Action::SaveAs
         | Action::Open
+        | Action::RecentFiles
+        | Action::FuzzyFindFile
+        | Action::OpenAsText
+        | Action::SaveLocalCopy
+        | Action::WriteCopy
+        | Action::RecoveryCheckpoint
         | Action::SwitchProject
         | Action::New
         | Action::Close
@@ -2655,6 +3013,7 @@ pub fn action_to_events(
         | Action::PrevSplit
         | Action::Copy
         | Action::CopyWithTheme(_)
+        | Action::CopyAsRichText
         | Action::Cut
         | Action::Paste
         | Action::YankWordForward
@@ -2664,11 +3023,16 @@ pub fn action_to_events(
         | Action::AddCursorNextMatch
         | Action::AddCursorAbove
         | Action::AddCursorBelow
+        | Action::RotatePrimaryCursorNext
+        | Action::RotatePrimaryCursorPrev
+        | Action::SelectionInfo
         | Action::CommandPalette
         | Action::QuickOpen
         | Action::ShowHelp
+        | Action::OpenTutorial
         | Action::ToggleLineWrap
         | Action::ToggleReadOnly
+        | Action::ClearFileOverrides
         | Action::ToggleComposeMode
         | Action::SetComposeWidth
         | Action::IncreaseSplitSize
@@ -2679,18 +3043,31 @@ pub fn action_to_events(
         | Action::GoToMatchingBracket
         | Action::JumpToNextError
         | Action::JumpToPreviousError
+        | Action::FixNextProblem
         | Action::ShowKeyboardShortcuts
         | Action::ShowWarnings
         | Action::ShowStatusLog
+        | Action::ShowMessages
+        | Action::ToggleMessagesFilter
         | Action::ShowLspStatus
         | Action::ClearWarnings
         | Action::SmartHome
         | Action::ToggleComment
+        | Action::ReflowParagraph
+        | Action::ReflowParagraphToColumn
+        | Action::UnwrapParagraph
+        | Action::SortLines
+        | Action::SortLinesNumeric
+        | Action::SortLinesReverse
         | Action::ToggleFold
         | Action::SetBookmark(_)
         | Action::JumpToBookmark(_)
         | Action::ClearBookmark(_)
         | Action::ListBookmarks
+        | Action::ToggleLineMark
+        | Action::NextLineMark
+        | Action::PrevLineMark
+        | Action::ClearLineMarks
         | Action::ToggleSearchCaseSensitive
         | Action::ToggleSearchWholeWord
         | Action::ToggleSearchRegex
@@ -2704,8 +3081,14 @@ pub fn action_to_events(
         | Action::PromptRecordMacro
         | Action::PromptPlayMacro
         | Action::PlayLastMacro
+        | Action::PromptSaveMacro
+        | Action::PromptLoadMacro
+        | Action::ListSavedMacros
         | Action::PromptSetBookmark
         | Action::PromptJumpToBookmark
+        | Action::BookmarkSet
+        | Action::BookmarkGoto
+        | Action::OpenBookmarkPicker
         | Action::PromptConfirm
         | Action::PromptConfirmWithText(_)
         | Action::PromptCancel
@@ -2747,6 +3130,8 @@ pub fn action_to_events(
         | Action::ToggleMenuBar
         | Action::ToggleTabBar
         | Action::ToggleStatusBar
+        | Action::ZoomIn
+        | Action::ZoomOut
         | Action::ToggleVerticalScrollbar
         | Action::ToggleHorizontalScrollbar
         | Action::FocusFileExplorer
@@ -2772,6 +3157,8 @@ pub fn action_to_events(
         | Action::LspCompletion
         | Action::LspGotoDefinition
         | Action::LspReferences
+        | Action::LspPeekDefinition
+        | Action::LspPeekReferences
         | Action::LspRename
         | Action::LspHover
         | Action::LspSignatureHelp
@@ -2779,12 +3166,20 @@ pub fn action_to_events(
         | Action::LspRestart
         | Action::LspStop
         | Action::LspToggleForBuffer
+        | Action::LspShowEnvironment
         | Action::ToggleInlayHints
         | Action::ToggleMouseHover
         | Action::ToggleLineNumbers
         | Action::ToggleScrollSync
+        | Action::TogglePrivacyMode
+        | Action::DiffBuffers
+        | Action::DiffNextHunk
+        | Action::DiffPrevHunk
+        | Action::DiffAcceptLeft
+        | Action::DiffAcceptRight
         | Action::ToggleMouseCapture
         | Action::DumpConfig
+        | Action::ManageWorkspaceTrust
         | Action::Search
         | Action::FindInSelection
         | Action::FindNext
@@ -2793,6 +3188,9 @@ pub fn action_to_events(
         | Action::FindSelectionPrevious
         | Action::Replace
         | Action::QueryReplace
+        | Action::ProjectSearch
+        | Action::ProjectSearchOpenResult
+        | Action::ProjectReplace
         | Action::MenuActivate
         | Action::MenuClose
         | Action::MenuLeft
@@ -2813,10 +3211,12 @@ pub fn action_to_events(
         | Action::SelectLocale
         | Action::Revert
         | Action::ToggleAutoRevert
+        | Action::CancelRemoteTransfer
         | Action::FormatBuffer
         | Action::TrimTrailingWhitespace
         | Action::EnsureFinalNewline
         | Action::OpenTerminal
+        | Action::OpenRemoteTerminal
         | Action::CloseTerminal
         | Action::FocusTerminal
         | Action::TerminalEscape
@@ -2837,6 +3237,7 @@ pub fn action_to_events(
         | Action::SetEncoding
         | Action::ReloadWithEncoding
         | Action::SetLanguage
+        | Action::ReloadGrammars
         | Action::ToggleIndentationStyle
         | Action::ToggleTabIndicators
         | Action::ToggleWhitespaceIndicators
@@ -2844,8 +3245,14 @@ pub fn action_to_events(
         | Action::ResetBufferSettings
         | Action::ShellCommand
         | Action::ShellCommandReplace
+        | Action::Prettify
+        | Action::ExportHtml
+        | Action::ExportSettingsBundle
+        | Action::ImportSettingsBundle
         | Action::CalibrateInput
         | Action::EventDebug
+        | Action::ShowUndoHistorySizes
+        | Action::DiagnoseBufferPerformance
         | Action::LoadPluginFromBuffer
         | Action::OpenKeybindingEditor
         | Action::AddRuler
@@ -2869,15 +3276,22 @@ pub fn action_to_events(
         }
 
         Action::SelectLine => {
-            // Select the entire line for each cursor
+            // Select the entire line for each cursor; with a selection already
+            // present, extend it to whole lines and grow it by the next line
             for (cursor_id, cursor) in cursors.iter() {
-                // Use iterator to get line bounds
-                let mut iter = state
+                let (start, end) = match cursor.selection_range() {
+                    Some(range) => (range.start, range.end),
+                    None => (cursor.position, cursor.position),
+                };
+                let line_start = state
                     .buffer
-                    .line_iterator(cursor.position, estimated_line_length);
-                if let Some((line_start, line_content)) = iter.next_line() {
+                    .line_iterator(start, estimated_line_length)
+                    .current_position();
+                // Use iterator to get line bounds
+                let mut iter = state.buffer.line_iterator(end, estimated_line_length);
+                if let Some((end_line_start, line_content)) = iter.next_line() {
                     // Include newline if present
-                    let line_end = line_start + line_content.len();
+                    let line_end = end_line_start + line_content.len();
 
                     events.push(Event::MoveCursor {
                         cursor_id,
@@ -2892,13 +3306,40 @@ pub fn action_to_events(
             }
         }
 
+        Action::SelectParagraph => {
+            // Select the blank-line-delimited paragraph for each cursor,
+            // growing to the adjacent paragraph on repeat
+            for (cursor_id, cursor) in cursors.iter() {
+                if let Some(range) = select_paragraph_range(
+                    &mut state.buffer,
+                    cursor.position,
+                    cursor.selection_range(),
+                    estimated_line_length,
+                ) {
+                    events.push(Event::MoveCursor {
+                        cursor_id,
+                        old_position: cursor.position,
+                        new_position: range.end,
+                        old_anchor: cursor.anchor,
+                        new_anchor: Some(range.start),
+                        old_sticky_column: cursor.sticky_column,
+                        new_sticky_column: 0, // Reset sticky column
+                    });
+                }
+            }
+        }
+
         Action::ExpandSelection => {
             // Expand selection for each cursor
             for (cursor_id, cursor) in cursors.iter() {
                 if let Some(anchor) = cursor.anchor {
                     // Already have a selection - expand by one word to the right
                     // First move to the start of the next word, then to its end
-                    let next_word_start = find_word_start_right(&state.buffer, cursor.position);
+                    let next_word_start = find_word_start_right(
+                        &state.buffer,
+                        cursor.position,
+                        &WordRules::default(),
+                    );
                     let new_end = find_word_end(&state.buffer, next_word_start);
                     events.push(Event::MoveCursor {
                         cursor_id,
@@ -2919,7 +3360,11 @@ pub fn action_to_events(
                     let (final_start, final_end) =
                         if word_start == word_end || cursor.position == word_end {
                             // Find the next word (skip non-word characters to find it)
-                            let next_start = find_word_start_right(&state.buffer, cursor.position);
+                            let next_start = find_word_start_right(
+                                &state.buffer,
+                                cursor.position,
+                                &WordRules::default(),
+                            );
                             let next_end = find_word_end(&state.buffer, next_start);
                             // Select FROM cursor position TO the end of next word
                             (cursor.position, next_end)
@@ -3008,6 +3453,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         println!("Generated events: {:?}", events);
@@ -3067,6 +3513,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(events.len(), 1);
@@ -3091,6 +3538,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(events.len(), 1);
@@ -3145,6 +3593,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -3198,6 +3647,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -3251,6 +3701,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert!(events.is_empty());
@@ -3301,6 +3752,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert!(events.is_empty());
@@ -3360,6 +3812,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -3422,6 +3875,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -3490,6 +3944,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -3554,6 +4009,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(events.len(), 1);
@@ -3586,6 +4042,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(events.len(), 1);
@@ -3647,6 +4104,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(events.len(), 1);
@@ -3682,6 +4140,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(events.len(), 1);
@@ -3746,6 +4205,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(events.len(), 1);
@@ -3781,6 +4241,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(events.len(), 1);
@@ -3843,6 +4304,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(events.len(), 1);
@@ -3899,6 +4361,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(events.len(), 1);
@@ -3958,6 +4421,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         if let Event::MoveCursor { new_position, .. } = &events[0] {
@@ -3977,6 +4441,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         if let Event::MoveCursor { new_position, .. } = &events[0] {
@@ -4029,6 +4494,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(
@@ -4048,6 +4514,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         assert_eq!(
@@ -4181,6 +4648,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -4243,6 +4711,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -4322,6 +4791,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         println!("MoveUp events: {:?}", events);
@@ -4405,6 +4875,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         println!("MoveDown events: {:?}", events);
@@ -4466,6 +4937,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -4484,6 +4956,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -4506,6 +4979,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -4524,6 +4998,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -4547,6 +5022,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events.iter() {
@@ -4595,6 +5071,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         println!("Events: {:?}", events);
@@ -4636,6 +5113,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -4672,6 +5150,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -4705,6 +5184,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -4737,6 +5217,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -4794,6 +5275,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -4874,6 +5356,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -4941,6 +5424,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -4958,6 +5442,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -4975,6 +5460,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -4999,6 +5485,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -5035,6 +5522,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -5117,6 +5605,7 @@ mod tests {
                 true,
                 80,
                 24,
+                false,
             )
             .unwrap();
             for event in events {
@@ -5142,6 +5631,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -5179,6 +5669,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
         for event in events {
@@ -5241,6 +5732,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -5297,6 +5789,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -5352,6 +5845,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -5407,6 +5901,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -5463,6 +5958,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -5519,6 +6015,7 @@ mod tests {
             true,
             80,
             24,
+            false,
         )
         .unwrap();
 
@@ -5528,6 +6025,87 @@ mod tests {
 
         assert_eq!(state.buffer.to_string().unwrap(), "(bc)");
     }
+
+    fn paragraph_at(text: &str, position: usize, selection: Option<Range<usize>>) -> Option<&str> {
+        let mut buffer = Buffer::from_bytes(text.as_bytes().to_vec(), test_fs());
+        select_paragraph_range(&mut buffer, position, selection, 80).map(|range| &text[range])
+    }
+
+    #[test]
+    fn test_select_paragraph_at_buffer_start_and_end() {
+        let text = "one\ntwo\n\nthree\nfour";
+        assert_eq!(paragraph_at(text, 0, None), Some("one\ntwo\n"));
+        assert_eq!(paragraph_at(text, 5, None), Some("one\ntwo\n"));
+        // Last paragraph without a trailing newline ends at the buffer end
+        assert_eq!(paragraph_at(text, text.len(), None), Some("three\nfour"));
+    }
+
+    #[test]
+    fn test_select_paragraph_around_blank_line_runs() {
+        let text = "\n  \nfirst\n\n\t\n\nsecond\nline\n\n";
+        // Leading blank lines select the following paragraph
+        assert_eq!(paragraph_at(text, 0, None), Some("first\n"));
+        // A run of blank (whitespace-only) lines selects the next paragraph
+        let in_run = text.find('\t').unwrap();
+        assert_eq!(paragraph_at(text, in_run, None), Some("second\nline\n"));
+        // Trailing blank lines fall back to the preceding paragraph
+        assert_eq!(paragraph_at(text, text.len(), None), Some("second\nline\n"));
+        assert_eq!(paragraph_at("\n\n", 1, None), None);
+    }
+
+    #[test]
+    fn test_select_paragraph_repeat_grows_to_adjacent_paragraph() {
+        let text = "a\n\nb\n\nc\n";
+        let first = paragraph_at(text, 0, None).unwrap();
+        assert_eq!(first, "a\n");
+        // Repeat extends forward across the blank run
+        assert_eq!(paragraph_at(text, 2, Some(0..2)), Some("a\n\nb\n"));
+        assert_eq!(paragraph_at(text, 5, Some(0..5)), Some("a\n\nb\n\nc\n"));
+        // At the end of the buffer it extends backward instead
+        assert_eq!(paragraph_at(text, 8, Some(6..8)), Some("b\n\nc\n"));
+        // A selection ending mid-paragraph first completes that paragraph
+        assert_eq!(paragraph_at(text, 4, Some(3..4)), Some("b\n"));
+    }
+
+    #[test]
+    fn test_merge_cursors_keeps_separated_runs_apart() {
+        let mut state = EditorState::new(
+            80,
+            24,
+            crate::config::LARGE_FILE_THRESHOLD_BYTES as usize,
+            test_fs(),
+        );
+        let mut cursors = Cursors::new();
+        state.apply(
+            &mut cursors,
+            &Event::Insert {
+                position: 0,
+                text: "a\nb\nx\nc".to_string(),
+                cursor_id: CursorId(0),
+            },
+        );
+        cursors.primary_mut().position = 0;
+        let second = cursors.add(Cursor::new(2));
+        let third = cursors.add(Cursor::new(6));
+
+        // Lines 0 and 1 merge; line 3 is separated by line 2 and stays put
+        let events = merge_cursors_into_selection_events(&mut state, &cursors);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[0],
+            Event::MoveCursor {
+                cursor_id: CursorId(0),
+                new_position: 2,
+                new_anchor: Some(0),
+                ..
+            }
+        ));
+        assert!(matches!(
+            events[1],
+            Event::RemoveCursor { cursor_id, .. } if cursor_id == second
+        ));
+        assert!(!events.iter().any(|e| e.cursor_id() == Some(third)));
+    }
 }
 
 #[cfg(test)]
@@ -153,6 +153,30 @@ impl ModeRegistry {
 
         registry.register(special_mode);
 
+        // Built-in "messages" mode for the *Messages* transcript
+        let messages_mode = BufferMode::new("messages")
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(
+                KeyCode::Char('f'),
+                KeyModifiers::NONE,
+                "toggle_messages_filter",
+            );
+
+        registry.register(messages_mode);
+
+        // Built-in "grep-results" mode for the *Project Search* results
+        let grep_results_mode = BufferMode::new("grep-results")
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(
+                KeyCode::Enter,
+                KeyModifiers::NONE,
+                "project_search_open_result",
+            );
+
+        registry.register(grep_results_mode);
+
         registry
     }
 
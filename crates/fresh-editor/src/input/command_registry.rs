@@ -7,6 +7,7 @@ use crate::input::commands::{get_all_commands, Command, Suggestion};
 use crate::input::fuzzy::fuzzy_match;
 use crate::input::keybindings::Action;
 use crate::input::keybindings::KeyContext;
+use std::collections::HashSet;
 use std::sync::{Arc, RwLock};
 
 /// Registry for managing editor commands
@@ -20,6 +21,10 @@ pub struct CommandRegistry {
     /// Plugin-registered commands (dynamically added/removed)
     plugin_commands: Arc<RwLock<Vec<Command>>>,
 
+    /// Names of plugin commands remembered from the last session whose
+    /// plugin hasn't registered them again yet
+    placeholders: RwLock<HashSet<String>>,
+
     /// Command usage history (most recent first)
     /// Used to sort command palette suggestions by recency
     command_history: Vec<String>,
@@ -34,6 +39,7 @@ impl CommandRegistry {
         Self {
             builtin_commands: get_all_commands(),
             plugin_commands: Arc::new(RwLock::new(Vec::new())),
+            placeholders: RwLock::new(HashSet::new()),
             command_history: Vec::new(),
         }
     }
@@ -82,6 +88,7 @@ impl CommandRegistry {
 
         // Remove existing command with same name
         commands.retain(|c| c.name != command.name);
+        self.placeholders.write().unwrap().remove(&command.name);
 
         // Add new command
         commands.push(command);
@@ -91,6 +98,43 @@ impl CommandRegistry {
         );
     }
 
+    /// Register a command remembered from the last session, to stand in
+    /// for it until its plugin registers it again. Commands that already
+    /// exist are left alone.
+    pub fn register_placeholder(&self, command: Command) {
+        let mut commands = self.plugin_commands.write().unwrap();
+        if commands.iter().any(|c| c.name == command.name) {
+            return;
+        }
+        self.placeholders
+            .write()
+            .unwrap()
+            .insert(command.name.clone());
+        commands.push(command);
+    }
+
+    /// Whether a command is still a placeholder
+    pub fn is_placeholder(&self, name: &str) -> bool {
+        self.placeholders.read().unwrap().contains(name)
+    }
+
+    /// Remove the placeholders no plugin registered again
+    pub fn remove_placeholders(&self) {
+        let placeholders = std::mem::take(&mut *self.placeholders.write().unwrap());
+        if !placeholders.is_empty() {
+            tracing::debug!("Removing {} stale plugin commands", placeholders.len());
+            self.plugin_commands
+                .write()
+                .unwrap()
+                .retain(|c| !placeholders.contains(&c.name));
+        }
+    }
+
+    /// Commands registered by plugins
+    pub fn plugin_commands(&self) -> Vec<Command> {
+        self.plugin_commands.read().unwrap().clone()
+    }
+
     /// Unregister a command by name
     pub fn unregister(&self, name: &str) {
         let mut commands = self.plugin_commands.write().unwrap();
@@ -201,31 +245,36 @@ impl CommandRegistry {
         };
 
         // Helper to create a suggestion from a command
-        let make_suggestion =
-            |cmd: &Command, score: i32, localized_name: String, localized_desc: String| {
-                let mut available = is_available(cmd);
-                if cmd.action == Action::FindInSelection && !selection_active {
-                    available = false;
-                }
-                // Disable LSP start/restart/toggle commands when no LSP is configured
-                if !has_lsp_config
-                    && matches!(cmd.action, Action::LspRestart | Action::LspToggleForBuffer)
-                {
-                    available = false;
-                }
-                let keybinding =
-                    keybinding_resolver.get_keybinding_for_action(&cmd.action, current_context);
-                let history_pos = self.history_position(&cmd.name);
-
-                let suggestion = Suggestion::with_source(
-                    localized_name,
-                    Some(localized_desc),
-                    !available,
-                    keybinding,
-                    Some(cmd.source.clone()),
-                );
-                (suggestion, history_pos, score)
-            };
+        let make_suggestion = |cmd: &Command,
+                               score: i32,
+                               localized_name: String,
+                               localized_desc: String| {
+            let mut available = is_available(cmd);
+            if cmd.action == Action::FindInSelection && !selection_active {
+                available = false;
+            }
+            // Disable LSP start/restart/toggle commands when no LSP is configured
+            if !has_lsp_config
+                && matches!(
+                    cmd.action,
+                    Action::LspRestart | Action::LspToggleForBuffer | Action::LspShowEnvironment
+                )
+            {
+                available = false;
+            }
+            let keybinding =
+                keybinding_resolver.get_keybinding_for_action(&cmd.action, current_context);
+            let history_pos = self.history_position(&cmd.name);
+
+            let suggestion = Suggestion::with_source(
+                localized_name,
+                Some(localized_desc),
+                !available,
+                keybinding,
+                Some(cmd.source.clone()),
+            );
+            (suggestion, history_pos, score)
+        };
 
         // First, try to match by name only
         // Commands with unmet custom contexts are completely hidden
@@ -426,6 +475,32 @@ mod tests {
         assert_eq!(found.description, "Second version");
     }
 
+    #[test]
+    fn test_placeholders_until_registered() {
+        let registry = CommandRegistry::new();
+        let command = |name: &str| Command {
+            name: name.to_string(),
+            description: String::new(),
+            action: Action::PluginAction(name.to_lowercase()),
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Plugin("test".to_string()),
+        };
+
+        registry.register_placeholder(command("Kept"));
+        registry.register_placeholder(command("Stale"));
+        assert!(registry.is_placeholder("Kept"));
+        assert_eq!(registry.plugin_command_count(), 2);
+
+        registry.register(command("Kept"));
+        assert!(!registry.is_placeholder("Kept"));
+        assert!(registry.is_placeholder("Stale"));
+
+        registry.remove_placeholders();
+        assert!(registry.find_by_name("Kept").is_some());
+        assert!(registry.find_by_name("Stale").is_none());
+    }
+
     #[test]
     fn test_unregister_by_prefix() {
         let registry = CommandRegistry::new();
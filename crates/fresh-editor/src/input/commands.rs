@@ -176,6 +176,34 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.recent_files",
+        desc_key: "cmd.recent_files_desc",
+        action: || Action::RecentFiles,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.fuzzy_find_file",
+        desc_key: "cmd.fuzzy_find_file_desc",
+        action: || Action::FuzzyFindFile,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.open_as_text",
+        desc_key: "cmd.open_as_text_desc",
+        action: || Action::OpenAsText,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.save_local_copy",
+        desc_key: "cmd.save_local_copy_desc",
+        action: || Action::SaveLocalCopy,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.switch_project",
         desc_key: "cmd.switch_project_desc",
@@ -197,6 +225,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.write_copy",
+        desc_key: "cmd.write_copy_desc",
+        action: || Action::WriteCopy,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.checkpoint",
+        desc_key: "cmd.checkpoint_desc",
+        action: || Action::RecoveryCheckpoint,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.new_file",
         desc_key: "cmd.new_file_desc",
@@ -232,6 +274,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.cancel_remote_transfer",
+        desc_key: "cmd.cancel_remote_transfer_desc",
+        action: || Action::CancelRemoteTransfer,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.format_buffer",
         desc_key: "cmd.format_buffer_desc",
@@ -296,6 +345,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.copy_as_rich_text",
+        desc_key: "cmd.copy_as_rich_text_desc",
+        action: || Action::CopyAsRichText,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.cut",
         desc_key: "cmd.cut_desc",
@@ -359,6 +415,34 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_case_and_advance",
+        desc_key: "cmd.toggle_case_and_advance_desc",
+        action: || Action::ToggleCaseAndAdvance,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.increment_number",
+        desc_key: "cmd.increment_number_desc",
+        action: || Action::IncrementNumber(1),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.decrement_number",
+        desc_key: "cmd.decrement_number_desc",
+        action: || Action::IncrementNumber(-1),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.increment_number_sequential",
+        desc_key: "cmd.increment_number_sequential_desc",
+        action: || Action::IncrementNumberSequential(1),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.sort_lines",
         desc_key: "cmd.sort_lines_desc",
@@ -366,6 +450,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.sort_lines_numeric",
+        desc_key: "cmd.sort_lines_numeric_desc",
+        action: || Action::SortLinesNumeric,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.sort_lines_reverse",
+        desc_key: "cmd.sort_lines_reverse_desc",
+        action: || Action::SortLinesReverse,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.open_line",
         desc_key: "cmd.open_line_desc",
@@ -416,6 +514,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.select_paragraph",
+        desc_key: "cmd.select_paragraph_desc",
+        action: || Action::SelectParagraph,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.expand_selection",
         desc_key: "cmd.expand_selection_desc",
@@ -452,6 +557,48 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.clear_secondary_cursors",
+        desc_key: "cmd.clear_secondary_cursors_desc",
+        action: || Action::ClearSecondaryCursors,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.split_selection_into_lines",
+        desc_key: "cmd.split_selection_into_lines_desc",
+        action: || Action::SplitSelectionIntoLines,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.merge_cursors_into_selection",
+        desc_key: "cmd.merge_cursors_into_selection_desc",
+        action: || Action::MergeCursorsIntoSelection,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.rotate_primary_cursor_next",
+        desc_key: "cmd.rotate_primary_cursor_next_desc",
+        action: || Action::RotatePrimaryCursorNext,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.rotate_primary_cursor_prev",
+        desc_key: "cmd.rotate_primary_cursor_prev_desc",
+        action: || Action::RotatePrimaryCursorPrev,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.selection_info",
+        desc_key: "cmd.selection_info_desc",
+        action: || Action::SelectionInfo,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Buffer navigation
     CommandDef {
         name_key: "cmd.next_buffer",
@@ -553,6 +700,48 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_privacy_mode",
+        desc_key: "cmd.toggle_privacy_mode_desc",
+        action: || Action::TogglePrivacyMode,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.diff_buffers",
+        desc_key: "cmd.diff_buffers_desc",
+        action: || Action::DiffBuffers,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.diff_next_hunk",
+        desc_key: "cmd.diff_next_hunk_desc",
+        action: || Action::DiffNextHunk,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.diff_prev_hunk",
+        desc_key: "cmd.diff_prev_hunk_desc",
+        action: || Action::DiffPrevHunk,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.diff_accept_left",
+        desc_key: "cmd.diff_accept_left_desc",
+        action: || Action::DiffAcceptLeft,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.diff_accept_right",
+        desc_key: "cmd.diff_accept_right_desc",
+        action: || Action::DiffAcceptRight,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_fold",
         desc_key: "cmd.toggle_fold_desc",
@@ -618,6 +807,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.reload_grammars",
+        desc_key: "cmd.reload_grammars_desc",
+        action: || Action::ReloadGrammars,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_indentation",
         desc_key: "cmd.toggle_indentation_desc",
@@ -710,6 +906,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal, FileExplorer, Terminal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.zoom_in",
+        desc_key: "cmd.zoom_in_desc",
+        action: || Action::ZoomIn,
+        contexts: &[Normal, FileExplorer, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.zoom_out",
+        desc_key: "cmd.zoom_out_desc",
+        action: || Action::ZoomOut,
+        contexts: &[Normal, FileExplorer, Terminal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_vertical_scrollbar",
         desc_key: "cmd.toggle_vertical_scrollbar_desc",
@@ -802,6 +1012,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.clear_file_overrides",
+        desc_key: "cmd.clear_file_overrides_desc",
+        action: || Action::ClearFileOverrides,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.set_background",
         desc_key: "cmd.set_background_desc",
@@ -873,6 +1090,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.project_search",
+        desc_key: "cmd.project_search_desc",
+        action: || Action::ProjectSearch,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.project_replace",
+        desc_key: "cmd.project_replace_desc",
+        action: || Action::ProjectReplace,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     // Navigation
     CommandDef {
         name_key: "cmd.goto_line",
@@ -923,6 +1154,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.peek_definition",
+        desc_key: "cmd.peek_definition_desc",
+        action: || Action::LspPeekDefinition,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.peek_references",
+        desc_key: "cmd.peek_references_desc",
+        action: || Action::LspPeekReferences,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.show_signature_help",
         desc_key: "cmd.show_signature_help_desc",
@@ -958,6 +1203,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.show_lsp_environment",
+        desc_key: "cmd.show_lsp_environment_desc",
+        action: || Action::LspShowEnvironment,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_mouse_hover",
         desc_key: "cmd.toggle_mouse_hover_desc",
@@ -987,6 +1239,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.reflow_paragraph",
+        desc_key: "cmd.reflow_paragraph_desc",
+        action: || Action::ReflowParagraph,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.reflow_paragraph_to_column",
+        desc_key: "cmd.reflow_paragraph_to_column_desc",
+        action: || Action::ReflowParagraphToColumn,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.unwrap_paragraph",
+        desc_key: "cmd.unwrap_paragraph_desc",
+        action: || Action::UnwrapParagraph,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.dedent_selection",
         desc_key: "cmd.dedent_selection_desc",
@@ -1016,6 +1289,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.fix_next_problem",
+        desc_key: "cmd.fix_next_problem_desc",
+        action: || Action::FixNextProblem,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // LSP
     CommandDef {
         name_key: "cmd.rename_symbol",
@@ -1032,6 +1312,34 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_line_mark",
+        desc_key: "cmd.toggle_line_mark_desc",
+        action: || Action::ToggleLineMark,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.next_line_mark",
+        desc_key: "cmd.next_line_mark_desc",
+        action: || Action::NextLineMark,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.prev_line_mark",
+        desc_key: "cmd.prev_line_mark_desc",
+        action: || Action::PrevLineMark,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.clear_line_marks",
+        desc_key: "cmd.clear_line_marks_desc",
+        action: || Action::ClearLineMarks,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.list_macros",
         desc_key: "cmd.list_macros_desc",
@@ -1067,6 +1375,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.save_macro",
+        desc_key: "cmd.save_macro_desc",
+        action: || Action::PromptSaveMacro,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.load_macro",
+        desc_key: "cmd.load_macro_desc",
+        action: || Action::PromptLoadMacro,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.list_saved_macros",
+        desc_key: "cmd.list_saved_macros_desc",
+        action: || Action::ListSavedMacros,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.set_bookmark",
         desc_key: "cmd.set_bookmark_desc",
@@ -1081,6 +1410,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.bookmark_set",
+        desc_key: "cmd.bookmark_set_desc",
+        action: || Action::BookmarkSet,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.bookmark_goto",
+        desc_key: "cmd.bookmark_goto_desc",
+        action: || Action::BookmarkGoto,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.open_bookmark_picker",
+        desc_key: "cmd.open_bookmark_picker_desc",
+        action: || Action::OpenBookmarkPicker,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Help
     CommandDef {
         name_key: "cmd.show_manual",
@@ -1089,6 +1439,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.open_tutorial",
+        desc_key: "cmd.open_tutorial_desc",
+        action: || Action::OpenTutorial,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.show_keyboard_shortcuts",
         desc_key: "cmd.show_keyboard_shortcuts_desc",
@@ -1103,6 +1460,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.show_messages",
+        desc_key: "cmd.show_messages_desc",
+        action: || Action::ShowMessages,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.show_lsp_status",
         desc_key: "cmd.show_lsp_status_desc",
@@ -1125,6 +1489,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.manage_workspace_trust",
+        desc_key: "cmd.manage_workspace_trust_desc",
+        action: || Action::ManageWorkspaceTrust,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_inlay_hints",
         desc_key: "cmd.toggle_inlay_hints_desc",
@@ -1204,6 +1575,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.open_remote_terminal",
+        desc_key: "cmd.open_remote_terminal_desc",
+        action: || Action::OpenRemoteTerminal,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.focus_terminal",
         desc_key: "cmd.focus_terminal_desc",
@@ -1240,6 +1618,34 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.prettify",
+        desc_key: "cmd.prettify_desc",
+        action: || Action::Prettify,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.export_html",
+        desc_key: "cmd.export_html_desc",
+        action: || Action::ExportHtml,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.export_settings_bundle",
+        desc_key: "cmd.export_settings_bundle_desc",
+        action: || Action::ExportSettingsBundle,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.import_settings_bundle",
+        desc_key: "cmd.import_settings_bundle_desc",
+        action: || Action::ImportSettingsBundle,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Debugging
     CommandDef {
         name_key: "cmd.event_debug",
@@ -1248,6 +1654,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.show_undo_history_sizes",
+        desc_key: "cmd.show_undo_history_sizes_desc",
+        action: || Action::ShowUndoHistorySizes,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.diagnose_buffer_performance",
+        desc_key: "cmd.diagnose_buffer_performance_desc",
+        action: || Action::DiagnoseBufferPerformance,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     // Plugin development
     CommandDef {
         name_key: "cmd.load_plugin_from_buffer",
@@ -159,6 +159,10 @@ pub enum DeferredAction {
     PopupBackspace,
     /// Copy text to clipboard (from popup text selection)
     CopyToClipboard(String),
+    /// Scroll the peek popup by a number of lines
+    PeekScroll(i32),
+    /// Switch the peek popup to the next (+1) or previous (-1) location
+    PeekSelectLocation(i32),
 
     // File browser actions
     FileBrowserSelectPrev,
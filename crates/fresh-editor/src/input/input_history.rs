@@ -248,6 +248,11 @@ impl InputHistory {
         }
     }
 
+    /// Whether a history item is currently being viewed
+    pub fn is_navigating(&self) -> bool {
+        self.position.is_some()
+    }
+
     /// Reset navigation state
     ///
     /// Call this when:
@@ -290,6 +290,7 @@ pub enum Action {
     SelectAll,
     SelectWord,
     SelectLine,
+    SelectParagraph,
     ExpandSelection,
 
     // Block/rectangular selection (column-wise)
@@ -319,6 +320,7 @@ pub enum Action {
     // Clipboard
     Copy,
     CopyWithTheme(String),
+    CopyAsRichText,
     Cut,
     Paste,
 
@@ -333,11 +335,23 @@ pub enum Action {
     AddCursorBelow,
     AddCursorNextMatch,
     RemoveSecondaryCursors,
+    ClearSecondaryCursors,
+    SplitSelectionIntoLines,
+    MergeCursorsIntoSelection,
+    RotatePrimaryCursorNext,
+    RotatePrimaryCursorPrev,
 
     // File operations
+    SelectionInfo,
     Save,
     SaveAs,
     Open,
+    RecentFiles,
+    FuzzyFindFile,
+    OpenAsText,
+    SaveLocalCopy,
+    WriteCopy,
+    RecoveryCheckpoint,
     SwitchProject,
     New,
     Close,
@@ -347,6 +361,7 @@ pub enum Action {
     Detach,
     Revert,
     ToggleAutoRevert,
+    CancelRemoteTransfer,
     FormatBuffer,
     TrimTrailingWhitespace,
     EnsureFinalNewline,
@@ -357,11 +372,15 @@ pub enum Action {
     GoToMatchingBracket,
     JumpToNextError,
     JumpToPreviousError,
+    FixNextProblem,
 
     // Smart editing
     SmartHome,
     DedentSelection,
     ToggleComment,
+    ReflowParagraph,
+    ReflowParagraphToColumn,
+    UnwrapParagraph,
     ToggleFold,
 
     // Bookmarks
@@ -370,6 +389,12 @@ pub enum Action {
     ClearBookmark(char),
     ListBookmarks,
 
+    // Line marks
+    ToggleLineMark,
+    NextLineMark,
+    PrevLineMark,
+    ClearLineMarks,
+
     // Search options
     ToggleSearchCaseSensitive,
     ToggleSearchWholeWord,
@@ -386,10 +411,16 @@ pub enum Action {
     PromptRecordMacro,
     PromptPlayMacro,
     PlayLastMacro,
+    PromptSaveMacro,
+    PromptLoadMacro,
+    ListSavedMacros,
 
     // Bookmarks (prompt-based)
     PromptSetBookmark,
     PromptJumpToBookmark,
+    BookmarkSet,
+    BookmarkGoto,
+    OpenBookmarkPicker,
 
     // Undo/redo
     Undo,
@@ -399,9 +430,12 @@ pub enum Action {
     ScrollUp,
     ScrollDown,
     ShowHelp,
+    OpenTutorial,
     ShowKeyboardShortcuts,
     ShowWarnings,
     ShowStatusLog,
+    ShowMessages,
+    ToggleMessagesFilter,
     ShowLspStatus,
     ClearWarnings,
     CommandPalette, // TODO: Consider dropping this now that we have QuickOpen
@@ -409,6 +443,7 @@ pub enum Action {
     QuickOpen,
     ToggleLineWrap,
     ToggleReadOnly,
+    ClearFileOverrides,
     ToggleComposeMode,
     SetComposeWidth,
     InspectThemeAtCursor,
@@ -495,6 +530,9 @@ pub enum Action {
     ToggleTabBar,
     // Status bar visibility
     ToggleStatusBar,
+    // UI density presets
+    ZoomIn,
+    ZoomOut,
     // Scrollbar visibility
     ToggleVerticalScrollbar,
     ToggleHorizontalScrollbar,
@@ -521,6 +559,8 @@ pub enum Action {
     LspCompletion,
     LspGotoDefinition,
     LspReferences,
+    LspPeekDefinition,
+    LspPeekReferences,
     LspRename,
     LspHover,
     LspSignatureHelp,
@@ -528,12 +568,19 @@ pub enum Action {
     LspRestart,
     LspStop,
     LspToggleForBuffer,
+    LspShowEnvironment,
     ToggleInlayHints,
     ToggleMouseHover,
 
     // View toggles
     ToggleLineNumbers,
     ToggleScrollSync,
+    TogglePrivacyMode,
+    DiffBuffers,     // Compare two open buffers side by side
+    DiffNextHunk,    // Next difference/conflict in a diff or merge session
+    DiffPrevHunk,    // Previous difference/conflict in a diff or merge session
+    DiffAcceptLeft,  // Write the left version of a change into the output file
+    DiffAcceptRight, // Write the right version of a change into the output file
     ToggleMouseCapture,
     ToggleDebugHighlights, // Debug mode: show highlight/overlay byte ranges
     SetBackground,
@@ -545,6 +592,7 @@ pub enum Action {
     SetEncoding,
     ReloadWithEncoding,
     SetLanguage,
+    ReloadGrammars,
     ToggleIndentationStyle,
     ToggleTabIndicators,
     ToggleWhitespaceIndicators,
@@ -554,6 +602,7 @@ pub enum Action {
 
     // Config operations
     DumpConfig,
+    ManageWorkspaceTrust, // List and revoke workspace trust decisions
 
     // Search and replace
     Search,
@@ -564,6 +613,9 @@ pub enum Action {
     FindSelectionPrevious, // Quick find previous occurrence of selection (Ctrl+Shift+F3)
     Replace,
     QueryReplace, // Interactive replace (y/n/!/q for each match)
+    ProjectSearch,
+    ProjectSearchOpenResult, // Enter in the *Project Search* buffer
+    ProjectReplace,
 
     // Menu navigation
     MenuActivate,     // Open menu bar (Alt or F10)
@@ -595,6 +647,7 @@ pub enum Action {
 
     // Terminal operations
     OpenTerminal,          // Open a new terminal in the current split
+    OpenRemoteTerminal,    // Open a terminal on the active buffer's remote host
     CloseTerminal,         // Close the current terminal
     FocusTerminal,         // Focus the terminal buffer (if viewing terminal, focus input)
     TerminalEscape,        // Escape from terminal mode back to editor
@@ -604,17 +657,30 @@ pub enum Action {
     // Shell command operations
     ShellCommand,        // Run shell command on buffer/selection, output to new buffer
     ShellCommandReplace, // Run shell command on buffer/selection, replace content
+    Prettify,            // Pipe the whole buffer through the language's formatter
 
+    ExportHtml,           // Export buffer/selection as syntax-highlighted HTML
+    ExportSettingsBundle, // Save user settings to a portable bundle file
+    ImportSettingsBundle, // Review and apply settings from a bundle file
     // Case conversion
-    ToUpperCase, // Convert selection to uppercase
-    ToLowerCase, // Convert selection to lowercase
-    SortLines,   // Sort selected lines alphabetically
+    ToUpperCase,          // Convert selection to uppercase
+    ToLowerCase,          // Convert selection to lowercase
+    ToggleCaseAndAdvance, // Toggle case of char under cursor and move right
+    SortLines,            // Sort selected lines alphabetically
+    SortLinesNumeric,     // Sort selected lines by their leading number
+    SortLinesReverse,     // Sort selected lines in reverse order
+
+    // Number increment
+    IncrementNumber(i64), // Add to the number at or after each cursor (negative decrements)
+    IncrementNumberSequential(i64), // Add 1x, 2x, 3x... the amount across cursors in order
 
     // Input calibration
     CalibrateInput, // Open the input calibration wizard
 
     // Event debug
-    EventDebug, // Open the event debug dialog
+    EventDebug,                // Open the event debug dialog
+    ShowUndoHistorySizes,      // Show per-buffer undo history sizes
+    DiagnoseBufferPerformance, // Report what makes the active buffer slow
 
     // Keybinding editor
     OpenKeybindingEditor, // Open the keybinding editor modal
@@ -720,6 +786,7 @@ impl Action {
             "select_all" => SelectAll,
             "select_word" => SelectWord,
             "select_line" => SelectLine,
+            "select_paragraph" => SelectParagraph,
             "expand_selection" => ExpandSelection,
 
             "block_select_left" => BlockSelectLeft,
@@ -741,6 +808,7 @@ impl Action {
             "set_mark" => SetMark,
 
             "copy" => Copy,
+            "copy_as_rich_text" => CopyAsRichText,
             "cut" => Cut,
             "paste" => Paste,
 
@@ -753,10 +821,22 @@ impl Action {
             "add_cursor_below" => AddCursorBelow,
             "add_cursor_next_match" => AddCursorNextMatch,
             "remove_secondary_cursors" => RemoveSecondaryCursors,
+            "clear_secondary_cursors" => ClearSecondaryCursors,
+            "split_selection_into_lines" => SplitSelectionIntoLines,
+            "merge_cursors_into_selection" => MergeCursorsIntoSelection,
+            "rotate_primary_cursor_next" => RotatePrimaryCursorNext,
+            "rotate_primary_cursor_prev" => RotatePrimaryCursorPrev,
+            "selection_info" => SelectionInfo,
 
             "save" => Save,
             "save_as" => SaveAs,
             "open" => Open,
+            "recent_files" => RecentFiles,
+            "fuzzy_find_file" => FuzzyFindFile,
+            "open_as_text" => OpenAsText,
+            "save_local_copy" => SaveLocalCopy,
+            "write_copy" => WriteCopy,
+            "checkpoint" => RecoveryCheckpoint,
             "switch_project" => SwitchProject,
             "new" => New,
             "close" => Close,
@@ -766,20 +846,30 @@ impl Action {
             "detach" => Detach,
             "revert" => Revert,
             "toggle_auto_revert" => ToggleAutoRevert,
+            "cancel_remote_transfer" => CancelRemoteTransfer,
             "format_buffer" => FormatBuffer,
             "goto_line" => GotoLine,
             "scan_line_index" => ScanLineIndex,
             "goto_matching_bracket" => GoToMatchingBracket,
             "jump_to_next_error" => JumpToNextError,
             "jump_to_previous_error" => JumpToPreviousError,
+            "fix_next_problem" => FixNextProblem,
 
             "smart_home" => SmartHome,
             "dedent_selection" => DedentSelection,
             "toggle_comment" => ToggleComment,
+            "reflow_paragraph" => ReflowParagraph,
+            "reflow_paragraph_to_column" => ReflowParagraphToColumn,
+            "unwrap_paragraph" => UnwrapParagraph,
             "toggle_fold" => ToggleFold,
 
             "list_bookmarks" => ListBookmarks,
 
+            "toggle_line_mark" => ToggleLineMark,
+            "next_line_mark" => NextLineMark,
+            "prev_line_mark" => PrevLineMark,
+            "clear_line_marks" => ClearLineMarks,
+
             "toggle_search_case_sensitive" => ToggleSearchCaseSensitive,
             "toggle_search_whole_word" => ToggleSearchWholeWord,
             "toggle_search_regex" => ToggleSearchRegex,
@@ -792,8 +882,14 @@ impl Action {
             "prompt_record_macro" => PromptRecordMacro,
             "prompt_play_macro" => PromptPlayMacro,
             "play_last_macro" => PlayLastMacro,
+            "prompt_save_macro" => PromptSaveMacro,
+            "prompt_load_macro" => PromptLoadMacro,
+            "list_saved_macros" => ListSavedMacros,
             "prompt_set_bookmark" => PromptSetBookmark,
             "prompt_jump_to_bookmark" => PromptJumpToBookmark,
+            "bookmark_set" => BookmarkSet,
+            "bookmark_goto" => BookmarkGoto,
+            "open_bookmark_picker" => OpenBookmarkPicker,
 
             "undo" => Undo,
             "redo" => Redo,
@@ -801,15 +897,19 @@ impl Action {
             "scroll_up" => ScrollUp,
             "scroll_down" => ScrollDown,
             "show_help" => ShowHelp,
+            "open_tutorial" => OpenTutorial,
             "keyboard_shortcuts" => ShowKeyboardShortcuts,
             "show_warnings" => ShowWarnings,
             "show_status_log" => ShowStatusLog,
+            "show_messages" => ShowMessages,
+            "toggle_messages_filter" => ToggleMessagesFilter,
             "show_lsp_status" => ShowLspStatus,
             "clear_warnings" => ClearWarnings,
             "command_palette" => CommandPalette,
             "quick_open" => QuickOpen,
             "toggle_line_wrap" => ToggleLineWrap,
             "toggle_read_only" => ToggleReadOnly,
+            "clear_file_overrides" => ClearFileOverrides,
             "toggle_compose_mode" => ToggleComposeMode,
             "set_compose_width" => SetComposeWidth,
 
@@ -869,6 +969,8 @@ impl Action {
             "toggle_file_explorer" => ToggleFileExplorer,
             "toggle_menu_bar" => ToggleMenuBar,
             "toggle_tab_bar" => ToggleTabBar,
+            "zoom_in" => ZoomIn,
+            "zoom_out" => ZoomOut,
             "toggle_vertical_scrollbar" => ToggleVerticalScrollbar,
             "toggle_horizontal_scrollbar" => ToggleHorizontalScrollbar,
             "focus_file_explorer" => FocusFileExplorer,
@@ -893,6 +995,8 @@ impl Action {
             "lsp_completion" => LspCompletion,
             "lsp_goto_definition" => LspGotoDefinition,
             "lsp_references" => LspReferences,
+            "lsp_peek_definition" => LspPeekDefinition,
+            "lsp_peek_references" => LspPeekReferences,
             "lsp_rename" => LspRename,
             "lsp_hover" => LspHover,
             "lsp_signature_help" => LspSignatureHelp,
@@ -900,11 +1004,18 @@ impl Action {
             "lsp_restart" => LspRestart,
             "lsp_stop" => LspStop,
             "lsp_toggle_for_buffer" => LspToggleForBuffer,
+            "lsp_show_environment" => LspShowEnvironment,
             "toggle_inlay_hints" => ToggleInlayHints,
             "toggle_mouse_hover" => ToggleMouseHover,
 
             "toggle_line_numbers" => ToggleLineNumbers,
             "toggle_scroll_sync" => ToggleScrollSync,
+            "toggle_privacy_mode" => TogglePrivacyMode,
+            "diff_buffers" => DiffBuffers,
+            "diff_next_hunk" => DiffNextHunk,
+            "diff_prev_hunk" => DiffPrevHunk,
+            "diff_accept_left" => DiffAcceptLeft,
+            "diff_accept_right" => DiffAcceptRight,
             "toggle_mouse_capture" => ToggleMouseCapture,
             "toggle_debug_highlights" => ToggleDebugHighlights,
             "set_background" => SetBackground,
@@ -918,12 +1029,14 @@ impl Action {
             "set_line_ending" => SetLineEnding,
             "set_encoding" => SetEncoding,
             "reload_with_encoding" => ReloadWithEncoding,
+            "reload_grammars" => ReloadGrammars,
             "toggle_indentation_style" => ToggleIndentationStyle,
             "toggle_tab_indicators" => ToggleTabIndicators,
             "toggle_whitespace_indicators" => ToggleWhitespaceIndicators,
             "reset_buffer_settings" => ResetBufferSettings,
 
             "dump_config" => DumpConfig,
+            "manage_workspace_trust" => ManageWorkspaceTrust,
 
             "search" => Search,
             "find_in_selection" => FindInSelection,
@@ -933,6 +1046,9 @@ impl Action {
             "find_selection_previous" => FindSelectionPrevious,
             "replace" => Replace,
             "query_replace" => QueryReplace,
+            "project_search" => ProjectSearch,
+            "project_search_open_result" => ProjectSearchOpenResult,
+            "project_replace" => ProjectReplace,
 
             "menu_activate" => MenuActivate,
             "menu_close" => MenuClose,
@@ -943,6 +1059,7 @@ impl Action {
             "menu_execute" => MenuExecute,
 
             "open_terminal" => OpenTerminal,
+            "open_remote_terminal" => OpenRemoteTerminal,
             "close_terminal" => CloseTerminal,
             "focus_terminal" => FocusTerminal,
             "terminal_escape" => TerminalEscape,
@@ -951,13 +1068,22 @@ impl Action {
 
             "shell_command" => ShellCommand,
             "shell_command_replace" => ShellCommandReplace,
+            "prettify" => Prettify,
+            "export_html" => ExportHtml,
+            "export_settings_bundle" => ExportSettingsBundle,
+            "import_settings_bundle" => ImportSettingsBundle,
 
             "to_upper_case" => ToUpperCase,
             "to_lower_case" => ToLowerCase,
+            "toggle_case_and_advance" => ToggleCaseAndAdvance,
             "sort_lines" => SortLines,
+            "sort_lines_numeric" => SortLinesNumeric,
+            "sort_lines_reverse" => SortLinesReverse,
 
             "calibrate_input" => CalibrateInput,
             "event_debug" => EventDebug,
+            "show_undo_history_sizes" => ShowUndoHistorySizes,
+            "diagnose_buffer_performance" => DiagnoseBufferPerformance,
             "load_plugin_from_buffer" => LoadPluginFromBuffer,
             "open_keybinding_editor" => OpenKeybindingEditor,
 
@@ -997,6 +1123,18 @@ impl Action {
                 let map_name = args.get("map")?.as_str()?;
                 Self::SwitchKeybindingMap(map_name.to_string())
             },
+            "increment_number" => {
+                let amount = args.get("amount").and_then(|v| v.as_i64()).unwrap_or(1);
+                Self::IncrementNumber(amount)
+            },
+            "decrement_number" => {
+                let amount = args.get("amount").and_then(|v| v.as_i64()).unwrap_or(1);
+                Self::IncrementNumber(amount.checked_neg()?)
+            },
+            "increment_number_sequential" => {
+                let amount = args.get("amount").and_then(|v| v.as_i64()).unwrap_or(1);
+                Self::IncrementNumberSequential(amount)
+            },
         }
     }
 
@@ -1038,7 +1176,10 @@ impl Action {
                 | Action::SelectAll
                 | Action::SelectWord
                 | Action::SelectLine
+                | Action::SelectParagraph
                 | Action::ExpandSelection
+                | Action::SplitSelectionIntoLines
+                | Action::MergeCursorsIntoSelection
                 // Block selection
                 | Action::BlockSelectLeft
                 | Action::BlockSelectRight
@@ -1056,6 +1197,11 @@ impl Action {
                 | Action::DeleteToLineEnd
                 | Action::DeleteToLineStart
                 | Action::TransposeChars
+                | Action::ToggleCaseAndAdvance
+                | Action::IncrementNumber(_)
+                | Action::IncrementNumberSequential(_)
+                | Action::ReflowParagraph
+                | Action::UnwrapParagraph
                 | Action::OpenLine
                 | Action::DuplicateLine
                 | Action::MoveLineUp
@@ -1328,6 +1474,7 @@ impl KeybindingResolver {
                 | Action::TerminalEscape
                 | Action::ToggleKeyboardCapture
                 | Action::OpenTerminal
+                | Action::OpenRemoteTerminal
                 | Action::CloseTerminal
                 | Action::TerminalPaste
                 // File explorer
@@ -1699,17 +1846,21 @@ impl KeybindingResolver {
 
     /// Parse modifiers from strings
     fn parse_modifiers(modifiers: &[String]) -> KeyModifiers {
-        let mut result = KeyModifiers::empty();
-        for m in modifiers {
-            match m.to_lowercase().as_str() {
-                "ctrl" | "control" => result |= KeyModifiers::CONTROL,
-                "shift" => result |= KeyModifiers::SHIFT,
-                "alt" => result |= KeyModifiers::ALT,
-                "super" | "cmd" | "command" | "meta" => result |= KeyModifiers::SUPER,
-                _ => {}
-            }
-        }
-        result
+        modifiers
+            .iter()
+            .filter_map(|m| Self::parse_modifier(m))
+            .fold(KeyModifiers::empty(), |result, m| result | m)
+    }
+
+    /// Parse one modifier name; unknown names are None
+    fn parse_modifier(modifier: &str) -> Option<KeyModifiers> {
+        Some(match modifier.to_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "shift" => KeyModifiers::SHIFT,
+            "alt" => KeyModifiers::ALT,
+            "super" | "cmd" | "command" | "meta" => KeyModifiers::SUPER,
+            _ => return None,
+        })
     }
 
     /// Create default keybindings organized by context
@@ -1806,6 +1957,7 @@ impl KeybindingResolver {
             Action::SelectAll => t!("action.select_all"),
             Action::SelectWord => t!("action.select_word"),
             Action::SelectLine => t!("action.select_line"),
+            Action::SelectParagraph => t!("action.select_paragraph"),
             Action::ExpandSelection => t!("action.expand_selection"),
             Action::BlockSelectLeft => t!("action.block_select_left"),
             Action::BlockSelectRight => t!("action.block_select_right"),
@@ -1825,6 +1977,7 @@ impl KeybindingResolver {
             Action::SetMark => t!("action.set_mark"),
             Action::Copy => t!("action.copy"),
             Action::CopyWithTheme(theme) if theme.is_empty() => t!("action.copy_with_formatting"),
+            Action::CopyAsRichText => t!("action.copy_as_rich_text"),
             Action::CopyWithTheme(theme) => t!("action.copy_with_theme", theme = theme),
             Action::Cut => t!("action.cut"),
             Action::Paste => t!("action.paste"),
@@ -1836,9 +1989,21 @@ impl KeybindingResolver {
             Action::AddCursorBelow => t!("action.add_cursor_below"),
             Action::AddCursorNextMatch => t!("action.add_cursor_next_match"),
             Action::RemoveSecondaryCursors => t!("action.remove_secondary_cursors"),
+            Action::ClearSecondaryCursors => t!("action.clear_secondary_cursors"),
+            Action::SplitSelectionIntoLines => t!("action.split_selection_into_lines"),
+            Action::MergeCursorsIntoSelection => t!("action.merge_cursors_into_selection"),
+            Action::RotatePrimaryCursorNext => t!("action.rotate_primary_cursor_next"),
+            Action::RotatePrimaryCursorPrev => t!("action.rotate_primary_cursor_prev"),
+            Action::SelectionInfo => t!("action.selection_info"),
             Action::Save => t!("action.save"),
             Action::SaveAs => t!("action.save_as"),
             Action::Open => t!("action.open"),
+            Action::RecentFiles => t!("action.recent_files"),
+            Action::FuzzyFindFile => t!("action.fuzzy_find_file"),
+            Action::OpenAsText => t!("action.open_as_text"),
+            Action::SaveLocalCopy => t!("action.save_local_copy"),
+            Action::WriteCopy => t!("action.write_copy"),
+            Action::RecoveryCheckpoint => t!("action.checkpoint"),
             Action::SwitchProject => t!("action.switch_project"),
             Action::New => t!("action.new"),
             Action::Close => t!("action.close"),
@@ -1848,6 +2013,7 @@ impl KeybindingResolver {
             Action::Detach => t!("action.detach"),
             Action::Revert => t!("action.revert"),
             Action::ToggleAutoRevert => t!("action.toggle_auto_revert"),
+            Action::CancelRemoteTransfer => t!("action.cancel_remote_transfer"),
             Action::FormatBuffer => t!("action.format_buffer"),
             Action::TrimTrailingWhitespace => t!("action.trim_trailing_whitespace"),
             Action::EnsureFinalNewline => t!("action.ensure_final_newline"),
@@ -1856,14 +2022,22 @@ impl KeybindingResolver {
             Action::GoToMatchingBracket => t!("action.goto_matching_bracket"),
             Action::JumpToNextError => t!("action.jump_to_next_error"),
             Action::JumpToPreviousError => t!("action.jump_to_previous_error"),
+            Action::FixNextProblem => t!("action.fix_next_problem"),
             Action::SmartHome => t!("action.smart_home"),
             Action::DedentSelection => t!("action.dedent_selection"),
             Action::ToggleComment => t!("action.toggle_comment"),
+            Action::ReflowParagraph => t!("action.reflow_paragraph"),
+            Action::ReflowParagraphToColumn => t!("action.reflow_paragraph_to_column"),
+            Action::UnwrapParagraph => t!("action.unwrap_paragraph"),
             Action::ToggleFold => t!("action.toggle_fold"),
             Action::SetBookmark(c) => t!("action.set_bookmark", key = c),
             Action::JumpToBookmark(c) => t!("action.jump_to_bookmark", key = c),
             Action::ClearBookmark(c) => t!("action.clear_bookmark", key = c),
             Action::ListBookmarks => t!("action.list_bookmarks"),
+            Action::ToggleLineMark => t!("action.toggle_line_mark"),
+            Action::NextLineMark => t!("action.next_line_mark"),
+            Action::PrevLineMark => t!("action.prev_line_mark"),
+            Action::ClearLineMarks => t!("action.clear_line_marks"),
             Action::ToggleSearchCaseSensitive => t!("action.toggle_search_case_sensitive"),
             Action::ToggleSearchWholeWord => t!("action.toggle_search_whole_word"),
             Action::ToggleSearchRegex => t!("action.toggle_search_regex"),
@@ -1877,16 +2051,25 @@ impl KeybindingResolver {
             Action::PromptRecordMacro => t!("action.prompt_record_macro"),
             Action::PromptPlayMacro => t!("action.prompt_play_macro"),
             Action::PlayLastMacro => t!("action.play_last_macro"),
+            Action::PromptSaveMacro => t!("action.prompt_save_macro"),
+            Action::PromptLoadMacro => t!("action.prompt_load_macro"),
+            Action::ListSavedMacros => t!("action.list_saved_macros"),
             Action::PromptSetBookmark => t!("action.prompt_set_bookmark"),
             Action::PromptJumpToBookmark => t!("action.prompt_jump_to_bookmark"),
+            Action::BookmarkSet => t!("action.bookmark_set"),
+            Action::BookmarkGoto => t!("action.bookmark_goto"),
+            Action::OpenBookmarkPicker => t!("action.open_bookmark_picker"),
             Action::Undo => t!("action.undo"),
             Action::Redo => t!("action.redo"),
             Action::ScrollUp => t!("action.scroll_up"),
             Action::ScrollDown => t!("action.scroll_down"),
             Action::ShowHelp => t!("action.show_help"),
+            Action::OpenTutorial => t!("action.open_tutorial"),
             Action::ShowKeyboardShortcuts => t!("action.show_keyboard_shortcuts"),
             Action::ShowWarnings => t!("action.show_warnings"),
             Action::ShowStatusLog => t!("action.show_status_log"),
+            Action::ShowMessages => t!("action.show_messages"),
+            Action::ToggleMessagesFilter => t!("action.toggle_messages_filter"),
             Action::ShowLspStatus => t!("action.show_lsp_status"),
             Action::ClearWarnings => t!("action.clear_warnings"),
             Action::CommandPalette => t!("action.command_palette"),
@@ -1894,6 +2077,7 @@ impl KeybindingResolver {
             Action::InspectThemeAtCursor => t!("action.inspect_theme_at_cursor"),
             Action::ToggleLineWrap => t!("action.toggle_line_wrap"),
             Action::ToggleReadOnly => t!("action.toggle_read_only"),
+            Action::ClearFileOverrides => t!("action.clear_file_overrides"),
             Action::ToggleComposeMode => t!("action.toggle_compose_mode"),
             Action::SetComposeWidth => t!("action.set_compose_width"),
             Action::NextBuffer => t!("action.next_buffer"),
@@ -1953,6 +2137,8 @@ impl KeybindingResolver {
             Action::ToggleMenuBar => t!("action.toggle_menu_bar"),
             Action::ToggleTabBar => t!("action.toggle_tab_bar"),
             Action::ToggleStatusBar => t!("action.toggle_status_bar"),
+            Action::ZoomIn => t!("action.zoom_in"),
+            Action::ZoomOut => t!("action.zoom_out"),
             Action::ToggleVerticalScrollbar => t!("action.toggle_vertical_scrollbar"),
             Action::ToggleHorizontalScrollbar => t!("action.toggle_horizontal_scrollbar"),
             Action::FocusFileExplorer => t!("action.focus_file_explorer"),
@@ -1976,6 +2162,8 @@ impl KeybindingResolver {
             Action::LspCompletion => t!("action.lsp_completion"),
             Action::LspGotoDefinition => t!("action.lsp_goto_definition"),
             Action::LspReferences => t!("action.lsp_references"),
+            Action::LspPeekDefinition => t!("action.lsp_peek_definition"),
+            Action::LspPeekReferences => t!("action.lsp_peek_references"),
             Action::LspRename => t!("action.lsp_rename"),
             Action::LspHover => t!("action.lsp_hover"),
             Action::LspSignatureHelp => t!("action.lsp_signature_help"),
@@ -1983,10 +2171,17 @@ impl KeybindingResolver {
             Action::LspRestart => t!("action.lsp_restart"),
             Action::LspStop => t!("action.lsp_stop"),
             Action::LspToggleForBuffer => t!("action.lsp_toggle_for_buffer"),
+            Action::LspShowEnvironment => t!("action.lsp_show_environment"),
             Action::ToggleInlayHints => t!("action.toggle_inlay_hints"),
             Action::ToggleMouseHover => t!("action.toggle_mouse_hover"),
             Action::ToggleLineNumbers => t!("action.toggle_line_numbers"),
             Action::ToggleScrollSync => t!("action.toggle_scroll_sync"),
+            Action::TogglePrivacyMode => t!("action.toggle_privacy_mode"),
+            Action::DiffBuffers => t!("action.diff_buffers"),
+            Action::DiffNextHunk => t!("action.diff_next_hunk"),
+            Action::DiffPrevHunk => t!("action.diff_prev_hunk"),
+            Action::DiffAcceptLeft => t!("action.diff_accept_left"),
+            Action::DiffAcceptRight => t!("action.diff_accept_right"),
             Action::ToggleMouseCapture => t!("action.toggle_mouse_capture"),
             Action::ToggleDebugHighlights => t!("action.toggle_debug_highlights"),
             Action::SetBackground => t!("action.set_background"),
@@ -1998,11 +2193,13 @@ impl KeybindingResolver {
             Action::SetEncoding => t!("action.set_encoding"),
             Action::ReloadWithEncoding => t!("action.reload_with_encoding"),
             Action::SetLanguage => t!("action.set_language"),
+            Action::ReloadGrammars => t!("action.reload_grammars"),
             Action::ToggleIndentationStyle => t!("action.toggle_indentation_style"),
             Action::ToggleTabIndicators => t!("action.toggle_tab_indicators"),
             Action::ToggleWhitespaceIndicators => t!("action.toggle_whitespace_indicators"),
             Action::ResetBufferSettings => t!("action.reset_buffer_settings"),
             Action::DumpConfig => t!("action.dump_config"),
+            Action::ManageWorkspaceTrust => t!("action.manage_workspace_trust"),
             Action::Search => t!("action.search"),
             Action::FindInSelection => t!("action.find_in_selection"),
             Action::FindNext => t!("action.find_next"),
@@ -2011,6 +2208,9 @@ impl KeybindingResolver {
             Action::FindSelectionPrevious => t!("action.find_selection_previous"),
             Action::Replace => t!("action.replace"),
             Action::QueryReplace => t!("action.query_replace"),
+            Action::ProjectSearch => t!("action.project_search"),
+            Action::ProjectSearchOpenResult => t!("action.project_search_open_result"),
+            Action::ProjectReplace => t!("action.project_replace"),
             Action::MenuActivate => t!("action.menu_activate"),
             Action::MenuClose => t!("action.menu_close"),
             Action::MenuLeft => t!("action.menu_left"),
@@ -2030,6 +2230,7 @@ impl KeybindingResolver {
             Action::SwitchToPreviousTab => t!("action.switch_to_previous_tab"),
             Action::SwitchToTabByName => t!("action.switch_to_tab_by_name"),
             Action::OpenTerminal => t!("action.open_terminal"),
+            Action::OpenRemoteTerminal => t!("action.open_remote_terminal"),
             Action::CloseTerminal => t!("action.close_terminal"),
             Action::FocusTerminal => t!("action.focus_terminal"),
             Action::TerminalEscape => t!("action.terminal_escape"),
@@ -2047,11 +2248,23 @@ impl KeybindingResolver {
             Action::SettingsDecrement => t!("action.settings_decrement"),
             Action::ShellCommand => t!("action.shell_command"),
             Action::ShellCommandReplace => t!("action.shell_command_replace"),
+            Action::Prettify => t!("action.prettify"),
+            Action::ExportHtml => t!("action.export_html"),
+            Action::ExportSettingsBundle => t!("action.export_settings_bundle"),
+            Action::ImportSettingsBundle => t!("action.import_settings_bundle"),
             Action::ToUpperCase => t!("action.to_uppercase"),
             Action::ToLowerCase => t!("action.to_lowercase"),
+            Action::ToggleCaseAndAdvance => t!("action.toggle_case_and_advance"),
             Action::SortLines => t!("action.sort_lines"),
+            Action::SortLinesNumeric => t!("action.sort_lines_numeric"),
+            Action::SortLinesReverse => t!("action.sort_lines_reverse"),
+            Action::IncrementNumber(amount) if *amount < 0 => t!("action.decrement_number"),
+            Action::IncrementNumber(_) => t!("action.increment_number"),
+            Action::IncrementNumberSequential(_) => t!("action.increment_number_sequential"),
             Action::CalibrateInput => t!("action.calibrate_input"),
             Action::EventDebug => t!("action.event_debug"),
+            Action::ShowUndoHistorySizes => t!("action.show_undo_history_sizes"),
+            Action::DiagnoseBufferPerformance => t!("action.diagnose_buffer_performance"),
             Action::LoadPluginFromBuffer => "Load Plugin from Buffer".into(),
             Action::OpenKeybindingEditor => "Keybinding Editor".into(),
             Action::None => t!("action.none"),
@@ -2069,6 +2282,52 @@ impl KeybindingResolver {
         Self::parse_modifiers(modifiers)
     }
 
+    /// Why a configured binding would be dropped or misapplied when loaded:
+    /// an unknown action, a key or modifier that doesn't parse, or an unknown
+    /// `when` context. Empty if the binding is fine.
+    pub fn binding_problems(binding: &crate::config::Keybinding) -> Vec<String> {
+        let mut problems = Vec::new();
+        if Action::from_str(&binding.action, &binding.args).is_none() {
+            if Action::all_action_names().contains(&binding.action) {
+                problems.push(format!("invalid args for action \"{}\"", binding.action));
+            } else {
+                problems.push(format!("unknown action \"{}\"", binding.action));
+            }
+        }
+
+        let presses: Vec<(&str, &[String])> = if binding.keys.is_empty() {
+            vec![(binding.key.as_str(), binding.modifiers.as_slice())]
+        } else {
+            binding
+                .keys
+                .iter()
+                .map(|press| (press.key.as_str(), press.modifiers.as_slice()))
+                .collect()
+        };
+        for (key, modifiers) in presses {
+            if key.is_empty() {
+                problems.push("missing key".to_string());
+            } else if Self::parse_key(key).is_none() {
+                problems.push(format!("unknown key \"{}\"", key));
+            }
+            for modifier in modifiers {
+                if Self::parse_modifier(modifier).is_none() {
+                    problems.push(format!("unknown modifier \"{}\"", modifier));
+                }
+            }
+        }
+
+        if let Some(when) = &binding.when {
+            if KeyContext::from_when_clause(when).is_none() {
+                problems.push(format!(
+                    "unknown context \"{}\" in \"when\", binding applies in normal mode",
+                    when
+                ));
+            }
+        }
+        problems
+    }
+
     /// Format an action name string as a human-readable description.
     /// Used by the keybinding editor to display action names without needing
     /// a full Action enum parse.
@@ -2310,6 +2569,31 @@ mod tests {
         assert_eq!(KeyContext::from_when_clause(""), None);
     }
 
+    #[test]
+    fn test_binding_problems() {
+        let binding: crate::config::Keybinding = serde_json::from_value(serde_json::json!({
+            "key": "s", "modifiers": ["ctrl"], "action": "save"
+        }))
+        .unwrap();
+        assert!(KeybindingResolver::binding_problems(&binding).is_empty());
+
+        let binding: crate::config::Keybinding = serde_json::from_value(serde_json::json!({
+            "keys": [{"key": "x", "modifiers": ["ctl"]}, {"key": "nope"}],
+            "action": "sav",
+            "when": "editor"
+        }))
+        .unwrap();
+        assert_eq!(
+            KeybindingResolver::binding_problems(&binding),
+            vec![
+                "unknown action \"sav\"",
+                "unknown modifier \"ctl\"",
+                "unknown key \"nope\"",
+                "unknown context \"editor\" in \"when\", binding applies in normal mode",
+            ]
+        );
+    }
+
     #[test]
     fn test_key_context_to_when_clause() {
         assert_eq!(KeyContext::Normal.to_when_clause(), "normal");
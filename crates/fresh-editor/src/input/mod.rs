@@ -14,8 +14,11 @@ pub mod key_translator;
 pub mod keybindings;
 mod line_move;
 pub mod multi_cursor;
+mod number_increment;
 pub mod position_history;
 pub mod quick_open;
+pub(crate) mod reflow;
+pub(crate) mod sort_lines;
 
 #[cfg(test)]
 pub mod tests_language_features;
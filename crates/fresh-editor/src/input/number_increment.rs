@@ -0,0 +1,278 @@
+//! Increment/decrement of the number under (or after) the cursor.
+//!
+//! Decimal, hexadecimal (`0x`) and binary (`0b`) literals are recognized.
+//! Zero-padding is preserved, as is the letter case of hex digits: when any
+//! digit is uppercase, the result is written in uppercase.
+
+use crate::model::cursor::Cursors;
+use crate::model::event::Event;
+use crate::state::EditorState;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Radix {
+    Binary,
+    Decimal,
+    Hex,
+}
+
+impl Radix {
+    fn value(self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Decimal => 10,
+            Radix::Hex => 16,
+        }
+    }
+}
+
+/// A number literal found in a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NumberToken {
+    /// Byte range of the whole literal, including sign and prefix
+    range: Range<usize>,
+    /// Byte range of the digits only
+    digits: Range<usize>,
+    radix: Radix,
+    negative: bool,
+}
+
+/// Find the number literal at or after byte column `col` in `line`.
+fn find_number(line: &str, col: usize) -> Option<NumberToken> {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let prefixed = |marker: u8, is_digit: fn(&u8) -> bool| {
+            bytes[i] == b'0'
+                && bytes.get(i + 1).map(u8::to_ascii_lowercase) == Some(marker)
+                && bytes.get(i + 2).is_some_and(is_digit)
+        };
+        let (radix, digits_start) = if prefixed(b'x', u8::is_ascii_hexdigit) {
+            (Radix::Hex, i + 2)
+        } else if prefixed(b'b', |b| matches!(b, b'0' | b'1')) {
+            (Radix::Binary, i + 2)
+        } else {
+            (Radix::Decimal, i)
+        };
+        let digits_len = bytes[digits_start..]
+            .iter()
+            .take_while(|b| char::from(**b).is_digit(radix.value()))
+            .count();
+        let digits = digits_start..digits_start + digits_len;
+
+        // A minus sign directly before a decimal number makes it negative,
+        // unless it is part of a word (as in `x-1`)
+        let negative = radix == Radix::Decimal
+            && i > 0
+            && bytes[i - 1] == b'-'
+            && !(i > 1 && (bytes[i - 2].is_ascii_alphanumeric() || bytes[i - 2] == b'_'));
+        let start = if negative { i - 1 } else { i };
+
+        if digits.end > col {
+            return Some(NumberToken {
+                range: start..digits.end,
+                digits,
+                radix,
+                negative,
+            });
+        }
+        i = digits.end;
+    }
+    None
+}
+
+/// Compute the replacement for the number at or after `col` in `line`
+/// after adding `delta`.
+///
+/// Returns the byte range to replace and the new text, or `None` when there
+/// is no number or the result doesn't fit.
+fn increment_in_line(line: &str, col: usize, delta: i128) -> Option<(Range<usize>, String)> {
+    let token = find_number(line, col)?;
+    let digits = &line[token.digits.clone()];
+    let magnitude = i128::from_str_radix(digits, token.radix.value()).ok()?;
+
+    let replacement = match token.radix {
+        Radix::Decimal => {
+            let value = if token.negative {
+                -magnitude
+            } else {
+                magnitude
+            };
+            let result = value.checked_add(delta)?;
+            // Keep the digit count when the number was zero-padded
+            let width = if digits.len() > 1 && digits.starts_with('0') {
+                digits.len()
+            } else {
+                0
+            };
+            let sign = if result < 0 { "-" } else { "" };
+            format!("{}{:0width$}", sign, result.unsigned_abs(), width = width)
+        }
+        Radix::Hex | Radix::Binary => {
+            // Prefixed literals are unsigned and wrap around at 64 bits;
+            // wider literals are left alone rather than truncated
+            let magnitude = u64::try_from(magnitude).ok()?;
+            let result = magnitude.wrapping_add(delta as u64);
+            let prefix = &line[token.range.start..token.digits.start];
+            let width = digits.len();
+            let body = match token.radix {
+                Radix::Binary => format!("{:0width$b}", result, width = width),
+                _ if digits.bytes().any(|b| b.is_ascii_uppercase()) => {
+                    format!("{:0width$X}", result, width = width)
+                }
+                _ => format!("{:0width$x}", result, width = width),
+            };
+            format!("{}{}", prefix, body)
+        }
+    };
+    Some((token.range, replacement))
+}
+
+/// Add `amount` to the number at or after each cursor on its line.
+///
+/// With `sequential`, cursors in document order add `amount`, `2 * amount`,
+/// `3 * amount`, ... which turns copies of one number into a sequence. The
+/// cursor ends on the last character of the new number.
+pub(crate) fn increment_numbers(
+    state: &mut EditorState,
+    cursors: &Cursors,
+    events: &mut Vec<Event>,
+    amount: i64,
+    sequential: bool,
+    estimated_line_length: usize,
+) {
+    let mut targets: Vec<_> = cursors
+        .iter()
+        .map(|(cursor_id, cursor)| (cursor_id, *cursor))
+        .collect();
+    targets.sort_by_key(|(_, cursor)| cursor.position);
+
+    // Compute edits in document order so sequential steps follow the text
+    let mut edits = Vec::new();
+    for (index, (cursor_id, cursor)) in targets.into_iter().enumerate() {
+        let mut iter = state
+            .buffer
+            .line_iterator(cursor.position, estimated_line_length);
+        let line_start = iter.current_position();
+        let Some((_, line)) = iter.next_line() else {
+            continue;
+        };
+        let line = line.trim_end_matches(['\n', '\r']);
+        let step = if sequential { index as i128 + 1 } else { 1 };
+        let Some((range, text)) = increment_in_line(
+            line,
+            cursor.position - line_start,
+            i128::from(amount) * step,
+        ) else {
+            continue;
+        };
+        let range = line_start + range.start..line_start + range.end;
+        // Several cursors on the same number change it once
+        if edits
+            .iter()
+            .any(|(_, _, r, _): &(_, _, Range<usize>, _)| r.start == range.start)
+        {
+            continue;
+        }
+        edits.push((cursor_id, cursor, range, text));
+    }
+
+    // Apply from the end of the buffer so earlier positions stay valid
+    for (cursor_id, cursor, range, text) in edits.into_iter().rev() {
+        let deleted_text = state.get_text_range(range.start, range.end);
+        let new_end = range.start + text.len();
+        events.push(Event::Delete {
+            range: range.clone(),
+            deleted_text,
+            cursor_id,
+        });
+        events.push(Event::Insert {
+            position: range.start,
+            text,
+            cursor_id,
+        });
+        events.push(Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: new_end - 1,
+            old_anchor: cursor.anchor,
+            new_anchor: None,
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: 0,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bump(line: &str, col: usize, delta: i128) -> Option<String> {
+        increment_in_line(line, col, delta).map(|(range, text)| {
+            let mut result = line.to_string();
+            result.replace_range(range, &text);
+            result
+        })
+    }
+
+    #[test]
+    fn test_decimal_at_or_after_cursor() {
+        assert_eq!(bump("x = 41;", 0, 1).as_deref(), Some("x = 42;"));
+        assert_eq!(bump("x = 41;", 5, 1).as_deref(), Some("x = 42;"));
+        assert_eq!(bump("a1 b2", 2, 1).as_deref(), Some("a1 b3"));
+        assert_eq!(bump("no digits", 0, 1), None);
+        assert_eq!(bump("7 then", 3, 1), None);
+    }
+
+    #[test]
+    fn test_negative_numbers() {
+        assert_eq!(bump("-1", 0, 1).as_deref(), Some("0"));
+        assert_eq!(bump("0", 0, -1).as_deref(), Some("-1"));
+        assert_eq!(bump("(-5)", 0, 10).as_deref(), Some("(5)"));
+        // A dash inside a word is not a sign
+        assert_eq!(bump("x-1", 0, 1).as_deref(), Some("x-2"));
+    }
+
+    #[test]
+    fn test_zero_padded_keeps_width() {
+        assert_eq!(bump("file007.txt", 0, 1).as_deref(), Some("file008.txt"));
+        assert_eq!(bump("009", 0, 1).as_deref(), Some("010"));
+        assert_eq!(bump("099", 0, 1).as_deref(), Some("100"));
+        assert_eq!(bump("0999", 0, 1).as_deref(), Some("1000"));
+        assert_eq!(bump("010", 0, -11).as_deref(), Some("-001"));
+        // Unpadded numbers are not padded
+        assert_eq!(bump("10", 0, -1).as_deref(), Some("9"));
+    }
+
+    #[test]
+    fn test_hex_and_binary() {
+        assert_eq!(bump("0xff", 0, 1).as_deref(), Some("0x100"));
+        assert_eq!(bump("0x0f", 3, 1).as_deref(), Some("0x10"));
+        assert_eq!(bump("0x00FF", 0, 1).as_deref(), Some("0x0100"));
+        // Any uppercase digit makes the result uppercase; the prefix is kept
+        assert_eq!(bump("color: 0XaB;", 0, -1).as_deref(), Some("color: 0XAA;"));
+        assert_eq!(bump("0x0", 0, -1).as_deref(), Some("0xffffffffffffffff"));
+        assert_eq!(bump("0b0111", 0, 1).as_deref(), Some("0b1000"));
+        // Hex literals are never negative
+        assert_eq!(bump("-0x10", 0, 1).as_deref(), Some("-0x11"));
+        // `0x` without digits is a plain zero
+        assert_eq!(bump("0x", 0, 1).as_deref(), Some("1x"));
+    }
+
+    #[test]
+    fn test_literals_wider_than_64_bits_are_left_alone() {
+        assert_eq!(bump("0x1ffffffffffffffff", 0, 1), None);
+        assert_eq!(bump(&format!("0b1{}", "0".repeat(64)), 0, -1), None);
+        // Too wide even for 128 bits
+        assert_eq!(bump(&format!("0x{}", "f".repeat(40)), 0, 1), None);
+        assert_eq!(
+            bump("0xffffffffffffffff", 0, -1).as_deref(),
+            Some("0xfffffffffffffffe")
+        );
+    }
+}
@@ -232,6 +232,18 @@ impl PositionHistory {
         self.current_index.and_then(|idx| self.entries.get(idx))
     }
 
+    /// The `limit` most recent entries, newest first, each paired with
+    /// whether it is the current position
+    pub fn recent(&self, limit: usize) -> Vec<(&PositionEntry, bool)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .rev()
+            .take(limit)
+            .map(|(idx, entry)| (entry, self.current_index == Some(idx)))
+            .collect()
+    }
+
     /// Clear all history
     pub fn clear(&mut self) {
         self.entries.clear();
@@ -277,6 +289,22 @@ mod tests {
         assert!(!history.can_go_forward());
     }
 
+    #[test]
+    fn test_recent_is_newest_first_and_marks_current() {
+        let mut history = PositionHistory::new();
+        for position in [10, 20, 30] {
+            history.push(make_entry(1, position));
+        }
+        history.back();
+
+        let recent: Vec<(usize, bool)> = history
+            .recent(2)
+            .into_iter()
+            .map(|(entry, current)| (entry.position, current))
+            .collect();
+        assert_eq!(recent, vec![(30, false), (20, true)]);
+    }
+
     #[test]
     fn test_push_single_entry() {
         let mut history = PositionHistory::new();
@@ -0,0 +1,393 @@
+//! Reflow Paragraph and Unwrap Paragraph.
+//!
+//! A paragraph is a run of non-blank lines sharing the same prefix: the
+//! indentation plus an optional line comment marker (the language's
+//! `comment_prefix`) or blockquote markers (`>`). Reflow re-wraps the words
+//! of a paragraph at the wrap column and repeats the first line's prefix on
+//! every line; unwrap joins the paragraph into a single line.
+
+use crate::model::buffer::Buffer;
+use crate::model::cursor::Cursors;
+use crate::model::event::{CursorId, Event};
+use crate::primitives::display_width::{char_width, str_width};
+use crate::state::EditorState;
+use std::ops::Range;
+
+/// Settings for one reflow invocation
+pub(crate) struct ReflowOptions<'a> {
+    /// Column to wrap at; `None` joins each paragraph into one line
+    pub column: Option<usize>,
+    /// Line comment marker of the buffer's language, e.g. `//`
+    pub comment_prefix: Option<&'a str>,
+    /// Display width of a tab in the prefix
+    pub tab_size: usize,
+}
+
+/// Byte length of a line's prefix: indentation, then a comment marker or
+/// blockquote markers, then the whitespace that follows them
+fn prefix_len(line: &str, comment_prefix: Option<&str>) -> usize {
+    let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+    let rest = &line[indent..];
+    let marker_end = match comment_prefix.filter(|c| !c.is_empty() && rest.starts_with(c)) {
+        Some(marker) => {
+            // Doc comment variants like `///` and `//!` share the marker
+            let tail = &rest[marker.len()..];
+            let decoration = tail.len()
+                - tail
+                    .trim_start_matches(|c: char| marker.contains(c) || c == '!')
+                    .len();
+            marker.len() + decoration
+        }
+        None if rest.starts_with('>') => rest.len() - rest.trim_start_matches(['>', ' ']).len(),
+        None => 0,
+    };
+    let tail = &rest[marker_end..];
+    indent + marker_end + tail.len() - tail.trim_start_matches([' ', '\t']).len()
+}
+
+/// The prefix lines must share to belong to the same paragraph (trailing
+/// whitespace after a marker doesn't matter), or `None` for a blank line
+fn line_key(line: &str, comment_prefix: Option<&str>) -> Option<String> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let (prefix, body) = line.split_at(prefix_len(line, comment_prefix));
+    if body.trim().is_empty() {
+        return None;
+    }
+    let marker = prefix.trim_end();
+    Some(if marker.is_empty() { prefix } else { marker }.to_string())
+}
+
+/// Length of a line without its line ending
+fn content_len(line: &str) -> usize {
+    line.trim_end_matches(['\n', '\r']).len()
+}
+
+/// A rewritten paragraph
+#[derive(Debug)]
+struct Reflowed {
+    text: String,
+    /// Length of the first line's prefix, which is kept as is
+    prefix_len: usize,
+    /// Old byte range and new start of each word, relative to the paragraph
+    words: Vec<(Range<usize>, usize)>,
+}
+
+impl Reflowed {
+    /// Where a position inside the old paragraph ends up: on the same word,
+    /// at the same offset within it
+    fn map_offset(&self, offset: usize) -> usize {
+        if offset <= self.prefix_len {
+            return offset;
+        }
+        for (old, new_start) in &self.words {
+            if offset < old.start {
+                return *new_start;
+            }
+            if offset <= old.end {
+                return new_start + (offset - old.start);
+            }
+        }
+        self.text.len()
+    }
+}
+
+/// Re-wrap the lines of one paragraph at `options.column`, or join them
+fn reflow_text(text: &str, options: &ReflowOptions, newline: &str) -> Reflowed {
+    let mut words = Vec::new();
+    let mut line_start = 0;
+    for line in text.split('\n') {
+        let body_start = line_start + prefix_len(line, options.comment_prefix);
+        let body = &text[body_start..line_start + line.trim_end_matches('\r').len()];
+        let mut word_start = None;
+        for (i, c) in body.char_indices().chain([(body.len(), ' ')]) {
+            match (c.is_whitespace(), word_start) {
+                (true, Some(start)) => {
+                    words.push(body_start + start..body_start + i);
+                    word_start = None;
+                }
+                (false, None) => word_start = Some(i),
+                _ => {}
+            }
+        }
+        line_start += line.len() + 1;
+    }
+
+    let prefix_len = prefix_len(text, options.comment_prefix);
+    let prefix = &text[..prefix_len];
+    let prefix_width: usize = prefix
+        .chars()
+        .map(|c| {
+            if c == '\t' {
+                options.tab_size
+            } else {
+                char_width(c)
+            }
+        })
+        .sum();
+    let available = options
+        .column
+        .map(|column| column.saturating_sub(prefix_width).max(1));
+
+    let mut out = String::from(prefix);
+    let mut line_width = 0;
+    let mut mapping = Vec::with_capacity(words.len());
+    for range in words {
+        let word = &text[range.clone()];
+        let width = str_width(word);
+        if line_width > 0 {
+            if available.is_some_and(|available| line_width + 1 + width > available) {
+                out.push_str(newline);
+                out.push_str(prefix);
+                line_width = 0;
+            } else {
+                out.push(' ');
+                line_width += 1;
+            }
+        }
+        mapping.push((range, out.len()));
+        out.push_str(word);
+        line_width += width;
+    }
+
+    Reflowed {
+        text: out,
+        prefix_len,
+        words: mapping,
+    }
+}
+
+/// Byte range of the paragraph containing the line at `pos`, from the
+/// first line's start to the end of the last line's text
+fn paragraph_at(
+    buffer: &mut Buffer,
+    pos: usize,
+    comment_prefix: Option<&str>,
+    estimated_line_length: usize,
+) -> Option<Range<usize>> {
+    let mut iter = buffer.line_iterator(pos, estimated_line_length);
+    let (line_start, line) = iter.next_line()?;
+    let key = line_key(&line, comment_prefix)?;
+
+    let mut start = line_start;
+    let mut iter = buffer.line_iterator(pos, estimated_line_length);
+    while let Some((prev_start, prev)) = iter.prev() {
+        if line_key(&prev, comment_prefix).as_ref() != Some(&key) {
+            break;
+        }
+        start = prev_start;
+    }
+
+    let mut end = start;
+    let mut iter = buffer.line_iterator(pos, estimated_line_length);
+    while let Some((next_start, next)) = iter.next_line() {
+        if line_key(&next, comment_prefix).as_ref() != Some(&key) {
+            break;
+        }
+        end = next_start + content_len(&next);
+    }
+    Some(start..end)
+}
+
+/// Paragraphs of the lines touched by `selection`
+fn paragraphs_in(
+    buffer: &mut Buffer,
+    selection: Range<usize>,
+    comment_prefix: Option<&str>,
+    estimated_line_length: usize,
+) -> Vec<Range<usize>> {
+    let mut paragraphs = Vec::new();
+    let mut current: Option<(String, Range<usize>)> = None;
+    let mut iter = buffer.line_iterator(selection.start, estimated_line_length);
+    while let Some((line_start, line)) = iter.next_line() {
+        if line_start >= selection.end && line_start > selection.start {
+            break;
+        }
+        let line_end = line_start + content_len(&line);
+        match (line_key(&line, comment_prefix), current.as_mut()) {
+            (Some(key), Some((current_key, range))) if *current_key == key => {
+                range.end = line_end;
+            }
+            (key, _) => {
+                paragraphs.extend(current.take().map(|(_, range)| range));
+                current = key.map(|key| (key, line_start..line_end));
+            }
+        }
+    }
+    paragraphs.extend(current.map(|(_, range)| range));
+    paragraphs
+}
+
+/// Reflow the paragraph under each cursor, or every paragraph touched by
+/// its selection, as one edit. Cursors and selection anchors stay on the
+/// same words.
+///
+/// Returns the number of paragraphs that changed.
+pub(crate) fn reflow_paragraphs(
+    state: &mut EditorState,
+    cursors: &Cursors,
+    events: &mut Vec<Event>,
+    options: &ReflowOptions,
+    estimated_line_length: usize,
+) -> usize {
+    let mut ranges = Vec::new();
+    for (_, cursor) in cursors.iter() {
+        match cursor.selection_range() {
+            Some(selection) => ranges.extend(paragraphs_in(
+                &mut state.buffer,
+                selection,
+                options.comment_prefix,
+                estimated_line_length,
+            )),
+            None => ranges.extend(paragraph_at(
+                &mut state.buffer,
+                cursor.position,
+                options.comment_prefix,
+                estimated_line_length,
+            )),
+        }
+    }
+    ranges.sort_by_key(|range| range.start);
+    // Several cursors in one paragraph reflow it once
+    let mut paragraphs: Vec<(Range<usize>, Reflowed)> = Vec::new();
+    let newline = state.buffer.line_ending().as_str();
+    for range in ranges {
+        if paragraphs
+            .last()
+            .is_some_and(|(last, _)| range.start <= last.end)
+        {
+            continue;
+        }
+        let text = state.get_text_range(range.start, range.end);
+        let reflowed = reflow_text(&text, options, newline);
+        if reflowed.text != text {
+            paragraphs.push((range, reflowed));
+        }
+    }
+    if paragraphs.is_empty() {
+        return 0;
+    }
+
+    let map_position = |pos: usize| {
+        let mut shift = 0isize;
+        for (range, reflowed) in &paragraphs {
+            if pos < range.start {
+                break;
+            }
+            let new_start = range.start.saturating_add_signed(shift);
+            if pos <= range.end {
+                return new_start + reflowed.map_offset(pos - range.start);
+            }
+            shift += reflowed.text.len() as isize - range.len() as isize;
+        }
+        pos.saturating_add_signed(shift)
+    };
+
+    // The edits don't move cursors themselves; the MoveCursor events below
+    // place every cursor on its word
+    for (range, reflowed) in paragraphs.iter().rev() {
+        events.push(Event::Delete {
+            range: range.clone(),
+            deleted_text: state.get_text_range(range.start, range.end),
+            cursor_id: CursorId::UNDO_SENTINEL,
+        });
+        events.push(Event::Insert {
+            position: range.start,
+            text: reflowed.text.clone(),
+            cursor_id: CursorId::UNDO_SENTINEL,
+        });
+    }
+    for (cursor_id, cursor) in cursors.iter() {
+        events.push(Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: map_position(cursor.position),
+            old_anchor: cursor.anchor,
+            new_anchor: cursor.anchor.map(map_position),
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: 0,
+        });
+    }
+    paragraphs.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(column: Option<usize>, comment_prefix: Option<&str>) -> ReflowOptions<'_> {
+        ReflowOptions {
+            column,
+            comment_prefix,
+            tab_size: 4,
+        }
+    }
+
+    fn reflow(text: &str, column: usize, comment_prefix: Option<&str>) -> String {
+        reflow_text(text, &options(Some(column), comment_prefix), "\n").text
+    }
+
+    #[test]
+    fn test_reflow_plain_paragraph() {
+        assert_eq!(
+            reflow("one two three\nfour five six seven", 10, None),
+            "one two\nthree four\nfive six\nseven"
+        );
+        // Words longer than the column get a line of their own
+        assert_eq!(reflow("a abcdefghijkl b", 5, None), "a\nabcdefghijkl\nb");
+        assert_eq!(
+            reflow("  indented text here", 12, None),
+            "  indented\n  text here"
+        );
+    }
+
+    #[test]
+    fn test_reflow_keeps_comment_and_quote_prefixes() {
+        assert_eq!(
+            reflow("    // alpha beta\n    //   gamma delta", 18, Some("//")),
+            "    // alpha beta\n    // gamma delta"
+        );
+        assert_eq!(
+            reflow("/// one two three four", 14, Some("//")),
+            "/// one two\n/// three four"
+        );
+        assert_eq!(reflow("# a b c d e f", 7, Some("#")), "# a b c\n# d e f");
+        assert_eq!(
+            reflow("> > quoted text goes here", 14, None),
+            "> > quoted\n> > text goes\n> > here"
+        );
+    }
+
+    #[test]
+    fn test_unwrap_joins_lines() {
+        let text = "// first line\r\n// second line";
+        let joined = reflow_text(text, &options(None, Some("//")), "\n");
+        assert_eq!(joined.text, "// first line second line");
+    }
+
+    #[test]
+    fn test_line_keys_separate_paragraphs() {
+        assert_eq!(line_key("  // x\n", Some("//")).as_deref(), Some("  //"));
+        assert_eq!(line_key("  //    y", Some("//")).as_deref(), Some("  //"));
+        assert_eq!(line_key("    // x", Some("//")).as_deref(), Some("    //"));
+        assert_eq!(line_key("  //\n", Some("//")), None);
+        assert_eq!(line_key("   \n", None), None);
+        assert_eq!(line_key("text", None).as_deref(), Some(""));
+        assert_eq!(line_key("  text", None).as_deref(), Some("  "));
+    }
+
+    #[test]
+    fn test_map_offset_keeps_cursor_on_word() {
+        let text = "// alpha beta\n// gamma";
+        let reflowed = reflow_text(text, &options(None, Some("//")), "\n");
+        assert_eq!(reflowed.text, "// alpha beta gamma");
+        // Inside the prefix
+        assert_eq!(reflowed.map_offset(1), 1);
+        // In "gamma" on the second line
+        let gamma = text.find("gamma").unwrap();
+        let new_gamma = reflowed.text.find("gamma").unwrap();
+        assert_eq!(reflowed.map_offset(gamma + 2), new_gamma + 2);
+        // Before "gamma", in the second line's prefix
+        assert_eq!(reflowed.map_offset(gamma - 2), new_gamma);
+        assert_eq!(reflowed.map_offset(text.len()), reflowed.text.len());
+    }
+}
@@ -0,0 +1,120 @@
+//! Sort Lines and its numeric and reverse variants.
+//!
+//! Lines are compared as whole strings (optionally ignoring case) or by the
+//! number each line starts with. The sort is stable, so lines that compare
+//! equal keep their order; in numeric mode, lines without a leading number
+//! sort before all numbered lines.
+
+use std::cmp::Ordering;
+
+/// How to order the lines
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SortOptions {
+    /// Compare the number at the start of each line instead of the text
+    pub numeric: bool,
+    /// Largest first
+    pub reverse: bool,
+    /// Ignore case when comparing text
+    pub case_insensitive: bool,
+}
+
+/// The number a line starts with, after leading whitespace: an optional
+/// sign, digits, and an optional fraction
+fn leading_number(line: &str) -> Option<f64> {
+    let trimmed = line.trim_start();
+    let sign_len = usize::from(trimmed.starts_with(['-', '+']));
+    let digits = &trimmed[sign_len..];
+    let int_len = digits.len()
+        - digits
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .len();
+    if int_len == 0 {
+        return None;
+    }
+    let mut len = sign_len + int_len;
+    if let Some(fraction) = trimmed[len..].strip_prefix('.') {
+        let fraction_len = fraction.len()
+            - fraction
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .len();
+        if fraction_len > 0 {
+            len += 1 + fraction_len;
+        }
+    }
+    trimmed[..len].parse().ok()
+}
+
+fn compare(a: &str, b: &str, options: SortOptions) -> Ordering {
+    if options.numeric {
+        match (leading_number(a), leading_number(b)) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            (x, y) => x.is_some().cmp(&y.is_some()),
+        }
+    } else if options.case_insensitive {
+        a.to_lowercase().cmp(&b.to_lowercase())
+    } else {
+        a.cmp(b)
+    }
+}
+
+/// Sort the lines of `text`, joining them with `line_ending`. A trailing line
+/// ending stays at the end, so the text after the sorted block is untouched.
+pub(crate) fn sort_lines(text: &str, line_ending: &str, options: SortOptions) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    if options.reverse {
+        lines.sort_by(|a, b| compare(b, a, options));
+    } else {
+        lines.sort_by(|a, b| compare(a, b, options));
+    }
+    let mut sorted = lines.join(line_ending);
+    if text.ends_with('\n') {
+        sorted.push_str(line_ending);
+    }
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_lines_alphabetic() {
+        let sorted = sort_lines("cherry\napple\nbanana\n", "\n", SortOptions::default());
+        assert_eq!(sorted, "apple\nbanana\ncherry\n");
+    }
+
+    #[test]
+    fn test_sort_lines_numeric() {
+        let options = SortOptions {
+            numeric: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            sort_lines("10 items\n2 items\nnone\n-1.5 x\n1 item", "\n", options),
+            "none\n-1.5 x\n1 item\n2 items\n10 items"
+        );
+    }
+
+    #[test]
+    fn test_sort_lines_reverse_case_insensitive() {
+        let options = SortOptions {
+            reverse: true,
+            case_insensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            sort_lines("Banana\napple\nCherry", "\n", options),
+            "Cherry\nBanana\napple"
+        );
+    }
+
+    #[test]
+    fn test_sort_lines_keeps_multibyte_text_and_crlf() {
+        let sorted = sort_lines(
+            "éclair\r\nçava\r\nañejo\r\n",
+            "\r\n",
+            SortOptions::default(),
+        );
+        assert_eq!(sorted, "añejo\r\nçava\r\néclair\r\n");
+    }
+}
@@ -10,7 +10,9 @@ mod tests {
     use crate::input::multi_cursor::{add_cursor_at_next_match, AddCursorResult};
     use crate::model::buffer::Buffer;
     use crate::model::cursor::Cursors;
-    use crate::primitives::word_navigation::{find_word_start_left, find_word_start_right};
+    use crate::primitives::word_navigation::{
+        find_word_start_left, find_word_start_right, WordRules,
+    };
     use crate::state::EditorState;
 
     // --- Auto-Pairs Logic Tests ---
@@ -63,16 +65,16 @@ mod tests {
 
         // Position 0 ('f'): Word
         // next boundary is 3 ('o' -> '.')
-        assert_eq!(find_word_start_right(&buffer, 0), 3);
+        assert_eq!(find_word_start_right(&buffer, 0, &WordRules::default()), 3);
 
         // Position 3 ('.'): Punctuation
         // next boundary is 4 ('.' -> 'b')
-        assert_eq!(find_word_start_right(&buffer, 3), 4);
+        assert_eq!(find_word_start_right(&buffer, 3, &WordRules::default()), 4);
 
         // Position 4 ('b'): Word
         // 'bar_baz' is all word chars? '_' is word char.
         // so it should go to end (11)
-        assert_eq!(find_word_start_right(&buffer, 4), 11);
+        assert_eq!(find_word_start_right(&buffer, 4, &WordRules::default()), 11);
     }
 
     #[test]
@@ -81,10 +83,10 @@ mod tests {
         let buffer = Buffer::from_str("a . b", 0, test_fs());
 
         // 0 ('a') -> Word. Ends at 1. Skip whitespace -> 2 ('.')
-        assert_eq!(find_word_start_right(&buffer, 0), 2);
+        assert_eq!(find_word_start_right(&buffer, 0, &WordRules::default()), 2);
 
         // 2 ('.') -> Punctuation. Ends at 3. Skip whitespace -> 4 ('b')
-        assert_eq!(find_word_start_right(&buffer, 2), 4);
+        assert_eq!(find_word_start_right(&buffer, 2, &WordRules::default()), 4);
     }
 
     #[test]
@@ -94,15 +96,15 @@ mod tests {
 
         // 7 (end) -> Left -> 4 ('b')
         // 'bar' is word.
-        assert_eq!(find_word_start_left(&buffer, 7), 4);
+        assert_eq!(find_word_start_left(&buffer, 7, &WordRules::default()), 4);
 
         // 4 ('b') -> Left -> 3 ('.')
         // '.' is punctuation
-        assert_eq!(find_word_start_left(&buffer, 4), 3);
+        assert_eq!(find_word_start_left(&buffer, 4, &WordRules::default()), 3);
 
         // 3 ('.') -> Left -> 0 ('f')
         // 'foo' is word
-        assert_eq!(find_word_start_left(&buffer, 3), 0);
+        assert_eq!(find_word_start_left(&buffer, 3, &WordRules::default()), 0);
     }
 
     // --- Multi-Cursor Tests ---
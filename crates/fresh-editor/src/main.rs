@@ -5,12 +5,14 @@ use crossterm::event::{
     MouseEvent,
 };
 use fresh::input::key_translator::KeyTranslator;
+use fresh::primitives::glob_match;
 #[cfg(target_os = "linux")]
 use fresh::services::gpm::{gpm_to_crossterm, GpmClient};
 use fresh::services::terminal_modes::{self, KeyboardConfig, TerminalModes};
 use fresh::services::tracing_setup;
+use fresh::services::workspace_trust::TrustPolicy;
 use fresh::{
-    app::Editor,
+    app::{DiffFile, Editor},
     client, config,
     config_io::DirectoryContext,
     model::filesystem::{FileSystem, StdFileSystem},
@@ -18,13 +20,15 @@ use fresh::{
     services::release_checker,
     services::remote,
     services::signal_handler,
+    services::stdin_spool::{self, StdinSpool},
     services::tracing_setup::TracingHandles,
     workspace,
 };
 use ratatui::Terminal;
 use std::{
+    collections::HashMap,
     io::{self, stdout},
-    path::PathBuf,
+    path::{Path, PathBuf},
     time::Duration,
 };
 
@@ -36,6 +40,8 @@ use std::{
     "Commands (use --cmd):\n",
     "  config show               Print effective configuration\n",
     "  config paths              Show directories used by Fresh\n",
+    "  config check              Check config files for errors (also --check-config)\n",
+    "  config git-tools          Print git difftool/mergetool configuration\n",
     "  init                      Initialize a new plugin/theme/language\n",
     "\n",
     "Session commands:\n",
@@ -58,6 +64,7 @@ use std::{
     "  fresh file.txt                               Open a file\n",
     "  fresh 'file.txt:10-20@\"Check this code\"'     Open with range selected and popup\n",
     "  fresh -a                                     Attach to session (current dir)\n",
+    "  fresh -d old.rs new.rs                       Compare two files side by side\n",
     "  fresh -a mysession                           Attach to named session\n",
     "  fresh --cmd session new proj                 Start session named 'proj'\n",
     "  fresh --cmd session open-file . main.rs     Open file in current dir session\n",
@@ -94,11 +101,13 @@ use std::{
 ))]
 struct Cli {
     /// Run a command instead of opening files
-    /// Commands: session (list|attach|new|kill|open-file), config (show|paths), init
+    /// Commands: session (list|attach|new|kill|open-file), config (show|paths|check|git-tools), init
     #[arg(long, num_args = 1.., value_name = "COMMAND", allow_hyphen_values = true)]
     cmd: Vec<String>,
 
-    /// Files to open (supports file:line:col, ranges, and @"message" syntax)
+    /// Files to open (supports file:line:col, ranges, and @"message" syntax).
+    /// A preceding +N, + or +/pattern moves to line N, the last line or the
+    /// first regex match of the next file.
     #[arg(value_name = "FILES")]
     files: Vec<String>,
 
@@ -106,18 +115,78 @@ struct Cli {
     #[arg(short = 'a', long, value_name = "NAME", num_args = 0..=1, default_missing_value = "")]
     attach: Option<String>,
 
+    /// Compare two files side by side; RIGHT receives accepted changes (git difftool).
+    /// Either file may be remote (user@host:path)
+    #[arg(
+        short = 'd',
+        long,
+        num_args = 2,
+        value_names = ["LEFT", "RIGHT"],
+        conflicts_with_all = ["files", "merge", "stdin", "attach"]
+    )]
+    diff: Vec<PathBuf>,
+
+    /// Compare LOCAL and REMOTE, resolving conflicts into MERGED (git mergetool)
+    #[arg(
+        long,
+        num_args = 3,
+        value_names = ["LOCAL", "REMOTE", "MERGED"],
+        conflicts_with_all = ["files", "stdin", "attach"]
+    )]
+    merge: Vec<PathBuf>,
+
+    /// Start the interactive tutorial
+    #[arg(long, conflicts_with_all = ["files", "diff", "merge", "stdin", "attach"])]
+    tutor: bool,
+
     /// Read content from stdin (alternative to using "-" as filename)
     #[arg(long)]
     stdin: bool,
 
+    /// Write the stdin buffer to stdout on exit, to use the editor as a
+    /// filter (e.g. `git diff | fresh - --to-stdout | less`)
+    #[arg(long)]
+    to_stdout: bool,
+
+    /// Open every file read-only, including files opened later in the session
+    #[arg(short = 'R', long, visible_alias = "read-only")]
+    readonly: bool,
+
+    /// Run commands once the editor has started, separated by ";"
+    /// (e.g. "goto-line 100; toggle-line-numbers")
+    #[arg(long, value_name = "COMMANDS")]
+    execute: Option<String>,
+
+    /// Stop at the first --execute command that fails and exit with status 1
+    #[arg(long, requires = "execute")]
+    execute_strict: bool,
+
+    /// Quit once the files given are closed; exit status 1 if one was closed
+    /// unsaved (for use as git's core.editor)
+    #[arg(long, requires = "files", conflicts_with_all = ["diff", "merge", "attach"])]
+    wait: bool,
+
     /// Disable plugin loading
     #[arg(long)]
     no_plugins: bool,
 
+    /// Trust the workspace without asking (loads its plugins and commands)
+    #[arg(long, conflicts_with = "no_trust")]
+    trust: bool,
+
+    /// Open the workspace restricted without asking
+    #[arg(long)]
+    no_trust: bool,
+
     /// Path to configuration file
     #[arg(long, value_name = "PATH")]
     config: Option<PathBuf>,
 
+    /// Check each config file (and --config) for syntax errors, unknown keys
+    /// and invalid keybindings, then exit; exit status 1 if any were found
+    #[arg(long)]
+    check_config: bool,
+
     /// Path to log file for editor diagnostics
     #[arg(long, value_name = "PATH")]
     log_file: Option<PathBuf>,
@@ -138,6 +207,37 @@ struct Cli {
     #[arg(long, value_name = "LOCALE")]
     locale: Option<String>,
 
+    /// Use this color theme for this run (e.g., 'dark', 'light', 'nord')
+    #[arg(long, value_name = "NAME")]
+    theme: Option<String>,
+
+    /// SSH port for remote files (user@host:path) that don't specify one
+    #[arg(long, value_name = "PORT")]
+    port: Option<u16>,
+
+    /// SSH private key for remote files: PATH for every host, or HOST=PATH
+    /// for one host. Repeatable; overrides IdentityFile from ~/.ssh/config.
+    /// The passphrase of an encrypted key is asked for before the editor starts
+    #[arg(short = 'i', long, value_name = "[HOST=]PATH")]
+    identity: Vec<String>,
+
+    /// Run without a terminal, driven by JSON commands sent to
+    /// --control-socket (for integration tests and automation)
+    #[arg(
+        long,
+        requires = "control_socket",
+        conflicts_with_all = ["diff", "merge", "stdin", "attach", "tutor"]
+    )]
+    headless: bool,
+
+    /// Socket (a named pipe on Windows) that --headless reads commands from
+    #[arg(long, value_name = "PATH", requires = "headless")]
+    control_socket: Option<PathBuf>,
+
+    /// Print how long each startup phase takes, then exit
+    #[arg(long, conflicts_with_all = ["diff", "merge", "stdin", "attach", "tutor"])]
+    profile_startup: bool,
+
     // === Hidden internal flags ===
     /// Start as a daemon server (internal)
     #[arg(long, hide = true)]
@@ -176,8 +276,22 @@ struct Cli {
 struct Args {
     files: Vec<String>,
     stdin: bool,
+    /// Write the stdin buffer to the original stdout on exit (--to-stdout)
+    to_stdout: bool,
+    /// Open every file read-only (--readonly)
+    readonly: bool,
+    /// Quit once the CLI files are closed (--wait)
+    wait: bool,
+    /// Commands to run after startup (--execute)
+    execute: Option<String>,
+    /// Fail fast on --execute errors (--execute-strict)
+    execute_strict: bool,
     no_plugins: bool,
+    /// Workspace trust from --trust/--no-trust (None asks)
+    trust: Option<bool>,
     config: Option<PathBuf>,
+    /// Validate the config files and exit (--check-config)
+    check_config: bool,
     log_file: Option<PathBuf>,
     event_log: Option<PathBuf>,
     no_session: bool,
@@ -185,6 +299,15 @@ struct Args {
     dump_config: bool,
     show_paths: bool,
     locale: Option<String>,
+    /// Color theme for this run (--theme)
+    theme: Option<String>,
+    /// SSH port for remote locations without their own (--port)
+    port: Option<u16>,
+    /// SSH private keys as given to --identity ([HOST=]PATH)
+    identity: Vec<String>,
+    profile_startup: bool,
+    /// Control socket for --headless
+    headless_socket: Option<PathBuf>,
     check_plugin: Option<PathBuf>,
     init: Option<Option<String>>,
     server: bool,
@@ -195,6 +318,10 @@ struct Args {
     kill: Option<Option<String>>,
     /// Open files in a session without attaching (session_name, files, wait)
     open_files_in_session: Option<(Option<String>, Vec<String>, bool)>,
+    /// Files for --diff (LEFT, RIGHT) or --merge (LOCAL, REMOTE, MERGED)
+    diff_files: Vec<PathBuf>,
+    /// Open the interactive tutorial (--tutor)
+    tutor: bool,
     /// Launch in GUI mode
     #[cfg(feature = "gui")]
     gui: bool,
@@ -337,6 +464,14 @@ impl From<Cli> for Args {
                 ["config", "paths"] => {
                     (false, None, false, None, false, true, None, cli.files, None)
                 }
+                // Sets check_config below
+                ["config", "check"] => (
+                    false, None, false, None, false, false, None, cli.files, None,
+                ),
+                ["config", "git-tools"] => {
+                    print_git_tool_config();
+                    std::process::exit(0);
+                }
                 // Init command
                 ["init", pkg_type, ..] => (
                     false,
@@ -363,7 +498,7 @@ impl From<Cli> for Args {
                 // Unknown command
                 _ => {
                     eprintln!("Unknown command: {}", cli.cmd.join(" "));
-                    eprintln!("Available commands: session (list|attach|new|kill|info|open-file), config (show|paths), init");
+                    eprintln!("Available commands: session (list|attach|new|kill|info|open-file), config (show|paths|check|git-tools), init");
                     std::process::exit(1);
                 }
             }
@@ -398,7 +533,18 @@ impl From<Cli> for Args {
         Args {
             files,
             stdin: cli.stdin,
+            to_stdout: cli.to_stdout,
+            readonly: cli.readonly,
+            wait: cli.wait,
+            execute: cli.execute,
+            execute_strict: cli.execute_strict,
             no_plugins: cli.no_plugins,
+            trust: match (cli.trust, cli.no_trust) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            check_config: cli.check_config || cli.cmd == ["config", "check"],
             config: cli.config,
             log_file: cli.log_file,
             event_log: cli.event_log,
@@ -407,6 +553,11 @@ impl From<Cli> for Args {
             dump_config,
             show_paths,
             locale: cli.locale,
+            theme: cli.theme,
+            port: cli.port,
+            identity: cli.identity,
+            profile_startup: cli.profile_startup,
+            headless_socket: cli.control_socket.filter(|_| cli.headless),
             check_plugin: cli.check_plugin,
             init,
             server: cli.server,
@@ -415,6 +566,12 @@ impl From<Cli> for Args {
             session_name,
             kill,
             open_files_in_session,
+            diff_files: if cli.merge.is_empty() {
+                cli.diff
+            } else {
+                cli.merge
+            },
+            tutor: cli.tutor,
             #[cfg(feature = "gui")]
             gui: cli.gui,
         }
@@ -424,7 +581,7 @@ impl From<Cli> for Args {
 /// Parsed file location from CLI argument in file:line:col format
 /// Also supports range selections (file:L-EL or file:L:C-EL:EC) and
 /// hover messages (file:L@"message").
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FileLocation {
     path: PathBuf,
     line: Option<usize>,
@@ -432,23 +589,49 @@ struct FileLocation {
     end_line: Option<usize>,
     end_column: Option<usize>,
     message: Option<String>,
+    /// SSH connection ("user@host[:port]") the file lives on, for remote files
+    connection: Option<String>,
+    /// Regex to jump to the first match of, from `+/pattern`
+    search: Option<String>,
 }
 
-/// Parsed remote location from CLI argument in user@host:path format
+/// Parsed remote location from CLI argument in [user@]host[:port]:path format
 #[derive(Debug, Clone)]
 struct RemoteLocation {
+    /// Empty when not given; filled from the ssh config or the local user
     user: String,
+    /// Host name or ssh config alias
     host: String,
+    port: Option<u16>,
+    /// Private key from --identity; None uses the ssh config's
+    identity_file: Option<PathBuf>,
     path: String,
     line: Option<usize>,
     column: Option<usize>,
+    /// Regex to jump to the first match of, from `+/pattern`
+    search: Option<String>,
+}
+
+impl RemoteLocation {
+    /// The connection this location is reached through
+    fn key(&self, ssh_config: &remote::SshConfig) -> remote::ConnectionKey {
+        let user = Some(self.user.as_str()).filter(|user| !user.is_empty());
+        remote::ConnectionKey::resolve(
+            user,
+            &self.host,
+            self.port,
+            self.identity_file.clone(),
+            ssh_config,
+        )
+    }
 }
 
-/// Either a local or remote file location
+/// A local file, a remote file, or an http(s) URL to download
 #[derive(Debug)]
 enum ParsedLocation {
     Local(FileLocation),
     Remote(RemoteLocation),
+    Url(String),
 }
 
 struct IterationOutcome {
@@ -463,18 +646,24 @@ struct SetupState {
     terminal: Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
     terminal_size: (u16, u16),
     file_locations: Vec<FileLocation>,
+    /// Files for --diff or --merge, local or remote
+    diff_files: Vec<DiffFile>,
+    /// http(s) URLs to download and open read-only
+    urls: Vec<String>,
     show_file_explorer: bool,
     dir_context: DirectoryContext,
     current_working_dir: Option<PathBuf>,
     /// Stdin streaming state (if --stdin flag or "-" file was used)
     /// Contains temp file path and background thread handle
     stdin_stream: Option<StdinStreamState>,
+    /// The original stdout, when --to-stdout moved the TUI to the terminal
+    filter_output: Option<std::fs::File>,
     /// Filesystem implementation (local or remote)
     filesystem: std::sync::Arc<dyn FileSystem + Send + Sync>,
     /// Process spawner for plugin command execution (local or remote)
     process_spawner: std::sync::Arc<dyn remote::ProcessSpawner>,
-    /// Remote session resources - must be kept alive for remote editing
-    _remote_session: Option<RemoteSession>,
+    /// Remote session resources, one per connection - must be kept alive for remote editing
+    _remote_sessions: HashMap<remote::ConnectionKey, RemoteSession>,
     /// Key translator for input calibration
     key_translator: KeyTranslator,
     #[cfg(target_os = "linux")]
@@ -489,13 +678,14 @@ struct SetupState {
 /// State for stdin streaming in background
 #[cfg(unix)]
 pub struct StdinStreamState {
-    /// Path to temp file where stdin is being written
-    pub temp_path: PathBuf,
+    /// Where stdin is being collected (in memory, then a temp file)
+    pub spool: std::sync::Arc<StdinSpool>,
     /// Handle to background thread (None if completed)
     pub thread_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
 }
 
-/// Start streaming stdin to temp file in background.
+/// Start streaming stdin into a spool in background. Small inputs stay in
+/// memory; larger ones continue in a temp file.
 /// Returns immediately with streaming state. Editor can start while data streams in.
 /// Must be called BEFORE enabling raw terminal mode.
 #[cfg(unix)]
@@ -511,25 +701,22 @@ fn start_stdin_streaming() -> AnyhowResult<StdinStreamState> {
         anyhow::bail!("Failed to dup stdin: {}", io::Error::last_os_error());
     }
 
-    // Create empty temp file
+    // The temp file is only created if stdin outgrows memory
     let temp_dir = std::env::temp_dir();
     let temp_path = temp_dir.join(format!("fresh-stdin-{}.tmp", std::process::id()));
-    File::create(&temp_path)?;
+    let spool = std::sync::Arc::new(StdinSpool::new(temp_path));
 
     // Reopen stdin from /dev/tty so crossterm can use it for keyboard input
     reopen_stdin_from_tty()?;
     tracing::info!("Reopened stdin from /dev/tty for terminal input");
 
-    // Spawn background thread to drain pipe into temp file
-    let temp_path_clone = temp_path.clone();
+    // Spawn background thread to drain pipe into the spool
+    let thread_spool = std::sync::Arc::clone(&spool);
     let thread_handle = std::thread::spawn(move || {
-        use std::io::{Read, Write};
+        use std::io::Read;
 
         // SAFETY: pipe_fd is a valid duplicated file descriptor
         let mut pipe_file = unsafe { File::from_raw_fd(pipe_fd) };
-        let mut temp_file = std::fs::OpenOptions::new()
-            .append(true)
-            .open(&temp_path_clone)?;
 
         const CHUNK_SIZE: usize = 64 * 1024;
         let mut buffer = vec![0u8; CHUNK_SIZE];
@@ -539,9 +726,7 @@ fn start_stdin_streaming() -> AnyhowResult<StdinStreamState> {
             if bytes_read == 0 {
                 break; // EOF
             }
-            temp_file.write_all(&buffer[..bytes_read])?;
-            // Flush each chunk so main thread can see progress
-            temp_file.flush()?;
+            thread_spool.write(&buffer[..bytes_read])?;
         }
 
         tracing::info!("Stdin streaming complete");
@@ -549,7 +734,7 @@ fn start_stdin_streaming() -> AnyhowResult<StdinStreamState> {
     });
 
     Ok(StdinStreamState {
-        temp_path,
+        spool,
         thread_handle: Some(thread_handle),
     })
 }
@@ -557,18 +742,18 @@ fn start_stdin_streaming() -> AnyhowResult<StdinStreamState> {
 /// Windows stdin stream state
 #[cfg(windows)]
 pub struct StdinStreamState {
-    pub temp_path: PathBuf,
+    pub spool: std::sync::Arc<StdinSpool>,
     pub thread_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
 }
 
-/// Stream stdin content to a temp file on Windows.
+/// Stream stdin content into a spool on Windows (memory, then a temp file).
 /// This is called when stdin is a pipe (e.g., `cat file.txt | fresh`).
 /// We duplicate the stdin handle, spawn a thread to read from it,
 /// and then reopen stdin from CONIN$ for keyboard input.
 #[cfg(windows)]
 fn start_stdin_streaming() -> AnyhowResult<StdinStreamState> {
     use std::fs::File;
-    use std::io::{Read, Write};
+    use std::io::Read;
     use std::os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle};
     use windows_sys::Win32::Foundation::{
         DuplicateHandle, DUPLICATE_SAME_ACCESS, HANDLE, INVALID_HANDLE_VALUE,
@@ -606,11 +791,12 @@ fn start_stdin_streaming() -> AnyhowResult<StdinStreamState> {
         );
     }
 
-    // Create a temp file to store the piped content
+    // The temp file is only created if stdin outgrows memory
     let temp_dir = std::env::temp_dir();
     let temp_path = temp_dir.join(format!("fresh-stdin-{}.txt", std::process::id()));
+    let spool = std::sync::Arc::new(StdinSpool::new(temp_path));
 
-    let temp_path_clone = temp_path.clone();
+    let thread_spool = std::sync::Arc::clone(&spool);
 
     // Cast handle to usize for Send across thread boundary
     // SAFETY: HANDLE is a pointer-sized value, usize preserves it exactly
@@ -626,26 +812,24 @@ fn start_stdin_streaming() -> AnyhowResult<StdinStreamState> {
         // Forget the OwnedHandle since File now owns it
         std::mem::forget(owned_handle);
 
-        let mut temp_file = File::create(&temp_path_clone)?;
         let mut buffer = [0u8; 8192];
 
         loop {
             match pipe_reader.read(&mut buffer) {
                 Ok(0) => break, // EOF
                 Ok(n) => {
-                    temp_file.write_all(&buffer[..n])?;
+                    thread_spool.write(&buffer[..n])?;
                 }
                 Err(e) if e.kind() == io::ErrorKind::BrokenPipe => break,
                 Err(e) => return Err(e.into()),
             }
         }
 
-        temp_file.flush()?;
         Ok(())
     });
 
     Ok(StdinStreamState {
-        temp_path,
+        spool,
         thread_handle: Some(thread_handle),
     })
 }
@@ -656,6 +840,12 @@ fn stdin_has_data() -> bool {
     !io::stdin().is_terminal()
 }
 
+/// Check if stdout goes to a pipe or file rather than the terminal
+fn stdout_is_redirected() -> bool {
+    use std::io::IsTerminal;
+    !io::stdout().is_terminal()
+}
+
 /// Reopen stdin from /dev/tty after reading piped content.
 /// This allows crossterm to use the terminal for keyboard input
 /// even though the original stdin was a pipe.
@@ -720,10 +910,111 @@ fn reopen_stdin_from_tty() -> AnyhowResult<()> {
     Ok(())
 }
 
+/// Point stdout at /dev/tty so the TUI draws on the terminal, returning the
+/// original stdout (a pipe or file) for --to-stdout to write to on exit.
+#[cfg(unix)]
+fn redirect_stdout_to_tty() -> AnyhowResult<std::fs::File> {
+    use std::fs::{File, OpenOptions};
+    use std::os::unix::io::{AsRawFd, FromRawFd};
+
+    // Keep the original stdout before fd 1 is replaced
+    let stdout_fd = io::stdout().as_raw_fd();
+    let pipe_fd = unsafe { libc::dup(stdout_fd) };
+    if pipe_fd == -1 {
+        anyhow::bail!("Failed to dup stdout: {}", io::Error::last_os_error());
+    }
+    // SAFETY: pipe_fd is a valid duplicated file descriptor that we own
+    let output = unsafe { File::from_raw_fd(pipe_fd) };
+
+    let tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+
+    // SAFETY: dup2 is safe to call with valid file descriptors
+    let result = unsafe { libc::dup2(tty.as_raw_fd(), libc::STDOUT_FILENO) };
+    if result == -1 {
+        anyhow::bail!(io::Error::last_os_error());
+    }
+
+    Ok(output)
+}
+
+/// Point stdout at CONOUT$ on Windows, returning the original stdout for
+/// --to-stdout to write to on exit.
+#[cfg(windows)]
+fn redirect_stdout_to_tty() -> AnyhowResult<std::fs::File> {
+    use std::fs::File;
+    use std::os::windows::io::FromRawHandle;
+    use windows_sys::Win32::Foundation::{
+        DuplicateHandle, DUPLICATE_SAME_ACCESS, HANDLE, INVALID_HANDLE_VALUE,
+    };
+    use windows_sys::Win32::Storage::FileSystem::{
+        CreateFileW, FILE_GENERIC_READ, FILE_GENERIC_WRITE, FILE_SHARE_READ, FILE_SHARE_WRITE,
+        OPEN_EXISTING,
+    };
+    use windows_sys::Win32::System::Console::{GetStdHandle, SetStdHandle, STD_OUTPUT_HANDLE};
+    use windows_sys::Win32::System::Threading::GetCurrentProcess;
+
+    let stdout_handle = unsafe { GetStdHandle(STD_OUTPUT_HANDLE) };
+    if stdout_handle == INVALID_HANDLE_VALUE || stdout_handle.is_null() {
+        anyhow::bail!("Failed to get stdout handle");
+    }
+
+    // Keep the original stdout before it is replaced
+    let mut duplicated_handle: HANDLE = std::ptr::null_mut();
+    let current_process = unsafe { GetCurrentProcess() };
+    let success = unsafe {
+        DuplicateHandle(
+            current_process,
+            stdout_handle,
+            current_process,
+            &mut duplicated_handle,
+            0,
+            0, // not inheritable
+            DUPLICATE_SAME_ACCESS,
+        )
+    };
+    if success == 0 {
+        anyhow::bail!(
+            "Failed to duplicate stdout handle: {}",
+            io::Error::last_os_error()
+        );
+    }
+    // SAFETY: We own this duplicated handle; File closes it when dropped
+    let output = unsafe { File::from_raw_handle(duplicated_handle) };
+
+    // "CONOUT$" is the console output device, like /dev/tty on Unix
+    let conout: Vec<u16> = "CONOUT$\0".encode_utf16().collect();
+    let conout_handle = unsafe {
+        CreateFileW(
+            conout.as_ptr(),
+            FILE_GENERIC_READ | FILE_GENERIC_WRITE,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            std::ptr::null(),
+            OPEN_EXISTING,
+            0,
+            std::ptr::null_mut(),
+        )
+    };
+    if conout_handle == INVALID_HANDLE_VALUE {
+        anyhow::bail!("Failed to open CONOUT$: {}", io::Error::last_os_error());
+    }
+
+    let success = unsafe { SetStdHandle(STD_OUTPUT_HANDLE, conout_handle) };
+    if success == 0 {
+        anyhow::bail!(
+            "Failed to set stdout to CONOUT$: {}",
+            io::Error::last_os_error()
+        );
+    }
+
+    Ok(output)
+}
+
 fn handle_first_run_setup(
     editor: &mut Editor,
     args: &Args,
     file_locations: &[FileLocation],
+    diff_files: &[DiffFile],
+    urls: &[String],
     show_file_explorer: bool,
     stdin_stream: &mut Option<StdinStreamState>,
     tracing_handles: &mut Option<TracingHandles>,
@@ -739,6 +1030,10 @@ fn handle_first_run_setup(
         editor.set_status_log_path(handles.status.path);
     }
 
+    if let Some(script) = &args.execute {
+        editor.queue_startup_commands(script, args.execute_strict);
+    }
+
     if workspace_enabled {
         match editor.try_restore_workspace() {
             Ok(true) => {
@@ -753,32 +1048,37 @@ fn handle_first_run_setup(
         }
     }
 
+    // A diff session replaces the usual startup: no CLI files, explorer or recovery
+    if let [left, right, merged @ ..] = diff_files {
+        return editor
+            .start_diff_session_for(left, right, merged.first())
+            .context("Failed to start diff session");
+    }
+
     // Handle stdin streaming (takes priority over files)
     // Opens with empty/partial buffer, content streams in background
     if let Some(mut stream_state) = stdin_stream.take() {
-        tracing::info!("Opening stdin buffer from: {:?}", stream_state.temp_path);
-        editor.open_stdin_buffer(&stream_state.temp_path, stream_state.thread_handle.take())?;
+        tracing::info!("Opening stdin buffer");
+        editor.open_stdin_spool(
+            stream_state.spool.clone(),
+            stream_state.thread_handle.take(),
+        )?;
     }
 
     // Queue CLI files to be opened after the TUI starts
     // This ensures they go through the same code path as interactive file opens,
-    // with consistent error handling (e.g., encoding confirmation prompts in the UI)
-    let mut has_cli_files = false;
-    for loc in file_locations {
-        if loc.path.is_dir() {
-            continue;
-        }
-        tracing::info!("[SYNTAX DEBUG] Queueing CLI file for open: {:?}", loc.path);
-        editor.queue_file_open(
-            loc.path.clone(),
-            loc.line,
-            loc.column,
-            loc.end_line,
-            loc.end_column,
-            loc.message.clone(),
-            None,
-        );
-        has_cli_files = true;
+    // with consistent error handling (e.g., encoding confirmation prompts in the UI).
+    // The queue is drained after everything below (restored workspace, recovery,
+    // tutorial), so CLI files end up focused; files already in the restored
+    // workspace are focused rather than opened twice.
+    let has_cli_files = queue_cli_files(editor, file_locations);
+    if args.wait && has_cli_files {
+        editor.wait_for_queued_files();
+    }
+
+    // URLs download in the background and open when they arrive
+    for url in urls {
+        editor.open_url(url);
     }
 
     // Schedule hot exit recovery for CLI-opened files (not covered by workspace restore)
@@ -805,6 +1105,10 @@ fn handle_first_run_setup(
         }
     }
 
+    if args.tutor {
+        editor.open_tutorial();
+    }
+
     Ok(())
 }
 
@@ -833,6 +1137,8 @@ fn parse_file_location(input: &str) -> FileLocation {
         end_line: None,
         end_column: None,
         message: None,
+        connection: None,
+        search: None,
     };
 
     let full_path = PathBuf::from(input);
@@ -973,6 +1279,8 @@ fn parse_range(location: &str, path: PathBuf, message: Option<String>) -> Option
         end_line: Some(end_line),
         end_column: end_col,
         message,
+        connection: None,
+        search: None,
     })
 }
 
@@ -988,67 +1296,64 @@ fn parse_line_col(s: &str) -> Option<(usize, Option<usize>)> {
     }
 }
 
-/// Parse a location that may be local (file:line:col) or remote (user@host:path:line:col)
+/// A TCP port (1-65535) written as plain digits
+fn parse_ssh_port(segment: &str) -> Option<u16> {
+    if segment.is_empty() || !segment.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    segment.parse::<u16>().ok().filter(|&port| port != 0)
+}
+
+/// The --identity key for `host`: a HOST=PATH entry naming it, else the
+/// first bare PATH. The part before `=` is only a host if it has no path
+/// separator, so paths containing `=` still work.
+fn identity_for_host(identities: &[String], host: &str) -> Option<PathBuf> {
+    let mut fallback = None;
+    for entry in identities {
+        match entry.split_once('=') {
+            Some((name, path)) if !name.is_empty() && !name.contains(['/', '\\']) => {
+                if name == host {
+                    return Some(fresh::primitives::path_utils::expand_tilde(path));
+                }
+            }
+            _ => {
+                fallback = fallback.or(Some(entry.as_str()));
+            }
+        }
+    }
+    fallback.map(fresh::primitives::path_utils::expand_tilde)
+}
+
+/// Whether the text after a possible port starts with a path rather than a
+/// line number
+fn looks_like_path(rest: &str) -> bool {
+    if rest.starts_with('/') || rest.starts_with('~') {
+        return true;
+    }
+    let first = rest.split(':').next().unwrap_or_default();
+    !first.is_empty() && !first.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Parse a location that may be local (file:line:col), remote (user@host:path:line:col)
+/// or an http(s) URL
 ///
 /// Remote format: user@host:path or user@host:path:line or user@host:path:line:col
-/// The path can be absolute (/path) or relative (path)
+/// The path can be absolute (/path) or relative (path). A port (1-65535) may
+/// follow the host (user@host:2222:/path) as long as what comes after it
+/// starts with `/`, `~` or a non-numeric segment, which keeps it apart from a
+/// numeric relative path followed by a line number (user@host:2222:10).
 fn parse_location(input: &str) -> ParsedLocation {
+    // URLs come first: "https://user@host:8080/..." would otherwise look remote
+    if fresh::services::url_fetch::is_url(input) {
+        return ParsedLocation::Url(input.to_string());
+    }
+
     // Check for SSH-style syntax: user@host:path
     // Must have @ before the first : to be considered remote
     // Also skip if it looks like a Windows path (single letter before :)
-    if let Some(at_pos) = input.find('@') {
-        // Everything before @ is the user
-        let user = &input[..at_pos];
-
-        // Everything after @ contains host:path[:line[:col]]
-        let after_at = &input[at_pos + 1..];
-
-        // Find the first : which separates host from path
-        if let Some(colon_pos) = after_at.find(':') {
-            let host = &after_at[..colon_pos];
-            let path_and_rest = &after_at[colon_pos + 1..];
-
-            // Validate: user and host must be non-empty and not contain spaces
-            if !user.is_empty()
-                && !host.is_empty()
-                && !user.contains(' ')
-                && !host.contains(' ')
-                && !path_and_rest.is_empty()
-            {
-                // Now parse path:line:col from path_and_rest
-                // We need to distinguish between path components and line:col suffixes
-                // Strategy: work backwards, try to parse numeric suffixes
-
-                let parts: Vec<&str> = path_and_rest.rsplitn(3, ':').collect();
-
-                let (path, line, column) = match parts.as_slice() {
-                    [maybe_col, maybe_line, rest] => {
-                        if let (Ok(line), Ok(col)) =
-                            (maybe_line.parse::<usize>(), maybe_col.parse::<usize>())
-                        {
-                            (rest.to_string(), Some(line), Some(col))
-                        } else {
-                            (path_and_rest.to_string(), None, None)
-                        }
-                    }
-                    [maybe_line, rest] => {
-                        if let Ok(line) = maybe_line.parse::<usize>() {
-                            (rest.to_string(), Some(line), None)
-                        } else {
-                            (path_and_rest.to_string(), None, None)
-                        }
-                    }
-                    _ => (path_and_rest.to_string(), None, None),
-                };
-
-                return ParsedLocation::Remote(RemoteLocation {
-                    user: user.to_string(),
-                    host: host.to_string(),
-                    path,
-                    line,
-                    column,
-                });
-            }
+    if let Some((user, after_at)) = input.split_once('@').filter(|(user, _)| !user.is_empty()) {
+        if let Some(remote) = parse_remote_location(user, after_at) {
+            return ParsedLocation::Remote(remote);
         }
     }
 
@@ -1056,6 +1361,313 @@ fn parse_location(input: &str) -> ParsedLocation {
     ParsedLocation::Local(parse_file_location(input))
 }
 
+/// Parse the host[:port]:path[:line[:col]] part of a remote location
+fn parse_remote_location(user: &str, after_at: &str) -> Option<RemoteLocation> {
+    // Find the first : which separates host from path
+    let (host, path_and_rest) = after_at.split_once(':')?;
+
+    // Optional port between host and path: user@host:2222:/path.
+    // A number followed by another number is a path and a line
+    // (user@host:2222:10), not a port.
+    let (port, path_and_rest) = match path_and_rest.split_once(':') {
+        Some((port, rest)) if looks_like_path(rest) => match parse_ssh_port(port) {
+            Some(port) => (Some(port), rest),
+            None => (None, path_and_rest),
+        },
+        _ => (None, path_and_rest),
+    };
+
+    // Validate: host must be non-empty and neither may contain spaces
+    if host.is_empty() || user.contains(' ') || host.contains(' ') || path_and_rest.is_empty() {
+        return None;
+    }
+
+    // Now parse path:line:col from path_and_rest
+    // We need to distinguish between path components and line:col suffixes
+    // Strategy: work backwards, try to parse numeric suffixes
+    let parts: Vec<&str> = path_and_rest.rsplitn(3, ':').collect();
+
+    let (path, line, column) = match parts.as_slice() {
+        [maybe_col, maybe_line, rest] => {
+            if let (Ok(line), Ok(col)) = (maybe_line.parse::<usize>(), maybe_col.parse::<usize>()) {
+                (rest.to_string(), Some(line), Some(col))
+            } else {
+                (path_and_rest.to_string(), None, None)
+            }
+        }
+        [maybe_line, rest] => {
+            if let Ok(line) = maybe_line.parse::<usize>() {
+                (rest.to_string(), Some(line), None)
+            } else {
+                (path_and_rest.to_string(), None, None)
+            }
+        }
+        _ => (path_and_rest.to_string(), None, None),
+    };
+
+    Some(RemoteLocation {
+        user: user.to_string(),
+        host: host.to_string(),
+        port,
+        identity_file: None,
+        path,
+        line,
+        column,
+        search: None,
+    })
+}
+
+/// Like [`parse_location`], but `alias:path` is also remote when `alias` is
+/// a `Host` in the ssh config and no local file has that name
+fn parse_location_with_ssh_config(input: &str, ssh_config: &remote::SshConfig) -> ParsedLocation {
+    let location = parse_location(input);
+    if !matches!(location, ParsedLocation::Local(_)) || input.contains('@') {
+        return location;
+    }
+    let is_alias = input
+        .split_once(':')
+        .is_some_and(|(host, _)| ssh_config.has_host(host));
+    if !is_alias || std::path::Path::new(input).exists() {
+        return location;
+    }
+    parse_remote_location("", input)
+        .map(ParsedLocation::Remote)
+        .unwrap_or(location)
+}
+
+/// Where to put the cursor in the next file, from a vim-style argument
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum StartPosition {
+    /// `+N`: line N
+    Line(usize),
+    /// `+`: the last line
+    End,
+    /// `+/pattern`: the first match of a regex
+    Search(String),
+}
+
+/// Parse `+N`, `+` or `+/pattern`. Anything else, and an existing file
+/// with such a name, is a file argument.
+fn parse_start_position(arg: &str) -> Option<StartPosition> {
+    let rest = arg.strip_prefix('+')?;
+    if std::path::Path::new(arg).is_file() {
+        return None;
+    }
+    if rest.is_empty() {
+        return Some(StartPosition::End);
+    }
+    if let Some(pattern) = rest.strip_prefix('/') {
+        return (!pattern.is_empty()).then(|| StartPosition::Search(pattern.to_string()));
+    }
+    rest.parse().ok().map(StartPosition::Line)
+}
+
+/// Parse the file arguments, applying each `+N`, `+` or `+/pattern` to the
+/// file that follows it. "-" (stdin) is handled separately and skipped.
+fn parse_file_args(files: &[String], ssh_config: &remote::SshConfig) -> Vec<ParsedLocation> {
+    let mut locations = Vec::new();
+    let mut start = None;
+    for arg in files {
+        if arg == "-" {
+            start = None;
+            continue;
+        }
+        if let Some(position) = parse_start_position(arg) {
+            start = Some(position);
+            continue;
+        }
+        let mut location = parse_location_with_ssh_config(arg, ssh_config);
+        if let Some(position) = start.take() {
+            location.apply_start_position(position);
+        }
+        locations.push(location);
+    }
+    locations
+}
+
+/// Most files a single glob argument may open
+const MAX_GLOB_MATCHES: usize = 200;
+
+/// Expand local arguments like `src\*.rs` that the shell left alone (cmd.exe
+/// never expands wildcards). Only patterns that aren't the name of an existing
+/// file are expanded, and each match keeps the argument's line and column.
+fn expand_file_globs(locations: Vec<ParsedLocation>) -> AnyhowResult<Vec<ParsedLocation>> {
+    let mut expanded = Vec::with_capacity(locations.len());
+    for location in locations {
+        let ParsedLocation::Local(file) = &location else {
+            expanded.push(location);
+            continue;
+        };
+        let pattern = file.path.to_string_lossy();
+        if !glob_match::is_glob_pattern(&pattern) || file.path.exists() {
+            expanded.push(location);
+            continue;
+        }
+        for path in glob_files(&pattern)? {
+            expanded.push(ParsedLocation::Local(FileLocation {
+                path,
+                ..file.clone()
+            }));
+        }
+    }
+    Ok(expanded)
+}
+
+/// The files matching `pattern`, in order. `\` separates directories like
+/// `/` on every platform.
+fn glob_files(pattern: &str) -> AnyhowResult<Vec<PathBuf>> {
+    let entries = glob::glob(&pattern.replace('\\', "/"))
+        .with_context(|| format!("{}: invalid glob pattern", pattern))?;
+    let mut files = Vec::new();
+    // Entries that can't be read are skipped, like a shell does
+    for path in entries.flatten().filter(|path| path.is_file()) {
+        if files.len() == MAX_GLOB_MATCHES {
+            anyhow::bail!(
+                "{}: matches more than {} files, use a narrower pattern",
+                pattern,
+                MAX_GLOB_MATCHES
+            );
+        }
+        files.push(path);
+    }
+    if files.is_empty() {
+        anyhow::bail!("{}: no matching files", pattern);
+    }
+    Ok(files)
+}
+
+/// Take the directory argument out of `locations`. It becomes the working
+/// directory, and relative file paths among the rest are resolved against
+/// it, so `fresh ~/proj src/main.rs` opens `~/proj/src/main.rs`. A relative
+/// local directory is made absolute with `current_dir` first. Passing more
+/// than one directory is an error. Local files next to remote ones are never
+/// the working directory.
+fn split_working_dir(
+    locations: Vec<FileLocation>,
+    is_dir: impl Fn(&FileLocation) -> bool,
+    current_dir: &Path,
+) -> AnyhowResult<(Option<PathBuf>, Vec<FileLocation>)> {
+    let (dirs, mut files): (Vec<_>, Vec<_>) = locations.into_iter().partition(|loc| {
+        loc.connection.as_deref() != Some(fresh::app::LOCAL_CONNECTION) && is_dir(loc)
+    });
+    let dir = match dirs.as_slice() {
+        [] => return Ok((None, files)),
+        [dir] if dir.connection.is_some() => dir.path.clone(),
+        [dir] => current_dir.join(&dir.path),
+        [first, second, ..] => anyhow::bail!(
+            "Only one directory can be opened, got {} and {}",
+            first.path.display(),
+            second.path.display()
+        ),
+    };
+    for file in &mut files {
+        if file.path.is_relative() {
+            file.path = dir.join(&file.path);
+        }
+    }
+    Ok((Some(dir), files))
+}
+
+/// Convert parsed arguments to the files to open, setting URLs aside.
+/// Next to remote files (`remote`), local ones are opened on this machine by
+/// absolute path, since the editor's filesystem and working directory are
+/// remote.
+fn file_locations_from_args(
+    parsed_locations: Vec<ParsedLocation>,
+    remote: bool,
+    ssh_config: &remote::SshConfig,
+    current_dir: &Path,
+) -> (Vec<FileLocation>, Vec<String>) {
+    let mut urls = Vec::new();
+    let file_locations = parsed_locations
+        .into_iter()
+        .filter_map(|loc| match loc {
+            ParsedLocation::Local(fl) if remote => Some(FileLocation {
+                connection: Some(fresh::app::LOCAL_CONNECTION.to_string()),
+                path: current_dir.join(&fl.path),
+                ..fl
+            }),
+            ParsedLocation::Local(fl) => Some(fl),
+            ParsedLocation::Remote(rl) => Some(FileLocation {
+                connection: Some(rl.key(ssh_config).to_string()),
+                path: PathBuf::from(&rl.path),
+                line: rl.line,
+                column: rl.column,
+                end_line: None,
+                end_column: None,
+                message: None,
+                search: rl.search,
+            }),
+            ParsedLocation::Url(url) => {
+                urls.push(url);
+                None
+            }
+        })
+        .collect();
+    (file_locations, urls)
+}
+
+/// Queue files from the command line to be opened once the editor runs.
+/// Returns true if any were queued.
+fn queue_cli_files(editor: &mut Editor, file_locations: &[FileLocation]) -> bool {
+    let mut queued = false;
+    for loc in file_locations {
+        if loc.path.is_dir() {
+            continue;
+        }
+        tracing::info!("[SYNTAX DEBUG] Queueing CLI file for open: {:?}", loc.path);
+        if let Some(connection) = &loc.connection {
+            editor.queue_remote_file_open(
+                loc.path.clone(),
+                loc.line,
+                loc.column,
+                connection.clone(),
+            );
+        } else {
+            editor.queue_file_open(
+                loc.path.clone(),
+                loc.line,
+                loc.column,
+                loc.end_line,
+                loc.end_column,
+                loc.message.clone(),
+                None,
+            );
+        }
+        if let Some(pattern) = &loc.search {
+            editor.queue_file_search(pattern.clone());
+        }
+        queued = true;
+    }
+    queued
+}
+
+impl ParsedLocation {
+    fn apply_start_position(&mut self, position: StartPosition) {
+        let (line, column, search) = match self {
+            ParsedLocation::Local(fl) => {
+                fl.end_line = None;
+                fl.end_column = None;
+                (&mut fl.line, &mut fl.column, &mut fl.search)
+            }
+            ParsedLocation::Remote(rl) => (&mut rl.line, &mut rl.column, &mut rl.search),
+            ParsedLocation::Url(_) => return,
+        };
+        match position {
+            StartPosition::Line(n) => {
+                *line = Some(n);
+                *column = None;
+            }
+            // Clamped to the last line when the cursor moves
+            StartPosition::End => {
+                *line = Some(usize::MAX);
+                *column = None;
+            }
+            StartPosition::Search(pattern) => *search = Some(pattern),
+        }
+    }
+}
+
 /// Holds resources needed for remote editing (kept alive for duration of session)
 struct RemoteSession {
     /// The SSH connection - dropping this closes the connection
@@ -1064,48 +1676,73 @@ struct RemoteSession {
     _runtime: tokio::runtime::Runtime,
 }
 
-/// Result of creating filesystem - includes optional remote session to keep alive
+/// Result of creating filesystem - includes remote sessions to keep alive
 struct FilesystemResult {
     filesystem: std::sync::Arc<dyn FileSystem + Send + Sync>,
     /// Process spawner for plugin command execution
     process_spawner: std::sync::Arc<dyn remote::ProcessSpawner>,
-    /// Remote session resources - must be kept alive for remote editing
-    remote_session: Option<RemoteSession>,
+    /// One session per remote connection - must be kept alive for remote editing
+    remote_sessions: HashMap<remote::ConnectionKey, RemoteSession>,
 }
 
 /// Create filesystem for local or remote editing
-fn create_filesystem(remote_info: &Option<RemoteLocation>) -> AnyhowResult<FilesystemResult> {
-    if let Some(remote) = remote_info {
-        connect_remote(remote)
-    } else {
-        Ok(FilesystemResult {
+///
+/// With remote connections, the filesystem is a router that acts as the
+/// first connection and hands the others out to the buffers opened on them.
+fn create_filesystem(connections: &[remote::ConnectionKey]) -> AnyhowResult<FilesystemResult> {
+    let Some((primary_key, others)) = connections.split_first() else {
+        return Ok(FilesystemResult {
             filesystem: std::sync::Arc::new(StdFileSystem),
             process_spawner: std::sync::Arc::new(remote::LocalProcessSpawner),
-            remote_session: None,
-        })
+            remote_sessions: HashMap::new(),
+        });
+    };
+
+    let mut remote_sessions = HashMap::new();
+    let (primary, process_spawner, session) = connect_remote(primary_key)?;
+    remote_sessions.insert(primary_key.clone(), session);
+    let mut router = remote::FileSystemRouter::new(primary_key.clone(), primary);
+    for key in others {
+        let (filesystem, _, session) = connect_remote(key)?;
+        router.add(key.clone(), filesystem);
+        remote_sessions.insert(key.clone(), session);
     }
+
+    Ok(FilesystemResult {
+        filesystem: std::sync::Arc::new(router),
+        process_spawner,
+        remote_sessions,
+    })
 }
 
-/// Establish SSH connection to remote host and return RemoteFileSystem
-fn connect_remote(remote: &RemoteLocation) -> AnyhowResult<FilesystemResult> {
+/// Establish SSH connection to remote host and return its RemoteFileSystem
+#[allow(clippy::type_complexity)]
+fn connect_remote(
+    key: &remote::ConnectionKey,
+) -> AnyhowResult<(
+    std::sync::Arc<dyn FileSystem + Send + Sync>,
+    std::sync::Arc<dyn remote::ProcessSpawner>,
+    RemoteSession,
+)> {
     // Create a Tokio runtime for the SSH connection
     let rt = tokio::runtime::Runtime::new()
         .context("Failed to create Tokio runtime for remote connection")?;
 
-    let connection_params = remote::ConnectionParams {
-        user: remote.user.clone(),
-        host: remote.host.clone(),
-        port: None, // TODO: support port in remote location parsing
-        identity_file: None,
+    // Ask for the key's passphrase while the terminal is still in cooked mode
+    let params = key.params();
+    let passphrase = match &params.identity_file {
+        Some(identity) => remote::prompt_key_passphrase(identity)
+            .context(format!("Failed to connect to remote host {}", key))?,
+        None => None,
     };
 
     // Establish SSH connection (this is async, so we block on it)
     let connection = rt
-        .block_on(remote::SshConnection::connect(connection_params))
-        .context(format!(
-            "Failed to connect to remote host {}@{}",
-            remote.user, remote.host
-        ))?;
+        .block_on(remote::SshConnection::connect_with_passphrase(
+            params,
+            passphrase.as_ref(),
+        ))
+        .context(format!("Failed to connect to remote host {}", key))?;
 
     let connection_string = connection.connection_string();
     let channel = connection.channel();
@@ -1118,14 +1755,14 @@ fn connect_remote(remote: &RemoteLocation) -> AnyhowResult<FilesystemResult> {
     ));
     let process_spawner = std::sync::Arc::new(remote::RemoteProcessSpawner::new(channel));
 
-    Ok(FilesystemResult {
+    Ok((
         filesystem,
         process_spawner,
-        remote_session: Some(RemoteSession {
+        RemoteSession {
             _connection: connection,
             _runtime: rt,
-        }),
-    })
+        },
+    ))
 }
 
 fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
@@ -1135,8 +1772,9 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
         .unwrap_or_else(fresh::services::log_dirs::main_log_path);
     let tracing_handles = tracing_setup::init_global(&log_file);
 
-    // Clean up stale log files from dead processes on startup
+    // Clean up stale log files and stdin temp files from dead processes on startup
     fresh::services::log_dirs::cleanup_stale_logs();
+    fresh::services::log_dirs::cleanup_stale_stdin_temps();
 
     tracing::info!("Editor starting");
 
@@ -1146,6 +1784,7 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic| {
         terminal_modes::emergency_cleanup();
+        stdin_spool::remove_spilled_file();
         original_hook(panic);
     }));
 
@@ -1155,16 +1794,13 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
 
     // Start stdin streaming in background BEFORE entering raw mode
     // This is critical - once raw mode is enabled, stdin is used for terminal events
-    // Background thread streams pipe → temp file while editor runs
+    // Background thread streams pipe → memory or temp file while editor runs
     let stdin_stream = if stdin_requested {
         if stdin_has_data() {
             tracing::info!("Starting background stdin streaming");
             match start_stdin_streaming() {
                 Ok(stream_state) => {
-                    tracing::info!(
-                        "Stdin streaming started, temp file: {:?}",
-                        stream_state.temp_path
-                    );
+                    tracing::info!("Stdin streaming started");
                     Some(stream_state)
                 }
                 Err(e) => {
@@ -1183,67 +1819,104 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
         None
     };
 
+    // With --to-stdout the TUI draws on the terminal directly, keeping stdout
+    // for the edited stdin buffer. Must also happen before raw mode.
+    let filter_output = if args.to_stdout {
+        if !stdin_requested {
+            eprintln!("Error: --to-stdout needs piped input (\"-\" or --stdin)");
+            anyhow::bail!(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--to-stdout without stdin",
+            ));
+        }
+        if !stdout_is_redirected() {
+            eprintln!("Error: --to-stdout specified but stdout is a terminal (not piped)");
+            anyhow::bail!(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "stdout is not piped",
+            ));
+        }
+        let output = redirect_stdout_to_tty()?;
+        tracing::info!("Moved stdout to the terminal for --to-stdout");
+        Some(output)
+    } else {
+        None
+    };
+
     // Determine working directory early for config loading
     // Filter out "-" from files list since it's handled via stdin_stream
-    // Parse locations which may be local or remote (user@host:path)
-    let parsed_locations: Vec<ParsedLocation> = args
-        .files
+    // Parse locations which may be local or remote (user@host:path, or
+    // alias:path for a host in ~/.ssh/config)
+    let ssh_config = remote::SshConfig::load();
+    // Wildcards the shell didn't expand (cmd.exe) are expanded here
+    let mut parsed_locations = expand_file_globs(parse_file_args(&args.files, &ssh_config))?;
+    // --diff/--merge files may mix local and remote sides
+    let mut diff_locations: Vec<(PathBuf, Option<RemoteLocation>)> = args
+        .diff_files
         .iter()
-        .filter(|f| *f != "-")
-        .map(|f| parse_location(f))
+        .map(|path| (path.clone(), parse_diff_location(path, &ssh_config)))
         .collect();
 
+    // --port applies to every remote location that doesn't name its own port,
+    // --identity to every one it names or matches
+    let remotes = parsed_locations
+        .iter_mut()
+        .filter_map(|loc| match loc {
+            ParsedLocation::Remote(remote) => Some(remote),
+            ParsedLocation::Local(_) | ParsedLocation::Url(_) => None,
+        })
+        .chain(
+            diff_locations
+                .iter_mut()
+                .filter_map(|(_, remote)| remote.as_mut()),
+        );
+    for remote in remotes {
+        remote.port = remote.port.or(args.port);
+        remote.identity_file = identity_for_host(&args.identity, &remote.host);
+    }
+
     // Check for remote locations - for now, collect them separately
     let remote_locations: Vec<&RemoteLocation> = parsed_locations
         .iter()
         .filter_map(|loc| match loc {
             ParsedLocation::Remote(r) => Some(r),
-            ParsedLocation::Local(_) => None,
+            ParsedLocation::Local(_) | ParsedLocation::Url(_) => None,
         })
+        .chain(
+            diff_locations
+                .iter()
+                .filter_map(|(_, remote)| remote.as_ref()),
+        )
         .collect();
 
-    // If there are remote locations, validate they're all on the same host
-    let remote_info: Option<RemoteLocation> = if !remote_locations.is_empty() {
-        let first = remote_locations[0];
-        for r in &remote_locations[1..] {
-            if r.user != first.user || r.host != first.host {
-                anyhow::bail!(
-                    "Cannot open files from multiple remote hosts. \
-                     First: {}@{}, found: {}@{}",
-                    first.user,
-                    first.host,
-                    r.user,
-                    r.host
-                );
-            }
+    // One connection per distinct user@host:port, in the order first named;
+    // the first one is what the file explorer and plugins see
+    let mut connections: Vec<remote::ConnectionKey> = Vec::new();
+    for key in remote_locations.iter().map(|r| r.key(&ssh_config)) {
+        if !connections.contains(&key) {
+            connections.push(key);
         }
-        // Check that there are no local files mixed with remote
-        let has_local = parsed_locations
-            .iter()
-            .any(|loc| matches!(loc, ParsedLocation::Local(_)));
-        if has_local {
-            anyhow::bail!(
-                "Cannot mix local and remote files. Use either local paths or remote paths (user@host:path)."
-            );
-        }
-        Some(first.clone())
-    } else {
-        None
-    };
+    }
+    // Convert to FileLocation for downstream code; URLs are opened separately
+    let current_dir = std::env::current_dir().unwrap_or_default();
+    let (file_locations, urls) = file_locations_from_args(
+        parsed_locations,
+        !connections.is_empty(),
+        &ssh_config,
+        &current_dir,
+    );
 
-    // Convert to FileLocation for downstream code
-    let file_locations: Vec<FileLocation> = parsed_locations
+    // Local diff sides are relative to where fresh was started, also when
+    // the editor's working directory is on a remote host
+    let diff_files: Vec<DiffFile> = diff_locations
         .into_iter()
-        .map(|loc| match loc {
-            ParsedLocation::Local(fl) => fl,
-            ParsedLocation::Remote(rl) => FileLocation {
+        .map(|(path, remote)| match remote {
+            Some(rl) => DiffFile {
+                connection: Some(rl.key(&ssh_config).to_string()),
                 path: PathBuf::from(&rl.path),
-                line: rl.line,
-                column: rl.column,
-                end_line: None,
-                end_column: None,
-                message: None,
             },
+            None if DiffFile::is_null_device(&path) => DiffFile::local(&path),
+            None => DiffFile::local(&current_dir.join(path)),
         })
         .collect();
 
@@ -1252,28 +1925,21 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
     let FilesystemResult {
         filesystem,
         process_spawner,
-        remote_session,
-    } = create_filesystem(&remote_info)?;
+        remote_sessions,
+    } = create_filesystem(&connections)?;
 
-    let mut working_dir = None;
-    let mut show_file_explorer = false;
-
-    // Only set working_dir if exactly one parameter is passed and it's a directory
-    if file_locations.len() == 1 {
-        if let Some(first_loc) = file_locations.first() {
-            // Use the filesystem to check if path is a directory
-            // This works for both local and remote paths
-            let is_directory = filesystem.is_dir(&first_loc.path).unwrap_or(false);
-            if is_directory {
-                working_dir = Some(first_loc.path.clone());
-                show_file_explorer = true;
-            }
-        }
-    }
+    // The first directory argument becomes the working directory and shows
+    // the file explorer; the other arguments open as files
+    let (working_dir, file_locations) = split_working_dir(
+        file_locations,
+        |loc| filesystem.is_dir(&loc.path).unwrap_or(false),
+        &current_dir,
+    )?;
+    let show_file_explorer = working_dir.is_some();
 
     // Load config using the layered config system
     // For remote editing, use current local dir for config (remote doesn't have our config)
-    let effective_working_dir = if remote_info.is_some() {
+    let effective_working_dir = if !connections.is_empty() {
         std::env::current_dir().unwrap_or_default()
     } else {
         working_dir
@@ -1286,7 +1952,7 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
 
     let mut config = if let Some(config_path) = &args.config {
         // Explicit config file overrides layered system
-        match config::Config::load_from_file(config_path) {
+        match config::Config::load_from_file_with_env(config_path) {
             Ok(cfg) => cfg,
             Err(e) => {
                 eprintln!(
@@ -1305,6 +1971,9 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
     if args.no_upgrade_check {
         config.check_for_updates = false;
     }
+    if let Some(theme) = &args.theme {
+        config.override_theme(fresh::view::theme::normalize_theme_name(theme).into());
+    }
 
     // Initialize i18n with locale: CLI arg > config > environment
     // This ensures menu defaults are created with the correct translations
@@ -1368,16 +2037,19 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
         terminal,
         terminal_size: (size.width, size.height),
         file_locations,
+        diff_files,
+        urls,
         show_file_explorer,
         dir_context,
         current_working_dir,
         stdin_stream,
+        filter_output,
         key_translator,
         gpm_client,
         terminal_modes,
         filesystem,
         process_spawner,
-        _remote_session: remote_session,
+        _remote_sessions: remote_sessions,
     })
 }
 
@@ -2048,6 +2720,93 @@ MIT
     Ok(())
 }
 
+/// Run the startup sequence headlessly (no terminal modes, render to an
+/// in-memory backend), print a phase-by-phase timing report and exit.
+/// Background work (grammar build, plugin loading) is waited for and listed
+/// separately since it doesn't delay the first frame.
+fn profile_startup_command(args: &Args) -> AnyhowResult<()> {
+    use fresh::services::startup_profile::StartupProfile;
+    use std::time::Instant;
+
+    let mut profile = StartupProfile::new();
+
+    let dir_context = DirectoryContext::from_system()?;
+    let working_dir = std::env::current_dir().unwrap_or_default();
+    let config = if let Some(config_path) = &args.config {
+        config::Config::load_from_file_with_env(config_path).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to load config from {}: {}",
+                config_path.display(),
+                e
+            )
+        })?
+    } else {
+        config::Config::load_with_layers(&dir_context, &working_dir)
+    };
+    profile.mark("config load");
+
+    let locale_override = args.locale.as_deref().or(config.locale.as_option());
+    fresh::i18n::init_with_config(locale_override);
+    profile.mark("i18n init");
+
+    let FilesystemResult {
+        filesystem,
+        process_spawner,
+        ..
+    } = create_filesystem(&[])?;
+    let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+    let mut editor = Editor::with_working_dir(
+        config,
+        width,
+        height,
+        None,
+        dir_context,
+        !args.no_plugins,
+        fresh::view::color_support::ColorCapability::detect(),
+        filesystem,
+    )?;
+    editor.set_process_spawner(process_spawner);
+    profile.mark("editor construction");
+    let construction_phases = editor.startup_timings().len();
+    for (phase, duration) in editor.startup_timings() {
+        profile.detail(*phase, *duration);
+    }
+
+    if !args.no_session {
+        if let Err(e) = editor.try_restore_workspace() {
+            eprintln!("Failed to restore workspace: {}", e);
+        }
+    }
+    for file in args
+        .files
+        .iter()
+        .filter(|f| *f != "-" && parse_start_position(f).is_none())
+    {
+        if let ParsedLocation::Local(location) = parse_location(file) {
+            if let Err(e) = editor.open_file(&location.path) {
+                eprintln!("Failed to open {}: {}", location.path.display(), e);
+            }
+        }
+    }
+    profile.mark("session restore and file open");
+
+    let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(width, height))?;
+    terminal.draw(|frame| editor.render(frame))?;
+    profile.mark("first render");
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    while editor.startup_work_pending() && Instant::now() < deadline {
+        editor.process_async_messages();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    for (phase, duration) in &editor.startup_timings()[construction_phases..] {
+        profile.background(*phase, *duration);
+    }
+
+    print!("{}", profile.report());
+    Ok(())
+}
+
 // === Session persistence commands ===
 
 /// List active sessions
@@ -2190,6 +2949,55 @@ fn kill_session_command(session: Option<&str>, args: &Args) -> AnyhowResult<()>
 }
 
 /// Run as a daemon server
+/// Run the editor without a terminal until a `quit` command arrives
+fn run_headless_command(args: &Args, socket: &std::path::Path) -> AnyhowResult<()> {
+    use fresh::server::protocol::TermSize;
+    use fresh::server::{HeadlessConfig, HeadlessEditor};
+
+    // Files are parsed as in the normal CLI (file:line:col, +N, globs), but
+    // the headless editor only has the local filesystem
+    let ssh_config = remote::SshConfig::load();
+    let parsed_locations = expand_file_globs(parse_file_args(&args.files, &ssh_config))?;
+    if let Some(ParsedLocation::Remote(remote)) = parsed_locations
+        .iter()
+        .find(|loc| matches!(loc, ParsedLocation::Remote(_)))
+    {
+        anyhow::bail!(
+            "Headless mode can't open remote files, got {}:{}",
+            remote.host,
+            remote.path
+        );
+    }
+    let current_dir = std::env::current_dir()?;
+    let (file_locations, urls) =
+        file_locations_from_args(parsed_locations, false, &ssh_config, &current_dir);
+    let (working_dir, file_locations) =
+        split_working_dir(file_locations, |loc| loc.path.is_dir(), &current_dir)?;
+    let working_dir = working_dir.unwrap_or(current_dir);
+
+    let dir_context = fresh::config_io::DirectoryContext::from_system()?;
+    let editor_config = if let Some(config_path) = &args.config {
+        config::Config::load_from_file_with_env(config_path)?
+    } else {
+        config::Config::load_with_layers(&dir_context, &working_dir)
+    };
+
+    let mut headless = HeadlessEditor::new(HeadlessConfig {
+        control_socket: socket.to_path_buf(),
+        working_dir,
+        term_size: TermSize::new(80, 24),
+        editor_config,
+        dir_context,
+        plugins_enabled: !args.no_plugins,
+    })?;
+    queue_cli_files(headless.editor_mut(), &file_locations);
+    for url in &urls {
+        headless.editor_mut().open_url(url);
+    }
+    headless.run()?;
+    Ok(())
+}
+
 fn run_server_command(args: &Args) -> AnyhowResult<()> {
     use fresh::server::{EditorServer, EditorServerConfig};
 
@@ -2215,7 +3023,7 @@ fn run_server_command(args: &Args) -> AnyhowResult<()> {
     // Load editor config
     eprintln!("[server] Loading editor config...");
     let editor_config = if let Some(config_path) = &args.config {
-        config::Config::load_from_file(config_path)?
+        config::Config::load_from_file_with_env(config_path)?
     } else {
         config::Config::load_with_layers(&dir_context, &working_dir)
     };
@@ -2580,6 +3388,55 @@ fn run_attach(session_name: Option<&str>) -> AnyhowResult<()> {
     Ok(())
 }
 
+/// A --diff/--merge file on a remote host (`user@host:path`, or
+/// `alias:path` for a host in the ssh config); None for a local path
+fn parse_diff_location(
+    path: &std::path::Path,
+    ssh_config: &remote::SshConfig,
+) -> Option<RemoteLocation> {
+    match parse_location_with_ssh_config(&path.to_string_lossy(), ssh_config) {
+        ParsedLocation::Remote(remote) => Some(remote),
+        ParsedLocation::Local(_) | ParsedLocation::Url(_) => None,
+    }
+}
+
+/// Check that the local files given to --diff/--merge can be compared.
+/// Remote ones are checked once connected.
+fn validate_diff_files(files: &[PathBuf]) -> AnyhowResult<()> {
+    let ssh_config = remote::SshConfig::load();
+    let is_local = |path: &PathBuf| parse_diff_location(path, &ssh_config).is_none();
+    // The null device stands for the missing side of an added or deleted file
+    let is_file = |path: &PathBuf| is_local(path) && !DiffFile::is_null_device(path);
+    for path in files.iter().filter(|path| is_file(path)) {
+        if !path.exists() {
+            anyhow::bail!("{}: no such file", path.display());
+        }
+        if !path.is_file() {
+            anyhow::bail!("{}: not a regular file", path.display());
+        }
+    }
+    if let [left, right, ..] = files {
+        if is_file(left) && is_file(right) && left.canonicalize()? == right.canonicalize()? {
+            anyhow::bail!("cannot compare {} with itself", left.display());
+        }
+    }
+    Ok(())
+}
+
+/// Print git configuration that makes Fresh the difftool and mergetool.
+fn print_git_tool_config() {
+    println!("# Add to ~/.gitconfig to use Fresh with `git difftool` and `git mergetool`");
+    println!("[diff]");
+    println!("\ttool = fresh");
+    println!("[difftool \"fresh\"]");
+    println!("\tcmd = fresh --diff \"$LOCAL\" \"$REMOTE\"");
+    println!("[merge]");
+    println!("\ttool = fresh");
+    println!("[mergetool \"fresh\"]");
+    println!("\tcmd = fresh --merge \"$LOCAL\" \"$REMOTE\" \"$MERGED\"");
+    println!("\ttrustExitCode = true");
+}
+
 /// Print deprecation warnings for old CLI flags
 fn print_deprecation_warnings(cli: &Cli) {
     // Only print warnings if no --cmd is used (i.e., using deprecated flags directly)
@@ -2600,6 +3457,10 @@ fn print_deprecation_warnings(cli: &Cli) {
 }
 
 fn main() -> AnyhowResult<()> {
+    // Started by ssh as SSH_ASKPASS to answer a key passphrase prompt
+    if let Some(code) = remote::askpass_main() {
+        std::process::exit(code);
+    }
     real_main()
 }
 
@@ -2612,6 +3473,13 @@ fn real_main() -> AnyhowResult<()> {
     // Convert to legacy Args format for compatibility
     let args: Args = cli.into();
 
+    // Decide how workspace trust works before any config layer is loaded
+    fresh::services::workspace_trust::set_policy(match args.trust {
+        Some(true) => TrustPolicy::TrustAll,
+        Some(false) => TrustPolicy::TrustNone,
+        None => TrustPolicy::Ask,
+    });
+
     // Handle --show-paths early (no terminal setup needed)
     if args.show_paths {
         let dir_context = fresh::config_io::DirectoryContext::from_system()?;
@@ -2619,12 +3487,36 @@ fn real_main() -> AnyhowResult<()> {
         return Ok(());
     }
 
+    // Handle --check-config early (no terminal setup needed)
+    if args.check_config {
+        let dir_context = fresh::config_io::DirectoryContext::from_system()?;
+        let working_dir = std::env::current_dir().unwrap_or_default();
+        let resolver = fresh::config_io::ConfigResolver::new(dir_context, working_dir);
+        let mut paths = resolver.existing_layer_paths();
+        paths.extend(args.config.clone());
+        if paths.is_empty() {
+            println!("No config files found");
+        }
+        let mut found_problems = false;
+        for path in &paths {
+            let problems = fresh::config_io::check_config_file(path);
+            if problems.is_empty() {
+                println!("{}: ok", path.display());
+            }
+            for problem in problems {
+                eprintln!("{}", problem);
+                found_problems = true;
+            }
+        }
+        std::process::exit(i32::from(found_problems));
+    }
+
     // Handle --dump-config early (no terminal setup needed)
     if args.dump_config {
         let dir_context = fresh::config_io::DirectoryContext::from_system()?;
         let working_dir = std::env::current_dir().unwrap_or_default();
         let config = if let Some(config_path) = &args.config {
-            match config::Config::load_from_file(config_path) {
+            match config::Config::load_from_file_with_env(config_path) {
                 Ok(cfg) => cfg,
                 Err(e) => {
                     eprintln!(
@@ -2682,16 +3574,34 @@ fn real_main() -> AnyhowResult<()> {
         return run_server_command(&args);
     }
 
+    // Handle --headless: no terminal, commands come over the control socket
+    if let Some(socket) = &args.headless_socket {
+        return run_headless_command(&args, socket);
+    }
+
     // Handle open-file in session: send files to running session without attaching
     if let Some((session_name, files, wait)) = &args.open_files_in_session {
         return run_open_files_command(session_name.as_deref(), files, *wait);
     }
 
+    // Check --diff/--merge files before touching the terminal
+    if !args.diff_files.is_empty() {
+        if let Err(e) = validate_diff_files(&args.diff_files) {
+            eprintln!("Error: {}", e);
+            std::process::exit(2);
+        }
+    }
+
     // Handle --attach: connect to existing session
     if args.attach {
         return run_attach_command(&args);
     }
 
+    // Handle --profile-startup: headless startup with a timing report
+    if args.profile_startup {
+        return profile_startup_command(&args);
+    }
+
     // Handle --gui: launch in native window mode (no terminal setup needed)
     #[cfg(feature = "gui")]
     if args.gui {
@@ -2711,10 +3621,13 @@ fn real_main() -> AnyhowResult<()> {
         mut terminal,
         terminal_size,
         file_locations,
+        diff_files,
+        urls,
         show_file_explorer,
         dir_context,
         current_working_dir: initial_working_dir,
         mut stdin_stream,
+        mut filter_output,
         key_translator,
         #[cfg(target_os = "linux")]
         gpm_client,
@@ -2723,7 +3636,7 @@ fn real_main() -> AnyhowResult<()> {
         mut terminal_modes,
         filesystem,
         process_spawner,
-        _remote_session,
+        _remote_sessions,
     } = initialize_app(&args).context("Failed to initialize application")?;
 
     let mut current_working_dir = initial_working_dir;
@@ -2736,10 +3649,15 @@ fn real_main() -> AnyhowResult<()> {
     let mut restore_workspace_on_restart = false;
 
     // Main editor loop - supports restarting with a new working directory
-    // Returns (loop_result, last_update_result) tuple
-    let (result, last_update_result) = loop {
+    // Returns (loop_result, last_update_result, exit_code) tuple
+    let (result, last_update_result, exit_code) = loop {
         let first_run = is_first_run;
-        let workspace_enabled = !args.no_session;
+        // Diff sessions neither restore nor save the workspace; neither do
+        // CLI file opens unless `session.restore_with_cli_files` is on
+        let has_cli_files = first_run && file_locations.iter().any(|loc| !loc.path.is_dir());
+        let workspace_enabled = !args.no_session
+            && args.diff_files.is_empty()
+            && (!has_cli_files || config.session.restore_with_cli_files);
 
         // Detect terminal color capability
         let color_capability = fresh::view::color_support::ColorCapability::detect();
@@ -2761,6 +3679,7 @@ fn real_main() -> AnyhowResult<()> {
 
         // Set the process spawner (LocalProcessSpawner for local, RemoteProcessSpawner for remote)
         editor.set_process_spawner(process_spawner.clone());
+        editor.set_open_read_only(args.readonly);
 
         #[cfg(target_os = "linux")]
         if gpm_client.is_some() {
@@ -2772,6 +3691,8 @@ fn real_main() -> AnyhowResult<()> {
                 &mut editor,
                 &args,
                 &file_locations,
+                &diff_files,
+                &urls,
                 show_file_explorer,
                 &mut stdin_stream,
                 &mut tracing_handles,
@@ -2805,6 +3726,8 @@ fn real_main() -> AnyhowResult<()> {
             tracing::warn!("Failed to start recovery session: {}", e);
         }
 
+        editor.prompt_workspace_trust_if_unknown();
+
         let iteration = run_editor_iteration(
             &mut editor,
             workspace_enabled,
@@ -2817,7 +3740,20 @@ fn real_main() -> AnyhowResult<()> {
 
         let update_result = iteration.update_result;
         let restart_dir = iteration.restart_dir;
-        let loop_result = iteration.loop_result;
+        let mut loop_result = iteration.loop_result;
+        let exit_code = editor
+            .startup_commands_exit_code()
+            .or_else(|| editor.diff_session_exit_code())
+            .or_else(|| editor.cli_wait_exit_code());
+
+        // --to-stdout: pass the edited stdin on to the next command
+        if let Some(output) = filter_output.as_mut().filter(|_| restart_dir.is_none()) {
+            if loop_result.is_ok() {
+                loop_result = editor
+                    .write_stdin_buffer(output)
+                    .context("Failed to write the stdin buffer to stdout");
+            }
+        }
 
         drop(editor);
 
@@ -2835,7 +3771,7 @@ fn real_main() -> AnyhowResult<()> {
             continue;
         }
 
-        break (loop_result, update_result);
+        break (loop_result, update_result, exit_code);
     };
 
     // Restore terminal state
@@ -2862,7 +3798,14 @@ fn real_main() -> AnyhowResult<()> {
         }
     }
 
-    result.context("Editor loop returned an error")
+    result.context("Editor loop returned an error")?;
+
+    // git reads the exit code to tell a saved resolution or commit message
+    // from an aborted one
+    if let Some(code) = exit_code.filter(|code| *code != 0) {
+        std::process::exit(code);
+    }
+    Ok(())
 }
 
 /// Main event loop
@@ -3040,6 +3983,8 @@ where
                 } else {
                     tracing::debug!("Workspace saved successfully");
                 }
+            } else {
+                editor.save_all_global_file_states();
             }
             break;
         }
@@ -3051,6 +3996,11 @@ where
             }
             last_render = Instant::now();
             needs_render = false;
+
+            // --execute commands run once the first frame is on screen
+            if editor.run_startup_commands() {
+                needs_render = true;
+            }
         }
 
         let event = if let Some(e) = pending_event.take() {
@@ -3067,7 +4017,7 @@ where
 
         let Some(event) = event else { continue };
 
-        let (event, next) = coalesce_mouse_moves(event)?;
+        let (event, wheel_ticks, next) = coalesce_mouse_events(event)?;
         pending_event = next;
 
         // Event debug dialog receives ALL RAW events (before any translation or processing)
@@ -3100,7 +4050,7 @@ where
                 }
             }
             CrosstermEvent::Mouse(mouse_event) => {
-                if handle_mouse_event(editor, mouse_event)? {
+                if handle_mouse_event(editor, mouse_event, wheel_ticks)? {
                     needs_render = true;
                 }
             }
@@ -3231,45 +4181,69 @@ fn handle_key_event(editor: &mut Editor, key_event: KeyEvent) -> AnyhowResult<()
     Ok(())
 }
 
-/// Handle a mouse event
+/// Handle a mouse event (`ticks` coalesced wheel events for scrolling)
 /// Returns true if a re-render is needed
-fn handle_mouse_event(editor: &mut Editor, mouse_event: MouseEvent) -> AnyhowResult<bool> {
+fn handle_mouse_event(
+    editor: &mut Editor,
+    mouse_event: MouseEvent,
+    ticks: u16,
+) -> AnyhowResult<bool> {
     tracing::trace!(
-        "Mouse event received: kind={:?}, column={}, row={}, modifiers={:?}",
+        "Mouse event received: kind={:?}, column={}, row={}, modifiers={:?}, ticks={}",
         mouse_event.kind,
         mouse_event.column,
         mouse_event.row,
-        mouse_event.modifiers
+        mouse_event.modifiers,
+        ticks
     );
 
     // Delegate to the editor's handle_mouse method
     editor
-        .handle_mouse(mouse_event)
+        .handle_mouse_with_ticks(mouse_event, ticks)
         .context("Failed to handle mouse event")
 }
 
-/// Skip stale mouse move events, return the latest one.
-/// If we read a non-move event while draining, return it as pending.
-fn coalesce_mouse_moves(
-    event: CrosstermEvent,
-) -> AnyhowResult<(CrosstermEvent, Option<CrosstermEvent>)> {
+/// Whether two events can be merged: mouse moves, or wheel ticks in the same
+/// direction with the same modifiers.
+fn mouse_events_coalesce(first: &CrosstermEvent, next: &CrosstermEvent) -> bool {
     use crossterm::event::MouseEventKind;
 
-    // Only coalesce mouse moves
-    if !matches!(&event, CrosstermEvent::Mouse(m) if m.kind == MouseEventKind::Moved) {
-        return Ok((event, None));
+    match (first, next) {
+        (CrosstermEvent::Mouse(a), CrosstermEvent::Mouse(b)) => match a.kind {
+            MouseEventKind::Moved => b.kind == MouseEventKind::Moved,
+            MouseEventKind::ScrollUp
+            | MouseEventKind::ScrollDown
+            | MouseEventKind::ScrollLeft
+            | MouseEventKind::ScrollRight => a.kind == b.kind && a.modifiers == b.modifiers,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Skip stale mouse move events and merge bursts of wheel ticks, returning
+/// the latest event and how many wheel ticks it stands for.
+/// If we read an event that doesn't coalesce while draining, return it as pending.
+fn coalesce_mouse_events(
+    event: CrosstermEvent,
+) -> AnyhowResult<(CrosstermEvent, u16, Option<CrosstermEvent>)> {
+    // Only coalesce mouse moves and wheel ticks
+    if !mouse_events_coalesce(&event, &event) {
+        return Ok((event, 1, None));
     }
 
     let mut latest = event;
+    let mut ticks: u16 = 1;
     while event_poll(Duration::ZERO)? {
         let next = event_read()?;
-        if matches!(&next, CrosstermEvent::Mouse(m) if m.kind == MouseEventKind::Moved) {
-            latest = next; // Newer move, skip the old one
+        if mouse_events_coalesce(&latest, &next) {
+            latest = next; // Newer event, skip the old one
+            ticks = ticks.saturating_add(1);
         } else {
-            return Ok((latest, Some(next))); // Hit a click/key, save it
+            return Ok((latest, ticks, Some(next))); // Hit a click/key, save it
         }
     }
-    Ok((latest, None))
+    Ok((latest, ticks, None))
 }
 
 #[cfg(test)]
@@ -3353,6 +4327,223 @@ mod tests {
         assert_eq!(loc.column, None);
     }
 
+    #[test]
+    fn test_parse_start_position() {
+        assert_eq!(parse_start_position("+42"), Some(StartPosition::Line(42)));
+        assert_eq!(parse_start_position("+"), Some(StartPosition::End));
+        assert_eq!(
+            parse_start_position("+/fn main"),
+            Some(StartPosition::Search("fn main".to_string()))
+        );
+        assert_eq!(parse_start_position("+/"), None);
+        assert_eq!(parse_start_position("+notes.txt"), None);
+        assert_eq!(parse_start_position("file.txt"), None);
+    }
+
+    #[test]
+    fn test_parse_file_args_applies_start_to_next_file() {
+        let args: Vec<String> = ["+10", "a.rs", "b.rs:5", "+", "c.rs", "+/TODO", "d.rs:3:7"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let locations = parse_file_args(&args, &remote::SshConfig::default());
+        let locals: Vec<&FileLocation> = locations
+            .iter()
+            .map(|loc| match loc {
+                ParsedLocation::Local(fl) => fl,
+                other => panic!("Expected local, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(locals.len(), 4);
+
+        assert_eq!(locals[0].path, PathBuf::from("a.rs"));
+        assert_eq!(locals[0].line, Some(10));
+        // Only the file right after the argument is affected
+        assert_eq!(locals[1].line, Some(5));
+        assert_eq!(locals[1].search, None);
+        assert_eq!(locals[2].line, Some(usize::MAX));
+        // A search starts from the file's own line:col
+        assert_eq!(locals[3].line, Some(3));
+        assert_eq!(locals[3].column, Some(7));
+        assert_eq!(locals[3].search.as_deref(), Some("TODO"));
+    }
+
+    #[test]
+    fn test_parse_file_args_line_replaces_range_and_skips_stdin() {
+        let args: Vec<String> = ["+7", "a.rs:1-3", "+9", "-", "b.rs"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let locations = parse_file_args(&args, &remote::SshConfig::default());
+        match locations.as_slice() {
+            [ParsedLocation::Local(a), ParsedLocation::Local(b)] => {
+                assert_eq!(a.line, Some(7));
+                assert_eq!(a.end_line, None);
+                // A position before "-" isn't carried over to the next file
+                assert_eq!(b.line, None);
+            }
+            other => panic!("Expected two local files, got {:?}", other),
+        }
+    }
+
+    fn expand_args(args: &[String]) -> AnyhowResult<Vec<ParsedLocation>> {
+        expand_file_globs(parse_file_args(args, &remote::SshConfig::default()))
+    }
+
+    #[test]
+    fn test_expand_file_globs_with_either_separator() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().to_string_lossy().to_string();
+        std::fs::create_dir(temp.path().join("src")).unwrap();
+        for name in ["a.rs", "b.rs", "notes.txt"] {
+            std::fs::write(temp.path().join("src").join(name), "").unwrap();
+        }
+
+        for pattern in [
+            format!("{}/src/*.rs:10:2", dir),
+            format!("{}\\src\\*.rs:10:2", dir),
+        ] {
+            let locations = expand_args(&[pattern]).unwrap();
+            let names: Vec<(String, Option<usize>, Option<usize>)> = locations
+                .iter()
+                .map(|loc| match loc {
+                    ParsedLocation::Local(fl) => (
+                        fl.path.file_name().unwrap().to_string_lossy().to_string(),
+                        fl.line,
+                        fl.column,
+                    ),
+                    other => panic!("Expected local, got {:?}", other),
+                })
+                .collect();
+            assert_eq!(
+                names,
+                vec![
+                    ("a.rs".to_string(), Some(10), Some(2)),
+                    ("b.rs".to_string(), Some(10), Some(2)),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_validate_diff_files_accepts_null_device() {
+        let temp = tempfile::tempdir().unwrap();
+        let added = temp.path().join("added.rs");
+        std::fs::write(&added, "fn main() {}\n").unwrap();
+        let null = PathBuf::from("/dev/null");
+
+        validate_diff_files(&[null.clone(), added.clone()]).unwrap();
+        validate_diff_files(&[added.clone(), null]).unwrap();
+        let error = validate_diff_files(&[PathBuf::from("/dev/zero"), added]).unwrap_err();
+        assert!(error.to_string().contains("not a regular file"));
+    }
+
+    #[test]
+    fn test_expand_file_globs_errors_and_leaves_remote_alone() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().to_string_lossy().to_string();
+
+        let error = expand_args(&[format!("{}/*.rs", dir)]).unwrap_err();
+        assert!(error.to_string().contains("no matching files"));
+
+        for i in 0..=MAX_GLOB_MATCHES {
+            std::fs::write(temp.path().join(format!("{}.txt", i)), "").unwrap();
+        }
+        let error = expand_args(&[format!("{}/*.txt", dir)]).unwrap_err();
+        assert!(error.to_string().contains("matches more than"));
+
+        let locations = expand_args(&["user@host:src/*.rs:3".to_string()]).unwrap();
+        match locations.as_slice() {
+            [ParsedLocation::Remote(rl)] => {
+                assert_eq!(rl.path, "src/*.rs");
+                assert_eq!(rl.line, Some(3));
+            }
+            other => panic!("Expected one remote location, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_split_working_dir_resolves_files_against_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp.path().join("proj")).unwrap();
+        std::fs::create_dir(temp.path().join("other")).unwrap();
+        let absolute = temp.path().join("notes.txt");
+        let locations = vec![
+            parse_file_location("proj"),
+            parse_file_location("src/main.rs:3"),
+            parse_file_location(&absolute.to_string_lossy()),
+        ];
+        let is_dir = |loc: &FileLocation| temp.path().join(&loc.path).is_dir();
+
+        let (dir, files) = split_working_dir(locations, is_dir, temp.path()).unwrap();
+        assert_eq!(dir, Some(temp.path().join("proj")));
+        let paths: Vec<&Path> = files.iter().map(|loc| loc.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                temp.path().join("proj/src/main.rs").as_path(),
+                absolute.as_path()
+            ]
+        );
+        assert_eq!(files[0].line, Some(3));
+
+        let (dir, files) =
+            split_working_dir(vec![parse_file_location("a.txt")], is_dir, temp.path()).unwrap();
+        assert_eq!(dir, None);
+        assert_eq!(files[0].path, PathBuf::from("a.txt"));
+
+        let error = split_working_dir(
+            vec![parse_file_location("proj"), parse_file_location("other")],
+            is_dir,
+            temp.path(),
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("Only one directory"));
+    }
+
+    #[test]
+    fn test_local_files_next_to_remote_ones() {
+        let current_dir = tempfile::tempdir().unwrap();
+        let current_dir = current_dir.path();
+        let absolute = current_dir.join("other/notes.md");
+        let args: Vec<String> = vec![
+            "notes.txt:4".to_string(),
+            "user@host:/srv/app".to_string(),
+            absolute.to_string_lossy().into_owned(),
+        ];
+        let ssh_config = remote::SshConfig::default();
+        let parsed = parse_file_args(&args, &ssh_config);
+        let (locations, urls) = file_locations_from_args(parsed, true, &ssh_config, current_dir);
+        assert!(urls.is_empty());
+
+        // Local files are opened on this machine by absolute path
+        let local = Some(fresh::app::LOCAL_CONNECTION);
+        assert_eq!(locations[0].connection.as_deref(), local);
+        assert_eq!(locations[0].path, current_dir.join("notes.txt"));
+        assert_eq!(locations[0].line, Some(4));
+        assert_eq!(locations[2].connection.as_deref(), local);
+        assert_eq!(locations[2].path, absolute);
+        assert!(locations[1].connection.is_some());
+        assert_ne!(locations[1].connection.as_deref(), local);
+
+        // Only the remote directory becomes the working directory, and it
+        // doesn't capture the local paths
+        let (dir, files) = split_working_dir(locations, |_| true, current_dir).unwrap();
+        assert_eq!(dir, Some(PathBuf::from("/srv/app")));
+        let paths: Vec<&Path> = files.iter().map(|loc| loc.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![current_dir.join("notes.txt").as_path(), absolute.as_path()]
+        );
+
+        // Without remote files, local ones stay as given
+        let parsed = parse_file_args(&args[..1], &ssh_config);
+        let (locations, _) = file_locations_from_args(parsed, false, &ssh_config, current_dir);
+        assert_eq!(locations[0].connection, None);
+        assert_eq!(locations[0].path, PathBuf::from("notes.txt"));
+    }
+
     // Tests for parse_location (local vs remote detection)
 
     #[test]
@@ -3363,7 +4554,7 @@ mod tests {
                 assert_eq!(fl.path, PathBuf::from("file.txt"));
                 assert_eq!(fl.line, None);
             }
-            ParsedLocation::Remote(_) => panic!("Expected local, got remote"),
+            other => panic!("Expected local, got {:?}", other),
         }
     }
 
@@ -3375,7 +4566,7 @@ mod tests {
                 assert_eq!(fl.path, PathBuf::from("/path/to/file.rs"));
                 assert_eq!(fl.line, Some(42));
             }
-            ParsedLocation::Remote(_) => panic!("Expected local, got remote"),
+            other => panic!("Expected local, got {:?}", other),
         }
     }
 
@@ -3390,7 +4581,7 @@ mod tests {
                 assert_eq!(rl.line, None);
                 assert_eq!(rl.column, None);
             }
-            ParsedLocation::Local(_) => panic!("Expected remote, got local"),
+            other => panic!("Expected remote, got {:?}", other),
         }
     }
 
@@ -3405,7 +4596,7 @@ mod tests {
                 assert_eq!(rl.line, Some(42));
                 assert_eq!(rl.column, None);
             }
-            ParsedLocation::Local(_) => panic!("Expected remote, got local"),
+            other => panic!("Expected remote, got {:?}", other),
         }
     }
 
@@ -3420,7 +4611,7 @@ mod tests {
                 assert_eq!(rl.line, Some(100));
                 assert_eq!(rl.column, Some(25));
             }
-            ParsedLocation::Local(_) => panic!("Expected remote, got local"),
+            other => panic!("Expected remote, got {:?}", other),
         }
     }
 
@@ -3433,10 +4624,172 @@ mod tests {
                 assert_eq!(rl.host, "host");
                 assert_eq!(rl.path, "relative/path/file.txt");
             }
-            ParsedLocation::Local(_) => panic!("Expected remote, got local"),
+            other => panic!("Expected remote, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_location_remote_with_port() {
+        let loc = parse_location("user@host:2222:/path/file.rs:10:5");
+        match loc {
+            ParsedLocation::Remote(rl) => {
+                assert_eq!(rl.host, "host");
+                assert_eq!(rl.port, Some(2222));
+                assert_eq!(rl.path, "/path/file.rs");
+                assert_eq!(rl.line, Some(10));
+                assert_eq!(rl.column, Some(5));
+            }
+            other => panic!("Expected remote, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_location_remote_with_port_and_home_path() {
+        let loc = parse_location("user@host:2222:~");
+        match loc {
+            ParsedLocation::Remote(rl) => {
+                assert_eq!(rl.port, Some(2222));
+                assert_eq!(rl.path, "~");
+                assert_eq!(rl.line, None);
+            }
+            other => panic!("Expected remote, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_location_remote_numbers_without_port() {
+        // A relative path followed by line and column has no port
+        let loc = parse_location("user@host:2222:10:5");
+        match loc {
+            ParsedLocation::Remote(rl) => {
+                assert_eq!(rl.port, None);
+                assert_eq!(rl.path, "2222");
+                assert_eq!(rl.line, Some(10));
+                assert_eq!(rl.column, Some(5));
+            }
+            other => panic!("Expected remote, got {:?}", other),
+        }
+
+        // An absolute path followed by a line number has no port either
+        let loc = parse_location("user@host:/srv/app.py:42");
+        match loc {
+            ParsedLocation::Remote(rl) => {
+                assert_eq!(rl.port, None);
+                assert_eq!(rl.path, "/srv/app.py");
+                assert_eq!(rl.line, Some(42));
+            }
+            other => panic!("Expected remote, got {:?}", other),
+        }
+
+        // Out-of-range ports are not ports
+        let loc = parse_location("user@host:99999:/etc/hosts");
+        match loc {
+            ParsedLocation::Remote(rl) => {
+                assert_eq!(rl.port, None);
+                assert_eq!(rl.path, "99999:/etc/hosts");
+            }
+            other => panic!("Expected remote, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_location_remote_with_port_and_relative_path() {
+        let loc = parse_location("user@host:22:src/main.rs:3");
+        match loc {
+            ParsedLocation::Remote(rl) => {
+                assert_eq!(rl.port, Some(22));
+                assert_eq!(rl.path, "src/main.rs");
+                assert_eq!(rl.line, Some(3));
+            }
+            other => panic!("Expected remote, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_location_remote_invalid_ports() {
+        // Port 0 and signed numbers are not ports
+        for input in ["user@host:0:/etc/hosts", "user@host:+22:/etc/hosts"] {
+            match parse_location(input) {
+                ParsedLocation::Remote(rl) => {
+                    assert_eq!(rl.port, None, "{}", input);
+                    assert!(rl.path.ends_with(":/etc/hosts"), "{}", input);
+                }
+                other => panic!("Expected remote for {}, got {:?}", input, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_location_ssh_config_alias() {
+        let temp = tempfile::tempdir().unwrap();
+        let config_path = temp.path().join("config");
+        std::fs::write(
+            &config_path,
+            "Host myserver\n  User deploy\n  Port 2200\n  IdentityFile /keys/id\n",
+        )
+        .unwrap();
+        let ssh_config = remote::SshConfig::load_from(&config_path, temp.path());
+
+        match parse_location_with_ssh_config("myserver:/etc/nginx/nginx.conf:12", &ssh_config) {
+            ParsedLocation::Remote(rl) => {
+                assert_eq!(rl.user, "");
+                assert_eq!(rl.host, "myserver");
+                assert_eq!(rl.path, "/etc/nginx/nginx.conf");
+                assert_eq!(rl.line, Some(12));
+                let key = rl.key(&ssh_config);
+                assert_eq!(key.user, "deploy");
+                assert_eq!(key.port, Some(2200));
+                assert_eq!(key.identity_file, Some(PathBuf::from("/keys/id")));
+
+                // --identity beats IdentityFile
+                let mut rl = rl;
+                rl.identity_file = Some(PathBuf::from("/keys/cli"));
+                assert_eq!(
+                    rl.key(&ssh_config).identity_file,
+                    Some(PathBuf::from("/keys/cli"))
+                );
+            }
+            other => panic!("Expected remote, got {:?}", other),
+        }
+
+        // Unknown hosts stay local, and the command line beats the config
+        assert!(matches!(
+            parse_location_with_ssh_config("otherhost:/etc/hosts", &ssh_config),
+            ParsedLocation::Local(_)
+        ));
+        match parse_location_with_ssh_config("root@myserver:2222:/etc/hosts", &ssh_config) {
+            ParsedLocation::Remote(rl) => {
+                let key = rl.key(&ssh_config);
+                assert_eq!(key.user, "root");
+                assert_eq!(key.port, Some(2222));
+            }
+            other => panic!("Expected remote, got {:?}", other),
         }
     }
 
+    #[test]
+    fn test_identity_for_host() {
+        let identities = vec![
+            "/keys/default".to_string(),
+            "web=/keys/web".to_string(),
+            "/keys/with=sign".to_string(),
+        ];
+        assert_eq!(
+            identity_for_host(&identities, "web"),
+            Some(PathBuf::from("/keys/web"))
+        );
+        assert_eq!(
+            identity_for_host(&identities, "db"),
+            Some(PathBuf::from("/keys/default"))
+        );
+        assert_eq!(
+            identity_for_host(&identities[2..], "db"),
+            Some(PathBuf::from("/keys/with=sign"))
+        );
+        assert_eq!(identity_for_host(&identities[1..2], "db"), None);
+        assert_eq!(identity_for_host(&[], "db"), None);
+    }
+
     #[test]
     fn test_parse_location_email_like_not_remote() {
         // An email-like string without a path should be treated as local
@@ -3445,7 +4798,7 @@ mod tests {
             ParsedLocation::Local(fl) => {
                 assert_eq!(fl.path, PathBuf::from("user@host"));
             }
-            ParsedLocation::Remote(_) => panic!("Expected local, got remote"),
+            other => panic!("Expected local, got {:?}", other),
         }
     }
 
@@ -3457,7 +4810,26 @@ mod tests {
             ParsedLocation::Local(fl) => {
                 assert_eq!(fl.path, PathBuf::from("/path/with@sign/file.txt"));
             }
-            ParsedLocation::Remote(_) => panic!("Expected local, got remote"),
+            other => panic!("Expected local, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_location_url() {
+        let loc = parse_location("https://example.com/src/main.rs");
+        match loc {
+            ParsedLocation::Url(url) => assert_eq!(url, "https://example.com/src/main.rs"),
+            other => panic!("Expected URL, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_location_url_with_credentials_not_remote() {
+        // user@host:port inside a URL must not be mistaken for SSH syntax
+        let loc = parse_location("http://user@example.com:8080/file.txt");
+        match loc {
+            ParsedLocation::Url(url) => assert_eq!(url, "http://user@example.com:8080/file.txt"),
+            other => panic!("Expected URL, got {:?}", other),
         }
     }
 
@@ -392,6 +392,9 @@ pub struct BufferSnapshot {
     pub piece_tree: PieceTree,
     pub buffers: Vec<StringBuffer>,
     pub next_buffer_id: usize,
+    /// Line ending the buffer is saved with, so converting it undoes together
+    /// with the rewritten line breaks
+    pub line_ending: LineEnding,
 }
 
 impl TextBuffer {
@@ -1163,13 +1166,21 @@ impl TextBuffer {
     /// If the line ending format has been changed (via set_line_ending), all content
     /// will be converted to the new format during save.
     pub fn save_to_file<P: AsRef<Path>>(&mut self, path: P) -> anyhow::Result<()> {
-        let dest_path = path.as_ref();
+        let buffer_path = path.as_ref();
+        // Write through symlinks: an atomic rename onto the link would replace
+        // it with a regular file. The buffer keeps the link's path.
+        let link_target = if self.fs.is_symlink(buffer_path).unwrap_or(false) {
+            Some(self.fs.canonicalize(buffer_path)?)
+        } else {
+            None
+        };
+        let dest_path = link_target.as_deref().unwrap_or(buffer_path);
         let total = self.total_bytes();
 
         // Handle empty files
         if total == 0 {
             self.fs.write_file(dest_path, &[])?;
-            self.finalize_save(dest_path)?;
+            self.finalize_save(buffer_path)?;
             return Ok(());
         }
 
@@ -1217,10 +1228,32 @@ impl TextBuffer {
             self.fs.write_patched(src_for_patch, dest_path, &ops)?;
         }
 
-        self.finalize_save(dest_path)?;
+        self.finalize_save(buffer_path)?;
         Ok(())
     }
 
+    /// Write the buffer's contents to `path` without making it the buffer's
+    /// file: the file path, saved snapshot and encoding state are untouched,
+    /// so the buffer stays modified relative to its own file.
+    pub fn write_copy_to(&self, path: &Path) -> io::Result<()> {
+        if self.file_path.as_deref() == Some(path) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot write a copy over the buffer's own file",
+            ));
+        }
+        if self.total_bytes() == 0 {
+            return self.fs.write_file(path, &[]);
+        }
+        let recipe = self.build_write_recipe()?;
+        match recipe.src_path.as_deref() {
+            Some(src) if recipe.has_copy_ops() => {
+                self.fs.write_patched(src, path, &recipe.to_write_ops())
+            }
+            _ => self.fs.write_file(path, &recipe.flatten_inserts()),
+        }
+    }
+
     /// Write using in-place mode to preserve file ownership.
     ///
     /// This is used when the file is owned by a different user and we need
@@ -2128,6 +2161,7 @@ impl TextBuffer {
         self.piece_tree = snapshot.piece_tree.clone();
         self.buffers = snapshot.buffers.clone();
         self.next_buffer_id = snapshot.next_buffer_id;
+        self.line_ending = snapshot.line_ending;
         self.mark_content_modified();
     }
 
@@ -2141,6 +2175,7 @@ impl TextBuffer {
             piece_tree: self.piece_tree.clone(),
             buffers: self.buffers.clone(),
             next_buffer_id: self.next_buffer_id,
+            line_ending: self.line_ending,
         })
     }
 
@@ -2627,6 +2662,77 @@ impl TextBuffer {
         );
     }
 
+    /// Create an empty buffer for `path` whose content will arrive through
+    /// [`Self::append_streaming_bytes`], taking the encoding and line ending
+    /// from `sample`, the start of the file. Returns None if the sample is
+    /// binary or needs converting, which can't be done piece by piece.
+    pub fn for_streamed_file(
+        path: PathBuf,
+        sample: &[u8],
+        fs: Arc<dyn FileSystem + Send + Sync>,
+    ) -> Option<Self> {
+        let (encoding, is_binary) = Self::detect_encoding_or_binary(sample);
+        if is_binary || !matches!(encoding, Encoding::Utf8 | Encoding::Ascii) {
+            return None;
+        }
+        let line_ending = Self::detect_line_ending(sample);
+        let mut buffer = Self::empty(fs);
+        buffer.file_path = Some(path);
+        buffer.encoding = encoding;
+        buffer.original_encoding = encoding;
+        buffer.line_ending = line_ending;
+        buffer.original_line_ending = line_ending;
+        Some(buffer)
+    }
+
+    /// Take the content streamed so far as what is on disk
+    pub fn finish_streamed_file(&mut self) {
+        self.saved_file_size = Some(self.total_bytes());
+        self.mark_saved_snapshot();
+    }
+
+    /// The bytes a save would write, if all of them are in memory. None if
+    /// the save would copy unloaded regions from the file instead.
+    pub fn in_memory_save_contents(&self) -> io::Result<Option<Vec<u8>>> {
+        let recipe = self.build_write_recipe()?;
+        let mut contents = Vec::new();
+        for action in &recipe.actions {
+            match action {
+                RecipeAction::Copy { .. } => return Ok(None),
+                RecipeAction::Insert { index } => {
+                    contents.extend_from_slice(&recipe.insert_data[*index])
+                }
+            }
+        }
+        Ok(Some(contents))
+    }
+
+    /// Append bytes from a streaming source that is still in memory.
+    /// Used for stdin streaming of inputs small enough not to need a file.
+    pub fn append_streaming_bytes(&mut self, bytes: Vec<u8>) {
+        if bytes.is_empty() {
+            return;
+        }
+        let old_size = self.total_bytes();
+        let additional_bytes = bytes.len();
+
+        let buffer_id = self.next_buffer_id;
+        self.next_buffer_id += 1;
+
+        let new_buffer = StringBuffer::new(buffer_id, bytes);
+        let line_feed_cnt = new_buffer.line_feed_count();
+        self.buffers.push(new_buffer);
+
+        self.piece_tree.insert(
+            old_size,
+            BufferLocation::Stored(buffer_id),
+            0,
+            additional_bytes,
+            line_feed_cnt,
+            &self.buffers,
+        );
+    }
+
     /// Check if the buffer has been modified since last save
     pub fn is_modified(&self) -> bool {
         self.modified
@@ -3133,7 +3239,8 @@ impl TextBuffer {
             PieceTree::empty()
         };
         pristine.split_leaves_to_chunk_size(LOAD_CHUNK_SIZE);
-        pristine.update_leaf_line_feeds(scan_updates);
+        let pristine_updates = self.pristine_line_feed_updates(&pristine, scan_updates);
+        pristine.update_leaf_line_feeds(&pristine_updates);
 
         // Snapshot the pristine tree as saved_root.
         self.saved_root = pristine.root();
@@ -3190,6 +3297,50 @@ impl TextBuffer {
         self.line_feeds_scanned = true;
     }
 
+    /// Map line feed counts of the current tree's leaves (`scan_updates`, by
+    /// leaf index, plus counts the leaves already carry) onto the leaves of
+    /// `pristine`.
+    ///
+    /// Chunks loaded before the scan split the current tree at other
+    /// boundaries than `pristine`'s, so counts are matched by the file range
+    /// they cover. Pristine leaves without a matching range are scanned.
+    fn pristine_line_feed_updates(
+        &self,
+        pristine: &PieceTree,
+        scan_updates: &[(usize, usize)],
+    ) -> Vec<(usize, usize)> {
+        let scanned: std::collections::HashMap<usize, usize> =
+            scan_updates.iter().copied().collect();
+        let mut counts_by_range = std::collections::HashMap::new();
+        for (idx, leaf) in self.piece_tree.get_leaves().iter().enumerate() {
+            let file_offset = match leaf.location {
+                BufferLocation::Stored(_) => Some(leaf.offset),
+                BufferLocation::Added(id) => self
+                    .buffers
+                    .get(id)
+                    .and_then(|b| b.stored_file_offset)
+                    .map(|chunk_offset| chunk_offset + leaf.offset),
+            };
+            let count = scanned.get(&idx).copied().or(leaf.line_feed_cnt);
+            if let (Some(file_offset), Some(count)) = (file_offset, count) {
+                counts_by_range.insert((file_offset, leaf.bytes), count);
+            }
+        }
+
+        pristine
+            .get_leaves()
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, leaf)| {
+                let count = match counts_by_range.get(&(leaf.offset, leaf.bytes)) {
+                    Some(&count) => count,
+                    None => self.scan_leaf(leaf).ok()?,
+                };
+                Some((idx, count))
+            })
+            .collect()
+    }
+
     /// Resolve the exact byte offset for a given line number (0-indexed).
     ///
     /// Uses the tree's line feed counts to find the piece containing the target line,
@@ -4184,7 +4335,9 @@ impl TextBuffer {
     /// - **Large files (≥ 1MB)**: Returns estimated line number using `byte_offset / estimated_line_length`
     ///
     /// Large files don't maintain line metadata for performance reasons. The estimation
-    /// uses the configured `estimated_line_length` (default 80 bytes).
+    /// uses the configured `estimated_line_length` (default 80 bytes). Once a line
+    /// feed scan has counted the newlines of every leaf (see `prepare_line_scan`),
+    /// line numbers in loaded chunks are exact.
     pub fn get_line_number(&self, byte_offset: usize) -> usize {
         self.offset_to_position(byte_offset)
             .map(|pos| pos.line)
@@ -5513,6 +5666,64 @@ mod tests {
             }
         }
 
+        /// Before a line feed scan, large-file line numbers are estimated from
+        /// `estimated_line_length`; after it they are exact, and line starts
+        /// resolve without loading the whole file
+        #[test]
+        fn test_large_file_line_numbers_estimated_vs_exact() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("uneven_lines.txt");
+
+            // Lines of varying length, so the average is far from 80 bytes
+            let mut content = Vec::new();
+            let mut line_starts = Vec::new();
+            while content.len() < LOAD_CHUNK_SIZE * 3 {
+                line_starts.push(content.len());
+                content.extend(std::iter::repeat_n(b'x', line_starts.len() % 200));
+                content.push(b'\n');
+            }
+            File::create(&file_path)
+                .unwrap()
+                .write_all(&content)
+                .unwrap();
+
+            let mut buffer = TextBuffer::load_from_file(&file_path, 1, test_fs()).unwrap();
+            assert!(buffer.large_file);
+            let estimated_line_length = buffer.estimated_line_length();
+
+            // Lines in the second and third chunk, loaded as rendering would
+            let probes = [line_starts.len() / 2, line_starts.len() - 1];
+            for &line in &probes {
+                let offset = line_starts[line];
+                buffer.get_text_range_mut(offset, 1).unwrap();
+                assert_eq!(
+                    buffer.get_line_number(offset),
+                    offset / estimated_line_length
+                );
+                assert_ne!(buffer.get_line_number(offset), line);
+            }
+
+            let (chunks, _) = buffer.prepare_line_scan();
+            let leaves = buffer.piece_tree_leaves();
+            let updates: Vec<(usize, usize)> = chunks
+                .iter()
+                .filter(|chunk| !chunk.already_known)
+                .map(|chunk| {
+                    let count = buffer.scan_leaf(&leaves[chunk.leaf_index]).unwrap();
+                    (chunk.leaf_index, count)
+                })
+                .collect();
+            buffer.rebuild_with_pristine_saved_root(&updates);
+            assert_eq!(buffer.line_count(), Some(line_starts.len() + 1));
+
+            for &line in &probes {
+                let offset = line_starts[line];
+                assert_eq!(buffer.resolve_line_byte_offset(line), Some(offset));
+                buffer.get_text_range_mut(offset, 1).unwrap();
+                assert_eq!(buffer.get_line_number(offset), line);
+            }
+        }
+
         /// Test that save_to_file works correctly with partially loaded large files
         /// This is a regression test for a bug where saving would silently produce
         /// an empty file if any buffer regions were still unloaded.
@@ -6118,6 +6329,28 @@ mod tests {
             assert_eq!(&saved_bytes, b"Line 1\nLine 2\nLine 3\n");
         }
 
+        #[test]
+        fn test_write_copy_to_keeps_buffer_attached_to_its_file() {
+            use tempfile::TempDir;
+
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("original.txt");
+            let copy_path = temp_dir.path().join("copy.txt");
+            std::fs::write(&file_path, b"Line 1\nLine 2\n").unwrap();
+
+            let mut buffer =
+                TextBuffer::load_from_file(&file_path, DEFAULT_LARGE_FILE_THRESHOLD, test_fs())
+                    .unwrap();
+            buffer.insert(0, "New ");
+            buffer.write_copy_to(&copy_path).unwrap();
+
+            assert_eq!(std::fs::read(&copy_path).unwrap(), b"New Line 1\nLine 2\n");
+            assert_eq!(std::fs::read(&file_path).unwrap(), b"Line 1\nLine 2\n");
+            assert_eq!(buffer.file_path(), Some(file_path.as_path()));
+            assert!(buffer.is_modified());
+            assert!(buffer.write_copy_to(&file_path).is_err());
+        }
+
         #[test]
         #[cfg(unix)]
         fn test_save_to_unwritable_file() -> anyhow::Result<()> {
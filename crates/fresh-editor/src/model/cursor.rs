@@ -300,6 +300,48 @@ impl Cursors {
         self.primary_id = first_id; // Update primary to be the first cursor
     }
 
+    /// Make an existing cursor the primary one. Returns false if `id` is unknown.
+    pub fn set_primary(&mut self, id: CursorId) -> bool {
+        if !self.cursors.contains_key(&id) {
+            return false;
+        }
+        self.primary_id = id;
+        true
+    }
+
+    /// Make the next (or previous) cursor in document order the primary one,
+    /// wrapping around at the ends. Returns the new primary cursor ID.
+    pub fn rotate_primary(&mut self, forward: bool) -> CursorId {
+        let mut ordered: Vec<(usize, CursorId)> = self
+            .cursors
+            .iter()
+            .map(|(id, c)| (c.position, *id))
+            .collect();
+        ordered.sort_by_key(|(pos, id)| (*pos, id.0));
+
+        let current = ordered
+            .iter()
+            .position(|(_, id)| *id == self.primary_id)
+            .unwrap_or(0);
+        let len = ordered.len();
+        let next = if forward {
+            (current + 1) % len
+        } else {
+            (current + len - 1) % len
+        };
+        self.primary_id = ordered[next].1;
+        self.primary_id
+    }
+
+    /// Index of the primary cursor in document order (0-based)
+    pub fn primary_index(&self) -> usize {
+        let primary = self.primary();
+        self.cursors
+            .iter()
+            .filter(|(id, c)| (c.position, id.0) < (primary.position, self.primary_id.0))
+            .count()
+    }
+
     /// Get all cursor IDs
     pub fn ids(&self) -> Vec<CursorId> {
         self.cursors.keys().copied().collect()
@@ -451,6 +493,26 @@ mod tests {
         assert_eq!(cursors.count(), 1);
     }
 
+    #[test]
+    fn test_cursors_rotate_primary() {
+        let mut cursors = Cursors::new();
+        let last = cursors.add(Cursor::new(20));
+        let middle = cursors.add(Cursor::new(10));
+        let first = CursorId(0);
+        assert_eq!(cursors.primary_id(), middle);
+        assert_eq!(cursors.primary_index(), 1);
+
+        assert_eq!(cursors.rotate_primary(true), last);
+        assert_eq!(cursors.rotate_primary(true), first);
+        assert_eq!(cursors.primary_index(), 0);
+        assert_eq!(cursors.rotate_primary(false), last);
+        assert_eq!(cursors.primary_index(), 2);
+
+        assert!(cursors.set_primary(middle));
+        assert!(!cursors.set_primary(CursorId(99)));
+        assert_eq!(cursors.primary_id(), middle);
+    }
+
     #[test]
     fn test_cursors_normalize() {
         let mut cursors = Cursors::new();
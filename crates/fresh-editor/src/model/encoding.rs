@@ -124,6 +124,38 @@ impl Encoding {
         }
     }
 
+    /// Look up an encoding by name, ignoring case, spaces, `-` and `_`.
+    ///
+    /// Accepts display names ("UTF-16 LE"), prompt suggestions with their
+    /// description in parentheses, and common aliases ("latin1",
+    /// "iso-8859-1", "cp1252", "sjis").
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalize = |s: &str| {
+            s.chars()
+                .filter(|c| !matches!(c, ' ' | '-' | '_'))
+                .collect::<String>()
+                .to_lowercase()
+        };
+        let name = name.split('(').next().unwrap_or(name);
+        let wanted = normalize(name);
+        if let Some(encoding) = Self::all()
+            .iter()
+            .find(|enc| normalize(enc.display_name()) == wanted)
+        {
+            return Some(*encoding);
+        }
+        Some(match wanted.as_str() {
+            "utf8bom" | "utf8sig" => Self::Utf8Bom,
+            "usascii" => Self::Ascii,
+            "iso88591" | "latin" => Self::Latin1,
+            "cp1252" => Self::Windows1252,
+            "cp1250" => Self::Windows1250,
+            "cp936" => Self::Gbk,
+            "sjis" | "cp932" => Self::ShiftJis,
+            _ => return None,
+        })
+    }
+
     /// All available encodings for UI display
     pub fn all() -> &'static [Encoding] {
         &[
@@ -645,6 +677,20 @@ mod tests {
         assert_eq!(Encoding::Windows1250.display_name(), "Windows-1250");
     }
 
+    #[test]
+    fn test_encoding_from_name() {
+        assert_eq!(Encoding::from_name("UTF-16 LE"), Some(Encoding::Utf16Le));
+        assert_eq!(Encoding::from_name("utf-16le"), Some(Encoding::Utf16Le));
+        assert_eq!(
+            Encoding::from_name("Latin-1 (ISO-8859-1 / Latin-1 – Western European)"),
+            Some(Encoding::Latin1)
+        );
+        assert_eq!(Encoding::from_name("iso-8859-1"), Some(Encoding::Latin1));
+        assert_eq!(Encoding::from_name("shift_jis"), Some(Encoding::ShiftJis));
+        assert_eq!(Encoding::from_name("sjis"), Some(Encoding::ShiftJis));
+        assert_eq!(Encoding::from_name("klingon"), None);
+    }
+
     #[test]
     fn test_encoding_bom() {
         assert!(Encoding::Utf8Bom.has_bom());
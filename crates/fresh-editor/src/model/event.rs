@@ -1,4 +1,4 @@
-use crate::model::buffer::BufferSnapshot;
+use crate::model::buffer::{BufferSnapshot, TextBuffer};
 pub use fresh_core::api::{OverlayColorSpec, OverlayOptions};
 pub use fresh_core::overlay::{OverlayHandle, OverlayNamespace};
 pub use fresh_core::{BufferId, ContainerId, CursorId, LeafId, SplitDirection, SplitId};
@@ -462,6 +462,19 @@ impl Event {
         }
     }
 
+    /// Bytes of text retained by this event (inserted or deleted text)
+    ///
+    /// Used to bound undo history memory. BulkEdit snapshots are shared with
+    /// the buffer and are not counted.
+    pub fn text_bytes(&self) -> usize {
+        match self {
+            Self::Insert { text, .. } => text.len(),
+            Self::Delete { deleted_text, .. } => deleted_text.len(),
+            Self::Batch { events, .. } => events.iter().map(|e| e.text_bytes()).sum(),
+            _ => 0,
+        }
+    }
+
     /// Returns true if this event modifies the buffer content
     pub fn modifies_buffer(&self) -> bool {
         match self {
@@ -471,6 +484,24 @@ impl Event {
         }
     }
 
+    /// Apply only this event's buffer content changes to `buffer`
+    fn apply_to_text(&self, buffer: &mut TextBuffer) {
+        match self {
+            Self::Insert { position, text, .. } => buffer.insert(*position, text),
+            Self::Delete { range, .. } => buffer.delete(range.clone()),
+            Self::BulkEdit {
+                new_snapshot: Some(snapshot),
+                ..
+            } => buffer.restore_buffer_state(snapshot),
+            Self::Batch { events, .. } => {
+                for event in events {
+                    event.apply_to_text(buffer);
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Returns true if this event is a write action (modifies state in a way that should be undoable)
     /// Returns false for readonly actions like cursor movement, scrolling, viewport changes, etc.
     ///
@@ -557,6 +588,28 @@ pub struct Snapshot {
     pub cursor_positions: Vec<(CursorId, usize, Option<usize>)>,
 }
 
+/// Percentage of a history limit that trimming brings the log back down to
+const TRIM_LOW_WATER_PERCENT: usize = 90;
+
+/// Stand-in for the entries discarded from the front of an [`EventLog`]
+///
+/// The buffer content at the checkpoint is whatever the first retained entry
+/// was applied to; undo stops there. The checkpoint only keeps a summary of
+/// what was folded into it, so memory stays bounded however long the session.
+/// The content itself is rebuilt on request by
+/// [`EventLog::checkpoint_snapshot`].
+#[derive(Debug, Clone, Default)]
+pub struct HistoryCheckpoint {
+    /// Number of entries folded into the checkpoint
+    pub entries: usize,
+
+    /// Bytes of inserted/deleted text those entries held
+    pub text_bytes: usize,
+
+    /// Timestamp of the newest folded entry (milliseconds since epoch)
+    pub timestamp: u64,
+}
+
 /// The event log - append-only log of all events
 pub struct EventLog {
     /// All logged events
@@ -578,6 +631,26 @@ pub struct EventLog {
     /// Index at which the buffer was last saved (for tracking modified status)
     /// When current_index equals saved_at_index, the buffer is not modified
     saved_at_index: Option<usize>,
+
+    /// Maximum number of retained entries (0 = unlimited)
+    max_entries: usize,
+
+    /// Maximum bytes of inserted/deleted text retained (0 = unlimited)
+    max_text_bytes: usize,
+
+    /// Bytes of text currently retained by `entries`
+    text_bytes: usize,
+
+    /// Entries compacted away from the front because of the limits.
+    /// States before the first retained entry can no longer be reached by undo.
+    checkpoint: Option<HistoryCheckpoint>,
+
+    /// Set when entries are first discarded; cleared by `take_trim_notice`
+    trim_notice_pending: bool,
+
+    /// Set when entries are first discarded while streaming; cleared once the
+    /// checkpoint content is written by `stream_checkpoint_content`
+    checkpoint_content_pending: bool,
 }
 
 impl EventLog {
@@ -591,6 +664,204 @@ impl EventLog {
             #[cfg(feature = "runtime")]
             stream_file: None,
             saved_at_index: Some(0), // New buffer starts at "saved" state (index 0)
+            max_entries: 0,
+            max_text_bytes: 0,
+            text_bytes: 0,
+            checkpoint: None,
+            trim_notice_pending: false,
+            checkpoint_content_pending: false,
+        }
+    }
+
+    /// Create an event log that keeps at most `max_entries` entries and
+    /// `max_text_bytes` bytes of inserted/deleted text (0 = unlimited)
+    pub fn with_limits(max_entries: usize, max_text_bytes: usize) -> Self {
+        let mut log = Self::new();
+        log.set_limits(max_entries, max_text_bytes);
+        log
+    }
+
+    /// Change the history limits, trimming immediately if they are exceeded
+    pub fn set_limits(&mut self, max_entries: usize, max_text_bytes: usize) {
+        self.max_entries = max_entries;
+        self.max_text_bytes = max_text_bytes;
+        self.enforce_limits();
+    }
+
+    /// Number of entries discarded because of the history limits
+    pub fn trimmed_count(&self) -> usize {
+        self.checkpoint
+            .as_ref()
+            .map_or(0, |checkpoint| checkpoint.entries)
+    }
+
+    /// Summary of the entries compacted away, if history was ever trimmed
+    pub fn checkpoint(&self) -> Option<&HistoryCheckpoint> {
+        self.checkpoint.as_ref()
+    }
+
+    /// Rebuild the buffer content at the checkpoint
+    ///
+    /// `buffer` must hold the content at `current_index`; a scratch copy of it
+    /// is rewound through the retained entries.
+    pub fn checkpoint_snapshot(&self, buffer: &TextBuffer) -> Option<Arc<BufferSnapshot>> {
+        self.checkpoint.as_ref()?;
+        Some(self.rewind_to_checkpoint(buffer).snapshot_buffer_state())
+    }
+
+    fn rewind_to_checkpoint(&self, buffer: &TextBuffer) -> TextBuffer {
+        let mut scratch = TextBuffer::new(0, buffer.filesystem().clone());
+        scratch.restore_buffer_state(&buffer.snapshot_buffer_state());
+        for entry in self.entries[..self.current_index].iter().rev() {
+            if let Some(inverse) = entry.event.inverse() {
+                inverse.apply_to_text(&mut scratch);
+            }
+        }
+        scratch
+    }
+
+    /// Whether history crossed its limits while streaming and the checkpoint
+    /// content has not been written to the event stream yet
+    pub fn needs_checkpoint_content(&self) -> bool {
+        self.checkpoint_content_pending
+    }
+
+    /// Write the buffer content at the checkpoint to the event stream, so a
+    /// streamed log can be replayed from there
+    ///
+    /// This happens once per limit crossing; later trimmed batches only
+    /// write a summary, since the stream keeps every event after it.
+    /// `buffer` must hold the content at `current_index`.
+    #[cfg(feature = "runtime")]
+    pub fn stream_checkpoint_content(&mut self, buffer: &TextBuffer) {
+        if !std::mem::take(&mut self.checkpoint_content_pending) {
+            return;
+        }
+        // The content is null for large files whose text is not loaded
+        let content = self.rewind_to_checkpoint(buffer).to_string();
+        self.write_checkpoint_line(content);
+    }
+
+    /// Write a checkpoint line to the event stream
+    #[cfg(feature = "runtime")]
+    fn write_checkpoint_line(&mut self, content: Option<String>) {
+        let (Some(checkpoint), Some(file)) = (self.checkpoint.as_ref(), self.stream_file.as_mut())
+        else {
+            return;
+        };
+        use std::io::Write;
+
+        let mut checkpoint_info = serde_json::json!({
+            "type": "checkpoint",
+            "timestamp": chrono::Local::now().to_rfc3339(),
+            "first_retained_index": checkpoint.entries,
+            "compacted_entries": checkpoint.entries,
+            "compacted_text_bytes": checkpoint.text_bytes,
+        });
+        if let Some(content) = content {
+            checkpoint_info["content"] = content.into();
+        }
+
+        if let Err(e) = writeln!(file, "{checkpoint_info}") {
+            tracing::trace!("Warning: Failed to write checkpoint to stream: {e}");
+        }
+        if let Err(e) = file.flush() {
+            tracing::trace!("Warning: Failed to flush event stream: {e}");
+        }
+    }
+
+    /// Configured limits as `(max_entries, max_text_bytes)` (0 = unlimited)
+    pub fn limits(&self) -> (usize, usize) {
+        (self.max_entries, self.max_text_bytes)
+    }
+
+    /// Bytes of inserted/deleted text currently retained
+    pub fn text_bytes(&self) -> usize {
+        self.text_bytes
+    }
+
+    /// Approximate memory held by the retained entries, including per-entry
+    /// overhead on top of their text
+    pub fn approx_memory_bytes(&self) -> usize {
+        self.entries.capacity() * std::mem::size_of::<LogEntry>()
+            + self.text_bytes
+            + self
+                .entries
+                .iter()
+                .filter_map(|entry| entry.description.as_ref())
+                .map(String::len)
+                .sum::<usize>()
+    }
+
+    /// Returns true once after history was first trimmed, for notifying the user
+    pub fn take_trim_notice(&mut self) -> bool {
+        std::mem::take(&mut self.trim_notice_pending)
+    }
+
+    /// Fold the oldest entries into the checkpoint once the log exceeds its limits
+    ///
+    /// A limit that is exceeded is trimmed down to [`TRIM_LOW_WATER_PERCENT`]
+    /// of itself, so entries are removed in batches rather than one per append.
+    /// Only entries before `current_index` are discarded, so redo history is
+    /// never lost. If the saved position is discarded, the buffer can no longer
+    /// return to the saved state through undo and is reported as modified.
+    /// A streamed log still has every event; only the in-memory copy is compacted.
+    fn enforce_limits(&mut self) {
+        let over_entries = self.max_entries > 0 && self.entries.len() > self.max_entries;
+        let over_bytes = self.max_text_bytes > 0 && self.text_bytes > self.max_text_bytes;
+        if !over_entries && !over_bytes {
+            return;
+        }
+        let target = |limit: usize, over: bool| match (limit, over) {
+            (0, _) => usize::MAX,
+            (limit, true) => limit * TRIM_LOW_WATER_PERCENT / 100,
+            (limit, false) => limit,
+        };
+        let max_entries = target(self.max_entries, over_entries);
+        let max_text_bytes = target(self.max_text_bytes, over_bytes);
+
+        let mut dropped = 0;
+        let mut dropped_bytes = 0;
+        // The newest undoable entry is always kept, even if it alone is over the limit
+        while dropped + 1 < self.current_index
+            && (self.entries.len() - dropped > max_entries
+                || self.text_bytes - dropped_bytes > max_text_bytes)
+        {
+            dropped_bytes += self.entries[dropped].event.text_bytes();
+            dropped += 1;
+        }
+        if dropped == 0 {
+            return;
+        }
+
+        let newest_dropped = self.entries[dropped - 1].timestamp;
+        self.entries.drain(..dropped);
+        self.text_bytes -= dropped_bytes;
+        self.current_index -= dropped;
+        self.saved_at_index = self
+            .saved_at_index
+            .and_then(|saved_idx| saved_idx.checked_sub(dropped));
+
+        let first_crossing = self.checkpoint.is_none();
+        if first_crossing {
+            self.trim_notice_pending = true;
+        }
+        let checkpoint = self
+            .checkpoint
+            .get_or_insert_with(HistoryCheckpoint::default);
+        checkpoint.entries += dropped;
+        checkpoint.text_bytes += dropped_bytes;
+        checkpoint.timestamp = newest_dropped;
+
+        // The content is streamed once, on the next tick; later batches are
+        // summarized right away
+        #[cfg(feature = "runtime")]
+        if self.stream_file.is_some() {
+            if first_crossing {
+                self.checkpoint_content_pending = true;
+            } else if !self.checkpoint_content_pending {
+                self.write_checkpoint_line(None);
+            }
         }
     }
 
@@ -712,6 +983,11 @@ impl EventLog {
         if self.current_index < self.entries.len() {
             if event.is_write_action() {
                 // Write action: truncate redo history and log normally
+                let redo_bytes: usize = self.entries[self.current_index..]
+                    .iter()
+                    .map(|entry| entry.event.text_bytes())
+                    .sum();
+                self.text_bytes -= redo_bytes;
                 self.entries.truncate(self.current_index);
 
                 // Invalidate saved_at_index if it pointed to a truncated entry
@@ -727,12 +1003,15 @@ impl EventLog {
         }
 
         // Stream event to file if enabled (runtime only)
+        // Absolute index, so it stays meaningful after compaction
+        #[cfg(feature = "runtime")]
+        let stream_index = self.trimmed_count() + self.entries.len();
         #[cfg(feature = "runtime")]
         if let Some(ref mut file) = self.stream_file {
             use std::io::Write;
 
             let stream_entry = serde_json::json!({
-                "index": self.entries.len(),
+                "index": stream_index,
                 "timestamp": chrono::Local::now().to_rfc3339(),
                 "event": event,
             });
@@ -746,9 +1025,11 @@ impl EventLog {
             }
         }
 
+        self.text_bytes += event.text_bytes();
         let entry = LogEntry::new(event);
         self.entries.push(entry);
         self.current_index = self.entries.len();
+        self.enforce_limits();
 
         // Check if we should create a snapshot
         if self.entries.len().is_multiple_of(self.snapshot_interval) {
@@ -784,6 +1065,22 @@ impl EventLog {
         self.current_index < self.entries.len()
     }
 
+    /// Number of steps Undo can take back (write actions before the current position)
+    pub fn undo_depth(&self) -> usize {
+        self.entries[..self.current_index]
+            .iter()
+            .filter(|entry| entry.event.is_write_action())
+            .count()
+    }
+
+    /// Number of steps Redo can take forward (write actions after the current position)
+    pub fn redo_depth(&self) -> usize {
+        self.entries[self.current_index..]
+            .iter()
+            .filter(|entry| entry.event.is_write_action())
+            .count()
+    }
+
     /// Move back through events (for undo)
     /// Collects all events up to and including the first write action, returns their inverses
     /// This processes readonly events (like scrolling) and stops at write events (like Insert/Delete)
@@ -865,6 +1162,9 @@ impl EventLog {
         self.entries.clear();
         self.current_index = 0;
         self.snapshots.clear();
+        self.text_bytes = 0;
+        self.checkpoint = None;
+        self.checkpoint_content_pending = false;
     }
 
     /// Save event log to JSON Lines format
@@ -895,6 +1195,7 @@ impl EventLog {
                 continue;
             }
             let entry: LogEntry = serde_json::from_str(&line)?;
+            log.text_bytes += entry.event.text_bytes();
             log.entries.push(entry);
         }
 
@@ -1076,6 +1377,33 @@ mod tests {
         assert_eq!(log.current_index(), 1);
     }
 
+    #[test]
+    fn test_undo_redo_depth_counts_write_actions() {
+        let mut log = EventLog::new();
+        for (position, text) in ["a", "b"].iter().enumerate() {
+            log.append(Event::Insert {
+                position,
+                text: text.to_string(),
+                cursor_id: CursorId(0),
+            });
+            log.append(Event::MoveCursor {
+                cursor_id: CursorId(0),
+                old_position: position,
+                new_position: position + 1,
+                old_anchor: None,
+                new_anchor: None,
+                old_sticky_column: 0,
+                new_sticky_column: 0,
+            });
+        }
+        assert_eq!((log.undo_depth(), log.redo_depth()), (2, 0));
+
+        log.undo();
+        assert_eq!((log.undo_depth(), log.redo_depth()), (1, 1));
+        log.undo();
+        assert_eq!((log.undo_depth(), log.redo_depth()), (0, 2));
+    }
+
     #[test]
     fn test_event_inverse() {
         let insert = Event::Insert {
@@ -1262,4 +1590,201 @@ mod tests {
             "Should not be at saved position after undo + new edit"
         );
     }
+
+    fn insert(position: usize, text: &str) -> Event {
+        Event::Insert {
+            position,
+            text: text.to_string(),
+            cursor_id: CursorId(0),
+        }
+    }
+
+    #[test]
+    fn test_max_entries_discards_oldest() {
+        let mut log = EventLog::with_limits(10, 0);
+        for i in 0..11 {
+            log.append(insert(i, "x"));
+        }
+        // Going over the limit trims a batch down to 90% of it
+        assert_eq!(log.len(), 9);
+        assert_eq!(log.trimmed_count(), 2);
+        log.append(insert(11, "x"));
+        assert_eq!(log.len(), 10);
+        assert_eq!(log.trimmed_count(), 2);
+
+        for i in 12..25 {
+            log.append(insert(i, "x"));
+        }
+        assert_eq!(log.len(), 9);
+        assert_eq!(log.current_index(), 9);
+        assert_eq!(log.trimmed_count(), 16);
+        assert!(log.take_trim_notice());
+        assert!(!log.take_trim_notice());
+
+        // Only the retained entries can be undone
+        let mut undone = 0;
+        while log.can_undo() {
+            log.undo();
+            undone += 1;
+        }
+        assert_eq!(undone, 9);
+    }
+
+    #[test]
+    fn test_trimming_keeps_redo_history() {
+        let mut log = EventLog::with_limits(5, 0);
+        for i in 0..5 {
+            log.append(insert(i, "x"));
+        }
+        log.undo();
+        log.undo();
+
+        // Tightening the limit may only discard entries before current_index
+        log.set_limits(2, 0);
+        assert_eq!(log.current_index(), 1);
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.redo().len(), 1);
+        assert!(log.can_redo());
+    }
+
+    #[test]
+    fn test_saved_position_survives_trimming() {
+        let mut log = EventLog::with_limits(10, 0);
+        for i in 0..10 {
+            log.append(insert(i, "x"));
+        }
+        log.mark_saved();
+
+        // Trimming shifts the saved marker along with the entries
+        for i in 0..5 {
+            log.append(insert(i, "y"));
+        }
+        assert_eq!(log.trimmed_count(), 6);
+        assert!(!log.is_at_saved_position());
+        for _ in 0..5 {
+            log.undo();
+        }
+        assert!(log.is_at_saved_position());
+
+        // Once the saved state itself is discarded it is no longer reachable
+        for i in 0..11 {
+            log.append(insert(i, "z"));
+        }
+        while log.can_undo() {
+            log.undo();
+            assert!(!log.is_at_saved_position());
+        }
+    }
+
+    #[test]
+    fn test_max_text_bytes_discards_oldest() {
+        let mut log = EventLog::with_limits(0, 100);
+        for i in 0..10 {
+            log.append(insert(i, &"a".repeat(30)));
+        }
+        // Trimming goes down to 90 bytes, so 3 entries are kept
+        assert_eq!(log.len(), 3);
+
+        // The newest entry is kept even if it alone exceeds the limit
+        log.append(insert(0, &"b".repeat(500)));
+        assert_eq!(log.len(), 1);
+        assert!(log.can_undo());
+    }
+
+    #[test]
+    fn test_checkpoint_summarizes_compacted_entries() {
+        let mut log = EventLog::with_limits(4, 0);
+        assert!(log.checkpoint().is_none());
+        for i in 0..10 {
+            log.append(insert(i, "ab"));
+        }
+
+        let checkpoint = log.checkpoint().unwrap();
+        assert_eq!(checkpoint.entries, 6);
+        assert_eq!(checkpoint.text_bytes, 12);
+        assert_eq!(log.text_bytes(), 8);
+        assert!(log.approx_memory_bytes() >= log.text_bytes());
+
+        log.clear();
+        assert!(log.checkpoint().is_none());
+    }
+
+    #[test]
+    fn test_checkpoint_snapshot_rebuilds_trimmed_content() {
+        let mut log = EventLog::with_limits(4, 0);
+        let mut buffer = TextBuffer::from_str_test("hello");
+        let events = [
+            insert(5, " world"),
+            Event::Delete {
+                range: 0..1,
+                deleted_text: "h".to_string(),
+                cursor_id: CursorId(0),
+            },
+            insert(0, "H"),
+            insert(11, "!"),
+            insert(12, "!"),
+        ];
+        for event in events {
+            event.apply_to_text(&mut buffer);
+            log.append(event);
+        }
+        // Nothing to stream without an event stream
+        assert!(!log.needs_checkpoint_content());
+
+        // Undo the retained entry too; the rewind starts from current_index
+        let undo = log.undo();
+        for event in &undo {
+            event.apply_to_text(&mut buffer);
+        }
+        assert_eq!(log.trimmed_count(), 2);
+
+        let snapshot = log.checkpoint_snapshot(&buffer).unwrap();
+        let mut rebuilt = TextBuffer::from_str_test("");
+        rebuilt.restore_buffer_state(&snapshot);
+        assert_eq!(rebuilt.to_string().unwrap(), "ello world");
+        assert_eq!(buffer.to_string().unwrap(), "Hello world!");
+    }
+
+    #[cfg(feature = "runtime")]
+    #[test]
+    fn test_streaming_keeps_compacted_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let mut log = EventLog::with_limits(3, 0);
+        log.enable_streaming(&path).unwrap();
+        let mut buffer = TextBuffer::from_str_test("");
+        for i in 0..8 {
+            buffer.insert(i, "x");
+            log.append(insert(i, "x"));
+            if log.needs_checkpoint_content() {
+                log.stream_checkpoint_content(&buffer);
+            }
+        }
+        assert_eq!(log.len(), 2);
+
+        let streamed: Vec<serde_json::Value> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let indices: Vec<u64> = streamed
+            .iter()
+            .filter_map(|value| value.get("index").and_then(|i| i.as_u64()))
+            .collect();
+        assert_eq!(indices, (0..8).collect::<Vec<u64>>());
+
+        // One checkpoint per trimmed batch; only the first carries the content
+        // to replay from, the stream has every event after it
+        let checkpoints: Vec<&serde_json::Value> = streamed
+            .iter()
+            .filter(|value| value["type"] == "checkpoint")
+            .collect();
+        assert_eq!(checkpoints.len(), 3);
+        assert_eq!(checkpoints[0]["first_retained_index"], 2);
+        assert_eq!(checkpoints[0]["content"], "xx");
+        assert_eq!(checkpoints[2]["first_retained_index"], 6);
+        assert!(checkpoints[2].get("content").is_none());
+        assert!(!log.needs_checkpoint_content());
+    }
 }
@@ -87,6 +87,52 @@ impl DirEntry {
     }
 }
 
+/// Kind of object a path refers to (after following symlinks)
+///
+/// Only regular files can be loaded into a buffer. Opening a FIFO blocks until
+/// a writer appears and devices can be endless, so the editor refuses those.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Regular,
+    Directory,
+    /// Named pipe
+    Fifo,
+    Socket,
+    CharDevice,
+    BlockDevice,
+}
+
+impl FileKind {
+    /// Classify from the file type bits of a Unix `st_mode`
+    pub fn from_mode(mode: u32) -> Self {
+        match mode & 0o170000 {
+            0o040000 => FileKind::Directory,
+            0o010000 => FileKind::Fifo,
+            0o140000 => FileKind::Socket,
+            0o020000 => FileKind::CharDevice,
+            0o060000 => FileKind::BlockDevice,
+            _ => FileKind::Regular,
+        }
+    }
+
+    /// Whether this is a FIFO, socket or device rather than a file or directory
+    pub fn is_special(&self) -> bool {
+        !matches!(self, FileKind::Regular | FileKind::Directory)
+    }
+
+    /// Short user-facing name, e.g. for error messages
+    pub fn description(&self) -> &'static str {
+        match self {
+            FileKind::Regular => "file",
+            FileKind::Directory => "directory",
+            FileKind::Fifo => "named pipe (FIFO)",
+            FileKind::Socket => "socket",
+            FileKind::CharDevice => "character device",
+            FileKind::BlockDevice => "block device",
+        }
+    }
+}
+
 // ============================================================================
 // Metadata Types
 // ============================================================================
@@ -292,6 +338,30 @@ impl Seek for StdFileReader {
 
 impl FileReader for StdFileReader {}
 
+// ============================================================================
+// Shells
+// ============================================================================
+
+/// A shell on a pseudo-terminal, started by [`FileSystem::open_shell`]
+pub struct ShellPty {
+    /// What the shell prints; reads return 0 once it has exited
+    pub output: Box<dyn Read + Send>,
+    /// Keystrokes and size changes for the shell
+    pub input: Box<dyn ShellInput>,
+}
+
+/// The input side of a [`ShellPty`]
+pub trait ShellInput: Send {
+    /// Send keystrokes to the shell
+    fn write(&mut self, data: &[u8]) -> io::Result<()>;
+
+    /// Tell the shell its terminal is now `cols` by `rows`
+    fn resize(&mut self, cols: u16, rows: u16) -> io::Result<()>;
+
+    /// Hang up the terminal, ending the shell
+    fn close(&mut self);
+}
+
 // ============================================================================
 // File Search Types
 // ============================================================================
@@ -506,6 +576,25 @@ pub trait FileSystem: Send + Sync {
     /// Check if path is a file
     fn is_file(&self, path: &Path) -> io::Result<bool>;
 
+    /// Check if path is a symlink (doesn't follow the final component)
+    ///
+    /// Backends without symlink support report `false`.
+    fn is_symlink(&self, _path: &Path) -> io::Result<bool> {
+        Ok(false)
+    }
+
+    /// Get the kind of object at path, following symlinks
+    ///
+    /// Backends that can only tell files from directories report everything
+    /// else as `Regular`.
+    fn file_kind(&self, path: &Path) -> io::Result<FileKind> {
+        if self.is_dir(path)? {
+            Ok(FileKind::Directory)
+        } else {
+            Ok(FileKind::Regular)
+        }
+    }
+
     /// Set file permissions
     fn set_permissions(&self, path: &Path, permissions: &FilePermissions) -> io::Result<()>;
 
@@ -589,6 +678,48 @@ pub trait FileSystem: Send + Sync {
         None
     }
 
+    /// Whether the filesystem can be reached right now
+    ///
+    /// Remote filesystems report `false` while their connection is down and
+    /// being re-established; the status bar shows this.
+    fn is_connected(&self) -> bool {
+        true
+    }
+
+    /// Make one round trip to the host behind this filesystem
+    ///
+    /// Remote filesystems send a request and wait for the answer, so timing
+    /// this gives the connection's latency. Local filesystems return at once.
+    fn ping(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Get the filesystem of another connection reachable through this one
+    ///
+    /// When files from several remote hosts are open, the editor's filesystem
+    /// routes between one filesystem per connection. `connection` is the
+    /// value that connection reports from `remote_connection_info`. Returns
+    /// `None` for filesystems that only reach themselves.
+    fn connection_filesystem(
+        &self,
+        _connection: &str,
+    ) -> Option<std::sync::Arc<dyn FileSystem + Send + Sync>> {
+        None
+    }
+
+    /// Start the login shell of the host behind this filesystem
+    ///
+    /// Remote filesystems open a pseudo-terminal of `cols` by `rows` on their
+    /// host, over the existing connection, with the shell in `cwd` (the home
+    /// directory if `None`). Local filesystems return `Unsupported`; the
+    /// terminal manager spawns local shells itself.
+    fn open_shell(&self, _cwd: Option<&Path>, _cols: u16, _rows: u16) -> io::Result<ShellPty> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "shells can only be opened on remote hosts",
+        ))
+    }
+
     /// Get the home directory for this filesystem
     ///
     /// For local filesystems, returns the local home directory.
@@ -1026,6 +1157,34 @@ impl FileSystem for StdFileSystem {
         Ok(std::fs::metadata(path)?.is_file())
     }
 
+    fn is_symlink(&self, path: &Path) -> io::Result<bool> {
+        Ok(std::fs::symlink_metadata(path)?.file_type().is_symlink())
+    }
+
+    fn file_kind(&self, path: &Path) -> io::Result<FileKind> {
+        let file_type = std::fs::metadata(path)?.file_type();
+        if file_type.is_dir() {
+            return Ok(FileKind::Directory);
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+            if file_type.is_fifo() {
+                return Ok(FileKind::Fifo);
+            }
+            if file_type.is_socket() {
+                return Ok(FileKind::Socket);
+            }
+            if file_type.is_char_device() {
+                return Ok(FileKind::CharDevice);
+            }
+            if file_type.is_block_device() {
+                return Ok(FileKind::BlockDevice);
+            }
+        }
+        Ok(FileKind::Regular)
+    }
+
     fn set_permissions(&self, path: &Path, permissions: &FilePermissions) -> io::Result<()> {
         std::fs::set_permissions(path, permissions.to_std())
     }
@@ -1381,6 +1540,34 @@ mod tests {
         assert!(link_to_dir.is_dir());
     }
 
+    #[test]
+    fn test_file_kind_from_mode() {
+        assert_eq!(FileKind::from_mode(0o100644), FileKind::Regular);
+        assert_eq!(FileKind::from_mode(0o040755), FileKind::Directory);
+        assert_eq!(FileKind::from_mode(0o010644), FileKind::Fifo);
+        assert_eq!(FileKind::from_mode(0o140755), FileKind::Socket);
+        assert_eq!(FileKind::from_mode(0o020666), FileKind::CharDevice);
+        assert_eq!(FileKind::from_mode(0o060660), FileKind::BlockDevice);
+        assert!(FileKind::Fifo.is_special());
+        assert!(!FileKind::Regular.is_special());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_std_filesystem_symlink_and_kind() {
+        let fs = StdFileSystem;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = temp_dir.path().join("target.txt");
+        let link = temp_dir.path().join("link.txt");
+        fs.write_file(&target, b"content").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        assert!(fs.is_symlink(&link).unwrap());
+        assert!(!fs.is_symlink(&target).unwrap());
+        assert_eq!(fs.file_kind(&link).unwrap(), FileKind::Regular);
+        assert_eq!(fs.file_kind(temp_dir.path()).unwrap(), FileKind::Directory);
+    }
+
     #[test]
     fn test_metadata_builder() {
         let meta = FileMetadata::default()
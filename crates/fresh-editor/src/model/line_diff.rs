@@ -4,6 +4,7 @@
 //! insertions, deletions, and modifications. It uses a longest common subsequence (LCS)
 //! approach to identify which lines are unchanged, then marks the ranges that differ.
 
+use crate::model::composite_buffer::DiffHunk;
 use std::ops::Range;
 
 /// Type of change detected for a line range
@@ -82,6 +83,71 @@ pub fn diff_lines(saved: &[u8], current: &[u8]) -> LineDiff {
     }
 }
 
+/// Largest LCS table (old lines × new lines) [`diff_hunks`] computes
+pub const MAX_LCS_CELLS: usize = 16_000_000;
+
+/// Compare two byte slices line by line and return the differing regions as hunks.
+///
+/// Each hunk pairs a run of lines in `old` with the run of lines in `new` that
+/// replaced it; either side may be empty for pure insertions or deletions.
+///
+/// Lines shared at the start and end are matched up front, so only the part
+/// in between goes through the quadratic LCS. If that part is still larger
+/// than [`MAX_LCS_CELLS`], it is reported as a single hunk.
+pub fn diff_hunks(old: &[u8], new: &[u8]) -> Vec<DiffHunk> {
+    if old == new {
+        return vec![];
+    }
+    let old_lines: Vec<&[u8]> = old.split(|&b| b == b'\n').collect();
+    let new_lines: Vec<&[u8]> = new.split(|&b| b == b'\n').collect();
+
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old_lines[prefix..old_lines.len() - suffix];
+    let new_middle = &new_lines[prefix..new_lines.len() - suffix];
+
+    if old_middle.len().saturating_mul(new_middle.len()) > MAX_LCS_CELLS {
+        return vec![DiffHunk::new(
+            prefix,
+            old_middle.len(),
+            prefix,
+            new_middle.len(),
+        )];
+    }
+    let lcs = longest_common_subsequence(old_middle, new_middle);
+
+    let mut hunks = Vec::new();
+    let mut old_idx = 0;
+    let mut new_idx = 0;
+    // A sentinel match past the end of both sides closes the final hunk
+    let end = LineMatch {
+        saved_idx: old_middle.len(),
+        current_idx: new_middle.len(),
+    };
+    for m in lcs.iter().chain(std::iter::once(&end)) {
+        if m.saved_idx > old_idx || m.current_idx > new_idx {
+            hunks.push(DiffHunk::new(
+                prefix + old_idx,
+                m.saved_idx - old_idx,
+                prefix + new_idx,
+                m.current_idx - new_idx,
+            ));
+        }
+        old_idx = m.saved_idx + 1;
+        new_idx = m.current_idx + 1;
+    }
+    hunks
+}
+
 /// Represents a match between saved and current line indices
 #[derive(Debug, Clone, Copy)]
 struct LineMatch {
@@ -486,6 +552,57 @@ mod tests {
         assert!(!diff.equal);
         assert_eq!(diff.changed_lines, vec![0..1]);
     }
+
+    #[test]
+    fn test_diff_hunks() {
+        let hunk_ranges = |old: &[u8], new: &[u8]| -> Vec<(usize, usize, usize, usize)> {
+            diff_hunks(old, new)
+                .into_iter()
+                .map(|h| (h.old_start, h.old_count, h.new_start, h.new_count))
+                .collect()
+        };
+
+        assert!(hunk_ranges(b"a\nb\n", b"a\nb\n").is_empty());
+        // Modification, insertion and deletion
+        assert_eq!(hunk_ranges(b"a\nb\nc\n", b"a\nB\nc\n"), vec![(1, 1, 1, 1)]);
+        assert_eq!(hunk_ranges(b"a\nc\n", b"a\nb\nc\n"), vec![(1, 0, 1, 1)]);
+        assert_eq!(hunk_ranges(b"a\nb\nc\n", b"a\nc\n"), vec![(1, 1, 1, 0)]);
+        // Changes at both ends produce separate hunks
+        assert_eq!(
+            hunk_ranges(b"x\nsame\ny", b"X\nsame\nY"),
+            vec![(0, 1, 0, 1), (2, 1, 2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_diff_hunks_very_different_sizes() {
+        // A small file against a large one that shares its first and last lines
+        let small = b"start\nend\n".to_vec();
+        let mut large = b"start\n".to_vec();
+        for i in 0..5000 {
+            large.extend_from_slice(format!("line {}\n", i).as_bytes());
+        }
+        large.extend_from_slice(b"end\n");
+        let hunks = diff_hunks(&small, &large);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(
+            (hunks[0].old_start, hunks[0].old_count),
+            (1, 0),
+            "only the inserted lines differ"
+        );
+        assert_eq!((hunks[0].new_start, hunks[0].new_count), (1, 5000));
+
+        // Too large to align line by line: one hunk spans the differing middle
+        let old: Vec<u8> = (0..5000)
+            .flat_map(|i| format!("a{}\n", i).into_bytes())
+            .collect();
+        let new: Vec<u8> = (0..5000)
+            .flat_map(|i| format!("b{}\n", i).into_bytes())
+            .collect();
+        let hunks = diff_hunks(&old, &new);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!((hunks[0].old_count, hunks[0].new_count), (5000, 5000));
+    }
 }
 
 #[cfg(test)]
@@ -4,10 +4,12 @@
 //! enabling a 4-level overlay architecture (System → User → Project → Session).
 
 use crate::config::{
-    AcceptSuggestionOnEnter, ClipboardConfig, CursorStyle, FileBrowserConfig, FileExplorerConfig,
-    FormatterConfig, HighlighterPreference, Keybinding, KeybindingMapName, KeymapConfig,
-    LanguageConfig, LineEndingOption, OnSaveAction, PluginConfig, TerminalConfig, ThemeName,
-    WarningsConfig,
+    AcceptSuggestionOnEnter, AutoCommand, ClipboardConfig, CursorStyle, FileBrowserConfig,
+    FileExplorerConfig, FoldIndicators, FormatterConfig, HighlighterPreference, Keybinding,
+    KeybindingMapName, KeymapConfig, LanguageConfig, LineEndingOption, LspDownload, MouseConfig,
+    OnSaveAction, OpenExternalRule, PluginConfig, PrivacyConfig, ProcessConfig,
+    SessionRestoreConfig, ShellEnvMode, TerminalConfig, ThemeName, UiDensity, WarningsConfig,
+    WordGranularity,
 };
 use crate::types::LspServerConfig;
 use serde::{Deserialize, Serialize};
@@ -78,6 +80,7 @@ pub struct PartialConfig {
     pub theme: Option<ThemeName>,
     pub locale: Option<String>,
     pub check_for_updates: Option<bool>,
+    pub allow_network_fetch: Option<bool>,
     pub editor: Option<PartialEditorConfig>,
     pub file_explorer: Option<PartialFileExplorerConfig>,
     pub file_browser: Option<PartialFileBrowserConfig>,
@@ -88,7 +91,13 @@ pub struct PartialConfig {
     pub active_keybinding_map: Option<KeybindingMapName>,
     pub languages: Option<HashMap<String, PartialLanguageConfig>>,
     pub lsp: Option<HashMap<String, LspServerConfig>>,
+    pub lsp_downloads: Option<HashMap<String, LspDownload>>,
+    pub auto_commands: Option<Vec<AutoCommand>>,
+    pub open_external: Option<Vec<OpenExternalRule>>,
     pub warnings: Option<PartialWarningsConfig>,
+    pub privacy: Option<PartialPrivacyConfig>,
+    pub session: Option<PartialSessionConfig>,
+    pub process: Option<PartialProcessConfig>,
     pub plugins: Option<HashMap<String, PartialPluginConfig>>,
     pub packages: Option<PartialPackagesConfig>,
 }
@@ -99,6 +108,8 @@ impl Merge for PartialConfig {
         self.theme.merge_from(&other.theme);
         self.locale.merge_from(&other.locale);
         self.check_for_updates.merge_from(&other.check_for_updates);
+        self.allow_network_fetch
+            .merge_from(&other.allow_network_fetch);
 
         // Nested structs: merge recursively
         merge_partial(&mut self.editor, &other.editor);
@@ -107,15 +118,21 @@ impl Merge for PartialConfig {
         merge_partial(&mut self.clipboard, &other.clipboard);
         merge_partial(&mut self.terminal, &other.terminal);
         merge_partial(&mut self.warnings, &other.warnings);
+        merge_partial(&mut self.privacy, &other.privacy);
+        merge_partial(&mut self.session, &other.session);
+        merge_partial(&mut self.process, &other.process);
         merge_partial(&mut self.packages, &other.packages);
 
         // Lists: higher precedence replaces (per design doc)
         self.keybindings.merge_from(&other.keybindings);
+        self.auto_commands.merge_from(&other.auto_commands);
+        self.open_external.merge_from(&other.open_external);
 
         // HashMaps: merge entries, higher precedence wins on key collision
         merge_hashmap(&mut self.keybinding_maps, &other.keybinding_maps);
         merge_hashmap_recursive(&mut self.languages, &other.languages);
         merge_hashmap_recursive(&mut self.lsp, &other.lsp);
+        merge_hashmap(&mut self.lsp_downloads, &other.lsp_downloads);
         merge_hashmap_recursive(&mut self.plugins, &other.plugins);
 
         self.active_keybinding_map
@@ -123,6 +140,32 @@ impl Merge for PartialConfig {
     }
 }
 
+impl PartialConfig {
+    /// Drop every setting that makes the editor run a command or load code:
+    /// LSP servers and downloads, auto-commands, external openers, plugins,
+    /// package sources, the spawned-process environment and per-language
+    /// formatters and on-save actions.
+    /// Applied to the project and session layers of untrusted workspaces.
+    pub fn strip_command_settings(&mut self) {
+        self.lsp = None;
+        self.lsp_downloads = None;
+        self.auto_commands = None;
+        self.open_external = None;
+        self.plugins = None;
+        self.packages = None;
+        self.process = None;
+        for language in self
+            .languages
+            .iter_mut()
+            .flat_map(|langs| langs.values_mut())
+        {
+            language.formatter = None;
+            language.format_on_save = None;
+            language.on_save = None;
+        }
+    }
+}
+
 /// Helper to merge nested partial structs.
 fn merge_partial<T: Merge + Clone>(target: &mut Option<T>, other: &Option<T>) {
     match (target, other) {
@@ -140,21 +183,31 @@ pub struct PartialEditorConfig {
     pub auto_indent: Option<bool>,
     pub auto_close: Option<bool>,
     pub auto_surround: Option<bool>,
+    pub toggle_case_wraps_lines: Option<bool>,
+    pub word_granularity: Option<WordGranularity>,
+    pub wrap_column: Option<usize>,
     pub line_numbers: Option<bool>,
     pub relative_line_numbers: Option<bool>,
+    pub fold_indicators: Option<FoldIndicators>,
     pub scroll_offset: Option<usize>,
+    pub center_on_match: Option<bool>,
+    pub center_on_goto: Option<bool>,
     pub syntax_highlighting: Option<bool>,
     pub line_wrap: Option<bool>,
     pub wrap_indent: Option<bool>,
     pub highlight_timeout_ms: Option<u64>,
     pub snapshot_interval: Option<usize>,
+    pub max_undo_entries: Option<usize>,
+    pub max_undo_memory_mb: Option<usize>,
     pub large_file_threshold_bytes: Option<u64>,
     pub estimated_line_length: Option<usize>,
     pub enable_inlay_hints: Option<bool>,
     pub enable_semantic_tokens_full: Option<bool>,
     pub diagnostics_inline_text: Option<bool>,
+    pub fix_next_problem_auto_apply: Option<bool>,
     pub recovery_enabled: Option<bool>,
     pub auto_recovery_save_interval_secs: Option<u32>,
+    pub recovery_max_size_mb: Option<u32>,
     pub auto_save_enabled: Option<bool>,
     pub auto_save_interval_secs: Option<u32>,
     pub persist_unnamed_buffers: Option<bool>,
@@ -163,12 +216,20 @@ pub struct PartialEditorConfig {
     pub mouse_hover_enabled: Option<bool>,
     pub mouse_hover_delay_ms: Option<u64>,
     pub double_click_time_ms: Option<u64>,
+    pub mouse: Option<PartialMouseConfig>,
     pub auto_revert_poll_interval_ms: Option<u64>,
+    pub auto_revert_max_poll_interval_ms: Option<u64>,
+    pub remote_auto_revert_poll_interval_ms: Option<u64>,
     pub read_concurrency: Option<usize>,
+    pub large_file_line_scan: Option<bool>,
+    pub long_line_threshold: Option<usize>,
     pub file_tree_poll_interval_ms: Option<u64>,
     pub default_line_ending: Option<LineEndingOption>,
     pub trim_trailing_whitespace_on_save: Option<bool>,
     pub ensure_final_newline_on_save: Option<bool>,
+    pub follow_symlinks: Option<bool>,
+    pub recent_files_limit: Option<usize>,
+    pub fuzzy_finder_follow_symlinks: Option<bool>,
     pub highlight_matching_brackets: Option<bool>,
     pub rainbow_brackets: Option<bool>,
     pub cursor_style: Option<CursorStyle>,
@@ -183,10 +244,14 @@ pub struct PartialEditorConfig {
     pub show_menu_bar: Option<bool>,
     pub show_tab_bar: Option<bool>,
     pub show_status_bar: Option<bool>,
+    pub show_remote_latency: Option<bool>,
+    pub ui_density: Option<UiDensity>,
     pub show_vertical_scrollbar: Option<bool>,
     pub show_horizontal_scrollbar: Option<bool>,
     pub use_terminal_bg: Option<bool>,
     pub rulers: Option<Vec<usize>>,
+    pub export_html_line_numbers: Option<bool>,
+    pub export_html_wrap_width: Option<usize>,
     pub whitespace_show: Option<bool>,
     pub whitespace_spaces_leading: Option<bool>,
     pub whitespace_spaces_inner: Option<bool>,
@@ -202,10 +267,17 @@ impl Merge for PartialEditorConfig {
         self.auto_indent.merge_from(&other.auto_indent);
         self.auto_close.merge_from(&other.auto_close);
         self.auto_surround.merge_from(&other.auto_surround);
+        self.toggle_case_wraps_lines
+            .merge_from(&other.toggle_case_wraps_lines);
+        self.word_granularity.merge_from(&other.word_granularity);
+        self.wrap_column.merge_from(&other.wrap_column);
         self.line_numbers.merge_from(&other.line_numbers);
         self.relative_line_numbers
             .merge_from(&other.relative_line_numbers);
+        self.fold_indicators.merge_from(&other.fold_indicators);
         self.scroll_offset.merge_from(&other.scroll_offset);
+        self.center_on_match.merge_from(&other.center_on_match);
+        self.center_on_goto.merge_from(&other.center_on_goto);
         self.syntax_highlighting
             .merge_from(&other.syntax_highlighting);
         self.line_wrap.merge_from(&other.line_wrap);
@@ -213,6 +285,9 @@ impl Merge for PartialEditorConfig {
         self.highlight_timeout_ms
             .merge_from(&other.highlight_timeout_ms);
         self.snapshot_interval.merge_from(&other.snapshot_interval);
+        self.max_undo_entries.merge_from(&other.max_undo_entries);
+        self.max_undo_memory_mb
+            .merge_from(&other.max_undo_memory_mb);
         self.large_file_threshold_bytes
             .merge_from(&other.large_file_threshold_bytes);
         self.estimated_line_length
@@ -223,9 +298,13 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.enable_semantic_tokens_full);
         self.diagnostics_inline_text
             .merge_from(&other.diagnostics_inline_text);
+        self.fix_next_problem_auto_apply
+            .merge_from(&other.fix_next_problem_auto_apply);
         self.recovery_enabled.merge_from(&other.recovery_enabled);
         self.auto_recovery_save_interval_secs
             .merge_from(&other.auto_recovery_save_interval_secs);
+        self.recovery_max_size_mb
+            .merge_from(&other.recovery_max_size_mb);
         self.auto_save_enabled.merge_from(&other.auto_save_enabled);
         self.auto_save_interval_secs
             .merge_from(&other.auto_save_interval_secs);
@@ -240,9 +319,18 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.mouse_hover_delay_ms);
         self.double_click_time_ms
             .merge_from(&other.double_click_time_ms);
+        merge_partial(&mut self.mouse, &other.mouse);
         self.auto_revert_poll_interval_ms
             .merge_from(&other.auto_revert_poll_interval_ms);
+        self.auto_revert_max_poll_interval_ms
+            .merge_from(&other.auto_revert_max_poll_interval_ms);
+        self.remote_auto_revert_poll_interval_ms
+            .merge_from(&other.remote_auto_revert_poll_interval_ms);
         self.read_concurrency.merge_from(&other.read_concurrency);
+        self.large_file_line_scan
+            .merge_from(&other.large_file_line_scan);
+        self.long_line_threshold
+            .merge_from(&other.long_line_threshold);
         self.file_tree_poll_interval_ms
             .merge_from(&other.file_tree_poll_interval_ms);
         self.default_line_ending
@@ -251,6 +339,11 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.trim_trailing_whitespace_on_save);
         self.ensure_final_newline_on_save
             .merge_from(&other.ensure_final_newline_on_save);
+        self.follow_symlinks.merge_from(&other.follow_symlinks);
+        self.recent_files_limit
+            .merge_from(&other.recent_files_limit);
+        self.fuzzy_finder_follow_symlinks
+            .merge_from(&other.fuzzy_finder_follow_symlinks);
         self.highlight_matching_brackets
             .merge_from(&other.highlight_matching_brackets);
         self.rainbow_brackets.merge_from(&other.rainbow_brackets);
@@ -273,12 +366,19 @@ impl Merge for PartialEditorConfig {
         self.show_menu_bar.merge_from(&other.show_menu_bar);
         self.show_tab_bar.merge_from(&other.show_tab_bar);
         self.show_status_bar.merge_from(&other.show_status_bar);
+        self.show_remote_latency
+            .merge_from(&other.show_remote_latency);
+        self.ui_density.merge_from(&other.ui_density);
         self.show_vertical_scrollbar
             .merge_from(&other.show_vertical_scrollbar);
         self.show_horizontal_scrollbar
             .merge_from(&other.show_horizontal_scrollbar);
         self.use_terminal_bg.merge_from(&other.use_terminal_bg);
         self.rulers.merge_from(&other.rulers);
+        self.export_html_line_numbers
+            .merge_from(&other.export_html_line_numbers);
+        self.export_html_wrap_width
+            .merge_from(&other.export_html_wrap_width);
         self.whitespace_show.merge_from(&other.whitespace_show);
         self.whitespace_spaces_leading
             .merge_from(&other.whitespace_spaces_leading);
@@ -330,6 +430,33 @@ impl Merge for PartialFileBrowserConfig {
     }
 }
 
+/// Partial mouse configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialMouseConfig {
+    pub scroll_lines: Option<usize>,
+    pub wheel_moves_cursor: Option<bool>,
+    pub scroll_columns: Option<usize>,
+    pub scroll_hovered_split: Option<bool>,
+    pub click_moves_cursor: Option<bool>,
+    pub alt_bypasses_capture: Option<bool>,
+}
+
+impl Merge for PartialMouseConfig {
+    fn merge_from(&mut self, other: &Self) {
+        self.scroll_lines.merge_from(&other.scroll_lines);
+        self.wheel_moves_cursor
+            .merge_from(&other.wheel_moves_cursor);
+        self.scroll_columns.merge_from(&other.scroll_columns);
+        self.scroll_hovered_split
+            .merge_from(&other.scroll_hovered_split);
+        self.click_moves_cursor
+            .merge_from(&other.click_moves_cursor);
+        self.alt_bypasses_capture
+            .merge_from(&other.alt_bypasses_capture);
+    }
+}
+
 /// Partial clipboard configuration.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
@@ -374,6 +501,53 @@ impl Merge for PartialWarningsConfig {
     }
 }
 
+/// Partial Privacy Mode configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialPrivacyConfig {
+    pub extra_patterns: Option<Vec<String>>,
+    pub mask_env_files: Option<bool>,
+}
+
+impl Merge for PartialPrivacyConfig {
+    fn merge_from(&mut self, other: &Self) {
+        self.extra_patterns.merge_from(&other.extra_patterns);
+        self.mask_env_files.merge_from(&other.mask_env_files);
+    }
+}
+
+/// Partial session restore configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialSessionConfig {
+    pub restore_with_cli_files: Option<bool>,
+    pub remember_file_positions: Option<bool>,
+}
+
+impl Merge for PartialSessionConfig {
+    fn merge_from(&mut self, other: &Self) {
+        self.restore_with_cli_files
+            .merge_from(&other.restore_with_cli_files);
+        self.remember_file_positions
+            .merge_from(&other.remember_file_positions);
+    }
+}
+
+/// Partial spawned-process environment configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialProcessConfig {
+    pub shell_env: Option<ShellEnvMode>,
+    pub env: Option<HashMap<String, String>>,
+}
+
+impl Merge for PartialProcessConfig {
+    fn merge_from(&mut self, other: &Self) {
+        self.shell_env.merge_from(&other.shell_env);
+        merge_hashmap(&mut self.env, &other.env);
+    }
+}
+
 /// Partial packages configuration for plugin/theme package management.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
@@ -415,6 +589,8 @@ pub struct PartialLanguageConfig {
     pub auto_indent: Option<bool>,
     pub auto_close: Option<bool>,
     pub auto_surround: Option<bool>,
+    pub word_characters: Option<String>,
+    pub auto_close_pairs: Option<Vec<String>>,
     pub highlighter: Option<HighlighterPreference>,
     pub textmate_grammar: Option<std::path::PathBuf>,
     pub show_whitespace_tabs: Option<bool>,
@@ -422,6 +598,7 @@ pub struct PartialLanguageConfig {
     pub tab_size: Option<usize>,
     pub formatter: Option<FormatterConfig>,
     pub format_on_save: Option<bool>,
+    pub trim_trailing_whitespace_on_save: Option<bool>,
     pub on_save: Option<Vec<OnSaveAction>>,
 }
 
@@ -434,6 +611,8 @@ impl Merge for PartialLanguageConfig {
         self.auto_indent.merge_from(&other.auto_indent);
         self.auto_close.merge_from(&other.auto_close);
         self.auto_surround.merge_from(&other.auto_surround);
+        self.word_characters.merge_from(&other.word_characters);
+        self.auto_close_pairs.merge_from(&other.auto_close_pairs);
         self.highlighter.merge_from(&other.highlighter);
         self.textmate_grammar.merge_from(&other.textmate_grammar);
         self.show_whitespace_tabs
@@ -442,6 +621,8 @@ impl Merge for PartialLanguageConfig {
         self.tab_size.merge_from(&other.tab_size);
         self.formatter.merge_from(&other.formatter);
         self.format_on_save.merge_from(&other.format_on_save);
+        self.trim_trailing_whitespace_on_save
+            .merge_from(&other.trim_trailing_whitespace_on_save);
         self.on_save.merge_from(&other.on_save);
     }
 }
@@ -490,21 +671,31 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             auto_indent: Some(cfg.auto_indent),
             auto_close: Some(cfg.auto_close),
             auto_surround: Some(cfg.auto_surround),
+            toggle_case_wraps_lines: Some(cfg.toggle_case_wraps_lines),
+            word_granularity: Some(cfg.word_granularity),
+            wrap_column: Some(cfg.wrap_column),
             line_numbers: Some(cfg.line_numbers),
             relative_line_numbers: Some(cfg.relative_line_numbers),
+            fold_indicators: Some(cfg.fold_indicators),
             scroll_offset: Some(cfg.scroll_offset),
+            center_on_match: Some(cfg.center_on_match),
+            center_on_goto: Some(cfg.center_on_goto),
             syntax_highlighting: Some(cfg.syntax_highlighting),
             line_wrap: Some(cfg.line_wrap),
             wrap_indent: Some(cfg.wrap_indent),
             highlight_timeout_ms: Some(cfg.highlight_timeout_ms),
             snapshot_interval: Some(cfg.snapshot_interval),
+            max_undo_entries: Some(cfg.max_undo_entries),
+            max_undo_memory_mb: Some(cfg.max_undo_memory_mb),
             large_file_threshold_bytes: Some(cfg.large_file_threshold_bytes),
             estimated_line_length: Some(cfg.estimated_line_length),
             enable_inlay_hints: Some(cfg.enable_inlay_hints),
             enable_semantic_tokens_full: Some(cfg.enable_semantic_tokens_full),
             diagnostics_inline_text: Some(cfg.diagnostics_inline_text),
+            fix_next_problem_auto_apply: Some(cfg.fix_next_problem_auto_apply),
             recovery_enabled: Some(cfg.recovery_enabled),
             auto_recovery_save_interval_secs: Some(cfg.auto_recovery_save_interval_secs),
+            recovery_max_size_mb: Some(cfg.recovery_max_size_mb),
             auto_save_enabled: Some(cfg.auto_save_enabled),
             auto_save_interval_secs: Some(cfg.auto_save_interval_secs),
             persist_unnamed_buffers: Some(cfg.persist_unnamed_buffers),
@@ -513,12 +704,20 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             mouse_hover_enabled: Some(cfg.mouse_hover_enabled),
             mouse_hover_delay_ms: Some(cfg.mouse_hover_delay_ms),
             double_click_time_ms: Some(cfg.double_click_time_ms),
+            mouse: Some(PartialMouseConfig::from(&cfg.mouse)),
             auto_revert_poll_interval_ms: Some(cfg.auto_revert_poll_interval_ms),
+            auto_revert_max_poll_interval_ms: Some(cfg.auto_revert_max_poll_interval_ms),
+            remote_auto_revert_poll_interval_ms: Some(cfg.remote_auto_revert_poll_interval_ms),
             read_concurrency: Some(cfg.read_concurrency),
+            large_file_line_scan: Some(cfg.large_file_line_scan),
+            long_line_threshold: Some(cfg.long_line_threshold),
             file_tree_poll_interval_ms: Some(cfg.file_tree_poll_interval_ms),
             default_line_ending: Some(cfg.default_line_ending.clone()),
             trim_trailing_whitespace_on_save: Some(cfg.trim_trailing_whitespace_on_save),
             ensure_final_newline_on_save: Some(cfg.ensure_final_newline_on_save),
+            follow_symlinks: Some(cfg.follow_symlinks),
+            recent_files_limit: Some(cfg.recent_files_limit),
+            fuzzy_finder_follow_symlinks: Some(cfg.fuzzy_finder_follow_symlinks),
             highlight_matching_brackets: Some(cfg.highlight_matching_brackets),
             rainbow_brackets: Some(cfg.rainbow_brackets),
             cursor_style: Some(cfg.cursor_style),
@@ -535,10 +734,14 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             show_menu_bar: Some(cfg.show_menu_bar),
             show_tab_bar: Some(cfg.show_tab_bar),
             show_status_bar: Some(cfg.show_status_bar),
+            show_remote_latency: Some(cfg.show_remote_latency),
+            ui_density: Some(cfg.ui_density),
             show_vertical_scrollbar: Some(cfg.show_vertical_scrollbar),
             show_horizontal_scrollbar: Some(cfg.show_horizontal_scrollbar),
             use_terminal_bg: Some(cfg.use_terminal_bg),
             rulers: Some(cfg.rulers.clone()),
+            export_html_line_numbers: Some(cfg.export_html_line_numbers),
+            export_html_wrap_width: Some(cfg.export_html_wrap_width),
             whitespace_show: Some(cfg.whitespace_show),
             whitespace_spaces_leading: Some(cfg.whitespace_spaces_leading),
             whitespace_spaces_inner: Some(cfg.whitespace_spaces_inner),
@@ -558,11 +761,19 @@ impl PartialEditorConfig {
             auto_indent: self.auto_indent.unwrap_or(defaults.auto_indent),
             auto_close: self.auto_close.unwrap_or(defaults.auto_close),
             auto_surround: self.auto_surround.unwrap_or(defaults.auto_surround),
+            toggle_case_wraps_lines: self
+                .toggle_case_wraps_lines
+                .unwrap_or(defaults.toggle_case_wraps_lines),
+            word_granularity: self.word_granularity.unwrap_or(defaults.word_granularity),
+            wrap_column: self.wrap_column.unwrap_or(defaults.wrap_column),
             line_numbers: self.line_numbers.unwrap_or(defaults.line_numbers),
             relative_line_numbers: self
                 .relative_line_numbers
                 .unwrap_or(defaults.relative_line_numbers),
+            fold_indicators: self.fold_indicators.unwrap_or(defaults.fold_indicators),
             scroll_offset: self.scroll_offset.unwrap_or(defaults.scroll_offset),
+            center_on_match: self.center_on_match.unwrap_or(defaults.center_on_match),
+            center_on_goto: self.center_on_goto.unwrap_or(defaults.center_on_goto),
             syntax_highlighting: self
                 .syntax_highlighting
                 .unwrap_or(defaults.syntax_highlighting),
@@ -572,6 +783,10 @@ impl PartialEditorConfig {
                 .highlight_timeout_ms
                 .unwrap_or(defaults.highlight_timeout_ms),
             snapshot_interval: self.snapshot_interval.unwrap_or(defaults.snapshot_interval),
+            max_undo_entries: self.max_undo_entries.unwrap_or(defaults.max_undo_entries),
+            max_undo_memory_mb: self
+                .max_undo_memory_mb
+                .unwrap_or(defaults.max_undo_memory_mb),
             large_file_threshold_bytes: self
                 .large_file_threshold_bytes
                 .unwrap_or(defaults.large_file_threshold_bytes),
@@ -587,10 +802,16 @@ impl PartialEditorConfig {
             diagnostics_inline_text: self
                 .diagnostics_inline_text
                 .unwrap_or(defaults.diagnostics_inline_text),
+            fix_next_problem_auto_apply: self
+                .fix_next_problem_auto_apply
+                .unwrap_or(defaults.fix_next_problem_auto_apply),
             recovery_enabled: self.recovery_enabled.unwrap_or(defaults.recovery_enabled),
             auto_recovery_save_interval_secs: self
                 .auto_recovery_save_interval_secs
                 .unwrap_or(defaults.auto_recovery_save_interval_secs),
+            recovery_max_size_mb: self
+                .recovery_max_size_mb
+                .unwrap_or(defaults.recovery_max_size_mb),
             auto_save_enabled: self.auto_save_enabled.unwrap_or(defaults.auto_save_enabled),
             auto_save_interval_secs: self
                 .auto_save_interval_secs
@@ -611,10 +832,26 @@ impl PartialEditorConfig {
             double_click_time_ms: self
                 .double_click_time_ms
                 .unwrap_or(defaults.double_click_time_ms),
+            mouse: self
+                .mouse
+                .map(|m| m.resolve(&defaults.mouse))
+                .unwrap_or_else(|| defaults.mouse.clone()),
             auto_revert_poll_interval_ms: self
                 .auto_revert_poll_interval_ms
                 .unwrap_or(defaults.auto_revert_poll_interval_ms),
+            auto_revert_max_poll_interval_ms: self
+                .auto_revert_max_poll_interval_ms
+                .unwrap_or(defaults.auto_revert_max_poll_interval_ms),
+            remote_auto_revert_poll_interval_ms: self
+                .remote_auto_revert_poll_interval_ms
+                .unwrap_or(defaults.remote_auto_revert_poll_interval_ms),
             read_concurrency: self.read_concurrency.unwrap_or(defaults.read_concurrency),
+            large_file_line_scan: self
+                .large_file_line_scan
+                .unwrap_or(defaults.large_file_line_scan),
+            long_line_threshold: self
+                .long_line_threshold
+                .unwrap_or(defaults.long_line_threshold),
             file_tree_poll_interval_ms: self
                 .file_tree_poll_interval_ms
                 .unwrap_or(defaults.file_tree_poll_interval_ms),
@@ -627,6 +864,13 @@ impl PartialEditorConfig {
             ensure_final_newline_on_save: self
                 .ensure_final_newline_on_save
                 .unwrap_or(defaults.ensure_final_newline_on_save),
+            follow_symlinks: self.follow_symlinks.unwrap_or(defaults.follow_symlinks),
+            recent_files_limit: self
+                .recent_files_limit
+                .unwrap_or(defaults.recent_files_limit),
+            fuzzy_finder_follow_symlinks: self
+                .fuzzy_finder_follow_symlinks
+                .unwrap_or(defaults.fuzzy_finder_follow_symlinks),
             highlight_matching_brackets: self
                 .highlight_matching_brackets
                 .unwrap_or(defaults.highlight_matching_brackets),
@@ -657,6 +901,10 @@ impl PartialEditorConfig {
             show_menu_bar: self.show_menu_bar.unwrap_or(defaults.show_menu_bar),
             show_tab_bar: self.show_tab_bar.unwrap_or(defaults.show_tab_bar),
             show_status_bar: self.show_status_bar.unwrap_or(defaults.show_status_bar),
+            show_remote_latency: self
+                .show_remote_latency
+                .unwrap_or(defaults.show_remote_latency),
+            ui_density: self.ui_density.unwrap_or(defaults.ui_density),
             show_vertical_scrollbar: self
                 .show_vertical_scrollbar
                 .unwrap_or(defaults.show_vertical_scrollbar),
@@ -665,6 +913,12 @@ impl PartialEditorConfig {
                 .unwrap_or(defaults.show_horizontal_scrollbar),
             use_terminal_bg: self.use_terminal_bg.unwrap_or(defaults.use_terminal_bg),
             rulers: self.rulers.unwrap_or_else(|| defaults.rulers.clone()),
+            export_html_line_numbers: self
+                .export_html_line_numbers
+                .unwrap_or(defaults.export_html_line_numbers),
+            export_html_wrap_width: self
+                .export_html_wrap_width
+                .unwrap_or(defaults.export_html_wrap_width),
             whitespace_show: self.whitespace_show.unwrap_or(defaults.whitespace_show),
             whitespace_spaces_leading: self
                 .whitespace_spaces_leading
@@ -730,6 +984,40 @@ impl PartialFileBrowserConfig {
     }
 }
 
+impl From<&MouseConfig> for PartialMouseConfig {
+    fn from(cfg: &MouseConfig) -> Self {
+        Self {
+            scroll_lines: Some(cfg.scroll_lines),
+            wheel_moves_cursor: Some(cfg.wheel_moves_cursor),
+            scroll_columns: Some(cfg.scroll_columns),
+            scroll_hovered_split: Some(cfg.scroll_hovered_split),
+            click_moves_cursor: Some(cfg.click_moves_cursor),
+            alt_bypasses_capture: Some(cfg.alt_bypasses_capture),
+        }
+    }
+}
+
+impl PartialMouseConfig {
+    pub fn resolve(self, defaults: &MouseConfig) -> MouseConfig {
+        MouseConfig {
+            scroll_lines: self.scroll_lines.unwrap_or(defaults.scroll_lines),
+            wheel_moves_cursor: self
+                .wheel_moves_cursor
+                .unwrap_or(defaults.wheel_moves_cursor),
+            scroll_columns: self.scroll_columns.unwrap_or(defaults.scroll_columns),
+            scroll_hovered_split: self
+                .scroll_hovered_split
+                .unwrap_or(defaults.scroll_hovered_split),
+            click_moves_cursor: self
+                .click_moves_cursor
+                .unwrap_or(defaults.click_moves_cursor),
+            alt_bypasses_capture: self
+                .alt_bypasses_capture
+                .unwrap_or(defaults.alt_bypasses_capture),
+        }
+    }
+}
+
 impl From<&ClipboardConfig> for PartialClipboardConfig {
     fn from(cfg: &ClipboardConfig) -> Self {
         Self {
@@ -776,6 +1064,66 @@ impl From<&WarningsConfig> for PartialWarningsConfig {
     }
 }
 
+impl From<&PrivacyConfig> for PartialPrivacyConfig {
+    fn from(cfg: &PrivacyConfig) -> Self {
+        Self {
+            extra_patterns: Some(cfg.extra_patterns.clone()),
+            mask_env_files: Some(cfg.mask_env_files),
+        }
+    }
+}
+
+impl From<&SessionRestoreConfig> for PartialSessionConfig {
+    fn from(cfg: &SessionRestoreConfig) -> Self {
+        Self {
+            restore_with_cli_files: Some(cfg.restore_with_cli_files),
+            remember_file_positions: Some(cfg.remember_file_positions),
+        }
+    }
+}
+
+impl From<&ProcessConfig> for PartialProcessConfig {
+    fn from(cfg: &ProcessConfig) -> Self {
+        Self {
+            shell_env: Some(cfg.shell_env),
+            env: Some(cfg.env.clone()),
+        }
+    }
+}
+
+impl PartialProcessConfig {
+    pub fn resolve(self, defaults: &ProcessConfig) -> ProcessConfig {
+        ProcessConfig {
+            shell_env: self.shell_env.unwrap_or(defaults.shell_env),
+            env: self.env.unwrap_or_else(|| defaults.env.clone()),
+        }
+    }
+}
+
+impl PartialSessionConfig {
+    pub fn resolve(self, defaults: &SessionRestoreConfig) -> SessionRestoreConfig {
+        SessionRestoreConfig {
+            restore_with_cli_files: self
+                .restore_with_cli_files
+                .unwrap_or(defaults.restore_with_cli_files),
+            remember_file_positions: self
+                .remember_file_positions
+                .unwrap_or(defaults.remember_file_positions),
+        }
+    }
+}
+
+impl PartialPrivacyConfig {
+    pub fn resolve(self, defaults: &PrivacyConfig) -> PrivacyConfig {
+        PrivacyConfig {
+            extra_patterns: self
+                .extra_patterns
+                .unwrap_or_else(|| defaults.extra_patterns.clone()),
+            mask_env_files: self.mask_env_files.unwrap_or(defaults.mask_env_files),
+        }
+    }
+}
+
 impl PartialWarningsConfig {
     pub fn resolve(self, defaults: &WarningsConfig) -> WarningsConfig {
         WarningsConfig {
@@ -833,6 +1181,8 @@ impl From<&LanguageConfig> for PartialLanguageConfig {
             auto_indent: Some(cfg.auto_indent),
             auto_close: cfg.auto_close,
             auto_surround: cfg.auto_surround,
+            word_characters: cfg.word_characters.clone(),
+            auto_close_pairs: cfg.auto_close_pairs.clone(),
             highlighter: Some(cfg.highlighter),
             textmate_grammar: cfg.textmate_grammar.clone(),
             show_whitespace_tabs: Some(cfg.show_whitespace_tabs),
@@ -840,6 +1190,7 @@ impl From<&LanguageConfig> for PartialLanguageConfig {
             tab_size: cfg.tab_size,
             formatter: cfg.formatter.clone(),
             format_on_save: Some(cfg.format_on_save),
+            trim_trailing_whitespace_on_save: cfg.trim_trailing_whitespace_on_save,
             on_save: Some(cfg.on_save.clone()),
         }
     }
@@ -859,6 +1210,12 @@ impl PartialLanguageConfig {
             auto_indent: self.auto_indent.unwrap_or(defaults.auto_indent),
             auto_close: self.auto_close.or(defaults.auto_close),
             auto_surround: self.auto_surround.or(defaults.auto_surround),
+            word_characters: self
+                .word_characters
+                .or_else(|| defaults.word_characters.clone()),
+            auto_close_pairs: self
+                .auto_close_pairs
+                .or_else(|| defaults.auto_close_pairs.clone()),
             highlighter: self.highlighter.unwrap_or(defaults.highlighter),
             textmate_grammar: self
                 .textmate_grammar
@@ -870,6 +1227,9 @@ impl PartialLanguageConfig {
             tab_size: self.tab_size.or(defaults.tab_size),
             formatter: self.formatter.or_else(|| defaults.formatter.clone()),
             format_on_save: self.format_on_save.unwrap_or(defaults.format_on_save),
+            trim_trailing_whitespace_on_save: self
+                .trim_trailing_whitespace_on_save
+                .or(defaults.trim_trailing_whitespace_on_save),
             on_save: self.on_save.unwrap_or_else(|| defaults.on_save.clone()),
         }
     }
@@ -882,6 +1242,7 @@ impl From<&crate::config::Config> for PartialConfig {
             theme: Some(cfg.theme.clone()),
             locale: cfg.locale.0.clone(),
             check_for_updates: Some(cfg.check_for_updates),
+            allow_network_fetch: Some(cfg.allow_network_fetch),
             editor: Some(PartialEditorConfig::from(&cfg.editor)),
             file_explorer: Some(PartialFileExplorerConfig::from(&cfg.file_explorer)),
             file_browser: Some(PartialFileBrowserConfig::from(&cfg.file_browser)),
@@ -897,7 +1258,13 @@ impl From<&crate::config::Config> for PartialConfig {
                     .collect(),
             ),
             lsp: Some(cfg.lsp.clone()),
+            lsp_downloads: Some(cfg.lsp_downloads.clone()),
+            auto_commands: Some(cfg.auto_commands.clone()),
+            open_external: Some(cfg.open_external.clone()),
             warnings: Some(PartialWarningsConfig::from(&cfg.warnings)),
+            privacy: Some(PartialPrivacyConfig::from(&cfg.privacy)),
+            session: Some(PartialSessionConfig::from(&cfg.session)),
+            process: Some(PartialProcessConfig::from(&cfg.process)),
             // Only include plugins that differ from defaults
             // Path is auto-discovered at runtime and should never be saved
             plugins: {
@@ -994,6 +1361,9 @@ impl PartialConfig {
                 self.locale.or_else(|| defaults.locale.0.clone()),
             ),
             check_for_updates: self.check_for_updates.unwrap_or(defaults.check_for_updates),
+            allow_network_fetch: self
+                .allow_network_fetch
+                .unwrap_or(defaults.allow_network_fetch),
             editor: self
                 .editor
                 .map(|e| e.resolve(&defaults.editor))
@@ -1023,15 +1393,37 @@ impl PartialConfig {
                 .unwrap_or_else(|| defaults.active_keybinding_map.clone()),
             languages,
             lsp,
+            lsp_downloads: self
+                .lsp_downloads
+                .unwrap_or_else(|| defaults.lsp_downloads.clone()),
+            auto_commands: self
+                .auto_commands
+                .unwrap_or_else(|| defaults.auto_commands.clone()),
+            open_external: self
+                .open_external
+                .unwrap_or_else(|| defaults.open_external.clone()),
             warnings: self
                 .warnings
                 .map(|e| e.resolve(&defaults.warnings))
                 .unwrap_or_else(|| defaults.warnings.clone()),
+            privacy: self
+                .privacy
+                .map(|e| e.resolve(&defaults.privacy))
+                .unwrap_or_else(|| defaults.privacy.clone()),
+            session: self
+                .session
+                .map(|e| e.resolve(&defaults.session))
+                .unwrap_or_else(|| defaults.session.clone()),
+            process: self
+                .process
+                .map(|e| e.resolve(&defaults.process))
+                .unwrap_or_else(|| defaults.process.clone()),
             plugins,
             packages: self
                 .packages
                 .map(|e| e.resolve(&defaults.packages))
                 .unwrap_or_else(|| defaults.packages.clone()),
+            runtime_layer: Default::default(),
         }
     }
 }
@@ -1047,6 +1439,9 @@ impl Default for LanguageConfig {
             auto_indent: true,
             auto_close: None,
             auto_surround: None,
+            word_characters: None,
+            auto_close_pairs: None,
+            trim_trailing_whitespace_on_save: None,
             highlighter: HighlighterPreference::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -1184,6 +1579,22 @@ mod tests {
         assert_eq!(higher.line_numbers, Some(true)); // Filled from lower
     }
 
+    #[test]
+    fn merge_mouse_config_per_field() {
+        let mut higher: PartialEditorConfig =
+            serde_json::from_str(r#"{"mouse": {"scroll_lines": 5}}"#).unwrap();
+        let lower: PartialEditorConfig =
+            serde_json::from_str(r#"{"mouse": {"scroll_lines": 1, "click_moves_cursor": false}}"#)
+                .unwrap();
+
+        higher.merge_from(&lower);
+        let resolved = higher.resolve(&crate::config::EditorConfig::default());
+
+        assert_eq!(resolved.mouse.scroll_lines, 5); // Higher wins
+        assert!(!resolved.mouse.click_moves_cursor); // Filled from lower
+        assert!(resolved.mouse.alt_bypasses_capture); // Default
+    }
+
     #[test]
     fn merge_partial_config_combines_languages() {
         let mut higher = PartialConfig {
@@ -0,0 +1,240 @@
+//! Content-anchored positions that survive whole-buffer replacement.
+//!
+//! Reverting, auto-revert, recovery and formatters replace the entire buffer
+//! text, so a plain byte offset lands somewhere unrelated once lines were
+//! added or removed above it. An anchor instead remembers the text of the
+//! line a position was on (and of its neighbours) and looks for that line
+//! again near its old line number in the new text. When no line matches,
+//! the old line number and column are clamped to the new text.
+
+/// How many lines above and below the old line number are searched
+const SEARCH_WINDOW: usize = 2000;
+
+/// A byte position remembered by the surrounding line content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentAnchor {
+    /// Zero-based line number in the old text
+    line: usize,
+    /// Byte offset of the position within its line
+    column: usize,
+    /// Text of the line (without line ending)
+    line_text: String,
+    /// Text of the previous line, if any
+    before: Option<String>,
+    /// Text of the next line, if any
+    after: Option<String>,
+}
+
+impl ContentAnchor {
+    /// Remember `position` (a byte offset into `text`)
+    pub fn capture(text: &str, position: usize) -> Self {
+        Self::capture_in(&split_lines(text), text.len(), position)
+    }
+
+    /// Find the remembered position in `text`, returning a byte offset on a
+    /// char boundary
+    pub fn resolve(&self, text: &str) -> usize {
+        self.resolve_in(&split_lines(text))
+    }
+
+    fn capture_in(lines: &[(usize, &str)], text_len: usize, position: usize) -> Self {
+        let position = position.min(text_len);
+        let line = lines
+            .iter()
+            .rposition(|&(start, _)| start <= position)
+            .unwrap_or(0);
+        let (start, line_text) = lines[line];
+        Self {
+            line,
+            column: (position - start).min(line_text.len()),
+            line_text: line_text.to_string(),
+            before: line.checked_sub(1).map(|prev| lines[prev].1.to_string()),
+            after: lines.get(line + 1).map(|(_, next)| next.to_string()),
+        }
+    }
+
+    fn resolve_in(&self, lines: &[(usize, &str)]) -> usize {
+        let (line, column) = self
+            .best_match(lines)
+            .unwrap_or_else(|| (self.line.min(lines.len() - 1), self.column));
+        let (start, line_text) = lines[line];
+        let mut column = column.min(line_text.len());
+        while !line_text.is_char_boundary(column) {
+            column -= 1;
+        }
+        start + column
+    }
+
+    /// Best matching line near the old line number and the column on it
+    fn best_match(&self, lines: &[(usize, &str)]) -> Option<(usize, usize)> {
+        let wanted = self.line_text.trim();
+        let first = self.line.saturating_sub(SEARCH_WINDOW);
+        let last = (self.line + SEARCH_WINDOW).min(lines.len() - 1);
+
+        let mut best: Option<(u32, usize, usize)> = None; // (score, distance, line)
+        for candidate in first..=last {
+            let text = lines[candidate].1;
+            let mut score = if text == self.line_text {
+                4
+            } else if text.trim() == wanted {
+                3
+            } else {
+                continue;
+            };
+            // A blank line matches almost anywhere; only its neighbours
+            // can tell which one it was
+            if wanted.is_empty() {
+                score = 1;
+            }
+            let neighbour_matches = |neighbour: Option<&str>, expected: &Option<String>| matches!((neighbour, expected), (Some(n), Some(e)) if n.trim() == e.trim());
+            if neighbour_matches(
+                candidate.checked_sub(1).map(|prev| lines[prev].1),
+                &self.before,
+            ) {
+                score += 1;
+            }
+            if neighbour_matches(lines.get(candidate + 1).map(|l| l.1), &self.after) {
+                score += 1;
+            }
+            if wanted.is_empty() && score < 2 {
+                continue;
+            }
+
+            let distance = candidate.abs_diff(self.line);
+            let better = match best {
+                None => true,
+                Some((best_score, best_distance, _)) => {
+                    score > best_score || (score == best_score && distance < best_distance)
+                }
+            };
+            if better {
+                best = Some((score, distance, candidate));
+            }
+        }
+
+        best.map(|(_, _, line)| (line, self.column_on(lines[line].1)))
+    }
+
+    /// Column on a matching line, shifted by any change in indentation
+    fn column_on(&self, text: &str) -> usize {
+        let old_indent = indent_len(&self.line_text);
+        let new_indent = indent_len(text);
+        if self.column >= old_indent {
+            new_indent + (self.column - old_indent)
+        } else {
+            self.column.min(new_indent)
+        }
+    }
+}
+
+/// Moves many positions from one text to its replacement, splitting each
+/// text into lines only once
+pub struct Relocator<'a> {
+    old_lines: Vec<(usize, &'a str)>,
+    old_len: usize,
+    new_lines: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Relocator<'a> {
+    pub fn new(old: &'a str, new: &'a str) -> Self {
+        Self {
+            old_lines: split_lines(old),
+            old_len: old.len(),
+            new_lines: split_lines(new),
+        }
+    }
+
+    /// Where `position` in the old text is in the new text
+    pub fn relocate(&self, position: usize) -> usize {
+        ContentAnchor::capture_in(&self.old_lines, self.old_len, position)
+            .resolve_in(&self.new_lines)
+    }
+}
+
+/// Start offset and content (without line ending) of every line. Always
+/// returns at least one line, and a trailing newline yields an empty last line.
+fn split_lines(text: &str) -> Vec<(usize, &str)> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for chunk in text.split_inclusive('\n') {
+        let content = chunk.strip_suffix('\n').unwrap_or(chunk);
+        let content = content.strip_suffix('\r').unwrap_or(content);
+        lines.push((start, content));
+        start += chunk.len();
+    }
+    if text.is_empty() || text.ends_with('\n') {
+        lines.push((text.len(), ""));
+    }
+    lines
+}
+
+fn indent_len(text: &str) -> usize {
+    text.len() - text.trim_start().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(old: &str, position: usize, new: &str) -> usize {
+        ContentAnchor::capture(old, position).resolve(new)
+    }
+
+    #[test]
+    fn test_lines_inserted_above_move_the_position_down() {
+        let old = "one\ntwo\nthree\n";
+        let new = "zero\nminus one\none\ntwo\nthree\n";
+        // "th|ree"
+        assert_eq!(roundtrip(old, 10, new), new.find("three").unwrap() + 2);
+    }
+
+    #[test]
+    fn test_lines_removed_above_move_the_position_up() {
+        let old = "a\nb\nc\nfn main() {}\n";
+        let new = "c\nfn main() {}\n";
+        let pos = old.find("main").unwrap();
+        assert_eq!(roundtrip(old, pos, new), new.find("main").unwrap());
+    }
+
+    #[test]
+    fn test_lines_inserted_below_keep_the_position() {
+        let old = "let x = 1;\nlet y = 2;\n";
+        let new = "let x = 1;\nlet y = 2;\nlet z = 3;\n";
+        assert_eq!(roundtrip(old, 15, new), 15);
+    }
+
+    #[test]
+    fn test_duplicate_lines_prefer_matching_neighbours() {
+        let old = "a\n}\nb\n}\nc\n";
+        let new = "x\na\n}\nb\n}\nc\n";
+        // Cursor on the second "}" (between "b" and "c")
+        let pos = 6;
+        assert_eq!(roundtrip(old, pos, new), 8);
+    }
+
+    #[test]
+    fn test_reindented_line_keeps_column_within_content() {
+        let old = "fn f() {\nlet value = 1;\n}\n";
+        let new = "fn f() {\n    let value = 1;\n}\n";
+        let pos = old.find("value").unwrap();
+        assert_eq!(roundtrip(old, pos, new), new.find("value").unwrap());
+    }
+
+    #[test]
+    fn test_unmatched_line_falls_back_to_line_number() {
+        let old = "one\ntwo\nthree\n";
+        let new = "uno\ndos\ntres\n";
+        // "tw|o" -> line 1, column 2
+        assert_eq!(roundtrip(old, 6, new), 6);
+        // Past the end clamps to the last line
+        assert_eq!(roundtrip(old, 12, "x"), 1);
+    }
+
+    #[test]
+    fn test_resolves_to_char_boundary() {
+        let old = "abc\n";
+        let new = "é\n";
+        let pos = roundtrip(old, 1, new);
+        assert!(new.is_char_boundary(pos));
+    }
+}
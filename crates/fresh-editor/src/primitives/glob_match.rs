@@ -48,6 +48,24 @@ pub fn path_glob_matches(pattern: &str, path: &str) -> bool {
     path_glob_match_bytes(pattern.as_bytes(), path.as_bytes())
 }
 
+/// Match a user-supplied glob against a file path: patterns containing a
+/// path separator match the full path, others only the file name.
+///
+/// Examples:
+/// - `"*.png"` matches `"/home/me/logo.png"`
+/// - `"**/docs/*.pdf"` matches `"/home/me/project/docs/guide.pdf"`
+pub fn file_glob_matches(pattern: &str, path: &str) -> bool {
+    if is_path_pattern(pattern) {
+        path_glob_matches(pattern, path)
+    } else {
+        let file_name = std::path::Path::new(path)
+            .file_name()
+            .map(|n| n.to_string_lossy())
+            .unwrap_or_default();
+        filename_glob_matches(pattern, &file_name)
+    }
+}
+
 /// Iterative glob matching on byte slices using a backtracking algorithm.
 /// Used for filename matching where `*` matches any character.
 fn glob_match_bytes(pattern: &[u8], text: &[u8]) -> bool {
@@ -197,6 +215,21 @@ fn path_glob_match_bytes(pattern: &[u8], text: &[u8]) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_file_glob_matches() {
+        assert!(file_glob_matches("*.rs", "/home/me/project/src/main.rs"));
+        assert!(!file_glob_matches("*.rs", "/home/me/project/README.md"));
+        assert!(file_glob_matches(
+            "**/src/*.rs",
+            "/home/me/project/src/main.rs"
+        ));
+        assert!(!file_glob_matches(
+            "**/tests/*.rs",
+            "/home/me/project/src/main.rs"
+        ));
+        assert!(file_glob_matches("Makefile", "/home/me/project/Makefile"));
+    }
+
     #[test]
     fn test_is_glob_pattern() {
         assert!(is_glob_pattern("*.conf"));
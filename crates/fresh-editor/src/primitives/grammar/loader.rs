@@ -523,6 +523,9 @@ mod tests {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: crate::config::HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: true,
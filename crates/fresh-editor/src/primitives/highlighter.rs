@@ -4,6 +4,7 @@
 //! - **Viewport-only parsing**: Only highlights visible lines for instant performance with large files
 //! - **Incremental updates**: Re-parses only edited regions
 //! - **Lazy initialization**: Parsing happens on first render
+//! - **Shared grammars**: Each language's highlight queries compile once, on first use
 //!
 //! # Performance
 //! Must work instantly when loading a 1GB file and jumping to an arbitrary offset.
@@ -17,7 +18,9 @@ use fresh_languages::tree_sitter_highlight::{
 };
 pub use fresh_languages::{HighlightCategory, Language};
 use ratatui::style::Color;
+use std::collections::HashMap;
 use std::ops::Range;
+use std::sync::{Arc, Mutex, OnceLock};
 
 /// Maximum bytes to parse in a single operation (for viewport highlighting)
 const MAX_PARSE_BYTES: usize = LARGE_FILE_THRESHOLD_BYTES as usize; // 1MB
@@ -68,6 +71,23 @@ struct HighlightCache {
     spans: Vec<CachedSpan>,
 }
 
+/// The highlight configuration of `language`, compiled on first use and
+/// shared by every highlighter of that language afterwards
+fn highlight_config(language: &Language) -> Result<Arc<HighlightConfiguration>, String> {
+    static CONFIGS: OnceLock<Mutex<HashMap<&'static str, Arc<HighlightConfiguration>>>> =
+        OnceLock::new();
+    let mut configs = CONFIGS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(config) = configs.get(language.id()) {
+        return Ok(Arc::clone(config));
+    }
+    let config = Arc::new(language.highlight_config()?);
+    configs.insert(language.id(), Arc::clone(&config));
+    Ok(config)
+}
+
 /// Syntax highlighter with incremental viewport-based parsing
 pub struct Highlighter {
     /// Tree-sitter highlighter instance
@@ -75,7 +95,7 @@ pub struct Highlighter {
     /// Language being highlighted
     language: Language,
     /// Highlight configuration for the language
-    config: HighlightConfiguration,
+    config: Arc<HighlightConfiguration>,
     /// Cache of highlighted spans (only for visible viewport)
     cache: Option<HighlightCache>,
     /// Last known buffer length (for detecting complete buffer changes)
@@ -85,7 +105,7 @@ pub struct Highlighter {
 impl Highlighter {
     /// Create a new highlighter for the given language
     pub fn new(language: Language) -> Result<Self, String> {
-        let config = language.highlight_config()?;
+        let config = highlight_config(&language)?;
         Ok(Self {
             ts_highlighter: TSHighlighter::new(),
             language,
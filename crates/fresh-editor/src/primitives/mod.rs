@@ -15,11 +15,14 @@
 //! | Reference highlighting | `reference_highlight_text` | `reference_highlighter` |
 
 // Pure modules - available for both runtime and WASM
+pub mod content_anchor;
 pub mod display_width;
 pub mod glob_match;
 pub mod grapheme;
 pub mod line_wrapping;
 pub mod path_utils;
+pub mod secret_mask;
+pub mod selection_stats;
 pub mod snippet;
 pub mod text_property;
 
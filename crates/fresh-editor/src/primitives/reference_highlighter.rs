@@ -20,13 +20,18 @@
 //! # Supported Languages for Scope-Aware Highlighting
 //! - Rust, Python, JavaScript, TypeScript, Go, C, C++
 //! - Other languages fall back to identifier or text matching
+//!
+//! The queries of a language are compiled the first time a buffer in that
+//! language needs them and shared by every buffer in it afterwards.
 
 use crate::model::buffer::Buffer;
 use crate::primitives::highlighter::{HighlightSpan, Language};
 use crate::primitives::word_navigation::{find_word_end, find_word_start, is_word_char};
 use fresh_languages::tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
 use ratatui::style::Color;
+use std::collections::HashMap;
 use std::ops::Range;
+use std::sync::{Arc, Mutex, OnceLock};
 
 /// Default subtle background color for occurrence highlights
 /// A dark gray that's visible but not distracting
@@ -40,8 +45,16 @@ pub struct ReferenceHighlighter {
     pub min_word_length: usize,
     /// Whether semantic highlighting is enabled
     pub enabled: bool,
-    /// Tree-sitter parser (optional, for syntax-aware highlighting)
+    /// Language for syntax-aware highlighting
+    language: Option<Language>,
+    /// Tree-sitter parser, created on first use
     parser: Option<Parser>,
+}
+
+/// Compiled tree-sitter queries of one language
+struct LanguageQueries {
+    /// Grammar, `None` if this tree-sitter version can't parse with it
+    ts_language: Option<fresh_languages::tree_sitter::Language>,
     /// Query to find identifier nodes (fallback when locals not available)
     identifier_query: Option<Query>,
     /// Query for local variable tracking (scope-aware highlighting)
@@ -172,6 +185,107 @@ const C_LOCALS_QUERY: &str = r#"
 (identifier) @local.reference
 "#;
 
+/// The compiled queries of `language`, compiled on first use and shared by
+/// every buffer in that language afterwards
+fn language_queries(language: &Language) -> Arc<LanguageQueries> {
+    static QUERIES: OnceLock<Mutex<HashMap<&'static str, Arc<LanguageQueries>>>> = OnceLock::new();
+    let mut queries = QUERIES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    Arc::clone(
+        queries
+            .entry(language.id())
+            .or_insert_with(|| Arc::new(compile_language_queries(language))),
+    )
+}
+
+/// Compile the identifier and locals queries of `language`
+fn compile_language_queries(language: &Language) -> LanguageQueries {
+    let ts_language: fresh_languages::tree_sitter::Language = match language {
+        Language::Rust => fresh_languages::tree_sitter_rust::LANGUAGE.into(),
+        Language::Python => fresh_languages::tree_sitter_python::LANGUAGE.into(),
+        Language::JavaScript => fresh_languages::tree_sitter_javascript::LANGUAGE.into(),
+        Language::TypeScript => fresh_languages::tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        Language::Go => fresh_languages::tree_sitter_go::LANGUAGE.into(),
+        Language::C => fresh_languages::tree_sitter_c::LANGUAGE.into(),
+        Language::Cpp => fresh_languages::tree_sitter_cpp::LANGUAGE.into(),
+        Language::Java => fresh_languages::tree_sitter_java::LANGUAGE.into(),
+        Language::Php => fresh_languages::tree_sitter_php::LANGUAGE_PHP.into(),
+        Language::Ruby => fresh_languages::tree_sitter_ruby::LANGUAGE.into(),
+        Language::Bash => fresh_languages::tree_sitter_bash::LANGUAGE.into(),
+        Language::Lua => fresh_languages::tree_sitter_lua::LANGUAGE.into(),
+        Language::Pascal => fresh_languages::tree_sitter_pascal::LANGUAGE.into(),
+        Language::Json => fresh_languages::tree_sitter_json::LANGUAGE.into(),
+        Language::HTML => fresh_languages::tree_sitter_html::LANGUAGE.into(),
+        Language::CSS => fresh_languages::tree_sitter_css::LANGUAGE.into(),
+        Language::CSharp => fresh_languages::tree_sitter_c_sharp::LANGUAGE.into(),
+        Language::Odin => fresh_languages::tree_sitter_odin::LANGUAGE.into(),
+    };
+
+    let mut queries = LanguageQueries {
+        ts_language: None,
+        identifier_query: None,
+        locals_query: None,
+        locals_captures: LocalsCaptures::default(),
+    };
+    if Parser::new().set_language(&ts_language).is_err() {
+        tracing::warn!("Failed to set language for semantic highlighting parser");
+        return queries;
+    }
+
+    // Try to create locals query for scope-aware highlighting
+    if let Some(locals_source) = get_locals_query(language) {
+        match Query::new(&ts_language, locals_source) {
+            Ok(query) => {
+                // Extract capture indices
+                for (i, name) in query.capture_names().iter().enumerate() {
+                    match *name {
+                        "local.scope" => queries.locals_captures.scope = Some(i as u32),
+                        "local.definition" => queries.locals_captures.definition = Some(i as u32),
+                        "local.reference" => queries.locals_captures.reference = Some(i as u32),
+                        _ => {}
+                    }
+                }
+
+                queries.locals_query = Some(query);
+                tracing::debug!(
+                    "Locals query enabled for {:?} (scope-aware highlighting)",
+                    language
+                );
+            }
+            Err(e) => {
+                tracing::debug!(
+                    "Locals query failed for {:?}, falling back to identifier matching: {}",
+                    language,
+                    e
+                );
+            }
+        }
+    }
+
+    // Create identifier query as fallback
+    match Query::new(&ts_language, IDENTIFIER_QUERY) {
+        Ok(query) => {
+            queries.identifier_query = Some(query);
+            tracing::debug!(
+                "Tree-sitter semantic highlighting enabled for {:?}",
+                language
+            );
+        }
+        Err(e) => {
+            tracing::debug!(
+                "Identifier query not supported for {:?}, using text matching: {}",
+                language,
+                e
+            );
+        }
+    }
+
+    queries.ts_language = Some(ts_language);
+    queries
+}
+
 impl ReferenceHighlighter {
     /// Create a new semantic highlighter with default settings
     pub fn new() -> Self {
@@ -179,10 +293,8 @@ impl ReferenceHighlighter {
             highlight_color: DEFAULT_HIGHLIGHT_COLOR,
             min_word_length: 2,
             enabled: true,
+            language: None,
             parser: None,
-            identifier_query: None,
-            locals_query: None,
-            locals_captures: LocalsCaptures::default(),
         }
     }
 
@@ -202,110 +314,42 @@ impl ReferenceHighlighter {
     ///
     /// This enables syntax-aware identifier matching for the given language.
     /// If the language is not supported or parsing fails, falls back to text matching.
+    /// The queries are compiled when first needed.
     pub fn set_language(&mut self, language: &Language) {
-        let ts_language = match language {
-            Language::Rust => fresh_languages::tree_sitter_rust::LANGUAGE.into(),
-            Language::Python => fresh_languages::tree_sitter_python::LANGUAGE.into(),
-            Language::JavaScript => fresh_languages::tree_sitter_javascript::LANGUAGE.into(),
-            Language::TypeScript => {
-                fresh_languages::tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()
-            }
-            Language::Go => fresh_languages::tree_sitter_go::LANGUAGE.into(),
-            Language::C => fresh_languages::tree_sitter_c::LANGUAGE.into(),
-            Language::Cpp => fresh_languages::tree_sitter_cpp::LANGUAGE.into(),
-            Language::Java => fresh_languages::tree_sitter_java::LANGUAGE.into(),
-            Language::Php => fresh_languages::tree_sitter_php::LANGUAGE_PHP.into(),
-            Language::Ruby => fresh_languages::tree_sitter_ruby::LANGUAGE.into(),
-            Language::Bash => fresh_languages::tree_sitter_bash::LANGUAGE.into(),
-            Language::Lua => fresh_languages::tree_sitter_lua::LANGUAGE.into(),
-            Language::Pascal => fresh_languages::tree_sitter_pascal::LANGUAGE.into(),
-            Language::Json => fresh_languages::tree_sitter_json::LANGUAGE.into(),
-            Language::HTML => fresh_languages::tree_sitter_html::LANGUAGE.into(),
-            Language::CSS => fresh_languages::tree_sitter_css::LANGUAGE.into(),
-            Language::CSharp => fresh_languages::tree_sitter_c_sharp::LANGUAGE.into(),
-            Language::Odin => fresh_languages::tree_sitter_odin::LANGUAGE.into(),
-        };
-
-        // Create parser
-        let mut parser = Parser::new();
-        if parser.set_language(&ts_language).is_err() {
-            tracing::warn!("Failed to set language for semantic highlighting parser");
-            self.parser = None;
-            self.identifier_query = None;
-            self.locals_query = None;
-            self.locals_captures = LocalsCaptures::default();
-            return;
-        }
-
-        // Try to create locals query for scope-aware highlighting
-        if let Some(locals_source) = get_locals_query(language) {
-            match Query::new(&ts_language, locals_source) {
-                Ok(query) => {
-                    // Extract capture indices
-                    let mut captures = LocalsCaptures::default();
-                    for (i, name) in query.capture_names().iter().enumerate() {
-                        match *name {
-                            "local.scope" => captures.scope = Some(i as u32),
-                            "local.definition" => captures.definition = Some(i as u32),
-                            "local.reference" => captures.reference = Some(i as u32),
-                            _ => {}
-                        }
-                    }
+        self.language = Some(*language);
+        self.parser = None;
+    }
 
-                    self.locals_query = Some(query);
-                    self.locals_captures = captures;
-                    tracing::debug!(
-                        "Locals query enabled for {:?} (scope-aware highlighting)",
-                        language
-                    );
-                }
-                Err(e) => {
-                    tracing::debug!(
-                        "Locals query failed for {:?}, falling back to identifier matching: {}",
-                        language,
-                        e
-                    );
-                    self.locals_query = None;
-                    self.locals_captures = LocalsCaptures::default();
-                }
-            }
-        } else {
-            self.locals_query = None;
-            self.locals_captures = LocalsCaptures::default();
-        }
+    /// Queries of the language set by `set_language`
+    fn queries(&self) -> Option<Arc<LanguageQueries>> {
+        self.language.as_ref().map(language_queries)
+    }
 
-        // Create identifier query as fallback
-        match Query::new(&ts_language, IDENTIFIER_QUERY) {
-            Ok(query) => {
-                self.parser = Some(parser);
-                self.identifier_query = Some(query);
-                tracing::debug!(
-                    "Tree-sitter semantic highlighting enabled for {:?}",
-                    language
-                );
-            }
-            Err(e) => {
-                tracing::debug!(
-                    "Identifier query not supported for {:?}, using text matching: {}",
-                    language,
-                    e
-                );
-                self.parser = None;
-                self.identifier_query = None;
-            }
+    /// The parser for the current language, created on first use
+    fn parser(&mut self, queries: &LanguageQueries) -> Option<&mut Parser> {
+        if self.parser.is_none() {
+            let mut parser = Parser::new();
+            parser.set_language(queries.ts_language.as_ref()?).ok()?;
+            self.parser = Some(parser);
         }
+        self.parser.as_mut()
     }
 
     /// Check if locals-based (scope-aware) highlighting is available
     pub fn has_locals(&self) -> bool {
-        self.locals_query.is_some()
-            && self.locals_captures.definition.is_some()
-            && self.locals_captures.reference.is_some()
+        self.queries().is_some_and(|queries| {
+            queries.ts_language.is_some()
+                && queries.locals_query.is_some()
+                && queries.locals_captures.definition.is_some()
+                && queries.locals_captures.reference.is_some()
+        })
     }
 
     /// Check if tree-sitter mode is available
     pub fn has_tree_sitter(&self) -> bool {
-        self.parser.is_some() && self.identifier_query.is_some()
+        self.queries().is_some_and(|queries| {
+            queries.ts_language.is_some() && queries.identifier_query.is_some()
+        })
     }
 
     /// Get highlights for word occurrences in the viewport
@@ -376,24 +420,27 @@ impl ReferenceHighlighter {
         viewport_end: usize,
         context_bytes: usize,
     ) -> Vec<HighlightSpan> {
-        let parser = match &mut self.parser {
-            Some(p) => p,
-            None => return Vec::new(),
+        let Some(queries) = self.queries() else {
+            return Vec::new();
         };
-        let query = match &self.locals_query {
+        let query = match &queries.locals_query {
             Some(q) => q,
             None => return Vec::new(),
         };
 
-        let def_idx = match self.locals_captures.definition {
+        let def_idx = match queries.locals_captures.definition {
             Some(i) => i,
             None => return Vec::new(),
         };
-        let ref_idx = match self.locals_captures.reference {
+        let ref_idx = match queries.locals_captures.reference {
             Some(i) => i,
             None => return Vec::new(),
         };
-        let scope_idx = self.locals_captures.scope;
+        let scope_idx = queries.locals_captures.scope;
+        let parser = match self.parser(&queries) {
+            Some(p) => p,
+            None => return Vec::new(),
+        };
 
         // Parse the entire visible region plus context
         let parse_start = viewport_start.saturating_sub(context_bytes);
@@ -588,14 +635,17 @@ impl ReferenceHighlighter {
         viewport_end: usize,
         context_bytes: usize,
     ) -> Vec<HighlightSpan> {
-        let parser = match &mut self.parser {
-            Some(p) => p,
-            None => return Vec::new(),
+        let Some(queries) = self.queries() else {
+            return Vec::new();
         };
-        let query = match &self.identifier_query {
+        let query = match &queries.identifier_query {
             Some(q) => q,
             None => return Vec::new(),
         };
+        let parser = match self.parser(&queries) {
+            Some(p) => p,
+            None => return Vec::new(),
+        };
 
         // Get text to parse - use context around viewport for better parsing
         let parse_start = viewport_start.saturating_sub(context_bytes);
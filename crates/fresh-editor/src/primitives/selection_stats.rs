@@ -0,0 +1,79 @@
+//! Size statistics for selected text.
+//!
+//! Used by the status bar's live selection indicator and the "Selection
+//! Info" command. With multiple cursors, each selection is added in turn and
+//! the totals are aggregated.
+
+/// Aggregate size of one or more selections
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SelectionStats {
+    /// Number of non-empty selections
+    pub selections: usize,
+    /// Total size in bytes
+    pub bytes: usize,
+    /// Total number of characters
+    pub chars: usize,
+    /// Total number of whitespace-separated words
+    pub words: usize,
+    /// Total number of lines spanned
+    pub lines: usize,
+}
+
+impl SelectionStats {
+    /// Add one selection's text. Empty text is ignored.
+    pub fn add_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.selections += 1;
+        self.bytes += text.len();
+        self.chars += text.chars().count();
+        self.words += text.split_whitespace().count();
+        self.lines += line_span(text);
+    }
+}
+
+/// Number of lines `text` spans. A trailing newline ends the last line
+/// rather than starting a new one, so selecting two whole lines counts two.
+fn line_span(text: &str) -> usize {
+    let newlines = text.matches('\n').count();
+    if newlines > 0 && text.ends_with('\n') {
+        newlines
+    } else {
+        newlines + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_line_selection() {
+        let mut stats = SelectionStats::default();
+        stats.add_text("hello wörld");
+        assert_eq!(
+            stats,
+            SelectionStats {
+                selections: 1,
+                bytes: 12,
+                chars: 11,
+                words: 2,
+                lines: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_multi_line_and_aggregate() {
+        let mut stats = SelectionStats::default();
+        stats.add_text("fn main() {\n    body\n}\n");
+        assert_eq!(stats.lines, 3);
+        stats.add_text("one\ntwo");
+        stats.add_text("");
+        assert_eq!(stats.selections, 2);
+        assert_eq!(stats.lines, 5);
+        assert_eq!(stats.words, 7);
+        assert_eq!(stats.bytes, 23 + 7);
+    }
+}
@@ -1,6 +1,7 @@
 //! Word boundary detection and navigation helpers
 
 use crate::model::buffer::Buffer;
+use std::ops::Range;
 
 /// Check if a byte is a word character (alphanumeric or underscore)
 pub fn is_word_char(byte: u8) -> bool {
@@ -201,6 +202,69 @@ pub fn find_word_end(buffer: &Buffer, pos: usize) -> usize {
 
 use crate::primitives::grapheme::{next_grapheme_boundary, prev_grapheme_boundary};
 
+/// Which characters make up words, and whether words split into sub-words.
+///
+/// Resolved per buffer from `editor.word_granularity` and the language's
+/// `word_characters`. The default treats letters, digits and `_` as word
+/// characters and never splits identifiers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordRules {
+    /// Also stop at camelCase humps, separators (`_` and the extra word
+    /// characters) and letter/digit changes inside identifiers
+    pub subword: bool,
+    /// Characters besides letters, digits and `_` that belong to words
+    /// (e.g. `-` for kebab-case identifiers in CSS and Lisps)
+    pub extra_chars: Vec<char>,
+}
+
+impl WordRules {
+    /// Check if a character is part of a word under these rules
+    pub fn is_word_char(&self, c: char) -> bool {
+        c.is_alphanumeric() || c == '_' || self.extra_chars.contains(&c)
+    }
+
+    /// Word characters that join sub-words rather than being part of them
+    fn is_separator(&self, c: char) -> bool {
+        c == '_' || self.extra_chars.contains(&c)
+    }
+}
+
+/// Whether a sub-word ends between `prev` and `next`, two word characters.
+/// `after` is the character following `next`, needed to split acronyms
+/// (`HTMLParser` → `HTML` + `Parser`).
+///
+/// Separators lead the sub-word that follows them, so `my_var` splits into
+/// `my` and `_var`: deleting a word backward removes `_var` in one go.
+fn is_subword_boundary(prev: char, next: char, after: Option<char>, rules: &WordRules) -> bool {
+    let prev_sep = rules.is_separator(prev);
+    let next_sep = rules.is_separator(next);
+    if prev_sep || next_sep {
+        return next_sep && !prev_sep;
+    }
+    if prev.is_numeric() != next.is_numeric() {
+        return true;
+    }
+    if next.is_uppercase() {
+        return !prev.is_uppercase() || after.is_some_and(char::is_lowercase);
+    }
+    false
+}
+
+/// Whether byte index `idx` of `text` falls on a sub-word boundary. `tail` is
+/// the character just past the end of `text`, if the caller has it.
+fn splits_subword(text: &str, idx: usize, tail: Option<char>, rules: &WordRules) -> bool {
+    let Some(prev) = text[..idx].chars().next_back() else {
+        return false;
+    };
+    let mut rest = text[idx..].chars().chain(tail);
+    let Some(next) = rest.next() else {
+        return false;
+    };
+    rules.is_word_char(prev)
+        && rules.is_word_char(next)
+        && is_subword_boundary(prev, next, rest.next(), rules)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum CharClass {
     Word,
@@ -208,8 +272,8 @@ enum CharClass {
     Punctuation,
 }
 
-fn get_grapheme_class(g: &str) -> CharClass {
-    if g.chars().any(|c| c.is_alphanumeric() || c == '_') {
+fn get_grapheme_class(g: &str, rules: &WordRules) -> CharClass {
+    if g.chars().any(|c| rules.is_word_char(c)) {
         CharClass::Word
     } else if g.chars().all(|c| c.is_whitespace()) {
         CharClass::Whitespace
@@ -218,8 +282,55 @@ fn get_grapheme_class(g: &str) -> CharClass {
     }
 }
 
+/// Whether a run of `class` graphemes stops at `idx` because a sub-word ends there
+fn ends_run(
+    text: &str,
+    idx: usize,
+    tail: Option<char>,
+    class: CharClass,
+    rules: &WordRules,
+) -> bool {
+    class == CharClass::Word && rules.subword && splits_subword(text, idx, tail, rules)
+}
+
+/// End of the run of `class` graphemes starting at `from`
+fn run_end(text: &str, from: usize, class: CharClass, rules: &WordRules) -> usize {
+    let mut idx = from;
+    while idx < text.len() {
+        let next = next_grapheme_boundary(text, idx);
+        if get_grapheme_class(&text[idx..next], rules) != class
+            || (idx > from && ends_run(text, idx, None, class, rules))
+        {
+            break;
+        }
+        idx = next;
+    }
+    idx
+}
+
+/// Start of the run of `class` graphemes ending at `to`
+fn run_start(
+    text: &str,
+    to: usize,
+    tail: Option<char>,
+    class: CharClass,
+    rules: &WordRules,
+) -> usize {
+    let mut idx = to;
+    while idx > 0 {
+        let prev = prev_grapheme_boundary(text, idx);
+        if get_grapheme_class(&text[prev..idx], rules) != class
+            || (idx < to && ends_run(text, idx, tail, class, rules))
+        {
+            break;
+        }
+        idx = prev;
+    }
+    idx
+}
+
 /// Find the start of the word to the left of the given position
-pub fn find_word_start_left(buffer: &Buffer, pos: usize) -> usize {
+pub fn find_word_start_left(buffer: &Buffer, pos: usize, rules: &WordRules) -> usize {
     if pos == 0 {
         return 0;
     }
@@ -236,19 +347,16 @@ pub fn find_word_start_left(buffer: &Buffer, pos: usize) -> usize {
     // Convert to string safely (replacing invalid sequences which might happen at the start boundary)
     let text = String::from_utf8_lossy(&bytes);
 
-    // The end of the string corresponds to `pos`
-    let mut current_idx = text.len();
+    // The character after `pos` decides whether an acronym ends right before it
+    let tail = if rules.subword {
+        let after = buffer.slice_bytes(actual_pos..(actual_pos + 4).min(buf_len));
+        String::from_utf8_lossy(&after).chars().next()
+    } else {
+        None
+    };
 
     // 1. Consume whitespace to the left
-    while current_idx > 0 {
-        let prev = prev_grapheme_boundary(&text, current_idx);
-        let g = &text[prev..current_idx];
-        if get_grapheme_class(g) == CharClass::Whitespace {
-            current_idx = prev;
-        } else {
-            break;
-        }
-    }
+    let mut current_idx = run_start(&text, text.len(), tail, CharClass::Whitespace, rules);
 
     if current_idx == 0 {
         // We consumed everything up to the start of our chunk
@@ -262,18 +370,10 @@ pub fn find_word_start_left(buffer: &Buffer, pos: usize) -> usize {
 
     // 2. Identify class of the token we hit
     let prev = prev_grapheme_boundary(&text, current_idx);
-    let target_class = get_grapheme_class(&text[prev..current_idx]);
-
-    // 3. Consume all characters of the same class
-    while current_idx > 0 {
-        let prev = prev_grapheme_boundary(&text, current_idx);
-        let g = &text[prev..current_idx];
-        if get_grapheme_class(g) == target_class {
-            current_idx = prev;
-        } else {
-            break;
-        }
-    }
+    let target_class = get_grapheme_class(&text[prev..current_idx], rules);
+
+    // 3. Consume all characters of the same class (up to a sub-word boundary)
+    current_idx = run_start(&text, current_idx, tail, target_class, rules);
 
     let delta = text.len() - current_idx;
     actual_pos.saturating_sub(delta)
@@ -285,7 +385,7 @@ pub fn find_word_start_left(buffer: &Buffer, pos: usize) -> usize {
 /// - From within a word: jump to the end of the current word
 /// - From whitespace: skip whitespace, then jump to end of next token (word or punctuation)
 /// - From punctuation: consume all punctuation, then stop
-pub fn find_word_end_right(buffer: &Buffer, pos: usize) -> usize {
+pub fn find_word_end_right(buffer: &Buffer, pos: usize, rules: &WordRules) -> usize {
     let buf_len = buffer.len();
     if pos >= buf_len {
         return buf_len;
@@ -297,73 +397,37 @@ pub fn find_word_end_right(buffer: &Buffer, pos: usize) -> usize {
     let bytes = buffer.slice_bytes(start..end);
     let text = String::from_utf8_lossy(&bytes);
 
-    let mut current_idx = 0;
-    if current_idx >= text.len() {
+    if text.is_empty() {
         return start;
     }
 
     // Look at the grapheme at current position
-    let next_bound = next_grapheme_boundary(&text, current_idx);
-    let start_class = get_grapheme_class(&text[current_idx..next_bound]);
-
-    match start_class {
-        CharClass::Word => {
-            // In a word: jump to end of current word
-            while current_idx < text.len() {
-                let next = next_grapheme_boundary(&text, current_idx);
-                let g = &text[current_idx..next];
-                if get_grapheme_class(g) == CharClass::Word {
-                    current_idx = next;
-                } else {
-                    break;
-                }
-            }
-        }
+    let next_bound = next_grapheme_boundary(&text, 0);
+    let start_class = get_grapheme_class(&text[..next_bound], rules);
+
+    let current_idx = match start_class {
+        // In a word: jump to end of current word
+        // On punctuation: consume all punctuation, then stop
+        CharClass::Word | CharClass::Punctuation => run_end(&text, 0, start_class, rules),
         CharClass::Whitespace => {
             // On whitespace: skip whitespace, then consume next token
-            while current_idx < text.len() {
-                let next = next_grapheme_boundary(&text, current_idx);
-                let g = &text[current_idx..next];
-                if get_grapheme_class(g) == CharClass::Whitespace {
-                    current_idx = next;
-                } else {
-                    break;
-                }
-            }
-            // Consume the token we landed on (word or punctuation)
-            if current_idx < text.len() {
-                let next = next_grapheme_boundary(&text, current_idx);
-                let landed_class = get_grapheme_class(&text[current_idx..next]);
-                while current_idx < text.len() {
-                    let next = next_grapheme_boundary(&text, current_idx);
-                    let g = &text[current_idx..next];
-                    if get_grapheme_class(g) == landed_class {
-                        current_idx = next;
-                    } else {
-                        break;
-                    }
-                }
-            }
-        }
-        CharClass::Punctuation => {
-            // On punctuation: consume all punctuation, then stop
-            while current_idx < text.len() {
-                let next = next_grapheme_boundary(&text, current_idx);
-                let g = &text[current_idx..next];
-                if get_grapheme_class(g) == CharClass::Punctuation {
-                    current_idx = next;
-                } else {
-                    break;
-                }
+            let landed = run_end(&text, 0, CharClass::Whitespace, rules);
+            if landed < text.len() {
+                // Consume the token we landed on (word or punctuation)
+                let next = next_grapheme_boundary(&text, landed);
+                let landed_class = get_grapheme_class(&text[landed..next], rules);
+                run_end(&text, landed, landed_class, rules)
+            } else {
+                landed
             }
         }
-    }
+    };
 
     start + current_idx
 }
 
 /// Find the start of the word to the right of the given position
-pub fn find_word_start_right(buffer: &Buffer, pos: usize) -> usize {
+pub fn find_word_start_right(buffer: &Buffer, pos: usize, rules: &WordRules) -> usize {
     let buf_len = buffer.len();
     if pos >= buf_len {
         return buf_len;
@@ -375,52 +439,109 @@ pub fn find_word_start_right(buffer: &Buffer, pos: usize) -> usize {
     let bytes = buffer.slice_bytes(start..end);
     let text = String::from_utf8_lossy(&bytes);
 
-    let mut current_idx = 0;
-    if current_idx >= text.len() {
+    if text.is_empty() {
         return start;
     }
 
     // Look at the grapheme at current position
-    let next_bound = next_grapheme_boundary(&text, current_idx);
-    let start_class = get_grapheme_class(&text[current_idx..next_bound]);
+    let next_bound = next_grapheme_boundary(&text, 0);
+    let start_class = get_grapheme_class(&text[..next_bound], rules);
 
     // 1. If starting on whitespace, just consume it and stop
     if start_class == CharClass::Whitespace {
-        while current_idx < text.len() {
-            let next = next_grapheme_boundary(&text, current_idx);
-            let g = &text[current_idx..next];
-            if get_grapheme_class(g) == CharClass::Whitespace {
-                current_idx = next;
-            } else {
-                break;
-            }
-        }
-        return start + current_idx;
+        return start + run_end(&text, 0, CharClass::Whitespace, rules);
     }
 
     // 2. Otherwise (Word or Punctuation), consume all characters of same class
-    while current_idx < text.len() {
-        let next = next_grapheme_boundary(&text, current_idx);
-        let g = &text[current_idx..next];
-        if get_grapheme_class(g) == start_class {
-            current_idx = next;
-        } else {
-            break;
+    let current_idx = run_end(&text, 0, start_class, rules);
+
+    // 3. Then consume subsequent whitespace to land at start of next token
+    start + run_end(&text, current_idx, CharClass::Whitespace, rules)
+}
+
+/// Find the word under the cursor for word selection (double-click, Ctrl+W).
+///
+/// A position right after a word selects that word. In sub-word mode only the
+/// sub-word is selected, without its leading separators. Returns an empty
+/// range at `pos` when there's no word there.
+pub fn find_word_range(buffer: &Buffer, pos: usize, rules: &WordRules) -> Range<usize> {
+    if *rules == WordRules::default() {
+        let word_start = find_word_start(buffer, pos);
+        return word_start..find_word_end(buffer, word_start);
+    }
+
+    let buf_len = buffer.len();
+    let pos = pos.min(buf_len);
+
+    // Only read a small window around the position for efficiency, starting
+    // on a character boundary so offsets into the text stay exact
+    let start = pos.saturating_sub(1000);
+    let end = (pos + 1000).min(buf_len);
+    let bytes = buffer.slice_bytes(start..end);
+    let skip = bytes.iter().take_while(|&&b| b & 0xC0 == 0x80).count();
+    let start = start + skip;
+    let text = String::from_utf8_lossy(&bytes[skip..]);
+    let offset = pos - start;
+
+    let mut word_start = offset;
+    if !text[offset..]
+        .chars()
+        .next()
+        .is_some_and(|c| rules.is_word_char(c))
+    {
+        match text[..offset].chars().next_back() {
+            Some(c) if rules.is_word_char(c) => word_start -= c.len_utf8(),
+            _ => return pos..pos,
         }
     }
 
-    // 3. Then consume subsequent whitespace to land at start of next token
-    while current_idx < text.len() {
-        let next = next_grapheme_boundary(&text, current_idx);
-        let g = &text[current_idx..next];
-        if get_grapheme_class(g) == CharClass::Whitespace {
-            current_idx = next;
-        } else {
+    let ends_word = |idx: usize| rules.subword && splits_subword(&text, idx, None, rules);
+    let mut word_end = word_start;
+    while let Some(c) = text[..word_start].chars().next_back() {
+        if !rules.is_word_char(c) || ends_word(word_start) {
+            break;
+        }
+        word_start -= c.len_utf8();
+    }
+    while let Some(c) = text[word_end..].chars().next() {
+        if !rules.is_word_char(c) || (word_end > word_start && ends_word(word_end)) {
             break;
         }
+        word_end += c.len_utf8();
     }
 
-    start + current_idx
+    if rules.subword {
+        let separators: usize = text[word_start..word_end]
+            .chars()
+            .take_while(|&c| rules.is_separator(c))
+            .map(char::len_utf8)
+            .sum();
+        if word_start + separators < word_end {
+            word_start += separators;
+        }
+    }
+
+    start + word_start..start + word_end
+}
+
+/// Check that a search match `text[start..end]` doesn't cut through a word,
+/// for whole-word search. In sub-word mode a match may also begin or end at
+/// a camelCase hump, separator or letter/digit change.
+pub fn is_whole_word_match(text: &str, start: usize, end: usize, rules: &WordRules) -> bool {
+    let cuts_word = |idx: usize| {
+        let (Some(prev), Some(next)) =
+            (text[..idx].chars().next_back(), text[idx..].chars().next())
+        else {
+            return false;
+        };
+        rules.is_word_char(prev)
+            && rules.is_word_char(next)
+            && !(rules.subword
+                && (rules.is_separator(prev)
+                    || rules.is_separator(next)
+                    || splits_subword(text, idx, None, rules)))
+    };
+    !cuts_word(start) && !cuts_word(end)
 }
 
 #[cfg(test)]
@@ -459,15 +580,148 @@ mod tests {
     #[test]
     fn test_find_word_start_left() {
         let buffer = Buffer::from_str_test("hello world test");
-        assert_eq!(find_word_start_left(&buffer, 6), 0); // From "world" to "hello"
-        assert_eq!(find_word_start_left(&buffer, 12), 6); // From "test" to "world"
+        assert_eq!(find_word_start_left(&buffer, 6, &WordRules::default()), 0); // From "world" to "hello"
+        assert_eq!(find_word_start_left(&buffer, 12, &WordRules::default()), 6);
+        // From "test" to "world"
     }
 
     #[test]
     fn test_find_word_start_right() {
         let buffer = Buffer::from_str_test("hello world test");
-        assert_eq!(find_word_start_right(&buffer, 0), 6); // From "hello" to "world"
-        assert_eq!(find_word_start_right(&buffer, 6), 12); // From "world" to "test"
+        assert_eq!(find_word_start_right(&buffer, 0, &WordRules::default()), 6); // From "hello" to "world"
+        assert_eq!(find_word_start_right(&buffer, 6, &WordRules::default()), 12);
+        // From "world" to "test"
+    }
+
+    fn subword() -> WordRules {
+        WordRules {
+            subword: true,
+            extra_chars: Vec::new(),
+        }
+    }
+
+    fn kebab(subword: bool) -> WordRules {
+        WordRules {
+            subword,
+            extra_chars: vec!['-'],
+        }
+    }
+
+    /// Positions visited by repeatedly moving with `step` from `from`
+    fn stops(text: &str, from: usize, step: impl Fn(&Buffer, usize) -> usize) -> Vec<usize> {
+        let buffer = Buffer::from_str_test(text);
+        let mut stops = Vec::new();
+        let mut pos = from;
+        loop {
+            let next = step(&buffer, pos);
+            if next == pos {
+                return stops;
+            }
+            stops.push(next);
+            pos = next;
+        }
+    }
+
+    #[test]
+    fn test_subword_camel_case() {
+        let text = "myVariableName parseHTMLResponse";
+        let right = |b: &Buffer, p| find_word_end_right(b, p, &subword());
+        assert_eq!(stops(text, 0, right), vec![2, 10, 14, 20, 24, 32]);
+        let left = |b: &Buffer, p| find_word_start_left(b, p, &subword());
+        assert_eq!(stops(text, text.len(), left), vec![24, 20, 15, 10, 2, 0]);
+
+        // Word granularity keeps identifiers whole
+        let right = |b: &Buffer, p| find_word_end_right(b, p, &WordRules::default());
+        assert_eq!(stops(text, 0, right), vec![14, 32]);
+    }
+
+    #[test]
+    fn test_subword_snake_and_screaming_snake() {
+        // Separators lead the sub-word after them
+        let text = "my_var_name MAX_BUFFER_SIZE";
+        let right = |b: &Buffer, p| find_word_start_right(b, p, &subword());
+        assert_eq!(stops(text, 0, right), vec![2, 6, 12, 15, 22, 27]);
+        let left = |b: &Buffer, p| find_word_start_left(b, p, &subword());
+        assert_eq!(stops(text, text.len(), left), vec![22, 15, 12, 6, 2, 0]);
+    }
+
+    #[test]
+    fn test_subword_letter_digit_boundaries() {
+        let text = "utf8Decoder";
+        let right = |b: &Buffer, p| find_word_end_right(b, p, &subword());
+        assert_eq!(stops(text, 0, right), vec![3, 4, 11]);
+    }
+
+    #[test]
+    fn test_subword_left_splits_acronym_at_cursor() {
+        // The character after the cursor is needed to see `HTML|Parser`
+        let buffer = Buffer::from_str_test("HTMLParser");
+        assert_eq!(find_word_start_left(&buffer, 5, &subword()), 4);
+    }
+
+    #[test]
+    fn test_word_characters_kebab_case() {
+        let text = "background-color: red";
+        let right = |b: &Buffer, p| find_word_end_right(b, p, &kebab(false));
+        assert_eq!(stops(text, 0, right), vec![16, 17, 21]);
+        let right = |b: &Buffer, p| find_word_end_right(b, p, &kebab(true));
+        assert_eq!(stops(text, 0, right), vec![10, 16, 17, 21]);
+
+        // Without the extra character `-` is punctuation
+        let right = |b: &Buffer, p| find_word_end_right(b, p, &WordRules::default());
+        assert_eq!(stops(text, 0, right), vec![10, 11, 16, 17, 21]);
+    }
+
+    #[test]
+    fn test_subword_unicode_identifiers() {
+        // "naïveÉtat" splits at the hump; "größeMaß" keeps ß inside words;
+        // uncased scripts only split at case and digit changes
+        let text = "naïveÉtat größeMaß 变量Name";
+        let right = |b: &Buffer, p| find_word_end_right(b, p, &subword());
+        let naive = "naïve".len();
+        let etat = naive + "État".len();
+        let grosse = etat + 1 + "größe".len();
+        let mass = grosse + "Maß".len();
+        let bianliang = mass + 1 + "变量".len();
+        assert_eq!(
+            stops(text, 0, right),
+            vec![naive, etat, grosse, mass, bianliang, text.len()]
+        );
+    }
+
+    #[test]
+    fn test_find_word_range() {
+        let buffer = Buffer::from_str_test("let myVariableName = MAX_SIZE;");
+        assert_eq!(find_word_range(&buffer, 8, &WordRules::default()), 4..18);
+        assert_eq!(find_word_range(&buffer, 8, &subword()), 6..14);
+        // Separators are left out of the selected sub-word
+        assert_eq!(find_word_range(&buffer, 26, &subword()), 25..29);
+        assert_eq!(find_word_range(&buffer, 24, &subword()), 25..29);
+        // Right after a word selects it; whitespace selects nothing
+        assert_eq!(find_word_range(&buffer, 18, &subword()), 14..18);
+        assert_eq!(find_word_range(&buffer, 19, &subword()), 19..19);
+
+        let buffer = Buffer::from_str_test("a { margin-top: 0 }");
+        assert_eq!(find_word_range(&buffer, 5, &kebab(false)), 4..14);
+        assert_eq!(find_word_range(&buffer, 12, &kebab(true)), 11..14);
+    }
+
+    #[test]
+    fn test_is_whole_word_match() {
+        let text = "myVariableName my_var background-color";
+        let find = |needle: &str| text.find(needle).unwrap();
+        let whole = |needle: &str, rules: &WordRules| {
+            let start = find(needle);
+            is_whole_word_match(text, start, start + needle.len(), rules)
+        };
+        assert!(!whole("Variable", &WordRules::default()));
+        assert!(whole("Variable", &subword()));
+        assert!(!whole("Var", &subword()));
+        assert!(whole("var", &subword()));
+        assert!(whole("myVariableName", &subword()));
+        assert!(whole("color", &WordRules::default()));
+        assert!(!whole("color", &kebab(false)));
+        assert!(whole("color", &kebab(true)));
     }
 
     // ========================================================================
@@ -442,6 +442,7 @@ impl EditorServer {
                 .unwrap_or_else(|| "session".to_string())
         });
         editor.set_session_name(Some(session_display_name));
+        editor.prompt_workspace_trust_if_unknown();
 
         self.terminal = Some(terminal);
         self.editor = Some(editor);
@@ -0,0 +1,351 @@
+//! Headless mode driven over a control socket
+//!
+//! `fresh --headless --control-socket /tmp/fresh.sock file.txt` runs the
+//! editor without a terminal: frames are rendered into a [`CaptureBackend`]
+//! of a fixed virtual size and thrown away, and scripts drive the editor by
+//! sending one JSON command per line over the socket. Every command gets one
+//! JSON line back, e.g.
+//!
+//! ```text
+//! {"type":"send_key","key":"s","modifiers":["ctrl"]}
+//! {"ok":true}
+//! {"type":"get_buffer_text"}
+//! {"ok":true,"text":"hello\n"}
+//! ```
+
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::Terminal;
+use serde::{Deserialize, Serialize};
+
+use crate::app::Editor;
+use crate::config::Config;
+use crate::config_io::DirectoryContext;
+use crate::input::keybindings::KeybindingResolver;
+use crate::model::filesystem::{FileSystem, StdFileSystem};
+use crate::server::capture_backend::CaptureBackend;
+use crate::server::ipc::{ControlSocketListener, StreamWrapper};
+use crate::server::protocol::TermSize;
+use crate::view::color_support::ColorCapability;
+
+/// A command read from the control socket
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HeadlessCommand {
+    /// Open a file and make it the active buffer
+    OpenFile { path: String },
+    /// Press a key, named as in keybindings ("Enter", "a", "F5")
+    SendKey {
+        key: String,
+        #[serde(default)]
+        modifiers: Vec<String>,
+    },
+    /// Press the key of each character of `text`
+    TypeText { text: String },
+    /// The full text of the active buffer
+    GetBufferText,
+    /// The rendered screen, one line per row
+    GetScreen,
+    /// Change the virtual terminal size
+    Resize { cols: u16, rows: u16 },
+    /// Exit without saving
+    Quit,
+}
+
+/// The reply to a [`HeadlessCommand`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeadlessResponse {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HeadlessResponse {
+    fn ok(text: Option<String>) -> Self {
+        Self {
+            ok: true,
+            text,
+            error: None,
+        }
+    }
+
+    fn error(error: impl ToString) -> Self {
+        Self {
+            ok: false,
+            text: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// Configuration for headless mode
+#[derive(Debug, Clone)]
+pub struct HeadlessConfig {
+    /// Socket (or named pipe) to accept commands on
+    pub control_socket: PathBuf,
+    pub working_dir: PathBuf,
+    /// Virtual terminal size
+    pub term_size: TermSize,
+    pub editor_config: Config,
+    pub dir_context: DirectoryContext,
+    pub plugins_enabled: bool,
+}
+
+/// A connected script and the part of a line it has sent so far
+struct ControlClient {
+    stream: StreamWrapper,
+    pending: Vec<u8>,
+}
+
+/// Editor running without a terminal, driven over a control socket
+pub struct HeadlessEditor {
+    editor: Editor,
+    terminal: Terminal<CaptureBackend>,
+    listener: ControlSocketListener,
+    clients: Vec<ControlClient>,
+    /// The last frame rendered, for `get_screen`
+    screen: Buffer,
+    quit_requested: bool,
+}
+
+impl HeadlessEditor {
+    /// Create the editor and start listening. Files queued on
+    /// [`Self::editor_mut`] are opened before the first command runs.
+    pub fn new(config: HeadlessConfig) -> io::Result<Self> {
+        let TermSize { cols, rows } = config.term_size;
+        let terminal = Terminal::new(CaptureBackend::new(cols, rows))
+            .map_err(|e| io::Error::other(format!("Failed to create terminal: {}", e)))?;
+        let filesystem: Arc<dyn FileSystem + Send + Sync> = Arc::new(StdFileSystem);
+
+        let editor = Editor::with_working_dir(
+            config.editor_config,
+            cols,
+            rows,
+            Some(config.working_dir),
+            config.dir_context,
+            config.plugins_enabled,
+            ColorCapability::TrueColor,
+            filesystem,
+        )
+        .map_err(|e| io::Error::other(format!("Failed to create editor: {}", e)))?;
+
+        let listener = ControlSocketListener::bind(&config.control_socket)?;
+        tracing::info!("Headless editor listening on {:?}", config.control_socket);
+
+        let mut headless = Self {
+            editor,
+            terminal,
+            listener,
+            clients: Vec::new(),
+            screen: Buffer::empty(ratatui::layout::Rect::new(0, 0, cols, rows)),
+            quit_requested: false,
+        };
+        headless.render()?;
+        Ok(headless)
+    }
+
+    /// The editor, to queue files on before [`Self::run`]
+    pub fn editor_mut(&mut self) -> &mut Editor {
+        &mut self.editor
+    }
+
+    /// Serve commands until `quit` or the editor quits on its own
+    pub fn run(&mut self) -> io::Result<()> {
+        if self.editor.process_pending_file_opens() {
+            self.render()?;
+        }
+        while !self.quit_requested && !self.editor.should_quit() {
+            if let Some(stream) = self.listener.accept()? {
+                self.clients.push(ControlClient {
+                    stream,
+                    pending: Vec::new(),
+                });
+            }
+
+            let mut busy = self.serve_clients()?;
+            busy |= self.editor.process_async_messages();
+            busy |= self.editor.process_pending_file_opens();
+            if busy {
+                self.render()?;
+            } else {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the complete lines each client has sent. Returns true if any ran.
+    fn serve_clients(&mut self) -> io::Result<bool> {
+        let mut ran = false;
+        let mut index = 0;
+        while index < self.clients.len() {
+            let mut buf = [0u8; 4096];
+            let closed = match self.clients[index].stream.try_read(&mut buf) {
+                Ok(0) => true,
+                Ok(n) => {
+                    self.clients[index].pending.extend_from_slice(&buf[..n]);
+                    false
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => false,
+                Err(e) => {
+                    tracing::debug!("Control socket read error: {}", e);
+                    true
+                }
+            };
+
+            while let Some(end) = self.clients[index].pending.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = self.clients[index].pending.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line);
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let response = match serde_json::from_str::<HeadlessCommand>(&line) {
+                    Ok(command) => self.execute(command),
+                    Err(e) => HeadlessResponse::error(format!("invalid command: {}", e)),
+                };
+                let mut json = serde_json::to_string(&response).map_err(io::Error::other)?;
+                json.push('\n');
+                let stream = &self.clients[index].stream;
+                if let Err(e) = stream
+                    .write_all(json.as_bytes())
+                    .and_then(|_| stream.flush())
+                {
+                    tracing::debug!("Control socket write error: {}", e);
+                }
+                ran = true;
+            }
+
+            if closed {
+                self.clients.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        Ok(ran)
+    }
+
+    /// Run one command against the editor
+    pub fn execute(&mut self, command: HeadlessCommand) -> HeadlessResponse {
+        let result = match command {
+            HeadlessCommand::OpenFile { path } => self
+                .editor
+                .open_file(std::path::Path::new(&path))
+                .map(|_| None)
+                .map_err(|e| e.to_string()),
+            HeadlessCommand::SendKey { key, modifiers } => {
+                // Single characters keep their case; names go through the
+                // keybinding parser
+                let mut chars = key.chars();
+                let code = match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some(KeyCode::Char(c)),
+                    _ => KeybindingResolver::parse_key_public(&key),
+                };
+                match code {
+                    Some(code) => self
+                        .press(code, KeybindingResolver::parse_modifiers_public(&modifiers))
+                        .map(|_| None),
+                    None => Err(format!("unknown key \"{}\"", key)),
+                }
+            }
+            HeadlessCommand::TypeText { text } => text
+                .chars()
+                .try_for_each(|c| self.press(KeyCode::Char(c), KeyModifiers::NONE))
+                .map(|_| None),
+            HeadlessCommand::GetBufferText => Ok(Some(
+                self.editor
+                    .get_buffer_content(self.editor.active_buffer())
+                    .unwrap_or_default(),
+            )),
+            HeadlessCommand::GetScreen => self
+                .render()
+                .map(|_| Some(screen_text(&self.screen)))
+                .map_err(|e| e.to_string()),
+            HeadlessCommand::Resize { cols, rows } if cols == 0 || rows == 0 => {
+                Err(format!("invalid size {}x{}", cols, rows))
+            }
+            HeadlessCommand::Resize { cols, rows } => {
+                self.terminal.backend_mut().resize(cols, rows);
+                self.editor.resize(cols, rows);
+                Ok(None)
+            }
+            HeadlessCommand::Quit => {
+                self.quit_requested = true;
+                Ok(None)
+            }
+        };
+        match result {
+            Ok(text) => HeadlessResponse::ok(text),
+            Err(error) => HeadlessResponse::error(error),
+        }
+    }
+
+    fn press(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Result<(), String> {
+        self.editor
+            .handle_key(code, modifiers)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Render a frame into the virtual terminal and keep it for `get_screen`
+    fn render(&mut self) -> io::Result<()> {
+        let editor = &mut self.editor;
+        let frame = self
+            .terminal
+            .draw(|frame| editor.render(frame))
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        self.screen = frame.buffer.clone();
+        // Nobody is watching the escape sequences
+        self.terminal.backend_mut().clear_buffer();
+        Ok(())
+    }
+}
+
+/// The text of a rendered frame, one line per row
+fn screen_text(buffer: &Buffer) -> String {
+    buffer
+        .content
+        .chunks(usize::from(buffer.area.width.max(1)))
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands_use_snake_case_tags() {
+        let command: HeadlessCommand =
+            serde_json::from_str(r#"{"type":"send_key","key":"Enter"}"#).unwrap();
+        assert_eq!(
+            command,
+            HeadlessCommand::SendKey {
+                key: "Enter".to_string(),
+                modifiers: vec![],
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&HeadlessCommand::GetBufferText).unwrap(),
+            r#"{"type":"get_buffer_text"}"#
+        );
+    }
+
+    #[test]
+    fn test_response_omits_empty_fields() {
+        assert_eq!(
+            serde_json::to_string(&HeadlessResponse::ok(None)).unwrap(),
+            r#"{"ok":true}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&HeadlessResponse::error("boom")).unwrap(),
+            r#"{"ok":false,"error":"boom"}"#
+        );
+    }
+}
@@ -258,6 +258,60 @@ impl Drop for ServerListener {
     }
 }
 
+/// Listener for a single socket at a path chosen by the user, used by
+/// `--headless --control-socket` where scripts send one JSON command per line
+pub struct ControlSocketListener {
+    listener: LocalListener,
+    path: PathBuf,
+}
+
+impl ControlSocketListener {
+    /// Listen at `path`, replacing a stale socket file left there
+    pub fn bind(path: &Path) -> io::Result<Self> {
+        // Named pipes on Windows leave no file behind
+        if cfg!(unix) && path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        let listener = ListenerOptions::new()
+            .name(platform::socket_name_for_path(path)?)
+            .create_sync()
+            .map_err(|e| io::Error::new(io::ErrorKind::AddrInUse, e.to_string()))?;
+        listener.set_nonblocking(ListenerNonblockingMode::Accept)?;
+        Ok(Self {
+            listener,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Accept a pending connection, if any
+    pub fn accept(&mut self) -> io::Result<Option<StreamWrapper>> {
+        match self.listener.accept() {
+            Ok(stream) => Ok(Some(StreamWrapper::new(stream))),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            #[cfg(windows)]
+            Err(e) if platform_windows::is_transient_pipe_error(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Connect to a control socket (blocking stream)
+    pub fn connect(path: &Path) -> io::Result<StreamWrapper> {
+        let stream = Stream::connect(platform::socket_name_for_path(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::ConnectionRefused, e.to_string()))?;
+        Ok(StreamWrapper::new(stream))
+    }
+}
+
+impl Drop for ControlSocketListener {
+    fn drop(&mut self) {
+        if cfg!(unix) && self.path.exists() {
+            // Best-effort cleanup of the socket file on shutdown
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
 /// Wrapper for LocalSocketStream that provides thread-safe sharing
 /// Uses Arc<Mutex<>> internally to allow cloning and use across threads
 #[derive(Clone)]
@@ -9,11 +9,15 @@
 //! - **Data socket**: Pure byte stream for stdin/stdout relay (hot path)
 //! - **Control socket**: JSON messages for resize, ping/pong, etc (cold path)
 //!
+//! [`headless`] reuses the capture backend to run the editor without any
+//! client terminal, driven by scripts over a single JSON socket.
+//!
 //! See `docs/internal/session-persistence-design.md` for full design.
 
 pub mod capture_backend;
 pub mod daemon;
 pub mod editor_server;
+pub mod headless;
 pub mod input_parser;
 pub mod ipc;
 pub mod protocol;
@@ -28,8 +32,9 @@ pub use daemon::{
     daemonize, is_process_running, read_pid_file, spawn_server_detached, write_pid_file,
 };
 pub use editor_server::{EditorServer, EditorServerConfig};
+pub use headless::{HeadlessCommand, HeadlessConfig, HeadlessEditor, HeadlessResponse};
 pub use input_parser::InputParser;
-pub use ipc::{ServerListener, SocketPaths};
+pub use ipc::{ControlSocketListener, ServerListener, SocketPaths};
 pub use protocol::{ClientHello, ControlMessage, ServerHello, PROTOCOL_VERSION};
 #[cfg(test)]
 pub use runner::{Server, ServerConfig};
@@ -1316,4 +1316,75 @@ mod integration_tests {
 
         teardown_editor_server_e2e(conn, shutdown_handle, server_handle, socket_paths, temp_dir);
     }
+
+    /// Headless mode: a queued file opens at its line, then a script types
+    /// and reads back the buffer and the rendered screen over the control
+    /// socket
+    #[test]
+    fn test_headless_editor_over_control_socket() {
+        use crate::config::Config;
+        use crate::config_io::DirectoryContext;
+        use crate::server::headless::{HeadlessConfig, HeadlessEditor, HeadlessResponse};
+        use crate::server::ipc::ControlSocketListener;
+        use std::io::{BufRead, BufReader};
+        use std::sync::mpsc;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("notes.txt");
+        std::fs::write(&file, "first\nworld\n").unwrap();
+        let socket = temp_dir
+            .path()
+            .join(format!("{}.sock", unique_session_name("headless")));
+
+        let config = HeadlessConfig {
+            control_socket: socket.clone(),
+            working_dir: temp_dir.path().to_path_buf(),
+            term_size: TermSize::new(80, 24),
+            editor_config: Config::default(),
+            dir_context: DirectoryContext::for_testing(temp_dir.path()),
+            plugins_enabled: false,
+        };
+        let (ready_tx, ready_rx) = mpsc::channel();
+        // Editor is not Send, so it has to be created in the thread
+        let handle = thread::spawn(move || {
+            let mut headless = HeadlessEditor::new(config).unwrap();
+            headless
+                .editor_mut()
+                .queue_file_open(file, Some(2), None, None, None, None, None);
+            ready_tx.send(()).unwrap();
+            headless.run()
+        });
+        ready_rx.recv().unwrap();
+
+        let stream = ControlSocketListener::connect(&socket).unwrap();
+        let mut reader = BufReader::new(&stream);
+        let mut send = |command: &str| -> HeadlessResponse {
+            stream
+                .write_all(format!("{}\n", command).as_bytes())
+                .unwrap();
+            stream.flush().unwrap();
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            serde_json::from_str(&line).unwrap()
+        };
+
+        assert!(send(r#"{"type":"type_text","text":"hello "}"#).ok);
+        let text = send(r#"{"type":"get_buffer_text"}"#);
+        assert_eq!(text.text.as_deref(), Some("first\nhello world\n"));
+
+        let screen = send(r#"{"type":"get_screen"}"#).text.unwrap();
+        assert!(screen.contains("hello world"), "Screen:\n{}", screen);
+        assert_eq!(screen.lines().count(), 24);
+
+        let bad_key = send(r#"{"type":"send_key","key":"NoSuchKey"}"#);
+        assert!(!bad_key.ok);
+        assert!(!send("not json").ok);
+        assert!(!send(r#"{"type":"resize","cols":0,"rows":24}"#).ok);
+        assert!(send(r#"{"type":"resize","cols":100,"rows":30}"#).ok);
+        let screen = send(r#"{"type":"get_screen"}"#).text.unwrap();
+        assert_eq!(screen.lines().count(), 30);
+
+        assert!(send(r#"{"type":"quit"}"#).ok);
+        assert!(handle.join().unwrap().is_ok());
+    }
 }
@@ -137,6 +137,13 @@ pub enum AsyncMessage {
         ranges: Vec<FoldingRange>,
     },
 
+    /// Round-trip time of a latency probe sent to the LSP server
+    LspLatencyProbe {
+        language: String,
+        request_id: u64,
+        latency: Result<std::time::Duration, String>,
+    },
+
     /// LSP semantic tokens response (full, full/delta, or range)
     LspSemanticTokens {
         request_id: u64,
@@ -152,9 +159,35 @@ pub enum AsyncMessage {
     /// Client should re-pull diagnostics for all open documents
     LspDiagnosticRefresh { language: String },
 
+    /// LSP server asks the editor to apply an edit (workspace/applyEdit),
+    /// e.g. while running a code action's command
+    LspApplyEdit {
+        language: String,
+        edit: lsp_types::WorkspaceEdit,
+    },
+
     /// File changed externally (future: file watching)
     FileChanged { path: String },
 
+    /// Round trip of a remote connection's keepalive ping, None if it was
+    /// missed
+    RemoteLatency {
+        connection: String,
+        round_trip: Option<std::time::Duration>,
+    },
+
+    /// The next piece of a remote file being downloaded
+    RemoteDownloadChunk { transfer_id: u64, bytes: Vec<u8> },
+
+    /// Bytes of a remote upload written so far
+    RemoteUploadProgress { transfer_id: u64, sent: u64 },
+
+    /// A remote download or upload ended
+    RemoteTransferFinished {
+        transfer_id: u64,
+        result: Result<(), String>,
+    },
+
     /// Git status updated (future: git integration)
     GitStatusChanged { status: String },
 
@@ -180,6 +213,30 @@ pub enum AsyncMessage {
     /// File open dialog: async shortcuts (Windows drive letters) loaded
     FileOpenShortcutsLoaded(Vec<crate::app::file_open::NavigationShortcut>),
 
+    /// Fuzzy finder: a batch of paths found by the working directory walk
+    FuzzyFinderFiles {
+        walk_id: u64,
+        paths: Vec<String>,
+        /// Whether the walk is finished
+        done: bool,
+    },
+
+    /// Project search: a batch of matching lines
+    ProjectSearchMatches {
+        search_id: u64,
+        matches: Vec<crate::app::project_search::ProjectSearchMatch>,
+        /// Whether the search is finished
+        done: bool,
+    },
+
+    /// Project replace: writable files with matches and their match counts
+    ProjectReplaceScanned {
+        replace_id: u64,
+        files: Vec<(std::path::PathBuf, usize)>,
+        /// Files with matches that can't be written
+        read_only: usize,
+    },
+
     /// Terminal output received (triggers redraw)
     TerminalOutput { terminal_id: TerminalId },
 
@@ -235,6 +292,53 @@ pub enum AsyncMessage {
         exit_code: i32,
     },
 
+    /// A program started by an `open_external` rule failed
+    ExternalOpenFailed {
+        /// File that was handed to the program
+        path: std::path::PathBuf,
+        /// Program that was run
+        command: String,
+        /// Error or stderr output
+        error: String,
+    },
+
+    /// Progress of a URL download started with `fresh <url>`
+    UrlDownloadProgress {
+        /// URL being downloaded
+        url: String,
+        /// Bytes received so far
+        received: u64,
+        /// Total size, if the server sent a Content-Length
+        total: Option<u64>,
+    },
+
+    /// A URL download finished
+    UrlDownloaded {
+        /// URL that was requested
+        url: String,
+        /// Downloaded file, or the error that stopped the download
+        result: Result<crate::services::url_fetch::FetchedUrl, String>,
+    },
+
+    /// Progress of a language server download from `lsp_downloads`
+    LspDownloadProgress {
+        /// Language the server is for
+        language: String,
+        /// Bytes received so far
+        received: u64,
+        /// Total size, if the server sent a Content-Length
+        total: Option<u64>,
+    },
+
+    /// A language server download finished and, if it succeeded, was
+    /// verified and installed
+    LspDownloaded {
+        /// Language the server is for
+        language: String,
+        /// Path of the installed binary, or why the download failed
+        result: Result<std::path::PathBuf, String>,
+    },
+
     /// LSP server status update (progress, messages, etc.)
     LspStatusUpdate {
         language: String,
@@ -269,8 +269,9 @@ impl Clipboard {
             return false;
         }
 
-        // Check system clipboard via the static clipboard
-        if self.use_system_clipboard {
+        // Check system clipboard via the static clipboard (skipped in
+        // internal-only mode, matching paste())
+        if self.use_system_clipboard && !self.internal_only {
             if let Ok(mut guard) = SYSTEM_CLIPBOARD.lock() {
                 if guard.is_none() {
                     if let Ok(cb) = arboard::Clipboard::new() {
@@ -0,0 +1,249 @@
+//! Per-file overrides the user picked by hand (language, encoding, line
+//! ending, read-only), remembered across sessions.
+//!
+//! The store is project-scoped: one JSON file per working directory under
+//! `{data_dir}/file_overrides/`, keyed by absolute file path. Entries for
+//! files that no longer exist are dropped when the store is loaded, and
+//! renames done inside the editor move the entry along with the file.
+//!
+//! Workspaces don't record these settings; this store is their only home.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::buffer::LineEnding;
+use crate::model::encoding::Encoding;
+
+/// Current override store format version
+pub const FILE_OVERRIDES_VERSION: u32 = 1;
+
+/// Overrides remembered for one file. `None` fields use auto-detection.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileOverride {
+    /// Syntax name chosen with "Set Language" (e.g., "Rust", "Plain Text")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// Encoding chosen with "Set Encoding" or "Reload with Encoding"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<Encoding>,
+
+    /// Line ending chosen with "Set Line Ending" ("LF", "CRLF" or "CR")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_ending: Option<String>,
+
+    /// Read-only state chosen with "Toggle Read Only"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+}
+
+impl FileOverride {
+    /// Whether no override is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The line ending override, if it names a known line ending
+    pub fn line_ending(&self) -> Option<LineEnding> {
+        match self.line_ending.as_deref()? {
+            "LF" => Some(LineEnding::LF),
+            "CRLF" => Some(LineEnding::CRLF),
+            "CR" => Some(LineEnding::CR),
+            _ => None,
+        }
+    }
+}
+
+/// On-disk layout of the store
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedFileOverrides {
+    version: u32,
+    #[serde(default)]
+    files: HashMap<PathBuf, FileOverride>,
+}
+
+/// Project-scoped store of per-file overrides
+#[derive(Debug, Default)]
+pub struct FileOverrideStore {
+    /// Where the store is persisted (None keeps it in memory only)
+    path: Option<PathBuf>,
+    files: HashMap<PathBuf, FileOverride>,
+}
+
+impl FileOverrideStore {
+    /// Load the store from `path`, dropping entries for files that no
+    /// longer exist. A missing or unreadable file gives an empty store.
+    pub fn load(path: PathBuf) -> Self {
+        let persisted: PersistedFileOverrides = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .filter(|p: &PersistedFileOverrides| p.version <= FILE_OVERRIDES_VERSION)
+            .unwrap_or_default();
+
+        let total = persisted.files.len();
+        let files: HashMap<_, _> = persisted
+            .files
+            .into_iter()
+            .filter(|(file, o)| file.exists() && !o.is_empty())
+            .collect();
+
+        let store = Self {
+            path: Some(path),
+            files,
+        };
+        if store.files.len() != total {
+            tracing::debug!(
+                "Pruned {} stale file override(s)",
+                total - store.files.len()
+            );
+            store.save();
+        }
+        store
+    }
+
+    /// The overrides for `file`, if any
+    pub fn get(&self, file: &Path) -> Option<&FileOverride> {
+        self.files.get(file)
+    }
+
+    /// Change the overrides for `file` and persist the store
+    pub fn update(&mut self, file: &Path, f: impl FnOnce(&mut FileOverride)) {
+        let entry = self.files.entry(file.to_path_buf()).or_default();
+        f(entry);
+        if entry.is_empty() {
+            self.files.remove(file);
+        }
+        self.save();
+    }
+
+    /// Forget the overrides for `file`. Returns whether there were any.
+    pub fn remove(&mut self, file: &Path) -> bool {
+        let removed = self.files.remove(file).is_some();
+        if removed {
+            self.save();
+        }
+        removed
+    }
+
+    /// Move overrides after `from` was renamed to `to`. Works for files and
+    /// for directories (every entry below `from` moves).
+    pub fn rename(&mut self, from: &Path, to: &Path) {
+        let moved: Vec<PathBuf> = self
+            .files
+            .keys()
+            .filter(|file| file.starts_with(from))
+            .cloned()
+            .collect();
+        if moved.is_empty() {
+            return;
+        }
+        for old in moved {
+            if let (Some(entry), Ok(rest)) = (self.files.remove(&old), old.strip_prefix(from)) {
+                let new = if rest.as_os_str().is_empty() {
+                    to.to_path_buf()
+                } else {
+                    to.join(rest)
+                };
+                self.files.insert(new, entry);
+            }
+        }
+        self.save();
+    }
+
+    /// Write the store to disk (atomic write)
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = (|| -> io::Result<()> {
+            if self.files.is_empty() {
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
+                return Ok(());
+            }
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let persisted = PersistedFileOverrides {
+                version: FILE_OVERRIDES_VERSION,
+                files: self.files.clone(),
+            };
+            let content = serde_json::to_string_pretty(&persisted)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            let temp_path = path.with_extension("json.tmp");
+            let mut file = std::fs::File::create(&temp_path)?;
+            file.write_all(content.as_bytes())?;
+            file.sync_all()?;
+            std::fs::rename(&temp_path, path)
+        })();
+        if let Err(e) = result {
+            tracing::warn!("Failed to save file overrides to {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_round_trip_and_prune() {
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().join("store").join("project.json");
+        let kept = temp_dir.path().join("kept.txt");
+        let gone = temp_dir.path().join("gone.txt");
+        std::fs::write(&kept, "a").unwrap();
+        std::fs::write(&gone, "b").unwrap();
+
+        let mut store = FileOverrideStore::load(store_path.clone());
+        store.update(&kept, |o| o.language = Some("Rust".to_string()));
+        store.update(&gone, |o| o.encoding = Some(Encoding::Latin1));
+        std::fs::remove_file(&gone).unwrap();
+
+        let store = FileOverrideStore::load(store_path);
+        assert_eq!(
+            store.get(&kept).and_then(|o| o.language.as_deref()),
+            Some("Rust")
+        );
+        assert!(store.get(&gone).is_none());
+    }
+
+    #[test]
+    fn test_empty_override_is_removed() {
+        let mut store = FileOverrideStore::default();
+        let file = Path::new("/project/a.txt");
+        store.update(file, |o| o.read_only = Some(true));
+        assert!(store.get(file).is_some());
+        store.update(file, |o| o.read_only = None);
+        assert!(store.get(file).is_none());
+        assert!(!store.remove(file));
+    }
+
+    #[test]
+    fn test_rename_moves_entries() {
+        let mut store = FileOverrideStore::default();
+        store.update(Path::new("/project/old.txt"), |o| {
+            o.line_ending = Some("CRLF".to_string())
+        });
+        store.update(Path::new("/project/dir/inner.txt"), |o| {
+            o.read_only = Some(true)
+        });
+
+        store.rename(Path::new("/project/old.txt"), Path::new("/project/new.txt"));
+        store.rename(Path::new("/project/dir"), Path::new("/project/moved"));
+
+        assert_eq!(
+            store
+                .get(Path::new("/project/new.txt"))
+                .and_then(FileOverride::line_ending),
+            Some(LineEnding::CRLF)
+        );
+        assert!(store.get(Path::new("/project/old.txt")).is_none());
+        assert!(store.get(Path::new("/project/moved/inner.txt")).is_some());
+    }
+}
@@ -6,7 +6,7 @@
 //! drives, slow disks, etc.).
 
 use crate::model::filesystem::{
-    DirEntry, FileMetadata, FilePermissions, FileReader, FileSystem, FileWriter,
+    DirEntry, FileKind, FileMetadata, FilePermissions, FileReader, FileSystem, FileWriter,
 };
 use std::io;
 use std::path::{Path, PathBuf};
@@ -267,6 +267,18 @@ impl FileSystem for SlowFileSystem {
         self.inner.is_file(path)
     }
 
+    fn is_symlink(&self, path: &Path) -> io::Result<bool> {
+        self.add_delay(self.config.other_delay);
+        self.metrics.other_calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.is_symlink(path)
+    }
+
+    fn file_kind(&self, path: &Path) -> io::Result<FileKind> {
+        self.add_delay(self.config.other_delay);
+        self.metrics.other_calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.file_kind(path)
+    }
+
     fn set_permissions(&self, path: &Path, permissions: &FilePermissions) -> io::Result<()> {
         self.add_delay(self.config.other_delay);
         self.metrics.other_calls.fetch_add(1, Ordering::SeqCst);
@@ -5,7 +5,8 @@
 //! - `$XDG_STATE_HOME/fresh/logs/` (typically `~/.local/state/fresh/logs/`)
 //!
 //! Each Fresh instance uses PID-based log files to support concurrent runs.
-//! On startup, stale log files from dead processes are cleaned up automatically.
+//! On startup, stale log files and stdin temp files from dead processes are
+//! cleaned up automatically.
 
 use std::fs;
 use std::path::PathBuf;
@@ -130,7 +131,6 @@ fn cleanup_legacy_tmp_logs() {
         "fresh-warnings-",
         "fresh-lsp-",
         "rust-analyzer-",
-        "fresh-stdin-",
         "fresh.log", // Old single fresh.log in /tmp
     ];
 
@@ -211,6 +211,46 @@ fn cleanup_stale_logs_in_dir(dir: &std::path::Path, current_pid: u32) {
     }
 }
 
+/// Clean up stdin temp files (`fresh-stdin-{PID}.tmp` or `.txt`) left in
+/// the temp directory by sessions whose process no longer exists.
+pub fn cleanup_stale_stdin_temps() {
+    cleanup_stale_stdin_temps_in_dir(&std::env::temp_dir(), std::process::id());
+}
+
+/// Clean up stdin temp files of dead processes in a specific directory
+fn cleanup_stale_stdin_temps_in_dir(dir: &std::path::Path, current_pid: u32) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let file_name = entry.file_name();
+        let Some(pid) = extract_pid_from_stdin_temp(&file_name.to_string_lossy()) else {
+            continue;
+        };
+        if pid == current_pid
+            || is_process_running(pid)
+            || !entry.file_type().map(|t| t.is_file()).unwrap_or(false)
+        {
+            continue;
+        }
+        if let Err(e) = fs::remove_file(entry.path()) {
+            tracing::debug!("Failed to clean up stdin temp {:?}: {}", entry.path(), e);
+        } else {
+            tracing::info!("Cleaned up stale stdin temp file: {:?}", entry.path());
+        }
+    }
+}
+
+/// Extract PID from a stdin temp file name like "fresh-stdin-12345.tmp"
+fn extract_pid_from_stdin_temp(name: &str) -> Option<u32> {
+    let rest = name.strip_prefix("fresh-stdin-")?;
+    let pid_str = rest
+        .strip_suffix(".tmp")
+        .or_else(|| rest.strip_suffix(".txt"))?;
+    pid_str.parse().ok()
+}
+
 /// Check if a file is older than the specified duration
 fn is_file_older_than(path: &std::path::Path, age: Duration) -> bool {
     let Ok(metadata) = fs::metadata(path) else {
@@ -399,6 +439,38 @@ mod tests {
         assert_eq!(extract_pid_from_filename("invalid"), None);
     }
 
+    #[test]
+    fn test_extract_pid_from_stdin_temp() {
+        assert_eq!(extract_pid_from_stdin_temp("fresh-stdin-42.tmp"), Some(42));
+        assert_eq!(extract_pid_from_stdin_temp("fresh-stdin-42.txt"), Some(42));
+        assert_eq!(extract_pid_from_stdin_temp("fresh-stdin-42.log"), None);
+        assert_eq!(extract_pid_from_stdin_temp("fresh-42.tmp"), None);
+    }
+
+    #[test]
+    fn test_cleanup_stale_stdin_temps_keeps_live_processes() {
+        let temp = tempfile::tempdir().unwrap();
+        // Above any real pid_max, so no process has it
+        let dead_pid = i32::MAX as u32;
+        let live = temp
+            .path()
+            .join(format!("fresh-stdin-{}.tmp", std::process::id()));
+        let dead = temp.path().join(format!("fresh-stdin-{}.txt", dead_pid));
+        let other = temp.path().join("fresh-stdin-notes.txt");
+        for path in [&live, &dead, &other] {
+            fs::write(path, "data").unwrap();
+        }
+
+        assert!(is_process_running(std::process::id()));
+        assert!(!is_process_running(dead_pid));
+        // Pass a different "current" PID so our own file is only kept for being alive
+        cleanup_stale_stdin_temps_in_dir(temp.path(), 0);
+
+        assert!(live.exists());
+        assert!(!dead.exists());
+        assert!(other.exists());
+    }
+
     #[test]
     fn test_current_process_is_running() {
         assert!(is_process_running(std::process::id()));
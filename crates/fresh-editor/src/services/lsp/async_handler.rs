@@ -16,6 +16,7 @@ use crate::services::async_bridge::{
     LspServerStatus,
 };
 use crate::services::process_limits::ProcessLimits;
+use crate::services::remote::{ProcessSpawner, SpawnError, StdioProcess};
 use lsp_types::{
     notification::{
         DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
@@ -38,8 +39,8 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc as std_mpsc, Arc, Mutex};
 use std::time::Instant;
-use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
-use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::process::Command;
 use tokio::sync::{mpsc, oneshot};
 
 /// Grace period after didOpen before sending didChange (in milliseconds)
@@ -57,6 +58,12 @@ const DID_OPEN_GRACE_PERIOD_MS: u64 = 200;
 const LSP_ERROR_CONTENT_MODIFIED: i64 = -32801;
 const LSP_ERROR_SERVER_CANCELLED: i64 = -32802;
 
+/// JSON-RPC MethodNotFound, the expected answer to the `$/` latency probe
+const JSONRPC_METHOD_NOT_FOUND: i64 = -32601;
+
+/// How long a latency probe waits before reporting the server as unresponsive
+const LATENCY_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
 /// Check if a document is already open and should skip didOpen.
 /// Returns true if the document is already open (should skip), false if it should proceed.
 fn should_skip_did_open(
@@ -499,6 +506,9 @@ enum LspCommand {
         diagnostics: Vec<lsp_types::Diagnostic>,
     },
 
+    /// Run a code action's command (workspace/executeCommand)
+    ExecuteCommand { command: lsp_types::Command },
+
     /// Request document diagnostics (pull model)
     DocumentDiagnostic {
         request_id: u64,
@@ -544,6 +554,9 @@ enum LspCommand {
         request_id: u64,
     },
 
+    /// Measure the round-trip time of a request queued behind pending edits
+    LatencyProbe { request_id: u64 },
+
     /// Custom request initiated by a plugin
     PluginRequest {
         request_id: u64,
@@ -555,10 +568,17 @@ enum LspCommand {
     Shutdown,
 }
 
+/// Stdin of a language server: a pipe to a local process, or one bridged
+/// over SSH to a process on the remote host
+type ServerStdin = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Stdout of a language server, see [`ServerStdin`]
+type ServerStdout = BufReader<Box<dyn AsyncRead + Send + Unpin>>;
+
 /// Mutable state for LSP command processing
 struct LspState {
     /// Stdin for sending messages (shared with stdout reader for server responses)
-    stdin: Arc<tokio::sync::Mutex<ChildStdin>>,
+    stdin: Arc<tokio::sync::Mutex<ServerStdin>>,
 
     /// Next request ID
     next_id: i64,
@@ -1615,6 +1635,29 @@ impl LspState {
         }
     }
 
+    /// Handle workspace/executeCommand. Edits the command makes come back
+    /// from the server as workspace/applyEdit requests.
+    #[allow(clippy::type_complexity)]
+    async fn handle_execute_command(
+        &mut self,
+        command: lsp_types::Command,
+        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+    ) -> Result<(), String> {
+        use lsp_types::{ExecuteCommandParams, WorkDoneProgressParams};
+
+        tracing::trace!("LSP: execute command {}", command.command);
+
+        let params = ExecuteCommandParams {
+            command: command.command,
+            arguments: command.arguments.unwrap_or_default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        };
+        self.send_request_sequential::<_, Value>("workspace/executeCommand", Some(params), pending)
+            .await
+            .map(|_| ())
+            .inspect_err(|e| tracing::warn!("Execute command request failed: {}", e))
+    }
+
     /// Handle document diagnostic request (pull diagnostics)
     #[allow(clippy::type_complexity)]
     async fn handle_document_diagnostic(
@@ -2071,6 +2114,44 @@ impl LspState {
         });
     }
 
+    /// Time a request that the server answers without doing any work.
+    ///
+    /// Servers must answer unknown `$/` requests with MethodNotFound, so any
+    /// response, including that error, completes the round-trip. Because
+    /// commands are processed in order, the time includes catching up on any
+    /// didChange notifications sent before the probe.
+    #[allow(clippy::type_complexity)]
+    async fn handle_latency_probe(
+        &mut self,
+        request_id: u64,
+        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+    ) {
+        let lsp_id = self.next_id;
+        let start = Instant::now();
+        let response = tokio::time::timeout(
+            LATENCY_PROBE_TIMEOUT,
+            self.send_request_sequential::<Value, Value>("$/fresh/latencyProbe", None, pending),
+        )
+        .await;
+        let latency = match response {
+            Ok(Ok(_)) => Ok(start.elapsed()),
+            Ok(Err(e)) if e.starts_with("LSP error:") => Ok(start.elapsed()),
+            Ok(Err(e)) => Err(e),
+            Err(_) => {
+                pending.lock().unwrap().remove(&lsp_id);
+                Err(format!(
+                    "no response within {}s",
+                    LATENCY_PROBE_TIMEOUT.as_secs()
+                ))
+            }
+        };
+        let _ = self.async_tx.send(AsyncMessage::LspLatencyProbe {
+            language: self.language.clone(),
+            request_id,
+            latency,
+        });
+    }
+
     /// Handle shutdown command
     async fn handle_shutdown(&mut self) -> Result<(), String> {
         tracing::info!("Shutting down async LSP server");
@@ -2126,14 +2207,15 @@ impl LspState {
 
 /// Async LSP task that handles all I/O
 struct LspTask {
-    /// Process handle - kept alive for lifetime management (kill_on_drop)
-    _process: Child,
+    /// Process handle - kept alive for lifetime management (ends the
+    /// process when dropped)
+    _process: Box<dyn Send>,
 
     /// Stdin for sending messages
-    stdin: ChildStdin,
+    stdin: ServerStdin,
 
     /// Stdout for receiving messages
-    stdout: BufReader<ChildStdout>,
+    stdout: ServerStdout,
 
     /// Next request ID
     next_id: i64,
@@ -2171,7 +2253,9 @@ struct LspTask {
 }
 
 impl LspTask {
-    /// Create a new LSP task
+    /// Create a new LSP task, running the server on the remote host through
+    /// `remote` if given
+    #[allow(clippy::too_many_arguments)]
     async fn spawn(
         command: &str,
         args: &[String],
@@ -2181,31 +2265,64 @@ impl LspTask {
         process_limits: &ProcessLimits,
         stderr_log_path: std::path::PathBuf,
         language_id_overrides: HashMap<String, String>,
+        remote: Option<Arc<dyn ProcessSpawner>>,
     ) -> Result<Self, String> {
         tracing::info!("Spawning async LSP server: {} {:?}", command, args);
-        tracing::info!("Process limits: {:?}", process_limits);
         tracing::info!("LSP stderr will be logged to: {:?}", stderr_log_path);
 
-        // Check if the command exists before trying to spawn
-        // This provides a clearer error message than the generic "No such file or directory"
-        if !Self::command_exists(command) {
+        let process = match remote {
+            Some(spawner) => {
+                Self::spawn_remote(spawner.as_ref(), command, args, env, &stderr_log_path).await?
+            }
+            None => Self::spawn_local(command, args, env, process_limits, &stderr_log_path)?,
+        };
+
+        Ok(Self {
+            _process: process.guard,
+            stdin: process.stdin,
+            stdout: BufReader::new(process.stdout),
+            next_id: 0,
+            pending: HashMap::new(),
+            capabilities: None,
+            document_versions: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            pending_opens: HashMap::new(),
+            initialized: false,
+            async_tx,
+            language,
+            server_command: command.to_string(),
+            stderr_log_path,
+            language_id_overrides,
+        })
+    }
+
+    /// Start the server on this machine
+    fn spawn_local(
+        command: &str,
+        args: &[String],
+        env: &std::collections::HashMap<String, String>,
+        process_limits: &ProcessLimits,
+        stderr_log_path: &std::path::Path,
+    ) -> Result<StdioProcess, String> {
+        tracing::info!("Process limits: {:?}", process_limits);
+
+        // Check if the command exists before trying to spawn, on the PATH the
+        // server will see. This provides a clearer error message than the
+        // generic "No such file or directory"
+        let process_env = crate::services::shell_env::current();
+        let Some(resolved) = process_env.find_command(command, env) else {
             return Err(format!(
                 "LSP server executable '{}' not found. Please install it or check your PATH.",
                 command
             ));
-        }
+        };
+        tracing::info!("Resolved LSP server '{}' to {:?}", command, resolved);
 
-        // Create stderr log file and redirect process stderr directly to it
-        let stderr_file = std::fs::File::create(&stderr_log_path).map_err(|e| {
-            format!(
-                "Failed to create LSP stderr log file {:?}: {}",
-                stderr_log_path, e
-            )
-        })?;
+        // Redirect process stderr directly to the log file
+        let stderr_file = create_stderr_log(stderr_log_path)?;
 
-        let mut cmd = Command::new(command);
+        let mut cmd = Command::new(&resolved);
         cmd.args(args)
-            .envs(env)
+            .envs(process_env.vars_with(env))
             .stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::from(stderr_file))
@@ -2234,65 +2351,43 @@ impl LspTask {
             .take()
             .ok_or_else(|| "Failed to get stdin".to_string())?;
 
-        let stdout = BufReader::new(
-            process
-                .stdout
-                .take()
-                .ok_or_else(|| "Failed to get stdout".to_string())?,
-        );
+        let stdout = process
+            .stdout
+            .take()
+            .ok_or_else(|| "Failed to get stdout".to_string())?;
 
-        Ok(Self {
-            _process: process,
-            stdin,
-            stdout,
-            next_id: 0,
-            pending: HashMap::new(),
-            capabilities: None,
-            document_versions: Arc::new(std::sync::Mutex::new(HashMap::new())),
-            pending_opens: HashMap::new(),
-            initialized: false,
-            async_tx,
-            language,
-            server_command: command.to_string(),
-            stderr_log_path,
-            language_id_overrides,
+        Ok(StdioProcess {
+            stdin: Box::new(stdin),
+            stdout: Box::new(stdout),
+            guard: Box::new(process),
         })
     }
 
-    /// Check if a command exists in PATH or as an absolute path
-    fn command_exists(command: &str) -> bool {
-        use std::path::Path;
-
-        // If it's an absolute path, check if the file exists and is executable
-        if command.contains('/') || command.contains('\\') {
-            let path = Path::new(command);
-            return path.exists() && path.is_file();
-        }
-
-        // Otherwise, search in PATH
-        if let Ok(path_var) = std::env::var("PATH") {
-            #[cfg(unix)]
-            let separator = ':';
-            #[cfg(windows)]
-            let separator = ';';
-
-            for dir in path_var.split(separator) {
-                let full_path = Path::new(dir).join(command);
-                if full_path.exists() && full_path.is_file() {
-                    return true;
-                }
-                // On Windows, also check with .exe extension
-                #[cfg(windows)]
-                {
-                    let with_exe = Path::new(dir).join(format!("{}.exe", command));
-                    if with_exe.exists() && with_exe.is_file() {
-                        return true;
-                    }
-                }
-            }
-        }
+    /// Start the server on the remote host, its stdio bridged over SSH.
+    /// Process limits only apply to local servers.
+    async fn spawn_remote(
+        spawner: &dyn ProcessSpawner,
+        command: &str,
+        args: &[String],
+        env: &std::collections::HashMap<String, String>,
+        stderr_log_path: &std::path::Path,
+    ) -> Result<StdioProcess, String> {
+        // The remote stderr is written to the local log
+        let stderr_file = create_stderr_log(stderr_log_path)?;
 
-        false
+        spawner
+            .spawn_stdio(command.to_string(), args.to_vec(), env.clone(), stderr_file)
+            .await
+            .map_err(|e| match e {
+                SpawnError::Process(error) if error.starts_with("command not found") => format!(
+                    "LSP server executable '{}' not found on the remote host. Please install it there or check its PATH.",
+                    command
+                ),
+                e => format!(
+                    "Failed to spawn LSP server '{}' on the remote host: {}",
+                    command, e
+                ),
+            })
     }
 
     /// Spawn the stdout reader task that continuously reads and dispatches LSP messages
@@ -2300,12 +2395,12 @@ impl LspTask {
     #[allow(clippy::too_many_arguments)]
     #[allow(clippy::let_underscore_must_use)] // async_tx.send() is best-effort; receiver drop means editor shutdown
     fn spawn_stdout_reader(
-        mut stdout: BufReader<ChildStdout>,
+        mut stdout: ServerStdout,
         pending: Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
         async_tx: std_mpsc::Sender<AsyncMessage>,
         language: String,
         server_command: String,
-        stdin_writer: Arc<tokio::sync::Mutex<ChildStdin>>,
+        stdin_writer: Arc<tokio::sync::Mutex<ServerStdin>>,
         stderr_log_path: std::path::PathBuf,
         shutting_down: Arc<AtomicBool>,
         document_versions: Arc<std::sync::Mutex<HashMap<PathBuf, i64>>>,
@@ -2680,6 +2775,14 @@ impl LspTask {
                                 });
                             }
                         }
+                        LspCommand::ExecuteCommand { command } => {
+                            if state.initialized {
+                                tracing::info!("Processing ExecuteCommand {}", command.command);
+                                let _ = state.handle_execute_command(command, &pending).await;
+                            } else {
+                                tracing::trace!("LSP not initialized, cannot execute command");
+                            }
+                        }
                         LspCommand::DocumentDiagnostic {
                             request_id,
                             uri,
@@ -2852,6 +2955,17 @@ impl LspTask {
                             );
                             let _ = state.handle_cancel_request(request_id).await;
                         }
+                        LspCommand::LatencyProbe { request_id } => {
+                            if state.initialized {
+                                state.handle_latency_probe(request_id, &pending).await;
+                            } else {
+                                let _ = state.async_tx.send(AsyncMessage::LspLatencyProbe {
+                                    language: language_clone.clone(),
+                                    request_id,
+                                    latency: Err("LSP not initialized".to_string()),
+                                });
+                            }
+                        }
                         LspCommand::PluginRequest {
                             request_id,
                             method,
@@ -2904,10 +3018,18 @@ impl LspTask {
     }
 }
 
+/// Create the log file a server's stderr is written to
+fn create_stderr_log(stderr_log_path: &std::path::Path) -> Result<std::fs::File, String> {
+    std::fs::File::create(stderr_log_path).map_err(|e| {
+        format!(
+            "Failed to create LSP stderr log file {:?}: {}",
+            stderr_log_path, e
+        )
+    })
+}
+
 /// Standalone function to read a message from stdout (for reader task)
-async fn read_message_from_stdout(
-    stdout: &mut BufReader<ChildStdout>,
-) -> Result<JsonRpcMessage, String> {
+async fn read_message_from_stdout(stdout: &mut ServerStdout) -> Result<JsonRpcMessage, String> {
     // Read headers
     let mut content_length: Option<usize> = None;
 
@@ -2963,7 +3085,7 @@ async fn handle_message_dispatch(
     async_tx: &std_mpsc::Sender<AsyncMessage>,
     language: &str,
     server_command: &str,
-    stdin_writer: &Arc<tokio::sync::Mutex<ChildStdin>>,
+    stdin_writer: &Arc<tokio::sync::Mutex<ServerStdin>>,
     document_versions: &Arc<std::sync::Mutex<HashMap<PathBuf, i64>>>,
 ) -> Result<(), String> {
     match message {
@@ -2973,8 +3095,11 @@ async fn handle_message_dispatch(
                 let result = if let Some(error) = response.error {
                     // Per LSP spec: ContentModified and ServerCancelled are expected
                     // during editing. Suppress them like VS Code and Neovim do.
+                    // MethodNotFound answers optional requests (the latency probe);
+                    // the caller still gets the error.
                     if error.code == LSP_ERROR_CONTENT_MODIFIED
                         || error.code == LSP_ERROR_SERVER_CANCELLED
+                        || error.code == JSONRPC_METHOD_NOT_FOUND
                     {
                         tracing::debug!(
                             "LSP response: {} (code {}), discarding",
@@ -3082,6 +3207,27 @@ async fn handle_message_dispatch(
                         error: None,
                     }
                 }
+                "workspace/applyEdit" => {
+                    // Typically sent while running a code action's command;
+                    // the main loop applies the edit
+                    let edit = request.params.clone().and_then(|params| {
+                        serde_json::from_value::<lsp_types::ApplyWorkspaceEditParams>(params).ok()
+                    });
+                    let applied = edit.is_some();
+                    if let Some(params) = edit {
+                        tracing::info!("LSP ({}) requested workspace/applyEdit", language);
+                        let _ = async_tx.send(AsyncMessage::LspApplyEdit {
+                            language: language.to_string(),
+                            edit: params.edit,
+                        });
+                    }
+                    JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id: request.id,
+                        result: Some(serde_json::json!({ "applied": applied })),
+                        error: None,
+                    }
+                }
                 "workspace/diagnostic/refresh" => {
                     // Server wants us to re-pull diagnostics for all open documents
                     // This typically happens after the project finishes loading
@@ -3404,7 +3550,9 @@ pub struct LspHandle {
 // paths are secondary, and try_send in Drop is inherently best-effort cleanup.
 #[allow(clippy::let_underscore_must_use)]
 impl LspHandle {
-    /// Spawn a new LSP server in an async task
+    /// Spawn a new LSP server in an async task, on the remote host through
+    /// `remote` if given
+    #[allow(clippy::too_many_arguments)]
     pub fn spawn(
         runtime: &tokio::runtime::Handle,
         command: &str,
@@ -3414,6 +3562,7 @@ impl LspHandle {
         async_bridge: &AsyncBridge,
         process_limits: ProcessLimits,
         language_id_overrides: std::collections::HashMap<String, String>,
+        remote: Option<Arc<dyn ProcessSpawner>>,
     ) -> Result<Self, String> {
         let (command_tx, command_rx) = mpsc::channel(100); // Buffer up to 100 commands
         let async_tx = async_bridge.sender();
@@ -3444,6 +3593,7 @@ impl LspHandle {
                 &process_limits,
                 stderr_log_path_clone.clone(),
                 language_id_overrides,
+                remote,
             )
             .await
             {
@@ -3782,6 +3932,13 @@ impl LspHandle {
             .map_err(|_| "Failed to send code_actions command".to_string())
     }
 
+    /// Run a code action's command on the server
+    pub fn execute_command(&self, command: lsp_types::Command) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::ExecuteCommand { command })
+            .map_err(|_| "Failed to send execute_command command".to_string())
+    }
+
     /// Request document diagnostics (pull model)
     ///
     /// This sends a textDocument/diagnostic request to fetch diagnostics on demand.
@@ -3881,6 +4038,14 @@ impl LspHandle {
             .map_err(|_| "Failed to send cancel_request command".to_string())
     }
 
+    /// Measure the server's round-trip latency; the result arrives as
+    /// `AsyncMessage::LspLatencyProbe`
+    pub fn latency_probe(&self, request_id: u64) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::LatencyProbe { request_id })
+            .map_err(|_| "Failed to send latency_probe command".to_string())
+    }
+
     /// Send a custom LSP request initiated by a plugin
     pub fn send_plugin_request(
         &self,
@@ -4111,6 +4276,7 @@ mod tests {
             &async_bridge,
             ProcessLimits::unlimited(),
             Default::default(),
+            None,
         );
 
         // Should succeed in spawning
@@ -4139,6 +4305,7 @@ mod tests {
             &async_bridge,
             ProcessLimits::unlimited(),
             Default::default(),
+            None,
         )
         .unwrap();
 
@@ -4167,6 +4334,7 @@ mod tests {
             &async_bridge,
             ProcessLimits::unlimited(),
             Default::default(),
+            None,
         )
         .unwrap();
 
@@ -4201,6 +4369,7 @@ mod tests {
             &async_bridge,
             ProcessLimits::unlimited(),
             Default::default(),
+            None,
         )
         .unwrap();
 
@@ -4236,6 +4405,7 @@ mod tests {
             &async_bridge,
             ProcessLimits::unlimited(),
             Default::default(),
+            None,
         );
 
         // Should succeed in creating handle (error happens asynchronously)
@@ -4275,6 +4445,7 @@ mod tests {
                     &async_bridge,
                     ProcessLimits::unlimited(),
                     Default::default(),
+                    None,
                 )
                 .unwrap()
             });
@@ -4346,6 +4517,7 @@ mod tests {
             &async_bridge,
             ProcessLimits::unlimited(),
             Default::default(),
+            None,
         )
         .unwrap();
 
@@ -4397,6 +4569,7 @@ mod tests {
             &async_bridge,
             ProcessLimits::unlimited(),
             Default::default(),
+            None,
         )
         .unwrap();
 
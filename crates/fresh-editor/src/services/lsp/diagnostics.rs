@@ -154,34 +154,42 @@ pub fn diagnostic_to_overlay(
     let end_byte = buffer.lsp_position_to_byte(end_line, end_char);
 
     // Determine overlay face based on diagnostic severity using theme colors
-    let (face, priority) = match diagnostic.severity {
+    let attrs = theme.distinctions();
+    let (color, modifier, priority) = match diagnostic.severity {
         Some(DiagnosticSeverity::ERROR) => (
-            OverlayFace::Background {
-                color: theme.diagnostic_error_bg,
-            },
+            theme.diagnostic_error_bg,
+            attrs.diagnostic_error,
             100, // Highest priority
         ),
         Some(DiagnosticSeverity::WARNING) => (
-            OverlayFace::Background {
-                color: theme.diagnostic_warning_bg,
-            },
+            theme.diagnostic_warning_bg,
+            attrs.diagnostic_warning,
             50, // Medium priority
         ),
         Some(DiagnosticSeverity::INFORMATION) => (
-            OverlayFace::Background {
-                color: theme.diagnostic_info_bg,
-            },
+            theme.diagnostic_info_bg,
+            attrs.diagnostic_info,
             30, // Lower priority
         ),
         Some(DiagnosticSeverity::HINT) | None => (
-            OverlayFace::Background {
-                color: theme.diagnostic_hint_bg,
-            },
+            theme.diagnostic_hint_bg,
+            attrs.diagnostic_hint,
             10, // Lowest priority
         ),
         _ => return None, // Unknown severity
     };
 
+    // Severity also differs by text attributes when the theme asks for it
+    let face = if modifier.is_empty() {
+        OverlayFace::Background { color }
+    } else {
+        OverlayFace::Style {
+            style: ratatui::style::Style::default()
+                .bg(color)
+                .add_modifier(modifier),
+        }
+    };
+
     Some((start_byte..end_byte, face, priority))
 }
 
@@ -0,0 +1,235 @@
+//! Installing language servers downloaded from `lsp_downloads` URLs.
+//!
+//! When a server's command can't be found and its language has an
+//! `lsp_downloads` entry, the editor offers to fetch it. The file's SHA-256
+//! must match the configured checksum before anything is written. Files
+//! whose URL ends in `.gz` are decompressed (up to [`MAX_BINARY_BYTES`]),
+//! and the result is installed as an executable under
+//! `<data_dir>/lsp-servers/<language>/`, next to a `.sha256` file
+//! recording which download it came from. A binary is only
+//! reused while that record matches the configured checksum, so changing
+//! the URL and checksum in the config leads to a fresh download.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+
+use crate::config::LspDownload;
+
+/// Largest server download accepted (200 MiB)
+pub const MAX_SERVER_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Largest server binary accepted after decompression (512 MiB), so a
+/// corrupt or malicious archive can't exhaust memory
+pub const MAX_BINARY_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Time allowed for a server download
+pub const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Directory downloaded servers for `language` are installed into
+pub fn install_dir(data_dir: &Path, language: &str) -> PathBuf {
+    data_dir.join("lsp-servers").join(language)
+}
+
+/// Path the server binary for `command` is installed at. Only the file name
+/// of `command` is used, so `/usr/bin/rust-analyzer` and `rust-analyzer`
+/// install to the same place.
+pub fn binary_path(data_dir: &Path, language: &str, command: &str) -> PathBuf {
+    let name = Path::new(command)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| language.to_string());
+    install_dir(data_dir, language).join(name)
+}
+
+/// The installed binary for `command`, if one was downloaded from `download`
+pub fn installed_binary(
+    data_dir: &Path,
+    language: &str,
+    command: &str,
+    download: &LspDownload,
+) -> Option<PathBuf> {
+    let binary = binary_path(data_dir, language, command);
+    let recorded = std::fs::read_to_string(checksum_path(&binary)).ok()?;
+    (binary.is_file() && recorded.trim().eq_ignore_ascii_case(download.sha256.trim()))
+        .then_some(binary)
+}
+
+/// Lowercase hex SHA-256 of `bytes`
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Verify `content` (as downloaded) against the configured checksum and
+/// install it as the server binary for `command`. Returns the binary's path.
+pub fn install(
+    data_dir: &Path,
+    language: &str,
+    command: &str,
+    download: &LspDownload,
+    content: &[u8],
+) -> Result<PathBuf, String> {
+    let actual = sha256_hex(content);
+    if !actual.eq_ignore_ascii_case(download.sha256.trim()) {
+        return Err(format!(
+            "checksum mismatch (expected {}, got {})",
+            download.sha256.trim(),
+            actual
+        ));
+    }
+
+    let binary = if is_gzip_url(&download.url) {
+        gunzip(content, MAX_BINARY_BYTES)?
+    } else {
+        content.to_vec()
+    };
+
+    let path = binary_path(data_dir, language, command);
+    let dir = install_dir(data_dir, language);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    // Write next to the target and rename, so a failed write never leaves a
+    // truncated binary that looks installed
+    let partial = path.with_extension("partial");
+    std::fs::write(&partial, &binary).map_err(|e| e.to_string())?;
+    make_executable(&partial)?;
+    std::fs::rename(&partial, &path).map_err(|e| e.to_string())?;
+    std::fs::write(
+        checksum_path(&path),
+        download.sha256.trim().to_ascii_lowercase(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    tracing::info!("Installed {} language server at {:?}", language, path);
+    Ok(path)
+}
+
+/// Host of a download URL, shown when asking for confirmation
+pub fn url_host(url: &str) -> &str {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    without_scheme
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or(without_scheme)
+}
+
+fn is_gzip_url(url: &str) -> bool {
+    url.split(['?', '#'])
+        .next()
+        .is_some_and(|path| path.to_ascii_lowercase().ends_with(".gz"))
+}
+
+/// Decompress a gzip download, refusing output larger than `limit` bytes
+fn gunzip(content: &[u8], limit: u64) -> Result<Vec<u8>, String> {
+    let mut decoded = Vec::new();
+    flate2::read::GzDecoder::new(content)
+        .take(limit + 1)
+        .read_to_end(&mut decoded)
+        .map_err(|e| format!("failed to decompress: {}", e))?;
+    if decoded.len() as u64 > limit {
+        return Err(format!(
+            "decompressed server is larger than {} bytes",
+            limit
+        ));
+    }
+    Ok(decoded)
+}
+
+fn checksum_path(binary: &Path) -> PathBuf {
+    let mut name = binary.as_os_str().to_owned();
+    name.push(".sha256");
+    PathBuf::from(name)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn download_for(url: &str, content: &[u8]) -> LspDownload {
+        LspDownload {
+            url: url.to_string(),
+            sha256: sha256_hex(content),
+        }
+    }
+
+    #[test]
+    fn test_install_verifies_and_records_checksum() {
+        let temp = tempfile::tempdir().unwrap();
+        let content = b"#!/bin/sh\necho server\n";
+        let download = download_for("https://example.com/server", content);
+
+        assert!(installed_binary(temp.path(), "rust", "rust-analyzer", &download).is_none());
+        let path = install(temp.path(), "rust", "rust-analyzer", &download, content).unwrap();
+        assert_eq!(path, temp.path().join("lsp-servers/rust/rust-analyzer"));
+        assert_eq!(std::fs::read(&path).unwrap(), content);
+        assert_eq!(
+            installed_binary(temp.path(), "rust", "/usr/bin/rust-analyzer", &download),
+            Some(path)
+        );
+
+        // A different pinned checksum means a different download
+        let other = download_for("https://example.com/server", b"newer");
+        assert!(installed_binary(temp.path(), "rust", "rust-analyzer", &other).is_none());
+    }
+
+    #[test]
+    fn test_install_rejects_checksum_mismatch() {
+        let temp = tempfile::tempdir().unwrap();
+        let download = download_for("https://example.com/server", b"expected");
+
+        let error =
+            install(temp.path(), "rust", "rust-analyzer", &download, b"tampered").unwrap_err();
+        assert!(error.contains("checksum mismatch"), "{}", error);
+        assert!(!binary_path(temp.path(), "rust", "rust-analyzer").exists());
+    }
+
+    #[test]
+    fn test_install_decompresses_gzip() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"server binary").unwrap();
+        let compressed = encoder.finish().unwrap();
+        let download = download_for("https://example.com/server.gz?raw=1", &compressed);
+
+        let path = install(temp.path(), "go", "gopls", &download, &compressed).unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), b"server binary");
+    }
+
+    #[test]
+    fn test_gunzip_rejects_output_over_limit() {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(&vec![0u8; 64 * 1024]).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(gunzip(&compressed, 64 * 1024).unwrap().len(), 64 * 1024);
+        let error = gunzip(&compressed, 1024).unwrap_err();
+        assert!(error.contains("larger than 1024 bytes"), "{}", error);
+    }
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(
+            url_host("https://github.com/o/r/releases/download/v1/x.gz"),
+            "github.com"
+        );
+        assert_eq!(url_host("http://example.com?x=1"), "example.com");
+    }
+}
@@ -8,9 +8,11 @@
 
 use crate::services::async_bridge::AsyncBridge;
 use crate::services::lsp::async_handler::LspHandle;
+use crate::services::remote::ProcessSpawner;
 use crate::types::LspServerConfig;
 use lsp_types::{SemanticTokensLegend, Uri};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Result of attempting to spawn an LSP server
@@ -52,6 +54,10 @@ pub struct LspManager {
     /// Async bridge for communication
     async_bridge: Option<AsyncBridge>,
 
+    /// Spawner for the remote host when editing remotely; servers then run
+    /// there, next to the files, instead of on this machine
+    remote_spawner: Option<Arc<dyn ProcessSpawner>>,
+
     /// Restart attempt timestamps per language (for tracking restart frequency)
     restart_attempts: HashMap<String, Vec<Instant>>,
 
@@ -69,6 +75,13 @@ pub struct LspManager {
     /// These will not auto-restart until user manually restarts them
     disabled_languages: HashSet<String>,
 
+    /// Whether the workspace is trusted. In an untrusted workspace no server
+    /// starts automatically, as if every language had auto_start=false.
+    workspace_trusted: bool,
+
+    /// Languages the user was already asked about in an untrusted workspace
+    trust_prompted_languages: HashSet<String>,
+
     /// Completion trigger characters per language (from server capabilities)
     completion_trigger_characters: HashMap<String, Vec<String>>,
 
@@ -98,11 +111,14 @@ impl LspManager {
             per_language_root_uris: HashMap::new(),
             runtime: None,
             async_bridge: None,
+            remote_spawner: None,
             restart_attempts: HashMap::new(),
             restart_cooldown: HashSet::new(),
             pending_restarts: HashMap::new(),
             allowed_languages: HashSet::new(),
             disabled_languages: HashSet::new(),
+            workspace_trusted: true,
+            trust_prompted_languages: HashSet::new(),
             completion_trigger_characters: HashMap::new(),
             semantic_token_legends: HashMap::new(),
             semantic_tokens_full_support: HashMap::new(),
@@ -128,6 +144,22 @@ impl LspManager {
         &self.allowed_languages
     }
 
+    /// Set whether the workspace is trusted
+    pub fn set_workspace_trusted(&mut self, trusted: bool) {
+        self.workspace_trusted = trusted;
+    }
+
+    /// Whether the workspace is trusted
+    pub fn is_workspace_trusted(&self) -> bool {
+        self.workspace_trusted
+    }
+
+    /// Whether to ask before starting the server for a language in an
+    /// untrusted workspace. True only the first time per language.
+    pub fn take_trust_prompt(&mut self, language: &str) -> bool {
+        !self.workspace_trusted && self.trust_prompted_languages.insert(language.to_string())
+    }
+
     /// Get the configuration for a specific language
     pub fn get_config(&self, language: &str) -> Option<&LspServerConfig> {
         self.config.get(language)
@@ -246,7 +278,8 @@ impl LspManager {
         }
 
         // Check if auto_start is enabled or language was manually allowed
-        if !config.auto_start && !self.allowed_languages.contains(language) {
+        let auto_start = config.auto_start && self.workspace_trusted;
+        if !auto_start && !self.allowed_languages.contains(language) {
             return LspSpawnResult::NotAutoStart;
         }
 
@@ -266,6 +299,17 @@ impl LspManager {
         self.async_bridge = Some(async_bridge);
     }
 
+    /// Run servers spawned from now on on the remote host through
+    /// `spawner`, or on this machine if None
+    pub fn set_remote_spawner(&mut self, spawner: Option<Arc<dyn ProcessSpawner>>) {
+        self.remote_spawner = spawner;
+    }
+
+    /// Whether servers run on the remote host
+    pub fn is_remote(&self) -> bool {
+        self.remote_spawner.is_some()
+    }
+
     /// Set configuration for a language
     pub fn set_language_config(&mut self, language: String, config: LspServerConfig) {
         self.config.insert(language, config);
@@ -435,6 +479,7 @@ impl LspManager {
             async_bridge,
             config.process_limits.clone(),
             config.language_id_overrides.clone(),
+            self.remote_spawner.clone(),
         ) {
             Ok(handle) => {
                 // Initialize the handle (non-blocking)
@@ -870,6 +915,32 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_lsp_manager_untrusted_workspace_does_not_auto_start() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let mut manager = LspManager::new(None);
+        manager.set_runtime(rt.handle().clone(), AsyncBridge::new());
+        manager.set_language_config(
+            "rust".to_string(),
+            LspServerConfig {
+                enabled: true,
+                command: "rust-analyzer".to_string(),
+                args: vec![],
+                process_limits: crate::services::process_limits::ProcessLimits::unlimited(),
+                auto_start: true,
+                initialization_options: None,
+                env: Default::default(),
+                language_id_overrides: Default::default(),
+            },
+        );
+        manager.set_workspace_trusted(false);
+
+        assert_eq!(manager.try_spawn("rust"), LspSpawnResult::NotAutoStart);
+        // Asked once per language
+        assert!(manager.take_trust_prompt("rust"));
+        assert!(!manager.take_trust_prompt("rust"));
+    }
+
     #[test]
     fn test_lsp_manager_shutdown_all() {
         let mut manager = LspManager::new(None);
@@ -891,6 +962,9 @@ mod tests {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: crate::config::HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: false,
@@ -911,6 +985,9 @@ mod tests {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: crate::config::HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: false,
@@ -931,6 +1008,9 @@ mod tests {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: crate::config::HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: false,
@@ -992,6 +1072,9 @@ mod tests {
                 auto_indent: true,
                 auto_close: None,
                 auto_surround: None,
+                word_characters: None,
+                auto_close_pairs: None,
+                trim_trailing_whitespace_on_save: None,
                 highlighter: crate::config::HighlighterPreference::Auto,
                 textmate_grammar: None,
                 show_whitespace_tabs: false,
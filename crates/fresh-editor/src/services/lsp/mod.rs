@@ -74,6 +74,9 @@
 //!   channels. `LspTask` runs in a separate tokio task, managing the server
 //!   subprocess and JSON-RPC I/O. Each handle has a unique `id` for tracking.
 //!
+//! - **`download`**: Installs servers fetched from `lsp_downloads` URLs after
+//!   verifying their checksums.
+//!
 //! - **`diagnostics`**: Converts LSP diagnostics to editor overlays (colored
 //!   underlines for errors, warnings, etc.).
 //!
@@ -116,6 +119,7 @@
 
 pub mod async_handler;
 pub mod diagnostics;
+pub mod download;
 pub mod manager;
 pub mod semantic_tokens;
 
@@ -5,6 +5,7 @@
 
 pub mod async_bridge;
 pub mod clipboard;
+pub mod file_overrides;
 pub mod fs;
 #[cfg(target_os = "linux")]
 pub mod gpm;
@@ -12,15 +13,23 @@ pub mod log_dirs;
 pub mod lsp;
 pub mod plugins;
 pub mod process_limits;
+pub mod recent_files;
 pub mod recovery;
 pub mod release_checker;
 pub mod remote;
+pub mod saved_macros;
+pub mod settings_bundle;
+pub mod shell_env;
 pub mod signal_handler;
+pub mod startup_profile;
 pub mod status_log;
+pub mod stdin_spool;
 pub mod styled_html;
 pub mod telemetry;
 pub mod terminal;
 pub mod terminal_modes;
 pub mod time_source;
 pub mod tracing_setup;
+pub mod url_fetch;
 pub mod warning_log;
+pub mod workspace_trust;
@@ -0,0 +1,85 @@
+//! Commands plugins registered in the last session
+//!
+//! Plugins load in the background at startup, so their commands would be
+//! missing from the palette for a moment after every launch. The commands
+//! registered once loading finished are remembered in
+//! `plugin_commands.json` in the data directory and registered as
+//! placeholders at the next startup. Invoking one before its plugin has
+//! loaded waits for the load, since plugin actions queue behind it on the
+//! plugin thread. Placeholders no plugin registers again are removed once
+//! loading is done.
+
+use crate::config_io::DirectoryContext;
+use fresh_core::command::Command;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+
+/// Plugin commands remembered across sessions
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PluginCommandCache {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    pub commands: Vec<Command>,
+}
+
+impl PluginCommandCache {
+    /// Load the cache, starting empty if the file is missing or unreadable
+    pub fn load(dir_context: &DirectoryContext) -> Self {
+        let path = dir_context.plugin_commands_path();
+        let mut cache: Self = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| match serde_json::from_str(&content) {
+                Ok(cache) => Some(cache),
+                Err(e) => {
+                    tracing::warn!("Ignoring unreadable {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        cache.path = path;
+        cache
+    }
+
+    /// Write the cache back to disk
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        std::fs::write(&self.path, json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+
+        let mut cache = PluginCommandCache::load(&dir_context);
+        assert!(cache.commands.is_empty());
+        cache.commands.push(Command {
+            name: "Format Table".to_string(),
+            description: "Align the columns of a table".to_string(),
+            action_name: "format_table".to_string(),
+            plugin_name: "tables".to_string(),
+            custom_contexts: Vec::new(),
+        });
+        cache.save().unwrap();
+
+        let names: Vec<_> = PluginCommandCache::load(&dir_context)
+            .commands
+            .into_iter()
+            .map(|command| (command.name, command.action_name))
+            .collect();
+        assert_eq!(
+            names,
+            [("Format Table".to_string(), "format_table".to_string())]
+        );
+    }
+}
@@ -17,6 +17,10 @@ use super::bridge::EditorServiceBridge;
 #[cfg(feature = "plugins")]
 use fresh_plugin_runtime::PluginThreadHandle;
 
+#[cfg(feature = "plugins")]
+type PendingPluginLoad =
+    fresh_plugin_runtime::thread::oneshot::Receiver<(Vec<String>, HashMap<String, PluginConfig>)>;
+
 /// Unified plugin manager that abstracts over the plugin system.
 ///
 /// When the `plugins` feature is enabled, this wraps `PluginThreadHandle`.
@@ -24,6 +28,9 @@ use fresh_plugin_runtime::PluginThreadHandle;
 pub struct PluginManager {
     #[cfg(feature = "plugins")]
     inner: Option<PluginThreadHandle>,
+    /// Directory loads queued by `start_loading_plugins_from_dir_with_config`
+    #[cfg(feature = "plugins")]
+    pending_loads: Vec<PendingPluginLoad>,
     #[cfg(not(feature = "plugins"))]
     _phantom: std::marker::PhantomData<()>,
 }
@@ -51,6 +58,7 @@ impl PluginManager {
                     Ok(handle) => {
                         return Self {
                             inner: Some(handle),
+                            pending_loads: Vec::new(),
                         }
                     }
                     Err(e) => {
@@ -62,7 +70,10 @@ impl PluginManager {
             } else {
                 tracing::info!("Plugins disabled via --no-plugins flag");
             }
-            Self { inner: None }
+            Self {
+                inner: None,
+                pending_loads: Vec::new(),
+            }
         }
 
         #[cfg(not(feature = "plugins"))]
@@ -157,6 +168,69 @@ impl PluginManager {
         (Vec::new(), HashMap::new())
     }
 
+    /// Queue loading plugins from a directory on the plugin thread without
+    /// waiting. Results are collected with `take_finished_plugin_loads`.
+    #[cfg(feature = "plugins")]
+    pub fn start_loading_plugins_from_dir_with_config(
+        &mut self,
+        dir: &Path,
+        plugin_configs: &HashMap<String, PluginConfig>,
+    ) {
+        if let Some(ref manager) = self.inner {
+            self.pending_loads
+                .push(manager.start_loading_plugins_from_dir_with_config(dir, plugin_configs));
+        }
+    }
+
+    /// Queue loading plugins from a directory (no-op when plugins disabled).
+    #[cfg(not(feature = "plugins"))]
+    pub fn start_loading_plugins_from_dir_with_config(
+        &mut self,
+        dir: &Path,
+        plugin_configs: &HashMap<String, PluginConfig>,
+    ) {
+        let _ = (dir, plugin_configs);
+    }
+
+    /// Whether any queued directory load hasn't been collected yet.
+    pub fn plugin_loads_pending(&self) -> bool {
+        #[cfg(feature = "plugins")]
+        {
+            !self.pending_loads.is_empty()
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            false
+        }
+    }
+
+    /// Collect the results of queued directory loads that have finished
+    /// (non-blocking), in the same form as `load_plugins_from_dir_with_config`.
+    pub fn take_finished_plugin_loads(
+        &mut self,
+    ) -> Vec<(Vec<String>, HashMap<String, PluginConfig>)> {
+        #[cfg(feature = "plugins")]
+        {
+            let mut finished = Vec::new();
+            self.pending_loads.retain(|rx| match rx.try_recv() {
+                Ok(result) => {
+                    finished.push(result);
+                    false
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => true,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    finished.push((vec!["Plugin thread closed".to_string()], HashMap::new()));
+                    false
+                }
+            });
+            finished
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            Vec::new()
+        }
+    }
+
     /// Unload a plugin by name.
     pub fn unload_plugin(&self, name: &str) -> anyhow::Result<()> {
         #[cfg(feature = "plugins")]
@@ -10,6 +10,7 @@ pub mod api {
     pub use fresh_core::api::*;
 }
 pub mod bridge;
+pub mod command_cache;
 pub mod event_hooks;
 pub mod hooks;
 pub mod manager;
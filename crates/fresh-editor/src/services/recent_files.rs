@@ -0,0 +1,124 @@
+//! Recently opened files
+//!
+//! Every local file opened in any session is remembered in
+//! `recent_files.json` in the data directory, by canonical path with the
+//! time it was last opened, newest first and capped at
+//! `editor.recent_files_limit` entries. The Recent Files picker lists them.
+//! The file is re-read before each update so several running editors don't
+//! drop each other's entries.
+
+use crate::config_io::DirectoryContext;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A remembered file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentFile {
+    /// Canonical path of the file
+    pub path: PathBuf,
+    /// When the file was last opened, in seconds since the Unix epoch
+    pub opened_at: u64,
+}
+
+/// Recently opened files, newest first
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RecentFiles {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    files: Vec<RecentFile>,
+}
+
+impl RecentFiles {
+    /// Load the list, starting empty if the file is missing or unreadable
+    pub fn load(dir_context: &DirectoryContext) -> Self {
+        let path = dir_context.recent_files_path();
+        let mut recent: Self = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| match serde_json::from_str(&content) {
+                Ok(recent) => Some(recent),
+                Err(e) => {
+                    tracing::warn!("Ignoring unreadable {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        recent.path = path;
+        recent
+    }
+
+    /// Write the list back to disk
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        std::fs::write(&self.path, json)
+    }
+
+    /// Move `path` to the front of the list as opened now, keeping at most
+    /// `limit` files. Returns false for paths that can't be canonicalized,
+    /// which includes files that don't exist yet.
+    pub fn record(&mut self, path: &Path, limit: usize) -> bool {
+        let Ok(path) = std::fs::canonicalize(path) else {
+            return false;
+        };
+        let opened_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        self.files.retain(|file| file.path != path);
+        self.files.insert(0, RecentFile { path, opened_at });
+        self.files.truncate(limit);
+        true
+    }
+
+    /// The remembered files that still exist, newest first
+    pub fn existing(&self) -> impl Iterator<Item = &RecentFile> {
+        self.files.iter().filter(|file| file.path.is_file())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_dedupes_by_canonical_path_and_caps() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+        let dir = temp.path().join("project");
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(dir.join(name), name).unwrap();
+        }
+
+        let mut recent = RecentFiles::load(&dir_context);
+        assert!(recent.record(&dir.join("a.txt"), 2));
+        assert!(recent.record(&dir.join("b.txt"), 2));
+        assert!(recent.record(&dir.join("src").join("..").join("a.txt"), 2));
+        assert!(recent.record(&dir.join("c.txt"), 2));
+        assert!(!recent.record(&dir.join("missing.txt"), 2));
+        recent.save().unwrap();
+
+        let names: Vec<_> = RecentFiles::load(&dir_context)
+            .existing()
+            .map(|file| file.path.file_name().unwrap().to_owned())
+            .collect();
+        assert_eq!(names, ["c.txt", "a.txt"]);
+    }
+
+    #[test]
+    fn test_deleted_files_are_not_listed() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+        let file = temp.path().join("gone.txt");
+        std::fs::write(&file, "").unwrap();
+
+        let mut recent = RecentFiles::load(&dir_context);
+        recent.record(&file, 10);
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(recent.existing().count(), 0);
+    }
+}
@@ -19,6 +19,7 @@
 //! ├── {hash}.meta.json       # Recovery metadata with chunk index
 //! ├── {hash}.chunk.0         # Chunk 0 binary content
 //! ├── {hash}.chunk.1         # Chunk 1 binary content
+//! ├── {hash}.base            # Base snapshot (large unnamed buffers only)
 //! └── ...
 //! ```
 //!
@@ -27,6 +28,20 @@
 //! All recovery data uses a chunked format:
 //! - For small files/new buffers: single chunk containing full content
 //! - For large files: only modified regions stored as chunks
+//! - For large new buffers: a base snapshot plus a delta chunk since the base
+//!
+//! Each file's length and checksum are recorded in the metadata. Entries that
+//! fail verification are listed with [`RecoveryEntry::corruption`] set and are
+//! never restored.
+//!
+//! ## Large Buffers and Disk Usage
+//!
+//! - Buffers above `large_buffer_threshold` are only ever stored as deltas
+//! - After each save a buffer backs off for a multiple of the write duration
+//! - The directory is kept under `max_total_bytes` by pruning entries from
+//!   earlier sessions, oldest first; entries of other running editors are
+//!   never pruned
+//! - A full disk suspends recovery for the session instead of failing edits
 //!
 //! ## Usage
 //!
@@ -54,15 +69,23 @@ pub mod types;
 
 pub use storage::RecoveryStorage;
 pub use types::{
-    generate_buffer_id, path_hash, ChunkMeta, ChunkedRecoveryData, ChunkedRecoveryIndex,
-    InplaceWriteRecovery, RecoveryChunk, RecoveryEntry, RecoveryMetadata, RecoveryResult,
-    SessionInfo, MAX_CHUNK_SIZE,
+    content_checksum, generate_buffer_id, path_hash, BaseSnapshotMeta, ChunkMeta,
+    ChunkedRecoveryData, ChunkedRecoveryIndex, InplaceWriteRecovery, RecoveryChunk, RecoveryEntry,
+    RecoveryMetadata, RecoveryResult, SessionInfo, COMPRESSION_THRESHOLD, INCREMENTAL_THRESHOLD,
+    MAX_CHUNK_SIZE,
 };
 
 use std::collections::HashMap;
 use std::io;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+/// A buffer is not saved again until this many times its last write duration
+/// has passed, keeping recovery IO to a fraction of editing time
+const BACKOFF_FACTOR: u32 = 10;
+
+/// Upper bound for the write-duration backoff
+const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
 
 /// Configuration for the recovery service
 #[derive(Debug, Clone)]
@@ -71,6 +94,13 @@ pub struct RecoveryConfig {
     pub enabled: bool,
     /// Maximum age of recovery files before cleanup (in seconds)
     pub max_recovery_age_secs: u64,
+    /// Whether large chunks are compressed on disk
+    pub compress: bool,
+    /// Buffers of at least this many bytes are "large": they are only ever
+    /// stored as a delta, and the editor shows an indicator while saving them
+    pub large_buffer_threshold: usize,
+    /// Maximum total size of the recovery directory in bytes (0 = unlimited)
+    pub max_total_bytes: u64,
 }
 
 impl Default for RecoveryConfig {
@@ -78,10 +108,28 @@ impl Default for RecoveryConfig {
         Self {
             enabled: true,
             max_recovery_age_secs: 7 * 24 * 60 * 60, // 7 days
+            compress: true,
+            large_buffer_threshold: INCREMENTAL_THRESHOLD,
+            max_total_bytes: 1024 * 1024 * 1024, // 1 GB
         }
     }
 }
 
+/// Delay before a buffer may be saved again, given how long its last save took
+fn backoff_for(write_duration: Duration) -> Duration {
+    write_duration
+        .saturating_mul(BACKOFF_FACTOR)
+        .min(MAX_BACKOFF)
+}
+
+/// Whether an IO error means the disk (or the user's quota) is full
+pub fn is_storage_full(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::StorageFull | io::ErrorKind::QuotaExceeded
+    )
+}
+
 /// The main recovery service
 ///
 /// This is the high-level interface for the recovery system.
@@ -94,6 +142,10 @@ pub struct RecoveryService {
     config: RecoveryConfig,
     /// Last auto-recovery-save time per buffer
     last_save_times: HashMap<String, Instant>,
+    /// Per-buffer time before which auto-saves are skipped
+    backoff_until: HashMap<String, Instant>,
+    /// Set when recovery was switched off for this session (e.g. disk full)
+    suspended: bool,
     /// Session started flag
     session_started: bool,
 }
@@ -105,16 +157,23 @@ impl RecoveryService {
             storage: RecoveryStorage::new()?,
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
+            backoff_until: HashMap::new(),
+            suspended: false,
             session_started: false,
         })
     }
 
     /// Create a new recovery service with custom config
     pub fn with_config(config: RecoveryConfig) -> io::Result<Self> {
+        let mut storage = RecoveryStorage::new()?;
+        storage.set_compression(config.compress);
+        storage.set_incremental_threshold(config.large_buffer_threshold);
         Ok(Self {
-            storage: RecoveryStorage::new()?,
+            storage,
             config,
             last_save_times: HashMap::new(),
+            backoff_until: HashMap::new(),
+            suspended: false,
             session_started: false,
         })
     }
@@ -126,23 +185,40 @@ impl RecoveryService {
             storage: RecoveryStorage::with_dir(storage_dir),
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
+            backoff_until: HashMap::new(),
+            suspended: false,
             session_started: false,
         }
     }
 
     /// Create a new recovery service with custom config and storage directory
     pub fn with_config_and_dir(config: RecoveryConfig, storage_dir: PathBuf) -> Self {
+        let mut storage = RecoveryStorage::with_dir(storage_dir);
+        storage.set_compression(config.compress);
+        storage.set_incremental_threshold(config.large_buffer_threshold);
         Self {
-            storage: RecoveryStorage::with_dir(storage_dir),
+            storage,
             config,
             last_save_times: HashMap::new(),
+            backoff_until: HashMap::new(),
+            suspended: false,
             session_started: false,
         }
     }
 
-    /// Check if recovery is enabled
+    /// Check if recovery is enabled (and not suspended for this session)
     pub fn is_enabled(&self) -> bool {
-        self.config.enabled
+        self.config.enabled && !self.suspended
+    }
+
+    /// Whether recovery was switched off for this session after a write failure
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// Whether a buffer of this size gets the large-buffer treatment
+    pub fn is_large_buffer(&self, size: usize) -> bool {
+        size >= self.config.large_buffer_threshold
     }
 
     /// Get the storage backend
@@ -242,7 +318,7 @@ impl RecoveryService {
     /// Returns true if recovery_pending is true. The recovery_pending flag is now
     /// tracked on the buffer itself (TextBuffer.recovery_pending) rather than in this service.
     pub fn needs_auto_recovery_save(&self, _buffer_id: &str, recovery_pending: bool) -> bool {
-        if !self.config.enabled {
+        if !self.is_enabled() {
             return false;
         }
 
@@ -250,6 +326,15 @@ impl RecoveryService {
         recovery_pending
     }
 
+    /// Check if a buffer is still backing off after a slow recovery write
+    ///
+    /// Periodic saves skip backed-off buffers; the exit flush ignores this.
+    pub fn is_backed_off(&self, buffer_id: &str) -> bool {
+        self.backoff_until
+            .get(buffer_id)
+            .is_some_and(|until| Instant::now() < *until)
+    }
+
     /// Get buffer ID for a path
     pub fn get_buffer_id(&self, path: Option<&Path>) -> String {
         self.storage.get_buffer_id(path)
@@ -275,6 +360,9 @@ impl RecoveryService {
     /// - `line_count`: Number of lines in the buffer
     /// - `original_file_size`: Size of the original file (0 for new buffers)
     /// - `final_size`: Total size after applying all modifications
+    ///
+    /// If the disk is full, recovery is suspended for the rest of the session
+    /// (see [`is_storage_full`]) and the error is returned to the caller.
     #[allow(clippy::too_many_arguments)]
     pub fn save_buffer(
         &mut self,
@@ -286,11 +374,12 @@ impl RecoveryService {
         original_file_size: usize,
         final_size: usize,
     ) -> io::Result<()> {
-        if !self.config.enabled {
+        if !self.is_enabled() {
             return Ok(());
         }
 
-        self.storage.save_recovery(
+        let started = Instant::now();
+        if let Err(e) = self.storage.save_recovery(
             buffer_id,
             chunks,
             original_path,
@@ -298,9 +387,26 @@ impl RecoveryService {
             line_count,
             original_file_size,
             final_size,
-        )?;
-        self.last_save_times
-            .insert(buffer_id.to_string(), Instant::now());
+        ) {
+            if is_storage_full(&e) {
+                self.suspended = true;
+                tracing::warn!(
+                    "Recovery disabled for this session: no space left for recovery files ({})",
+                    e
+                );
+            }
+            return Err(e);
+        }
+        let now = Instant::now();
+        self.backoff_until.insert(
+            buffer_id.to_string(),
+            now + backoff_for(now.duration_since(started)),
+        );
+        self.last_save_times.insert(buffer_id.to_string(), now);
+
+        if let Err(e) = self.enforce_size_limit() {
+            tracing::warn!("Failed to prune recovery directory: {}", e);
+        }
 
         tracing::trace!(
             "Saved recovery for buffer {} (original: {} bytes, final: {} bytes)",
@@ -319,12 +425,16 @@ impl RecoveryService {
 
         self.storage.delete_recovery(buffer_id)?;
         self.last_save_times.remove(buffer_id);
+        self.backoff_until.remove(buffer_id);
 
         tracing::debug!("Deleted recovery for buffer {}", buffer_id);
         Ok(())
     }
 
     /// List all recoverable entries
+    ///
+    /// Entries that failed their integrity check are included (with
+    /// `corruption` set) so they can be reported; `load_recovery` refuses them.
     pub fn list_recoverable(&self) -> io::Result<Vec<RecoveryEntry>> {
         self.storage.list_entries()
     }
@@ -335,6 +445,14 @@ impl RecoveryService {
     /// can apply chunks directly to the buffer (more efficient than full reconstruction).
     /// For new buffer entries (original_file_size == 0), the full content is in the chunks.
     pub fn load_recovery(&self, entry: &RecoveryEntry) -> io::Result<RecoveryResult> {
+        // Never restore content that failed verification
+        if let Some(ref reason) = entry.corruption {
+            return Ok(RecoveryResult::Corrupted {
+                id: entry.id.clone(),
+                reason: format!("{}: {}", entry.metadata.display_name(), reason),
+            });
+        }
+
         // Check if we need the original file for reconstruction
         if entry.metadata.original_file_size > 0 {
             // Large file recovery - return chunks to apply on top of original
@@ -377,6 +495,14 @@ impl RecoveryService {
             }
         }
 
+        // Large new buffer - base snapshot plus delta
+        if let Some(content) = self.storage.read_incremental_content(&entry.id)? {
+            return Ok(RecoveryResult::Recovered {
+                original_path: entry.metadata.original_path.clone(),
+                content,
+            });
+        }
+
         // New buffer or small file - chunk contains full content
         // Load the chunk data directly
         let chunked_data = self
@@ -463,6 +589,57 @@ impl RecoveryService {
         Ok(cleaned)
     }
 
+    /// Keep the recovery directory under `max_total_bytes`
+    ///
+    /// Prunes entries of earlier sessions, oldest first. Entries saved during
+    /// this session, or last saved by another editor that is still running,
+    /// are kept even if that leaves the directory over the limit. Only
+    /// metadata is read, so this is cheap to run after every save. Returns
+    /// the number of pruned entries.
+    pub fn enforce_size_limit(&self) -> io::Result<usize> {
+        if self.config.max_total_bytes == 0 {
+            return Ok(0);
+        }
+        let mut total = self.storage.total_disk_size()?;
+        if total <= self.config.max_total_bytes {
+            return Ok(0);
+        }
+
+        let mut prunable: Vec<_> = self
+            .storage
+            .list_metadata()?
+            .into_iter()
+            .filter(|(id, metadata)| {
+                !self.last_save_times.contains_key(id) && !metadata.is_owned_by_live_session()
+            })
+            .collect();
+        if prunable.is_empty() {
+            return Ok(0);
+        }
+        prunable.sort_by_key(|(_, metadata)| metadata.updated_at);
+
+        let mut pruned = 0;
+        for (id, _) in prunable {
+            if total <= self.config.max_total_bytes {
+                break;
+            }
+            let size = self.storage.entry_disk_size(&id);
+            if self.storage.delete_recovery(&id).is_ok() {
+                total = total.saturating_sub(size);
+                pruned += 1;
+            }
+        }
+
+        if pruned > 0 {
+            tracing::info!(
+                "Pruned {} recovery entries from earlier sessions to stay under {} bytes",
+                pruned,
+                self.config.max_total_bytes
+            );
+        }
+        Ok(pruned)
+    }
+
     /// Clean up orphaned files
     pub fn cleanup_orphans(&self) -> io::Result<usize> {
         self.storage.cleanup_orphans()
@@ -475,6 +652,8 @@ impl Default for RecoveryService {
             storage: RecoveryStorage::default(),
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
+            backoff_until: HashMap::new(),
+            suspended: false,
             session_started: false,
         })
     }
@@ -492,6 +671,8 @@ mod tests {
             storage,
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
+            backoff_until: HashMap::new(),
+            suspended: false,
             session_started: false,
         };
         (service, temp_dir)
@@ -622,4 +803,152 @@ mod tests {
             _ => panic!("Expected RecoveredChunks result, got {:?}", result),
         }
     }
+
+    #[test]
+    fn test_backoff_scales_with_write_duration() {
+        assert_eq!(backoff_for(Duration::ZERO), Duration::ZERO);
+        assert_eq!(
+            backoff_for(Duration::from_millis(300)),
+            Duration::from_secs(3)
+        );
+        // Pathologically slow writes are capped
+        assert_eq!(backoff_for(Duration::from_secs(3600)), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_size_limit_prunes_earlier_sessions_first() {
+        let (mut service, _temp) = create_test_service();
+        service.config.max_total_bytes = 1500;
+        service.start_session().unwrap();
+
+        // Left over from an earlier session
+        let content = vec![b'a'; 1000];
+        let chunks = vec![RecoveryChunk::new(0, 0, content.clone())];
+        service
+            .storage
+            .save_recovery("old", chunks, None, None, None, 0, content.len())
+            .unwrap();
+
+        // Saving a buffer of this session pushes the directory over the limit
+        let chunks = vec![RecoveryChunk::new(0, 0, content.clone())];
+        service
+            .save_buffer("current", chunks, None, None, None, 0, content.len())
+            .unwrap();
+
+        let ids: Vec<_> = service
+            .list_recoverable()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(ids, vec!["current".to_string()]);
+
+        // The current session's data is kept even if still over the limit
+        service.config.max_total_bytes = 1;
+        assert_eq!(service.enforce_size_limit().unwrap(), 0);
+        assert_eq!(service.list_recoverable().unwrap().len(), 1);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_size_limit_keeps_entries_of_running_editors() {
+        let (mut service, temp) = create_test_service();
+        service.config.max_total_bytes = 2500;
+        service.start_session().unwrap();
+
+        let content = vec![b'a'; 1000];
+        for id in ["running", "crashed"] {
+            let chunks = vec![RecoveryChunk::new(0, 0, content.clone())];
+            service
+                .storage
+                .save_recovery(id, chunks, None, None, None, 0, content.len())
+                .unwrap();
+        }
+        // "running" belongs to another editor that is still alive, "crashed"
+        // to one that predates session tracking
+        let set_session_pid = |id: &str, pid: Option<u32>| {
+            let path = temp.path().join(format!("{}.meta.json", id));
+            let mut meta: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            meta["session_pid"] = serde_json::json!(pid);
+            std::fs::write(&path, meta.to_string()).unwrap();
+        };
+        set_session_pid("running", Some(std::os::unix::process::parent_id()));
+        set_session_pid("crashed", None);
+
+        let chunks = vec![RecoveryChunk::new(0, 0, content.clone())];
+        service
+            .save_buffer("current", chunks, None, None, None, 0, content.len())
+            .unwrap();
+
+        let mut ids: Vec<_> = service
+            .list_recoverable()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["current".to_string(), "running".to_string()]);
+
+        // Nothing left that may be pruned
+        service.config.max_total_bytes = 1;
+        assert_eq!(service.enforce_size_limit().unwrap(), 0);
+        assert_eq!(service.list_recoverable().unwrap().len(), 2);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_disk_full_suspends_recovery() {
+        let (mut service, temp) = create_test_service();
+        service.start_session().unwrap();
+
+        // Writes of the first chunk land on /dev/full, which fails with ENOSPC
+        std::os::unix::fs::symlink("/dev/full", temp.path().join("full.chunk.0.tmp")).unwrap();
+        let chunks = vec![RecoveryChunk::new(0, 0, b"content".to_vec())];
+        let error = service
+            .save_buffer("full", chunks, None, None, None, 0, 7)
+            .unwrap_err();
+        assert!(is_storage_full(&error));
+        assert!(service.is_suspended());
+
+        // Later saves are skipped instead of failing again
+        assert!(!service.needs_auto_recovery_save("other", true));
+        let chunks = vec![RecoveryChunk::new(0, 0, b"content".to_vec())];
+        service
+            .save_buffer("other", chunks, None, None, None, 0, 7)
+            .unwrap();
+        assert!(service.list_recoverable().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_suspended_service_skips_saves() {
+        let (mut service, _temp) = create_test_service();
+        service.start_session().unwrap();
+        service.suspended = true;
+
+        assert!(!service.is_enabled());
+        assert!(!service.needs_auto_recovery_save("test", true));
+        let chunks = vec![RecoveryChunk::new(0, 0, b"content".to_vec())];
+        service
+            .save_buffer("test", chunks, None, None, None, 0, 7)
+            .unwrap();
+        assert!(service.list_recoverable().unwrap().is_empty());
+
+        // Ending the session still removes the lock
+        service.end_session().unwrap();
+        assert!(service.storage.read_session_lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_is_storage_full() {
+        assert!(is_storage_full(&io::Error::from(
+            io::ErrorKind::StorageFull
+        )));
+        assert!(is_storage_full(&io::Error::from(
+            io::ErrorKind::QuotaExceeded
+        )));
+        assert!(!is_storage_full(&io::Error::from(
+            io::ErrorKind::PermissionDenied
+        )));
+    }
 }
@@ -2,17 +2,32 @@
 //!
 //! This module handles reading and writing recovery files with atomic operations
 //! to ensure data integrity even during crashes.
+//!
+//! Every file is written to a temp file and renamed into place, and the
+//! metadata (written last) records the length and checksum of each file.
+//! Each save writes its chunk and base files under new names and deletes the
+//! previous save's files only once the new metadata is in place, so a crash
+//! at any point leaves one complete snapshot on disk.
 
 use super::types::{
-    generate_buffer_id, path_hash, ChunkedRecoveryData, ChunkedRecoveryIndex, RecoveryChunk,
-    RecoveryEntry, RecoveryMetadata, SessionInfo,
+    content_checksum, generate_buffer_id, path_hash, BaseSnapshotMeta, ChunkedRecoveryData,
+    ChunkedRecoveryIndex, RecoveryChunk, RecoveryEntry, RecoveryMetadata, SessionInfo,
+    COMPRESSION_THRESHOLD, DELTA_BLOCK_SIZE, INCREMENTAL_THRESHOLD,
 };
 use crate::input::input_history::get_data_dir;
+use std::borrow::Cow;
 use std::fs::{self, File};
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
+/// Size and integrity info of a file written by `write_blob`
+struct StoredBlob {
+    stored_size: usize,
+    checksum: String,
+    compressed: bool,
+}
+
 /// Recovery storage manager
 ///
 /// Handles all file I/O for the recovery system with atomic operations.
@@ -20,6 +35,10 @@ use std::time::SystemTime;
 pub struct RecoveryStorage {
     /// Base directory for recovery files
     recovery_dir: PathBuf,
+    /// Whether large chunks are compressed on disk
+    compress: bool,
+    /// Full-content saves of at least this many bytes use the incremental layout
+    incremental_threshold: usize,
 }
 
 impl RecoveryStorage {
@@ -27,18 +46,36 @@ impl RecoveryStorage {
     const META_EXT: &'static str = "meta.json";
     /// File extension for content files
     const CONTENT_EXT: &'static str = "content";
+    /// File extension for the base snapshot of the incremental layout
+    const BASE_EXT: &'static str = "base";
     /// Session lock file name
     const SESSION_LOCK: &'static str = "session.lock";
 
     /// Create a new recovery storage manager
     pub fn new() -> io::Result<Self> {
         let recovery_dir = Self::get_recovery_dir()?;
-        Ok(Self { recovery_dir })
+        Ok(Self::with_dir(recovery_dir))
     }
 
     /// Create a recovery storage with a custom directory (for testing)
     pub fn with_dir(recovery_dir: PathBuf) -> Self {
-        Self { recovery_dir }
+        Self {
+            recovery_dir,
+            compress: true,
+            incremental_threshold: INCREMENTAL_THRESHOLD,
+        }
+    }
+
+    /// Enable or disable compression of large chunks
+    ///
+    /// Only affects new writes; compressed entries are always readable.
+    pub fn set_compression(&mut self, enabled: bool) {
+        self.compress = enabled;
+    }
+
+    /// Set the buffer size from which full-content saves only store a delta
+    pub fn set_incremental_threshold(&mut self, threshold: usize) {
+        self.incremental_threshold = threshold;
     }
 
     /// Get the recovery directory path
@@ -140,16 +177,41 @@ impl RecoveryStorage {
         (meta_path, content_path)
     }
 
-    /// Get path for a chunk file
-    fn chunk_path(&self, id: &str, chunk_index: usize) -> PathBuf {
-        self.recovery_dir
-            .join(format!("{id}.chunk.{}", chunk_index))
+    /// Get path for a chunk file written by save `generation`
+    ///
+    /// Generation 0 uses the `{id}.chunk.N` names of older versions.
+    fn chunk_path(&self, id: &str, generation: u64, chunk_index: usize) -> PathBuf {
+        let name = if generation == 0 {
+            format!("{id}.chunk.{chunk_index}")
+        } else {
+            format!("{id}.chunk.{generation}.{chunk_index}")
+        };
+        self.recovery_dir.join(name)
+    }
+
+    /// Get path for the base snapshot file written by save `generation`
+    fn base_path(&self, id: &str, generation: u64) -> PathBuf {
+        let name = if generation == 0 {
+            format!("{id}.{}", Self::BASE_EXT)
+        } else {
+            format!("{id}.{}.{generation}", Self::BASE_EXT)
+        };
+        self.recovery_dir.join(name)
     }
 
-    /// List all chunk files for a given ID
+    /// List all chunk files for a given ID, of any generation
     fn list_chunk_paths(&self, id: &str) -> io::Result<Vec<PathBuf>> {
+        self.list_numbered_paths(&format!("{id}.chunk"))
+    }
+
+    /// List all base snapshot files for a given ID, of any generation
+    fn list_base_paths(&self, id: &str) -> io::Result<Vec<PathBuf>> {
+        self.list_numbered_paths(&format!("{id}.{}", Self::BASE_EXT))
+    }
+
+    /// List files named `stem` or `stem` followed by dot-separated numbers
+    fn list_numbered_paths(&self, stem: &str) -> io::Result<Vec<PathBuf>> {
         let mut paths = Vec::new();
-        let prefix = format!("{id}.chunk.");
 
         if !self.recovery_dir.exists() {
             return Ok(paths);
@@ -158,7 +220,14 @@ impl RecoveryStorage {
         for entry in fs::read_dir(&self.recovery_dir)? {
             let entry = entry?;
             if let Some(name) = entry.file_name().to_str() {
-                if name.starts_with(&prefix) {
+                // Skip temp files left by an interrupted write
+                let matches = name.strip_prefix(stem).is_some_and(|rest| {
+                    rest.is_empty()
+                        || rest.strip_prefix('.').is_some_and(|numbers| {
+                            numbers.split('.').all(|n| n.parse::<u64>().is_ok())
+                        })
+                });
+                if matches {
                     paths.push(entry.path());
                 }
             }
@@ -167,11 +236,18 @@ impl RecoveryStorage {
         Ok(paths)
     }
 
-    /// Delete all chunk files for a given ID
-    fn delete_chunk_files(&self, id: &str) -> io::Result<()> {
-        for path in self.list_chunk_paths(id)? {
-            if let Err(e) = fs::remove_file(&path) {
-                tracing::warn!("Failed to remove chunk file {}: {}", path.display(), e);
+    /// Delete all chunk and base files for a given ID
+    fn delete_data_files(&self, id: &str) -> io::Result<()> {
+        self.delete_data_files_except(id, &[])
+    }
+
+    /// Delete the chunk and base files for a given ID that aren't in `keep`
+    fn delete_data_files_except(&self, id: &str, keep: &[PathBuf]) -> io::Result<()> {
+        let mut paths = self.list_chunk_paths(id)?;
+        paths.extend(self.list_base_paths(id)?);
+        for path in paths.iter().filter(|path| !keep.contains(path)) {
+            if let Err(e) = fs::remove_file(path) {
+                tracing::warn!("Failed to remove recovery file {}: {}", path.display(), e);
             }
         }
         Ok(())
@@ -186,7 +262,15 @@ impl RecoveryStorage {
     /// ## File Layout
     ///
     /// - `{id}.meta.json` - Contains RecoveryMetadata with embedded ChunkedRecoveryIndex
-    /// - `{id}.chunk.0`, `{id}.chunk.1`, ... - Raw binary content for each chunk
+    /// - `{id}.chunk.G.0`, `{id}.chunk.G.1`, ... - Raw binary content for each
+    ///   chunk, where G is the save's generation
+    /// - `{id}.base.G` - Base snapshot, only for full-content saves of at least
+    ///   the incremental threshold; the chunks then hold the delta against it
+    ///
+    /// Chunks are written under the new generation's names before the
+    /// metadata, and the previous generation's files are deleted only after
+    /// the metadata has been renamed into place. The metadata carries each
+    /// chunk's checksum, so a file damaged on disk is detected on load.
     #[allow(clippy::too_many_arguments)]
     pub fn save_recovery(
         &self,
@@ -202,23 +286,45 @@ impl RecoveryStorage {
 
         let (meta_path, _content_path) = self.recovery_paths(id);
 
-        // Delete any existing chunk files first
-        self.delete_chunk_files(id)?;
+        // Never overwrite the files the current metadata refers to
+        let previous = self.read_chunked_index(id).ok().flatten();
+        let generation = previous.as_ref().map_or(0, |index| index.generation + 1);
+
+        // Large full-content saves only write what changed since the base
+        let is_full_content = original_file_size == 0 && chunks.len() == 1 && chunks[0].offset == 0;
+        let (chunks, base) =
+            if is_full_content && chunks[0].content.len() >= self.incremental_threshold {
+                let content = chunks
+                    .into_iter()
+                    .next()
+                    .map(|c| c.content)
+                    .unwrap_or_default();
+                let (delta, base) =
+                    self.write_incremental(id, generation, previous.as_ref(), &content)?;
+                (delta, Some(base))
+            } else {
+                (chunks, None)
+            };
 
         // Create chunked data structure
         let chunked_data = ChunkedRecoveryData::new(original_file_size, final_size, chunks);
 
-        // Write each chunk to a separate file
+        // Create the index (metadata without binary content)
+        let mut index = chunked_data.to_index();
+        index.base = base;
+        index.generation = generation;
+
+        // Write each chunk to a separate file, recording its integrity info
         let mut total_chunk_bytes = 0u64;
         for (i, chunk) in chunked_data.chunks.iter().enumerate() {
-            let chunk_path = self.chunk_path(id, i);
-            self.atomic_write(&chunk_path, &chunk.content)?;
+            let blob = self.write_blob(&self.chunk_path(id, generation, i), &chunk.content)?;
+            let chunk_meta = &mut index.chunks[i];
+            chunk_meta.stored_size = Some(blob.stored_size);
+            chunk_meta.checksum = Some(blob.checksum);
+            chunk_meta.compressed = blob.compressed;
             total_chunk_bytes += chunk.content.len() as u64;
         }
 
-        // Create the index (metadata without binary content)
-        let index = chunked_data.to_index();
-
         // Get original file's mtime if it exists
         let original_mtime = original_path.and_then(|p| {
             fs::metadata(p)
@@ -274,9 +380,65 @@ impl RecoveryStorage {
         let meta_json = serde_json::to_string_pretty(&meta_file).map_err(io::Error::other)?;
         self.atomic_write(&meta_path, meta_json.as_bytes())?;
 
+        // Only now drop the files the new metadata no longer refers to
+        let mut keep: Vec<PathBuf> = (0..chunked_data.chunks.len())
+            .map(|i| self.chunk_path(id, generation, i))
+            .collect();
+        if let Some(base) = &meta_file.chunked_index.base {
+            keep.push(self.base_path(id, base.generation));
+        }
+        self.delete_data_files_except(id, &keep)?;
+
         Ok(metadata)
     }
 
+    /// Write the incremental layout for a full-content save
+    ///
+    /// Reuses the base of the `previous` save when the changed region is
+    /// small, returning a single delta chunk against it. Otherwise writes a
+    /// fresh base for save `generation` and returns no chunks.
+    fn write_incremental(
+        &self,
+        id: &str,
+        generation: u64,
+        previous: Option<&ChunkedRecoveryIndex>,
+        content: &[u8],
+    ) -> io::Result<(Vec<RecoveryChunk>, BaseSnapshotMeta)> {
+        let existing_base = previous
+            .and_then(|index| index.base.clone())
+            .filter(|base| self.base_path(id, base.generation).exists());
+
+        if let Some(base) = existing_base {
+            let delta = compute_delta(&base, content);
+            // Rebase once the delta grows past half the buffer
+            if delta.content.len() <= content.len() / 2 {
+                return Ok((vec![delta], base));
+            }
+        }
+
+        let blob = self.write_blob(&self.base_path(id, generation), content)?;
+        let block_count = content.len() / DELTA_BLOCK_SIZE;
+        let base = BaseSnapshotMeta {
+            size: content.len(),
+            stored_size: blob.stored_size,
+            checksum: blob.checksum,
+            compressed: blob.compressed,
+            head_blocks: (0..block_count)
+                .map(|i| {
+                    content_checksum(&content[i * DELTA_BLOCK_SIZE..(i + 1) * DELTA_BLOCK_SIZE])
+                })
+                .collect(),
+            tail_blocks: (0..block_count)
+                .map(|i| {
+                    let end = content.len() - i * DELTA_BLOCK_SIZE;
+                    content_checksum(&content[end - DELTA_BLOCK_SIZE..end])
+                })
+                .collect(),
+            generation,
+        };
+        Ok((Vec::new(), base))
+    }
+
     /// Read chunked recovery index from metadata file
     pub fn read_chunked_index(&self, id: &str) -> io::Result<Option<ChunkedRecoveryIndex>> {
         let (meta_path, _) = self.recovery_paths(id);
@@ -313,7 +475,7 @@ impl RecoveryStorage {
         // Load content for each chunk from its file
         let mut chunks = Vec::with_capacity(index.chunks.len());
         for (i, chunk_meta) in index.chunks.iter().enumerate() {
-            let chunk_path = self.chunk_path(id, i);
+            let chunk_path = self.chunk_path(id, index.generation, i);
             if !chunk_path.exists() {
                 return Err(io::Error::new(
                     io::ErrorKind::NotFound,
@@ -321,7 +483,13 @@ impl RecoveryStorage {
                 ));
             }
 
-            let content = fs::read(&chunk_path)?;
+            let content = self.read_blob(
+                &chunk_path,
+                chunk_meta.size,
+                chunk_meta.stored_size,
+                chunk_meta.checksum.as_deref(),
+                chunk_meta.compressed,
+            )?;
 
             chunks.push(RecoveryChunk {
                 offset: chunk_meta.offset,
@@ -368,29 +536,69 @@ impl RecoveryStorage {
             ));
         }
 
-        // Apply chunks to reconstruct content
-        let mut result = Vec::with_capacity(chunked_data.final_size);
-        let mut original_pos = 0;
-
-        for chunk in &chunked_data.chunks {
-            // Copy unchanged content before this chunk
-            if chunk.offset > original_pos {
-                result.extend_from_slice(&original_content[original_pos..chunk.offset]);
-            }
+        Ok(apply_chunks(
+            &original_content,
+            &chunked_data.chunks,
+            chunked_data.final_size,
+        ))
+    }
 
-            // Insert the modified chunk content
-            result.extend_from_slice(&chunk.content);
+    /// Reconstruct full content of an entry stored in the incremental layout
+    ///
+    /// Returns `None` if the entry has no base snapshot (use the regular
+    /// chunked path instead).
+    pub fn read_incremental_content(&self, id: &str) -> io::Result<Option<Vec<u8>>> {
+        let base = match self.read_chunked_index(id)?.and_then(|index| index.base) {
+            Some(base) => base,
+            None => return Ok(None),
+        };
+        let base_content = self.read_blob(
+            &self.base_path(id, base.generation),
+            base.size,
+            Some(base.stored_size),
+            Some(&base.checksum),
+            base.compressed,
+        )?;
+        let chunked_data = self.read_chunked_content(id)?.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "Chunked recovery data not found")
+        })?;
+        Ok(Some(apply_chunks(
+            &base_content,
+            &chunked_data.chunks,
+            chunked_data.final_size,
+        )))
+    }
 
-            // Move past the replaced region in the original
-            original_pos = chunk.offset + chunk.original_len;
+    /// Check that every file an entry refers to is present and intact
+    ///
+    /// Returns a human-readable reason on failure.
+    pub fn verify_entry(&self, id: &str) -> Result<(), String> {
+        let index = match self.read_chunked_index(id) {
+            Ok(Some(index)) => index,
+            Ok(None) => return Err("missing chunk index".to_string()),
+            Err(e) => return Err(format!("unreadable metadata: {e}")),
+        };
+        for (i, chunk_meta) in index.chunks.iter().enumerate() {
+            self.read_blob(
+                &self.chunk_path(id, index.generation, i),
+                chunk_meta.size,
+                chunk_meta.stored_size,
+                chunk_meta.checksum.as_deref(),
+                chunk_meta.compressed,
+            )
+            .map_err(|e| e.to_string())?;
         }
-
-        // Copy any remaining content after the last chunk
-        if original_pos < original_content.len() {
-            result.extend_from_slice(&original_content[original_pos..]);
+        if let Some(base) = index.base {
+            self.read_blob(
+                &self.base_path(id, base.generation),
+                base.size,
+                Some(base.stored_size),
+                Some(&base.checksum),
+                base.compressed,
+            )
+            .map_err(|e| e.to_string())?;
         }
-
-        Ok(result)
+        Ok(())
     }
 
     /// Read recovery metadata
@@ -430,9 +638,9 @@ impl RecoveryStorage {
             )
         })?;
 
-        // Require at least one chunk file
+        // Require at least one chunk file (or a base snapshot)
         let chunk_paths = self.list_chunk_paths(id)?;
-        if chunk_paths.is_empty() {
+        if chunk_paths.is_empty() && self.list_base_paths(id)?.is_empty() {
             return Ok(None);
         }
 
@@ -441,6 +649,7 @@ impl RecoveryStorage {
             metadata,
             content_path,
             metadata_path: meta_path,
+            corruption: None,
         }))
     }
 
@@ -453,8 +662,8 @@ impl RecoveryStorage {
             fs::remove_file(&content_path)?;
         }
 
-        // Delete chunk files (for Chunked format)
-        self.delete_chunk_files(id)?;
+        // Delete chunk files and base snapshots (for Chunked format)
+        self.delete_data_files(id)?;
 
         // Delete metadata file
         if meta_path.exists() {
@@ -464,27 +673,85 @@ impl RecoveryStorage {
         Ok(())
     }
 
-    /// List all recovery entries
-    pub fn list_entries(&self) -> io::Result<Vec<RecoveryEntry>> {
+    /// Total size on disk of all files belonging to an entry
+    pub fn entry_disk_size(&self, id: &str) -> u64 {
+        let (meta_path, content_path) = self.recovery_paths(id);
+        let chunk_paths = self.list_chunk_paths(id).unwrap_or_default();
+        let base_paths = self.list_base_paths(id).unwrap_or_default();
+        [meta_path, content_path]
+            .iter()
+            .chain(chunk_paths.iter())
+            .chain(base_paths.iter())
+            .filter_map(|path| fs::metadata(path).ok())
+            .map(|m| m.len())
+            .sum()
+    }
+
+    /// Total size on disk of the recovery directory
+    pub fn total_disk_size(&self) -> io::Result<u64> {
         if !self.recovery_dir.exists() {
-            return Ok(Vec::new());
+            return Ok(0);
         }
 
-        let mut entries = Vec::new();
+        let mut total = 0;
+        for entry in fs::read_dir(&self.recovery_dir)? {
+            let metadata = entry?.metadata()?;
+            if metadata.is_file() {
+                total += metadata.len();
+            }
+        }
+        Ok(total)
+    }
+
+    /// IDs of all entries that have a metadata file
+    fn list_entry_ids(&self) -> io::Result<Vec<String>> {
+        if !self.recovery_dir.exists() {
+            return Ok(Vec::new());
+        }
 
+        let suffix = format!(".{}", Self::META_EXT);
+        let mut ids = Vec::new();
         for entry in fs::read_dir(&self.recovery_dir)? {
-            let entry = entry?;
-            let path = entry.path();
+            let path = entry?.path();
+            // Look for .meta.json files; the ID is everything before the suffix
+            if let Some(id) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|name| name.strip_suffix(&suffix))
+            {
+                ids.push(id.to_string());
+            }
+        }
+        Ok(ids)
+    }
 
-            // Look for .meta.json files
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.ends_with(&format!(".{}", Self::META_EXT)) {
-                    // Extract the ID (everything before .meta.json)
-                    let id = name.trim_end_matches(&format!(".{}", Self::META_EXT));
-                    if let Ok(Some(entry)) = self.load_entry(id) {
-                        entries.push(entry);
-                    }
+    /// List the metadata of all entries without verifying their data files
+    ///
+    /// Cheap enough to call on every save, unlike [`Self::list_entries`].
+    /// Entries whose metadata can't be read are skipped.
+    pub fn list_metadata(&self) -> io::Result<Vec<(String, RecoveryMetadata)>> {
+        Ok(self
+            .list_entry_ids()?
+            .into_iter()
+            .filter_map(|id| {
+                let metadata = self.read_metadata(&id).ok().flatten()?;
+                Some((id, metadata))
+            })
+            .collect())
+    }
+
+    /// List all recovery entries
+    pub fn list_entries(&self) -> io::Result<Vec<RecoveryEntry>> {
+        let mut entries = Vec::new();
+
+        for id in self.list_entry_ids()? {
+            if let Ok(Some(mut entry)) = self.load_entry(&id) {
+                // Corrupt entries are still listed so they can be reported
+                if let Err(reason) = self.verify_entry(&id) {
+                    tracing::warn!("Recovery entry {} failed integrity check: {}", id, reason);
+                    entry.corruption = Some(reason);
                 }
+                entries.push(entry);
             }
         }
 
@@ -520,6 +787,15 @@ impl RecoveryStorage {
                 } else if name.ends_with(&format!(".{}", Self::CONTENT_EXT)) {
                     name.trim_end_matches(&format!(".{}", Self::CONTENT_EXT))
                         .to_string()
+                } else if name.ends_with(&format!(".{}", Self::BASE_EXT)) {
+                    name.trim_end_matches(&format!(".{}", Self::BASE_EXT))
+                        .to_string()
+                } else if name.contains(&format!(".{}.", Self::BASE_EXT)) {
+                    // Handle base files like "id.base.3"
+                    name.split(&format!(".{}.", Self::BASE_EXT))
+                        .next()
+                        .unwrap_or("")
+                        .to_string()
                 } else if name.contains(".chunk.") {
                     // Handle chunk files like "id.chunk.0"
                     name.split(".chunk.").next().unwrap_or("").to_string()
@@ -536,8 +812,10 @@ impl RecoveryStorage {
                 let (meta_path, _content_path) = self.recovery_paths(&id);
                 let chunk_paths = self.list_chunk_paths(&id).unwrap_or_default();
 
-                // Need meta + chunk files
-                let is_valid = meta_path.exists() && !chunk_paths.is_empty();
+                // Need meta + chunk files (or a base snapshot)
+                let base_paths = self.list_base_paths(&id).unwrap_or_default();
+                let is_valid =
+                    meta_path.exists() && (!chunk_paths.is_empty() || !base_paths.is_empty());
 
                 if !is_valid {
                     if let Err(e) = fs::remove_file(&meta_path) {
@@ -547,7 +825,7 @@ impl RecoveryStorage {
                             e
                         );
                     }
-                    if let Err(e) = self.delete_chunk_files(&id) {
+                    if let Err(e) = self.delete_data_files(&id) {
                         tracing::warn!("Failed to remove orphan chunk files for {}: {}", id, e);
                     }
                     cleaned += 1;
@@ -667,7 +945,7 @@ impl RecoveryStorage {
     /// page cache survives), but system crashes/power loss could leave corrupted recovery
     /// files. Async I/O would let us have both safety and performance.
     fn atomic_write(&self, target: &Path, content: &[u8]) -> io::Result<()> {
-        let temp_path = target.with_extension("tmp");
+        let temp_path = Self::temp_path(target);
 
         // Write to temp file
         let mut file = File::create(&temp_path)?;
@@ -679,13 +957,168 @@ impl RecoveryStorage {
 
         Ok(())
     }
+
+    /// Temp file used by `atomic_write` ("{name}.tmp", so chunk temp files
+    /// never look like chunks)
+    fn temp_path(target: &Path) -> PathBuf {
+        let mut name = target.file_name().unwrap_or_default().to_os_string();
+        name.push(".tmp");
+        target.with_file_name(name)
+    }
+
+    /// Atomically write a chunk or base file, compressing it when worthwhile
+    fn write_blob(&self, target: &Path, content: &[u8]) -> io::Result<StoredBlob> {
+        let compressed = if self.compress && content.len() >= COMPRESSION_THRESHOLD {
+            Some(compress(content)?)
+        } else {
+            None
+        };
+        // Keep the compressed form only when it actually saves space
+        let (bytes, compressed) = match compressed {
+            Some(c) if c.len() < content.len() => (Cow::Owned(c), true),
+            _ => (Cow::Borrowed(content), false),
+        };
+
+        self.atomic_write(target, &bytes)?;
+
+        Ok(StoredBlob {
+            stored_size: bytes.len(),
+            checksum: content_checksum(&bytes),
+            compressed,
+        })
+    }
+
+    /// Read a chunk or base file, verifying its length and checksum
+    ///
+    /// `stored_size` and `checksum` are None for entries written before
+    /// integrity info existed; those are checked against `size` only.
+    fn read_blob(
+        &self,
+        path: &Path,
+        size: usize,
+        stored_size: Option<usize>,
+        checksum: Option<&str>,
+        compressed: bool,
+    ) -> io::Result<Vec<u8>> {
+        let corrupted = |reason: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), reason),
+            )
+        };
+
+        let bytes = fs::read(path).map_err(|e| corrupted(e.to_string()))?;
+
+        let expected_len = stored_size.unwrap_or(size);
+        if bytes.len() != expected_len {
+            return Err(corrupted(format!(
+                "expected {} bytes, found {} (truncated write?)",
+                expected_len,
+                bytes.len()
+            )));
+        }
+        if let Some(expected) = checksum {
+            if content_checksum(&bytes) != expected {
+                return Err(corrupted("checksum mismatch".to_string()));
+            }
+        }
+
+        if !compressed {
+            return Ok(bytes);
+        }
+        let content = decompress(&bytes, size).map_err(|e| corrupted(e.to_string()))?;
+        if content.len() != size {
+            return Err(corrupted(format!(
+                "expected {} bytes after decompression, found {}",
+                size,
+                content.len()
+            )));
+        }
+        Ok(content)
+    }
+}
+
+/// Apply chunks (sorted by offset) on top of `original`
+fn apply_chunks(original: &[u8], chunks: &[RecoveryChunk], final_size: usize) -> Vec<u8> {
+    let mut result = Vec::with_capacity(final_size);
+    let mut original_pos = 0;
+
+    for chunk in chunks {
+        // Copy unchanged content before this chunk
+        if chunk.offset > original_pos {
+            result.extend_from_slice(&original[original_pos..chunk.offset]);
+        }
+
+        // Insert the modified chunk content
+        result.extend_from_slice(&chunk.content);
+
+        // Move past the replaced region in the original
+        original_pos = chunk.offset + chunk.original_len;
+    }
+
+    // Copy any remaining content after the last chunk
+    if original_pos < original.len() {
+        result.extend_from_slice(&original[original_pos..]);
+    }
+
+    result
+}
+
+/// Compute a single chunk that turns `base` into `content`
+///
+/// The unchanged prefix and suffix are found by comparing block checksums, so
+/// the base doesn't have to be read back from disk.
+fn compute_delta(base: &BaseSnapshotMeta, content: &[u8]) -> RecoveryChunk {
+    let max_common = base.size.min(content.len());
+
+    let mut prefix = 0;
+    for (i, expected) in base.head_blocks.iter().enumerate() {
+        let end = (i + 1) * DELTA_BLOCK_SIZE;
+        if end > max_common || content_checksum(&content[end - DELTA_BLOCK_SIZE..end]) != *expected
+        {
+            break;
+        }
+        prefix = end;
+    }
+
+    let mut suffix = 0;
+    for (i, expected) in base.tail_blocks.iter().enumerate() {
+        let len = (i + 1) * DELTA_BLOCK_SIZE;
+        if prefix + len > max_common {
+            break;
+        }
+        let start = content.len() - len;
+        if content_checksum(&content[start..start + DELTA_BLOCK_SIZE]) != *expected {
+            break;
+        }
+        suffix = len;
+    }
+
+    RecoveryChunk::new(
+        prefix,
+        base.size - prefix - suffix,
+        content[prefix..content.len() - suffix].to_vec(),
+    )
+}
+
+/// zstd level for recovery content: saves run often, so favour speed
+const ZSTD_LEVEL: i32 = 1;
+
+/// Compress recovery content with zstd
+fn compress(content: &[u8]) -> io::Result<Vec<u8>> {
+    zstd::encode_all(content, ZSTD_LEVEL)
+}
+
+/// Decompress zstd-compressed recovery content
+fn decompress(bytes: &[u8], size_hint: usize) -> io::Result<Vec<u8>> {
+    let mut content = Vec::with_capacity(size_hint);
+    zstd::stream::read::Decoder::new(bytes)?.read_to_end(&mut content)?;
+    Ok(content)
 }
 
 impl Default for RecoveryStorage {
     fn default() -> Self {
-        Self::new().unwrap_or_else(|_| Self {
-            recovery_dir: std::env::temp_dir().join("fresh-recovery"),
-        })
+        Self::new().unwrap_or_else(|_| Self::with_dir(std::env::temp_dir().join("fresh-recovery")))
     }
 }
 
@@ -696,9 +1129,7 @@ mod tests {
 
     fn create_test_storage() -> (RecoveryStorage, TempDir) {
         let temp_dir = TempDir::new().unwrap();
-        let storage = RecoveryStorage {
-            recovery_dir: temp_dir.path().to_path_buf(),
-        };
+        let storage = RecoveryStorage::with_dir(temp_dir.path().to_path_buf());
         (storage, temp_dir)
     }
 
@@ -749,6 +1180,7 @@ mod tests {
         assert_eq!(chunked_data.chunks[0].content, content);
 
         // Verify checksum
+        assert!(storage.verify_entry(&id).is_ok());
     }
 
     #[test]
@@ -827,7 +1259,7 @@ mod tests {
         assert_eq!(read_content, content);
 
         // Temp file should not exist
-        let temp_path = target.with_extension("tmp");
+        let temp_path = RecoveryStorage::temp_path(&target);
         assert!(!temp_path.exists());
     }
 
@@ -867,15 +1299,15 @@ mod tests {
         assert_eq!(metadata.original_file_size, original_size);
 
         // Verify chunk files exist
-        assert!(storage.chunk_path(id, 0).exists());
-        assert!(storage.chunk_path(id, 1).exists());
-        assert!(!storage.chunk_path(id, 2).exists()); // Only 2 chunks
+        assert!(storage.chunk_path(id, 0, 0).exists());
+        assert!(storage.chunk_path(id, 0, 1).exists());
+        assert!(!storage.chunk_path(id, 0, 2).exists()); // Only 2 chunks
 
         // Verify chunk file contents
-        let chunk0_content = fs::read(storage.chunk_path(id, 0)).unwrap();
+        let chunk0_content = fs::read(storage.chunk_path(id, 0, 0)).unwrap();
         assert_eq!(chunk0_content, b"INSERTED_AT_START");
 
-        let chunk1_content = fs::read(storage.chunk_path(id, 1)).unwrap();
+        let chunk1_content = fs::read(storage.chunk_path(id, 0, 1)).unwrap();
         assert_eq!(chunk1_content, b"REPLACED");
 
         // Verify metadata file contains chunked_index
@@ -981,18 +1413,18 @@ mod tests {
         // Verify files exist
         let (meta_path, _) = storage.recovery_paths(id);
         assert!(meta_path.exists());
-        assert!(storage.chunk_path(id, 0).exists());
-        assert!(storage.chunk_path(id, 1).exists());
-        assert!(storage.chunk_path(id, 2).exists());
+        assert!(storage.chunk_path(id, 0, 0).exists());
+        assert!(storage.chunk_path(id, 0, 1).exists());
+        assert!(storage.chunk_path(id, 0, 2).exists());
 
         // Delete recovery
         storage.delete_recovery(id).unwrap();
 
         // Verify all files are gone
         assert!(!meta_path.exists());
-        assert!(!storage.chunk_path(id, 0).exists());
-        assert!(!storage.chunk_path(id, 1).exists());
-        assert!(!storage.chunk_path(id, 2).exists());
+        assert!(!storage.chunk_path(id, 0, 0).exists());
+        assert!(!storage.chunk_path(id, 0, 1).exists());
+        assert!(!storage.chunk_path(id, 0, 2).exists());
     }
 
     #[test]
@@ -1001,8 +1433,8 @@ mod tests {
         storage.ensure_dir().unwrap();
 
         // Create orphan chunk files (no metadata)
-        let orphan_chunk0 = storage.chunk_path("orphan", 0);
-        let orphan_chunk1 = storage.chunk_path("orphan", 1);
+        let orphan_chunk0 = storage.chunk_path("orphan", 0, 0);
+        let orphan_chunk1 = storage.chunk_path("orphan", 0, 1);
         fs::write(&orphan_chunk0, b"orphan chunk 0").unwrap();
         fs::write(&orphan_chunk1, b"orphan chunk 1").unwrap();
 
@@ -1053,6 +1485,261 @@ mod tests {
         assert_eq!(large_entry.metadata.original_file_size, 100);
 
         // Both should have valid checksums
+        assert!(new_entry.is_recoverable());
+        assert!(large_entry.is_recoverable());
+    }
+
+    // ========================================================================
+    // Integrity, compression and incremental layout tests
+    // ========================================================================
+
+    /// Content that compresses well but isn't trivially repetitive
+    fn large_content(len: usize) -> Vec<u8> {
+        let line = b"fn main() { println!(\"hello\"); }\n";
+        (0..len)
+            .map(|i| line[i % line.len()] ^ ((i / 4096) as u8 & 1))
+            .collect()
+    }
+
+    #[test]
+    fn test_torn_chunk_write_is_reported_corrupted() {
+        let (storage, _temp) = create_test_storage();
+
+        let content = b"content that will be truncated";
+        let chunks = vec![RecoveryChunk::new(0, 0, content.to_vec())];
+        storage
+            .save_recovery("torn", chunks, None, Some("Torn"), None, 0, content.len())
+            .unwrap();
+
+        // Simulate a crash halfway through writing the chunk
+        fs::write(storage.chunk_path("torn", 0, 0), &content[..10]).unwrap();
+
+        let entries = storage.list_entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].is_recoverable());
+        assert!(entries[0]
+            .corruption
+            .as_ref()
+            .unwrap()
+            .contains("truncated"));
+    }
+
+    #[test]
+    fn test_chunk_checksum_mismatch_is_reported_corrupted() {
+        let (storage, _temp) = create_test_storage();
+
+        let chunks = vec![RecoveryChunk::new(0, 0, b"old snapshot".to_vec())];
+        storage
+            .save_recovery("mixed", chunks, None, None, None, 0, 12)
+            .unwrap();
+
+        // Chunk changed on disk after its metadata was written
+        fs::write(storage.chunk_path("mixed", 0, 0), b"new snapshot").unwrap();
+
+        let reason = storage.verify_entry("mixed").unwrap_err();
+        assert!(reason.contains("checksum mismatch"));
+        assert!(storage.read_chunked_content("mixed").is_err());
+    }
+
+    #[test]
+    fn test_leftover_temp_file_is_not_a_chunk() {
+        let (storage, _temp) = create_test_storage();
+
+        let chunks = vec![RecoveryChunk::new(0, 0, b"intact".to_vec())];
+        storage
+            .save_recovery("tmp", chunks, None, None, None, 0, 6)
+            .unwrap();
+
+        // A crash during the next save leaves only the temp file behind
+        let temp = RecoveryStorage::temp_path(&storage.chunk_path("tmp", 1, 0));
+        fs::write(&temp, b"partial").unwrap();
+
+        assert_eq!(storage.list_chunk_paths("tmp").unwrap().len(), 1);
+        let entries = storage.list_entries().unwrap();
+        assert!(entries[0].is_recoverable());
+        let data = storage.read_chunked_content("tmp").unwrap().unwrap();
+        assert_eq!(data.chunks[0].content, b"intact");
+    }
+
+    #[test]
+    fn test_crashed_save_leaves_previous_snapshot_intact() {
+        let (storage, _temp) = create_test_storage();
+
+        let chunks = vec![RecoveryChunk::new(0, 0, b"first".to_vec())];
+        storage
+            .save_recovery("gen", chunks, None, None, None, 0, 5)
+            .unwrap();
+
+        // The next save wrote its chunk, then crashed before its metadata
+        fs::write(storage.chunk_path("gen", 1, 0), b"second").unwrap();
+
+        assert!(storage.verify_entry("gen").is_ok());
+        let data = storage.read_chunked_content("gen").unwrap().unwrap();
+        assert_eq!(data.chunks[0].content, b"first");
+
+        // A completed save replaces the previous generation's files
+        let chunks = vec![RecoveryChunk::new(0, 0, b"second".to_vec())];
+        storage
+            .save_recovery("gen", chunks, None, None, None, 0, 6)
+            .unwrap();
+        assert!(!storage.chunk_path("gen", 0, 0).exists());
+        assert_eq!(storage.list_chunk_paths("gen").unwrap().len(), 1);
+        let data = storage.read_chunked_content("gen").unwrap().unwrap();
+        assert_eq!(data.chunks[0].content, b"second");
+    }
+
+    #[test]
+    fn test_format_v2_entry_still_loads() {
+        let (storage, _temp) = create_test_storage();
+        storage.ensure_dir().unwrap();
+
+        // Metadata as written by the previous format (no checksums)
+        let meta = r#"{
+            "original_path": null,
+            "buffer_name": "Untitled-1",
+            "created_at": 1,
+            "updated_at": 1,
+            "content_size": 5,
+            "line_count": 1,
+            "original_mtime": null,
+            "format_version": 2,
+            "chunk_count": 1,
+            "original_file_size": 0,
+            "chunked_index": {
+                "original_size": 0,
+                "final_size": 5,
+                "chunks": [{ "offset": 0, "original_len": 0, "size": 5 }]
+            }
+        }"#;
+        fs::write(storage.recovery_dir.join("legacy.meta.json"), meta).unwrap();
+        fs::write(storage.chunk_path("legacy", 0, 0), b"hello").unwrap();
+
+        let entries = storage.list_entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].is_recoverable());
+        let data = storage.read_chunked_content("legacy").unwrap().unwrap();
+        assert_eq!(data.chunks[0].content, b"hello");
+
+        // Without a checksum, truncation is still caught by the length check
+        fs::write(storage.chunk_path("legacy", 0, 0), b"hel").unwrap();
+        assert!(storage.verify_entry("legacy").is_err());
+    }
+
+    #[test]
+    fn test_large_chunk_is_compressed() {
+        let (storage, _temp) = create_test_storage();
+
+        let content = large_content(COMPRESSION_THRESHOLD * 2);
+        let chunks = vec![RecoveryChunk::new(0, 0, content.clone())];
+        storage
+            .save_recovery("big", chunks, None, None, None, 0, content.len())
+            .unwrap();
+
+        let index = storage.read_chunked_index("big").unwrap().unwrap();
+        assert!(index.chunks[0].compressed);
+        let on_disk = fs::metadata(storage.chunk_path("big", 0, 0)).unwrap().len() as usize;
+        assert!(on_disk < content.len());
+
+        let data = storage.read_chunked_content("big").unwrap().unwrap();
+        assert_eq!(data.chunks[0].content, content);
+    }
+
+    #[test]
+    fn test_compression_disabled() {
+        let (mut storage, _temp) = create_test_storage();
+        storage.set_compression(false);
+
+        let content = large_content(COMPRESSION_THRESHOLD * 2);
+        let chunks = vec![RecoveryChunk::new(0, 0, content.clone())];
+        storage
+            .save_recovery("plain", chunks, None, None, None, 0, content.len())
+            .unwrap();
+
+        let index = storage.read_chunked_index("plain").unwrap().unwrap();
+        assert!(!index.chunks[0].compressed);
+        assert_eq!(
+            fs::read(storage.chunk_path("plain", 0, 0)).unwrap(),
+            content
+        );
+    }
+
+    #[test]
+    fn test_incremental_save_writes_only_delta() {
+        let (storage, _temp) = create_test_storage();
+        let save = |content: &[u8]| {
+            let chunks = vec![RecoveryChunk::new(0, 0, content.to_vec())];
+            storage
+                .save_recovery("incr", chunks, None, None, None, 0, content.len())
+                .unwrap()
+        };
+
+        // First save writes the base and no chunks
+        let mut content = large_content(INCREMENTAL_THRESHOLD);
+        save(&content);
+        assert_eq!(storage.list_base_paths("incr").unwrap().len(), 1);
+        assert!(storage.list_chunk_paths("incr").unwrap().is_empty());
+        assert!(storage.load_entry("incr").unwrap().is_some());
+
+        // An insertion in the middle only stores the changed blocks
+        let middle = content.len() / 2;
+        content.splice(middle..middle, b"INSERTED".iter().copied());
+        let metadata = save(&content);
+        assert!((metadata.content_size as usize) < DELTA_BLOCK_SIZE * 2 + 8);
+
+        let restored = storage.read_incremental_content("incr").unwrap().unwrap();
+        assert_eq!(restored, content);
+
+        // Edits at both ends still restore exactly
+        content[0] = b'#';
+        content.push(b'!');
+        save(&content);
+        let restored = storage.read_incremental_content("incr").unwrap().unwrap();
+        assert_eq!(restored, content);
+
+        // Deleting removes the base too
+        storage.delete_recovery("incr").unwrap();
+        assert!(storage.list_base_paths("incr").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_incremental_rebases_after_large_change() {
+        let (storage, _temp) = create_test_storage();
+
+        let content = large_content(INCREMENTAL_THRESHOLD);
+        let chunks = vec![RecoveryChunk::new(0, 0, content)];
+        storage
+            .save_recovery("rebase", chunks, None, None, None, 0, INCREMENTAL_THRESHOLD)
+            .unwrap();
+
+        // Entirely different content: the delta would be the whole buffer
+        let replaced = vec![b'x'; INCREMENTAL_THRESHOLD];
+        let chunks = vec![RecoveryChunk::new(0, 0, replaced.clone())];
+        storage
+            .save_recovery("rebase", chunks, None, None, None, 0, replaced.len())
+            .unwrap();
+
+        assert!(storage.list_chunk_paths("rebase").unwrap().is_empty());
+        let restored = storage.read_incremental_content("rebase").unwrap().unwrap();
+        assert_eq!(restored, replaced);
+    }
+
+    #[test]
+    fn test_torn_base_write_is_reported_corrupted() {
+        let (storage, _temp) = create_test_storage();
+
+        let content = large_content(INCREMENTAL_THRESHOLD);
+        let chunks = vec![RecoveryChunk::new(0, 0, content.clone())];
+        storage
+            .save_recovery("torn-base", chunks, None, None, None, 0, content.len())
+            .unwrap();
+
+        let base_path = storage.base_path("torn-base", 0);
+        let on_disk = fs::read(&base_path).unwrap();
+        fs::write(&base_path, &on_disk[..on_disk.len() / 2]).unwrap();
+
+        let entries = storage.list_entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].is_recoverable());
     }
 
     // ========================================================================
@@ -10,6 +10,14 @@
 //!
 //! For small files or new buffers, there's typically a single chunk containing
 //! the full content. For large files, only modified regions are stored as chunks.
+//!
+//! Large unnamed buffers use an incremental layout instead: a `{id}.base`
+//! snapshot plus a delta chunk describing what changed since the base was
+//! written, so each interval only rewrites the edited region.
+//!
+//! Every chunk and base records the length and SHA-256 checksum of its on-disk
+//! bytes. Entries whose files don't match are reported as corrupted rather than
+//! restored. Entries written before checksums existed are verified by length only.
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -18,6 +26,15 @@ use std::time::SystemTime;
 /// Maximum chunk size for chunked recovery (1 MB)
 pub const MAX_CHUNK_SIZE: usize = 1024 * 1024;
 
+/// Chunks at least this large are zstd-compressed on disk (64 KB)
+pub const COMPRESSION_THRESHOLD: usize = 64 * 1024;
+
+/// Full-content saves at least this large use the base + delta layout (4 MB)
+pub const INCREMENTAL_THRESHOLD: usize = 4 * 1024 * 1024;
+
+/// Block size used to find the unchanged prefix/suffix against the base
+pub const DELTA_BLOCK_SIZE: usize = 64 * 1024;
+
 /// Metadata for a single chunk (stored in JSON, without binary content)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChunkMeta {
@@ -27,6 +44,15 @@ pub struct ChunkMeta {
     pub original_len: usize,
     /// Size of the new content in bytes
     pub size: usize,
+    /// Size of the chunk file on disk (differs from `size` when compressed)
+    #[serde(default)]
+    pub stored_size: Option<usize>,
+    /// SHA-256 of the chunk file bytes (None for entries from older versions)
+    #[serde(default)]
+    pub checksum: Option<String>,
+    /// Whether the chunk file is zstd-compressed
+    #[serde(default)]
+    pub compressed: bool,
 }
 
 /// A chunk with its binary content (used in memory, not serialized directly)
@@ -55,12 +81,15 @@ impl RecoveryChunk {
         self.content.len()
     }
 
-    /// Convert to metadata (without content)
+    /// Convert to metadata (without content or integrity fields)
     pub fn to_meta(&self) -> ChunkMeta {
         ChunkMeta {
             offset: self.offset,
             original_len: self.original_len,
             size: self.content.len(),
+            stored_size: None,
+            checksum: None,
+            compressed: false,
         }
     }
 }
@@ -74,6 +103,40 @@ pub struct ChunkedRecoveryIndex {
     pub final_size: usize,
     /// Metadata for each chunk (content stored in separate .chunk.N files)
     pub chunks: Vec<ChunkMeta>,
+    /// Base snapshot the chunks apply to (incremental layout only)
+    #[serde(default)]
+    pub base: Option<BaseSnapshotMeta>,
+    /// Save that wrote the chunk files. Each save uses new file names, so
+    /// the files of the previous save stay intact until its metadata is
+    /// replaced. Entries from older versions are generation 0.
+    #[serde(default)]
+    pub generation: u64,
+}
+
+/// Metadata for the `{id}.base` snapshot of the incremental layout
+///
+/// Block checksums are kept aligned both from the start and from the end of
+/// the base, so an insertion in the middle of the buffer still lets the
+/// unchanged tail be matched without reading the base back from disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseSnapshotMeta {
+    /// Uncompressed size of the base content
+    pub size: usize,
+    /// Size of the base file on disk
+    pub stored_size: usize,
+    /// SHA-256 of the base file bytes
+    pub checksum: String,
+    /// Whether the base file is zstd-compressed
+    #[serde(default)]
+    pub compressed: bool,
+    /// Checksums of `DELTA_BLOCK_SIZE` blocks counted from the start
+    pub head_blocks: Vec<String>,
+    /// Checksums of `DELTA_BLOCK_SIZE` blocks counted from the end
+    pub tail_blocks: Vec<String>,
+    /// Save that wrote the base file (it is kept across saves that only
+    /// write a delta against it)
+    #[serde(default)]
+    pub generation: u64,
 }
 
 /// Chunked recovery data with full content (used in memory)
@@ -103,6 +166,8 @@ impl ChunkedRecoveryData {
             original_size: self.original_size,
             final_size: self.final_size,
             chunks: self.chunks.iter().map(|c| c.to_meta()).collect(),
+            base: None,
+            generation: 0,
         }
     }
 }
@@ -144,11 +209,19 @@ pub struct RecoveryMetadata {
     /// Original file size (0 for new buffers, needed for reconstruction)
     #[serde(default)]
     pub original_file_size: usize,
+
+    /// Process ID of the editor that last saved this entry (None for
+    /// entries written before this was recorded)
+    #[serde(default)]
+    pub session_pid: Option<u32>,
 }
 
 impl RecoveryMetadata {
     /// Current format version
-    pub const FORMAT_VERSION: u32 = 2;
+    ///
+    /// Version 3 added checksums, compression and the incremental base layout.
+    /// Version 2 entries are still readable.
+    pub const FORMAT_VERSION: u32 = 3;
 
     /// Create new metadata
     pub fn new(
@@ -176,6 +249,7 @@ impl RecoveryMetadata {
             format_version: Self::FORMAT_VERSION,
             chunk_count,
             original_file_size,
+            session_pid: Some(std::process::id()),
         }
     }
 
@@ -188,6 +262,13 @@ impl RecoveryMetadata {
         self.content_size = content_size;
         self.line_count = line_count;
         self.chunk_count = chunk_count;
+        self.session_pid = Some(std::process::id());
+    }
+
+    /// Whether another editor that is still running last saved this entry
+    pub fn is_owned_by_live_session(&self) -> bool {
+        self.session_pid
+            .is_some_and(|pid| pid != std::process::id() && is_process_running(pid))
     }
 
     /// Get a display name for this recovery entry
@@ -271,9 +352,17 @@ pub struct RecoveryEntry {
 
     /// Path to the metadata file
     pub metadata_path: PathBuf,
+
+    /// Why this entry failed its integrity check (None if intact)
+    pub corruption: Option<String>,
 }
 
 impl RecoveryEntry {
+    /// Whether this entry passed its integrity check and can be restored
+    pub fn is_recoverable(&self) -> bool {
+        self.corruption.is_none()
+    }
+
     /// Check if the original file has been modified since recovery was saved.
     /// Returns true if the file was modified (recovery may be invalid).
     /// Returns false if the file is unchanged or doesn't exist.
@@ -390,6 +479,14 @@ pub fn path_hash(path: &std::path::Path) -> String {
     format!("{:x}", hasher.finalize())[..16].to_string()
 }
 
+/// Compute the SHA-256 checksum of recovery file bytes as a hex string
+pub fn content_checksum(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
 /// Generate a unique ID for an unsaved buffer
 pub fn generate_buffer_id() -> String {
     use std::time::SystemTime;
@@ -4,12 +4,15 @@ import sys
 import os
 import json
 import base64
+import queue
 import stat
 import shutil
 import subprocess
 import re
 import threading
 import select
+import signal
+import struct
 from concurrent.futures import ThreadPoolExecutor
 
 CHUNK = 65536
@@ -17,6 +20,10 @@ VERSION = 1
 
 # Active background processes: id -> Popen
 procs = {}
+# Pseudo-terminals of running shells: id -> (master fd, input queue)
+ptys = {}
+# Input of processes started with spawn: id -> input queue
+pipes = {}
 # Request IDs marked for cancellation
 cancelled = set()
 # Lock for thread-safe access to procs/cancelled
@@ -562,6 +569,242 @@ def cmd_cancel(id, p):
     send(id, r={})
 
 
+def cmd_spawn(id, p):
+    """Start a long-running process with piped stdio, such as a language
+    server, streaming its stdout and stderr until it exits."""
+    cmd = p["cmd"]
+    args = p.get("args", [])
+    # ${env:VAR} in a value refers to this host's environment
+    env = dict(os.environ)
+    for name, value in p.get("env", {}).items():
+        env[name] = re.sub(
+            r"\$\{env:([^}]*)\}", lambda m: os.environ.get(m.group(1), ""), value
+        )
+
+    try:
+        proc = subprocess.Popen(
+            [cmd] + args,
+            env=env,
+            stdin=subprocess.PIPE,
+            stdout=subprocess.PIPE,
+            stderr=subprocess.PIPE,
+            bufsize=0,
+        )
+    except FileNotFoundError:
+        send(id, e=f"command not found: {cmd}")
+        return
+    except PermissionError:
+        send(id, e=f"permission denied: {cmd}")
+        return
+
+    # Input is written on its own thread, like a terminal's
+    typed = queue.Queue()
+    with lock:
+        procs[id] = proc
+        pipes[id] = typed
+    send(id, d={"started": proc.pid})
+
+    def write_input():
+        """Write to the process until its input is closed."""
+        while True:
+            data = typed.get()
+            if data is None:
+                break
+            try:
+                while data:
+                    data = data[proc.stdin.write(data):]
+            except OSError:
+                # The process is gone; its output ends as well
+                break
+        with lock:
+            pipes.pop(id, None)
+        try:
+            proc.stdin.close()
+        except OSError:
+            pass
+
+    def stream(pipe, key):
+        """Send what the process writes to one of its outputs."""
+        while True:
+            try:
+                data = os.read(pipe.fileno(), CHUNK)
+            except OSError:
+                break
+            if not data:
+                break
+            send(id, d={key: b64(data)})
+
+    def stream_output():
+        """Send the process's output until it exits."""
+        errors = threading.Thread(target=stream, args=(proc.stderr, "err"), daemon=True)
+        errors.start()
+        try:
+            stream(proc.stdout, "out")
+            errors.join()
+            send(id, r={"code": proc.wait()})
+        except Exception as e:
+            send(id, e=str(e))
+        finally:
+            with lock:
+                procs.pop(id, None)
+                cancelled.discard(id)
+            typed.put(None)
+
+    threading.Thread(target=write_input, daemon=True).start()
+    threading.Thread(target=stream_output, daemon=True).start()
+
+
+def cmd_stdin(id, p):
+    """Write to a process started with spawn, closing its input if asked."""
+    with lock:
+        typed = pipes.get(p["id"])
+    if typed is None:
+        send(id, e="process not found")
+        return
+    if p.get("data"):
+        typed.put(unb64(p["data"]))
+    if p.get("close"):
+        typed.put(None)
+    send(id, r={})
+
+
+# === Terminals ===
+
+
+def set_winsize(fd, cols, rows):
+    """Set the size of a pseudo-terminal; its shell gets SIGWINCH."""
+    import fcntl
+    import termios
+
+    fcntl.ioctl(fd, termios.TIOCSWINSZ, struct.pack("HHHH", rows, cols, 0, 0))
+
+
+def cmd_pty(id, p):
+    """Run the user's login shell on a new pseudo-terminal, streaming its output."""
+    # Unix-only modules, imported here so the agent starts anywhere
+    import fcntl
+    import pty
+    import termios
+
+    cwd = validate_path(p["cwd"]) if p.get("cwd") else os.path.expanduser("~")
+    shell = os.environ.get("SHELL") or "/bin/sh"
+    env = dict(os.environ, TERM="xterm-256color")
+
+    master, slave = pty.openpty()
+    set_winsize(master, p.get("cols", 80), p.get("rows", 24))
+
+    def take_terminal():
+        os.setsid()
+        fcntl.ioctl(0, termios.TIOCSCTTY, 0)
+
+    try:
+        proc = subprocess.Popen(
+            [shell, "-l"],
+            cwd=cwd,
+            stdin=slave,
+            stdout=slave,
+            stderr=slave,
+            env=env,
+            preexec_fn=take_terminal,
+        )
+    except BaseException:
+        os.close(master)
+        raise
+    finally:
+        os.close(slave)
+
+    # Input is written on its own thread: a shell that isn't reading must
+    # not hold up the requests behind its keystrokes
+    typed = queue.Queue()
+    with lock:
+        procs[id] = proc
+        ptys[id] = (master, typed)
+
+    def write_input():
+        """Write keystrokes to the shell, and close the terminal once its
+        output has ended."""
+        gone = False
+        while True:
+            data = typed.get()
+            if data is None:
+                break
+            try:
+                while data and not gone:
+                    data = data[os.write(master, data):]
+            except OSError:
+                # The shell is gone; its output ends as well
+                gone = True
+        with lock:
+            ptys.pop(id, None)
+        os.close(master)
+
+    def stream_output():
+        """Send the shell's output until it exits."""
+        try:
+            while True:
+                try:
+                    data = os.read(master, 4096)
+                except OSError:
+                    # EIO once the shell and everything it started are gone
+                    break
+                if not data:
+                    break
+                send(id, d={"out": b64(data)})
+            send(id, r={"code": proc.wait()})
+        except Exception as e:
+            send(id, e=str(e))
+        finally:
+            with lock:
+                procs.pop(id, None)
+                ptys.pop(id, None)
+                cancelled.discard(id)
+            typed.put(None)
+
+    threading.Thread(target=write_input, daemon=True).start()
+    threading.Thread(target=stream_output, daemon=True).start()
+
+
+def cmd_pty_write(id, p):
+    """Type into a terminal opened with pty."""
+    with lock:
+        entry = ptys.get(p["id"])
+    if entry is None:
+        send(id, e="terminal not found")
+        return
+    entry[1].put(unb64(p["data"]))
+    send(id, r={})
+
+
+def cmd_pty_resize(id, p):
+    """Resize a terminal opened with pty."""
+    with lock:
+        entry = ptys.get(p["id"])
+        if entry is not None:
+            set_winsize(entry[0], p["cols"], p["rows"])
+    if entry is None:
+        send(id, e="terminal not found")
+        return
+    send(id, r={})
+
+
+def cmd_pty_close(id, p):
+    """Hang up a terminal opened with pty, ending its shell."""
+    with lock:
+        proc = procs.get(p["id"]) if p["id"] in ptys else None
+    if not proc:
+        send(id, e="terminal not found")
+        return
+    try:
+        os.killpg(proc.pid, signal.SIGHUP)
+        try:
+            proc.wait(timeout=2)
+        except subprocess.TimeoutExpired:
+            os.killpg(proc.pid, signal.SIGKILL)
+    except ProcessLookupError:
+        pass
+    send(id, r={})
+
+
 # === Method dispatch ===
 
 METHODS = {
@@ -587,8 +830,27 @@ METHODS = {
     "exec": cmd_exec,
     "kill": cmd_kill,
     "cancel": cmd_cancel,
+    "spawn": cmd_spawn,
+    "stdin": cmd_stdin,
+    "pty": cmd_pty,
+    "pty_write": cmd_pty_write,
+    "pty_resize": cmd_pty_resize,
+    "pty_close": cmd_pty_close,
 }
 
+# Handled on the main thread, in the order they arrive: keystrokes and size
+# changes must reach a terminal in order, and only after it is open; the same
+# goes for the input of a spawned process
+IN_ORDER = {"pty", "pty_write", "pty_resize", "spawn", "stdin"}
+
+
+def in_order(line):
+    """Whether a request must not be reordered with the ones around it."""
+    try:
+        return json.loads(line).get("m") in IN_ORDER
+    except (json.JSONDecodeError, AttributeError):
+        return False
+
 
 def handle_request(line):
     """Parse and handle a single request."""
@@ -638,7 +900,10 @@ def main():
         line = line.strip()
         if not line:
             continue
-        pool.submit(handle_request, line)
+        if in_order(line):
+            handle_request(line)
+        else:
+            pool.submit(handle_request, line)
 
     pool.shutdown(wait=True)
 
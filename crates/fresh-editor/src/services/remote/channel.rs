@@ -5,7 +5,7 @@
 use crate::services::remote::protocol::{AgentRequest, AgentResponse};
 use std::collections::HashMap;
 use std::io;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
 use tokio::sync::{mpsc, oneshot};
@@ -50,16 +50,26 @@ struct PendingRequest {
     result_tx: oneshot::Sender<Result<serde_json::Value, String>>,
 }
 
-/// Communication channel with the remote agent
-pub struct AgentChannel {
+/// The SSH process pipes a channel currently talks over
+struct Transport {
     /// Sender to the write task
     write_tx: mpsc::Sender<String>,
+    /// Whether these pipes are still open
+    connected: Arc<AtomicBool>,
+}
+
+/// Communication channel with the remote agent
+///
+/// The pipes can be replaced with [`AgentChannel::reconnect`] after the
+/// connection drops, so the filesystems and process spawners sharing the
+/// channel keep working once a new SSH process is up.
+pub struct AgentChannel {
+    /// Current pipes to the agent
+    transport: Mutex<Transport>,
     /// Pending requests awaiting responses
     pending: Arc<Mutex<HashMap<u64, PendingRequest>>>,
     /// Next request ID
     next_id: AtomicU64,
-    /// Whether the channel is connected
-    connected: Arc<std::sync::atomic::AtomicBool>,
     /// Runtime handle for blocking operations
     runtime_handle: tokio::runtime::Handle,
     /// Capacity for per-request streaming data channels
@@ -82,15 +92,45 @@ impl AgentChannel {
     /// Lower capacity makes channel overflow more likely if `try_send` is used,
     /// which is useful for stress-testing backpressure handling.
     pub fn with_capacity(
-        mut reader: tokio::io::BufReader<tokio::process::ChildStdout>,
-        mut writer: tokio::process::ChildStdin,
+        reader: tokio::io::BufReader<tokio::process::ChildStdout>,
+        writer: tokio::process::ChildStdin,
         data_channel_capacity: usize,
     ) -> Self {
         let pending: Arc<Mutex<HashMap<u64, PendingRequest>>> =
             Arc::new(Mutex::new(HashMap::new()));
-        let connected = Arc::new(std::sync::atomic::AtomicBool::new(true));
         // Capture the runtime handle for later use in blocking operations
         let runtime_handle = tokio::runtime::Handle::current();
+        let transport = Self::start_transport(reader, writer, &pending);
+
+        Self {
+            transport: Mutex::new(transport),
+            pending,
+            next_id: AtomicU64::new(1),
+            runtime_handle,
+            data_channel_capacity,
+        }
+    }
+
+    /// Talk to the agent over new pipes, after the old ones closed
+    ///
+    /// Must be called from within a Tokio runtime context.
+    pub fn reconnect(
+        &self,
+        reader: tokio::io::BufReader<tokio::process::ChildStdout>,
+        writer: tokio::process::ChildStdin,
+    ) {
+        let transport = Self::start_transport(reader, writer, &self.pending);
+        *self.transport.lock().unwrap() = transport;
+    }
+
+    /// Spawn the tasks that write requests to and read responses from
+    /// the agent's pipes
+    fn start_transport(
+        mut reader: tokio::io::BufReader<tokio::process::ChildStdout>,
+        mut writer: tokio::process::ChildStdin,
+        pending: &Arc<Mutex<HashMap<u64, PendingRequest>>>,
+    ) -> Transport {
+        let connected = Arc::new(AtomicBool::new(true));
 
         // Channel for outgoing requests
         let (write_tx, mut write_rx) = mpsc::channel::<String>(64);
@@ -118,24 +158,18 @@ impl AgentChannel {
             loop {
                 line.clear();
                 match reader.read_line(&mut line).await {
-                    Ok(0) => {
-                        // EOF
-                        connected_read.store(false, Ordering::SeqCst);
-                        break;
-                    }
+                    // EOF or a read error
+                    Ok(0) | Err(_) => break,
                     Ok(_) => {
                         if let Ok(resp) = serde_json::from_str::<AgentResponse>(&line) {
                             Self::handle_response(&pending_read, resp).await;
                         }
                     }
-                    Err(_) => {
-                        connected_read.store(false, Ordering::SeqCst);
-                        break;
-                    }
                 }
             }
 
-            // Clean up pending requests on disconnect.
+            // Clean up pending requests on disconnect, before reporting it,
+            // so none made over the next pipes are failed here
             let mut pending = pending_read.lock().unwrap();
             for (id, req) in pending.drain() {
                 match req.result_tx.send(Err("connection closed".to_string())) {
@@ -148,15 +182,13 @@ impl AgentChannel {
                     }
                 }
             }
+            drop(pending);
+            connected_read.store(false, Ordering::SeqCst);
         });
 
-        Self {
+        Transport {
             write_tx,
-            pending,
-            next_id: AtomicU64::new(1),
             connected,
-            runtime_handle,
-            data_channel_capacity,
         }
     }
 
@@ -217,7 +249,11 @@ impl AgentChannel {
 
     /// Check if the channel is connected
     pub fn is_connected(&self) -> bool {
-        self.connected.load(Ordering::SeqCst)
+        self.transport
+            .lock()
+            .unwrap()
+            .connected
+            .load(Ordering::SeqCst)
     }
 
     /// Send a request and wait for the final result (ignoring streaming data)
@@ -250,9 +286,31 @@ impl AgentChannel {
         ),
         ChannelError,
     > {
-        if !self.is_connected() {
-            return Err(ChannelError::ChannelClosed);
-        }
+        let (_, data_rx, result_rx) = self.request_streaming_with_id(method, params).await?;
+        Ok((data_rx, result_rx))
+    }
+
+    /// Like [`Self::request_streaming`], also returning the request's ID, by
+    /// which later requests can refer to what it started
+    pub async fn request_streaming_with_id(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<
+        (
+            u64,
+            mpsc::Receiver<serde_json::Value>,
+            oneshot::Receiver<Result<serde_json::Value, String>>,
+        ),
+        ChannelError,
+    > {
+        let write_tx = {
+            let transport = self.transport.lock().unwrap();
+            if !transport.connected.load(Ordering::SeqCst) {
+                return Err(ChannelError::ChannelClosed);
+            }
+            transport.write_tx.clone()
+        };
 
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
 
@@ -268,12 +326,12 @@ impl AgentChannel {
 
         // Build and send request
         let req = AgentRequest::new(id, method, params);
-        self.write_tx
+        write_tx
             .send(req.to_json_line())
             .await
             .map_err(|_| ChannelError::ChannelClosed)?;
 
-        Ok((data_rx, result_rx))
+        Ok((id, data_rx, result_rx))
     }
 
     /// Send a request synchronously (blocking)
@@ -329,6 +387,11 @@ impl AgentChannel {
             .block_on(self.request_with_data(method, params))
     }
 
+    /// Runtime the channel's tasks run on
+    pub fn runtime_handle(&self) -> &tokio::runtime::Handle {
+        &self.runtime_handle
+    }
+
     /// Cancel a request
     pub async fn cancel(&self, request_id: u64) -> Result<(), ChannelError> {
         use crate::services::remote::protocol::cancel_params;
@@ -3,12 +3,16 @@
 //! Handles spawning SSH process and bootstrapping the Python agent.
 
 use crate::services::remote::channel::AgentChannel;
+use crate::services::remote::passphrase::{terminal_askpass_env, Passphrase};
 use crate::services::remote::protocol::AgentResponse;
+use crate::services::remote::ssh_config::{local_username, SshConfig};
 use crate::services::remote::AGENT_SOURCE;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::{Child, Command};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
 
 /// Error type for SSH connection
 #[derive(Debug, thiserror::Error)]
@@ -25,8 +29,8 @@ pub enum SshError {
     #[error("Connection closed")]
     ConnectionClosed,
 
-    #[error("Authentication failed")]
-    AuthenticationFailed,
+    #[error("{0}: the key was unlocked, but the server rejected it or the connection failed (check terminal for SSH errors)")]
+    AuthenticationFailed(String),
 }
 
 /// SSH connection parameters
@@ -64,135 +68,178 @@ impl ConnectionParams {
         })
     }
 
-    /// Format as connection string
-    pub fn to_string(&self) -> String {
-        if let Some(port) = self.port {
-            format!("{}@{}:{}", self.user, self.host, port)
+    /// The `[user@]host` argument for ssh; without a user, ssh picks one
+    fn destination(&self) -> String {
+        if self.user.is_empty() {
+            self.host.clone()
         } else {
             format!("{}@{}", self.user, self.host)
         }
     }
 }
 
+/// Formats as the connection string, `[user@]host[:port]`
+impl std::fmt::Display for ConnectionParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.destination())?;
+        if let Some(port) = self.port {
+            write!(f, ":{}", port)?;
+        }
+        Ok(())
+    }
+}
+
+/// Identity of an SSH connection: files with the same key share one
+/// connection and agent
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConnectionKey {
+    pub user: String,
+    pub host: String,
+    pub port: Option<u16>,
+    pub identity_file: Option<PathBuf>,
+}
+
+impl ConnectionKey {
+    /// Key for a host as named on the command line. Whatever isn't given
+    /// is taken from the ssh config entry for `host`; the user falls back
+    /// to the local username, as with ssh.
+    pub fn resolve(
+        user: Option<&str>,
+        host: &str,
+        port: Option<u16>,
+        identity_file: Option<PathBuf>,
+        ssh_config: &SshConfig,
+    ) -> Self {
+        let settings = ssh_config.lookup(host);
+        Self {
+            user: user
+                .map(str::to_string)
+                .or(settings.user)
+                .unwrap_or_else(local_username),
+            host: host.to_string(),
+            port: port.or(settings.port),
+            identity_file: identity_file.or(settings.identity_file),
+        }
+    }
+
+    /// Parameters for connecting with this key
+    pub fn params(&self) -> ConnectionParams {
+        ConnectionParams {
+            user: self.user.clone(),
+            host: self.host.clone(),
+            port: self.port,
+            identity_file: self.identity_file.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for ConnectionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.params().fmt(f)
+    }
+}
+
+/// Number of keys the SSH agent holds, or None when no agent is reachable.
+/// `ssh` offers these before any identity file, through `SSH_AUTH_SOCK` or,
+/// on Windows, the OpenSSH agent's named pipe; `ssh-add` finds it the same way.
+async fn agent_key_count() -> Option<usize> {
+    if cfg!(unix) && std::env::var_os("SSH_AUTH_SOCK").is_none() {
+        return None;
+    }
+    let output = Command::new("ssh-add")
+        .arg("-l")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .await
+        .ok()?;
+    // 1 means the agent is running but has no identities
+    match output.status.code() {
+        Some(0) => Some(String::from_utf8_lossy(&output.stdout).lines().count()),
+        Some(1) => Some(0),
+        _ => None,
+    }
+}
+
+/// Error text for an SSH connection that closed before the agent started,
+/// saying what the SSH agent had to offer
+fn connection_closed_message(agent_keys: Option<usize>) -> String {
+    match agent_keys {
+        Some(0) => {
+            "connection closed; ssh-agent has no keys loaded (check terminal for SSH errors)"
+                .to_string()
+        }
+        Some(count) => format!(
+            "connection closed; ssh-agent offered {} key{}, all rejected or unusable \
+             (load the right key with ssh-add or pass --identity; check terminal for SSH errors)",
+            count,
+            if count == 1 { "" } else { "s" }
+        ),
+        None => "connection closed (check terminal for SSH errors)".to_string(),
+    }
+}
+
+/// How often a live connection is checked with a ping
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How long a ping may take before the connection counts as dead
+const KEEPALIVE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// First and longest wait between reconnection attempts
+const RECONNECT_DELAY_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_DELAY_MAX: Duration = Duration::from_secs(60);
+
 /// Active SSH connection with bootstrapped agent
+///
+/// A background task pings the agent every [`KEEPALIVE_INTERVAL`] and, once
+/// the connection drops (laptop sleep, VPN change), starts new SSH processes
+/// with exponential backoff until one comes up. The channel is reused, so
+/// buffers keep their filesystem; operations fail while it is down.
 pub struct SshConnection {
-    /// SSH child process
-    process: Child,
+    /// Current SSH child process
+    process: Arc<Mutex<Child>>,
     /// Communication channel with agent (wrapped in Arc for sharing)
-    channel: std::sync::Arc<AgentChannel>,
+    channel: Arc<AgentChannel>,
     /// Connection parameters
     params: ConnectionParams,
+    /// Keepalive and reconnection task
+    supervisor: tokio::task::JoinHandle<()>,
 }
 
 impl SshConnection {
     /// Establish a new SSH connection and bootstrap the agent
     pub async fn connect(params: ConnectionParams) -> Result<Self, SshError> {
-        let mut cmd = Command::new("ssh");
-
-        // Don't check host key strictly for ease of use
-        cmd.arg("-o").arg("StrictHostKeyChecking=accept-new");
-        // Allow password prompts - SSH will use the terminal for this
-        // Note: We inherit stderr so SSH can prompt for password if needed
-
-        if let Some(port) = params.port {
-            cmd.arg("-p").arg(port.to_string());
-        }
-
-        if let Some(ref identity) = params.identity_file {
-            cmd.arg("-i").arg(identity);
-        }
-
-        cmd.arg(format!("{}@{}", params.user, params.host));
-
-        // Bootstrap the agent using Python itself to read the exact byte count.
-        // This avoids requiring bash or other shell utilities on the remote.
-        // Python reads exactly N bytes (the agent code), execs it, and the agent
-        // then continues reading from stdin for protocol messages.
-        //
-        // Note: SSH passes the remote command through a shell, so we need to
-        // properly quote the Python code. We use double quotes for the outer
-        // shell and avoid problematic characters in the Python code.
-        let agent_len = AGENT_SOURCE.len();
-        let bootstrap = format!(
-            "python3 -u -c \"import sys;exec(sys.stdin.read({}))\"",
-            agent_len
-        );
-        cmd.arg(bootstrap);
-
-        cmd.stdin(Stdio::piped());
-        cmd.stdout(Stdio::piped());
-        // Inherit stderr so SSH can prompt for password on the terminal
-        cmd.stderr(Stdio::inherit());
-
-        let mut child = cmd.spawn()?;
-
-        // Get handles
-        let mut stdin = child
-            .stdin
-            .take()
-            .ok_or_else(|| SshError::AgentStartFailed("failed to get stdin".to_string()))?;
-        let stdout = child
-            .stdout
-            .take()
-            .ok_or_else(|| SshError::AgentStartFailed("failed to get stdout".to_string()))?;
-        // Note: stderr is inherited so SSH can prompt for password on the terminal
-
-        // Send the agent code (exact byte count)
-        stdin.write_all(AGENT_SOURCE.as_bytes()).await?;
-        stdin.flush().await?;
-
-        // Create buffered reader for stdout
-        let mut reader = BufReader::new(stdout);
-
-        // Wait for ready message from agent
-        // No timeout needed - all failure modes (auth failure, network issues, etc.)
-        // result in SSH exiting and us getting EOF. User can Ctrl+C if needed.
-        let mut ready_line = String::new();
-        match reader.read_line(&mut ready_line).await {
-            Ok(0) => {
-                return Err(SshError::AgentStartFailed(
-                    "connection closed (check terminal for SSH errors)".to_string(),
-                ));
-            }
-            Ok(_) => {}
-            Err(e) => return Err(SshError::AgentStartFailed(format!("read error: {}", e))),
-        }
-
-        let ready: AgentResponse = serde_json::from_str(&ready_line).map_err(|e| {
-            SshError::AgentStartFailed(format!(
-                "invalid ready message '{}': {}",
-                ready_line.trim(),
-                e
-            ))
-        })?;
-
-        if !ready.is_ready() {
-            return Err(SshError::AgentStartFailed(
-                "agent did not send ready message".to_string(),
-            ));
-        }
+        Self::connect_with_passphrase(params, None).await
+    }
 
-        // Check protocol version
-        let version = ready.version.unwrap_or(0);
-        if version != crate::services::remote::protocol::PROTOCOL_VERSION {
-            return Err(SshError::VersionMismatch {
-                expected: crate::services::remote::protocol::PROTOCOL_VERSION,
-                got: version,
-            });
-        }
+    /// Like [`Self::connect`], answering the passphrase prompt for the
+    /// identity file with `passphrase` instead of asking on the terminal
+    pub async fn connect_with_passphrase(
+        params: ConnectionParams,
+        passphrase: Option<&Passphrase>,
+    ) -> Result<Self, SshError> {
+        let (child, reader, stdin) = start_agent(&params, passphrase, true).await?;
 
         // Create channel (takes ownership of stdin for writing)
-        let channel = std::sync::Arc::new(AgentChannel::new(reader, stdin));
+        let channel = Arc::new(AgentChannel::new(reader, stdin));
+        let process = Arc::new(Mutex::new(child));
+        let supervisor = tokio::spawn(supervise(
+            Arc::clone(&channel),
+            Arc::clone(&process),
+            params.clone(),
+            passphrase.cloned(),
+        ));
 
         Ok(Self {
-            process: child,
+            process,
             channel,
             params,
+            supervisor,
         })
     }
 
     /// Get the communication channel as an Arc for sharing
-    pub fn channel(&self) -> std::sync::Arc<AgentChannel> {
+    pub fn channel(&self) -> Arc<AgentChannel> {
         self.channel.clone()
     }
 
@@ -214,12 +261,184 @@ impl SshConnection {
 
 impl Drop for SshConnection {
     fn drop(&mut self) {
+        self.supervisor.abort();
         // Best-effort kill of the SSH process during cleanup.
         // If it fails (process already exited, permission error, etc.)
         // there's nothing we can do in a Drop impl — the OS will clean
         // up the zombie when our process exits.
-        if let Ok(()) = self.process.start_kill() {}
+        if let Ok(()) = self.process.lock().unwrap().start_kill() {}
+    }
+}
+
+/// Start `ssh`, bootstrap the agent on the remote host and wait for it to be
+/// ready. `interactive` lets ssh ask for passwords and keyboard-interactive
+/// challenges on the terminal; without it, as when reconnecting under the
+/// editor, ssh never prompts and its messages are discarded.
+async fn start_agent(
+    params: &ConnectionParams,
+    passphrase: Option<&Passphrase>,
+    interactive: bool,
+) -> Result<(Child, BufReader<ChildStdout>, ChildStdin), SshError> {
+    let mut cmd = Command::new("ssh");
+
+    // Don't check host key strictly for ease of use
+    cmd.arg("-o").arg("StrictHostKeyChecking=accept-new");
+
+    if let Some(port) = params.port {
+        cmd.arg("-p").arg(port.to_string());
+    }
+
+    if let Some(ref identity) = params.identity_file {
+        cmd.arg("-i").arg(identity);
+    }
+    if interactive {
+        // Passwords and one-time codes are asked for by our askpass helper
+        cmd.envs(terminal_askpass_env(passphrase)?);
+    } else if let Some(passphrase) = passphrase {
+        cmd.envs(passphrase.askpass_env()?);
+        cmd.arg("-o").arg("ConnectTimeout=10");
+    } else {
+        // Keys from the agent or without a passphrase only
+        cmd.arg("-o").arg("BatchMode=yes");
+        cmd.arg("-o").arg("ConnectTimeout=10");
     }
+
+    cmd.arg(params.destination());
+
+    // Bootstrap the agent using Python itself to read the exact byte count.
+    // This avoids requiring bash or other shell utilities on the remote.
+    // Python reads exactly N bytes (the agent code), execs it, and the agent
+    // then continues reading from stdin for protocol messages.
+    //
+    // Note: SSH passes the remote command through a shell, so we need to
+    // properly quote the Python code. We use double quotes for the outer
+    // shell and avoid problematic characters in the Python code.
+    let agent_len = AGENT_SOURCE.len();
+    let bootstrap = format!(
+        "python3 -u -c \"import sys;exec(sys.stdin.read({}))\"",
+        agent_len
+    );
+    cmd.arg(bootstrap);
+
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    // Inherit stderr so banners and password prompts reach the terminal;
+    // while the editor draws on it, ssh output would garble the screen
+    cmd.stderr(if interactive {
+        Stdio::inherit()
+    } else {
+        Stdio::null()
+    });
+
+    let mut child = cmd.spawn()?;
+
+    // Get handles
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| SshError::AgentStartFailed("failed to get stdin".to_string()))?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| SshError::AgentStartFailed("failed to get stdout".to_string()))?;
+
+    // Send the agent code (exact byte count)
+    stdin.write_all(AGENT_SOURCE.as_bytes()).await?;
+    stdin.flush().await?;
+
+    // Create buffered reader for stdout
+    let mut reader = BufReader::new(stdout);
+
+    // Wait for ready message from agent
+    // No timeout needed - all failure modes (auth failure, network issues, etc.)
+    // result in SSH exiting and us getting EOF. User can Ctrl+C if needed.
+    let mut ready_line = String::new();
+    match reader.read_line(&mut ready_line).await {
+        // With a verified passphrase the key itself was fine
+        Ok(0) if passphrase.is_some() => {
+            return Err(SshError::AuthenticationFailed(params.to_string()));
+        }
+        Ok(0) => {
+            return Err(SshError::AgentStartFailed(connection_closed_message(
+                agent_key_count().await,
+            )));
+        }
+        Ok(_) => {}
+        Err(e) => return Err(SshError::AgentStartFailed(format!("read error: {}", e))),
+    }
+
+    let ready: AgentResponse = serde_json::from_str(&ready_line).map_err(|e| {
+        SshError::AgentStartFailed(format!(
+            "invalid ready message '{}': {}",
+            ready_line.trim(),
+            e
+        ))
+    })?;
+
+    if !ready.is_ready() {
+        return Err(SshError::AgentStartFailed(
+            "agent did not send ready message".to_string(),
+        ));
+    }
+
+    // Check protocol version
+    let version = ready.version.unwrap_or(0);
+    if version != crate::services::remote::protocol::PROTOCOL_VERSION {
+        return Err(SshError::VersionMismatch {
+            expected: crate::services::remote::protocol::PROTOCOL_VERSION,
+            got: version,
+        });
+    }
+
+    Ok((child, reader, stdin))
+}
+
+/// Keep the connection alive: ping it while it is up, and bring it back
+/// with exponential backoff once it is down
+async fn supervise(
+    channel: Arc<AgentChannel>,
+    process: Arc<Mutex<Child>>,
+    params: ConnectionParams,
+    passphrase: Option<Passphrase>,
+) {
+    loop {
+        tokio::time::sleep(KEEPALIVE_INTERVAL).await;
+        if channel.is_connected() {
+            let ping = channel.request("info", serde_json::json!({}));
+            if !matches!(
+                tokio::time::timeout(KEEPALIVE_TIMEOUT, ping).await,
+                Ok(Ok(_))
+            ) {
+                // A hung connection: ending ssh closes the channel
+                tracing::warn!("Remote host {} stopped responding", params);
+                if let Ok(()) = process.lock().unwrap().start_kill() {}
+            }
+            continue;
+        }
+
+        tracing::warn!("Connection to {} lost, reconnecting", params);
+        let mut delay = RECONNECT_DELAY_MIN;
+        loop {
+            match start_agent(&params, passphrase.as_ref(), false).await {
+                Ok((child, reader, stdin)) => {
+                    *process.lock().unwrap() = child;
+                    channel.reconnect(reader, stdin);
+                    tracing::info!("Reconnected to {}", params);
+                    break;
+                }
+                Err(e) => {
+                    tracing::debug!("Reconnecting to {} failed: {}", params, e);
+                    tokio::time::sleep(delay).await;
+                    delay = next_reconnect_delay(delay);
+                }
+            }
+        }
+    }
+}
+
+/// The wait after a failed reconnection attempt that waited `delay`
+fn next_reconnect_delay(delay: Duration) -> Duration {
+    (delay * 2).min(RECONNECT_DELAY_MAX)
 }
 
 /// Spawn a local agent process for testing (no SSH)
@@ -337,6 +556,58 @@ mod tests {
         assert!(ConnectionParams::parse("user@").is_none());
     }
 
+    #[test]
+    fn test_resolve_key_without_config() {
+        let key = ConnectionKey::resolve(None, "host", None, None, &SshConfig::default());
+        assert_eq!(key.user, local_username());
+        assert_eq!(key.port, None);
+        assert_eq!(key.identity_file, None);
+
+        let key = ConnectionKey::resolve(
+            Some("alice"),
+            "host",
+            Some(22),
+            Some(PathBuf::from("/keys/alice")),
+            &SshConfig::default(),
+        );
+        assert_eq!(key.params().to_string(), "alice@host:22");
+        assert_eq!(
+            key.params().identity_file,
+            Some(PathBuf::from("/keys/alice"))
+        );
+
+        let anonymous = ConnectionParams {
+            user: String::new(),
+            host: "host".to_string(),
+            port: None,
+            identity_file: None,
+        };
+        assert_eq!(anonymous.to_string(), "host");
+    }
+
+    #[test]
+    fn test_connection_closed_message_counts_agent_keys() {
+        assert!(connection_closed_message(Some(3)).contains("offered 3 keys, all rejected"));
+        assert!(connection_closed_message(Some(1)).contains("offered 1 key,"));
+        assert!(connection_closed_message(Some(0)).contains("no keys loaded"));
+        assert_eq!(
+            connection_closed_message(None),
+            "connection closed (check terminal for SSH errors)"
+        );
+    }
+
+    #[test]
+    fn test_reconnect_delay_doubles_up_to_max() {
+        assert_eq!(
+            next_reconnect_delay(RECONNECT_DELAY_MIN),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            next_reconnect_delay(Duration::from_secs(45)),
+            RECONNECT_DELAY_MAX
+        );
+    }
+
     #[test]
     fn test_connection_string() {
         let params = ConnectionParams {
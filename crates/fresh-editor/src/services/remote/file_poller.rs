@@ -0,0 +1,167 @@
+//! Change polling for files on remote hosts
+//!
+//! The editor's main loop checks open local files for auto-revert with a
+//! cheap `stat` each. For a file behind a remote connection every `stat` is a
+//! round trip, so a task on the editor's tokio runtime checks those instead,
+//! every `remote_auto_revert_poll_interval_ms`, and reports a changed
+//! modification time or size as [`AsyncMessage::FileChanged`], the same
+//! message local changes go through, once when the file goes missing and
+//! again when it is back. Files whose connection is down are skipped until it
+//! is back.
+
+use crate::model::filesystem::FileSystem;
+use crate::services::async_bridge::AsyncMessage;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// A remote file being checked for changes
+struct WatchedFile {
+    filesystem: Arc<dyn FileSystem + Send + Sync>,
+    /// Modification time and size at the last check
+    last_seen: Option<(SystemTime, u64)>,
+    /// Whether the file was gone at the last check
+    missing: bool,
+}
+
+type WatchedFiles = Arc<Mutex<HashMap<PathBuf, WatchedFile>>>;
+
+/// Background task polling remote files for changes
+pub struct RemoteFilePoller {
+    watched: WatchedFiles,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl RemoteFilePoller {
+    /// Start polling on `runtime` every `interval`, sending changes to `sender`
+    pub fn start(
+        runtime: &tokio::runtime::Handle,
+        interval: Duration,
+        sender: mpsc::Sender<AsyncMessage>,
+    ) -> Self {
+        let watched = WatchedFiles::default();
+        // A zero interval would make the timer panic
+        let interval = interval.max(Duration::from_millis(1));
+        let task = runtime.spawn(poll_loop(Arc::clone(&watched), interval, sender));
+        Self { watched, task }
+    }
+
+    /// Poll exactly these files, each through its own filesystem. Files that
+    /// were already polled keep what was seen of them.
+    pub fn set_watched(&self, files: Vec<(PathBuf, Arc<dyn FileSystem + Send + Sync>)>) {
+        let mut watched = self.watched.lock().unwrap();
+        let mut previous = std::mem::take(&mut *watched);
+        for (path, filesystem) in files {
+            let (last_seen, missing) = previous
+                .remove(&path)
+                .map_or((None, false), |file| (file.last_seen, file.missing));
+            watched.insert(
+                path,
+                WatchedFile {
+                    filesystem,
+                    last_seen,
+                    missing,
+                },
+            );
+        }
+    }
+}
+
+impl Drop for RemoteFilePoller {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn poll_loop(watched: WatchedFiles, interval: Duration, sender: mpsc::Sender<AsyncMessage>) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        let files: Vec<_> = watched
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, file)| file.filesystem.is_connected())
+            .map(|(path, file)| (path.clone(), Arc::clone(&file.filesystem)))
+            .collect();
+
+        for (path, filesystem) in files {
+            // Remote filesystems block on their own runtime
+            let stat_path = path.clone();
+            let metadata =
+                match tokio::task::spawn_blocking(move || filesystem.metadata(&stat_path)).await {
+                    Ok(metadata) => metadata,
+                    Err(_) => continue,
+                };
+
+            let mut watched = watched.lock().unwrap();
+            // No longer watched
+            let Some(file) = watched.get_mut(&path) else {
+                continue;
+            };
+            let changed = match metadata {
+                Ok(metadata) => match metadata.modified {
+                    Some(modified) => {
+                        let seen = (modified, metadata.size);
+                        let last = file.last_seen.replace(seen);
+                        std::mem::take(&mut file.missing) || last.is_some_and(|last| last != seen)
+                    }
+                    None => false,
+                },
+                // Deleted, or renamed away
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    let newly_missing = file.last_seen.is_some() && !file.missing;
+                    file.missing = true;
+                    newly_missing
+                }
+                // Unreachable for now; the connection check catches up
+                Err(_) => false,
+            };
+            drop(watched);
+            if changed {
+                tracing::debug!("Remote file changed: {:?}", path);
+                let message = AsyncMessage::FileChanged {
+                    path: path.display().to_string(),
+                };
+                if sender.send(message).is_err() {
+                    // The editor is gone
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::filesystem::StdFileSystem;
+
+    #[test]
+    fn test_reports_changed_file_once() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("watched.txt");
+        std::fs::write(&path, "one").unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let poller = RemoteFilePoller::start(runtime.handle(), Duration::from_millis(10), sender);
+        poller.set_watched(vec![(path.clone(), Arc::new(StdFileSystem))]);
+
+        // Wait for the first check to record the file before changing it
+        while poller.watched.lock().unwrap()[&path].last_seen.is_none() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        std::fs::write(&path, "one two").unwrap();
+
+        match receiver.recv_timeout(Duration::from_secs(5)).unwrap() {
+            AsyncMessage::FileChanged { path: changed } => {
+                assert_eq!(changed, path.display().to_string())
+            }
+            other => panic!("unexpected message {:?}", other),
+        }
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+}
@@ -3,7 +3,8 @@
 //! Implements the FileSystem trait for remote operations via SSH agent.
 
 use crate::model::filesystem::{
-    DirEntry, EntryType, FileMetadata, FilePermissions, FileReader, FileSystem, FileWriter, WriteOp,
+    DirEntry, EntryType, FileKind, FileMetadata, FilePermissions, FileReader, FileSystem,
+    FileWriter, ShellPty, WriteOp,
 };
 use crate::services::remote::channel::{AgentChannel, ChannelError};
 use crate::services::remote::protocol::{
@@ -37,11 +38,6 @@ impl RemoteFileSystem {
         &self.connection_string
     }
 
-    /// Check if connected
-    pub fn is_connected(&self) -> bool {
-        self.channel.is_connected()
-    }
-
     /// Extract the remote temp directory from an agent `info` response.
     /// Falls back to `/tmp` if the response is missing or doesn't contain `temp_dir`.
     fn parse_temp_dir_from_info(info: Option<&serde_json::Value>) -> PathBuf {
@@ -57,6 +53,10 @@ impl RemoteFileSystem {
     fn to_io_error(e: ChannelError) -> io::Error {
         match e {
             ChannelError::Io(e) => e,
+            ChannelError::ChannelClosed => io::Error::new(
+                io::ErrorKind::NotConnected,
+                "remote connection lost; reconnecting, try again once it is back",
+            ),
             ChannelError::Remote(msg) => {
                 let kind = if msg.contains("not found") || msg.contains("No such file") {
                     io::ErrorKind::NotFound
@@ -392,6 +392,32 @@ impl FileSystem for RemoteFileSystem {
             .unwrap_or(false))
     }
 
+    fn is_symlink(&self, path: &Path) -> io::Result<bool> {
+        // The agent reports "link" from lstat when asked to follow symlinks
+        let path_str = path.to_string_lossy();
+        let result = self
+            .channel
+            .request_blocking("stat", stat_params(&path_str, true))
+            .map_err(Self::to_io_error)?;
+
+        Ok(result
+            .get("link")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false))
+    }
+
+    fn file_kind(&self, path: &Path) -> io::Result<FileKind> {
+        let path_str = path.to_string_lossy();
+        let result = self
+            .channel
+            .request_blocking("stat", stat_params(&path_str, true))
+            .map_err(Self::to_io_error)?;
+
+        let rm: RemoteMetadata = serde_json::from_value(result)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(FileKind::from_mode(rm.mode))
+    }
+
     fn set_permissions(&self, path: &Path, permissions: &FilePermissions) -> io::Result<()> {
         #[cfg(unix)]
         {
@@ -468,6 +494,23 @@ impl FileSystem for RemoteFileSystem {
         Some(&self.connection_string)
     }
 
+    fn is_connected(&self) -> bool {
+        self.channel.is_connected()
+    }
+
+    fn ping(&self) -> io::Result<()> {
+        self.channel
+            .request_blocking("info", serde_json::json!({}))
+            .map(|_| ())
+            .map_err(Self::to_io_error)
+    }
+
+    fn open_shell(&self, cwd: Option<&Path>, cols: u16, rows: u16) -> io::Result<ShellPty> {
+        let cwd = cwd.map(|dir| dir.to_string_lossy());
+        super::pty::open_shell(Arc::clone(&self.channel), cwd.as_deref(), cols, rows)
+            .map_err(Self::to_io_error)
+    }
+
     fn home_dir(&self) -> io::Result<PathBuf> {
         let result = self
             .channel
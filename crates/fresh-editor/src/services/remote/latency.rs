@@ -0,0 +1,145 @@
+//! Latency of remote connections
+//!
+//! A task on the editor's tokio runtime pings each watched connection every
+//! [`PING_INTERVAL`] and reports the round trip, or a missed ping after
+//! [`PING_TIMEOUT`], as [`AsyncMessage::RemoteLatency`]. The editor keeps a
+//! [`RollingLatency`] per connection for the status bar badge. Connections
+//! that are down aren't pinged; the badge shows them as reconnecting.
+
+use crate::model::filesystem::FileSystem;
+use crate::services::async_bridge::AsyncMessage;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How often each connection is pinged
+const PING_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a ping may take before it counts as missed
+const PING_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Round trips averaged for the badge
+const AVERAGE_SAMPLES: usize = 5;
+
+type WatchedConnections = Arc<Mutex<HashMap<String, Arc<dyn FileSystem + Send + Sync>>>>;
+
+/// Background task measuring the latency of remote connections
+pub struct RemoteLatencyMonitor {
+    watched: WatchedConnections,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl RemoteLatencyMonitor {
+    /// Start pinging on `runtime`, sending measurements to `sender`
+    pub fn start(runtime: &tokio::runtime::Handle, sender: mpsc::Sender<AsyncMessage>) -> Self {
+        let watched = WatchedConnections::default();
+        let task = runtime.spawn(ping_loop(Arc::clone(&watched), sender));
+        Self { watched, task }
+    }
+
+    /// Measure `connection` (as its filesystem reports it) from now on
+    pub fn watch(&self, connection: &str, filesystem: &Arc<dyn FileSystem + Send + Sync>) {
+        let mut watched = self.watched.lock().unwrap();
+        if !watched.contains_key(connection) {
+            watched.insert(connection.to_string(), Arc::clone(filesystem));
+        }
+    }
+}
+
+impl Drop for RemoteLatencyMonitor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn ping_loop(watched: WatchedConnections, sender: mpsc::Sender<AsyncMessage>) {
+    let mut ticker = tokio::time::interval(PING_INTERVAL);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        let connections: Vec<_> = watched
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, filesystem)| filesystem.is_connected())
+            .map(|(connection, filesystem)| (connection.clone(), Arc::clone(filesystem)))
+            .collect();
+
+        for (connection, filesystem) in connections {
+            let started = Instant::now();
+            // Remote filesystems block on their own runtime
+            let ping = tokio::task::spawn_blocking(move || filesystem.ping());
+            let round_trip = match tokio::time::timeout(PING_TIMEOUT, ping).await {
+                Ok(Ok(Ok(()))) => Some(started.elapsed()),
+                _ => None,
+            };
+            let message = AsyncMessage::RemoteLatency {
+                connection,
+                round_trip,
+            };
+            if sender.send(message).is_err() {
+                // The editor is gone
+                return;
+            }
+        }
+    }
+}
+
+/// Recent round trips of one connection
+#[derive(Debug, Default)]
+pub struct RollingLatency {
+    samples: VecDeque<Duration>,
+    /// Whether the last ping went unanswered
+    missed: bool,
+}
+
+impl RollingLatency {
+    /// Record a round trip, or a missed ping (`None`)
+    pub fn record(&mut self, round_trip: Option<Duration>) {
+        let Some(round_trip) = round_trip else {
+            self.missed = true;
+            return;
+        };
+        self.missed = false;
+        if self.samples.len() == AVERAGE_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(round_trip);
+    }
+
+    /// Average of the recent round trips, None before the first one
+    pub fn average(&self) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(self.samples.iter().sum::<Duration>() / self.samples.len() as u32)
+    }
+
+    /// Whether the last ping went unanswered
+    pub fn missed(&self) -> bool {
+        self.missed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_average_keeps_recent_samples() {
+        let mut latency = RollingLatency::default();
+        assert_eq!(latency.average(), None);
+
+        for ms in [500, 10, 20, 30, 40, 50] {
+            latency.record(Some(Duration::from_millis(ms)));
+        }
+        // The 500ms sample has rolled out
+        assert_eq!(latency.average(), Some(Duration::from_millis(30)));
+
+        latency.record(None);
+        assert!(latency.missed());
+        assert_eq!(latency.average(), Some(Duration::from_millis(30)));
+        latency.record(Some(Duration::from_millis(30)));
+        assert!(!latency.missed());
+    }
+}
@@ -5,9 +5,17 @@
 
 mod channel;
 mod connection;
+mod file_poller;
 mod filesystem;
+mod latency;
+mod passphrase;
 mod protocol;
+mod pty;
+mod router;
 mod spawner;
+mod ssh_config;
+mod stdio;
+mod transfer;
 
 pub use channel::AgentChannel;
 /// Test-only global: microseconds to sleep per chunk in the consumer loop.
@@ -20,15 +28,22 @@ pub use connection::spawn_local_agent;
 /// Like `spawn_local_agent` but with a custom data channel capacity.
 #[doc(hidden)]
 pub use connection::spawn_local_agent_with_capacity;
-pub use connection::{ConnectionParams, SshConnection};
+pub use connection::{ConnectionKey, ConnectionParams, SshConnection};
+pub use file_poller::RemoteFilePoller;
 pub use filesystem::RemoteFileSystem;
+pub use latency::{RemoteLatencyMonitor, RollingLatency};
+pub use passphrase::{askpass_main, prompt_key_passphrase, Passphrase, PassphraseError};
 pub use protocol::{
     decode_base64, encode_base64, ls_params, read_params, stat_params, sudo_write_params,
     write_params, AgentRequest, AgentResponse,
 };
+pub use router::FileSystemRouter;
 pub use spawner::{
     LocalProcessSpawner, ProcessSpawner, RemoteProcessSpawner, SpawnError, SpawnResult,
+    StdioProcess,
 };
+pub use ssh_config::{local_username, HostSettings, SshConfig};
+pub use transfer::{RemoteTransfer, STREAMED_TRANSFER_BYTES};
 
 /// The Python agent source code, embedded at compile time.
 pub const AGENT_SOURCE: &str = include_str!("agent.py");
@@ -0,0 +1,285 @@
+//! Passphrases for encrypted SSH keys, and the askpass helper
+//!
+//! When the key given with `--identity` is encrypted, the passphrase is asked
+//! for on the terminal before the editor takes it over, and checked against
+//! the key with `ssh-keygen` so a typo can be retried (three attempts) and
+//! told apart from the server rejecting the key. It is handed to `ssh` and
+//! `ssh-keygen` through `SSH_ASKPASS`, pointing back at this executable,
+//! which prints it only for passphrase prompts (never for a password prompt).
+//!
+//! While connecting before the editor starts, the same helper also asks on
+//! the terminal, with echo off, for passwords and keyboard-interactive
+//! challenges (one-time codes). `ssh` keeps writing banners and "Permission
+//! denied, please try again." to the terminal between the prompts. When
+//! stdin isn't a terminal and `SSH_ASKPASS` is set, the user's own askpass
+//! program answers instead.
+//!
+//! The passphrase is kept in a [`Passphrase`], whose `Debug` output is
+//! redacted, and is never logged.
+
+use std::ffi::OsString;
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+
+/// Environment variable carrying the passphrase to the askpass helper
+pub const ASKPASS_ENV: &str = "FRESH_SSH_PASSPHRASE";
+
+/// Environment variable telling the askpass helper to ask on the terminal
+/// for anything but the key passphrase
+const ASKPASS_TERMINAL_ENV: &str = "FRESH_SSH_ASKPASS_TERMINAL";
+
+/// How many times a wrong passphrase may be typed
+const MAX_ATTEMPTS: usize = 3;
+
+/// A key passphrase typed by the user
+#[derive(Clone)]
+pub struct Passphrase(String);
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Passphrase(<redacted>)")
+    }
+}
+
+impl Passphrase {
+    /// Environment for a child `ssh` or `ssh-keygen` so it reads the
+    /// passphrase from the askpass helper instead of the terminal
+    pub(crate) fn askpass_env(&self) -> io::Result<Vec<(&'static str, OsString)>> {
+        let mut env = helper_env()?;
+        env.push((ASKPASS_ENV, self.0.clone().into()));
+        Ok(env)
+    }
+}
+
+/// Environment making `ssh` run this executable as its askpass program
+fn helper_env() -> io::Result<Vec<(&'static str, OsString)>> {
+    Ok(vec![
+        ("SSH_ASKPASS", std::env::current_exe()?.into_os_string()),
+        ("SSH_ASKPASS_REQUIRE", "force".into()),
+    ])
+}
+
+/// Environment for an `ssh` started before the editor takes over the
+/// terminal, so passwords and challenges are asked for on the terminal, and
+/// the key passphrase, if any, is answered without asking
+pub(crate) fn terminal_askpass_env(
+    passphrase: Option<&Passphrase>,
+) -> io::Result<Vec<(&'static str, OsString)>> {
+    if passphrase.is_none()
+        && !io::stdin().is_terminal()
+        && std::env::var_os("SSH_ASKPASS").is_some()
+    {
+        // Leave it to the user's askpass program
+        return Ok(vec![("SSH_ASKPASS_REQUIRE", "force".into())]);
+    }
+    let mut env = match passphrase {
+        Some(passphrase) => passphrase.askpass_env()?,
+        None => helper_env()?,
+    };
+    env.push((ASKPASS_TERMINAL_ENV, "1".into()));
+    Ok(env)
+}
+
+/// Run as the askpass helper when started by `ssh` with [`ASKPASS_ENV`] or
+/// [`ASKPASS_TERMINAL_ENV`] set. Returns the exit code, or None when this is
+/// a normal start.
+pub fn askpass_main() -> Option<i32> {
+    let passphrase = std::env::var_os(ASKPASS_ENV);
+    let ask_terminal = std::env::var_os(ASKPASS_TERMINAL_ENV).is_some();
+    if passphrase.is_none() && !ask_terminal {
+        return None;
+    }
+    let prompt = std::env::args().nth(1).unwrap_or_default();
+    let answer = match passphrase {
+        Some(passphrase) if prompt.to_lowercase().contains("passphrase") => {
+            Some(passphrase.to_string_lossy().into_owned())
+        }
+        _ if ask_terminal => ask_on_terminal(&prompt),
+        // Anything else (a password, a host key confirmation) is declined
+        _ => None,
+    };
+    let Some(answer) = answer else {
+        return Some(1);
+    };
+    let mut stdout = io::stdout();
+    let written = stdout
+        .write_all(answer.as_bytes())
+        .and_then(|_| stdout.write_all(b"\n"))
+        .and_then(|_| stdout.flush());
+    Some(if written.is_ok() { 0 } else { 1 })
+}
+
+/// Answer an `ssh` prompt on the terminal. `ssh` says what kind of prompt it
+/// is in `SSH_ASKPASS_PROMPT`: notifications are only shown, confirmations
+/// are read with echo, and passwords and challenges without. Returns None
+/// when the user cancels.
+fn ask_on_terminal(prompt: &str) -> Option<String> {
+    let read = match std::env::var("SSH_ASKPASS_PROMPT").as_deref() {
+        Ok("none") => {
+            eprintln!("{}", prompt.trim_end());
+            return Some(String::new());
+        }
+        Ok("confirm") => read_terminal_line(prompt, true),
+        _ => read_terminal_line(prompt, false),
+    };
+    read.unwrap_or_else(|e| {
+        eprintln!("Could not read from the terminal: {}", e);
+        None
+    })
+}
+
+/// Whether the private key at `path` needs a passphrase. Keys `ssh-keygen`
+/// can't read for another reason are left for `ssh` to report.
+fn key_is_encrypted(path: &Path) -> bool {
+    let output = Command::new("ssh-keygen")
+        .arg("-y")
+        .arg("-P")
+        .arg("")
+        .arg("-f")
+        .arg(path)
+        .stdin(Stdio::null())
+        .output();
+    match output {
+        Ok(output) => {
+            !output.status.success()
+                && String::from_utf8_lossy(&output.stderr)
+                    .to_lowercase()
+                    .contains("passphrase")
+        }
+        Err(e) => {
+            tracing::debug!("Could not run ssh-keygen to inspect {:?}: {}", path, e);
+            false
+        }
+    }
+}
+
+/// Whether `passphrase` decrypts the private key at `path`
+fn passphrase_unlocks(path: &Path, passphrase: &Passphrase) -> io::Result<bool> {
+    let status = Command::new("ssh-keygen")
+        .arg("-y")
+        .arg("-f")
+        .arg(path)
+        .envs(passphrase.askpass_env()?)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    Ok(status.success())
+}
+
+/// Read a line from the terminal, echoing what is typed only with `echo`.
+/// Returns None when the user presses Esc or Ctrl+C.
+fn read_terminal_line(prompt: &str, echo: bool) -> io::Result<Option<String>> {
+    let mut stderr = io::stderr();
+    write!(stderr, "{}", prompt)?;
+    stderr.flush()?;
+
+    terminal::enable_raw_mode()?;
+    let mut line = String::new();
+    let result = loop {
+        let key = match event::read() {
+            Ok(Event::Key(key)) if key.kind != KeyEventKind::Release => key,
+            Ok(_) => continue,
+            Err(e) => break Err(e),
+        };
+        match key.code {
+            KeyCode::Enter => break Ok(Some(line)),
+            KeyCode::Esc => break Ok(None),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break Ok(None),
+            KeyCode::Backspace => {
+                if line.pop().is_some() && echo {
+                    if let Err(e) = write_now(&mut stderr, "\x08 \x08") {
+                        break Err(e);
+                    }
+                }
+            }
+            KeyCode::Char(c) => {
+                line.push(c);
+                if echo {
+                    if let Err(e) = write_now(&mut stderr, c.encode_utf8(&mut [0; 4])) {
+                        break Err(e);
+                    }
+                }
+            }
+            _ => {}
+        }
+    };
+    // Best-effort: the caller reports the read error, if any
+    #[allow(clippy::let_underscore_must_use)]
+    let _ = terminal::disable_raw_mode();
+    writeln!(stderr)?;
+    result
+}
+
+/// Write to the terminal right away, as raw mode doesn't echo
+fn write_now(stderr: &mut io::Stderr, text: &str) -> io::Result<()> {
+    stderr.write_all(text.as_bytes())?;
+    stderr.flush()
+}
+
+/// Why no passphrase could be had for a key
+#[derive(Debug, thiserror::Error)]
+pub enum PassphraseError {
+    #[error("wrong passphrase for {} ({} attempts)", .0.display(), MAX_ATTEMPTS)]
+    Wrong(PathBuf),
+
+    #[error("passphrase entry for {} was cancelled", .0.display())]
+    Cancelled(PathBuf),
+
+    #[error("could not read the passphrase for {}: {}", .0.display(), .1)]
+    Io(PathBuf, io::Error),
+}
+
+/// Ask for the passphrase of the private key at `path` if it is encrypted,
+/// allowing [`MAX_ATTEMPTS`] tries. Must run before the editor puts the
+/// terminal in raw mode. Returns None for keys without a passphrase.
+pub fn prompt_key_passphrase(path: &Path) -> Result<Option<Passphrase>, PassphraseError> {
+    if !key_is_encrypted(path) {
+        return Ok(None);
+    }
+    let io_error = |e| PassphraseError::Io(path.to_path_buf(), e);
+    let prompt = format!("Enter passphrase for key '{}': ", path.display());
+    for attempt in 1..=MAX_ATTEMPTS {
+        let Some(line) = read_terminal_line(&prompt, false).map_err(io_error)? else {
+            return Err(PassphraseError::Cancelled(path.to_path_buf()));
+        };
+        let passphrase = Passphrase(line);
+        if passphrase_unlocks(path, &passphrase).map_err(io_error)? {
+            return Ok(Some(passphrase));
+        }
+        if attempt < MAX_ATTEMPTS {
+            eprintln!("Wrong passphrase, try again.");
+        }
+    }
+    Err(PassphraseError::Wrong(path.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passphrase_debug_is_redacted() {
+        let passphrase = Passphrase("hunter2".to_string());
+        assert_eq!(format!("{:?}", passphrase), "Passphrase(<redacted>)");
+        let env = passphrase.askpass_env().unwrap();
+        assert!(env
+            .iter()
+            .any(|(name, value)| *name == ASKPASS_ENV && value == "hunter2"));
+    }
+
+    #[test]
+    fn test_terminal_askpass_env_keeps_passphrase() {
+        let passphrase = Passphrase("hunter2".to_string());
+        let env = terminal_askpass_env(Some(&passphrase)).unwrap();
+        let names: Vec<_> = env.iter().map(|(name, _)| *name).collect();
+        assert!(names.contains(&"SSH_ASKPASS"));
+        assert!(names.contains(&ASKPASS_ENV));
+        assert!(names.contains(&ASKPASS_TERMINAL_ENV));
+    }
+}
@@ -206,6 +206,71 @@ pub fn exec_params(cmd: &str, args: &[String], cwd: Option<&str>) -> serde_json:
     params
 }
 
+/// Build params for spawn request
+pub fn spawn_params(
+    cmd: &str,
+    args: &[String],
+    env: &std::collections::HashMap<String, String>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "cmd": cmd,
+        "args": args,
+        "env": env
+    })
+}
+
+/// Build params for stdin request
+pub fn stdin_params(process_id: u64, data: &[u8]) -> serde_json::Value {
+    serde_json::json!({
+        "id": process_id,
+        "data": encode_base64(data)
+    })
+}
+
+/// Build params for a stdin request closing the process's input
+pub fn stdin_close_params(process_id: u64) -> serde_json::Value {
+    serde_json::json!({"id": process_id, "close": true})
+}
+
+/// Build params for kill request
+pub fn kill_params(process_id: u64) -> serde_json::Value {
+    serde_json::json!({"id": process_id})
+}
+
+/// Build params for pty request
+pub fn pty_params(cwd: Option<&str>, cols: u16, rows: u16) -> serde_json::Value {
+    let mut params = serde_json::json!({
+        "cols": cols,
+        "rows": rows
+    });
+    if let Some(dir) = cwd {
+        params["cwd"] = serde_json::json!(dir);
+    }
+    params
+}
+
+/// Build params for pty_write request
+pub fn pty_write_params(pty_id: u64, data: &[u8]) -> serde_json::Value {
+    serde_json::json!({
+        "id": pty_id,
+        "data": encode_base64(data)
+    })
+}
+
+/// Build params for pty_resize request
+pub fn pty_resize_params(pty_id: u64, cols: u16, rows: u16) -> serde_json::Value {
+    serde_json::json!({
+        "id": pty_id,
+        "cols": cols,
+        "rows": rows
+    })
+}
+
+/// Build params for pty_close request
+pub fn pty_close_params(pty_id: u64) -> serde_json::Value {
+    serde_json::json!({"id": pty_id})
+}
+
 /// Build params for cancel request
 pub fn cancel_params(request_id: u64) -> serde_json::Value {
     serde_json::json!({"id": request_id})
@@ -0,0 +1,155 @@
+//! Shells on the remote host
+//!
+//! The agent runs the user's login shell on a pseudo-terminal it opens for a
+//! `pty` request, and streams the terminal's output as that request's data.
+//! The request's ID names the terminal to the `pty_write`, `pty_resize` and
+//! `pty_close` requests that follow. Those are sent from one task, so
+//! keystrokes and size changes reach the agent in order, and the agent
+//! handles them in the order they arrive.
+
+use crate::model::filesystem::{ShellInput, ShellPty};
+use crate::services::remote::channel::{AgentChannel, ChannelError};
+use crate::services::remote::protocol::{
+    decode_base64, pty_close_params, pty_params, pty_resize_params, pty_write_params,
+};
+use std::io::{self, Read};
+use std::sync::{mpsc, Arc};
+use tokio::sync::mpsc as tokio_mpsc;
+
+/// Input for the remote terminal, in the order it was given
+enum PtyInput {
+    Write(Vec<u8>),
+    Resize { cols: u16, rows: u16 },
+    Close,
+}
+
+/// Open a `cols` by `rows` terminal on the remote host running the login
+/// shell in `cwd` (the home directory if `None`)
+///
+/// Only waits for the request to be sent; if the agent can't start the
+/// shell, its error is shown as the terminal's output.
+pub fn open_shell(
+    channel: Arc<AgentChannel>,
+    cwd: Option<&str>,
+    cols: u16,
+    rows: u16,
+) -> Result<ShellPty, ChannelError> {
+    let runtime = channel.runtime_handle().clone();
+    let (pty_id, mut data_rx, result_rx) =
+        runtime.block_on(channel.request_streaming_with_id("pty", pty_params(cwd, cols, rows)))?;
+
+    let (output_tx, output_rx) = mpsc::channel::<Vec<u8>>();
+    runtime.spawn(async move {
+        // Keep draining after the reader is gone: unread data would hold up
+        // the responses to every other request
+        while let Some(data) = data_rx.recv().await {
+            let Some(out) = data.get("out").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            match decode_base64(out) {
+                Ok(bytes) => {
+                    // The terminal may already be closed
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = output_tx.send(bytes);
+                }
+                Err(e) => tracing::warn!("Bad output from remote terminal {}: {}", pty_id, e),
+            }
+        }
+        if let Ok(Err(error)) = result_rx.await {
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = output_tx.send(format!("\r\n{}\r\n", error).into_bytes());
+        }
+    });
+
+    let (input_tx, mut input_rx) = tokio_mpsc::unbounded_channel::<PtyInput>();
+    runtime.spawn(async move {
+        while let Some(input) = input_rx.recv().await {
+            let (method, params) = match input {
+                PtyInput::Write(data) => ("pty_write", pty_write_params(pty_id, &data)),
+                PtyInput::Resize { cols, rows } => {
+                    ("pty_resize", pty_resize_params(pty_id, cols, rows))
+                }
+                PtyInput::Close => ("pty_close", pty_close_params(pty_id)),
+            };
+            // Don't wait for the answer, so keystrokes aren't held up by
+            // the round trip; requests to a shell that has exited fail
+            match channel.request_streaming(method, params).await {
+                Ok((_, result_rx)) => {
+                    tokio::spawn(async move {
+                        if let Ok(Err(error)) = result_rx.await {
+                            tracing::debug!("Remote terminal {} {}: {}", pty_id, method, error);
+                        }
+                    });
+                }
+                Err(e) => tracing::debug!("Remote terminal {} {}: {}", pty_id, method, e),
+            }
+            if method == "pty_close" {
+                break;
+            }
+        }
+    });
+
+    Ok(ShellPty {
+        output: Box::new(RemoteOutput {
+            chunks: output_rx,
+            pending: Vec::new(),
+            offset: 0,
+        }),
+        input: Box::new(RemoteInput { input_tx }),
+    })
+}
+
+/// Output of a remote terminal; ends once the shell has exited
+struct RemoteOutput {
+    chunks: mpsc::Receiver<Vec<u8>>,
+    /// Chunk being read, from `offset` on
+    pending: Vec<u8>,
+    offset: usize,
+}
+
+impl Read for RemoteOutput {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.offset >= self.pending.len() {
+            match self.chunks.recv() {
+                Ok(chunk) => {
+                    self.pending = chunk;
+                    self.offset = 0;
+                }
+                Err(_) => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.pending.len() - self.offset);
+        buf[..n].copy_from_slice(&self.pending[self.offset..self.offset + n]);
+        self.offset += n;
+        Ok(n)
+    }
+}
+
+/// Input side of a remote terminal
+struct RemoteInput {
+    input_tx: tokio_mpsc::UnboundedSender<PtyInput>,
+}
+
+impl RemoteInput {
+    fn send(&self, input: PtyInput) -> io::Result<()> {
+        self.input_tx
+            .send(input)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "remote terminal is closed"))
+    }
+}
+
+impl ShellInput for RemoteInput {
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        self.send(PtyInput::Write(data.to_vec()))
+    }
+
+    fn resize(&mut self, cols: u16, rows: u16) -> io::Result<()> {
+        self.send(PtyInput::Resize { cols, rows })
+    }
+
+    fn close(&mut self) {
+        // Already closed if the input task is gone
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send(PtyInput::Close);
+    }
+}
@@ -0,0 +1,250 @@
+//! Routing between several remote connections
+//!
+//! When files from more than one host are opened, the editor keeps a single
+//! filesystem that behaves like the first (primary) connection: the file
+//! explorer, relative paths and plugins all use it. Buffers opened on another
+//! connection ask the router for that connection's filesystem and keep it, so
+//! their reads, saves and polling go over the right channel.
+
+use crate::model::filesystem::{
+    DirEntry, FileKind, FileMetadata, FilePermissions, FileReader, FileSearchCursor,
+    FileSearchOptions, FileSystem, FileWriter, SearchMatch, ShellPty, WriteOp,
+};
+use crate::services::remote::ConnectionKey;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Filesystem that acts as its primary connection and hands out the
+/// filesystems of the other connections by name
+pub struct FileSystemRouter {
+    primary: Arc<dyn FileSystem + Send + Sync>,
+    connections: HashMap<ConnectionKey, Arc<dyn FileSystem + Send + Sync>>,
+}
+
+impl FileSystemRouter {
+    /// Create a router whose default is the `primary` connection
+    pub fn new(key: ConnectionKey, primary: Arc<dyn FileSystem + Send + Sync>) -> Self {
+        let mut connections = HashMap::new();
+        connections.insert(key, Arc::clone(&primary));
+        Self {
+            primary,
+            connections,
+        }
+    }
+
+    /// Add another connection
+    pub fn add(&mut self, key: ConnectionKey, filesystem: Arc<dyn FileSystem + Send + Sync>) {
+        self.connections.insert(key, filesystem);
+    }
+
+    /// Filesystem of the connection with this key
+    pub fn route(&self, key: &ConnectionKey) -> Option<Arc<dyn FileSystem + Send + Sync>> {
+        self.connections.get(key).cloned()
+    }
+}
+
+impl FileSystem for FileSystemRouter {
+    fn read_file(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.primary.read_file(path)
+    }
+
+    fn read_range(&self, path: &Path, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+        self.primary.read_range(path, offset, len)
+    }
+
+    fn count_line_feeds_in_range(&self, path: &Path, offset: u64, len: usize) -> io::Result<usize> {
+        self.primary.count_line_feeds_in_range(path, offset, len)
+    }
+
+    fn write_file(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.primary.write_file(path, data)
+    }
+
+    fn create_file(&self, path: &Path) -> io::Result<Box<dyn FileWriter>> {
+        self.primary.create_file(path)
+    }
+
+    fn open_file(&self, path: &Path) -> io::Result<Box<dyn FileReader>> {
+        self.primary.open_file(path)
+    }
+
+    fn open_file_for_write(&self, path: &Path) -> io::Result<Box<dyn FileWriter>> {
+        self.primary.open_file_for_write(path)
+    }
+
+    fn open_file_for_append(&self, path: &Path) -> io::Result<Box<dyn FileWriter>> {
+        self.primary.open_file_for_append(path)
+    }
+
+    fn set_file_length(&self, path: &Path, len: u64) -> io::Result<()> {
+        self.primary.set_file_length(path, len)
+    }
+
+    fn write_patched(&self, src_path: &Path, dst_path: &Path, ops: &[WriteOp]) -> io::Result<()> {
+        self.primary.write_patched(src_path, dst_path, ops)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.primary.rename(from, to)
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+        self.primary.copy(from, to)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.primary.remove_file(path)
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        self.primary.remove_dir(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.primary.metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.primary.symlink_metadata(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.primary.exists(path)
+    }
+
+    fn metadata_if_exists(&self, path: &Path) -> Option<FileMetadata> {
+        self.primary.metadata_if_exists(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> io::Result<bool> {
+        self.primary.is_dir(path)
+    }
+
+    fn is_file(&self, path: &Path) -> io::Result<bool> {
+        self.primary.is_file(path)
+    }
+
+    fn is_symlink(&self, path: &Path) -> io::Result<bool> {
+        self.primary.is_symlink(path)
+    }
+
+    fn file_kind(&self, path: &Path) -> io::Result<FileKind> {
+        self.primary.file_kind(path)
+    }
+
+    fn set_permissions(&self, path: &Path, permissions: &FilePermissions) -> io::Result<()> {
+        self.primary.set_permissions(path, permissions)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<DirEntry>> {
+        self.primary.read_dir(path)
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        self.primary.create_dir(path)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.primary.create_dir_all(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.primary.canonicalize(path)
+    }
+
+    fn current_uid(&self) -> u32 {
+        self.primary.current_uid()
+    }
+
+    fn is_owner(&self, path: &Path) -> bool {
+        self.primary.is_owner(path)
+    }
+
+    fn temp_path_for(&self, path: &Path) -> PathBuf {
+        self.primary.temp_path_for(path)
+    }
+
+    fn unique_temp_path(&self, dest_path: &Path) -> PathBuf {
+        self.primary.unique_temp_path(dest_path)
+    }
+
+    fn remote_connection_info(&self) -> Option<&str> {
+        self.primary.remote_connection_info()
+    }
+
+    fn is_connected(&self) -> bool {
+        self.primary.is_connected()
+    }
+
+    fn ping(&self) -> io::Result<()> {
+        self.primary.ping()
+    }
+
+    fn connection_filesystem(&self, connection: &str) -> Option<Arc<dyn FileSystem + Send + Sync>> {
+        self.connections
+            .iter()
+            .find(|(key, _)| key.to_string() == connection)
+            .map(|(_, filesystem)| Arc::clone(filesystem))
+    }
+
+    fn open_shell(&self, cwd: Option<&Path>, cols: u16, rows: u16) -> io::Result<ShellPty> {
+        self.primary.open_shell(cwd, cols, rows)
+    }
+
+    fn home_dir(&self) -> io::Result<PathBuf> {
+        self.primary.home_dir()
+    }
+
+    fn search_file(
+        &self,
+        path: &Path,
+        pattern: &str,
+        opts: &FileSearchOptions,
+        cursor: &mut FileSearchCursor,
+    ) -> io::Result<Vec<SearchMatch>> {
+        self.primary.search_file(path, pattern, opts, cursor)
+    }
+
+    fn sudo_write(
+        &self,
+        path: &Path,
+        data: &[u8],
+        mode: u32,
+        uid: u32,
+        gid: u32,
+    ) -> io::Result<()> {
+        self.primary.sudo_write(path, data, mode, uid, gid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::filesystem::{NoopFileSystem, StdFileSystem};
+
+    fn key(host: &str, port: Option<u16>) -> ConnectionKey {
+        ConnectionKey {
+            user: "alice".to_string(),
+            host: host.to_string(),
+            port,
+            identity_file: None,
+        }
+    }
+
+    #[test]
+    fn test_router_hands_out_each_connection() {
+        let web: Arc<dyn FileSystem + Send + Sync> = Arc::new(StdFileSystem);
+        let db: Arc<dyn FileSystem + Send + Sync> = Arc::new(NoopFileSystem);
+        let mut router = FileSystemRouter::new(key("web", None), Arc::clone(&web));
+        router.add(key("db", Some(2222)), Arc::clone(&db));
+
+        let routed = router.connection_filesystem("alice@db:2222").unwrap();
+        assert!(Arc::ptr_eq(&routed, &db));
+        let routed = router.connection_filesystem("alice@web").unwrap();
+        assert!(Arc::ptr_eq(&routed, &web));
+        assert!(router.connection_filesystem("alice@db").is_none());
+        assert!(router.route(&key("db", Some(2222))).is_some());
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! Executes processes on the remote host via the SSH agent.
 //!
+//! Language servers for remote files are started here too, with
+//! [`ProcessSpawner::spawn_stdio`], their stdio bridged over the connection.
+//!
 //! NOTE: This module is currently unused but provides infrastructure for
 //! plugin process execution on remote hosts. It will be integrated with
 //! the Editor's SpawnProcess handler to allow plugins like live_grep,
@@ -9,7 +12,9 @@
 
 use crate::services::remote::channel::{AgentChannel, ChannelError};
 use crate::services::remote::protocol::{decode_base64, exec_params};
+use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
 
 /// Result of spawning a process
 ///
@@ -37,6 +42,14 @@ pub enum SpawnError {
     Decode(String),
 }
 
+/// A running process with piped stdin and stdout, such as a language server
+pub struct StdioProcess {
+    pub stdin: Box<dyn AsyncWrite + Send + Unpin>,
+    pub stdout: Box<dyn AsyncRead + Send + Unpin>,
+    /// Ends the process when dropped
+    pub guard: Box<dyn Send>,
+}
+
 /// Trait for spawning processes (local or remote)
 ///
 /// NOTE: Unused until process spawner is integrated with Editor.
@@ -51,6 +64,22 @@ pub trait ProcessSpawner: Send + Sync {
         args: Vec<String>,
         cwd: Option<String>,
     ) -> Result<SpawnResult, SpawnError>;
+
+    /// Start a long-running process with piped stdin and stdout, writing its
+    /// stderr to `stderr`. Only remote spawners support this; language
+    /// servers on this machine are started directly.
+    async fn spawn_stdio(
+        &self,
+        command: String,
+        _args: Vec<String>,
+        _env: HashMap<String, String>,
+        _stderr: std::fs::File,
+    ) -> Result<StdioProcess, SpawnError> {
+        Err(SpawnError::Process(format!(
+            "cannot start {} through this spawner",
+            command
+        )))
+    }
 }
 
 /// Local process spawner using tokio
@@ -68,7 +97,8 @@ impl ProcessSpawner for LocalProcessSpawner {
         cwd: Option<String>,
     ) -> Result<SpawnResult, SpawnError> {
         let mut cmd = tokio::process::Command::new(&command);
-        cmd.args(&args);
+        cmd.args(&args)
+            .envs(crate::services::shell_env::current().vars());
 
         if let Some(ref dir) = cwd {
             cmd.current_dir(dir);
@@ -147,6 +177,16 @@ impl ProcessSpawner for RemoteProcessSpawner {
             exit_code,
         })
     }
+
+    async fn spawn_stdio(
+        &self,
+        command: String,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+        stderr: std::fs::File,
+    ) -> Result<StdioProcess, SpawnError> {
+        super::stdio::spawn_stdio(Arc::clone(&self.channel), &command, &args, &env, stderr).await
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,452 @@
+//! OpenSSH client config (`~/.ssh/config`) lookup
+//!
+//! Only what's needed to connect is read: `User`, `Port`, `IdentityFile`
+//! and `HostName` (for the `%h` token), from `Host` blocks and the
+//! top-level defaults, with `Include` followed. As in ssh, the first value
+//! found for an option wins, so specific `Host` blocks must come before
+//! `Host *`. `Match` blocks are skipped. The host alias itself is still
+//! what gets passed to `ssh`, so options not read here (ProxyJump,
+//! HostName, ...) keep working.
+
+use std::path::{Path, PathBuf};
+
+/// Same limit as ssh, guards against include cycles
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Parsed client config
+#[derive(Debug, Clone, Default)]
+pub struct SshConfig {
+    blocks: Vec<HostBlock>,
+}
+
+/// Options of one `Host` line, or of the top of a file (which applies to
+/// every host)
+#[derive(Debug, Clone)]
+struct HostBlock {
+    /// None for options that apply to every host; an empty list never
+    /// matches (`Match` blocks)
+    patterns: Option<Vec<String>>,
+    options: Vec<(String, String)>,
+}
+
+/// Connection settings for one host
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostSettings {
+    pub hostname: Option<String>,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    pub identity_file: Option<PathBuf>,
+}
+
+impl SshConfig {
+    /// Load `~/.ssh/config`, empty if there is none
+    pub fn load() -> Self {
+        let Some(ssh_dir) = dirs::home_dir().map(|home| home.join(".ssh")) else {
+            return Self::default();
+        };
+        Self::load_from(&ssh_dir.join("config"), &ssh_dir)
+    }
+
+    /// Load a config file; relative `Include` paths are resolved against
+    /// `include_dir` (`~/.ssh` for the user config)
+    pub fn load_from(path: &Path, include_dir: &Path) -> Self {
+        let mut config = Self::default();
+        config.read_file(path, include_dir, 0, None);
+        config
+    }
+
+    /// Read a file whose leading options apply to `patterns` (None for
+    /// every host)
+    fn read_file(
+        &mut self,
+        path: &Path,
+        include_dir: &Path,
+        depth: usize,
+        patterns: Option<Vec<String>>,
+    ) {
+        match std::fs::read_to_string(path) {
+            Ok(text) => self.parse(&text, include_dir, depth, patterns),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => tracing::warn!("Failed to read {}: {}", path.display(), e),
+        }
+    }
+
+    fn parse(
+        &mut self,
+        text: &str,
+        include_dir: &Path,
+        depth: usize,
+        patterns: Option<Vec<String>>,
+    ) {
+        self.blocks.push(HostBlock {
+            patterns,
+            options: Vec::new(),
+        });
+        for line in text.lines() {
+            let Some((keyword, args)) = split_line(line) else {
+                continue;
+            };
+            match keyword.as_str() {
+                "host" => self.blocks.push(HostBlock {
+                    patterns: Some(args),
+                    options: Vec::new(),
+                }),
+                "match" => self.blocks.push(HostBlock {
+                    patterns: Some(Vec::new()),
+                    options: Vec::new(),
+                }),
+                "include" if depth < MAX_INCLUDE_DEPTH => {
+                    // Included lines belong to the block the Include is in
+                    let enclosing = self.blocks.last().and_then(|b| b.patterns.clone());
+                    for pattern in &args {
+                        for file in expand_include(pattern, include_dir) {
+                            self.read_file(&file, include_dir, depth + 1, enclosing.clone());
+                        }
+                    }
+                    self.blocks.push(HostBlock {
+                        patterns: enclosing,
+                        options: Vec::new(),
+                    });
+                }
+                "include" => {
+                    tracing::warn!("ssh config: Include nested too deeply, ignoring");
+                }
+                _ => {
+                    if let (Some(block), Some(value)) = (self.blocks.last_mut(), args.first()) {
+                        block.options.push((keyword, value.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether `alias` is named by a `Host` line without wildcards
+    pub fn has_host(&self, alias: &str) -> bool {
+        self.blocks
+            .iter()
+            .filter_map(|block| block.patterns.as_ref())
+            .flatten()
+            .any(|pattern| pattern == alias)
+    }
+
+    /// Settings for `alias`; the first value found for each option wins
+    pub fn lookup(&self, alias: &str) -> HostSettings {
+        let mut settings = HostSettings::default();
+        let options = self
+            .blocks
+            .iter()
+            .filter(|block| block.matches(alias))
+            .flat_map(|block| &block.options);
+        for (keyword, value) in options {
+            match keyword.as_str() {
+                "hostname" if settings.hostname.is_none() => {
+                    settings.hostname = Some(value.clone());
+                }
+                "user" if settings.user.is_none() => settings.user = Some(value.clone()),
+                "port" if settings.port.is_none() => {
+                    settings.port = value.parse().ok().filter(|&port| port != 0);
+                }
+                "identityfile" if settings.identity_file.is_none() => {
+                    settings.identity_file = Some(PathBuf::from(value));
+                }
+                _ => {}
+            }
+        }
+
+        // Tokens are expanded once everything is known, as ssh does
+        let hostname = settings
+            .hostname
+            .clone()
+            .unwrap_or_else(|| alias.to_string());
+        let user = settings.user.clone().unwrap_or_else(local_username);
+        if let Some(identity) = &settings.identity_file {
+            let expanded = expand_tokens(&identity.to_string_lossy(), &hostname, &user);
+            settings.identity_file = Some(PathBuf::from(expanded));
+        }
+        settings
+    }
+}
+
+impl HostBlock {
+    fn matches(&self, alias: &str) -> bool {
+        let Some(patterns) = &self.patterns else {
+            return true;
+        };
+        let mut matched = false;
+        for pattern in patterns {
+            if let Some(negated) = pattern.strip_prefix('!') {
+                if wildcard_match(negated, alias) {
+                    return false;
+                }
+            } else if wildcard_match(pattern, alias) {
+                matched = true;
+            }
+        }
+        matched
+    }
+}
+
+/// Split a config line into a lowercased keyword and its arguments.
+/// Keywords may be separated from arguments by `=`; arguments may be quoted.
+fn split_line(line: &str) -> Option<(String, Vec<String>)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let end = line
+        .find(|c: char| c.is_whitespace() || c == '=')
+        .unwrap_or(line.len());
+    let keyword = line[..end].to_ascii_lowercase();
+    let rest = line[end..].trim_start();
+    let rest = rest.strip_prefix('=').unwrap_or(rest);
+
+    let mut args = Vec::new();
+    let mut chars = rest.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(first) = chars.next() else {
+            break;
+        };
+        let mut arg = String::new();
+        if first == '"' {
+            arg.extend(chars.by_ref().take_while(|&c| c != '"'));
+        } else {
+            arg.push(first);
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                arg.push(c);
+            }
+        }
+        args.push(arg);
+    }
+    Some((keyword, args))
+}
+
+/// Files named by an `Include` argument, sorted. Wildcards are allowed in
+/// the file name.
+fn expand_include(pattern: &str, include_dir: &Path) -> Vec<PathBuf> {
+    let path = match pattern.strip_prefix("~/") {
+        Some(rest) => match dirs::home_dir() {
+            Some(home) => home.join(rest),
+            None => return Vec::new(),
+        },
+        None => include_dir.join(pattern),
+    };
+    let Some(name) = path.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+        return Vec::new();
+    };
+    if !name.contains(['*', '?']) {
+        return vec![path];
+    }
+    let Some(dir) = path.parent() else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|file| {
+            file.is_file()
+                && file
+                    .file_name()
+                    .is_some_and(|n| wildcard_match(&name, &n.to_string_lossy()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// ssh pattern matching: `*` matches any run of characters, `?` exactly one
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Expand `~` and the `%d`, `%h`, `%r`, `%u` and `%%` tokens of a path
+fn expand_tokens(value: &str, hostname: &str, remote_user: &str) -> String {
+    let home = dirs::home_dir()
+        .map(|h| h.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let value = match value.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("{home}{rest}"),
+        _ => value.to_string(),
+    };
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('d') => out.push_str(&home),
+            Some('h') => out.push_str(hostname),
+            Some('r') => out.push_str(remote_user),
+            Some('u') => out.push_str(&local_username()),
+            Some('%') => out.push('%'),
+            Some(other) => {
+                out.push('%');
+                out.push(other);
+            }
+            None => out.push('%'),
+        }
+    }
+    out
+}
+
+/// Name of the user running the editor, which ssh uses when none is given
+pub fn local_username() -> String {
+    ["USER", "LOGNAME", "USERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|name| !name.is_empty()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_host_alias_with_wildcard_defaults() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = write(
+            temp.path(),
+            "config",
+            "# comment\n\
+             Host myserver web-*\n\
+             \x20   HostName 10.0.0.5\n\
+             \x20   User deploy\n\
+             \x20   Port=2222\n\
+             \x20   IdentityFile \"/keys/%h key\"\n\
+             \n\
+             Host *\n\
+             \x20   User fallback\n\
+             \x20   Port 22\n\
+             \x20   IdentityFile /keys/default\n",
+        );
+        let config = SshConfig::load_from(&config, temp.path());
+
+        assert!(config.has_host("myserver"));
+        assert!(!config.has_host("web-1"));
+        assert_eq!(
+            config.lookup("myserver"),
+            HostSettings {
+                hostname: Some("10.0.0.5".to_string()),
+                user: Some("deploy".to_string()),
+                port: Some(2222),
+                identity_file: Some(PathBuf::from("/keys/10.0.0.5 key")),
+            }
+        );
+        assert_eq!(config.lookup("web-1").user.as_deref(), Some("deploy"));
+        assert_eq!(
+            config.lookup("other"),
+            HostSettings {
+                hostname: None,
+                user: Some("fallback".to_string()),
+                port: Some(22),
+                identity_file: Some(PathBuf::from("/keys/default")),
+            }
+        );
+    }
+
+    #[test]
+    fn test_negated_patterns_and_match_blocks() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = write(
+            temp.path(),
+            "config",
+            "Match host foo\n  User from-match\n\
+             Host *.internal !bastion.internal\n  User inside\n\
+             Host *\n  User outside\n",
+        );
+        let config = SshConfig::load_from(&config, temp.path());
+        assert_eq!(config.lookup("db.internal").user.as_deref(), Some("inside"));
+        assert_eq!(
+            config.lookup("bastion.internal").user.as_deref(),
+            Some("outside")
+        );
+        assert_eq!(config.lookup("foo").user.as_deref(), Some("outside"));
+    }
+
+    #[test]
+    fn test_include_globs_relative_to_ssh_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        write(
+            temp.path(),
+            "config.d/10-work",
+            "Host work\n  User alice\n  Port 2200\n",
+        );
+        write(temp.path(), "config.d/20-home", "Host home\n  User bob\n");
+        write(temp.path(), "extra", "Port 2300\n");
+        let config = write(
+            temp.path(),
+            "config",
+            "Include config.d/*\n\
+             Host home\n  Include extra\n\
+             Host *\n  Port 22\n",
+        );
+        let config = SshConfig::load_from(&config, temp.path());
+
+        assert!(config.has_host("work"));
+        assert_eq!(config.lookup("work").user.as_deref(), Some("alice"));
+        assert_eq!(config.lookup("work").port, Some(2200));
+        // Options at the top of an included file belong to the enclosing Host
+        assert_eq!(config.lookup("home").port, Some(2300));
+        assert_eq!(config.lookup("other").port, Some(22));
+    }
+
+    #[test]
+    fn test_include_cycle_terminates() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = write(temp.path(), "config", "Include config\nHost a\n  User x\n");
+        let config = SshConfig::load_from(&config, temp.path());
+        assert_eq!(config.lookup("a").user.as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn test_missing_config_is_empty() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = SshConfig::load_from(&temp.path().join("nope"), temp.path());
+        assert!(!config.has_host("anything"));
+        assert_eq!(config.lookup("anything"), HostSettings::default());
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("web-?", "web-1"));
+        assert!(!wildcard_match("web-?", "web-10"));
+        assert!(wildcard_match("*.example.*", "a.example.org"));
+        assert!(!wildcard_match("*.example", "example"));
+    }
+}
@@ -0,0 +1,149 @@
+//! Long-running processes on the remote host
+//!
+//! The agent starts the process for a `spawn` request, answers with a
+//! `started` message once it runs, and then streams its stdout and stderr as
+//! the request's data until it exits. The request's ID names the process to
+//! the `stdin` requests that feed its input, which are sent from one task so
+//! they reach the agent in order, and to the `kill` request that ends it.
+
+use crate::services::remote::channel::AgentChannel;
+use crate::services::remote::protocol::{
+    decode_base64, kill_params, spawn_params, stdin_close_params, stdin_params,
+};
+use crate::services::remote::spawner::{SpawnError, StdioProcess};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+/// Bytes buffered in each direction between the process and its reader or
+/// writer
+const PIPE_BYTES: usize = 64 * 1024;
+
+/// Start `command` on the remote host with piped stdin and stdout, writing
+/// its stderr to `stderr`
+///
+/// Waits until the process runs, so a command missing on the remote host is
+/// reported here ("command not found: ...").
+pub async fn spawn_stdio(
+    channel: Arc<AgentChannel>,
+    command: &str,
+    args: &[String],
+    env: &HashMap<String, String>,
+    mut stderr: std::fs::File,
+) -> Result<StdioProcess, SpawnError> {
+    let runtime = channel.runtime_handle().clone();
+    let (process_id, mut data_rx, result_rx) = channel
+        .request_streaming_with_id("spawn", spawn_params(command, args, env))
+        .await?;
+
+    // Anything but `started` means the agent gave up on the request
+    let started = data_rx.recv().await;
+    if started.is_none_or(|data| data.get("started").is_none()) {
+        return Err(match result_rx.await {
+            Ok(Err(error)) => SpawnError::Process(error),
+            _ => SpawnError::Process(format!("{} exited at once", command)),
+        });
+    }
+
+    let (chunk_tx, mut chunk_rx) = mpsc::unbounded_channel::<Vec<u8>>();
+    let name = command.to_string();
+    runtime.spawn(async move {
+        // Keep draining after the reader is gone: unread data would hold up
+        // the responses to every other request
+        while let Some(data) = data_rx.recv().await {
+            for (key, value) in data.as_object().into_iter().flatten() {
+                let Some(bytes) = value.as_str().and_then(|s| decode_base64(s).ok()) else {
+                    continue;
+                };
+                match key.as_str() {
+                    "out" => {
+                        // The reader may already be gone
+                        #[allow(clippy::let_underscore_must_use)]
+                        let _ = chunk_tx.send(bytes);
+                    }
+                    "err" => {
+                        if let Err(e) = stderr.write_all(&bytes) {
+                            tracing::debug!("Could not log stderr of remote {}: {}", name, e);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        match result_rx.await {
+            Ok(Ok(result)) => tracing::info!("Remote {} exited: {}", name, result),
+            Ok(Err(error)) => tracing::warn!("Remote {} failed: {}", name, error),
+            Err(_) => tracing::warn!("Remote {} lost with the connection", name),
+        }
+    });
+
+    let (mut stdout_writer, stdout) = tokio::io::duplex(PIPE_BYTES);
+    runtime.spawn(async move {
+        while let Some(chunk) = chunk_rx.recv().await {
+            if stdout_writer.write_all(&chunk).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let (stdin, mut stdin_reader) = tokio::io::duplex(PIPE_BYTES);
+    let input_channel = Arc::clone(&channel);
+    runtime.spawn(async move {
+        let mut buf = vec![0; PIPE_BYTES];
+        loop {
+            let params = match stdin_reader.read(&mut buf).await {
+                Ok(0) | Err(_) => stdin_close_params(process_id),
+                Ok(n) => stdin_params(process_id, &buf[..n]),
+            };
+            let closing = params.get("close").is_some();
+            // Don't wait for the answer, so writes aren't held up by the
+            // round trip; writes to a process that has exited fail
+            match input_channel.request_streaming("stdin", params).await {
+                Ok((_, result_rx)) => {
+                    tokio::spawn(async move {
+                        if let Ok(Err(error)) = result_rx.await {
+                            tracing::debug!("Remote process {} stdin: {}", process_id, error);
+                        }
+                    });
+                }
+                Err(e) => {
+                    tracing::debug!("Remote process {} stdin: {}", process_id, e);
+                    break;
+                }
+            }
+            if closing {
+                break;
+            }
+        }
+    });
+
+    Ok(StdioProcess {
+        stdin: Box::new(stdin),
+        stdout: Box::new(stdout),
+        guard: Box::new(KillOnDrop {
+            channel,
+            process_id,
+        }),
+    })
+}
+
+/// Ends the remote process when dropped
+struct KillOnDrop {
+    channel: Arc<AgentChannel>,
+    process_id: u64,
+}
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let channel = Arc::clone(&self.channel);
+        let process_id = self.process_id;
+        self.channel.runtime_handle().spawn(async move {
+            // Fails if the process has already exited
+            if let Err(e) = channel.request("kill", kill_params(process_id)).await {
+                tracing::debug!("Remote process {} kill: {}", process_id, e);
+            }
+        });
+    }
+}
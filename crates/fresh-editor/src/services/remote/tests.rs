@@ -132,6 +132,23 @@ fn test_exec_params() {
     assert!(params.get("cwd").is_none());
 }
 
+#[test]
+fn test_spawn_and_stdin_params() {
+    let env = std::collections::HashMap::from([("RUST_LOG".to_string(), "info".to_string())]);
+    let params = spawn_params("rust-analyzer", &[], &env);
+    assert_eq!(params["cmd"], "rust-analyzer");
+    assert_eq!(params["args"], serde_json::json!([]));
+    assert_eq!(params["env"]["RUST_LOG"], "info");
+
+    let params = stdin_params(7, b"Content-Length: 2\r\n\r\n{}");
+    assert_eq!(params["id"], 7);
+    assert_eq!(
+        decode_base64(params["data"].as_str().unwrap()).unwrap(),
+        b"Content-Length: 2\r\n\r\n{}"
+    );
+    assert_eq!(stdin_close_params(7)["close"], true);
+}
+
 #[test]
 fn test_cancel_params() {
     let params = cancel_params(42);
@@ -0,0 +1,267 @@
+//! Chunked transfers of remote files
+//!
+//! Remote files the editor would otherwise read or write in one request are
+//! moved in [`CHUNK_BYTES`] pieces on the runtime's blocking pool instead, so
+//! the UI keeps running. Downloads send each piece as
+//! [`AsyncMessage::RemoteDownloadChunk`] for the buffer to append; uploads
+//! write to a temp file next to the target, report
+//! [`AsyncMessage::RemoteUploadProgress`] and rename it over the target at
+//! the end. Both finish with [`AsyncMessage::RemoteTransferFinished`], unless
+//! cancelled.
+
+use crate::model::filesystem::FileSystem;
+use crate::services::async_bridge::AsyncMessage;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+
+/// Files this large or larger are transferred in chunks
+pub const STREAMED_TRANSFER_BYTES: u64 = 256 * 1024;
+
+/// Bytes read or written per request
+const CHUNK_BYTES: usize = 256 * 1024;
+
+/// Tells the messages of a transfer from those of others
+static NEXT_TRANSFER_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A download or upload running in the background. Dropping it cancels the
+/// transfer; a cancelled upload leaves the target untouched.
+pub struct RemoteTransfer {
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl RemoteTransfer {
+    /// Download the first `size` bytes of `path` on `runtime`
+    pub fn download(
+        runtime: &tokio::runtime::Handle,
+        sender: mpsc::Sender<AsyncMessage>,
+        filesystem: Arc<dyn FileSystem + Send + Sync>,
+        path: PathBuf,
+        size: u64,
+    ) -> Self {
+        let transfer = Self::new();
+        let worker = Worker {
+            transfer_id: transfer.id,
+            cancelled: Arc::clone(&transfer.cancelled),
+            sender,
+        };
+        runtime.spawn_blocking(move || worker.download(filesystem.as_ref(), &path, size));
+        transfer
+    }
+
+    /// Replace `path` with `contents` on `runtime`
+    pub fn upload(
+        runtime: &tokio::runtime::Handle,
+        sender: mpsc::Sender<AsyncMessage>,
+        filesystem: Arc<dyn FileSystem + Send + Sync>,
+        path: PathBuf,
+        contents: Vec<u8>,
+    ) -> Self {
+        let transfer = Self::new();
+        let worker = Worker {
+            transfer_id: transfer.id,
+            cancelled: Arc::clone(&transfer.cancelled),
+            sender,
+        };
+        runtime.spawn_blocking(move || worker.upload(filesystem.as_ref(), &path, &contents));
+        transfer
+    }
+
+    fn new() -> Self {
+        Self {
+            id: NEXT_TRANSFER_ID.fetch_add(1, Ordering::Relaxed),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Id carried by the transfer's messages
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl Drop for RemoteTransfer {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// The blocking side of a transfer
+struct Worker {
+    transfer_id: u64,
+    cancelled: Arc<AtomicBool>,
+    sender: mpsc::Sender<AsyncMessage>,
+}
+
+impl Worker {
+    fn download(&self, filesystem: &dyn FileSystem, path: &Path, size: u64) {
+        let mut offset = 0;
+        while offset < size {
+            if self.cancelled() {
+                return;
+            }
+            let len = CHUNK_BYTES.min((size - offset) as usize);
+            let bytes = match filesystem.read_range(path, offset, len) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    self.finish(Err(e));
+                    return;
+                }
+            };
+            offset += bytes.len() as u64;
+            let message = AsyncMessage::RemoteDownloadChunk {
+                transfer_id: self.transfer_id,
+                bytes,
+            };
+            if self.sender.send(message).is_err() {
+                // The editor is gone
+                return;
+            }
+        }
+        self.finish(Ok(()));
+    }
+
+    fn upload(&self, filesystem: &dyn FileSystem, path: &Path, contents: &[u8]) {
+        let temp_path = filesystem.temp_path_for(path);
+        match self.write_chunks(filesystem, &temp_path, path, contents) {
+            Ok(true) => self.finish(Ok(())),
+            result => {
+                if let Err(e) = filesystem.remove_file(&temp_path) {
+                    tracing::debug!("Could not remove {}: {}", temp_path.display(), e);
+                }
+                if let Err(e) = result {
+                    self.finish(Err(e));
+                }
+            }
+        }
+    }
+
+    /// Write `contents` to `temp_path` and move it over `path`. Returns
+    /// false if cancelled before the move.
+    fn write_chunks(
+        &self,
+        filesystem: &dyn FileSystem,
+        temp_path: &Path,
+        path: &Path,
+        contents: &[u8],
+    ) -> io::Result<bool> {
+        filesystem.create_file(temp_path)?;
+        let mut sent = 0;
+        for chunk in contents.chunks(CHUNK_BYTES) {
+            if self.cancelled() {
+                return Ok(false);
+            }
+            let mut writer = filesystem.open_file_for_append(temp_path)?;
+            writer.write_all(chunk)?;
+            writer.sync_all()?;
+            sent += chunk.len() as u64;
+            // The editor may already be shutting down
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = self.sender.send(AsyncMessage::RemoteUploadProgress {
+                transfer_id: self.transfer_id,
+                sent,
+            });
+        }
+        // Keep the mode of the file being replaced
+        if let Some(permissions) = filesystem
+            .metadata_if_exists(path)
+            .and_then(|metadata| metadata.permissions)
+        {
+            filesystem.set_permissions(temp_path, &permissions)?;
+        }
+        if self.cancelled() {
+            return Ok(false);
+        }
+        filesystem.rename(temp_path, path)?;
+        Ok(true)
+    }
+
+    fn cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn finish(&self, result: io::Result<()>) {
+        if self.cancelled() {
+            return;
+        }
+        // The editor may already be shutting down
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.sender.send(AsyncMessage::RemoteTransferFinished {
+            transfer_id: self.transfer_id,
+            result: result.map_err(|e| e.to_string()),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::filesystem::StdFileSystem;
+
+    fn test_worker() -> (Worker, mpsc::Receiver<AsyncMessage>) {
+        let (sender, receiver) = mpsc::channel();
+        let worker = Worker {
+            transfer_id: 7,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            sender,
+        };
+        (worker, receiver)
+    }
+
+    #[test]
+    fn test_download_and_upload_in_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("access.log");
+        let contents: Vec<u8> = (0..CHUNK_BYTES * 2 + 10).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &contents).unwrap();
+
+        let (worker, receiver) = test_worker();
+        worker.download(&StdFileSystem, &path, contents.len() as u64);
+        drop(worker);
+        let mut downloaded = Vec::new();
+        let mut finished = false;
+        for message in receiver {
+            match message {
+                AsyncMessage::RemoteDownloadChunk { transfer_id, bytes } => {
+                    assert_eq!(transfer_id, 7);
+                    assert!(bytes.len() <= CHUNK_BYTES);
+                    downloaded.extend(bytes);
+                }
+                AsyncMessage::RemoteTransferFinished { result, .. } => {
+                    assert!(result.is_ok());
+                    finished = true;
+                }
+                _ => unreachable!(),
+            }
+        }
+        assert!(finished);
+        assert_eq!(downloaded, contents);
+
+        let (worker, receiver) = test_worker();
+        worker.upload(&StdFileSystem, &path, b"replaced");
+        drop(worker);
+        assert!(receiver.iter().any(|message| matches!(
+            message,
+            AsyncMessage::RemoteTransferFinished { result: Ok(()), .. }
+        )));
+        assert_eq!(std::fs::read(&path).unwrap(), b"replaced");
+        assert!(!StdFileSystem.temp_path_for(&path).exists());
+    }
+
+    #[test]
+    fn test_cancelled_upload_leaves_target_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("access.log");
+        std::fs::write(&path, "original").unwrap();
+
+        let (worker, receiver) = test_worker();
+        worker.cancelled.store(true, Ordering::Relaxed);
+        worker.upload(&StdFileSystem, &path, b"replaced");
+        drop(worker);
+        assert_eq!(receiver.iter().count(), 0);
+        assert_eq!(std::fs::read(&path).unwrap(), b"original");
+        assert!(!StdFileSystem.temp_path_for(&path).exists());
+    }
+}
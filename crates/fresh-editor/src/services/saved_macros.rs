@@ -0,0 +1,148 @@
+//! Macros saved across sessions
+//!
+//! Recorded macros live in registers for the session only. Save Macro stores
+//! one under a name in `macros.json` in the config directory, and Load Macro
+//! copies it back into a register. The file is an object from name to the
+//! macro's actions, each written by name with its argument when it takes one
+//! (`"MoveDown"`, `{"InsertChar": "x"}`), so it can be edited by hand. An
+//! entry that no longer reads as actions is kept as it is and only reported
+//! when loaded. The file is re-read before each update so several running
+//! editors don't drop each other's macros.
+
+use crate::config_io::DirectoryContext;
+use crate::input::keybindings::Action;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+
+/// Saved macros by name
+#[derive(Debug, Default)]
+pub struct SavedMacros {
+    path: PathBuf,
+    macros: BTreeMap<String, serde_json::Value>,
+}
+
+impl SavedMacros {
+    /// Load the saved macros, starting empty if the file is missing
+    ///
+    /// A file that isn't a JSON object is an error rather than empty, so
+    /// saving doesn't overwrite a hand-edited file with a typo in it.
+    pub fn load(dir_context: &DirectoryContext) -> io::Result<Self> {
+        let path = dir_context.saved_macros_path();
+        let macros = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e),
+                )
+            })?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self { path, macros })
+    }
+
+    /// Write the macros back to disk
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.macros).map_err(io::Error::other)?;
+        std::fs::write(&self.path, json)
+    }
+
+    /// Store `actions` under `name`, replacing any macro saved with that name
+    pub fn insert(&mut self, name: &str, actions: &[Action]) -> serde_json::Result<()> {
+        self.macros
+            .insert(name.to_string(), serde_json::to_value(actions)?);
+        Ok(())
+    }
+
+    /// The actions saved under `name`, or the reason they can't be read
+    pub fn get(&self, name: &str) -> Option<serde_json::Result<Vec<Action>>> {
+        self.macros
+            .get(name)
+            .map(|value| serde_json::from_value(value.clone()))
+    }
+
+    /// The saved macros by name, each with its actions or the reason they
+    /// can't be read
+    pub fn iter(&self) -> impl Iterator<Item = (&str, serde_json::Result<Vec<Action>>)> {
+        self.macros
+            .iter()
+            .map(|(name, value)| (name.as_str(), serde_json::from_value(value.clone())))
+    }
+
+    /// Number of saved macros
+    pub fn len(&self) -> usize {
+        self.macros.len()
+    }
+
+    /// Whether no macros are saved
+    pub fn is_empty(&self) -> bool {
+        self.macros.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+        let actions = vec![
+            Action::InsertChar('x'),
+            Action::MoveLineEnd,
+            Action::PromptConfirmWithText("42".to_string()),
+        ];
+
+        let mut saved = SavedMacros::load(&dir_context).unwrap();
+        assert!(saved.is_empty());
+        saved.insert("append x", &actions).unwrap();
+        saved.save().unwrap();
+
+        let saved = SavedMacros::load(&dir_context).unwrap();
+        assert_eq!(saved.get("append x").unwrap().unwrap(), actions);
+        assert!(saved.get("missing").is_none());
+    }
+
+    #[test]
+    fn test_hand_edited_file_is_read_and_kept() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+        let path = dir_context.saved_macros_path();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"{"edited": [{"InsertChar": "a"}, "InsertNewline"], "broken": ["NoSuchAction"]}"#,
+        )
+        .unwrap();
+
+        let mut saved = SavedMacros::load(&dir_context).unwrap();
+        assert_eq!(
+            saved.get("edited").unwrap().unwrap(),
+            [Action::InsertChar('a'), Action::InsertNewline]
+        );
+        assert!(saved.get("broken").unwrap().is_err());
+
+        // Saving another macro leaves the unreadable entry for fixing by hand
+        saved.insert("new", &[Action::MoveDown]).unwrap();
+        saved.save().unwrap();
+        let saved = SavedMacros::load(&dir_context).unwrap();
+        let names: Vec<_> = saved.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["broken", "edited", "new"]);
+    }
+
+    #[test]
+    fn test_invalid_file_is_an_error() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+        let path = dir_context.saved_macros_path();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{ not json").unwrap();
+
+        assert!(SavedMacros::load(&dir_context).is_err());
+    }
+}
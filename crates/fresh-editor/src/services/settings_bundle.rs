@@ -0,0 +1,843 @@
+//! Settings bundles for moving a user's setup between machines.
+//!
+//! A bundle is a single versioned JSON file holding the user config,
+//! keybindings, user themes, the installed package list and recorded macros.
+//! Paths under the config or home directory are stored as `${config_dir}` /
+//! `${home}` placeholders so the bundle can be imported on another machine,
+//! and token-like LSP environment variables are never written out.
+//!
+//! Bundles written by newer versions are read leniently: sections this
+//! version doesn't understand are skipped and reported instead of failing
+//! the whole import.
+
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsStr;
+use std::io;
+use std::path::{Path, MAIN_SEPARATOR};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::config::Config;
+use crate::input::keybindings::Action;
+
+/// Version of the bundle layout written by this build
+pub const BUNDLE_FORMAT_VERSION: u64 = 1;
+
+/// Placeholder for the fresh config directory inside bundled paths
+const CONFIG_DIR_VAR: &str = "${config_dir}";
+
+/// Placeholder for the user's home directory inside bundled paths
+const HOME_VAR: &str = "${home}";
+
+/// Top-level config keys that belong to the keybindings category
+const KEYBINDING_KEYS: &[&str] = &["keybindings", "keybinding_maps", "active_keybinding_map"];
+
+/// Words that mark an environment variable name as holding a secret. They
+/// match whole `_`-separated segments, so `AUTH` doesn't catch `AUTHOR`.
+const SECRET_KEY_MARKERS: &[&str] = &[
+    "TOKEN",
+    "SECRET",
+    "PASSWORD",
+    "PASSWD",
+    "API_KEY",
+    "APIKEY",
+    "CREDENTIAL",
+    "PRIVATE_KEY",
+    "AUTH",
+];
+
+/// Package directories under the config dir, with the package kind they hold
+const PACKAGE_DIRS: &[(&str, &str)] = &[
+    ("plugin", "plugins/packages"),
+    ("theme", "themes/packages"),
+    ("language", "languages/packages"),
+    ("bundle", "bundles/packages"),
+];
+
+/// A category of settings that can be exported and individually opted out
+/// of on import.
+///
+/// There is no snippets category: Fresh only expands snippets sent by
+/// language servers and keeps no user snippet files to carry. A `snippets`
+/// section in a bundle is skipped like any other unknown section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BundleCategory {
+    Config,
+    Keybindings,
+    Themes,
+    Packages,
+    Macros,
+}
+
+impl BundleCategory {
+    pub const ALL: [BundleCategory; 5] = [
+        BundleCategory::Config,
+        BundleCategory::Keybindings,
+        BundleCategory::Themes,
+        BundleCategory::Packages,
+        BundleCategory::Macros,
+    ];
+
+    /// Section name used in the bundle file and in the import prompt
+    pub fn key(self) -> &'static str {
+        match self {
+            BundleCategory::Config => "config",
+            BundleCategory::Keybindings => "keybindings",
+            BundleCategory::Themes => "themes",
+            BundleCategory::Packages => "packages",
+            BundleCategory::Macros => "macros",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.key() == key)
+    }
+}
+
+/// An installed package, recorded so it can be reinstalled elsewhere
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundledPackage {
+    pub name: String,
+    /// "plugin", "theme", "language" or "bundle"
+    pub kind: String,
+    pub version: String,
+    /// Git URL or relocated local path the package was installed from
+    pub source: String,
+}
+
+/// The contents of a settings bundle
+///
+/// Each section is `None` when absent from the bundle, so an import only
+/// touches what was actually exported.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SettingsBundle {
+    /// Version of fresh that wrote the bundle
+    pub fresh_version: String,
+    /// User config without keybinding keys
+    pub config: Option<Map<String, Value>>,
+    /// The keybinding keys of the user config
+    pub keybindings: Option<Map<String, Value>>,
+    /// User theme files by file name
+    pub themes: Option<BTreeMap<String, String>>,
+    pub packages: Option<Vec<BundledPackage>>,
+    pub macros: Option<BTreeMap<char, Vec<Action>>>,
+    /// Sections (or entries) present in the file that this version could
+    /// not read; they are skipped on import
+    pub skipped: Vec<String>,
+}
+
+impl SettingsBundle {
+    /// Gather the settings stored under `config_dir`, together with the
+    /// recorded `macros`.
+    ///
+    /// Returns the bundle and the dotted names of secret values that were
+    /// left out of it.
+    pub fn collect(
+        config_dir: &Path,
+        home_dir: Option<&Path>,
+        macros: &HashMap<char, Vec<Action>>,
+    ) -> io::Result<(Self, Vec<String>)> {
+        let mut excluded = Vec::new();
+        let mut config = read_user_config(config_dir)?;
+        strip_secrets(&mut config, &mut excluded);
+        for value in config.values_mut() {
+            relocate_paths(value, config_dir, home_dir);
+        }
+        let keybindings = split_keybindings(&mut config);
+
+        let mut themes = BTreeMap::new();
+        let themes_dir = config_dir.join("themes");
+        if themes_dir.is_dir() {
+            for entry in std::fs::read_dir(&themes_dir)? {
+                let path = entry?.path();
+                if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+                    if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                        themes.insert(name.to_string(), std::fs::read_to_string(&path)?);
+                    }
+                }
+            }
+        }
+
+        let mut packages = installed_packages(config_dir)?;
+        for package in &mut packages {
+            let mut source = Value::String(std::mem::take(&mut package.source));
+            relocate_paths(&mut source, config_dir, home_dir);
+            if let Value::String(source) = source {
+                package.source = source;
+            }
+        }
+
+        let bundle = Self {
+            fresh_version: env!("CARGO_PKG_VERSION").to_string(),
+            config: Some(config),
+            keybindings: Some(keybindings),
+            themes: Some(themes),
+            packages: Some(packages),
+            macros: Some(macros.iter().map(|(k, v)| (*k, v.clone())).collect()),
+            skipped: Vec::new(),
+        };
+        Ok((bundle, excluded))
+    }
+
+    /// Serialize the bundle to its file representation
+    pub fn to_json(&self) -> String {
+        let mut root = Map::new();
+        root.insert("format_version".into(), BUNDLE_FORMAT_VERSION.into());
+        root.insert("fresh_version".into(), self.fresh_version.clone().into());
+        if let Some(config) = &self.config {
+            root.insert("config".into(), Value::Object(config.clone()));
+        }
+        if let Some(keybindings) = &self.keybindings {
+            root.insert("keybindings".into(), Value::Object(keybindings.clone()));
+        }
+        if let Some(themes) = &self.themes {
+            root.insert("themes".into(), serde_json::json!(themes));
+        }
+        if let Some(packages) = &self.packages {
+            root.insert("packages".into(), serde_json::json!(packages));
+        }
+        if let Some(macros) = &self.macros {
+            root.insert("macros".into(), serde_json::json!(macros));
+        }
+        serde_json::to_string_pretty(&Value::Object(root)).unwrap_or_default()
+    }
+
+    /// Parse a bundle file, skipping sections this version can't read
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let root: Map<String, Value> = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        if !root.get("format_version").is_some_and(Value::is_u64) {
+            return Err("not a fresh settings bundle (missing format_version)".to_string());
+        }
+
+        let mut bundle = Self {
+            fresh_version: root
+                .get("fresh_version")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown")
+                .to_string(),
+            ..Default::default()
+        };
+
+        for (key, value) in root {
+            match key.as_str() {
+                "format_version" | "fresh_version" => {}
+                "config" => match value {
+                    Value::Object(map) => bundle.config = Some(map),
+                    _ => bundle.skipped.push(key),
+                },
+                "keybindings" => match value {
+                    Value::Object(map) => bundle.keybindings = Some(map),
+                    _ => bundle.skipped.push(key),
+                },
+                "themes" => match serde_json::from_value::<BTreeMap<String, String>>(value) {
+                    Ok(mut themes) => {
+                        // Theme names become file names; never let them
+                        // point outside the themes directory
+                        themes.retain(|name, _| {
+                            let plain = Path::new(name).file_name() == Some(OsStr::new(name))
+                                && name.ends_with(".json");
+                            if !plain {
+                                bundle.skipped.push(format!("themes.{}", name));
+                            }
+                            plain
+                        });
+                        bundle.themes = Some(themes);
+                    }
+                    Err(_) => bundle.skipped.push(key),
+                },
+                "packages" => match serde_json::from_value(value) {
+                    Ok(packages) => bundle.packages = Some(packages),
+                    Err(_) => bundle.skipped.push(key),
+                },
+                "macros" => {
+                    let Value::Object(entries) = value else {
+                        bundle.skipped.push(key);
+                        continue;
+                    };
+                    let mut macros = BTreeMap::new();
+                    for (register, actions) in entries {
+                        let mut chars = register.chars();
+                        let parsed = serde_json::from_value::<Vec<Action>>(actions);
+                        match (chars.next(), chars.next(), parsed) {
+                            (Some(c), None, Ok(actions)) => {
+                                macros.insert(c, actions);
+                            }
+                            // Unknown actions from a newer version
+                            _ => bundle.skipped.push(format!("macros.{}", register)),
+                        }
+                    }
+                    bundle.macros = Some(macros);
+                }
+                _ => bundle.skipped.push(key),
+            }
+        }
+        Ok(bundle)
+    }
+}
+
+/// Describe what importing `incoming` would change relative to `local`.
+///
+/// Both bundles are expected in their relocated form (as produced by
+/// [`SettingsBundle::collect`]). Categories without changes are omitted.
+pub fn review(
+    local: &SettingsBundle,
+    incoming: &SettingsBundle,
+) -> BTreeMap<BundleCategory, Vec<String>> {
+    let mut changes = BTreeMap::new();
+
+    for (category, ours, theirs) in [
+        (BundleCategory::Config, &local.config, &incoming.config),
+        (
+            BundleCategory::Keybindings,
+            &local.keybindings,
+            &incoming.keybindings,
+        ),
+    ] {
+        if let Some(theirs) = theirs {
+            let mut lines = Vec::new();
+            diff_values(
+                "",
+                &Value::Object(ours.clone().unwrap_or_default()),
+                &Value::Object(theirs.clone()),
+                &mut lines,
+            );
+            changes.insert(category, lines);
+        }
+    }
+
+    if let Some(themes) = &incoming.themes {
+        let ours = local.themes.clone().unwrap_or_default();
+        let lines = themes
+            .iter()
+            .filter_map(|(name, content)| match ours.get(name) {
+                None => Some(format!("+ {}", name)),
+                Some(existing) if existing != content => Some(format!("~ {}", name)),
+                Some(_) => None,
+            })
+            .collect();
+        changes.insert(BundleCategory::Themes, lines);
+    }
+
+    if let Some(packages) = &incoming.packages {
+        let ours = local.packages.clone().unwrap_or_default();
+        let lines = packages
+            .iter()
+            .filter_map(|pkg| {
+                match ours
+                    .iter()
+                    .find(|p| p.kind == pkg.kind && p.name == pkg.name)
+                {
+                    None => Some(format!(
+                        "+ {} {} {} ({})",
+                        pkg.kind, pkg.name, pkg.version, pkg.source
+                    )),
+                    Some(existing) if existing.version != pkg.version => Some(format!(
+                        "~ {} {}: {} -> {}",
+                        pkg.kind, pkg.name, existing.version, pkg.version
+                    )),
+                    Some(_) => None,
+                }
+            })
+            .collect();
+        changes.insert(BundleCategory::Packages, lines);
+    }
+
+    if let Some(macros) = &incoming.macros {
+        let ours = local.macros.clone().unwrap_or_default();
+        let lines = macros
+            .iter()
+            .filter_map(|(register, actions)| match ours.get(register) {
+                None => Some(format!("+ '{}' ({} actions)", register, actions.len())),
+                Some(existing) if existing != actions => {
+                    Some(format!("~ '{}' ({} actions)", register, actions.len()))
+                }
+                Some(_) => None,
+            })
+            .collect();
+        changes.insert(BundleCategory::Macros, lines);
+    }
+
+    changes.retain(|_, lines: &mut Vec<String>| !lines.is_empty());
+    changes
+}
+
+/// Render a review as the text shown before an import is applied
+pub fn format_review(
+    bundle: &SettingsBundle,
+    changes: &BTreeMap<BundleCategory, Vec<String>>,
+) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "Settings bundle from fresh {} (+ added, - removed, ~ changed)\n",
+        bundle.fresh_version
+    ));
+    if !bundle.skipped.is_empty() {
+        out.push_str(&format!(
+            "Skipped sections not supported by this version: {}\n",
+            bundle.skipped.join(", ")
+        ));
+    }
+    for (category, lines) in changes {
+        out.push_str(&format!("\n[{}]\n", category.key()));
+        if *category == BundleCategory::Packages {
+            out.push_str(
+                "Packages are not installed automatically; install them with the package manager.\n",
+            );
+        }
+        for line in lines {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Build the user config that results from importing `categories` of
+/// `incoming` over the `current` user config file contents.
+///
+/// Placeholders are expanded for this machine, and secrets that only exist
+/// locally (and were therefore never bundled) are kept.
+pub fn merge_user_config(
+    current: &Map<String, Value>,
+    incoming: &SettingsBundle,
+    categories: &[BundleCategory],
+    config_dir: &Path,
+    home_dir: Option<&Path>,
+) -> Map<String, Value> {
+    let mut merged = current.clone();
+
+    if let Some(config) = incoming
+        .config
+        .as_ref()
+        .filter(|_| categories.contains(&BundleCategory::Config))
+    {
+        merged.retain(|key, _| KEYBINDING_KEYS.contains(&key.as_str()));
+        merged.extend(config.clone());
+        restore_secrets(current, &mut merged);
+    }
+    if let Some(keybindings) = incoming
+        .keybindings
+        .as_ref()
+        .filter(|_| categories.contains(&BundleCategory::Keybindings))
+    {
+        merged.retain(|key, _| !KEYBINDING_KEYS.contains(&key.as_str()));
+        merged.extend(keybindings.clone());
+    }
+
+    for value in merged.values_mut() {
+        expand_paths(value, config_dir, home_dir);
+    }
+    merged
+}
+
+/// Read the user config file as a JSON object (empty when missing)
+pub fn read_user_config(config_dir: &Path) -> io::Result<Map<String, Value>> {
+    let path = config_dir.join(Config::FILENAME);
+    match std::fs::read_to_string(&path) {
+        Ok(text) => {
+            serde_json::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Map::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether an environment variable name looks like it holds a secret
+fn is_secret_key(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    let segments: Vec<&str> = upper.split(['_', '-', '.']).collect();
+    SECRET_KEY_MARKERS.iter().any(|marker| {
+        let words: Vec<&str> = marker.split('_').collect();
+        segments.windows(words.len()).any(|window| window == words)
+    })
+}
+
+/// Remove secret-looking LSP environment variables, recording their names
+fn strip_secrets(config: &mut Map<String, Value>, excluded: &mut Vec<String>) {
+    let Some(Value::Object(servers)) = config.get_mut("lsp") else {
+        return;
+    };
+    for (language, server) in servers.iter_mut() {
+        if let Some(Value::Object(env)) = server.get_mut("env") {
+            env.retain(|name, _| {
+                let secret = is_secret_key(name);
+                if secret {
+                    excluded.push(format!("lsp.{}.env.{}", language, name));
+                }
+                !secret
+            });
+        }
+    }
+}
+
+/// Copy secret-looking LSP environment variables from `source` into servers
+/// that still exist in `target`
+fn restore_secrets(source: &Map<String, Value>, target: &mut Map<String, Value>) {
+    let Some(Value::Object(source_servers)) = source.get("lsp") else {
+        return;
+    };
+    let Some(Value::Object(target_servers)) = target.get_mut("lsp") else {
+        return;
+    };
+    for (language, server) in source_servers {
+        let (Some(Value::Object(env)), Some(Value::Object(target_server))) =
+            (server.get("env"), target_servers.get_mut(language))
+        else {
+            continue;
+        };
+        let target_env = target_server
+            .entry("env")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(target_env) = target_env {
+            for (name, value) in env.iter().filter(|(name, _)| is_secret_key(name)) {
+                target_env
+                    .entry(name.clone())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+    }
+}
+
+/// Move the keybinding keys out of `config` into their own map
+fn split_keybindings(config: &mut Map<String, Value>) -> Map<String, Value> {
+    KEYBINDING_KEYS
+        .iter()
+        .filter_map(|key| config.remove(*key).map(|value| (key.to_string(), value)))
+        .collect()
+}
+
+/// Replace `prefix` at the start of a path string with `var`
+fn replace_path_prefix(s: &str, prefix: &str, var: &str) -> Option<String> {
+    if prefix.is_empty() {
+        return None;
+    }
+    let rest = s.strip_prefix(prefix)?;
+    (rest.is_empty() || rest.starts_with(['/', MAIN_SEPARATOR])).then(|| format!("{}{}", var, rest))
+}
+
+/// Rewrite every string under the config or home directory to use the
+/// matching placeholder
+fn relocate_paths(value: &mut Value, config_dir: &Path, home_dir: Option<&Path>) {
+    let config_dir = config_dir.to_string_lossy();
+    let home_dir = home_dir.map(|h| h.to_string_lossy().into_owned());
+    map_strings(value, &mut |s| {
+        replace_path_prefix(s, &config_dir, CONFIG_DIR_VAR).or_else(|| {
+            home_dir
+                .as_deref()
+                .and_then(|home| replace_path_prefix(s, home, HOME_VAR))
+        })
+    });
+}
+
+/// Inverse of [`relocate_paths`] for this machine's directories
+fn expand_paths(value: &mut Value, config_dir: &Path, home_dir: Option<&Path>) {
+    let config_dir = config_dir.to_string_lossy();
+    let home_dir = home_dir.map(|h| h.to_string_lossy().into_owned());
+    map_strings(value, &mut |s| {
+        replace_path_prefix(s, CONFIG_DIR_VAR, &config_dir).or_else(|| {
+            home_dir
+                .as_deref()
+                .and_then(|home| replace_path_prefix(s, HOME_VAR, home))
+        })
+    });
+}
+
+/// Apply `f` to every string in `value`, replacing it when `f` returns Some
+fn map_strings(value: &mut Value, f: &mut dyn FnMut(&str) -> Option<String>) {
+    match value {
+        Value::String(s) => {
+            if let Some(replaced) = f(s) {
+                *s = replaced;
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| map_strings(item, f)),
+        Value::Object(map) => map.values_mut().for_each(|item| map_strings(item, f)),
+        _ => {}
+    }
+}
+
+/// Record differences between two JSON values as `+`/`-`/`~` lines keyed
+/// by dotted path
+fn diff_values(path: &str, ours: &Value, theirs: &Value, lines: &mut Vec<String>) {
+    match (ours, theirs) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys: std::collections::BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                match (a.get(key), b.get(key)) {
+                    (Some(x), Some(y)) => diff_values(&child, x, y, lines),
+                    (None, Some(y)) => lines.push(format!("+ {} = {}", child, y)),
+                    (Some(x), None) => lines.push(format!("- {} (was {})", child, x)),
+                    (None, None) => {}
+                }
+            }
+        }
+        (a, b) if a != b => lines.push(format!("~ {}: {} -> {}", path, a, b)),
+        _ => {}
+    }
+}
+
+/// List packages installed under `config_dir`
+fn installed_packages(config_dir: &Path) -> io::Result<Vec<BundledPackage>> {
+    let mut packages = Vec::new();
+    for (kind, dir) in PACKAGE_DIRS {
+        let dir = config_dir.join(dir);
+        if !dir.is_dir() {
+            continue;
+        }
+        let mut entries: Vec<_> = std::fs::read_dir(&dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect();
+        entries.sort();
+        for path in entries {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if name.starts_with('.') {
+                continue;
+            }
+            let version = read_json(&path.join("package.json"))
+                .and_then(|m| m.get("version").and_then(Value::as_str).map(str::to_string))
+                .unwrap_or_else(|| "unknown".to_string());
+            packages.push(BundledPackage {
+                name: name.to_string(),
+                kind: kind.to_string(),
+                version,
+                source: package_source(&path).unwrap_or_default(),
+            });
+        }
+    }
+    Ok(packages)
+}
+
+/// Where a package was installed from: its git remote, or the source
+/// recorded by the package manager for local and monorepo installs
+fn package_source(path: &Path) -> Option<String> {
+    let git_config = std::fs::read_to_string(path.join(".git").join("config")).ok();
+    let remote = git_config.as_deref().and_then(|config| {
+        config.lines().find_map(|line| {
+            let (key, value) = line.trim().split_once('=')?;
+            (key.trim() == "url").then(|| value.trim().to_string())
+        })
+    });
+    remote.or_else(|| {
+        let source = read_json(&path.join(".fresh-source.json"))?;
+        ["original_url", "local_path"]
+            .iter()
+            .find_map(|key| source.get(*key).and_then(Value::as_str))
+            .map(str::to_string)
+    })
+}
+
+fn read_json(path: &Path) -> Option<Map<String, Value>> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn secret_keys_match_whole_segments() {
+        for name in [
+            "GITHUB_TOKEN",
+            "token",
+            "AUTH",
+            "NPM_AUTH_HEADER",
+            "OPENAI_API_KEY",
+            "GPG_PRIVATE_KEY",
+            "DB_PASSWORD",
+        ] {
+            assert!(is_secret_key(name), "{} should be secret", name);
+        }
+        for name in [
+            "AUTHOR",
+            "OAUTH_CALLBACK_URL",
+            "RUST_LOG",
+            "KEY_PATH",
+            "TOKENIZER_MODEL",
+        ] {
+            assert!(!is_secret_key(name), "{} should not be secret", name);
+        }
+    }
+
+    #[test]
+    fn collect_relocates_paths_and_strips_secrets() {
+        let temp = tempfile::tempdir().unwrap();
+        let config_dir = temp.path().join("config");
+        let home = temp.path().to_path_buf();
+        let config = json!({
+            "editor": { "tab_size": 2 },
+            "keybindings": [{ "key": "x", "modifiers": ["ctrl"], "action": "cut" }],
+            "lsp": { "rust": {
+                "command": format!("{}/bin/ra", home.display()),
+                "env": { "GITHUB_TOKEN": "abc", "RUST_LOG": "info" }
+            }},
+            "plugin_dir": format!("{}/plugins", config_dir.display()),
+        });
+        write(&config_dir.join("config.json"), &config.to_string());
+        write(&config_dir.join("themes/mine.json"), "{}");
+        write(
+            &config_dir.join("plugins/packages/hello/package.json"),
+            r#"{"version": "1.2.0"}"#,
+        );
+        write(
+            &config_dir.join("plugins/packages/hello/.git/config"),
+            "[remote \"origin\"]\n\turl = https://example.com/hello.git\n",
+        );
+
+        let (bundle, excluded) =
+            SettingsBundle::collect(&config_dir, Some(&home), &HashMap::new()).unwrap();
+
+        assert_eq!(excluded, vec!["lsp.rust.env.GITHUB_TOKEN".to_string()]);
+        let config = Value::Object(bundle.config.clone().unwrap());
+        assert_eq!(config["lsp"]["rust"]["command"], "${home}/bin/ra");
+        assert_eq!(config["lsp"]["rust"]["env"], json!({ "RUST_LOG": "info" }));
+        assert_eq!(config["plugin_dir"], "${config_dir}/plugins");
+        assert!(config.get("keybindings").is_none());
+        assert!(bundle.keybindings.unwrap().contains_key("keybindings"));
+        assert_eq!(
+            bundle.themes.unwrap().keys().collect::<Vec<_>>(),
+            ["mine.json"]
+        );
+        assert_eq!(
+            bundle.packages.unwrap(),
+            vec![BundledPackage {
+                name: "hello".into(),
+                kind: "plugin".into(),
+                version: "1.2.0".into(),
+                source: "https://example.com/hello.git".into(),
+            }]
+        );
+        assert!(!config.to_string().contains(&*home.to_string_lossy()));
+    }
+
+    #[test]
+    fn round_trip_and_unknown_sections_are_skipped() {
+        let bundle = SettingsBundle {
+            fresh_version: "1.0.0".into(),
+            config: Some(json!({ "theme": "dark" }).as_object().unwrap().clone()),
+            macros: Some(BTreeMap::from([('q', vec![Action::MoveDown])])),
+            ..Default::default()
+        };
+        let mut value: Value = serde_json::from_str(&bundle.to_json()).unwrap();
+        assert_eq!(value["format_version"], BUNDLE_FORMAT_VERSION);
+        assert_eq!(
+            SettingsBundle::from_json(&bundle.to_json()).unwrap(),
+            bundle
+        );
+
+        value["snippets"] = json!({ "rust": [] });
+        value["themes"] = json!({ "../escape.json": "{}", "ok.json": "{}" });
+        value["macros"]["w"] = json!(["SomeFutureAction"]);
+        let parsed = SettingsBundle::from_json(&value.to_string()).unwrap();
+        assert_eq!(
+            parsed.skipped,
+            vec![
+                "macros.w".to_string(),
+                "snippets".into(),
+                "themes.../escape.json".into()
+            ]
+        );
+        assert_eq!(
+            parsed.themes.unwrap().keys().collect::<Vec<_>>(),
+            ["ok.json"]
+        );
+        assert_eq!(parsed.macros, bundle.macros);
+        assert_eq!(parsed.config, bundle.config);
+
+        assert!(SettingsBundle::from_json(r#"{"theme": "dark"}"#).is_err());
+    }
+
+    #[test]
+    fn review_lists_changes_per_category() {
+        let local = SettingsBundle {
+            config: Some(
+                json!({ "editor": { "tab_size": 4, "rulers": [80] } })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            ),
+            themes: Some(BTreeMap::from([("a.json".into(), "{}".into())])),
+            ..Default::default()
+        };
+        let incoming = SettingsBundle {
+            config: Some(
+                json!({ "editor": { "tab_size": 2 }, "theme": "light" })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            ),
+            themes: Some(BTreeMap::from([("a.json".into(), "{}".into())])),
+            ..Default::default()
+        };
+
+        let changes = review(&local, &incoming);
+        assert_eq!(
+            changes[&BundleCategory::Config],
+            vec![
+                "- editor.rulers (was [80])".to_string(),
+                "~ editor.tab_size: 4 -> 2".into(),
+                "+ theme = \"light\"".into(),
+            ]
+        );
+        // Identical themes produce no review entry
+        assert!(!changes.contains_key(&BundleCategory::Themes));
+    }
+
+    #[test]
+    fn merge_respects_opt_out_and_keeps_local_secrets() {
+        let current = json!({
+            "theme": "dark",
+            "keybindings": [],
+            "lsp": { "rust": { "command": "ra", "env": { "API_TOKEN": "s3cret" } } },
+        });
+        let incoming = SettingsBundle {
+            config: Some(
+                json!({
+                    "theme": "light",
+                    "lsp": { "rust": { "command": "${config_dir}/ra", "env": {} } },
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            ),
+            keybindings: Some(
+                json!({ "keybindings": [{ "key": "k" }] })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            ),
+            ..Default::default()
+        };
+        let config_dir = Path::new("/cfg");
+
+        let merged = Value::Object(merge_user_config(
+            current.as_object().unwrap(),
+            &incoming,
+            &[BundleCategory::Config],
+            config_dir,
+            None,
+        ));
+        assert_eq!(merged["theme"], "light");
+        assert_eq!(merged["keybindings"], json!([]));
+        assert_eq!(
+            merged["lsp"]["rust"]["command"],
+            format!("{}/ra", config_dir.display())
+        );
+        assert_eq!(merged["lsp"]["rust"]["env"]["API_TOKEN"], "s3cret");
+    }
+}
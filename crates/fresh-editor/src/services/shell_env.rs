@@ -0,0 +1,342 @@
+//! Environment of spawned processes
+//!
+//! Editors launched from a desktop entry or an app launcher don't see the
+//! PATH and other variables set up by the user's shell profile, so LSP
+//! servers and formatters installed by version managers aren't found.
+//! `process.shell_env` chooses where local child processes (LSP servers,
+//! formatters, on-save actions, shell commands, terminals and plugin
+//! processes) get their environment from:
+//!
+//! - `inherit`: the editor's own environment, unchanged
+//! - `login_shell`: the environment printed by `$SHELL -lic env`, captured
+//!   once per process and reused
+//! - `explicit`: the editor's own environment plus `process.env`
+//!
+//! Per-server and per-command `env` maps are applied on top in every mode,
+//! with `${env:VAR}` expanded against the chosen environment. Processes on
+//! a remote host are spawned by the remote agent and keep its environment.
+
+use crate::config::{ProcessConfig, ShellEnvMode};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
+
+/// How long to wait for the login shell before falling back to inherit
+const LOGIN_SHELL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Printed before `env` so anything a shell profile prints is skipped
+const ENV_MARKER: &str = "__FRESH_SHELL_ENV__";
+
+static CURRENT: RwLock<Option<Arc<ProcessEnvironment>>> = RwLock::new(None);
+static LOGIN_SHELL_ENV: OnceLock<Result<BTreeMap<String, String>, String>> = OnceLock::new();
+
+/// Variables set on local child processes on top of the editor's environment
+#[derive(Debug, Clone, Default)]
+pub struct ProcessEnvironment {
+    mode: ShellEnvMode,
+    overlay: BTreeMap<String, String>,
+    error: Option<String>,
+}
+
+/// Apply the `process` config to every process spawned from now on.
+/// The login shell is only run the first time it's needed.
+pub fn configure(config: &ProcessConfig) {
+    let env = Arc::new(ProcessEnvironment::from_config(config));
+    if let Some(error) = &env.error {
+        tracing::warn!(
+            "Using the editor's environment for child processes: {}",
+            error
+        );
+    }
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = Some(env);
+}
+
+/// The environment for processes spawned now
+pub fn current() -> Arc<ProcessEnvironment> {
+    CURRENT
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+impl ProcessEnvironment {
+    fn from_config(config: &ProcessConfig) -> Self {
+        match config.shell_env {
+            ShellEnvMode::Inherit => Self::default(),
+            ShellEnvMode::LoginShell => {
+                match LOGIN_SHELL_ENV.get_or_init(capture_login_shell_env) {
+                    Ok(vars) => Self {
+                        mode: ShellEnvMode::LoginShell,
+                        overlay: vars.clone(),
+                        error: None,
+                    },
+                    Err(e) => Self {
+                        mode: ShellEnvMode::LoginShell,
+                        overlay: BTreeMap::new(),
+                        error: Some(e.clone()),
+                    },
+                }
+            }
+            ShellEnvMode::Explicit => {
+                let base = Self {
+                    mode: ShellEnvMode::Explicit,
+                    ..Self::default()
+                };
+                let overlay = base.expand_map(&config.env);
+                Self { overlay, ..base }
+            }
+        }
+    }
+
+    /// The configured mode
+    pub fn mode(&self) -> ShellEnvMode {
+        self.mode
+    }
+
+    /// Why the login shell environment couldn't be used, if it couldn't
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// A variable as a spawned process would see it, before per-command maps
+    pub fn var(&self, name: &str) -> Option<String> {
+        self.overlay
+            .get(name)
+            .cloned()
+            .or_else(|| std::env::var(name).ok())
+    }
+
+    /// Replace every `${env:VAR}` in `value`; unset variables expand to ""
+    pub fn expand(&self, value: &str) -> String {
+        let mut out = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(start) = rest.find("${env:") {
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            out.push_str(&rest[..start]);
+            let name = &rest[start + "${env:".len()..start + len];
+            out.push_str(&self.var(name).unwrap_or_default());
+            rest = &rest[start + len + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    fn expand_map(&self, vars: &HashMap<String, String>) -> BTreeMap<String, String> {
+        vars.iter()
+            .map(|(name, value)| (name.clone(), self.expand(value)))
+            .collect()
+    }
+
+    /// Variables to set on a child process without an `env` map of its own
+    pub fn vars(&self) -> &BTreeMap<String, String> {
+        &self.overlay
+    }
+
+    /// Variables to set on a child process, given its own `env` map.
+    /// Pass the result to `Command::envs`; everything else is inherited.
+    pub fn vars_with(&self, extra: &HashMap<String, String>) -> BTreeMap<String, String> {
+        let mut vars = self.overlay.clone();
+        vars.extend(self.expand_map(extra));
+        vars
+    }
+
+    /// Resolve a command the way a child process with `extra` would:
+    /// paths are checked as given, bare names are searched on its PATH
+    pub fn find_command(&self, command: &str, extra: &HashMap<String, String>) -> Option<PathBuf> {
+        if command.contains('/') || command.contains('\\') {
+            let path = Path::new(command);
+            return path.is_file().then(|| path.to_path_buf());
+        }
+        let path_var = match extra.get("PATH") {
+            Some(value) => self.expand(value),
+            None => self.var("PATH")?,
+        };
+        std::env::split_paths(&path_var).find_map(|dir| {
+            let candidate = dir.join(command);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+            #[cfg(windows)]
+            {
+                let with_exe = dir.join(format!("{}.exe", command));
+                if with_exe.is_file() {
+                    return Some(with_exe);
+                }
+            }
+            None
+        })
+    }
+
+    /// Variables a child process with `extra` sees differently from the
+    /// editor, as (name, editor's value, child's value)
+    pub fn diff(&self, extra: &HashMap<String, String>) -> Vec<(String, Option<String>, String)> {
+        self.vars_with(extra)
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let own = std::env::var(&name).ok();
+                (own.as_deref() != Some(value.as_str())).then_some((name, own, value))
+            })
+            .collect()
+    }
+}
+
+/// Run the user's shell as an interactive login shell and read its
+/// environment
+#[cfg(unix)]
+fn capture_login_shell_env() -> Result<BTreeMap<String, String>, String> {
+    use std::io::Read;
+    use std::process::{Command, Stdio};
+    use std::time::Instant;
+
+    let shell = std::env::var("SHELL")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| "$SHELL is not set".to_string())?;
+    let started = Instant::now();
+
+    let mut child = Command::new(&shell)
+        .args([
+            "-l",
+            "-i",
+            "-c",
+            &format!("printf '%s' {ENV_MARKER}; env -0"),
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("failed to run {}: {}", shell, e))?;
+
+    // Read on a thread so a chatty profile can't fill the pipe while we wait
+    let mut stdout = child.stdout.take().ok_or("no stdout")?;
+    let reader = std::thread::spawn(move || {
+        let mut output = Vec::new();
+        stdout.read_to_end(&mut output).map(|_| output)
+    });
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if started.elapsed() < LOGIN_SHELL_TIMEOUT => {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            Ok(None) => {
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = child.kill();
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = child.wait();
+                return Err(format!(
+                    "{} -lic env did not finish within {}s",
+                    shell,
+                    LOGIN_SHELL_TIMEOUT.as_secs()
+                ));
+            }
+            Err(e) => return Err(format!("failed to wait for {}: {}", shell, e)),
+        }
+    }
+
+    let output = reader
+        .join()
+        .map_err(|_| "reader thread panicked".to_string())?
+        .map_err(|e| format!("failed to read {} output: {}", shell, e))?;
+    let vars = parse_env_output(&String::from_utf8_lossy(&output))
+        .ok_or_else(|| format!("{} -lic env printed no environment", shell))?;
+    tracing::info!(
+        "Captured {} variables from {} in {:?}",
+        vars.len(),
+        shell,
+        started.elapsed()
+    );
+    Ok(vars)
+}
+
+#[cfg(not(unix))]
+fn capture_login_shell_env() -> Result<BTreeMap<String, String>, String> {
+    Err("login_shell is not supported on this platform".to_string())
+}
+
+/// Parse the NUL-separated `env -0` output following the marker
+fn parse_env_output(output: &str) -> Option<BTreeMap<String, String>> {
+    let (_, env) = output.split_once(ENV_MARKER)?;
+    let vars: BTreeMap<String, String> = env
+        .split('\0')
+        .filter_map(|entry| entry.split_once('='))
+        .filter(|(name, _)| !name.is_empty() && !IGNORED_VARS.contains(name))
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    (!vars.is_empty()).then_some(vars)
+}
+
+/// Variables describing the capturing shell itself rather than the user's
+/// environment
+const IGNORED_VARS: &[&str] = &["_", "SHLVL", "PWD", "OLDPWD"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explicit(vars: &[(&str, &str)]) -> ProcessEnvironment {
+        ProcessEnvironment::from_config(&ProcessConfig {
+            shell_env: ShellEnvMode::Explicit,
+            env: vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn test_expand_env_references() {
+        let env = explicit(&[("FRESH_TEST_ROOT", "/opt/tools")]);
+        assert_eq!(
+            env.expand("${env:FRESH_TEST_ROOT}/bin:${env:FRESH_TEST_UNSET_VAR}"),
+            "/opt/tools/bin:"
+        );
+        assert_eq!(env.expand("no refs ${env:"), "no refs ${env:");
+    }
+
+    #[test]
+    fn test_command_env_overrides_and_expands() {
+        let env = explicit(&[("FRESH_TEST_A", "one")]);
+        let extra = HashMap::from([
+            (
+                "FRESH_TEST_A".to_string(),
+                "${env:FRESH_TEST_A}-two".to_string(),
+            ),
+            ("FRESH_TEST_B".to_string(), "three".to_string()),
+        ]);
+        let vars = env.vars_with(&extra);
+        assert_eq!(vars["FRESH_TEST_A"], "one-two");
+        assert_eq!(vars["FRESH_TEST_B"], "three");
+        assert!(env
+            .diff(&extra)
+            .iter()
+            .any(|(name, own, value)| name == "FRESH_TEST_B" && own.is_none() && value == "three"));
+    }
+
+    #[test]
+    fn test_find_command_uses_child_path() {
+        let temp = tempfile::tempdir().unwrap();
+        let tool = temp.path().join("fresh-test-tool");
+        std::fs::write(&tool, "").unwrap();
+
+        let env = ProcessEnvironment::default();
+        assert_eq!(env.find_command("fresh-test-tool", &HashMap::new()), None);
+        let extra = HashMap::from([("PATH".to_string(), temp.path().display().to_string())]);
+        assert_eq!(env.find_command("fresh-test-tool", &extra), Some(tool));
+    }
+
+    #[test]
+    fn test_parse_env_output_skips_profile_noise() {
+        let output = format!("Welcome!\n{ENV_MARKER}PATH=/a:/b\0SHLVL=2\0MULTI=x\ny\0");
+        let vars = parse_env_output(&output).unwrap();
+        assert_eq!(vars.get("PATH").map(String::as_str), Some("/a:/b"));
+        assert_eq!(vars.get("MULTI").map(String::as_str), Some("x\ny"));
+        assert!(!vars.contains_key("SHLVL"));
+        assert_eq!(parse_env_output("no marker"), None);
+    }
+}
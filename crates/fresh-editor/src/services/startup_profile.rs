@@ -0,0 +1,142 @@
+//! Startup phase timing for `--profile-startup`
+//!
+//! Phases are recorded in order with [`StartupProfile::mark`], each one
+//! measuring the time since the previous mark. Work that runs off the
+//! critical path (grammar build, plugin loading) is added separately with
+//! [`StartupProfile::background`] and reported after time-to-first-render.
+
+use std::time::{Duration, Instant};
+
+/// Time-to-first-render budget reported by `--profile-startup`
+pub const FIRST_RENDER_BUDGET: Duration = Duration::from_millis(100);
+
+/// Timings of the phases of one editor startup
+#[derive(Debug)]
+pub struct StartupProfile {
+    start: Instant,
+    last: Instant,
+    phases: Vec<(String, Duration)>,
+    background: Vec<(String, Duration)>,
+}
+
+impl StartupProfile {
+    /// Start timing now
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            start: now,
+            last: now,
+            phases: Vec::new(),
+            background: Vec::new(),
+        }
+    }
+
+    /// End the current phase, naming it `phase`
+    pub fn mark(&mut self, phase: impl Into<String>) {
+        let now = Instant::now();
+        self.phases.push((phase.into(), now - self.last));
+        self.last = now;
+    }
+
+    /// Record a sub-phase that was timed elsewhere (e.g. inside editor construction)
+    pub fn detail(&mut self, phase: impl Into<String>, duration: Duration) {
+        self.phases.push((format!("  {}", phase.into()), duration));
+    }
+
+    /// Record background work that took `duration` from being started to finishing
+    pub fn background(&mut self, phase: impl Into<String>, duration: Duration) {
+        self.background.push((phase.into(), duration));
+    }
+
+    /// Time from the start to the last mark
+    pub fn elapsed(&self) -> Duration {
+        self.last - self.start
+    }
+
+    /// Format the phase-by-phase report. The critical path ends at the last
+    /// mark, which the caller makes the first render.
+    pub fn report(&self) -> String {
+        let total = self.elapsed();
+        let width = self
+            .phases
+            .iter()
+            .chain(&self.background)
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0)
+            .max("time to first render".len());
+
+        let mut out = String::from("Startup profile\n\n");
+        for (name, duration) in &self.phases {
+            let percent = if total.is_zero() {
+                0.0
+            } else {
+                duration.as_secs_f64() / total.as_secs_f64() * 100.0
+            };
+            out.push_str(&format!(
+                "{:<width$}  {:>9}  {:>5.1}%\n",
+                name,
+                format_ms(*duration),
+                percent
+            ));
+        }
+        out.push_str(&format!(
+            "{:<width$}  {:>9}  (budget {})\n",
+            "time to first render",
+            format_ms(total),
+            format_ms(FIRST_RENDER_BUDGET)
+        ));
+
+        if !self.background.is_empty() {
+            out.push_str("\nBackground (from editor construction until done)\n\n");
+            for (name, duration) in &self.background {
+                out.push_str(&format!("{:<width$}  {:>9}\n", name, format_ms(*duration)));
+            }
+        }
+        out
+    }
+}
+
+impl Default for StartupProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lists_phases_in_order() {
+        let mut profile = StartupProfile::new();
+        profile.mark("config load");
+        profile.detail("plugin thread spawn", Duration::from_millis(3));
+        profile.mark("first render");
+        profile.background("grammar build", Duration::from_millis(40));
+
+        let report = profile.report();
+        let config = report.find("config load").unwrap();
+        let spawn = report.find("  plugin thread spawn").unwrap();
+        let render = report.find("first render").unwrap();
+        assert!(config < spawn && spawn < render);
+        assert!(report.contains("time to first render"));
+        assert!(report.contains("budget 100.0 ms"));
+        assert!(report.contains("grammar build"));
+        assert!(report.contains("40.0 ms"));
+    }
+
+    #[test]
+    fn test_elapsed_is_sum_of_marked_phases() {
+        let mut profile = StartupProfile::new();
+        profile.mark("a");
+        std::thread::sleep(Duration::from_millis(2));
+        profile.mark("b");
+        let sum: Duration = profile.phases.iter().map(|(_, d)| *d).sum();
+        assert_eq!(profile.elapsed(), sum);
+    }
+}
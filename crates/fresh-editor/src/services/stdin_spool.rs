@@ -0,0 +1,154 @@
+//! Collects piped stdin while it streams in
+//!
+//! Small inputs (`echo hi | fresh -`) stay in memory and never touch the
+//! disk. Once more than [`MEMORY_LIMIT`] bytes arrive, everything read so far
+//! is written to a temp file and the rest is appended to it, so large inputs
+//! can be loaded lazily like any big file. A temp file that was created is
+//! removed by [`remove_spilled_file`] from the panic hook.
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Inputs up to this size are kept in memory
+pub const MEMORY_LIMIT: usize = 1024 * 1024;
+
+/// The temp file created for stdin in this process, if any
+static SPILLED_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Remove the stdin temp file, if one was created
+pub fn remove_spilled_file() {
+    let path = SPILLED_FILE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+    if let Some(path) = path {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+enum Storage {
+    Memory(Vec<u8>),
+    /// Spilled to `temp_path`; the handle is None when someone else writes it
+    File(Option<File>),
+}
+
+/// Where stdin has been collected so far
+pub enum SpoolContents {
+    /// Still in memory: the bytes after the requested offset
+    Memory(Vec<u8>),
+    /// In this file, from the start
+    File(PathBuf),
+}
+
+/// Stdin collected by the reading thread and read by the editor
+pub struct StdinSpool {
+    storage: Mutex<Storage>,
+    temp_path: PathBuf,
+    memory_limit: usize,
+}
+
+impl StdinSpool {
+    /// Collect in memory, spilling to `temp_path` past [`MEMORY_LIMIT`]
+    pub fn new(temp_path: PathBuf) -> Self {
+        Self::with_memory_limit(temp_path, MEMORY_LIMIT)
+    }
+
+    pub fn with_memory_limit(temp_path: PathBuf, memory_limit: usize) -> Self {
+        Self {
+            storage: Mutex::new(Storage::Memory(Vec::new())),
+            temp_path,
+            memory_limit,
+        }
+    }
+
+    /// Stdin that is (being) written to `path` by someone else
+    pub fn from_file(path: &Path) -> Self {
+        Self {
+            storage: Mutex::new(Storage::File(None)),
+            temp_path: path.to_path_buf(),
+            memory_limit: 0,
+        }
+    }
+
+    /// Append a chunk read from stdin
+    pub fn write(&self, chunk: &[u8]) -> io::Result<()> {
+        let mut storage = self.storage.lock().unwrap_or_else(|e| e.into_inner());
+        match &mut *storage {
+            Storage::Memory(data) if data.len() + chunk.len() <= self.memory_limit => {
+                data.extend_from_slice(chunk);
+            }
+            Storage::Memory(data) => {
+                let mut file = File::create(&self.temp_path)?;
+                *SPILLED_FILE.lock().unwrap_or_else(|e| e.into_inner()) =
+                    Some(self.temp_path.clone());
+                file.write_all(data)?;
+                file.write_all(chunk)?;
+                file.flush()?;
+                tracing::info!(
+                    "Stdin exceeded {} bytes, continuing in {:?}",
+                    self.memory_limit,
+                    self.temp_path
+                );
+                *storage = Storage::File(Some(file));
+            }
+            Storage::File(Some(file)) => {
+                file.write_all(chunk)?;
+                // Flush each chunk so the editor can see progress
+                file.flush()?;
+            }
+            Storage::File(None) => {
+                return Err(io::Error::other("stdin is written by another process"));
+            }
+        }
+        Ok(())
+    }
+
+    /// What arrived after the first `offset` bytes
+    pub fn contents_since(&self, offset: usize) -> SpoolContents {
+        match &*self.storage.lock().unwrap_or_else(|e| e.into_inner()) {
+            Storage::Memory(data) => {
+                SpoolContents::Memory(data.get(offset..).unwrap_or_default().to_vec())
+            }
+            Storage::File(_) => SpoolContents::File(self.temp_path.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_input_stays_in_memory() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("stdin.tmp");
+        let spool = StdinSpool::with_memory_limit(path.clone(), 8);
+        spool.write(b"hi\n").unwrap();
+        spool.write(b"yo\n").unwrap();
+
+        match spool.contents_since(3) {
+            SpoolContents::Memory(bytes) => assert_eq!(bytes, b"yo\n"),
+            SpoolContents::File(_) => panic!("expected memory"),
+        }
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_large_input_spills_everything_to_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("stdin.tmp");
+        let spool = StdinSpool::with_memory_limit(path.clone(), 8);
+        spool.write(b"12345").unwrap();
+        spool.write(b"67890").unwrap();
+        spool.write(b"ab").unwrap();
+
+        match spool.contents_since(5) {
+            SpoolContents::File(file) => assert_eq!(file, path),
+            SpoolContents::Memory(_) => panic!("expected file"),
+        }
+        assert_eq!(std::fs::read(&path).unwrap(), b"1234567890ab");
+    }
+}
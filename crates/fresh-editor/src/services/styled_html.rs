@@ -1,11 +1,17 @@
-//! Styled text rendering for clipboard copy feature
+//! Styled text rendering for clipboard copy and HTML export
 //!
 //! This module renders styled text with syntax highlighting as HTML
-//! for pasting into rich text editors (Google Docs, Word, etc.)
+//! for pasting into rich text editors (Google Docs, Word, etc.) and
+//! for exporting buffers as standalone HTML documents.
 
+use crate::primitives::display_width::char_width;
 use crate::primitives::highlighter::HighlightSpan;
 use crate::view::theme::Theme;
 use ratatui::style::Color;
+use std::io::{self, Write};
+
+/// Font stack shared by clipboard snippets and exported documents
+const FONT_FAMILY: &str = "'Fira Mono','Fira Code',Consolas,'Courier New',monospace";
 
 /// Convert a ratatui Color to a CSS hex color string
 fn color_to_css(color: Color, default: &str) -> String {
@@ -31,6 +37,31 @@ fn color_to_css(color: Color, default: &str) -> String {
     }
 }
 
+/// Append a character to `html`, escaping HTML special characters
+fn push_escaped(html: &mut String, ch: char) {
+    match ch {
+        '<' => html.push_str("&lt;"),
+        '>' => html.push_str("&gt;"),
+        '&' => html.push_str("&amp;"),
+        '"' => html.push_str("&quot;"),
+        '\'' => html.push_str("&#39;"),
+        _ => html.push(ch),
+    }
+}
+
+/// Build a per-byte color lookup for `text` from highlight spans
+fn color_map(text: &str, highlight_spans: &[HighlightSpan]) -> Vec<Option<Color>> {
+    let mut color_map: Vec<Option<Color>> = vec![None; text.len()];
+    for span in highlight_spans {
+        let start = span.range.start.min(text.len());
+        let end = span.range.end.min(text.len());
+        for slot in &mut color_map[start..end] {
+            *slot = Some(span.color);
+        }
+    }
+    color_map
+}
+
 /// Render styled text with syntax highlighting to HTML with inline CSS
 ///
 /// The generated HTML uses a `<pre>` block with inline styles for each
@@ -49,20 +80,13 @@ pub fn render_styled_html(text: &str, highlight_spans: &[HighlightSpan], theme:
     let fg_color = color_to_css(theme.editor_fg, "#d4d4d4");
 
     // Build a map of byte offset to color for quick lookup
-    let mut color_map: Vec<Option<Color>> = vec![None; text.len()];
-    for span in highlight_spans {
-        let start = span.range.start.min(text.len());
-        let end = span.range.end.min(text.len());
-        for slot in &mut color_map[start..end] {
-            *slot = Some(span.color);
-        }
-    }
+    let color_map = color_map(text, highlight_spans);
 
     // Build HTML with spans for colored regions
     let mut html = String::new();
     html.push_str(&format!(
-        "<pre style=\"background-color:{};color:{};font-family:'Fira Mono','Fira Code',Consolas,'Courier New',monospace;font-size:14px;padding:12px 16px;border-radius:6px;margin:0;white-space:pre;overflow-x:auto;\">",
-        bg_color, fg_color
+        "<pre style=\"background-color:{};color:{};font-family:{};font-size:14px;padding:12px 16px;border-radius:6px;margin:0;white-space:pre;overflow-x:auto;\">",
+        bg_color, fg_color, FONT_FAMILY
     ));
 
     let mut current_color: Option<Color> = None;
@@ -97,15 +121,7 @@ pub fn render_styled_html(text: &str, highlight_spans: &[HighlightSpan], theme:
             current_color = char_color;
         }
 
-        // Escape HTML special characters and add the character
-        match ch {
-            '<' => html.push_str("&lt;"),
-            '>' => html.push_str("&gt;"),
-            '&' => html.push_str("&amp;"),
-            '"' => html.push_str("&quot;"),
-            '\'' => html.push_str("&#39;"),
-            _ => html.push(ch),
-        }
+        push_escaped(&mut html, ch);
 
         byte_offset += char_byte_len;
     }
@@ -119,6 +135,189 @@ pub fn render_styled_html(text: &str, highlight_spans: &[HighlightSpan], theme:
     html
 }
 
+/// Options for exporting text as a standalone HTML document
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HtmlExportOptions {
+    /// Prefix each line with its line number
+    pub line_numbers: bool,
+    /// Wrap lines at this many columns (0 disables wrapping)
+    pub wrap_width: usize,
+}
+
+/// Streaming writer for standalone HTML documents with syntax highlighting
+///
+/// Text is fed in chunks via [`HtmlDocumentWriter::write_chunk`] so that huge
+/// buffers can be exported without materializing the whole document in memory.
+/// Colors come from highlight spans already resolved against the theme.
+pub struct HtmlDocumentWriter<W: Write> {
+    out: W,
+    options: HtmlExportOptions,
+    fg_color: String,
+    /// Number of the line currently being written (1-based)
+    line_number: usize,
+    /// Width of the line number column
+    gutter_width: usize,
+    /// Display column within the current visual row
+    column: usize,
+    at_line_start: bool,
+}
+
+impl<W: Write> HtmlDocumentWriter<W> {
+    /// Write the document header and open the code block
+    ///
+    /// `title` (usually the file name) and `timestamp` are recorded in a
+    /// header comment. `first_line` is the number of the first exported line
+    /// and `last_line` the number of the last, used to size the gutter.
+    pub fn begin(
+        mut out: W,
+        title: &str,
+        timestamp: &str,
+        theme: &Theme,
+        options: HtmlExportOptions,
+        first_line: usize,
+        last_line: usize,
+    ) -> io::Result<Self> {
+        let bg_color = color_to_css(theme.editor_bg, "#1e1e1e");
+        let fg_color = color_to_css(theme.editor_fg, "#d4d4d4");
+        let gutter_color = color_to_css(theme.line_number_fg, &fg_color);
+
+        let mut escaped_title = String::new();
+        for ch in title.chars() {
+            push_escaped(&mut escaped_title, ch);
+        }
+        // "--" may not appear inside an HTML comment
+        let comment_title = title.replace("--", "- -");
+
+        writeln!(out, "<!DOCTYPE html>")?;
+        writeln!(
+            out,
+            "<!-- Exported from Fresh: {} at {} -->",
+            comment_title, timestamp
+        )?;
+        writeln!(out, "<html>")?;
+        writeln!(out, "<head>")?;
+        writeln!(out, "<meta charset=\"utf-8\">")?;
+        writeln!(out, "<title>{}</title>", escaped_title)?;
+        writeln!(out, "<style>")?;
+        writeln!(out, "body {{ margin: 0; background-color: {}; }}", bg_color)?;
+        writeln!(
+            out,
+            "pre {{ margin: 0; padding: 12px 16px; color: {}; font-family: {}; font-size: 14px; white-space: pre; }}",
+            fg_color, FONT_FAMILY
+        )?;
+        writeln!(out, ".ln {{ color: {}; user-select: none; }}", gutter_color)?;
+        writeln!(out, "</style>")?;
+        writeln!(out, "</head>")?;
+        writeln!(out, "<body>")?;
+        write!(out, "<pre>")?;
+
+        Ok(Self {
+            out,
+            options,
+            fg_color,
+            line_number: first_line,
+            gutter_width: last_line.max(first_line).to_string().len(),
+            column: 0,
+            at_line_start: true,
+        })
+    }
+
+    /// Write a chunk of text with highlight spans relative to the chunk
+    ///
+    /// Chunks may end anywhere on a character boundary; line numbering and
+    /// wrapping carry over to the next chunk.
+    pub fn write_chunk(&mut self, text: &str, highlight_spans: &[HighlightSpan]) -> io::Result<()> {
+        let color_map = color_map(text, highlight_spans);
+        let mut html = String::with_capacity(text.len() * 2);
+        let mut current_color: Option<Color> = None;
+        let mut byte_offset = 0;
+
+        for ch in text.chars() {
+            let char_color = color_map.get(byte_offset).copied().flatten();
+            byte_offset += ch.len_utf8();
+
+            if ch == '\r' {
+                continue;
+            }
+            if ch == '\n' {
+                self.close_color(&mut html, &mut current_color);
+                if self.at_line_start {
+                    // Empty line still gets its number
+                    self.push_gutter(&mut html, true);
+                }
+                html.push('\n');
+                self.line_number += 1;
+                self.column = 0;
+                self.at_line_start = true;
+                continue;
+            }
+
+            if self.at_line_start {
+                self.push_gutter(&mut html, true);
+                self.at_line_start = false;
+            }
+
+            let width = char_width(ch);
+            if self.options.wrap_width > 0
+                && self.column > 0
+                && self.column + width > self.options.wrap_width
+            {
+                // Continue on a new visual row, without a line number
+                self.close_color(&mut html, &mut current_color);
+                html.push('\n');
+                self.push_gutter(&mut html, false);
+                self.column = 0;
+            }
+
+            if char_color != current_color {
+                self.close_color(&mut html, &mut current_color);
+                if let Some(color) = char_color {
+                    let css_color = color_to_css(color, &self.fg_color);
+                    html.push_str(&format!("<span style=\"color:{};\">", css_color));
+                    current_color = Some(color);
+                }
+            }
+
+            push_escaped(&mut html, ch);
+            self.column += width;
+        }
+
+        self.close_color(&mut html, &mut current_color);
+        self.out.write_all(html.as_bytes())
+    }
+
+    /// Close the code block and the document, returning the underlying writer
+    pub fn finish(mut self) -> io::Result<W> {
+        writeln!(self.out, "</pre>")?;
+        writeln!(self.out, "</body>")?;
+        writeln!(self.out, "</html>")?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn close_color(&self, html: &mut String, current_color: &mut Option<Color>) {
+        if current_color.take().is_some() {
+            html.push_str("</span>");
+        }
+    }
+
+    fn push_gutter(&self, html: &mut String, with_number: bool) {
+        if !self.options.line_numbers {
+            return;
+        }
+        let label = if with_number {
+            self.line_number.to_string()
+        } else {
+            String::new()
+        };
+        html.push_str(&format!(
+            "<span class=\"ln\">{:>width$} </span>",
+            label,
+            width = self.gutter_width
+        ));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,6 +368,91 @@ mod tests {
         assert!(html.contains("main()"));
     }
 
+    fn export(chunks: &[&str], options: HtmlExportOptions) -> String {
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+        let mut writer = HtmlDocumentWriter::begin(
+            Vec::new(),
+            "a--b.rs",
+            "2024-01-01 00:00:00",
+            &theme,
+            options,
+            1,
+            12,
+        )
+        .unwrap();
+        for chunk in chunks {
+            writer.write_chunk(chunk, &[]).unwrap();
+        }
+        String::from_utf8(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_export_document_header() {
+        let html = export(&["<x>\n"], HtmlExportOptions::default());
+
+        assert!(html.starts_with(
+            "<!DOCTYPE html>\n<!-- Exported from Fresh: a- -b.rs at 2024-01-01 00:00:00 -->"
+        ));
+        assert!(html.contains("<title>a--b.rs</title>"));
+        assert!(html.contains("<pre>&lt;x&gt;\n</pre>"));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_export_line_numbers_across_chunks() {
+        let options = HtmlExportOptions {
+            line_numbers: true,
+            wrap_width: 0,
+        };
+        let html = export(&["one\ntw", "o\n\nthree"], options);
+
+        assert!(html.contains("<span class=\"ln\"> 1 </span>one\n"));
+        assert!(html.contains("<span class=\"ln\"> 2 </span>two\n"));
+        assert!(html.contains("<span class=\"ln\"> 3 </span>\n"));
+        assert!(html.contains("<span class=\"ln\"> 4 </span>three"));
+    }
+
+    #[test]
+    fn test_export_wraps_long_lines() {
+        let options = HtmlExportOptions {
+            line_numbers: true,
+            wrap_width: 4,
+        };
+        let html = export(&["abcdefghij\n"], options);
+
+        assert!(html.contains(
+            "<span class=\"ln\"> 1 </span>abcd\n<span class=\"ln\">   </span>efgh\n<span class=\"ln\">   </span>ij\n"
+        ));
+    }
+
+    #[test]
+    fn test_export_highlight_spans_close_at_line_end() {
+        use std::ops::Range;
+
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+        let mut writer = HtmlDocumentWriter::begin(
+            Vec::new(),
+            "x",
+            "now",
+            &theme,
+            HtmlExportOptions::default(),
+            1,
+            1,
+        )
+        .unwrap();
+        let spans = vec![HighlightSpan {
+            range: Range { start: 0, end: 5 },
+            color: Color::Blue,
+            category: None,
+        }];
+        writer.write_chunk("ab\ncd", &spans).unwrap();
+        let html = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        assert!(html.contains(
+            "<span style=\"color:#2472c8;\">ab</span>\n<span style=\"color:#2472c8;\">cd</span>"
+        ));
+    }
+
     #[test]
     fn test_color_to_css() {
         assert_eq!(color_to_css(Color::Black, "#fff"), "#000000");
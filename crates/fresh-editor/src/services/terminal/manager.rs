@@ -2,6 +2,7 @@
 //!
 //! This module provides a manager for terminal sessions that:
 //! - Spawns PTY processes with proper shell detection
+//! - Runs shells on a remote host over its SSH connection
 //! - Manages multiple concurrent terminals
 //! - Routes input/output between the editor and terminal processes
 //! - Handles terminal resize events
@@ -13,16 +14,18 @@
 //!
 //! ## PTY Read Loop
 //!
-//! The read loop in `spawn()` performs incremental streaming: for each PTY read,
-//! it calls `process_output()` to update the terminal grid, then `flush_new_scrollback()`
-//! to append any new scrollback lines to the backing file. This ensures scrollback is
+//! The read loop in `start()`, shared by local and remote terminals, performs
+//! incremental streaming: for each PTY read, it calls `process_output()` to update
+//! the terminal grid, then `flush_new_scrollback()` to append any new scrollback
+//! lines to the backing file. This ensures scrollback is
 //! written incrementally as lines scroll off screen, avoiding O(n) work on mode switches.
 
 use super::term::TerminalState;
+use crate::model::filesystem::{FileSystem, ShellInput, ShellPty};
 use crate::services::async_bridge::AsyncBridge;
-use portable_pty::{native_pty_system, CommandBuilder, PtySize};
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
 use std::collections::HashMap;
-use std::io::{Read, Write};
+use std::io::{self, Read, Write};
 use std::sync::atomic::AtomicBool;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
@@ -53,8 +56,11 @@ pub struct TerminalHandle {
     rows: u16,
     /// Working directory used for the terminal
     cwd: Option<std::path::PathBuf>,
-    /// Shell executable used to spawn the terminal
+    /// Shell executable used to spawn the terminal, or the host a remote
+    /// terminal runs on
     shell: String,
+    /// Whether the shell runs on a remote host
+    remote: bool,
 }
 
 impl TerminalHandle {
@@ -106,6 +112,11 @@ impl TerminalHandle {
     pub fn shell(&self) -> &str {
         &self.shell
     }
+
+    /// Whether the shell runs on a remote host, over its SSH connection
+    pub fn is_remote(&self) -> bool {
+        self.remote
+    }
 }
 
 /// Manager for multiple terminal sessions
@@ -160,311 +171,294 @@ impl TerminalManager {
         let id = TerminalId(self.next_id);
         self.next_id += 1;
 
-        // Try to spawn a real PTY-backed terminal first.
-        let handle_result: Result<TerminalHandle, String> = (|| {
-            // Create PTY
-            let pty_system = native_pty_system();
-            let pty_pair = pty_system
-                .openpty(PtySize {
-                    rows,
-                    cols,
-                    pixel_width: 0,
-                    pixel_height: 0,
-                })
-                .map_err(|e| {
-                    #[cfg(windows)]
-                    {
-                        format!(
-                            "Failed to open PTY: {}. Note: Terminal requires Windows 10 version 1809 or later with ConPTY support.",
-                            e
-                        )
-                    }
-                    #[cfg(not(windows))]
-                    {
-                        format!("Failed to open PTY: {}", e)
-                    }
-                })?;
-
-            // Detect shell
-            let shell = detect_shell();
-            tracing::info!("Spawning terminal with shell: {}", shell);
-
-            // Build command
-            let mut cmd = CommandBuilder::new(&shell);
-            if let Some(ref dir) = cwd {
-                cmd.cwd(dir);
-            }
+        // Detect shell
+        let shell = detect_shell();
+        tracing::info!("Spawning terminal with shell: {}", shell);
+        let pty = open_local_shell(&shell, cols, rows, cwd.as_deref())?;
+
+        let handle = TerminalHandle {
+            cwd,
+            shell,
+            ..self.start(id, pty, cols, rows, log_path, backing_path)
+        };
+        self.terminals.insert(id, handle);
+        tracing::info!("Created terminal {:?} ({}x{})", id, cols, rows);
 
-            // On Windows, set environment variables that help with ConPTY
-            #[cfg(windows)]
-            {
-                // Set TERM to help shells understand they're in a terminal
-                cmd.env("TERM", "xterm-256color");
-                // Ensure PROMPT is set for cmd.exe
-                if shell.to_lowercase().contains("cmd") {
-                    cmd.env("PROMPT", "$P$G");
-                }
-            }
+        Ok(id)
+    }
 
-            // Spawn the shell process
-            let mut child = pty_pair
-                .slave
-                .spawn_command(cmd)
-                .map_err(|e| format!("Failed to spawn shell '{}': {}", shell, e))?;
+    /// Spawn a terminal running the login shell of the remote host behind
+    /// `filesystem`, over its connection
+    ///
+    /// Takes the same arguments as [`Self::spawn`], with `cwd` a directory on
+    /// the remote host (its home directory if `None`). Remote terminals can't
+    /// be restored with the session.
+    pub fn spawn_remote(
+        &mut self,
+        cols: u16,
+        rows: u16,
+        filesystem: &dyn FileSystem,
+        cwd: Option<std::path::PathBuf>,
+        log_path: Option<std::path::PathBuf>,
+        backing_path: Option<std::path::PathBuf>,
+    ) -> Result<TerminalId, String> {
+        let host = filesystem
+            .remote_connection_info()
+            .ok_or_else(|| "not connected to a remote host".to_string())?
+            .to_string();
+        let id = TerminalId(self.next_id);
+        self.next_id += 1;
 
-            tracing::debug!("Shell process spawned successfully");
+        tracing::info!("Spawning remote terminal on {}", host);
+        let pty = filesystem
+            .open_shell(cwd.as_deref(), cols, rows)
+            .map_err(|e| format!("Failed to open a shell on {}: {}", host, e))?;
+
+        let handle = TerminalHandle {
+            cwd,
+            shell: host,
+            remote: true,
+            ..self.start(id, pty, cols, rows, log_path, backing_path)
+        };
+        self.terminals.insert(id, handle);
+        tracing::info!("Created remote terminal {:?} ({}x{})", id, cols, rows);
 
-            // Create terminal state
-            let state = Arc::new(Mutex::new(TerminalState::new(cols, rows)));
+        Ok(id)
+    }
 
-            // Initialize backing_file_history_end if backing file already exists (session restore)
-            // This ensures enter_terminal_mode doesn't truncate existing history to 0
-            if let Some(ref p) = backing_path {
-                if let Ok(metadata) = std::fs::metadata(p) {
-                    if metadata.len() > 0 {
-                        if let Ok(mut s) = state.lock() {
-                            s.set_backing_file_history_end(metadata.len());
-                        }
+    /// Start the threads that feed the output of the shell on `pty` to the
+    /// terminal emulator and the editor's input to the shell. The handle
+    /// returned is that of a local terminal with no `cwd` or `shell` set.
+    fn start(
+        &self,
+        id: TerminalId,
+        pty: ShellPty,
+        cols: u16,
+        rows: u16,
+        log_path: Option<std::path::PathBuf>,
+        backing_path: Option<std::path::PathBuf>,
+    ) -> TerminalHandle {
+        // Create terminal state
+        let state = Arc::new(Mutex::new(TerminalState::new(cols, rows)));
+
+        // Initialize backing_file_history_end if backing file already exists (session restore)
+        // This ensures enter_terminal_mode doesn't truncate existing history to 0
+        if let Some(ref p) = backing_path {
+            if let Ok(metadata) = std::fs::metadata(p) {
+                if metadata.len() > 0 {
+                    if let Ok(mut s) = state.lock() {
+                        s.set_backing_file_history_end(metadata.len());
                     }
                 }
             }
+        }
 
-            // Create communication channel
-            let (command_tx, command_rx) = mpsc::channel::<TerminalCommand>();
-
-            // Alive flag
-            let alive = Arc::new(AtomicBool::new(true));
-            let alive_clone = alive.clone();
-
-            // Get master for I/O
-            let mut master = pty_pair
-                .master
-                .take_writer()
-                .map_err(|e| format!("Failed to get PTY writer: {}", e))?;
-
-            let mut reader = pty_pair
-                .master
-                .try_clone_reader()
-                .map_err(|e| format!("Failed to get PTY reader: {}", e))?;
-
-            // Clone state for reader thread
-            let state_clone = state.clone();
-            let async_bridge = self.async_bridge.clone();
-
-            // Optional raw log writer for full-session capture (for live terminal resume)
-            let mut log_writer = log_path
-                .as_ref()
-                .and_then(|p| {
+        // Create communication channel
+        let (command_tx, command_rx) = mpsc::channel::<TerminalCommand>();
+
+        // Alive flag
+        let alive = Arc::new(AtomicBool::new(true));
+        let alive_clone = alive.clone();
+
+        let ShellPty {
+            output: mut reader,
+            mut input,
+        } = pty;
+
+        // Clone state for reader thread
+        let state_clone = state.clone();
+        let async_bridge = self.async_bridge.clone();
+
+        // Optional raw log writer for full-session capture (for live terminal resume)
+        let mut log_writer = log_path
+            .as_ref()
+            .and_then(|p| {
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(p)
+                    .ok()
+            })
+            .map(std::io::BufWriter::new);
+
+        // Backing file writer for incremental scrollback streaming
+        // During session restore, the backing file may already contain scrollback content.
+        // We open for append to continue streaming new scrollback after the existing content.
+        // For new terminals, append mode also works (creates file if needed).
+        let mut backing_writer = backing_path
+            .as_ref()
+            .and_then(|p| {
+                // Check if backing file exists and has content (session restore case)
+                let existing_has_content =
+                    p.exists() && std::fs::metadata(p).map(|m| m.len() > 0).unwrap_or(false);
+
+                if existing_has_content {
+                    // Session restore: open for append to continue streaming new scrollback
+                    // The existing content is preserved and loaded into buffer separately.
+                    // Note: enter_terminal_mode will truncate when user re-enters terminal.
                     std::fs::OpenOptions::new()
                         .create(true)
                         .append(true)
                         .open(p)
                         .ok()
-                })
-                .map(std::io::BufWriter::new);
-
-            // Backing file writer for incremental scrollback streaming
-            // During session restore, the backing file may already contain scrollback content.
-            // We open for append to continue streaming new scrollback after the existing content.
-            // For new terminals, append mode also works (creates file if needed).
-            let mut backing_writer = backing_path
-                .as_ref()
-                .and_then(|p| {
-                    // Check if backing file exists and has content (session restore case)
-                    let existing_has_content =
-                        p.exists() && std::fs::metadata(p).map(|m| m.len() > 0).unwrap_or(false);
-
-                    if existing_has_content {
-                        // Session restore: open for append to continue streaming new scrollback
-                        // The existing content is preserved and loaded into buffer separately.
-                        // Note: enter_terminal_mode will truncate when user re-enters terminal.
-                        std::fs::OpenOptions::new()
-                            .create(true)
-                            .append(true)
-                            .open(p)
-                            .ok()
-                    } else {
-                        // New terminal: start fresh with truncate
-                        std::fs::OpenOptions::new()
-                            .create(true)
-                            .write(true)
-                            .truncate(true)
-                            .open(p)
-                            .ok()
+                } else {
+                    // New terminal: start fresh with truncate
+                    std::fs::OpenOptions::new()
+                        .create(true)
+                        .write(true)
+                        .truncate(true)
+                        .open(p)
+                        .ok()
+                }
+            })
+            .map(std::io::BufWriter::new);
+
+        // Spawn reader thread
+        let terminal_id = id;
+        let pty_response_tx = command_tx.clone();
+        thread::spawn(move || {
+            tracing::debug!("Terminal {:?} reader thread started", terminal_id);
+            let mut buf = [0u8; 4096];
+            let mut total_bytes = 0usize;
+            loop {
+                match reader.read(&mut buf) {
+                    Ok(0) => {
+                        // EOF - process exited
+                        tracing::info!(
+                            "Terminal {:?} EOF after {} total bytes",
+                            terminal_id,
+                            total_bytes
+                        );
+                        break;
                     }
-                })
-                .map(std::io::BufWriter::new);
-
-            // Spawn reader thread
-            let terminal_id = id;
-            let pty_response_tx = command_tx.clone();
-            thread::spawn(move || {
-                tracing::debug!("Terminal {:?} reader thread started", terminal_id);
-                let mut buf = [0u8; 4096];
-                let mut total_bytes = 0usize;
-                loop {
-                    match reader.read(&mut buf) {
-                        Ok(0) => {
-                            // EOF - process exited
-                            tracing::info!(
-                                "Terminal {:?} EOF after {} total bytes",
-                                terminal_id,
-                                total_bytes
-                            );
-                            break;
-                        }
-                        Ok(n) => {
-                            total_bytes += n;
-                            tracing::debug!(
-                                "Terminal {:?} received {} bytes (total: {})",
-                                terminal_id,
-                                n,
-                                total_bytes
-                            );
-                            // Process output through terminal emulator and stream scrollback
-                            if let Ok(mut state) = state_clone.lock() {
-                                state.process_output(&buf[..n]);
-
-                                // Send any PTY write responses (e.g., DSR cursor position)
-                                // This is critical for Windows ConPTY where PowerShell waits
-                                // for cursor position response before showing the prompt
-                                for response in state.drain_pty_write_queue() {
-                                    tracing::debug!(
-                                        "Terminal {:?} sending PTY response: {:?}",
-                                        terminal_id,
-                                        response
-                                    );
-                                    // Receiver may be dropped if writer thread exited.
-                                    #[allow(clippy::let_underscore_must_use)]
-                                    let _ = pty_response_tx
-                                        .send(TerminalCommand::Write(response.into_bytes()));
-                                }
+                    Ok(n) => {
+                        total_bytes += n;
+                        tracing::debug!(
+                            "Terminal {:?} received {} bytes (total: {})",
+                            terminal_id,
+                            n,
+                            total_bytes
+                        );
+                        // Process output through terminal emulator and stream scrollback
+                        if let Ok(mut state) = state_clone.lock() {
+                            state.process_output(&buf[..n]);
+
+                            // Send any PTY write responses (e.g., DSR cursor position)
+                            // This is critical for Windows ConPTY where PowerShell waits
+                            // for cursor position response before showing the prompt
+                            for response in state.drain_pty_write_queue() {
+                                tracing::debug!(
+                                    "Terminal {:?} sending PTY response: {:?}",
+                                    terminal_id,
+                                    response
+                                );
+                                // Receiver may be dropped if writer thread exited.
+                                #[allow(clippy::let_underscore_must_use)]
+                                let _ = pty_response_tx
+                                    .send(TerminalCommand::Write(response.into_bytes()));
+                            }
 
-                                // Incrementally stream new scrollback lines to backing file
-                                if let Some(ref mut writer) = backing_writer {
-                                    match state.flush_new_scrollback(writer) {
-                                        Ok(lines_written) => {
-                                            if lines_written > 0 {
-                                                // Update the history end offset
-                                                if let Ok(pos) = writer.get_ref().metadata() {
-                                                    state.set_backing_file_history_end(pos.len());
-                                                }
-                                                // Best-effort flush; backing file errors handled below.
-                                                #[allow(clippy::let_underscore_must_use)]
-                                                let _ = writer.flush();
+                            // Incrementally stream new scrollback lines to backing file
+                            if let Some(ref mut writer) = backing_writer {
+                                match state.flush_new_scrollback(writer) {
+                                    Ok(lines_written) => {
+                                        if lines_written > 0 {
+                                            // Update the history end offset
+                                            if let Ok(pos) = writer.get_ref().metadata() {
+                                                state.set_backing_file_history_end(pos.len());
                                             }
-                                        }
-                                        Err(e) => {
-                                            tracing::warn!(
-                                                "Terminal backing file write error: {}",
-                                                e
-                                            );
-                                            backing_writer = None;
+                                            // Best-effort flush; backing file errors handled below.
+                                            #[allow(clippy::let_underscore_must_use)]
+                                            let _ = writer.flush();
                                         }
                                     }
+                                    Err(e) => {
+                                        tracing::warn!("Terminal backing file write error: {}", e);
+                                        backing_writer = None;
+                                    }
                                 }
                             }
+                        }
 
-                            // Append raw bytes to log if available (for session restore replay)
-                            if let Some(w) = log_writer.as_mut() {
-                                if let Err(e) = w.write_all(&buf[..n]) {
-                                    tracing::warn!("Terminal log write error: {}", e);
-                                    log_writer = None; // stop logging on error
-                                } else if let Err(e) = w.flush() {
-                                    tracing::warn!("Terminal log flush error: {}", e);
-                                    log_writer = None;
-                                }
-                            }
-
-                            // Notify main loop to redraw (receiver may be dropped during shutdown).
-                            if let Some(ref bridge) = async_bridge {
-                                #[allow(clippy::let_underscore_must_use)]
-                                let _ = bridge.sender().send(
-                                    crate::services::async_bridge::AsyncMessage::TerminalOutput {
-                                        terminal_id,
-                                    },
-                                );
+                        // Append raw bytes to log if available (for session restore replay)
+                        if let Some(w) = log_writer.as_mut() {
+                            if let Err(e) = w.write_all(&buf[..n]) {
+                                tracing::warn!("Terminal log write error: {}", e);
+                                log_writer = None; // stop logging on error
+                            } else if let Err(e) = w.flush() {
+                                tracing::warn!("Terminal log flush error: {}", e);
+                                log_writer = None;
                             }
                         }
-                        Err(e) => {
-                            tracing::error!("Terminal read error: {}", e);
-                            break;
+
+                        // Notify main loop to redraw (receiver may be dropped during shutdown).
+                        if let Some(ref bridge) = async_bridge {
+                            #[allow(clippy::let_underscore_must_use)]
+                            let _ = bridge.sender().send(
+                                crate::services::async_bridge::AsyncMessage::TerminalOutput {
+                                    terminal_id,
+                                },
+                            );
                         }
                     }
+                    Err(e) => {
+                        tracing::error!("Terminal read error: {}", e);
+                        break;
+                    }
                 }
-                alive_clone.store(false, std::sync::atomic::Ordering::Relaxed);
-                // Best-effort flush of log/backing files during teardown.
-                if let Some(mut w) = log_writer {
-                    #[allow(clippy::let_underscore_must_use)]
-                    let _ = w.flush();
-                }
-                if let Some(mut w) = backing_writer {
-                    #[allow(clippy::let_underscore_must_use)]
-                    let _ = w.flush();
-                }
-                // Notify that terminal exited (receiver may be dropped during shutdown).
-                if let Some(ref bridge) = async_bridge {
-                    #[allow(clippy::let_underscore_must_use)]
-                    let _ = bridge.sender().send(
-                        crate::services::async_bridge::AsyncMessage::TerminalExited { terminal_id },
-                    );
-                }
-            });
-
-            // Spawn writer thread
-            let pty_size_ref = pty_pair.master;
-            thread::spawn(move || {
-                loop {
-                    match command_rx.recv() {
-                        Ok(TerminalCommand::Write(data)) => {
-                            if let Err(e) = master.write_all(&data) {
-                                tracing::error!("Terminal write error: {}", e);
-                                break;
-                            }
-                            // Best-effort flush — PTY write errors are handled above.
-                            #[allow(clippy::let_underscore_must_use)]
-                            let _ = master.flush();
-                        }
-                        Ok(TerminalCommand::Resize { cols, rows }) => {
-                            if let Err(e) = pty_size_ref.resize(PtySize {
-                                rows,
-                                cols,
-                                pixel_width: 0,
-                                pixel_height: 0,
-                            }) {
-                                tracing::warn!("Failed to resize PTY: {}", e);
-                            }
-                        }
-                        Ok(TerminalCommand::Shutdown) | Err(_) => {
+            }
+            alive_clone.store(false, std::sync::atomic::Ordering::Relaxed);
+            // Best-effort flush of log/backing files during teardown.
+            if let Some(mut w) = log_writer {
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = w.flush();
+            }
+            if let Some(mut w) = backing_writer {
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = w.flush();
+            }
+            // Notify that terminal exited (receiver may be dropped during shutdown).
+            if let Some(ref bridge) = async_bridge {
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = bridge.sender().send(
+                    crate::services::async_bridge::AsyncMessage::TerminalExited { terminal_id },
+                );
+            }
+        });
+
+        // Spawn writer thread
+        thread::spawn(move || {
+            loop {
+                match command_rx.recv() {
+                    Ok(TerminalCommand::Write(data)) => {
+                        if let Err(e) = input.write(&data) {
+                            tracing::error!("Terminal write error: {}", e);
                             break;
                         }
                     }
+                    Ok(TerminalCommand::Resize { cols, rows }) => {
+                        if let Err(e) = input.resize(cols, rows) {
+                            tracing::warn!("Failed to resize PTY: {}", e);
+                        }
+                    }
+                    Ok(TerminalCommand::Shutdown) | Err(_) => {
+                        break;
+                    }
                 }
-                // Best-effort child process cleanup during teardown.
-                #[allow(clippy::let_underscore_must_use)]
-                let _ = child.kill();
-                #[allow(clippy::let_underscore_must_use)]
-                let _ = child.wait();
-            });
-
-            // Create handle
-            Ok(TerminalHandle {
-                state,
-                command_tx,
-                alive,
-                cols,
-                rows,
-                cwd: cwd.clone(),
-                shell,
-            })
-        })();
-
-        let handle = handle_result?;
-
-        self.terminals.insert(id, handle);
-        tracing::info!("Created terminal {:?} ({}x{})", id, cols, rows);
-
-        Ok(id)
+            }
+            input.close();
+        });
+
+        TerminalHandle {
+            state,
+            command_tx,
+            alive,
+            cols,
+            rows,
+            cwd: None,
+            shell: String::new(),
+            remote: false,
+        }
     }
 
     /// Get a terminal handle by ID
@@ -533,6 +527,121 @@ impl Drop for TerminalManager {
     }
 }
 
+/// Run `shell` on a new local PTY of `cols` by `rows`
+fn open_local_shell(
+    shell: &str,
+    cols: u16,
+    rows: u16,
+    cwd: Option<&std::path::Path>,
+) -> Result<ShellPty, String> {
+    // Create PTY
+    let pty_system = native_pty_system();
+    let pty_pair = pty_system
+        .openpty(PtySize {
+            rows,
+            cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .map_err(|e| {
+            #[cfg(windows)]
+            {
+                format!(
+                    "Failed to open PTY: {}. Note: Terminal requires Windows 10 version 1809 or later with ConPTY support.",
+                    e
+                )
+            }
+            #[cfg(not(windows))]
+            {
+                format!("Failed to open PTY: {}", e)
+            }
+        })?;
+
+    // Build command
+    let mut cmd = CommandBuilder::new(shell);
+    if let Some(dir) = cwd {
+        cmd.cwd(dir);
+    }
+    for (name, value) in crate::services::shell_env::current().vars() {
+        cmd.env(name, value);
+    }
+
+    // On Windows, set environment variables that help with ConPTY
+    #[cfg(windows)]
+    {
+        // Set TERM to help shells understand they're in a terminal
+        cmd.env("TERM", "xterm-256color");
+        // Ensure PROMPT is set for cmd.exe
+        if shell.to_lowercase().contains("cmd") {
+            cmd.env("PROMPT", "$P$G");
+        }
+    }
+
+    // Spawn the shell process
+    let child = pty_pair
+        .slave
+        .spawn_command(cmd)
+        .map_err(|e| format!("Failed to spawn shell '{}': {}", shell, e))?;
+
+    tracing::debug!("Shell process spawned successfully");
+
+    // Get master for I/O
+    let writer = pty_pair
+        .master
+        .take_writer()
+        .map_err(|e| format!("Failed to get PTY writer: {}", e))?;
+
+    let reader = pty_pair
+        .master
+        .try_clone_reader()
+        .map_err(|e| format!("Failed to get PTY reader: {}", e))?;
+
+    Ok(ShellPty {
+        output: reader,
+        input: Box::new(LocalShellInput {
+            writer,
+            master: pty_pair.master,
+            child,
+        }),
+    })
+}
+
+/// Input side of a shell on a local PTY
+struct LocalShellInput {
+    writer: Box<dyn Write + Send>,
+    master: Box<dyn MasterPty + Send>,
+    child: Box<dyn Child + Send + Sync>,
+}
+
+impl ShellInput for LocalShellInput {
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        self.writer.write_all(data)?;
+        // Best-effort flush — PTY write errors are handled above.
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.writer.flush();
+        Ok(())
+    }
+
+    fn resize(&mut self, cols: u16, rows: u16) -> io::Result<()> {
+        self.master
+            .resize(PtySize {
+                rows,
+                cols,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| io::Error::other(e.to_string()))
+    }
+
+    fn close(&mut self) {
+        // Best-effort child process cleanup during teardown.
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.child.kill();
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.child.wait();
+    }
+}
+
 /// Detect the user's shell
 pub fn detect_shell() -> String {
     // Try $SHELL environment variable first
@@ -0,0 +1,152 @@
+//! Downloading files from http(s) URLs.
+//!
+//! Used to open `fresh https://...` as a read-only buffer. Downloads follow
+//! redirects, are bounded by a timeout and a size cap, and report progress
+//! so the status bar can show how far along they are.
+
+use std::io::Read;
+use std::time::Duration;
+
+use ureq::ResponseExt;
+
+/// Largest file that will be downloaded (20 MiB)
+pub const MAX_DOWNLOAD_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Time allowed for the whole download, including redirects
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Number of redirects followed before giving up
+const MAX_REDIRECTS: u32 = 10;
+
+/// Size of the chunks read from the response body between progress reports
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A downloaded file
+#[derive(Debug)]
+pub struct FetchedUrl {
+    /// URL the content was served from, after redirects
+    pub final_url: String,
+    /// Raw response body
+    pub content: Vec<u8>,
+}
+
+/// Whether `input` is an http or https URL
+pub fn is_url(input: &str) -> bool {
+    let lower = input.get(..8).unwrap_or(input).to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+/// Buffer name for a URL: its last non-empty path segment, or the host when
+/// the path is empty (e.g. `https://example.com/`).
+pub fn url_file_name(url: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let without_suffix = without_scheme
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(without_scheme);
+    let mut segments = without_suffix.split('/');
+    let host = segments.next().unwrap_or_default();
+    segments
+        .rev()
+        .find(|segment| !segment.is_empty())
+        .unwrap_or(host)
+        .to_string()
+}
+
+/// Download `url`, calling `on_progress(received, total)` as data arrives.
+///
+/// HTTP error statuses, bodies over [`MAX_DOWNLOAD_BYTES`] and timeouts are
+/// returned as errors rather than partial content.
+pub fn fetch_url(
+    url: &str,
+    on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<FetchedUrl, String> {
+    fetch_url_with_limits(url, MAX_DOWNLOAD_BYTES, DOWNLOAD_TIMEOUT, on_progress)
+}
+
+/// Like [`fetch_url`], with a custom size cap and timeout (e.g. for
+/// language server binaries, which are far larger than source files)
+pub fn fetch_url_with_limits(
+    url: &str,
+    max_bytes: u64,
+    timeout: Duration,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<FetchedUrl, String> {
+    tracing::debug!("Downloading {}", url);
+    let agent = ureq::Agent::config_builder()
+        .timeout_global(Some(timeout))
+        .max_redirects(MAX_REDIRECTS)
+        .build()
+        .new_agent();
+    let response = agent
+        .get(url)
+        .header("User-Agent", "fresh-editor")
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::StatusCode(code) => format!("HTTP {}", code),
+            e => e.to_string(),
+        })?;
+
+    let final_url = response.get_uri().to_string();
+    let total = response
+        .headers()
+        .get("content-length")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if total.is_some_and(|total| total > max_bytes) {
+        return Err(too_large_error(max_bytes));
+    }
+
+    let mut reader = response.into_body().into_reader();
+    let mut content = Vec::with_capacity(total.unwrap_or(0) as usize);
+    let mut chunk = vec![0u8; CHUNK_SIZE];
+    loop {
+        let read = reader.read(&mut chunk).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        content.extend_from_slice(&chunk[..read]);
+        if content.len() as u64 > max_bytes {
+            return Err(too_large_error(max_bytes));
+        }
+        on_progress(content.len() as u64, total);
+    }
+
+    tracing::debug!("Downloaded {} bytes from {}", content.len(), final_url);
+    Ok(FetchedUrl { final_url, content })
+}
+
+fn too_large_error(max_bytes: u64) -> String {
+    format!("file is larger than {} MiB", max_bytes / (1024 * 1024))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_url() {
+        assert!(is_url("https://example.com/main.rs"));
+        assert!(is_url("http://example.com"));
+        assert!(is_url("HTTPS://EXAMPLE.COM/a"));
+        assert!(!is_url("user@host:/path/file.rs"));
+        assert!(!is_url("/tmp/http://file"));
+        assert!(!is_url("ftp://example.com/file"));
+        assert!(!is_url("http"));
+    }
+
+    #[test]
+    fn test_url_file_name() {
+        assert_eq!(
+            url_file_name("https://raw.githubusercontent.com/o/r/main/src/main.rs"),
+            "main.rs"
+        );
+        assert_eq!(
+            url_file_name("https://example.com/docs/README.md?raw=1#top"),
+            "README.md"
+        );
+        assert_eq!(url_file_name("https://example.com/dir/"), "dir");
+        assert_eq!(url_file_name("https://example.com/"), "example.com");
+        assert_eq!(url_file_name("http://example.com"), "example.com");
+    }
+}
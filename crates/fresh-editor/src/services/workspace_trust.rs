@@ -0,0 +1,189 @@
+//! Workspace trust decisions
+//!
+//! A cloned repository can carry a `plugins/` directory and LSP, formatter
+//! and on-save commands in its `.fresh/` config, all of which run code as
+//! soon as the editor starts. Each workspace (keyed by its canonical path) is
+//! therefore trusted or untrusted, and the decision is remembered in
+//! `workspace_trust.json` in the data directory. In a workspace that isn't
+//! trusted the editor ignores command-like settings from the project and
+//! session config layers, skips plugins from the working directory, asks
+//! before running on-save commands and asks before starting LSP servers.
+//!
+//! Checks are off until the binary sets a policy with [`set_policy`], so
+//! embedders and tests keep loading everything as before.
+
+use crate::config_io::DirectoryContext;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How trust is decided for every workspace opened by this process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustPolicy {
+    /// No checks: every workspace is trusted (embedders and tests)
+    Off,
+    /// Use the remembered decision and ask about unknown workspaces
+    Ask,
+    /// Trust every workspace without asking (`--trust`)
+    TrustAll,
+    /// Trust no workspace and don't ask (`--no-trust`)
+    TrustNone,
+}
+
+static POLICY: AtomicU8 = AtomicU8::new(0);
+
+/// Set the trust policy for this process
+pub fn set_policy(policy: TrustPolicy) {
+    let value = match policy {
+        TrustPolicy::Off => 0,
+        TrustPolicy::Ask => 1,
+        TrustPolicy::TrustAll => 2,
+        TrustPolicy::TrustNone => 3,
+    };
+    POLICY.store(value, Ordering::SeqCst);
+}
+
+/// The trust policy for this process
+pub fn policy() -> TrustPolicy {
+    match POLICY.load(Ordering::SeqCst) {
+        1 => TrustPolicy::Ask,
+        2 => TrustPolicy::TrustAll,
+        3 => TrustPolicy::TrustNone,
+        _ => TrustPolicy::Off,
+    }
+}
+
+/// Trust state of a workspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceTrust {
+    Trusted,
+    Untrusted,
+    /// Never decided; restricted like an untrusted workspace until the user
+    /// answers the prompt
+    Unknown,
+}
+
+impl WorkspaceTrust {
+    pub fn is_trusted(self) -> bool {
+        self == Self::Trusted
+    }
+}
+
+/// Trust state of `working_dir` under the current policy
+pub fn workspace_trust(dir_context: &DirectoryContext, working_dir: &Path) -> WorkspaceTrust {
+    match policy() {
+        TrustPolicy::Off | TrustPolicy::TrustAll => WorkspaceTrust::Trusted,
+        TrustPolicy::TrustNone => WorkspaceTrust::Untrusted,
+        TrustPolicy::Ask => match TrustStore::load(dir_context).decision(working_dir) {
+            Some(true) => WorkspaceTrust::Trusted,
+            Some(false) => WorkspaceTrust::Untrusted,
+            None => WorkspaceTrust::Unknown,
+        },
+    }
+}
+
+/// Path a workspace is remembered under
+pub fn canonical_workspace(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Remembered trust decisions, keyed by canonical workspace path
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TrustStore {
+    #[serde(skip)]
+    path: PathBuf,
+    /// true = trusted, false = untrusted
+    #[serde(default)]
+    workspaces: BTreeMap<PathBuf, bool>,
+}
+
+impl TrustStore {
+    /// Load the decisions, starting empty if the file is missing or unreadable
+    pub fn load(dir_context: &DirectoryContext) -> Self {
+        let path = dir_context.workspace_trust_path();
+        let mut store: Self = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| match serde_json::from_str(&content) {
+                Ok(store) => Some(store),
+                Err(e) => {
+                    tracing::warn!("Ignoring unreadable {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        store.path = path;
+        store
+    }
+
+    /// Write the decisions back to disk
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        std::fs::write(&self.path, json)
+    }
+
+    /// The remembered decision for a workspace, if any
+    pub fn decision(&self, workspace: &Path) -> Option<bool> {
+        self.workspaces
+            .get(&canonical_workspace(workspace))
+            .copied()
+    }
+
+    /// Remember whether a workspace is trusted
+    pub fn set(&mut self, workspace: &Path, trusted: bool) {
+        self.workspaces
+            .insert(canonical_workspace(workspace), trusted);
+    }
+
+    /// Forget the decision for a workspace. Returns false if there was none.
+    pub fn revoke(&mut self, workspace: &Path) -> bool {
+        self.workspaces
+            .remove(&canonical_workspace(workspace))
+            .is_some()
+    }
+
+    /// All remembered decisions, sorted by path
+    pub fn decisions(&self) -> impl Iterator<Item = (&Path, bool)> {
+        self.workspaces
+            .iter()
+            .map(|(path, trusted)| (path.as_path(), *trusted))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decisions_round_trip_by_canonical_path() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+        let project = temp.path().join("project");
+        std::fs::create_dir_all(project.join("src")).unwrap();
+
+        let mut store = TrustStore::load(&dir_context);
+        assert_eq!(store.decision(&project), None);
+        store.set(&project.join("src").join(".."), true);
+        store.save().unwrap();
+
+        let mut store = TrustStore::load(&dir_context);
+        assert_eq!(store.decision(&project), Some(true));
+        assert!(store.revoke(&project));
+        assert!(!store.revoke(&project));
+        assert_eq!(store.decisions().count(), 0);
+    }
+
+    #[test]
+    fn test_unreadable_store_starts_empty() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+        let path = dir_context.workspace_trust_path();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(TrustStore::load(&dir_context).decisions().count(), 0);
+    }
+}
@@ -14,6 +14,7 @@ use crate::primitives::grammar::GrammarRegistry;
 use crate::primitives::highlight_engine::HighlightEngine;
 use crate::primitives::indent::IndentCalculator;
 use crate::primitives::reference_highlighter::ReferenceHighlighter;
+use crate::primitives::selection_stats::SelectionStats;
 use crate::primitives::text_property::TextPropertyManager;
 use crate::view::bracket_highlight_overlay::BracketHighlightOverlay;
 use crate::view::conceal::ConcealManager;
@@ -73,6 +74,14 @@ pub struct BufferSettings {
     /// Whether to surround selected text with matching pairs when typing a delimiter.
     /// Set based on global + language config.
     pub auto_surround: bool,
+
+    /// The bracket and quote pairs to auto-close and skip over.
+    /// Set based on language config.
+    pub auto_close_pairs: Vec<(char, char)>,
+
+    /// Which characters form words and whether word movement stops inside
+    /// identifiers. Set based on global + language config.
+    pub word_rules: crate::primitives::word_navigation::WordRules,
 }
 
 impl Default for BufferSettings {
@@ -83,10 +92,24 @@ impl Default for BufferSettings {
             tab_size: 4,
             auto_close: true,
             auto_surround: true,
+            auto_close_pairs: crate::config::default_auto_close_pairs(""),
+            word_rules: Default::default(),
         }
     }
 }
 
+/// How long the last render of a buffer spent in each stage, reported by
+/// "Diagnose Buffer Performance"
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderTimings {
+    /// Building view lines (tokens, wrapping, folds, virtual lines)
+    pub layout: std::time::Duration,
+    /// Syntax highlighting the viewport and its context
+    pub highlight: std::time::Duration,
+    /// Reference/bracket overlays and other decorations, excluding highlighting
+    pub decorations: std::time::Duration,
+}
+
 /// The complete editor state - everything needed to represent the current editing session
 ///
 /// NOTE: Viewport is NOT stored here - it lives in SplitViewState.
@@ -162,6 +185,14 @@ pub struct EditorState {
     /// Bracket matching highlight overlay
     pub bracket_highlight_overlay: BracketHighlightOverlay,
 
+    /// Whether the buffer has a line longer than `editor.long_line_threshold`
+    /// (typically a minified file). Such buffers are highlighted only within
+    /// the viewport and skip bracket matching.
+    pub long_lines: bool,
+
+    /// Stage timings of the last render of this buffer
+    pub render_timings: RenderTimings,
+
     /// Cached LSP semantic tokens (converted to buffer byte ranges)
     pub semantic_tokens: Option<SemanticTokenStore>,
 
@@ -195,6 +226,23 @@ impl EditorState {
         }
     }
 
+    /// Check whether any line is longer than `threshold` bytes and switch
+    /// long-line mode on or off accordingly. Large files that are not fully
+    /// loaded are not scanned. Returns whether long-line mode is on.
+    pub fn detect_long_lines(&mut self, threshold: usize) -> bool {
+        let was_long = self.long_lines;
+        self.long_lines = threshold > 0
+            && self.buffer.len() > threshold
+            && self
+                .buffer
+                .to_string()
+                .is_some_and(|text| text.split('\n').any(|line| line.len() > threshold));
+        if self.long_lines != was_long {
+            self.bracket_highlight_overlay.enabled = !self.long_lines;
+        }
+        self.long_lines
+    }
+
     /// Create a new state with a buffer and default (plain text) language.
     /// All other fields are initialized to their defaults.
     fn new_from_buffer(buffer: Buffer) -> Self {
@@ -225,6 +273,8 @@ impl EditorState {
             debug_highlight_mode: false,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            long_lines: false,
+            render_timings: RenderTimings::default(),
             semantic_tokens: None,
             folding_ranges: Vec::new(),
             language: "text".to_string(),
@@ -929,6 +979,18 @@ impl EditorState {
         }
     }
 
+    /// Size of the selections in `cursors`, summed across all of them
+    pub fn selection_stats(&mut self, cursors: &Cursors) -> SelectionStats {
+        let mut stats = SelectionStats::default();
+        for (_, cursor) in cursors.iter() {
+            if let Some(range) = cursor.selection_range() {
+                let text = self.get_text_range(range.start, range.end);
+                stats.add_text(&text);
+            }
+        }
+        stats
+    }
+
     /// Get the content of a line by its byte offset
     ///
     /// Returns the line containing the given offset, along with its start position.
@@ -1248,6 +1310,23 @@ mod tests {
         assert!(state.buffer.is_empty());
     }
 
+    #[test]
+    fn test_detect_long_lines() {
+        let mut state = EditorState::new(
+            80,
+            24,
+            crate::config::LARGE_FILE_THRESHOLD_BYTES as usize,
+            test_fs(),
+        );
+        state.buffer = Buffer::from_str_test(&format!("short\n{}\nshort\n", "x".repeat(50)));
+
+        assert!(state.detect_long_lines(40));
+        assert!(!state.bracket_highlight_overlay.enabled);
+        assert!(!state.detect_long_lines(0));
+        assert!(!state.detect_long_lines(50));
+        assert!(state.bracket_highlight_overlay.enabled);
+    }
+
     #[test]
     fn test_apply_insert() {
         let mut state = EditorState::new(
@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 pub mod context_keys {
     pub const LINE_NUMBERS: &str = "line_numbers";
     pub const LINE_WRAP: &str = "line_wrap";
+    pub const WHITESPACE_INDICATORS: &str = "whitespace_indicators";
     pub const COMPOSE_MODE: &str = "compose_mode";
     pub const FILE_EXPLORER: &str = "file_explorer";
     pub const MENU_BAR: &str = "menu_bar";
@@ -30,6 +31,9 @@ pub mod context_keys {
     pub const HORIZONTAL_SCROLLBAR: &str = "horizontal_scrollbar";
     pub const SCROLL_SYNC: &str = "scroll_sync";
     pub const HAS_SAME_BUFFER_SPLITS: &str = "has_same_buffer_splits";
+    pub const HAS_MULTIPLE_SPLITS: &str = "has_multiple_splits";
+    pub const CAN_PASTE: &str = "can_paste";
+    pub const AUTO_REVERT: &str = "auto_revert";
     pub const KEYMAP_DEFAULT: &str = "keymap_default";
     pub const KEYMAP_EMACS: &str = "keymap_emacs";
     pub const KEYMAP_VSCODE: &str = "keymap_vscode";
@@ -116,7 +120,8 @@ pub struct LspServerConfig {
     pub initialization_options: Option<serde_json::Value>,
 
     /// Environment variables to set for the LSP server process.
-    /// These are added to (or override) the inherited parent environment.
+    /// These are added to (or override) the environment chosen by
+    /// `process.shell_env`. Values may reference other variables as `${env:VAR}`.
     #[serde(default)]
     pub env: HashMap<String, String>,
 
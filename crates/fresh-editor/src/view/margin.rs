@@ -400,6 +400,24 @@ impl MarginManager {
         }
     }
 
+    /// Marker IDs and current byte positions of every indicator in a
+    /// namespace, sorted by position
+    pub fn line_indicator_positions(&self, namespace: &str) -> Vec<(MarkerId, usize)> {
+        let mut positions: Vec<(MarkerId, usize)> = self
+            .line_indicators
+            .iter()
+            .filter(|(_, indicators)| indicators.contains_key(namespace))
+            .filter_map(|(&marker_id, _)| {
+                let marker_id = MarkerId(marker_id);
+                self.indicator_markers
+                    .get_position(marker_id)
+                    .map(|pos| (marker_id, pos))
+            })
+            .collect();
+        positions.sort_by_key(|&(_, pos)| pos);
+        positions
+    }
+
     /// Get the line indicator for a specific line number
     ///
     /// This looks up all indicators whose markers resolve to the given line.
@@ -869,6 +887,37 @@ mod tests {
         assert_eq!(breakpoint.unwrap().symbol, "●");
     }
 
+    #[test]
+    fn test_line_indicator_positions_sorted_by_namespace() {
+        let mut manager = MarginManager::new();
+        manager.set_line_indicator(
+            line_to_byte(7),
+            "line-mark".to_string(),
+            LineIndicator::new("◆", Color::Blue, 20),
+        );
+        manager.set_line_indicator(
+            line_to_byte(2),
+            "git-gutter".to_string(),
+            LineIndicator::new("│", Color::Green, 10),
+        );
+        manager.set_line_indicator(
+            line_to_byte(3),
+            "line-mark".to_string(),
+            LineIndicator::new("◆", Color::Blue, 20),
+        );
+
+        let lines: Vec<usize> = manager
+            .line_indicator_positions("line-mark")
+            .into_iter()
+            .map(|(_, pos)| byte_to_line(pos))
+            .collect();
+        assert_eq!(lines, vec![3, 7]);
+
+        manager.adjust_for_insert(0, line_to_byte(1));
+        let (_, first) = manager.line_indicator_positions("line-mark")[0];
+        assert_eq!(byte_to_line(first), 4);
+    }
+
     #[test]
     fn test_line_indicator_remove_specific() {
         let mut manager = MarginManager::new();
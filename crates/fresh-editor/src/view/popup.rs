@@ -62,6 +62,8 @@ pub enum PopupKind {
     List,
     /// Generic text popup
     Text,
+    /// Peek Definition / Peek References - scroll, switch location, Enter jumps
+    Peek,
 }
 
 /// Content of a popup window
@@ -4,17 +4,20 @@
 //! - `completion`: LSP completion popups with type-to-filter
 //! - `hover`: Read-only hover/documentation popups
 //! - `action`: Action popups with selectable actions
+//! - `peek`: Peek Definition / Peek References popups
 //! - `base`: Shared input handling logic
 
 pub mod action;
 pub mod base;
 pub mod completion;
 pub mod hover;
+pub mod peek;
 
 pub use action::handle_action_input;
 pub use base::{handle_list_navigation, try_handle_shared, SharedHandleResult};
 pub use completion::{handle_completion_input, handle_completion_input_with_popup};
 pub use hover::handle_hover_input;
+pub use peek::handle_peek_input;
 
 use crate::input::handler::{InputContext, InputResult};
 use crate::view::popup::{Popup, PopupKind};
@@ -32,6 +35,7 @@ pub fn handle_popup_input(
         PopupKind::Completion => handle_completion_input_with_popup(event, popup, ctx),
         PopupKind::Hover => handle_hover_input(event, popup, ctx),
         PopupKind::Action => handle_action_input(event, popup, ctx),
+        PopupKind::Peek => handle_peek_input(event, popup, ctx),
         PopupKind::List | PopupKind::Text => {
             // Generic list/text popups use the default action-like behavior
             handle_action_input(event, popup, ctx)
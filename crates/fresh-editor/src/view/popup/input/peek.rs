@@ -0,0 +1,44 @@
+//! Input handling for Peek Definition / Peek References popups.
+//!
+//! Peek popups show a read-only window into another file and support:
+//! - Escape: dismiss the popup
+//! - Enter: jump to the peeked location
+//! - Arrow keys / PageUp / PageDown: scroll the peeked file
+//! - Tab/Shift+Tab: switch between locations when there are several
+
+use super::base::{try_handle_shared, SharedHandleResult};
+use crate::input::handler::{DeferredAction, InputContext, InputResult};
+use crate::view::popup::Popup;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Handle input for peek popups
+pub fn handle_peek_input(
+    event: &KeyEvent,
+    popup: &mut Popup,
+    ctx: &mut InputContext,
+) -> InputResult {
+    // The editor owns the peeked file, so scrolling is deferred to it rather
+    // than moving the popup's own scroll offset
+    let page = popup.max_height.saturating_sub(3).max(1) as i32;
+    let deferred = match event.code {
+        KeyCode::Up if event.modifiers.is_empty() => Some(DeferredAction::PeekScroll(-1)),
+        KeyCode::Down if event.modifiers.is_empty() => Some(DeferredAction::PeekScroll(1)),
+        KeyCode::PageUp => Some(DeferredAction::PeekScroll(-page)),
+        KeyCode::PageDown => Some(DeferredAction::PeekScroll(page)),
+        KeyCode::Tab if event.modifiers.is_empty() => Some(DeferredAction::PeekSelectLocation(1)),
+        KeyCode::BackTab => Some(DeferredAction::PeekSelectLocation(-1)),
+        KeyCode::Enter => Some(DeferredAction::ConfirmPopup),
+        _ => None,
+    };
+    if let Some(action) = deferred {
+        ctx.defer(action);
+        return InputResult::Consumed;
+    }
+
+    // Shared handling (Esc, Ctrl+C)
+    match try_handle_shared(event, Some(popup), ctx) {
+        SharedHandleResult::Handled(result) => result,
+        // Consume all other keys (modal behavior)
+        SharedHandleResult::NotHandled => InputResult::Consumed,
+    }
+}
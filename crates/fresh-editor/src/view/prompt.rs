@@ -21,6 +21,8 @@ pub enum PromptType {
     SwitchProject,
     /// Save current buffer to a new file
     SaveFileAs,
+    /// Write a copy of the current buffer to a file, keeping its own file
+    WriteCopy,
     /// Search for text in buffer
     Search,
     /// Search for text in buffer (for replace operation - will prompt for replacement after)
@@ -33,6 +35,14 @@ pub enum PromptType {
     QueryReplace { search: String },
     /// Query replace confirmation prompt (y/n/!/q for each match)
     QueryReplaceConfirm,
+    /// Search all files under the working directory
+    ProjectSearch,
+    /// Project replace - entering search term
+    ProjectReplaceSearch,
+    /// Project replace - entering replacement text
+    ProjectReplace { search: String },
+    /// Project replace confirmation, once the files are known
+    ProjectReplaceConfirm,
     /// Execute a command by name (M-x)
     Command,
     /// Quick Open - unified prompt with prefix-based provider routing
@@ -63,10 +73,24 @@ pub enum PromptType {
     RecordMacro,
     /// Play a macro - prompts for register (0-9)
     PlayMacro,
+    /// Save a macro across sessions - prompts for register and name
+    SaveMacro,
+    /// Load a saved macro - prompts for name and register
+    LoadMacro,
     /// Set a bookmark - prompts for register (0-9)
     SetBookmark,
     /// Jump to a bookmark - prompts for register (0-9)
     JumpToBookmark,
+    /// Set a named bookmark - prompts for its name
+    SetNamedBookmark,
+    /// Jump to a named bookmark - prompts for its name
+    JumpToNamedBookmark,
+    /// Pick a bookmark from the list of all bookmarks
+    BookmarkPicker,
+    /// Pick a file from the recently opened files
+    RecentFiles,
+    /// Pick a file under the working directory by fuzzy search
+    FuzzyFinder,
     /// Set compose width (empty clears to viewport)
     SetComposeWidth,
     /// Add a vertical ruler at a column position
@@ -75,6 +99,8 @@ pub enum PromptType {
     RemoveRuler,
     /// Set tab size for current buffer
     SetTabSize,
+    /// Column to reflow the paragraph at
+    ReflowColumn,
     /// Set line ending format for current buffer
     SetLineEnding,
     /// Set text encoding format for current buffer
@@ -98,6 +124,10 @@ pub enum PromptType {
     ConfirmRevert,
     /// Confirm saving over a file that changed on disk
     ConfirmSaveConflict,
+    /// Choose what to do with a buffer whose file was deleted or moved on disk
+    ConfirmMissingFile {
+        buffer_id: crate::model::event::BufferId,
+    },
     /// Confirm saving with sudo after permission denied
     ConfirmSudoSave {
         info: crate::model::buffer::SudoSaveRequired,
@@ -111,6 +141,11 @@ pub enum PromptType {
     },
     /// Confirm quitting with modified buffers
     ConfirmQuitWithModified,
+    /// Confirm copying a selection that Privacy Mode is masking
+    ConfirmCopySecret,
+    /// Confirm downloading the missing language server for a language
+    /// from its `lsp_downloads` URL
+    ConfirmLspDownload { language: String },
     /// File Explorer rename operation
     /// Stores the original path and name for the file/directory being renamed
     FileExplorerRename {
@@ -130,10 +165,25 @@ pub enum PromptType {
     ConfirmLargeFileEncoding { path: std::path::PathBuf },
     /// Switch to a tab by name (from the current split's open buffers)
     SwitchToTab,
+    /// Choose the buffers for "Diff: Compare Open Buffers"; `first` is set
+    /// once the first buffer has been chosen
+    DiffBuffers {
+        first: Option<crate::model::event::BufferId>,
+    },
+    /// Choose a remembered workspace trust decision to revoke
+    ManageWorkspaceTrust,
     /// Run shell command on buffer/selection
     /// If replace is true, replace the input with the output
     /// If replace is false, output goes to a new buffer
     ShellCommand { replace: bool },
+    /// Export buffer/selection as HTML to the entered path
+    ExportHtml,
+    /// Write a settings bundle to the entered path
+    ExportSettingsBundle,
+    /// Read a settings bundle from the entered path and review it
+    ImportSettingsBundle,
+    /// Choose which reviewed settings bundle categories to apply
+    ApplySettingsBundle,
     /// Async prompt from plugin (for editor.prompt() API)
     /// The result is returned via callback resolution
     AsyncPrompt,
@@ -165,6 +215,9 @@ pub struct Prompt {
     /// When true, navigating suggestions updates the input text (selected) to match.
     /// Used by plugin prompts that want picker-like behavior (e.g. compose width).
     pub sync_input_on_navigate: bool,
+    /// Whether the input was recalled from history, so Down keeps cycling
+    /// through history instead of moving through suggestions
+    pub browsing_history: bool,
 }
 
 impl Prompt {
@@ -181,6 +234,7 @@ impl Prompt {
             selection_anchor: None,
             suggestions_set_for_input: None,
             sync_input_on_navigate: false,
+            browsing_history: false,
         }
     }
 
@@ -209,6 +263,7 @@ impl Prompt {
             selection_anchor: None,
             suggestions_set_for_input: None,
             sync_input_on_navigate: false,
+            browsing_history: false,
         }
     }
 
@@ -236,6 +291,7 @@ impl Prompt {
             selection_anchor,
             suggestions_set_for_input: None,
             sync_input_on_navigate: false,
+            browsing_history: false,
         }
     }
 
@@ -326,6 +382,16 @@ impl Prompt {
         self.clear_selection();
     }
 
+    /// Whether Up/Down recall executed commands: in the command palette and
+    /// in Quick Open's `>` command mode
+    pub fn recalls_commands(&self) -> bool {
+        match self.prompt_type {
+            PromptType::Command => true,
+            PromptType::QuickOpen => self.input.starts_with('>'),
+            _ => false,
+        }
+    }
+
     /// Select next suggestion
     pub fn select_next_suggestion(&mut self) {
         if !self.suggestions.is_empty() {
@@ -132,6 +132,20 @@ impl InputHandler for Prompt {
             // should be able to register a callback for selection changes instead of having
             // hardcoded prompt type checks here. This would make the suggestion UI more flexible
             // and allow custom handling for any prompt type without modifying this code.
+
+            // In the command palette and Quick Open's `>` mode, Up from the
+            // top suggestion recalls previously executed commands
+            KeyCode::Up
+                if self.recalls_commands()
+                    && (self.browsing_history || self.selected_suggestion.unwrap_or(0) == 0) =>
+            {
+                ctx.defer(DeferredAction::PromptHistoryPrev);
+                InputResult::Consumed
+            }
+            KeyCode::Down if self.recalls_commands() && self.browsing_history => {
+                ctx.defer(DeferredAction::PromptHistoryNext);
+                InputResult::Consumed
+            }
             KeyCode::Up => {
                 if !self.suggestions.is_empty() {
                     // Don't wrap around - stay at 0 if already at the beginning
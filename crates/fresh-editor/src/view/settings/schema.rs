@@ -332,6 +332,18 @@ fn parse_properties(
 
     for (name, prop) in properties {
         let path = format!("{}/{}", parent_path, name);
+
+        // The fields of a nested object placed in a section (e.g. `editor.mouse`)
+        // are listed in that section rather than edited as one JSON value
+        let resolved = resolve_ref(prop, defs);
+        if let (Some(section), Some(inner_props)) = (&prop.section, &resolved.properties) {
+            for mut setting in parse_properties(inner_props, &path, defs, enum_values_map) {
+                setting.section.get_or_insert_with(|| section.clone());
+                settings.push(setting);
+            }
+            continue;
+        }
+
         let setting = parse_setting(name, &path, prop, defs, enum_values_map);
         settings.push(setting);
     }
@@ -654,6 +666,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sectioned_object_fields_join_the_section() {
+        let schema = r##"
+{
+  "type": "object",
+  "properties": {
+    "editor": { "$ref": "#/$defs/EditorConfig" }
+  },
+  "$defs": {
+    "EditorConfig": {
+      "type": "object",
+      "properties": {
+        "mouse": { "$ref": "#/$defs/MouseConfig", "x-section": "Mouse" }
+      }
+    },
+    "MouseConfig": {
+      "type": "object",
+      "properties": {
+        "scroll_lines": { "type": "integer", "default": 3 }
+      }
+    }
+  }
+}
+"##;
+        let categories = parse_schema(schema).unwrap();
+        let editor = &categories[0];
+
+        assert_eq!(editor.settings.len(), 1);
+        let scroll_lines = &editor.settings[0];
+        assert_eq!(scroll_lines.path, "/editor/mouse/scroll_lines");
+        assert_eq!(scroll_lines.section.as_deref(), Some("Mouse"));
+        assert!(matches!(
+            scroll_lines.setting_type,
+            SettingType::Integer { .. }
+        ));
+    }
+
     #[test]
     fn test_humanize_name() {
         assert_eq!(humanize_name("tab_size"), "Tab Size");
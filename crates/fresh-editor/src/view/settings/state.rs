@@ -515,7 +515,10 @@ impl SettingsState {
             }
         }
 
-        serde_json::from_value(config_value)
+        // Runtime overrides aren't serialized; carry them over
+        let mut new_config: Config = serde_json::from_value(config_value)?;
+        new_config.runtime_layer = config.runtime_layer.clone();
+        Ok(new_config)
     }
 
     /// Discard all pending changes
@@ -23,6 +23,7 @@
 /// └────────────────────┘      └──────────┴─────────┘
 ///  (horizontal split)          (mixed splits)
 /// ```
+use crate::config::WhitespaceVisibility;
 use crate::model::buffer::Buffer;
 use crate::model::cursor::Cursors;
 use crate::model::event::{BufferId, ContainerId, LeafId, SplitDirection, SplitId};
@@ -93,6 +94,11 @@ pub struct BufferViewState {
     /// Compose mode forces this to false; leaving compose restores from config.
     pub show_line_numbers: bool,
 
+    /// Per-split whitespace indicator override.
+    /// `None` follows the buffer's settings (config and language defaults);
+    /// toggling whitespace indicators in this split sets it.
+    pub whitespace: Option<WhitespaceVisibility>,
+
     /// Optional view transform payload
     pub view_transform: Option<ViewTransformPayload>,
 
@@ -137,6 +143,7 @@ impl BufferViewState {
             compose_column_guides: None,
             rulers: Vec::new(),
             show_line_numbers: true,
+            whitespace: None,
             view_transform: None,
             view_transform_stale: false,
             plugin_state: std::collections::HashMap::new(),
@@ -174,6 +181,7 @@ impl Clone for BufferViewState {
             compose_column_guides: self.compose_column_guides.clone(),
             rulers: self.rulers.clone(),
             show_line_numbers: self.show_line_numbers,
+            whitespace: self.whitespace,
             view_transform: self.view_transform.clone(),
             view_transform_stale: self.view_transform_stale,
             plugin_state: self.plugin_state.clone(),
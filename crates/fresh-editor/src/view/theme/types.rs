@@ -3,17 +3,19 @@
 //! This module contains all theme-related data structures that can be used
 //! without filesystem access. This enables WASM compatibility and easier testing.
 
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub const THEME_DARK: &str = "dark";
 pub const THEME_LIGHT: &str = "light";
 pub const THEME_HIGH_CONTRAST: &str = "high-contrast";
+pub const THEME_WCAG_CONTRAST: &str = "wcag-contrast";
 pub const THEME_NOSTALGIA: &str = "nostalgia";
 pub const THEME_DRACULA: &str = "dracula";
 pub const THEME_NORD: &str = "nord";
 pub const THEME_SOLARIZED_DARK: &str = "solarized-dark";
+pub const THEME_DEUTERANOPIA: &str = "deuteranopia";
 
 /// A builtin theme with its name, pack, and embedded JSON content.
 pub struct BuiltinTheme {
@@ -180,6 +182,212 @@ pub struct ThemeFile {
     pub diagnostic: DiagnosticColors,
     /// Syntax highlighting colors
     pub syntax: SyntaxColors,
+    /// Non-color attributes for semantic distinctions
+    #[serde(default)]
+    pub accessibility: AccessibilityAttributes,
+}
+
+/// Non-color text attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TextAttribute {
+    Bold,
+    Dim,
+    Italic,
+    Underlined,
+    Reversed,
+    CrossedOut,
+}
+
+impl TextAttribute {
+    const ALL: [TextAttribute; 6] = [
+        TextAttribute::Bold,
+        TextAttribute::Dim,
+        TextAttribute::Italic,
+        TextAttribute::Underlined,
+        TextAttribute::Reversed,
+        TextAttribute::CrossedOut,
+    ];
+
+    fn modifier(self) -> Modifier {
+        match self {
+            TextAttribute::Bold => Modifier::BOLD,
+            TextAttribute::Dim => Modifier::DIM,
+            TextAttribute::Italic => Modifier::ITALIC,
+            TextAttribute::Underlined => Modifier::UNDERLINED,
+            TextAttribute::Reversed => Modifier::REVERSED,
+            TextAttribute::CrossedOut => Modifier::CROSSED_OUT,
+        }
+    }
+}
+
+fn attributes_to_modifier(attrs: &[TextAttribute]) -> Modifier {
+    attrs
+        .iter()
+        .fold(Modifier::empty(), |m, attr| m | attr.modifier())
+}
+
+fn modifier_to_attributes(modifier: Modifier) -> Vec<TextAttribute> {
+    TextAttribute::ALL
+        .into_iter()
+        .filter(|attr| modifier.contains(attr.modifier()))
+        .collect()
+}
+
+/// Non-color attributes for semantic distinctions
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AccessibilityAttributes {
+    /// Also render semantic distinctions (diagnostic severity, diff lines,
+    /// current search match, selection) with the attributes below, so they
+    /// stay visible without relying on color perception
+    #[serde(default)]
+    pub distinguish_without_color: bool,
+    /// Error diagnostics
+    #[serde(default = "default_attr_diagnostic_error")]
+    pub diagnostic_error: Vec<TextAttribute>,
+    /// Warning diagnostics
+    #[serde(default = "default_attr_diagnostic_warning")]
+    pub diagnostic_warning: Vec<TextAttribute>,
+    /// Info diagnostics
+    #[serde(default = "default_attr_diagnostic_info")]
+    pub diagnostic_info: Vec<TextAttribute>,
+    /// Hint diagnostics
+    #[serde(default = "default_attr_diagnostic_hint")]
+    pub diagnostic_hint: Vec<TextAttribute>,
+    /// Added lines and changed text in diff views
+    #[serde(default = "default_attr_diff_add")]
+    pub diff_add: Vec<TextAttribute>,
+    /// Removed lines and changed text in diff views
+    #[serde(default = "default_attr_diff_remove")]
+    pub diff_remove: Vec<TextAttribute>,
+    /// Search matches other than the current one
+    #[serde(default = "default_attr_search_match")]
+    pub search_match: Vec<TextAttribute>,
+    /// The search match at the cursor
+    #[serde(default = "default_attr_search_current_match")]
+    pub search_current_match: Vec<TextAttribute>,
+    /// Selected text
+    #[serde(default = "default_attr_selection")]
+    pub selection: Vec<TextAttribute>,
+    /// Line number of the cursor line
+    #[serde(default = "default_attr_current_line")]
+    pub current_line: Vec<TextAttribute>,
+}
+
+impl Default for AccessibilityAttributes {
+    fn default() -> Self {
+        Self {
+            distinguish_without_color: false,
+            diagnostic_error: default_attr_diagnostic_error(),
+            diagnostic_warning: default_attr_diagnostic_warning(),
+            diagnostic_info: default_attr_diagnostic_info(),
+            diagnostic_hint: default_attr_diagnostic_hint(),
+            diff_add: default_attr_diff_add(),
+            diff_remove: default_attr_diff_remove(),
+            search_match: default_attr_search_match(),
+            search_current_match: default_attr_search_current_match(),
+            selection: default_attr_selection(),
+            current_line: default_attr_current_line(),
+        }
+    }
+}
+
+// Default non-color attributes
+fn default_attr_diagnostic_error() -> Vec<TextAttribute> {
+    vec![TextAttribute::Bold, TextAttribute::Underlined]
+}
+fn default_attr_diagnostic_warning() -> Vec<TextAttribute> {
+    vec![TextAttribute::Underlined]
+}
+fn default_attr_diagnostic_info() -> Vec<TextAttribute> {
+    vec![TextAttribute::Italic]
+}
+fn default_attr_diagnostic_hint() -> Vec<TextAttribute> {
+    vec![TextAttribute::Dim]
+}
+fn default_attr_diff_add() -> Vec<TextAttribute> {
+    vec![TextAttribute::Bold]
+}
+fn default_attr_diff_remove() -> Vec<TextAttribute> {
+    vec![TextAttribute::Italic]
+}
+fn default_attr_search_match() -> Vec<TextAttribute> {
+    vec![TextAttribute::Underlined]
+}
+fn default_attr_search_current_match() -> Vec<TextAttribute> {
+    vec![TextAttribute::Bold, TextAttribute::Reversed]
+}
+fn default_attr_selection() -> Vec<TextAttribute> {
+    vec![TextAttribute::Reversed]
+}
+fn default_attr_current_line() -> Vec<TextAttribute> {
+    vec![TextAttribute::Bold]
+}
+
+/// Resolved non-color attributes for each semantic distinction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SemanticAttributes {
+    pub diagnostic_error: Modifier,
+    pub diagnostic_warning: Modifier,
+    pub diagnostic_info: Modifier,
+    pub diagnostic_hint: Modifier,
+    pub diff_add: Modifier,
+    pub diff_remove: Modifier,
+    pub search_match: Modifier,
+    pub search_current_match: Modifier,
+    pub selection: Modifier,
+    pub current_line: Modifier,
+}
+
+impl SemanticAttributes {
+    /// No attributes: distinctions rely on color alone
+    pub const NONE: Self = Self {
+        diagnostic_error: Modifier::empty(),
+        diagnostic_warning: Modifier::empty(),
+        diagnostic_info: Modifier::empty(),
+        diagnostic_hint: Modifier::empty(),
+        diff_add: Modifier::empty(),
+        diff_remove: Modifier::empty(),
+        search_match: Modifier::empty(),
+        search_current_match: Modifier::empty(),
+        selection: Modifier::empty(),
+        current_line: Modifier::empty(),
+    };
+}
+
+impl From<&AccessibilityAttributes> for SemanticAttributes {
+    fn from(defs: &AccessibilityAttributes) -> Self {
+        Self {
+            diagnostic_error: attributes_to_modifier(&defs.diagnostic_error),
+            diagnostic_warning: attributes_to_modifier(&defs.diagnostic_warning),
+            diagnostic_info: attributes_to_modifier(&defs.diagnostic_info),
+            diagnostic_hint: attributes_to_modifier(&defs.diagnostic_hint),
+            diff_add: attributes_to_modifier(&defs.diff_add),
+            diff_remove: attributes_to_modifier(&defs.diff_remove),
+            search_match: attributes_to_modifier(&defs.search_match),
+            search_current_match: attributes_to_modifier(&defs.search_current_match),
+            selection: attributes_to_modifier(&defs.selection),
+            current_line: attributes_to_modifier(&defs.current_line),
+        }
+    }
+}
+
+impl AccessibilityAttributes {
+    fn from_resolved(distinguish_without_color: bool, attrs: &SemanticAttributes) -> Self {
+        Self {
+            distinguish_without_color,
+            diagnostic_error: modifier_to_attributes(attrs.diagnostic_error),
+            diagnostic_warning: modifier_to_attributes(attrs.diagnostic_warning),
+            diagnostic_info: modifier_to_attributes(attrs.diagnostic_info),
+            diagnostic_hint: modifier_to_attributes(attrs.diagnostic_hint),
+            diff_add: modifier_to_attributes(attrs.diff_add),
+            diff_remove: modifier_to_attributes(attrs.diff_remove),
+            search_match: modifier_to_attributes(attrs.search_match),
+            search_current_match: modifier_to_attributes(attrs.search_current_match),
+            selection: modifier_to_attributes(attrs.selection),
+            current_line: modifier_to_attributes(attrs.current_line),
+        }
+    }
 }
 
 /// Editor area colors
@@ -939,6 +1147,11 @@ pub struct Theme {
     pub syntax_variable: Color,
     pub syntax_constant: Color,
     pub syntax_operator: Color,
+
+    /// Whether semantic distinctions also use non-color attributes
+    pub distinguish_without_color: bool,
+    /// Non-color attributes (see [`Theme::distinctions`])
+    pub attributes: SemanticAttributes,
 }
 
 impl From<ThemeFile> for Theme {
@@ -1042,6 +1255,8 @@ impl From<ThemeFile> for Theme {
             syntax_variable: file.syntax.variable.into(),
             syntax_constant: file.syntax.constant.into(),
             syntax_operator: file.syntax.operator.into(),
+            distinguish_without_color: file.accessibility.distinguish_without_color,
+            attributes: (&file.accessibility).into(),
         }
     }
 }
@@ -1154,6 +1369,10 @@ impl From<Theme> for ThemeFile {
                 constant: theme.syntax_constant.into(),
                 operator: theme.syntax_operator.into(),
             },
+            accessibility: AccessibilityAttributes::from_resolved(
+                theme.distinguish_without_color,
+                &theme.attributes,
+            ),
         }
     }
 }
@@ -1168,6 +1387,18 @@ impl Theme {
             .map(|tf| tf.into())
     }
 
+    /// Non-color attributes for semantic distinctions
+    ///
+    /// Empty unless the theme sets `distinguish_without_color`, so renderers
+    /// can add these unconditionally.
+    pub fn distinctions(&self) -> &SemanticAttributes {
+        if self.distinguish_without_color {
+            &self.attributes
+        } else {
+            &SemanticAttributes::NONE
+        }
+    }
+
     /// Parse theme from JSON string (no I/O).
     pub fn from_json(json: &str) -> Result<Self, String> {
         let theme_file: ThemeFile =
@@ -1328,6 +1559,69 @@ mod tests {
         assert_eq!(theme.name, "test");
     }
 
+    #[test]
+    fn test_distinctions_empty_unless_enabled() {
+        let dark = Theme::load_builtin(THEME_DARK).expect("Dark theme must exist");
+        assert!(!dark.distinguish_without_color);
+        assert_eq!(*dark.distinctions(), SemanticAttributes::NONE);
+
+        let json = r#"{"name":"test","editor":{},"ui":{},"search":{},"diagnostic":{},"syntax":{},
+            "accessibility":{"distinguish_without_color":true,"selection":["underlined","bold"]}}"#;
+        let theme = Theme::from_json(json).expect("Should parse accessibility section");
+        assert_eq!(
+            theme.distinctions().selection,
+            Modifier::BOLD | Modifier::UNDERLINED
+        );
+        // Unset attributes keep their defaults
+        assert_eq!(
+            theme.distinctions().diagnostic_error,
+            Modifier::BOLD | Modifier::UNDERLINED
+        );
+
+        let file: ThemeFile = theme.into();
+        assert!(file.accessibility.distinguish_without_color);
+        assert_eq!(
+            file.accessibility.selection,
+            vec![TextAttribute::Bold, TextAttribute::Underlined]
+        );
+    }
+
+    #[test]
+    fn test_builtin_distinctions_differ_without_color() {
+        for builtin in BUILTIN_THEMES {
+            let theme = Theme::load_builtin(builtin.name).expect("Builtin theme must load");
+            if !theme.distinguish_without_color {
+                continue;
+            }
+            let attrs = theme.distinctions();
+
+            let severities = [
+                attrs.diagnostic_error,
+                attrs.diagnostic_warning,
+                attrs.diagnostic_info,
+                attrs.diagnostic_hint,
+            ];
+            for (i, severity) in severities.iter().enumerate() {
+                assert!(!severity.is_empty(), "{}: empty severity", builtin.name);
+                for other in &severities[i + 1..] {
+                    assert_ne!(severity, other, "{}: severities collide", builtin.name);
+                }
+            }
+            assert_ne!(attrs.diff_add, attrs.diff_remove, "{}", builtin.name);
+            // The current match is drawn with both match and current-match attributes
+            assert!(
+                !attrs
+                    .search_current_match
+                    .difference(attrs.search_match)
+                    .is_empty(),
+                "{}: current search match not distinguishable",
+                builtin.name
+            );
+            assert!(!attrs.selection.is_empty(), "{}", builtin.name);
+            assert!(!attrs.current_line.is_empty(), "{}", builtin.name);
+        }
+    }
+
     #[test]
     fn test_default_reset_color() {
         // Test that "Default" maps to Color::Reset
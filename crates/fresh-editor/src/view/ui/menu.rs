@@ -132,6 +132,15 @@ fn is_checkbox_checked(checkbox: &Option<String>, context: &MenuContext) -> bool
     }
 }
 
+/// A menu item contributed by a plugin to an existing menu
+#[derive(Debug, Clone)]
+pub struct PluginMenuItem {
+    /// Label or id of the menu the item was added to
+    pub menu_label: String,
+    pub item: MenuItem,
+    pub position: fresh_core::api::MenuPosition,
+}
+
 /// Menu bar state (tracks which menu is open and which item is highlighted)
 ///
 /// TODO: The menu system design could be improved to handle dynamic items better.
@@ -152,6 +161,9 @@ pub struct MenuState {
     pub submenu_path: Vec<usize>,
     /// Runtime menu additions from plugins
     pub plugin_menus: Vec<Menu>,
+    /// Items plugins added to built-in menus, replayed when the built-in
+    /// menus are rebuilt (e.g. after a locale change)
+    pub plugin_items: Vec<PluginMenuItem>,
     /// Context containing named boolean states for conditions and checkboxes
     pub context: MenuContext,
     /// Path to the themes directory for expanding DynamicSubmenu items.
@@ -166,6 +178,7 @@ impl MenuState {
             highlighted_item: None,
             submenu_path: Vec::new(),
             plugin_menus: Vec::new(),
+            plugin_items: Vec::new(),
             context: MenuContext::default(),
             themes_dir,
         }
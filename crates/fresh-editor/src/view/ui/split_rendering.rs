@@ -4,12 +4,14 @@ use std::collections::BTreeMap;
 
 use crate::app::types::ViewLineMapping;
 use crate::app::BufferMetadata;
+use crate::config::{FoldIndicators, WhitespaceVisibility};
 use crate::model::buffer::Buffer;
 use crate::model::cursor::SelectionMode;
 use crate::model::event::{BufferId, EventLog, LeafId, SplitDirection};
 use crate::primitives::ansi::AnsiParser;
 use crate::primitives::ansi_background::AnsiBackground;
 use crate::primitives::display_width::char_width;
+use crate::primitives::secret_mask::{SecretMasker, MASK_CHAR};
 use crate::state::{EditorState, ViewMode};
 use crate::view::folding::FoldManager;
 use crate::view::split::SplitManager;
@@ -396,6 +398,8 @@ struct ViewPreferences {
     rulers: Vec<usize>,
     /// Per-split line number visibility (from BufferViewState)
     show_line_numbers: bool,
+    /// Per-split whitespace indicators, falling back to the buffer's settings
+    whitespace: WhitespaceVisibility,
 }
 
 struct LineRenderInput<'a> {
@@ -423,6 +427,8 @@ struct LineRenderInput<'a> {
     software_cursor_only: bool,
     /// Whether to show line numbers in the gutter
     show_line_numbers: bool,
+    /// Whitespace indicators to draw in this split
+    whitespace: WhitespaceVisibility,
     /// Whether the gutter shows byte offsets instead of line numbers
     /// (large file without line index scan)
     byte_offset_mode: bool,
@@ -574,7 +580,9 @@ fn render_left_margin(
             width = ctx.state.margins.left_config.width
         );
         let margin_style = if is_cursor_line {
-            Style::default().fg(ctx.theme.editor_fg)
+            Style::default()
+                .fg(ctx.theme.editor_fg)
+                .add_modifier(ctx.theme.distinctions().current_line)
         } else {
             Style::default().fg(ctx.theme.line_number_fg)
         };
@@ -595,7 +603,9 @@ fn render_left_margin(
         );
         // Use brighter color for the cursor line
         let margin_style = if is_cursor_line {
-            Style::default().fg(ctx.theme.editor_fg)
+            Style::default()
+                .fg(ctx.theme.editor_fg)
+                .add_modifier(ctx.theme.distinctions().current_line)
         } else {
             Style::default().fg(ctx.theme.line_number_fg)
         };
@@ -610,8 +620,11 @@ fn render_left_margin(
         let (rendered_text, style_opt) = margin_content.render(ctx.state.margins.left_config.width);
 
         // Use custom style if provided, otherwise use default theme color
-        let margin_style =
+        let mut margin_style =
             style_opt.unwrap_or_else(|| Style::default().fg(ctx.theme.line_number_fg));
+        if is_cursor_line {
+            margin_style = margin_style.add_modifier(ctx.theme.distinctions().current_line);
+        }
 
         push_span_with_map(line_spans, line_view_map, rendered_text, margin_style, None);
     }
@@ -658,15 +671,15 @@ fn compute_char_style(ctx: &CharStyleContext) -> CharStyleOutput {
     let highlight_color = ctx.highlight_color;
 
     // Find overlays for this byte position
-    let overlays: Vec<&crate::view::overlay::Overlay> = if let Some(bp) = ctx.byte_pos {
-        ctx.viewport_overlays
-            .iter()
-            .filter(|(_, range)| range.contains(&bp))
-            .map(|(overlay, _)| overlay)
-            .collect()
-    } else {
-        Vec::new()
-    };
+    let overlays: Vec<&(crate::view::overlay::Overlay, Range<usize>)> =
+        if let Some(bp) = ctx.byte_pos {
+            ctx.viewport_overlays
+                .iter()
+                .filter(|(_, range)| range.contains(&bp))
+                .collect()
+        } else {
+            Vec::new()
+        };
 
     // Start with token style if present (for injected content like annotation headers)
     // Otherwise use ANSI/syntax/theme default
@@ -732,7 +745,7 @@ fn compute_char_style(ctx: &CharStyleContext) -> CharStyleOutput {
     }
 
     // Apply overlay styles
-    for overlay in &overlays {
+    for (overlay, range) in overlays {
         match &overlay.face {
             OverlayFace::Underline {
                 color,
@@ -770,13 +783,23 @@ fn compute_char_style(ctx: &CharStyleContext) -> CharStyleOutput {
                 style = style.patch(themed_style);
             }
         }
+
+        // The search match starting at the primary cursor is the current match
+        let is_search_overlay = overlay
+            .namespace
+            .as_ref()
+            .is_some_and(|ns| ns.as_str() == "search");
+        if is_search_overlay && range.start == ctx.primary_cursor_position {
+            style = style.add_modifier(ctx.theme.distinctions().search_current_match);
+        }
     }
 
     // Apply selection highlighting
     if ctx.is_selected {
         style = Style::default()
             .fg(ctx.theme.editor_fg)
-            .bg(ctx.theme.selection_bg);
+            .bg(ctx.theme.selection_bg)
+            .add_modifier(ctx.theme.distinctions().selection);
     }
 
     // Apply cursor styling - make all cursors visible with reversed colors
@@ -863,12 +886,15 @@ impl SplitRenderer {
         is_maximized: bool,
         relative_line_numbers: bool,
         tab_bar_visible: bool,
+        tight_tabs: bool,
         use_terminal_bg: bool,
         session_mode: bool,
         software_cursor_only: bool,
         show_vertical_scrollbar: bool,
         show_horizontal_scrollbar: bool,
         diagnostics_inline_text: bool,
+        fold_indicators: FoldIndicators,
+        privacy: Option<&SecretMasker>,
     ) -> (
         Vec<(LeafId, BufferId, Rect, Rect, usize, usize)>,
         HashMap<LeafId, crate::view::ui::tabs::TabLayout>, // tab layouts per split
@@ -930,6 +956,7 @@ impl SplitRenderer {
                     is_active,
                     tab_scroll_offset,
                     tab_hover_for_split,
+                    tight_tabs,
                 );
 
                 // Store the tab layout for this split
@@ -1024,6 +1051,7 @@ impl SplitRenderer {
                             is_active,
                             view_state,
                             use_terminal_bg,
+                            privacy,
                         );
 
                         // Render scrollbar for composite buffer
@@ -1151,7 +1179,10 @@ impl SplitRenderer {
                     software_cursor_only,
                     &view_prefs.rulers,
                     view_prefs.show_line_numbers,
+                    view_prefs.whitespace,
                     diagnostics_inline_text,
+                    fold_indicators,
+                    privacy,
                 );
 
                 drop(_render_buf_span);
@@ -1291,6 +1322,8 @@ impl SplitRenderer {
         show_vertical_scrollbar: bool,
         show_horizontal_scrollbar: bool,
         diagnostics_inline_text: bool,
+        fold_indicators: FoldIndicators,
+        privacy: Option<&SecretMasker>,
     ) -> HashMap<LeafId, Vec<ViewLineMapping>> {
         let visible_buffers = split_manager.get_visible_buffers(area);
         let active_split_id = split_manager.active_split();
@@ -1382,7 +1415,10 @@ impl SplitRenderer {
                 session_mode,
                 software_cursor_only,
                 view_prefs.show_line_numbers,
+                view_prefs.whitespace,
                 diagnostics_inline_text,
+                fold_indicators,
+                privacy,
             );
 
             view_line_mappings.insert(split_id, layout_output.view_line_mappings);
@@ -1437,6 +1473,7 @@ impl SplitRenderer {
         _is_active: bool,
         view_state: &mut crate::view::composite_view::CompositeViewState,
         use_terminal_bg: bool,
+        privacy: Option<&SecretMasker>,
     ) {
         use crate::model::composite_buffer::{CompositeLayout, RowType};
 
@@ -1595,6 +1632,7 @@ impl SplitRenderer {
                         &ViewMode::Source, // Composite view uses source mode
                         &empty_folds,
                         theme,
+                        privacy,
                     );
 
                     // Build source_line -> ViewLine index mapping
@@ -1738,8 +1776,24 @@ impl SplitRenderer {
                         None
                     };
 
+                    // Changed rows also get a +/- marker and text attributes when
+                    // the theme distinguishes them without color
+                    let attrs = theme.distinctions();
+                    let (diff_marker, diff_modifier) = match aligned_row.row_type {
+                        RowType::Addition => ('+', attrs.diff_add),
+                        RowType::Deletion => ('-', attrs.diff_remove),
+                        RowType::Modification if pane_idx == 0 => ('-', attrs.diff_remove),
+                        RowType::Modification => ('+', attrs.diff_add),
+                        _ => (' ', Modifier::empty()),
+                    };
+                    let diff_marker = if theme.distinguish_without_color {
+                        diff_marker
+                    } else {
+                        ' '
+                    };
+
                     // Line number
-                    let line_num = format!("{:>3} ", source_line_ref.line + 1);
+                    let line_num = format!("{:>3}{}", source_line_ref.line + 1, diff_marker);
                     let line_num_style = Style::default().fg(theme.line_number_fg).bg(bg);
 
                     let is_cursor_pane = is_focused_pane;
@@ -1779,6 +1833,7 @@ impl SplitRenderer {
                             cursor_column,
                             &inline_ranges,
                             highlight_bg,
+                            diff_modifier,
                             pane_selection_cols,
                         );
                     } else {
@@ -1873,6 +1928,7 @@ impl SplitRenderer {
         cursor_column: usize,
         inline_ranges: &[Range<usize>],
         highlight_bg: Option<Color>,
+        diff_modifier: Modifier,
         selection_cols: Option<(usize, usize)>, // (start_col, end_col) for selection
     ) {
         let text = &view_line.text;
@@ -1929,6 +1985,12 @@ impl SplitRenderer {
                 Style::default().fg(color).bg(char_bg)
             } else {
                 Style::default().fg(theme.editor_fg).bg(char_bg)
+            }
+            .add_modifier(diff_modifier);
+            let char_style = if in_selection {
+                char_style.add_modifier(theme.distinctions().selection)
+            } else {
+                char_style
             };
 
             // Handle cursor - cursor_column is absolute position, compare directly with col
@@ -2153,7 +2215,7 @@ impl SplitRenderer {
     }
 
     fn resolve_view_preferences(
-        _state: &EditorState,
+        state: &EditorState,
         split_view_states: Option<&HashMap<LeafId, crate::view::split::SplitViewState>>,
         split_id: LeafId,
     ) -> ViewPreferences {
@@ -2166,6 +2228,9 @@ impl SplitRenderer {
                     view_transform: view_state.view_transform.clone(),
                     rulers: view_state.rulers.clone(),
                     show_line_numbers: view_state.show_line_numbers,
+                    whitespace: view_state
+                        .whitespace
+                        .unwrap_or(state.buffer_settings.whitespace),
                 };
             }
         }
@@ -2178,6 +2243,7 @@ impl SplitRenderer {
             view_transform: None,
             rulers: Vec::new(),
             show_line_numbers: true,
+            whitespace: state.buffer_settings.whitespace,
         }
     }
 
@@ -2528,6 +2594,7 @@ impl SplitRenderer {
         view_mode: &ViewMode,
         folds: &FoldManager,
         theme: &crate::view::theme::Theme,
+        privacy: Option<&SecretMasker>,
     ) -> ViewData {
         let adjusted_visible_count = Self::fold_adjusted_visible_count(
             &state.buffer,
@@ -2593,6 +2660,12 @@ impl SplitRenderer {
             }
         }
 
+        // Privacy Mode masks secrets on the visible lines only; the buffer
+        // itself is never modified.
+        if let Some(masker) = privacy.filter(|_| !is_binary) {
+            tokens = Self::apply_privacy_mask(tokens, state, masker);
+        }
+
         // Apply wrapping transform - always enabled for safety, but with different thresholds.
         // When line_wrap is on: wrap at viewport width for normal text flow.
         // When line_wrap is off: wrap at MAX_SAFE_LINE_WIDTH to prevent memory exhaustion
@@ -3164,6 +3237,107 @@ impl SplitRenderer {
         output
     }
 
+    /// Privacy Mode: replace every character of a detected secret with
+    /// [`MASK_CHAR`]. Only the source range covered by `tokens` is scanned.
+    /// Each masked character becomes its own token carrying its source
+    /// offset, so cursor positions and mouse clicks still map correctly.
+    fn apply_privacy_mask(
+        tokens: Vec<fresh_core::api::ViewTokenWire>,
+        state: &mut EditorState,
+        masker: &SecretMasker,
+    ) -> Vec<fresh_core::api::ViewTokenWire> {
+        use fresh_core::api::{ViewTokenWire, ViewTokenWireKind};
+
+        let source_start = tokens.iter().filter_map(|t| t.source_offset).min();
+        let source_end = tokens
+            .iter()
+            .filter_map(|t| match (&t.kind, t.source_offset) {
+                (ViewTokenWireKind::Text(text), Some(offset)) => Some(offset + text.len()),
+                (_, Some(offset)) => Some(offset + 1),
+                (_, None) => None,
+            })
+            .max();
+        let (Some(source_start), Some(source_end)) = (source_start, source_end) else {
+            return tokens;
+        };
+
+        let env_file = state
+            .buffer
+            .file_path()
+            .and_then(|path| path.file_name())
+            .is_some_and(|name| SecretMasker::is_env_file(&name.to_string_lossy()));
+        let text = state.get_text_range(source_start, source_end);
+        let ranges: Vec<Range<usize>> = masker
+            .secret_ranges(&text, env_file)
+            .into_iter()
+            .map(|r| r.start + source_start..r.end + source_start)
+            .collect();
+        if ranges.is_empty() {
+            return tokens;
+        }
+        let is_masked = |byte: usize| ranges.iter().any(|r| r.contains(&byte));
+
+        let mut output = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            let offset = match token.source_offset {
+                Some(o) => o,
+                None => {
+                    output.push(token);
+                    continue;
+                }
+            };
+
+            match &token.kind {
+                ViewTokenWireKind::Text(text) => {
+                    let mut current_byte = offset;
+                    let mut visible_start = offset;
+                    let mut visible_chars = String::new();
+                    for ch in text.chars() {
+                        if is_masked(current_byte) {
+                            if !visible_chars.is_empty() {
+                                output.push(ViewTokenWire {
+                                    source_offset: Some(visible_start),
+                                    kind: ViewTokenWireKind::Text(std::mem::take(
+                                        &mut visible_chars,
+                                    )),
+                                    style: token.style.clone(),
+                                });
+                            }
+                            output.push(ViewTokenWire {
+                                source_offset: Some(current_byte),
+                                kind: ViewTokenWireKind::Text(MASK_CHAR.to_string()),
+                                style: token.style.clone(),
+                            });
+                        } else {
+                            if visible_chars.is_empty() {
+                                visible_start = current_byte;
+                            }
+                            visible_chars.push(ch);
+                        }
+                        current_byte += ch.len_utf8();
+                    }
+                    if !visible_chars.is_empty() {
+                        output.push(ViewTokenWire {
+                            source_offset: Some(visible_start),
+                            kind: ViewTokenWireKind::Text(visible_chars),
+                            style: token.style.clone(),
+                        });
+                    }
+                }
+                ViewTokenWireKind::Space if is_masked(offset) => {
+                    output.push(ViewTokenWire {
+                        source_offset: Some(offset),
+                        kind: ViewTokenWireKind::Text(MASK_CHAR.to_string()),
+                        style: token.style.clone(),
+                    });
+                }
+                _ => output.push(token),
+            }
+        }
+
+        output
+    }
+
     fn build_base_tokens(
         buffer: &mut Buffer,
         top_byte: usize,
@@ -3980,17 +4154,28 @@ impl SplitRenderer {
         highlight_context_bytes: usize,
         view_mode: &ViewMode,
         diagnostics_inline_text: bool,
+        fold_indicators: FoldIndicators,
     ) -> DecorationContext {
         use crate::view::folding::indent_folding;
 
         // Extend highlighting range by ~1 viewport size before/after for better context.
         // This helps tree-sitter parse multi-line constructs that span viewport boundaries.
-        let viewport_size = viewport_end.saturating_sub(viewport_start);
-        let highlight_start = viewport_start.saturating_sub(viewport_size);
-        let highlight_end = viewport_end
-            .saturating_add(viewport_size)
-            .min(state.buffer.len());
+        // Buffers with minified-length lines stay within the viewport: there the
+        // extra context is a single enormous line and costs far more than it helps.
+        let (highlight_start, highlight_end, highlight_context_bytes) = if state.long_lines {
+            (viewport_start, viewport_end.min(state.buffer.len()), 0)
+        } else {
+            let viewport_size = viewport_end.saturating_sub(viewport_start);
+            (
+                viewport_start.saturating_sub(viewport_size),
+                viewport_end
+                    .saturating_add(viewport_size)
+                    .min(state.buffer.len()),
+                highlight_context_bytes,
+            )
+        };
 
+        let highlight_started = std::time::Instant::now();
         let highlight_spans = state.highlighter.highlight_viewport(
             &state.buffer,
             highlight_start,
@@ -3998,6 +4183,7 @@ impl SplitRenderer {
             theme,
             highlight_context_bytes,
         );
+        state.render_timings.highlight = highlight_started.elapsed();
 
         // Update reference highlight overlays (debounced, creates overlays that auto-adjust)
         state.reference_highlight_overlay.update(
@@ -4129,8 +4315,13 @@ impl SplitRenderer {
             line_indicators.entry(key).or_insert(diff_ind);
         }
 
-        let fold_indicators =
-            Self::fold_indicators_for_viewport(state, folds, viewport_start, viewport_end);
+        let fold_indicators = Self::fold_indicators_for_viewport(
+            state,
+            folds,
+            viewport_start,
+            viewport_end,
+            fold_indicators,
+        );
 
         DecorationContext {
             highlight_spans,
@@ -4149,13 +4340,20 @@ impl SplitRenderer {
         folds: &FoldManager,
         viewport_start: usize,
         viewport_end: usize,
+        mode: FoldIndicators,
     ) -> BTreeMap<usize, FoldIndicator> {
         let mut indicators = BTreeMap::new();
+        if !mode.shows(true) {
+            return indicators;
+        }
 
         // Collapsed headers from marker-based folds — always keyed by header_byte
         for range in folds.resolved_ranges(&state.buffer, &state.marker_list) {
             indicators.insert(range.header_byte, FoldIndicator { collapsed: true });
         }
+        if !mode.shows(false) {
+            return indicators;
+        }
 
         if !state.folding_ranges.is_empty() {
             // Use LSP-provided folding ranges — key by line-start byte
@@ -4315,6 +4513,7 @@ impl SplitRenderer {
             session_mode,
             software_cursor_only,
             show_line_numbers,
+            whitespace,
             byte_offset_mode,
         } = input;
 
@@ -4679,7 +4878,7 @@ impl SplitRenderer {
                     // Leading = before first non-ws char, Trailing = after last non-ws char
                     // All-whitespace lines match both leading and trailing
                     let ws_show_tab = is_tab_start && {
-                        let ws = &state.buffer_settings.whitespace;
+                        let ws = &whitespace;
                         match (first_non_ws_idx, last_non_ws_idx) {
                             (None, _) | (_, None) => ws.tabs_leading || ws.tabs_trailing,
                             (Some(first), Some(last)) => {
@@ -4694,7 +4893,7 @@ impl SplitRenderer {
                         }
                     };
                     let ws_show_space = ch == ' ' && !is_tab_start && {
-                        let ws = &state.buffer_settings.whitespace;
+                        let ws = &whitespace;
                         match (first_non_ws_idx, last_non_ws_idx) {
                             (None, _) | (_, None) => ws.spaces_leading || ws.spaces_trailing,
                             (Some(first), Some(last)) => {
@@ -5451,7 +5650,10 @@ impl SplitRenderer {
         session_mode: bool,
         software_cursor_only: bool,
         show_line_numbers: bool,
+        whitespace: WhitespaceVisibility,
         diagnostics_inline_text: bool,
+        fold_indicators: FoldIndicators,
+        privacy: Option<&SecretMasker>,
     ) -> BufferLayoutOutput {
         let _span = tracing::trace_span!("compute_buffer_layout").entered();
 
@@ -5494,6 +5696,7 @@ impl SplitRenderer {
         // Clone view_transform so we can reuse it if scrolling triggers a rebuild
         let view_transform_for_rebuild = view_transform.clone();
 
+        let layout_started = std::time::Instant::now();
         let view_data = {
             let _span = tracing::trace_span!("build_view_data").entered();
             Self::build_view_data(
@@ -5508,6 +5711,7 @@ impl SplitRenderer {
                 &view_mode,
                 folds,
                 theme,
+                privacy,
             )
         };
 
@@ -5536,6 +5740,7 @@ impl SplitRenderer {
                 &view_mode,
                 folds,
                 theme,
+                privacy,
             );
             viewport.scroll_to_end_of_view(&rebuilt.lines);
             (rebuilt, None)
@@ -5564,6 +5769,7 @@ impl SplitRenderer {
                     &view_mode,
                     folds,
                     theme,
+                    privacy,
                 );
                 let _ = viewport.ensure_visible_in_layout(&rebuilt.lines, &primary, gutter_width);
                 rebuilt
@@ -5574,6 +5780,7 @@ impl SplitRenderer {
             view_data
         };
 
+        state.render_timings.layout = layout_started.elapsed();
         let view_anchor = Self::calculate_view_anchor(&view_data.lines, viewport.top_byte);
 
         let selection = Self::selection_context(state, cursors);
@@ -5622,6 +5829,7 @@ impl SplitRenderer {
             adjusted_visible_count,
         );
 
+        let decorations_started = std::time::Instant::now();
         let decorations = Self::decoration_context(
             state,
             viewport_start,
@@ -5632,7 +5840,11 @@ impl SplitRenderer {
             highlight_context_bytes,
             &view_mode,
             diagnostics_inline_text,
+            fold_indicators,
         );
+        state.render_timings.decorations = decorations_started
+            .elapsed()
+            .saturating_sub(state.render_timings.highlight);
 
         let calculated_offset = viewport.top_view_line_offset;
 
@@ -5671,6 +5883,7 @@ impl SplitRenderer {
             session_mode,
             software_cursor_only,
             show_line_numbers,
+            whitespace,
             byte_offset_mode,
         });
 
@@ -5866,7 +6079,10 @@ impl SplitRenderer {
         software_cursor_only: bool,
         rulers: &[usize],
         show_line_numbers: bool,
+        whitespace: WhitespaceVisibility,
         diagnostics_inline_text: bool,
+        fold_indicators: FoldIndicators,
+        privacy: Option<&SecretMasker>,
     ) -> Vec<ViewLineMapping> {
         let layout_output = Self::compute_buffer_layout(
             state,
@@ -5887,7 +6103,10 @@ impl SplitRenderer {
             session_mode,
             software_cursor_only,
             show_line_numbers,
+            whitespace,
             diagnostics_inline_text,
+            fold_indicators,
+            privacy,
         );
 
         let view_line_mappings = layout_output.view_line_mappings.clone();
@@ -6236,6 +6455,7 @@ mod tests {
             &ViewMode::Source, // Tests use source mode
             &empty_folds,
             &theme,
+            None,
         );
         let view_anchor = SplitRenderer::calculate_view_anchor(&view_data.lines, 0);
 
@@ -6264,6 +6484,7 @@ mod tests {
             100_000,           // default highlight context bytes
             &ViewMode::Source, // Tests use source mode
             false,             // inline diagnostics off for test
+            FoldIndicators::Always,
         );
 
         let output = SplitRenderer::render_view_lines(LineRenderInput {
@@ -6285,6 +6506,7 @@ mod tests {
             session_mode: false,
             software_cursor_only: false,
             show_line_numbers: true, // Tests show line numbers
+            whitespace: state.buffer_settings.whitespace,
             byte_offset_mode: false, // Tests use exact line numbers
         });
 
@@ -6322,6 +6544,7 @@ mod tests {
             &ViewMode::Source,
             &folds,
             &theme,
+            None,
         );
 
         let lines: Vec<String> = view_data.lines.iter().map(|l| l.text.clone()).collect();
@@ -6364,8 +6587,10 @@ mod tests {
         let mut folds = FoldManager::new();
         folds.add(&mut state.marker_list, start, end, None);
 
-        let indicators =
-            SplitRenderer::fold_indicators_for_viewport(&state, &folds, 0, state.buffer.len());
+        let indicators_for = |mode| {
+            SplitRenderer::fold_indicators_for_viewport(&state, &folds, 0, state.buffer.len(), mode)
+        };
+        let indicators = indicators_for(FoldIndicators::Always);
 
         // Collapsed fold: header is line 0 (byte 0)
         assert_eq!(indicators.get(&0).map(|i| i.collapsed), Some(true));
@@ -6375,6 +6600,10 @@ mod tests {
             indicators.get(&line1_byte).map(|i| i.collapsed),
             Some(false)
         );
+
+        let collapsed_only = indicators_for(FoldIndicators::Collapsed);
+        assert_eq!(collapsed_only.keys().copied().collect::<Vec<_>>(), vec![0]);
+        assert!(indicators_for(FoldIndicators::Never).is_empty());
     }
 
     #[test]
@@ -242,6 +242,38 @@ pub fn truncate_path(path: &Path, max_len: usize) -> TruncatedPath {
     }
 }
 
+/// Selections larger than this are shown in bytes, so rendering never reads
+/// megabytes of text per frame
+const LIVE_SELECTION_COUNT_LIMIT: usize = 256 * 1024;
+
+/// Size of the current selection(s): characters, plus lines when any
+/// selection spans more than one. Empty when nothing is selected.
+fn selection_indicator(state: &mut EditorState, cursors: &crate::model::cursor::Cursors) -> String {
+    let bytes: usize = cursors
+        .iter()
+        .filter_map(|(_, cursor)| cursor.selection_range())
+        .map(|range| range.len())
+        .sum();
+    if bytes == 0 {
+        return String::new();
+    }
+    if bytes > LIVE_SELECTION_COUNT_LIMIT {
+        return format!(" | {}", t!("status.selection_bytes", bytes = bytes));
+    }
+
+    let stats = state.selection_stats(cursors);
+    let summary = if stats.lines > stats.selections {
+        t!(
+            "status.selection_lines",
+            chars = stats.chars,
+            lines = stats.lines
+        )
+    } else {
+        t!("status.selection_chars", chars = stats.chars)
+    };
+    format!(" | {}", summary)
+}
+
 /// Renders the status bar and prompt/minibuffer
 pub struct StatusBarRenderer;
 
@@ -261,7 +293,10 @@ impl StatusBarRenderer {
     /// * `warning_level` - LSP warning level (for coloring LSP indicator)
     /// * `general_warning_count` - Number of general warnings (for badge display)
     /// * `remote_connection` - Optional remote connection info (e.g., "user@host")
+    /// * `remote_badge` - Optional connection latency badge and how alarming it is
     /// * `session_name` - Optional session name (for session persistence mode)
+    /// * `search_count` - Current search match and match count, e.g. "3/57"
+    /// * `privacy_mode` - Whether Privacy Mode is on (shows a watermark)
     ///
     /// # Returns
     /// Layout information with positions of clickable indicators
@@ -283,8 +318,12 @@ impl StatusBarRenderer {
         general_warning_count: usize,
         hover: StatusBarHover,
         remote_connection: Option<&str>,
+        remote_badge: Option<(&str, WarningLevel)>,
         session_name: Option<&str>,
         read_only: bool,
+        missing_on_disk: bool,
+        search_count: Option<&str>,
+        privacy_mode: bool,
     ) -> StatusBarLayout {
         Self::render_status(
             frame,
@@ -303,8 +342,12 @@ impl StatusBarRenderer {
             general_warning_count,
             hover,
             remote_connection,
+            remote_badge,
             session_name,
             read_only,
+            missing_on_disk,
+            search_count,
+            privacy_mode,
         )
     }
 
@@ -489,8 +532,12 @@ impl StatusBarRenderer {
         general_warning_count: usize,
         hover: StatusBarHover,
         remote_connection: Option<&str>,
+        remote_badge: Option<(&str, WarningLevel)>,
         session_name: Option<&str>,
         read_only: bool,
+        missing_on_disk: bool,
+        search_count: Option<&str>,
+        privacy_mode: bool,
     ) -> StatusBarLayout {
         // Initialize layout tracking
         let mut layout = StatusBarLayout::default();
@@ -504,6 +551,7 @@ impl StatusBarRenderer {
         };
 
         let read_only_indicator = if read_only { " [RO]" } else { "" };
+        let missing_indicator = if missing_on_disk { " [missing]" } else { "" };
 
         // Format chord state if present
         let chord_display = if !chord_state.is_empty() {
@@ -573,6 +621,9 @@ impl StatusBarRenderer {
             String::new()
         };
 
+        // Build selection size indicator (only show while something is selected)
+        let selection_indicator = selection_indicator(state, cursors);
+
         // Build cursor count indicator (only show if multiple cursors)
         let cursor_count_indicator = if cursors.count() > 1 {
             format!(" | {}", t!("status.cursors", count = cursors.count()))
@@ -580,6 +631,11 @@ impl StatusBarRenderer {
             String::new()
         };
 
+        // Build search match indicator (only show while a search is active)
+        let search_indicator = search_count
+            .map(|count| format!(" | {}", t!("status.search_count", count = count)))
+            .unwrap_or_default();
+
         // Build status message parts
         let mut message_parts: Vec<&str> = Vec::new();
         if let Some(msg) = status_message {
@@ -600,7 +656,7 @@ impl StatusBarRenderer {
         };
 
         // Build left status (file info, position, diagnostics, messages)
-        // Session/Remote indicator comes first (if present), then filename
+        // Privacy watermark, then Session/Remote indicator (if present), then filename
         // Line and column are 0-indexed internally, but displayed as 1-indexed (standard editor convention)
         // For virtual buffers with hidden cursors, don't show line/column info
         let remote_prefix = remote_connection
@@ -609,23 +665,28 @@ impl StatusBarRenderer {
         let session_prefix = session_name
             .map(|name| format!("[{}] ", name))
             .unwrap_or_default();
+        let privacy_prefix = if privacy_mode {
+            format!("[{}] ", t!("privacy.watermark"))
+        } else {
+            String::new()
+        };
         let byte_offset_mode = state.buffer.line_count().is_none();
         let base_status = if state.show_cursors {
             if byte_offset_mode {
                 format!(
-                    "{session_prefix}{remote_prefix}{filename}{modified}{read_only_indicator} | Byte {}{diagnostics_summary}{cursor_count_indicator}",
+                    "{privacy_prefix}{session_prefix}{remote_prefix}{filename}{modified}{read_only_indicator}{missing_indicator} | Byte {}{selection_indicator}{diagnostics_summary}{cursor_count_indicator}{search_indicator}",
                     cursor.position
                 )
             } else {
                 format!(
-                    "{session_prefix}{remote_prefix}{filename}{modified}{read_only_indicator} | Ln {}, Col {}{diagnostics_summary}{cursor_count_indicator}",
+                    "{privacy_prefix}{session_prefix}{remote_prefix}{filename}{modified}{read_only_indicator}{missing_indicator} | Ln {}, Col {}{selection_indicator}{diagnostics_summary}{cursor_count_indicator}{search_indicator}",
                     line + 1,
                     col + 1
                 )
             }
         } else {
             // Virtual buffer - just show filename and modified indicator
-            format!("{session_prefix}{remote_prefix}{filename}{modified}{read_only_indicator}{diagnostics_summary}")
+            format!("{privacy_prefix}{session_prefix}{remote_prefix}{filename}{modified}{read_only_indicator}{missing_indicator}{diagnostics_summary}")
         };
 
         // Track where the message starts for click detection
@@ -635,9 +696,15 @@ impl StatusBarRenderer {
         let left_status = format!("{base_status}{chord_display}{message_suffix}");
 
         // Build right-side indicators (these stay fixed on the right)
-        // Order: [Line ending] [Language] [LSP indicator] [warning badge] [update] [Palette]
+        // Order: [Remote latency] [Line ending] [Language] [LSP indicator] [warning badge] [update] [Palette]
         // Note: Remote indicator is now on the left side, before the filename
 
+        // Remote latency badge (colored when the connection is slow or down)
+        let remote_badge_text = remote_badge
+            .map(|(text, _)| format!(" {} ", text))
+            .unwrap_or_default();
+        let remote_badge_width = str_width(&remote_badge_text);
+
         // Line ending indicator (clickable to change format)
         let line_ending_text = format!(" {} ", state.buffer.line_ending().display_name());
         let line_ending_width = str_width(&line_ending_text);
@@ -684,10 +751,11 @@ impl StatusBarRenderer {
         let padded_cmd_palette = format!(" {} ", cmd_palette_indicator);
 
         // Calculate available width and right side width
-        // Right side: [Line ending] [Encoding] [Language] [LSP indicator] [warning badge] [update] [Palette]
+        // Right side: [Remote latency] [Line ending] [Encoding] [Language] [LSP indicator] [warning badge] [update] [Palette]
         let available_width = area.width as usize;
         let cmd_palette_width = str_width(&padded_cmd_palette);
-        let right_side_width = line_ending_width
+        let right_side_width = remote_badge_width
+            + line_ending_width
             + encoding_width
             + language_width
             + lsp_indicator_width
@@ -778,6 +846,26 @@ impl StatusBarRenderer {
                 current_col = area.x + (available_width - right_side_width) as u16;
             }
 
+            // Add remote latency badge
+            if let Some((_, level)) = remote_badge {
+                let (fg, bg) = match level {
+                    WarningLevel::Error => (
+                        theme.status_error_indicator_fg,
+                        theme.status_error_indicator_bg,
+                    ),
+                    WarningLevel::Warning => (
+                        theme.status_warning_indicator_fg,
+                        theme.status_warning_indicator_bg,
+                    ),
+                    WarningLevel::None => (theme.status_bar_fg, theme.status_bar_bg),
+                };
+                spans.push(Span::styled(
+                    remote_badge_text.clone(),
+                    Style::default().fg(fg).bg(bg),
+                ));
+                current_col += remote_badge_width as u16;
+            }
+
             // Add line ending indicator (clickable to change format)
             {
                 let is_hovering = hover == StatusBarHover::LineEndingIndicator;
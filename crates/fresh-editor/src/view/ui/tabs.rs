@@ -225,6 +225,33 @@ fn resolve_tab_names(
     result
 }
 
+/// Tab suffix for a buffer whose file was deleted or moved on disk
+fn missing_indicator(
+    buffer_metadata: &HashMap<BufferId, BufferMetadata>,
+    id: &BufferId,
+) -> &'static str {
+    if buffer_metadata
+        .get(id)
+        .is_some_and(|m| m.missing_on_disk.is_some())
+    {
+        " [missing]"
+    } else {
+        ""
+    }
+}
+
+/// Text of a tab's name and close button.
+///
+/// Normally the name is padded on both sides (" {label} " + "× "); `tight`
+/// tabs drop that padding (`{label} ` + "×") to fit more tabs in the bar.
+fn tab_texts(label: &str, tight: bool) -> (String, &'static str) {
+    if tight {
+        (format!("{label} "), "×")
+    } else {
+        (format!(" {label} "), "× ")
+    }
+}
+
 /// Calculate tab widths for scroll offset calculations.
 /// Returns (tab_widths, rendered_buffer_ids) where tab_widths includes separators.
 /// This uses the same logic as render_for_split to ensure consistency.
@@ -233,6 +260,7 @@ pub fn calculate_tab_widths(
     buffers: &HashMap<BufferId, EditorState>,
     buffer_metadata: &HashMap<BufferId, BufferMetadata>,
     composite_buffers: &HashMap<BufferId, crate::model::composite_buffer::CompositeBuffer>,
+    tight: bool,
 ) -> (Vec<usize>, Vec<BufferId>) {
     let mut tab_widths: Vec<usize> = Vec::new();
     let mut rendered_buffer_ids: Vec<BufferId> = Vec::new();
@@ -279,9 +307,13 @@ pub fn calculate_tab_widths(
             ""
         };
 
-        // Same format as render_for_split: " {name}{modified}{binary_indicator} " + "× "
-        let tab_name_text = format!(" {name}{modified}{binary_indicator} ");
-        let close_text = "× ";
+        let missing_indicator = missing_indicator(buffer_metadata, id);
+
+        // Same format as render_for_split
+        let (tab_name_text, close_text) = tab_texts(
+            &format!("{name}{modified}{binary_indicator}{missing_indicator}"),
+            tight,
+        );
         let tab_width = str_width(&tab_name_text) + str_width(close_text);
 
         // Add separator if not first tab
@@ -309,6 +341,7 @@ impl TabsRenderer {
     /// * `theme` - The active theme for colors
     /// * `is_active_split` - Whether this split is the active one
     /// * `hovered_tab` - Optional (buffer_id, is_close_button) if a tab is being hovered
+    /// * `tight` - Whether tabs are drawn without padding (compact densities)
     ///
     /// # Returns
     /// `TabLayout` containing hit areas for mouse interaction.
@@ -325,6 +358,7 @@ impl TabsRenderer {
         is_active_split: bool,
         tab_scroll_offset: usize,
         hovered_tab: Option<(BufferId, bool)>, // (buffer_id, is_close_button)
+        tight: bool,
     ) -> TabLayout {
         let mut layout = TabLayout::new(area);
         const SCROLL_INDICATOR_LEFT: &str = "<";
@@ -418,12 +452,15 @@ impl TabsRenderer {
                 base_style
             };
 
-            // Build tab content: " {name}{modified}{binary_indicator} "
-            let tab_name_text = format!(" {name}{modified}{binary_indicator} ");
-            let tab_name_width = str_width(&tab_name_text);
+            let missing_indicator = missing_indicator(buffer_metadata, id);
 
-            // Close button: "× "
-            let close_text = "× ";
+            // Build tab content: " {name}{modified}{binary_indicator}{missing_indicator} "
+            // and the close button "× ", without the padding for tight tabs
+            let (tab_name_text, close_text) = tab_texts(
+                &format!("{name}{modified}{binary_indicator}{missing_indicator}"),
+                tight,
+            );
+            let tab_name_width = str_width(&tab_name_text);
             let close_width = str_width(close_text);
 
             let total_width = tab_name_width + close_width;
@@ -687,6 +724,7 @@ impl TabsRenderer {
             true, // Legacy behavior: always treat as active
             0,    // Default tab_scroll_offset for legacy render
             None, // No hover state for legacy render
+            false,
         );
     }
 }
@@ -320,6 +320,11 @@ impl VirtualTextManager {
         self.texts.is_empty()
     }
 
+    /// Iterate over all virtual text entries (in no particular order)
+    pub fn iter(&self) -> impl Iterator<Item = &VirtualText> {
+        self.texts.values()
+    }
+
     /// Query virtual texts in a byte range
     ///
     /// Returns a vector of (byte_position, &VirtualText) pairs, sorted by:
@@ -29,6 +29,7 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::config::WhitespaceVisibility;
 use crate::input::input_history::get_data_dir;
 
 /// Current workspace file format version
@@ -52,6 +53,10 @@ pub struct Workspace {
     /// Active split ID
     pub active_split_id: usize,
 
+    /// Split that was maximized, hiding the others
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximized_split_id: Option<usize>,
+
     /// Per-split view states (keyed by split_id)
     pub split_states: HashMap<usize, SerializedSplitViewState>,
 
@@ -74,6 +79,10 @@ pub struct Workspace {
     #[serde(default)]
     pub bookmarks: HashMap<char, SerializedBookmark>,
 
+    /// Named bookmarks (name -> file position)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub named_bookmarks: HashMap<String, SerializedBookmark>,
+
     /// Open terminal workspaces (for restoration)
     #[serde(default)]
     pub terminals: Vec<SerializedTerminalWorkspace>,
@@ -201,6 +210,24 @@ pub struct SerializedFileState {
     /// Collapsed folding ranges for this buffer/view
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub folds: Vec<SerializedFoldRange>,
+
+    /// Anonymous line marks (0-indexed line numbers)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub line_marks: Vec<usize>,
+
+    /// Line number visibility for this buffer in this split
+    /// (None = use the config default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub show_line_numbers: Option<bool>,
+
+    /// Line wrapping for this buffer in this split (None = use the config default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_wrap: Option<bool>,
+
+    /// Whitespace indicator override for this buffer in this split
+    /// (None = follow the buffer's settings)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub whitespace: Option<WhitespaceVisibility>,
 }
 
 /// Line-based folded range for persistence
@@ -328,7 +355,7 @@ pub struct SearchOptions {
 /// Serialized bookmark (file path + byte offset)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SerializedBookmark {
-    /// File path (relative to working_dir)
+    /// File path (relative to working_dir, absolute for files outside it)
     pub file_path: PathBuf,
     /// Byte offset position in the file
     pub position: usize,
@@ -398,32 +425,31 @@ impl PersistedFileState {
 /// will restore the same scroll/cursor position.
 ///
 /// Each file's state is stored in a separate JSON file at
-/// `$XDG_DATA_HOME/fresh/file_states/{encoded_path}.json` to avoid conflicts
-/// between concurrent editors. States are loaded lazily when opening files
-/// and saved immediately when closing files or saving the workspace.
+/// `{data_dir}/file_states/{encoded_path}.json` (see
+/// [`DirectoryContext::file_states_dir`]) to avoid conflicts between
+/// concurrent editors. States are loaded lazily when opening files and saved
+/// immediately when closing files or saving the workspace. Only the
+/// [`MAX_FILE_STATES`] most recently saved files are kept.
+///
+/// [`DirectoryContext::file_states_dir`]: crate::config_io::DirectoryContext::file_states_dir
 pub struct PersistedFileWorkspace;
 
-impl PersistedFileWorkspace {
-    /// Get the directory for file state files
-    fn states_dir() -> io::Result<PathBuf> {
-        Ok(get_data_dir()?.join("file_states"))
-    }
+/// How many files' states are remembered; older ones are forgotten
+pub const MAX_FILE_STATES: usize = 1000;
 
+impl PersistedFileWorkspace {
     /// Get the state file path for a source file
-    fn state_file_path(source_path: &Path) -> io::Result<PathBuf> {
+    fn state_file_path(states_dir: &Path, source_path: &Path) -> PathBuf {
         let canonical = source_path
             .canonicalize()
             .unwrap_or_else(|_| source_path.to_path_buf());
         let filename = format!("{}.json", encode_path_for_filename(&canonical));
-        Ok(Self::states_dir()?.join(filename))
+        states_dir.join(filename)
     }
 
     /// Load the state for a file by its absolute path (from disk)
-    pub fn load(path: &Path) -> Option<SerializedFileState> {
-        let state_path = match Self::state_file_path(path) {
-            Ok(p) => p,
-            Err(_) => return None,
-        };
+    pub fn load(states_dir: &Path, path: &Path) -> Option<SerializedFileState> {
+        let state_path = Self::state_file_path(states_dir, path);
 
         if !state_path.exists() {
             return None;
@@ -448,14 +474,8 @@ impl PersistedFileWorkspace {
     }
 
     /// Save the state for a file by its absolute path (to disk, atomic write)
-    pub fn save(path: &Path, state: SerializedFileState) {
-        let state_path = match Self::state_file_path(path) {
-            Ok(p) => p,
-            Err(e) => {
-                tracing::warn!("Failed to get state path for {:?}: {}", path, e);
-                return;
-            }
-        };
+    pub fn save(states_dir: &Path, path: &Path, state: SerializedFileState) {
+        let state_path = Self::state_file_path(states_dir, path);
 
         // Ensure directory exists
         if let Some(parent) = state_path.parent() {
@@ -489,6 +509,28 @@ impl PersistedFileWorkspace {
             tracing::warn!("Failed to save file state for {:?}: {}", path, e);
         } else {
             tracing::trace!("File state saved for {:?}", path);
+            Self::prune(states_dir, MAX_FILE_STATES);
+        }
+    }
+
+    /// Delete the least recently saved states beyond the newest `keep`
+    fn prune(states_dir: &Path, keep: usize) {
+        let Ok(entries) = std::fs::read_dir(states_dir) else {
+            return;
+        };
+        let mut states: Vec<(SystemTime, PathBuf)> = entries
+            .flatten()
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .collect();
+        if states.len() <= keep {
+            return;
+        }
+        states.sort_by(|a, b| b.0.cmp(&a.0));
+        for (_, path) in states.drain(keep..) {
+            if let Err(e) = std::fs::remove_file(&path) {
+                tracing::debug!("Failed to remove old file state {:?}: {}", path, e);
+            }
         }
     }
 }
@@ -773,12 +815,14 @@ impl Workspace {
                 unnamed_recovery_id: None,
             },
             active_split_id: 0,
+            maximized_split_id: None,
             split_states: HashMap::new(),
             config_overrides: WorkspaceConfigOverrides::default(),
             file_explorer: FileExplorerState::default(),
             histories: WorkspaceHistories::default(),
             search_options: SearchOptions::default(),
             bookmarks: HashMap::new(),
+            named_bookmarks: HashMap::new(),
             terminals: Vec::new(),
             external_files: Vec::new(),
             unnamed_buffers: Vec::new(),
@@ -944,6 +988,10 @@ mod tests {
             compose_width: None,
             plugin_state: HashMap::new(),
             folds: Vec::new(),
+            line_marks: vec![3, 17],
+            show_line_numbers: Some(false),
+            line_wrap: Some(true),
+            whitespace: Some(WhitespaceVisibility::default()),
         };
 
         let json = serde_json::to_string(&file_state).unwrap();
@@ -955,6 +1003,31 @@ mod tests {
         assert_eq!(restored.additional_cursors.len(), 1);
         assert_eq!(restored.scroll.top_byte, 500);
         assert_eq!(restored.scroll.left_column, 10);
+        assert_eq!(restored.line_marks, vec![3, 17]);
+        assert_eq!(restored.show_line_numbers, Some(false));
+        assert_eq!(restored.line_wrap, Some(true));
+        assert_eq!(restored.whitespace, Some(WhitespaceVisibility::default()));
+    }
+
+    #[test]
+    fn test_file_states_keep_most_recent() {
+        let temp = tempfile::tempdir().unwrap();
+        let now = SystemTime::now();
+        for age in 0..4u64 {
+            let path = temp.path().join(format!("file{}.json", age));
+            let file = std::fs::File::create(&path).unwrap();
+            file.set_modified(now - std::time::Duration::from_secs(age * 60))
+                .unwrap();
+        }
+
+        PersistedFileWorkspace::prune(temp.path(), 2);
+
+        let mut left: Vec<String> = std::fs::read_dir(temp.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(left, vec!["file0.json", "file1.json"]);
     }
 
     #[test]
@@ -1586,6 +1586,120 @@ done
         std::env::temp_dir().join("fake_lsp_server_env_echo.sh")
     }
 
+    /// Spawn a fake LSP server that offers code actions
+    ///
+    /// On didOpen it publishes an error on the first three characters of the
+    /// document. A codeAction request is answered with the JSON in the
+    /// `FRESH_TEST_CODE_ACTIONS` environment variable, with `__URI__`
+    /// replaced by the document's URI. `workspace/executeCommand` makes the
+    /// server send a `workspace/applyEdit` inserting "CMD " at the start of
+    /// the document.
+    pub fn spawn_with_code_actions() -> anyhow::Result<Self> {
+        let (stop_tx, stop_rx) = mpsc::channel();
+
+        let script = r#"#!/bin/bash
+
+# Function to read a message
+read_message() {
+    # Read headers
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        # Empty line marks end of headers
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+
+    # Read content
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+# Function to send a message
+send_message() {
+    local message="$1"
+    local length=${#message}
+    echo -en "Content-Length: $length\r\n\r\n$message"
+}
+
+ACTIONS="${FRESH_TEST_CODE_ACTIONS:-[]}"
+
+# Main loop
+while true; do
+    # Read incoming message
+    msg=$(read_message)
+
+    if [ -z "$msg" ]; then
+        break
+    fi
+
+    # Extract method from JSON
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | head -1 | cut -d':' -f2)
+
+case "$method" in
+    "initialize")
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"codeActionProvider":true,"executeCommandProvider":{"commands":["fake.insertText"]},"textDocumentSync":1}}}'
+        ;;
+    "textDocument/didOpen")
+        uri=$(echo "$msg" | grep -o '"uri":"[^"]*"' | head -1 | cut -d'"' -f4)
+        send_message '{"jsonrpc":"2.0","method":"textDocument/publishDiagnostics","params":{"uri":"'$uri'","diagnostics":[{"range":{"start":{"line":0,"character":0},"end":{"line":0,"character":3}},"severity":1,"message":"Fake problem"}]}}'
+        ;;
+    "textDocument/codeAction")
+        uri=$(echo "$msg" | grep -o '"uri":"[^"]*"' | head -1 | cut -d'"' -f4)
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":'"${ACTIONS//__URI__/$uri}"'}'
+        ;;
+    "workspace/executeCommand")
+        send_message '{"jsonrpc":"2.0","id":9000,"method":"workspace/applyEdit","params":{"edit":{"changes":{"'$uri'":[{"range":{"start":{"line":0,"character":0},"end":{"line":0,"character":0}},"newText":"CMD "}]}}}}'
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+        ;;
+    "textDocument/diagnostic")
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"items":[]}}'
+        ;;
+    "textDocument/inlayHint")
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[]}'
+        ;;
+    "shutdown")
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+        break
+        ;;
+esac
+done
+"#;
+
+        // Tests using this server run in parallel, so the script is swapped
+        // in atomically rather than truncated under a running server
+        let script_path = Self::code_actions_script_path();
+        let mut script_file = tempfile::NamedTempFile::new_in(std::env::temp_dir())?;
+        std::io::Write::write_all(&mut script_file, script.as_bytes())?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = script_file.as_file().metadata()?.permissions();
+            perms.set_mode(0o755);
+            script_file.as_file().set_permissions(perms)?;
+        }
+        script_file.persist(&script_path)?;
+
+        let handle = Some(thread::spawn(move || {
+            let _ = stop_rx.recv();
+        }));
+
+        Ok(Self { handle, stop_tx })
+    }
+
+    /// Get the path to the code actions fake LSP server script
+    pub fn code_actions_script_path() -> std::path::PathBuf {
+        std::env::temp_dir().join("fake_lsp_server_code_actions.sh")
+    }
+
     /// Stop the server
     pub fn stop(&mut self) {
         let _ = self.stop_tx.send(());
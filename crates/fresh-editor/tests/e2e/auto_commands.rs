@@ -0,0 +1,87 @@
+//! E2E tests for config-driven auto-commands (`auto_commands` in the config)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{AutoCommand, AutoCommandEvent, Config};
+use tempfile::TempDir;
+
+fn auto_command(event: AutoCommandEvent, command: &str) -> AutoCommand {
+    AutoCommand {
+        event,
+        command: command.to_string(),
+        args: Default::default(),
+        language: None,
+        pattern: None,
+        enabled: true,
+    }
+}
+
+/// Test that on_open commands only run for files matching the pattern
+#[test]
+fn test_on_open_auto_command_with_pattern() {
+    let temp_dir = TempDir::new().unwrap();
+    let notes = temp_dir.path().join("notes.txt");
+    let readme = temp_dir.path().join("README.md");
+    std::fs::write(&notes, "first\nsecond\n").unwrap();
+    std::fs::write(&readme, "# Title\n").unwrap();
+
+    let mut config = Config::default();
+    config.auto_commands.push(AutoCommand {
+        pattern: Some("*.txt".to_string()),
+        ..auto_command(AutoCommandEvent::OnOpen, "move_document_end")
+    });
+    let mut harness = EditorTestHarness::with_config(100, 24, config).unwrap();
+
+    harness.open_file(&notes).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), "first\nsecond\n".len());
+
+    harness.open_file(&readme).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), 0);
+}
+
+/// Test that on_save commands resolve palette names and respect the language
+#[test]
+fn test_on_save_auto_command_for_language() {
+    let temp_dir = TempDir::new().unwrap();
+    let rust_file = temp_dir.path().join("main.rs");
+    let text_file = temp_dir.path().join("notes.txt");
+    std::fs::write(&rust_file, "fn main() {}\n").unwrap();
+    std::fs::write(&text_file, "plain\n").unwrap();
+
+    let mut config = Config::default();
+    config.auto_commands.push(AutoCommand {
+        language: Some("rust".to_string()),
+        ..auto_command(AutoCommandEvent::OnSave, "Select All")
+    });
+    let mut harness = EditorTestHarness::with_config(100, 24, config).unwrap();
+
+    // Saving the Rust file selects everything, so typing replaces it
+    harness.open_file(&rust_file).unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "x");
+
+    // Other languages are left alone
+    harness.open_file(&text_file).unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "xplain\n");
+}
+
+/// Test that unknown commands are reported when the config is loaded
+#[test]
+fn test_unknown_auto_command_is_reported() {
+    let mut config = Config::default();
+    config
+        .auto_commands
+        .push(auto_command(AutoCommandEvent::OnSave, "not_a_command"));
+    let mut harness = EditorTestHarness::with_config(120, 24, config).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Unknown command(s) in auto_commands: not_a_command");
+}
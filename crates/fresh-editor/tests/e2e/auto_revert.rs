@@ -307,6 +307,89 @@ fn test_auto_revert_preserves_cursor_position() {
     );
 }
 
+/// Line of `content` that starts at or contains byte `pos`
+fn line_at(content: &str, pos: usize) -> &str {
+    let start = content[..pos].rfind('\n').map_or(0, |i| i + 1);
+    content[start..].lines().next().unwrap_or("")
+}
+
+/// Lines inserted above the cursor externally move the cursor and the top of
+/// the view along with their lines instead of leaving them at the old offset
+#[test]
+fn test_auto_revert_cursor_follows_lines_inserted_above() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("insert_above.txt");
+
+    let lines: Vec<String> = (1..=100).map(|i| format!("Line number {}", i)).collect();
+    let content = lines.join("\n");
+    write_and_sync(&file_path, &content);
+    harness.open_file(&file_path).unwrap();
+
+    use crossterm::event::{KeyCode, KeyModifiers};
+    for _ in 0..60 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        line_at(&content, harness.cursor_position()),
+        "Line number 61"
+    );
+    let top_line_before = line_at(&content, harness.top_byte()).to_string();
+
+    harness.sleep(FILE_CHANGE_DELAY);
+    let inserted: Vec<String> = (1..=5).map(|i| format!("Inserted {}", i)).collect();
+    let modified_content = format!("{}\n{}", inserted.join("\n"), content);
+    write_and_sync(&file_path, &modified_content);
+
+    let expected = modified_content.clone();
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == expected)
+        .expect("Auto-revert should update buffer");
+    harness.render().unwrap();
+
+    // Still at the end of the same line, and the same line is at the top
+    let cursor_after = harness.cursor_position();
+    assert_eq!(line_at(&modified_content, cursor_after), "Line number 61");
+    assert_eq!(
+        cursor_after,
+        modified_content.find("Line number 61").unwrap() + "Line number 61".len()
+    );
+    assert_eq!(
+        line_at(&modified_content, harness.top_byte()),
+        top_line_before
+    );
+}
+
+/// Lines inserted below the cursor externally leave the cursor where it was
+#[test]
+fn test_auto_revert_cursor_unaffected_by_lines_inserted_below() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("insert_below.txt");
+
+    let content = "fn main() {\n    let x = 1;\n}\n";
+    write_and_sync(&file_path, content);
+    harness.open_file(&file_path).unwrap();
+
+    use crossterm::event::{KeyCode, KeyModifiers};
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    let cursor_before = harness.cursor_position();
+
+    harness.sleep(FILE_CHANGE_DELAY);
+    let modified_content = "fn main() {\n    let x = 1;\n}\n\nfn helper() {}\n";
+    write_and_sync(&file_path, modified_content);
+
+    let expected = modified_content.to_string();
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == expected)
+        .expect("Auto-revert should update buffer");
+
+    assert_eq!(harness.cursor_position(), cursor_before);
+}
+
 /// Test that auto-revert is not disabled by a single save operation
 /// Previously, saving the file would immediately trigger auto-revert disable
 /// because the file change event would come too quickly after the previous event
@@ -466,3 +549,96 @@ fn test_auto_revert_with_temp_rename_save() {
         harness.assert_buffer_content(&new_content);
     }
 }
+
+/// Test that a change is picked up from the file size alone, as happens on
+/// filesystems with coarse modification times (NFS, some containers)
+#[test]
+fn test_auto_revert_detects_size_change_with_same_mtime() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("coarse_mtime.txt");
+
+    write_and_sync(&file_path, "short");
+    let original_mtime = fs::metadata(&file_path).unwrap().modified().unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.assert_buffer_content("short");
+
+    // Rewrite the file with a different size but put the old mtime back
+    write_and_sync(&file_path, "a much longer replacement");
+    File::options()
+        .write(true)
+        .open(&file_path)
+        .unwrap()
+        .set_modified(original_mtime)
+        .unwrap();
+
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "a much longer replacement")
+        .expect("Auto-revert should notice the size change");
+}
+
+/// A file deleted on disk keeps its buffer content, is marked missing in the
+/// tab and status bar, and the mark clears once the file is back.
+#[test]
+fn test_deleted_file_is_marked_missing_until_restored() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("deleted.txt");
+
+    write_and_sync(&file_path, "keep me");
+    harness.open_file(&file_path).unwrap();
+    harness.assert_buffer_content("keep me");
+
+    fs::remove_file(&file_path).unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("[missing]"))
+        .expect("Deleted file should be marked missing");
+    harness.assert_buffer_content("keep me");
+
+    write_and_sync(&file_path, "keep me");
+    harness
+        .wait_until(|h| !h.screen_to_string().contains("[missing]"))
+        .expect("Missing mark should clear when the file reappears");
+    harness.assert_buffer_content("keep me");
+}
+
+/// Saving a buffer whose file was renamed on disk offers to follow the
+/// rename, which rebinds the buffer to the new path.
+#[test]
+fn test_renamed_file_can_follow_rename_on_save() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut harness = EditorTestHarness::with_temp_project(120, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let old_path = project_dir.join("before.txt");
+    let new_path = project_dir.join("after.txt");
+
+    write_and_sync(&old_path, "renamed content");
+    harness.open_file(&old_path).unwrap();
+
+    fs::rename(&old_path, &new_path).unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("[missing]"))
+        .expect("Renamed file should be marked missing");
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("(f)ollow rename");
+
+    harness.type_text("f").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("after.txt");
+    harness.assert_screen_not_contains("[missing]");
+    assert!(
+        !old_path.exists(),
+        "Following the rename must not recreate the old file"
+    );
+    harness.assert_buffer_content("renamed content");
+}
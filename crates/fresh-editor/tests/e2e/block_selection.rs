@@ -542,3 +542,45 @@ fn test_block_selection_copy_copies_rectangular_region() {
         "Block selection copy should produce exactly the rectangular region"
     );
 }
+
+/// Test Alt+drag selects a rectangle; typing edits every row it spans except
+/// rows too short to reach it, and Escape collapses to one cursor
+#[test]
+fn test_alt_drag_block_selection() {
+    use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("abcdef\na\nabcdef\nabcdef").unwrap();
+    harness.render().unwrap();
+
+    let (content_first_row, _) = harness.content_area_rows();
+    let first_row = content_first_row as u16;
+    let gutter_width = 8;
+    for (kind, col, row) in [
+        (MouseEventKind::Down(MouseButton::Left), 2, 0),
+        (MouseEventKind::Drag(MouseButton::Left), 3, 2),
+        (MouseEventKind::Drag(MouseButton::Left), 4, 3),
+        (MouseEventKind::Up(MouseButton::Left), 4, 3),
+    ] {
+        harness
+            .send_mouse(MouseEvent {
+                kind,
+                column: gutter_width + col,
+                row: first_row + row,
+                modifiers: KeyModifiers::ALT,
+            })
+            .unwrap();
+    }
+    harness.render().unwrap();
+    assert!(harness.has_selection(), "Alt+drag should select a block");
+
+    harness.type_text("X").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "abXef\na\nabXef\nabXef"
+    );
+    assert_eq!(harness.cursor_count(), 3);
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_count(), 1);
+}
@@ -0,0 +1,71 @@
+//! E2E tests for the bookmark picker
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+const CONTENT: &str = "zero\none\ntwo\nthree\nfour\nfive\n";
+
+fn run_commands(harness: &mut EditorTestHarness, script: &str) {
+    harness.editor_mut().queue_startup_commands(script, false);
+    harness.editor_mut().run_startup_commands();
+    harness.render().unwrap();
+}
+
+/// The picker lists bookmarks most recently used first with their line,
+/// filters as you type and jumps to the one chosen
+#[test]
+fn test_bookmark_picker_lists_filters_and_jumps() {
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text(CONTENT).unwrap();
+
+    run_commands(&mut harness, "goto-line 5; bookmark-set later");
+    run_commands(&mut harness, "goto-line 2; bookmark-set first");
+    run_commands(&mut harness, "goto-line 1; bookmark-goto later");
+    assert_eq!(harness.cursor_position(), 19);
+
+    run_commands(&mut harness, "goto-line 1; open-bookmark-picker");
+    let screen = harness.screen_to_string();
+    let later = screen.find("later — ").expect("picker should list 'later'");
+    let first = screen.find("first — ").expect("picker should list 'first'");
+    assert!(later < first, "most recently used bookmark comes first");
+    harness.assert_screen_contains("test_buffer.txt:2 — one");
+    harness.assert_screen_contains("test_buffer.txt:5 — four");
+
+    harness.type_text("first").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("later — ");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), "zero\n".len());
+}
+
+/// Line marks follow the bookmarks under the gutter glyph and can be jumped to
+#[test]
+fn test_bookmark_picker_lists_line_marks() {
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text(CONTENT).unwrap();
+
+    run_commands(&mut harness, "goto-line 4");
+    harness
+        .send_key(KeyCode::F(2), KeyModifiers::CONTROL)
+        .unwrap();
+    run_commands(&mut harness, "goto-line 2; bookmark-set first");
+
+    run_commands(&mut harness, "goto-line 1; open-bookmark-picker");
+    let screen = harness.screen_to_string();
+    let first = screen.find("first — ").expect("picker should list 'first'");
+    let mark = screen
+        .find("◆ — ")
+        .expect("picker should list the line mark");
+    assert!(first < mark, "line marks follow the bookmarks");
+    harness.assert_screen_contains("test_buffer.txt:4 — three");
+
+    harness.type_text("three").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("first — ");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), "zero\none\ntwo\n".len());
+}
@@ -136,6 +136,42 @@ fn test_quit_with_confirmation_cancel() {
     );
 }
 
+/// Test that `fresh --wait` quits once the files it opened are closed, with
+/// a failing exit code when one was closed unsaved
+#[test]
+fn test_wait_quits_when_cli_files_are_closed() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let message = temp_dir.path().join("COMMIT_EDITMSG");
+    let notes = temp_dir.path().join("notes.txt");
+    std::fs::write(&message, "").unwrap();
+    std::fs::write(&notes, "notes\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    for path in [&message, &notes] {
+        harness
+            .editor_mut()
+            .queue_file_open(path.clone(), None, None, None, None, None, None);
+    }
+    harness.editor_mut().wait_for_queued_files();
+    harness.editor_mut().process_pending_file_opens();
+    harness.render().unwrap();
+
+    // Closing one of the files isn't enough
+    let notes_id = harness.editor().active_buffer();
+    harness.editor_mut().close_buffer(notes_id).unwrap();
+    harness.render().unwrap();
+    assert!(!harness.should_quit());
+    assert_eq!(harness.editor().cli_wait_exit_code(), Some(0));
+
+    harness.type_text("Fix the parser").unwrap();
+    assert_eq!(harness.editor().cli_wait_exit_code(), Some(1));
+    let message_id = harness.editor().active_buffer();
+    harness.editor_mut().force_close_buffer(message_id).unwrap();
+    harness.render().unwrap();
+    assert!(harness.should_quit());
+    assert_eq!(harness.editor().cli_wait_exit_code(), Some(1));
+}
+
 /// Test that undo restores non-dirty status when undoing all changes
 #[test]
 fn test_undo_restores_non_dirty_status() {
@@ -1,7 +1,7 @@
 //! Tests for buffer settings commands:
 //! - Set Tab Size
 //! - Toggle Indentation: Spaces ↔ Tabs
-//! - Toggle Tab Indicators
+//! - Toggle Tab Indicators (per split)
 //! - Toggle Line Numbers
 //! - Reset Buffer Settings
 
@@ -134,6 +134,31 @@ fn test_toggle_tab_indicators_command() {
     );
 }
 
+/// Test that toggling tab indicators only affects the active split
+#[test]
+fn test_toggle_tab_indicators_is_per_split() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.go");
+    std::fs::write(&file_path, "\thello").unwrap();
+
+    let config = Config::default();
+    let mut harness = EditorTestHarness::with_config(100, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Show the same buffer in a second split, then toggle indicators there
+    run_command(&mut harness, "Split Vertical");
+    run_command(&mut harness, "Toggle Tab Indicators");
+
+    let screen = harness.screen_to_string();
+    assert_eq!(
+        screen.matches('→').count(),
+        1,
+        "Only the active split should show the tab indicator. Screen:\n{}",
+        screen
+    );
+}
+
 /// Test that "Set Tab Size" command changes tab rendering width
 #[test]
 fn test_set_tab_size_command() {
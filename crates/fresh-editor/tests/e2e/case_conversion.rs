@@ -398,3 +398,102 @@ fn test_case_conversion_from_command_palette() {
         "Text should be converted to uppercase via command palette"
     );
 }
+
+/// Test toggling the case of the char under the cursor with Alt+` (vim `~`)
+#[test]
+fn test_toggle_case_and_advance() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("hEllo, wörld").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+
+    // Holding the key walks along the line; punctuation is stepped over
+    for _ in 0..9 {
+        harness
+            .send_key(KeyCode::Char('`'), KeyModifiers::ALT)
+            .unwrap();
+    }
+    harness.render().unwrap();
+
+    let buffer_content = harness.get_buffer_content().unwrap();
+    assert_eq!(buffer_content, "HeLLO, WÖrld");
+    assert_eq!(harness.cursor_position(), "HeLLO, WÖ".len());
+
+    // Undo reverts only the last toggle
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    let buffer_content = harness.get_buffer_content().unwrap();
+    assert_eq!(buffer_content, "HeLLO, Wörld");
+}
+
+/// Test that toggling case stops at the end of the line by default
+#[test]
+fn test_toggle_case_and_advance_stops_at_line_end() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("ab\ncd").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+
+    for _ in 0..4 {
+        harness
+            .send_key(KeyCode::Char('`'), KeyModifiers::ALT)
+            .unwrap();
+    }
+    harness.render().unwrap();
+
+    let buffer_content = harness.get_buffer_content().unwrap();
+    assert_eq!(buffer_content, "AB\ncd");
+    assert_eq!(harness.cursor_position(), 2);
+}
+
+/// Test that toggling case continues onto the next line when configured
+#[test]
+fn test_toggle_case_and_advance_wraps_lines() {
+    let mut config = fresh::config::Config::default();
+    config.editor.toggle_case_wraps_lines = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+
+    harness.type_text("ab\ncd").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+
+    for _ in 0..4 {
+        harness
+            .send_key(KeyCode::Char('`'), KeyModifiers::ALT)
+            .unwrap();
+    }
+    harness.render().unwrap();
+
+    let buffer_content = harness.get_buffer_content().unwrap();
+    assert_eq!(buffer_content, "AB\nCD");
+    assert_eq!(harness.cursor_position(), 5);
+}
+
+/// Test toggling case at every cursor
+#[test]
+fn test_toggle_case_and_advance_multi_cursor() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("abc\ndef").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        .unwrap();
+
+    for _ in 0..2 {
+        harness
+            .send_key(KeyCode::Char('`'), KeyModifiers::ALT)
+            .unwrap();
+    }
+    harness.render().unwrap();
+
+    let buffer_content = harness.get_buffer_content().unwrap();
+    assert_eq!(buffer_content, "ABc\nDEf");
+}
@@ -20,7 +20,7 @@ fn test_command_palette_trigger() {
     // Check that suggestions are visible (commands sorted alphabetically, so Add Cursor commands appear first)
     harness.assert_screen_contains("Add Cursor Above");
     harness.assert_screen_contains("Add Cursor Below");
-    harness.assert_screen_contains("Close Buffer");
+    harness.assert_screen_contains("Add Ruler");
 }
 
 /// Test command palette autocomplete filtering
@@ -1047,3 +1047,29 @@ fn test_command_palette_select_cursor_style() {
         .wait_for_screen_contains("Cursor style changed")
         .unwrap();
 }
+
+/// Diagnose Buffer Performance reports the buffer and names the long line
+/// as the suspected cause when long-line mode is not on
+#[test]
+fn test_diagnose_buffer_performance_names_long_line() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut config = fresh::config::Config::default();
+    config.editor.long_line_threshold = 100;
+    let mut harness = EditorTestHarness::with_config(100, 30, config).unwrap();
+
+    // Typed text does not switch on long-line mode (only loading a file does)
+    harness.type_text(&"x".repeat(300)).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Diagnose Buffer Performance").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("Performance:");
+    harness.assert_screen_contains("300-byte line");
+    harness.assert_screen_contains("no server running");
+}
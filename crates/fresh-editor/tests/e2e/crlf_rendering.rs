@@ -1,5 +1,6 @@
 use crate::common::harness::EditorTestHarness;
 use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::model::buffer::LineEnding;
 use tempfile::TempDir;
 
 /// Test rendering of buffer with CRLF line endings
@@ -776,3 +777,130 @@ fn test_set_line_ending_converts_on_save_crlf_to_lf() {
         "All line endings should be converted to LF"
     );
 }
+
+/// Test that setting the line ending rewrites the buffer's line breaks as a
+/// single undo step, keeping the cursor on its line
+#[test]
+fn test_set_line_ending_rewrites_buffer_as_one_undo_step() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("crlf_rewrite.txt");
+    std::fs::write(&file_path, "Line 1\r\nLine 2\r\nLine 3\r\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 16);
+
+    harness
+        .editor_mut()
+        .queue_startup_commands("set-line-ending lf", false);
+    harness.editor_mut().run_startup_commands();
+    harness.render().unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "Line 1\nLine 2\nLine 3\n"
+    );
+    assert_eq!(harness.cursor_position(), 14);
+    harness.assert_screen_contains("LF");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "Line 1\r\nLine 2\r\nLine 3\r\n"
+    );
+}
+
+/// Test that undoing a line ending conversion also restores the line ending
+/// the buffer is shown and saved with, and redo converts it again
+#[test]
+fn test_undo_set_line_ending_restores_line_ending() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("crlf_undo.txt");
+    std::fs::write(&file_path, "Line 1\r\nLine 2\r\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    harness
+        .editor_mut()
+        .queue_startup_commands("set-line-ending lf", false);
+    harness.editor_mut().run_startup_commands();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("CRLF");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.editor().active_state().buffer.line_ending(),
+        LineEnding::CRLF
+    );
+    harness.assert_screen_contains("CRLF");
+
+    harness
+        .send_key(KeyCode::Char('y'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "Line 1\nLine 2\n");
+    assert_eq!(
+        harness.editor().active_state().buffer.line_ending(),
+        LineEnding::LF
+    );
+
+    // Undo again, edit and save: the file keeps its CRLF line breaks
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("x").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| !h.editor().active_state().buffer.is_modified())
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "xLine 1\r\nLine 2\r\n"
+    );
+}
+
+/// Test that setting the line ending of a file without line breaks can
+/// still be undone
+#[test]
+fn test_undo_set_line_ending_without_line_breaks() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("one_line.txt");
+    std::fs::write(&file_path, "no breaks").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    let original = harness.editor().active_state().buffer.line_ending();
+    let target = if original == LineEnding::CRLF {
+        "lf"
+    } else {
+        "crlf"
+    };
+
+    harness
+        .editor_mut()
+        .queue_startup_commands(&format!("set-line-ending {}", target), false);
+    harness.editor_mut().run_startup_commands();
+    assert_ne!(
+        harness.editor().active_state().buffer.line_ending(),
+        original
+    );
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.editor().active_state().buffer.line_ending(),
+        original
+    );
+    assert!(!harness.editor().active_state().buffer.is_modified());
+}
@@ -0,0 +1,268 @@
+//! E2E tests for diff and merge sessions (`fresh --diff` / `fresh --merge`,
+//! and comparing open buffers).
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::view::overlay::{OverlayFace, OverlayNamespace};
+use tempfile::TempDir;
+
+fn next_split(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char(']'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Test navigating hunks and taking the left version into the right file
+#[test]
+fn test_diff_session_accept_left_and_save() {
+    let temp_dir = TempDir::new().unwrap();
+    let left = temp_dir.path().join("left.txt");
+    let right = temp_dir.path().join("right.txt");
+    std::fs::write(&left, "same\nold line\nsame again\n").unwrap();
+    std::fs::write(&right, "same\nnew line\nsame again\nextra\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness
+        .editor_mut()
+        .start_diff_session(&left, &right, None)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("old line");
+    harness.assert_screen_contains("new line");
+    assert_eq!(harness.editor().diff_session_exit_code(), Some(0));
+
+    // F7 jumps to the first change on the left side
+    harness.send_key(KeyCode::F(7), KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), "same\n".len());
+
+    // The left file is read-only; F6 copies its version into the right file
+    harness.send_key(KeyCode::F(6), KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "same\nold line\nsame again\n"
+    );
+    // Unsaved output means the session was aborted
+    assert_eq!(harness.editor().diff_session_exit_code(), Some(1));
+
+    next_split(&mut harness);
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "same\nold line\nsame again\nextra\n"
+    );
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&right).unwrap(),
+        "same\nold line\nsame again\nextra\n"
+    );
+    assert_eq!(harness.editor().diff_session_exit_code(), Some(0));
+}
+
+/// Byte ranges and theme keys of the changed-line highlights in the active buffer
+fn diff_highlights(harness: &EditorTestHarness) -> Vec<(std::ops::Range<usize>, String)> {
+    let namespace = OverlayNamespace::from_string("diff-mode".to_string());
+    let state = harness.editor().active_state();
+    let mut highlights: Vec<_> = state
+        .overlays
+        .all()
+        .iter()
+        .filter(|overlay| overlay.namespace.as_ref() == Some(&namespace))
+        .filter_map(|overlay| match &overlay.face {
+            OverlayFace::ThemedStyle {
+                bg_theme: Some(key),
+                ..
+            } => Some((overlay.range(&state.marker_list), key.clone())),
+            _ => None,
+        })
+        .collect();
+    highlights.sort_by_key(|(range, _)| range.start);
+    highlights
+}
+
+/// Test that changed lines are highlighted on both sides and follow accepted changes
+#[test]
+fn test_diff_session_highlights_changed_lines() {
+    let temp_dir = TempDir::new().unwrap();
+    let left = temp_dir.path().join("left.txt");
+    let right = temp_dir.path().join("right.txt");
+    std::fs::write(&left, "same\nold line\nsame again\n").unwrap();
+    std::fs::write(&right, "same\nnew line\nsame again\nextra\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness
+        .editor_mut()
+        .start_diff_session(&left, &right, None)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        diff_highlights(&harness),
+        vec![(5..13, "editor.diff_modify_bg".to_string())]
+    );
+
+    next_split(&mut harness);
+    assert_eq!(
+        diff_highlights(&harness),
+        vec![
+            (5..13, "editor.diff_modify_bg".to_string()),
+            (25..30, "editor.diff_add_bg".to_string()),
+        ]
+    );
+
+    // Taking the left version leaves only the added line highlighted
+    next_split(&mut harness);
+    harness.send_key(KeyCode::F(7), KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::F(6), KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert!(diff_highlights(&harness).is_empty());
+    next_split(&mut harness);
+    assert_eq!(
+        diff_highlights(&harness),
+        vec![(25..30, "editor.diff_add_bg".to_string())]
+    );
+}
+
+/// Test resolving a conflict in the merge output from the compared versions
+#[test]
+fn test_merge_session_accept_right_resolves_conflict() {
+    let temp_dir = TempDir::new().unwrap();
+    let local = temp_dir.path().join("file_LOCAL.txt");
+    let remote = temp_dir.path().join("file_REMOTE.txt");
+    let merged = temp_dir.path().join("file.txt");
+    std::fs::write(&local, "top\nours\nbottom\n").unwrap();
+    std::fs::write(&remote, "top\ntheirs\nbottom\n").unwrap();
+    std::fs::write(
+        &merged,
+        "top\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> branch\nbottom\n",
+    )
+    .unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness
+        .editor_mut()
+        .start_diff_session(&local, &remote, Some(&merged))
+        .unwrap();
+    harness.render().unwrap();
+    // Conflict markers left in the output mean the merge isn't resolved
+    assert_eq!(harness.editor().diff_session_exit_code(), Some(1));
+
+    harness.send_key(KeyCode::F(7), KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::F(6), KeyModifiers::SHIFT)
+        .unwrap();
+    harness.render().unwrap();
+
+    // Focus the merge output below the two versions and save it
+    next_split(&mut harness);
+    next_split(&mut harness);
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "top\ntheirs\nbottom\n"
+    );
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&merged).unwrap(),
+        "top\ntheirs\nbottom\n"
+    );
+    assert_eq!(harness.editor().diff_session_exit_code(), Some(0));
+}
+
+/// Test that git's `/dev/null` side of an added file compares as empty
+#[test]
+fn test_diff_session_against_null_device() {
+    let temp_dir = TempDir::new().unwrap();
+    let added = temp_dir.path().join("added.txt");
+    std::fs::write(&added, "first\nsecond\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness
+        .editor_mut()
+        .start_diff_session(std::path::Path::new("/dev/null"), &added, None)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "");
+    harness.assert_screen_contains("second");
+    harness.assert_screen_contains("Comparing files: 1 difference");
+    assert_eq!(harness.editor().diff_session_exit_code(), Some(0));
+}
+
+/// Test that the commands explain themselves outside of a diff session
+#[test]
+fn test_diff_commands_without_session() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.type_text("text").unwrap();
+    harness.send_key(KeyCode::F(6), KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Not in a diff session");
+    assert_eq!(harness.editor().diff_session_exit_code(), None);
+}
+
+/// Test comparing two open buffers, including unsaved changes, and reusing
+/// the two visible splits on the next comparison
+#[test]
+fn test_compare_open_buffers_uses_unsaved_content() {
+    let temp_dir = TempDir::new().unwrap();
+    let first = temp_dir.path().join("first.txt");
+    let second = temp_dir.path().join("second.txt");
+    std::fs::write(&first, "same\nline\nend\n").unwrap();
+    std::fs::write(&second, "same\nline\nend\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(140, 24).unwrap();
+    harness.open_file(&first).unwrap();
+    harness.open_file(&second).unwrap();
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("unsaved\n").unwrap();
+
+    harness.editor_mut().start_diff_buffers();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Compare buffer:");
+    harness.type_text("first.txt").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_screen_contains("first.txt with:");
+    harness.type_text("second.txt").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    // Buffers are named by path, so only check how the names end
+    let comparing = harness.editor().get_status_message().cloned().unwrap();
+    assert!(
+        comparing.starts_with("Comparing ")
+            && comparing.contains("first.txt with ")
+            && comparing.contains("second.txt: 1 difference(s)"),
+        "unexpected status: {}",
+        comparing
+    );
+    harness.assert_screen_contains("unsaved");
+    // Doesn't decide the exit code like --diff does
+    assert_eq!(harness.editor().diff_session_exit_code(), None);
+
+    // F7 jumps to where the unsaved lines were added
+    harness.send_key(KeyCode::F(7), KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), "same\nline\nend\n".len());
+
+    // With the two buffers visible, comparing again doesn't prompt
+    harness.editor_mut().start_diff_buffers();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("Compare buffer:");
+    assert_eq!(harness.editor().get_status_message(), Some(&comparing));
+}
+
+/// Test that comparing needs two open buffers
+#[test]
+fn test_compare_open_buffers_needs_two_buffers() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.editor_mut().start_diff_buffers();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Open at least two buffers to compare");
+}
@@ -2228,3 +2228,31 @@ fn test_reload_with_encoding_menu_item() {
     // Verify the menu item is present
     harness.assert_screen_contains("Reload with Encoding...");
 }
+
+/// Reloading with another encoding re-decodes the file and drops the undo
+/// history, whose byte offsets belong to the old decoding
+#[test]
+fn test_reload_with_encoding_clears_undo_history() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let file_path = harness.project_dir().unwrap().join("reload_undo.txt");
+    std::fs::write(&file_path, [0x63, 0x61, 0x66, 0xE9, 0x0A]).unwrap(); // "café\n" in Latin-1
+
+    harness.open_file(&file_path).unwrap();
+    harness.type_text("x").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("xcafé\n");
+
+    harness
+        .editor_mut()
+        .queue_startup_commands("reload-with-encoding iso-8859-1", false);
+    harness.editor_mut().run_startup_commands();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Reloaded with Latin-1");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("xcafé\n");
+}
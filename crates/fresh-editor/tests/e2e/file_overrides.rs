@@ -0,0 +1,174 @@
+//! E2E tests for remembered per-file overrides (language, read-only, ...)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config_io::DirectoryContext;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Helper: open command palette, type a command name, and press Enter.
+fn run_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Helper: start a new editor session in `project` that shares `dir_context`
+/// and open `file` in it.
+fn open_in_new_session(
+    project: &Path,
+    dir_context: &DirectoryContext,
+    file: &Path,
+) -> EditorTestHarness {
+    let mut harness = EditorTestHarness::with_shared_dir_context(
+        100,
+        24,
+        Default::default(),
+        project.to_path_buf(),
+        dir_context.clone(),
+    )
+    .unwrap();
+    harness.open_file(file).unwrap();
+    harness.render().unwrap();
+    harness
+}
+
+/// Test that language and read-only choices survive a restart until cleared
+#[test]
+fn test_overrides_restored_on_reopen_until_cleared() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir(&project).unwrap();
+    let project = project.canonicalize().unwrap();
+    let file = project.join("main.rs");
+    std::fs::write(&file, "fn main() {}\n").unwrap();
+    let dir_context = DirectoryContext::for_testing(&temp_dir.path().join("context"));
+
+    // Session 1: pick a language and make the file read-only
+    let detected_language = {
+        let mut harness = open_in_new_session(&project, &dir_context, &file);
+        let detected = harness.editor().active_state().language.clone();
+
+        run_command(&mut harness, "Set Language");
+        harness.wait_for_prompt().unwrap();
+        harness.type_text("Plain Text").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        run_command(&mut harness, "Toggle Read-Only Mode");
+        detected
+    };
+    assert_ne!(detected_language, "text");
+
+    // Session 2: both choices are re-applied after detection
+    {
+        let mut harness = open_in_new_session(&project, &dir_context, &file);
+        assert_eq!(harness.editor().active_state().language, "text");
+        harness.type_text("x").unwrap();
+        assert_eq!(harness.get_buffer_content().unwrap(), "fn main() {}\n");
+
+        run_command(&mut harness, "Clear Overrides for This File");
+        harness.assert_screen_contains("Cleared remembered overrides");
+    }
+
+    // Session 3: back to auto-detection
+    {
+        let mut harness = open_in_new_session(&project, &dir_context, &file);
+        assert_eq!(harness.editor().active_state().language, detected_language);
+        harness.type_text("x").unwrap();
+        assert_eq!(harness.get_buffer_content().unwrap(), "xfn main() {}\n");
+    }
+}
+
+/// Test that --readonly opens files read-only, refuses to save them, and
+/// that making one writable isn't remembered for later sessions
+#[test]
+fn test_readonly_session_blocks_saves_until_toggled() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir(&project).unwrap();
+    let project = project.canonicalize().unwrap();
+    let file = project.join("nginx.conf");
+    std::fs::write(&file, "worker_processes 1;\n").unwrap();
+    let dir_context = DirectoryContext::for_testing(&temp_dir.path().join("context"));
+
+    {
+        let mut harness = EditorTestHarness::with_shared_dir_context(
+            100,
+            24,
+            Default::default(),
+            project.clone(),
+            dir_context.clone(),
+        )
+        .unwrap();
+        harness.editor_mut().set_open_read_only(true);
+        harness.open_file(&file).unwrap();
+        harness.render().unwrap();
+        harness.assert_screen_contains("[RO]");
+
+        harness.type_text("x").unwrap();
+        assert_eq!(
+            harness.get_buffer_content().unwrap(),
+            "worker_processes 1;\n"
+        );
+        harness
+            .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.render().unwrap();
+        harness.assert_screen_contains("Buffer is read-only");
+
+        run_command(&mut harness, "Toggle Read-Only Mode");
+        harness.type_text("x").unwrap();
+        harness
+            .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.render().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "xworker_processes 1;\n"
+        );
+    }
+
+    // Without --readonly the file opens writable again
+    let mut harness = open_in_new_session(&project, &dir_context, &file);
+    harness.type_text("y").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "yxworker_processes 1;\n"
+    );
+}
+
+/// Test that overrides for deleted files are pruned when the store is loaded
+#[test]
+fn test_overrides_for_deleted_files_are_pruned() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir(&project).unwrap();
+    let project = project.canonicalize().unwrap();
+    let file = project.join("notes.txt");
+    std::fs::write(&file, "notes\n").unwrap();
+    let dir_context = DirectoryContext::for_testing(&temp_dir.path().join("context"));
+    let store_path = dir_context.file_overrides_path(&project);
+
+    {
+        let mut harness = open_in_new_session(&project, &dir_context, &file);
+        run_command(&mut harness, "Toggle Read-Only Mode");
+    }
+    assert!(store_path.exists());
+
+    std::fs::remove_file(&file).unwrap();
+    let _harness = EditorTestHarness::with_shared_dir_context(
+        100,
+        24,
+        Default::default(),
+        project.clone(),
+        dir_context.clone(),
+    )
+    .unwrap();
+    assert!(!store_path.exists());
+}
@@ -1503,3 +1503,42 @@ fn test_fold_unfold_at_end_of_large_file_gutter_click() {
     harness.assert_screen_contains("b_body_1");
     harness.assert_screen_contains("b_body_3");
 }
+
+/// `editor.fold_indicators` controls which arrows are drawn and clickable
+#[test]
+fn test_fold_indicators_config() {
+    use fresh::config::{Config, FoldIndicators};
+
+    let content: String = (0..10).map(|i| format!("line {i}\n")).collect();
+    let fixture = TestFixture::new("fold_indicators.py", &content).unwrap();
+    let header_row = (layout::CONTENT_START_ROW + 2) as u16;
+
+    let harness_with = |mode| {
+        let mut config = Config::default();
+        config.editor.fold_indicators = mode;
+        let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+        harness.open_file(&fixture.path).unwrap();
+        set_fold_range(&mut harness, 2, 5);
+        harness.render().unwrap();
+        harness
+    };
+
+    // "collapsed": no arrow on an expanded block, one after folding it
+    let mut harness = harness_with(FoldIndicators::Collapsed);
+    assert!(!harness.get_row_text(header_row).starts_with('▾'));
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, 2)
+        .unwrap();
+    harness.editor_mut().toggle_fold_at_cursor();
+    harness.render().unwrap();
+    assert!(harness.get_row_text(header_row).starts_with('▸'));
+    harness.assert_screen_not_contains("line 3");
+    harness.mouse_click(0, header_row).unwrap();
+    harness.assert_screen_contains("line 3");
+
+    // "never": no arrows, and clicking the gutter doesn't fold
+    let mut harness = harness_with(FoldIndicators::Never);
+    assert!(!harness.get_row_text(header_row).starts_with('▾'));
+    harness.mouse_click(0, header_row).unwrap();
+    harness.assert_screen_contains("line 3");
+}
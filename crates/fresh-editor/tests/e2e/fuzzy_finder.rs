@@ -0,0 +1,73 @@
+//! E2E tests for the fuzzy file finder
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crate::e2e::remote_fs_test::create_test_filesystem;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Run editor ticks until `condition` holds, giving the walk and the query
+/// debounce time to finish
+fn tick_until(harness: &mut EditorTestHarness, condition: impl Fn(&EditorTestHarness) -> bool) {
+    for _ in 0..200 {
+        harness.tick_and_render().unwrap();
+        if condition(harness) {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+        harness.advance_time(Duration::from_millis(100));
+    }
+    panic!("condition not met; screen:\n{}", harness.screen_to_string());
+}
+
+/// The finder lists files outside .gitignore, narrows them as you type and
+/// opens the one chosen
+#[test]
+fn test_fuzzy_finder_skips_ignored_files_and_opens_match() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project = harness.project_dir().unwrap();
+    std::fs::create_dir_all(project.join("src")).unwrap();
+    std::fs::create_dir_all(project.join("build")).unwrap();
+    std::fs::write(project.join(".gitignore"), "build/\n").unwrap();
+    std::fs::write(project.join("src").join("main.rs"), "fn main() {}").unwrap();
+    std::fs::write(project.join("src").join("util.rs"), "pub fn util() {}").unwrap();
+    std::fs::write(project.join("build").join("artifact.rs"), "").unwrap();
+
+    harness
+        .editor_mut()
+        .queue_startup_commands("fuzzy-find-file", false);
+    harness.editor_mut().run_startup_commands();
+    tick_until(&mut harness, |h| h.screen_to_string().contains("util.rs"));
+    harness.assert_screen_contains("main.rs");
+    harness.assert_screen_not_contains("artifact.rs");
+
+    harness.type_text("main").unwrap();
+    tick_until(&mut harness, |h| !h.screen_to_string().contains("util.rs"));
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "fn main() {}");
+}
+
+/// The walk reads the local disk, so the finder doesn't open on a remote
+/// host
+#[test]
+fn test_fuzzy_finder_unavailable_on_remote_host() {
+    let Some((fs, _temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let mut harness =
+        EditorTestHarness::create(100, 24, HarnessOptions::new().with_filesystem(Arc::new(fs)))
+            .unwrap();
+
+    harness
+        .editor_mut()
+        .queue_startup_commands("fuzzy-find-file", false);
+    harness.editor_mut().run_startup_commands();
+    assert!(!harness.editor().is_prompting());
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("The fuzzy finder isn't available on a remote host")
+    );
+}
@@ -0,0 +1,94 @@
+//! E2E tests for reloading grammars without restarting
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use std::fs;
+use std::time::Duration;
+
+const TEST_GRAMMAR: &str = r#"%YAML 1.2
+---
+name: TestLang
+scope: source.testlang
+file_extensions: [tl]
+
+contexts:
+  main:
+    - match: \b(fn|let)\b
+      scope: keyword.control.testlang
+"#;
+
+/// Harness with its own config directory and an open `.tl` file
+fn harness_with_tl_file(temp: &tempfile::TempDir) -> (EditorTestHarness, DirectoryContext) {
+    let dir_context = DirectoryContext::for_testing(temp.path());
+    fs::create_dir_all(&dir_context.config_dir).unwrap();
+    let project = temp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    let file = project.join("main.tl");
+    fs::write(&file, "fn main() {\n    let x = 1;\n}\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_shared_dir_context(
+        80,
+        24,
+        Config::default(),
+        project,
+        dir_context.clone(),
+    )
+    .unwrap();
+    harness.open_file(&file).unwrap();
+    assert_ne!(harness.editor().active_state().display_name, "TestLang");
+    (harness, dir_context)
+}
+
+fn install_grammar(dir_context: &DirectoryContext) {
+    let grammar_dir = dir_context.config_dir.join("grammars").join("testlang");
+    fs::create_dir_all(&grammar_dir).unwrap();
+    fs::write(grammar_dir.join("testlang.sublime-syntax"), TEST_GRAMMAR).unwrap();
+}
+
+/// Reload Grammars picks up a grammar installed after startup and
+/// re-detects the language of open buffers
+#[test]
+fn test_reload_grammars_command_applies_new_grammar() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let (mut harness, dir_context) = harness_with_tl_file(&temp);
+    install_grammar(&dir_context);
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Reload Grammars").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    // The startup grammar build may still be running, in which case the
+    // reload is queued behind it; wait for the reload itself to finish
+    harness
+        .wait_until(|h| {
+            h.editor()
+                .get_status_message()
+                .is_some_and(|msg| msg.starts_with("Grammars reloaded"))
+        })
+        .unwrap();
+    assert_eq!(harness.editor().active_state().display_name, "TestLang");
+}
+
+/// Changes to the grammar directories are noticed by polling, without
+/// running the command
+#[test]
+fn test_grammar_directory_changes_reload_automatically() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let (mut harness, dir_context) = harness_with_tl_file(&temp);
+
+    // First poll records the (empty) baseline
+    harness.advance_time(Duration::from_secs(3));
+    harness.process_async_and_render().unwrap();
+
+    install_grammar(&dir_context);
+    harness.advance_time(Duration::from_secs(3));
+    harness
+        .wait_until(|h| h.editor().active_state().display_name == "TestLang")
+        .unwrap();
+}
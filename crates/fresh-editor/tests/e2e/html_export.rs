@@ -0,0 +1,75 @@
+//! E2E tests for exporting buffers as syntax-highlighted HTML.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Run "Export Buffer as HTML" from the command palette and accept the
+/// suggested destination
+fn export_with_default_path(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("export buffer as html").unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+}
+
+/// Test exporting a whole buffer next to its file
+#[test]
+fn test_export_buffer_as_html() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("main.rs");
+    std::fs::write(&file_path, "fn main() {\n    let x = 1 < 2;\n}\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    export_with_default_path(&mut harness);
+
+    let html = std::fs::read_to_string(temp_dir.path().join("main.rs.html")).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>\n<!-- Exported from Fresh: main.rs at "));
+    assert!(html.contains("<title>main.rs</title>"));
+    // The numbers are highlighted, so the escaped `<` sits between spans
+    assert!(html.contains("</span> &lt; <span"));
+    assert!(!html.contains("1 < 2"));
+    assert!(html.contains("main"));
+    assert!(html.trim_end().ends_with("</html>"));
+    harness.assert_screen_contains("Exported HTML");
+}
+
+/// Test that only the selection is exported when there is one, with line
+/// numbers continuing from the buffer
+#[test]
+fn test_export_selection_as_html_with_line_numbers() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.txt");
+    std::fs::write(&file_path, "first\nsecond\nthird\n").unwrap();
+
+    let mut config = fresh::config::Config::default();
+    config.editor.export_html_line_numbers = true;
+    let mut harness = EditorTestHarness::with_config(100, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Select the second line
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::SHIFT).unwrap();
+    harness.render().unwrap();
+
+    export_with_default_path(&mut harness);
+
+    let html = std::fs::read_to_string(temp_dir.path().join("notes.txt.html")).unwrap();
+    assert!(html.contains("<span class=\"ln\">2 </span>second"));
+    assert!(!html.contains("first"));
+    assert!(!html.contains("third"));
+}
@@ -0,0 +1,129 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn increment(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('='), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn decrement(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Test incrementing the number after the cursor with Alt+= (vim Ctrl+A)
+#[test]
+fn test_increment_number_after_cursor() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("let x = 41;").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+
+    increment(&mut harness);
+    assert_eq!(harness.get_buffer_content().unwrap(), "let x = 42;");
+    // The cursor lands on the last digit, like in Vim
+    assert_eq!(harness.cursor_position(), "let x = 4".len());
+
+    decrement(&mut harness);
+    decrement(&mut harness);
+    assert_eq!(harness.get_buffer_content().unwrap(), "let x = 40;");
+
+    // Each change is a single undo step
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "let x = 41;");
+}
+
+/// Test that hex numbers keep their prefix and letter case
+#[test]
+fn test_increment_hex_number() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("mask = 0x0F, color = 0xfe").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+
+    increment(&mut harness);
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "mask = 0x10, color = 0xfe"
+    );
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    increment(&mut harness);
+    increment(&mut harness);
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "mask = 0x10, color = 0x100"
+    );
+}
+
+/// Test that zero-padded numbers keep their width
+#[test]
+fn test_increment_zero_padded_number() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("frame_009.png").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+
+    increment(&mut harness);
+    assert_eq!(harness.get_buffer_content().unwrap(), "frame_010.png");
+
+    for _ in 0..3 {
+        decrement(&mut harness);
+    }
+    assert_eq!(harness.get_buffer_content().unwrap(), "frame_007.png");
+}
+
+/// Test incrementing at every cursor, then as a sequence
+#[test]
+fn test_increment_number_multi_cursor() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("item 0\nitem 0\nitem 0").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    for _ in 0..2 {
+        harness
+            .send_key(KeyCode::Down, KeyModifiers::CONTROL | KeyModifiers::ALT)
+            .unwrap();
+    }
+
+    increment(&mut harness);
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "item 1\nitem 1\nitem 1"
+    );
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("increment numbers sequentially").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "item 2\nitem 3\nitem 4"
+    );
+
+    // The whole multi-cursor change undoes at once
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "item 1\nitem 1\nitem 1"
+    );
+}
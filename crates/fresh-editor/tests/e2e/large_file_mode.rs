@@ -1765,3 +1765,69 @@ fn test_large_file_gutter_indicators_viewport_filtering() {
         screen_back_eof
     );
 }
+
+/// Write a large file of 100k fixed-width lines ("Line 000000 content\n")
+fn write_numbered_large_file(dir: &std::path::Path) -> std::path::PathBuf {
+    let file_path = dir.join("numbered.txt");
+    let mut content = String::new();
+    for i in 0..100_000 {
+        content.push_str(&format!("Line {:06} content\n", i));
+    }
+    std::fs::write(&file_path, &content).unwrap();
+    file_path
+}
+
+/// With `editor.large_file_line_scan`, opening a large file counts its lines
+/// in the background and the gutter switches from byte offsets to line numbers
+#[test]
+fn test_large_file_line_scan_on_open() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let file_path = write_numbered_large_file(temp_dir.path());
+
+    let mut config = fresh::config::Config::default();
+    config.editor.large_file_line_scan = true;
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        80,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Byte 0");
+
+    while harness.editor_mut().process_line_scan() {}
+    harness.render().unwrap();
+    harness.assert_screen_contains("Ln 1");
+    harness.assert_screen_not_contains("Byte 0");
+
+    // Go to Line no longer asks to scan
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_screen_not_contains("Scan file");
+}
+
+/// Jumping to a line of an unscanned large file (e.g. `file:line` on the
+/// command line) counts lines first, then lands on the exact position
+/// instead of an estimate
+#[test]
+fn test_goto_line_col_in_unscanned_large_file() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let file_path = write_numbered_large_file(temp_dir.path());
+    let line_len = "Line 000000 content\n".len();
+
+    let mut harness =
+        EditorTestHarness::with_working_dir(80, 24, temp_dir.path().to_path_buf()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness.editor_mut().goto_line_col(50_000, Some(6));
+    assert_eq!(harness.cursor_position(), 0);
+
+    while harness.editor_mut().process_line_scan() {}
+    assert_eq!(harness.cursor_position(), 49_999 * line_len + 5);
+    harness.render().unwrap();
+    harness.assert_screen_contains("Line 049999 content");
+}
@@ -0,0 +1,110 @@
+//! E2E tests for anonymous line marks (gutter glyphs cycled with F2 keys)
+
+use crate::common::harness::{layout, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+const CONTENT: &str = "zero\none\ntwo\nthree\nfour\nfive\n";
+
+fn run_palette_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn go_to_line(harness: &mut EditorTestHarness, line: usize) {
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, line)
+        .unwrap();
+}
+
+fn content_row(line: usize) -> u16 {
+    (layout::CONTENT_START_ROW + line) as u16
+}
+
+/// Ctrl+F2 marks lines, Alt+F2 / Shift+F2 cycle through them with wrapping
+#[test]
+fn test_toggle_and_cycle_line_marks() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text(CONTENT).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("◆");
+
+    go_to_line(&mut harness, 2);
+    harness
+        .send_key(KeyCode::F(2), KeyModifiers::CONTROL)
+        .unwrap();
+    go_to_line(&mut harness, 4);
+    harness
+        .send_key(KeyCode::F(2), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert!(harness.get_row_text(content_row(2)).starts_with('◆'));
+    assert!(harness.get_row_text(content_row(4)).starts_with('◆'));
+    assert!(!harness.get_row_text(content_row(3)).starts_with('◆'));
+
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    let next = |harness: &mut EditorTestHarness| {
+        harness.send_key(KeyCode::F(2), KeyModifiers::ALT).unwrap();
+        harness.cursor_position()
+    };
+    assert_eq!(next(&mut harness), 9, "first mark is on 'two'");
+    assert_eq!(next(&mut harness), 19, "second mark is on 'four'");
+    assert_eq!(next(&mut harness), 9, "cycling wraps to the first mark");
+
+    harness
+        .send_key(KeyCode::F(2), KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), 19, "previous wraps to the last");
+
+    // Toggling again on a marked line removes its mark
+    harness
+        .send_key(KeyCode::F(2), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert!(!harness.get_row_text(content_row(4)).starts_with('◆'));
+    assert_eq!(next(&mut harness), 9);
+    assert_eq!(next(&mut harness), 9, "only one mark is left");
+}
+
+/// Clicking the indicator column toggles a mark that then follows edits, and
+/// Clear All Marks in Buffer removes every mark
+#[test]
+fn test_gutter_click_marks_follow_edits_and_clear() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text(CONTENT).unwrap();
+    harness.render().unwrap();
+
+    harness.mouse_click(0, content_row(3)).unwrap();
+    harness.render().unwrap();
+    assert!(harness.get_row_text(content_row(3)).starts_with('◆'));
+    assert_eq!(
+        harness.cursor_position(),
+        0,
+        "gutter click doesn't move the cursor"
+    );
+
+    // Insert a line above the mark: it moves down with 'three'
+    harness.type_text("new\n").unwrap();
+    harness.render().unwrap();
+    assert!(harness.get_row_text(content_row(4)).starts_with('◆'));
+    assert!(harness.get_row_text(content_row(4)).contains("three"));
+
+    run_palette_command(&mut harness, "Clear All Marks in Buffer");
+    harness.assert_screen_not_contains("◆");
+    harness.send_key(KeyCode::F(2), KeyModifiers::ALT).unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("No line marks in this buffer")
+    );
+}
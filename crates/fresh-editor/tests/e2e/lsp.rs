@@ -8072,3 +8072,70 @@ log("STOPPED")
 
     Ok(())
 }
+
+/// Peek Definition shows the target in a popup without moving the cursor;
+/// Enter then jumps there
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "FakeLspServer uses a Bash script which is not available on Windows"
+)]
+fn test_lsp_peek_definition() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn()?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    let mut content = String::from("fn peek_target() {}\n");
+    for i in 0..40 {
+        content.push_str(&format!("let value_{i} = {i};\n"));
+    }
+    std::fs::write(&test_file, &content)?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::script_path().to_string_lossy().to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+        },
+    );
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&test_file)?;
+    harness.send_key(KeyCode::End, KeyModifiers::CONTROL)?;
+    harness.render()?;
+    let cursor_before = harness.cursor_position();
+    assert!(
+        !harness.screen_to_string().contains("peek_target"),
+        "the definition line should be scrolled out of view"
+    );
+
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.wait_for_prompt()?;
+    harness.type_text("Peek Definition")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+
+    // The definition is shown in the peek popup, the cursor stays put
+    harness.wait_until(|h| h.screen_to_string().contains("fn peek_target"))?;
+    harness.assert_screen_contains("test.rs:1");
+    assert_eq!(harness.cursor_position(), cursor_before);
+
+    // Enter promotes the peek to a real jump and closes the popup
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.render()?;
+    assert_eq!(harness.cursor_position(), 0);
+    assert!(!harness.editor().active_state().popups.is_visible());
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+//! E2E tests for downloading missing language servers from `lsp_downloads`
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, LspDownload};
+use fresh::config_io::DirectoryContext;
+use fresh::services::lsp::download;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+
+/// Command that is never on PATH
+const MISSING_COMMAND: &str = "fresh-test-missing-language-server";
+
+/// Serve `body` once on a local port, returning its URL
+fn serve_once(body: Vec<u8>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0u8; 4096];
+        let _ = stream.read(&mut request);
+        let header = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        );
+        stream.write_all(header.as_bytes()).unwrap();
+        stream.write_all(&body).unwrap();
+    });
+    format!("http://{}/server", addr)
+}
+
+/// Rust configured with a server command that isn't installed, downloadable
+/// from `download`
+fn config_with_download(download: LspDownload) -> Config {
+    let mut config = Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: MISSING_COMMAND.to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+        },
+    );
+    config.lsp_downloads.insert("rust".to_string(), download);
+    config
+}
+
+fn open_rust_file(
+    config: Config,
+    project: &Path,
+    dir_context: DirectoryContext,
+) -> anyhow::Result<EditorTestHarness> {
+    let file = project.join("main.rs");
+    std::fs::write(&file, "fn main() {}\n")?;
+    let mut harness = EditorTestHarness::with_shared_dir_context(
+        120,
+        30,
+        config,
+        project.to_path_buf(),
+        dir_context,
+    )?;
+    harness.open_file(&file)?;
+    harness.render()?;
+    Ok(harness)
+}
+
+/// A missing server is downloaded after confirming, verified, installed
+/// under the data directory and started
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "FakeLspServer uses a Bash script which is not available on Windows"
+)]
+fn test_confirmed_download_installs_and_starts_server() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn()?;
+    let script = std::fs::read(FakeLspServer::script_path())?;
+    let temp = tempfile::tempdir()?;
+    let dir_context = DirectoryContext::for_testing(temp.path());
+    let download = LspDownload {
+        url: serve_once(script.clone()),
+        sha256: download::sha256_hex(&script),
+    };
+
+    let mut harness = open_rust_file(
+        config_with_download(download),
+        temp.path(),
+        dir_context.clone(),
+    )?;
+    harness.wait_for_screen_contains("Download it from 127.0.0.1")?;
+    harness.type_text("y")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+
+    harness.wait_until(|h| h.editor().is_lsp_server_ready("rust"))?;
+    let installed = download::binary_path(&dir_context.data_dir, "rust", MISSING_COMMAND);
+    assert_eq!(std::fs::read(&installed)?, script);
+    Ok(())
+}
+
+/// A server installed in an earlier session is used without asking
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "FakeLspServer uses a Bash script which is not available on Windows"
+)]
+fn test_previously_downloaded_server_is_reused() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn()?;
+    let script = std::fs::read(FakeLspServer::script_path())?;
+    let temp = tempfile::tempdir()?;
+    let dir_context = DirectoryContext::for_testing(temp.path());
+    // Nothing listens here: a download attempt would fail
+    let download = LspDownload {
+        url: "http://127.0.0.1:9/server".to_string(),
+        sha256: download::sha256_hex(&script),
+    };
+    download::install(
+        &dir_context.data_dir,
+        "rust",
+        MISSING_COMMAND,
+        &download,
+        &script,
+    )
+    .map_err(anyhow::Error::msg)?;
+
+    let mut harness = open_rust_file(config_with_download(download), temp.path(), dir_context)?;
+    harness.wait_until(|h| h.editor().is_lsp_server_ready("rust"))?;
+    assert!(!harness.screen_to_string().contains("Download it from"));
+    Ok(())
+}
+
+/// A download whose checksum doesn't match is rejected and nothing is installed
+#[test]
+fn test_download_with_wrong_checksum_is_rejected() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let dir_context = DirectoryContext::for_testing(temp.path());
+    let download = LspDownload {
+        url: serve_once(b"#!/bin/sh\nexit 0\n".to_vec()),
+        sha256: download::sha256_hex(b"something else"),
+    };
+
+    let mut harness = open_rust_file(
+        config_with_download(download),
+        temp.path(),
+        dir_context.clone(),
+    )?;
+    harness.wait_for_screen_contains("Download it from 127.0.0.1")?;
+    harness.type_text("y")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+
+    // The status message is longer than the status bar, so check it directly
+    harness.wait_until(|h| {
+        h.editor()
+            .get_status_message()
+            .is_some_and(|msg| msg.contains("checksum mismatch"))
+    })?;
+    assert!(!download::binary_path(&dir_context.data_dir, "rust", MISSING_COMMAND).exists());
+    Ok(())
+}
+
+/// Declining keeps the "not found" behavior, and no download is attempted
+#[test]
+fn test_declined_download_is_not_fetched() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let dir_context = DirectoryContext::for_testing(temp.path());
+    let download = LspDownload {
+        url: "http://127.0.0.1:9/server".to_string(),
+        sha256: download::sha256_hex(b"server"),
+    };
+
+    let mut harness = open_rust_file(
+        config_with_download(download),
+        temp.path(),
+        dir_context.clone(),
+    )?;
+    harness.wait_for_screen_contains("Download it from 127.0.0.1")?;
+    harness.type_text("n")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.render()?;
+
+    harness.assert_screen_contains("Not downloading the rust language server");
+    assert!(!download::install_dir(&dir_context.data_dir, "rust").exists());
+    Ok(())
+}
+
+/// With `allow_network_fetch` off, a missing server is reported as before
+/// and no download is offered
+#[test]
+fn test_no_download_offer_when_network_fetch_disabled() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let dir_context = DirectoryContext::for_testing(temp.path());
+    let mut config = config_with_download(LspDownload {
+        url: "http://127.0.0.1:9/server".to_string(),
+        sha256: download::sha256_hex(b"server"),
+    });
+    config.allow_network_fetch = false;
+
+    let mut harness = open_rust_file(config, temp.path(), dir_context)?;
+    // The rust plugin replaces the error's status message with its own hint
+    harness.wait_until(|h| h.editor().has_lsp_error())?;
+    assert!(!harness.editor().is_prompting());
+    assert!(!harness.screen_to_string().contains("Download it from"));
+    Ok(())
+}
@@ -0,0 +1,143 @@
+//! E2E tests for "Fix Next Problem".
+//!
+//! Uses a fake LSP server that reports one error and answers code action
+//! requests with a configurable list of actions, passed to it through the
+//! `env` field of `LspServerConfig`.
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+const REPLACE_FOO: &str = r#"{"title":"Replace foo","kind":"quickfix","edit":{"changes":{"__URI__":[{"range":{"start":{"line":0,"character":0},"end":{"line":0,"character":3}},"newText":"baz"}]}}}"#;
+
+/// Open a file with one problem, served by the code actions fake LSP, and
+/// run "Fix Next Problem" once its diagnostic has arrived.
+fn run_fix_next_problem(
+    actions: &str,
+) -> anyhow::Result<(EditorTestHarness, FakeLspServer, tempfile::TempDir)> {
+    let fake_server = FakeLspServer::spawn_with_code_actions()?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, "foo bar\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::code_actions_script_path()
+                .to_string_lossy()
+                .to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: std::collections::HashMap::from([(
+                "FRESH_TEST_CODE_ACTIONS".to_string(),
+                actions.to_string(),
+            )]),
+            language_id_overrides: Default::default(),
+        },
+    );
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+
+    harness.open_file(&test_file)?;
+    harness.render()?;
+
+    // Wait for the published error to show up in the status bar
+    harness.wait_until(|h| h.screen_to_string().contains("E:1"))?;
+
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.type_text("Fix Next Problem")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+
+    Ok((harness, fake_server, temp_dir))
+}
+
+/// A single quick fix is applied without showing the menu
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "FakeLspServer uses a Bash script which is not available on Windows"
+)]
+fn test_fix_next_problem_applies_single_fix() -> anyhow::Result<()> {
+    let (mut harness, _fake_server, _temp_dir) = run_fix_next_problem(&format!("[{REPLACE_FOO}]"))?;
+
+    harness.wait_until(|h| {
+        h.editor().get_status_message().map(String::as_str) == Some("Applied fix: Replace foo")
+    })?;
+
+    assert_eq!(harness.get_buffer_content().unwrap(), "baz bar\n");
+    assert!(!harness.editor().active_state().popups.is_visible());
+
+    Ok(())
+}
+
+/// Several quick fixes are offered in the menu instead of being applied
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "FakeLspServer uses a Bash script which is not available on Windows"
+)]
+fn test_fix_next_problem_shows_menu_for_multiple_fixes() -> anyhow::Result<()> {
+    let remove_foo = r#"{"title":"Remove foo","kind":"quickfix","edit":{"changes":{"__URI__":[{"range":{"start":{"line":0,"character":0},"end":{"line":0,"character":4}},"newText":""}]}}}"#;
+    // Refactorings are left out of the menu
+    let extract = r#"{"title":"Extract variable","kind":"refactor.extract"}"#;
+    let (mut harness, _fake_server, _temp_dir) =
+        run_fix_next_problem(&format!("[{REPLACE_FOO},{remove_foo},{extract}]"))?;
+
+    harness.wait_until(|h| h.editor().active_state().popups.is_visible())?;
+    harness.render()?;
+
+    harness.assert_screen_contains("Code Actions (2):");
+    harness.assert_screen_contains("Replace foo");
+    harness.assert_screen_contains("Remove foo");
+    harness.assert_screen_not_contains("Extract variable");
+    assert_eq!(harness.get_buffer_content().unwrap(), "foo bar\n");
+
+    Ok(())
+}
+
+/// A problem without quick fixes is reported in the status bar
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "FakeLspServer uses a Bash script which is not available on Windows"
+)]
+fn test_fix_next_problem_without_fixes() -> anyhow::Result<()> {
+    let (mut harness, _fake_server, _temp_dir) = run_fix_next_problem("[]")?;
+
+    harness.wait_until(|h| {
+        h.editor().get_status_message().map(String::as_str)
+            == Some("No quick fix available for this problem")
+    })?;
+
+    assert_eq!(harness.get_buffer_content().unwrap(), "foo bar\n");
+    assert!(!harness.editor().active_state().popups.is_visible());
+
+    Ok(())
+}
+
+/// A quick fix carrying both an edit and a command gets the edit applied
+/// first, then the command run on the server
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "FakeLspServer uses a Bash script which is not available on Windows"
+)]
+fn test_fix_next_problem_runs_command_after_edit() -> anyhow::Result<()> {
+    let fix = r#"{"title":"Replace foo and insert","kind":"quickfix","edit":{"changes":{"__URI__":[{"range":{"start":{"line":0,"character":0},"end":{"line":0,"character":3}},"newText":"baz"}]}},"command":{"title":"Insert","command":"fake.insertText"}}"#;
+    let (mut harness, _fake_server, _temp_dir) = run_fix_next_problem(&format!("[{fix}]"))?;
+
+    // The server answers the command with a workspace/applyEdit inserting "CMD "
+    harness.wait_until(|h| h.get_buffer_content().as_deref() == Some("CMD baz bar\n"))?;
+
+    Ok(())
+}
@@ -432,3 +432,73 @@ fn test_macro_playback_is_undoable() {
         screen_after_undo
     );
 }
+
+/// Test that a saved macro can be loaded into a register in a later session
+#[test]
+fn test_saved_macro_survives_restart() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use fresh::config_io::DirectoryContext;
+
+    fn run_command(harness: &mut EditorTestHarness, command_name: &str, input: &str) {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.wait_for_prompt().unwrap();
+        harness.type_text(command_name).unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.render().unwrap();
+        if !input.is_empty() {
+            harness.type_text(input).unwrap();
+            harness
+                .send_key(KeyCode::Enter, KeyModifiers::NONE)
+                .unwrap();
+            harness.render().unwrap();
+        }
+    }
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+    let new_session = || {
+        EditorTestHarness::with_shared_dir_context(
+            100,
+            24,
+            Default::default(),
+            temp_dir.path().to_path_buf(),
+            dir_context.clone(),
+        )
+        .unwrap()
+    };
+
+    // Session 1: record "xyz" into register 1 and save it
+    {
+        let mut harness = new_session();
+        harness.render().unwrap();
+        run_command(&mut harness, "Record Macro", "1");
+        harness.type_text("xyz").unwrap();
+        run_command(&mut harness, "Stop Recording", "");
+        run_command(&mut harness, "Save Macro", "1 type xyz");
+        harness.assert_screen_contains("saved as 'type xyz'");
+    }
+    let file = std::fs::read_to_string(dir_context.saved_macros_path()).unwrap();
+    assert!(file.contains("\"type xyz\""), "macros file: {}", file);
+
+    // Session 2: load it into register 2 and play it
+    let mut harness = new_session();
+    harness.render().unwrap();
+    run_command(&mut harness, "Load Macro", "type xyz 2");
+    harness.assert_screen_contains("Loaded macro 'type xyz'");
+    run_command(&mut harness, "Play Last Macro", "");
+    harness.assert_screen_contains("xyz");
+
+    // Unknown names are reported rather than loaded
+    run_command(&mut harness, "Load Macro", "missing 3");
+    harness.assert_screen_contains("No saved macro named 'missing'");
+
+    // The list shows each action as it is stored in the file
+    run_command(&mut harness, "List Saved Macros", "");
+    harness.assert_screen_contains("Macro 'type xyz'");
+    harness.assert_screen_contains(r#". {"InsertChar":"x"}"#);
+    harness.assert_screen_not_contains("InsertChar('x')");
+}
@@ -892,3 +892,52 @@ fn test_menu_arrow_navigation_skips_hidden_menus() {
     harness.assert_screen_not_contains("New Folder");
     harness.assert_screen_contains("Restart Server");
 }
+
+/// Test that Paste and Close Split are only enabled when they can act
+#[test]
+fn test_paste_and_close_split_enabled_by_state() {
+    use fresh::types::context_keys;
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness.editor_mut().set_clipboard_for_test(String::new());
+
+    // The clipboard is only inspected while a menu is open
+    harness
+        .send_key(KeyCode::Char('e'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    let context = harness.editor().menu_context();
+    assert!(!context.get(context_keys::CAN_PASTE));
+    assert!(!context.get(context_keys::HAS_MULTIPLE_SPLITS));
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+
+    harness
+        .editor_mut()
+        .set_clipboard_for_test("text".to_string());
+    harness.editor_mut().split_pane_vertical();
+    harness
+        .send_key(KeyCode::Char('e'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    let context = harness.editor().menu_context();
+    assert!(context.get(context_keys::CAN_PASTE));
+    assert!(context.get(context_keys::HAS_MULTIPLE_SPLITS));
+}
+
+/// Test that the File menu's Auto-Revert item shows a checkmark for the live state
+#[test]
+fn test_file_menu_auto_revert_checkbox() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("☑ Auto-Revert");
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+
+    harness.editor_mut().toggle_auto_revert();
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("☐ Auto-Revert");
+}
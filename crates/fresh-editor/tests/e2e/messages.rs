@@ -0,0 +1,106 @@
+//! E2E tests for the *Messages* transcript and command palette history
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn run_palette_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Status messages stay readable in *Messages* after being replaced,
+/// and `f` filters the transcript down to important ones
+#[test]
+fn test_messages_transcript_and_filter() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness
+        .editor_mut()
+        .set_status_message("First routine message".to_string());
+    harness
+        .editor_mut()
+        .set_important_status_message("Something went wrong".to_string());
+    harness
+        .editor_mut()
+        .set_status_message("Second routine message".to_string());
+
+    run_palette_command(&mut harness, "Show Messages");
+    harness.assert_screen_contains("*Messages*");
+    harness.assert_screen_contains("First routine message");
+    harness.assert_screen_contains("Something went wrong");
+    harness.assert_screen_contains("Second routine message");
+
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Something went wrong");
+    harness.assert_screen_not_contains("First routine message");
+    harness.assert_screen_not_contains("Second routine message");
+
+    // New messages show up while the transcript is open
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .editor_mut()
+        .set_status_message("Third routine message".to_string());
+    harness.render().unwrap();
+    harness.assert_screen_contains("Third routine message");
+}
+
+/// Failures reported by the editor itself are tagged important
+#[test]
+fn test_failed_revert_is_important() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let file_path = harness.project_dir().unwrap().join("gone.txt");
+    std::fs::write(&file_path, "content").unwrap();
+    harness.open_file(&file_path).unwrap();
+    std::fs::remove_file(&file_path).unwrap();
+
+    assert!(!harness.editor_mut().revert_file().unwrap());
+
+    run_palette_command(&mut harness, "Show Messages");
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("File does not exist");
+}
+
+/// Up in the command palette recalls the last executed command
+#[test]
+fn test_command_palette_recalls_executed_command() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+
+    run_palette_command(&mut harness, "Show Messages");
+    harness.assert_screen_contains("*Messages*");
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("*Messages*");
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().prompt_input(), Some(">Show Messages"));
+
+    // Down past the most recent entry restores the original input
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.editor().prompt_input(), Some(">"));
+
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("*Messages*");
+}
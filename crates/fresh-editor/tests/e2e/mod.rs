@@ -1,11 +1,13 @@
 pub mod altgr_shift;
 pub mod ansi_cursor;
+pub mod auto_commands;
 pub mod auto_indent;
 pub mod auto_revert;
 pub mod basic;
 pub mod binary_file;
 pub mod block_selection;
 pub mod blog_showcases;
+pub mod bookmark_picker;
 pub mod buffer_lifecycle;
 pub mod buffer_settings_commands;
 pub mod case_conversion;
@@ -15,6 +17,7 @@ pub mod crlf_rendering;
 pub mod csharp_language_coherence;
 pub mod ctrl_end_wrapped;
 pub mod cursor_style_rendering;
+pub mod diff_mode;
 pub mod document_model;
 pub mod duplicate_line;
 pub mod emacs_actions;
@@ -22,13 +25,18 @@ pub mod encoding;
 pub mod explorer_menu;
 pub mod file_browser;
 pub mod file_explorer;
+pub mod file_overrides;
 pub mod file_permissions;
 pub mod folding;
+pub mod fuzzy_finder;
 pub mod glob_language_detection;
+pub mod grammar_reload;
 #[cfg(feature = "gui")]
 pub mod gui;
 pub mod hanging_wrap_indent;
 pub mod horizontal_scrollbar;
+pub mod html_export;
+pub mod increment_number;
 pub mod indent_dedent;
 pub mod inline_diagnostics;
 pub mod issue_1147_wrapped_line_nav;
@@ -37,6 +45,7 @@ pub mod language_features_e2e;
 pub mod large_file_inplace_write_bug;
 pub mod large_file_mode;
 pub mod lifecycle;
+pub mod line_marks;
 pub mod line_wrap_scroll_bugs;
 pub mod line_wrapping;
 pub mod live_grep;
@@ -46,7 +55,9 @@ pub mod lsp_completion_french_locale;
 pub mod lsp_completion_popup_behavior;
 pub mod lsp_config;
 pub mod lsp_diagnostic_flow;
+pub mod lsp_download;
 pub mod lsp_env;
+pub mod lsp_fix_next_problem;
 pub mod lsp_no_config;
 pub mod lsp_order;
 pub mod lsp_publish_diagnostics_capability;
@@ -58,13 +69,16 @@ pub mod menu_bar;
 pub mod menu_cursor_bleed;
 pub mod menu_tab_color_bleed;
 pub mod merge_conflict;
+pub mod messages;
 pub mod mouse;
 pub mod movement;
 pub mod multi_file_opening;
 pub mod multibyte_characters;
 pub mod multicursor;
 pub mod on_save_actions;
+pub mod open_external;
 pub mod open_folder;
+pub mod open_url;
 pub mod paste;
 #[cfg(feature = "plugins")]
 pub mod plugins;
@@ -74,9 +88,14 @@ pub mod position_history;
 pub mod position_history_bugs;
 pub mod position_history_debug;
 pub mod position_history_truncate_debug;
+pub mod privacy_mode;
+pub mod project_replace;
+pub mod project_search;
 pub mod prompt;
 pub mod prompt_editing;
+pub mod recent_files;
 pub mod recovery;
+pub mod reflow_paragraph;
 pub mod remote_fs_test;
 pub mod rendering;
 pub mod save_as_language_detection;
@@ -101,6 +120,9 @@ pub mod split_tabs;
 pub mod split_view;
 pub mod split_view_expectations;
 pub mod split_view_markdown_compose;
+pub mod startup_commands;
+#[cfg(feature = "plugins")]
+pub mod startup_profile;
 pub mod stdin_input;
 pub mod sudo_save_prompt;
 #[cfg(unix)]
@@ -118,6 +140,7 @@ pub mod theme;
 pub mod toggle_bars;
 pub mod toggle_comment;
 pub mod triple_click;
+pub mod tutorial;
 pub mod undo_bulk_edit_after_save;
 pub mod undo_redo;
 pub mod unicode_cursor;
@@ -130,4 +153,6 @@ pub mod vi_mode;
 pub mod virtual_lines;
 pub mod visual_regression;
 pub mod warning_indicators;
+pub mod word_granularity;
 pub mod workspace;
+pub mod write_copy;
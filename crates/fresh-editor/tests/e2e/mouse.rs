@@ -2062,3 +2062,179 @@ fn test_hover_popup_position_with_file_explorer() {
         screen
     );
 }
+
+/// Test that the wheel scrolls by the configured number of lines per notch
+#[test]
+fn test_mouse_scroll_lines_configurable() {
+    let mut config = fresh::config::Config::default();
+    config.editor.mouse.scroll_lines = 5;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+
+    let content: String = (0..100).map(|i| format!("line {i}\n")).collect();
+    let _fixture = harness.load_buffer_from_text(&content).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.top_line_number(), 0);
+
+    harness.mouse_scroll_down(20, 10).unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(harness.top_line_number(), 5);
+    assert_eq!(
+        harness.cursor_position(),
+        0,
+        "Scrolling should not move the cursor"
+    );
+}
+
+/// Test that a burst of wheel ticks coalesced by the event loop scrolls as
+/// far as the individual ticks would have
+#[test]
+fn test_mouse_scroll_coalesced_ticks() {
+    use crossterm::event::{MouseEvent, MouseEventKind};
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let content: String = (0..100).map(|i| format!("line {i}\n")).collect();
+    let _fixture = harness.load_buffer_from_text(&content).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .editor_mut()
+        .handle_mouse_with_ticks(
+            MouseEvent {
+                kind: MouseEventKind::ScrollDown,
+                column: 20,
+                row: 10,
+                modifiers: KeyModifiers::NONE,
+            },
+            4,
+        )
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(harness.top_line_number(), 12);
+}
+
+/// Test that the wheel scrolls the split under the mouse when configured,
+/// leaving the focused split alone
+#[test]
+fn test_mouse_scroll_hovered_split() {
+    let mut config = fresh::config::Config::default();
+    config.editor.mouse.scroll_hovered_split = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+
+    let content: String = (0..100).map(|i| format!("line {i}\n")).collect();
+    let _fixture = harness.load_buffer_from_text(&content).unwrap();
+
+    // Create vertical split via command palette
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text("split vert").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    // The new (right) split has focus; scroll over the left one
+    harness.mouse_scroll_down(10, 10).unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(
+        harness.top_line_number(),
+        0,
+        "Focused split should not scroll"
+    );
+    // The first row of the right split (line 0) shows line 3 on the left
+    let screen = harness.screen_to_string();
+    let first_row = screen
+        .lines()
+        .find(|l| l.contains("line 0"))
+        .expect("right split should still show line 0");
+    assert!(
+        first_row
+            .find("line 3")
+            .is_some_and(|pos| Some(pos) < first_row.find("line 0")),
+        "Left split should have scrolled: {first_row}"
+    );
+}
+
+/// Test that the wheel can move the cursor instead of scrolling
+#[test]
+fn test_mouse_wheel_moves_cursor() {
+    let mut config = fresh::config::Config::default();
+    config.editor.mouse.scroll_lines = 2;
+    config.editor.mouse.wheel_moves_cursor = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+
+    let _fixture = harness.load_buffer_from_text("a\nb\nc\nd\ne\n").unwrap();
+    harness.render().unwrap();
+
+    harness.mouse_scroll_down(20, 10).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), 4);
+
+    harness.mouse_scroll_up(20, 10).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), 0);
+}
+
+/// Test that clicks on text can be ignored while the rest of the UI still works
+#[test]
+fn test_mouse_click_ignored_when_disabled() {
+    let mut config = fresh::config::Config::default();
+    config.editor.mouse.click_moves_cursor = false;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+
+    harness.type_text("First line\n").unwrap();
+    harness.type_text("Second line\n").unwrap();
+    harness.render().unwrap();
+    let buffer_len = harness.buffer_len();
+
+    harness.mouse_click(10, 2).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), buffer_len);
+
+    harness.mouse_drag(10, 2, 15, 3).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), buffer_len);
+    assert_eq!(harness.get_selected_text(), "");
+}
+
+/// Test that Alt+click releases mouse capture until the next key press
+#[test]
+fn test_alt_click_suspends_mouse_capture() {
+    use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("First line\n").unwrap();
+    harness.render().unwrap();
+    let buffer_len = harness.buffer_len();
+
+    for kind in [
+        MouseEventKind::Down(MouseButton::Left),
+        MouseEventKind::Up(MouseButton::Left),
+    ] {
+        harness
+            .send_mouse(MouseEvent {
+                kind,
+                column: 10,
+                row: 2,
+                modifiers: KeyModifiers::ALT,
+            })
+            .unwrap();
+    }
+    harness.render().unwrap();
+
+    // The click is left to the terminal and does not move the cursor
+    assert_eq!(harness.cursor_position(), buffer_len);
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Mouse released for terminal selection, press any key to resume")
+    );
+
+    // Typing resumes capture and edits as usual
+    harness.type_text("x").unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "First line\nx");
+}
@@ -339,3 +339,46 @@ fn test_open_files_from_different_directories() {
     harness.render().unwrap();
     harness.assert_buffer_content("fn main() {}");
 }
+
+/// Open a 300-line file at `file:200:3` and return how many lines sit above
+/// the target in the viewport, and the viewport height
+fn rows_above_opened_line(config: fresh::config::Config) -> (usize, usize) {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("long.txt");
+    let content: String = (1..=300).map(|i| format!("line {}\n", i)).collect();
+    std::fs::write(&file, content).unwrap();
+
+    let mut harness = EditorTestHarness::with_config(80, 30, config).unwrap();
+    harness
+        .editor_mut()
+        .queue_file_open(file, Some(200), Some(3), None, None, None, None);
+    harness.editor_mut().process_pending_file_opens();
+    harness.render().unwrap();
+    harness.assert_screen_contains("line 200");
+
+    let rows_above = 199 - harness.top_line_number();
+    (rows_above, harness.viewport_height())
+}
+
+/// Jumping to a line centers it by default; with `editor.center_on_goto`
+/// off it is only scrolled into view
+#[test]
+fn test_open_file_at_line_centers_it() {
+    let (rows_above, height) = rows_above_opened_line(fresh::config::Config::default());
+    assert!(
+        rows_above.abs_diff(height / 2) <= 1,
+        "line should be centered: {} rows above in a {}-row view",
+        rows_above,
+        height
+    );
+
+    let mut config = fresh::config::Config::default();
+    config.editor.center_on_goto = false;
+    let (rows_above, height) = rows_above_opened_line(config);
+    assert!(
+        rows_above > height / 2 + 2,
+        "line should only scroll into view: {} rows above in a {}-row view",
+        rows_above,
+        height
+    );
+}
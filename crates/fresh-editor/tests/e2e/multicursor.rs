@@ -2065,3 +2065,158 @@ fn test_multi_cursor_enter_markdown_bullet_list() {
         x_count, content_after
     );
 }
+
+/// Helper: run a command from the command palette
+fn run_palette_command(harness: &mut EditorTestHarness, command_name: &str) {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// "Clear Secondary Cursors" keeps the primary cursor, not the original one
+#[test]
+fn test_clear_secondary_cursors_keeps_primary() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    // Start at the beginning of "Line 3", not at the end of the buffer
+    harness.type_text("Line 1\nLine 2\nLine 3\n").unwrap();
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+
+    // The cursor added last (start of "Line 1") becomes primary
+    harness.editor_mut().add_cursor_above();
+    harness.editor_mut().add_cursor_above();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().active_cursors().count(), 3);
+    assert_eq!(harness.editor().active_cursors().primary().position, 0);
+    harness.assert_screen_contains("3 cursors");
+
+    run_palette_command(&mut harness, "Clear Secondary Cursors");
+
+    let cursors = harness.editor().active_cursors();
+    assert_eq!(cursors.count(), 1);
+    assert_eq!(cursors.primary().position, 0);
+    harness.assert_screen_not_contains("3 cursors");
+}
+
+/// Rotating the primary cursor walks the cursors in document order and wraps
+#[test]
+fn test_rotate_primary_cursor() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    // Start at the beginning of "Line 3", not at the end of the buffer
+    harness.type_text("Line 1\nLine 2\nLine 3\n").unwrap();
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    harness.editor_mut().add_cursor_above();
+    harness.editor_mut().add_cursor_above();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().active_cursors().primary().position, 0);
+
+    run_palette_command(&mut harness, "Rotate Primary Cursor Forward");
+    assert_eq!(harness.editor().active_cursors().primary().position, 7);
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Primary cursor 2 of 3")
+    );
+
+    run_palette_command(&mut harness, "Rotate Primary Cursor Backward");
+    run_palette_command(&mut harness, "Rotate Primary Cursor Backward");
+    assert_eq!(harness.editor().active_cursors().primary().position, 14);
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Primary cursor 3 of 3")
+    );
+
+    // Rotating doesn't add or remove cursors
+    assert_eq!(harness.editor().active_cursors().count(), 3);
+    harness.assert_buffer_content("Line 1\nLine 2\nLine 3\n");
+}
+
+fn sorted_cursor_positions(harness: &EditorTestHarness) -> Vec<usize> {
+    let mut positions: Vec<usize> = harness
+        .editor()
+        .active_cursors()
+        .iter()
+        .map(|(_, c)| c.position)
+        .collect();
+    positions.sort();
+    positions
+}
+
+/// Split Selection into Lines puts a cursor at the end of each selected line,
+/// and at the selection end on a partially selected last line
+#[test]
+fn test_split_selection_into_lines_partial_lines() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("alpha\nbeta\ngamma\ndelta").unwrap();
+
+    // Select from the middle of "alpha" to the middle of "gamma"
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 2)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::SHIFT, 2)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::SHIFT, 2)
+        .unwrap();
+    assert_eq!(harness.get_selection_range(), Some(2..15));
+
+    run_palette_command(&mut harness, "Split Selection into Lines");
+    assert_eq!(sorted_cursor_positions(&harness), vec![5, 10, 15]);
+    assert!(!harness.has_selection());
+
+    harness.type_text("!").unwrap();
+    harness.assert_buffer_content("alpha!\nbeta!\ngamm!a\ndelta");
+}
+
+/// A selection ending at the start of a line doesn't get a cursor on that line
+#[test]
+fn test_split_selection_into_lines_ending_at_line_start() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("alpha\nbeta\ngamma").unwrap();
+
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::SHIFT, 2)
+        .unwrap();
+    assert_eq!(harness.get_selection_range(), Some(0..11));
+
+    run_palette_command(&mut harness, "Split Selection into Lines");
+    assert_eq!(sorted_cursor_positions(&harness), vec![5, 10]);
+}
+
+/// Merge Cursors into Selection turns cursors on adjacent lines into one
+/// selection spanning them
+#[test]
+fn test_merge_cursors_into_selection() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("alpha\nbeta\ngamma").unwrap();
+
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness.editor_mut().add_cursor_below();
+    harness.editor_mut().add_cursor_below();
+    assert_eq!(harness.cursor_count(), 3);
+
+    run_palette_command(&mut harness, "Merge Cursors into Selection");
+    assert_eq!(harness.cursor_count(), 1);
+    assert_eq!(harness.get_selection_range(), Some(1..12));
+}
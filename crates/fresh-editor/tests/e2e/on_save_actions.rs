@@ -30,6 +30,7 @@ fn test_format_on_save() {
         args: vec![],
         stdin: true,
         timeout_ms: 5000,
+        env: Default::default(),
     };
 
     // Create config for "plaintext" language (matches .txt files)
@@ -44,6 +45,9 @@ fn test_format_on_save() {
             auto_indent: false,
             auto_close: None,
             auto_surround: None,
+            word_characters: None,
+            auto_close_pairs: None,
+            trim_trailing_whitespace_on_save: None,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -96,6 +100,7 @@ fn test_on_save_linter_style() {
         stdin: false,
         timeout_ms: 5000,
         enabled: true,
+        env: Default::default(),
     };
 
     let mut config = Config::default();
@@ -109,6 +114,9 @@ fn test_on_save_linter_style() {
             auto_indent: false,
             auto_close: None,
             auto_surround: None,
+            word_characters: None,
+            auto_close_pairs: None,
+            trim_trailing_whitespace_on_save: None,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -161,6 +169,7 @@ fn test_on_save_action_failure() {
         stdin: false,
         timeout_ms: 5000,
         enabled: true,
+        env: Default::default(),
     };
 
     let mut config = Config::default();
@@ -174,6 +183,9 @@ fn test_on_save_action_failure() {
             auto_indent: false,
             auto_close: None,
             auto_surround: None,
+            word_characters: None,
+            auto_close_pairs: None,
+            trim_trailing_whitespace_on_save: None,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -235,6 +247,7 @@ fn test_on_save_file_placeholder() {
         stdin: false,
         timeout_ms: 5000,
         enabled: true,
+        env: Default::default(),
     };
 
     let mut config = Config::default();
@@ -248,6 +261,9 @@ fn test_on_save_file_placeholder() {
             auto_indent: false,
             auto_close: None,
             auto_surround: None,
+            word_characters: None,
+            auto_close_pairs: None,
+            trim_trailing_whitespace_on_save: None,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -303,6 +319,7 @@ fn test_formatter_stdin_mode() {
         args: vec!["a-z".to_string(), "A-Z".to_string()],
         stdin: true,
         timeout_ms: 5000,
+        env: Default::default(),
     };
 
     let mut config = Config::default();
@@ -316,6 +333,9 @@ fn test_formatter_stdin_mode() {
             auto_indent: false,
             auto_close: None,
             auto_surround: None,
+            word_characters: None,
+            auto_close_pairs: None,
+            trim_trailing_whitespace_on_save: None,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -369,6 +389,7 @@ fn test_on_save_stops_on_failure() {
         stdin: false,
         timeout_ms: 5000,
         enabled: true,
+        env: Default::default(),
     };
 
     let action2 = OnSaveAction {
@@ -378,6 +399,7 @@ fn test_on_save_stops_on_failure() {
         stdin: false,
         timeout_ms: 5000,
         enabled: true,
+        env: Default::default(),
     };
 
     let mut config = Config::default();
@@ -391,6 +413,9 @@ fn test_on_save_stops_on_failure() {
             auto_indent: false,
             auto_close: None,
             auto_surround: None,
+            word_characters: None,
+            auto_close_pairs: None,
+            trim_trailing_whitespace_on_save: None,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -474,6 +499,7 @@ fn test_formatter_not_found_shows_message() {
         args: vec![],
         stdin: true,
         timeout_ms: 5000,
+        env: Default::default(),
     };
 
     let mut config = Config::default();
@@ -487,6 +513,9 @@ fn test_formatter_not_found_shows_message() {
             auto_indent: false,
             auto_close: None,
             auto_surround: None,
+            word_characters: None,
+            auto_close_pairs: None,
+            trim_trailing_whitespace_on_save: None,
             highlighter: Default::default(),
             textmate_grammar: None,
             show_whitespace_tabs: true,
@@ -555,6 +584,83 @@ fn test_trim_trailing_whitespace_on_save() {
     assert_eq!(disk_content, "line 1\nline 2\nline 3\n");
 }
 
+/// Saving with the cursor after a line's trailing whitespace keeps that
+/// whitespace, and the trim is undone in one step
+#[test]
+fn test_trim_trailing_whitespace_on_save_keeps_cursor_line() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file_path = project_dir.join("test.rs");
+    std::fs::write(&file_path, "let a = 1;  \nlet b = 2;\t\nlet c = 3; \n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.trim_trailing_whitespace_on_save = true;
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, config, project_dir).unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("let a = 1;  \nlet b = 2;\nlet c = 3;\n");
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "let a = 1;  \nlet b = 2;\nlet c = 3;\n"
+    );
+    assert_eq!(harness.cursor_position(), 12);
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("let a = 1;  \nlet b = 2;\t\nlet c = 3; \n");
+}
+
+/// A language can turn trimming on when it's off globally
+#[test]
+fn test_trim_trailing_whitespace_on_save_language_override() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let rust_path = project_dir.join("test.rs");
+    let text_path = project_dir.join("notes.txt");
+    std::fs::write(&rust_path, "fn main() {}   \n").unwrap();
+    std::fs::write(&text_path, "notes   \n").unwrap();
+
+    let mut config = Config::default();
+    config
+        .languages
+        .get_mut("rust")
+        .unwrap()
+        .trim_trailing_whitespace_on_save = Some(true);
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, config, project_dir).unwrap();
+
+    for path in [&rust_path, &text_path] {
+        harness.open_file(path).unwrap();
+        harness.type_text("x").unwrap();
+        harness
+            .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+            .unwrap();
+        harness
+            .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+            .unwrap();
+    }
+
+    assert_eq!(
+        std::fs::read_to_string(&rust_path).unwrap(),
+        "fn main() {}\n"
+    );
+    assert_eq!(std::fs::read_to_string(&text_path).unwrap(), "notes   \n");
+}
+
 /// Test ensure_final_newline_on_save adds newline at end
 #[test]
 fn test_ensure_final_newline_on_save() {
@@ -589,6 +695,70 @@ fn test_ensure_final_newline_on_save() {
     assert_eq!(disk_content, "line 1\nline 2\n");
 }
 
+/// An empty buffer stays empty
+#[test]
+fn test_ensure_final_newline_on_save_empty_buffer() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file_path = project_dir.join("empty.rs");
+    std::fs::write(&file_path, "").unwrap();
+
+    let mut config = Config::default();
+    config.editor.ensure_final_newline_on_save = true;
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, config, project_dir).unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.type_text("x").unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    harness.assert_buffer_content("");
+    assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "");
+}
+
+/// A CRLF file gets a CRLF final newline, which undo removes again
+#[test]
+fn test_ensure_final_newline_on_save_crlf() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file_path = project_dir.join("test.rs");
+    std::fs::write(&file_path, "line 1\r\nline 2").unwrap();
+
+    let mut config = Config::default();
+    config.editor.ensure_final_newline_on_save = true;
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, config, project_dir).unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    harness.assert_buffer_content("line 1\r\nline 2\r\n");
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "line 1\r\nline 2\r\n"
+    );
+    // The cursor stays where it was
+    assert_eq!(harness.cursor_position(), 0);
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("line 1\r\nline 2");
+}
+
 /// Test both whitespace cleanup options together
 #[test]
 fn test_whitespace_cleanup_combined() {
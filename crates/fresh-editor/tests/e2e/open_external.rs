@@ -0,0 +1,86 @@
+//! E2E tests for `open_external` rules
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, OpenExternalRule};
+use tempfile::TempDir;
+
+fn config_with_rule(pattern: &str, command: &str) -> Config {
+    Config {
+        open_external: vec![OpenExternalRule {
+            pattern: pattern.to_string(),
+            command: command.to_string(),
+            args: Vec::new(),
+            enabled: true,
+        }],
+        ..Default::default()
+    }
+}
+
+/// Files matching a rule go to the external program; "Open as Text" loads
+/// them in the editor anyway
+#[test]
+#[cfg(unix)]
+fn test_matching_file_opens_externally_until_opened_as_text() {
+    let temp_dir = TempDir::new().unwrap();
+    let image = temp_dir.path().join("logo.png");
+    std::fs::write(&image, "not really a png").unwrap();
+    let notes = temp_dir.path().join("notes.txt");
+    std::fs::write(&notes, "plain text").unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        config_with_rule("*.png", "true"),
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+
+    harness
+        .editor_mut()
+        .queue_file_open(image.clone(), None, None, None, None, None, None);
+    harness.editor_mut().process_pending_file_opens();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Opened logo.png with true");
+    assert_ne!(harness.get_buffer_content().unwrap(), "not really a png");
+
+    // Files that don't match open as usual
+    harness.open_file(&notes).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "plain text");
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Open as Text").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "not really a png");
+}
+
+/// Disabled rules are ignored
+#[test]
+fn test_disabled_rule_opens_as_text() {
+    let temp_dir = TempDir::new().unwrap();
+    let image = temp_dir.path().join("logo.png");
+    std::fs::write(&image, "not really a png").unwrap();
+
+    let mut config = config_with_rule("*.png", "true");
+    config.open_external[0].enabled = false;
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+
+    harness
+        .editor_mut()
+        .queue_file_open(image, None, None, None, None, None, None);
+    harness.editor_mut().process_pending_file_opens();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "not really a png");
+}
@@ -0,0 +1,115 @@
+//! E2E tests for opening http(s) URLs
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use tempfile::TempDir;
+
+/// Serve a single HTTP response on a local port, returning the base URL
+fn serve_once(status: &str, body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let status = status.to_string();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0u8; 4096];
+        let _ = stream.read(&mut request);
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).unwrap();
+    });
+    format!("http://{}", addr)
+}
+
+fn run_palette_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// A downloaded file opens read-only under its last path segment, and
+/// "Save a Local Copy" turns it into an editable file
+#[test]
+fn test_url_opens_read_only_and_saves_local_copy() {
+    let temp_dir = TempDir::new().unwrap();
+    let base = serve_once("200 OK", "fn main() {}\n");
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        24,
+        Config::default(),
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+
+    harness
+        .editor_mut()
+        .open_url(&format!("{}/src/main.rs?raw=1", base));
+    harness.wait_for_buffer_content("fn main() {}\n").unwrap();
+    harness.assert_screen_contains("main.rs");
+
+    // Read-only: typing doesn't change the content
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "fn main() {}\n");
+
+    run_palette_command(&mut harness, "Save a Local Copy");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    let saved = temp_dir.path().join("main.rs");
+    assert_eq!(std::fs::read_to_string(&saved).unwrap(), "fn main() {}\n");
+
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "xfn main() {}\n");
+}
+
+/// HTTP errors are reported instead of opening an empty buffer
+#[test]
+fn test_url_http_error_shows_status() {
+    let base = serve_once("404 Not Found", "");
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+
+    harness
+        .editor_mut()
+        .open_url(&format!("{}/missing.rs", base));
+    // The message includes the full URL, so check it rather than the
+    // truncated status bar
+    harness
+        .wait_until(|h| {
+            h.editor()
+                .get_status_message()
+                .is_some_and(|msg| msg.contains("HTTP 404"))
+        })
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "");
+}
+
+/// `allow_network_fetch: false` stops downloads entirely
+#[test]
+fn test_url_fetch_disabled() {
+    let config = Config {
+        allow_network_fetch: false,
+        ..Default::default()
+    };
+    let mut harness = EditorTestHarness::with_config(120, 24, config).unwrap();
+
+    harness.editor_mut().open_url("http://127.0.0.1:9/never.rs");
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some(
+            "Not downloading http://127.0.0.1:9/never.rs: network fetches are disabled \
+             (allow_network_fetch)"
+        )
+    );
+    assert_eq!(harness.get_buffer_content().unwrap(), "");
+}
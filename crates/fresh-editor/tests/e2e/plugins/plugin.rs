@@ -1404,3 +1404,123 @@ editor.setStatus("Uppercase plugin loaded");
         screen
     );
 }
+
+/// `buffer_modified_changed` fires when a buffer becomes modified and again
+/// when undo returns it to the saved state, and BufferInfo reports the
+/// undo/redo depth
+#[test]
+fn test_buffer_modified_changed_hook_and_undo_depth() {
+    init_tracing_from_env();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"/// <reference path="./lib/fresh.d.ts" />
+const editor = getEditor();
+
+let changes = 0;
+
+globalThis.onModifiedChanged = function(data: BufferModifiedChangedEvent): void {
+    changes++;
+    const info = editor.getBufferInfo(data.buffer_id);
+    editor.setStatus(
+        `change ${changes}: modified=${data.modified} undo=${info?.undo_depth} redo=${info?.redo_depth}`
+    );
+};
+
+editor.on("buffer_modified_changed", "onModifiedChanged");
+editor.setStatus("Modified hook plugin loaded");
+"#;
+
+    fs::write(plugins_dir.join("test_modified_hook.ts"), test_plugin).unwrap();
+
+    let test_file_path = project_root.join("test_modified.txt");
+    fs::write(&test_file_path, "hello\n").unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 30, Default::default(), project_root)
+            .unwrap();
+    // Opening the file replaces the plugin's status, so wait for it first
+    harness
+        .wait_for_screen_contains("Modified hook plugin loaded")
+        .unwrap();
+    harness.open_file(&test_file_path).unwrap();
+
+    harness.type_text("x").unwrap();
+    harness
+        .wait_for_screen_contains("change 1: modified=true undo=1 redo=0")
+        .unwrap();
+
+    // Further edits don't fire again
+    harness.type_text("y").unwrap();
+    harness.render().unwrap();
+    assert!(!harness.screen_to_string().contains("change 2"));
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("change 2: modified=false undo=0 redo=2")
+        .unwrap();
+}
+
+/// A `before_quit` handler can cancel the quit; without it the editor quits
+#[test]
+fn test_before_quit_hook_can_cancel_quit() {
+    init_tracing_from_env();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    // Cancels the first quit only
+    let test_plugin = r#"/// <reference path="./lib/fresh.d.ts" />
+const editor = getEditor();
+
+let quits = 0;
+
+globalThis.onBeforeQuit = function(data: BeforeQuitEvent): void {
+    quits++;
+    if (quits === 1 && data.modified_count === 0) {
+        editor.cancelQuit();
+    }
+};
+
+editor.on("before_quit", "onBeforeQuit");
+editor.setStatus("Quit hook plugin loaded");
+"#;
+
+    fs::write(plugins_dir.join("test_quit_hook.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 30, Default::default(), project_root)
+            .unwrap();
+    harness
+        .wait_for_screen_contains("Quit hook plugin loaded")
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("Quit cancelled by a plugin")
+        .unwrap();
+    assert!(!harness.editor().should_quit());
+
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_until(|h| h.editor().should_quit()).unwrap();
+}
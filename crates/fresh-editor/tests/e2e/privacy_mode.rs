@@ -0,0 +1,82 @@
+//! E2E tests for Privacy Mode (masking secrets while screen sharing)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn run_palette_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Secrets are drawn as blocks while Privacy Mode is on, but the buffer
+/// itself keeps the real text
+#[test]
+fn test_privacy_mode_masks_secrets_on_screen_only() {
+    let content = "user = alice\npassword = hunter2\n";
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text(content).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("password = hunter2");
+
+    run_palette_command(&mut harness, "Toggle Privacy Mode");
+    harness.assert_screen_contains("password = ███████");
+    harness.assert_screen_contains("user = alice");
+    harness.assert_screen_contains("[PRIVACY]");
+    harness.assert_screen_not_contains("hunter2");
+    assert_eq!(harness.get_buffer_content().unwrap(), content);
+
+    run_palette_command(&mut harness, "Toggle Privacy Mode");
+    harness.assert_screen_contains("password = hunter2");
+    harness.assert_screen_not_contains("[PRIVACY]");
+}
+
+/// Copying a selection that covers a masked secret asks for confirmation
+#[test]
+fn test_copying_masked_secret_asks_first() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("password = hunter2\n")
+        .unwrap();
+    run_palette_command(&mut harness, "Toggle Privacy Mode");
+
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("masked secret");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Copy cancelled")
+    );
+
+    harness
+        .send_key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("c").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('v'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "password = hunter2\npassword = hunter2\n"
+    );
+}
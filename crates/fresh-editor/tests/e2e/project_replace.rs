@@ -0,0 +1,85 @@
+//! E2E tests for project-wide replace
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crate::e2e::remote_fs_test::create_test_filesystem;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Nothing changes before the file count is confirmed; then every match is
+/// replaced, one undoable edit per buffer
+#[test]
+fn test_project_replace_confirms_then_replaces_in_every_file() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project = harness.project_dir().unwrap();
+    std::fs::write(project.join("a.txt"), "foo bar foo").unwrap();
+    std::fs::write(project.join("b.txt"), "foo").unwrap();
+    std::fs::write(project.join("c.txt"), "nothing here").unwrap();
+
+    harness.editor_mut().project_replace("foo", "baz", false);
+    for _ in 0..200 {
+        harness.tick_and_render().unwrap();
+        if harness.screen_to_string().contains("in 2 files?") {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    harness.assert_screen_contains("Replace 3 occurrences of 'foo' in 2 files?");
+
+    harness.type_text("y").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Replaced 3 occurrences in 2 files");
+
+    harness.open_file(&project.join("a.txt")).unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "baz bar baz");
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "foo bar foo");
+}
+
+/// Files over the large-file threshold aren't read, so they aren't offered
+/// for replacing
+#[test]
+fn test_project_replace_skips_large_files() {
+    let mut config = Config::default();
+    config.editor.large_file_threshold_bytes = 100;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(100, 24, config).unwrap();
+    let project = harness.project_dir().unwrap();
+    std::fs::write(project.join("a.txt"), "foo").unwrap();
+    std::fs::write(project.join("huge.log"), "foo\n".repeat(100)).unwrap();
+
+    harness.editor_mut().project_replace("foo", "baz", false);
+    for _ in 0..200 {
+        harness.tick_and_render().unwrap();
+        if harness.screen_to_string().contains("files?") {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    harness.assert_screen_contains("Replace 1 occurrences of 'foo' in 1 files?");
+}
+
+/// The scan reads the local disk, so nothing is offered for replacing on a
+/// remote host
+#[test]
+fn test_project_replace_unavailable_on_remote_host() {
+    let Some((fs, _temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let mut harness =
+        EditorTestHarness::create(100, 24, HarnessOptions::new().with_filesystem(Arc::new(fs)))
+            .unwrap();
+
+    harness.editor_mut().project_replace("foo", "baz", false);
+    assert!(!harness.editor().is_prompting());
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Replace in project isn't available on a remote host")
+    );
+}
@@ -0,0 +1,61 @@
+//! E2E tests for project-wide search
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crate::e2e::remote_fs_test::create_test_filesystem;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Matches are listed as `path:line:col: text`, ignored files are left out,
+/// and Enter opens the file at the match
+#[test]
+fn test_project_search_lists_matches_and_opens_result() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project = harness.project_dir().unwrap();
+    std::fs::create_dir_all(project.join("build")).unwrap();
+    std::fs::write(project.join(".gitignore"), "build/\n").unwrap();
+    std::fs::write(project.join("notes.txt"), "first\nsecond needle here\n").unwrap();
+    std::fs::write(project.join("build").join("out.txt"), "needle\n").unwrap();
+
+    harness.editor_mut().project_search("needle", false);
+    for _ in 0..200 {
+        harness.tick_and_render().unwrap();
+        if harness.screen_to_string().contains("1 matches") {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    harness.assert_screen_contains("notes.txt:2:8: second needle here");
+    harness.assert_screen_not_contains("out.txt");
+
+    // The cursor starts on the first result
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "first\nsecond needle here\n"
+    );
+    assert_eq!(harness.cursor_position(), "first\nsecond ".len());
+}
+
+/// The walk reads the local disk, so the search doesn't run on a remote
+/// host
+#[test]
+fn test_project_search_unavailable_on_remote_host() {
+    let Some((fs, _temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let mut harness =
+        EditorTestHarness::create(100, 24, HarnessOptions::new().with_filesystem(Arc::new(fs)))
+            .unwrap();
+
+    harness.editor_mut().project_search("needle", false);
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Project search isn't available on a remote host")
+    );
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("*Project Search*");
+}
@@ -0,0 +1,57 @@
+//! E2E tests for the Recent Files picker
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn run_commands(harness: &mut EditorTestHarness, script: &str) {
+    harness.editor_mut().queue_startup_commands(script, false);
+    harness.editor_mut().run_startup_commands();
+    harness.render().unwrap();
+}
+
+/// Opened files are listed newest first without duplicates, deleted files
+/// are left out, and confirming opens the chosen file
+#[test]
+fn test_recent_files_lists_newest_first_and_opens() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project = harness.project_dir().unwrap();
+    for (name, content) in [
+        ("alpha.txt", "alpha"),
+        ("beta.txt", "beta"),
+        ("gone.txt", "gone"),
+    ] {
+        std::fs::write(project.join(name), content).unwrap();
+    }
+
+    harness.open_file(&project.join("alpha.txt")).unwrap();
+    harness.open_file(&project.join("gone.txt")).unwrap();
+    harness.open_file(&project.join("beta.txt")).unwrap();
+    harness.open_file(&project.join("alpha.txt")).unwrap();
+    std::fs::remove_file(project.join("gone.txt")).unwrap();
+
+    run_commands(&mut harness, "recent-files");
+    // The suggestion box (two entries and its borders) sits right above the
+    // prompt; the tab bar names the same files
+    let screen = harness.screen_to_string();
+    let lines: Vec<&str> = screen.lines().collect();
+    let prompt_row = lines
+        .iter()
+        .position(|line| line.contains("Recent files: "))
+        .expect("picker prompt should be shown");
+    let suggestions = lines[prompt_row.saturating_sub(4)..prompt_row].join("\n");
+    let alpha = suggestions
+        .find("alpha.txt")
+        .expect("alpha.txt should be listed");
+    let beta = suggestions
+        .find("beta.txt")
+        .expect("beta.txt should be listed");
+    assert!(alpha < beta, "most recently opened file comes first");
+    assert_eq!(suggestions.matches("alpha.txt").count(), 1);
+    assert!(!suggestions.contains("gone.txt"));
+
+    harness.type_text("beta").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "beta");
+}
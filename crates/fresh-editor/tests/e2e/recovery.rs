@@ -34,6 +34,64 @@ fn test_typing_marks_buffer_recovery_dirty() {
     );
 }
 
+/// Test that read-only buffers get no recovery files until editing is allowed again
+#[test]
+fn test_read_only_buffer_skips_recovery() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = TestFixture::new("test_recovery_read_only.txt", "secret").unwrap();
+    harness.open_file(&_fixture.path).unwrap();
+    harness.type_text("edited ").unwrap();
+
+    let buffer_id = harness.editor().active_buffer();
+    harness.editor_mut().mark_buffer_read_only(buffer_id, true);
+    harness.advance_time(std::time::Duration::from_millis(2100));
+    let saved = harness
+        .editor_mut()
+        .auto_recovery_save_dirty_buffers()
+        .unwrap();
+    assert_eq!(
+        saved, 0,
+        "Read-only buffers should not be saved for recovery"
+    );
+
+    harness.editor_mut().mark_buffer_read_only(buffer_id, false);
+    harness.advance_time(std::time::Duration::from_millis(2100));
+    let saved = harness
+        .editor_mut()
+        .auto_recovery_save_dirty_buffers()
+        .unwrap();
+    assert_eq!(
+        saved, 1,
+        "Recovery should resume once the buffer is writable"
+    );
+}
+
+/// Test that a forced checkpoint saves recovery data before the interval is up
+#[test]
+fn test_force_recovery_checkpoint_ignores_interval() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = TestFixture::new("test_recovery_checkpoint.txt", "content").unwrap();
+    harness.open_file(&_fixture.path).unwrap();
+    harness.type_text("edited ").unwrap();
+
+    let saved = harness
+        .editor_mut()
+        .auto_recovery_save_dirty_buffers()
+        .unwrap();
+    assert_eq!(saved, 0, "The periodic save should wait for the interval");
+    assert!(harness.editor().is_active_buffer_recovery_dirty());
+
+    harness.editor_mut().force_recovery_checkpoint();
+    assert!(
+        !harness.editor().is_active_buffer_recovery_dirty(),
+        "The checkpoint should save the buffer right away"
+    );
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Recovery checkpoint: saved 1 buffer(s)")
+    );
+}
+
 /// Test that deleting text marks the buffer as recovery-dirty
 #[test]
 fn test_delete_marks_buffer_recovery_dirty() {
@@ -0,0 +1,125 @@
+//! E2E tests for Reflow Paragraph and Unwrap Paragraph
+
+use crate::common::fixtures::TestFixture;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+fn harness_with_wrap_column(column: usize) -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.wrap_column = column;
+    EditorTestHarness::with_config(80, 24, config).unwrap()
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Alt+Q re-wraps an indented line comment, repeats the comment prefix on
+/// every line, keeps the cursor on its word and undoes in one step
+#[test]
+fn test_reflow_indented_comment() {
+    let original = "fn main() {\n    // This is a long comment that should be wrapped at a narrow column so it spans lines.\n    let x = 1;\n}\n";
+    let mut harness = harness_with_wrap_column(40);
+    let fixture = TestFixture::new("main.rs", original).unwrap();
+    harness.open_file(&fixture.path).unwrap();
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    let comment = original.lines().nth(1).unwrap();
+    let col = comment.find("wrapped").unwrap() + 3;
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, col)
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::ALT)
+        .unwrap();
+    let expected = "fn main() {\n    // This is a long comment that\n    // should be wrapped at a narrow\n    // column so it spans lines.\n    let x = 1;\n}\n";
+    assert_eq!(harness.get_buffer_content().unwrap(), expected);
+    assert_eq!(
+        harness.cursor_position(),
+        expected.find("wrapped").unwrap() + 3
+    );
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), original);
+}
+
+/// Unwrap Paragraph joins a blockquote into one line without crossing the
+/// blank line or merging lines with a different prefix
+#[test]
+fn test_unwrap_blockquote_paragraph() {
+    let mut harness = harness_with_wrap_column(80);
+    let fixture = TestFixture::new(
+        "notes.md",
+        "> one two three\n> four five\nplain text\n\n> six\n",
+    )
+    .unwrap();
+    harness.open_file(&fixture.path).unwrap();
+
+    run_command(&mut harness, "Unwrap Paragraph");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "> one two three four five\nplain text\n\n> six\n"
+    );
+}
+
+/// With a selection, every paragraph in it is reflowed and blank lines
+/// between them are kept
+#[test]
+fn test_reflow_selection_keeps_paragraph_breaks() {
+    let mut harness = harness_with_wrap_column(10);
+    let _fixture = harness
+        .load_buffer_from_text("aaa bbb ccc ddd\n\neee fff\nggg\n")
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    run_command(&mut harness, "Reflow Paragraph");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "aaa bbb\nccc ddd\n\neee fff\nggg\n"
+    );
+
+    // Selection end stays at the end of the buffer
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(harness.cursor_position(), content.len());
+}
+
+/// Reflow Paragraph to Column wraps at the column given, here as the
+/// argument of an `--execute` command, instead of `editor.wrap_column`
+#[test]
+fn test_reflow_paragraph_to_column_argument() {
+    let mut harness = harness_with_wrap_column(80);
+    let _fixture = harness
+        .load_buffer_from_text("aaa bbb ccc ddd eee\n")
+        .unwrap();
+
+    harness
+        .editor_mut()
+        .queue_startup_commands("reflow-paragraph-to-column 8", false);
+    harness.editor_mut().run_startup_commands();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "aaa bbb\nccc ddd\neee\n"
+    );
+
+    harness
+        .editor_mut()
+        .queue_startup_commands("reflow-paragraph-to-column 0", false);
+    harness.editor_mut().run_startup_commands();
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Invalid column: 0")
+    );
+}
@@ -3,8 +3,10 @@ use crossterm::event::{KeyCode, KeyModifiers};
 use fresh::services::remote::{spawn_local_agent, RemoteFileSystem};
 use std::sync::Arc;
 
-fn create_test_filesystem() -> Option<(RemoteFileSystem, tempfile::TempDir, tokio::runtime::Runtime)>
-{
+/// A remote filesystem served by an agent on this machine, with a temp dir
+/// to put files in
+pub(crate) fn create_test_filesystem(
+) -> Option<(RemoteFileSystem, tempfile::TempDir, tokio::runtime::Runtime)> {
     let temp_dir = tempfile::tempdir().ok()?;
     let rt = tokio::runtime::Runtime::new().ok()?;
 
@@ -319,3 +321,52 @@ fn test_remote_large_file_edits_beginning_middle_end() {
         );
     }
 }
+
+/// A remote file that loads whole downloads in chunks: the buffer opens
+/// read-only at once and becomes editable when the last chunk is in
+#[test]
+fn test_remote_file_downloads_in_chunks() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let file_path = temp_dir.path().join("access.log");
+    let content: String = (0..40_000)
+        .map(|i| format!("GET /page/{} 200\n", i))
+        .collect();
+    std::fs::write(&file_path, &content).unwrap();
+
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new()
+            .with_config(fresh::config::Config {
+                editor: fresh::config::EditorConfig {
+                    // Load the file whole rather than lazily
+                    large_file_threshold_bytes: 10 * 1024 * 1024,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .with_filesystem(Arc::new(fs)),
+    )
+    .unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    assert!(harness.editor().is_editing_disabled());
+    for _ in 0..500 {
+        harness.tick_and_render().unwrap();
+        if !harness.editor().is_editing_disabled() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert!(!harness.editor().is_editing_disabled());
+    assert_eq!(harness.get_buffer_content().unwrap(), content);
+
+    harness.type_text("x").unwrap();
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .starts_with("xGET /page/0"));
+}
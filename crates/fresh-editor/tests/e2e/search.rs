@@ -2484,6 +2484,87 @@ fn test_regex_replace_with_capture_group() {
     assert_eq!(content, "ooblaoobla");
 }
 
+/// Query replace in regex mode previews each match's replacement, with
+/// capture groups and case escapes expanded
+#[test]
+fn test_regex_query_replace_previews_each_replacement() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "key=value\nname=fresh\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(
+            KeyCode::Char('r'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        )
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::ALT)
+        .unwrap();
+    harness.type_text(r"(\w+)=(\w+)").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text(r"$2=\U$1").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Replace with 'value=KEY'? (y)es (n)o (a)ll (c)ancel");
+
+    harness.type_text("y").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Replace with 'fresh=NAME'? (y)es (n)o (a)ll (c)ancel");
+
+    harness.type_text("y").unwrap();
+    harness.render().unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(content, "value=KEY\nfresh=NAME\n");
+}
+
+/// A replacement naming a group the pattern doesn't have is reported, and
+/// nothing is replaced
+#[test]
+fn test_regex_replace_with_unknown_group_reports_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "key=value").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::ALT)
+        .unwrap();
+    harness.type_text(r"(\w+)=").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("$2").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(
+        harness
+            .editor()
+            .get_status_message()
+            .map(|msg| msg.as_str()),
+        Some("Invalid replacement: the search pattern has no group '2'")
+    );
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(content, "key=value");
+}
+
 /// Reproduce the performance issue where editor_tick takes ~700ms after a large
 /// search completes with many matches. This test uses tracing to identify the
 /// bottleneck. Run with: RUST_LOG=info cargo test -p fresh-editor --test e2e_tests
@@ -2787,3 +2868,56 @@ fn test_search_f3_navigates_all_matches_large_file() {
         harness.assert_screen_contains(&format!("Match {} of 4", n));
     }
 }
+
+/// Search for `needle` in a long file, jump to the next match with F3 and
+/// return how many lines sit above the match in the viewport
+fn rows_above_next_match(config: Config) -> (usize, usize) {
+    let content: String = (0..300)
+        .map(|i| {
+            if i == 120 || i == 240 {
+                format!("needle {}\n", i)
+            } else {
+                format!("line {}\n", i)
+            }
+        })
+        .collect();
+    let mut harness = EditorTestHarness::with_config(100, 30, config).unwrap();
+    let _fixture = harness.load_buffer_from_text(&content).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("needle").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.send_key(KeyCode::F(3), KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("needle 240");
+
+    let rows_above = 240 - harness.top_line_number();
+    (rows_above, harness.viewport_height())
+}
+
+/// `editor.center_on_match` recenters on each match; by default the match is
+/// only scrolled into view
+#[test]
+fn test_center_on_match() {
+    let mut config = Config::default();
+    config.editor.center_on_match = true;
+    let (rows_above, height) = rows_above_next_match(config);
+    assert!(
+        rows_above.abs_diff(height / 2) <= 1,
+        "match should be centered: {} rows above in a {}-row view",
+        rows_above,
+        height
+    );
+
+    let (rows_above, height) = rows_above_next_match(Config::default());
+    assert!(
+        rows_above > height / 2 + 2,
+        "match should only scroll into view: {} rows above in a {}-row view",
+        rows_above,
+        height
+    );
+}
@@ -294,6 +294,87 @@ fn test_select_line_last() {
     );
 }
 
+/// Test that repeating select line grows the selection by whole lines
+#[test]
+fn test_select_line_repeat_grows_selection() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness
+        .type_text("first line\nsecond line\nthird line")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('l'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('l'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_selected_text(), "first line\nsecond line\n");
+
+    // Growing past the last line keeps the selection at the buffer end
+    harness
+        .send_key(KeyCode::Char('l'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('l'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.get_selected_text(),
+        "first line\nsecond line\nthird line"
+    );
+}
+
+/// Run the "Select Paragraph" command from the command palette
+fn select_paragraph(harness: &mut EditorTestHarness) {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("select paragraph").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+}
+
+/// Test select paragraph with multiple cursors, and growing it on repeat
+#[test]
+fn test_select_paragraph_multi_cursor() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    let _fixture = harness.load_buffer_from_text("a\n\nb\n").unwrap();
+
+    // One cursor on "a", one on the blank line below it
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        .unwrap();
+    assert_eq!(harness.editor().active_cursors().count(), 2);
+
+    // The cursor on the blank line selects the following paragraph
+    select_paragraph(&mut harness);
+    let mut ranges: Vec<_> = harness
+        .editor()
+        .active_cursors()
+        .iter()
+        .filter_map(|(_, c)| c.selection_range())
+        .collect();
+    ranges.sort_by_key(|r| r.start);
+    assert_eq!(ranges, vec![0..2, 3..5]);
+
+    // Repeating grows the first selection forward; the last paragraph has
+    // nothing after it and grows backward
+    select_paragraph(&mut harness);
+    for (_, cursor) in harness.editor().active_cursors().iter() {
+        assert_eq!(cursor.selection_range(), Some(0..5));
+    }
+}
+
 /// Test select word with multiple cursors
 #[test]
 fn test_select_word_multi_cursor() {
@@ -2228,3 +2309,73 @@ fn test_ctrl_d_at_word_end() {
         "Ctrl+D at word end should select entire 'word'"
     );
 }
+
+/// The status bar shows the selection size live, with lines once a
+/// selection spans more than one
+#[test]
+fn test_status_bar_shows_selection_size() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.type_text("hello world\nsecond").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("selected");
+
+    for _ in 0..5 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    harness.render().unwrap();
+    harness.assert_screen_contains("5 chars selected");
+
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("17 chars, 2 lines selected");
+}
+
+/// "Selection Info" reports bytes, chars, words and lines, aggregated
+/// across all selections
+#[test]
+fn test_selection_info_command() {
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.type_text("héllo world\nfoo bar").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+
+    let run_selection_info = |harness: &mut EditorTestHarness| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("Selection Info").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.render().unwrap();
+        harness.editor().get_status_message().cloned()
+    };
+
+    assert_eq!(
+        run_selection_info(&mut harness).as_deref(),
+        Some("No selection")
+    );
+
+    // Select the first line, then extend into the second
+    harness.send_key(KeyCode::End, KeyModifiers::SHIFT).unwrap();
+    assert_eq!(
+        run_selection_info(&mut harness).as_deref(),
+        Some("12 bytes, 11 chars, 2 words, 1 lines")
+    );
+
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(
+        run_selection_info(&mut harness).as_deref(),
+        Some("20 bytes, 19 chars, 4 words, 2 lines")
+    );
+}
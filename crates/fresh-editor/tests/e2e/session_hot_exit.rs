@@ -90,6 +90,52 @@ fn test_session_hot_exit_preserves_unsaved_changes() {
     }
 }
 
+/// Test that unsaved changes recovered with the workspace win over a fresh
+/// load when the same file is also passed on the command line.
+#[test]
+fn test_hot_exit_changes_kept_for_cli_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file1 = project_dir.join("hello.txt");
+    std::fs::write(&file1, "original content").unwrap();
+
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+    let mut config = Config::default();
+    config.editor.hot_exit = true;
+    config.session.restore_with_cli_files = true;
+    let options = || {
+        HarnessOptions::new()
+            .with_config(config.clone())
+            .with_working_dir(project_dir.clone())
+            .with_shared_dir_context(dir_context.clone())
+            .without_empty_plugins_dir()
+    };
+
+    // First session: modify the file and exit without saving
+    {
+        let mut harness = EditorTestHarness::create(80, 24, options()).unwrap();
+        harness.open_file(&file1).unwrap();
+        harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+        harness.type_text(" EDITED").unwrap();
+        harness.shutdown(true).unwrap();
+    }
+
+    // Second session: restore the workspace and pass the same file on the CLI
+    {
+        let mut harness = EditorTestHarness::create(80, 24, options()).unwrap();
+        let restored = harness.startup(true, std::slice::from_ref(&file1)).unwrap();
+        assert!(restored, "Session should have been restored");
+        harness.assert_buffer_content("original content EDITED");
+        assert!(harness.editor().active_state().buffer.is_modified());
+        assert_eq!(
+            harness.editor().get_status_message().map(String::as_str),
+            Some("hello.txt has unsaved changes recovered from the previous session")
+        );
+    }
+}
+
 /// Test that unnamed buffer content is preserved across session server restarts.
 #[test]
 fn test_session_unnamed_buffer_preserved_across_restart() {
@@ -229,6 +229,35 @@ fn test_no_auto_close_when_config_disabled() {
     );
 }
 
+/// Test that a language's auto_close_pairs replace the default pairs
+#[test]
+fn test_auto_close_language_pairs() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.rs");
+    std::fs::write(&file_path, "").unwrap();
+
+    let mut config = Config::default();
+    config.editor.auto_close = true;
+    config.languages.get_mut("rust").unwrap().auto_close_pairs =
+        Some(vec!["()".to_string(), "||".to_string()]);
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    // "[" is no longer a pair; "|" is, and skips over its closing "|"
+    harness.type_text("[|x|").unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "[|x|");
+    assert_eq!(harness.cursor_position(), 4);
+
+    // Backspace between the two characters of a pair deletes both
+    harness.type_text("(").unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "[|x|");
+}
+
 // =============================================================================
 // Bracket Skip-Over Tests
 // =============================================================================
@@ -399,3 +399,89 @@ fn test_sort_lines_with_empty_lines() {
         "Empty lines should sort to the beginning"
     );
 }
+
+/// Run a command palette command by its full name
+fn run_palette_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains(">command"))
+        .unwrap();
+    harness.type_text(name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Test numeric sort orders by the leading number, not the text
+#[test]
+fn test_sort_lines_numeric() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("10 items\n2 items\n1 item\n").unwrap();
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    run_palette_command(&mut harness, "Sort Lines Numerically");
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "1 item\n2 items\n10 items\n"
+    );
+}
+
+/// Test reverse sort without a selection sorts the whole buffer
+#[test]
+fn test_sort_lines_reverse_whole_buffer() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("apple\ncherry\nbanana").unwrap();
+
+    run_palette_command(&mut harness, "Sort Lines in Reverse");
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "cherry\nbanana\napple"
+    );
+}
+
+/// Test a selection starting mid-line sorts whole lines of multi-byte text
+#[test]
+fn test_sort_lines_selection_inside_lines() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("zürich\nélan\nçava\nfin").unwrap();
+
+    // Select from the middle of "zürich" to the middle of "çava"
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+
+    run_palette_command(&mut harness, "Sort Lines");
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "zürich\nçava\nélan\nfin"
+    );
+
+    // The sort is one undo step
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "zürich\nélan\nçava\nfin"
+    );
+}
@@ -0,0 +1,62 @@
+//! E2E tests for commands run at startup with `--execute`
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Test that commands run in order, take prompt arguments and can be undone
+#[test]
+fn test_startup_commands_run_through_actions() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "one\ntwo\nthree\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness
+        .editor_mut()
+        .queue_startup_commands("goto-line 3; move_line_end; Go to Line 2", false);
+    assert!(harness.editor_mut().run_startup_commands());
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), "one\n".len());
+    // Only runs once
+    assert!(!harness.editor_mut().run_startup_commands());
+
+    harness
+        .editor_mut()
+        .queue_startup_commands("delete_line", false);
+    harness.editor_mut().run_startup_commands();
+    assert_eq!(harness.get_buffer_content().unwrap(), "one\nthree\n");
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "one\ntwo\nthree\n");
+    assert_eq!(harness.editor().startup_commands_exit_code(), None);
+}
+
+/// Test that a failing command is reported, and stops everything when strict
+#[test]
+fn test_startup_commands_report_failures() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.type_text("one\ntwo").unwrap();
+
+    harness
+        .editor_mut()
+        .queue_startup_commands("no-such-command; move_document_start", false);
+    harness.editor_mut().run_startup_commands();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Command 'no-such-command' failed: unknown command")
+    );
+    assert_eq!(harness.cursor_position(), 0);
+    assert!(!harness.should_quit());
+
+    harness
+        .editor_mut()
+        .queue_startup_commands("move_document_end 5; move_document_start", true);
+    harness.editor_mut().run_startup_commands();
+    assert_eq!(harness.cursor_position(), "one\ntwo".len());
+    assert!(harness.should_quit());
+    assert_eq!(harness.editor().startup_commands_exit_code(), Some(1));
+}
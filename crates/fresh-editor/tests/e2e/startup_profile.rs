@@ -0,0 +1,171 @@
+//! E2E tests for startup: background plugin loading and the time-to-first-render budget
+
+use fresh::app::Editor;
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use fresh::model::filesystem::StdFileSystem;
+use fresh::services::startup_profile::FIRST_RENDER_BUDGET;
+use fresh::view::color_support::ColorCapability;
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Write `count` plugins that each register one command
+fn write_plugins(project: &Path, count: usize) {
+    let plugins = project.join("plugins");
+    std::fs::create_dir_all(&plugins).unwrap();
+    for i in 0..count {
+        let source = format!(
+            "const editor = getEditor();\n\
+             editor.registerCommand(\"Startup Plugin {i}\", \"Registered at startup\", \"startup_plugin_{i}\", null);\n"
+        );
+        std::fs::write(plugins.join(format!("startup_plugin_{i}.ts")), source).unwrap();
+    }
+}
+
+/// Construct an editor the way `fresh` does at startup (background grammar
+/// build and plugin loading)
+fn start_editor(temp: &Path, project: &Path) -> Editor {
+    Editor::with_working_dir(
+        Config::default(),
+        80,
+        24,
+        Some(project.to_path_buf()),
+        DirectoryContext::for_testing(temp),
+        true,
+        ColorCapability::TrueColor,
+        Arc::new(StdFileSystem),
+    )
+    .unwrap()
+}
+
+fn wait_for_startup_work(editor: &mut Editor) {
+    let deadline = Instant::now() + Duration::from_secs(60);
+    while editor.startup_work_pending() {
+        assert!(
+            Instant::now() < deadline,
+            "background startup work didn't finish"
+        );
+        editor.process_async_messages();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Plugins load on the plugin thread after construction; their commands show
+/// up once the background load finishes and both jobs are timed
+#[test]
+fn test_plugins_load_in_background() {
+    let temp = tempfile::tempdir().unwrap();
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    write_plugins(&project, 3);
+
+    let mut editor = start_editor(temp.path(), &project);
+    assert!(editor.startup_work_pending());
+
+    wait_for_startup_work(&mut editor);
+    let registry = editor.command_registry().read().unwrap();
+    for i in 0..3 {
+        assert!(
+            registry
+                .find_by_name(&format!("Startup Plugin {i}"))
+                .is_some(),
+            "command of plugin {i} not registered"
+        );
+    }
+    drop(registry);
+
+    let phases: Vec<&str> = editor
+        .startup_timings()
+        .iter()
+        .map(|(name, _)| *name)
+        .collect();
+    assert!(phases.contains(&"plugin thread spawn"));
+    assert!(phases.contains(&"plugin loading"));
+    assert!(phases.contains(&"grammar build"));
+}
+
+/// Commands plugins registered last session are listed from the start, and
+/// those no plugin registers again go once loading finishes
+#[test]
+fn test_plugin_commands_listed_before_plugins_load() {
+    let temp = tempfile::tempdir().unwrap();
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    write_plugins(&project, 3);
+
+    let mut editor = start_editor(temp.path(), &project);
+    wait_for_startup_work(&mut editor);
+    drop(editor);
+
+    std::fs::remove_file(project.join("plugins").join("startup_plugin_2.ts")).unwrap();
+    let mut editor = start_editor(temp.path(), &project);
+    {
+        let registry = editor.command_registry().read().unwrap();
+        assert!(registry.find_by_name("Startup Plugin 0").is_some());
+        assert!(registry.is_placeholder("Startup Plugin 2"));
+    }
+
+    wait_for_startup_work(&mut editor);
+    let registry = editor.command_registry().read().unwrap();
+    assert!(!registry.is_placeholder("Startup Plugin 0"));
+    assert!(registry.find_by_name("Startup Plugin 0").is_some());
+    assert!(registry.find_by_name("Startup Plugin 2").is_none());
+}
+
+/// Time to first render with 10 plugins and a 20-buffer session stays within
+/// budget on a warm cache: plugin commands are placeholders until the
+/// plugins load, and only the active buffer of the session is read. The
+/// best of a few starts is taken. Timing-sensitive, so only meaningful in
+/// release builds.
+#[test]
+#[ignore] // Run with: cargo test --release test_time_to_first_render_budget -- --ignored
+fn test_time_to_first_render_budget() {
+    let temp = tempfile::tempdir().unwrap();
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    write_plugins(&project, 10);
+
+    // Save a 20-buffer session, and warm the caches on the way
+    let mut editor = start_editor(temp.path(), &project);
+    for i in 0..20 {
+        let path = project.join(format!("file_{i}.rs"));
+        std::fs::write(
+            &path,
+            format!("fn f{i}() {{\n    let x = {i};\n}}\n").repeat(50),
+        )
+        .unwrap();
+        editor.open_file(&path).unwrap();
+    }
+    wait_for_startup_work(&mut editor);
+    editor.save_workspace().unwrap();
+    drop(editor);
+
+    let mut best = Duration::MAX;
+    for _ in 0..3 {
+        let start = Instant::now();
+        let mut editor = start_editor(temp.path(), &project);
+        assert!(editor.try_restore_workspace().unwrap());
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|frame| editor.render(frame)).unwrap();
+        best = best.min(start.elapsed());
+
+        assert_eq!(editor.deferred_buffer_count(), 19);
+        assert!(editor
+            .command_registry()
+            .read()
+            .unwrap()
+            .find_by_name("Startup Plugin 9")
+            .is_some());
+        wait_for_startup_work(&mut editor);
+    }
+
+    assert!(
+        best < FIRST_RENDER_BUDGET,
+        "time to first render {:?} exceeds budget {:?}",
+        best,
+        FIRST_RENDER_BUDGET
+    );
+}
@@ -222,6 +222,84 @@ fn test_stdin_streaming_progress() {
     harness.assert_screen_contains("[stdin]");
 }
 
+/// Poll until the stdin thread has finished and its data is in the buffer
+fn finish_stdin_streaming(harness: &mut EditorTestHarness) {
+    for _ in 0..200 {
+        if !harness.editor().is_stdin_streaming() {
+            return;
+        }
+        harness.editor_mut().poll_stdin_streaming();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    panic!("stdin streaming did not complete");
+}
+
+/// Test that small stdin is loaded from memory without a temp file
+#[test]
+fn test_small_stdin_stays_in_memory() {
+    use fresh::services::stdin_spool::StdinSpool;
+    use std::sync::Arc;
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let temp_path = temp_dir.path().join("stdin.tmp");
+    let spool = Arc::new(StdinSpool::new(temp_path.clone()));
+
+    let thread_spool = Arc::clone(&spool);
+    let thread_handle = std::thread::spawn(move || -> anyhow::Result<()> {
+        thread_spool.write(b"hi\n")?;
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        thread_spool.write(b"there\n")?;
+        Ok(())
+    });
+    harness
+        .editor_mut()
+        .open_stdin_spool(spool, Some(thread_handle))
+        .unwrap();
+    finish_stdin_streaming(&mut harness);
+
+    harness.assert_buffer_content("hi\nthere\n");
+    assert!(!temp_path.exists());
+}
+
+/// Test that stdin outgrowing memory continues in a temp file mid-stream
+#[test]
+fn test_stdin_spills_to_temp_file_mid_stream() {
+    use fresh::services::stdin_spool::StdinSpool;
+    use std::sync::Arc;
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let temp_path = temp_dir.path().join("stdin.tmp");
+    let spool = Arc::new(StdinSpool::with_memory_limit(temp_path.clone(), 16));
+
+    // The first line is already in memory when the buffer opens
+    spool.write(b"Line 0\n").unwrap();
+    let thread_spool = Arc::clone(&spool);
+    let thread_handle = std::thread::spawn(move || -> anyhow::Result<()> {
+        for i in 1..6 {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            thread_spool.write(format!("Line {}\n", i).as_bytes())?;
+        }
+        Ok(())
+    });
+    harness
+        .editor_mut()
+        .open_stdin_spool(spool, Some(thread_handle))
+        .unwrap();
+    finish_stdin_streaming(&mut harness);
+
+    // The spilled part is backed by the temp file and loaded on demand
+    let buffer = &mut harness.editor_mut().active_state_mut().buffer;
+    let total_bytes = buffer.total_bytes();
+    let content = buffer.get_text_range_mut(0, total_bytes).unwrap();
+    assert_eq!(
+        String::from_utf8(content).unwrap(),
+        "Line 0\nLine 1\nLine 2\nLine 3\nLine 4\nLine 5\n"
+    );
+    assert!(temp_path.exists());
+}
+
 /// Test stdin with large file triggers lazy loading mode
 #[test]
 fn test_stdin_large_file_lazy_loading() {
@@ -255,3 +333,42 @@ fn test_stdin_large_file_lazy_loading() {
     harness.render().unwrap();
     harness.assert_screen_contains("[stdin]");
 }
+
+/// Test that --to-stdout writes the edited stdin buffer out
+#[test]
+fn test_write_stdin_buffer_after_editing() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let temp_file = create_stdin_temp_file("- old\n+ new\n");
+
+    harness
+        .editor_mut()
+        .open_stdin_buffer(temp_file.path(), None)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('k'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("kept").unwrap();
+
+    let mut output = Vec::new();
+    harness
+        .editor_mut()
+        .write_stdin_buffer(&mut output)
+        .unwrap();
+    assert_eq!(output, b"kept\n+ new\n");
+}
+
+/// Test that --to-stdout writes nothing without a stdin buffer
+#[test]
+fn test_write_stdin_buffer_without_stdin() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("not from stdin").unwrap();
+
+    let mut output = Vec::new();
+    harness
+        .editor_mut()
+        .write_stdin_buffer(&mut output)
+        .unwrap();
+    assert!(output.is_empty());
+}
@@ -410,3 +410,66 @@ fn test_symlink_canonicalization_consistency() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Test: With follow_symlinks off, the buffer keeps the link path and saving
+/// writes through the link instead of replacing it with a regular file
+#[test]
+fn test_edit_symlink_without_following() -> anyhow::Result<()> {
+    let mut config = fresh::config::Config::default();
+    config.editor.follow_symlinks = false;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config)?;
+    let project_dir = harness.project_dir().unwrap();
+
+    let real_file = project_dir.join("real.txt");
+    fs::write(&real_file, "original")?;
+    let link = project_dir.join("link.txt");
+    symlink(&real_file, &link)?;
+
+    harness.open_file(&link)?;
+    harness.assert_buffer_content("original");
+    harness.assert_screen_contains("link.txt");
+
+    harness.type_text("edited ")?;
+    harness.send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)?;
+    let real_clone = real_file.clone();
+    harness.wait_until(move |_| {
+        fs::read_to_string(&real_clone)
+            .map(|s| s == "edited original")
+            .unwrap_or(false)
+    })?;
+
+    assert!(
+        fs::symlink_metadata(&link)?.file_type().is_symlink(),
+        "Saving must not replace the symlink with a regular file"
+    );
+    Ok(())
+}
+
+/// Test: Opening a FIFO is refused with a clear message instead of blocking
+/// until a writer shows up
+#[test]
+fn test_open_fifo_does_not_block() -> anyhow::Result<()> {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24)?;
+    let project_dir = harness.project_dir().unwrap();
+
+    let fifo = project_dir.join("pipe");
+    let status = std::process::Command::new("mkfifo").arg(&fifo).status()?;
+    assert!(status.success(), "mkfifo failed");
+
+    // No writer is ever attached: reading the FIFO would hang this test
+    let err = harness
+        .editor_mut()
+        .open_file(&fifo)
+        .expect_err("Opening a FIFO should be refused");
+    assert!(
+        err.to_string().contains("named pipe"),
+        "Unexpected error: {}",
+        err
+    );
+
+    // The same goes for a symlink pointing at the FIFO
+    let link = project_dir.join("pipe_link");
+    symlink(&fifo, &link)?;
+    assert!(harness.editor_mut().open_file(&link).is_err());
+    Ok(())
+}
@@ -5,6 +5,7 @@ use crossterm::event::{KeyCode, KeyModifiers};
 use fresh::config::Config;
 use fresh::config_io::DirectoryContext;
 use ratatui::style::Color;
+use std::fmt::Write as _;
 use std::fs;
 use tempfile::TempDir;
 
@@ -540,3 +541,211 @@ fn test_issue_1001_config_with_spaces_in_theme_name_loads_correctly() {
 
     drop(temp_dir);
 }
+
+/// Describe the non-color attributes of a rendered cell, e.g. "bold+underlined"
+fn cell_attributes(harness: &EditorTestHarness, x: u16, y: u16) -> String {
+    use ratatui::style::Modifier;
+
+    let modifier = harness
+        .get_cell_style(x, y)
+        .map(|style| style.add_modifier)
+        .unwrap_or_default();
+    let names: Vec<&str> = [
+        (Modifier::BOLD, "bold"),
+        (Modifier::DIM, "dim"),
+        (Modifier::ITALIC, "italic"),
+        (Modifier::UNDERLINED, "underlined"),
+        (Modifier::REVERSED, "reversed"),
+        (Modifier::CROSSED_OUT, "crossed_out"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| modifier.contains(*flag))
+    .map(|(_, name)| name)
+    .collect();
+    if names.is_empty() {
+        "-".to_string()
+    } else {
+        names.join("+")
+    }
+}
+
+/// Cell position of `text` on screen. Unlike `find_text_on_screen`, which
+/// returns a byte offset, this stays right after multi-byte gutter symbols.
+fn text_cell(harness: &EditorTestHarness, text: &str) -> (u16, u16) {
+    let (_, y) = harness
+        .find_text_on_screen(text)
+        .unwrap_or_else(|| panic!("{text:?} not found on screen"));
+    let cells: Vec<String> = (0..harness.buffer().area.width)
+        .map(|x| harness.get_cell(x, y).unwrap_or_default())
+        .collect();
+    let len = text.chars().count();
+    let x = (0..=cells.len().saturating_sub(len))
+        .find(|&x| cells[x..x + len].concat() == text)
+        .unwrap_or_else(|| panic!("{text:?} not found on row {y}"));
+    (x as u16, y)
+}
+
+/// Column of the line number `digit` in the gutter of screen row `y`
+fn line_number_column(harness: &EditorTestHarness, y: u16, digit: &str) -> u16 {
+    (0..20)
+        .find(|&x| harness.get_cell(x, y).as_deref() == Some(digit))
+        .unwrap_or_else(|| panic!("line number {digit} not found on row {y}"))
+}
+
+fn severity_diagnostic(
+    start: u32,
+    end: u32,
+    severity: lsp_types::DiagnosticSeverity,
+) -> lsp_types::Diagnostic {
+    lsp_types::Diagnostic {
+        range: lsp_types::Range {
+            start: lsp_types::Position {
+                line: 0,
+                character: start,
+            },
+            end: lsp_types::Position {
+                line: 0,
+                character: end,
+            },
+        },
+        severity: Some(severity),
+        message: "diagnostic".to_string(),
+        ..Default::default()
+    }
+}
+
+/// Gutter marker, attributes and background of the diff line whose text
+/// starts at (`x`, `y`), e.g. "+ bold #1e3a1e"
+fn diff_line_report(harness: &EditorTestHarness, x: u16, y: u16) -> String {
+    let marker = harness.get_cell(x - 1, y).unwrap_or_default();
+    let marker = match marker.trim() {
+        "" => "none",
+        marker => marker,
+    };
+    let bg = match harness.get_cell_style(x + 1, y).and_then(|style| style.bg) {
+        Some(Color::Rgb(r, g, b)) => format!("#{r:02x}{g:02x}{b:02x}"),
+        other => format!("{other:?}"),
+    };
+    format!("{marker} {} {bg}", cell_attributes(harness, x + 1, y))
+}
+
+/// Show `old` and `new` side by side as a diff whose second line changed
+fn show_side_by_side_diff(harness: &mut EditorTestHarness, old: &str, new: &str) {
+    use fresh::model::composite_buffer::{
+        CompositeLayout, DiffHunk, LineAlignment, PaneStyle, SourcePane,
+    };
+    use fresh::primitives::text_property::TextPropertyEntry;
+
+    let editor = harness.editor_mut();
+    let old_id = editor.create_virtual_buffer("OLD".to_string(), "text".to_string(), true);
+    editor
+        .set_virtual_buffer_content(old_id, vec![TextPropertyEntry::text(old)])
+        .unwrap();
+    let new_id = editor.create_virtual_buffer("NEW".to_string(), "text".to_string(), true);
+    editor
+        .set_virtual_buffer_content(new_id, vec![TextPropertyEntry::text(new)])
+        .unwrap();
+
+    let composite_id = editor.create_composite_buffer(
+        "Diff View".to_string(),
+        "diff-view".to_string(),
+        CompositeLayout::SideBySide {
+            ratios: vec![0.5, 0.5],
+            show_separator: true,
+        },
+        vec![
+            SourcePane::new(old_id, "OLD", false).with_style(PaneStyle::old_diff()),
+            SourcePane::new(new_id, "NEW", false).with_style(PaneStyle::new_diff()),
+        ],
+    );
+    let alignment = LineAlignment::from_hunks(
+        &[DiffHunk::new(1, 1, 1, 1)],
+        old.lines().count(),
+        new.lines().count(),
+    );
+    editor.set_composite_alignment(composite_id, alignment);
+    editor.switch_buffer(composite_id);
+    harness.render().unwrap();
+}
+
+/// Render a buffer carrying every annotation type under each built-in theme and
+/// snapshot the non-color attributes of the annotated cells, along with the
+/// markers and backgrounds of added and removed diff lines. Themes that set
+/// `distinguish_without_color` must keep these distinctions apart without color.
+#[test]
+fn test_semantic_distinctions_snapshot() {
+    use lsp_types::DiagnosticSeverity;
+
+    let mut report = String::new();
+    for builtin in fresh::view::theme::BUILTIN_THEMES {
+        let config = Config {
+            theme: builtin.name.into(),
+            ..Default::default()
+        };
+        let mut harness = EditorTestHarness::with_config(80, 12, config).unwrap();
+        let _fixture = harness
+            .load_buffer_from_text("error warn info hint\nmatch match\nselected\n")
+            .unwrap();
+
+        // Diagnostics of every severity on the first line
+        let theme = harness.editor().theme().clone();
+        let diagnostics = vec![
+            severity_diagnostic(0, 5, DiagnosticSeverity::ERROR),
+            severity_diagnostic(6, 10, DiagnosticSeverity::WARNING),
+            severity_diagnostic(11, 15, DiagnosticSeverity::INFORMATION),
+            severity_diagnostic(16, 20, DiagnosticSeverity::HINT),
+        ];
+        let state = harness.editor_mut().active_state_mut();
+        fresh::services::lsp::diagnostics::apply_diagnostics_to_state(state, &diagnostics, &theme);
+
+        // Search: the cursor lands on the first "match", which is the current one
+        harness
+            .send_key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("match").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.process_async_and_render().unwrap();
+
+        let (diag_x, diag_y) = text_cell(&harness, "error warn");
+        let (match_x, match_y) = text_cell(&harness, "match match");
+        let error = cell_attributes(&harness, diag_x + 1, diag_y);
+        let warning = cell_attributes(&harness, diag_x + 7, diag_y);
+        let info = cell_attributes(&harness, diag_x + 12, diag_y);
+        let hint = cell_attributes(&harness, diag_x + 17, diag_y);
+        let current_match = cell_attributes(&harness, match_x + 1, match_y);
+        let other_match = cell_attributes(&harness, match_x + 7, match_y);
+
+        // Selection on the third line, which also becomes the cursor line
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        harness.send_key(KeyCode::End, KeyModifiers::SHIFT).unwrap();
+        harness.render().unwrap();
+
+        let (sel_x, sel_y) = text_cell(&harness, "selected");
+        let selection = cell_attributes(&harness, sel_x + 1, sel_y);
+        let cursor_line =
+            cell_attributes(&harness, line_number_column(&harness, sel_y, "3"), sel_y);
+        let other_line =
+            cell_attributes(&harness, line_number_column(&harness, diag_y, "1"), diag_y);
+
+        // A changed line in a side-by-side diff: removed on the left, added on the right
+        show_side_by_side_diff(&mut harness, "same\nold line\n", "same\nnew line\n");
+        let (old_x, old_y) = text_cell(&harness, "old line");
+        let (new_x, new_y) = text_cell(&harness, "new line");
+        let diff_remove = diff_line_report(&harness, old_x, old_y);
+        let diff_add = diff_line_report(&harness, new_x, new_y);
+
+        writeln!(
+            report,
+            "{}: error={error} warning={warning} info={info} hint={hint} \
+             current_match={current_match} other_match={other_match} \
+             selection={selection} cursor_line={cursor_line} other_line={other_line} \
+             diff_add=[{diff_add}] diff_remove=[{diff_remove}]",
+            builtin.name
+        )
+        .unwrap();
+    }
+
+    insta::assert_snapshot!("semantic_distinctions", report);
+}
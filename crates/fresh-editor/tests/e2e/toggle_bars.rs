@@ -374,3 +374,131 @@ fn test_all_bars_hidden() {
         status_bar_row
     );
 }
+
+/// Run a command palette command by name
+fn run_palette_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text(name).unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Test that zoom out/in cycles through density presets, hiding and restoring chrome
+#[test]
+fn test_zoom_cycles_ui_density() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.render().unwrap();
+    assert!(harness.editor().tab_bar_visible());
+
+    // Compact: menu bar goes away, tab bar stays
+    run_palette_command(&mut harness, "Zoom Out");
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("UI density: compact")
+    );
+    let row0 = harness.get_screen_row(layout::MENU_BAR_ROW);
+    assert!(!row0.contains("File"), "Compact should hide the menu bar");
+    assert!(harness.editor().tab_bar_visible());
+
+    // Dense: tab bar goes away too
+    run_palette_command(&mut harness, "Zoom Out");
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("UI density: dense")
+    );
+    assert!(!harness.editor().tab_bar_visible());
+
+    // Already at the densest preset
+    run_palette_command(&mut harness, "Zoom Out");
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Already at the densest UI density")
+    );
+
+    // Back to comfortable restores everything
+    run_palette_command(&mut harness, "Zoom In");
+    run_palette_command(&mut harness, "Zoom In");
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("UI density: comfortable")
+    );
+    assert!(harness.editor().tab_bar_visible());
+    let menu_bar = harness.get_menu_bar();
+    assert!(
+        menu_bar.contains("File"),
+        "Comfortable should show the menu bar"
+    );
+}
+
+/// Test that config ui_density: dense hides both bars on startup
+#[test]
+fn test_config_ui_density_dense() {
+    let mut config = Config::default();
+    config.editor.ui_density = fresh::config::UiDensity::Dense;
+
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.render().unwrap();
+
+    let row0 = harness.get_screen_row(0);
+    assert!(!row0.contains("File"), "Dense should hide the menu bar");
+    assert!(!harness.editor().tab_bar_visible());
+}
+
+/// Test that compact density draws tabs without padding and that clicks on
+/// tabs, close buttons and text still land on what is drawn there
+#[test]
+fn test_compact_density_mouse_hit_testing() {
+    let mut config = Config::default();
+    config.editor.ui_density = fresh::config::UiDensity::Compact;
+
+    let mut harness = EditorTestHarness::with_config(100, 24, config).unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let first = temp_dir.path().join("first.txt");
+    let second = temp_dir.path().join("second.txt");
+    std::fs::write(&first, "First file content").unwrap();
+    std::fs::write(&second, "Second file content").unwrap();
+    harness.open_file(&first).unwrap();
+    harness.open_file(&second).unwrap();
+    harness.render().unwrap();
+
+    // The menu bar is hidden, so the tab bar is the top row
+    let tab_row = harness.get_screen_row(0);
+    assert!(
+        tab_row.starts_with("first.txt × second.txt ×"),
+        "Compact tabs should drop their padding: {tab_row:?}"
+    );
+    let column_of =
+        |row: &str, needle: &str| row[..row.find(needle).unwrap()].chars().count() as u16;
+
+    // Clicking the first tab's name switches to it
+    harness
+        .mouse_click(column_of(&tab_row, "first"), 0)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("First file content");
+
+    // Clicking into the text right below the tab bar places the cursor there
+    let content_row = harness.get_screen_row(1);
+    let text_col = column_of(&content_row, "First file content");
+    harness.mouse_click(text_col + 6, 1).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), 6);
+
+    // Clicking the second tab's close button closes that tab
+    let close_col = tab_row
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| *c == '×')
+        .nth(1);
+    harness.mouse_click(close_col.unwrap().0 as u16, 0).unwrap();
+    harness.render().unwrap();
+    let tab_row = harness.get_screen_row(0);
+    assert_eq!(tab_row.matches('×').count(), 1, "tab bar: {tab_row:?}");
+    assert!(!tab_row.contains("second.txt"));
+}
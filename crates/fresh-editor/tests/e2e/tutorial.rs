@@ -0,0 +1,114 @@
+//! E2E tests for the interactive tutorial
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config_io::DirectoryContext;
+use tempfile::TempDir;
+
+/// Helper: start an editor that keeps its data in `dir_context`
+fn new_session(temp_dir: &TempDir, dir_context: &DirectoryContext) -> EditorTestHarness {
+    EditorTestHarness::with_shared_dir_context(
+        100,
+        40,
+        Default::default(),
+        temp_dir.path().to_path_buf(),
+        dir_context.clone(),
+    )
+    .unwrap()
+}
+
+/// Helper: replace the line under the cursor with `text`
+fn replace_line(harness: &mut EditorTestHarness, text: &str) {
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::SHIFT).unwrap();
+    harness.type_text(text).unwrap();
+}
+
+/// Solving an exercise reveals the next lesson; a wrong answer doesn't
+#[test]
+fn test_tutorial_checkpoint_reveals_next_lesson() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(&temp_dir.path().join("context"));
+    let mut harness = new_session(&temp_dir, &dir_context);
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Tutorial").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Lesson 1 of 7: Moving around");
+
+    // A wrong answer keeps the tutorial on lesson 1
+    replace_line(&mut harness, "The cursor moves by word.");
+    assert!(!harness.get_buffer_content().unwrap().contains("Lesson 2"));
+
+    // The cursor is still on the exercise line
+    replace_line(&mut harness, "The cursor moves by words.");
+    let content = harness.get_buffer_content().unwrap();
+    assert!(content.contains("Lesson 2 of 7: Selecting text"));
+    harness.assert_screen_contains("Lesson 1 complete!");
+
+    // The cursor moved to the new exercise
+    replace_line(&mut harness, "The quick brown fox jumps over the lazy dog.");
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .contains("Lesson 3 of 7: Working with lines"));
+}
+
+/// Progress is saved and the tutorial resumes at the next lesson
+#[test]
+fn test_tutorial_resumes_across_sessions() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(&temp_dir.path().join("context"));
+
+    {
+        let mut harness = new_session(&temp_dir, &dir_context);
+        harness.editor_mut().open_tutorial();
+        harness.render().unwrap();
+        replace_line(&mut harness, "The cursor moves by words.");
+        assert!(harness
+            .get_buffer_content()
+            .unwrap()
+            .contains("Lesson 2 of 7"));
+    }
+
+    let mut harness = new_session(&temp_dir, &dir_context);
+    harness.editor_mut().open_tutorial();
+    harness.render().unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert!(content.contains("Resuming at lesson 2"));
+    assert!(content.contains("Lesson 2 of 7: Selecting text"));
+    assert!(!content.contains("Lesson 1 of 7"));
+}
+
+/// The splits lesson only passes while the tutorial is shown in two splits
+#[test]
+fn test_tutorial_splits_lesson_requires_split() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(&temp_dir.path().join("context"));
+    std::fs::create_dir_all(&dir_context.data_dir).unwrap();
+    std::fs::write(dir_context.tutorial_progress_path(), r#"{"lesson": 5}"#).unwrap();
+
+    let mut harness = new_session(&temp_dir, &dir_context);
+    harness.editor_mut().open_tutorial();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Lesson 6 of 7: Splits");
+
+    replace_line(&mut harness, "done");
+    assert!(!harness.get_buffer_content().unwrap().contains("Lesson 7"));
+
+    // Splitting and editing again (in the original split) passes the check
+    harness.editor_mut().split_pane_vertical();
+    harness
+        .send_key(KeyCode::Char(']'), KeyModifiers::ALT)
+        .unwrap();
+    replace_line(&mut harness, "done");
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .contains("Lesson 7 of 7: The command palette"));
+}
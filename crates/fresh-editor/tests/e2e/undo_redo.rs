@@ -497,3 +497,39 @@ fn test_undo_to_empty_after_save_as() {
         final_content
     );
 }
+
+/// Once the undo limit compacts history, the debug command reports it per buffer
+#[test]
+fn test_show_undo_history_sizes_reports_compaction() {
+    let mut config = fresh::config::Config::default();
+    config.editor.max_undo_entries = 5;
+    let mut harness = EditorTestHarness::with_config(100, 24, config).unwrap();
+
+    harness.type_text("abcdefghij").unwrap();
+    assert!(harness.editor().active_event_log().trimmed_count() > 0);
+
+    // The content the trimmed history leads to is rebuilt from the buffer
+    let editor = harness.editor();
+    let buffer = &editor.active_state().buffer;
+    let snapshot = editor
+        .active_event_log()
+        .checkpoint_snapshot(buffer)
+        .unwrap();
+    let mut rebuilt = fresh::model::buffer::TextBuffer::new(0, buffer.filesystem().clone());
+    rebuilt.restore_buffer_state(&snapshot);
+    let rebuilt = rebuilt.to_string().unwrap();
+    assert!(rebuilt.len() < 10 && "abcdefghij".starts_with(&rebuilt));
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Show Undo History Sizes").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("*Undo History*");
+    harness.assert_screen_contains("older entries");
+    harness.assert_screen_contains("compacted");
+}
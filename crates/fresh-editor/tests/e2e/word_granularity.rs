@@ -0,0 +1,101 @@
+//! E2E tests for `editor.word_granularity` and per-language word characters
+
+use crate::common::fixtures::TestFixture;
+use crate::common::harness::{layout, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, WordGranularity};
+
+fn subword_harness() -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.word_granularity = WordGranularity::Subword;
+    EditorTestHarness::with_config(80, 24, config).unwrap()
+}
+
+/// Ctrl+Right stops at camelCase humps and Ctrl+Backspace removes one
+/// `_`-separated part at a time
+#[test]
+fn test_subword_movement_and_deletion() {
+    let mut harness = subword_harness();
+    let _fixture = harness
+        .load_buffer_from_text("myVariableName MAX_BUFFER_SIZE")
+        .unwrap();
+
+    let mut stops = Vec::new();
+    for _ in 0..3 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::CONTROL)
+            .unwrap();
+        stops.push(harness.cursor_position());
+    }
+    assert_eq!(stops, vec![2, 10, 14]);
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "myVariableName MAX_BUFFER"
+    );
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "myVariableName MAX");
+}
+
+/// Double-click selects the sub-word under the mouse, and whole-word search
+/// matches at sub-word boundaries
+#[test]
+fn test_subword_double_click_and_whole_word_search() {
+    let mut harness = subword_harness();
+    let _fixture = harness
+        .load_buffer_from_text("let myVariableName = 1;\n")
+        .unwrap();
+    harness.render().unwrap();
+
+    let row = layout::CONTENT_START_ROW as u16;
+    let row_text = harness.get_row_text(row);
+    let byte = row_text.find("Variable").unwrap();
+    let col = row_text[..byte].chars().count() as u16 + 2;
+    harness.mouse_click(col, row).unwrap();
+    harness.mouse_click(col, row).unwrap();
+    assert_eq!(harness.get_selected_text(), "Variable");
+
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Variable").unwrap();
+    harness
+        .send_key(KeyCode::Char('w'), KeyModifiers::ALT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("No matches found");
+    assert_eq!(harness.cursor_position(), 6);
+}
+
+/// `-` is a word character in CSS, so kebab-case properties are one word
+#[test]
+fn test_css_kebab_case_is_one_word() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let fixture = TestFixture::new("style.css", "a { background-color: red; }\n").unwrap();
+    harness.open_file(&fixture.path).unwrap();
+
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 4)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), 20);
+
+    harness
+        .send_key(KeyCode::Char('w'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_selected_text(), "background-color");
+}
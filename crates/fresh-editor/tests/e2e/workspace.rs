@@ -179,6 +179,10 @@ fn test_session_handles_missing_files() {
             result.is_ok(),
             "Session restore should handle missing files"
         );
+        assert_eq!(
+            harness.editor().get_status_message().map(String::as_str),
+            Some("Skipped 1 missing file(s): d.txt")
+        );
 
         // Surviving file's content should be accessible
         harness.open_file(&file1).unwrap();
@@ -186,6 +190,68 @@ fn test_session_handles_missing_files() {
     }
 }
 
+/// Test that files passed on the command line are opened on top of a restored
+/// workspace, focusing a file the workspace already has instead of opening it twice
+#[test]
+fn test_restore_workspace_with_cli_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file1 = project_dir.join("a.txt");
+    let file2 = project_dir.join("b.txt");
+    std::fs::write(&file1, "Content of file A").unwrap();
+    std::fs::write(&file2, "First line\nSecond line\nThird line").unwrap();
+
+    // First session: open both files, leaving a.txt focused
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        harness.open_file(&file2).unwrap();
+        harness.open_file(&file1).unwrap();
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    // Second session: restore, then open b.txt:2:3 as if from the command line
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        assert!(harness.editor_mut().try_restore_workspace().unwrap());
+        harness.assert_buffer_content("Content of file A");
+
+        harness.editor_mut().queue_file_open(
+            file2.clone(),
+            Some(2),
+            Some(3),
+            None,
+            None,
+            None,
+            None,
+        );
+        harness.editor_mut().process_pending_file_opens();
+        harness.render().unwrap();
+
+        // The CLI file is focused at the requested position
+        harness.assert_buffer_content("First line\nSecond line\nThird line");
+        assert_eq!(harness.cursor_position(), "First line\n".len() + 2);
+
+        // Both restored files have exactly one tab
+        let tabs = harness.screen_row_text(1);
+        assert_eq!(tabs.matches("a.txt").count(), 1, "Tabs: {tabs}");
+        assert_eq!(tabs.matches("b.txt").count(), 1, "Tabs: {tabs}");
+    }
+}
+
 /// Test that session does not auto-load (simulating --no-session behavior)
 #[test]
 fn test_no_session_flag_behavior() {
@@ -603,6 +669,60 @@ fn test_session_restores_cursor_in_splits() {
     }
 }
 
+/// Test that a maximized split is still maximized after restore
+#[test]
+fn test_session_restores_maximized_split() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file1 = project_dir.join("left.txt");
+    let file2 = project_dir.join("right.txt");
+    std::fs::write(&file1, "Left content").unwrap();
+    std::fs::write(&file2, "Right content").unwrap();
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        harness.open_file(&file1).unwrap();
+        split_vertical(&mut harness);
+        harness.open_file(&file2).unwrap();
+        prev_split(&mut harness);
+        harness.editor_mut().toggle_maximize_split();
+        harness.render().unwrap();
+        harness.assert_screen_contains("Left content");
+        harness.assert_screen_not_contains("Right content");
+
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        harness.editor_mut().try_restore_workspace().unwrap();
+        harness.render().unwrap();
+        harness.assert_screen_contains("Left content");
+        harness.assert_screen_not_contains("Right content");
+
+        // Un-maximizing brings the other split back
+        harness.editor_mut().toggle_maximize_split();
+        harness.render().unwrap();
+        harness.assert_screen_contains("Right content");
+    }
+}
+
 /// Test that session saves and restores scroll position in splits
 #[test]
 fn test_session_restores_scroll_in_splits() {
@@ -1703,3 +1823,188 @@ fn test_reopen_with_file_arg_restores_session_and_opens_new_file() {
         harness.assert_screen_contains("MODIFIED");
     }
 }
+
+/// Test that a closed file reopens at its last cursor position, clamped when
+/// the file shrank, and at the top when `remember_file_positions` is off
+#[test]
+fn test_reopened_file_restores_cursor_position() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let file = project_dir.join("notes.txt");
+    std::fs::write(&file, "line 1\nline 2\nline 3\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        80,
+        24,
+        Config::default(),
+        project_dir.clone(),
+    )
+    .unwrap();
+
+    let close_and_reopen = |harness: &mut EditorTestHarness| {
+        let buffer_id = harness.editor().active_buffer();
+        harness.editor_mut().close_buffer(buffer_id).unwrap();
+        harness.open_file(&file).unwrap();
+    };
+
+    harness.open_file(&file).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 20);
+
+    close_and_reopen(&mut harness);
+    assert_eq!(harness.cursor_position(), 20);
+
+    // The file shrank while closed: the cursor lands at its end
+    let buffer_id = harness.editor().active_buffer();
+    harness.editor_mut().close_buffer(buffer_id).unwrap();
+    std::fs::write(&file, "short\n").unwrap();
+    harness.open_file(&file).unwrap();
+    assert_eq!(harness.cursor_position(), 6);
+
+    let mut config = Config::default();
+    config.session.remember_file_positions = false;
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 24, config, project_dir).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    close_and_reopen(&mut harness);
+    assert_eq!(harness.cursor_position(), 0);
+}
+
+/// Test that named bookmarks are saved with the session and reopen their
+/// file when it was closed
+#[test]
+fn test_session_restores_named_bookmarks() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file1 = project_dir.join("a.txt");
+    let file2 = project_dir.join("b.txt");
+    std::fs::write(&file1, "Line A1\nLine A2\n").unwrap();
+    std::fs::write(&file2, "Content of file B").unwrap();
+
+    // First session: bookmark the second line of a.txt, then close it
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        harness.open_file(&file1).unwrap();
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        harness
+            .editor_mut()
+            .queue_startup_commands("bookmark-set intro", false);
+        harness.editor_mut().run_startup_commands();
+        let a_buffer = harness.editor().active_buffer();
+
+        harness.open_file(&file2).unwrap();
+        harness.editor_mut().close_buffer(a_buffer).unwrap();
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    // Second session: jumping to the bookmark opens a.txt again
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        let restored = harness.editor_mut().try_restore_workspace().unwrap();
+        assert!(restored, "Session should have been restored");
+        harness.assert_buffer_content("Content of file B");
+
+        harness
+            .editor_mut()
+            .queue_startup_commands("bookmark-goto intro", false);
+        harness.editor_mut().run_startup_commands();
+        harness.assert_buffer_content("Line A1\nLine A2\n");
+        assert_eq!(harness.cursor_position(), "Line A1\n".len());
+    }
+}
+
+/// Background tabs of a restored session are read when first shown, with
+/// the cursor they were saved with
+#[test]
+fn test_session_reads_background_tabs_when_shown() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file1 = project_dir.join("a.txt");
+    let file2 = project_dir.join("b.txt");
+    std::fs::write(&file1, "Line A1\nLine A2\nLine A3\n").unwrap();
+    std::fs::write(&file2, "Content of file B").unwrap();
+
+    // First session: move down in a.txt, then leave b.txt active
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        harness.open_file(&file1).unwrap();
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        harness.open_file(&file2).unwrap();
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    // Second session: a.txt is read once its tab is shown
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        assert!(harness.editor_mut().try_restore_workspace().unwrap());
+        harness.render().unwrap();
+        harness.assert_buffer_content("Content of file B");
+        assert_eq!(harness.editor().deferred_buffer_count(), 1);
+        harness.assert_screen_contains("a.txt");
+
+        // Saving the session before a.txt is shown keeps its cursor
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        assert!(harness.editor_mut().try_restore_workspace().unwrap());
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("Next Buffer").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.render().unwrap();
+
+        harness.assert_buffer_content("Line A1\nLine A2\nLine A3\n");
+        assert_eq!(harness.editor().deferred_buffer_count(), 0);
+        assert_eq!(harness.cursor_position(), "Line A1\nLine A2\n".len());
+        harness.assert_screen_contains("Line A3");
+    }
+}
@@ -0,0 +1,55 @@
+//! E2E tests for "Write Copy": writing a buffer to another file while it
+//! stays attached to its own.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Test that the copy gets the unsaved edits while the buffer keeps its file
+/// and stays modified
+#[test]
+fn test_write_copy_keeps_buffer_on_original_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.txt");
+    let copy_path = temp_dir.path().join("notes-copy.txt");
+    std::fs::write(&file_path, "original\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.type_text("edited ").unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("write copy").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    // Replace the suggested path (the buffer's own file)
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(&copy_path.to_string_lossy()).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(&copy_path).unwrap(),
+        "edited original\n"
+    );
+    assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "original\n");
+    let buffer = &harness.editor().active_state().buffer;
+    assert_eq!(buffer.file_path(), Some(file_path.as_path()));
+    assert!(buffer.is_modified());
+    let status = harness.editor().get_status_message().cloned().unwrap();
+    assert!(
+        status.starts_with("Wrote copy to ") && status.ends_with("notes-copy.txt"),
+        "unexpected status: {}",
+        status
+    );
+}
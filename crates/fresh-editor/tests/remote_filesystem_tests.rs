@@ -1628,3 +1628,101 @@ fn test_unique_temp_path_uses_system_temp_dir() {
         name,
     );
 }
+
+#[cfg(unix)]
+#[test]
+fn test_open_shell_runs_commands_and_resizes() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let mut shell = fs.open_shell(Some(temp_dir.path()), 80, 24).unwrap();
+    shell.input.resize(100, 30).unwrap();
+    shell
+        .input
+        .write(b"stty size; pwd; echo fresh-$((1+1)); exit\n")
+        .unwrap();
+
+    // Read on a thread so a hung shell fails the test instead of blocking it
+    let mut output = shell.output;
+    let (done_tx, done_rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut text = Vec::new();
+        let _ = std::io::Read::read_to_end(&mut output, &mut text);
+        let _ = done_tx.send(text);
+    });
+    let text = done_rx
+        .recv_timeout(std::time::Duration::from_secs(20))
+        .expect("shell should exit");
+    let text = String::from_utf8_lossy(&text);
+
+    // The typed command line is echoed, so look for what only its output has
+    assert!(text.contains("fresh-2"), "output: {:?}", text);
+    assert!(text.contains("30 100"), "output: {:?}", text);
+    let dir_name = temp_dir.path().file_name().unwrap().to_string_lossy();
+    assert!(text.contains(dir_name.as_ref()), "output: {:?}", text);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_spawn_stdio_pipes_input_and_output() {
+    use fresh::services::remote::{ProcessSpawner, RemoteProcessSpawner, SpawnError};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let Ok(rt) = tokio::runtime::Runtime::new() else {
+        eprintln!("Skipping test: could not create runtime");
+        return;
+    };
+    let Ok(channel) = rt.block_on(spawn_local_agent()) else {
+        eprintln!("Skipping test: could not spawn agent");
+        return;
+    };
+    let spawner = RemoteProcessSpawner::new(channel);
+    let temp_dir = tempfile::tempdir().unwrap();
+    let log_path = temp_dir.path().join("stderr.log");
+
+    let env = std::collections::HashMap::from([("GREETING".to_string(), "hi".to_string())]);
+    let script = "echo \"$GREETING\" >&2; cat".to_string();
+    let output = rt.block_on(async {
+        let mut process = spawner
+            .spawn_stdio(
+                "sh".to_string(),
+                vec!["-c".to_string(), script],
+                env,
+                std::fs::File::create(&log_path).unwrap(),
+            )
+            .await
+            .unwrap();
+        process
+            .stdin
+            .write_all(b"Content-Length: 2\r\n\r\n{}")
+            .await
+            .unwrap();
+        // Closing stdin ends `cat`, and with it the output
+        drop(process.stdin);
+        let mut output = Vec::new();
+        tokio::time::timeout(
+            std::time::Duration::from_secs(20),
+            process.stdout.read_to_end(&mut output),
+        )
+        .await
+        .expect("process should exit")
+        .unwrap();
+        output
+    });
+    assert_eq!(output, b"Content-Length: 2\r\n\r\n{}");
+    assert_eq!(std::fs::read_to_string(&log_path).unwrap(), "hi\n");
+
+    let missing = rt.block_on(spawner.spawn_stdio(
+        "fresh-no-such-language-server".to_string(),
+        Vec::new(),
+        Default::default(),
+        std::fs::File::create(&log_path).unwrap(),
+    ));
+    match missing {
+        Err(SpawnError::Process(error)) => assert!(error.contains("command not found")),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("a missing command should not start"),
+    }
+}
@@ -712,9 +712,10 @@ impl JsEditorApi {
     // === Status ===
 
     pub fn set_status(&self, msg: String) {
-        let _ = self
-            .command_sender
-            .send(PluginCommand::SetStatus { message: msg });
+        let _ = self.command_sender.send(PluginCommand::SetStatus {
+            message: msg,
+            plugin_name: Some(self.plugin_name.clone()),
+        });
     }
 
     // === Clipboard ===
@@ -957,6 +958,21 @@ impl JsEditorApi {
             .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))
     }
 
+    /// Get the most recent Back/Forward navigation history entries, newest first
+    #[plugin_api(ts_return = "PositionHistoryEntry[]")]
+    pub fn get_position_history<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+    ) -> rquickjs::Result<Value<'js>> {
+        let entries = if let Ok(s) = self.state_snapshot.read() {
+            s.position_history.clone()
+        } else {
+            Vec::new()
+        };
+        rquickjs_serde::to_value(ctx, &entries)
+            .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))
+    }
+
     /// Get all cursor positions as byte offsets
     #[plugin_api(ts_return = "number[]")]
     pub fn get_all_cursor_positions<'js>(
@@ -1221,6 +1237,12 @@ impl JsEditorApi {
         }
     }
 
+    /// Keep the editor open. Call from a `before_quit` handler, before any
+    /// `await`; calls made later arrive after the quit went ahead.
+    pub fn cancel_quit(&self) -> bool {
+        self.command_sender.send(PluginCommand::CancelQuit).is_ok()
+    }
+
     // === Environment ===
 
     /// Get an environment variable
@@ -4606,9 +4628,10 @@ impl QuickJsBackend {
 
     /// Send a status message to the editor
     pub fn send_status(&self, message: String) {
-        let _ = self
-            .command_sender
-            .send(PluginCommand::SetStatus { message });
+        let _ = self.command_sender.send(PluginCommand::SetStatus {
+            message,
+            plugin_name: None,
+        });
     }
 
     /// Send a hook-completed sentinel to the editor.
@@ -4967,7 +4990,7 @@ mod tests {
 
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::SetStatus { message } => {
+            PluginCommand::SetStatus { message, .. } => {
                 assert_eq!(message, "Hello from test");
             }
             _ => panic!("Expected SetStatus command, got {:?}", cmd),
@@ -5167,7 +5190,7 @@ mod tests {
         // Check the command was sent
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::SetStatus { message } => {
+            PluginCommand::SetStatus { message, .. } => {
                 assert_eq!(message, "sync action executed");
             }
             _ => panic!("Expected SetStatus from action, got {:?}", cmd),
@@ -5210,7 +5233,7 @@ mod tests {
         // Check the command was sent (async should complete)
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::SetStatus { message } => {
+            PluginCommand::SetStatus { message, .. } => {
                 assert_eq!(message, "async action executed");
             }
             _ => panic!("Expected SetStatus from async action, got {:?}", cmd),
@@ -5250,7 +5273,7 @@ mod tests {
 
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::SetStatus { message } => {
+            PluginCommand::SetStatus { message, .. } => {
                 assert_eq!(message, "handler executed");
             }
             _ => panic!("Expected SetStatus, got {:?}", cmd),
@@ -5321,7 +5344,7 @@ mod tests {
 
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::SetStatus { message } => {
+            PluginCommand::SetStatus { message, .. } => {
                 assert!(message.contains("/test.txt"));
             }
             _ => panic!("Expected SetStatus from event handler, got {:?}", cmd),
@@ -5655,7 +5678,7 @@ mod tests {
 
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::SetStatus { message } => {
+            PluginCommand::SetStatus { message, .. } => {
                 assert_eq!(message, "Hello, TypeScript");
             }
             _ => panic!("Expected SetStatus, got {:?}", cmd),
@@ -6450,6 +6473,9 @@ mod tests {
                     is_composing_in_any_split: false,
                     compose_width: None,
                     language: "text".to_string(),
+                    undo_depth: 0,
+                    redo_depth: 0,
+                    last_saved_at: None,
                 },
             );
             state.buffers.insert(
@@ -6464,6 +6490,9 @@ mod tests {
                     is_composing_in_any_split: false,
                     compose_width: None,
                     language: "text".to_string(),
+                    undo_depth: 0,
+                    redo_depth: 0,
+                    last_saved_at: None,
                 },
             );
         }
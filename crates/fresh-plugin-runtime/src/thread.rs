@@ -505,26 +505,40 @@ impl PluginThreadHandle {
         dir: &Path,
         plugin_configs: &HashMap<String, PluginConfig>,
     ) -> (Vec<String>, HashMap<String, PluginConfig>) {
+        self.start_loading_plugins_from_dir_with_config(dir, plugin_configs)
+            .recv()
+            .unwrap_or_else(|_| (vec!["Plugin thread closed".to_string()], HashMap::new()))
+    }
+
+    /// Queue loading all plugins from a directory without waiting for it.
+    ///
+    /// Requests sent after this one (hooks, actions, queries) are handled by
+    /// the plugin thread only once loading has finished, so they never observe
+    /// a half-loaded plugin set. The receiver yields the same result as
+    /// [`Self::load_plugins_from_dir_with_config`].
+    pub fn start_loading_plugins_from_dir_with_config(
+        &self,
+        dir: &Path,
+        plugin_configs: &HashMap<String, PluginConfig>,
+    ) -> oneshot::Receiver<(Vec<String>, HashMap<String, PluginConfig>)> {
         let (tx, rx) = oneshot::channel();
         let Some(sender) = self.request_sender.as_ref() else {
-            return (vec!["Plugin thread shut down".to_string()], HashMap::new());
+            let _ = tx.send((vec!["Plugin thread shut down".to_string()], HashMap::new()));
+            return rx;
         };
-        if sender
-            .send(PluginRequest::LoadPluginsFromDirWithConfig {
-                dir: dir.to_path_buf(),
-                plugin_configs: plugin_configs.clone(),
-                response: tx,
-            })
-            .is_err()
-        {
-            return (
-                vec!["Plugin thread not responding".to_string()],
-                HashMap::new(),
-            );
+        if let Err(err) = sender.send(PluginRequest::LoadPluginsFromDirWithConfig {
+            dir: dir.to_path_buf(),
+            plugin_configs: plugin_configs.clone(),
+            response: tx,
+        }) {
+            if let PluginRequest::LoadPluginsFromDirWithConfig { response, .. } = err.0 {
+                let _ = response.send((
+                    vec!["Plugin thread not responding".to_string()],
+                    HashMap::new(),
+                ));
+            }
         }
-
-        rx.recv()
-            .unwrap_or_else(|_| (vec!["Plugin thread closed".to_string()], HashMap::new()))
+        rx
     }
 
     /// Load a plugin from source code directly (blocking).
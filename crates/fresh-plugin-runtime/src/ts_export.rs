@@ -15,15 +15,15 @@ use oxc_span::SourceType;
 use ts_rs::{Config as TsConfig, TS};
 
 use fresh_core::api::{
-    ActionPopupAction, ActionPopupOptions, ActionSpec, BackgroundProcessResult, BufferInfo,
-    BufferSavedDiff, CompositeHunk, CompositeLayoutConfig, CompositePaneStyle,
-    CompositeSourceConfig, CreateCompositeBufferOptions, CreateTerminalOptions,
+    ActionPopupAction, ActionPopupOptions, ActionSpec, BackgroundProcessResult, BeforeQuitEvent,
+    BufferInfo, BufferModifiedChangedEvent, BufferSavedDiff, CompositeHunk, CompositeLayoutConfig,
+    CompositePaneStyle, CompositeSourceConfig, CreateCompositeBufferOptions, CreateTerminalOptions,
     CreateVirtualBufferInExistingSplitOptions, CreateVirtualBufferInSplitOptions,
     CreateVirtualBufferOptions, CursorInfo, DirEntry, FormatterPackConfig, GrepMatch, JsDiagnostic,
     JsPosition, JsRange, JsTextPropertyEntry, LanguagePackConfig, LayoutHints, LspServerPackConfig,
-    OverlayColorSpec, OverlayOptions, ProcessLimitsPackConfig, ReplaceResult, SpawnResult,
-    TerminalResult, TextPropertiesAtCursor, TsHighlightSpan, ViewTokenStyle, ViewTokenWire,
-    ViewTokenWireKind, ViewportInfo, VirtualBufferResult,
+    OverlayColorSpec, OverlayOptions, PositionHistoryEntry, ProcessLimitsPackConfig, ReplaceResult,
+    SpawnResult, TerminalResult, TextPropertiesAtCursor, TsHighlightSpan, ViewTokenStyle,
+    ViewTokenWire, ViewTokenWireKind, ViewportInfo, VirtualBufferResult,
 };
 use fresh_core::command::Suggestion;
 use fresh_core::file_explorer::FileExplorerDecoration;
@@ -45,6 +45,11 @@ fn get_type_decl(type_name: &str) -> Option<String> {
         "ActionSpec" => Some(ActionSpec::decl(&cfg)),
         "BufferSavedDiff" => Some(BufferSavedDiff::decl(&cfg)),
         "LayoutHints" => Some(LayoutHints::decl(&cfg)),
+        "PositionHistoryEntry" => Some(PositionHistoryEntry::decl(&cfg)),
+
+        // Hook payload types
+        "BufferModifiedChangedEvent" => Some(BufferModifiedChangedEvent::decl(&cfg)),
+        "BeforeQuitEvent" => Some(BeforeQuitEvent::decl(&cfg)),
 
         // Process types
         "SpawnResult" => Some(SpawnResult::decl(&cfg)),
@@ -151,6 +156,8 @@ const DEPENDENCY_TYPES: &[&str] = &[
     "OverlayOptions",                 // Used by TextPropertyEntry.style and InlineOverlay
     "OverlayColorSpec",               // Used by OverlayOptions.fg/bg
     "InlineOverlay",                  // Used by TextPropertyEntry.inlineOverlays
+    "BufferModifiedChangedEvent",     // Payload of the buffer_modified_changed hook
+    "BeforeQuitEvent",                // Payload of the before_quit hook
 ];
 
 /// Collect TypeScript type declarations based on referenced types from proc macro
@@ -390,6 +397,9 @@ mod tests {
             "LspServerPackConfig",
             "ProcessLimitsPackConfig",
             "FormatterPackConfig",
+            "PositionHistoryEntry",
+            "BufferModifiedChangedEvent",
+            "BeforeQuitEvent",
         ];
 
         for type_name in &expected_types {
@@ -758,6 +768,7 @@ mod tests {
             "getBufferInfo",
             "getPrimaryCursor",
             "getAllCursors",
+            "getPositionHistory",
             "getAllCursorPositions",
             "getViewport",
             "getCursorLine",
@@ -776,6 +787,7 @@ mod tests {
             "closeBuffer",
             "on",
             "off",
+            "cancelQuit",
             "getEnv",
             "getCwd",
             "pathJoin",